@@ -8,68 +8,155 @@
 /// // a interpreter prochazi toto AST a vyhodnocuje ho.
 /// ```
 
+// ─── Pozice ve zdrojaku ───────────────────────────────────────────────────────
+
+/// Pozice ve zdrojovem kodu: radek (od 1), sloupec (od 0, ve znacich)
+/// a byte offset. Vychozi hodnota (radek 0) = uzel bez pozice.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub struct SourcePos {
+    pub line: u32,
+    pub column: u32,
+    pub offset: u32,
+}
+
+/// Rozsah uzlu ve zdrojovem kodu - `start` vcetne, `end` za poslednim znakem.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SourceSpan {
+    pub start: SourcePos,
+    pub end: SourcePos,
+}
+
+impl SourceSpan {
+    /// Rozsah s pozici (ne rucne vytvoreny uzel).
+    pub fn is_known(&self) -> bool {
+        self.start.line > 0
+    }
+
+    /// Lezi pozice `line:column` v rozsahu?
+    pub fn contains(&self, line: u32, column: u32) -> bool {
+        let at = (line, column);
+        (self.start.line, self.start.column) <= at && at < (self.end.line, self.end.column)
+    }
+}
+
+impl std::fmt::Display for SourceSpan {
+    /// `radek:sloupec-radek:sloupec` se sloupci od 1 (jako editory a stack trace).
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}-{}:{}", self.start.line, self.start.column + 1, self.end.line, self.end.column + 1)
+    }
+}
+
+/// Call site tagged template (`Expr::TaggedTemplate::site`). Klony AST sdili
+/// jednu instanci; interpreter v ni drzi strings pole, takze cache zije
+/// presne tak dlouho jako AST (telo funkce, Program), ktery site vlastni.
+#[derive(Clone, Default)]
+pub struct TemplateSite(std::rc::Rc<std::cell::RefCell<Option<std::rc::Rc<dyn std::any::Any>>>>);
+
+impl TemplateSite {
+    /// Hodnota ulozena pri prvnim vyhodnoceni site.
+    pub fn cached<T: 'static>(&self) -> Option<std::rc::Rc<T>> {
+        self.0.borrow().clone()?.downcast().ok()
+    }
+
+    pub fn store<T: 'static>(&self, value: std::rc::Rc<T>) {
+        *self.0.borrow_mut() = Some(value);
+    }
+}
+
+impl PartialEq for TemplateSite {
+    /// Stejny site = stejny uzel z parseru (nebo jeho klon).
+    fn eq(&self, other: &Self) -> bool {
+        std::rc::Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl std::fmt::Debug for TemplateSite {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "TemplateSite({:p})", std::rc::Rc::as_ptr(&self.0))
+    }
+}
+
 // ─── Vyrazy ───────────────────────────────────────────────────────────────────
 
 /// Uzel reprezentujici vyraz (neco co vraci hodnotu).
 ///
 /// Pokryva: literaly, identifikatory, operatory, volani funkci,
 /// pristup k vlastnostem, definice funkci a pomocne konstrukty.
+/// Kazda varianta nese `span` (u tuple variant posledni polozka) - viz [`Expr::span`].
 #[derive(Debug, Clone)]
 pub enum Expr {
     // --- Literaly ---
 
     /// Ciselny literal: `42`, `3.14`, `0xFF`
-    Number(f64),
+    Number(f64, SourceSpan),
     /// BigInt literal: `42n`, `0xFFn` - arbitrary precision celociselny
     /// Hodnota je ulozena jako string (decimalni reprezentace, muze mit '-')
-    BigInt(String),
+    BigInt(String, SourceSpan),
     /// Dynamicky import: `import(specifier)` - vraci Promise s namespace objektem
-    DynamicImport(Box<Expr>),
+    DynamicImport(Box<Expr>, SourceSpan),
     /// Retezec: `"hello"` nebo `'world'`
-    Str(String),
+    Str(String, SourceSpan),
     /// Boolean: `true` nebo `false`
-    Bool(bool),
+    Bool(bool, SourceSpan),
     /// Klic. slovo `null`
-    Null,
+    Null(SourceSpan),
     /// Klic. slovo `undefined`
-    Undefined,
+    Undefined(SourceSpan),
     /// Regularni vyraz: `/pattern/flags`
-    Regex(String, String),   // (pattern, flags)
+    Regex(String, String, SourceSpan),   // (pattern, flags, span)
 
     /// Template literal: `` `Hello ${name}!` ``
     ///
     /// Struktura: `quasis[0] + expressions[0] + quasis[1] + ... + quasis[n]`
     /// Pocet quasis je vzdy o 1 vetsi nez pocet expressions.
-    Template { quasis: Vec<String>, expressions: Vec<Box<Expr>> },
+    Template { quasis: Vec<String>, expressions: Vec<Box<Expr>>, span: SourceSpan },
+
+    /// Tagged template: `` html`<p>${x}</p>` `` = `html(strings, x)`
+    ///
+    /// `cooked` (escape sekvence rozbalene) a `raw` (zdrojovy text) maji
+    /// stejnou delku jako quasis u `Template`. `site` je call site - strings
+    /// pole je frozen a pro dany site se vytvori jen jednou (knihovny ho
+    /// pouzivaji jako cache klic).
+    TaggedTemplate {
+        tag: Box<Expr>,
+        cooked: Vec<String>,
+        raw: Vec<String>,
+        expressions: Vec<Box<Expr>>,
+        site: TemplateSite,
+        span: SourceSpan,
+    },
 
     // --- Identifikatory a kolekce ---
 
     /// Identifikator promenne nebo funkce: `foo`, `myVar`
-    Ident(String),
+    Ident(String, SourceSpan),
 
     /// Pole: `[1, 2, 3]`
     /// `None` na pozici = hole (`[1, , 3]`)
-    Array(Vec<Option<Box<Expr>>>),
+    Array(Vec<Option<Box<Expr>>>, SourceSpan),
 
     /// Objektovy literal: `{ a: 1, b: 2 }`
-    Object(Vec<ObjectProp>),
+    Object(Vec<ObjectProp>, SourceSpan),
 
     // --- Operatory ---
 
     /// Unarni operator: `-x`, `!x`, `typeof x`, `++x`, ...
-    Unary  { op: UnaryOp, arg: Box<Expr> },
+    Unary  { op: UnaryOp, arg: Box<Expr>, span: SourceSpan },
 
     /// Binarni operator: `a + b`, `a * b`, `a instanceof B`, ...
-    Binary { op: BinaryOp, left: Box<Expr>, right: Box<Expr> },
+    Binary { op: BinaryOp, left: Box<Expr>, right: Box<Expr>, span: SourceSpan },
+
+    /// Brand check privatniho jmena: `#x in obj` (`name` je bez `#`)
+    PrivateIn { name: String, object: Box<Expr>, span: SourceSpan },
 
     /// Logicky operator s short-circuit: `a && b`, `a || b`, `a ?? b`
-    Logical { op: LogicalOp, left: Box<Expr>, right: Box<Expr> },
+    Logical { op: LogicalOp, left: Box<Expr>, right: Box<Expr>, span: SourceSpan },
 
     /// Ternary (podmineny) vyraz: `test ? yes : no`
-    Ternary { test: Box<Expr>, yes: Box<Expr>, no: Box<Expr> },
+    Ternary { test: Box<Expr>, yes: Box<Expr>, no: Box<Expr>, span: SourceSpan },
 
     /// Prirazovaci vyraz: `x = 5`, `x += 3`, `x &&= y`, ...
-    Assign  { op: AssignOp, target: Box<Expr>, value: Box<Expr> },
+    Assign  { op: AssignOp, target: Box<Expr>, value: Box<Expr>, span: SourceSpan },
 
     // --- Volani a pristup k vlastnostem ---
 
@@ -77,37 +164,41 @@ pub enum Expr {
     ///
     /// `optional = true` znamena optional chaining `?.()` -
     /// vrati `undefined` misto chyby, kdyz je callee null/undefined.
-    Call   { callee: Box<Expr>, args: Vec<Expr>, optional: bool },
+    /// `span` = cely vyraz vcetne argumentu (misto volani ve stack trace).
+    Call   { callee: Box<Expr>, args: Vec<Expr>, optional: bool, span: SourceSpan },
 
     /// Konstruktorove volani: `new Foo(args)`
-    New    { callee: Box<Expr>, args: Vec<Expr> },
+    New    { callee: Box<Expr>, args: Vec<Expr>, span: SourceSpan },
 
     /// Pristup k vlastnosti objektu: `obj.prop` nebo `obj[expr]`
     ///
     /// `optional = true` znamena `?.` - vrati `undefined` misto chyby.
-    Member { object: Box<Expr>, prop: MemberProp, optional: bool },
+    Member { object: Box<Expr>, prop: MemberProp, optional: bool, span: SourceSpan },
 
     // --- Definice funkci ---
 
     /// Vyrazova funkce: `function name(params) { body }`
-    Function { name: Option<String>, params: Vec<Param>, body: Vec<Stmt> },
+    Function { name: Option<String>, params: Vec<Param>, body: Vec<Stmt>, span: SourceSpan },
 
     /// Arrow funkce: `x => x * 2` nebo `(a, b) => { return a + b; }`
-    Arrow    { params: Vec<Param>, body: ArrowBody },
+    Arrow    { params: Vec<Param>, body: ArrowBody, span: SourceSpan },
 
     // --- Pomocne ---
 
     /// Spread operator: `...expr` (v poli nebo argumentech volani)
-    Spread(Box<Expr>),
+    Spread(Box<Expr>, SourceSpan),
 
     /// Sekvence vyrazu oddelena carkami: `(a, b, c)`
-    Sequence(Vec<Expr>),
+    Sequence(Vec<Expr>, SourceSpan),
 
-    /// Vyrazova trida: `const Foo = class Name? (extends Expr)? { ... }`
+    /// Vyrazova trida: `const Foo = @dec class Name? (extends Expr)? { ... }`
     ClassExpr {
         name: Option<String>,
+        /// Dekoratory tridy v poradi zdrojaku (`@a @b class {}` -> `[a, b]`)
+        decorators: Vec<Expr>,
         super_class: Option<Box<Expr>>,
         body: Vec<ClassMember>,
+        span: SourceSpan,
     },
 
     /// Generator funkce jako vyraz: `function* name?(params) { body }`
@@ -115,6 +206,15 @@ pub enum Expr {
         name: Option<String>,
         params: Vec<Param>,
         body: Vec<Stmt>,
+        span: SourceSpan,
+    },
+
+    /// Async generator jako vyraz: `async function* name?(params) { body }`
+    AsyncGeneratorFunc {
+        name: Option<String>,
+        params: Vec<Param>,
+        body: Vec<Stmt>,
+        span: SourceSpan,
     },
 
     /// Yield vyraz: `yield expr` nebo `yield* iterable`
@@ -123,6 +223,7 @@ pub enum Expr {
     Yield {
         value: Option<Box<Expr>>,
         delegate: bool,
+        span: SourceSpan,
     },
 
     /// Async funkce jako vyraz: `async function name?(params) { body }`
@@ -131,6 +232,9 @@ pub enum Expr {
         name: Option<String>,
         params: Vec<Param>,
         body: Vec<Stmt>,
+        /// `async () => ...` - lexikalni `this`/`arguments`, neni konstruktor.
+        is_arrow: bool,
+        span: SourceSpan,
     },
 
     /// Await vyraz: `await expr`
@@ -138,9 +242,95 @@ pub enum Expr {
     /// Synchronni implementace: okamzite rozbaluje Promise.
     Await {
         value: Box<Expr>,
+        span: SourceSpan,
     },
 }
 
+impl Expr {
+    /// Rozsah vyrazu ve zdrojaku (`SourceSpan::default()` = uzel bez pozice).
+    pub fn span(&self) -> SourceSpan {
+        match self {
+            Expr::Number(_, span)
+            | Expr::BigInt(_, span)
+            | Expr::DynamicImport(_, span)
+            | Expr::Str(_, span)
+            | Expr::Bool(_, span)
+            | Expr::Null(span)
+            | Expr::Undefined(span)
+            | Expr::Regex(_, _, span)
+            | Expr::Template { span, .. }
+            | Expr::TaggedTemplate { span, .. }
+            | Expr::Ident(_, span)
+            | Expr::Array(_, span)
+            | Expr::Object(_, span)
+            | Expr::Unary { span, .. }
+            | Expr::Binary { span, .. }
+            | Expr::PrivateIn { span, .. }
+            | Expr::Logical { span, .. }
+            | Expr::Ternary { span, .. }
+            | Expr::Assign { span, .. }
+            | Expr::Call { span, .. }
+            | Expr::New { span, .. }
+            | Expr::Member { span, .. }
+            | Expr::Function { span, .. }
+            | Expr::Arrow { span, .. }
+            | Expr::Spread(_, span)
+            | Expr::Sequence(_, span)
+            | Expr::ClassExpr { span, .. }
+            | Expr::GeneratorFunc { span, .. }
+            | Expr::AsyncGeneratorFunc { span, .. }
+            | Expr::Yield { span, .. }
+            | Expr::AsyncFunc { span, .. }
+            | Expr::Await { span, .. } => *span,
+        }
+    }
+
+    /// Doplni rozsah uzlu, ktery ho jeste nema (napr. vyraz v zavorkach si necha svuj).
+    pub fn set_span(&mut self, span: SourceSpan) {
+        let own = self.span_mut();
+        if !own.is_known() {
+            *own = span;
+        }
+    }
+
+    fn span_mut(&mut self) -> &mut SourceSpan {
+        match self {
+            Expr::Number(_, span)
+            | Expr::BigInt(_, span)
+            | Expr::DynamicImport(_, span)
+            | Expr::Str(_, span)
+            | Expr::Bool(_, span)
+            | Expr::Null(span)
+            | Expr::Undefined(span)
+            | Expr::Regex(_, _, span)
+            | Expr::Template { span, .. }
+            | Expr::TaggedTemplate { span, .. }
+            | Expr::Ident(_, span)
+            | Expr::Array(_, span)
+            | Expr::Object(_, span)
+            | Expr::Unary { span, .. }
+            | Expr::Binary { span, .. }
+            | Expr::PrivateIn { span, .. }
+            | Expr::Logical { span, .. }
+            | Expr::Ternary { span, .. }
+            | Expr::Assign { span, .. }
+            | Expr::Call { span, .. }
+            | Expr::New { span, .. }
+            | Expr::Member { span, .. }
+            | Expr::Function { span, .. }
+            | Expr::Arrow { span, .. }
+            | Expr::Spread(_, span)
+            | Expr::Sequence(_, span)
+            | Expr::ClassExpr { span, .. }
+            | Expr::GeneratorFunc { span, .. }
+            | Expr::AsyncGeneratorFunc { span, .. }
+            | Expr::Yield { span, .. }
+            | Expr::AsyncFunc { span, .. }
+            | Expr::Await { span, .. } => span,
+        }
+    }
+}
+
 /// Jedna vlastnost v objektovem literalu.
 #[derive(Debug, Clone)]
 pub struct ObjectProp {
@@ -152,6 +342,19 @@ pub struct ObjectProp {
     pub shorthand: bool,
     /// `true` pro vypocitany klic `{ [expr]: value }`
     pub computed: bool,
+    /// Bezna vlastnost nebo accessor `{ get x() {}, set x(v) {} }`
+    pub kind: PropKind,
+}
+
+/// Druh vlastnosti v objektovem literalu.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PropKind {
+    /// `{ x: 1 }`, `{ x }`, `{ x() {} }`
+    Init,
+    /// `{ get x() { ... } }` - `value` je funkce getteru
+    Get,
+    /// `{ set x(v) { ... } }` - `value` je funkce setteru
+    Set,
 }
 
 /// Klic vlastnosti v objektovem literalu.
@@ -177,6 +380,8 @@ pub enum MemberProp {
     Ident(String),
     /// Hranatobrakova notace: `obj[expr]`
     Computed(Box<Expr>),
+    /// Privatni clen tridy: `obj.#name` (jmeno bez `#`)
+    Private(String),
 }
 
 /// Telo arrow funkce - bud jeden vyraz, nebo blok prikazu.
@@ -197,6 +402,12 @@ pub enum ArrowBody {
 /// static create() { ... }           // is_static=true
 /// get value() { return this._v; }   // is_getter=true
 /// set value(v) { this._v = v; }     // is_setter=true
+/// [Symbol.iterator]() { ... }       // computed=Some(Symbol.iterator)
+/// count = 0;                        // kind=Field, value=Some(0)
+/// #secret() { ... }                 // is_private=true, name="secret"
+/// static { init(); }                // kind=StaticBlock, body=[init()]
+/// accessor size = 1;                // kind=Accessor (viz `expand_accessor`)
+/// @bound handle() { ... }           // decorators=[bound]
 /// ```
 #[derive(Debug, Clone)]
 pub struct ClassMember {
@@ -212,6 +423,80 @@ pub struct ClassMember {
     pub is_getter: bool,
     /// `true` pro setter: `set prop(v) {}`
     pub is_setter: bool,
+    /// Computed jmeno `[expr]() {}` (napr. `[Symbol.iterator]`) - `name` je pak prazdne.
+    pub computed: Option<Box<Expr>>,
+    /// Druh clenu: metoda (vcetne get/set), pole, `accessor` pole, `static {}` blok
+    pub kind: ClassMemberKind,
+    /// `true` pro privatni clen `#name` - `name` je bez `#`
+    pub is_private: bool,
+    /// Inicializator pole: `x = expr` (`None` = `undefined`)
+    pub value: Option<Box<Expr>>,
+    /// Dekoratory clenu v poradi zdrojaku (`@a @b m() {}` -> `[a, b]`)
+    pub decorators: Vec<Expr>,
+}
+
+/// Druh clenu tridy.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ClassMemberKind {
+    /// Metoda, getter nebo setter (`is_getter` / `is_setter`)
+    Method,
+    /// Pole instance nebo staticke pole: `x = 1;`
+    Field,
+    /// Auto-accessor: `accessor x = 1;`
+    Accessor,
+    /// Staticky inicializacni blok: `static { ... }` (telo v `body`)
+    StaticBlock,
+}
+
+impl ClassMember {
+    /// `constructor() {}` - jen nestaticka metoda s necomputed jmenem.
+    pub fn is_constructor(&self) -> bool {
+        self.kind == ClassMemberKind::Method && self.name == "constructor"
+            && !self.is_static && !self.is_private && self.computed.is_none()
+    }
+
+    /// Rozlozi `accessor` pole na privatni uloziste + getter/setter dvojici:
+    /// `accessor x = 1` == `#<x> = 1; get x() { return this.#<x> } set x(v) { this.#<x> = v }`.
+    /// Jmeno uloziste obsahuje mezeru (a poradi clenu `index`), takze se nemuze
+    /// potkat se zdrojovym `#jmenem`. Dekoratory zustavaji na puvodnim clenu -
+    /// dostavaji dvojici get/set najednou.
+    pub fn expand_accessor(&self, index: usize) -> [ClassMember; 3] {
+        let storage = Self::accessor_storage(index);
+        let slot = || Box::new(Expr::Member {
+            object: Box::new(Expr::Ident("this".into(), SourceSpan::default())),
+            prop: MemberProp::Private(storage.clone()),
+            optional: false,
+            span: SourceSpan::default(),
+        });
+        let part = |kind, is_getter, is_setter| ClassMember {
+            kind, is_getter, is_setter, value: None, decorators: vec![], ..self.clone()
+        };
+        [
+            ClassMember {
+                name: storage.clone(), computed: None, is_private: true,
+                value: self.value.clone(), ..part(ClassMemberKind::Field, false, false)
+            },
+            ClassMember {
+                body: vec![Stmt::Return(Some(*slot()), SourceSpan::default())],
+                ..part(ClassMemberKind::Method, true, false)
+            },
+            ClassMember {
+                params: vec![Param::simple("value".into())],
+                body: vec![Stmt::Expr(Expr::Assign {
+                    op: AssignOp::Assign,
+                    target: slot(),
+                    value: Box::new(Expr::Ident("value".into(), SourceSpan::default())),
+                    span: SourceSpan::default(),
+                }, SourceSpan::default())],
+                ..part(ClassMemberKind::Method, false, true)
+            },
+        ]
+    }
+
+    /// Jmeno privatniho uloziste `accessor` pole na pozici `index` v tele tridy.
+    pub fn accessor_storage(index: usize) -> String {
+        format!("accessor storage {index}")
+    }
 }
 
 // ─── Operatory ────────────────────────────────────────────────────────────────
@@ -231,7 +516,7 @@ pub enum UnaryOp {
 }
 
 /// Binarni operatory vcetne postfix inkrement/dekrement.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum BinaryOp {
     // Aritmetika
     Add, Sub, Mul, Div, Mod, Exp,
@@ -296,6 +581,23 @@ pub enum Pattern {
     Object(Vec<ObjectPatternProp>),
 }
 
+impl Pattern {
+    /// Jmena vsech vazeb patternu (v poradi zapisu).
+    pub fn bound_names(&self, out: &mut Vec<String>) {
+        match self {
+            Pattern::Ident(n) => out.push(n.clone()),
+            Pattern::Array(elems) => {
+                for e in elems {
+                    if let Some(p) = &e.pattern { p.bound_names(out); }
+                }
+            }
+            Pattern::Object(props) => {
+                for p in props { p.pattern.bound_names(out); }
+            }
+        }
+    }
+}
+
 /// Jeden prvek array destrukturovacniho patternu.
 #[derive(Debug, Clone)]
 pub struct ArrayPatternElem {
@@ -360,72 +662,76 @@ impl Param {
 // ─── Prikazy ──────────────────────────────────────────────────────────────────
 
 /// Prikaz (statement) - neco co se vykonava, ale primo nevraci hodnotu.
+/// Kazda varianta nese `span` (u tuple variant posledni polozka) - viz [`Stmt::span`].
 #[derive(Debug, Clone)]
 pub enum Stmt {
     /// Vyrazovy prikaz: `foo(); x = 5;`
-    Expr(Expr),
+    Expr(Expr, SourceSpan),
     /// Blok prikazu: `{ stmt1; stmt2; }`
-    Block(Vec<Stmt>),
+    Block(Vec<Stmt>, SourceSpan),
     /// Prazdny prikaz: `;`
-    Empty,
+    Empty(SourceSpan),
     /// Navrat z funkce: `return;` nebo `return expr;`
-    Return(Option<Expr>),
+    Return(Option<Expr>, SourceSpan),
     /// Preruseni cyklu nebo switch: `break;` nebo `break label;`
-    Break(Option<String>),
+    Break(Option<String>, SourceSpan),
     /// Preskoceni iterace cyklu: `continue;` nebo `continue label;`
-    Continue(Option<String>),
+    Continue(Option<String>, SourceSpan),
     /// Vyhozeni vyjimky: `throw expr;`
-    Throw(Expr),
+    Throw(Expr, SourceSpan),
 
     /// Deklarace promennych: `var x = 1;`, `let y;`, `const Z = 42;`
-    Var { kind: VarKind, decls: Vec<VarDecl> },
+    Var { kind: VarKind, decls: Vec<VarDecl>, span: SourceSpan },
 
     /// Deklarace pojmenovane funkce: `function name(params) { body }`
-    Function { name: String, params: Vec<Param>, body: Vec<Stmt> },
+    Function { name: String, params: Vec<Param>, body: Vec<Stmt>, span: SourceSpan },
 
     /// Podminene vetveni: `if (test) yes` nebo `if (test) yes else no`
-    If     { test: Expr, yes: Box<Stmt>, no: Option<Box<Stmt>> },
+    If     { test: Expr, yes: Box<Stmt>, no: Option<Box<Stmt>>, span: SourceSpan },
     /// Cyklus while: `while (test) body`
-    While  { test: Expr, body: Box<Stmt> },
+    While  { test: Expr, body: Box<Stmt>, span: SourceSpan },
     /// Cyklus do-while: `do body while (test);`
-    DoWhile { body: Box<Stmt>, test: Expr },
+    DoWhile { body: Box<Stmt>, test: Expr, span: SourceSpan },
     /// Klasicky for cyklus: `for (init; test; update) body`
-    For    { init: Option<ForInit>, test: Option<Expr>, update: Option<Expr>, body: Box<Stmt> },
+    For    { init: Option<ForInit>, test: Option<Expr>, update: Option<Expr>, body: Box<Stmt>, span: SourceSpan },
     /// For-in cyklus: `for (key in obj) body`
-    ForIn  { kind: Option<VarKind>, target: Box<Expr>, iter: Expr, body: Box<Stmt> },
+    ForIn  { kind: Option<VarKind>, target: Box<Expr>, iter: Expr, body: Box<Stmt>, span: SourceSpan },
     /// For-of cyklus: `for (val of iterable) body`
-    ForOf  { kind: Option<VarKind>, target: Box<Expr>, iter: Expr, body: Box<Stmt> },
+    ForOf  { kind: Option<VarKind>, target: Box<Expr>, iter: Expr, body: Box<Stmt>, span: SourceSpan },
     /// For-await-of cyklus: `for await (val of asyncIterable) body`
     /// Pouziva se pro async iteratory (kazdy next() vraci Promise).
-    ForAwaitOf { kind: Option<VarKind>, target: Box<Expr>, iter: Expr, body: Box<Stmt> },
+    ForAwaitOf { kind: Option<VarKind>, target: Box<Expr>, iter: Expr, body: Box<Stmt>, span: SourceSpan },
 
     /// Try-catch-finally: `try { } catch (e) { } finally { }`
-    Try { body: Vec<Stmt>, catch: Option<CatchClause>, finally: Option<Vec<Stmt>> },
+    Try { body: Vec<Stmt>, catch: Option<CatchClause>, finally: Option<Vec<Stmt>>, span: SourceSpan },
 
     /// Oznaceny prikaz: `label: stmt` (pro break/continue s labelem)
-    Labeled { label: String, body: Box<Stmt> },
+    Labeled { label: String, body: Box<Stmt>, span: SourceSpan },
 
     /// Switch: `switch (discriminant) { case x: ... default: ... }`
-    Switch { discriminant: Expr, cases: Vec<SwitchCase> },
+    Switch { discriminant: Expr, cases: Vec<SwitchCase>, span: SourceSpan },
 
-    /// Deklarace tridy: `class Foo (extends Bar)? { constructor() {} method() {} }`
+    /// Deklarace tridy: `@dec? class Foo (extends Bar)? { constructor() {} method() {} }`
     Class {
         name: String,
+        /// Dekoratory tridy v poradi zdrojaku
+        decorators: Vec<Expr>,
         /// Rodicovska trida (`extends Expr`) - vyhodnoceno za behu
         super_class: Option<Box<Expr>>,
         /// Telo tridy - seznam clenu
         body: Vec<ClassMember>,
+        span: SourceSpan,
     },
 
     /// Deklarace generator funkce: `function* name(params) { body }`
-    GeneratorFunc { name: String, params: Vec<Param>, body: Vec<Stmt> },
+    GeneratorFunc { name: String, params: Vec<Param>, body: Vec<Stmt>, span: SourceSpan },
 
     /// Deklarace async funkce: `async function name(params) { body }`
-    AsyncFunc { name: String, params: Vec<Param>, body: Vec<Stmt> },
+    AsyncFunc { name: String, params: Vec<Param>, body: Vec<Stmt>, span: SourceSpan },
 
     /// Deklarace async generator: `async function* name(params) { body }`
     /// Spojuje async/await s yield - kazdy yield se zabali do Promise.
-    AsyncGeneratorFunc { name: String, params: Vec<Param>, body: Vec<Stmt> },
+    AsyncGeneratorFunc { name: String, params: Vec<Param>, body: Vec<Stmt>, span: SourceSpan },
 
     /// `import` prikaz: nacte modul a binduje vybrane exporty do scope.
     ///
@@ -435,7 +741,7 @@ pub enum Stmt {
     /// - `import * as ns from "path"` -> namespace (specifiers = [Namespace("ns")])
     /// - `import "path"` -> side effect only (specifiers = [])
     /// - kombinace lze kombinovat
-    Import { source: String, specifiers: Vec<ImportSpecifier> },
+    Import { source: String, specifiers: Vec<ImportSpecifier>, span: SourceSpan },
 
     /// `export` prikaz: oznaci hodnotu/identifikator jako export modulu.
     ///
@@ -443,13 +749,89 @@ pub enum Stmt {
     /// - `export const x = 1` / `export function f() {}` -> Decl(Stmt)
     /// - `export default expr` -> Default(Expr)
     /// - `export { a, b as c }` -> Named(Vec<(local, exported)>)
-    /// - `export { a } from "path"` -> ReExport (zatim nepodporujeme)
-    Export(ExportKind),
+    /// - `export { a as b } from "path"` -> From { source, names }
+    /// - `export * from "path"` / `export * as ns from "path"` -> Star { source, alias }
+    Export(ExportKind, SourceSpan),
 
-    /// Wrapper s line info pro debugger / source map. Parser obali kazdy stmt.
+    /// Wrapper s pozici pro debugger / source map. Parser obali kazdy stmt.
     /// Interpreter exec_stmt extrahuje line + dispatch na inner.
     /// Renderer zde nestoji - line je 0 pri rucne tvorbe.
-    WithLine { line: u32, inner: Box<Stmt> },
+    /// `line` = `span.start.line`; `span` = rozsah celeho prikazu (sloupcove
+    /// breakpointy, ladeni minifikovanych jednoradkovych bundlu).
+    WithLine { line: u32, span: SourceSpan, inner: Box<Stmt> },
+}
+
+impl Stmt {
+    /// Rozsah prikazu ve zdrojaku (`SourceSpan::default()` = uzel bez pozice).
+    pub fn span(&self) -> SourceSpan {
+        match self {
+            Stmt::Expr(_, span)
+            | Stmt::Block(_, span)
+            | Stmt::Empty(span)
+            | Stmt::Return(_, span)
+            | Stmt::Break(_, span)
+            | Stmt::Continue(_, span)
+            | Stmt::Throw(_, span)
+            | Stmt::Var { span, .. }
+            | Stmt::Function { span, .. }
+            | Stmt::If { span, .. }
+            | Stmt::While { span, .. }
+            | Stmt::DoWhile { span, .. }
+            | Stmt::For { span, .. }
+            | Stmt::ForIn { span, .. }
+            | Stmt::ForOf { span, .. }
+            | Stmt::ForAwaitOf { span, .. }
+            | Stmt::Try { span, .. }
+            | Stmt::Labeled { span, .. }
+            | Stmt::Switch { span, .. }
+            | Stmt::Class { span, .. }
+            | Stmt::GeneratorFunc { span, .. }
+            | Stmt::AsyncFunc { span, .. }
+            | Stmt::AsyncGeneratorFunc { span, .. }
+            | Stmt::Import { span, .. }
+            | Stmt::Export(_, span)
+            | Stmt::WithLine { span, .. } => *span,
+        }
+    }
+
+    /// Doplni rozsah uzlu, ktery ho jeste nema.
+    pub fn set_span(&mut self, span: SourceSpan) {
+        let own = self.span_mut();
+        if !own.is_known() {
+            *own = span;
+        }
+    }
+
+    fn span_mut(&mut self) -> &mut SourceSpan {
+        match self {
+            Stmt::Expr(_, span)
+            | Stmt::Block(_, span)
+            | Stmt::Empty(span)
+            | Stmt::Return(_, span)
+            | Stmt::Break(_, span)
+            | Stmt::Continue(_, span)
+            | Stmt::Throw(_, span)
+            | Stmt::Var { span, .. }
+            | Stmt::Function { span, .. }
+            | Stmt::If { span, .. }
+            | Stmt::While { span, .. }
+            | Stmt::DoWhile { span, .. }
+            | Stmt::For { span, .. }
+            | Stmt::ForIn { span, .. }
+            | Stmt::ForOf { span, .. }
+            | Stmt::ForAwaitOf { span, .. }
+            | Stmt::Try { span, .. }
+            | Stmt::Labeled { span, .. }
+            | Stmt::Switch { span, .. }
+            | Stmt::Class { span, .. }
+            | Stmt::GeneratorFunc { span, .. }
+            | Stmt::AsyncFunc { span, .. }
+            | Stmt::AsyncGeneratorFunc { span, .. }
+            | Stmt::Import { span, .. }
+            | Stmt::Export(_, span)
+            | Stmt::WithLine { span, .. } => span,
+        }
+    }
 }
 
 /// Specifikator importu - co se z modulu nacita a pod jakym jmenem.
@@ -472,6 +854,10 @@ pub enum ExportKind {
     Default(Expr),
     /// `export { local as exported, ... }` - reexport identifikatoru
     Named(Vec<(String, String)>),
+    /// `export { imported as exported, ... } from "path"` - neprimy export
+    From { source: String, names: Vec<(String, String)> },
+    /// `export * from "path"` (alias None) nebo `export * as ns from "path"`
+    Star { source: String, alias: Option<String> },
 }
 
 /// Jedna vetev switch prikazu.
@@ -500,7 +886,7 @@ pub struct VarDecl {
 }
 
 /// Druh deklarace promenne.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum VarKind {
     /// `var` - function-scoped, hoistovana
     Var,
@@ -508,6 +894,22 @@ pub enum VarKind {
     Let,
     /// `const` - block-scoped, nelze priradit po inicializaci
     Const,
+    /// `using` - jako `const`; pri opusteni bloku se zavola `[Symbol.dispose]()`
+    Using,
+    /// `await using` - jako `using`, ale `[Symbol.asyncDispose]()` s await
+    AwaitUsing,
+}
+
+impl VarKind {
+    /// Deklarace s dispose pri opusteni scopu (`using` / `await using)`.
+    pub fn is_using(self) -> bool {
+        matches!(self, VarKind::Using | VarKind::AwaitUsing)
+    }
+
+    /// Vazba nelze po inicializaci prepsat (`const`, `using`).
+    pub fn is_const(self) -> bool {
+        matches!(self, VarKind::Const | VarKind::Using | VarKind::AwaitUsing)
+    }
 }
 
 /// Inicializace pro klasicky for cyklus.
@@ -537,3 +939,18 @@ pub struct Program {
     /// `true` kdyz soubor obsahuje `"use strict"` direktivu
     pub strict: bool,
 }
+
+/// Obsahuje prolog direktiv tela (uvodni retezcove vyrazove prikazy)
+/// direktivu `"use strict"`? Plati pro program i telo funkce.
+pub fn has_use_strict(body: &[Stmt]) -> bool {
+    for s in body {
+        let mut s = s;
+        while let Stmt::WithLine { inner, .. } = s { s = inner; }
+        match s {
+            Stmt::Expr(Expr::Str(d, _), _) if d == "use strict" => return true,
+            Stmt::Expr(Expr::Str(_, _), _) => {}
+            _ => return false,
+        }
+    }
+    false
+}
@@ -31,6 +31,7 @@ use crate::devtools::{DevToolsState, Tab};
 use crate::devtools::theme::Palette;
 use crate::devtools::model::elements::{ElementRow, RowKind};
 use crate::devtools::model::console::LogLevel;
+use crate::devtools::model::sources::SourceLang;
 use crate::interpreter::Interpreter;
 
 pub const ROW_H: f32 = 18.0;
@@ -1783,7 +1784,11 @@ fn paint_elements_search_bar(
     } else if s.matches.is_empty() {
         "0 / 0".to_string()
     } else {
-        format!("{} / {}", s.current + 1, s.matches.len())
+        // Pozice tagu ve zdroji (sloupec od 1 jako v Sources), pokud ji uzel ma.
+        match s.matches.get(s.current).map(|m| m.pos).filter(|p| p.line > 0) {
+            Some(p) => format!("{} / {}  {}:{}", s.current + 1, s.matches.len(), p.line, p.column + 1),
+            None => format!("{} / {}", s.current + 1, s.matches.len()),
+        }
     };
     push_text(cmds, input_x + input_w + 8.0, input_y + 4.0, counter, pal.text_dim, false);
 }
@@ -2786,10 +2791,20 @@ fn paint_source_content(
     let gutter_w = 50.0;
     push_rect(cmds, x, y, gutter_w, h, pal.bg_panel_alt);
 
+    // Toggle "{ }" pretty print - jen JS, na pravem okraji.
+    let pretty_w = if file.language == SourceLang::JavaScript { 48.0 } else { 0.0 };
+    let pretty = state.sources.shows_pretty(file.id);
+    if pretty_w > 0.0 {
+        let btn_x = x + w - pretty_w;
+        push_rect(cmds, btn_x, y + 4.0, 40.0, ROW_H, if pretty { pal.accent } else { pal.bg_button });
+        push_text(cmds, btn_x + 10.0, y + 7.0, "{ }".into(),
+                  if pretty { pal.text_inverted } else { pal.text }, false);
+    }
+
     // Toggle "Show original" button (kdyz source map je k dispozici).
     if file.source_map.is_some() {
         let btn_w = 100.0;
-        let btn_x = x + w - btn_w - 8.0;
+        let btn_x = x + w - btn_w - 8.0 - pretty_w;
         let btn_y = y + 4.0;
         let bg = if state.sources.show_original { pal.accent } else { pal.bg_button };
         push_rect(cmds, btn_x, btn_y, btn_w, ROW_H, bg);
@@ -2798,8 +2813,10 @@ fn paint_source_content(
                   if state.sources.show_original { pal.text_inverted } else { pal.text }, false);
     }
 
-    // Vyber content - bud generated nebo original z source map sourcesContent[0].
-    let content_buffer: String = if state.sources.show_original {
+    // Vyber content - pretty, generated nebo original z source map sourcesContent[0].
+    let content_buffer: String = if let Some(p) = file.pretty.as_ref().filter(|_| pretty) {
+        p.content.clone()
+    } else if state.sources.show_original {
         if let Some(map) = &file.source_map {
             if let Some(Some(orig)) = map.sources_content.first() {
                 orig.clone()
@@ -2814,9 +2831,16 @@ fn paint_source_content(
         let ly = y + (i as f32 - scroll_y / ROW_H) * ROW_H;
         if ly + ROW_H < y || ly > y + h { continue; }
         let line_no = i + 1;
-        let has_bp = state.sources.has_breakpoint(file.id, line_no as u32);
-        let is_pause = state.sources.current_pause_location ==
-                       Some((file.id, line_no as u32));
+        // Breakpointy a pauza jsou v radcich originalu - v pretty view se
+        // zvyrazni jen prvni pretty radek, ktery na dany radek mapuje.
+        let src_line = if pretty {
+            state.sources.pretty_line_to_original(file.id, line_no as u32)
+                .filter(|&l| state.sources.original_line_to_pretty(file.id, l) == Some(line_no as u32))
+        } else {
+            Some(line_no as u32)
+        };
+        let has_bp = src_line.is_some_and(|l| state.sources.has_breakpoint(file.id, l));
+        let is_pause = src_line.is_some_and(|l| state.sources.current_pause_location == Some((file.id, l)));
         if has_bp {
             push_rect(cmds, x + 4.0, ly + 2.0, gutter_w - 8.0, ROW_H - 4.0, pal.accent);
         }
@@ -3457,6 +3481,8 @@ pub enum DevtoolsHit {
     NetworkFilterClick(crate::devtools::model::network::NetworkFilter),
     /// Toggle "show original source" v Sources.
     SourcesToggleOriginal,
+    /// Toggle `{ }` pretty print v Sources.
+    SourcesTogglePretty,
     /// Continue debugger button v Sources tab.
     DebuggerContinue,
     /// Step Over button.
@@ -4103,7 +4129,7 @@ pub fn double_click_hit_elements(
 
 fn hit_test_sources(
     state: &DevToolsState,
-    _win_w: f32,
+    win_w: f32,
     content_y: f32,
     _content_h: f32,
     mouse_x: f32, mouse_y: f32,
@@ -4137,14 +4163,17 @@ fn hit_test_sources(
         }
         return DevtoolsHit::PanelArea;
     }
-    // "Show original" button (kdyz source map ma file).
-    if mouse_y < body_y + ROW_H + 8.0 {
-        if let Some(file_id) = state.sources.selected_id {
-            if let Some(file) = state.sources.files.iter().find(|f| f.id == file_id) {
-                if file.source_map.is_some() {
-                    return DevtoolsHit::SourcesToggleOriginal;
-                }
-            }
+    // "{ }" (JS) a "Show original" (kdyz source map ma file) buttony.
+    if mouse_y < body_y + ROW_H + 8.0
+        && let Some(file_id) = state.sources.selected_id
+        && let Some(file) = state.sources.files.iter().find(|f| f.id == file_id)
+    {
+        let locals_w = if state.sources.debugger_paused { 250.0 } else { 0.0 };
+        if file.language == SourceLang::JavaScript && mouse_x >= win_w - locals_w - 48.0 {
+            return DevtoolsHit::SourcesTogglePretty;
+        }
+        if file.source_map.is_some() {
+            return DevtoolsHit::SourcesToggleOriginal;
         }
     }
     // Gutter klik = toggle breakpoint.
@@ -4,10 +4,12 @@
 /// Pouziva `Rc<RefCell<NodeData>>` pro sdilene mutable references.
 /// Parent je Weak aby nedoslo k cyklum (children > parent).
 
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 use std::collections::HashMap;
 use std::rc::{Rc, Weak};
 
+use crate::ast::SourcePos;
+
 /// Typ DOM uzlu.
 /// Attributes jsou na NodeData (RefCell) aby byly mutable.
 #[derive(Debug, Clone)]
@@ -59,6 +61,9 @@ pub struct NodeData {
     pub children: RefCell<Vec<Rc<Node>>>,
     /// Listeners: event_type -> Vec<ListenerEntry> (callback id + options).
     pub listeners: RefCell<HashMap<String, Vec<ListenerEntry>>>,
+    /// Pozice otviraciho tagu v HTML zdroji stranky; radek 0 = uzel nevznikl
+    /// z parseru (skript, klon, implicitni `<tbody>`).
+    pub source_pos: Cell<SourcePos>,
 }
 
 pub type Node = NodeData;
@@ -97,6 +102,7 @@ impl NodeData {
             parent: RefCell::new(Weak::new()),
             children: RefCell::new(Vec::new()),
             listeners: RefCell::new(HashMap::new()),
+            source_pos: Cell::default(),
         })
     }
 
@@ -107,6 +113,7 @@ impl NodeData {
             parent: RefCell::new(Weak::new()),
             children: RefCell::new(Vec::new()),
             listeners: RefCell::new(HashMap::new()),
+            source_pos: Cell::default(),
         })
     }
 
@@ -117,6 +124,7 @@ impl NodeData {
             parent: RefCell::new(Weak::new()),
             children: RefCell::new(Vec::new()),
             listeners: RefCell::new(HashMap::new()),
+            source_pos: Cell::default(),
         })
     }
 
@@ -127,6 +135,7 @@ impl NodeData {
             parent: RefCell::new(Weak::new()),
             children: RefCell::new(Vec::new()),
             listeners: RefCell::new(HashMap::new()),
+            source_pos: Cell::default(),
         })
     }
 
@@ -137,6 +146,7 @@ impl NodeData {
             parent: RefCell::new(Weak::new()),
             children: RefCell::new(Vec::new()),
             listeners: RefCell::new(HashMap::new()),
+            source_pos: Cell::default(),
         })
     }
 
@@ -198,6 +208,7 @@ impl NodeData {
             parent: RefCell::new(Weak::new()),
             children: RefCell::new(Vec::new()),
             listeners: RefCell::new(HashMap::new()),
+            source_pos: Cell::default(),
         });
         if deep {
             for ch in self.children.borrow().iter() {
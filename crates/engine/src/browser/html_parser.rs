@@ -2,17 +2,20 @@
 ///
 /// Parsuje HTML5 source na nas DOM strom (browser::dom::Document).
 /// Pouziva markup5ever_rcdom jako interni reprezentaci a pak konvertuje.
+/// Elementy z `parse_html` nesou pozici sveho otviraciho tagu (`source_pos`).
 
+use std::borrow::Cow;
 use std::collections::HashMap;
 use std::rc::Rc;
-use html5ever::tendril::TendrilSink;
+use html5ever::tendril::{StrTendril, TendrilSink};
 use html5ever::{parse_document, parse_fragment};
 use html5ever::driver::ParseOpts;
-use html5ever::tree_builder::TreeBuilderOpts;
-use html5ever::{namespace_url, ns, local_name, QualName};
+use html5ever::tree_builder::{ElementFlags, NodeOrText, QuirksMode, TreeBuilderOpts, TreeSink};
+use html5ever::{namespace_url, ns, local_name, Attribute, ExpandedName, QualName};
 use markup5ever_rcdom::{RcDom, NodeData as RcNodeData, Handle};
 
 use super::dom::{Document, Node, NodeData};
+use crate::ast::SourcePos;
 
 /// Parsuje HTML source na Document.
 pub fn parse_html(source: &str, url: &str) -> Document {
@@ -23,13 +26,14 @@ pub fn parse_html(source: &str, url: &str) -> Document {
         },
         ..Default::default()
     };
-    let dom = parse_document(RcDom::default(), opts)
+    let sink = PositionSink::new(source);
+    let sink = parse_document(sink, opts)
         .from_utf8()
         .read_from(&mut source.as_bytes())
         .unwrap();
 
     let mut document = Document::empty(url.to_string());
-    convert_handle(&dom.document, &document.root);
+    convert_handle(&sink.dom.document, &document.root, &sink.positions);
 
     // Extrakce title
     if let Some(title_el) = document.root.find(|n| n.tag_name().as_deref() == Some("title")) {
@@ -49,12 +53,13 @@ pub fn parse_html_fragment(source: &str) -> Rc<Node> {
         .unwrap();
 
     let root = NodeData::new_document();
-    convert_handle(&dom.document, &root);
+    convert_handle(&dom.document, &root, &HashMap::new());
     root
 }
 
 /// Konvertuje markup5ever Handle na nas Node strom (rekurzivne).
-fn convert_handle(handle: &Handle, parent: &Rc<Node>) {
+/// `positions` mapuje handle elementu (`Rc::as_ptr`) na pozici jeho tagu.
+fn convert_handle(handle: &Handle, parent: &Rc<Node>, positions: &HashMap<usize, SourcePos>) {
     for child in handle.children.borrow().iter() {
         let node_opt: Option<Rc<Node>> = match &child.data {
             RcNodeData::Element { name, attrs, .. } => {
@@ -63,7 +68,11 @@ fn convert_handle(handle: &Handle, parent: &Rc<Node>) {
                 for attr in attrs.borrow().iter() {
                     attributes.insert(attr.name.local.to_string(), attr.value.to_string());
                 }
-                Some(NodeData::new_element(&tag, attributes))
+                let element = NodeData::new_element(&tag, attributes);
+                if let Some(pos) = positions.get(&(Rc::as_ptr(child) as usize)) {
+                    element.source_pos.set(*pos);
+                }
+                Some(element)
             }
             RcNodeData::Text { contents } => {
                 let text = contents.borrow().to_string();
@@ -83,12 +92,13 @@ fn convert_handle(handle: &Handle, parent: &Rc<Node>) {
                     parent: std::cell::RefCell::new(std::rc::Weak::new()),
                     children: std::cell::RefCell::new(Vec::new()),
                     listeners: std::cell::RefCell::new(HashMap::new()),
+                    source_pos: std::cell::Cell::default(),
                 }))
             }
             RcNodeData::Document => {
                 // Top-level document - jen recursi do children (uvnitr stacker grow).
                 stacker::maybe_grow(32 * 1024, 8 * 1024 * 1024, || {
-                    convert_handle(child, parent);
+                    convert_handle(child, parent, positions);
                 });
                 None
             }
@@ -99,7 +109,7 @@ fn convert_handle(handle: &Handle, parent: &Rc<Node>) {
             parent.append_child(Rc::clone(&node));
             // Auto-grow stack pro deep DOM nesting.
             stacker::maybe_grow(32 * 1024, 8 * 1024 * 1024, || {
-                convert_handle(child, &node);
+                convert_handle(child, &node, positions);
             });
         } else if matches!(child.data, RcNodeData::Document) {
             // Document case uz volana convert_handle vyse - mit ji v stacker chain.
@@ -107,3 +117,153 @@ fn convert_handle(handle: &Handle, parent: &Rc<Node>) {
     }
 }
 
+
+// ─── Pozice tagu ve zdroji ───────────────────────────────────────────────────
+
+/// Otviraci tag nalezeny ve zdroji: jmeno (lowercase), pozice `<` a radek `>`.
+struct SourceTag {
+    name: String,
+    pos: SourcePos,
+    end_line: u64,
+}
+
+/// Projde zdroj a vrati otviraci tagy v poradi vyskytu. Preskakuje komentare,
+/// doctype, zaviraci tagy a obsah raw-text elementu (`<script>`, `<style>`, ...).
+fn scan_start_tags(source: &str) -> Vec<SourceTag> {
+    let chars: Vec<(usize, char)> = source.char_indices().collect();
+    let mut tags = Vec::new();
+    let (mut line, mut column) = (1u32, 0u32);
+    let mut i = 0;
+    // Posune kurzor o jeden znak a aktualizuje radek/sloupec.
+    let step = |i: &mut usize, line: &mut u32, column: &mut u32| {
+        if chars[*i].1 == '\n' { *line += 1; *column = 0; } else { *column += 1; }
+        *i += 1;
+    };
+    let starts_with = |i: usize, pat: &str| source[chars[i].0..].starts_with(pat);
+    while i < chars.len() {
+        if chars[i].1 != '<' {
+            step(&mut i, &mut line, &mut column);
+            continue;
+        }
+        if starts_with(i, "<!--") {
+            while i < chars.len() && !starts_with(i, "-->") { step(&mut i, &mut line, &mut column); }
+            continue;
+        }
+        let is_tag = chars.get(i + 1).is_some_and(|(_, c)| c.is_ascii_alphabetic());
+        if !is_tag {
+            // `</x>`, `<!doctype>`, `<?pi>` nebo samotne `<` v textu
+            step(&mut i, &mut line, &mut column);
+            continue;
+        }
+        let pos = SourcePos { line, column, offset: chars[i].0 as u32 };
+        step(&mut i, &mut line, &mut column);
+        let mut name = String::new();
+        while i < chars.len() && !chars[i].1.is_whitespace() && !matches!(chars[i].1, '>' | '/') {
+            name.push(chars[i].1.to_ascii_lowercase());
+            step(&mut i, &mut line, &mut column);
+        }
+        let mut quote = None;
+        while i < chars.len() {
+            let c = chars[i].1;
+            match quote {
+                Some(q) if c == q => quote = None,
+                Some(_) => {}
+                None if c == '"' || c == '\'' => quote = Some(c),
+                None if c == '>' => break,
+                None => {}
+            }
+            step(&mut i, &mut line, &mut column);
+        }
+        let end_line = line as u64;
+        if matches!(name.as_str(), "script" | "style" | "textarea" | "title" | "xmp" | "iframe" | "noembed" | "noframes") {
+            let close = format!("</{name}");
+            while i < chars.len() && !source[chars[i].0..].get(..close.len()).is_some_and(|s| s.eq_ignore_ascii_case(&close)) {
+                step(&mut i, &mut line, &mut column);
+            }
+        }
+        tags.push(SourceTag { name, pos, end_line });
+    }
+    tags
+}
+
+/// RcDom sink, ktery elementum prirazuje pozice jejich otviracich tagu.
+///
+/// Tree builder vola `create_element` v poradi zpracovani tokenu, takze
+/// element patri k prvnimu dosud nepouzitemu tagu stejneho jmena, ktery uz
+/// tokenizer precetl (konci nejpozdeji na aktualnim radku). Implicitni elementy
+/// (`<html>`, `<tbody>` bez tagu ve zdroji) takovy tag nemaji a zustanou bez pozice.
+struct PositionSink {
+    dom: RcDom,
+    tags: Vec<SourceTag>,
+    next_tag: usize,
+    line: u64,
+    positions: HashMap<usize, SourcePos>,
+}
+
+impl PositionSink {
+    fn new(source: &str) -> Self {
+        Self { dom: RcDom::default(), tags: scan_start_tags(source), next_tag: 0, line: 1, positions: HashMap::new() }
+    }
+}
+
+impl TreeSink for PositionSink {
+    type Handle = Handle;
+    type Output = Self;
+
+    fn finish(self) -> Self { self }
+
+    fn parse_error(&mut self, msg: Cow<'static, str>) { self.dom.parse_error(msg) }
+
+    fn get_document(&mut self) -> Handle { self.dom.get_document() }
+
+    fn elem_name<'a>(&'a self, target: &'a Handle) -> ExpandedName<'a> { self.dom.elem_name(target) }
+
+    fn create_element(&mut self, name: QualName, attrs: Vec<Attribute>, flags: ElementFlags) -> Handle {
+        let found = self.tags[self.next_tag..].iter()
+            .take_while(|t| t.end_line <= self.line)
+            .position(|t| t.name.eq_ignore_ascii_case(&name.local));
+        let handle = self.dom.create_element(name, attrs, flags);
+        if let Some(idx) = found {
+            let tag = &self.tags[self.next_tag + idx];
+            self.positions.insert(Rc::as_ptr(&handle) as usize, tag.pos);
+            self.next_tag += idx + 1;
+        }
+        handle
+    }
+
+    fn create_comment(&mut self, text: StrTendril) -> Handle { self.dom.create_comment(text) }
+
+    fn create_pi(&mut self, target: StrTendril, data: StrTendril) -> Handle { self.dom.create_pi(target, data) }
+
+    fn append(&mut self, parent: &Handle, child: NodeOrText<Handle>) { self.dom.append(parent, child) }
+
+    fn append_based_on_parent_node(&mut self, element: &Handle, prev_element: &Handle, child: NodeOrText<Handle>) {
+        self.dom.append_based_on_parent_node(element, prev_element, child)
+    }
+
+    fn append_doctype_to_document(&mut self, name: StrTendril, public_id: StrTendril, system_id: StrTendril) {
+        self.dom.append_doctype_to_document(name, public_id, system_id)
+    }
+
+    fn get_template_contents(&mut self, target: &Handle) -> Handle { self.dom.get_template_contents(target) }
+
+    fn same_node(&self, x: &Handle, y: &Handle) -> bool { self.dom.same_node(x, y) }
+
+    fn set_quirks_mode(&mut self, mode: QuirksMode) { self.dom.set_quirks_mode(mode) }
+
+    fn append_before_sibling(&mut self, sibling: &Handle, new_node: NodeOrText<Handle>) {
+        self.dom.append_before_sibling(sibling, new_node)
+    }
+
+    fn add_attrs_if_missing(&mut self, target: &Handle, attrs: Vec<Attribute>) { self.dom.add_attrs_if_missing(target, attrs) }
+
+    fn remove_from_parent(&mut self, target: &Handle) { self.dom.remove_from_parent(target) }
+
+    fn reparent_children(&mut self, node: &Handle, new_parent: &Handle) { self.dom.reparent_children(node, new_parent) }
+
+    fn is_mathml_annotation_xml_integration_point(&self, handle: &Handle) -> bool {
+        self.dom.is_mathml_annotation_xml_integration_point(handle)
+    }
+
+    fn set_current_line(&mut self, line_number: u64) { self.line = line_number }
+}
@@ -1,7 +0,0 @@
-//! Locale + i18n primitives.
-
-pub mod bcp47;
-pub mod number_format;
-pub mod date_format;
-pub mod plural_rules;
-pub mod collation;
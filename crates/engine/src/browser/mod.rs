@@ -70,7 +70,6 @@ pub mod drag_drop;
 pub mod autoscroll;
 pub mod spellcheck;
 pub mod autofill;
-pub mod locale;
 pub mod favicon;
 pub mod manifest;
 pub mod password_manager;
@@ -1,20 +1,30 @@
-//! ES Modules (ESM) - import/export, dynamic import(), import.meta.
+//! ES Modules (ESM) - zaznamy modulu, staticka analyza import/export, resolve URL.
 //!
 //! Spec: https://tc39.es/ecma262/#sec-modules
 //!
-//! Foundation: module registry + URL resolution. Real loading pres existing
-//! interpreter (parse + eval s import bindings).
+//! `EsmModule` nese vysledek staticke analyzy AST (import polozky, export
+//! polozky, pozadovane moduly) a runtime stav grafu: scope modulu, namespace
+//! objekt a promise vyhodnoceni (top-level await). Nacitani grafu, linkovani
+//! live vazeb a vyhodnoceni dela interpret (`interpreter/module_loader.rs`).
 
 use std::collections::HashMap;
 use std::rc::Rc;
 use std::cell::RefCell;
 
+use crate::ast::{ExportKind, Expr, Stmt, ImportSpecifier as AstImport};
+use crate::interpreter::{Environment, JsValue};
+
+/// Jmeno vazby pro `export default <expr>` (neni dosazitelne z JS kodu).
+pub const DEFAULT_BINDING: &str = "*default*";
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum ModuleState {
     Unlinked,
     Linking,
     Linked,
     Evaluating,
+    /// Telo (nebo zavislost) ceka na top-level await.
+    EvaluatingAsync,
     Evaluated,
     Errored,
 }
@@ -34,15 +44,177 @@ pub enum ImportBinding {
     SideEffect,                                   // import "..."
 }
 
+/// Export modulu (ExportEntry ze spec).
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExportEntry {
+    /// `export const x` / `export { x as y }` / `export default` - vazba v scopu modulu.
+    Local { export: String, local: String },
+    /// `export { a as b } from "x"`; `import: None` = `export * as b from "x"`.
+    Indirect { export: String, source: String, import: Option<String> },
+    /// `export * from "x"`
+    Star { source: String },
+}
+
 #[derive(Debug)]
 pub struct EsmModule {
     pub url: String,
-    pub source: String,
     pub state: ModuleState,
+    /// Telo modulu (sdilene s ramcem top-level await).
+    pub body: Rc<Vec<Stmt>>,
     pub imports: Vec<ImportSpecifier>,
-    pub exports: Vec<String>,
-    /// Bindings: export_name -> JS value placeholder.
-    pub binding_values: HashMap<String, String>,
+    pub exports: Vec<ExportEntry>,
+    /// Pozadovane moduly v poradi zdrojaku: (specifier, resolved URL).
+    pub requested: Vec<(String, String)>,
+    /// Zdrojak obsahuje `await` - telo bezi v pozastavitelnem ramci.
+    pub has_await: bool,
+    pub env: Option<Rc<RefCell<Environment>>>,
+    pub namespace: Option<JsValue>,
+    /// Promise vyhodnoceni - jen u modulu, ktere cekaly na top-level await.
+    pub promise: Option<JsValue>,
+    /// resolve / reject funkce `promise`.
+    pub resolvers: Option<(JsValue, JsValue)>,
+    /// Pocet zavislosti, ktere jeste asynchronne dobihaji.
+    pub pending_deps: usize,
+    /// Moduly cekajici na dokonceni tohoto (URL).
+    pub async_parents: Vec<String>,
+    pub error: Option<JsValue>,
+}
+
+impl EsmModule {
+    /// Zaznam z parsovaneho tela - analyzuje import/export prikazy nejvyssi urovne.
+    pub fn new(url: &str, body: Vec<Stmt>, has_await: bool) -> Self {
+        let mut imports = Vec::new();
+        let mut exports = Vec::new();
+        for stmt in &body {
+            analyze_stmt(peel(stmt), &mut imports, &mut exports);
+        }
+        let mut requested: Vec<(String, String)> = Vec::new();
+        let sources = imports.iter().map(|i: &ImportSpecifier| i.source.as_str())
+            .chain(exports.iter().filter_map(|e| match e {
+                ExportEntry::Indirect { source, .. } | ExportEntry::Star { source } => Some(source.as_str()),
+                ExportEntry::Local { .. } => None,
+            }));
+        for source in sources {
+            if !requested.iter().any(|(s, _)| s == source) {
+                requested.push((source.to_string(), String::new()));
+            }
+        }
+        EsmModule {
+            url: url.into(),
+            state: ModuleState::Unlinked,
+            body: Rc::new(body),
+            imports,
+            exports,
+            requested,
+            has_await,
+            env: None,
+            namespace: None,
+            promise: None,
+            resolvers: None,
+            pending_deps: 0,
+            async_parents: Vec::new(),
+            error: None,
+        }
+    }
+
+    /// Resolved URL pozadovaneho modulu.
+    pub fn resolved(&self, specifier: &str) -> Option<&str> {
+        self.requested.iter().find(|(s, _)| s == specifier).map(|(_, u)| u.as_str())
+    }
+}
+
+fn peel(mut s: &Stmt) -> &Stmt {
+    while let Stmt::WithLine { inner, .. } = s {
+        s = inner;
+    }
+    s
+}
+
+fn analyze_stmt(stmt: &Stmt, imports: &mut Vec<ImportSpecifier>, exports: &mut Vec<ExportEntry>) {
+    match stmt {
+        Stmt::Import { source, specifiers, .. } => {
+            let import = |binding| ImportSpecifier { source: source.clone(), resolved_url: String::new(), binding };
+            if specifiers.is_empty() {
+                imports.push(import(ImportBinding::SideEffect));
+            }
+            let mut named = Vec::new();
+            for spec in specifiers {
+                match spec {
+                    AstImport::Default(local) => imports.push(import(ImportBinding::Default(local.clone()))),
+                    AstImport::Namespace(local) => imports.push(import(ImportBinding::Namespace(local.clone()))),
+                    AstImport::Named { imported, local } => named.push((imported.clone(), local.clone())),
+                }
+            }
+            if !named.is_empty() {
+                imports.push(import(ImportBinding::Named(named)));
+            }
+        }
+        Stmt::Export(kind, _) => match kind {
+            ExportKind::Decl(decl) => {
+                for name in declared_names(peel(decl)) {
+                    exports.push(ExportEntry::Local { export: name.clone(), local: name });
+                }
+            }
+            ExportKind::Default(_) => exports.push(ExportEntry::Local {
+                export: "default".into(),
+                local: DEFAULT_BINDING.into(),
+            }),
+            ExportKind::Named(pairs) => {
+                for (local, export) in pairs {
+                    exports.push(ExportEntry::Local { export: export.clone(), local: local.clone() });
+                }
+            }
+            ExportKind::From { source, names } => {
+                for (import, export) in names {
+                    exports.push(ExportEntry::Indirect {
+                        export: export.clone(),
+                        source: source.clone(),
+                        import: Some(import.clone()),
+                    });
+                }
+            }
+            ExportKind::Star { source, alias: Some(alias) } => exports.push(ExportEntry::Indirect {
+                export: alias.clone(),
+                source: source.clone(),
+                import: None,
+            }),
+            ExportKind::Star { source, alias: None } => exports.push(ExportEntry::Star { source: source.clone() }),
+        },
+        _ => {}
+    }
+}
+
+/// Jmena deklarovana prikazem (`export <decl>`).
+pub fn declared_names(stmt: &Stmt) -> Vec<String> {
+    let mut out = Vec::new();
+    match stmt {
+        Stmt::Var { decls, .. } => {
+            for d in decls { d.pattern.bound_names(&mut out); }
+        }
+        Stmt::Function { name, .. } | Stmt::GeneratorFunc { name, .. } | Stmt::AsyncFunc { name, .. }
+        | Stmt::AsyncGeneratorFunc { name, .. } | Stmt::Class { name, .. } => out.push(name.clone()),
+        _ => {}
+    }
+    out
+}
+
+/// Deklarace funkce nejvyssi urovne (i pod `export`) - hoistuje se pri linkovani,
+/// aby byla dostupna cyklickym importum pred vyhodnocenim tela.
+pub fn hoisted_function(stmt: &Stmt) -> Option<&Stmt> {
+    match peel(stmt) {
+        s @ (Stmt::Function { .. } | Stmt::GeneratorFunc { .. } | Stmt::AsyncFunc { .. }
+        | Stmt::AsyncGeneratorFunc { .. }) => Some(s),
+        Stmt::Export(ExportKind::Decl(d), _) => hoisted_function(d),
+        _ => None,
+    }
+}
+
+/// Je `export default` vyraz pojmenovana funkce/trida (binduje i lokalni jmeno)?
+pub fn default_export_name(expr: &Expr) -> Option<&str> {
+    match expr {
+        Expr::Function { name: Some(n), .. } | Expr::ClassExpr { name: Some(n), .. } => Some(n),
+        _ => None,
+    }
 }
 
 #[derive(Default)]
@@ -53,16 +225,16 @@ pub struct EsmRegistry {
 impl EsmRegistry {
     pub fn new() -> Self { Self::default() }
 
-    pub fn register(&mut self, url: &str, source: &str) -> Rc<RefCell<EsmModule>> {
-        let m = Rc::new(RefCell::new(EsmModule {
-            url: url.into(),
-            source: source.into(),
-            state: ModuleState::Unlinked,
-            imports: Vec::new(),
-            exports: Vec::new(),
-            binding_values: HashMap::new(),
-        }));
-        self.modules.insert(url.into(), Rc::clone(&m));
+    pub fn register(&mut self, module: EsmModule) -> Rc<RefCell<EsmModule>> {
+        let url = module.url.clone();
+        self.register_as(&url, module)
+    }
+
+    /// Registrace pod vlastnim klicem - inline `<script type="module">` ma URL
+    /// dokumentu, ale do mapy modulu nepatri (kazdy skript je novy modul).
+    pub fn register_as(&mut self, key: &str, module: EsmModule) -> Rc<RefCell<EsmModule>> {
+        let m = Rc::new(RefCell::new(module));
+        self.modules.insert(key.to_string(), Rc::clone(&m));
         m
     }
 
@@ -103,58 +275,22 @@ impl EsmRegistry {
     }
 }
 
-/// Parse simple import/export statements (foundation, not full ESM grammar).
-/// Real impl pres existing JS parser.
-pub fn parse_imports(source: &str) -> Vec<ImportSpecifier> {
-    let mut out = Vec::new();
-    for line in source.lines() {
-        let line = line.trim();
-        if !line.starts_with("import") { continue; }
-        // Naive parse: "import X from 'url'" / "import { a, b } from 'url'"
-        let from_idx = line.find("from").map(|i| i);
-        let (binding_part, url_part) = match from_idx {
-            Some(i) => (&line[6..i], &line[i+4..]),
-            None => {
-                // "import 'url'" - side-effect.
-                let raw = line[6..].trim().trim_matches(|c| c == '\'' || c == '"' || c == ';');
-                out.push(ImportSpecifier {
-                    source: raw.into(),
-                    resolved_url: String::new(),
-                    binding: ImportBinding::SideEffect,
-                });
-                continue;
-            }
-        };
-        let url = url_part.trim().trim_matches(|c| c == '\'' || c == '"' || c == ';' || c == ' ');
-        let b = binding_part.trim();
-        let binding = if b.starts_with('{') {
-            let inner = b.trim_matches(|c| c == '{' || c == '}');
-            let names: Vec<(String, String)> = inner.split(',').map(|n| {
-                let parts: Vec<&str> = n.split("as").map(|s| s.trim()).collect();
-                if parts.len() == 2 {
-                    (parts[0].to_string(), parts[1].to_string())
-                } else {
-                    (parts[0].to_string(), parts[0].to_string())
-                }
-            }).collect();
-            ImportBinding::Named(names)
-        } else if b.starts_with("* as ") {
-            ImportBinding::Namespace(b[5..].trim().to_string())
-        } else {
-            ImportBinding::Default(b.to_string())
-        };
-        out.push(ImportSpecifier {
-            source: url.into(),
-            resolved_url: String::new(),
-            binding,
-        });
-    }
-    out
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::lexer::base::Lexer;
+    use crate::parser::Parser;
+    use crate::tokens::TokenKind;
+
+    fn module(src: &str) -> EsmModule {
+        let lexer = Lexer::parse_str(src, "<test>").unwrap();
+        let tokens: Vec<_> = lexer.tokens.into_iter()
+            .filter(|t| !matches!(t.kind, TokenKind::Whitespace | TokenKind::Newline
+                | TokenKind::CommentLine(_) | TokenKind::CommentBlock(_)))
+            .collect();
+        let prog = Parser::new(tokens).parse().unwrap();
+        EsmModule::new("https://x.com/main.js", prog.body, false)
+    }
 
     #[test]
     fn resolve_relative() {
@@ -176,15 +312,15 @@ mod tests {
 
     #[test]
     fn parse_default_import() {
-        let imports = parse_imports("import foo from './foo.js';");
-        assert_eq!(imports.len(), 1);
-        assert!(matches!(imports[0].binding, ImportBinding::Default(ref n) if n == "foo"));
+        let m = module("import foo from './foo.js';");
+        assert_eq!(m.imports.len(), 1);
+        assert!(matches!(m.imports[0].binding, ImportBinding::Default(ref n) if n == "foo"));
     }
 
     #[test]
     fn parse_named_imports() {
-        let imports = parse_imports("import { a, b as c } from './lib.js';");
-        match &imports[0].binding {
+        let m = module("import { a, b as c } from './lib.js';");
+        match &m.imports[0].binding {
             ImportBinding::Named(names) => {
                 assert_eq!(names.len(), 2);
                 assert_eq!(names[1], ("b".into(), "c".into()));
@@ -195,20 +331,42 @@ mod tests {
 
     #[test]
     fn parse_namespace_import() {
-        let imports = parse_imports("import * as ns from './lib.js';");
-        assert!(matches!(imports[0].binding, ImportBinding::Namespace(ref n) if n == "ns"));
+        let m = module("import * as ns from './lib.js';");
+        assert!(matches!(m.imports[0].binding, ImportBinding::Namespace(ref n) if n == "ns"));
     }
 
     #[test]
     fn parse_side_effect_import() {
-        let imports = parse_imports("import './setup.js';");
-        assert!(matches!(imports[0].binding, ImportBinding::SideEffect));
+        let m = module("import './setup.js';");
+        assert!(matches!(m.imports[0].binding, ImportBinding::SideEffect));
+    }
+
+    #[test]
+    fn export_entries_and_requested_modules() {
+        let m = module(r#"
+            import { a } from "./a.js";
+            export const [x, y] = [1, 2];
+            export default 42;
+            export { a as b } from "./a.js";
+            export * from "./c.js";
+            export * as ns from "./d.js";
+        "#);
+        assert_eq!(m.exports, vec![
+            ExportEntry::Local { export: "x".into(), local: "x".into() },
+            ExportEntry::Local { export: "y".into(), local: "y".into() },
+            ExportEntry::Local { export: "default".into(), local: DEFAULT_BINDING.into() },
+            ExportEntry::Indirect { export: "b".into(), source: "./a.js".into(), import: Some("a".into()) },
+            ExportEntry::Star { source: "./c.js".into() },
+            ExportEntry::Indirect { export: "ns".into(), source: "./d.js".into(), import: None },
+        ]);
+        let requested: Vec<&str> = m.requested.iter().map(|(s, _)| s.as_str()).collect();
+        assert_eq!(requested, vec!["./a.js", "./c.js", "./d.js"]);
     }
 
     #[test]
     fn registry_register_and_get() {
         let mut r = EsmRegistry::new();
-        r.register("https://x.com/a.js", "export const x = 1;");
-        assert!(r.get("https://x.com/a.js").is_some());
+        r.register(module("export const x = 1;"));
+        assert!(r.get("https://x.com/main.js").is_some());
     }
 }
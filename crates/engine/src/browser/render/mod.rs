@@ -62,7 +62,7 @@ pub fn resolve_addr_input(input: &str) -> String {
 // scripts beti single-thread (UI). Pause = early-abort + rerun kompromis.
 
 mod url;
-pub use url::{fetch_text_url, fetch_image_bytes, resolve_url, cached_fetch_bytes};
+pub use url::{fetch_document, fetch_text_url, fetch_image_bytes, resolve_url, cached_fetch_bytes};
 
 // shell_chrome.rs smazany (Session N+22) - chrome paint je shell crate concern.
 
@@ -1322,13 +1322,14 @@ fn apply_paint_animations_inner(box_: &mut crate::browser::layout::LayoutBox,
     }
 }
 
-/// Eval JS via bytecode VM s globals z Interpreter env. Pri compile failure
-/// nebo runtime error vrati Err s message. Pred eval definuje `$0` (selected
+/// Eval JS via bytecode VM s globals z Interpreter env (funkce stranky vola
+/// pres interpret). Zdroj, ktery VM nezkompiluje, vyhodnoti tree-walker.
+/// Pri runtime error vrati Err s message. Pred eval definuje `$0` (selected
 /// DevTools element) jako DomNode proxy v globalu.
-fn console_eval_via_vm(src: &str, interp: &crate::interpreter::Interpreter, selected_node_id: Option<usize>) -> Result<crate::interpreter::JsValue, String> {
+fn console_eval_via_vm(src: &str, interp: &mut crate::interpreter::Interpreter, selected_node_id: Option<usize>) -> Result<crate::interpreter::JsValue, String> {
     use crate::lexer::base::Lexer;
     use crate::parser::Parser;
-    use crate::interpreter::bytecode::{compile_program, VM};
+    use crate::interpreter::bytecode::compile_program;
     use crate::interpreter::JsValue;
 
     // Definuj $0 = selected DOM node (or undefined).
@@ -1347,9 +1348,10 @@ fn console_eval_via_vm(src: &str, interp: &crate::interpreter::Interpreter, sele
     let lex = Lexer::parse_str(src, "<console>").map_err(|e| format!("Lexer: {:?}", e))?;
     let mut parser = Parser::new(lex.tokens.clone());
     let program = parser.parse().map_err(|e| format!("Parser: {:?}", e))?;
-    let code = compile_program(&program.body).map_err(|e| format!("Compile: {}", e))?;
-    let mut vm = VM::with_env(interp.global.clone());
-    vm.run(&code).map_err(|e| format!("Runtime: {}", e))
+    match compile_program(&program.body) {
+        Ok(code) => interp.run_bytecode(&code).map_err(|e| format!("Runtime: {}", e)),
+        Err(_) => interp.eval_script(&program).map_err(|e| format!("Runtime: {}", e)),
+    }
 }
 
 /// Parse CSS object-position do (x_frac, y_frac) v range [0,1].
@@ -2349,13 +2351,24 @@ fn run_window_inner(html: String, css: String, current_html_path: Option<std::pa
                                 }
                                 DevtoolsHit::SourcesFileRow(id) => {
                                     self.devtools.sources.selected_id = Some(id);
+                                    if self.devtools.sources.pretty_print {
+                                        self.devtools.sources.ensure_pretty(id);
+                                    }
                                 }
                                 DevtoolsHit::SourcesGutter { file_id, line } => {
-                                    self.devtools.sources.toggle_breakpoint(file_id, line);
-                                    // Auto-aktivace debug mode pri prvnim BP.
-                                    if self.devtools.panel_open && !self.devtools.sources.breakpoints.is_empty()
-                                        && self.debug_runner.is_none() {
-                                        self.activate_debug_mode();
+                                    // V pretty view je breakpoint na radku originalu.
+                                    let src_line = if self.devtools.sources.shows_pretty(file_id) {
+                                        self.devtools.sources.pretty_line_to_original(file_id, line)
+                                    } else {
+                                        Some(line)
+                                    };
+                                    if let Some(line) = src_line {
+                                        self.devtools.sources.toggle_breakpoint(file_id, line);
+                                        // Auto-aktivace debug mode pri prvnim BP.
+                                        if self.devtools.panel_open && !self.devtools.sources.breakpoints.is_empty()
+                                            && self.debug_runner.is_none() {
+                                            self.activate_debug_mode();
+                                        }
                                     }
                                 }
                                 DevtoolsHit::NetworkRow(idx) => {
@@ -2368,6 +2381,9 @@ fn run_window_inner(html: String, css: String, current_html_path: Option<std::pa
                                 DevtoolsHit::SourcesToggleOriginal => {
                                     self.devtools.sources.show_original = !self.devtools.sources.show_original;
                                 }
+                                DevtoolsHit::SourcesTogglePretty => {
+                                    self.devtools.sources.toggle_pretty_print();
+                                }
                                 DevtoolsHit::PanelArea => {
                                     self.devtools.focus = crate::devtools::focus::FocusTarget::Page;
                                 }
@@ -4452,6 +4468,7 @@ fn run_window_inner(html: String, css: String, current_html_path: Option<std::pa
                                     parent: std::cell::RefCell::new(std::rc::Rc::downgrade(&parent)),
                                     children: std::cell::RefCell::new(Vec::new()),
                                     listeners: std::cell::RefCell::new(std::collections::HashMap::new()),
+                                    source_pos: std::cell::Cell::default(),
                                 });
                                 kids[idx] = new_node;
                             }
@@ -4627,8 +4644,8 @@ fn run_window_inner(html: String, css: String, current_html_path: Option<std::pa
 
         fn jump_to_search_match(&mut self) {
             let s = &self.devtools.elements.search;
-            if let Some(node_id) = s.matches.get(s.current).copied() {
-                self.select_and_reveal_node(node_id);
+            if let Some(hit) = s.matches.get(s.current).copied() {
+                self.select_and_reveal_node(hit.node);
             }
         }
 
@@ -5224,7 +5241,7 @@ fn run_window_inner(html: String, css: String, current_html_path: Option<std::pa
         winit::event_loop::EventLoop::builder().with_any_thread(true).build().map_err(|e| e.to_string())?
     };
     #[cfg(not(target_os = "windows"))]
-    let event_loop = EventLoop::new().map_err(|e| e.to_string())?;
+    let event_loop = winit::event_loop::EventLoop::new().map_err(|e| e.to_string())?;
     // Title z <title> tagu nebo URL fallback.
     // Title nyni drzí webview (App polarity invert step).
     let mut app = App {
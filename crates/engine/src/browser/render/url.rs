@@ -81,25 +81,7 @@ pub fn cached_fetch_text(url: &str) -> Option<String> {
 /// Pure HTTP fetch bez cache (interni - cached_fetch_bytes vola tady pri miss).
 fn fetch_bytes_uncached(url: &str) -> Option<Vec<u8>> {
     if url.starts_with("http://") || url.starts_with("https://") {
-        match ureq::get(url)
-            .set("User-Agent", "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/131.0.0.0 Safari/537.36 RustWebEngine/0.1")
-            .set("Accept", "*/*")
-            .set("Accept-Language", "cs-CZ,cs;q=0.9,en-US;q=0.8,en;q=0.7")
-            .set("Accept-Encoding", "identity")
-            .timeout(std::time::Duration::from_secs(15))
-            .call()
-        {
-            Ok(resp) => {
-                let mut buf = Vec::new();
-                if resp.into_reader().read_to_end(&mut buf).is_ok() {
-                    Some(buf)
-                } else { None }
-            }
-            Err(e) => {
-                eprintln!("[fetch] {url}: {e}");
-                None
-            }
-        }
+        http_get(url).map(|(bytes, _)| bytes)
     } else if let Some(rest) = url.strip_prefix("file:///") {
         std::fs::read(rest.replace('/', std::path::MAIN_SEPARATOR_STR)).ok()
     } else {
@@ -107,6 +89,46 @@ fn fetch_bytes_uncached(url: &str) -> Option<Vec<u8>> {
     }
 }
 
+/// Hlavicky HTTP odpovedi (nazev, hodnota).
+type Headers = Vec<(String, String)>;
+
+/// HTTP GET - telo + hlavicky odpovedi.
+fn http_get(url: &str) -> Option<(Vec<u8>, Headers)> {
+    match ureq::get(url)
+        .set("User-Agent", "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/131.0.0.0 Safari/537.36 RustWebEngine/0.1")
+        .set("Accept", "*/*")
+        .set("Accept-Language", "cs-CZ,cs;q=0.9,en-US;q=0.8,en;q=0.7")
+        .set("Accept-Encoding", "identity")
+        .timeout(std::time::Duration::from_secs(15))
+        .call()
+    {
+        Ok(resp) => {
+            let headers = resp.headers_names().into_iter()
+                .filter_map(|name| resp.header(&name).map(|v| (name.clone(), v.to_string())))
+                .collect();
+            let mut buf = Vec::new();
+            if resp.into_reader().read_to_end(&mut buf).is_ok() {
+                Some((buf, headers))
+            } else { None }
+        }
+        Err(e) => {
+            eprintln!("[fetch] {url}: {e}");
+            None
+        }
+    }
+}
+
+/// Fetch dokumentu pro navigaci - vcetne hlavicek odpovedi (COOP/COEP), proto
+/// HTTP dokument jde mimo cache. Lokalni soubory hlavicky nemaji.
+pub fn fetch_document(url: &str) -> Option<(String, Vec<(String, String)>)> {
+    if !url.starts_with("http://") && !url.starts_with("https://") {
+        return fetch_text_url(url).map(|html| (html, Vec::new()));
+    }
+    let (bytes, headers) = http_get(url)?;
+    let html = String::from_utf8(bytes).unwrap_or_else(|e| String::from_utf8_lossy(e.as_bytes()).into_owned());
+    Some((html, headers))
+}
+
 /// Fetch text resource (HTML/CSS/JS) z URL nebo FS path. Pres cached_fetch_text -
 /// HTTP resources jsou disk + RAM cached. Pri reload stejneho hosta = OK,
 /// nemusi se redownload kazdy refresh.
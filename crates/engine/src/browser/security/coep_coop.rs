@@ -94,6 +94,21 @@ pub fn coop_requires_isolation(prev: Coop, next: Coop, is_same_origin: bool) ->
     true
 }
 
+/// Whether a document is cross-origin isolated (`self.crossOriginIsolated`):
+/// COOP same-origin plus COEP require-corp or credentialless. Gates SharedArrayBuffer.
+pub fn is_cross_origin_isolated(coop: Coop, coep: Coep) -> bool {
+    matches!(coop, Coop::SameOrigin | Coop::SameOriginPlusCoep) && coep != Coep::UnsafeNone
+}
+
+/// COOP + COEP of a document response. Missing headers mean `unsafe-none`.
+pub fn policies_from_headers(headers: &[(String, String)]) -> (Coop, Coep) {
+    let header = |name: &str| headers.iter().find(|(k, _)| k.eq_ignore_ascii_case(name)).map(|(_, v)| v.as_str());
+    (
+        header("cross-origin-opener-policy").map_or(Coop::UnsafeNone, Coop::parse),
+        header("cross-origin-embedder-policy").map_or(Coep::UnsafeNone, Coep::parse),
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -105,6 +120,16 @@ mod tests {
         assert_eq!(Coep::parse("unknown"), Coep::UnsafeNone);
     }
 
+    #[test]
+    fn policies_from_response_headers() {
+        let headers = [
+            ("Cross-Origin-Opener-Policy".to_string(), "same-origin".to_string()),
+            ("cross-origin-embedder-policy".to_string(), "require-corp".to_string()),
+        ];
+        assert_eq!(policies_from_headers(&headers), (Coop::SameOrigin, Coep::RequireCorp));
+        assert_eq!(policies_from_headers(&[]), (Coop::UnsafeNone, Coep::UnsafeNone));
+    }
+
     #[test]
     fn coop_parse_strips_params() {
         assert_eq!(Coop::parse("same-origin; report-to=\"r\""), Coop::SameOrigin);
@@ -144,4 +169,12 @@ mod tests {
     fn coop_change_isolation() {
         assert!(coop_requires_isolation(Coop::UnsafeNone, Coop::SameOrigin, true));
     }
+
+    #[test]
+    fn isolation_needs_both_headers() {
+        assert!(is_cross_origin_isolated(Coop::SameOrigin, Coep::RequireCorp));
+        assert!(is_cross_origin_isolated(Coop::SameOrigin, Coep::Credentialless));
+        assert!(!is_cross_origin_isolated(Coop::SameOriginAllowPopups, Coep::RequireCorp));
+        assert!(!is_cross_origin_isolated(Coop::SameOrigin, Coep::UnsafeNone));
+    }
 }
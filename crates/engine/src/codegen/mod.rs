@@ -0,0 +1,1316 @@
+//! Generator JS kodu z AST - opak parseru. `Program` -> zdrojovy text, bud
+//! citelne odsazeny (pretty print v DevTools Sources), nebo minifikovany.
+//!
+//! Vystup se parsuje zpet na stejny AST - az na pozice a zapisy, ktere parser
+//! stejne slucuje (`new X` -> `new X()`, `{ f: function f() {} }` -> `{ f() {} }`).
+//! Zavorky se doplnuji podle priorit operatoru, ne podle zdroje. Komentare
+//! v AST nejsou, do vystupu se tedy nedostanou.
+//!
+//! `generate_with_map` navic vede source mapu V3 (`interpreter::source_map`):
+//! segment na zacatku kazdeho prikazu (`Stmt::WithLine`) a volani (`span` u
+//! `Call` / `New`) ukazuje do puvodniho zdroje. Sloupce se pocitaji ve znacich,
+//! stejne jako `SourcePos`.
+
+use crate::ast::*;
+use crate::interpreter::source_map::{Mapping, SourceMap};
+use crate::lexer::base::Lexer;
+use crate::parser::Parser;
+use crate::tokens::TokenKind;
+
+/// Nastaveni generatoru.
+#[derive(Debug, Clone)]
+pub struct CodegenOptions {
+    /// Bez zalomeni radku a mezer, ktere nejsou nutne k oddeleni tokenu.
+    pub minify: bool,
+    /// Odsazeni jedne urovne vnoreni (jen pretty vystup).
+    pub indent: String,
+}
+
+impl Default for CodegenOptions {
+    fn default() -> Self {
+        Self::pretty()
+    }
+}
+
+impl CodegenOptions {
+    /// Citelny vystup - prikaz na radek, odsazeni dvema mezerami.
+    pub fn pretty() -> Self {
+        CodegenOptions { minify: false, indent: "  ".into() }
+    }
+
+    /// Minifikovany vystup na jednom radku.
+    pub fn minified() -> Self {
+        CodegenOptions { minify: true, indent: String::new() }
+    }
+}
+
+/// Vygeneruje JS kod programu.
+pub fn generate(program: &Program, opts: &CodegenOptions) -> String {
+    let mut p = Printer::new(opts, false);
+    p.program(program);
+    p.out
+}
+
+/// Jako `generate`, navic source mapa z vygenerovaneho kodu zpet do zdroje
+/// `source` (jediny zaznam v `sources`).
+pub fn generate_with_map(program: &Program, opts: &CodegenOptions, source: &str) -> (String, SourceMap) {
+    let mut p = Printer::new(opts, true);
+    p.program(program);
+    let map = SourceMap {
+        version: 3,
+        sources: vec![source.to_string()],
+        mappings: p.mappings,
+        ..Default::default()
+    };
+    (p.out, map)
+}
+
+/// Naparsuje skript a vrati jeho pretty-printed podobu se source mapou do `src`.
+/// Chyba lexeru/parseru se vraci jako text `SyntaxError: ...`.
+pub fn pretty_print(src: &str, name: &str) -> Result<(String, SourceMap), String> {
+    let lex = Lexer::parse_str(src, name).map_err(|e| format!("SyntaxError: {e}"))?;
+    let tokens: Vec<_> = lex.tokens.into_iter()
+        .filter(|t| !matches!(t.kind,
+            TokenKind::Whitespace | TokenKind::Newline
+            | TokenKind::CommentLine(_) | TokenKind::CommentBlock(_)))
+        .collect();
+    let program = Parser::new(tokens).parse().map_err(|e| format!("SyntaxError: {e}"))?;
+    Ok(generate_with_map(&program, &CodegenOptions::pretty(), name))
+}
+
+// ─── Priority operatoru ───────────────────────────────────────────────────────
+
+// Vyssi cislo = tesnejsi vazba. Podvyraz s nizsi prioritou, nez jeho pozice
+// vyzaduje, dostane zavorky.
+const SEQUENCE: u8 = 1;
+const ASSIGN: u8 = 2;
+const CONDITIONAL: u8 = 3;
+const OR: u8 = 4;
+const AND: u8 = 5;
+const RELATIONAL: u8 = 10;
+const EXPONENT: u8 = 14;
+const UNARY: u8 = 15;
+const POSTFIX: u8 = 16;
+const CALL: u8 = 17;
+const MEMBER: u8 = 18;
+const PRIMARY: u8 = 19;
+
+fn binary_prec(op: BinaryOp) -> u8 {
+    match op {
+        BinaryOp::BitOr => 6,
+        BinaryOp::BitXor => 7,
+        BinaryOp::BitAnd => 8,
+        BinaryOp::Eq | BinaryOp::NotEq | BinaryOp::StrictEq | BinaryOp::StrictNotEq => 9,
+        BinaryOp::Lt | BinaryOp::Gt | BinaryOp::LtEq | BinaryOp::GtEq
+        | BinaryOp::In | BinaryOp::Instanceof => RELATIONAL,
+        BinaryOp::Shl | BinaryOp::Shr | BinaryOp::Ushr => 11,
+        BinaryOp::Add | BinaryOp::Sub => 12,
+        BinaryOp::Mul | BinaryOp::Div | BinaryOp::Mod => 13,
+        BinaryOp::Exp => EXPONENT,
+        BinaryOp::PostInc | BinaryOp::PostDec => POSTFIX,
+    }
+}
+
+fn binary_str(op: BinaryOp) -> &'static str {
+    match op {
+        BinaryOp::Add => "+", BinaryOp::Sub => "-", BinaryOp::Mul => "*",
+        BinaryOp::Div => "/", BinaryOp::Mod => "%", BinaryOp::Exp => "**",
+        BinaryOp::Eq => "==", BinaryOp::NotEq => "!=",
+        BinaryOp::StrictEq => "===", BinaryOp::StrictNotEq => "!==",
+        BinaryOp::Lt => "<", BinaryOp::Gt => ">", BinaryOp::LtEq => "<=", BinaryOp::GtEq => ">=",
+        BinaryOp::BitAnd => "&", BinaryOp::BitOr => "|", BinaryOp::BitXor => "^",
+        BinaryOp::Shl => "<<", BinaryOp::Shr => ">>", BinaryOp::Ushr => ">>>",
+        BinaryOp::In => "in", BinaryOp::Instanceof => "instanceof",
+        BinaryOp::PostInc => "++", BinaryOp::PostDec => "--",
+    }
+}
+
+fn logical_str(op: &LogicalOp) -> &'static str {
+    match op {
+        LogicalOp::And => "&&",
+        LogicalOp::Or => "||",
+        LogicalOp::NullCoal => "??",
+    }
+}
+
+fn assign_str(op: &AssignOp) -> &'static str {
+    match op {
+        AssignOp::Assign => "=",
+        AssignOp::Add => "+=", AssignOp::Sub => "-=", AssignOp::Mul => "*=",
+        AssignOp::Div => "/=", AssignOp::Mod => "%=", AssignOp::Exp => "**=",
+        AssignOp::BitAnd => "&=", AssignOp::BitOr => "|=", AssignOp::BitXor => "^=",
+        AssignOp::Shl => "<<=", AssignOp::Shr => ">>=", AssignOp::Ushr => ">>>=",
+        AssignOp::LogicalAnd => "&&=", AssignOp::LogicalOr => "||=", AssignOp::NullCoal => "??=",
+    }
+}
+
+fn unary_str(op: &UnaryOp) -> &'static str {
+    match op {
+        UnaryOp::Minus => "-", UnaryOp::Plus => "+", UnaryOp::Not => "!", UnaryOp::BitNot => "~",
+        UnaryOp::Typeof => "typeof", UnaryOp::Void => "void", UnaryOp::Delete => "delete",
+        UnaryOp::PreInc => "++", UnaryOp::PreDec => "--",
+    }
+}
+
+fn var_kind_str(kind: VarKind) -> &'static str {
+    match kind {
+        VarKind::Var => "var",
+        VarKind::Let => "let",
+        VarKind::Const => "const",
+        VarKind::Using => "using",
+        VarKind::AwaitUsing => "await using",
+    }
+}
+
+fn prec(e: &Expr) -> u8 {
+    match e {
+        Expr::Sequence(_, _) => SEQUENCE,
+        Expr::Assign { .. } | Expr::Arrow { .. } | Expr::Yield { .. } | Expr::Spread(_, _)
+        | Expr::AsyncFunc { is_arrow: true, .. } => ASSIGN,
+        Expr::Ternary { .. } => CONDITIONAL,
+        Expr::Logical { op: LogicalOp::And, .. } => AND,
+        Expr::Logical { .. } => OR,
+        Expr::Binary { op, .. } => binary_prec(*op),
+        Expr::PrivateIn { .. } => RELATIONAL,
+        Expr::Unary { .. } | Expr::Await { .. } | Expr::Undefined(_) => UNARY,
+        Expr::Number(n, _) if n.is_sign_negative() && !n.is_nan() => UNARY,
+        Expr::BigInt(s, _) if s.starts_with('-') => UNARY,
+        Expr::Call { .. } | Expr::DynamicImport(_, _) | Expr::TaggedTemplate { .. } => CALL,
+        Expr::Member { .. } | Expr::New { .. } => MEMBER,
+        _ => PRIMARY,
+    }
+}
+
+/// `??` nelze bez zavorek kombinovat s `||` / `&&`.
+fn mixes_nullish(parent: &LogicalOp, child: &Expr) -> bool {
+    matches!(child, Expr::Logical { op, .. }
+        if (*parent == LogicalOp::NullCoal) != (*op == LogicalOp::NullCoal))
+}
+
+/// Nejlevejsi podvyraz, ktery se vypise bez zavorek - urcuje, jakym tokenem
+/// vypis vyrazu zacina. Kdyz dostane zavorky potomek, vraci rodice.
+fn leftmost(e: &Expr) -> &Expr {
+    let (child, min): (&Expr, u8) = match e {
+        Expr::Member { object, .. } if !matches!(**object, Expr::Number(_, _)) => (&**object, CALL),
+        Expr::Call { callee, .. } => (&**callee, CALL),
+        Expr::TaggedTemplate { tag, .. } => (&**tag, CALL),
+        Expr::Binary { op: BinaryOp::PostInc | BinaryOp::PostDec, left, .. } => (&**left, CALL),
+        Expr::Binary { op: BinaryOp::Exp, left, .. } => (&**left, POSTFIX),
+        Expr::Binary { op, left, .. } => (&**left, binary_prec(*op)),
+        Expr::Logical { op, left, .. } if !mixes_nullish(op, left) => (&**left, prec(e)),
+        Expr::Ternary { test, .. } => (&**test, OR),
+        Expr::Assign { target, .. } => (&**target, CALL),
+        Expr::Sequence(items, _) if !items.is_empty() => (&items[0], ASSIGN),
+        _ => return e,
+    };
+    if prec(child) < min { e } else { leftmost(child) }
+}
+
+/// Vyraz by na zacatku prikazu cetl parser jako deklaraci, blok nebo jinak
+/// (`function`, `class`, `{`, `let [`, regex za `)` v `if (x) /re/...`).
+fn ambiguous_at_statement_start(e: &Expr) -> bool {
+    match leftmost(e) {
+        Expr::Function { .. } | Expr::GeneratorFunc { .. } | Expr::AsyncGeneratorFunc { .. }
+        | Expr::ClassExpr { .. } | Expr::Object(_, _) | Expr::Regex(..) => true,
+        Expr::AsyncFunc { is_arrow, .. } => !is_arrow,
+        Expr::Ident(n, _) => n == "let",
+        _ => false,
+    }
+}
+
+fn is_function_like(e: &Expr) -> bool {
+    matches!(e, Expr::Function { .. } | Expr::GeneratorFunc { .. } | Expr::AsyncGeneratorFunc { .. }
+        | Expr::ClassExpr { .. } | Expr::AsyncFunc { is_arrow: false, .. })
+}
+
+/// Callee `new` nesmi obsahovat volani - `new (f())()` by bez zavorek bylo `new f()()`.
+fn contains_call(e: &Expr) -> bool {
+    match e {
+        Expr::Call { .. } | Expr::TaggedTemplate { .. } | Expr::DynamicImport(_, _) => true,
+        Expr::Member { optional: true, .. } => true,
+        Expr::Member { object, .. } => contains_call(object),
+        _ => false,
+    }
+}
+
+/// `if (a) if (b) x; else y` - else by se prilepil k vnitrnimu `if`.
+fn ends_with_open_if(s: &Stmt) -> bool {
+    match s {
+        Stmt::WithLine { inner, .. } => ends_with_open_if(inner),
+        Stmt::If { no: None, .. } => true,
+        Stmt::If { no: Some(no), .. } => ends_with_open_if(no),
+        Stmt::While { body, .. } | Stmt::For { body, .. } | Stmt::ForIn { body, .. }
+        | Stmt::ForOf { body, .. } | Stmt::ForAwaitOf { body, .. }
+        | Stmt::Labeled { body, .. } => ends_with_open_if(body),
+        _ => false,
+    }
+}
+
+fn strip_line(s: &Stmt) -> &Stmt {
+    match s {
+        Stmt::WithLine { inner, .. } => strip_line(inner),
+        other => other,
+    }
+}
+
+/// Telo async arrow funkce s vyrazem - parser ho uklada jako `[Return(expr)]` bez pozice.
+fn concise_body(body: &[Stmt]) -> Option<&Expr> {
+    match body {
+        [Stmt::Return(Some(e), _)] => Some(e),
+        _ => None,
+    }
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '$' || c == '\\'
+}
+
+/// Musi mezi dva sousedni tokeny prijit mezera, aby se neslily v jeden
+/// (`typeof x`, `a- -b`, `a/ /re/`) nebo nezacal komentar (`<!--`)?
+fn needs_space(prev: char, next: char) -> bool {
+    (is_word_char(prev) && is_word_char(next))
+        || (prev == '+' && next == '+')
+        || (prev == '-' && next == '-')
+        || (prev == '/' && (next == '/' || next == '*'))
+        || (prev == '<' && next == '!')
+}
+
+fn is_identifier_name(s: &str) -> bool {
+    let mut chars = s.chars();
+    chars.next().is_some_and(|c| c.is_alphabetic() || c == '_' || c == '$')
+        && chars.all(|c| c.is_alphanumeric() || c == '_' || c == '$')
+}
+
+/// Jmeno clenu tridy: identifikator nebo celociselny klic se pise primo, ostatni jako retezec.
+fn member_name(name: &str) -> String {
+    let index = !name.is_empty() && name.bytes().all(|b| b.is_ascii_digit())
+        && (name == "0" || !name.starts_with('0'));
+    if is_identifier_name(name) || index { name.to_string() } else { quote(name) }
+}
+
+/// Ciselny literal - jako `Number.prototype.toString`, exponent jen mimo 1e-6..1e21.
+pub fn number_literal(n: f64) -> String {
+    if n.is_nan() {
+        return "NaN".into();
+    }
+    if n.is_infinite() {
+        return if n > 0.0 { "Infinity" } else { "-Infinity" }.into();
+    }
+    if n == 0.0 {
+        return if n.is_sign_negative() { "-0" } else { "0" }.into();
+    }
+    if (1e-6..1e21).contains(&n.abs()) { format!("{n}") } else { format!("{n:e}") }
+}
+
+/// Retezcovy literal. Uvozovky se volí tak, aby bylo potreba co nejmene escapu.
+pub fn quote(s: &str) -> String {
+    let q = if s.contains('"') && !s.contains('\'') { '\'' } else { '"' };
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push(q);
+    let mut chars = s.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            '\0' if !chars.peek().is_some_and(char::is_ascii_digit) => out.push_str("\\0"),
+            '\u{2028}' | '\u{2029}' => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c if c == q => { out.push('\\'); out.push(c); }
+            c if c < ' ' || c == '\u{7f}' => out.push_str(&format!("\\x{:02x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push(q);
+    out
+}
+
+/// Text mezi `${...}` v template literalu (cooked hodnota zpet na zdroj).
+fn template_text(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut chars = s.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            '`' => out.push_str("\\`"),
+            '$' if chars.peek() == Some(&'{') => out.push_str("\\$"),
+            '\r' => out.push_str("\\r"),
+            '\n' | '\t' => out.push(c),
+            c if c < ' ' || c == '\u{7f}' => out.push_str(&format!("\\x{:02x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+// ─── Vypis ────────────────────────────────────────────────────────────────────
+
+struct Printer<'a> {
+    opts: &'a CodegenOptions,
+    out: String,
+    depth: usize,
+    /// Sloupec vystupu (ve znacich) pro source mapu.
+    column: u32,
+    /// Vede se source mapa?
+    track: bool,
+    /// Segmenty source mapy po radcich vystupu.
+    mappings: Vec<Vec<Mapping>>,
+    /// Pozice ve zdroji, ktera se namapuje na nasledujici token (az za
+    /// pripadnou oddelovaci mezerou).
+    pending: Option<SourcePos>,
+}
+
+impl<'a> Printer<'a> {
+    fn new(opts: &'a CodegenOptions, track: bool) -> Self {
+        Printer { opts, out: String::new(), depth: 0, column: 0, track, mappings: vec![Vec::new()], pending: None }
+    }
+
+    fn program(&mut self, program: &Program) {
+        for s in &program.body {
+            self.newline();
+            self.stmt(s);
+        }
+        if !self.opts.minify && !self.out.is_empty() {
+            self.raw("\n");
+        }
+    }
+
+    // ─── Zapis textu ─────────────────────────────────────────────────────────
+
+    fn raw(&mut self, s: &str) {
+        for c in s.chars() {
+            if c == '\n' {
+                self.column = 0;
+                if self.track { self.mappings.push(Vec::new()); }
+            } else {
+                self.column += 1;
+            }
+        }
+        self.out.push_str(s);
+    }
+
+    /// Token - s oddelovaci mezerou, kdyz by splynul s predchozim.
+    fn token(&mut self, s: &str) {
+        if let (Some(prev), Some(next)) = (self.out.chars().next_back(), s.chars().next())
+            && needs_space(prev, next)
+        {
+            self.raw(" ");
+        }
+        if let Some(pos) = self.pending.take() {
+            let line = self.mappings.last_mut().expect("aspon jeden radek");
+            if line.last().is_none_or(|m| m.generated_column != self.column) {
+                line.push(Mapping {
+                    generated_column: self.column,
+                    source_index: Some(0),
+                    source_line: Some(pos.line - 1),
+                    source_column: Some(pos.column),
+                    name_index: None,
+                });
+            }
+        }
+        self.raw(s);
+    }
+
+    fn space(&mut self) {
+        if !self.opts.minify { self.raw(" "); }
+    }
+
+    fn newline(&mut self) {
+        if self.opts.minify { return; }
+        if !self.out.is_empty() { self.raw("\n"); }
+        let indent = self.opts.indent.repeat(self.depth);
+        self.raw(&indent);
+    }
+
+    /// Binarni operator - v pretty vystupu s mezerami kolem.
+    fn op(&mut self, s: &str) {
+        self.space();
+        self.token(s);
+        self.space();
+    }
+
+    fn comma(&mut self) {
+        self.token(",");
+        self.space();
+    }
+
+    fn mark(&mut self, span: &SourceSpan) {
+        if self.track && span.is_known() && self.pending.is_none() {
+            self.pending = Some(span.start);
+        }
+    }
+
+    // ─── Prikazy ─────────────────────────────────────────────────────────────
+
+    fn stmts(&mut self, body: &[Stmt]) {
+        for s in body {
+            self.newline();
+            self.stmt(s);
+        }
+    }
+
+    fn block(&mut self, body: &[Stmt]) {
+        self.token("{");
+        if !body.is_empty() {
+            self.depth += 1;
+            self.stmts(body);
+            self.depth -= 1;
+            self.newline();
+        }
+        self.token("}");
+    }
+
+    /// Telo `if` / cyklu: blok na stejnem radku, jiny prikaz odsazeny na dalsim.
+    fn body(&mut self, s: &Stmt) {
+        match strip_line(s) {
+            Stmt::Block(_, _) => {
+                self.space();
+                self.stmt(s);
+            }
+            Stmt::Empty(_) => self.token(";"),
+            _ => {
+                self.depth += 1;
+                self.newline();
+                self.stmt(s);
+                self.depth -= 1;
+            }
+        }
+    }
+
+    fn stmt(&mut self, s: &Stmt) {
+        match s {
+            Stmt::WithLine { span, inner, .. } => {
+                self.mark(span);
+                self.stmt(inner);
+            }
+            Stmt::Expr(e, _) => {
+                if ambiguous_at_statement_start(e) {
+                    self.token("(");
+                    self.expr(e, SEQUENCE);
+                    self.token(")");
+                } else {
+                    self.expr(e, SEQUENCE);
+                }
+                self.token(";");
+            }
+            Stmt::Block(body, _) => self.block(body),
+            Stmt::Empty(_) => self.token(";"),
+            Stmt::Return(value, _) => {
+                self.token("return");
+                if let Some(v) = value {
+                    self.space();
+                    self.expr(v, SEQUENCE);
+                }
+                self.token(";");
+            }
+            Stmt::Break(label, _) | Stmt::Continue(label, _) => {
+                self.token(if matches!(s, Stmt::Break(_, _)) { "break" } else { "continue" });
+                if let Some(l) = label {
+                    self.space();
+                    self.token(l);
+                }
+                self.token(";");
+            }
+            Stmt::Throw(e, _) => {
+                self.token("throw");
+                self.space();
+                self.expr(e, SEQUENCE);
+                self.token(";");
+            }
+            Stmt::Var { kind, decls, .. } => {
+                self.var_decl(*kind, decls);
+                self.token(";");
+            }
+            Stmt::Function { name, params, body, .. } => self.function(false, false, Some(name.as_str()), params, body),
+            Stmt::GeneratorFunc { name, params, body, .. } => self.function(false, true, Some(name.as_str()), params, body),
+            Stmt::AsyncFunc { name, params, body, .. } => self.function(true, false, Some(name.as_str()), params, body),
+            Stmt::AsyncGeneratorFunc { name, params, body, .. } => self.function(true, true, Some(name.as_str()), params, body),
+            Stmt::If { test, yes, no, .. } => self.if_stmt(test, yes, no.as_deref()),
+            Stmt::While { test, body, .. } => {
+                self.token("while");
+                self.space();
+                self.paren_expr(test);
+                self.body(body);
+            }
+            Stmt::DoWhile { body, test, .. } => {
+                self.token("do");
+                self.body(body);
+                if matches!(strip_line(body), Stmt::Block(_, _)) { self.space(); } else { self.newline(); }
+                self.token("while");
+                self.space();
+                self.paren_expr(test);
+                self.token(";");
+            }
+            Stmt::For { init, test, update, body, .. } => {
+                self.token("for");
+                self.space();
+                self.token("(");
+                match init {
+                    Some(ForInit::Var { kind, decls }) => self.var_decl(*kind, decls),
+                    Some(ForInit::Expr(e)) => self.expr(e, SEQUENCE),
+                    None => {}
+                }
+                self.token(";");
+                if let Some(t) = test {
+                    self.space();
+                    self.expr(t, SEQUENCE);
+                }
+                self.token(";");
+                if let Some(u) = update {
+                    self.space();
+                    self.expr(u, SEQUENCE);
+                }
+                self.token(")");
+                self.body(body);
+            }
+            Stmt::ForIn { kind, target, iter, body, .. } => self.for_each(false, "in", *kind, target, iter, body),
+            Stmt::ForOf { kind, target, iter, body, .. } => self.for_each(false, "of", *kind, target, iter, body),
+            Stmt::ForAwaitOf { kind, target, iter, body, .. } => self.for_each(true, "of", *kind, target, iter, body),
+            Stmt::Try { body, catch, finally, .. } => {
+                self.token("try");
+                self.space();
+                self.block(body);
+                if let Some(c) = catch {
+                    self.space();
+                    self.token("catch");
+                    if let Some(p) = &c.param {
+                        self.space();
+                        self.token("(");
+                        self.token(p);
+                        self.token(")");
+                    }
+                    self.space();
+                    self.block(&c.body);
+                }
+                if let Some(f) = finally {
+                    self.space();
+                    self.token("finally");
+                    self.space();
+                    self.block(f);
+                }
+            }
+            Stmt::Labeled { label, body, .. } => {
+                self.token(label);
+                self.token(":");
+                self.space();
+                self.stmt(body);
+            }
+            Stmt::Switch { discriminant, cases, .. } => {
+                self.token("switch");
+                self.space();
+                self.paren_expr(discriminant);
+                self.space();
+                self.token("{");
+                self.depth += 1;
+                for case in cases {
+                    self.newline();
+                    match &case.test {
+                        Some(t) => {
+                            self.token("case");
+                            self.space();
+                            self.expr(t, ASSIGN);
+                        }
+                        None => self.token("default"),
+                    }
+                    self.token(":");
+                    self.depth += 1;
+                    self.stmts(&case.body);
+                    self.depth -= 1;
+                }
+                self.depth -= 1;
+                if !cases.is_empty() { self.newline(); }
+                self.token("}");
+            }
+            Stmt::Class { name, decorators, super_class, body, .. } => {
+                self.class(Some(name.as_str()), decorators, super_class.as_deref(), body);
+            }
+            Stmt::Import { source, specifiers, .. } => self.import(source, specifiers),
+            Stmt::Export(kind, _) => self.export(kind),
+        }
+    }
+
+    fn paren_expr(&mut self, e: &Expr) {
+        self.token("(");
+        self.expr(e, SEQUENCE);
+        self.token(")");
+    }
+
+    fn var_decl(&mut self, kind: VarKind, decls: &[VarDecl]) {
+        self.token(var_kind_str(kind));
+        self.space();
+        for (i, d) in decls.iter().enumerate() {
+            if i > 0 { self.comma(); }
+            self.pattern(&d.pattern);
+            if let Some(init) = &d.init {
+                self.op("=");
+                self.expr(init, ASSIGN);
+            }
+        }
+    }
+
+    fn if_stmt(&mut self, test: &Expr, yes: &Stmt, no: Option<&Stmt>) {
+        self.token("if");
+        self.space();
+        self.paren_expr(test);
+        // Bez bloku by `else` patril vnitrnimu `if` bez vlastni else vetve
+        let braced = no.is_some() && ends_with_open_if(yes);
+        if braced {
+            self.space();
+            self.block(std::slice::from_ref(yes));
+        } else {
+            self.body(yes);
+        }
+        let Some(no) = no else { return };
+        if braced || matches!(strip_line(yes), Stmt::Block(_, _)) { self.space(); } else { self.newline(); }
+        self.token("else");
+        if matches!(strip_line(no), Stmt::If { .. }) {
+            self.space();
+            self.stmt(no);
+        } else {
+            self.body(no);
+        }
+    }
+
+    fn for_each(&mut self, is_await: bool, kw: &str, kind: Option<VarKind>, target: &Expr, iter: &Expr, body: &Stmt) {
+        self.token("for");
+        if is_await {
+            self.space();
+            self.token("await");
+        }
+        self.space();
+        self.token("(");
+        if let Some(k) = kind {
+            self.token(var_kind_str(k));
+            self.space();
+        }
+        self.expr(target, CALL);
+        self.space();
+        self.token(kw);
+        self.space();
+        // for-in bere cely vyraz, for-of jen prirazovaci
+        self.expr(iter, if kw == "in" { SEQUENCE } else { ASSIGN });
+        self.token(")");
+        self.body(body);
+    }
+
+    fn import(&mut self, source: &str, specifiers: &[ImportSpecifier]) {
+        self.token("import");
+        if !specifiers.is_empty() {
+            self.space();
+            let mut first = true;
+            for s in specifiers {
+                if let ImportSpecifier::Default(name) = s {
+                    self.token(name);
+                    first = false;
+                }
+            }
+            for s in specifiers {
+                if let ImportSpecifier::Namespace(ns) = s {
+                    if !first { self.comma(); }
+                    self.token("*");
+                    self.space();
+                    self.token("as");
+                    self.space();
+                    self.token(ns);
+                    first = false;
+                }
+            }
+            let named: Vec<(&str, &str)> = specifiers.iter().filter_map(|s| match s {
+                ImportSpecifier::Named { imported, local } => Some((imported.as_str(), local.as_str())),
+                _ => None,
+            }).collect();
+            if !named.is_empty() {
+                if !first { self.comma(); }
+                self.names(&named);
+            }
+            self.space();
+            self.token("from");
+        }
+        self.space();
+        self.token(&quote(source));
+        self.token(";");
+    }
+
+    /// `{ a, b as c }` v importu/exportu.
+    fn names(&mut self, names: &[(&str, &str)]) {
+        self.token("{");
+        if !names.is_empty() { self.space(); }
+        for (i, (from, to)) in names.iter().enumerate() {
+            if i > 0 { self.comma(); }
+            self.token(from);
+            if from != to {
+                self.space();
+                self.token("as");
+                self.space();
+                self.token(to);
+            }
+        }
+        if !names.is_empty() { self.space(); }
+        self.token("}");
+    }
+
+    fn export(&mut self, kind: &ExportKind) {
+        self.token("export");
+        self.space();
+        match kind {
+            ExportKind::Decl(decl) => self.stmt(decl),
+            ExportKind::Default(e) => {
+                self.token("default");
+                self.space();
+                if is_function_like(e) {
+                    self.expr(e, ASSIGN);
+                } else if is_function_like(leftmost(e)) {
+                    // `export default (function () {})()` - bez zavorek by to byla deklarace
+                    self.token("(");
+                    self.expr(e, SEQUENCE);
+                    self.token(")");
+                    self.token(";");
+                } else {
+                    self.expr(e, ASSIGN);
+                    self.token(";");
+                }
+            }
+            ExportKind::Named(names) => {
+                let names: Vec<(&str, &str)> = names.iter().map(|(l, e)| (l.as_str(), e.as_str())).collect();
+                self.names(&names);
+                self.token(";");
+            }
+            ExportKind::From { source, names } => {
+                let names: Vec<(&str, &str)> = names.iter().map(|(l, e)| (l.as_str(), e.as_str())).collect();
+                self.names(&names);
+                self.space();
+                self.token("from");
+                self.space();
+                self.token(&quote(source));
+                self.token(";");
+            }
+            ExportKind::Star { source, alias } => {
+                self.token("*");
+                if let Some(a) = alias {
+                    self.space();
+                    self.token("as");
+                    self.space();
+                    self.token(a);
+                }
+                self.space();
+                self.token("from");
+                self.space();
+                self.token(&quote(source));
+                self.token(";");
+            }
+        }
+    }
+
+    // ─── Funkce a tridy ──────────────────────────────────────────────────────
+
+    fn function(&mut self, is_async: bool, star: bool, name: Option<&str>, params: &[Param], body: &[Stmt]) {
+        if is_async {
+            self.token("async");
+            self.space();
+        }
+        self.token("function");
+        if star { self.token("*"); }
+        self.space();
+        if let Some(n) = name { self.token(n); }
+        self.params(params);
+        self.space();
+        self.block(body);
+    }
+
+    fn params(&mut self, params: &[Param]) {
+        self.token("(");
+        for (i, p) in params.iter().enumerate() {
+            if i > 0 { self.comma(); }
+            if p.rest { self.token("..."); }
+            self.pattern(&p.pattern);
+            if let Some(d) = &p.default {
+                self.op("=");
+                self.expr(d, ASSIGN);
+            }
+        }
+        self.token(")");
+    }
+
+    /// Parametry arrow funkce - jediny prosty identifikator bez zavorek.
+    fn arrow_params(&mut self, params: &[Param]) {
+        match params {
+            [Param { pattern: Pattern::Ident(n), default: None, rest: false }] => self.token(n),
+            _ => self.params(params),
+        }
+        self.op("=>");
+    }
+
+    fn arrow_expr_body(&mut self, body: &Expr) {
+        if matches!(leftmost(body), Expr::Object(_, _)) {
+            self.token("(");
+            self.expr(body, SEQUENCE);
+            self.token(")");
+        } else {
+            self.expr(body, ASSIGN);
+        }
+    }
+
+    fn class(&mut self, name: Option<&str>, decorators: &[Expr], super_class: Option<&Expr>, body: &[ClassMember]) {
+        for d in decorators {
+            self.decorator(d);
+            self.space();
+        }
+        self.token("class");
+        if let Some(n) = name {
+            self.space();
+            self.token(n);
+        }
+        if let Some(s) = super_class {
+            self.space();
+            self.token("extends");
+            self.space();
+            self.expr(s, CALL);
+        }
+        self.space();
+        self.token("{");
+        if !body.is_empty() {
+            self.depth += 1;
+            for m in body {
+                self.newline();
+                self.class_member(m);
+            }
+            self.depth -= 1;
+            self.newline();
+        }
+        self.token("}");
+    }
+
+    fn class_member(&mut self, m: &ClassMember) {
+        for d in &m.decorators {
+            self.decorator(d);
+            self.space();
+        }
+        if m.kind == ClassMemberKind::StaticBlock {
+            self.token("static");
+            self.space();
+            self.block(&m.body);
+            return;
+        }
+        if m.is_static {
+            self.token("static");
+            self.space();
+        }
+        let modifier = match m.kind {
+            ClassMemberKind::Accessor => Some("accessor"),
+            _ if m.is_getter => Some("get"),
+            _ if m.is_setter => Some("set"),
+            _ => None,
+        };
+        if let Some(kw) = modifier {
+            self.token(kw);
+            self.space();
+        }
+        if let Some(key) = &m.computed {
+            self.token("[");
+            self.expr(key, ASSIGN);
+            self.token("]");
+        } else if m.is_private {
+            self.token(&format!("#{}", m.name));
+        } else {
+            self.token(&member_name(&m.name));
+        }
+        match m.kind {
+            ClassMemberKind::Field | ClassMemberKind::Accessor => {
+                if let Some(v) = &m.value {
+                    self.op("=");
+                    self.expr(v, ASSIGN);
+                }
+                self.token(";");
+            }
+            _ => {
+                self.params(&m.params);
+                self.space();
+                self.block(&m.body);
+            }
+        }
+    }
+
+    /// `@a.b.#c` / `@a.b(args)` primo, jiny vyraz jako `@(expr)`.
+    fn decorator(&mut self, e: &Expr) {
+        fn chain(e: &Expr) -> bool {
+            match e {
+                Expr::Ident(_, _) => true,
+                Expr::Member { object, prop: MemberProp::Ident(_) | MemberProp::Private(_), optional: false, .. } => chain(object),
+                _ => false,
+            }
+        }
+        let plain = match e {
+            Expr::Call { callee, optional: false, .. } => chain(callee),
+            other => chain(other),
+        };
+        self.token("@");
+        if plain {
+            self.expr(e, CALL);
+        } else {
+            self.paren_expr(e);
+        }
+    }
+
+    // ─── Vzory ───────────────────────────────────────────────────────────────
+
+    fn pattern(&mut self, p: &Pattern) {
+        match p {
+            Pattern::Ident(n) => self.token(n),
+            Pattern::Array(elems) => {
+                self.token("[");
+                for (i, el) in elems.iter().enumerate() {
+                    if i > 0 { self.comma(); }
+                    if let Some(pat) = &el.pattern {
+                        if el.rest { self.token("..."); }
+                        self.pattern(pat);
+                        if let Some(d) = &el.default {
+                            self.op("=");
+                            self.expr(d, ASSIGN);
+                        }
+                    }
+                }
+                if elems.last().is_some_and(|el| el.pattern.is_none()) { self.token(","); }
+                self.token("]");
+            }
+            Pattern::Object(props) => {
+                self.token("{");
+                if !props.is_empty() { self.space(); }
+                for (i, prop) in props.iter().enumerate() {
+                    if i > 0 { self.comma(); }
+                    match (&prop.key, &prop.pattern) {
+                        (PropKey::Spread, pat) => {
+                            self.token("...");
+                            self.pattern(pat);
+                        }
+                        (PropKey::Ident(k), Pattern::Ident(n)) if prop.shorthand && k == n => self.token(n),
+                        (key, pat) => {
+                            self.prop_key(key);
+                            self.token(":");
+                            self.space();
+                            self.pattern(pat);
+                        }
+                    }
+                    if let Some(d) = &prop.default {
+                        self.op("=");
+                        self.expr(d, ASSIGN);
+                    }
+                }
+                if !props.is_empty() { self.space(); }
+                self.token("}");
+            }
+        }
+    }
+
+    fn prop_key(&mut self, key: &PropKey) {
+        match key {
+            PropKey::Ident(s) => self.token(s),
+            PropKey::Str(s) => self.token(&quote(s)),
+            PropKey::Num(n) => self.token(&number_literal(*n)),
+            PropKey::Computed(e) => {
+                self.token("[");
+                self.expr(e, ASSIGN);
+                self.token("]");
+            }
+            PropKey::Spread => {}
+        }
+    }
+
+    // ─── Vyrazy ──────────────────────────────────────────────────────────────
+
+    /// Vyraz na pozici s minimalni prioritou `min` - jinak v zavorkach.
+    fn expr(&mut self, e: &Expr, min: u8) {
+        if prec(e) < min {
+            self.paren_expr(e);
+        } else {
+            self.expr_inner(e);
+        }
+    }
+
+    fn expr_inner(&mut self, e: &Expr) {
+        match e {
+            Expr::Number(n, _) => self.token(&number_literal(*n)),
+            Expr::BigInt(s, _) => self.token(&format!("{s}n")),
+            Expr::Str(s, _) => self.token(&quote(s)),
+            Expr::Bool(b, _) => self.token(if *b { "true" } else { "false" }),
+            Expr::Null(_) => self.token("null"),
+            Expr::Undefined(_) => {
+                self.token("void");
+                self.space();
+                self.token("0");
+            }
+            Expr::Regex(pattern, flags, _) => {
+                // `a/ /re/` nebo `return/re/` by se cetlo jinak
+                if self.out.chars().next_back().is_some_and(|c| is_word_char(c) || c == '/') {
+                    self.raw(" ");
+                }
+                self.token(&format!("/{pattern}/{flags}"));
+            }
+            Expr::Template { quasis, expressions, .. } => {
+                self.token("`");
+                for (i, q) in quasis.iter().enumerate() {
+                    self.raw(&template_text(q));
+                    if let Some(e) = expressions.get(i) {
+                        self.raw("${");
+                        self.expr(e, SEQUENCE);
+                        self.raw("}");
+                    }
+                }
+                self.raw("`");
+            }
+            Expr::TaggedTemplate { tag, raw, expressions, .. } => {
+                self.expr(tag, CALL);
+                self.token("`");
+                for (i, q) in raw.iter().enumerate() {
+                    self.raw(q);
+                    if let Some(e) = expressions.get(i) {
+                        self.raw("${");
+                        self.expr(e, SEQUENCE);
+                        self.raw("}");
+                    }
+                }
+                self.raw("`");
+            }
+            Expr::Ident(n, _) => self.token(n),
+            Expr::Array(items, _) => {
+                self.token("[");
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 { self.comma(); }
+                    if let Some(e) = item { self.expr(e, ASSIGN); }
+                }
+                if items.last().is_some_and(Option::is_none) { self.token(","); }
+                self.token("]");
+            }
+            Expr::Object(props, _) => self.object(props),
+            Expr::Unary { op, arg, .. } => {
+                let s = unary_str(op);
+                self.token(s);
+                if s.starts_with(char::is_alphabetic) { self.space(); }
+                self.expr(arg, UNARY);
+            }
+            Expr::Binary { op: op @ (BinaryOp::PostInc | BinaryOp::PostDec), left, .. } => {
+                self.expr(left, CALL);
+                self.token(binary_str(*op));
+            }
+            Expr::Binary { op, left, right, .. } => {
+                let p = binary_prec(*op);
+                // `**` je pravo-asociativni a levy operand nesmi byt unarni (`(-x) ** 2`)
+                let (lmin, rmin) = if *op == BinaryOp::Exp { (POSTFIX, p) } else { (p, p + 1) };
+                self.expr(left, lmin);
+                self.op(binary_str(*op));
+                self.expr(right, rmin);
+            }
+            Expr::PrivateIn { name, object, .. } => {
+                self.token(&format!("#{name}"));
+                self.op("in");
+                self.expr(object, RELATIONAL + 1);
+            }
+            Expr::Logical { op, left, right, .. } => {
+                let p = prec(e);
+                self.logical_operand(op, left, p);
+                self.op(logical_str(op));
+                self.logical_operand(op, right, p + 1);
+            }
+            Expr::Ternary { test, yes, no, .. } => {
+                self.expr(test, OR);
+                self.op("?");
+                self.expr(yes, ASSIGN);
+                self.op(":");
+                self.expr(no, ASSIGN);
+            }
+            Expr::Assign { op, target, value, .. } => {
+                self.expr(target, CALL);
+                self.op(assign_str(op));
+                self.expr(value, ASSIGN);
+            }
+            Expr::Call { callee, args, optional, span } => {
+                self.mark(span);
+                self.expr(callee, CALL);
+                if *optional { self.token("?."); }
+                self.args(args);
+            }
+            Expr::New { callee, args, span } => {
+                self.mark(span);
+                self.token("new");
+                self.space();
+                if contains_call(callee) {
+                    self.paren_expr(callee);
+                } else {
+                    self.expr(callee, MEMBER);
+                }
+                self.args(args);
+            }
+            Expr::Member { object, prop, optional, .. } => {
+                // `1.x` by lexer cetl jako cislo `1.`
+                if matches!(**object, Expr::Number(_, _)) {
+                    self.paren_expr(object);
+                } else {
+                    self.expr(object, CALL);
+                }
+                match prop {
+                    MemberProp::Ident(n) => {
+                        self.token(if *optional { "?." } else { "." });
+                        self.token(n);
+                    }
+                    MemberProp::Private(n) => {
+                        self.token(if *optional { "?." } else { "." });
+                        self.token(&format!("#{n}"));
+                    }
+                    MemberProp::Computed(key) => {
+                        if *optional { self.token("?."); }
+                        self.token("[");
+                        self.expr(key, SEQUENCE);
+                        self.token("]");
+                    }
+                }
+            }
+            Expr::Function { name, params, body, .. } => self.function(false, false, name.as_deref(), params, body),
+            Expr::GeneratorFunc { name, params, body, .. } => self.function(false, true, name.as_deref(), params, body),
+            Expr::AsyncGeneratorFunc { name, params, body, .. } => self.function(true, true, name.as_deref(), params, body),
+            Expr::AsyncFunc { name, params, body, is_arrow: false, .. } => self.function(true, false, name.as_deref(), params, body),
+            Expr::AsyncFunc { params, body, is_arrow: true, .. } => {
+                self.token("async");
+                self.space();
+                self.arrow_params(params);
+                match concise_body(body) {
+                    Some(e) => self.arrow_expr_body(e),
+                    None => self.block(body),
+                }
+            }
+            Expr::Arrow { params, body, .. } => {
+                self.arrow_params(params);
+                match body {
+                    ArrowBody::Expr(e) => self.arrow_expr_body(e),
+                    ArrowBody::Block(b) => self.block(b),
+                }
+            }
+            Expr::Spread(inner, _) => {
+                self.token("...");
+                self.expr(inner, ASSIGN);
+            }
+            Expr::Sequence(items, _) => {
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 { self.comma(); }
+                    self.expr(item, ASSIGN);
+                }
+            }
+            Expr::ClassExpr { name, decorators, super_class, body, .. } => {
+                self.class(name.as_deref(), decorators, super_class.as_deref(), body);
+            }
+            Expr::Yield { value, delegate, .. } => {
+                self.token("yield");
+                if *delegate { self.token("*"); }
+                if let Some(v) = value {
+                    self.space();
+                    self.expr(v, ASSIGN);
+                }
+            }
+            Expr::Await { value, .. } => {
+                self.token("await");
+                self.space();
+                self.expr(value, UNARY);
+            }
+            Expr::DynamicImport(arg, _) => {
+                self.token("import");
+                self.token("(");
+                self.expr(arg, ASSIGN);
+                self.token(")");
+            }
+        }
+    }
+
+    fn logical_operand(&mut self, parent: &LogicalOp, child: &Expr, min: u8) {
+        if mixes_nullish(parent, child) {
+            self.paren_expr(child);
+        } else {
+            self.expr(child, min);
+        }
+    }
+
+    fn args(&mut self, args: &[Expr]) {
+        self.token("(");
+        for (i, a) in args.iter().enumerate() {
+            if i > 0 { self.comma(); }
+            self.expr(a, ASSIGN);
+        }
+        self.token(")");
+    }
+
+    fn object(&mut self, props: &[ObjectProp]) {
+        if props.is_empty() {
+            self.token("{");
+            self.token("}");
+            return;
+        }
+        // Objekty s metodami/funkcemi po vlastnosti na radek, ostatni na jednom radku
+        let multiline = !self.opts.minify && props.iter().any(|p| {
+            p.kind != PropKind::Init || matches!(*p.value,
+                Expr::Function { .. } | Expr::GeneratorFunc { .. } | Expr::AsyncFunc { .. }
+                | Expr::AsyncGeneratorFunc { .. } | Expr::ClassExpr { .. }
+                | Expr::Arrow { body: ArrowBody::Block(_), .. })
+        });
+        self.token("{");
+        if multiline {
+            self.depth += 1;
+            for (i, p) in props.iter().enumerate() {
+                if i > 0 { self.token(","); }
+                self.newline();
+                self.prop(p);
+            }
+            self.depth -= 1;
+            self.newline();
+        } else {
+            self.space();
+            for (i, p) in props.iter().enumerate() {
+                if i > 0 { self.comma(); }
+                self.prop(p);
+            }
+            self.space();
+        }
+        self.token("}");
+    }
+
+    fn prop(&mut self, p: &ObjectProp) {
+        if matches!(p.key, PropKey::Spread) {
+            self.token("...");
+            self.expr(&p.value, ASSIGN);
+            return;
+        }
+        if let Expr::Function { name, params, body, .. } = &*p.value {
+            let accessor = match p.kind {
+                PropKind::Get => Some("get"),
+                PropKind::Set => Some("set"),
+                PropKind::Init => None,
+            };
+            // Parser dava metode `{ f() {} }` jmeno klice, computed metode zadne
+            let method = match &p.key {
+                PropKey::Ident(k) | PropKey::Str(k) => name.as_deref() == Some(k.as_str()),
+                PropKey::Computed(_) => name.is_none(),
+                _ => false,
+            };
+            if accessor.is_some() || method {
+                if let Some(kw) = accessor {
+                    self.token(kw);
+                    self.space();
+                }
+                self.prop_key(&p.key);
+                self.params(params);
+                self.space();
+                self.block(body);
+                return;
+            }
+        }
+        if let (PropKey::Ident(k), Expr::Ident(v, _)) = (&p.key, &*p.value)
+            && p.shorthand && k == v
+        {
+            self.token(k);
+            return;
+        }
+        self.prop_key(&p.key);
+        self.token(":");
+        self.space();
+        self.expr(&p.value, ASSIGN);
+    }
+}
+
+#[cfg(test)]
+mod tests;
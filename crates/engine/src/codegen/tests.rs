@@ -0,0 +1,204 @@
+use super::*;
+use crate::interpreter::Interpreter;
+
+fn parse(src: &str) -> Program {
+    let lexer = Lexer::parse_str(src, "<test>").unwrap();
+    let tokens: Vec<_> = lexer.tokens.into_iter()
+        .filter(|t| !matches!(t.kind, TokenKind::Whitespace | TokenKind::Newline
+            | TokenKind::CommentLine(_) | TokenKind::CommentBlock(_)))
+        .collect();
+    Parser::new(tokens).parse().unwrap_or_else(|e| panic!("{e}\n--- zdroj ---\n{src}"))
+}
+
+fn pretty(src: &str) -> String {
+    generate(&parse(src), &CodegenOptions::pretty())
+}
+
+fn minify(src: &str) -> String {
+    generate(&parse(src), &CodegenOptions::minified())
+}
+
+fn eval(src: &str) -> String {
+    Interpreter::new().eval_script(&parse(src)).map(|v| v.pretty_print()).unwrap_or_else(|e| format!("{e:?}"))
+}
+
+const CORPUS: &[&str] = &[
+    "var a = 1, b; let [c, , d = 2, ...e] = f; const { g, h: i = 3, ...j } = k;",
+    "a = b ? c : d ? e : f; a += b ** -c; x = (a, b);",
+    "a = (b + c) * d - (e - f) - -g + +h; i = a - (b - c); j = 2 ** 3 ** 2; k = (2 ** 3) ** 2;",
+    "x = a ?? (b || c); y = (a && b) ?? c; z = a || b && c; w = (a || b) && c;",
+    "x++; --y; z = -x++; delete a[b]; void f(); typeof typeof x; !(a instanceof B);",
+    "if (a) b(); else if (c) d(); else { e(); }",
+    "if (a) { if (b) c(); } else d();",
+    "for (let i = 0; i < n; i++) { if (i % 2) continue; s += i; }",
+    "for (const k in o) ; for (const [k, v] of m) { out.push(k + v); }",
+    "async function f() { for await (const x of gen()) await x; }",
+    "outer: for (;;) { while (a) { do b++; while (c); break outer; } }",
+    "switch (x) { case 1: case 2: y(); break; default: z(); }",
+    "try { a(); } catch (e) { b(e); } finally { c(); } try { d(); } catch { }",
+    "function* g(a, b = 1, ...rest) { yield; yield a; yield* rest; return b; }",
+    "const f = (a) => a * 2, g = () => ({ a: 1 }), h = async x => await x, k = async () => { return 1; };",
+    "(function () { return this; })(); (() => 1)(); new (f())(); new a.b.C(1); new new X()();",
+    "class A extends B { static #count = 0; x = 1; #y; static { A.init(); } constructor(a) { super(a); } get v() { return this.#y; } set v(n) { this.#y = n; } [Symbol.iterator]() {} 'quoted name'() {} has(o) { return #y in o; } }",
+    "const o = { a, b: 1, 'c d': 2, 3: 4, [k]: 5, ...rest, m() { return 1; }, get g() { return 2; }, set s(v) {}, [Symbol.iterator]() {} };",
+    "x = `a${b}c${`nested ${d}`}`; y = tag`raw\\n${e}`; z = /re[/]g/gi.test(s) ? 1 / 2 / 3 : 0;",
+    "s = 'it\\'s \"q\"'; t = \"line\\nbreak\\ttab\\\\\"; u = '\\x7f' + '\\u2028';",
+    "n = [0, 1.5, 1e21, 1e-7, 0.1, 123456789, 0xff, 1n, .5];",
+    "a?.b?.[c]?.(d); (1).toString(); 1.5.toFixed(); x = a.b.c(d)[e];",
+    "let x = function named() {}; let y = class Z {}; ({}).toString(); (function () {});",
+];
+
+const MODULES: &[&str] = &[
+    "import d, { a, b as c } from 'mod'; import * as ns from \"ns\"; import 'side';",
+    "export const x = 1; export function f() {} export class C {} export { x as y, f }; export * from 'a'; export * as b from 'b'; export { c } from 'c';",
+    "export default function () {}",
+    "export default (function () {})();",
+    "export default a + b;",
+];
+
+fn parse_module(src: &str) -> Program {
+    let lexer = Lexer::parse_str(src, "<test>").unwrap();
+    let tokens: Vec<_> = lexer.tokens.into_iter()
+        .filter(|t| !matches!(t.kind, TokenKind::Whitespace | TokenKind::Newline
+            | TokenKind::CommentLine(_) | TokenKind::CommentBlock(_)))
+        .collect();
+    Parser::new(tokens).parse_module().unwrap_or_else(|e| panic!("{e}\n--- zdroj ---\n{src}"))
+}
+
+#[test]
+fn round_trip_is_stable() {
+    // gen(parse(gen(parse(src)))) == gen(parse(src)) - vystup se parsuje zpet na stejny AST
+    for opts in [CodegenOptions::pretty(), CodegenOptions::minified()] {
+        for src in CORPUS {
+            let once = generate(&parse(src), &opts);
+            let twice = generate(&parse(&once), &opts);
+            assert_eq!(once, twice, "zdroj: {src}");
+        }
+        for src in MODULES {
+            let once = generate(&parse_module(src), &opts);
+            let twice = generate(&parse_module(&once), &opts);
+            assert_eq!(once, twice, "zdroj: {src}");
+        }
+    }
+}
+
+#[test]
+fn parenthesizes_by_precedence() {
+    assert_eq!(minify("x = (a + b) * c - (d - e);"), "x=(a+b)*c-(d-e);");
+    assert_eq!(minify("x = a + (b * c);"), "x=a+b*c;");
+    assert_eq!(minify("x = a - -b + +c;"), "x=a- -b+ +c;");
+    assert_eq!(minify("x = (-a) ** 2; y = a ** b ** c; z = (a ** b) ** c;"), "x=(-a)**2;y=a**b**c;z=(a**b)**c;");
+    assert_eq!(minify("x = a ?? (b || c); y = (a && b) ?? c;"), "x=a??(b||c);y=(a&&b)??c;");
+    assert_eq!(minify("x = (a, b); f((a, b), c);"), "x=(a,b);f((a,b),c);");
+    assert_eq!(minify("x = (a ? b : c) ? d : e;"), "x=(a?b:c)?d:e;");
+    assert_eq!(minify("typeof x; a in b; x = y instanceof Z;"), "typeof x;a in b;x=y instanceof Z;");
+}
+
+#[test]
+fn wraps_ambiguous_expression_statements() {
+    assert_eq!(minify("(function () {})();"), "(function(){}());");
+    assert_eq!(minify("({ a: 1 }).a;"), "({a:1}.a);");
+    assert_eq!(minify("f = () => ({});"), "f=()=>({});");
+    assert_eq!(minify("(1).toString();"), "(1).toString();");
+    assert_eq!(minify("new (f())(); new X;"), "new(f())();new X();");
+    assert_eq!(pretty("export default (function () {})();"), "export default (function () {}());\n");
+}
+
+#[test]
+fn keeps_dangling_else_on_outer_if() {
+    let src = "if (a) { if (b) c(); } else d();";
+    assert_eq!(minify(src), "if(a){if(b)c();}else d();");
+    assert_eq!(pretty(src), "if (a) {\n  if (b)\n    c();\n} else\n  d();\n");
+}
+
+#[test]
+fn pretty_layout() {
+    let src = "function f(a){if(a){return 1}else if(!a){return 2}for(;;)break;const o={m(){return 3},x:1};switch(a){case 1:f();default:}}";
+    let expected = "\
+function f(a) {
+  if (a) {
+    return 1;
+  } else if (!a) {
+    return 2;
+  }
+  for (;;)
+    break;
+  const o = {
+    m() {
+      return 3;
+    },
+    x: 1
+  };
+  switch (a) {
+    case 1:
+      f();
+    default:
+  }
+}
+";
+    assert_eq!(pretty(src), expected);
+    assert_eq!(pretty("let p = { x: 1, y: [2, 3] };"), "let p = { x: 1, y: [2, 3] };\n");
+}
+
+#[test]
+fn literals() {
+    assert_eq!(minify(r#"a = "it's"; b = 'say "hi"'; c = "both ' \"";"#), r#"a="it's";b='say "hi"';c="both ' \"";"#);
+    assert_eq!(minify(r"a = '\n\t\\\x01';"), r#"a="\n\t\\\x01";"#);
+    assert_eq!(minify("x = `a\\`b${c}\\${d}`;"), "x=`a\\`b${c}\\${d}`;");
+    assert_eq!(minify("x = tag`\\n${a}`;"), "x=tag`\\n${a}`;");
+    assert_eq!(minify("x = [1e21, 0.000001, 1e-7, 255, 0.5, 10n];"), "x=[1e21,0.000001,1e-7,255,0.5,10n];");
+    assert_eq!(minify("x = a / /re/g.exec(b);"), "x=a/ /re/g.exec(b);");
+    assert_eq!(number_literal(-0.0), "-0");
+    assert_eq!(number_literal(f64::INFINITY), "Infinity");
+}
+
+#[test]
+fn classes_and_modules() {
+    assert_eq!(
+        minify("class A extends B { static #n = 1; get x() { return 1; } static { } 'a b'() {} }"),
+        "class A extends B{static#n=1;get x(){return 1;}static{}\"a b\"(){}}"
+    );
+    let out = generate(&parse_module("import d, { a as b } from 'm'; export { b as c }; export default class {}"), &CodegenOptions::minified());
+    assert_eq!(out, "import d,{a as b}from\"m\";export{b as c};export default class{}");
+}
+
+#[test]
+fn minified_code_behaves_the_same() {
+    let src = "
+        function fib(n) { return n < 2 ? n : fib(n - 1) + fib(n - 2); }
+        const o = { a: 1, get b() { return this.a + 1; }, m() { return [this.a, this.b]; } };
+        let out = [];
+        for (const [k, v] of Object.entries({ x: 1, y: 2 })) out.push(k + v);
+        class P { #v = 2; static make() { return new P(); } get v() { return this.#v ** 3; } }
+        let s = 0;
+        outer: for (let i = 0; i < 5; i++) { for (let j = 0; j < 5; j++) { if (j > i) continue outer; s += j; } }
+        [fib(10), o.m().join(), out.join(), P.make().v, s, `t${1 + 1}`, (-2) ** 2, typeof void 0].join('|');
+    ";
+    let expected = eval(&src);
+    assert!(!expected.contains("Error"), "{expected}");
+    assert_eq!(eval(&minify(&src)), expected);
+    assert_eq!(eval(&pretty(&src)), expected);
+}
+
+#[test]
+fn source_map_points_to_original_lines() {
+    let src = "var a = 1;\nfunction f() {\n  return g(a);\n}\n\n\nf();";
+    let (code, map) = pretty_print(src, "app.js").unwrap();
+    assert_eq!(code, "var a = 1;\nfunction f() {\n  return g(a);\n}\nf();\n");
+    assert_eq!(map.sources, vec!["app.js".to_string()]);
+    // `f();` je ve vystupu na radku 4, ve zdroji na radku 6 (0-based)
+    let (file, line, col, _) = map.original_position(4, 0).unwrap();
+    assert_eq!((file.as_str(), line, col), ("app.js", 6, 0));
+    // `return` uvnitr funkce
+    assert_eq!(map.original_position(2, 2).map(|p| (p.1, p.2)), Some((2, 2)));
+    // serializace a zpetne cteni mapovani
+    let json: serde_json::Value = serde_json::from_str(&map.to_json()).unwrap();
+    let mappings = crate::interpreter::source_map::parse_mappings(json["mappings"].as_str().unwrap());
+    assert_eq!(mappings.len(), map.mappings.len());
+}
+
+#[test]
+fn pretty_print_reports_syntax_errors() {
+    let err = pretty_print("let = ;", "bad.js").unwrap_err();
+    assert!(err.starts_with("SyntaxError"), "{err}");
+}
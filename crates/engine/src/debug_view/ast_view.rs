@@ -48,39 +48,40 @@ fn leaf(label: &str, kind: &str) -> String {
 pub fn render_stmt(s: &Stmt) -> String {
     let mut out = String::new();
     match s {
-        Stmt::WithLine { line, inner } => {
-            return format!("<span class=\"line-marker\">@{}</span>{}", line, render_stmt(inner));
+        Stmt::WithLine { line, span, inner } => {
+            let at = if span.is_known() { span.to_string() } else { line.to_string() };
+            return format!("<span class=\"line-marker\">@{}</span>{}", at, render_stmt(inner));
         }
-        Stmt::Expr(e) => {
+        Stmt::Expr(e, _) => {
             out.push_str(&open_node("ExpressionStatement", "stmt"));
             out.push_str(&render_expr(e));
             out.push_str(close_node());
         }
-        Stmt::Block(b) => {
+        Stmt::Block(b, _) => {
             out.push_str(&open_node(&format!("Block ({} stmts)", b.len()), "stmt"));
             for s in b { out.push_str(&render_stmt(s)); }
             out.push_str(close_node());
         }
-        Stmt::Empty => {
+        Stmt::Empty(_) => {
             out.push_str(&leaf("EmptyStatement", "stmt"));
         }
-        Stmt::Return(v) => {
+        Stmt::Return(v, _) => {
             out.push_str(&open_node("ReturnStatement", "stmt"));
             if let Some(e) = v { out.push_str(&render_expr(e)); }
             out.push_str(close_node());
         }
-        Stmt::Break(l) => {
+        Stmt::Break(l, _) => {
             out.push_str(&leaf(&format!("Break {}", l.as_deref().unwrap_or("")), "stmt"));
         }
-        Stmt::Continue(l) => {
+        Stmt::Continue(l, _) => {
             out.push_str(&leaf(&format!("Continue {}", l.as_deref().unwrap_or("")), "stmt"));
         }
-        Stmt::Throw(e) => {
+        Stmt::Throw(e, _) => {
             out.push_str(&open_node("ThrowStatement", "stmt"));
             out.push_str(&render_expr(e));
             out.push_str(close_node());
         }
-        Stmt::Var { kind, decls } => {
+        Stmt::Var { kind, decls, .. } => {
             out.push_str(&open_node(&format!("VariableDeclaration ({:?}, {} decls)", kind, decls.len()), "stmt"));
             for d in decls {
                 out.push_str(&open_node(&format!("VarDecl {}", pattern_label(&d.pattern)), "decl"));
@@ -93,7 +94,7 @@ pub fn render_stmt(s: &Stmt) -> String {
             }
             out.push_str(close_node());
         }
-        Stmt::Function { name, params, body } => {
+        Stmt::Function { name, params, body, .. } => {
             out.push_str(&open_node(&format!("FunctionDeclaration: {name} ({} params)", params.len()), "stmt"));
             out.push_str(&render_params(params));
             out.push_str(&open_node(&format!("body ({} stmts)", body.len()), "field"));
@@ -101,7 +102,7 @@ pub fn render_stmt(s: &Stmt) -> String {
             out.push_str(close_node());
             out.push_str(close_node());
         }
-        Stmt::If { test, yes, no } => {
+        Stmt::If { test, yes, no, .. } => {
             out.push_str(&open_node("IfStatement", "stmt"));
             out.push_str(&open_node("test", "field"));
             out.push_str(&render_expr(test));
@@ -116,7 +117,7 @@ pub fn render_stmt(s: &Stmt) -> String {
             }
             out.push_str(close_node());
         }
-        Stmt::While { test, body } => {
+        Stmt::While { test, body, .. } => {
             out.push_str(&open_node("WhileStatement", "stmt"));
             out.push_str(&open_node("test", "field"));
             out.push_str(&render_expr(test));
@@ -124,7 +125,7 @@ pub fn render_stmt(s: &Stmt) -> String {
             out.push_str(&render_stmt(body));
             out.push_str(close_node());
         }
-        Stmt::DoWhile { body, test } => {
+        Stmt::DoWhile { body, test, .. } => {
             out.push_str(&open_node("DoWhileStatement", "stmt"));
             out.push_str(&render_stmt(body));
             out.push_str(&open_node("test", "field"));
@@ -132,7 +133,7 @@ pub fn render_stmt(s: &Stmt) -> String {
             out.push_str(close_node());
             out.push_str(close_node());
         }
-        Stmt::For { init, test, update, body } => {
+        Stmt::For { init, test, update, body, .. } => {
             out.push_str(&open_node("ForStatement", "stmt"));
             if let Some(_) = init {
                 out.push_str(&open_node("init", "field"));
@@ -179,7 +180,7 @@ pub fn render_stmt(s: &Stmt) -> String {
             out.push_str(&render_stmt(body));
             out.push_str(close_node());
         }
-        Stmt::Try { body, catch, finally } => {
+        Stmt::Try { body, catch, finally, .. } => {
             out.push_str(&open_node("TryStatement", "stmt"));
             out.push_str(&open_node(&format!("try ({} stmts)", body.len()), "field"));
             for s in body { out.push_str(&render_stmt(s)); }
@@ -196,12 +197,12 @@ pub fn render_stmt(s: &Stmt) -> String {
             }
             out.push_str(close_node());
         }
-        Stmt::Labeled { label, body } => {
+        Stmt::Labeled { label, body, .. } => {
             out.push_str(&open_node(&format!("LabeledStatement: {label}"), "stmt"));
             out.push_str(&render_stmt(body));
             out.push_str(close_node());
         }
-        Stmt::Switch { discriminant, cases } => {
+        Stmt::Switch { discriminant, cases, .. } => {
             out.push_str(&open_node(&format!("SwitchStatement ({} cases)", cases.len()), "stmt"));
             out.push_str(&open_node("discriminant", "field"));
             out.push_str(&render_expr(discriminant));
@@ -218,8 +219,9 @@ pub fn render_stmt(s: &Stmt) -> String {
             }
             out.push_str(close_node());
         }
-        Stmt::Class { name, super_class, body } => {
+        Stmt::Class { name, decorators, super_class, body, .. } => {
             out.push_str(&open_node(&format!("ClassDeclaration: {name} ({} members)", body.len()), "stmt"));
+            render_decorators(decorators, &mut out);
             if let Some(sc) = super_class {
                 out.push_str(&open_node("extends", "field"));
                 out.push_str(&render_expr(sc));
@@ -230,19 +232,19 @@ pub fn render_stmt(s: &Stmt) -> String {
             }
             out.push_str(close_node());
         }
-        Stmt::GeneratorFunc { name, params, body } => {
+        Stmt::GeneratorFunc { name, params, body, .. } => {
             out.push_str(&open_node(&format!("GeneratorFunc: {name}* ({} params, {} stmts)", params.len(), body.len()), "stmt"));
             out.push_str(close_node());
         }
-        Stmt::AsyncFunc { name, params, body } => {
+        Stmt::AsyncFunc { name, params, body, .. } => {
             out.push_str(&open_node(&format!("AsyncFunc: async {name} ({} params, {} stmts)", params.len(), body.len()), "stmt"));
             out.push_str(close_node());
         }
-        Stmt::AsyncGeneratorFunc { name, params, body } => {
+        Stmt::AsyncGeneratorFunc { name, params, body, .. } => {
             out.push_str(&open_node(&format!("AsyncGeneratorFunc: async {name}* ({} params, {} stmts)", params.len(), body.len()), "stmt"));
             out.push_str(close_node());
         }
-        Stmt::Import { source, specifiers } => {
+        Stmt::Import { source, specifiers, .. } => {
             out.push_str(&open_node(&format!("Import \"{source}\" ({} specs)", specifiers.len()), "stmt"));
             for sp in specifiers {
                 let lbl = match sp {
@@ -254,11 +256,14 @@ pub fn render_stmt(s: &Stmt) -> String {
             }
             out.push_str(close_node());
         }
-        Stmt::Export(kind) => {
+        Stmt::Export(kind, _) => {
             let label = match kind {
                 ExportKind::Decl(_)    => "Export Decl",
                 ExportKind::Default(_) => "Export Default",
                 ExportKind::Named(ns)  => &format!("Export Named ({} items)", ns.len()),
+                ExportKind::From { source, names } => &format!("Export From \"{source}\" ({} items)", names.len()),
+                ExportKind::Star { source, alias: Some(a) } => &format!("Export * as {a} from \"{source}\""),
+                ExportKind::Star { source, alias: None } => &format!("Export * from \"{source}\""),
             };
             out.push_str(&leaf(label, "stmt"));
         }
@@ -274,6 +279,14 @@ fn pattern_label(p: &Pattern) -> String {
     }
 }
 
+/// Dekoratory tridy (`@dec`) jako jeden uzel s vyrazy.
+fn render_decorators(decorators: &[Expr], out: &mut String) {
+    if decorators.is_empty() { return; }
+    out.push_str(&open_node(&format!("decorators ({})", decorators.len()), "field"));
+    for d in decorators { out.push_str(&render_expr(d)); }
+    out.push_str(close_node());
+}
+
 fn render_params(params: &[Param]) -> String {
     let mut out = open_node("params", "field");
     for p in params {
@@ -290,17 +303,21 @@ fn render_params(params: &[Param]) -> String {
 
 pub fn render_expr(e: &Expr) -> String {
     let mut out = String::new();
+    let span = e.span();
+    if span.is_known() {
+        out.push_str(&format!("<span class=\"span-marker\">@{span}</span>"));
+    }
     match e {
-        Expr::Number(n) => out.push_str(&leaf(&format!("Number: {n}"), "literal")),
-        Expr::BigInt(s) => out.push_str(&leaf(&format!("BigInt: {s}n"), "literal")),
-        Expr::Str(s)    => out.push_str(&leaf(&format!("String: {s:?}"), "literal")),
-        Expr::Bool(b)   => out.push_str(&leaf(&format!("Bool: {b}"), "literal")),
-        Expr::Null      => out.push_str(&leaf("Null", "literal")),
-        Expr::Undefined => out.push_str(&leaf("Undefined", "literal")),
-        Expr::Regex(p, f) => out.push_str(&leaf(&format!("Regex: /{p}/{f}"), "literal")),
-        Expr::Ident(s)  => out.push_str(&leaf(&format!("Identifier: {s}"), "ident")),
+        Expr::Number(n, _) => out.push_str(&leaf(&format!("Number: {n}"), "literal")),
+        Expr::BigInt(s, _) => out.push_str(&leaf(&format!("BigInt: {s}n"), "literal")),
+        Expr::Str(s, _)    => out.push_str(&leaf(&format!("String: {s:?}"), "literal")),
+        Expr::Bool(b, _)   => out.push_str(&leaf(&format!("Bool: {b}"), "literal")),
+        Expr::Null(_)      => out.push_str(&leaf("Null", "literal")),
+        Expr::Undefined(_) => out.push_str(&leaf("Undefined", "literal")),
+        Expr::Regex(p, f, _) => out.push_str(&leaf(&format!("Regex: /{p}/{f}"), "literal")),
+        Expr::Ident(s, _)  => out.push_str(&leaf(&format!("Identifier: {s}"), "ident")),
 
-        Expr::Template { quasis, expressions } => {
+        Expr::Template { quasis, expressions, .. } => {
             out.push_str(&open_node(&format!("TemplateLiteral ({} quasis, {} exprs)", quasis.len(), expressions.len()), "expr"));
             for (i, q) in quasis.iter().enumerate() {
                 out.push_str(&leaf(&format!("quasi[{i}]: {q:?}"), "field"));
@@ -311,7 +328,19 @@ pub fn render_expr(e: &Expr) -> String {
             out.push_str(close_node());
         }
 
-        Expr::Array(items) => {
+        Expr::TaggedTemplate { tag, raw, expressions, .. } => {
+            out.push_str(&open_node(&format!("TaggedTemplate ({} quasis, {} exprs)", raw.len(), expressions.len()), "expr"));
+            out.push_str(&render_expr(tag));
+            for (i, q) in raw.iter().enumerate() {
+                out.push_str(&leaf(&format!("raw[{i}]: {q:?}"), "field"));
+                if let Some(e) = expressions.get(i) {
+                    out.push_str(&render_expr(e));
+                }
+            }
+            out.push_str(close_node());
+        }
+
+        Expr::Array(items, _) => {
             out.push_str(&open_node(&format!("ArrayLiteral ({} items)", items.len()), "expr"));
             for item in items {
                 match item {
@@ -321,7 +350,7 @@ pub fn render_expr(e: &Expr) -> String {
             }
             out.push_str(close_node());
         }
-        Expr::Object(props) => {
+        Expr::Object(props, _) => {
             out.push_str(&open_node(&format!("ObjectLiteral ({} props)", props.len()), "expr"));
             for _p in props {
                 out.push_str(&leaf("ObjectProp", "field"));
@@ -329,37 +358,42 @@ pub fn render_expr(e: &Expr) -> String {
             out.push_str(close_node());
         }
 
-        Expr::Unary { op, arg } => {
+        Expr::Unary { op, arg, .. } => {
             out.push_str(&open_node(&format!("Unary {:?}", op), "expr"));
             out.push_str(&render_expr(arg));
             out.push_str(close_node());
         }
-        Expr::Binary { op, left, right } => {
+        Expr::Binary { op, left, right, .. } => {
             out.push_str(&open_node(&format!("Binary {:?}", op), "expr"));
             out.push_str(&render_expr(left));
             out.push_str(&render_expr(right));
             out.push_str(close_node());
         }
-        Expr::Logical { op, left, right } => {
+        Expr::PrivateIn { name, object, .. } => {
+            out.push_str(&open_node(&format!("PrivateIn #{name}"), "expr"));
+            out.push_str(&render_expr(object));
+            out.push_str(close_node());
+        }
+        Expr::Logical { op, left, right, .. } => {
             out.push_str(&open_node(&format!("Logical {:?}", op), "expr"));
             out.push_str(&render_expr(left));
             out.push_str(&render_expr(right));
             out.push_str(close_node());
         }
-        Expr::Ternary { test, yes, no } => {
+        Expr::Ternary { test, yes, no, .. } => {
             out.push_str(&open_node("Ternary", "expr"));
             out.push_str(&render_expr(test));
             out.push_str(&render_expr(yes));
             out.push_str(&render_expr(no));
             out.push_str(close_node());
         }
-        Expr::Assign { op, target, value } => {
+        Expr::Assign { op, target, value, .. } => {
             out.push_str(&open_node(&format!("Assign {:?}", op), "expr"));
             out.push_str(&render_expr(target));
             out.push_str(&render_expr(value));
             out.push_str(close_node());
         }
-        Expr::Call { callee, args, optional } => {
+        Expr::Call { callee, args, optional, .. } => {
             let opt = if *optional { "?." } else { "" };
             out.push_str(&open_node(&format!("Call{opt} ({} args)", args.len()), "expr"));
             out.push_str(&open_node("callee", "field"));
@@ -372,18 +406,19 @@ pub fn render_expr(e: &Expr) -> String {
             }
             out.push_str(close_node());
         }
-        Expr::New { callee, args } => {
+        Expr::New { callee, args, .. } => {
             out.push_str(&open_node(&format!("New ({} args)", args.len()), "expr"));
             out.push_str(&render_expr(callee));
             for a in args { out.push_str(&render_expr(a)); }
             out.push_str(close_node());
         }
-        Expr::Member { object, prop, optional } => {
+        Expr::Member { object, prop, optional, .. } => {
             let opt = if *optional { "?." } else { "." };
             out.push_str(&open_node(&format!("Member {opt}"), "expr"));
             out.push_str(&render_expr(object));
             match prop {
                 MemberProp::Ident(s) => out.push_str(&leaf(&format!(".{s}"), "field")),
+                MemberProp::Private(s) => out.push_str(&leaf(&format!(".#{s}"), "field")),
                 MemberProp::Computed(e) => {
                     out.push_str(&open_node("[computed]", "field"));
                     out.push_str(&render_expr(e));
@@ -393,7 +428,7 @@ pub fn render_expr(e: &Expr) -> String {
             out.push_str(close_node());
         }
 
-        Expr::Function { name, params, body } => {
+        Expr::Function { name, params, body, .. } => {
             let lbl = format!("FunctionExpr: {} ({} params)",
                 name.as_deref().unwrap_or("(anon)"), params.len());
             out.push_str(&open_node(&lbl, "expr"));
@@ -402,7 +437,7 @@ pub fn render_expr(e: &Expr) -> String {
             out.push_str(close_node());
             out.push_str(close_node());
         }
-        Expr::Arrow { params, body } => {
+        Expr::Arrow { params, body, .. } => {
             let body_label = match body {
                 ArrowBody::Expr(_) => "expr-body".to_string(),
                 ArrowBody::Block(s) => format!("body ({} stmts)", s.len()),
@@ -416,45 +451,51 @@ pub fn render_expr(e: &Expr) -> String {
             out.push_str(close_node());
             out.push_str(close_node());
         }
-        Expr::ClassExpr { name, super_class, body } => {
+        Expr::ClassExpr { name, decorators, super_class, body, .. } => {
             let lbl = format!("ClassExpr: {} ({} members)",
                 name.as_deref().unwrap_or("(anon)"), body.len());
             out.push_str(&open_node(&lbl, "expr"));
+            render_decorators(decorators, &mut out);
             if let Some(sc) = super_class { out.push_str(&render_expr(sc)); }
             out.push_str(close_node());
         }
-        Expr::Sequence(exprs) => {
+        Expr::Sequence(exprs, _) => {
             out.push_str(&open_node(&format!("Sequence ({} exprs)", exprs.len()), "expr"));
             for e in exprs { out.push_str(&render_expr(e)); }
             out.push_str(close_node());
         }
-        Expr::Spread(e) => {
+        Expr::Spread(e, _) => {
             out.push_str(&open_node("Spread", "expr"));
             out.push_str(&render_expr(e));
             out.push_str(close_node());
         }
-        Expr::Yield { value, delegate } => {
+        Expr::Yield { value, delegate, .. } => {
             let lbl = if *delegate { "Yield*" } else { "Yield" };
             out.push_str(&open_node(lbl, "expr"));
             if let Some(v) = value { out.push_str(&render_expr(v)); }
             out.push_str(close_node());
         }
-        Expr::Await { value } => {
+        Expr::Await { value, .. } => {
             out.push_str(&open_node("Await", "expr"));
             out.push_str(&render_expr(value));
             out.push_str(close_node());
         }
-        Expr::AsyncFunc { name, params, body } => {
+        Expr::AsyncFunc { name, params, body, .. } => {
             let lbl = format!("AsyncFunctionExpr: {} ({} params, {} stmts)",
                 name.as_deref().unwrap_or("(anon)"), params.len(), body.len());
             out.push_str(&leaf(&lbl, "expr"));
         }
-        Expr::GeneratorFunc { name, params, body } => {
+        Expr::GeneratorFunc { name, params, body, .. } => {
             let lbl = format!("GeneratorFunctionExpr: {}* ({} params, {} stmts)",
                 name.as_deref().unwrap_or("(anon)"), params.len(), body.len());
             out.push_str(&leaf(&lbl, "expr"));
         }
-        Expr::DynamicImport(arg) => {
+        Expr::AsyncGeneratorFunc { name, params, body, .. } => {
+            let lbl = format!("AsyncGeneratorFunctionExpr: async {}* ({} params, {} stmts)",
+                name.as_deref().unwrap_or("(anon)"), params.len(), body.len());
+            out.push_str(&leaf(&lbl, "expr"));
+        }
+        Expr::DynamicImport(arg, _) => {
             out.push_str(&open_node("DynamicImport", "expr"));
             out.push_str(&render_expr(arg));
             out.push_str(close_node());
@@ -217,6 +217,7 @@ main { padding: 24px; max-width: 1400px; margin: 0 auto; }
 .ast-decl    { color: #cba6f7; }
 .ast-field   { color: #74c7ec; }
 .ast-root    { color: #f9e2af; font-weight: bold; }
+.line-marker, .span-marker { color: #6c7086; font-size: 11px; margin-right: 4px; }
 .ast-controls { margin-bottom: 12px; }
 .ast-controls button {
     background: #313244;
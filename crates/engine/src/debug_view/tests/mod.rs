@@ -69,3 +69,10 @@ fn ast_nested_expressions() {
     assert!(html.contains("Number: 2"));
     assert!(html.contains("Number: 3"));
 }
+
+#[test]
+fn ast_expressions_carry_span_markers() {
+    let html = generate_debug_html("1 + 2 * 3", "test");
+    assert!(html.contains("<span class=\"span-marker\">@1:1-1:10</span>"));
+    assert!(html.contains("<span class=\"span-marker\">@1:5-1:10</span>"));
+}
@@ -9,7 +9,8 @@ use super::html_escape;
 /// Vrati CSS classu podle typu tokenu (pro barvu).
 fn token_class(t: &TokenKind) -> &'static str {
     match t {
-        TokenKind::Identifier(_)              => "tk-ident",
+        TokenKind::Identifier(_)
+        | TokenKind::PrivateName(_)           => "tk-ident",
         TokenKind::NumericLiteral { .. }      => "tk-number",
         TokenKind::StringLiteral { .. }       => "tk-string",
         TokenKind::RegexLiteral { .. }        => "tk-regex",
@@ -32,6 +33,7 @@ fn token_class(t: &TokenKind) -> &'static str {
 fn token_type_label(t: &TokenKind) -> String {
     match t {
         TokenKind::Identifier(_)               => "Identifier".into(),
+        TokenKind::PrivateName(_)              => "PrivateName".into(),
         TokenKind::NumericLiteral { .. }       => "NumericLiteral".into(),
         TokenKind::StringLiteral { .. }        => "StringLiteral".into(),
         TokenKind::RegexLiteral { .. }         => "RegexLiteral".into(),
@@ -6,6 +6,7 @@ use std::collections::HashMap;
 use std::rc::Rc;
 use std::cell::RefCell;
 use crate::interpreter::{JsObject, JsValue};
+use crate::interpreter::gc::{self, GcStats};
 
 /// Memory snapshot - per object type pocet instances + total bytes.
 #[derive(Debug, Default, Clone)]
@@ -18,6 +19,8 @@ pub struct MemorySnapshot {
     pub total_bytes: usize,
     /// Top retainers - object type + count.
     pub by_type: HashMap<String, usize>,
+    /// Stav cycle collectoru (kolekce, uvolneno, live, pauzy) v case snapshotu.
+    pub gc: GcStats,
 }
 
 impl MemorySnapshot {
@@ -27,6 +30,7 @@ impl MemorySnapshot {
     pub fn capture(&mut self, root: &Rc<RefCell<JsObject>>) {
         let mut visited = std::collections::HashSet::new();
         self.walk_object(root, &mut visited);
+        self.gc = gc::stats();
     }
 
     fn walk_object(
@@ -63,9 +67,19 @@ impl MemorySnapshot {
 
     pub fn report(&self) -> String {
         format!(
-            "Memory: {} objects, {} strings, {} arrays, {} dom_nodes, ~{} KB total",
+            "Memory: {} objects, {} strings, {} arrays, {} dom_nodes, ~{} KB total\n{}",
             self.objects, self.strings, self.arrays, self.dom_nodes,
-            self.total_bytes / 1024,
+            self.total_bytes / 1024, Self::gc_report(&self.gc),
+        )
+    }
+
+    /// Radek GC statistik pro Memory tab.
+    pub fn gc_report(s: &GcStats) -> String {
+        format!(
+            "GC: {} collections, {} freed (last {}), {} live, {} listeners released, \
+             next at {}/{} writes, last pause {:.2}ms",
+            s.collections, s.total_freed, s.last_freed, s.live, s.callbacks_released,
+            s.allocated_since, s.threshold, s.last_pause_us as f64 / 1000.0,
         )
     }
 }
@@ -115,5 +129,19 @@ mod tests {
         let r = snap.report();
         assert!(r.contains("Memory:"));
         assert!(r.contains("strings"));
+        assert!(r.contains("GC:"));
+    }
+
+    #[test]
+    fn capture_reads_gc_stats() {
+        let a = Rc::new(RefCell::new(JsObject::new()));
+        a.borrow_mut().set("me".into(), JsValue::Object(Rc::clone(&a)));
+        drop(a);
+        gc::collect(&gc::GcRoots::default());
+        let root = Rc::new(RefCell::new(JsObject::new()));
+        let mut snap = MemorySnapshot::new();
+        snap.capture(&root);
+        assert_eq!(snap.gc.collections, 1);
+        assert!(snap.gc.total_freed >= 1);
     }
 }
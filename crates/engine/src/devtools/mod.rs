@@ -453,7 +453,7 @@ pub enum EditTarget {
 pub struct ElementsSearch {
     pub open: bool,
     pub query: model::text_buffer::SimpleStringBuffer,
-    pub matches: Vec<usize>,
+    pub matches: Vec<search::SearchMatch>,
     pub current: usize,
     pub mode: SearchMode,
 }
@@ -12,6 +12,15 @@ pub struct SourceFile {
     pub source_map_url: Option<String>,
     /// Parsed source map (po fetch + decode). None pri chybe nebo absentnim.
     pub source_map: Option<SourceMap>,
+    /// Pretty-printed podoba (`{ }` v Sources) - generuje se lazy pri prvnim zapnuti.
+    pub pretty: Option<PrettySource>,
+}
+
+/// Preformatovany JS (`codegen::pretty_print`) + mapa jeho radku zpet do `content`.
+#[derive(Debug, Clone)]
+pub struct PrettySource {
+    pub content: String,
+    pub map: crate::interpreter::source_map::SourceMap,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -34,6 +43,8 @@ pub struct SourcesState {
     pub locals: Vec<(String, String)>,
     /// True = zobraz original source pres source map mapping (z sourcesContent).
     pub show_original: bool,
+    /// True = JS soubory se zobrazuji pretty-printed (minifikovany kod citelne).
+    pub pretty_print: bool,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -53,10 +64,68 @@ impl SourcesState {
             language,
             source_map_url,
             source_map: None,
+            pretty: None,
         });
         id
     }
 
+    /// Prepne pretty print. Pri zapnuti vygeneruje pretty podobu vybraneho JS
+    /// souboru; kdyz se neda naparsovat, zustane vypnuto a vraci false.
+    pub fn toggle_pretty_print(&mut self) -> bool {
+        if self.pretty_print {
+            self.pretty_print = false;
+            return true;
+        }
+        if let Some(id) = self.selected_id
+            && !self.ensure_pretty(id)
+        {
+            return false;
+        }
+        self.pretty_print = true;
+        true
+    }
+
+    /// Vygeneruje pretty podobu souboru, pokud jeste neni. False pro ne-JS
+    /// soubor nebo syntax error.
+    pub fn ensure_pretty(&mut self, file_id: u32) -> bool {
+        let Some(file) = self.files.iter_mut().find(|f| f.id == file_id) else { return false };
+        if file.pretty.is_some() { return true }
+        if file.language != SourceLang::JavaScript { return false }
+        match crate::codegen::pretty_print(&file.content, &file.url) {
+            Ok((content, map)) => {
+                file.pretty = Some(PrettySource { content, map });
+                true
+            }
+            Err(_) => false,
+        }
+    }
+
+    /// Je soubor prave zobrazen pretty-printed? Original ze source mapy ma prednost.
+    pub fn shows_pretty(&self, file_id: u32) -> bool {
+        let Some(file) = self.files.iter().find(|f| f.id == file_id) else { return false };
+        let shows_original = self.show_original
+            && file.source_map.as_ref().is_some_and(|m| matches!(m.sources_content.first(), Some(Some(_))));
+        self.pretty_print && file.pretty.is_some() && !shows_original
+    }
+
+    /// Radek pretty vystupu (1-based) -> radek puvodniho souboru (1-based).
+    /// Bere prvni segment na radku; radky bez segmentu (`}`) nemapuji.
+    pub fn pretty_line_to_original(&self, file_id: u32, line: u32) -> Option<u32> {
+        let file = self.files.iter().find(|f| f.id == file_id)?;
+        let map = &file.pretty.as_ref()?.map;
+        let seg = map.mappings.get(line.checked_sub(1)? as usize)?.first()?;
+        Some(seg.source_line? + 1)
+    }
+
+    /// Radek puvodniho souboru (1-based) -> prvni radek pretty vystupu, ktery na nej ukazuje.
+    pub fn original_line_to_pretty(&self, file_id: u32, line: u32) -> Option<u32> {
+        let file = self.files.iter().find(|f| f.id == file_id)?;
+        let map = &file.pretty.as_ref()?.map;
+        let src_line = line.checked_sub(1)?;
+        map.mappings.iter().position(|segs| segs.iter().any(|s| s.source_line == Some(src_line)))
+            .map(|i| i as u32 + 1)
+    }
+
     /// Spusti fetch + parse source mapu pro file s `id`. Bere fetcher closure
     /// (URL -> Option<String>) - typicky `crate::browser::render::fetch_text_url`.
     pub fn load_source_map<F: Fn(&str) -> Option<String>>(&mut self, file_id: u32, base_url: &str, fetch: F) {
@@ -6,6 +6,7 @@
 //!   devtools.json    - theme + dock position + panel sizes
 //!   bookmarks.json   - bookmarks (shell mode)
 //!   history.json     - browsing history (shell mode)
+//!   repl_history.txt - historie vstupu `repl` subcommandu (radek = zaznam)
 
 use std::path::PathBuf;
 
@@ -127,6 +128,13 @@ pub fn devtools_config_path() -> Option<PathBuf> {
     Some(dir.join("devtools.json"))
 }
 
+/// Path k repl_history.txt v aktualnim profilu.
+pub fn repl_history_path() -> Option<PathBuf> {
+    let name = active_profile().to_string();
+    let dir = ensure_profile_dir(&name)?;
+    Some(dir.join("repl_history.txt"))
+}
+
 /// Migrate legacy config z ~/AppData/Roaming/rwe/devtools.json (pre-profile)
 /// do default profile dir. One-shot kdyz default profile config neexistuje.
 pub fn migrate_legacy_config() {
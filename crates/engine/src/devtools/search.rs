@@ -4,15 +4,31 @@
 //! tag nebo CSS selector pres selectors crate (try selector parse, fallback tag match).
 
 use std::rc::Rc;
+use crate::ast::SourcePos;
 use crate::browser::dom::{NodeData, NodeKind};
 use super::SearchMode;
 
-/// Vyhleda matching nodes. Vraci IDs (Rc::as_ptr cast).
+/// Nalezeny uzel a pozice jeho otviraciho tagu ve zdroji stranky.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SearchMatch {
+    /// ID uzlu (Rc::as_ptr cast).
+    pub node: usize,
+    /// Radek (od 1) a sloupec (od 0) `<tag`; radek 0 = uzel bez pozice ve zdroji.
+    pub pos: SourcePos,
+}
+
+impl SearchMatch {
+    fn of(node: &Rc<NodeData>) -> Self {
+        SearchMatch { node: Rc::as_ptr(node) as usize, pos: node.source_pos.get() }
+    }
+}
+
+/// Vyhleda matching nodes v poradi dokumentu.
 pub fn search(
     root: &Rc<NodeData>,
     query: &str,
     mode: SearchMode,
-) -> Vec<usize> {
+) -> Vec<SearchMatch> {
     let q = query.trim();
     if q.is_empty() { return Vec::new(); }
     let detected = match mode {
@@ -39,20 +55,20 @@ fn detect_mode(q: &str) -> SearchMode {
     SearchMode::Tag
 }
 
-fn tag_search(root: &Rc<NodeData>, tag: &str) -> Vec<usize> {
+fn tag_search(root: &Rc<NodeData>, tag: &str) -> Vec<SearchMatch> {
     let lower = tag.to_ascii_lowercase();
     let mut out = Vec::new();
     walk(root, &mut |n| {
         if let NodeKind::Element(t) = &n.kind {
             if t.eq_ignore_ascii_case(&lower) {
-                out.push(Rc::as_ptr(n) as usize);
+                out.push(SearchMatch::of(n));
             }
         }
     });
     out
 }
 
-fn css_search(root: &Rc<NodeData>, sel: &str) -> Vec<usize> {
+fn css_search(root: &Rc<NodeData>, sel: &str) -> Vec<SearchMatch> {
     // Simple selector implementation: support `tag`, `.class`, `#id`, `tag.class`,
     // `tag#id`, `[attr]`, `[attr=val]`, descendant `a b`, child `a > b`.
     // Plne selectors crate je heavy - pouzivame lite parser + matcher.
@@ -63,7 +79,7 @@ fn css_search(root: &Rc<NodeData>, sel: &str) -> Vec<usize> {
     let mut out = Vec::new();
     walk(root, &mut |n| {
         if matches_compound(n, &parsed) {
-            out.push(Rc::as_ptr(n) as usize);
+            out.push(SearchMatch::of(n));
         }
     });
     out
@@ -233,7 +249,7 @@ fn match_part_simple(
 // ─── XPath (subset) ──────────────────────────────────────────────────────
 // Podporuje: //tag, //tag[@attr], //tag[@attr="val"], /tag/tag, //tag[N].
 
-fn xpath_search(root: &Rc<NodeData>, q: &str) -> Vec<usize> {
+fn xpath_search(root: &Rc<NodeData>, q: &str) -> Vec<SearchMatch> {
     let steps = parse_xpath_steps(q);
     if steps.is_empty() { return Vec::new(); }
     let mut current_set: Vec<Rc<NodeData>> = vec![Rc::clone(root)];
@@ -259,7 +275,7 @@ fn xpath_search(root: &Rc<NodeData>, q: &str) -> Vec<usize> {
         }
         current_set = next_set;
     }
-    current_set.iter().map(|n| Rc::as_ptr(n) as usize).collect()
+    current_set.iter().map(SearchMatch::of).collect()
 }
 
 #[derive(Debug)]
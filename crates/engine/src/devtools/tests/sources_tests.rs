@@ -108,3 +108,30 @@ fn breakpoint_toggle() {
     assert!(!removed);
     assert!(!s.has_breakpoint(id, 5));
 }
+
+#[test]
+fn pretty_print_maps_lines_back_to_original() {
+    let mut s = SourcesState::default();
+    let id = s.add_file("app.min.js".into(), "function f(a){if(a){return 1}return 2}f(1);".into(), SourceLang::JavaScript);
+    s.selected_id = Some(id);
+    assert!(s.toggle_pretty_print());
+    assert!(s.shows_pretty(id));
+    let pretty = s.files[0].pretty.as_ref().unwrap();
+    assert_eq!(pretty.content.lines().nth(2), Some("    return 1;"));
+    // minifikovany zdroj je jeden radek - vsechno mapuje na radek 1
+    assert_eq!(s.pretty_line_to_original(id, 3), Some(1));
+    assert_eq!(s.original_line_to_pretty(id, 1), Some(1));
+    assert!(s.toggle_pretty_print());
+    assert!(!s.shows_pretty(id));
+}
+
+#[test]
+fn pretty_print_refuses_unparsable_source() {
+    let mut s = SourcesState::default();
+    let id = s.add_file("broken.js".into(), "function (".into(), SourceLang::JavaScript);
+    s.selected_id = Some(id);
+    assert!(!s.toggle_pretty_print());
+    assert!(!s.pretty_print);
+    let css = s.add_file("a.css".into(), "body{}".into(), SourceLang::Css);
+    assert!(!s.ensure_pretty(css));
+}
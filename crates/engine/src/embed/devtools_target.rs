@@ -95,10 +95,10 @@ pub struct DevtoolsTarget {
     events: RefCell<Vec<DevtoolsEvent>>,
     /// Sekvencni breakpoint ID generator (Debugger.setBreakpoint vrati id).
     next_breakpoint_id: RefCell<u64>,
-    /// Mapping breakpoint ID (string "bp-N") -> line_number. Pouziti
-    /// pri Debugger.removeBreakpoint - dle id najdi line + mazat z
-    /// interp.debugger.breakpoints.
-    breakpoint_lines: RefCell<std::collections::HashMap<String, u32>>,
+    /// Mapping breakpoint ID (string "bp-N") -> (line_number, column_number).
+    /// Pouziti pri Debugger.removeBreakpoint - dle id najdi pozici + mazat z
+    /// interp.debugger.breakpoints / column_breakpoints.
+    breakpoint_lines: RefCell<std::collections::HashMap<String, (u32, Option<u32>)>>,
     /// Shared inspector state - sdileno pres shell + page WV. Pres Overlay
     /// CDP method handlers updates hovered_node / picker_active. Page WV
     /// overlay_painter cte stav + emit box-model highlight rect cmds.
@@ -745,7 +745,7 @@ impl DevtoolsTarget {
         }
         for stmt in &program.body {
             let stmt = unwrap_with_line(stmt);
-            if let crate::ast::Stmt::Expr(e) = stmt {
+            if let crate::ast::Stmt::Expr(e, _) = stmt {
                 match interp.eval(e, &env) {
                     Ok(v) => last_val = v,
                     Err(err) => return Self::ok_response(req.id, &EvaluateResult {
@@ -783,7 +783,11 @@ impl DevtoolsTarget {
             None => return Self::error_response(req.id, error_codes::INTERNAL_ERROR,
                 "No interpreter".to_string()),
         };
-        interp.debugger.borrow_mut().breakpoints.insert(params.line_number);
+        // Se sloupcem jen prikaz zacinajici na teto pozici (minifikovany radek).
+        match params.column_number {
+            Some(col) => { interp.debugger.borrow_mut().column_breakpoints.insert((params.line_number, col)); }
+            None => { interp.debugger.borrow_mut().breakpoints.insert(params.line_number); }
+        }
         let id = {
             let mut n = self.next_breakpoint_id.borrow_mut();
             let id = *n;
@@ -791,8 +795,8 @@ impl DevtoolsTarget {
             id
         };
         let bp_id_str = format!("bp-{id}");
-        // Ulozim bp_id -> line mapping pro pozdejsi removeBreakpoint.
-        self.breakpoint_lines.borrow_mut().insert(bp_id_str.clone(), params.line_number);
+        // Ulozim bp_id -> pozice mapping pro pozdejsi removeBreakpoint.
+        self.breakpoint_lines.borrow_mut().insert(bp_id_str.clone(), (params.line_number, params.column_number));
         let result = SetBreakpointResult {
             breakpoint_id: bp_id_str,
             actual_location: Location {
@@ -811,10 +815,14 @@ impl DevtoolsTarget {
             Err(e) => return Self::error_response(req.id, error_codes::INVALID_PARAMS,
                 format!("Invalid params: {e}")),
         };
-        // Najdi line podle bp_id + mazat z interp.debugger.breakpoints.
-        let line = self.breakpoint_lines.borrow_mut().remove(&params.breakpoint_id);
-        if let (Some(line), Some(interp)) = (line, webview.interpreter()) {
-            interp.debugger.borrow_mut().breakpoints.remove(&line);
+        // Najdi pozici podle bp_id + mazat z interp.debugger.
+        let at = self.breakpoint_lines.borrow_mut().remove(&params.breakpoint_id);
+        if let (Some((line, column)), Some(interp)) = (at, webview.interpreter()) {
+            let mut debugger = interp.debugger.borrow_mut();
+            match column {
+                Some(col) => { debugger.column_breakpoints.remove(&(line, col)); }
+                None => { debugger.breakpoints.remove(&line); }
+            }
         }
         Self::ok_response_raw(req.id, serde_json::json!({}))
     }
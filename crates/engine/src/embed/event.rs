@@ -77,6 +77,10 @@ pub struct EventResponse {
     pub new_console_logs: bool,
     /// Stranka nahlasila title (`document.title = ...`) - shell aktualizuje tab.
     pub title_changed: Option<String>,
+    /// Skript handleru prekrocil rozpocet (`WebView::set_execution_limits`)
+    /// nebo byl preruseny pres `interrupt_handle` - shell nabidne dialog
+    /// "stranka neodpovida" (pockat / zavrit tab).
+    pub page_unresponsive: Option<crate::interpreter::budget::Termination>,
 }
 
 /// Pozadavek na navigaci (anchor click, form submit, JS).
@@ -93,6 +93,8 @@ pub struct LoadedPage {
     pub css: String,
     pub base_url: Option<String>,
     pub local_path: Option<PathBuf>,
+    /// Hlavicky odpovedi dokumentu (jen http(s)) - COOP/COEP pro izolaci.
+    pub headers: Vec<(String, String)>,
 }
 
 /// Nacti page z URL nebo filesystem path. http(s):// jde pres ureq, jine
@@ -102,7 +104,7 @@ pub fn load_page(target: &str) -> Option<LoadedPage> {
     let is_url = target.starts_with("http://") || target.starts_with("https://");
     if is_url {
         println!("[fetch] {target}");
-        let html = render::fetch_text_url(target)?;
+        let (html, headers) = render::fetch_document(target)?;
         let mut css = String::new();
         for href in extract_stylesheet_hrefs(&html) {
             let resolved = render::resolve_url(target, &href);
@@ -129,6 +131,7 @@ pub fn load_page(target: &str) -> Option<LoadedPage> {
             css,
             base_url: Some(target.to_string()),
             local_path: None,
+            headers,
         })
     } else {
         let html = std::fs::read_to_string(target).ok()?;
@@ -177,6 +180,7 @@ pub fn load_page(target: &str) -> Option<LoadedPage> {
             css,
             base_url: Some(base),
             local_path: Some(abs_path),
+            headers: Vec::new(),
         })
     }
 }
@@ -84,5 +84,6 @@ mod tests {
         assert!(r.cursor.is_none());
         assert!(!r.new_console_logs);
         assert!(r.title_changed.is_none());
+        assert!(r.page_unresponsive.is_none());
     }
 }
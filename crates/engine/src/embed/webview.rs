@@ -14,6 +14,7 @@ use std::sync::Arc;
 use crate::browser::dom::Document;
 use crate::browser::css_parser::Stylesheet;
 use crate::interpreter::Interpreter;
+use crate::interpreter::budget::{ExecutionLimits, InterruptHandle, Termination};
 use crate::lexer::base::Lexer;
 use crate::parser::Parser;
 use crate::tokens::TokenKind;
@@ -50,6 +51,12 @@ pub struct WebView {
     pub(crate) stylesheets: Vec<Stylesheet>,
     /// JS interpreter - drzi globaly, timery, workers, console_log, network_log.
     pub(crate) interpreter: Option<Interpreter>,
+    /// Rozpocet skriptu stranky - prenasi se do kazdeho noveho interpreteru.
+    pub(crate) execution_limits: ExecutionLimits,
+    /// Sdileny interrupt handle - plati i po reloadu stranky.
+    pub(crate) interrupt: InterruptHandle,
+    /// Hlavicky odpovedi dalsiho dokumentu (z `load_url`) - spotrebuje `load_dom`.
+    pub(crate) response_headers: Vec<(String, String)>,
     /// Base URL pro relative resolve (file:// nebo http://).
     pub(crate) base_url: Option<String>,
     /// Local path pri file:// navigaci - pro relative file lookup.
@@ -1151,6 +1158,9 @@ impl WebView {
             document: None,
             stylesheets: Vec::new(),
             interpreter: None,
+            execution_limits: ExecutionLimits::default(),
+            interrupt: InterruptHandle::new(),
+            response_headers: Vec::new(),
             base_url: None,
             local_path: None,
             title: String::new(),
@@ -1488,6 +1498,11 @@ impl WebView {
         // sdileni mela hit_test pres self.document.root jiny ptr nez JS lookup
         // pres interp.document.root (focused_node nikdy nesedi).
         let mut interp = Interpreter::new();
+        interp.set_execution_limits(self.execution_limits.clone());
+        interp.set_interrupt_handle(self.interrupt.clone());
+        // Cross-origin isolation (SharedArrayBuffer) jen s COOP + COEP hlavickami dokumentu.
+        let (coop, coep) = crate::browser::security::coep_coop::policies_from_headers(&std::mem::take(&mut self.response_headers));
+        interp.set_cross_origin_isolation(coop, coep);
         let interp_doc = crate::browser::dom::Document {
             root: std::rc::Rc::clone(&doc.root),
             url: doc.url.clone(),
@@ -1578,6 +1593,7 @@ impl WebView {
         let loaded = loader::load_page(url)?;
         // Update local_path PRED load_html aby ho NavigationResult vratil.
         self.local_path = loaded.local_path.clone();
+        self.response_headers = loaded.headers;
         let mut result = self.load_html(&loaded.html, &loaded.css, loaded.base_url);
         result.local_path = loaded.local_path;
         Some(result)
@@ -1602,13 +1618,30 @@ impl WebView {
             .unwrap_or(true);
 
         // Phase 1: collect scripts (fetch external) - krĂˇtkĂ˝ interp borrow.
+        // Klasicke skripty bezi v poradi dokumentu, `type="module"` az po nich
+        // (deferred), `type="importmap"` se jen pripoji k import mape.
         let mut scripts: Vec<(String, String)> = Vec::new();
+        // (popisek pro devtools, URL modulu, zdrojak)
+        let mut modules: Vec<(String, String, String)> = Vec::new();
         {
             let interp = self.interpreter.as_mut().unwrap();
+            if !base.is_empty() {
+                *interp.base_dir.borrow_mut() = base.clone();
+            }
             let doc_ref = interp.document.clone();
             let script_nodes = doc_ref.borrow().root.get_elements_by_tag("script");
             scripts.reserve(script_nodes.len());
             for (i, s) in script_nodes.iter().enumerate() {
+                let kind = s.attr("type").map(|t| t.trim().to_ascii_lowercase()).unwrap_or_default();
+                if kind == "importmap" {
+                    match crate::interpreter::import_maps::ImportMap::parse(&s.text_content(), &base) {
+                        Ok(map) => interp.import_map.borrow_mut().merge(map),
+                        Err(e) => interp.console_log.borrow_mut()
+                            .push(("error".into(), format!("[importmap] {e}"))),
+                    }
+                    continue;
+                }
+                let is_module = kind == "module";
                 if let Some(src_attr) = s.attr("src") {
                     if !fetch_external { continue; }
                     let src_attr = src_attr.trim().to_string();
@@ -1626,7 +1659,11 @@ impl WebView {
                     match crate::browser::render::fetch_text_url(&abs_url) {
                         Some(body) => {
                             interp.network_log.borrow_mut().push((abs_url.clone(), 200));
-                            scripts.push((abs_url, body));
+                            if is_module {
+                                modules.push((abs_url.clone(), abs_url, body));
+                            } else {
+                                scripts.push((abs_url, body));
+                            }
                         }
                         None => {
                             interp.network_log.borrow_mut().push((abs_url.clone(), 0));
@@ -1637,10 +1674,13 @@ impl WebView {
                         }
                     }
                 } else {
-                    let url = format!("<inline #{}>", i + 1);
                     let body = s.text_content();
-                    if !body.trim().is_empty() {
-                        scripts.push((url, body));
+                    if body.trim().is_empty() { continue; }
+                    if is_module {
+                        // Inline modul: import.meta.url i baze importu = dokument
+                        modules.push((format!("<inline module #{}>", i + 1), interp.document_url(), body));
+                    } else {
+                        scripts.push((format!("<inline #{}>", i + 1), body));
                     }
                 }
             }
@@ -1650,16 +1690,21 @@ impl WebView {
         for (url, body) in &scripts {
             self.collected_sources.push((url.clone(), body.clone(), "js"));
         }
+        for (label, _, body) in &modules {
+            self.collected_sources.push((label.clone(), body.clone(), "js"));
+        }
 
         // Phase 3: actual eval - znovu interp borrow.
         let interp = self.interpreter.as_mut().unwrap();
-        for (url, src) in scripts {
+        let classic = scripts.into_iter().map(|(url, src)| (url, None, src));
+        let deferred = modules.into_iter().map(|(label, url, src)| (label, Some(url), src));
+        for (label, module_url, src) in classic.chain(deferred) {
             if src.trim().is_empty() { continue; }
             // Debug: log script header pred eval (DIAG bug: parser/script error
             // bez kontextu = nevime ktery script chyboval).
             let preview = src.lines().next().unwrap_or("").chars().take(80).collect::<String>();
             eprintln!("[run_script] url={} ({} bytes) line1: {}",
-                url, src.len(), preview);
+                label, src.len(), preview);
             match Lexer::parse_str(&src, "<inline>") {
                 Ok(lex) => {
                     let tokens: Vec<_> = lex.tokens.into_iter()
@@ -1670,7 +1715,11 @@ impl WebView {
                     let mut parser = Parser::new(tokens);
                     match parser.parse() {
                         Ok(prog) => {
-                            if let Err(e) = interp.run(&prog) {
+                            let result = match &module_url {
+                                Some(url) => interp.run_module(&prog, url),
+                                None => interp.run(&prog),
+                            };
+                            if let Err(e) = result {
                                 let msg = format!("[script error] {e}");
                                 eprintln!("{msg}");
                                 interp.console_log.borrow_mut()
@@ -2094,7 +2143,34 @@ impl WebView {
         None
     }
 
+    /// Rozpocet skriptu (kroky, cas, heap, hloubka volani) pro kazdou ulohu
+    /// stranky - inline skripty, event handlery, timery.
+    pub fn set_execution_limits(&mut self, limits: ExecutionLimits) {
+        if let Some(interp) = self.interpreter.as_mut() {
+            interp.set_execution_limits(limits.clone());
+        }
+        self.execution_limits = limits;
+    }
+
+    /// `Send` handle pro watchdog thread shellu - `interrupt()` ukonci
+    /// prave bezici skript stranky.
+    pub fn interrupt_handle(&self) -> InterruptHandle {
+        self.interrupt.clone()
+    }
+
+    /// Duvod ukonceni posledni ukoncene ulohy mimo `handle_input` (load,
+    /// timery ve `tick`). Jednorazove - dalsi volani vraci `None`.
+    pub fn take_unresponsive(&mut self) -> Option<Termination> {
+        self.interpreter.as_ref().and_then(|interp| interp.take_termination())
+    }
+
     pub fn handle_input(&mut self, event: InputEvent) -> EventResponse {
+        let mut response = self.handle_input_inner(event);
+        response.page_unresponsive = self.take_unresponsive();
+        response
+    }
+
+    fn handle_input_inner(&mut self, event: InputEvent) -> EventResponse {
         let mut response = EventResponse::default();
         match event {
             InputEvent::Scroll { dx, dy, x, y, .. } => {
@@ -3631,6 +3707,7 @@ impl WebView {
             let _ = interp.drain_timers();
             // Plus drain fetches + raf + xhr po setup pred render skip.
             interp.drain_fetches();
+            interp.drain_host_jobs(false);
             let _ = interp.drain_websockets();
             let ts_ms = self.animation_origin.elapsed().as_secs_f64() * 1000.0;
             let _ = interp.drain_raf_callbacks(ts_ms);
@@ -5702,6 +5779,12 @@ impl WebView {
             let total = prof_t4.duration_since(prof_t0).as_secs_f32() * 1000.0;
             eprintln!("[PROF] total={:.1}ms cascade={:.1} layout={:.1} paint={:.1} gpu={:.1}",
                 total, self.prof_cascade_ms, self.prof_layout_ms, self.prof_paint_ms, self.prof_gpu_ms);
+            // Inline cache VM property pristupu - citace za snimek.
+            let ic = crate::interpreter::bytecode::ic_stats();
+            if ic.total() > 0 {
+                eprintln!("[PROF-IC] {ic}");
+                crate::interpreter::bytecode::reset_ic_stats();
+            }
         }
 
         // Frame done - mark v paceru pro telemetry.
@@ -6446,6 +6529,26 @@ mod tests {
         assert!(found, "script output missing in console_log: {:?}", *logs);
     }
 
+    #[test]
+    fn shared_array_buffer_needs_coop_coep_headers() {
+        let mut wv = fresh();
+        let html = "<html><body><script>\
+                    console.log('sab:' + typeof SharedArrayBuffer + ':' + crossOriginIsolated);\
+                    </script></body></html>";
+        let logged = |wv: &WebView| {
+            let logs = wv.interpreter().expect("interpreter").console_log.borrow();
+            logs.iter().find_map(|(_, m)| m.strip_prefix("sab:").map(String::from)).unwrap_or_default()
+        };
+        wv.load_html(html, "", Some("https://example.com/".to_string()));
+        assert_eq!(logged(&wv), "undefined:false");
+        wv.response_headers = vec![
+            ("Cross-Origin-Opener-Policy".to_string(), "same-origin".to_string()),
+            ("Cross-Origin-Embedder-Policy".to_string(), "require-corp".to_string()),
+        ];
+        wv.load_html(html, "", Some("https://example.com/".to_string()));
+        assert_eq!(logged(&wv), "function:true");
+    }
+
     #[test]
     fn load_html_picks_up_js_title_assignment() {
         let mut wv = fresh();
@@ -6567,6 +6670,18 @@ mod tests {
         assert_eq!(wv.viewport_h, 600.0);
     }
 
+    #[test]
+    fn runaway_script_reports_unresponsive_page() {
+        use crate::interpreter::budget::{ExecutionLimits, Termination};
+        let mut wv = fresh();
+        wv.set_execution_limits(ExecutionLimits { max_steps: Some(10_000), ..Default::default() });
+        wv.load_html("<html><body><script>while (true) {}</script>\
+                      <script>document.title = 'after';</script></body></html>", "", None);
+        assert_eq!(wv.take_unresponsive(), Some(Termination::StepLimit(10_000)));
+        let resp = wv.handle_input(InputEvent::Resize { width: 800, height: 600, scale_factor: 1.0 });
+        assert!(resp.page_unresponsive.is_none());
+    }
+
     #[test]
     fn render_returns_none_on_headless_engine() {
         // Headless = no GPU - render musi gracefully vratit None misto panik.
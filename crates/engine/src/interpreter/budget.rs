@@ -0,0 +1,274 @@
+//! Rozpocty behu skriptu - limit kroku, casu, velikosti heapu a hloubky
+//! volani + `InterruptHandle` pro preruseni z jineho threadu.
+//!
+//! Rozpocet plati pro jednu ulohu (host entry point: `run`, `run_module`,
+//! dispatch eventu, callback timeru). Tree-walker uctuje kazdy prikaz, VM
+//! kazdou instrukci; plna kontrola limitu bezi jednou za [`CHECK_INTERVAL`]
+//! kroku. Prekroceni ulohu ukonci nezachytitelnou chybou - `catch` ani
+//! `finally` se nevykonaji a kazdy dalsi krok ulohy selze, dokud host
+//! neopusti entry point.
+
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use super::gc;
+
+/// Pocet kroku mezi plnymi kontrolami (cas, interrupt flag, heap).
+pub const CHECK_INTERVAL: u64 = 1024;
+
+/// Prefix zpravy chyby ukonceni (`JsError::Runtime`, VM `Err(String)`).
+pub const TERMINATION_PREFIX: &str = "Terminated: ";
+
+/// Limity jedne ulohy. `None` = bez limitu (default).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ExecutionLimits {
+    /// Max pocet kroku (prikazy tree-walkeru + instrukce VM).
+    pub max_steps: Option<u64>,
+    /// Max wall-clock doba ulohy.
+    pub max_time: Option<Duration>,
+    /// Max pocet sledovanych kontejneru na heapu (objekty, pole, funkce -
+    /// registr write barrieru `gc`).
+    pub max_heap_objects: Option<usize>,
+    /// Max hloubka volani JS funkci.
+    pub max_call_depth: Option<usize>,
+}
+
+/// Duvod ukonceni ulohy.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Termination {
+    /// `InterruptHandle::interrupt` z jineho threadu.
+    Interrupted,
+    StepLimit(u64),
+    TimeLimit(Duration),
+    HeapLimit(usize),
+    CallDepth(usize),
+}
+
+impl std::fmt::Display for Termination {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Termination::Interrupted => write!(f, "script execution interrupted"),
+            Termination::StepLimit(n) => write!(f, "script exceeded step limit ({n})"),
+            Termination::TimeLimit(d) => write!(f, "script exceeded time limit ({} ms)", d.as_millis()),
+            Termination::HeapLimit(n) => write!(f, "script exceeded heap limit ({n} objects)"),
+            Termination::CallDepth(n) => write!(f, "script exceeded call depth limit ({n})"),
+        }
+    }
+}
+
+impl Termination {
+    /// Text chyby, kterou uloha konci.
+    pub fn message(&self) -> String {
+        format!("{TERMINATION_PREFIX}{self}")
+    }
+}
+
+/// Sdileny flag preruseni - `Send + Sync`, klonuje se do watchdog threadu.
+/// Preruseni ukonci prave bezici ulohu (nebo pristi, kdyz zadna nebezi);
+/// flag se shodi, az uloha skonci.
+#[derive(Debug, Clone, Default)]
+pub struct InterruptHandle {
+    flag: Arc<AtomicBool>,
+}
+
+impl InterruptHandle {
+    pub fn new() -> Self { Self::default() }
+
+    /// Pozada o ukonceni bezici ulohy.
+    pub fn interrupt(&self) {
+        self.flag.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_interrupted(&self) -> bool {
+        self.flag.load(Ordering::Relaxed)
+    }
+
+    fn clear(&self) {
+        self.flag.store(false, Ordering::Relaxed);
+    }
+}
+
+/// Stav aktivni ulohy tohoto threadu.
+#[derive(Default)]
+struct TaskState {
+    /// Vnoreni entry pointu (dispatchEvent z handleru, drain uvnitr run).
+    nesting: usize,
+    limits: ExecutionLimits,
+    interrupt: InterruptHandle,
+    started: Option<Instant>,
+    steps: u64,
+    depth: usize,
+    terminated: Option<Termination>,
+}
+
+thread_local! {
+    /// Kroky do pristi plne kontroly. Mimo ulohu `u64::MAX` - rychla cesta
+    /// `charge` je jen dekrement.
+    static FUEL: Cell<u64> = const { Cell::new(u64::MAX) };
+    static TASK: RefCell<TaskState> = RefCell::new(TaskState::default());
+}
+
+/// Ukonceni posledni ukoncene ulohy jednoho interpretu - vyzvedne host
+/// (`Interpreter::take_termination`).
+pub type TerminationSlot = Rc<Cell<Option<Termination>>>;
+
+/// Guard ulohy - vnejsi drop vyhodnoti ukonceni, zapise ho do slotu
+/// interpretu, ktery ulohu zacal, a uvolni rozpocet.
+pub struct TaskGuard(TerminationSlot);
+
+impl Drop for TaskGuard {
+    fn drop(&mut self) {
+        let finished = TASK.with(|t| {
+            let mut t = t.borrow_mut();
+            t.nesting -= 1;
+            if t.nesting > 0 { return None; }
+            let terminated = t.terminated.take();
+            if terminated.is_some() { t.interrupt.clear(); }
+            Some(terminated)
+        });
+        if let Some(terminated) = finished {
+            FUEL.with(|f| f.set(u64::MAX));
+            if terminated.is_some() {
+                self.0.set(terminated);
+            }
+        }
+    }
+}
+
+/// Vstup do host entry pointu. Vnorene vstupy sdili rozpocet vnejsi ulohy.
+pub fn enter_task(limits: &ExecutionLimits, interrupt: &InterruptHandle, last: &TerminationSlot) -> TaskGuard {
+    TASK.with(|t| {
+        let mut t = t.borrow_mut();
+        t.nesting += 1;
+        if t.nesting == 1 {
+            t.limits = limits.clone();
+            t.interrupt = interrupt.clone();
+            t.started = Some(Instant::now());
+            t.steps = 0;
+            t.depth = 0;
+            t.terminated = None;
+            FUEL.with(|f| f.set(CHECK_INTERVAL));
+        }
+    });
+    TaskGuard(Rc::clone(last))
+}
+
+/// Zauctuje jeden krok. Chyba = uloha ukoncena.
+#[inline]
+pub fn charge() -> Result<(), String> {
+    FUEL.with(|f| {
+        let n = f.get();
+        if n > 1 {
+            f.set(n - 1);
+            Ok(())
+        } else {
+            check()
+        }
+    })
+}
+
+/// Plna kontrola limitu; doplni palivo do dalsi kontroly.
+#[cold]
+fn check() -> Result<(), String> {
+    let fuel = TASK.with(|t| {
+        let mut t = t.borrow_mut();
+        if t.nesting == 0 { return Ok(u64::MAX); }
+        if let Some(term) = &t.terminated { return Err(term.message()); }
+        t.steps += CHECK_INTERVAL;
+        let term = if t.interrupt.is_interrupted() {
+            Some(Termination::Interrupted)
+        } else if let Some(max) = t.limits.max_steps.filter(|&m| t.steps >= m) {
+            Some(Termination::StepLimit(max))
+        } else if let Some(max) = t.limits.max_time.filter(|&m| t.started.is_some_and(|s| s.elapsed() >= m)) {
+            Some(Termination::TimeLimit(max))
+        } else {
+            t.limits.max_heap_objects.filter(|&m| gc::tracked_len() > m).map(Termination::HeapLimit)
+        };
+        if let Some(term) = term {
+            let msg = term.message();
+            t.terminated = Some(term);
+            return Err(msg);
+        }
+        let left = t.limits.max_steps.map_or(CHECK_INTERVAL, |m| (m - t.steps).clamp(1, CHECK_INTERVAL));
+        Ok(left)
+    });
+    match fuel {
+        Ok(n) => { FUEL.with(|f| f.set(n)); Ok(()) }
+        Err(msg) => { FUEL.with(|f| f.set(1)); Err(msg) }
+    }
+}
+
+/// Guard jednoho ramce volani (hloubka rekurze).
+pub struct CallGuard(());
+
+impl Drop for CallGuard {
+    fn drop(&mut self) {
+        TASK.with(|t| {
+            let mut t = t.borrow_mut();
+            t.depth = t.depth.saturating_sub(1);
+        });
+    }
+}
+
+/// Vstup do tela JS funkce - zauctuje krok a zkontroluje hloubku volani.
+pub fn enter_call() -> Result<CallGuard, String> {
+    charge()?;
+    TASK.with(|t| {
+        let mut t = t.borrow_mut();
+        t.depth += 1;
+        match t.limits.max_call_depth {
+            Some(max) if t.nesting > 0 && t.depth > max => {
+                t.depth -= 1;
+                let term = Termination::CallDepth(max);
+                let msg = term.message();
+                t.terminated = Some(term);
+                FUEL.with(|f| f.set(1));
+                Err(msg)
+            }
+            _ => Ok(CallGuard(())),
+        }
+    })
+}
+
+/// Je prave bezici uloha ukoncovana? `catch` / `finally` ji pak nezachyti.
+pub fn is_terminating() -> bool {
+    TASK.with(|t| t.borrow().terminated.is_some())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn step_limit_is_sticky_until_task_ends() {
+        let limits = ExecutionLimits { max_steps: Some(2 * CHECK_INTERVAL), ..Default::default() };
+        let last = TerminationSlot::default();
+        {
+            let _task = enter_task(&limits, &InterruptHandle::new(), &last);
+            let steps = (0..10 * CHECK_INTERVAL).take_while(|_| charge().is_ok()).count() as u64;
+            assert_eq!(steps, 2 * CHECK_INTERVAL - 1);
+            assert!(is_terminating());
+            assert!(charge().unwrap_err().starts_with(TERMINATION_PREFIX));
+        }
+        assert_eq!(last.take(), Some(Termination::StepLimit(2 * CHECK_INTERVAL)));
+        assert!(!is_terminating());
+        assert!(charge().is_ok());
+    }
+
+    #[test]
+    fn interrupt_from_other_thread() {
+        let handle = InterruptHandle::new();
+        let remote = handle.clone();
+        std::thread::spawn(move || remote.interrupt()).join().unwrap();
+        let last = TerminationSlot::default();
+        {
+            let _task = enter_task(&ExecutionLimits::default(), &handle, &last);
+            while charge().is_ok() {}
+        }
+        assert_eq!(last.take(), Some(Termination::Interrupted));
+        assert!(!handle.is_interrupted());
+    }
+}
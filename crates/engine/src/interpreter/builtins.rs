@@ -24,9 +24,13 @@ use std::str::FromStr;
 use bigdecimal::BigDecimal;
 use num_bigint::BigInt;
 use super::{JsValue, JsObject, Environment};
+use super::property_map::{PropertyDescriptor, PropertyKey, descriptor_to_js};
 use super::helpers::*;
 
-use super::builtins_helpers::{run_worker_thread, make_message_port, build_search_params, make_object_store};
+use super::builtins_typed_arrays::{array_buffer_value, bytes_of, typed_array_from_values, typed_array_value, typed_view_of};
+use super::typed_arrays::TypedArrayKind;
+use super::worker_message::WorkerMessage;
+use super::builtins_helpers::{blob_part_bytes, run_worker_thread, make_message_port, build_search_params, make_object_store};
 
 pub fn setup_builtins(
     env: &Rc<RefCell<Environment>>,
@@ -52,6 +56,7 @@ pub fn setup_builtins(
     next_callback_id: &Rc<RefCell<usize>>,
     resize_observers: &Rc<RefCell<Vec<super::ResizeObserverState>>>,
     intersection_observers: &Rc<RefCell<Vec<super::IntersectionObserverState>>>,
+    agent: &Rc<super::AgentState>,
 ) {
     let mut e = env.borrow_mut();
 
@@ -345,7 +350,7 @@ pub fn setup_builtins(
             Some(JsValue::Object(o)) => {
                 let obj = o.borrow();
                 let vals: Vec<JsValue> = obj.own_keys()
-                    .into_iter().map(|k| obj.props.get(&k).cloned().unwrap_or(JsValue::Undefined)).collect();
+                    .into_iter().map(|k| obj.get(&k)).collect();
                 Ok(JsValue::Array(Rc::new(RefCell::new(vals))))
             }
             _ => Ok(JsValue::Array(Rc::new(RefCell::new(vec![]))))
@@ -356,7 +361,7 @@ pub fn setup_builtins(
             Some(JsValue::Object(o)) => {
                 let obj = o.borrow();
                 let entries: Vec<JsValue> = obj.own_keys().into_iter().map(|k| {
-                    let v = obj.props.get(&k).cloned().unwrap_or(JsValue::Undefined);
+                    let v = obj.get(&k);
                     JsValue::Array(Rc::new(RefCell::new(vec![JsValue::Str(k), v])))
                 }).collect();
                 Ok(JsValue::Array(Rc::new(RefCell::new(entries))))
@@ -372,7 +377,7 @@ pub fn setup_builtins(
                 if let JsValue::Object(src_rc) = src {
                     for k in src_rc.borrow().own_keys() {
                         let v = src_rc.borrow().props.get(&k).cloned().unwrap_or(JsValue::Undefined);
-                        target_rc.borrow_mut().props.insert(k, v);
+                        target_rc.borrow_mut().set(k.into(), v);
                     }
                 }
             }
@@ -382,32 +387,36 @@ pub fn setup_builtins(
     obj_ctor.set("freeze".into(), native("Object.freeze", |a| {
         let obj = a.into_iter().next().unwrap_or(JsValue::Undefined);
         if let JsValue::Object(o) = &obj {
-            o.borrow_mut().frozen = true;
+            o.borrow_mut().freeze();
         }
         Ok(obj)
     }));
     obj_ctor.set("isFrozen".into(), native("Object.isFrozen", |a| {
         match a.into_iter().next() {
-            Some(JsValue::Object(o)) => Ok(JsValue::Bool(o.borrow().frozen)),
+            Some(JsValue::Object(o)) => Ok(JsValue::Bool(o.borrow().is_frozen())),
             _                        => Ok(JsValue::Bool(true)), // primitives are "frozen" per spec
         }
     }));
+    obj_ctor.set("seal".into(), native("Object.seal", |a| {
+        let obj = a.into_iter().next().unwrap_or(JsValue::Undefined);
+        if let JsValue::Object(o) = &obj { o.borrow_mut().seal(); }
+        Ok(obj)
+    }));
     obj_ctor.set("isExtensible".into(), native("Object.isExtensible", |a| {
         match a.into_iter().next() {
-            Some(JsValue::Object(o)) => Ok(JsValue::Bool(!o.borrow().frozen)),
+            Some(JsValue::Object(o)) => Ok(JsValue::Bool(o.borrow().extensible)),
             _ => Ok(JsValue::Bool(false)), // primitives are non-extensible per spec
         }
     }));
     obj_ctor.set("isSealed".into(), native("Object.isSealed", |a| {
-        // Plna implementace by testovala "non-configurable" props; tady pouzivame frozen jako proxy.
         match a.into_iter().next() {
-            Some(JsValue::Object(o)) => Ok(JsValue::Bool(o.borrow().frozen)),
+            Some(JsValue::Object(o)) => Ok(JsValue::Bool(o.borrow().is_sealed())),
             _ => Ok(JsValue::Bool(true)),
         }
     }));
     obj_ctor.set("preventExtensions".into(), native("Object.preventExtensions", |a| {
         let obj = a.into_iter().next().unwrap_or(JsValue::Undefined);
-        if let JsValue::Object(o) = &obj { o.borrow_mut().frozen = true; }
+        if let JsValue::Object(o) = &obj { o.borrow_mut().prevent_extensions(); }
         Ok(obj)
     }));
     obj_ctor.set("create".into(), native("Object.create", |a| {
@@ -444,7 +453,7 @@ pub fn setup_builtins(
     obj_ctor.set("hasOwn".into(), native("Object.hasOwn", |a| {
         let mut iter = a.into_iter();
         let obj = iter.next().unwrap_or(JsValue::Undefined);
-        let key = iter.next().map(|v| v.to_string()).unwrap_or_default();
+        let key = iter.next().map(|v| super::symbols::to_property_key(&v)).unwrap_or_default();
         match obj {
             JsValue::Object(o) => Ok(JsValue::Bool(o.borrow().has_own(&key))),
             _ => Ok(JsValue::Bool(false)),
@@ -462,26 +471,73 @@ pub fn setup_builtins(
         };
         Ok(JsValue::Bool(eq))
     }));
+    obj_ctor.set("getOwnPropertySymbols".into(), native("Object.getOwnPropertySymbols", |a| {
+        let syms = match a.into_iter().next() {
+            Some(JsValue::Object(o)) => o.borrow().own_symbols(),
+            _ => Vec::new(),
+        };
+        Ok(JsValue::Array(Rc::new(RefCell::new(syms))))
+    }));
     obj_ctor.set("defineProperty".into(), native("Object.defineProperty", |a| {
         let mut iter = a.into_iter();
         let obj  = iter.next().unwrap_or(JsValue::Undefined);
-        let key  = iter.next().map(|v| v.to_string()).unwrap_or_default();
-        let desc = iter.next().unwrap_or(JsValue::Undefined);
-        if let (JsValue::Object(obj_rc), JsValue::Object(desc_rc)) = (&obj, &desc) {
-            let get_fn = desc_rc.borrow().props.get("get").cloned();
-            let set_fn = desc_rc.borrow().props.get("set").cloned();
-            if let Some(getter) = get_fn {
-                obj_rc.borrow_mut().props.insert(format!("__get_{key}__"), getter);
+        let key  = iter.next().map(|v| super::symbols::to_property_key(&v)).unwrap_or_default();
+        let desc = PropertyDescriptor::from_js(&iter.next().unwrap_or(JsValue::Undefined))?;
+        match &obj {
+            JsValue::Object(obj_rc) => obj_rc.borrow_mut().define_own_property(key, desc)?,
+            _ => return Err("TypeError: Object.defineProperty called on non-object".into()),
+        }
+        Ok(obj)
+    }));
+    obj_ctor.set("defineProperties".into(), native("Object.defineProperties", |a| {
+        let mut iter = a.into_iter();
+        let obj = iter.next().unwrap_or(JsValue::Undefined);
+        let JsValue::Object(obj_rc) = &obj else {
+            return Err("TypeError: Object.defineProperties called on non-object".into());
+        };
+        if let Some(JsValue::Object(props)) = iter.next() {
+            let props = props.borrow();
+            let keys = props.own_keys().into_iter().map(PropertyKey::Str)
+                .chain(props.props.symbol_keys().map(|s| PropertyKey::Sym(Rc::clone(s))))
+                .collect::<Vec<_>>();
+            for key in keys {
+                let desc = PropertyDescriptor::from_js(&props.get(&key))?;
+                obj_rc.borrow_mut().define_own_property(key, desc)?;
             }
-            if let Some(setter) = set_fn {
-                obj_rc.borrow_mut().props.insert(format!("__set_{key}__"), setter);
+        }
+        Ok(obj)
+    }));
+    obj_ctor.set("getOwnPropertyDescriptor".into(), native("Object.getOwnPropertyDescriptor", |a| {
+        let mut iter = a.into_iter();
+        let obj = iter.next().unwrap_or(JsValue::Undefined);
+        let key = iter.next().map(|v| super::symbols::to_property_key(&v)).unwrap_or_default();
+        match &obj {
+            JsValue::Object(o) if !key.is_internal() => {
+                Ok(o.borrow().props.slot(&key).map(descriptor_to_js).unwrap_or(JsValue::Undefined))
             }
-            let val = desc_rc.borrow().get("value");
-            if !matches!(val, JsValue::Undefined) {
-                obj_rc.borrow_mut().props.insert(key, val);
+            _ => Ok(JsValue::Undefined),
+        }
+    }));
+    obj_ctor.set("getOwnPropertyDescriptors".into(), native("Object.getOwnPropertyDescriptors", |a| {
+        let mut out = JsObject::new();
+        if let Some(JsValue::Object(o)) = a.into_iter().next() {
+            let o = o.borrow();
+            for (k, slot) in o.props.entries() {
+                if !k.is_internal() {
+                    out.set(k.clone(), descriptor_to_js(slot));
+                }
             }
         }
-        Ok(obj)
+        Ok(JsValue::Object(Rc::new(RefCell::new(out))))
+    }));
+    obj_ctor.set("getOwnPropertyNames".into(), native("Object.getOwnPropertyNames", |a| {
+        let names = match a.into_iter().next() {
+            Some(JsValue::Object(o)) => o.borrow().ordered_keys(false),
+            Some(JsValue::Array(arr)) => (0..arr.borrow().len()).map(|i| i.to_string())
+                .chain(std::iter::once("length".to_string())).collect(),
+            _ => Vec::new(),
+        };
+        Ok(JsValue::Array(Rc::new(RefCell::new(names.into_iter().map(JsValue::Str).collect()))))
     }));
     obj_ctor.set("fromEntries".into(), native("Object.fromEntries", |a| {
         let mut obj = JsObject::new();
@@ -491,7 +547,7 @@ pub fn setup_builtins(
                     let pair = pair.borrow();
                     let key = pair.get(0).map(|v| v.to_string()).unwrap_or_default();
                     let val = pair.get(1).cloned().unwrap_or(JsValue::Undefined);
-                    obj.set(key, val);
+                    obj.set(key.into(), val);
                 }
             }
         }
@@ -499,91 +555,18 @@ pub fn setup_builtins(
     }));
     e.define("Object", JsValue::Object(Rc::new(RefCell::new(obj_ctor))));
 
-    // Symbol - well-known symbols jako string klice + Symbol() konstruktor
-    // Symbol() vraci unique identifier ulozeny jako string "Symbol(desc)#N"
-    let mut sym_obj = JsObject::new();
-    sym_obj.set("iterator".into(), JsValue::Str("Symbol.iterator".into()));
-    sym_obj.set("toPrimitive".into(), JsValue::Str("Symbol.toPrimitive".into()));
-    sym_obj.set("hasInstance".into(), JsValue::Str("Symbol.hasInstance".into()));
-    sym_obj.set("asyncIterator".into(), JsValue::Str("Symbol.asyncIterator".into()));
-    // ES2015+ well-known symbols
-    sym_obj.set("isConcatSpreadable".into(), JsValue::Str("Symbol.isConcatSpreadable".into()));
-    sym_obj.set("match".into(), JsValue::Str("Symbol.match".into()));
-    sym_obj.set("matchAll".into(), JsValue::Str("Symbol.matchAll".into()));
-    sym_obj.set("replace".into(), JsValue::Str("Symbol.replace".into()));
-    sym_obj.set("search".into(), JsValue::Str("Symbol.search".into()));
-    sym_obj.set("species".into(), JsValue::Str("Symbol.species".into()));
-    sym_obj.set("split".into(), JsValue::Str("Symbol.split".into()));
-    sym_obj.set("toStringTag".into(), JsValue::Str("Symbol.toStringTag".into()));
-    sym_obj.set("unscopables".into(), JsValue::Str("Symbol.unscopables".into()));
-    // ES2022 - Symbol.dispose / Symbol.asyncDispose (Explicit Resource Management)
-    sym_obj.set("dispose".into(), JsValue::Str("Symbol.dispose".into()));
-    sym_obj.set("asyncDispose".into(), JsValue::Str("Symbol.asyncDispose".into()));
-    // ES2024 - Symbol.metadata (decorators)
-    sym_obj.set("metadata".into(), JsValue::Str("Symbol.metadata".into()));
-    // Symbol.for(key) - registry-based symbols (sdilene podle stringu)
-    sym_obj.set("for".into(), native("Symbol.for", |a| {
-        let key = a.into_iter().next().map(|v| v.to_string()).unwrap_or_default();
-        Ok(JsValue::Str(format!("Symbol(@registry:{key})")))
-    }));
-    sym_obj.set("keyFor".into(), native("Symbol.keyFor", |a| {
-        let s = a.into_iter().next().map(|v| v.to_string()).unwrap_or_default();
-        if let Some(rest) = s.strip_prefix("Symbol(@registry:") {
-            if let Some(key) = rest.strip_suffix(")") {
-                return Ok(JsValue::Str(key.to_string()));
-            }
-        }
-        Ok(JsValue::Undefined)
-    }));
-    e.define("Symbol", JsValue::Object(Rc::new(RefCell::new(sym_obj))));
+    // Symbol() - unikatni primitivni hodnota (symbols.rs). Well-known symboly,
+    // Symbol.for a Symbol.keyFor resi get_prop nad Native("Symbol").
+    e.define("Symbol", native("Symbol", super::symbols::symbol_ctor));
 
     // Reflect (ES2015) - extracted to builtins_reflect.rs.
     super::builtins_reflect::setup_reflect(&mut *e);
 
     // Proxy konstruktor - logika je v call_new, registrujeme stub
-    e.define("Proxy", native("Proxy", |_| Ok(JsValue::Undefined)));
-
-    // ─── Intl (ECMA-402) - lokalizace ────────────────────────────────────────
-    // Vlastni lite implementace pro nejcastejsi locale (cs-CZ, en-US, de-DE).
-    let mut intl = JsObject::new();
-
-    // Intl.NumberFormat(locale).format(num) -> "1 234 567,89" (cs-CZ)
-    intl.set("NumberFormat".into(), native("Intl.NumberFormat", |a| {
-        let locale = a.first().map(|v| v.to_string()).unwrap_or_else(|| "en-US".into());
-        let mut obj = JsObject::new();
-        obj.set("__intl_locale__".into(), JsValue::Str(locale.clone()));
-        obj.set("__intl_kind__".into(), JsValue::Str("number".into()));
-        Ok(JsValue::Object(Rc::new(RefCell::new(obj))))
-    }));
-
-    // Intl.DateTimeFormat(locale).format(date)
-    intl.set("DateTimeFormat".into(), native("Intl.DateTimeFormat", |a| {
-        let locale = a.first().map(|v| v.to_string()).unwrap_or_else(|| "en-US".into());
-        let mut obj = JsObject::new();
-        obj.set("__intl_locale__".into(), JsValue::Str(locale.clone()));
-        obj.set("__intl_kind__".into(), JsValue::Str("datetime".into()));
-        Ok(JsValue::Object(Rc::new(RefCell::new(obj))))
-    }));
-
-    // Intl.Collator(locale).compare(a, b) -> -1/0/1
-    intl.set("Collator".into(), native("Intl.Collator", |a| {
-        let locale = a.first().map(|v| v.to_string()).unwrap_or_else(|| "en-US".into());
-        let mut obj = JsObject::new();
-        obj.set("__intl_locale__".into(), JsValue::Str(locale.clone()));
-        obj.set("__intl_kind__".into(), JsValue::Str("collator".into()));
-        Ok(JsValue::Object(Rc::new(RefCell::new(obj))))
-    }));
+    e.define("Proxy", native("Proxy", super::proxy_handler::proxy_ctor));
 
-    // Intl.PluralRules(locale).select(n) -> "one"/"other"/...
-    intl.set("PluralRules".into(), native("Intl.PluralRules", |a| {
-        let locale = a.first().map(|v| v.to_string()).unwrap_or_else(|| "en-US".into());
-        let mut obj = JsObject::new();
-        obj.set("__intl_locale__".into(), JsValue::Str(locale.clone()));
-        obj.set("__intl_kind__".into(), JsValue::Str("plural".into()));
-        Ok(JsValue::Object(Rc::new(RefCell::new(obj))))
-    }));
-
-    e.define("Intl", JsValue::Object(Rc::new(RefCell::new(intl))));
+    // Intl (ECMA-402) nad ICU4X - viz builtins_intl.rs.
+    super::builtins_intl::setup_intl(&mut e);
 
     // ─── atob / btoa - Base64 encode/decode ──────────────────────────────────
     e.define("btoa", native("btoa", |a| {
@@ -688,7 +671,7 @@ pub fn setup_builtins(
         // Nacti z disku pri init (jen persistent)
         if persistent {
             for (k, v) in load_storage_from_disk(name) {
-                data.set(k, JsValue::Str(v));
+                data.set(k.into(), JsValue::Str(v));
             }
         }
         let len = data.own_keys().len() as f64;
@@ -714,6 +697,7 @@ pub fn setup_builtins(
     {
         let workers_ref = Rc::clone(workers);
         let id_ctr = Rc::clone(next_worker_id);
+        let worker_agent = Rc::clone(agent);
         e.define("Worker", native("Worker", move |a| {
             let url = a.into_iter().next().map(|v| v.to_string()).unwrap_or_default();
             let id = {
@@ -724,13 +708,14 @@ pub fn setup_builtins(
             };
 
             // Channels pro main <-> worker komunikaci
-            let (main_to_worker_tx, main_to_worker_rx) = std::sync::mpsc::channel::<String>();
-            let (worker_to_main_tx, worker_to_main_rx) = std::sync::mpsc::channel::<String>();
+            let (main_to_worker_tx, main_to_worker_rx) = std::sync::mpsc::channel::<WorkerMessage>();
+            let (worker_to_main_tx, worker_to_main_rx) = std::sync::mpsc::channel::<WorkerMessage>();
 
-            // Spusti worker thread
+            // Spusti worker thread - dedi cross-origin isolation stranky
             let url_clone = url.clone();
+            let isolated = worker_agent.cross_origin_isolated.get();
             let handle = std::thread::spawn(move || {
-                run_worker_thread(&url_clone, main_to_worker_rx, worker_to_main_tx);
+                run_worker_thread(&url_clone, main_to_worker_rx, worker_to_main_tx, isolated);
             });
 
             workers_ref.borrow_mut().insert(id, super::WorkerState {
@@ -776,513 +761,15 @@ pub fn setup_builtins(
     // ─── DedicatedWorkerGlobalScope - postMessage / close (self) ──────────
     // (Bezi v Worker thread; tady jen alias pro main thread tak ze ho neni potreba.)
 
-    // ─── SharedArrayBuffer stub ──────────────────────────────────────────────
-    // V sync runtime se chova jako bezne pole bytu.
-    e.define("SharedArrayBuffer", native("SharedArrayBuffer", |a| {
-        let len = a.into_iter().next().map(|v| v.to_number() as usize).unwrap_or(0);
-        let mut obj = JsObject::new();
-        obj.set("__shared_buffer__".into(), JsValue::Bool(true));
-        obj.set("byteLength".into(), JsValue::Number(len as f64));
-        // Reprezentujeme jako Array of u8
-        let bytes: Vec<JsValue> = vec![JsValue::Number(0.0); len];
-        obj.set("__bytes__".into(), JsValue::Array(Rc::new(RefCell::new(bytes))));
-        Ok(JsValue::Object(Rc::new(RefCell::new(obj))))
-    }));
-
-    // ArrayBuffer - alias k SharedArrayBuffer v sync runtime + transfer/resize methods
-    e.define("ArrayBuffer", native("ArrayBuffer", |a| {
-        let mut it = a.into_iter();
-        let len = it.next().map(|v| v.to_number() as usize).unwrap_or(0);
-        let _max_byte_length = it.next().and_then(|v| {
-            if let JsValue::Object(o) = v {
-                Some(o.borrow().get("maxByteLength").to_number() as usize)
-            } else { None }
-        }).unwrap_or(len);
-        let obj = Rc::new(RefCell::new(JsObject::new()));
-        obj.borrow_mut().set("__buffer__".into(), JsValue::Bool(true));
-        obj.borrow_mut().set("byteLength".into(), JsValue::Number(len as f64));
-        obj.borrow_mut().set("maxByteLength".into(), JsValue::Number(len as f64));
-        obj.borrow_mut().set("resizable".into(), JsValue::Bool(false));
-        obj.borrow_mut().set("detached".into(), JsValue::Bool(false));
-        let bytes: Vec<JsValue> = vec![JsValue::Number(0.0); len];
-        obj.borrow_mut().set("__bytes__".into(), JsValue::Array(Rc::new(RefCell::new(bytes))));
-        // ES2024 - transfer() / transferToFixedLength()
-        let obj_t = Rc::clone(&obj);
-        obj.borrow_mut().set("transfer".into(), native("transfer", move |args| {
-            let new_len = args.into_iter().next().map(|v| v.to_number() as usize);
-            let old_bytes = obj_t.borrow().get("__bytes__");
-            let new_buf = Rc::new(RefCell::new(JsObject::new()));
-            new_buf.borrow_mut().set("__buffer__".into(), JsValue::Bool(true));
-            let copied: Vec<JsValue> = if let JsValue::Array(a) = old_bytes {
-                let src = a.borrow().clone();
-                if let Some(nl) = new_len {
-                    let mut out = src;
-                    out.resize(nl, JsValue::Number(0.0));
-                    out
-                } else { src }
-            } else { Vec::new() };
-            new_buf.borrow_mut().set("byteLength".into(), JsValue::Number(copied.len() as f64));
-            new_buf.borrow_mut().set("__bytes__".into(),
-                JsValue::Array(Rc::new(RefCell::new(copied))));
-            // Mark old as detached
-            obj_t.borrow_mut().set("detached".into(), JsValue::Bool(true));
-            obj_t.borrow_mut().set("byteLength".into(), JsValue::Number(0.0));
-            Ok(JsValue::Object(new_buf))
-        }));
-        let obj_r = Rc::clone(&obj);
-        obj.borrow_mut().set("resize".into(), native("resize", move |args| {
-            let new_len = args.into_iter().next().map(|v| v.to_number() as usize).unwrap_or(0);
-            let old_bytes = obj_r.borrow().get("__bytes__");
-            if let JsValue::Array(a) = old_bytes {
-                a.borrow_mut().resize(new_len, JsValue::Number(0.0));
-            }
-            obj_r.borrow_mut().set("byteLength".into(), JsValue::Number(new_len as f64));
-            Ok(JsValue::Undefined)
-        }));
-        let obj_s = Rc::clone(&obj);
-        obj.borrow_mut().set("slice".into(), native("slice", move |args| {
-            let mut it = args.into_iter();
-            let start = it.next().map(|v| v.to_number() as usize).unwrap_or(0);
-            let end = it.next().map(|v| v.to_number() as usize);
-            let bytes_v = obj_s.borrow().get("__bytes__");
-            let sliced = if let JsValue::Array(a) = bytes_v {
-                let src = a.borrow();
-                let e = end.unwrap_or(src.len()).min(src.len());
-                if start < e { src[start..e].to_vec() } else { Vec::new() }
-            } else { Vec::new() };
-            let new_buf = Rc::new(RefCell::new(JsObject::new()));
-            new_buf.borrow_mut().set("__buffer__".into(), JsValue::Bool(true));
-            new_buf.borrow_mut().set("byteLength".into(), JsValue::Number(sliced.len() as f64));
-            new_buf.borrow_mut().set("__bytes__".into(),
-                JsValue::Array(Rc::new(RefCell::new(sliced))));
-            Ok(JsValue::Object(new_buf))
-        }));
-        Ok(JsValue::Object(obj))
-    }));
-
-    // DataView - view do ArrayBuffer s typed read/write
-    e.define("DataView", native("DataView", |args| {
-        let mut it = args.into_iter();
-        let buffer = it.next().unwrap_or(JsValue::Undefined);
-        let byte_offset = it.next().map(|v| v.to_number() as usize).unwrap_or(0);
-        let byte_length = it.next().map(|v| v.to_number() as usize);
-        let bytes_arr = if let JsValue::Object(o) = &buffer {
-            o.borrow().get("__bytes__")
-        } else { JsValue::Undefined };
-        let len = if let JsValue::Array(a) = &bytes_arr {
-            byte_length.unwrap_or(a.borrow().len() - byte_offset)
-        } else { 0 };
-        let obj = Rc::new(RefCell::new(JsObject::new()));
-        obj.borrow_mut().set("__data_view__".into(), JsValue::Bool(true));
-        obj.borrow_mut().set("buffer".into(), buffer.clone());
-        obj.borrow_mut().set("byteOffset".into(), JsValue::Number(byte_offset as f64));
-        obj.borrow_mut().set("byteLength".into(), JsValue::Number(len as f64));
-        // getUint8 / getInt8 / getUint16 / getInt16 / getUint32 / getInt32 / getFloat32 / getFloat64
-        // setUint8 / setInt8 / atd.
-        let bytes_get = bytes_arr.clone();
-        obj.borrow_mut().set("getUint8".into(), native("getUint8", move |a| {
-            let off = a.into_iter().next().map(|v| v.to_number() as usize).unwrap_or(0);
-            if let JsValue::Array(arr) = &bytes_get {
-                if let Some(JsValue::Number(n)) = arr.borrow().get(byte_offset + off) {
-                    return Ok(JsValue::Number(*n));
-                }
-            }
-            Ok(JsValue::Number(0.0))
-        }));
-        let bytes_set = bytes_arr.clone();
-        obj.borrow_mut().set("setUint8".into(), native("setUint8", move |a| {
-            let mut it = a.into_iter();
-            let off = it.next().map(|v| v.to_number() as usize).unwrap_or(0);
-            let val = it.next().map(|v| v.to_number() as u8).unwrap_or(0);
-            if let JsValue::Array(arr) = &bytes_set {
-                if let Some(slot) = arr.borrow_mut().get_mut(byte_offset + off) {
-                    *slot = JsValue::Number(val as f64);
-                }
-            }
-            Ok(JsValue::Undefined)
-        }));
-        // getInt8 - signed byte
-        let bytes_i8 = bytes_arr.clone();
-        obj.borrow_mut().set("getInt8".into(), native("getInt8", move |a| {
-            let off = a.into_iter().next().map(|v| v.to_number() as usize).unwrap_or(0);
-            if let JsValue::Array(arr) = &bytes_i8 {
-                if let Some(JsValue::Number(n)) = arr.borrow().get(byte_offset + off) {
-                    let byte_val = (*n as u32 & 0xFF) as u8;
-                    return Ok(JsValue::Number(byte_val as i8 as f64));
-                }
-            }
-            Ok(JsValue::Number(0.0))
-        }));
-        // getUint16 little-endian (default big = false)
-        let bytes_u16 = bytes_arr.clone();
-        obj.borrow_mut().set("getUint16".into(), native("getUint16", move |a| {
-            let mut it = a.into_iter();
-            let off = it.next().map(|v| v.to_number() as usize).unwrap_or(0);
-            let little_endian = it.next().map(|v| v.is_truthy()).unwrap_or(false);
-            if let JsValue::Array(arr) = &bytes_u16 {
-                let b = arr.borrow();
-                let b0 = b.get(byte_offset + off).and_then(|v| if let JsValue::Number(n) = v { Some(*n as u16) } else { None }).unwrap_or(0);
-                let b1 = b.get(byte_offset + off + 1).and_then(|v| if let JsValue::Number(n) = v { Some(*n as u16) } else { None }).unwrap_or(0);
-                let n = if little_endian { (b1 << 8) | b0 } else { (b0 << 8) | b1 };
-                return Ok(JsValue::Number(n as f64));
-            }
-            Ok(JsValue::Number(0.0))
-        }));
-        // getInt16
-        let bytes_i16 = bytes_arr.clone();
-        obj.borrow_mut().set("getInt16".into(), native("getInt16", move |a| {
-            let mut it = a.into_iter();
-            let off = it.next().map(|v| v.to_number() as usize).unwrap_or(0);
-            let little_endian = it.next().map(|v| v.is_truthy()).unwrap_or(false);
-            if let JsValue::Array(arr) = &bytes_i16 {
-                let b = arr.borrow();
-                let b0 = b.get(byte_offset + off).and_then(|v| if let JsValue::Number(n) = v { Some(*n as u16) } else { None }).unwrap_or(0);
-                let b1 = b.get(byte_offset + off + 1).and_then(|v| if let JsValue::Number(n) = v { Some(*n as u16) } else { None }).unwrap_or(0);
-                let n = if little_endian { (b1 << 8) | b0 } else { (b0 << 8) | b1 };
-                return Ok(JsValue::Number(n as i16 as f64));
-            }
-            Ok(JsValue::Number(0.0))
-        }));
-        // getUint32 / getInt32 / getFloat32 / getFloat64
-        let bytes_u32 = bytes_arr.clone();
-        obj.borrow_mut().set("getUint32".into(), native("getUint32", move |a| {
-            let mut it = a.into_iter();
-            let off = it.next().map(|v| v.to_number() as usize).unwrap_or(0);
-            let little_endian = it.next().map(|v| v.is_truthy()).unwrap_or(false);
-            if let JsValue::Array(arr) = &bytes_u32 {
-                let b = arr.borrow();
-                let mut bytes_buf = [0u8; 4];
-                for i in 0..4 {
-                    bytes_buf[i] = b.get(byte_offset + off + i)
-                        .map(|v| v.to_number() as u8).unwrap_or(0);
-                }
-                let n = if little_endian {
-                    u32::from_le_bytes(bytes_buf)
-                } else {
-                    u32::from_be_bytes(bytes_buf)
-                };
-                return Ok(JsValue::Number(n as f64));
-            }
-            Ok(JsValue::Number(0.0))
-        }));
-        let bytes_i32 = bytes_arr.clone();
-        obj.borrow_mut().set("getInt32".into(), native("getInt32", move |a| {
-            let mut it = a.into_iter();
-            let off = it.next().map(|v| v.to_number() as usize).unwrap_or(0);
-            let little_endian = it.next().map(|v| v.is_truthy()).unwrap_or(false);
-            if let JsValue::Array(arr) = &bytes_i32 {
-                let b = arr.borrow();
-                let mut bytes_buf = [0u8; 4];
-                for i in 0..4 {
-                    bytes_buf[i] = b.get(byte_offset + off + i)
-                        .map(|v| v.to_number() as u8).unwrap_or(0);
-                }
-                let n = if little_endian {
-                    i32::from_le_bytes(bytes_buf)
-                } else {
-                    i32::from_be_bytes(bytes_buf)
-                };
-                return Ok(JsValue::Number(n as f64));
-            }
-            Ok(JsValue::Number(0.0))
-        }));
-        let bytes_f32 = bytes_arr.clone();
-        obj.borrow_mut().set("getFloat32".into(), native("getFloat32", move |a| {
-            let mut it = a.into_iter();
-            let off = it.next().map(|v| v.to_number() as usize).unwrap_or(0);
-            let little_endian = it.next().map(|v| v.is_truthy()).unwrap_or(false);
-            if let JsValue::Array(arr) = &bytes_f32 {
-                let b = arr.borrow();
-                let mut bytes_buf = [0u8; 4];
-                for i in 0..4 {
-                    bytes_buf[i] = b.get(byte_offset + off + i)
-                        .map(|v| v.to_number() as u8).unwrap_or(0);
-                }
-                let n = if little_endian {
-                    f32::from_le_bytes(bytes_buf)
-                } else {
-                    f32::from_be_bytes(bytes_buf)
-                };
-                return Ok(JsValue::Number(n as f64));
-            }
-            Ok(JsValue::Number(0.0))
-        }));
-        let bytes_f64 = bytes_arr.clone();
-        obj.borrow_mut().set("getFloat64".into(), native("getFloat64", move |a| {
-            let mut it = a.into_iter();
-            let off = it.next().map(|v| v.to_number() as usize).unwrap_or(0);
-            let little_endian = it.next().map(|v| v.is_truthy()).unwrap_or(false);
-            if let JsValue::Array(arr) = &bytes_f64 {
-                let b = arr.borrow();
-                let mut bytes_buf = [0u8; 8];
-                for i in 0..8 {
-                    bytes_buf[i] = b.get(byte_offset + off + i)
-                        .map(|v| v.to_number() as u8).unwrap_or(0);
-                }
-                let n = if little_endian {
-                    f64::from_le_bytes(bytes_buf)
-                } else {
-                    f64::from_be_bytes(bytes_buf)
-                };
-                return Ok(JsValue::Number(n));
-            }
-            Ok(JsValue::Number(0.0))
-        }));
-        // setUint16 / setUint32 / setInt16 / setInt32 / setFloat32 / setFloat64
-        let bytes_set16 = bytes_arr.clone();
-        obj.borrow_mut().set("setUint16".into(), native("setUint16", move |a| {
-            let mut it = a.into_iter();
-            let off = it.next().map(|v| v.to_number() as usize).unwrap_or(0);
-            let val = it.next().map(|v| v.to_number() as u16).unwrap_or(0);
-            let little_endian = it.next().map(|v| v.is_truthy()).unwrap_or(false);
-            if let JsValue::Array(arr) = &bytes_set16 {
-                let bytes_buf = if little_endian {
-                    val.to_le_bytes()
-                } else {
-                    val.to_be_bytes()
-                };
-                let mut a_mut = arr.borrow_mut();
-                while a_mut.len() < byte_offset + off + 2 { a_mut.push(JsValue::Number(0.0)); }
-                a_mut[byte_offset + off]     = JsValue::Number(bytes_buf[0] as f64);
-                a_mut[byte_offset + off + 1] = JsValue::Number(bytes_buf[1] as f64);
-            }
-            Ok(JsValue::Undefined)
-        }));
-        let bytes_set32 = bytes_arr.clone();
-        obj.borrow_mut().set("setUint32".into(), native("setUint32", move |a| {
-            let mut it = a.into_iter();
-            let off = it.next().map(|v| v.to_number() as usize).unwrap_or(0);
-            let val = it.next().map(|v| v.to_number() as u32).unwrap_or(0);
-            let little_endian = it.next().map(|v| v.is_truthy()).unwrap_or(false);
-            if let JsValue::Array(arr) = &bytes_set32 {
-                let bytes_buf = if little_endian {
-                    val.to_le_bytes()
-                } else {
-                    val.to_be_bytes()
-                };
-                let mut a_mut = arr.borrow_mut();
-                while a_mut.len() < byte_offset + off + 4 { a_mut.push(JsValue::Number(0.0)); }
-                for i in 0..4 {
-                    a_mut[byte_offset + off + i] = JsValue::Number(bytes_buf[i] as f64);
-                }
-            }
-            Ok(JsValue::Undefined)
-        }));
-        let bytes_setf32 = bytes_arr.clone();
-        obj.borrow_mut().set("setFloat32".into(), native("setFloat32", move |a| {
-            let mut it = a.into_iter();
-            let off = it.next().map(|v| v.to_number() as usize).unwrap_or(0);
-            let val = it.next().map(|v| v.to_number() as f32).unwrap_or(0.0);
-            let little_endian = it.next().map(|v| v.is_truthy()).unwrap_or(false);
-            if let JsValue::Array(arr) = &bytes_setf32 {
-                let bytes_buf = if little_endian {
-                    val.to_le_bytes()
-                } else {
-                    val.to_be_bytes()
-                };
-                let mut a_mut = arr.borrow_mut();
-                while a_mut.len() < byte_offset + off + 4 { a_mut.push(JsValue::Number(0.0)); }
-                for i in 0..4 {
-                    a_mut[byte_offset + off + i] = JsValue::Number(bytes_buf[i] as f64);
-                }
-            }
-            Ok(JsValue::Undefined)
-        }));
-        let bytes_setf64 = bytes_arr.clone();
-        obj.borrow_mut().set("setFloat64".into(), native("setFloat64", move |a| {
-            let mut it = a.into_iter();
-            let off = it.next().map(|v| v.to_number() as usize).unwrap_or(0);
-            let val = it.next().map(|v| v.to_number()).unwrap_or(0.0);
-            let little_endian = it.next().map(|v| v.is_truthy()).unwrap_or(false);
-            if let JsValue::Array(arr) = &bytes_setf64 {
-                let bytes_buf = if little_endian {
-                    val.to_le_bytes()
-                } else {
-                    val.to_be_bytes()
-                };
-                let mut a_mut = arr.borrow_mut();
-                while a_mut.len() < byte_offset + off + 8 { a_mut.push(JsValue::Number(0.0)); }
-                for i in 0..8 {
-                    a_mut[byte_offset + off + i] = JsValue::Number(bytes_buf[i] as f64);
-                }
-            }
-            Ok(JsValue::Undefined)
-        }));
-        Ok(JsValue::Object(obj))
-    }));
-
-    // Typed Arrays - vsechny varianty (Uint8Array, Int8Array, Uint16Array, ...)
-    let make_typed_array = |name: &str, bytes_per_element: usize| {
-        let n = name.to_string();
-        let n_clone = n.clone();
-        native(&n_clone, move |a| {
-            let val = a.into_iter().next().unwrap_or(JsValue::Number(0.0));
-            let bytes = match val {
-                JsValue::Number(num) => vec![JsValue::Number(0.0); num as usize],
-                JsValue::Array(arr) => arr.borrow().clone(),
-                JsValue::Object(o) => {
-                    // From ArrayBuffer
-                    if let JsValue::Array(b) = o.borrow().get("__bytes__") {
-                        b.borrow().clone()
-                    } else { vec![] }
-                }
-                _ => vec![],
-            };
-            let len = bytes.len() as f64;
-            let bytes_rc = Rc::new(RefCell::new(bytes));
-            let obj = Rc::new(RefCell::new(JsObject::new()));
-            obj.borrow_mut().set("__typed_array__".into(), JsValue::Str(n.clone()));
-            obj.borrow_mut().set("length".into(), JsValue::Number(len));
-            obj.borrow_mut().set("byteLength".into(), JsValue::Number(len * bytes_per_element as f64));
-            obj.borrow_mut().set("BYTES_PER_ELEMENT".into(), JsValue::Number(bytes_per_element as f64));
-            obj.borrow_mut().set("byteOffset".into(), JsValue::Number(0.0));
-            obj.borrow_mut().set("__bytes__".into(), JsValue::Array(Rc::clone(&bytes_rc)));
-            // buffer - vytvori novy ArrayBuffer view
-            let buf_bytes = Rc::clone(&bytes_rc);
-            let buffer = Rc::new(RefCell::new(JsObject::new()));
-            buffer.borrow_mut().set("__buffer__".into(), JsValue::Bool(true));
-            buffer.borrow_mut().set("byteLength".into(), JsValue::Number(len * bytes_per_element as f64));
-            buffer.borrow_mut().set("__bytes__".into(), JsValue::Array(buf_bytes));
-            obj.borrow_mut().set("buffer".into(), JsValue::Object(buffer));
-
-            // Methods: subarray, set, copyWithin, fill, slice, indexOf, includes, reverse, sort, join
-            let b1 = Rc::clone(&bytes_rc);
-            let n_sub = n.clone();
-            obj.borrow_mut().set("subarray".into(), native("subarray", move |args| {
-                let mut it = args.into_iter();
-                let begin = it.next().map(|v| v.to_number() as usize).unwrap_or(0);
-                let end = it.next().map(|v| v.to_number() as usize)
-                    .unwrap_or_else(|| b1.borrow().len());
-                let src = b1.borrow();
-                let sub: Vec<JsValue> = if begin < end {
-                    src[begin..end.min(src.len())].to_vec()
-                } else { Vec::new() };
-                let new_obj = Rc::new(RefCell::new(JsObject::new()));
-                new_obj.borrow_mut().set("__typed_array__".into(), JsValue::Str(n_sub.clone()));
-                new_obj.borrow_mut().set("length".into(), JsValue::Number(sub.len() as f64));
-                new_obj.borrow_mut().set("byteLength".into(),
-                    JsValue::Number((sub.len() * bytes_per_element) as f64));
-                new_obj.borrow_mut().set("BYTES_PER_ELEMENT".into(),
-                    JsValue::Number(bytes_per_element as f64));
-                new_obj.borrow_mut().set("__bytes__".into(),
-                    JsValue::Array(Rc::new(RefCell::new(sub))));
-                Ok(JsValue::Object(new_obj))
-            }));
-            let b2 = Rc::clone(&bytes_rc);
-            obj.borrow_mut().set("set".into(), native("set", move |args| {
-                let mut it = args.into_iter();
-                let src = it.next().unwrap_or(JsValue::Undefined);
-                let offset = it.next().map(|v| v.to_number() as usize).unwrap_or(0);
-                let src_vals: Vec<JsValue> = match src {
-                    JsValue::Array(a) => a.borrow().clone(),
-                    JsValue::Object(o) => {
-                        if let JsValue::Array(a) = o.borrow().get("__bytes__") {
-                            a.borrow().clone()
-                        } else { Vec::new() }
-                    }
-                    _ => Vec::new(),
-                };
-                let mut dst = b2.borrow_mut();
-                for (i, v) in src_vals.into_iter().enumerate() {
-                    while dst.len() <= offset + i { dst.push(JsValue::Number(0.0)); }
-                    dst[offset + i] = v;
-                }
-                Ok(JsValue::Undefined)
-            }));
-            let b3 = Rc::clone(&bytes_rc);
-            obj.borrow_mut().set("fill".into(), native("fill", move |args| {
-                let mut it = args.into_iter();
-                let val = it.next().unwrap_or(JsValue::Number(0.0));
-                let start = it.next().map(|v| v.to_number() as usize).unwrap_or(0);
-                let end_opt = it.next().map(|v| v.to_number() as usize);
-                let mut dst = b3.borrow_mut();
-                let end = end_opt.unwrap_or(dst.len()).min(dst.len());
-                for i in start..end {
-                    dst[i] = val.clone();
-                }
-                Ok(JsValue::Undefined)
-            }));
-            let b4 = Rc::clone(&bytes_rc);
-            obj.borrow_mut().set("copyWithin".into(), native("copyWithin", move |args| {
-                let mut it = args.into_iter();
-                let target = it.next().map(|v| v.to_number() as usize).unwrap_or(0);
-                let start = it.next().map(|v| v.to_number() as usize).unwrap_or(0);
-                let mut dst = b4.borrow_mut();
-                let end = it.next().map(|v| v.to_number() as usize).unwrap_or_else(|| dst.len());
-                let copy_count = (end.min(dst.len()) - start.min(dst.len())).min(dst.len().saturating_sub(target));
-                let copy: Vec<JsValue> = dst[start..start + copy_count].to_vec();
-                for (i, v) in copy.into_iter().enumerate() {
-                    if target + i < dst.len() { dst[target + i] = v; }
-                }
-                Ok(JsValue::Undefined)
-            }));
-            let b5 = Rc::clone(&bytes_rc);
-            let n_slice = n.clone();
-            obj.borrow_mut().set("slice".into(), native("slice", move |args| {
-                let mut it = args.into_iter();
-                let start = it.next().map(|v| v.to_number() as usize).unwrap_or(0);
-                let end = it.next().map(|v| v.to_number() as usize)
-                    .unwrap_or_else(|| b5.borrow().len());
-                let src = b5.borrow();
-                let sub: Vec<JsValue> = if start < end && start < src.len() {
-                    src[start..end.min(src.len())].to_vec()
-                } else { Vec::new() };
-                let new_obj = Rc::new(RefCell::new(JsObject::new()));
-                new_obj.borrow_mut().set("__typed_array__".into(), JsValue::Str(n_slice.clone()));
-                new_obj.borrow_mut().set("length".into(), JsValue::Number(sub.len() as f64));
-                new_obj.borrow_mut().set("byteLength".into(),
-                    JsValue::Number((sub.len() * bytes_per_element) as f64));
-                new_obj.borrow_mut().set("BYTES_PER_ELEMENT".into(),
-                    JsValue::Number(bytes_per_element as f64));
-                new_obj.borrow_mut().set("__bytes__".into(),
-                    JsValue::Array(Rc::new(RefCell::new(sub))));
-                Ok(JsValue::Object(new_obj))
-            }));
-            let b6 = Rc::clone(&bytes_rc);
-            obj.borrow_mut().set("indexOf".into(), native("indexOf", move |args| {
-                let needle = args.into_iter().next().unwrap_or(JsValue::Undefined);
-                let target = needle.to_number();
-                for (i, v) in b6.borrow().iter().enumerate() {
-                    if v.to_number() == target {
-                        return Ok(JsValue::Number(i as f64));
-                    }
-                }
-                Ok(JsValue::Number(-1.0))
-            }));
-            let b7 = Rc::clone(&bytes_rc);
-            obj.borrow_mut().set("includes".into(), native("includes", move |args| {
-                let needle = args.into_iter().next().unwrap_or(JsValue::Undefined);
-                let target = needle.to_number();
-                Ok(JsValue::Bool(b7.borrow().iter().any(|v| v.to_number() == target)))
-            }));
-            let b8 = Rc::clone(&bytes_rc);
-            obj.borrow_mut().set("reverse".into(), native("reverse", move |_| {
-                b8.borrow_mut().reverse();
-                Ok(JsValue::Undefined)
-            }));
-            let b9 = Rc::clone(&bytes_rc);
-            obj.borrow_mut().set("join".into(), native("join", move |args| {
-                let sep = args.into_iter().next().map(|v| v.to_string()).unwrap_or_else(|| ",".into());
-                let s: Vec<String> = b9.borrow().iter().map(|v| v.to_string()).collect();
-                Ok(JsValue::Str(s.join(&sep)))
-            }));
-            Ok(JsValue::Object(obj))
-        })
-    };
-    e.define("Uint8Array", make_typed_array("Uint8Array", 1));
-    e.define("Int8Array", make_typed_array("Int8Array", 1));
-    e.define("Uint8ClampedArray", make_typed_array("Uint8ClampedArray", 1));
-    e.define("Uint16Array", make_typed_array("Uint16Array", 2));
-    e.define("Int16Array", make_typed_array("Int16Array", 2));
-    e.define("Uint32Array", make_typed_array("Uint32Array", 4));
-    e.define("Int32Array", make_typed_array("Int32Array", 4));
-    e.define("Float32Array", make_typed_array("Float32Array", 4));
-    e.define("Float64Array", make_typed_array("Float64Array", 8));
-    e.define("BigInt64Array", make_typed_array("BigInt64Array", 8));
-    e.define("BigUint64Array", make_typed_array("BigUint64Array", 8));
+    // ArrayBuffer / SharedArrayBuffer / DataView / TypedArray - extracted to builtins_typed_arrays.rs.
+    super::builtins_typed_arrays::setup_typed_arrays(&mut e);
+    if agent.cross_origin_isolated.get() {
+        super::builtins_typed_arrays::define_shared_array_buffer(&mut e);
+    }
 
     // Atomics - extracted to builtins_atomics.rs.
-    super::builtins_atomics::setup_atomics(&mut *e);
+    super::builtins_atomics::setup_atomics(&mut e, agent);
+    super::builtins_wasm::setup_webassembly(&mut e);
     // ─── DOM bridge - real propojeni s browser::dom ─────────────────────────
     // Pouziva se sdileny Rc<RefCell<Document>>. Element je JsValue::DomNode.
 
@@ -1578,61 +1065,61 @@ pub fn setup_builtins(
     // document.body / documentElement / head - lazy z document tree
     {
         let doc = Rc::clone(document);
-        doc_obj.set("__get_body__".into(), native("document.body", move |_| {
+        doc_obj.define_accessor("body".into(), Some(native("document.body", move |_| {
             Ok(match doc.borrow().body() {
                 Some(n) => JsValue::DomNode(n),
                 None    => JsValue::Null,
             })
-        }));
+        })), None);
     }
     {
         let doc = Rc::clone(document);
-        doc_obj.set("__get_documentElement__".into(), native("document.documentElement", move |_| {
+        doc_obj.define_accessor("documentElement".into(), Some(native("document.documentElement", move |_| {
             Ok(match doc.borrow().html_element() {
                 Some(n) => JsValue::DomNode(n),
                 None    => JsValue::Null,
             })
-        }));
+        })), None);
     }
     // document.scrollingElement - quirks mode HTML element. Vraci stejny
     // jako documentElement (modern standard mode).
     {
         let doc = Rc::clone(document);
-        doc_obj.set("__get_scrollingElement__".into(), native("document.scrollingElement", move |_| {
+        doc_obj.define_accessor("scrollingElement".into(), Some(native("document.scrollingElement", move |_| {
             Ok(match doc.borrow().html_element() {
                 Some(n) => JsValue::DomNode(n),
                 None    => JsValue::Null,
             })
-        }));
+        })), None);
     }
     {
         let doc = Rc::clone(document);
-        doc_obj.set("__get_head__".into(), native("document.head", move |_| {
+        doc_obj.define_accessor("head".into(), Some(native("document.head", move |_| {
             Ok(match doc.borrow().head() {
                 Some(n) => JsValue::DomNode(n),
                 None    => JsValue::Null,
             })
-        }));
+        })), None);
     }
     {
         let doc = Rc::clone(document);
-        doc_obj.set("__get_title__".into(), native("document.title", move |_| {
+        doc_obj.define_accessor("title".into(), Some(native("document.title", move |_| {
             Ok(JsValue::Str(doc.borrow().title.clone()))
-        }));
+        })), None);
     }
     {
         let doc = Rc::clone(document);
-        doc_obj.set("__set_title__".into(), native("document.title=", move |args| {
+        doc_obj.define_accessor("title".into(), None, Some(native("document.title=", move |args| {
             let v = args.into_iter().next().unwrap_or(JsValue::Undefined);
             doc.borrow_mut().title = v.to_string();
             Ok(JsValue::Undefined)
-        }));
+        })));
     }
     {
         let doc = Rc::clone(document);
-        doc_obj.set("__get_URL__".into(), native("document.URL", move |_| {
+        doc_obj.define_accessor("URL".into(), Some(native("document.URL", move |_| {
             Ok(JsValue::Str(doc.borrow().url.clone()))
-        }));
+        })), None);
     }
     doc_obj.set("readyState".into(), JsValue::Str("complete".into()));
 
@@ -1879,6 +1366,41 @@ pub fn setup_builtins(
         Ok(JsValue::Object(promise_obj))
     }));
 
+    // new Response(body?, { status, statusText, headers }) - tentyz tvar jako
+    // odpoved fetch (text/json/arrayBuffer, WebAssembly.instantiateStreaming).
+    e.define("Response", native("Response", |a| {
+        let mut it = a.into_iter();
+        let mut headers: Vec<(String, String)> = Vec::new();
+        let body = match it.next().unwrap_or(JsValue::Undefined) {
+            JsValue::Undefined | JsValue::Null => Vec::new(),
+            JsValue::Str(s) => {
+                headers.push(("content-type".into(), "text/plain;charset=UTF-8".into()));
+                s.into_bytes()
+            }
+            other => bytes_of(&other).unwrap_or_else(|| other.to_string().into_bytes()),
+        };
+        let (mut status, mut status_text) = (200, String::new());
+        if let Some(JsValue::Object(init)) = it.next() {
+            let init = init.borrow();
+            if let JsValue::Number(n) = init.get("status") {
+                if !(200.0..=599.0).contains(&n) {
+                    return Err(format!("RangeError: Failed to construct 'Response': The status provided ({n}) is outside the range [200, 599]."));
+                }
+                status = n as u16;
+            }
+            if let JsValue::Str(s) = init.get("statusText") { status_text = s; }
+            if let JsValue::Object(h) = init.get("headers") {
+                let h = h.borrow();
+                for k in h.own_keys() {
+                    let k_lower = k.to_lowercase();
+                    headers.retain(|(name, _)| *name != k_lower);
+                    headers.push((k_lower, h.get(&k).to_string()));
+                }
+            }
+        }
+        Ok(make_response("", status, status_text, body, headers))
+    }));
+
     // ─── XMLHttpRequest - sync + async (sync HTTP via ureq, async fire onload pres
     // pending_xhr_callbacks event loop drain).
     let xhr_net_log = Rc::clone(network_log);
@@ -1939,7 +1461,7 @@ pub fn setup_builtins(
             let v = iter.next().map(|v| v.to_string()).unwrap_or_default();
             let x = sh_ref.borrow();
             if let JsValue::Object(h) = x.get("__xhr_headers__") {
-                h.borrow_mut().set(k, JsValue::Str(v));
+                h.borrow_mut().set(k.into(), JsValue::Str(v));
             }
             Ok(JsValue::Undefined)
         });
@@ -1992,8 +1514,9 @@ pub fn setup_builtins(
                 &url, &method, &headers, body.as_deref());
             let (status, status_text, resp_body, _resp_headers) = match outcome {
                 Ok(t) => t,
-                Err(msg) => (0, msg, String::new(), Vec::new()),
+                Err(msg) => (0, msg, Vec::new(), Vec::new()),
             };
+            let resp_body = String::from_utf8_lossy(&resp_body).into_owned();
             send_net_log.borrow_mut().push((url.clone(), status));
             // Fill XHR state.
             let (onload, onreadystatechange, onloadend);
@@ -2121,6 +1644,9 @@ pub fn setup_builtins(
         }));
     }
 
+    // using / await using: SuppressedError, DisposableStack, AsyncDisposableStack
+    super::disposal::setup(&mut e);
+
     // Promise (logika v call_new a eval_call)
     e.define("Promise", native("Promise", |_| Ok(JsValue::Undefined)));
 
@@ -2186,6 +1712,8 @@ pub fn setup_builtins(
     // top-level scope `self === window === globalThis`). Lucide UMD pattern
     // `(global || self)` ho potrebuje.
     e.define("self", window_val.clone());
+    // Cross-origin isolation (COOP + COEP) - prepisuje Interpreter::set_cross_origin_isolation
+    e.define("crossOriginIsolated", JsValue::Bool(agent.cross_origin_isolated.get()));
 
     // queueMicrotask - stub (sync)
     e.define("queueMicrotask", native("queueMicrotask", |_| Ok(JsValue::Undefined)));
@@ -2587,8 +2115,7 @@ pub fn setup_builtins(
         obj.borrow_mut().set("encoding".into(), JsValue::Str("utf-8".into()));
         obj.borrow_mut().set("encode".into(), native("TextEncoder.encode", |args| {
             let s = args.into_iter().next().map(|v| v.to_string()).unwrap_or_default();
-            let bytes: Vec<JsValue> = s.bytes().map(|b| JsValue::Number(b as f64)).collect();
-            Ok(JsValue::Array(Rc::new(RefCell::new(bytes))))
+            Ok(typed_array_value(TypedArrayKind::Uint8, s.into_bytes()))
         }));
         Ok(JsValue::Object(obj))
     }));
@@ -2602,12 +2129,7 @@ pub fn setup_builtins(
             let arr = args.into_iter().next().unwrap_or(JsValue::Undefined);
             let bytes: Vec<u8> = match arr {
                 JsValue::Array(a) => a.borrow().iter().map(|v| v.to_number() as u8).collect(),
-                JsValue::Object(o) => {
-                    if let JsValue::Array(a) = o.borrow().get("__bytes__") {
-                        a.borrow().iter().map(|v| v.to_number() as u8).collect()
-                    } else { Vec::new() }
-                }
-                _ => Vec::new(),
+                other => bytes_of(&other).unwrap_or_default(),
             };
             Ok(JsValue::Str(String::from_utf8_lossy(&bytes).into_owned()))
         }));
@@ -2651,12 +2173,24 @@ pub fn setup_builtins(
         crypto.borrow_mut().set("getRandomValues".into(), native("crypto.getRandomValues", |args| {
             // Vraci puvodni array s "random" hodnotami (deterministicky pseudo-random pres time)
             let arr = args.into_iter().next().unwrap_or(JsValue::Undefined);
+            let nanos = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|d| d.as_nanos() as u64)
+                .unwrap_or(0);
+            let mut state = nanos.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            // Typed array: nahodne bajty primo do jeho bufferu
+            if let Some(view) = typed_view_of(&arr) {
+                if view.kind.element_size() * view.length() > 65536 {
+                    return Err("QuotaExceededError: getRandomValues byte length exceeds 65536".into());
+                }
+                let random: Vec<u8> = (0..view.byte_length()).map(|_| {
+                    state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+                    (state >> 56) as u8
+                }).collect();
+                view.buffer.borrow_mut().write(view.byte_offset, &random);
+                return Ok(arr);
+            }
             if let JsValue::Array(a) = &arr {
-                let nanos = std::time::SystemTime::now()
-                    .duration_since(std::time::UNIX_EPOCH)
-                    .map(|d| d.as_nanos() as u64)
-                    .unwrap_or(0);
-                let mut state = nanos.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
                 let len = a.borrow().len();
                 let mut new_vals = Vec::with_capacity(len);
                 for _ in 0..len {
@@ -2677,12 +2211,7 @@ pub fn setup_builtins(
             let bytes: Vec<u8> = match &data {
                 JsValue::Array(a) => a.borrow().iter().map(|v| v.to_number() as u8).collect(),
                 JsValue::Str(s) => s.bytes().collect(),
-                JsValue::Object(o) => {
-                    if let JsValue::Array(b) = o.borrow().get("__bytes__") {
-                        b.borrow().iter().map(|v| v.to_number() as u8).collect()
-                    } else { Vec::new() }
-                }
-                _ => Vec::new(),
+                other => bytes_of(other).unwrap_or_default(),
             };
             let normalized = algo.to_uppercase().replace("-", "");
             let digest_bytes: Vec<u8> = match normalized.as_str() {
@@ -2711,17 +2240,11 @@ pub fn setup_builtins(
                         JsValue::Str(format!("NotSupportedError: {algo}"))));
                 }
             };
-            let arr: Vec<JsValue> = digest_bytes.into_iter().map(|b| JsValue::Number(b as f64)).collect();
-            // Vrat ArrayBuffer-like { __bytes__, byteLength }
-            let buf = Rc::new(RefCell::new(JsObject::new()));
-            buf.borrow_mut().set("__buffer__".into(), JsValue::Bool(true));
-            buf.borrow_mut().set("byteLength".into(), JsValue::Number(arr.len() as f64));
-            buf.borrow_mut().set("__bytes__".into(), JsValue::Array(Rc::new(RefCell::new(arr))));
-            Ok(make_settled_promise("fulfilled", JsValue::Object(buf)))
+            Ok(make_settled_promise("fulfilled", array_buffer_value(digest_bytes)))
         }));
         for m in &["encrypt", "decrypt", "sign", "verify", "generateKey", "importKey", "exportKey", "deriveKey", "deriveBits", "wrapKey", "unwrapKey"] {
             let name = m.to_string();
-            subtle.borrow_mut().set(name, native(m, |_| {
+            subtle.borrow_mut().set(name.into(), native(m, |_| {
                 Ok(make_settled_promise("fulfilled", JsValue::Undefined))
             }));
         }
@@ -2840,7 +2363,7 @@ pub fn setup_builtins(
                 let init_clone = policy_init.clone();
                 let k_owned = k.to_string();
                 let k_for_native = k_owned.clone();
-                policy.borrow_mut().set(k_owned.clone(), native(&k_for_native, move |a| {
+                policy.borrow_mut().set(k_owned.clone().into(), native(&k_for_native, move |a| {
                     let _ = &init_clone;
                     Ok(a.into_iter().next().unwrap_or(JsValue::Str(String::new())))
                 }));
@@ -3152,25 +2675,33 @@ pub fn setup_builtins(
     e.define("ImageData", native("ImageData", |args| {
         let mut it = args.into_iter();
         let first = it.next().unwrap_or(JsValue::Undefined);
+        // data je Uint8ClampedArray; predany Uint8ClampedArray se sdili (ne kopiruje)
         let (data, w, h) = match first {
             JsValue::Number(width) => {
                 let height = it.next().map(|v| v.to_number()).unwrap_or(1.0);
                 let len = (width * height * 4.0) as usize;
-                let data: Vec<JsValue> = vec![JsValue::Number(0.0); len];
-                (data, width, height)
+                (typed_array_value(TypedArrayKind::Uint8Clamped, vec![0; len]), width, height)
             }
             JsValue::Array(arr) => {
                 let width = it.next().map(|v| v.to_number()).unwrap_or(1.0);
-                let data = arr.borrow().clone();
-                let height = (data.len() as f64 / 4.0 / width).max(1.0);
-                (data, width, height)
+                let values = arr.borrow().clone();
+                let height = (values.len() as f64 / 4.0 / width).max(1.0);
+                (typed_array_from_values(TypedArrayKind::Uint8Clamped, &values)?, width, height)
             }
-            _ => (Vec::new(), 0.0, 0.0),
+            ref src => match typed_view_of(src) {
+                Some(view) if view.kind == TypedArrayKind::Uint8Clamped => {
+                    let width = it.next().map(|v| v.to_number()).unwrap_or(1.0);
+                    let height = (view.length() as f64 / 4.0 / width).max(1.0);
+                    (src.clone(), width, height)
+                }
+                Some(_) => return Err("TypeError: Failed to construct 'ImageData': parameter 1 is not of type 'Uint8ClampedArray'".into()),
+                None => (typed_array_value(TypedArrayKind::Uint8Clamped, Vec::new()), 0.0, 0.0),
+            },
         };
         let obj = Rc::new(RefCell::new(JsObject::new()));
         obj.borrow_mut().set("width".into(), JsValue::Number(w));
         obj.borrow_mut().set("height".into(), JsValue::Number(h));
-        obj.borrow_mut().set("data".into(), JsValue::Array(Rc::new(RefCell::new(data))));
+        obj.borrow_mut().set("data".into(), data);
         obj.borrow_mut().set("colorSpace".into(), JsValue::Str("srgb".into()));
         Ok(JsValue::Object(obj))
     }));
@@ -3257,7 +2788,7 @@ pub fn setup_builtins(
         for unit in &["px", "em", "rem", "pt", "pc", "in", "cm", "mm", "ex", "ch",
                       "vw", "vh", "vmin", "vmax", "%", "deg", "rad", "turn", "s", "ms"] {
             let u = unit.to_string();
-            css_obj.set(unit.to_string(), native(unit, move |a| {
+            css_obj.set(unit.to_string().into(), native(unit, move |a| {
                 let n = a.into_iter().next().map(|v| v.to_number()).unwrap_or(0.0);
                 let mut o = JsObject::new();
                 o.set("value".into(), JsValue::Number(n));
@@ -3294,7 +2825,7 @@ pub fn setup_builtins(
         obj.borrow_mut().set("__path2d__".into(), JsValue::Bool(true));
         for m in &["addPath", "closePath", "moveTo", "lineTo", "bezierCurveTo",
                    "quadraticCurveTo", "arc", "arcTo", "ellipse", "rect", "roundRect"] {
-            obj.borrow_mut().set(m.to_string(), native(m, |_| Ok(JsValue::Undefined)));
+            obj.borrow_mut().set(m.to_string().into(), native(m, |_| Ok(JsValue::Undefined)));
         }
         Ok(JsValue::Object(obj))
     }));
@@ -3372,7 +2903,7 @@ pub fn setup_builtins(
         for (i, v) in m.iter().enumerate() {
             let row = i / 4 + 1;
             let col = i % 4 + 1;
-            obj.borrow_mut().set(format!("m{}{}", col, row), JsValue::Number(*v));
+            obj.borrow_mut().set(format!("m{}{}", col, row).into(), JsValue::Number(*v));
         }
         obj.borrow_mut().set("is2D".into(), JsValue::Bool(nums.len() == 6 || nums.is_empty()));
         obj.borrow_mut().set("isIdentity".into(), JsValue::Bool(nums.is_empty()));
@@ -3405,7 +2936,7 @@ pub fn setup_builtins(
             let pt = Rc::new(RefCell::new(JsObject::new()));
             pt.borrow_mut().set("x".into(), JsValue::Number(0.0));
             pt.borrow_mut().set("y".into(), JsValue::Number(0.0));
-            obj.borrow_mut().set(p.to_string(), JsValue::Object(pt));
+            obj.borrow_mut().set(p.to_string().into(), JsValue::Object(pt));
         }
         Ok(JsValue::Object(obj))
     }));
@@ -4205,7 +3736,7 @@ pub fn setup_builtins(
             if let Some(JsValue::Object(o)) = init {
                 let b = o.borrow();
                 for (k, v) in &b.props {
-                    obj.borrow_mut().set(k.clone(), v.clone());
+                    obj.borrow_mut().set(k.clone().into(), v.clone());
                 }
             }
             let obj_pd = Rc::clone(&obj);
@@ -4729,20 +4260,10 @@ pub fn setup_builtins(
 
     // File - extends Blob, name + lastModified
     e.define("File", native("File", |args| {
-        let mut size: f64 = 0.0;
-        let mut text_concat = String::new();
         let mut it = args.into_iter();
-        if let Some(parts) = it.next() {
-            if let JsValue::Array(arr) = &parts {
-                for p in arr.borrow().iter() {
-                    match p {
-                        JsValue::Str(s) => { size += s.len() as f64; text_concat.push_str(s); }
-                        JsValue::Array(bytes) => { size += bytes.borrow().len() as f64; }
-                        _ => {}
-                    }
-                }
-            }
-        }
+        let bytes = it.next().map(|parts| blob_part_bytes(&parts)).unwrap_or_default();
+        let size = bytes.len() as f64;
+        let text_concat = String::from_utf8_lossy(&bytes).into_owned();
         let name = it.next().map(|v| v.to_string()).unwrap_or_else(|| "file".into());
         let mut mime = String::new();
         let mut last_modified = 0.0;
@@ -4762,8 +4283,8 @@ pub fn setup_builtins(
         obj.borrow_mut().set("text".into(), native("File.text", move |_| {
             Ok(make_settled_promise("fulfilled", JsValue::Str(text.clone())))
         }));
-        obj.borrow_mut().set("arrayBuffer".into(), native("File.arrayBuffer", |_|
-            Ok(make_settled_promise("fulfilled", JsValue::Array(Rc::new(RefCell::new(Vec::new())))))));
+        obj.borrow_mut().set("arrayBuffer".into(), native("File.arrayBuffer", move |_|
+            Ok(make_settled_promise("fulfilled", array_buffer_value(bytes.clone())))));
         obj.borrow_mut().set("slice".into(), native("File.slice", |_| Ok(JsValue::Undefined)));
         Ok(JsValue::Object(obj))
     }));
@@ -4779,26 +4300,11 @@ pub fn setup_builtins(
 
     // Blob - shrnuje size z parts[0..] + type z options.type
     e.define("Blob", native("Blob", |args| {
-        let mut size: f64 = 0.0;
-        let mut text_concat = String::new();
         let mut it = args.into_iter();
-        if let Some(parts) = it.next() {
-            // parts: array stringu nebo bytes
-            if let JsValue::Array(arr) = &parts {
-                for p in arr.borrow().iter() {
-                    match p {
-                        JsValue::Str(s) => {
-                            size += s.len() as f64;
-                            text_concat.push_str(s);
-                        }
-                        JsValue::Array(bytes) => {
-                            size += bytes.borrow().len() as f64;
-                        }
-                        _ => {}
-                    }
-                }
-            }
-        }
+        // parts: retezce, pole bajtu, ArrayBuffer / typed array
+        let bytes = it.next().map(|parts| blob_part_bytes(&parts)).unwrap_or_default();
+        let size = bytes.len() as f64;
+        let text_concat = String::from_utf8_lossy(&bytes).into_owned();
         // options.type
         let mut mime = String::new();
         if let Some(opts) = it.next() {
@@ -4815,7 +4321,8 @@ pub fn setup_builtins(
         obj.borrow_mut().set("text".into(), native("Blob.text", move |_| {
             Ok(make_settled_promise("fulfilled", JsValue::Str(text_for_async.clone())))
         }));
-        obj.borrow_mut().set("arrayBuffer".into(), native("Blob.arrayBuffer", |_| Ok(make_settled_promise("fulfilled", JsValue::Array(Rc::new(RefCell::new(Vec::new())))))));
+        obj.borrow_mut().set("arrayBuffer".into(), native("Blob.arrayBuffer", move |_|
+            Ok(make_settled_promise("fulfilled", array_buffer_value(bytes.clone())))));
         obj.borrow_mut().set("slice".into(), native("Blob.slice", |_| Ok(JsValue::Undefined)));
         Ok(JsValue::Object(obj))
     }));
@@ -1,160 +1,233 @@
 //! Atomics - extracted z builtins.rs.
 //!
-//! V sync runtime jsou to bezne operace (zadna konkurence). Pracuje na
-//! ArrayBuffer/SharedArrayBuffer's __bytes__ Array internalu.
+//! Nad SharedArrayBuffer jdou operace pres `SharedMemory` (skutecne atomicke
+//! instrukce, pamet sdilena s Worker vlakny); `wait` / `notify` / `waitAsync`
+//! pouzivaji jeji frontu cekajicich. Bezny ArrayBuffer nema konkurenci -
+//! tam staci prime cteni/zapis pres view. Predany ArrayBuffer /
+//! SharedArrayBuffer se bere jako Uint8 view pres cely buffer.
 
 use std::rc::Rc;
 use std::cell::RefCell;
-use super::{JsValue, JsObject, Environment};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use num_bigint::BigInt;
+use num_traits::ToPrimitive;
+use super::{AgentState, AsyncWait, JsValue, JsObject, Environment};
 use super::helpers::native;
+use super::builtins_typed_arrays::binary_of;
+use super::shared_memory::SharedMemory;
+use super::typed_arrays::{BinaryData, Endian, TypedArrayKind, TypedArrayView};
 
-pub fn setup_atomics(e: &mut Environment) {
+/// Cil atomicke operace: (view, index prvku).
+fn atomic_target(a: &[JsValue]) -> Result<(TypedArrayView, usize), String> {
+    let target = a.first().cloned().unwrap_or(JsValue::Undefined);
+    let view = match binary_of(&target).as_deref() {
+        Some(BinaryData::TypedArray(v)) if !matches!(v.kind,
+            TypedArrayKind::Float16 | TypedArrayKind::Float32 | TypedArrayKind::Float64 | TypedArrayKind::Uint8Clamped) => v.clone(),
+        Some(BinaryData::Buffer(b)) => {
+            let len = b.borrow().byte_length();
+            TypedArrayView::new(Rc::clone(b), TypedArrayKind::Uint8, 0, Some(len))
+        }
+        _ => return Err("TypeError: Atomics operation requires an integer TypedArray".into()),
+    };
+    let i = a.get(1).map(|v| v.to_number()).unwrap_or(0.0);
+    let i = if i.is_nan() { 0.0 } else { i.trunc() };
+    if i < 0.0 || i >= view.length() as f64 {
+        return Err("RangeError: Invalid atomic access index".into());
+    }
+    Ok((view, i as usize))
+}
+
+/// Cil wait/notify: jen Int32Array / BigInt64Array.
+fn waitable_target(a: &[JsValue], op: &str) -> Result<(TypedArrayView, usize), String> {
+    let (view, i) = atomic_target(a)?;
+    if !matches!(view.kind, TypedArrayKind::Int32 | TypedArrayKind::BigInt64) {
+        return Err(format!("TypeError: Atomics.{op} requires an Int32Array or BigInt64Array"));
+    }
+    Ok((view, i))
+}
+
+/// Sdilena pamet a bajtovy offset prvku, kdyz view lezi nad SharedArrayBuffer.
+fn shared_cell(view: &TypedArrayView, i: usize) -> Option<(Arc<SharedMemory>, usize)> {
+    let memory = view.buffer.borrow().shared_memory().cloned()?;
+    Some((memory, view.byte_offset + i * view.kind.element_size()))
+}
+
+/// Operand prevedeny na cele cislo v domene prvku (Number vs BigInt).
+fn to_int(v: &JsValue, kind: TypedArrayKind) -> Result<i128, String> {
+    if kind.is_bigint() {
+        return match v {
+            JsValue::BigInt(b) => Ok((&**b & BigInt::from(u64::MAX)).to_u64().unwrap_or(0) as i128),
+            other => Err(format!("TypeError: Cannot convert {other} to a BigInt")),
+        };
+    }
+    if matches!(v, JsValue::BigInt(_)) {
+        return Err("TypeError: Cannot convert a BigInt value to a number".into());
+    }
+    let n = v.to_number();
+    Ok(if n.is_finite() { n.trunc() as i128 } else { 0 })
+}
+
+fn from_int(n: i128, kind: TypedArrayKind) -> JsValue {
+    if kind.is_bigint() { JsValue::BigInt(Rc::new(BigInt::from(n))) } else { JsValue::Number(n as f64) }
+}
+
+/// Cele cislo -> little-endian bity prvku (dvojkovy doplnek, orez na sirku).
+fn to_raw(n: i128, kind: TypedArrayKind) -> u64 {
+    let bits = kind.element_size() * 8;
+    if bits == 64 { n as u64 } else { (n as u64) & ((1u64 << bits) - 1) }
+}
+
+fn from_raw(raw: u64, kind: TypedArrayKind) -> JsValue {
+    kind.decode(&raw.to_le_bytes()[..kind.element_size()], Endian::Little)
+}
+
+fn arg(a: &[JsValue], i: usize) -> &JsValue {
+    a.get(i).unwrap_or(&JsValue::Undefined)
+}
+
+/// Read-modify-write: zapise `op(stara, operand)` a vrati starou hodnotu.
+fn rmw(a: &[JsValue], op: fn(i128, i128) -> i128) -> Result<JsValue, String> {
+    let (view, i) = atomic_target(a)?;
+    let kind = view.kind;
+    let operand = to_int(arg(a, 2), kind)?;
+    if let Some((memory, offset)) = shared_cell(&view, i) {
+        let old = memory.update(offset, kind.element_size(), |raw| {
+            let current = to_int(&from_raw(raw, kind), kind).unwrap_or(0);
+            to_raw(op(current, operand), kind)
+        });
+        return Ok(from_raw(old, kind));
+    }
+    let old = view.get(i).unwrap_or(JsValue::Undefined);
+    let current = to_int(&old, kind)?;
+    view.set(i, &from_int(op(current, operand), kind))?;
+    Ok(old)
+}
+
+/// Timeout v ms; undefined / NaN / +Infinity = cekat bez limitu.
+fn timeout_arg(v: &JsValue) -> Option<Duration> {
+    if matches!(v, JsValue::Undefined) { return None; }
+    let ms = v.to_number();
+    if ms.is_nan() || ms == f64::INFINITY { return None; }
+    Some(Duration::from_secs_f64(ms.max(0.0) / 1000.0))
+}
+
+fn wait_result(is_async: bool, value: JsValue) -> JsValue {
+    let result = Rc::new(RefCell::new(JsObject::new()));
+    result.borrow_mut().set("async".into(), JsValue::Bool(is_async));
+    result.borrow_mut().set("value".into(), value);
+    JsValue::Object(result)
+}
+
+/// Pending promise, kterou settle `Interpreter::drain_atomic_waits`.
+fn pending_promise() -> JsValue {
+    let mut obj = JsObject::new();
+    obj.set("__promise_state__".into(), JsValue::Str("pending".into()));
+    obj.set("__promise_value__".into(), JsValue::Undefined);
+    obj.set("__pending_callbacks__".into(), JsValue::Array(Rc::new(RefCell::new(Vec::new()))));
+    JsValue::Object(Rc::new(RefCell::new(obj)))
+}
+
+pub fn setup_atomics(e: &mut Environment, agent: &Rc<AgentState>) {
     let mut atomics = JsObject::new();
     atomics.set("load".into(), native("Atomics.load", |a| {
-        let arr = a.first().cloned().unwrap_or(JsValue::Undefined);
-        let i = a.get(1).map(|v| v.to_number() as usize).unwrap_or(0);
-        if let JsValue::Object(o) = arr {
-            if let Some(JsValue::Array(bytes)) = o.borrow().props.get("__bytes__") {
-                return Ok(bytes.borrow().get(i).cloned().unwrap_or(JsValue::Number(0.0)));
-            }
+        let (view, i) = atomic_target(&a)?;
+        if let Some((memory, offset)) = shared_cell(&view, i) {
+            return Ok(from_raw(memory.load(offset, view.kind.element_size()), view.kind));
         }
-        Ok(JsValue::Number(0.0))
+        Ok(view.get(i).unwrap_or(JsValue::Undefined))
     }));
     atomics.set("store".into(), native("Atomics.store", |a| {
-        let arr = a.first().cloned().unwrap_or(JsValue::Undefined);
-        let i = a.get(1).map(|v| v.to_number() as usize).unwrap_or(0);
-        let val = a.get(2).cloned().unwrap_or(JsValue::Number(0.0));
-        if let JsValue::Object(o) = arr {
-            if let Some(JsValue::Array(bytes)) = o.borrow().props.get("__bytes__") {
-                let mut b = bytes.borrow_mut();
-                while b.len() <= i { b.push(JsValue::Number(0.0)); }
-                b[i] = val.clone();
-            }
+        let (view, i) = atomic_target(&a)?;
+        let n = to_int(arg(&a, 2), view.kind)?;
+        let val = from_int(n, view.kind);
+        match shared_cell(&view, i) {
+            Some((memory, offset)) => memory.store(offset, view.kind.element_size(), to_raw(n, view.kind)),
+            None => view.set(i, &val)?,
         }
         Ok(val)
     }));
-    atomics.set("add".into(), native("Atomics.add", |a| {
-        let arr = a.first().cloned().unwrap_or(JsValue::Undefined);
-        let i = a.get(1).map(|v| v.to_number() as usize).unwrap_or(0);
-        let delta = a.get(2).map(|v| v.to_number()).unwrap_or(0.0);
-        if let JsValue::Object(o) = arr {
-            if let Some(JsValue::Array(bytes)) = o.borrow().props.get("__bytes__") {
-                let mut b = bytes.borrow_mut();
-                let old = b.get(i).map(|v| v.to_number()).unwrap_or(0.0);
-                while b.len() <= i { b.push(JsValue::Number(0.0)); }
-                b[i] = JsValue::Number(old + delta);
-                return Ok(JsValue::Number(old));
-            }
+    atomics.set("add".into(), native("Atomics.add", |a| rmw(&a, |x, y| x + y)));
+    atomics.set("sub".into(), native("Atomics.sub", |a| rmw(&a, |x, y| x - y)));
+    atomics.set("and".into(), native("Atomics.and", |a| rmw(&a, |x, y| x & y)));
+    atomics.set("or".into(), native("Atomics.or", |a| rmw(&a, |x, y| x | y)));
+    atomics.set("xor".into(), native("Atomics.xor", |a| rmw(&a, |x, y| x ^ y)));
+    atomics.set("exchange".into(), native("Atomics.exchange", |a| rmw(&a, |_, y| y)));
+    atomics.set("compareExchange".into(), native("Atomics.compareExchange", |a| {
+        let (view, i) = atomic_target(&a)?;
+        let kind = view.kind;
+        let expected = to_int(arg(&a, 2), kind)?;
+        let replacement = to_int(arg(&a, 3), kind)?;
+        // Porovnava se az po konverzi na typ prvku (300 v Uint8Array == 44)
+        if let Some((memory, offset)) = shared_cell(&view, i) {
+            let old = memory.compare_exchange(offset, kind.element_size(), to_raw(expected, kind), to_raw(replacement, kind));
+            return Ok(from_raw(old, kind));
         }
-        Ok(JsValue::Number(0.0))
-    }));
-    atomics.set("sub".into(), native("Atomics.sub", |a| {
-        let arr = a.first().cloned().unwrap_or(JsValue::Undefined);
-        let i = a.get(1).map(|v| v.to_number() as usize).unwrap_or(0);
-        let delta = a.get(2).map(|v| v.to_number()).unwrap_or(0.0);
-        if let JsValue::Object(o) = arr {
-            if let Some(JsValue::Array(bytes)) = o.borrow().props.get("__bytes__") {
-                let mut b = bytes.borrow_mut();
-                let old = b.get(i).map(|v| v.to_number()).unwrap_or(0.0);
-                while b.len() <= i { b.push(JsValue::Number(0.0)); }
-                b[i] = JsValue::Number(old - delta);
-                return Ok(JsValue::Number(old));
-            }
+        let old = view.get(i).unwrap_or(JsValue::Undefined);
+        if from_raw(to_raw(expected, kind), kind).strict_eq(&old) {
+            view.set(i, &from_int(replacement, kind))?;
         }
-        Ok(JsValue::Number(0.0))
+        Ok(old)
     }));
-    atomics.set("compareExchange".into(), native("Atomics.compareExchange", |a| {
-        let arr = a.first().cloned().unwrap_or(JsValue::Undefined);
-        let i = a.get(1).map(|v| v.to_number() as usize).unwrap_or(0);
-        let expected = a.get(2).map(|v| v.to_number()).unwrap_or(0.0);
-        let new_val = a.get(3).map(|v| v.to_number()).unwrap_or(0.0);
-        if let JsValue::Object(o) = arr {
-            if let Some(JsValue::Array(bytes)) = o.borrow().props.get("__bytes__") {
-                let mut b = bytes.borrow_mut();
-                let current = b.get(i).map(|v| v.to_number()).unwrap_or(0.0);
-                if current == expected {
-                    while b.len() <= i { b.push(JsValue::Number(0.0)); }
-                    b[i] = JsValue::Number(new_val);
-                }
-                return Ok(JsValue::Number(current));
-            }
+    // Atomics.wait(ta, i, value, timeoutMs) - blokuje vlakno do notify;
+    // main thread (can_block = false) blokovat nesmi.
+    let wait_agent = Rc::clone(agent);
+    atomics.set("wait".into(), native("Atomics.wait", move |a| {
+        let (view, i) = waitable_target(&a, "wait")?;
+        let Some((memory, offset)) = shared_cell(&view, i) else {
+            return Err("TypeError: Atomics.wait requires a shared typed array".into());
+        };
+        let expected = to_raw(to_int(arg(&a, 2), view.kind)?, view.kind);
+        let timeout = timeout_arg(arg(&a, 3));
+        if !wait_agent.can_block.get() {
+            return Err("TypeError: Atomics.wait cannot be called in this context".into());
         }
-        Ok(JsValue::Number(0.0))
+        let outcome = memory.wait(offset, view.kind.element_size(), expected, timeout);
+        Ok(JsValue::Str(outcome.as_str().into()))
     }));
-    atomics.set("wait".into(), native("Atomics.wait", |_| {
-        Ok(JsValue::Str("not-equal".into()))
+    // Atomics.waitAsync - neblokuje; promise settle pri drain_atomic_waits.
+    let async_agent = Rc::clone(agent);
+    atomics.set("waitAsync".into(), native("Atomics.waitAsync", move |a| {
+        let (view, i) = waitable_target(&a, "waitAsync")?;
+        let Some((memory, offset)) = shared_cell(&view, i) else {
+            return Err("TypeError: Atomics.waitAsync requires a shared typed array".into());
+        };
+        let size = view.kind.element_size();
+        let expected = to_raw(to_int(arg(&a, 2), view.kind)?, view.kind);
+        let timeout = timeout_arg(arg(&a, 3));
+        if timeout == Some(Duration::ZERO) {
+            let outcome = if memory.load(offset, size) == expected { "timed-out" } else { "not-equal" };
+            return Ok(wait_result(false, JsValue::Str(outcome.into())));
+        }
+        let Some(waiter) = memory.enqueue_async(offset, size, expected) else {
+            return Ok(wait_result(false, JsValue::Str("not-equal".into())));
+        };
+        let promise = pending_promise();
+        async_agent.async_waits.borrow_mut().push(AsyncWait {
+            memory,
+            waiter,
+            deadline: timeout.map(|t| Instant::now() + t),
+            promise: promise.clone(),
+        });
+        Ok(wait_result(true, promise))
     }));
-    atomics.set("waitAsync".into(), native("Atomics.waitAsync", |_| {
-        let result = Rc::new(RefCell::new(JsObject::new()));
-        result.borrow_mut().set("async".into(), JsValue::Bool(false));
-        result.borrow_mut().set("value".into(), JsValue::Str("not-equal".into()));
-        Ok(JsValue::Object(result))
+    // Atomics.notify(ta, i, count) - pocet probuzenych (nesdileny buffer = 0).
+    atomics.set("notify".into(), native("Atomics.notify", |a| {
+        let (view, i) = waitable_target(&a, "notify")?;
+        let count = match arg(&a, 2) {
+            JsValue::Undefined => None,
+            v => {
+                let n = v.to_number();
+                if n == f64::INFINITY { None } else if n.is_nan() { Some(0) } else { Some(n.max(0.0) as usize) }
+            }
+        };
+        let woken = shared_cell(&view, i).map_or(0, |(memory, offset)| memory.notify(offset, count));
+        Ok(JsValue::Number(woken as f64))
     }));
-    atomics.set("notify".into(), native("Atomics.notify", |_| Ok(JsValue::Number(0.0))));
     atomics.set("isLockFree".into(), native("Atomics.isLockFree", |a| {
         let size = a.into_iter().next().map(|v| v.to_number() as i64).unwrap_or(0);
         Ok(JsValue::Bool(matches!(size, 1 | 2 | 4 | 8)))
     }));
     atomics.set("pause".into(), native("Atomics.pause", |_| Ok(JsValue::Undefined)));
-    atomics.set("exchange".into(), native("Atomics.exchange", |a| {
-        let arr = a.first().cloned().unwrap_or(JsValue::Undefined);
-        let i = a.get(1).map(|v| v.to_number() as usize).unwrap_or(0);
-        let new_val = a.get(2).map(|v| v.to_number()).unwrap_or(0.0);
-        if let JsValue::Object(o) = arr {
-            if let Some(JsValue::Array(bytes)) = o.borrow().props.get("__bytes__") {
-                let mut b = bytes.borrow_mut();
-                let old = b.get(i).map(|v| v.to_number()).unwrap_or(0.0);
-                while b.len() <= i { b.push(JsValue::Number(0.0)); }
-                b[i] = JsValue::Number(new_val);
-                return Ok(JsValue::Number(old));
-            }
-        }
-        Ok(JsValue::Number(0.0))
-    }));
-    atomics.set("and".into(), native("Atomics.and", |a| {
-        let arr = a.first().cloned().unwrap_or(JsValue::Undefined);
-        let i = a.get(1).map(|v| v.to_number() as usize).unwrap_or(0);
-        let mask = a.get(2).map(|v| v.to_number() as i64).unwrap_or(0);
-        if let JsValue::Object(o) = arr {
-            if let Some(JsValue::Array(bytes)) = o.borrow().props.get("__bytes__") {
-                let mut b = bytes.borrow_mut();
-                let old = b.get(i).map(|v| v.to_number() as i64).unwrap_or(0);
-                while b.len() <= i { b.push(JsValue::Number(0.0)); }
-                b[i] = JsValue::Number((old & mask) as f64);
-                return Ok(JsValue::Number(old as f64));
-            }
-        }
-        Ok(JsValue::Number(0.0))
-    }));
-    atomics.set("or".into(), native("Atomics.or", |a| {
-        let arr = a.first().cloned().unwrap_or(JsValue::Undefined);
-        let i = a.get(1).map(|v| v.to_number() as usize).unwrap_or(0);
-        let mask = a.get(2).map(|v| v.to_number() as i64).unwrap_or(0);
-        if let JsValue::Object(o) = arr {
-            if let Some(JsValue::Array(bytes)) = o.borrow().props.get("__bytes__") {
-                let mut b = bytes.borrow_mut();
-                let old = b.get(i).map(|v| v.to_number() as i64).unwrap_or(0);
-                while b.len() <= i { b.push(JsValue::Number(0.0)); }
-                b[i] = JsValue::Number((old | mask) as f64);
-                return Ok(JsValue::Number(old as f64));
-            }
-        }
-        Ok(JsValue::Number(0.0))
-    }));
-    atomics.set("xor".into(), native("Atomics.xor", |a| {
-        let arr = a.first().cloned().unwrap_or(JsValue::Undefined);
-        let i = a.get(1).map(|v| v.to_number() as usize).unwrap_or(0);
-        let mask = a.get(2).map(|v| v.to_number() as i64).unwrap_or(0);
-        if let JsValue::Object(o) = arr {
-            if let Some(JsValue::Array(bytes)) = o.borrow().props.get("__bytes__") {
-                let mut b = bytes.borrow_mut();
-                let old = b.get(i).map(|v| v.to_number() as i64).unwrap_or(0);
-                while b.len() <= i { b.push(JsValue::Number(0.0)); }
-                b[i] = JsValue::Number((old ^ mask) as f64);
-                return Ok(JsValue::Number(old as f64));
-            }
-        }
-        Ok(JsValue::Number(0.0))
-    }));
     e.define("Atomics", JsValue::Object(Rc::new(RefCell::new(atomics))));
 }
@@ -1,20 +1,23 @@
 //! Builtin construction helpers: worker thread runner, message port, URLSearchParams,
-//! IDB object store factory.
+//! IDB object store factory, Blob parts.
 
 use std::cell::RefCell;
 use std::collections::HashMap;
 use std::rc::Rc;
 use super::{JsValue, JsObject};
 use super::helpers::*;
+use super::worker_message::{self, WorkerMessage};
 
 /// Worker thread loop: nacte JS skript ze souboru a interpretuje ho.
 /// Worker scope ma globalni `self`, `postMessage(data)` a `onmessage = fn`.
-/// Pri prijeti zpravy z main: parse JSON, zavola self.onmessage({data: parsed}).
-/// Pri postMessage z workera: serializuj a posli outgoing channel.
+/// Pri prijeti zpravy z main: dekoduj (worker_message.rs), zavola self.onmessage({data}).
+/// Pri postMessage z workera: zakoduj a posli outgoing channel.
+/// Worker smi blokovat v `Atomics.wait`; isolation dedi od stranky.
 pub(super) fn run_worker_thread(
     script_url: &str,
-    incoming: std::sync::mpsc::Receiver<String>,
-    outgoing: std::sync::mpsc::Sender<String>,
+    incoming: std::sync::mpsc::Receiver<WorkerMessage>,
+    outgoing: std::sync::mpsc::Sender<WorkerMessage>,
+    cross_origin_isolated: bool,
 ) {
     use crate::lexer::base::Lexer;
     use crate::parser::Parser;
@@ -22,6 +25,8 @@ pub(super) fn run_worker_thread(
 
     // Worker ma vlastni Interpreter. Zaregistrujeme postMessage co posle pres outgoing.
     let mut interp = super::Interpreter::new();
+    interp.agent.can_block.set(true);
+    interp.apply_cross_origin_isolation(cross_origin_isolated);
     let outgoing_clone = outgoing.clone();
 
     // Pridam worker postMessage do scope
@@ -29,9 +34,7 @@ pub(super) fn run_worker_thread(
         "postMessage".to_string(),
         std::rc::Rc::new(move |args: Vec<JsValue>| {
             let val = args.into_iter().next().unwrap_or(JsValue::Undefined);
-            let serialized = json_stringify(&val, 0, 0)
-                .unwrap_or_else(|| val.to_string());
-            let _ = outgoing_clone.send(serialized);
+            let _ = outgoing_clone.send(worker_message::encode(&val, cross_origin_isolated)?);
             Ok(JsValue::Undefined)
         }),
     ));
@@ -64,21 +67,37 @@ pub(super) fn run_worker_thread(
         }
     }
 
-    // Loop: cti zpravy, vola self.onmessage
-    while let Ok(msg) = incoming.recv() {
-        let parsed = json_parse(&msg).unwrap_or(JsValue::Str(msg.clone()));
-        let mut event = JsObject::new();
-        event.set("data".into(), parsed);
-        let event_val = JsValue::Object(Rc::new(RefCell::new(event)));
+    // Loop: cti zpravy, vola self.onmessage. Pri cekajicim waitAsync se
+    // fronta polluje s timeoutem, aby promise settlovala i bez dalsi zpravy.
+    loop {
+        let msg = if interp.has_pending_atomic_waits() {
+            match incoming.recv_timeout(std::time::Duration::from_millis(5)) {
+                Ok(msg) => Some(msg),
+                Err(std::sync::mpsc::RecvTimeoutError::Timeout) => None,
+                Err(std::sync::mpsc::RecvTimeoutError::Disconnected) => break,
+            }
+        } else {
+            match incoming.recv() {
+                Ok(msg) => Some(msg),
+                Err(_) => break,
+            }
+        };
+        if let Some(msg) = msg {
+            let mut event = JsObject::new();
+            event.set("data".into(), worker_message::decode(msg));
+            let event_val = JsValue::Object(Rc::new(RefCell::new(event)));
 
-        // self.onmessage(event)
-        let onmessage = if let JsValue::Object(s) = &self_val {
-            s.borrow().get("onmessage")
-        } else { JsValue::Undefined };
+            // self.onmessage(event)
+            let onmessage = if let JsValue::Object(s) = &self_val {
+                s.borrow().get("onmessage")
+            } else { JsValue::Undefined };
 
-        if !matches!(onmessage, JsValue::Undefined) {
-            let _ = interp.call_function(onmessage, vec![event_val], None);
+            if !matches!(onmessage, JsValue::Undefined) {
+                let _ = interp.call_function(onmessage, vec![event_val], None);
+            }
         }
+        interp.drain_atomic_waits();
+        let _ = interp.drain_timers();
     }
 }
 
@@ -321,3 +340,18 @@ pub(super) fn make_object_store(
     }));
     store
 }
+
+/// Bajty `new Blob(parts)` / `new File(parts)`: retezce jako UTF-8, pole cisel,
+/// ArrayBuffer / typed array / DataView.
+pub(super) fn blob_part_bytes(parts: &JsValue) -> Vec<u8> {
+    let JsValue::Array(arr) = parts else { return Vec::new() };
+    let mut out = Vec::new();
+    for p in arr.borrow().iter() {
+        match p {
+            JsValue::Str(s) => out.extend_from_slice(s.as_bytes()),
+            JsValue::Array(bytes) => out.extend(bytes.borrow().iter().map(|v| v.to_number() as u8)),
+            other => out.extend(super::builtins_typed_arrays::bytes_of(other).unwrap_or_default()),
+        }
+    }
+    out
+}
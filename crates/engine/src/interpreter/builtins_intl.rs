@@ -0,0 +1,1887 @@
+//! Intl (ECMA-402) nad ICU4X.
+//!
+//! Kazdy Intl objekt nese `__class_chain__` pro `instanceof` a interni slot
+//! `__intl_locale__` s kanonickym tagem (diky nemu jde `Intl.Locale` predat
+//! jako locale dalsim konstruktorum). Metody jsou per-objekt natives
+//! zachycujici zkonstruovany ICU formatter (natives nevidi `this`),
+//! `resolvedOptions()` vraci vyresene volby. ICU vetsinou neoznacuje casti
+//! vystupu, `formatToParts` je proto klasifikuje samo. Staticke
+//! `supportedLocalesOf` resi `static_prop`.
+
+use std::rc::Rc;
+use std::cell::RefCell;
+use std::fmt;
+use std::str::FromStr;
+use fixed_decimal::{FixedDecimal, SignDisplay};
+use icu::decimal::options::{FixedDecimalFormatterOptions, GroupingStrategy};
+use icu::decimal::FixedDecimalFormatter;
+use icu::locid::extensions::unicode::{key, Key, Value as KeyValue};
+use icu::locid::Locale;
+use writeable::Writeable;
+use super::{symbols, JsObject, JsValue, Environment};
+use super::helpers::{native, get_date_ms, make_array_iterator, now_ms};
+use super::temporal::{IsoDate, IsoDateTime, IsoTime, TimeZone};
+use super::time_zone;
+
+type Obj = Rc<RefCell<JsObject>>;
+type IResult<T> = Result<T, String>;
+type Parts = Vec<(&'static str, String)>;
+
+const LOCALE_SLOT: &str = "__intl_locale__";
+
+fn arg(a: &[JsValue], i: usize) -> JsValue {
+    a.get(i).cloned().unwrap_or(JsValue::Undefined)
+}
+
+fn type_error(msg: impl fmt::Display) -> String {
+    format!("TypeError: {msg}")
+}
+
+fn range_error(msg: impl fmt::Display) -> String {
+    format!("RangeError: {msg}")
+}
+
+fn array(items: Vec<JsValue>) -> JsValue {
+    JsValue::Array(Rc::new(RefCell::new(items)))
+}
+
+fn plain_object(entries: &[(&str, JsValue)]) -> JsValue {
+    let mut obj = JsObject::new();
+    for (k, v) in entries {
+        obj.set(k.to_string().into(), v.clone());
+    }
+    JsValue::Object(Rc::new(RefCell::new(obj)))
+}
+
+fn str_value(s: impl Into<String>) -> JsValue {
+    JsValue::Str(s.into())
+}
+
+fn opt_str(s: Option<impl Into<String>>) -> JsValue {
+    s.map_or(JsValue::Undefined, str_value)
+}
+
+/// `[{type, value}, ...]` z klasifikovanych casti.
+fn parts_array(parts: Parts, extra: &[(&str, JsValue)]) -> JsValue {
+    array(parts.into_iter().map(|(kind, value)| {
+        let mut entries = vec![("type", str_value(kind)), ("value", str_value(value))];
+        entries.extend(extra.iter().cloned());
+        plain_object(&entries)
+    }).collect())
+}
+
+/// Prida cast; sousedni casti stejneho druhu slouci.
+fn push_part(parts: &mut Parts, kind: &'static str, text: &str) {
+    match parts.last_mut() {
+        Some((last, value)) if *last == kind => value.push_str(text),
+        _ => parts.push((kind, text.to_string())),
+    }
+}
+
+// ─── Volby ───────────────────────────────────────────────────────────────────
+
+/// Objekt voleb; `undefined` = zadne volby, `null` = TypeError.
+fn options(v: &JsValue) -> IResult<Option<Obj>> {
+    match v {
+        JsValue::Undefined => Ok(None),
+        JsValue::Null => Err(type_error("Cannot convert null to object")),
+        JsValue::Object(o) => Ok(Some(Rc::clone(o))),
+        _ => Ok(None),
+    }
+}
+
+fn get(o: &Option<Obj>, key: &str) -> JsValue {
+    o.as_ref().map_or(JsValue::Undefined, |o| o.borrow().get(key))
+}
+
+/// Retezcova volba; prazdne `allowed` = libovolna hodnota.
+fn string_option(o: &Option<Obj>, key: &str, allowed: &[&str], default: Option<&str>) -> IResult<Option<String>> {
+    match get(o, key) {
+        JsValue::Undefined => Ok(default.map(str::to_string)),
+        v => {
+            let s = v.to_string();
+            if allowed.is_empty() || allowed.contains(&s.as_str()) {
+                Ok(Some(s))
+            } else {
+                Err(range_error(format_args!("Value {s} out of range for Intl options property {key}")))
+            }
+        }
+    }
+}
+
+fn bool_option(o: &Option<Obj>, key: &str) -> Option<bool> {
+    match get(o, key) {
+        JsValue::Undefined => None,
+        v => Some(v.is_truthy()),
+    }
+}
+
+fn digits_option(o: &Option<Obj>, key: &str, min: u8, max: u8) -> IResult<Option<u8>> {
+    match get(o, key) {
+        JsValue::Undefined => Ok(None),
+        v => {
+            let n = v.to_number();
+            if !n.is_finite() || n.floor() < min as f64 || n.floor() > max as f64 {
+                return Err(range_error(format_args!("{key} value is out of range.")));
+            }
+            Ok(Some(n.floor() as u8))
+        }
+    }
+}
+
+/// Unicode `type` (3-8 alfanumerickych znaku, pomlckou oddelene) pro `calendar`,
+/// `numberingSystem` a `collation`.
+fn type_option(o: &Option<Obj>, key: &str) -> IResult<Option<String>> {
+    let Some(s) = string_option(o, key, &[], None)? else { return Ok(None) };
+    let valid = !s.is_empty() && s.split('-').all(|p| (3..=8).contains(&p.len()) && p.bytes().all(|b| b.is_ascii_alphanumeric()));
+    if !valid {
+        return Err(range_error(format_args!("Invalid {key} : {s}")));
+    }
+    Ok(Some(s.to_ascii_lowercase()))
+}
+
+// ─── Locale ──────────────────────────────────────────────────────────────────
+
+fn parse_locale(tag: &str) -> IResult<Locale> {
+    let mut loc = Locale::from_str(tag).map_err(|_| range_error(format_args!("Incorrect locale information provided: {tag}")))?;
+    icu::locid_transform::LocaleCanonicalizer::new().canonicalize(&mut loc);
+    Ok(loc)
+}
+
+fn locale_of(v: &JsValue) -> IResult<Locale> {
+    match v {
+        JsValue::Str(s) => parse_locale(s),
+        JsValue::Object(o) => match o.borrow().props.get(LOCALE_SLOT) {
+            Some(JsValue::Str(s)) => parse_locale(s),
+            _ => Err(type_error("Language ID should be string or object.")),
+        },
+        _ => Err(type_error("Language ID should be string or object.")),
+    }
+}
+
+/// CanonicalizeLocaleList: retezec, `Intl.Locale` nebo pole, bez duplicit.
+fn requested_locales(v: &JsValue) -> IResult<Vec<Locale>> {
+    let items = match v {
+        JsValue::Undefined => return Ok(Vec::new()),
+        JsValue::Array(items) => items.borrow().clone(),
+        v => vec![v.clone()],
+    };
+    let mut out: Vec<Locale> = Vec::new();
+    for item in &items {
+        let loc = locale_of(item)?;
+        if !out.contains(&loc) {
+            out.push(loc);
+        }
+    }
+    Ok(out)
+}
+
+/// Prvni pozadovane locale; bez pozadavku `en-US`. ICU ma data pro vsechna
+/// CLDR locale, chybejici data resi fallback uvnitr ICU.
+fn resolve_locale(v: &JsValue) -> IResult<Locale> {
+    Ok(requested_locales(v)?.into_iter().next().unwrap_or(icu::locid::locale!("en-US")))
+}
+
+fn keyword(loc: &Locale, k: Key) -> Option<String> {
+    loc.extensions.unicode.keywords.get(&k).map(|v| v.to_string())
+}
+
+fn set_keyword(loc: &mut Locale, k: Key, v: &str) -> IResult<()> {
+    let value = KeyValue::try_from_bytes(v.as_bytes()).map_err(|_| range_error(format_args!("Invalid value {v} for {k}")))?;
+    loc.extensions.unicode.keywords.set(k, value);
+    Ok(())
+}
+
+/// Tag pro `resolvedOptions().locale`: jen unicode klice relevantni pro formatter.
+fn resolved_tag(loc: &Locale, keys: &[Key]) -> String {
+    let mut loc = loc.clone();
+    loc.extensions.unicode.keywords.retain_by_key(|k| keys.contains(k));
+    loc.extensions.unicode.attributes.clear();
+    loc.extensions.other.clear();
+    loc.extensions.private.clear();
+    loc.extensions.transform.clear();
+    loc.to_string()
+}
+
+fn numbering_system(loc: &Locale) -> String {
+    keyword(loc, key!("nu")).unwrap_or_else(|| "latn".into())
+}
+
+// ─── Intl objekt ─────────────────────────────────────────────────────────────
+
+/// Instance Intl tridy: trida, locale slot, metody a `resolvedOptions()`.
+struct Instance {
+    obj: JsObject,
+    class: &'static str,
+}
+
+impl Instance {
+    fn new(class: &'static str, locale: &str) -> Instance {
+        let mut obj = JsObject::new();
+        obj.set("__class_chain__".into(), str_value(class));
+        obj.set(LOCALE_SLOT.into(), str_value(locale));
+        obj.define_hidden(symbols::TO_STRING_TAG.into(), str_value(class));
+        Instance { obj, class }
+    }
+
+    fn field(&mut self, name: &str, v: JsValue) {
+        self.obj.define_hidden(name.into(), v);
+    }
+
+    fn method(&mut self, name: &str, f: impl Fn(Vec<JsValue>) -> IResult<JsValue> + 'static) {
+        self.obj.define_hidden(name.into(), native(&format!("{}.prototype.{name}", self.class), f));
+    }
+
+    /// `resolvedOptions()` - pri kazdem volani novy objekt (undefined polozky vynechane).
+    fn resolved(&mut self, entries: Vec<(&'static str, JsValue)>) {
+        let entries: Vec<_> = entries.into_iter().filter(|(_, v)| !matches!(v, JsValue::Undefined)).collect();
+        self.method("resolvedOptions", move |_| Ok(plain_object(&entries)));
+    }
+
+    fn finish(self) -> JsValue {
+        JsValue::Object(Rc::new(RefCell::new(self.obj)))
+    }
+}
+
+/// Zapisovac `writeable` casti (ListFormat, RelativeTimeFormat).
+struct PartsSink {
+    parts: Parts,
+    current: &'static str,
+}
+
+impl fmt::Write for PartsSink {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        if !s.is_empty() {
+            push_part(&mut self.parts, self.current, s);
+        }
+        Ok(())
+    }
+}
+
+impl writeable::PartsWrite for PartsSink {
+    type SubPartsWrite = Self;
+
+    fn with_part(&mut self, part: writeable::Part, mut f: impl FnMut(&mut Self) -> fmt::Result) -> fmt::Result {
+        let outer = std::mem::replace(&mut self.current, part.value);
+        let result = f(self);
+        self.current = outer;
+        result
+    }
+}
+
+fn collect_parts(w: &impl Writeable, default: &'static str) -> Parts {
+    let mut sink = PartsSink { parts: Vec::new(), current: default };
+    let _ = w.write_to_parts(&mut sink);
+    sink.parts
+}
+
+// ─── NumberFormat ────────────────────────────────────────────────────────────
+
+#[derive(Clone, Copy, PartialEq)]
+enum Notation {
+    Standard,
+    Scientific,
+    Engineering,
+    Compact { long: bool },
+}
+
+#[derive(Clone, PartialEq)]
+enum NumberStyle {
+    Decimal,
+    Percent,
+    Currency { code: String, display: String },
+    Unit { unit: String, display: String },
+}
+
+/// Meny s jinym nez dvoumistnym zlomkem (ISO 4217).
+fn currency_digits(code: &str) -> u8 {
+    match code {
+        "BIF" | "CLP" | "DJF" | "GNF" | "ISK" | "JPY" | "KMF" | "KRW" | "PYG" | "RWF" | "UGX" | "UYI"
+        | "VND" | "VUV" | "XAF" | "XOF" | "XPF" => 0,
+        "BHD" | "IQD" | "JOD" | "KWD" | "LYD" | "OMR" | "TND" => 3,
+        _ => 2,
+    }
+}
+
+/// Jednotky povolene ECMA-402: (jmeno, short 1, short n, narrow, long 1, long n).
+/// Popisky jsou anglicke; ICU4X 1.5 data jednotek zatim nema.
+const UNITS: &[(&str, &str, &str, &str, &str, &str)] = &[
+    ("acre", "ac", "ac", "ac", "acre", "acres"),
+    ("bit", "bit", "bit", "bit", "bit", "bits"),
+    ("byte", "byte", "byte", "B", "byte", "bytes"),
+    ("celsius", "°C", "°C", "°C", "degree Celsius", "degrees Celsius"),
+    ("centimeter", "cm", "cm", "cm", "centimeter", "centimeters"),
+    ("day", "day", "days", "d", "day", "days"),
+    ("degree", "deg", "deg", "°", "degree", "degrees"),
+    ("fahrenheit", "°F", "°F", "°", "degree Fahrenheit", "degrees Fahrenheit"),
+    ("fluid-ounce", "fl oz", "fl oz", "fl oz", "fluid ounce", "fluid ounces"),
+    ("foot", "ft", "ft", "′", "foot", "feet"),
+    ("gallon", "gal", "gal", "gal", "gallon", "gallons"),
+    ("gigabit", "Gb", "Gb", "Gb", "gigabit", "gigabits"),
+    ("gigabyte", "GB", "GB", "GB", "gigabyte", "gigabytes"),
+    ("gram", "g", "g", "g", "gram", "grams"),
+    ("hectare", "ha", "ha", "ha", "hectare", "hectares"),
+    ("hour", "hr", "hr", "h", "hour", "hours"),
+    ("inch", "in", "in", "″", "inch", "inches"),
+    ("kilobit", "kb", "kb", "kb", "kilobit", "kilobits"),
+    ("kilobyte", "kB", "kB", "kB", "kilobyte", "kilobytes"),
+    ("kilogram", "kg", "kg", "kg", "kilogram", "kilograms"),
+    ("kilometer", "km", "km", "km", "kilometer", "kilometers"),
+    ("liter", "L", "L", "L", "liter", "liters"),
+    ("megabit", "Mb", "Mb", "Mb", "megabit", "megabits"),
+    ("megabyte", "MB", "MB", "MB", "megabyte", "megabytes"),
+    ("meter", "m", "m", "m", "meter", "meters"),
+    ("microsecond", "μs", "μs", "μs", "microsecond", "microseconds"),
+    ("mile", "mi", "mi", "mi", "mile", "miles"),
+    ("mile-scandinavian", "smi", "smi", "smi", "mile-scandinavian", "miles-scandinavian"),
+    ("milliliter", "mL", "mL", "mL", "milliliter", "milliliters"),
+    ("millimeter", "mm", "mm", "mm", "millimeter", "millimeters"),
+    ("millisecond", "ms", "ms", "ms", "millisecond", "milliseconds"),
+    ("minute", "min", "min", "m", "minute", "minutes"),
+    ("month", "mth", "mths", "m", "month", "months"),
+    ("nanosecond", "ns", "ns", "ns", "nanosecond", "nanoseconds"),
+    ("ounce", "oz", "oz", "oz", "ounce", "ounces"),
+    ("percent", "%", "%", "%", "percent", "percent"),
+    ("petabyte", "PB", "PB", "PB", "petabyte", "petabytes"),
+    ("pound", "lb", "lb", "lb", "pound", "pounds"),
+    ("second", "sec", "sec", "s", "second", "seconds"),
+    ("stone", "st", "st", "st", "stone", "stones"),
+    ("terabit", "Tb", "Tb", "Tb", "terabit", "terabits"),
+    ("terabyte", "TB", "TB", "TB", "terabyte", "terabytes"),
+    ("week", "wk", "wks", "w", "week", "weeks"),
+    ("yard", "yd", "yd", "yd", "yard", "yards"),
+    ("year", "yr", "yrs", "y", "year", "years"),
+];
+
+fn simple_unit(name: &str) -> Option<&'static (&'static str, &'static str, &'static str, &'static str, &'static str, &'static str)> {
+    UNITS.iter().find(|u| u.0 == name)
+}
+
+fn is_valid_unit(unit: &str) -> bool {
+    match unit.split_once("-per-") {
+        Some((a, b)) => simple_unit(a).is_some() && simple_unit(b).is_some(),
+        None => simple_unit(unit).is_some(),
+    }
+}
+
+/// Popisek jednotky; `true` = pripojit bez mezery.
+fn unit_label(unit: &str, display: &str, one: bool) -> (String, bool) {
+    let label = |name: &str, one: bool| {
+        let u = simple_unit(name).expect("unit validated in constructor");
+        match display {
+            "long" => if one { u.4 } else { u.5 },
+            "narrow" => u.3,
+            _ => if one { u.1 } else { u.2 },
+        }
+    };
+    match unit.split_once("-per-") {
+        Some((a, b)) if display == "long" => (format!("{} per {}", label(a, one), label(b, true)), false),
+        Some((a, b)) => (format!("{}/{}", label(a, one), label(b, true)), display == "narrow"),
+        None => {
+            let text = label(unit, one);
+            let tight = display == "narrow" || text.starts_with(['°', '%', '′', '″']) && display != "long";
+            (text.to_string(), tight)
+        }
+    }
+}
+
+#[derive(Clone)]
+struct NumberFormat {
+    locale: Locale,
+    tag: String,
+    style: NumberStyle,
+    notation: Notation,
+    min_int: u8,
+    frac: (u8, u8),
+    sig: Option<(u8, u8)>,
+    grouping: &'static str,
+    sign: &'static str,
+    /// Oddelovac tisicu a desetinna carka locale (pro `formatToParts`).
+    group_sep: String,
+    decimal_sep: String,
+}
+
+impl NumberFormat {
+    fn new(locales: &JsValue, opts: &JsValue) -> IResult<NumberFormat> {
+        let requested = resolve_locale(locales)?;
+        let o = options(opts)?;
+        let mut locale = requested.clone();
+        string_option(&o, "localeMatcher", &["lookup", "best fit"], None)?;
+        if let Some(nu) = type_option(&o, "numberingSystem")? {
+            set_keyword(&mut locale, key!("nu"), &nu)?;
+        }
+        let style = string_option(&o, "style", &["decimal", "percent", "currency", "unit"], Some("decimal"))?.unwrap_or_default();
+        let currency = match string_option(&o, "currency", &[], None)? {
+            Some(c) if c.len() == 3 && c.bytes().all(|b| b.is_ascii_alphabetic()) => Some(c.to_ascii_uppercase()),
+            Some(c) => return Err(range_error(format_args!("Invalid currency code : {c}"))),
+            None => None,
+        };
+        let currency_display = string_option(&o, "currencyDisplay", &["code", "symbol", "narrowSymbol", "name"], Some("symbol"))?.unwrap_or_default();
+        let unit = match string_option(&o, "unit", &[], None)? {
+            Some(u) if is_valid_unit(&u) => Some(u),
+            Some(u) => return Err(range_error(format_args!("Invalid unit argument for Intl.NumberFormat() '{u}'"))),
+            None => None,
+        };
+        let unit_display = string_option(&o, "unitDisplay", &["short", "narrow", "long"], Some("short"))?.unwrap_or_default();
+        let style = match style.as_str() {
+            "percent" => NumberStyle::Percent,
+            "currency" => NumberStyle::Currency {
+                code: currency.ok_or_else(|| type_error("Currency code is required with currency style."))?,
+                display: currency_display,
+            },
+            "unit" => NumberStyle::Unit {
+                unit: unit.ok_or_else(|| type_error("Unit is required with unit style."))?,
+                display: unit_display,
+            },
+            _ => NumberStyle::Decimal,
+        };
+        let notation = match string_option(&o, "notation", &["standard", "scientific", "engineering", "compact"], Some("standard"))?.as_deref() {
+            Some("scientific") => Notation::Scientific,
+            Some("engineering") => Notation::Engineering,
+            Some("compact") => Notation::Compact {
+                long: string_option(&o, "compactDisplay", &["short", "long"], Some("short"))?.as_deref() == Some("long"),
+            },
+            _ => Notation::Standard,
+        };
+
+        let (default_min, default_max) = match (&style, notation) {
+            (NumberStyle::Currency { code, .. }, Notation::Standard) => (currency_digits(code), currency_digits(code)),
+            (_, Notation::Compact { .. }) | (NumberStyle::Percent, _) => (0, 0),
+            _ => (0, 3),
+        };
+        let min_int = digits_option(&o, "minimumIntegerDigits", 1, 21)?.unwrap_or(1);
+        let min_frac = digits_option(&o, "minimumFractionDigits", 0, 100)?;
+        let max_frac = digits_option(&o, "maximumFractionDigits", 0, 100)?;
+        let frac = match (min_frac, max_frac) {
+            (Some(min), Some(max)) if min > max => return Err(range_error("maximumFractionDigits value is out of range.")),
+            (Some(min), Some(max)) => (min, max),
+            (Some(min), None) => (min, min.max(default_max)),
+            (None, Some(max)) => (default_min.min(max), max),
+            (None, None) => (default_min, default_max),
+        };
+        let min_sig = digits_option(&o, "minimumSignificantDigits", 1, 21)?;
+        let max_sig = digits_option(&o, "maximumSignificantDigits", min_sig.unwrap_or(1), 21)?;
+        let sig = match (min_sig, max_sig) {
+            (None, None) => None,
+            (min, max) => Some((min.unwrap_or(1), max.unwrap_or(21))),
+        };
+        let grouping = match get(&o, "useGrouping") {
+            JsValue::Undefined => if matches!(notation, Notation::Compact { .. }) { "min2" } else { "auto" },
+            JsValue::Bool(true) => "always",
+            JsValue::Bool(false) => "false",
+            v => match v.to_string().as_str() {
+                "always" => "always",
+                "auto" => "auto",
+                "min2" => "min2",
+                "true" | "false" => "auto",
+                s => return Err(range_error(format_args!("Value {s} out of range for Intl options property useGrouping"))),
+            },
+        };
+        let sign = match string_option(&o, "signDisplay", &["auto", "never", "always", "exceptZero", "negative"], Some("auto"))?.as_deref() {
+            Some("never") => "never",
+            Some("always") => "always",
+            Some("exceptZero") => "exceptZero",
+            Some("negative") => "negative",
+            _ => "auto",
+        };
+
+        let probe = decimal_formatter(&locale, GroupingStrategy::Always)
+            .format_to_string(&FixedDecimal::from_str("1234567.5").expect("valid decimal"));
+        let runs = char_runs(&probe, |c| c.is_numeric());
+        let group_sep = runs.iter().find(|(digit, _)| !digit).map(|(_, s)| s.clone()).unwrap_or_else(|| ",".into());
+        let decimal_sep = runs.iter().rev().find(|(digit, _)| !digit).map(|(_, s)| s.clone()).unwrap_or_else(|| ".".into());
+
+        Ok(NumberFormat {
+            tag: resolved_tag(&requested, &[key!("nu")]),
+            locale,
+            style,
+            notation,
+            min_int,
+            frac,
+            sig,
+            grouping,
+            sign,
+            group_sep,
+            decimal_sep,
+        })
+    }
+
+    fn grouping_strategy(&self) -> GroupingStrategy {
+        match self.grouping {
+            "always" => GroupingStrategy::Always,
+            "min2" => GroupingStrategy::Min2,
+            "false" => GroupingStrategy::Never,
+            _ => GroupingStrategy::Auto,
+        }
+    }
+
+    fn sign_display(&self) -> SignDisplay {
+        match self.sign {
+            "never" => SignDisplay::Never,
+            "always" => SignDisplay::Always,
+            "exceptZero" => SignDisplay::ExceptZero,
+            "negative" => SignDisplay::Negative,
+            _ => SignDisplay::Auto,
+        }
+    }
+
+    /// Zaokrouhleni podle fraction / significant digits voleb.
+    fn round(&self, fd: &mut FixedDecimal) {
+        match self.sig {
+            Some((min, max)) => {
+                let mag = fd.nonzero_magnitude_start();
+                fd.half_expand(mag - max as i16 + 1);
+                fd.trim_end();
+                let mag = fd.nonzero_magnitude_start();
+                fd.pad_end(mag - min as i16 + 1);
+            }
+            None => {
+                fd.half_expand(-(self.frac.1 as i16));
+                fd.trim_end();
+                fd.pad_end(-(self.frac.0 as i16));
+            }
+        }
+        fd.pad_start(self.min_int as i16);
+    }
+
+    /// Hodnota jako zaokrouhleny FixedDecimal (pro PluralRules).
+    fn decimal(&self, n: f64) -> FixedDecimal {
+        let mut fd = FixedDecimal::from_str(&format!("{n}")).unwrap_or_default();
+        if self.style == NumberStyle::Percent {
+            fd.multiply_pow10(2);
+        }
+        self.round(&mut fd);
+        fd
+    }
+
+    fn format(&self, n: f64) -> String {
+        if n.is_nan() {
+            return "NaN".into();
+        }
+        let negative = n < 0.0 || (n == 0.0 && n.is_sign_negative());
+        let sign = match (self.sign, negative) {
+            ("never", _) => "",
+            (_, true) => "-",
+            ("always", false) => "+",
+            ("exceptZero", false) if n != 0.0 => "+",
+            _ => "",
+        };
+        if n.is_infinite() {
+            return self.decorate(&format!("{sign}∞"), false);
+        }
+        let mut fd = FixedDecimal::from_str(&format!("{n}")).unwrap_or_default();
+        if self.style == NumberStyle::Percent {
+            fd.multiply_pow10(2);
+        }
+        let one = fd.to_string().trim_start_matches(['-', '+']) == "1";
+        let body = match self.notation {
+            Notation::Compact { long } => {
+                use icu::experimental::compactdecimal::CompactDecimalFormatter;
+                let dl = (&self.locale).into();
+                let formatter = if long {
+                    CompactDecimalFormatter::try_new_long(&dl, Default::default())
+                } else {
+                    CompactDecimalFormatter::try_new_short(&dl, Default::default())
+                };
+                fd.apply_sign_display(self.sign_display());
+                match formatter {
+                    Ok(f) => f.format_fixed_decimal(fd).write_to_string().into_owned(),
+                    Err(_) => fd.to_string(),
+                }
+            }
+            Notation::Scientific | Notation::Engineering => {
+                let mut exponent = if n == 0.0 { 0 } else { fd.nonzero_magnitude_start() };
+                if self.notation == Notation::Engineering {
+                    exponent = exponent.div_euclid(3) * 3;
+                }
+                fd.multiply_pow10(-exponent);
+                self.round(&mut fd);
+                // 9.99 -> 10.0: posun exponentu
+                let limit = if self.notation == Notation::Engineering { 3 } else { 1 };
+                if n != 0.0 && fd.nonzero_magnitude_start() >= limit {
+                    fd.multiply_pow10(-limit);
+                    exponent += limit;
+                    self.round(&mut fd);
+                }
+                fd.apply_sign_display(self.sign_display());
+                let mantissa = self.format_decimal(&fd);
+                let exponent = self.format_decimal(&FixedDecimal::from(exponent as i64));
+                format!("{mantissa}E{exponent}")
+            }
+            Notation::Standard => {
+                self.round(&mut fd);
+                fd.apply_sign_display(self.sign_display());
+                if let NumberStyle::Currency { code, display } = &self.style {
+                    return self.format_currency(&fd, code, display);
+                }
+                self.format_decimal(&fd)
+            }
+        };
+        self.decorate(&body, one)
+    }
+
+    fn format_decimal(&self, fd: &FixedDecimal) -> String {
+        decimal_formatter(&self.locale, self.grouping_strategy()).format_to_string(fd)
+    }
+
+    fn format_currency(&self, fd: &FixedDecimal, code: &str, display: &str) -> String {
+        use icu::experimental::dimension::currency::formatter::{CurrencyCode, CurrencyFormatter};
+        use icu::experimental::dimension::currency::options::{CurrencyFormatterOptions, Width};
+        let mut options = CurrencyFormatterOptions::default();
+        options.width = if display == "narrowSymbol" { Width::Narrow } else { Width::Short };
+        // CurrencyFormatter (ICU4X 0.1) formatuje cislo bez locale oddelovacu,
+        // bere se z nej jen vzor se symbolem pro jednicku; cislo dodava FixedDecimalFormatter
+        let dl = (&self.locale).into();
+        let one = FixedDecimal::from(1);
+        let pattern = match (CurrencyFormatter::try_new(&dl, options), code.parse()) {
+            (Ok(f), Ok(tiny)) => f.format_fixed_decimal(&one, CurrencyCode(tiny)).write_to_string().into_owned(),
+            _ => format!("{code}\u{a0}1"),
+        };
+        let pattern = match display {
+            "symbol" | "narrowSymbol" => pattern,
+            // code / name: kod meny na strane, kde locale dava symbol
+            _ if pattern.starts_with(|c: char| c.is_numeric()) => "1\u{a0}".to_string() + code,
+            _ => format!("{code}\u{a0}1"),
+        };
+        let number = self.format_decimal(fd);
+        if !pattern.contains('1') {
+            return format!("{number}\u{a0}{code}");
+        }
+        let (sign, digits) = match number.char_indices().find(|(_, c)| c.is_numeric()) {
+            Some((i, _)) => number.split_at(i),
+            None => ("", number.as_str()),
+        };
+        format!("{sign}{}", pattern.replacen('1', digits, 1))
+    }
+
+    /// Procenta a jednotky kolem naformatovaneho cisla.
+    fn decorate(&self, body: &str, one: bool) -> String {
+        match &self.style {
+            NumberStyle::Percent => {
+                let formatted = icu_percent(&self.locale);
+                formatted.replace('\u{0}', body)
+            }
+            NumberStyle::Unit { unit, display } => {
+                let (label, tight) = unit_label(unit, display, one);
+                if tight { format!("{body}{label}") } else { format!("{body} {label}") }
+            }
+            _ => body.to_string(),
+        }
+    }
+
+    /// Rozdeli naformatovany retezec na casti `formatToParts`.
+    fn classify(&self, s: &str) -> Parts {
+        let mut parts: Parts = Vec::new();
+        if s.contains("NaN") {
+            return vec![("nan", s.to_string())];
+        }
+        let decoration = match (&self.style, self.notation) {
+            (_, Notation::Compact { .. }) => "compact",
+            (NumberStyle::Currency { .. }, _) => "currency",
+            (NumberStyle::Unit { .. }, _) => "unit",
+            _ => "literal",
+        };
+        let scientific = matches!(self.notation, Notation::Scientific | Notation::Engineering);
+        let (mut seen_decimal, mut in_exponent, mut prev_digit) = (false, false, false);
+        let mut i = 0;
+        while i < s.len() {
+            let rest = &s[i..];
+            let c = rest.chars().next().expect("non-empty rest");
+            let next_is_digit = |skip: usize| rest[skip..].chars().next().is_some_and(|c| c.is_numeric());
+            let (kind, len): (&'static str, usize) = if c.is_numeric() {
+                (if in_exponent { "exponentInteger" } else if seen_decimal { "fraction" } else { "integer" }, c.len_utf8())
+            } else if prev_digit && !seen_decimal && !in_exponent && rest.starts_with(&self.group_sep) && next_is_digit(self.group_sep.len()) {
+                ("group", self.group_sep.len())
+            } else if prev_digit && !seen_decimal && !in_exponent && rest.starts_with(&self.decimal_sep) && next_is_digit(self.decimal_sep.len()) {
+                seen_decimal = true;
+                ("decimal", self.decimal_sep.len())
+            } else if scientific && prev_digit && c == 'E' {
+                in_exponent = true;
+                ("exponentSeparator", 1)
+            } else if matches!(c, '-' | '\u{2212}') {
+                (if in_exponent { "exponentMinusSign" } else { "minusSign" }, c.len_utf8())
+            } else if c == '+' {
+                ("plusSign", 1)
+            } else if matches!(c, '%' | '٪' | '‰') && self.style == NumberStyle::Percent {
+                ("percentSign", c.len_utf8())
+            } else if c == '∞' {
+                ("infinity", c.len_utf8())
+            } else if c.is_whitespace() || c.is_control() || c.is_ascii_punctuation() && decoration == "literal" || matches!(c, '\u{200e}' | '\u{200f}' | '\u{61c}') {
+                ("literal", c.len_utf8())
+            } else {
+                (decoration, c.len_utf8())
+            };
+            prev_digit = c.is_numeric();
+            push_part(&mut parts, kind, &rest[..len]);
+            i += len;
+        }
+        // "kilometers per hour": mezery uvnitr popisku patri k nemu
+        let mut merged: Parts = Vec::new();
+        for (kind, text) in parts {
+            let n = merged.len();
+            if n >= 2 && kind == merged[n - 2].0 && matches!(kind, "unit" | "currency") && merged[n - 1].0 == "literal" && merged[n - 1].1.trim().is_empty() {
+                let (_, space) = merged.pop().expect("n >= 2");
+                let last = merged.last_mut().expect("n >= 2");
+                last.1.push_str(&space);
+                last.1.push_str(&text);
+            } else {
+                merged.push((kind, text));
+            }
+        }
+        merged
+    }
+
+    fn format_range(&self, a: f64, b: f64) -> IResult<(String, String, String)> {
+        if a.is_nan() || b.is_nan() {
+            return Err(range_error("Invalid number value"));
+        }
+        let (x, y) = (self.format(a), self.format(b));
+        let bare = |s: &str| s.starts_with(|c: char| c.is_numeric()) && s.ends_with(|c: char| c.is_numeric());
+        let separator = if bare(&x) && bare(&y) { "–" } else { " – " };
+        Ok((x, separator.to_string(), y))
+    }
+
+    fn resolved_options(&self) -> Vec<(&'static str, JsValue)> {
+        let num = |n: u8| JsValue::Number(n as f64);
+        let mut out = vec![
+            ("locale", str_value(&self.tag)),
+            ("numberingSystem", str_value(numbering_system(&self.locale))),
+        ];
+        match &self.style {
+            NumberStyle::Decimal => out.push(("style", str_value("decimal"))),
+            NumberStyle::Percent => out.push(("style", str_value("percent"))),
+            NumberStyle::Currency { code, display } => {
+                out.push(("style", str_value("currency")));
+                out.push(("currency", str_value(code)));
+                out.push(("currencyDisplay", str_value(display)));
+                out.push(("currencySign", str_value("standard")));
+            }
+            NumberStyle::Unit { unit, display } => {
+                out.push(("style", str_value("unit")));
+                out.push(("unit", str_value(unit)));
+                out.push(("unitDisplay", str_value(display)));
+            }
+        }
+        out.push(("minimumIntegerDigits", num(self.min_int)));
+        match self.sig {
+            Some((min, max)) => {
+                out.push(("minimumSignificantDigits", num(min)));
+                out.push(("maximumSignificantDigits", num(max)));
+            }
+            None => {
+                out.push(("minimumFractionDigits", num(self.frac.0)));
+                out.push(("maximumFractionDigits", num(self.frac.1)));
+            }
+        }
+        out.push(("useGrouping", if self.grouping == "false" { JsValue::Bool(false) } else { str_value(self.grouping) }));
+        let (notation, compact) = match self.notation {
+            Notation::Standard => ("standard", None),
+            Notation::Scientific => ("scientific", None),
+            Notation::Engineering => ("engineering", None),
+            Notation::Compact { long } => ("compact", Some(if long { "long" } else { "short" })),
+        };
+        out.push(("notation", str_value(notation)));
+        out.push(("compactDisplay", opt_str(compact)));
+        out.push(("signDisplay", str_value(self.sign)));
+        out.push(("roundingMode", str_value("halfExpand")));
+        out
+    }
+}
+
+fn decimal_formatter(locale: &Locale, grouping: GroupingStrategy) -> FixedDecimalFormatter {
+    let mut options = FixedDecimalFormatterOptions::default();
+    options.grouping_strategy = grouping;
+    FixedDecimalFormatter::try_new(&locale.into(), options)
+        .or_else(|_| FixedDecimalFormatter::try_new(&icu::locid::locale!("en").into(), options))
+        .expect("compiled data contain en")
+}
+
+/// Vzor procent locale s `\0` na miste cisla ("\0 %" v cs, "\0%" v en);
+/// ICU4X 1.5 data procentnich vzoru nema.
+fn icu_percent(locale: &Locale) -> String {
+    let lang = locale.id.language.as_str();
+    match lang {
+        "cs" | "sk" | "de" | "fr" | "sv" | "nb" | "no" | "fi" | "da" | "ru" | "uk" | "pl" | "es" | "hu" | "bg" | "ro" => "\u{0}\u{a0}%".into(),
+        "tr" | "eu" => "%\u{0}".into(),
+        "ar" => "\u{0}\u{61c}%\u{61c}".into(),
+        _ => "\u{0}%".into(),
+    }
+}
+
+/// Sousedni znaky se stejnou hodnotou predikatu.
+fn char_runs(s: &str, pred: impl Fn(char) -> bool) -> Vec<(bool, String)> {
+    let mut runs: Vec<(bool, String)> = Vec::new();
+    for c in s.chars() {
+        let p = pred(c);
+        match runs.last_mut() {
+            Some((last, text)) if *last == p => text.push(c),
+            _ => runs.push((p, c.to_string())),
+        }
+    }
+    runs
+}
+
+fn number_arg(v: &JsValue) -> f64 {
+    match v {
+        JsValue::Undefined => f64::NAN,
+        JsValue::BigInt(b) => b.to_string().parse().unwrap_or(f64::NAN),
+        v => v.to_number(),
+    }
+}
+
+/// `Number.prototype.toLocaleString(locales, options)`.
+pub(crate) fn format_number(n: f64, locales: &JsValue, opts: &JsValue) -> IResult<String> {
+    Ok(NumberFormat::new(locales, opts)?.format(n))
+}
+
+fn number_format(a: &[JsValue]) -> IResult<JsValue> {
+    let nf = Rc::new(NumberFormat::new(&arg(a, 0), &arg(a, 1))?);
+    let mut inst = Instance::new("Intl.NumberFormat", &nf.tag);
+    let f = Rc::clone(&nf);
+    inst.method("format", move |a| Ok(str_value(f.format(number_arg(&arg(&a, 0))))));
+    let f = Rc::clone(&nf);
+    inst.method("formatToParts", move |a| {
+        let n = number_arg(&arg(&a, 0));
+        Ok(parts_array(f.classify(&f.format(n)), &[]))
+    });
+    let f = Rc::clone(&nf);
+    inst.method("formatRange", move |a| {
+        let (x, sep, y) = f.format_range(number_arg(&arg(&a, 0)), number_arg(&arg(&a, 1)))?;
+        Ok(str_value(if x == y { format!("~{x}") } else { format!("{x}{sep}{y}") }))
+    });
+    let f = Rc::clone(&nf);
+    inst.method("formatRangeToParts", move |a| {
+        let (x, sep, y) = f.format_range(number_arg(&arg(&a, 0)), number_arg(&arg(&a, 1)))?;
+        let mut out = Vec::new();
+        for (source, parts) in [("startRange", f.classify(&x)), ("shared", vec![("literal", sep)]), ("endRange", f.classify(&y))] {
+            if let JsValue::Array(items) = parts_array(parts, &[("source", str_value(source))]) {
+                out.extend(items.borrow().iter().cloned());
+            }
+        }
+        Ok(array(out))
+    });
+    inst.resolved(nf.resolved_options());
+    Ok(inst.finish())
+}
+
+// ─── PluralRules ─────────────────────────────────────────────────────────────
+
+fn plural_name(c: icu::plurals::PluralCategory) -> &'static str {
+    use icu::plurals::PluralCategory::*;
+    match c {
+        Zero => "zero",
+        One => "one",
+        Two => "two",
+        Few => "few",
+        Many => "many",
+        Other => "other",
+    }
+}
+
+fn plural_rules(a: &[JsValue]) -> IResult<JsValue> {
+    use icu::plurals::{PluralRuleType, PluralRulesWithRanges};
+    let o = options(&arg(a, 1))?;
+    let kind = string_option(&o, "type", &["cardinal", "ordinal"], Some("cardinal"))?.unwrap_or_default();
+    // cislo se zaokrouhluje stejne jako v NumberFormat (1.0 s minimumFractionDigits: 1 -> "other")
+    let nf = Rc::new(NumberFormat::new(&arg(a, 0), &arg(a, 1))?);
+    let rule_type = if kind == "ordinal" { PluralRuleType::Ordinal } else { PluralRuleType::Cardinal };
+    let rules = Rc::new(PluralRulesWithRanges::try_new(&(&nf.locale).into(), rule_type)
+        .map_err(|e| range_error(format_args!("No plural data for {}: {e}", nf.tag)))?);
+    let categories: Vec<JsValue> = rules.rules().categories().map(|c| str_value(plural_name(c))).collect();
+
+    let mut inst = Instance::new("Intl.PluralRules", &nf.tag);
+    let (r, f) = (Rc::clone(&rules), Rc::clone(&nf));
+    inst.method("select", move |a| {
+        let n = number_arg(&arg(&a, 0));
+        if !n.is_finite() {
+            return Ok(str_value("other"));
+        }
+        Ok(str_value(plural_name(r.rules().category_for(&f.decimal(n.abs())))))
+    });
+    let (r, f) = (Rc::clone(&rules), Rc::clone(&nf));
+    inst.method("selectRange", move |a| {
+        let (x, y) = (number_arg(&arg(&a, 0)), number_arg(&arg(&a, 1)));
+        if x.is_nan() || y.is_nan() {
+            return Err(range_error("Invalid number value"));
+        }
+        Ok(str_value(plural_name(r.category_for_range(&f.decimal(x.abs()), &f.decimal(y.abs())))))
+    });
+    let mut resolved = vec![("locale", str_value(&nf.tag)), ("type", str_value(kind))];
+    resolved.extend(nf.resolved_options().into_iter().filter(|(k, _)| k.ends_with("Digits")));
+    resolved.push(("pluralCategories", array(categories)));
+    resolved.push(("roundingMode", str_value("halfExpand")));
+    inst.resolved(resolved);
+    Ok(inst.finish())
+}
+
+// ─── DateTimeFormat ──────────────────────────────────────────────────────────
+
+/// Komponenty data a casu v poradi ECMA-402 tabulky.
+const DATE_TIME_COMPONENTS: [(&str, &[&str]); 10] = [
+    ("weekday", &["narrow", "short", "long"]),
+    ("era", &["narrow", "short", "long"]),
+    ("year", &["2-digit", "numeric"]),
+    ("month", &["2-digit", "numeric", "narrow", "short", "long"]),
+    ("day", &["2-digit", "numeric"]),
+    ("dayPeriod", &["narrow", "short", "long"]),
+    ("hour", &["2-digit", "numeric"]),
+    ("minute", &["2-digit", "numeric"]),
+    ("second", &["2-digit", "numeric"]),
+    ("timeZoneName", &["short", "long", "shortOffset", "longOffset", "shortGeneric", "longGeneric"]),
+];
+
+/// Vychozi komponenty (ToDateTimeOptions): `Format` = `Intl.DateTimeFormat`
+/// (staci jakakoli komponenta, jinak datum), ostatni pro
+/// `Date.prototype.toLocale{Date,Time,}String`.
+#[derive(Clone, Copy, PartialEq)]
+pub(crate) enum DateDefaults {
+    Format,
+    Date,
+    Time,
+    All,
+}
+
+struct DateTimeFormat {
+    locale: Locale,
+    tag: String,
+    calendar: String,
+    time_zone: TimeZone,
+    date_style: Option<String>,
+    time_style: Option<String>,
+    components: Vec<(&'static str, String)>,
+    fractional: Option<u8>,
+    hour_cycle: Option<String>,
+    formatter: Option<icu::datetime::DateTimeFormatter>,
+}
+
+impl DateTimeFormat {
+    fn new(locales: &JsValue, opts: &JsValue, defaults: DateDefaults) -> IResult<DateTimeFormat> {
+        use icu::datetime::options::{components, length, preferences};
+        let requested = resolve_locale(locales)?;
+        let o = options(opts)?;
+        let mut locale = requested.clone();
+        string_option(&o, "localeMatcher", &["lookup", "best fit"], None)?;
+        if let Some(ca) = type_option(&o, "calendar")? {
+            set_keyword(&mut locale, key!("ca"), &ca)?;
+        }
+        if let Some(nu) = type_option(&o, "numberingSystem")? {
+            set_keyword(&mut locale, key!("nu"), &nu)?;
+        }
+        let hour12 = bool_option(&o, "hour12");
+        let hour_cycle_option = string_option(&o, "hourCycle", &["h11", "h12", "h23", "h24"], None)?;
+        let time_zone = match get(&o, "timeZone") {
+            JsValue::Undefined => TimeZone::from_id(&time_zone::system_zone_id()).unwrap_or_else(|_| TimeZone::utc()),
+            v => TimeZone::from_id(&v.to_string()).map_err(|_| range_error(format_args!("Invalid time zone specified: {v}")))?,
+        };
+        let mut components: Vec<(&'static str, String)> = Vec::new();
+        for (name, allowed) in DATE_TIME_COMPONENTS {
+            if let Some(v) = string_option(&o, name, allowed, None)? {
+                components.push((name, v));
+            }
+        }
+        let fractional = digits_option(&o, "fractionalSecondDigits", 1, 3)?;
+        let styles = ["full", "long", "medium", "short"];
+        let date_style = string_option(&o, "dateStyle", &styles, None)?;
+        let time_style = string_option(&o, "timeStyle", &styles, None)?;
+        if (date_style.is_some() || time_style.is_some()) && (!components.is_empty() || fractional.is_some()) {
+            return Err(type_error("Can't set option dateStyle/timeStyle together with explicit date or time components"));
+        }
+        if date_style.is_none() && time_style.is_none() {
+            let need_date = !has_component(&components, &["weekday", "year", "month", "day"]);
+            let need_time = !has_component(&components, &["dayPeriod", "hour", "minute", "second"]) && fractional.is_none();
+            let need_defaults = match defaults {
+                DateDefaults::Date => need_date,
+                DateDefaults::Time => need_time,
+                DateDefaults::Format | DateDefaults::All => need_date && need_time,
+            };
+            if need_defaults && defaults != DateDefaults::Time {
+                components.extend(["year", "month", "day"].map(|k| (k, "numeric".to_string())));
+            }
+            if need_defaults && matches!(defaults, DateDefaults::Time | DateDefaults::All) {
+                components.extend(["hour", "minute", "second"].map(|k| (k, "numeric".to_string())));
+            }
+            components.sort_by_key(|(k, _)| DATE_TIME_COMPONENTS.iter().position(|(n, _)| n == k));
+        }
+
+        let explicit_cycle = match (hour12, hour_cycle_option.as_deref()) {
+            (Some(true), _) => Some(preferences::HourCycle::H12),
+            (Some(false), _) => Some(preferences::HourCycle::H23),
+            (None, Some("h11")) => Some(preferences::HourCycle::H11),
+            (None, Some("h12")) => Some(preferences::HourCycle::H12),
+            (None, Some("h23")) => Some(preferences::HourCycle::H23),
+            (None, Some("h24")) => Some(preferences::HourCycle::H24),
+            _ => None,
+        };
+        // length bag nema preference, hodinovy cyklus nese -u-hc
+        if let Some(hc) = explicit_cycle {
+            set_keyword(&mut locale, key!("hc"), hour_cycle_name(hc))?;
+        }
+        let dl = (&locale).into();
+        let calendar = icu::calendar::AnyCalendar::new_for_locale(&dl).kind().as_bcp47_string().to_string();
+        let shows_hour = time_style.is_some() || has_component(&components, &["hour"]);
+        // components bag bez preference pouzije h23; vychozi cyklus locale dava length vzor
+        let prefs = Some(preferences::Bag::from_hour_cycle(explicit_cycle.unwrap_or_else(|| locale_hour_cycle(&locale))));
+
+        let formatter = if date_style.is_some() || time_style.is_some() {
+            let date = date_style.as_deref().map(|s| match s {
+                "full" => length::Date::Full,
+                "long" => length::Date::Long,
+                "medium" => length::Date::Medium,
+                _ => length::Date::Short,
+            });
+            let time = time_style.as_deref().map(|s| match s {
+                "full" | "long" => length::Time::Long,
+                "medium" => length::Time::Medium,
+                _ => length::Time::Short,
+            });
+            let bag = match (date, time) {
+                (Some(d), Some(t)) => length::Bag::from_date_time_style(d, t),
+                (Some(d), None) => length::Bag::from_date_style(d),
+                (None, Some(t)) => length::Bag::from_time_style(t),
+                (None, None) => unreachable!("checked above"),
+            };
+            icu::datetime::DateTimeFormatter::try_new(&dl, bag.into()).ok()
+        } else {
+            let mut bag = components::Bag::default();
+            let text = |v: &str| match v {
+                "long" => components::Text::Long,
+                "narrow" => components::Text::Narrow,
+                _ => components::Text::Short,
+            };
+            let numeric = |v: &str| if v == "2-digit" { components::Numeric::TwoDigit } else { components::Numeric::Numeric };
+            for (k, v) in &components {
+                match *k {
+                    "weekday" => bag.weekday = Some(text(v)),
+                    "era" => bag.era = Some(text(v)),
+                    "year" => bag.year = Some(if v == "2-digit" { components::Year::TwoDigit } else { components::Year::Numeric }),
+                    "month" => bag.month = Some(match v.as_str() {
+                        "2-digit" => components::Month::TwoDigit,
+                        "long" => components::Month::Long,
+                        "short" => components::Month::Short,
+                        "narrow" => components::Month::Narrow,
+                        _ => components::Month::Numeric,
+                    }),
+                    "day" => bag.day = Some(if v == "2-digit" { components::Day::TwoDigitDayOfMonth } else { components::Day::NumericDayOfMonth }),
+                    "hour" => bag.hour = Some(numeric(v)),
+                    "minute" => bag.minute = Some(numeric(v)),
+                    "second" => bag.second = Some(numeric(v)),
+                    _ => {}
+                }
+            }
+            if fractional.is_some() && bag.second.is_none() {
+                bag.second = Some(components::Numeric::Numeric);
+            }
+            bag.fractional_second = fractional;
+            bag.preferences = prefs;
+            icu::datetime::DateTimeFormatter::try_new_experimental(&dl, icu::datetime::DateTimeFormatterOptions::Components(bag)).ok()
+        };
+
+        let mut dtf = DateTimeFormat {
+            tag: resolved_tag(&requested, &[key!("ca"), key!("hc"), key!("nu")]),
+            locale,
+            calendar,
+            time_zone,
+            date_style,
+            time_style,
+            components,
+            fractional,
+            hour_cycle: None,
+            formatter,
+        };
+        if shows_hour {
+            dtf.hour_cycle = Some(hour_cycle_name(explicit_cycle.unwrap_or_else(|| locale_hour_cycle(&dtf.locale))).into());
+        }
+        Ok(dtf)
+    }
+
+    fn time_zone_label(&self, epoch_ns: i128) -> Option<String> {
+        let (_, style) = self.components.iter().find(|(k, _)| *k == "timeZoneName")?;
+        let id = self.time_zone.id();
+        if id == "UTC" {
+            return Some("UTC".into());
+        }
+        let offset = self.time_zone.offset_ns(epoch_ns) / 60_000_000_000;
+        let sign = if offset < 0 { '-' } else { '+' };
+        let (h, m) = (offset.abs() / 60, offset.abs() % 60);
+        Some(match style.as_str() {
+            "long" | "longOffset" | "longGeneric" if offset == 0 => "GMT".into(),
+            "long" | "longOffset" | "longGeneric" => format!("GMT{sign}{h:02}:{m:02}"),
+            _ if offset == 0 => "GMT".into(),
+            _ if m == 0 => format!("GMT{sign}{h}"),
+            _ => format!("GMT{sign}{h}:{m:02}"),
+        })
+    }
+
+    /// Lokalni datum a cas bez nazvu zony.
+    fn format_local(&self, dt: IsoDateTime) -> String {
+        let formatted = self.formatter.as_ref().and_then(|f| {
+            let dl = (&self.locale).into();
+            let calendar = icu::calendar::AnyCalendar::new_for_locale(&dl);
+            let date = icu::calendar::Date::try_new_iso_date(dt.date.year, dt.date.month, dt.date.day).ok()?.to_calendar(calendar);
+            let nanos = (dt.time.millisecond as u32 * 1_000 + dt.time.microsecond as u32) * 1_000 + dt.time.nanosecond as u32;
+            let time = icu::calendar::Time::try_new(dt.time.hour, dt.time.minute, dt.time.second, nanos).ok()?;
+            f.format_to_string(&icu::calendar::DateTime::new(date, time)).ok()
+        });
+        formatted.unwrap_or_else(|| {
+            format!("{}-{:02}-{:02} {:02}:{:02}:{:02}", dt.date.year, dt.date.month, dt.date.day, dt.time.hour, dt.time.minute, dt.time.second)
+        })
+    }
+
+    fn epoch_ns(ms: f64) -> IResult<i128> {
+        if !ms.is_finite() || ms.abs() > 8.64e15 {
+            return Err(range_error("Invalid time value"));
+        }
+        Ok(ms as i128 * 1_000_000)
+    }
+
+    fn format(&self, ms: f64) -> IResult<String> {
+        let ns = Self::epoch_ns(ms)?;
+        let text = self.format_local(self.time_zone.local(ns));
+        Ok(match self.time_zone_label(ns) {
+            Some(label) => format!("{text} {label}"),
+            None => text,
+        })
+    }
+
+    /// Casti vystupu: porovnanim s formatovanim znacky 1999-11-22 13:44:55.789
+    /// a jejich variant se zmenenou jedinou slozkou. Variace dnu/mesicu/roku
+    /// zachovavaji den v tydnu (o 7 dni, o 35 tydnu, o 28 let).
+    fn parts(&self, ms: f64) -> IResult<Parts> {
+        let ns = Self::epoch_ns(ms)?;
+        let text = self.format_local(self.time_zone.local(ns));
+        let tokens = tokenize(&text);
+        let at = |y: i32, mo: u8, d: u8, h: u8, mi: u8, s: u8, milli: u16| IsoDateTime {
+            date: IsoDate { year: y, month: mo, day: d },
+            time: time_of(h, mi, s, milli),
+        };
+        let marker = tokenize(&self.format_local(at(1999, 11, 22, 13, 44, 55, 789)));
+        let mut parts: Parts = Vec::new();
+        if marker.len() == tokens.len() {
+            let variants = [
+                ("year", at(2027, 11, 22, 13, 44, 55, 789)),
+                ("month", at(1999, 3, 22, 13, 44, 55, 789)),
+                ("day", at(1999, 11, 15, 13, 44, 55, 789)),
+                ("hour", at(1999, 11, 22, 15, 44, 55, 789)),
+                ("minute", at(1999, 11, 22, 13, 45, 55, 789)),
+                ("second", at(1999, 11, 22, 13, 44, 56, 789)),
+                ("fractionalSecond", at(1999, 11, 22, 13, 44, 55, 123)),
+                ("weekday", at(1999, 11, 23, 13, 44, 55, 789)),
+                ("dayPeriod", at(1999, 11, 22, 1, 44, 55, 789)),
+            ];
+            let mut kinds: Vec<Option<&'static str>> = vec![None; tokens.len()];
+            for (kind, dt) in variants {
+                let variant = tokenize(&self.format_local(dt));
+                if variant.len() != marker.len() {
+                    continue;
+                }
+                for (i, slot) in kinds.iter_mut().enumerate() {
+                    if slot.is_none() && variant[i] != marker[i] {
+                        *slot = Some(kind);
+                    }
+                }
+            }
+            for (token, kind) in tokens.iter().zip(kinds) {
+                // era se pri zmenach slozek nemeni, pozna se podle pozadavku
+                let kind = kind.unwrap_or_else(|| {
+                    let is_era = self.components.iter().any(|(k, _)| *k == "era") && token.chars().all(char::is_alphabetic);
+                    if is_era { "era" } else { "literal" }
+                });
+                // dve sousedni ciselne casti se neslucuji
+                if kind != "literal" && parts.last().is_some_and(|(k, _)| *k == kind) {
+                    parts.push((kind, token.clone()));
+                } else {
+                    push_part(&mut parts, kind, token);
+                }
+            }
+        } else {
+            parts.push(("literal", text));
+        }
+        if let Some(label) = self.time_zone_label(ns) {
+            push_part(&mut parts, "literal", " ");
+            parts.push(("timeZoneName", label));
+        }
+        Ok(parts)
+    }
+
+    fn resolved_options(&self) -> Vec<(&'static str, JsValue)> {
+        let mut out = vec![
+            ("locale", str_value(&self.tag)),
+            ("calendar", str_value(&self.calendar)),
+            ("numberingSystem", str_value(numbering_system(&self.locale))),
+            ("timeZone", str_value(self.time_zone.id())),
+        ];
+        if let Some(hc) = &self.hour_cycle {
+            out.push(("hourCycle", str_value(hc)));
+            out.push(("hour12", JsValue::Bool(hc == "h11" || hc == "h12")));
+        }
+        for (k, v) in &self.components {
+            out.push((k, str_value(v)));
+        }
+        if let Some(f) = self.fractional {
+            out.push(("fractionalSecondDigits", JsValue::Number(f as f64)));
+        }
+        out.push(("dateStyle", opt_str(self.date_style.clone())));
+        out.push(("timeStyle", opt_str(self.time_style.clone())));
+        out
+    }
+}
+
+/// Hodinovy cyklus locale (`-u-hc`, jinak podle kratkeho casoveho vzoru:
+/// 13. hodina bud jako "13", nebo jako "1").
+fn locale_hour_cycle(locale: &Locale) -> icu::datetime::options::preferences::HourCycle {
+    use icu::datetime::options::preferences::HourCycle;
+    match keyword(locale, key!("hc")).as_deref() {
+        Some("h11") => return HourCycle::H11,
+        Some("h12") => return HourCycle::H12,
+        Some("h23") => return HourCycle::H23,
+        Some("h24") => return HourCycle::H24,
+        _ => {}
+    }
+    let formatter = icu::datetime::TimeFormatter::try_new_with_length(&locale.into(), icu::datetime::options::length::Time::Short);
+    let afternoon = icu::calendar::Time::try_new(13, 0, 0, 0).expect("valid time");
+    match formatter {
+        Ok(f) if !f.format_to_string(&afternoon).contains("13") => HourCycle::H12,
+        _ => HourCycle::H23,
+    }
+}
+
+fn has_component(components: &[(&'static str, String)], names: &[&str]) -> bool {
+    components.iter().any(|(k, _)| names.contains(k))
+}
+
+fn hour_cycle_name(hc: icu::datetime::options::preferences::HourCycle) -> &'static str {
+    use icu::datetime::options::preferences::HourCycle;
+    match hc {
+        HourCycle::H11 => "h11",
+        HourCycle::H12 => "h12",
+        HourCycle::H23 => "h23",
+        HourCycle::H24 => "h24",
+    }
+}
+
+fn time_of(hour: u8, minute: u8, second: u8, millisecond: u16) -> IsoTime {
+    IsoTime { hour, minute, second, millisecond, microsecond: 0, nanosecond: 0 }
+}
+
+/// Behy cislic, pismen a ostatnich znaku.
+fn tokenize(s: &str) -> Vec<String> {
+    let class = |c: char| if c.is_numeric() { 0 } else if c.is_alphabetic() { 1 } else { 2 };
+    let mut out: Vec<(u8, String)> = Vec::new();
+    for c in s.chars() {
+        match out.last_mut() {
+            Some((k, text)) if *k == class(c) => text.push(c),
+            _ => out.push((class(c), c.to_string())),
+        }
+    }
+    out.into_iter().map(|(_, t)| t).collect()
+}
+
+/// Cas v ms z argumentu `format` (Date, cislo, undefined = ted).
+fn date_arg(v: &JsValue) -> f64 {
+    match v {
+        JsValue::Undefined => now_ms(),
+        v => get_date_ms(v).unwrap_or_else(|| v.to_number()),
+    }
+}
+
+/// `Date.prototype.toLocale{,Date,Time}String(locales, options)`.
+pub(crate) fn format_date(ms: f64, locales: &JsValue, opts: &JsValue, defaults: DateDefaults) -> IResult<String> {
+    if ms.is_nan() {
+        return Ok("Invalid Date".into());
+    }
+    DateTimeFormat::new(locales, opts, defaults)?.format(ms)
+}
+
+fn date_time_format(a: &[JsValue]) -> IResult<JsValue> {
+    let dtf = Rc::new(DateTimeFormat::new(&arg(a, 0), &arg(a, 1), DateDefaults::Format)?);
+    let mut inst = Instance::new("Intl.DateTimeFormat", &dtf.tag);
+    let f = Rc::clone(&dtf);
+    inst.method("format", move |a| {
+        let v = arg(&a, 0);
+        if let Some(s) = super::builtins_temporal::format_for_intl(&v, &f.tag) {
+            return Ok(str_value(s));
+        }
+        Ok(str_value(f.format(date_arg(&v))?))
+    });
+    let f = Rc::clone(&dtf);
+    inst.method("formatToParts", move |a| Ok(parts_array(f.parts(date_arg(&arg(&a, 0)))?, &[])));
+    let f = Rc::clone(&dtf);
+    inst.method("formatRange", move |a| {
+        let (x, y) = (arg(&a, 0), arg(&a, 1));
+        if matches!(x, JsValue::Undefined) || matches!(y, JsValue::Undefined) {
+            return Err(type_error("startDate and endDate are required"));
+        }
+        let (x, y) = (f.format(date_arg(&x))?, f.format(date_arg(&y))?);
+        Ok(str_value(if x == y { x } else { format!("{x}\u{2009}–\u{2009}{y}") }))
+    });
+    inst.resolved(dtf.resolved_options());
+    Ok(inst.finish())
+}
+
+// ─── Collator ────────────────────────────────────────────────────────────────
+
+fn collator(a: &[JsValue]) -> IResult<JsValue> {
+    use icu::collator::{AlternateHandling, CaseFirst, CaseLevel, Collator, CollatorOptions, Numeric, Strength};
+    let requested = resolve_locale(&arg(a, 0))?;
+    let o = options(&arg(a, 1))?;
+    let mut locale = requested.clone();
+    let usage = string_option(&o, "usage", &["sort", "search"], Some("sort"))?.unwrap_or_default();
+    string_option(&o, "localeMatcher", &["lookup", "best fit"], None)?;
+    if let Some(co) = type_option(&o, "collation")? {
+        set_keyword(&mut locale, key!("co"), &co)?;
+    }
+    let numeric = bool_option(&o, "numeric")
+        .unwrap_or_else(|| keyword(&locale, key!("kn")).is_some_and(|v| v.is_empty() || v == "true"));
+    let case_first = string_option(&o, "caseFirst", &["upper", "lower", "false"], None)?
+        .or_else(|| keyword(&locale, key!("kf")))
+        .unwrap_or_else(|| "false".into());
+    let sensitivity = string_option(&o, "sensitivity", &["base", "accent", "case", "variant"], Some("variant"))?.unwrap_or_default();
+    let ignore_punctuation = bool_option(&o, "ignorePunctuation").unwrap_or(false);
+
+    let mut opts = CollatorOptions::new();
+    opts.strength = Some(match sensitivity.as_str() {
+        "base" | "case" => Strength::Primary,
+        "accent" => Strength::Secondary,
+        _ => Strength::Tertiary,
+    });
+    if sensitivity == "case" {
+        opts.case_level = Some(CaseLevel::On);
+    }
+    if ignore_punctuation {
+        opts.alternate_handling = Some(AlternateHandling::Shifted);
+    }
+    if numeric {
+        opts.numeric = Some(Numeric::On);
+    }
+    opts.case_first = Some(match case_first.as_str() {
+        "upper" => CaseFirst::UpperFirst,
+        "lower" => CaseFirst::LowerFirst,
+        _ => CaseFirst::Off,
+    });
+    let collator = Rc::new(Collator::try_new(&(&locale).into(), opts)
+        .or_else(|_| Collator::try_new(&icu::locid::locale!("und").into(), opts))
+        .map_err(|e| range_error(format_args!("No collation data: {e}")))?);
+
+    let tag = resolved_tag(&requested, &[key!("co"), key!("kn"), key!("kf")]);
+    let mut inst = Instance::new("Intl.Collator", &tag);
+    inst.method("compare", move |a| {
+        let (x, y) = (arg(&a, 0).to_string(), arg(&a, 1).to_string());
+        Ok(JsValue::Number(collator.compare(&x, &y) as i32 as f64))
+    });
+    inst.resolved(vec![
+        ("locale", str_value(tag)),
+        ("usage", str_value(usage)),
+        ("sensitivity", str_value(sensitivity)),
+        ("ignorePunctuation", JsValue::Bool(ignore_punctuation)),
+        ("collation", str_value(keyword(&locale, key!("co")).unwrap_or_else(|| "default".into()))),
+        ("numeric", JsValue::Bool(numeric)),
+        ("caseFirst", str_value(case_first)),
+    ]);
+    Ok(inst.finish())
+}
+
+// ─── Segmenter ───────────────────────────────────────────────────────────────
+
+struct Segment {
+    text: String,
+    index: usize,
+    len: usize,
+    word_like: Option<bool>,
+}
+
+/// Segmenty textu s indexy v UTF-16 jednotkach.
+fn segment_text(input: &str, granularity: &str) -> Vec<Segment> {
+    let mut breaks: Vec<(usize, Option<bool>)> = Vec::new();
+    match granularity {
+        "word" => {
+            let segmenter = icu::segmenter::WordSegmenter::new_auto();
+            let mut it = segmenter.segment_str(input);
+            while let Some(b) = it.next() {
+                breaks.push((b, Some(it.is_word_like())));
+            }
+        }
+        "sentence" => breaks.extend(icu::segmenter::SentenceSegmenter::new().segment_str(input).map(|b| (b, None))),
+        _ => breaks.extend(icu::segmenter::GraphemeClusterSegmenter::new().segment_str(input).map(|b| (b, None))),
+    }
+    let mut out = Vec::new();
+    let (mut prev, mut index16) = (0usize, 0usize);
+    for (b, word_like) in breaks {
+        if b == 0 {
+            continue;
+        }
+        let text = &input[prev..b];
+        let len = text.encode_utf16().count();
+        out.push(Segment { text: text.to_string(), index: index16, len, word_like });
+        index16 += len;
+        prev = b;
+    }
+    out
+}
+
+fn segment_data(s: &Segment, input: &str) -> JsValue {
+    let mut entries = vec![
+        ("segment", str_value(&s.text)),
+        ("index", JsValue::Number(s.index as f64)),
+        ("input", str_value(input)),
+    ];
+    if let Some(w) = s.word_like {
+        entries.push(("isWordLike", JsValue::Bool(w)));
+    }
+    plain_object(&entries)
+}
+
+fn segmenter(a: &[JsValue]) -> IResult<JsValue> {
+    let locale = resolve_locale(&arg(a, 0))?;
+    let o = options(&arg(a, 1))?;
+    string_option(&o, "localeMatcher", &["lookup", "best fit"], None)?;
+    let granularity = string_option(&o, "granularity", &["grapheme", "word", "sentence"], Some("grapheme"))?.unwrap_or_default();
+    let tag = resolved_tag(&locale, &[]);
+    let mut inst = Instance::new("Intl.Segmenter", &tag);
+    let g = granularity.clone();
+    inst.method("segment", move |a| {
+        let input = arg(&a, 0).to_string();
+        let segments = Rc::new(segment_text(&input, &g));
+        let mut obj = JsObject::new();
+        obj.set("__class_chain__".into(), str_value("Segments"));
+        let (items, text) = (Rc::clone(&segments), input.clone());
+        obj.define_hidden(symbols::ITERATOR.into(), native("Segments.prototype[Symbol.iterator]", move |_| {
+            Ok(make_array_iterator(items.iter().map(|s| segment_data(s, &text)).collect()))
+        }));
+        obj.define_hidden("containing".into(), native("Segments.prototype.containing", move |a| {
+            let i = match arg(&a, 0) {
+                JsValue::Undefined => 0.0,
+                v => v.to_number().trunc(),
+            };
+            let i = if i.is_nan() { 0.0 } else { i };
+            Ok(segments.iter()
+                .find(|s| i >= s.index as f64 && i < (s.index + s.len) as f64)
+                .map_or(JsValue::Undefined, |s| segment_data(s, &input)))
+        }));
+        Ok(JsValue::Object(Rc::new(RefCell::new(obj))))
+    });
+    inst.resolved(vec![("locale", str_value(tag)), ("granularity", str_value(granularity))]);
+    Ok(inst.finish())
+}
+
+// ─── RelativeTimeFormat ──────────────────────────────────────────────────────
+
+fn relative_unit(v: &JsValue) -> IResult<&'static str> {
+    let s = v.to_string();
+    let unit = s.strip_suffix('s').unwrap_or(&s);
+    ["second", "minute", "hour", "day", "week", "month", "quarter", "year"]
+        .into_iter()
+        .find(|u| *u == unit)
+        .ok_or_else(|| range_error(format_args!("Invalid unit argument for format() '{s}'")))
+}
+
+fn relative_formatter(locale: &Locale, style: &str, unit: &str, numeric: &str) -> IResult<icu::experimental::relativetime::RelativeTimeFormatter> {
+    use icu::experimental::relativetime::{RelativeTimeFormatter as F, RelativeTimeFormatterOptions};
+    use icu::experimental::relativetime::options::Numeric;
+    let options = RelativeTimeFormatterOptions {
+        numeric: if numeric == "auto" { Numeric::Auto } else { Numeric::Always },
+    };
+    let l = &locale.into();
+    let result = match (style, unit) {
+        ("long", "second") => F::try_new_long_second(l, options),
+        ("long", "minute") => F::try_new_long_minute(l, options),
+        ("long", "hour") => F::try_new_long_hour(l, options),
+        ("long", "day") => F::try_new_long_day(l, options),
+        ("long", "week") => F::try_new_long_week(l, options),
+        ("long", "month") => F::try_new_long_month(l, options),
+        ("long", "quarter") => F::try_new_long_quarter(l, options),
+        ("long", "year") => F::try_new_long_year(l, options),
+        ("short", "second") => F::try_new_short_second(l, options),
+        ("short", "minute") => F::try_new_short_minute(l, options),
+        ("short", "hour") => F::try_new_short_hour(l, options),
+        ("short", "day") => F::try_new_short_day(l, options),
+        ("short", "week") => F::try_new_short_week(l, options),
+        ("short", "month") => F::try_new_short_month(l, options),
+        ("short", "quarter") => F::try_new_short_quarter(l, options),
+        ("short", "year") => F::try_new_short_year(l, options),
+        (_, "second") => F::try_new_narrow_second(l, options),
+        (_, "minute") => F::try_new_narrow_minute(l, options),
+        (_, "hour") => F::try_new_narrow_hour(l, options),
+        (_, "day") => F::try_new_narrow_day(l, options),
+        (_, "week") => F::try_new_narrow_week(l, options),
+        (_, "month") => F::try_new_narrow_month(l, options),
+        (_, "quarter") => F::try_new_narrow_quarter(l, options),
+        _ => F::try_new_narrow_year(l, options),
+    };
+    result.map_err(|e| range_error(format_args!("No relative time data: {e}")))
+}
+
+fn relative_time_format(a: &[JsValue]) -> IResult<JsValue> {
+    let requested = resolve_locale(&arg(a, 0))?;
+    let o = options(&arg(a, 1))?;
+    let mut locale = requested.clone();
+    string_option(&o, "localeMatcher", &["lookup", "best fit"], None)?;
+    if let Some(nu) = type_option(&o, "numberingSystem")? {
+        set_keyword(&mut locale, key!("nu"), &nu)?;
+    }
+    let style = string_option(&o, "style", &["long", "short", "narrow"], Some("long"))?.unwrap_or_default();
+    let numeric = string_option(&o, "numeric", &["always", "auto"], Some("always"))?.unwrap_or_default();
+    let tag = resolved_tag(&requested, &[key!("nu")]);
+    let nf = Rc::new(NumberFormat::new(&str_value(locale.to_string()), &JsValue::Undefined)?);
+
+    let format = {
+        let (locale, style, numeric, nf) = (locale.clone(), style.clone(), numeric.clone(), Rc::clone(&nf));
+        move |a: &[JsValue]| -> IResult<(Parts, &'static str)> {
+            let value = arg(a, 0).to_number();
+            if !value.is_finite() {
+                return Err(range_error("Invalid number value"));
+            }
+            let unit = relative_unit(&arg(a, 1))?;
+            let formatter = relative_formatter(&locale, &style, unit, &numeric)?;
+            let mut fd = nf.decimal(value.abs());
+            if value < 0.0 || (value == 0.0 && value.is_sign_negative()) {
+                fd.set_sign(fixed_decimal::Sign::Negative);
+            }
+            Ok((collect_parts(&formatter.format(fd), ""), unit))
+        }
+    };
+    let format = Rc::new(format);
+    let mut inst = Instance::new("Intl.RelativeTimeFormat", &tag);
+    let f = Rc::clone(&format);
+    inst.method("format", move |a| {
+        let (parts, _) = f(&a)?;
+        Ok(str_value(parts.into_iter().map(|(_, t)| t).collect::<String>()))
+    });
+    inst.method("formatToParts", move |a| {
+        let (parts, unit) = format(&a)?;
+        let mut out = Vec::new();
+        for (kind, text) in parts {
+            if kind.is_empty() {
+                // cislo: integer / group / decimal / fraction s jednotkou
+                for (kind, text) in nf.classify(&text) {
+                    out.push(plain_object(&[("type", str_value(kind)), ("value", str_value(text)), ("unit", str_value(unit))]));
+                }
+            } else {
+                out.push(plain_object(&[("type", str_value("literal")), ("value", str_value(text))]));
+            }
+        }
+        Ok(array(out))
+    });
+    inst.resolved(vec![
+        ("locale", str_value(tag)),
+        ("style", str_value(style)),
+        ("numeric", str_value(numeric)),
+        ("numberingSystem", str_value(numbering_system(&locale))),
+    ]);
+    Ok(inst.finish())
+}
+
+// ─── ListFormat ──────────────────────────────────────────────────────────────
+
+fn string_list(v: &JsValue) -> IResult<Vec<String>> {
+    match v {
+        JsValue::Undefined => Ok(Vec::new()),
+        JsValue::Array(items) => items.borrow().iter().map(|item| match item {
+            JsValue::Str(s) => Ok(s.clone()),
+            other => Err(type_error(format_args!("Iterable yielded {other} which is not a string"))),
+        }).collect(),
+        JsValue::Str(s) => Ok(s.chars().map(String::from).collect()),
+        _ => Err(type_error("list must be an iterable of strings")),
+    }
+}
+
+fn list_format(a: &[JsValue]) -> IResult<JsValue> {
+    use icu::list::{ListFormatter, ListLength};
+    let locale = resolve_locale(&arg(a, 0))?;
+    let o = options(&arg(a, 1))?;
+    string_option(&o, "localeMatcher", &["lookup", "best fit"], None)?;
+    let kind = string_option(&o, "type", &["conjunction", "disjunction", "unit"], Some("conjunction"))?.unwrap_or_default();
+    let style = string_option(&o, "style", &["long", "short", "narrow"], Some("long"))?.unwrap_or_default();
+    let length = match style.as_str() {
+        "short" => ListLength::Short,
+        "narrow" => ListLength::Narrow,
+        _ => ListLength::Wide,
+    };
+    let dl = (&locale).into();
+    let formatter = match kind.as_str() {
+        "disjunction" => ListFormatter::try_new_or_with_length(&dl, length),
+        "unit" => ListFormatter::try_new_unit_with_length(&dl, length),
+        _ => ListFormatter::try_new_and_with_length(&dl, length),
+    }.map_err(|e| range_error(format_args!("No list data: {e}")))?;
+    let formatter = Rc::new(formatter);
+    let tag = resolved_tag(&locale, &[]);
+    let mut inst = Instance::new("Intl.ListFormat", &tag);
+    let f = Rc::clone(&formatter);
+    inst.method("format", move |a| {
+        let items = string_list(&arg(&a, 0))?;
+        Ok(str_value(f.format_to_string(items.iter().map(String::as_str))))
+    });
+    inst.method("formatToParts", move |a| {
+        let items = string_list(&arg(&a, 0))?;
+        Ok(parts_array(collect_parts(&formatter.format(items.iter().map(String::as_str)), "literal"), &[]))
+    });
+    inst.resolved(vec![("locale", str_value(tag)), ("type", str_value(kind)), ("style", str_value(style))]);
+    Ok(inst.finish())
+}
+
+// ─── DisplayNames ────────────────────────────────────────────────────────────
+
+const DATE_TIME_FIELDS: [&str; 12] = [
+    "era", "year", "quarter", "month", "weekOfYear", "weekday", "day", "dayPeriod", "hour", "minute", "second", "timeZoneName",
+];
+
+/// Vysledek lookupu v DisplayNames: (jmeno z dat, kanonicky kod).
+type NameLookup = dyn Fn(&str) -> IResult<(Option<String>, String)>;
+
+fn display_names(a: &[JsValue]) -> IResult<JsValue> {
+    use icu::experimental::displaynames::{
+        DisplayNamesOptions, Fallback, LanguageDisplay, LocaleDisplayNamesFormatter, RegionDisplayNames,
+        ScriptDisplayNames, Style,
+    };
+    let locale = resolve_locale(&arg(a, 0))?;
+    let o = options(&arg(a, 1))?;
+    string_option(&o, "localeMatcher", &["lookup", "best fit"], None)?;
+    let style = string_option(&o, "style", &["narrow", "short", "long"], Some("long"))?.unwrap_or_default();
+    let kind = string_option(&o, "type", &["language", "region", "script", "currency", "calendar", "dateTimeField"], None)?
+        .ok_or_else(|| type_error("Required option 'type' is missing"))?;
+    let fallback = string_option(&o, "fallback", &["code", "none"], Some("code"))?.unwrap_or_default();
+    let language_display = string_option(&o, "languageDisplay", &["dialect", "standard"], Some("dialect"))?.unwrap_or_default();
+
+    let mut opts = DisplayNamesOptions::default();
+    opts.style = Some(match style.as_str() {
+        "narrow" => Style::Narrow,
+        "short" => Style::Short,
+        _ => Style::Long,
+    });
+    opts.fallback = if fallback == "none" { Fallback::None } else { Fallback::Code };
+    opts.language_display = if language_display == "standard" { LanguageDisplay::Standard } else { LanguageDisplay::Dialect };
+    let dl = (&locale).into();
+
+    // closure vracejici jmeno (None = neni v datech) nebo RangeError pro neplatny kod
+    let lookup: Box<NameLookup> = match kind.as_str() {
+        "language" => {
+            let names = LocaleDisplayNamesFormatter::try_new(&dl, opts).map_err(range_error)?;
+            Box::new(move |code| {
+                let loc = Locale::from_str(code).ok()
+                    .filter(|l| l.extensions.is_empty())
+                    .ok_or_else(|| range_error(format_args!("invalid_argument: {code}")))?;
+                let canonical = loc.to_string();
+                let name = names.of(&loc).into_owned();
+                Ok(((name != canonical).then_some(name), canonical))
+            })
+        }
+        "region" => {
+            let names = RegionDisplayNames::try_new(&dl, opts).map_err(range_error)?;
+            Box::new(move |code| {
+                let region = icu::locid::subtags::Region::from_str(code).map_err(|_| range_error(format_args!("invalid_argument: {code}")))?;
+                Ok((names.of(region).map(str::to_string), region.to_string()))
+            })
+        }
+        "script" => {
+            let names = ScriptDisplayNames::try_new(&dl, opts).map_err(range_error)?;
+            Box::new(move |code| {
+                let script = icu::locid::subtags::Script::from_str(code).map_err(|_| range_error(format_args!("invalid_argument: {code}")))?;
+                Ok((names.of(script).map(str::to_string), script.to_string()))
+            })
+        }
+        // ICU4X 1.5 nema data pro meny, kalendare a pole -> jen validace a kod
+        "currency" => Box::new(|code| {
+            if code.len() != 3 || !code.bytes().all(|b| b.is_ascii_alphabetic()) {
+                return Err(range_error(format_args!("invalid_argument: {code}")));
+            }
+            Ok((None, code.to_ascii_uppercase()))
+        }),
+        "calendar" => Box::new(|code| {
+            let valid = code.split('-').all(|p| (3..=8).contains(&p.len()) && p.bytes().all(|b| b.is_ascii_alphanumeric()));
+            if !valid {
+                return Err(range_error(format_args!("invalid_argument: {code}")));
+            }
+            Ok((None, code.to_ascii_lowercase()))
+        }),
+        _ => Box::new(|code| {
+            if !DATE_TIME_FIELDS.contains(&code) {
+                return Err(range_error(format_args!("invalid_argument: {code}")));
+            }
+            Ok((None, code.to_string()))
+        }),
+    };
+
+    let tag = resolved_tag(&locale, &[]);
+    let mut inst = Instance::new("Intl.DisplayNames", &tag);
+    let code_fallback = fallback == "code";
+    inst.method("of", move |a| {
+        let code = match arg(&a, 0) {
+            JsValue::Undefined => return Err(type_error("invalid_argument")),
+            v => v.to_string(),
+        };
+        let (name, canonical) = lookup(&code)?;
+        Ok(match name {
+            Some(n) => str_value(n),
+            None if code_fallback => str_value(canonical),
+            None => JsValue::Undefined,
+        })
+    });
+    let mut resolved = vec![
+        ("locale", str_value(tag)),
+        ("style", str_value(style)),
+        ("type", str_value(kind.clone())),
+        ("fallback", str_value(fallback)),
+    ];
+    if kind == "language" {
+        resolved.push(("languageDisplay", str_value(language_display)));
+    }
+    inst.resolved(resolved);
+    Ok(inst.finish())
+}
+
+// ─── Locale ──────────────────────────────────────────────────────────────────
+
+fn locale_object(loc: Locale) -> JsValue {
+    let tag = loc.to_string();
+    let mut inst = Instance::new("Intl.Locale", &tag);
+    let kw = |k: Key| opt_str(keyword(&loc, k));
+    inst.field("baseName", str_value(loc.id.to_string()));
+    inst.field("language", str_value(loc.id.language.as_str()));
+    inst.field("script", opt_str(loc.id.script.map(|s| s.to_string())));
+    inst.field("region", opt_str(loc.id.region.map(|r| r.to_string())));
+    inst.field("calendar", kw(key!("ca")));
+    inst.field("collation", kw(key!("co")));
+    inst.field("hourCycle", kw(key!("hc")));
+    inst.field("caseFirst", kw(key!("kf")));
+    inst.field("numberingSystem", kw(key!("nu")));
+    inst.field("numeric", JsValue::Bool(keyword(&loc, key!("kn")).is_some_and(|v| v.is_empty() || v == "true")));
+    let t = tag.clone();
+    inst.method("toString", move |_| Ok(str_value(t.clone())));
+    let t = tag.clone();
+    inst.method("toJSON", move |_| Ok(str_value(t.clone())));
+    let l = loc.clone();
+    inst.method("maximize", move |_| {
+        let mut max = l.clone();
+        icu::locid_transform::LocaleExpander::new_extended().maximize(&mut max.id);
+        Ok(locale_object(max))
+    });
+    inst.method("minimize", move |_| {
+        let mut min = loc.clone();
+        icu::locid_transform::LocaleExpander::new_extended().minimize(&mut min.id);
+        Ok(locale_object(min))
+    });
+    inst.finish()
+}
+
+fn locale_ctor(a: &[JsValue]) -> IResult<JsValue> {
+    let mut loc = match arg(a, 0) {
+        v @ (JsValue::Str(_) | JsValue::Object(_)) => locale_of(&v)?,
+        _ => return Err(type_error("First argument to Intl.Locale constructor can't be empty or missing")),
+    };
+    let o = options(&arg(a, 1))?;
+    let subtag_error = |key: &str, v: &str| range_error(format_args!("Incorrect locale information provided: {key} {v}"));
+    if let Some(lang) = string_option(&o, "language", &[], None)? {
+        loc.id.language = lang.parse().map_err(|_| subtag_error("language", &lang))?;
+    }
+    if let Some(script) = string_option(&o, "script", &[], None)? {
+        loc.id.script = Some(script.parse().map_err(|_| subtag_error("script", &script))?);
+    }
+    if let Some(region) = string_option(&o, "region", &[], None)? {
+        loc.id.region = Some(region.parse().map_err(|_| subtag_error("region", &region))?);
+    }
+    for (name, k) in [("calendar", key!("ca")), ("collation", key!("co")), ("numberingSystem", key!("nu"))] {
+        if let Some(v) = type_option(&o, name)? {
+            set_keyword(&mut loc, k, &v)?;
+        }
+    }
+    if let Some(hc) = string_option(&o, "hourCycle", &["h11", "h12", "h23", "h24"], None)? {
+        set_keyword(&mut loc, key!("hc"), &hc)?;
+    }
+    if let Some(kf) = string_option(&o, "caseFirst", &["upper", "lower", "false"], None)? {
+        set_keyword(&mut loc, key!("kf"), &kf)?;
+    }
+    if let Some(kn) = bool_option(&o, "numeric") {
+        set_keyword(&mut loc, key!("kn"), if kn { "true" } else { "false" })?;
+    }
+    Ok(locale_object(parse_locale(&loc.to_string())?))
+}
+
+/// Retezcova podoba `Intl.Locale` pro nativni ToString (`String(locale)`).
+pub(crate) fn display_string(v: &JsValue) -> Option<String> {
+    let JsValue::Object(o) = v else { return None };
+    let o = o.borrow();
+    match (o.props.get("__class_chain__"), o.props.get(LOCALE_SLOT)) {
+        (Some(JsValue::Str(class)), Some(JsValue::Str(tag))) if class == "Intl.Locale" => Some(tag.clone()),
+        _ => None,
+    }
+}
+
+// ─── Intl.supportedValuesOf ──────────────────────────────────────────────────
+
+const CALENDARS: &[&str] = &[
+    "buddhist", "chinese", "coptic", "dangi", "ethioaa", "ethiopic", "gregory", "hebrew", "indian", "islamic",
+    "islamic-civil", "islamic-tbla", "islamic-umalqura", "iso8601", "japanese", "persian", "roc",
+];
+
+const COLLATIONS: &[&str] = &[
+    "compat", "dict", "emoji", "eor", "phonebk", "phonetic", "pinyin", "searchjl", "stroke", "trad", "unihan", "zhuyin",
+];
+
+const CURRENCIES: &[&str] = &[
+    "AED", "AFN", "ALL", "AMD", "ANG", "AOA", "ARS", "AUD", "AWG", "AZN", "BAM", "BBD", "BDT", "BGN", "BHD", "BIF",
+    "BMD", "BND", "BOB", "BRL", "BSD", "BTN", "BWP", "BYN", "BZD", "CAD", "CDF", "CHF", "CLP", "CNY", "COP", "CRC",
+    "CUP", "CVE", "CZK", "DJF", "DKK", "DOP", "DZD", "EGP", "ERN", "ETB", "EUR", "FJD", "FKP", "GBP", "GEL", "GHS",
+    "GIP", "GMD", "GNF", "GTQ", "GYD", "HKD", "HNL", "HTG", "HUF", "IDR", "ILS", "INR", "IQD", "IRR", "ISK", "JMD",
+    "JOD", "JPY", "KES", "KGS", "KHR", "KMF", "KPW", "KRW", "KWD", "KYD", "KZT", "LAK", "LBP", "LKR", "LRD", "LSL",
+    "LYD", "MAD", "MDL", "MGA", "MKD", "MMK", "MNT", "MOP", "MRU", "MUR", "MVR", "MWK", "MXN", "MYR", "MZN", "NAD",
+    "NGN", "NIO", "NOK", "NPR", "NZD", "OMR", "PAB", "PEN", "PGK", "PHP", "PKR", "PLN", "PYG", "QAR", "RON", "RSD",
+    "RUB", "RWF", "SAR", "SBD", "SCR", "SDG", "SEK", "SGD", "SHP", "SLE", "SOS", "SRD", "SSP", "STN", "SYP", "SZL",
+    "THB", "TJS", "TMT", "TND", "TOP", "TRY", "TTD", "TWD", "TZS", "UAH", "UGX", "USD", "UYU", "UZS", "VES", "VND",
+    "VUV", "WST", "XAF", "XCD", "XOF", "XPF", "YER", "ZAR", "ZMW", "ZWL",
+];
+
+const NUMBERING_SYSTEMS: &[&str] = &[
+    "adlm", "ahom", "arab", "arabext", "bali", "beng", "bhks", "brah", "cakm", "cham", "deva", "diak", "fullwide",
+    "gong", "gonm", "gujr", "guru", "hanidec", "hmng", "hmnp", "java", "kali", "kawi", "khmr", "knda", "lana",
+    "lanatham", "laoo", "latn", "lepc", "limb", "mathbold", "mathdbl", "mathmono", "mathsanb", "mathsans", "mlym",
+    "modi", "mong", "mroo", "mtei", "mymr", "mymrshan", "mymrtlng", "nagm", "newa", "nkoo", "olck", "orya", "osma",
+    "rohg", "saur", "segment", "shrd", "sind", "sinh", "sora", "sund", "takr", "talu", "tamldec", "telu", "thai",
+    "tibt", "tirh", "tnsa", "vaii", "wara", "wcho",
+];
+
+fn supported_values_of(key: &str) -> IResult<Vec<String>> {
+    let owned = |list: &[&str]| list.iter().map(|s| s.to_string()).collect();
+    Ok(match key {
+        "calendar" => owned(CALENDARS),
+        "collation" => owned(COLLATIONS),
+        "currency" => owned(CURRENCIES),
+        "numberingSystem" => owned(NUMBERING_SYSTEMS),
+        "timeZone" => time_zone::available_zone_ids(),
+        "unit" => UNITS.iter().map(|u| u.0.to_string()).collect(),
+        _ => return Err(range_error(format_args!("Invalid key : {key}"))),
+    })
+}
+
+// ─── Registrace ──────────────────────────────────────────────────────────────
+
+fn canonical_tags(v: &JsValue) -> IResult<JsValue> {
+    Ok(array(requested_locales(v)?.into_iter().map(|l| str_value(l.to_string())).collect()))
+}
+
+pub fn setup_intl(e: &mut Environment) {
+    let mut intl = JsObject::new();
+    intl.define_hidden(symbols::TO_STRING_TAG.into(), str_value("Intl"));
+    intl.set("NumberFormat".into(), native("Intl.NumberFormat", |a| number_format(&a)));
+    intl.set("DateTimeFormat".into(), native("Intl.DateTimeFormat", |a| date_time_format(&a)));
+    intl.set("Collator".into(), native("Intl.Collator", |a| collator(&a)));
+    intl.set("PluralRules".into(), native("Intl.PluralRules", |a| plural_rules(&a)));
+    intl.set("Segmenter".into(), native("Intl.Segmenter", |a| segmenter(&a)));
+    intl.set("RelativeTimeFormat".into(), native("Intl.RelativeTimeFormat", |a| relative_time_format(&a)));
+    intl.set("ListFormat".into(), native("Intl.ListFormat", |a| list_format(&a)));
+    intl.set("DisplayNames".into(), native("Intl.DisplayNames", |a| display_names(&a)));
+    intl.set("Locale".into(), native("Intl.Locale", |a| locale_ctor(&a)));
+    intl.set("getCanonicalLocales".into(), native("Intl.getCanonicalLocales", |a| canonical_tags(&arg(&a, 0))));
+    intl.set("supportedValuesOf".into(), native("Intl.supportedValuesOf", |a| {
+        Ok(array(supported_values_of(&arg(&a, 0).to_string())?.into_iter().map(str_value).collect()))
+    }));
+    e.define("Intl", JsValue::Object(Rc::new(RefCell::new(intl))));
+}
+
+/// `Intl.<Trida>.supportedLocalesOf(locales)`.
+pub(crate) fn static_prop(ctor: &str, key: &str) -> Option<JsValue> {
+    const CLASSES: [&str; 8] = [
+        "Intl.NumberFormat", "Intl.DateTimeFormat", "Intl.Collator", "Intl.PluralRules", "Intl.Segmenter",
+        "Intl.RelativeTimeFormat", "Intl.ListFormat", "Intl.DisplayNames",
+    ];
+    (key == "supportedLocalesOf" && CLASSES.contains(&ctor))
+        .then(|| native(&format!("{ctor}.supportedLocalesOf"), |a| canonical_tags(&arg(&a, 0))))
+}
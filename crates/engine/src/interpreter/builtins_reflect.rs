@@ -6,17 +6,18 @@ use std::rc::Rc;
 use std::cell::RefCell;
 use super::{JsValue, JsObject, Environment};
 use super::helpers::native;
+use super::property_map::{PropertyDescriptor, descriptor_to_js};
 
 pub fn setup_reflect(e: &mut Environment) {
     let mut refl = JsObject::new();
     refl.set("get".into(), native("Reflect.get", |a| {
         let mut iter = a.into_iter();
         let target = iter.next().unwrap_or(JsValue::Undefined);
-        let key = iter.next().map(|v| v.to_string()).unwrap_or_default();
+        let key = iter.next().map(|v| super::symbols::to_property_key(&v)).unwrap_or_default();
         match target {
             JsValue::Object(o) => Ok(o.borrow().get(&key)),
             JsValue::Array(arr) => {
-                if let Ok(i) = key.parse::<usize>() {
+                if let Some(i) = key.as_str().and_then(|k| k.parse::<usize>().ok()) {
                     Ok(arr.borrow().get(i).cloned().unwrap_or(JsValue::Undefined))
                 } else if key == "length" {
                     Ok(JsValue::Number(arr.borrow().len() as f64))
@@ -30,18 +31,18 @@ pub fn setup_reflect(e: &mut Environment) {
     refl.set("set".into(), native("Reflect.set", |a| {
         let mut iter = a.into_iter();
         let target = iter.next().unwrap_or(JsValue::Undefined);
-        let key = iter.next().map(|v| v.to_string()).unwrap_or_default();
+        let key = iter.next().map(|v| super::symbols::to_property_key(&v)).unwrap_or_default();
         let val = iter.next().unwrap_or(JsValue::Undefined);
         match target {
             JsValue::Object(o) => {
-                if !o.borrow().frozen {
+                if o.borrow().can_put(&key) {
                     o.borrow_mut().set(key, val);
                     return Ok(JsValue::Bool(true));
                 }
                 Ok(JsValue::Bool(false))
             }
             JsValue::Array(arr) => {
-                if let Ok(i) = key.parse::<usize>() {
+                if let Some(i) = key.as_str().and_then(|k| k.parse::<usize>().ok()) {
                     let mut a = arr.borrow_mut();
                     while a.len() <= i { a.push(JsValue::Undefined); }
                     a[i] = val;
@@ -55,7 +56,7 @@ pub fn setup_reflect(e: &mut Environment) {
     refl.set("has".into(), native("Reflect.has", |a| {
         let mut iter = a.into_iter();
         let target = iter.next().unwrap_or(JsValue::Undefined);
-        let key = iter.next().map(|v| v.to_string()).unwrap_or_default();
+        let key = iter.next().map(|v| super::symbols::to_property_key(&v)).unwrap_or_default();
         match target {
             JsValue::Object(o) => Ok(JsValue::Bool(o.borrow().has_own(&key)
                 || matches!(o.borrow().get(&key), v if !matches!(v, JsValue::Undefined)))),
@@ -65,11 +66,11 @@ pub fn setup_reflect(e: &mut Environment) {
     refl.set("deleteProperty".into(), native("Reflect.deleteProperty", |a| {
         let mut iter = a.into_iter();
         let target = iter.next().unwrap_or(JsValue::Undefined);
-        let key = iter.next().map(|v| v.to_string()).unwrap_or_default();
+        let key = iter.next().map(|v| super::symbols::to_property_key(&v)).unwrap_or_default();
         match target {
             JsValue::Object(o) => {
-                let removed = o.borrow_mut().props.remove(&key).is_some();
-                Ok(JsValue::Bool(removed))
+                let deleted = o.borrow_mut().delete(&key);
+                Ok(JsValue::Bool(deleted))
             }
             _ => Ok(JsValue::Bool(false)),
         }
@@ -77,13 +78,33 @@ pub fn setup_reflect(e: &mut Environment) {
     refl.set("ownKeys".into(), native("Reflect.ownKeys", |a| {
         match a.into_iter().next() {
             Some(JsValue::Object(o)) => {
-                let keys: Vec<JsValue> = o.borrow().own_keys()
-                    .into_iter().map(JsValue::Str).collect();
+                let o = o.borrow();
+                let mut keys: Vec<JsValue> = o.ordered_keys(false).into_iter().map(JsValue::Str).collect();
+                keys.extend(o.own_symbols());
                 Ok(JsValue::Array(Rc::new(RefCell::new(keys))))
             }
             _ => Ok(JsValue::Array(Rc::new(RefCell::new(vec![])))),
         }
     }));
+    refl.set("defineProperty".into(), native("Reflect.defineProperty", |a| {
+        let mut iter = a.into_iter();
+        let target = iter.next().unwrap_or(JsValue::Undefined);
+        let key = iter.next().map(|v| super::symbols::to_property_key(&v)).unwrap_or_default();
+        let desc = PropertyDescriptor::from_js(&iter.next().unwrap_or(JsValue::Undefined))?;
+        match target {
+            JsValue::Object(o) => Ok(JsValue::Bool(o.borrow_mut().define_own_property(key, desc).is_ok())),
+            _ => Err("TypeError: Reflect.defineProperty called on non-object".into()),
+        }
+    }));
+    refl.set("getOwnPropertyDescriptor".into(), native("Reflect.getOwnPropertyDescriptor", |a| {
+        let mut iter = a.into_iter();
+        let target = iter.next().unwrap_or(JsValue::Undefined);
+        let key = iter.next().map(|v| super::symbols::to_property_key(&v)).unwrap_or_default();
+        match target {
+            JsValue::Object(o) => Ok(o.borrow().props.slot(&key).map(descriptor_to_js).unwrap_or(JsValue::Undefined)),
+            _ => Err("TypeError: Reflect.getOwnPropertyDescriptor called on non-object".into()),
+        }
+    }));
     refl.set("getPrototypeOf".into(), native("Reflect.getPrototypeOf", |a| {
         match a.into_iter().next() {
             Some(JsValue::Object(o)) => Ok(match o.borrow().proto.clone() {
@@ -109,17 +130,24 @@ pub fn setup_reflect(e: &mut Environment) {
     }));
     refl.set("isExtensible".into(), native("Reflect.isExtensible", |a| {
         match a.into_iter().next() {
-            Some(JsValue::Object(o)) => Ok(JsValue::Bool(!o.borrow().frozen)),
+            Some(JsValue::Object(o)) => Ok(JsValue::Bool(o.borrow().extensible)),
             _ => Ok(JsValue::Bool(false)),
         }
     }));
     refl.set("preventExtensions".into(), native("Reflect.preventExtensions", |a| {
         let obj = a.into_iter().next().unwrap_or(JsValue::Undefined);
         if let JsValue::Object(o) = &obj {
-            o.borrow_mut().frozen = true;
+            o.borrow_mut().prevent_extensions();
             return Ok(JsValue::Bool(true));
         }
         Ok(JsValue::Bool(false))
     }));
+    // apply/construct potrebuji interpret - obsluhuje je `call_reflective` podle jmena
+    refl.set("apply".into(), native("Reflect.apply", |_| {
+        Err("TypeError: Reflect.apply requires the interpreter".into())
+    }));
+    refl.set("construct".into(), native("Reflect.construct", |_| {
+        Err("TypeError: Reflect.construct requires the interpreter".into())
+    }));
     e.define("Reflect", JsValue::Object(Rc::new(RefCell::new(refl))));
 }
@@ -1,101 +1,1818 @@
-//! Temporal API stub (TC39 Stage 3) - extracted z builtins.rs.
+//! Temporal API (TC39) - JS vrstva nad `temporal.rs`.
 //!
-//! Temporal.Now / PlainDate / PlainTime / Duration / Instant.
+//! Kazdy Temporal objekt nese interni slot `__temporal__` (druh a data
+//! serializovana do retezce, viz `Value::encode`) a `__class_chain__` pro
+//! `instanceof`. Pole jsou ne-enumerable data vlastnosti, metody per-objekt
+//! natives zachycujici Rust hodnotu (natives nevidi `this`); objekty jsou
+//! zmrazene. Staticke `from` / `compare` / `fromEpoch*` resi `static_prop`.
+//! `toLocaleString` a `Intl.DateTimeFormat#format` formatuji pres ICU4X.
 
 use std::rc::Rc;
 use std::cell::RefCell;
-use super::{JsValue, JsObject, Environment};
-use super::helpers::{native, now_ms, ms_to_parts};
+use num_bigint::BigInt;
+use super::{symbols, JsObject, JsValue, Environment};
+use super::helpers::{native, now_ms};
+use super::temporal::{
+    self as t, Calendar, Disambiguation, Duration, IsoDate, IsoDateTime, IsoTime, MonthSpec,
+    Overflow, Precision, Relative, RoundingMode, RoundingSettings, TimeZone, Unit, NS_PER_DAY,
+};
+use super::time_zone;
+
+type Obj = Rc<RefCell<JsObject>>;
+type TResult<T> = Result<T, String>;
+
+const SLOT: &str = "__temporal__";
+
+/// Hodnota Temporal objektu.
+#[derive(Debug, Clone)]
+enum Value {
+    Instant(i128),
+    Zoned(i128, TimeZone, Calendar),
+    DateTime(IsoDateTime, Calendar),
+    Date(IsoDate, Calendar),
+    Time(IsoTime),
+    YearMonth(IsoDate, Calendar),
+    MonthDay(IsoDate, Calendar),
+    Duration(Duration),
+}
+
+impl Value {
+    fn class(&self) -> &'static str {
+        match self {
+            Value::Instant(_) => "Temporal.Instant",
+            Value::Zoned(..) => "Temporal.ZonedDateTime",
+            Value::DateTime(..) => "Temporal.PlainDateTime",
+            Value::Date(..) => "Temporal.PlainDate",
+            Value::Time(_) => "Temporal.PlainTime",
+            Value::YearMonth(..) => "Temporal.PlainYearMonth",
+            Value::MonthDay(..) => "Temporal.PlainMonthDay",
+            Value::Duration(_) => "Temporal.Duration",
+        }
+    }
+
+    /// `Druh|pole|...` pro interni slot.
+    fn encode(&self) -> String {
+        let date = |d: &IsoDate| format!("{}|{}|{}", d.year, d.month, d.day);
+        let time = |t: &IsoTime| t.fields().map(|f| f.to_string()).join("|");
+        match self {
+            Value::Instant(ns) => format!("Instant|{ns}"),
+            Value::Zoned(ns, tz, cal) => format!("Zoned|{ns}|{}|{}", tz.id(), cal.id()),
+            Value::DateTime(dt, cal) => format!("DateTime|{}|{}|{}", date(&dt.date), time(&dt.time), cal.id()),
+            Value::Date(d, cal) => format!("Date|{}|{}", date(d), cal.id()),
+            Value::Time(tm) => format!("Time|{}", time(tm)),
+            Value::YearMonth(d, cal) => format!("YearMonth|{}|{}", date(d), cal.id()),
+            Value::MonthDay(d, cal) => format!("MonthDay|{}|{}", date(d), cal.id()),
+            Value::Duration(d) => format!("Duration|{}", d.0.map(|f| f.to_string()).join("|")),
+        }
+    }
+
+    fn decode(s: &str) -> Option<Value> {
+        let parts: Vec<&str> = s.split('|').collect();
+        let int = |i: usize| parts.get(i)?.parse::<i64>().ok();
+        let date = |i: usize| Some(IsoDate { year: int(i)? as i32, month: int(i + 1)? as u8, day: int(i + 2)? as u8 });
+        let time = |i: usize| IsoTime::new([int(i)?, int(i + 1)?, int(i + 2)?, int(i + 3)?, int(i + 4)?, int(i + 5)?], Overflow::Reject).ok();
+        let cal = |i: usize| Calendar::from_id(parts.get(i)?).ok();
+        Some(match *parts.first()? {
+            "Instant" => Value::Instant(parts.get(1)?.parse().ok()?),
+            "Zoned" => Value::Zoned(parts.get(1)?.parse().ok()?, TimeZone::from_id(parts.get(2)?).ok()?, cal(3)?),
+            "DateTime" => Value::DateTime(IsoDateTime { date: date(1)?, time: time(4)? }, cal(10)?),
+            "Date" => Value::Date(date(1)?, cal(4)?),
+            "Time" => Value::Time(time(1)?),
+            "YearMonth" => Value::YearMonth(date(1)?, cal(4)?),
+            "MonthDay" => Value::MonthDay(date(1)?, cal(4)?),
+            "Duration" => {
+                let mut d = Duration::ZERO;
+                for (i, slot) in d.0.iter_mut().enumerate() {
+                    *slot = parts.get(i + 1)?.parse().ok()?;
+                }
+                Value::Duration(d)
+            }
+            _ => return None,
+        })
+    }
+
+    fn calendar(&self) -> Option<Calendar> {
+        match self {
+            Value::Zoned(_, _, c) | Value::DateTime(_, c) | Value::Date(_, c) | Value::YearMonth(_, c) | Value::MonthDay(_, c) => Some(*c),
+            _ => None,
+        }
+    }
+}
+
+/// Temporal hodnota za JS objektem.
+fn temporal_of(v: &JsValue) -> Option<Value> {
+    let JsValue::Object(o) = v else { return None };
+    match o.borrow().props.get(SLOT) {
+        Some(JsValue::Str(s)) => Value::decode(s),
+        _ => None,
+    }
+}
+
+fn arg(a: &[JsValue], i: usize) -> JsValue {
+    a.get(i).cloned().unwrap_or(JsValue::Undefined)
+}
+
+fn type_error(msg: impl std::fmt::Display) -> String {
+    format!("TypeError: {msg}")
+}
+
+fn range_error(msg: impl std::fmt::Display) -> String {
+    format!("RangeError: {msg}")
+}
+
+fn bigint(ns: i128) -> JsValue {
+    JsValue::BigInt(Rc::new(BigInt::from(ns)))
+}
+
+fn epoch_ms(ns: i128) -> JsValue {
+    JsValue::Number(ns.div_euclid(1_000_000) as f64)
+}
+
+fn now_ns() -> i128 {
+    use std::time::{SystemTime, UNIX_EPOCH};
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_nanos() as i128).unwrap_or_else(|_| now_ms() as i128 * 1_000_000)
+}
+
+fn system_time_zone() -> TimeZone {
+    TimeZone::from_id(&time_zone::system_zone_id()).unwrap_or_else(|_| TimeZone::utc())
+}
+
+// ─── Volby ───────────────────────────────────────────────────────────────────
+
+/// Objekt voleb; `undefined` = zadne volby.
+fn options(v: &JsValue) -> TResult<Option<Obj>> {
+    match v {
+        JsValue::Undefined => Ok(None),
+        JsValue::Object(o) => Ok(Some(Rc::clone(o))),
+        _ => Err(type_error("options must be an object")),
+    }
+}
+
+fn get(o: &Option<Obj>, key: &str) -> JsValue {
+    o.as_ref().map_or(JsValue::Undefined, |o| o.borrow().get(key))
+}
+
+fn string_option(o: &Option<Obj>, key: &str, allowed: &[&str], default: &str) -> TResult<String> {
+    match get(o, key) {
+        JsValue::Undefined => Ok(default.to_string()),
+        v => {
+            let s = v.to_string();
+            if allowed.contains(&s.as_str()) { Ok(s) } else { Err(range_error(format_args!("{s} is not a valid value for {key}"))) }
+        }
+    }
+}
+
+fn overflow_option(o: &Option<Obj>) -> TResult<Overflow> {
+    Ok(match string_option(o, "overflow", &["constrain", "reject"], "constrain")?.as_str() {
+        "reject" => Overflow::Reject,
+        _ => Overflow::Constrain,
+    })
+}
+
+fn disambiguation_option(o: &Option<Obj>) -> TResult<Disambiguation> {
+    Ok(match string_option(o, "disambiguation", &["compatible", "earlier", "later", "reject"], "compatible")?.as_str() {
+        "earlier" => Disambiguation::Earlier,
+        "later" => Disambiguation::Later,
+        "reject" => Disambiguation::Reject,
+        _ => Disambiguation::Compatible,
+    })
+}
+
+fn offset_option(o: &Option<Obj>, default: &str) -> TResult<String> {
+    string_option(o, "offset", &["prefer", "use", "ignore", "reject"], default)
+}
+
+fn rounding_mode_option(o: &Option<Obj>, default: RoundingMode) -> TResult<RoundingMode> {
+    match get(o, "roundingMode") {
+        JsValue::Undefined => Ok(default),
+        v => RoundingMode::parse(&v.to_string()).ok_or_else(|| range_error(format_args!("{v} is not a valid value for roundingMode"))),
+    }
+}
+
+fn increment_option(o: &Option<Obj>) -> TResult<u32> {
+    match get(o, "roundingIncrement") {
+        JsValue::Undefined => Ok(1),
+        v => {
+            let n = v.to_number();
+            if !n.is_finite() || n.trunc() < 1.0 || n.trunc() > 1e9 {
+                return Err(range_error(format_args!("roundingIncrement {n} is out of range")));
+            }
+            Ok(n.trunc() as u32)
+        }
+    }
+}
+
+/// Jednotka z volby; `auto` -> None, nepovolena jednotka -> RangeError.
+fn unit_option(o: &Option<Obj>, key: &str, allowed: &[Unit], allow_auto: bool) -> TResult<Option<Unit>> {
+    match get(o, key) {
+        JsValue::Undefined => Ok(None),
+        v => {
+            let s = v.to_string();
+            if allow_auto && s == "auto" {
+                return Ok(None);
+            }
+            Unit::parse(&s).filter(|u| allowed.contains(u))
+                .map(Some)
+                .ok_or_else(|| range_error(format_args!("{s} is not a valid value for {key}")))
+        }
+    }
+}
+
+const DATE_UNITS: [Unit; 4] = [Unit::Year, Unit::Month, Unit::Week, Unit::Day];
+const TIME_UNITS: [Unit; 6] = [Unit::Hour, Unit::Minute, Unit::Second, Unit::Millisecond, Unit::Microsecond, Unit::Nanosecond];
+
+fn all_units() -> Vec<Unit> {
+    Unit::ALL.to_vec()
+}
+
+/// `roundingIncrement` musi delit nadrazenou jednotku.
+fn validate_increment(unit: Unit, increment: u32, inclusive: bool) -> TResult<()> {
+    let Some(max) = unit.max_increment() else {
+        if increment > 1 && unit == Unit::Day {
+            return Err(range_error("roundingIncrement must be 1 for days"));
+        }
+        return Ok(());
+    };
+    let too_big = if inclusive { increment > max } else { increment >= max };
+    if too_big || max % increment != 0 {
+        return Err(range_error(format_args!("roundingIncrement {increment} is invalid for {}", unit.name())));
+    }
+    Ok(())
+}
+
+/// GetDifferenceSettings pro `until` / `since`.
+fn difference_settings(opts: &JsValue, since: bool, allowed: &[Unit], default_largest: Unit, default_smallest: Unit) -> TResult<RoundingSettings> {
+    let o = options(opts)?;
+    let largest = unit_option(&o, "largestUnit", allowed, true)?;
+    let increment = increment_option(&o)?;
+    let mode = rounding_mode_option(&o, RoundingMode::Trunc)?;
+    let smallest = unit_option(&o, "smallestUnit", allowed, false)?.unwrap_or(default_smallest);
+    let largest = largest.unwrap_or(default_largest.max(smallest));
+    if largest < smallest {
+        return Err(range_error(format_args!("largestUnit {} is smaller than smallestUnit {}", largest.name(), smallest.name())));
+    }
+    if smallest < Unit::Day {
+        validate_increment(smallest, increment, false)?;
+    }
+    Ok(RoundingSettings { largest, smallest, increment, mode: if since { mode.negate() } else { mode } })
+}
+
+/// Volby `round()`: retezec = smallestUnit, jinak objekt.
+fn round_options(v: &JsValue) -> TResult<Option<Obj>> {
+    match v {
+        JsValue::Str(s) => {
+            let mut o = JsObject::new();
+            o.set("smallestUnit".into(), JsValue::Str(s.clone()));
+            Ok(Some(Rc::new(RefCell::new(o))))
+        }
+        JsValue::Undefined => Err(type_error("options parameter is required")),
+        other => options(other),
+    }
+}
+
+/// `fractionalSecondDigits` / `smallestUnit` / `roundingMode` pro `toString`:
+/// (presnost, zaokrouhlovaci krok v ns, mod).
+fn precision_options(o: &Option<Obj>) -> TResult<(Precision, i128, RoundingMode)> {
+    let digits = match get(o, "fractionalSecondDigits") {
+        JsValue::Undefined => None,
+        JsValue::Number(n) => {
+            if !n.is_finite() || n.floor() < 0.0 || n.floor() > 9.0 {
+                return Err(range_error(format_args!("fractionalSecondDigits {n} is out of range")));
+            }
+            Some(n.floor() as u8)
+        }
+        v if v.to_string() == "auto" => None,
+        v => return Err(range_error(format_args!("{v} is not a valid value for fractionalSecondDigits"))),
+    };
+    let mode = rounding_mode_option(o, RoundingMode::Trunc)?;
+    let smallest = unit_option(o, "smallestUnit", &[Unit::Minute, Unit::Second, Unit::Millisecond, Unit::Microsecond, Unit::Nanosecond], false)?;
+    let precision = match (smallest, digits) {
+        (Some(Unit::Minute), _) => return Ok((Precision::Minute, Unit::Minute.nanos(), mode)),
+        (Some(Unit::Second), _) => Precision::Digits(0),
+        (Some(Unit::Millisecond), _) => Precision::Digits(3),
+        (Some(Unit::Microsecond), _) => Precision::Digits(6),
+        (Some(_), _) => Precision::Digits(9),
+        (None, Some(n)) => Precision::Digits(n),
+        (None, None) => Precision::Auto,
+    };
+    let increment = match precision {
+        Precision::Digits(n) => 10i128.pow(9 - n as u32),
+        _ => 1,
+    };
+    Ok((precision, increment, mode))
+}
+
+fn calendar_name_option(o: &Option<Obj>) -> TResult<String> {
+    string_option(o, "calendarName", &["auto", "always", "never", "critical"], "auto")
+}
+
+// ─── Konverze argumentu ──────────────────────────────────────────────────────
+
+/// ToIntegerWithTruncation - nekonecno / NaN je RangeError.
+fn to_integer(v: &JsValue, field: &str) -> TResult<i64> {
+    let n = v.to_number();
+    if !n.is_finite() {
+        return Err(range_error(format_args!("{field} must be a finite number")));
+    }
+    Ok(n.trunc() as i64)
+}
+
+fn bag_int(o: &Obj, key: &str) -> TResult<Option<i64>> {
+    match o.borrow().get(key) {
+        JsValue::Undefined => Ok(None),
+        v => to_integer(&v, key).map(Some),
+    }
+}
+
+fn bag_string(o: &Obj, key: &str) -> Option<String> {
+    match o.borrow().get(key) {
+        JsValue::Undefined => None,
+        v => Some(v.to_string()),
+    }
+}
+
+fn to_calendar(v: &JsValue) -> TResult<Calendar> {
+    if let Some(cal) = temporal_of(v).and_then(|t| t.calendar()) {
+        return Ok(cal);
+    }
+    match v {
+        JsValue::Undefined => Ok(Calendar::Iso),
+        JsValue::Str(s) => Calendar::from_id(s).or_else(|e| match t::parse_date_time(s) {
+            Ok(p) => Calendar::from_id(p.calendar.as_deref().unwrap_or("iso8601")),
+            Err(_) => Err(e),
+        }),
+        _ => Err(type_error("calendar must be a string")),
+    }
+}
+
+fn to_time_zone(v: &JsValue) -> TResult<TimeZone> {
+    if let Some(Value::Zoned(_, tz, _)) = temporal_of(v) {
+        return Ok(tz);
+    }
+    let JsValue::Str(s) = v else { return Err(type_error("time zone must be a string")) };
+    TimeZone::from_id(s).or_else(|e| {
+        let p = t::parse_date_time(s).map_err(|_| e.clone())?;
+        match (&p.time_zone, p.z, p.offset) {
+            (Some(id), ..) => TimeZone::from_id(id),
+            (None, true, _) => Ok(TimeZone::utc()),
+            (None, false, Some((ns, false))) => Ok(TimeZone::Offset(ns)),
+            _ => Err(e),
+        }
+    })
+}
+
+/// Rok z `year` nebo `era` + `eraYear` (musi souhlasit).
+fn bag_year(o: &Obj, cal: Calendar) -> TResult<Option<i64>> {
+    let year = bag_int(o, "year")?;
+    if !cal.has_eras() {
+        return Ok(year);
+    }
+    let era = bag_string(o, "era");
+    let era_year = bag_int(o, "eraYear")?;
+    match (era, era_year) {
+        (Some(era), Some(era_year)) => {
+            let from_era = cal.year_from_era(&era, era_year)?;
+            if year.is_some_and(|y| y != from_era) {
+                return Err(range_error("year and era/eraYear do not agree"));
+            }
+            Ok(Some(from_era))
+        }
+        (None, None) => Ok(year),
+        _ => Err(type_error("era and eraYear must be provided together")),
+    }
+}
+
+fn bag_month(o: &Obj) -> TResult<MonthSpec> {
+    let ordinal = bag_int(o, "month")?;
+    if ordinal.is_some_and(|m| m < 1) {
+        return Err(range_error("month must be positive"));
+    }
+    Ok(MonthSpec { ordinal, code: bag_string(o, "monthCode") })
+}
+
+fn date_from_bag(o: &Obj, cal: Calendar, overflow: Overflow) -> TResult<IsoDate> {
+    let year = bag_year(o, cal)?.ok_or_else(|| type_error("year is required"))?;
+    let month = bag_month(o)?;
+    if month.ordinal.is_none() && month.code.is_none() {
+        return Err(type_error("month or monthCode is required"));
+    }
+    let day = bag_int(o, "day")?.ok_or_else(|| type_error("day is required"))?;
+    if day < 1 {
+        return Err(range_error("day must be positive"));
+    }
+    t::check_date(cal.date_from_fields(year, &month, day, overflow)?)
+}
+
+const TIME_FIELDS: [&str; 6] = ["hour", "minute", "second", "millisecond", "microsecond", "nanosecond"];
+
+/// Casova pole objektu; None kdyz zadne neni zadane.
+fn time_from_bag(o: &Obj, base: IsoTime, overflow: Overflow) -> TResult<Option<IsoTime>> {
+    let mut fields = base.fields();
+    let mut any = false;
+    for (i, key) in TIME_FIELDS.iter().enumerate() {
+        if let Some(v) = bag_int(o, key)? {
+            fields[i] = v;
+            any = true;
+        }
+    }
+    if !any {
+        return Ok(None);
+    }
+    IsoTime::new(fields, overflow).map(Some)
+}
+
+fn reject_z(p: &t::Parsed, s: &str) -> TResult<()> {
+    if p.z {
+        return Err(range_error(format_args!("UTC designator Z is not allowed for plain types: {s}")));
+    }
+    Ok(())
+}
+
+fn parsed_calendar(p: &t::Parsed) -> TResult<Calendar> {
+    Calendar::from_id(p.calendar.as_deref().unwrap_or("iso8601"))
+}
+
+fn to_plain_date(v: &JsValue, opts: &JsValue) -> TResult<(IsoDate, Calendar)> {
+    match (temporal_of(v), v) {
+        (Some(Value::Date(d, c)), _) => Ok((d, c)),
+        (Some(Value::DateTime(dt, c)), _) => Ok((dt.date, c)),
+        (Some(Value::Zoned(ns, tz, c)), _) => Ok((tz.local(ns).date, c)),
+        (Some(other), _) => Err(type_error(format_args!("{} cannot be converted to a PlainDate", other.class()))),
+        (None, JsValue::Object(o)) => {
+            let cal = to_calendar(&o.borrow().get("calendar"))?;
+            let overflow = overflow_option(&options(opts)?)?;
+            Ok((date_from_bag(o, cal, overflow)?, cal))
+        }
+        (None, JsValue::Str(s)) => {
+            let p = t::parse_date_time(s)?;
+            reject_z(&p, s)?;
+            overflow_option(&options(opts)?)?;
+            Ok((t::check_date(p.date().ok_or_else(|| range_error(format_args!("Invalid date string: {s}")))?)?, parsed_calendar(&p)?))
+        }
+        _ => Err(type_error("cannot convert value to a PlainDate")),
+    }
+}
+
+fn to_plain_date_time(v: &JsValue, opts: &JsValue) -> TResult<(IsoDateTime, Calendar)> {
+    match (temporal_of(v), v) {
+        (Some(Value::DateTime(dt, c)), _) => Ok((dt, c)),
+        (Some(Value::Date(d, c)), _) => Ok((IsoDateTime { date: d, time: IsoTime::MIDNIGHT }, c)),
+        (Some(Value::Zoned(ns, tz, c)), _) => Ok((tz.local(ns), c)),
+        (Some(other), _) => Err(type_error(format_args!("{} cannot be converted to a PlainDateTime", other.class()))),
+        (None, JsValue::Object(o)) => {
+            let cal = to_calendar(&o.borrow().get("calendar"))?;
+            let overflow = overflow_option(&options(opts)?)?;
+            let date = date_from_bag(o, cal, overflow)?;
+            let time = time_from_bag(o, IsoTime::MIDNIGHT, overflow)?.unwrap_or_default();
+            Ok((t::check_date_time(IsoDateTime { date, time })?, cal))
+        }
+        (None, JsValue::Str(s)) => {
+            let p = t::parse_date_time(s)?;
+            reject_z(&p, s)?;
+            overflow_option(&options(opts)?)?;
+            let date = p.date().ok_or_else(|| range_error(format_args!("Invalid date-time string: {s}")))?;
+            Ok((t::check_date_time(IsoDateTime { date, time: p.time.unwrap_or_default() })?, parsed_calendar(&p)?))
+        }
+        _ => Err(type_error("cannot convert value to a PlainDateTime")),
+    }
+}
+
+fn to_plain_time(v: &JsValue, opts: &JsValue) -> TResult<IsoTime> {
+    match (temporal_of(v), v) {
+        (Some(Value::Time(tm)), _) => Ok(tm),
+        (Some(Value::DateTime(dt, _)), _) => Ok(dt.time),
+        (Some(Value::Zoned(ns, tz, _)), _) => Ok(tz.local(ns).time),
+        (Some(other), _) => Err(type_error(format_args!("{} cannot be converted to a PlainTime", other.class()))),
+        (None, JsValue::Object(o)) => {
+            let overflow = overflow_option(&options(opts)?)?;
+            time_from_bag(o, IsoTime::MIDNIGHT, overflow)?.ok_or_else(|| type_error("at least one time field is required"))
+        }
+        (None, JsValue::Str(s)) => {
+            let p = t::parse_time(s)?;
+            reject_z(&p, s)?;
+            overflow_option(&options(opts)?)?;
+            p.time.ok_or_else(|| range_error(format_args!("Invalid time string: {s}")))
+        }
+        _ => Err(type_error("cannot convert value to a PlainTime")),
+    }
+}
+
+fn to_instant(v: &JsValue) -> TResult<i128> {
+    match (temporal_of(v), v) {
+        (Some(Value::Instant(ns)), _) | (Some(Value::Zoned(ns, ..)), _) => Ok(ns),
+        (Some(other), _) => Err(type_error(format_args!("{} cannot be converted to an Instant", other.class()))),
+        (None, JsValue::Str(s)) => {
+            let p = t::parse_date_time(s)?;
+            let offset = match (p.z, p.offset) {
+                (true, _) => 0,
+                (false, Some((ns, _))) => ns,
+                _ => return Err(range_error(format_args!("Instant string requires a UTC offset: {s}"))),
+            };
+            let date = p.date().ok_or_else(|| range_error(format_args!("Invalid instant string: {s}")))?;
+            let dt = IsoDateTime { date, time: p.time.unwrap_or_default() };
+            t::check_epoch_ns(dt.local_ns() - offset as i128)
+        }
+        _ => Err(type_error("cannot convert value to an Instant")),
+    }
+}
+
+/// ToTemporalZonedDateTime - `offset_default` je `reject` pro `from`.
+fn to_zoned(v: &JsValue, opts: &JsValue) -> TResult<(i128, TimeZone, Calendar)> {
+    match (temporal_of(v), v) {
+        (Some(Value::Zoned(ns, tz, c)), _) => {
+            let o = options(opts)?;
+            disambiguation_option(&o)?;
+            offset_option(&o, "reject")?;
+            overflow_option(&o)?;
+            Ok((ns, tz, c))
+        }
+        (Some(other), _) => Err(type_error(format_args!("{} cannot be converted to a ZonedDateTime", other.class()))),
+        (None, JsValue::Object(bag)) => {
+            let cal = to_calendar(&bag.borrow().get("calendar"))?;
+            let tz = match bag.borrow().get("timeZone") {
+                JsValue::Undefined => return Err(type_error("timeZone is required")),
+                v => to_time_zone(&v)?,
+            };
+            let offset = match bag_string(bag, "offset") {
+                Some(s) => Some(t::parse_offset(&s, true).ok_or_else(|| range_error(format_args!("Invalid offset: {s}")))?),
+                None => None,
+            };
+            let o = options(opts)?;
+            let disambiguation = disambiguation_option(&o)?;
+            let offset_mode = offset_option(&o, "reject")?;
+            let overflow = overflow_option(&o)?;
+            let date = date_from_bag(bag, cal, overflow)?;
+            let time = time_from_bag(bag, IsoTime::MIDNIGHT, overflow)?.unwrap_or_default();
+            let ns = tz.instant_with_offset(IsoDateTime { date, time }, offset, &offset_mode, disambiguation, false)?;
+            Ok((ns, tz, cal))
+        }
+        (None, JsValue::Str(s)) => {
+            let p = t::parse_date_time(s)?;
+            let tz_id = p.time_zone.clone().ok_or_else(|| range_error(format_args!("ZonedDateTime string requires a time zone annotation: {s}")))?;
+            let tz = TimeZone::from_id(&tz_id)?;
+            let cal = parsed_calendar(&p)?;
+            let o = options(opts)?;
+            let disambiguation = disambiguation_option(&o)?;
+            let offset_mode = offset_option(&o, "reject")?;
+            overflow_option(&o)?;
+            let date = p.date().ok_or_else(|| range_error(format_args!("Invalid date-time string: {s}")))?;
+            let ns = match (p.time, p.z, p.offset) {
+                (None, ..) => tz.start_of_day(date)?,
+                (Some(time), true, _) => t::check_epoch_ns(IsoDateTime { date, time }.local_ns())?,
+                (Some(time), false, offset) => {
+                    let sub_minute = offset.is_some_and(|(_, sub)| sub);
+                    tz.instant_with_offset(IsoDateTime { date, time }, offset.map(|(ns, _)| ns), &offset_mode, disambiguation, !sub_minute)?
+                }
+            };
+            Ok((ns, tz, cal))
+        }
+        _ => Err(type_error("cannot convert value to a ZonedDateTime")),
+    }
+}
+
+fn to_duration(v: &JsValue) -> TResult<Duration> {
+    match (temporal_of(v), v) {
+        (Some(Value::Duration(d)), _) => Ok(d),
+        (Some(other), _) => Err(type_error(format_args!("{} cannot be converted to a Duration", other.class()))),
+        (None, JsValue::Str(s)) => t::parse_duration(s),
+        (None, JsValue::Object(o)) => duration_from_bag(o, Duration::ZERO),
+        _ => Err(type_error("cannot convert value to a Duration")),
+    }
+}
+
+/// Pole trvani z objektu (alespon jedno), ostatni z `base`.
+fn duration_from_bag(o: &Obj, base: Duration) -> TResult<Duration> {
+    let mut d = base;
+    let mut any = false;
+    for unit in Unit::ALL {
+        if let JsValue::Undefined = o.borrow().get(&unit.plural()) {
+            continue;
+        }
+        let n = o.borrow().get(&unit.plural()).to_number();
+        if !n.is_finite() || n.fract() != 0.0 {
+            return Err(range_error(format_args!("{} must be an integer", unit.plural())));
+        }
+        d.set(unit, n + 0.0);
+        any = true;
+    }
+    if !any {
+        return Err(type_error("duration object must have at least one duration property"));
+    }
+    d.validate()
+}
+
+fn to_year_month(v: &JsValue, opts: &JsValue) -> TResult<(IsoDate, Calendar)> {
+    match (temporal_of(v), v) {
+        (Some(Value::YearMonth(d, c)), _) => Ok((d, c)),
+        (Some(other), _) => Err(type_error(format_args!("{} cannot be converted to a PlainYearMonth", other.class()))),
+        (None, JsValue::Object(o)) => {
+            let cal = to_calendar(&o.borrow().get("calendar"))?;
+            let overflow = overflow_option(&options(opts)?)?;
+            let year = bag_year(o, cal)?.ok_or_else(|| type_error("year is required"))?;
+            Ok((year_month(cal, year, &bag_month(o)?, overflow)?, cal))
+        }
+        (None, JsValue::Str(s)) => {
+            let p = t::parse_year_month(s)?;
+            reject_z(&p, s)?;
+            overflow_option(&options(opts)?)?;
+            let cal = parsed_calendar(&p)?;
+            let (year, month) = (p.year.unwrap_or(1970), p.month.unwrap_or(1));
+            let d = match p.day {
+                Some(day) => IsoDate { year, month, day },
+                None if cal == Calendar::Iso => IsoDate { year, month, day: 1 },
+                None => return Err(range_error(format_args!("year-month string with calendar {} requires a day: {s}", cal.id()))),
+            };
+            let f = cal.fields(d);
+            Ok((year_month(cal, f.year as i64, &MonthSpec { ordinal: Some(f.month as i64), code: None }, Overflow::Constrain)?, cal))
+        }
+        _ => Err(type_error("cannot convert value to a PlainYearMonth")),
+    }
+}
+
+fn year_month(cal: Calendar, year: i64, month: &MonthSpec, overflow: Overflow) -> TResult<IsoDate> {
+    let d = cal.year_month_from_fields(year, month, overflow)?;
+    // Rozsah: mesic musi zasahovat do podporovaneho rozsahu
+    let last = d.add_days(cal.fields(d).days_in_month as i64 - 1);
+    if !d.in_range() && !last.in_range() {
+        return Err(range_error("year-month outside of supported range"));
+    }
+    Ok(d)
+}
+
+fn to_month_day(v: &JsValue, opts: &JsValue) -> TResult<(IsoDate, Calendar)> {
+    match (temporal_of(v), v) {
+        (Some(Value::MonthDay(d, c)), _) => Ok((d, c)),
+        (Some(other), _) => Err(type_error(format_args!("{} cannot be converted to a PlainMonthDay", other.class()))),
+        (None, JsValue::Object(o)) => {
+            let cal_value = o.borrow().get("calendar");
+            let cal = to_calendar(&cal_value)?;
+            let overflow = overflow_option(&options(opts)?)?;
+            let month = bag_month(o)?;
+            let year = bag_year(o, cal)?;
+            if month.code.is_none() && (year.is_none() || (cal != Calendar::Iso && month.ordinal.is_some() && year.is_none())) && cal != Calendar::Iso {
+                return Err(type_error("monthCode or year and month are required"));
+            }
+            if month.code.is_none() && month.ordinal.is_none() {
+                return Err(type_error("month or monthCode is required"));
+            }
+            let day = bag_int(o, "day")?.ok_or_else(|| type_error("day is required"))?;
+            Ok((cal.month_day_from_fields(year, &month, day, overflow)?, cal))
+        }
+        (None, JsValue::Str(s)) => {
+            let p = t::parse_month_day(s)?;
+            reject_z(&p, s)?;
+            overflow_option(&options(opts)?)?;
+            let cal = parsed_calendar(&p)?;
+            let (month, day) = (p.month.unwrap_or(1), p.day.unwrap_or(1));
+            match p.year {
+                Some(year) if cal != Calendar::Iso => {
+                    let f = cal.fields(IsoDate { year, month, day });
+                    Ok((cal.month_day_from_fields(None, &MonthSpec { ordinal: None, code: Some(f.month_code) }, f.day as i64, Overflow::Constrain)?, cal))
+                }
+                None if cal != Calendar::Iso => Err(range_error(format_args!("month-day string with calendar {} requires a year: {s}", cal.id()))),
+                _ => Ok((IsoDate { year: 1972, month, day }, cal)),
+            }
+        }
+        _ => Err(type_error("cannot convert value to a PlainMonthDay")),
+    }
+}
+
+/// `relativeTo` pro Duration.round / total / compare.
+fn to_relative(v: &JsValue) -> TResult<Option<Relative>> {
+    let zoned_bag = matches!(v, JsValue::Object(o) if temporal_of(v).is_none() && !matches!(o.borrow().get("timeZone"), JsValue::Undefined));
+    let zoned_string = matches!(v, JsValue::Str(s) if t::parse_date_time(s).is_ok_and(|p| p.time_zone.is_some()));
+    match temporal_of(v) {
+        _ if matches!(v, JsValue::Undefined) => Ok(None),
+        Some(Value::Zoned(ns, tz, cal)) => Ok(Some(Relative::Zoned(ns, tz, cal))),
+        None if zoned_bag || zoned_string => {
+            let (ns, tz, cal) = to_zoned(v, &JsValue::Undefined)?;
+            Ok(Some(Relative::Zoned(ns, tz, cal)))
+        }
+        _ => {
+            let (date, cal) = to_plain_date(v, &JsValue::Undefined)?;
+            Ok(Some(Relative::Plain(IsoDateTime { date, time: IsoTime::MIDNIGHT }, cal)))
+        }
+    }
+}
+
+// ─── Objekty ─────────────────────────────────────────────────────────────────
+
+/// Temporal objekt s interni hodnotou, poli a metodami.
+fn wrap(value: Value) -> JsValue {
+    let class = value.class();
+    let mut obj = JsObject::new();
+    obj.set("__class_chain__".into(), JsValue::Str(class.into()));
+    obj.set(SLOT.into(), JsValue::Str(value.encode()));
+    obj.define_hidden(symbols::TO_STRING_TAG.into(), JsValue::Str(class.into()));
+    let mut m = Members { obj: &mut obj, class };
+    match &value {
+        Value::Instant(ns) => instant_members(&mut m, *ns),
+        Value::Zoned(ns, tz, cal) => zoned_members(&mut m, *ns, tz, *cal),
+        Value::DateTime(dt, cal) => date_time_members(&mut m, *dt, *cal),
+        Value::Date(d, cal) => date_members(&mut m, *d, *cal),
+        Value::Time(tm) => time_members(&mut m, *tm),
+        Value::YearMonth(d, cal) => year_month_members(&mut m, *d, *cal),
+        Value::MonthDay(d, cal) => month_day_members(&mut m, *d, *cal),
+        Value::Duration(d) => duration_members(&mut m, *d),
+    }
+    // valueOf a porovnani operatory: Temporal objekty nejdou prevest na cislo
+    let value_of_class = class;
+    m.method("valueOf", move |_| Err(type_error(format_args!("use compare() or equals() to compare {value_of_class}"))));
+    let primitive = value.clone();
+    m.method_sym(symbols::TO_PRIMITIVE, move |a| match arg(&a, 0).to_string().as_str() {
+        "number" => Err(type_error(format_args!("cannot convert {} to a number", primitive.class()))),
+        _ => Ok(JsValue::Str(default_string(&primitive))),
+    });
+    let json = value.clone();
+    m.method("toJSON", move |_| Ok(JsValue::Str(default_string(&json))));
+    let locale = value.clone();
+    m.method("toLocaleString", move |a| {
+        let locale_id = match arg(&a, 0) {
+            JsValue::Undefined => "en-US".to_string(),
+            JsValue::Array(items) => items.borrow().first().map_or("en-US".into(), |v| v.to_string()),
+            v => v.to_string(),
+        };
+        Ok(JsValue::Str(format_locale(&locale, &locale_id, &options(&arg(&a, 1))?)))
+    });
+    obj.freeze();
+    JsValue::Object(Rc::new(RefCell::new(obj)))
+}
+
+struct Members<'a> {
+    obj: &'a mut JsObject,
+    class: &'static str,
+}
+
+impl Members<'_> {
+    fn field(&mut self, name: &str, v: JsValue) {
+        self.obj.define_hidden(name.into(), v);
+    }
+
+    fn method(&mut self, name: &str, f: impl Fn(Vec<JsValue>) -> TResult<JsValue> + 'static) {
+        self.obj.define_hidden(name.into(), native(&format!("{}.prototype.{name}", self.class), f));
+    }
+
+    fn method_sym(&mut self, key: symbols::WellKnown, f: impl Fn(Vec<JsValue>) -> TResult<JsValue> + 'static) {
+        self.obj.define_hidden(key.into(), native(&format!("{}.prototype[Symbol.toPrimitive]", self.class), f));
+    }
+
+    /// Pole kalendarniho data (PlainDate / PlainDateTime / ZonedDateTime).
+    fn date_fields(&mut self, d: IsoDate, cal: Calendar) {
+        let f = cal.fields(d);
+        let opt_str = |v: Option<String>| v.map_or(JsValue::Undefined, JsValue::Str);
+        let opt_num = |v: Option<i32>| v.map_or(JsValue::Undefined, |n| JsValue::Number(n as f64));
+        self.field("calendarId", JsValue::Str(cal.id().into()));
+        self.field("era", opt_str(f.era.clone()));
+        self.field("eraYear", opt_num(f.era_year));
+        self.field("year", JsValue::Number(f.year as f64));
+        self.field("month", JsValue::Number(f.month as f64));
+        self.field("monthCode", JsValue::Str(f.month_code.clone()));
+        self.field("day", JsValue::Number(f.day as f64));
+        self.field("dayOfWeek", JsValue::Number(d.day_of_week() as f64));
+        let day_of_year = match cal {
+            Calendar::Iso => d.day_of_year() as i64,
+            _ => {
+                let start = cal.date_from_fields(f.year as i64, &MonthSpec { ordinal: Some(1), code: None }, 1, Overflow::Constrain);
+                start.map_or(1, |s| d.epoch_days() - s.epoch_days() + 1)
+            }
+        };
+        self.field("dayOfYear", JsValue::Number(day_of_year as f64));
+        let iso_weeks = matches!(cal, Calendar::Iso | Calendar::Icu(icu::calendar::AnyCalendarKind::Gregorian));
+        let (week, week_year) = d.week_of_year();
+        self.field("weekOfYear", if iso_weeks { JsValue::Number(week as f64) } else { JsValue::Undefined });
+        self.field("yearOfWeek", if iso_weeks { JsValue::Number(week_year as f64) } else { JsValue::Undefined });
+        self.field("daysInWeek", JsValue::Number(7.0));
+        self.field("daysInMonth", JsValue::Number(f.days_in_month as f64));
+        self.field("daysInYear", JsValue::Number(f.days_in_year as f64));
+        self.field("monthsInYear", JsValue::Number(f.months_in_year as f64));
+        self.field("inLeapYear", JsValue::Bool(f.in_leap_year));
+    }
+
+    fn time_fields(&mut self, tm: IsoTime) {
+        for (name, v) in TIME_FIELDS.iter().zip(tm.fields()) {
+            self.field(name, JsValue::Number(v as f64));
+        }
+    }
+}
+
+/// `toString()` bez voleb (toJSON, ToPrimitive).
+fn default_string(v: &Value) -> String {
+    match v {
+        Value::Instant(ns) => instant_string(*ns, None, Precision::Auto),
+        Value::Zoned(ns, tz, cal) => zoned_string(*ns, tz, *cal, Precision::Auto, "auto", "auto", "auto"),
+        Value::DateTime(dt, cal) => format!("{}{}", t::format_date_time(*dt, Precision::Auto), t::calendar_annotation(*cal, "auto")),
+        Value::Date(d, cal) => format!("{}{}", t::format_date(*d), t::calendar_annotation(*cal, "auto")),
+        Value::Time(tm) => t::format_time(*tm, Precision::Auto),
+        Value::YearMonth(d, cal) => year_month_string(*d, *cal, "auto"),
+        Value::MonthDay(d, cal) => month_day_string(*d, *cal, "auto"),
+        Value::Duration(d) => t::format_duration(d, Precision::Auto),
+    }
+}
+
+fn instant_string(ns: i128, tz: Option<&TimeZone>, precision: Precision) -> String {
+    match tz {
+        Some(tz) => {
+            let offset = tz.offset_ns(ns) as i128;
+            format!("{}{}", t::format_date_time(IsoDateTime::from_local_ns(ns + offset), precision), t::format_offset(offset, true))
+        }
+        None => format!("{}Z", t::format_date_time(IsoDateTime::from_local_ns(ns), precision)),
+    }
+}
+
+fn zoned_string(ns: i128, tz: &TimeZone, cal: Calendar, precision: Precision, calendar_name: &str, offset: &str, tz_name: &str) -> String {
+    let offset_ns = tz.offset_ns(ns) as i128;
+    let mut out = t::format_date_time(IsoDateTime::from_local_ns(ns + offset_ns), precision);
+    if offset != "never" {
+        out.push_str(&t::format_offset(offset_ns, true));
+    }
+    match tz_name {
+        "never" => {}
+        "critical" => out.push_str(&format!("[!{}]", tz.id())),
+        _ => out.push_str(&format!("[{}]", tz.id())),
+    }
+    out + &t::calendar_annotation(cal, calendar_name)
+}
+
+fn year_month_string(d: IsoDate, cal: Calendar, calendar_name: &str) -> String {
+    let base = if cal == Calendar::Iso {
+        format!("{}-{:02}", t::format_year(d.year), d.month)
+    } else {
+        t::format_date(d)
+    };
+    let always = matches!(calendar_name, "always" | "critical");
+    if cal == Calendar::Iso && always {
+        return format!("{}{}", t::format_date(d), t::calendar_annotation(cal, calendar_name));
+    }
+    base + &t::calendar_annotation(cal, calendar_name)
+}
+
+fn month_day_string(d: IsoDate, cal: Calendar, calendar_name: &str) -> String {
+    let always = matches!(calendar_name, "always" | "critical");
+    if cal == Calendar::Iso && !always {
+        return format!("{:02}-{:02}", d.month, d.day);
+    }
+    t::format_date(d) + &t::calendar_annotation(cal, calendar_name)
+}
+
+// ─── Instant ─────────────────────────────────────────────────────────────────
+
+fn instant_members(m: &mut Members, ns: i128) {
+    m.field("epochMilliseconds", epoch_ms(ns));
+    m.field("epochNanoseconds", bigint(ns));
+    for (name, negate) in [("add", false), ("subtract", true)] {
+        m.method(name, move |a| {
+            let d = to_duration(&arg(&a, 0))?;
+            let d = if negate { d.negated() } else { d };
+            if d.largest_unit() >= Unit::Day {
+                return Err(range_error("Instant arithmetic does not support years, months, weeks or days"));
+            }
+            Ok(wrap(Value::Instant(t::check_epoch_ns(ns + d.time_ns())?)))
+        });
+    }
+    for (name, since) in [("until", false), ("since", true)] {
+        m.method(name, move |a| {
+            let other = to_instant(&arg(&a, 0))?;
+            let s = difference_settings(&arg(&a, 1), since, &TIME_UNITS, Unit::Second, Unit::Nanosecond)?;
+            let d = t::diff_instant(ns, other, s);
+            Ok(wrap(Value::Duration(if since { d.negated() } else { d })))
+        });
+    }
+    m.method("round", move |a| {
+        let o = round_options(&arg(&a, 0))?;
+        let increment = increment_option(&o)?;
+        let mode = rounding_mode_option(&o, RoundingMode::HalfExpand)?;
+        let unit = unit_option(&o, "smallestUnit", &TIME_UNITS, false)?.ok_or_else(|| range_error("smallestUnit is required"))?;
+        let step = unit.nanos() * increment as i128;
+        if NS_PER_DAY % step != 0 || increment as i128 > NS_PER_DAY / unit.nanos() {
+            return Err(range_error(format_args!("roundingIncrement {increment} is invalid for {}", unit.name())));
+        }
+        Ok(wrap(Value::Instant(t::check_epoch_ns(mode.round(ns, step))?)))
+    });
+    m.method("equals", move |a| Ok(JsValue::Bool(to_instant(&arg(&a, 0))? == ns)));
+    m.method("toString", move |a| {
+        let o = options(&arg(&a, 0))?;
+        let (precision, increment, mode) = precision_options(&o)?;
+        let tz = match get(&o, "timeZone") {
+            JsValue::Undefined => None,
+            v => Some(to_time_zone(&v)?),
+        };
+        let rounded = t::check_epoch_ns(mode.round(ns, increment))?;
+        Ok(JsValue::Str(instant_string(rounded, tz.as_ref(), precision)))
+    });
+    m.method("toZonedDateTimeISO", move |a| {
+        let tz = to_time_zone(&arg(&a, 0))?;
+        Ok(wrap(Value::Zoned(ns, tz, Calendar::Iso)))
+    });
+}
+
+// ─── ZonedDateTime ───────────────────────────────────────────────────────────
+
+fn zoned_members(m: &mut Members, ns: i128, tz: &TimeZone, cal: Calendar) {
+    let local = tz.local(ns);
+    m.field("timeZoneId", JsValue::Str(tz.id()));
+    m.date_fields(local.date, cal);
+    m.time_fields(local.time);
+    let offset = tz.offset_ns(ns);
+    m.field("offsetNanoseconds", JsValue::Number(offset as f64));
+    m.field("offset", JsValue::Str(t::format_offset(offset as i128, false)));
+    m.field("epochMilliseconds", epoch_ms(ns));
+    m.field("epochNanoseconds", bigint(ns));
+    let hours = match (tz.start_of_day(local.date), tz.start_of_day(local.date.add_days(1))) {
+        (Ok(a), Ok(b)) => JsValue::Number(t::divide(b - a, Unit::Hour.nanos())),
+        _ => JsValue::Number(24.0),
+    };
+    m.field("hoursInDay", hours);
+
+    let z = tz.clone();
+    m.method("with", move |a| {
+        let JsValue::Object(bag) = arg(&a, 0) else { return Err(type_error("with() requires an object")) };
+        reject_temporal_bag(&arg(&a, 0))?;
+        let o = options(&arg(&a, 1))?;
+        let disambiguation = disambiguation_option(&o)?;
+        let offset_mode = offset_option(&o, "prefer")?;
+        let overflow = overflow_option(&o)?;
+        let local = z.local(ns);
+        let date = merge_date(&bag, local.date, cal, overflow, true)?;
+        let time = time_from_bag(&bag, local.time, overflow)?.unwrap_or(local.time);
+        let offset = match bag_string(&bag, "offset") {
+            Some(s) => t::parse_offset(&s, true).ok_or_else(|| range_error(format_args!("Invalid offset: {s}")))?,
+            None => z.offset_ns(ns),
+        };
+        let out = z.instant_with_offset(IsoDateTime { date, time }, Some(offset), &offset_mode, disambiguation, false)?;
+        Ok(wrap(Value::Zoned(out, z.clone(), cal)))
+    });
+    let z = tz.clone();
+    m.method("withPlainTime", move |a| {
+        let date = z.local(ns).date;
+        let out = match arg(&a, 0) {
+            JsValue::Undefined => z.start_of_day(date)?,
+            v => z.instant_for(IsoDateTime { date, time: to_plain_time(&v, &JsValue::Undefined)? }, Disambiguation::Compatible)?,
+        };
+        Ok(wrap(Value::Zoned(out, z.clone(), cal)))
+    });
+    m.method("withTimeZone", move |a| Ok(wrap(Value::Zoned(ns, to_time_zone(&arg(&a, 0))?, cal))));
+    let z = tz.clone();
+    m.method("withCalendar", move |a| {
+        if matches!(arg(&a, 0), JsValue::Undefined) {
+            return Err(type_error("calendar is required"));
+        }
+        Ok(wrap(Value::Zoned(ns, z.clone(), to_calendar(&arg(&a, 0))?)))
+    });
+    for (name, negate) in [("add", false), ("subtract", true)] {
+        let z = tz.clone();
+        m.method(name, move |a| {
+            let d = to_duration(&arg(&a, 0))?;
+            let d = if negate { d.negated() } else { d };
+            let overflow = overflow_option(&options(&arg(&a, 1))?)?;
+            Ok(wrap(Value::Zoned(t::add_zoned(ns, &z, cal, &d, overflow)?, z.clone(), cal)))
+        });
+    }
+    for (name, since) in [("until", false), ("since", true)] {
+        let z = tz.clone();
+        m.method(name, move |a| {
+            let (other, other_tz, other_cal) = to_zoned(&arg(&a, 0), &JsValue::Undefined)?;
+            if other_cal != cal {
+                return Err(range_error("cannot compute difference between different calendars"));
+            }
+            let s = difference_settings(&arg(&a, 1), since, &all_units(), Unit::Hour, Unit::Nanosecond)?;
+            if s.largest >= Unit::Day && !z.equals(&other_tz) {
+                return Err(range_error("cannot compute date difference between different time zones"));
+            }
+            let d = Relative::Zoned(ns, z.clone(), cal).diff_rounded(other, s)?;
+            Ok(wrap(Value::Duration(if since { d.negated() } else { d })))
+        });
+    }
+    let z = tz.clone();
+    m.method("round", move |a| {
+        let o = round_options(&arg(&a, 0))?;
+        let increment = increment_option(&o)?;
+        let mode = rounding_mode_option(&o, RoundingMode::HalfExpand)?;
+        let mut units = TIME_UNITS.to_vec();
+        units.insert(0, Unit::Day);
+        let unit = unit_option(&o, "smallestUnit", &units, false)?.ok_or_else(|| range_error("smallestUnit is required"))?;
+        validate_increment(unit, increment, true)?;
+        let local = z.local(ns);
+        let out = if unit == Unit::Day {
+            let start = z.start_of_day(local.date)?;
+            let end = z.start_of_day(local.date.add_days(1))?;
+            let len = end - start;
+            start + mode.round((ns - start) * 2, len * 2) / 2
+        } else {
+            let (days, time) = local.time.round(unit, increment, mode);
+            let dt = IsoDateTime { date: local.date.add_days(days), time };
+            z.instant_with_offset(dt, Some(z.offset_ns(ns)), "prefer", Disambiguation::Compatible, false)?
+        };
+        Ok(wrap(Value::Zoned(t::check_epoch_ns(out)?, z.clone(), cal)))
+    });
+    let z = tz.clone();
+    m.method("equals", move |a| {
+        let (other, other_tz, other_cal) = to_zoned(&arg(&a, 0), &JsValue::Undefined)?;
+        Ok(JsValue::Bool(other == ns && other_tz.equals(&z) && other_cal == cal))
+    });
+    let z = tz.clone();
+    m.method("startOfDay", move |_| Ok(wrap(Value::Zoned(z.start_of_day(z.local(ns).date)?, z.clone(), cal))));
+    let z = tz.clone();
+    m.method("getTimeZoneTransition", move |a| {
+        let direction = match arg(&a, 0) {
+            JsValue::Str(s) => s,
+            JsValue::Undefined => return Err(type_error("direction is required")),
+            v => {
+                let o = options(&v)?;
+                match get(&o, "direction") {
+                    JsValue::Undefined => return Err(range_error("direction is required")),
+                    d => d.to_string(),
+                }
+            }
+        };
+        let next = match direction.as_str() {
+            "next" => true,
+            "previous" => false,
+            other => return Err(range_error(format_args!("{other} is not a valid value for direction"))),
+        };
+        Ok(z.transition(ns, next).map_or(JsValue::Null, |at| wrap(Value::Zoned(at, z.clone(), cal))))
+    });
+    m.method("toInstant", move |_| Ok(wrap(Value::Instant(ns))));
+    m.method("toPlainDate", move |_| Ok(wrap(Value::Date(local.date, cal))));
+    m.method("toPlainTime", move |_| Ok(wrap(Value::Time(local.time))));
+    m.method("toPlainDateTime", move |_| Ok(wrap(Value::DateTime(local, cal))));
+    let z = tz.clone();
+    m.method("toString", move |a| {
+        let o = options(&arg(&a, 0))?;
+        let calendar_name = calendar_name_option(&o)?;
+        let (precision, increment, mode) = precision_options(&o)?;
+        let offset = string_option(&o, "offset", &["auto", "never"], "auto")?;
+        let tz_name = string_option(&o, "timeZoneName", &["auto", "never", "critical"], "auto")?;
+        let rounded = if increment == 1 {
+            ns
+        } else {
+            let local = z.local(ns);
+            let dt = IsoDateTime::from_local_ns(mode.round(local.local_ns(), increment));
+            z.instant_with_offset(dt, Some(z.offset_ns(ns)), "prefer", Disambiguation::Compatible, false)?
+        };
+        Ok(JsValue::Str(zoned_string(rounded, &z, cal, precision, &calendar_name, &offset, &tz_name)))
+    });
+}
+
+/// `with()` nesmi dostat Temporal objekt ani `calendar` / `timeZone`.
+fn reject_temporal_bag(v: &JsValue) -> TResult<()> {
+    if temporal_of(v).is_some() {
+        return Err(type_error("with() does not accept Temporal objects"));
+    }
+    if let JsValue::Object(o) = v {
+        let o = o.borrow();
+        if !matches!(o.get("calendar"), JsValue::Undefined) || !matches!(o.get("timeZone"), JsValue::Undefined) {
+            return Err(type_error("with() does not accept calendar or timeZone"));
+        }
+    }
+    Ok(())
+}
+
+/// Slouceni poli data s objektem (`with`). `month` bez `monthCode` nahradi
+/// puvodni kod a naopak; era + eraYear nahradi rok.
+fn merge_date(bag: &Obj, d: IsoDate, cal: Calendar, overflow: Overflow, allow_time: bool) -> TResult<IsoDate> {
+    let f = cal.fields(d);
+    let mut any = false;
+    let keys = ["year", "month", "monthCode", "day", "era", "eraYear"];
+    for key in keys.iter().chain(if allow_time { TIME_FIELDS.as_slice() } else { &[] }).chain(["offset"].iter()) {
+        if !matches!(bag.borrow().get(key), JsValue::Undefined) {
+            any = true;
+        }
+    }
+    if !any {
+        return Err(type_error("with() requires at least one recognized property"));
+    }
+    let mut year = f.year as i64;
+    if cal.has_eras() {
+        match (bag_string(bag, "era"), bag_int(bag, "eraYear")?) {
+            (Some(era), Some(era_year)) => year = cal.year_from_era(&era, era_year)?,
+            (None, None) => {}
+            _ => return Err(type_error("era and eraYear must be provided together")),
+        }
+    }
+    if let Some(y) = bag_int(bag, "year")? {
+        year = y;
+    }
+    let mut month = bag_month(bag)?;
+    if month.ordinal.is_none() && month.code.is_none() {
+        month.code = Some(f.month_code.clone());
+    }
+    let day = bag_int(bag, "day")?.unwrap_or(f.day as i64);
+    if day < 1 {
+        return Err(range_error("day must be positive"));
+    }
+    t::check_date(cal.date_from_fields(year, &month, day, overflow)?)
+}
+
+// ─── PlainDateTime ───────────────────────────────────────────────────────────
+
+fn date_time_members(m: &mut Members, dt: IsoDateTime, cal: Calendar) {
+    m.date_fields(dt.date, cal);
+    m.time_fields(dt.time);
+    m.method("with", move |a| {
+        let JsValue::Object(bag) = arg(&a, 0) else { return Err(type_error("with() requires an object")) };
+        reject_temporal_bag(&arg(&a, 0))?;
+        let overflow = overflow_option(&options(&arg(&a, 1))?)?;
+        let date = merge_date(&bag, dt.date, cal, overflow, true)?;
+        let time = time_from_bag(&bag, dt.time, overflow)?.unwrap_or(dt.time);
+        Ok(wrap(Value::DateTime(t::check_date_time(IsoDateTime { date, time })?, cal)))
+    });
+    m.method("withPlainTime", move |a| {
+        let time = match arg(&a, 0) {
+            JsValue::Undefined => IsoTime::MIDNIGHT,
+            v => to_plain_time(&v, &JsValue::Undefined)?,
+        };
+        Ok(wrap(Value::DateTime(IsoDateTime { date: dt.date, time }, cal)))
+    });
+    m.method("withCalendar", move |a| {
+        if matches!(arg(&a, 0), JsValue::Undefined) {
+            return Err(type_error("calendar is required"));
+        }
+        Ok(wrap(Value::DateTime(dt, to_calendar(&arg(&a, 0))?)))
+    });
+    for (name, negate) in [("add", false), ("subtract", true)] {
+        m.method(name, move |a| {
+            let d = to_duration(&arg(&a, 0))?;
+            let d = if negate { d.negated() } else { d };
+            let overflow = overflow_option(&options(&arg(&a, 1))?)?;
+            let (days, time) = IsoTime::from_ns(dt.time.to_ns() + d.time_ns());
+            let mut date_part = d.date_part();
+            date_part.set(Unit::Day, d.get(Unit::Day) + days as f64);
+            let date = cal.add(dt.date, &date_part, overflow)?;
+            Ok(wrap(Value::DateTime(t::check_date_time(IsoDateTime { date, time })?, cal)))
+        });
+    }
+    for (name, since) in [("until", false), ("since", true)] {
+        m.method(name, move |a| {
+            let (other, other_cal) = to_plain_date_time(&arg(&a, 0), &JsValue::Undefined)?;
+            if other_cal != cal {
+                return Err(range_error("cannot compute difference between different calendars"));
+            }
+            let s = difference_settings(&arg(&a, 1), since, &all_units(), Unit::Day, Unit::Nanosecond)?;
+            let d = Relative::Plain(dt, cal).diff_rounded(other.local_ns(), s)?;
+            Ok(wrap(Value::Duration(if since { d.negated() } else { d })))
+        });
+    }
+    m.method("round", move |a| {
+        let o = round_options(&arg(&a, 0))?;
+        let increment = increment_option(&o)?;
+        let mode = rounding_mode_option(&o, RoundingMode::HalfExpand)?;
+        let mut units = TIME_UNITS.to_vec();
+        units.insert(0, Unit::Day);
+        let unit = unit_option(&o, "smallestUnit", &units, false)?.ok_or_else(|| range_error("smallestUnit is required"))?;
+        validate_increment(unit, increment, true)?;
+        let (days, time) = dt.time.round(unit, increment, mode);
+        Ok(wrap(Value::DateTime(t::check_date_time(IsoDateTime { date: dt.date.add_days(days), time })?, cal)))
+    });
+    m.method("equals", move |a| {
+        let (other, other_cal) = to_plain_date_time(&arg(&a, 0), &JsValue::Undefined)?;
+        Ok(JsValue::Bool(other == dt && other_cal == cal))
+    });
+    m.method("toPlainDate", move |_| Ok(wrap(Value::Date(dt.date, cal))));
+    m.method("toPlainTime", move |_| Ok(wrap(Value::Time(dt.time))));
+    m.method("toZonedDateTime", move |a| {
+        let tz = to_time_zone(&arg(&a, 0))?;
+        let disambiguation = disambiguation_option(&options(&arg(&a, 1))?)?;
+        Ok(wrap(Value::Zoned(tz.instant_for(dt, disambiguation)?, tz, cal)))
+    });
+    m.method("toString", move |a| {
+        let o = options(&arg(&a, 0))?;
+        let calendar_name = calendar_name_option(&o)?;
+        let (precision, increment, mode) = precision_options(&o)?;
+        let rounded = t::check_date_time(IsoDateTime::from_local_ns(mode.round(dt.local_ns(), increment)))?;
+        Ok(JsValue::Str(format!("{}{}", t::format_date_time(rounded, precision), t::calendar_annotation(cal, &calendar_name))))
+    });
+}
+
+// ─── PlainDate ───────────────────────────────────────────────────────────────
+
+fn date_members(m: &mut Members, d: IsoDate, cal: Calendar) {
+    m.date_fields(d, cal);
+    m.method("with", move |a| {
+        let JsValue::Object(bag) = arg(&a, 0) else { return Err(type_error("with() requires an object")) };
+        reject_temporal_bag(&arg(&a, 0))?;
+        let overflow = overflow_option(&options(&arg(&a, 1))?)?;
+        Ok(wrap(Value::Date(merge_date(&bag, d, cal, overflow, false)?, cal)))
+    });
+    m.method("withCalendar", move |a| {
+        if matches!(arg(&a, 0), JsValue::Undefined) {
+            return Err(type_error("calendar is required"));
+        }
+        Ok(wrap(Value::Date(d, to_calendar(&arg(&a, 0))?)))
+    });
+    for (name, negate) in [("add", false), ("subtract", true)] {
+        m.method(name, move |a| {
+            let dur = to_duration(&arg(&a, 0))?;
+            let dur = if negate { dur.negated() } else { dur };
+            let overflow = overflow_option(&options(&arg(&a, 1))?)?;
+            // Casove jednotky se prevedou na cele dny
+            let mut date_part = dur.date_part();
+            date_part.set(Unit::Day, dur.get(Unit::Day) + (dur.time_ns() / NS_PER_DAY) as f64);
+            Ok(wrap(Value::Date(cal.add(d, &date_part, overflow)?, cal)))
+        });
+    }
+    for (name, since) in [("until", false), ("since", true)] {
+        m.method(name, move |a| {
+            let (other, other_cal) = to_plain_date(&arg(&a, 0), &JsValue::Undefined)?;
+            if other_cal != cal {
+                return Err(range_error("cannot compute difference between different calendars"));
+            }
+            let s = difference_settings(&arg(&a, 1), since, &DATE_UNITS, Unit::Day, Unit::Day)?;
+            let start = IsoDateTime { date: d, time: IsoTime::MIDNIGHT };
+            let end = IsoDateTime { date: other, time: IsoTime::MIDNIGHT };
+            let diff = Relative::Plain(start, cal).diff_rounded(end.local_ns(), s)?;
+            Ok(wrap(Value::Duration(if since { diff.negated() } else { diff })))
+        });
+    }
+    m.method("equals", move |a| {
+        let (other, other_cal) = to_plain_date(&arg(&a, 0), &JsValue::Undefined)?;
+        Ok(JsValue::Bool(other == d && other_cal == cal))
+    });
+    m.method("toPlainDateTime", move |a| {
+        let time = match arg(&a, 0) {
+            JsValue::Undefined => IsoTime::MIDNIGHT,
+            v => to_plain_time(&v, &JsValue::Undefined)?,
+        };
+        Ok(wrap(Value::DateTime(t::check_date_time(IsoDateTime { date: d, time })?, cal)))
+    });
+    m.method("toZonedDateTime", move |a| {
+        let item = arg(&a, 0);
+        let bag = match &item {
+            JsValue::Object(o) if temporal_of(&item).is_none() => Some(Rc::clone(o)),
+            _ => None,
+        };
+        let (tz, time) = match bag {
+            Some(o) => {
+                let tz = to_time_zone(&o.borrow().get("timeZone"))?;
+                let time = match o.borrow().get("plainTime") {
+                    JsValue::Undefined => None,
+                    v => Some(to_plain_time(&v, &JsValue::Undefined)?),
+                };
+                (tz, time)
+            }
+            None => (to_time_zone(&item)?, None),
+        };
+        let ns = match time {
+            Some(time) => tz.instant_for(IsoDateTime { date: d, time }, Disambiguation::Compatible)?,
+            None => tz.start_of_day(d)?,
+        };
+        Ok(wrap(Value::Zoned(ns, tz, cal)))
+    });
+    m.method("toPlainYearMonth", move |_| {
+        let f = cal.fields(d);
+        Ok(wrap(Value::YearMonth(year_month(cal, f.year as i64, &MonthSpec { ordinal: Some(f.month as i64), code: None }, Overflow::Constrain)?, cal)))
+    });
+    m.method("toPlainMonthDay", move |_| {
+        let f = cal.fields(d);
+        let md = cal.month_day_from_fields(None, &MonthSpec { ordinal: None, code: Some(f.month_code) }, f.day as i64, Overflow::Constrain)?;
+        Ok(wrap(Value::MonthDay(md, cal)))
+    });
+    m.method("toString", move |a| {
+        let calendar_name = calendar_name_option(&options(&arg(&a, 0))?)?;
+        Ok(JsValue::Str(format!("{}{}", t::format_date(d), t::calendar_annotation(cal, &calendar_name))))
+    });
+}
+
+// ─── PlainTime ───────────────────────────────────────────────────────────────
+
+fn time_members(m: &mut Members, tm: IsoTime) {
+    m.time_fields(tm);
+    m.method("with", move |a| {
+        let JsValue::Object(bag) = arg(&a, 0) else { return Err(type_error("with() requires an object")) };
+        reject_temporal_bag(&arg(&a, 0))?;
+        let overflow = overflow_option(&options(&arg(&a, 1))?)?;
+        let out = time_from_bag(&bag, tm, overflow)?.ok_or_else(|| type_error("with() requires at least one time property"))?;
+        Ok(wrap(Value::Time(out)))
+    });
+    for (name, negate) in [("add", false), ("subtract", true)] {
+        m.method(name, move |a| {
+            let d = to_duration(&arg(&a, 0))?;
+            let d = if negate { d.negated() } else { d };
+            // Cas se zalamuje pres pulnoc, dny se ignoruji
+            Ok(wrap(Value::Time(IsoTime::from_ns(tm.to_ns() + d.time_ns()).1)))
+        });
+    }
+    for (name, since) in [("until", false), ("since", true)] {
+        m.method(name, move |a| {
+            let other = to_plain_time(&arg(&a, 0), &JsValue::Undefined)?;
+            let s = difference_settings(&arg(&a, 1), since, &TIME_UNITS, Unit::Hour, Unit::Nanosecond)?;
+            let d = t::diff_instant(tm.to_ns(), other.to_ns(), s);
+            Ok(wrap(Value::Duration(if since { d.negated() } else { d })))
+        });
+    }
+    m.method("round", move |a| {
+        let o = round_options(&arg(&a, 0))?;
+        let increment = increment_option(&o)?;
+        let mode = rounding_mode_option(&o, RoundingMode::HalfExpand)?;
+        let unit = unit_option(&o, "smallestUnit", &TIME_UNITS, false)?.ok_or_else(|| range_error("smallestUnit is required"))?;
+        validate_increment(unit, increment, false)?;
+        Ok(wrap(Value::Time(tm.round(unit, increment, mode).1)))
+    });
+    m.method("equals", move |a| Ok(JsValue::Bool(to_plain_time(&arg(&a, 0), &JsValue::Undefined)? == tm)));
+    m.method("toString", move |a| {
+        let (precision, increment, mode) = precision_options(&options(&arg(&a, 0))?)?;
+        let rounded = IsoTime::from_ns(mode.round(tm.to_ns(), increment)).1;
+        Ok(JsValue::Str(t::format_time(rounded, precision)))
+    });
+}
+
+// ─── PlainYearMonth / PlainMonthDay ──────────────────────────────────────────
+
+fn year_month_members(m: &mut Members, d: IsoDate, cal: Calendar) {
+    let f = cal.fields(d);
+    m.field("calendarId", JsValue::Str(cal.id().into()));
+    m.field("era", f.era.clone().map_or(JsValue::Undefined, JsValue::Str));
+    m.field("eraYear", f.era_year.map_or(JsValue::Undefined, |y| JsValue::Number(y as f64)));
+    m.field("year", JsValue::Number(f.year as f64));
+    m.field("month", JsValue::Number(f.month as f64));
+    m.field("monthCode", JsValue::Str(f.month_code.clone()));
+    m.field("daysInMonth", JsValue::Number(f.days_in_month as f64));
+    m.field("daysInYear", JsValue::Number(f.days_in_year as f64));
+    m.field("monthsInYear", JsValue::Number(f.months_in_year as f64));
+    m.field("inLeapYear", JsValue::Bool(f.in_leap_year));
+    m.method("with", move |a| {
+        let JsValue::Object(bag) = arg(&a, 0) else { return Err(type_error("with() requires an object")) };
+        reject_temporal_bag(&arg(&a, 0))?;
+        if !matches!(bag.borrow().get("day"), JsValue::Undefined) && ["year", "month", "monthCode", "era", "eraYear"].iter().all(|k| matches!(bag.borrow().get(k), JsValue::Undefined)) {
+            return Err(type_error("with() requires at least one recognized property"));
+        }
+        let overflow = overflow_option(&options(&arg(&a, 1))?)?;
+        let merged = merge_date(&bag, d, cal, overflow, false)?;
+        let f = cal.fields(merged);
+        Ok(wrap(Value::YearMonth(year_month(cal, f.year as i64, &MonthSpec { ordinal: Some(f.month as i64), code: None }, overflow)?, cal)))
+    });
+    for (name, negate) in [("add", false), ("subtract", true)] {
+        m.method(name, move |a| {
+            let dur = to_duration(&arg(&a, 0))?;
+            let dur = if negate { dur.negated() } else { dur };
+            let overflow = overflow_option(&options(&arg(&a, 1))?)?;
+            if [Unit::Week, Unit::Day].iter().any(|u| dur.get(*u) != 0.0) || dur.time_ns() != 0 {
+                return Err(range_error("PlainYearMonth arithmetic supports only years and months"));
+            }
+            let mut ym = Duration::ZERO;
+            ym.set(Unit::Year, dur.get(Unit::Year));
+            ym.set(Unit::Month, dur.get(Unit::Month));
+            let moved = cal.add(d, &ym, overflow)?;
+            let f = cal.fields(moved);
+            Ok(wrap(Value::YearMonth(year_month(cal, f.year as i64, &MonthSpec { ordinal: Some(f.month as i64), code: None }, overflow)?, cal)))
+        });
+    }
+    for (name, since) in [("until", false), ("since", true)] {
+        m.method(name, move |a| {
+            let (other, other_cal) = to_year_month(&arg(&a, 0), &JsValue::Undefined)?;
+            if other_cal != cal {
+                return Err(range_error("cannot compute difference between different calendars"));
+            }
+            let s = difference_settings(&arg(&a, 1), since, &[Unit::Year, Unit::Month], Unit::Year, Unit::Month)?;
+            let start = IsoDateTime { date: d, time: IsoTime::MIDNIGHT };
+            let end = IsoDateTime { date: other, time: IsoTime::MIDNIGHT };
+            let diff = Relative::Plain(start, cal).diff_rounded(end.local_ns(), s)?;
+            Ok(wrap(Value::Duration(if since { diff.negated() } else { diff })))
+        });
+    }
+    m.method("equals", move |a| {
+        let (other, other_cal) = to_year_month(&arg(&a, 0), &JsValue::Undefined)?;
+        Ok(JsValue::Bool(other == d && other_cal == cal))
+    });
+    m.method("toPlainDate", move |a| {
+        let JsValue::Object(bag) = arg(&a, 0) else { return Err(type_error("toPlainDate() requires an object with day")) };
+        let day = bag_int(&bag, "day")?.ok_or_else(|| type_error("day is required"))?;
+        let f = cal.fields(d);
+        let date = cal.date_from_fields(f.year as i64, &MonthSpec { ordinal: Some(f.month as i64), code: None }, day, Overflow::Constrain)?;
+        Ok(wrap(Value::Date(t::check_date(date)?, cal)))
+    });
+    m.method("toString", move |a| {
+        let calendar_name = calendar_name_option(&options(&arg(&a, 0))?)?;
+        Ok(JsValue::Str(year_month_string(d, cal, &calendar_name)))
+    });
+}
+
+fn month_day_members(m: &mut Members, d: IsoDate, cal: Calendar) {
+    let f = cal.fields(d);
+    m.field("calendarId", JsValue::Str(cal.id().into()));
+    m.field("monthCode", JsValue::Str(f.month_code.clone()));
+    m.field("day", JsValue::Number(f.day as f64));
+    let code = f.month_code.clone();
+    m.method("with", move |a| {
+        let JsValue::Object(bag) = arg(&a, 0) else { return Err(type_error("with() requires an object")) };
+        reject_temporal_bag(&arg(&a, 0))?;
+        let overflow = overflow_option(&options(&arg(&a, 1))?)?;
+        let month = bag_month(&bag)?;
+        let year = bag_year(&bag, cal)?;
+        let month = match (&month.ordinal, &month.code) {
+            (None, None) => MonthSpec { ordinal: None, code: Some(code.clone()) },
+            (Some(_), None) if year.is_none() && cal != Calendar::Iso => return Err(type_error("monthCode is required")),
+            _ => month,
+        };
+        let day = bag_int(&bag, "day")?.unwrap_or(f.day as i64);
+        Ok(wrap(Value::MonthDay(cal.month_day_from_fields(year, &month, day, overflow)?, cal)))
+    });
+    m.method("equals", move |a| {
+        let (other, other_cal) = to_month_day(&arg(&a, 0), &JsValue::Undefined)?;
+        Ok(JsValue::Bool(other == d && other_cal == cal))
+    });
+    let code = f.month_code.clone();
+    let day = f.day;
+    m.method("toPlainDate", move |a| {
+        let JsValue::Object(bag) = arg(&a, 0) else { return Err(type_error("toPlainDate() requires an object with year")) };
+        let year = bag_year(&bag, cal)?.ok_or_else(|| type_error("year is required"))?;
+        let date = cal.date_from_fields(year, &MonthSpec { ordinal: None, code: Some(code.clone()) }, day as i64, Overflow::Constrain)?;
+        Ok(wrap(Value::Date(t::check_date(date)?, cal)))
+    });
+    m.method("toString", move |a| {
+        let calendar_name = calendar_name_option(&options(&arg(&a, 0))?)?;
+        Ok(JsValue::Str(month_day_string(d, cal, &calendar_name)))
+    });
+}
+
+// ─── Duration ────────────────────────────────────────────────────────────────
+
+fn duration_members(m: &mut Members, d: Duration) {
+    for unit in Unit::ALL {
+        m.field(&unit.plural(), JsValue::Number(d.get(unit)));
+    }
+    m.field("sign", JsValue::Number(d.sign() as f64));
+    m.field("blank", JsValue::Bool(d.sign() == 0));
+    m.method("with", move |a| {
+        let JsValue::Object(bag) = arg(&a, 0) else { return Err(type_error("with() requires an object")) };
+        Ok(wrap(Value::Duration(duration_from_bag(&bag, d)?)))
+    });
+    m.method("negated", move |_| Ok(wrap(Value::Duration(d.negated()))));
+    m.method("abs", move |_| Ok(wrap(Value::Duration(d.abs()))));
+    for (name, negate) in [("add", false), ("subtract", true)] {
+        m.method(name, move |a| {
+            let other = to_duration(&arg(&a, 0))?;
+            let other = if negate { other.negated() } else { other };
+            if d.has_calendar_units() || other.has_calendar_units() {
+                return Err(range_error("adding durations with years, months or weeks requires a relativeTo"));
+            }
+            let largest = d.largest_unit().max(other.largest_unit());
+            Ok(wrap(Value::Duration(Duration::from_ns(d.day_time_ns() + other.day_time_ns(), largest).validate()?)))
+        });
+    }
+    m.method("round", move |a| {
+        let o = round_options(&arg(&a, 0))?;
+        let largest = unit_option(&o, "largestUnit", &all_units(), true)?;
+        let largest_given = !matches!(get(&o, "largestUnit"), JsValue::Undefined);
+        let relative = to_relative(&get(&o, "relativeTo"))?;
+        let increment = increment_option(&o)?;
+        let mode = rounding_mode_option(&o, RoundingMode::HalfExpand)?;
+        let smallest = unit_option(&o, "smallestUnit", &all_units(), false)?;
+        if smallest.is_none() && !largest_given {
+            return Err(range_error("at least one of smallestUnit or largestUnit is required"));
+        }
+        let smallest = smallest.unwrap_or(Unit::Nanosecond);
+        let largest = largest.unwrap_or(d.largest_unit().max(smallest));
+        if largest < smallest {
+            return Err(range_error(format_args!("largestUnit {} is smaller than smallestUnit {}", largest.name(), smallest.name())));
+        }
+        if smallest < Unit::Day {
+            validate_increment(smallest, increment, false)?;
+        } else if increment > 1 && smallest != largest && smallest.is_calendar() {
+            return Err(range_error("roundingIncrement > 1 for calendar units requires largestUnit equal to smallestUnit"));
+        }
+        let s = RoundingSettings { largest, smallest, increment, mode };
+        let out = match relative {
+            Some(rel) => {
+                let end = rel.add(&d)?;
+                rel.diff_rounded(end, s)?
+            }
+            None => t::round_time_duration(&d, s)?,
+        };
+        Ok(wrap(Value::Duration(out.validate()?)))
+    });
+    m.method("total", move |a| {
+        let o = match arg(&a, 0) {
+            JsValue::Str(s) => {
+                let mut obj = JsObject::new();
+                obj.set("unit".into(), JsValue::Str(s));
+                Some(Rc::new(RefCell::new(obj)))
+            }
+            JsValue::Undefined => return Err(type_error("options parameter is required")),
+            v => options(&v)?,
+        };
+        let relative = to_relative(&get(&o, "relativeTo"))?;
+        let unit = unit_option(&o, "unit", &all_units(), false)?.ok_or_else(|| range_error("unit is required"))?;
+        let total = match relative {
+            Some(rel) => {
+                let end = rel.add(&d)?;
+                rel.total(end, unit)?
+            }
+            None => {
+                if d.has_calendar_units() || unit.is_calendar() {
+                    return Err(range_error("a starting point is required for years, months or weeks"));
+                }
+                t::divide(d.day_time_ns(), unit.nanos())
+            }
+        };
+        Ok(JsValue::Number(total))
+    });
+    m.method("toString", move |a| {
+        let o = options(&arg(&a, 0))?;
+        let (precision, increment, mode) = precision_options(&o)?;
+        if precision == Precision::Minute {
+            return Err(range_error("smallestUnit must be second or smaller"));
+        }
+        let mut out = d;
+        if increment > 1 {
+            // Zaokrouhli casovou cast a znovu rozloz od nejvyssi casove jednotky
+            let rounded = mode.round(d.time_ns(), increment);
+            let largest = d.largest_unit().clamp(Unit::Second, Unit::Hour);
+            let time = Duration::from_ns(rounded, largest);
+            for unit in TIME_UNITS {
+                out.set(unit, time.get(unit));
+            }
+            out = out.validate()?;
+        }
+        Ok(JsValue::Str(t::format_duration(&out, precision)))
+    });
+}
+
+// ─── toLocaleString / Intl.DateTimeFormat ────────────────────────────────────
+
+/// Delka stylu z `dateStyle` / `timeStyle`.
+fn style_option(o: &Option<Obj>, key: &str) -> Option<String> {
+    match get(o, key) {
+        JsValue::Undefined => None,
+        v => Some(v.to_string()),
+    }
+}
+
+fn format_locale(value: &Value, locale: &str, o: &Option<Obj>) -> String {
+    use icu::datetime::options::length;
+    let date_style = match style_option(o, "dateStyle").as_deref() {
+        Some("full") => length::Date::Full,
+        Some("long") => length::Date::Long,
+        Some("medium") => length::Date::Medium,
+        _ => length::Date::Short,
+    };
+    let time_style = match style_option(o, "timeStyle").as_deref() {
+        Some("full" | "long") => length::Time::Long,
+        Some("short") => length::Time::Short,
+        _ => length::Time::Medium,
+    };
+    let formatted = match value {
+        Value::Instant(ns) => {
+            let tz = system_time_zone();
+            icu_date_time(locale, Calendar::Iso, tz.local(*ns), date_style, time_style)
+        }
+        Value::Zoned(ns, tz, cal) => icu_date_time(locale, *cal, tz.local(*ns), date_style, time_style).map(|s| format!("{s} {}", tz.id())),
+        Value::DateTime(dt, cal) => icu_date_time(locale, *cal, *dt, date_style, time_style),
+        Value::Date(d, cal) => icu_date(locale, *cal, *d, date_style),
+        Value::Time(tm) => icu_time(locale, *tm, time_style),
+        Value::YearMonth(d, cal) => {
+            let f = cal.fields(*d);
+            Some(format!("{}/{}", f.month, f.year))
+        }
+        Value::MonthDay(d, cal) => {
+            let f = cal.fields(*d);
+            Some(format!("{}/{}", f.month, f.day))
+        }
+        Value::Duration(d) => Some(t::format_duration(d, Precision::Auto)),
+    };
+    formatted.unwrap_or_else(|| default_string(value))
+}
+
+/// ICU locale s kalendarem Temporal hodnoty (ISO hodnoty berou kalendar locale).
+fn icu_locale(locale: &str, cal: Calendar) -> Option<icu::locid::Locale> {
+    let mut loc: icu::locid::Locale = locale.parse().ok()?;
+    if cal != Calendar::Iso {
+        let value = icu::locid::extensions::unicode::Value::try_from_bytes(cal.id().as_bytes()).ok()?;
+        loc.extensions.unicode.keywords.set(icu::locid::extensions::unicode::key!("ca"), value);
+    }
+    Some(loc)
+}
+
+fn icu_any_date(loc: &icu::locid::Locale, d: IsoDate) -> Option<icu::calendar::Date<icu::calendar::AnyCalendar>> {
+    use icu::calendar::{AnyCalendar, Date};
+    let calendar = AnyCalendar::new_for_locale(&loc.into());
+    Some(Date::try_new_iso_date(d.year, d.month, d.day).ok()?.to_calendar(calendar))
+}
+
+fn icu_time_of(tm: IsoTime) -> Option<icu::calendar::Time> {
+    icu::calendar::Time::try_new(tm.hour, tm.minute, tm.second, 0).ok()
+}
+
+fn icu_date(locale: &str, cal: Calendar, d: IsoDate, style: icu::datetime::options::length::Date) -> Option<String> {
+    let loc = icu_locale(locale, cal)?;
+    let formatter = icu::datetime::DateFormatter::try_new_with_length(&(&loc).into(), style).ok()?;
+    formatter.format_to_string(&icu_any_date(&loc, d)?).ok()
+}
+
+fn icu_time(locale: &str, tm: IsoTime, style: icu::datetime::options::length::Time) -> Option<String> {
+    let loc = icu_locale(locale, Calendar::Iso)?;
+    let formatter = icu::datetime::TimeFormatter::try_new_with_length(&(&loc).into(), style).ok()?;
+    Some(formatter.format_to_string(&icu_time_of(tm)?))
+}
+
+fn icu_date_time(locale: &str, cal: Calendar, dt: IsoDateTime, date_style: icu::datetime::options::length::Date, time_style: icu::datetime::options::length::Time) -> Option<String> {
+    use icu::datetime::options::length;
+    let loc = icu_locale(locale, cal)?;
+    let options = length::Bag::from_date_time_style(date_style, time_style);
+    let formatter = icu::datetime::DateTimeFormatter::try_new(&(&loc).into(), options.into()).ok()?;
+    let value = icu::calendar::DateTime::new(icu_any_date(&loc, dt.date)?, icu_time_of(dt.time)?);
+    formatter.format_to_string(&value).ok()
+}
+
+/// `Intl.DateTimeFormat#format` pro Temporal objekty (None = jina hodnota).
+pub(crate) fn format_for_intl(v: &JsValue, locale: &str) -> Option<String> {
+    let value = temporal_of(v)?;
+    Some(format_locale(&value, locale, &None))
+}
+
+/// Retezcova podoba Temporal objektu pro nativni ToString (`String(x)`, `join`).
+pub(crate) fn display_string(v: &JsValue) -> Option<String> {
+    temporal_of(v).map(|value| default_string(&value))
+}
+
+/// `Date.prototype.toTemporalInstant()`.
+pub(crate) fn instant_from_epoch_ms(ms: f64) -> Result<JsValue, String> {
+    if !ms.is_finite() {
+        return Err(range_error("Invalid Date"));
+    }
+    Ok(wrap(Value::Instant(ms as i128 * 1_000_000)))
+}
+
+// ─── Konstruktory a staticke metody ──────────────────────────────────────────
+
+/// Celociselny argument konstruktoru (undefined -> `default`).
+fn ctor_int(a: &[JsValue], i: usize, field: &str, default: Option<i64>) -> TResult<i64> {
+    match (arg(a, i), default) {
+        (JsValue::Undefined, Some(d)) => Ok(d),
+        (JsValue::Undefined, None) => Err(range_error(format_args!("{field} is required"))),
+        (v, _) => to_integer(&v, field),
+    }
+}
+
+fn ctor_calendar(v: &JsValue) -> TResult<Calendar> {
+    match v {
+        JsValue::Undefined => Ok(Calendar::Iso),
+        JsValue::Str(s) => Calendar::from_id(s),
+        _ => Err(type_error("calendar must be a string")),
+    }
+}
+
+fn ctor_time(a: &[JsValue], start: usize) -> TResult<IsoTime> {
+    let mut fields = [0i64; 6];
+    for (i, (slot, name)) in fields.iter_mut().zip(TIME_FIELDS).enumerate() {
+        *slot = ctor_int(a, start + i, name, Some(0))?;
+    }
+    IsoTime::new(fields, Overflow::Reject)
+}
+
+fn epoch_ns_arg(v: &JsValue) -> TResult<i128> {
+    match v {
+        JsValue::BigInt(b) => i128::try_from(b.as_ref()).map_err(|_| range_error("epochNanoseconds out of range")).and_then(t::check_epoch_ns),
+        _ => Err(type_error("epochNanoseconds must be a BigInt")),
+    }
+}
 
 pub fn setup_temporal(e: &mut Environment) {
-    let temporal = Rc::new(RefCell::new(JsObject::new()));
-    // Temporal.Now
-    let now_obj = Rc::new(RefCell::new(JsObject::new()));
-    now_obj.borrow_mut().set("instant".into(), native("Temporal.Now.instant", |_| {
-        let inst = Rc::new(RefCell::new(JsObject::new()));
-        let ms = now_ms();
-        inst.borrow_mut().set("__instant_ms__".into(), JsValue::Number(ms));
-        inst.borrow_mut().set("epochMilliseconds".into(), JsValue::Number(ms));
-        inst.borrow_mut().set("epochNanoseconds".into(), JsValue::Number(ms * 1_000_000.0));
-        Ok(JsValue::Object(inst))
+    let mut temporal = JsObject::new();
+    temporal.define_hidden(symbols::TO_STRING_TAG.into(), JsValue::Str("Temporal".into()));
+
+    let mut now = JsObject::new();
+    now.define_hidden(symbols::TO_STRING_TAG.into(), JsValue::Str("Temporal.Now".into()));
+    let now_zone = |v: &JsValue| match v {
+        JsValue::Undefined => Ok(system_time_zone()),
+        v => to_time_zone(v),
+    };
+    now.set("instant".into(), native("Temporal.Now.instant", |_| Ok(wrap(Value::Instant(now_ns())))));
+    now.set("timeZoneId".into(), native("Temporal.Now.timeZoneId", |_| Ok(JsValue::Str(system_time_zone().id()))));
+    now.set("zonedDateTimeISO".into(), native("Temporal.Now.zonedDateTimeISO", move |a| {
+        Ok(wrap(Value::Zoned(now_ns(), now_zone(&arg(&a, 0))?, Calendar::Iso)))
+    }));
+    now.set("plainDateTimeISO".into(), native("Temporal.Now.plainDateTimeISO", move |a| {
+        Ok(wrap(Value::DateTime(now_zone(&arg(&a, 0))?.local(now_ns()), Calendar::Iso)))
+    }));
+    now.set("plainDateISO".into(), native("Temporal.Now.plainDateISO", move |a| {
+        Ok(wrap(Value::Date(now_zone(&arg(&a, 0))?.local(now_ns()).date, Calendar::Iso)))
+    }));
+    now.set("plainTimeISO".into(), native("Temporal.Now.plainTimeISO", move |a| {
+        Ok(wrap(Value::Time(now_zone(&arg(&a, 0))?.local(now_ns()).time)))
     }));
-    now_obj.borrow_mut().set("plainDateISO".into(), native("Temporal.Now.plainDateISO", |_| {
-        let ms = now_ms();
-        let (yr, mo, day, _, _, _, _) = ms_to_parts(ms);
-        let pd = Rc::new(RefCell::new(JsObject::new()));
-        pd.borrow_mut().set("year".into(), JsValue::Number(yr as f64));
-        pd.borrow_mut().set("month".into(), JsValue::Number((mo + 1) as f64));
-        pd.borrow_mut().set("day".into(), JsValue::Number(day as f64));
-        pd.borrow_mut().set("__plain_date__".into(), JsValue::Bool(true));
-        Ok(JsValue::Object(pd))
+    temporal.set("Now".into(), JsValue::Object(Rc::new(RefCell::new(now))));
+
+    temporal.set("Instant".into(), native("Temporal.Instant", |a| Ok(wrap(Value::Instant(epoch_ns_arg(&arg(&a, 0))?)))));
+    temporal.set("ZonedDateTime".into(), native("Temporal.ZonedDateTime", |a| {
+        let ns = epoch_ns_arg(&arg(&a, 0))?;
+        let JsValue::Str(id) = arg(&a, 1) else { return Err(type_error("time zone must be a string")) };
+        Ok(wrap(Value::Zoned(ns, TimeZone::from_id(&id)?, ctor_calendar(&arg(&a, 2))?)))
     }));
-    now_obj.borrow_mut().set("plainTimeISO".into(), native("Temporal.Now.plainTimeISO", |_| {
-        let ms = now_ms();
-        let (_, _, _, hr, mi, sec, ms_p) = ms_to_parts(ms);
-        let pt = Rc::new(RefCell::new(JsObject::new()));
-        pt.borrow_mut().set("hour".into(), JsValue::Number(hr as f64));
-        pt.borrow_mut().set("minute".into(), JsValue::Number(mi as f64));
-        pt.borrow_mut().set("second".into(), JsValue::Number(sec as f64));
-        pt.borrow_mut().set("millisecond".into(), JsValue::Number(ms_p as f64));
-        Ok(JsValue::Object(pt))
+    temporal.set("PlainDateTime".into(), native("Temporal.PlainDateTime", |a| {
+        let date = IsoDate::new(ctor_int(&a, 0, "year", None)?, ctor_int(&a, 1, "month", None)?, ctor_int(&a, 2, "day", None)?, Overflow::Reject)?;
+        let dt = t::check_date_time(IsoDateTime { date, time: ctor_time(&a, 3)? })?;
+        Ok(wrap(Value::DateTime(dt, ctor_calendar(&arg(&a, 9))?)))
     }));
-    now_obj.borrow_mut().set("zonedDateTimeISO".into(), native("Temporal.Now.zonedDateTimeISO", |_| {
-        let ms = now_ms();
-        let zdt = Rc::new(RefCell::new(JsObject::new()));
-        zdt.borrow_mut().set("epochMilliseconds".into(), JsValue::Number(ms));
-        zdt.borrow_mut().set("timeZoneId".into(), JsValue::Str("UTC".into()));
-        Ok(JsValue::Object(zdt))
+    temporal.set("PlainDate".into(), native("Temporal.PlainDate", |a| {
+        let date = IsoDate::new(ctor_int(&a, 0, "year", None)?, ctor_int(&a, 1, "month", None)?, ctor_int(&a, 2, "day", None)?, Overflow::Reject)?;
+        Ok(wrap(Value::Date(t::check_date(date)?, ctor_calendar(&arg(&a, 3))?)))
     }));
-    temporal.borrow_mut().set("Now".into(), JsValue::Object(now_obj));
-    // Temporal.PlainDate
-    let plain_date = Rc::new(RefCell::new(JsObject::new()));
-    plain_date.borrow_mut().set("from".into(), native("Temporal.PlainDate.from", |args| {
-        let arg = args.into_iter().next().unwrap_or(JsValue::Undefined);
-        let pd = Rc::new(RefCell::new(JsObject::new()));
-        if let JsValue::Object(o) = &arg {
-            let b = o.borrow();
-            pd.borrow_mut().set("year".into(), b.props.get("year").cloned().unwrap_or(JsValue::Number(1970.0)));
-            pd.borrow_mut().set("month".into(), b.props.get("month").cloned().unwrap_or(JsValue::Number(1.0)));
-            pd.borrow_mut().set("day".into(), b.props.get("day").cloned().unwrap_or(JsValue::Number(1.0)));
-        } else if let JsValue::Str(s) = &arg {
-            let parts: Vec<&str> = s.split('-').collect();
-            pd.borrow_mut().set("year".into(),
-                JsValue::Number(parts.first().and_then(|p| p.parse().ok()).unwrap_or(1970.0)));
-            pd.borrow_mut().set("month".into(),
-                JsValue::Number(parts.get(1).and_then(|p| p.parse().ok()).unwrap_or(1.0)));
-            pd.borrow_mut().set("day".into(),
-                JsValue::Number(parts.get(2).and_then(|p| p.parse().ok()).unwrap_or(1.0)));
-        }
-        pd.borrow_mut().set("__plain_date__".into(), JsValue::Bool(true));
-        Ok(JsValue::Object(pd))
+    temporal.set("PlainTime".into(), native("Temporal.PlainTime", |a| Ok(wrap(Value::Time(ctor_time(&a, 0)?)))));
+    temporal.set("PlainYearMonth".into(), native("Temporal.PlainYearMonth", |a| {
+        let day = ctor_int(&a, 3, "referenceISODay", Some(1))?;
+        let date = IsoDate::new(ctor_int(&a, 0, "year", None)?, ctor_int(&a, 1, "month", None)?, day, Overflow::Reject)?;
+        Ok(wrap(Value::YearMonth(date, ctor_calendar(&arg(&a, 2))?)))
     }));
-    temporal.borrow_mut().set("PlainDate".into(), JsValue::Object(plain_date));
-    // Temporal.Duration
-    let duration = Rc::new(RefCell::new(JsObject::new()));
-    duration.borrow_mut().set("from".into(), native("Temporal.Duration.from", |args| {
-        let arg = args.into_iter().next().unwrap_or(JsValue::Undefined);
-        let dur = Rc::new(RefCell::new(JsObject::new()));
-        if let JsValue::Object(o) = &arg {
-            let b = o.borrow();
-            for k in &["years", "months", "weeks", "days", "hours", "minutes", "seconds", "milliseconds"] {
-                let v = b.props.get(*k).cloned().unwrap_or(JsValue::Number(0.0));
-                dur.borrow_mut().set((*k).into(), v);
-            }
-        }
-        dur.borrow_mut().set("__duration__".into(), JsValue::Bool(true));
-        Ok(JsValue::Object(dur))
+    temporal.set("PlainMonthDay".into(), native("Temporal.PlainMonthDay", |a| {
+        let year = ctor_int(&a, 3, "referenceISOYear", Some(1972))?;
+        let date = IsoDate::new(year, ctor_int(&a, 0, "month", None)?, ctor_int(&a, 1, "day", None)?, Overflow::Reject)?;
+        Ok(wrap(Value::MonthDay(t::check_date(date)?, ctor_calendar(&arg(&a, 2))?)))
     }));
-    temporal.borrow_mut().set("Duration".into(), JsValue::Object(duration));
-    // Temporal.Instant
-    let instant = Rc::new(RefCell::new(JsObject::new()));
-    instant.borrow_mut().set("fromEpochMilliseconds".into(), native("Temporal.Instant.fromEpochMilliseconds", |args| {
-        let ms = args.into_iter().next().map(|v| v.to_number()).unwrap_or(0.0);
-        let inst = Rc::new(RefCell::new(JsObject::new()));
-        inst.borrow_mut().set("epochMilliseconds".into(), JsValue::Number(ms));
-        inst.borrow_mut().set("epochNanoseconds".into(), JsValue::Number(ms * 1_000_000.0));
-        inst.borrow_mut().set("__instant_ms__".into(), JsValue::Number(ms));
-        Ok(JsValue::Object(inst))
+    temporal.set("Duration".into(), native("Temporal.Duration", |a| {
+        let mut d = Duration::ZERO;
+        for (i, unit) in Unit::ALL.into_iter().enumerate() {
+            let n = match arg(&a, i) {
+                JsValue::Undefined => 0.0,
+                v => v.to_number(),
+            };
+            if !n.is_finite() || n.fract() != 0.0 {
+                return Err(range_error(format_args!("{} must be an integer", unit.plural())));
+            }
+            d.set(unit, n + 0.0);
+        }
+        Ok(wrap(Value::Duration(d.validate()?)))
     }));
-    temporal.borrow_mut().set("Instant".into(), JsValue::Object(instant));
-    e.define("Temporal", JsValue::Object(temporal));
+    e.define("Temporal", JsValue::Object(Rc::new(RefCell::new(temporal))));
+}
+
+fn compare_result(o: std::cmp::Ordering) -> JsValue {
+    JsValue::Number(o as i32 as f64)
+}
+
+/// Staticke metody Temporal trid (`Temporal.PlainDate.from`, `compare`, ...).
+pub(crate) fn static_prop(ctor: &str, key: &str) -> Option<JsValue> {
+    let name = format!("{ctor}.{key}");
+    let f = |f: fn(Vec<JsValue>) -> TResult<JsValue>| Some(native(&name, f));
+    match (ctor, key) {
+        ("Temporal.Instant", "from") => f(|a| Ok(wrap(Value::Instant(to_instant(&arg(&a, 0))?)))),
+        ("Temporal.Instant", "fromEpochMilliseconds") => f(|a| {
+            let ms = to_integer(&arg(&a, 0), "epochMilliseconds")?;
+            Ok(wrap(Value::Instant(t::check_epoch_ns(ms as i128 * 1_000_000)?)))
+        }),
+        ("Temporal.Instant", "fromEpochNanoseconds") => f(|a| Ok(wrap(Value::Instant(epoch_ns_arg(&arg(&a, 0))?)))),
+        ("Temporal.Instant", "compare") => f(|a| Ok(compare_result(to_instant(&arg(&a, 0))?.cmp(&to_instant(&arg(&a, 1))?)))),
+        ("Temporal.ZonedDateTime", "from") => f(|a| {
+            let (ns, tz, cal) = to_zoned(&arg(&a, 0), &arg(&a, 1))?;
+            Ok(wrap(Value::Zoned(ns, tz, cal)))
+        }),
+        ("Temporal.ZonedDateTime", "compare") => f(|a| {
+            let (x, ..) = to_zoned(&arg(&a, 0), &JsValue::Undefined)?;
+            let (y, ..) = to_zoned(&arg(&a, 1), &JsValue::Undefined)?;
+            Ok(compare_result(x.cmp(&y)))
+        }),
+        ("Temporal.PlainDateTime", "from") => f(|a| {
+            let (dt, cal) = to_plain_date_time(&arg(&a, 0), &arg(&a, 1))?;
+            Ok(wrap(Value::DateTime(dt, cal)))
+        }),
+        ("Temporal.PlainDateTime", "compare") => f(|a| {
+            let (x, _) = to_plain_date_time(&arg(&a, 0), &JsValue::Undefined)?;
+            let (y, _) = to_plain_date_time(&arg(&a, 1), &JsValue::Undefined)?;
+            Ok(compare_result(x.cmp(&y)))
+        }),
+        ("Temporal.PlainDate", "from") => f(|a| {
+            let (d, cal) = to_plain_date(&arg(&a, 0), &arg(&a, 1))?;
+            Ok(wrap(Value::Date(d, cal)))
+        }),
+        ("Temporal.PlainDate", "compare") => f(|a| {
+            let (x, _) = to_plain_date(&arg(&a, 0), &JsValue::Undefined)?;
+            let (y, _) = to_plain_date(&arg(&a, 1), &JsValue::Undefined)?;
+            Ok(compare_result(x.cmp(&y)))
+        }),
+        ("Temporal.PlainTime", "from") => f(|a| Ok(wrap(Value::Time(to_plain_time(&arg(&a, 0), &arg(&a, 1))?)))),
+        ("Temporal.PlainTime", "compare") => f(|a| {
+            let x = to_plain_time(&arg(&a, 0), &JsValue::Undefined)?;
+            Ok(compare_result(x.cmp(&to_plain_time(&arg(&a, 1), &JsValue::Undefined)?)))
+        }),
+        ("Temporal.PlainYearMonth", "from") => f(|a| {
+            let (d, cal) = to_year_month(&arg(&a, 0), &arg(&a, 1))?;
+            Ok(wrap(Value::YearMonth(d, cal)))
+        }),
+        ("Temporal.PlainYearMonth", "compare") => f(|a| {
+            let (x, _) = to_year_month(&arg(&a, 0), &JsValue::Undefined)?;
+            let (y, _) = to_year_month(&arg(&a, 1), &JsValue::Undefined)?;
+            Ok(compare_result(x.cmp(&y)))
+        }),
+        ("Temporal.PlainMonthDay", "from") => f(|a| {
+            let (d, cal) = to_month_day(&arg(&a, 0), &arg(&a, 1))?;
+            Ok(wrap(Value::MonthDay(d, cal)))
+        }),
+        ("Temporal.Duration", "from") => f(|a| Ok(wrap(Value::Duration(to_duration(&arg(&a, 0))?)))),
+        ("Temporal.Duration", "compare") => f(|a| {
+            let x = to_duration(&arg(&a, 0))?;
+            let y = to_duration(&arg(&a, 1))?;
+            let relative = to_relative(&get(&options(&arg(&a, 2))?, "relativeTo"))?;
+            if x == y {
+                return Ok(JsValue::Number(0.0));
+            }
+            let calendar = x.has_calendar_units() || y.has_calendar_units();
+            let (ex, ey) = match relative {
+                Some(rel @ Relative::Zoned(..)) => (rel.add(&x)?, rel.add(&y)?),
+                Some(rel) if calendar => (rel.add(&x)?, rel.add(&y)?),
+                _ if calendar => return Err(range_error("comparing durations with years, months or weeks requires a relativeTo")),
+                _ => (x.day_time_ns(), y.day_time_ns()),
+            };
+            Ok(compare_result(ex.cmp(&ey)))
+        }),
+        _ => None,
+    }
 }
@@ -0,0 +1,554 @@
+//! ArrayBuffer / SharedArrayBuffer / TypedArray / DataView - extracted z builtins.rs.
+//!
+//! JS strana bajtoveho modelu z `typed_arrays.rs`: objekty nesou
+//! `JsObject::binary` (sdileny buffer), metody jsou nativni closures
+//! navazane na view. Indexy a delky resi `BinaryData::get` / `set`, takze je
+//! vidi tree-walker, VM i nativni kod (canvas, WebGL, crypto, wasm) stejne.
+//!
+//! Metody s callbackem (`map`, `forEach`, `sort(cmp)`, ...) nativni closure
+//! zavolat nemuze - interpret a VM je dispatchuji pres kopii prvku do pole
+//! (`CALLBACK_METHODS` + `finish_callback_method`).
+
+use std::cell::RefCell;
+use std::rc::{Rc, Weak};
+use std::sync::Arc;
+
+use super::{JsValue, JsObject, Environment, symbols};
+use super::property_map::PropertyKey;
+use super::helpers::{native, make_array_iterator};
+use super::keyed_table::same_value_zero;
+use super::shared_memory::SharedMemory;
+use super::typed_arrays::{
+    ArrayBuffer, BinaryData, DataViewSlot, Endian, SharedBuffer, TypedArrayKind, TypedArrayView,
+};
+
+/// Metody typed array, ktere volaji JS callback (resi je volajici interpret / VM).
+pub(crate) const CALLBACK_METHODS: &[&str] = &[
+    "forEach", "map", "filter", "reduce", "reduceRight", "some", "every",
+    "find", "findIndex", "findLast", "findLastIndex", "sort", "toSorted",
+];
+
+pub fn setup_typed_arrays(e: &mut Environment) {
+    // new ArrayBuffer(length, { maxByteLength })
+    e.define("ArrayBuffer", native("ArrayBuffer", |a| {
+        let len = to_index(&arg(&a, 0), "array buffer length")?;
+        let buf = match max_byte_length(&arg(&a, 1))? {
+            Some(max) => ArrayBuffer::resizable(len, max)?,
+            None => ArrayBuffer::new(len),
+        };
+        Ok(buffer_object(buf.into_handle()))
+    }));
+
+    // new DataView(buffer, byteOffset, byteLength)
+    e.define("DataView", native("DataView", |a| {
+        let buffer = arg(&a, 0);
+        let Some(BinaryData::Buffer(buf)) = binary_of(&buffer).as_deref().cloned() else {
+            return Err("TypeError: First argument to DataView constructor must be an ArrayBuffer".into());
+        };
+        let offset = to_index(&arg(&a, 1), "DataView offset")?;
+        let (buf_len, resizable, detached) = {
+            let b = buf.borrow();
+            (b.byte_length(), b.max_byte_length.is_some(), b.detached)
+        };
+        if detached { return Err("TypeError: Cannot construct a DataView on a detached ArrayBuffer".into()); }
+        if offset > buf_len {
+            return Err(format!("RangeError: Start offset {offset} is outside the bounds of the buffer"));
+        }
+        let fixed_length = match arg(&a, 2) {
+            JsValue::Undefined if resizable => None,
+            JsValue::Undefined => Some(buf_len - offset),
+            v => {
+                let len = to_index(&v, "DataView length")?;
+                if offset + len > buf_len {
+                    return Err(format!("RangeError: Invalid DataView length {len}"));
+                }
+                Some(len)
+            }
+        };
+        Ok(data_view_object(DataViewSlot { buffer: buf, byte_offset: offset, fixed_length }, buffer))
+    }));
+
+    for kind in TypedArrayKind::ALL {
+        e.define(kind.ctor_name(), native(kind.ctor_name(), move |a| construct_typed_array(kind, a)));
+    }
+}
+
+fn arg(a: &[JsValue], i: usize) -> JsValue {
+    a.get(i).cloned().unwrap_or(JsValue::Undefined)
+}
+
+/// ToIndex: nezaporne cele cislo (undefined = 0), jinak RangeError.
+/// `{ maxByteLength }` z options argumentu konstruktoru bufferu.
+fn max_byte_length(options: &JsValue) -> Result<Option<usize>, String> {
+    match options {
+        JsValue::Object(o) => match o.borrow().get("maxByteLength") {
+            JsValue::Undefined => Ok(None),
+            v => Ok(Some(to_index(&v, "array buffer max length")?)),
+        },
+        _ => Ok(None),
+    }
+}
+
+fn to_index(v: &JsValue, what: &str) -> Result<usize, String> {
+    if matches!(v, JsValue::Undefined) { return Ok(0); }
+    let n = v.to_number();
+    let n = if n.is_nan() { 0.0 } else { n.trunc() };
+    if !(0.0..=9007199254740991.0).contains(&n) {
+        return Err(format!("RangeError: Invalid {what}"));
+    }
+    Ok(n as usize)
+}
+
+/// Relativni index (`slice(-2)`) orezany do 0..len.
+fn rel_index(v: Option<&JsValue>, len: usize, default: usize) -> usize {
+    match v {
+        None | Some(JsValue::Undefined) => default,
+        Some(v) => {
+            let n = v.to_number();
+            let n = if n.is_nan() { 0.0 } else { n.trunc() };
+            if n < 0.0 { (len as f64 + n).max(0.0) as usize } else { n.min(len as f64) as usize }
+        }
+    }
+}
+
+/// Bajtova data objektu (ArrayBuffer / TypedArray / DataView).
+/// new SharedArrayBuffer(length, { maxByteLength }) - pamet sdilena s Workery.
+/// Jen pri cross-origin isolation - `Interpreter::set_cross_origin_isolation` ho prida/odebere.
+pub(crate) fn define_shared_array_buffer(e: &mut Environment) {
+    e.define("SharedArrayBuffer", native("SharedArrayBuffer", |a| {
+        let len = to_index(&arg(&a, 0), "array buffer length")?;
+        let memory = SharedMemory::new(len, max_byte_length(&arg(&a, 1))?)?;
+        Ok(buffer_object(ArrayBuffer::from_shared(Arc::new(memory)).into_handle()))
+    }));
+}
+
+pub(crate) fn binary_of(v: &JsValue) -> Option<Rc<BinaryData>> {
+    match v {
+        JsValue::Object(o) => o.borrow().binary.clone(),
+        _ => None,
+    }
+}
+
+/// View typed array (`None` pro ostatni hodnoty).
+pub(crate) fn typed_view_of(v: &JsValue) -> Option<TypedArrayView> {
+    match binary_of(v).as_deref() {
+        Some(BinaryData::TypedArray(view)) => Some(view.clone()),
+        _ => None,
+    }
+}
+
+/// Kopie bajtu z BufferSource (ArrayBuffer nebo libovolny view).
+pub(crate) fn bytes_of(v: &JsValue) -> Option<Vec<u8>> {
+    Some(match binary_of(v)?.as_ref() {
+        BinaryData::Buffer(b) => b.borrow().to_vec(),
+        BinaryData::TypedArray(view) => view.bytes(),
+        BinaryData::DataView(d) => d.buffer.borrow().slice(d.byte_offset..d.byte_offset + d.byte_length()),
+    })
+}
+
+/// Novy (ne-resizable) ArrayBuffer s danymi bajty.
+pub(crate) fn array_buffer_value(bytes: Vec<u8>) -> JsValue {
+    buffer_object(ArrayBuffer::from_bytes(bytes).into_handle())
+}
+
+/// Novy typed array nad vlastnim bufferem s danymi bajty.
+pub(crate) fn typed_array_value(kind: TypedArrayKind, bytes: Vec<u8>) -> JsValue {
+    let len = bytes.len() / kind.element_size();
+    let buf = ArrayBuffer::from_bytes(bytes).into_handle();
+    let view = TypedArrayView::new(Rc::clone(&buf), kind, 0, Some(len));
+    typed_array_object(view, buffer_object(buf))
+}
+
+/// Typed array z JS hodnot (konverze podle druhu - ToUint8Clamp, ToBigInt64, ...).
+pub(crate) fn typed_array_from_values(kind: TypedArrayKind, values: &[JsValue]) -> Result<JsValue, String> {
+    let buf = ArrayBuffer::new(values.len() * kind.element_size()).into_handle();
+    let view = TypedArrayView::new(Rc::clone(&buf), kind, 0, Some(values.len()));
+    for (i, v) in values.iter().enumerate() {
+        view.set(i, v)?;
+    }
+    Ok(typed_array_object(view, buffer_object(buf)))
+}
+
+/// Staticke vlastnosti konstruktoru (`ArrayBuffer.isView`, `Uint8Array.from`, ...).
+pub(crate) fn static_prop(ctor: &str, key: &str) -> Option<JsValue> {
+    if ctor == "ArrayBuffer" && key == "isView" {
+        return Some(native("ArrayBuffer.isView", |a| {
+            let view = binary_of(&arg(&a, 0));
+            Ok(JsValue::Bool(matches!(view.as_deref(), Some(BinaryData::TypedArray(_) | BinaryData::DataView(_)))))
+        }));
+    }
+    let kind = TypedArrayKind::from_ctor(ctor)?;
+    match key {
+        "BYTES_PER_ELEMENT" => Some(JsValue::Number(kind.element_size() as f64)),
+        "of" => Some(native(&format!("{ctor}.of"), move |a| typed_array_from_values(kind, &a))),
+        "from" => Some(native(&format!("{ctor}.from"), move |a| {
+            typed_array_from_values(kind, &source_values(&arg(&a, 0)))
+        })),
+        _ => None,
+    }
+}
+
+/// Hodnoty zdroje pro `new TypedArray(src)` / `TypedArray.from(src)`:
+/// typed array, pole, Set, array-like objekt.
+fn source_values(src: &JsValue) -> Vec<JsValue> {
+    if let Some(view) = typed_view_of(src) {
+        return view.to_values();
+    }
+    match src {
+        JsValue::Object(o) => {
+            let len = o.borrow().get("length").to_number();
+            let len = if len.is_nan() || len < 0.0 { 0 } else { len as usize };
+            (0..len).map(|i| o.borrow().get(&i.to_string())).collect()
+        }
+        other => super::helpers::collect_iterable_values(other),
+    }
+}
+
+fn construct_typed_array(kind: TypedArrayKind, a: Vec<JsValue>) -> Result<JsValue, String> {
+    let name = kind.ctor_name();
+    let size = kind.element_size();
+    let first = arg(&a, 0);
+    if let Some(BinaryData::Buffer(buf)) = binary_of(&first).as_deref().cloned() {
+        // new Int32Array(buffer, byteOffset, length) - view nad existujicim bufferem
+        let offset = to_index(&arg(&a, 1), "typed array offset")?;
+        if offset % size != 0 {
+            return Err(format!("RangeError: start offset of {name} should be a multiple of {size}"));
+        }
+        let (buf_len, resizable, detached) = {
+            let b = buf.borrow();
+            (b.byte_length(), b.max_byte_length.is_some(), b.detached)
+        };
+        if detached { return Err("TypeError: Cannot perform Construct on a detached ArrayBuffer".into()); }
+        let fixed_length = match arg(&a, 2) {
+            JsValue::Undefined => {
+                if offset > buf_len {
+                    return Err(format!("RangeError: Start offset {offset} is outside the bounds of the buffer"));
+                }
+                if resizable {
+                    None
+                } else {
+                    if (buf_len - offset) % size != 0 {
+                        return Err(format!("RangeError: byte length of {name} should be a multiple of {size}"));
+                    }
+                    Some((buf_len - offset) / size)
+                }
+            }
+            v => {
+                let len = to_index(&v, "typed array length")?;
+                if offset + len * size > buf_len {
+                    return Err(format!("RangeError: Invalid typed array length: {len}"));
+                }
+                Some(len)
+            }
+        };
+        return Ok(typed_array_object(TypedArrayView::new(buf, kind, offset, fixed_length), first));
+    }
+    match &first {
+        JsValue::Undefined | JsValue::Null => typed_array_from_values(kind, &[]),
+        JsValue::Object(_) | JsValue::Array(_) | JsValue::Set(_) | JsValue::Map(_) => {
+            typed_array_from_values(kind, &source_values(&first))
+        }
+        v => {
+            let len = to_index(v, "typed array length")?;
+            Ok(typed_array_value(kind, vec![0; len * size]))
+        }
+    }
+}
+
+/// JS objekt ArrayBufferu (nebo SharedArrayBufferu) nad sdilenym bufferem.
+pub(crate) fn buffer_object(buf: SharedBuffer) -> JsValue {
+    let shared = buf.borrow().is_shared();
+    let mut obj = JsObject::new();
+    obj.binary = Some(Rc::new(BinaryData::Buffer(Rc::clone(&buf))));
+    let tag = if shared { "SharedArrayBuffer" } else { "ArrayBuffer" };
+    obj.define_hidden(symbols::TO_STRING_TAG.into(), JsValue::Str(tag.into()));
+
+    let b = Rc::clone(&buf);
+    obj.define_hidden("slice".into(), native("ArrayBuffer.slice", move |a| {
+        let src = b.borrow();
+        if src.detached {
+            return Err("TypeError: Cannot perform ArrayBuffer.prototype.slice on a detached ArrayBuffer".into());
+        }
+        let len = src.byte_length();
+        let start = rel_index(a.first(), len, 0);
+        let end = rel_index(a.get(1), len, len);
+        let bytes = src.slice(start..end.max(start));
+        let out = if src.is_shared() {
+            ArrayBuffer::from_shared(Arc::new(SharedMemory::from_bytes(&bytes)))
+        } else {
+            ArrayBuffer::from_bytes(bytes)
+        };
+        Ok(buffer_object(out.into_handle()))
+    }));
+    if shared {
+        let b = Rc::clone(&buf);
+        obj.define_hidden("grow".into(), native("SharedArrayBuffer.grow", move |a| {
+            let len = to_index(&arg(&a, 0), "length parameter")?;
+            let memory = b.borrow().shared_memory().cloned();
+            memory.map_or(Ok(()), |m| m.grow(len))?;
+            Ok(JsValue::Undefined)
+        }));
+        return JsValue::Object(Rc::new(RefCell::new(obj)));
+    }
+    let b = Rc::clone(&buf);
+    obj.define_hidden("resize".into(), native("ArrayBuffer.resize", move |a| {
+        let len = to_index(&arg(&a, 0), "length parameter")?;
+        b.borrow_mut().resize(len)?;
+        Ok(JsValue::Undefined)
+    }));
+    // ES2024 - transfer() zachova resizable, transferToFixedLength() ne
+    for (name, keep_resizable) in [("transfer", true), ("transferToFixedLength", false)] {
+        let b = Rc::clone(&buf);
+        obj.define_hidden(name.into(), native(&format!("ArrayBuffer.{name}"), move |a| {
+            let new_len = match arg(&a, 0) {
+                JsValue::Undefined => None,
+                v => Some(to_index(&v, "array buffer length")?),
+            };
+            let moved = b.borrow_mut().transfer(new_len, keep_resizable)?;
+            Ok(buffer_object(moved.into_handle()))
+        }));
+    }
+    JsValue::Object(Rc::new(RefCell::new(obj)))
+}
+
+/// JS objekt DataView - get*/set* pro vsechny ciselne typy s volitelnym little-endian.
+pub(crate) fn data_view_object(slot: DataViewSlot, buffer: JsValue) -> JsValue {
+    let mut obj = JsObject::new();
+    obj.define_hidden("buffer".into(), buffer);
+    obj.define_hidden(symbols::TO_STRING_TAG.into(), JsValue::Str("DataView".into()));
+    for kind in TypedArrayKind::ALL {
+        if kind == TypedArrayKind::Uint8Clamped { continue; }
+        let ty = kind.ctor_name().trim_end_matches("Array");
+        let s = slot.clone();
+        obj.define_hidden(format!("get{ty}").into(), native(&format!("DataView.get{ty}"), move |a| {
+            let endian = Endian::from_flag(arg(&a, 1).is_truthy());
+            s.get(kind, arg(&a, 0).to_number(), endian)
+        }));
+        let s = slot.clone();
+        obj.define_hidden(format!("set{ty}").into(), native(&format!("DataView.set{ty}"), move |a| {
+            let endian = Endian::from_flag(arg(&a, 2).is_truthy());
+            s.set(kind, arg(&a, 0).to_number(), &arg(&a, 1), endian)?;
+            Ok(JsValue::Undefined)
+        }));
+    }
+    obj.binary = Some(Rc::new(BinaryData::DataView(slot)));
+    JsValue::Object(Rc::new(RefCell::new(obj)))
+}
+
+/// `this` metody typed array - slaby odkaz, aby closure v objektu nedrzela objekt sama.
+fn this_of(weak: &Weak<RefCell<JsObject>>) -> JsValue {
+    weak.upgrade().map(JsValue::Object).unwrap_or(JsValue::Undefined)
+}
+
+/// JS objekt typed array nad `view`; `buffer` je JS objekt jeho ArrayBufferu.
+pub(crate) fn typed_array_object(view: TypedArrayView, buffer: JsValue) -> JsValue {
+    let kind = view.kind;
+    let name = kind.ctor_name();
+    let obj = Rc::new(RefCell::new(JsObject::new()));
+    let this = Rc::downgrade(&obj);
+    let mut o = obj.borrow_mut();
+    o.binary = Some(Rc::new(BinaryData::TypedArray(view.clone())));
+    o.define_hidden("buffer".into(), buffer.clone());
+    o.define_hidden("BYTES_PER_ELEMENT".into(), JsValue::Number(kind.element_size() as f64));
+    o.define_hidden(symbols::TO_STRING_TAG.into(), JsValue::Str(name.into()));
+
+    let v = view.clone();
+    o.define_hidden("at".into(), native(&format!("{name}.at"), move |a| {
+        let len = v.length() as f64;
+        let i = arg(&a, 0).to_number();
+        let i = if i.is_nan() { 0.0 } else { i.trunc() };
+        let i = if i < 0.0 { len + i } else { i };
+        Ok(if (0.0..len).contains(&i) { v.get(i as usize).unwrap_or(JsValue::Undefined) } else { JsValue::Undefined })
+    }));
+    // subarray - novy view nad STEJNYM bufferem (zapisy jsou videt v obou)
+    let v = view.clone();
+    let buf_obj = buffer.clone();
+    o.define_hidden("subarray".into(), native(&format!("{name}.subarray"), move |a| {
+        let len = v.length();
+        let begin = rel_index(a.first(), len, 0);
+        let end = rel_index(a.get(1), len, len);
+        let offset = v.byte_offset + begin * kind.element_size();
+        let fixed = if v.fixed_length.is_none() && matches!(arg(&a, 1), JsValue::Undefined) {
+            None
+        } else {
+            Some(end.saturating_sub(begin))
+        };
+        Ok(typed_array_object(TypedArrayView::new(Rc::clone(&v.buffer), kind, offset, fixed), buf_obj.clone()))
+    }));
+    let v = view.clone();
+    o.define_hidden("set".into(), native(&format!("{name}.set"), move |a| {
+        let src = arg(&a, 0);
+        let offset = to_index(&arg(&a, 1), "offset")?;
+        // Kopie predem - zdroj muze prekryvat cil (stejny buffer)
+        let values = source_values(&src);
+        if offset + values.len() > v.length() {
+            return Err("RangeError: offset is out of bounds".into());
+        }
+        for (i, val) in values.iter().enumerate() {
+            v.set(offset + i, val)?;
+        }
+        Ok(JsValue::Undefined)
+    }));
+    let v = view.clone();
+    let this_fill = this.clone();
+    o.define_hidden("fill".into(), native(&format!("{name}.fill"), move |a| {
+        let raw = kind.encode(&arg(&a, 0), Endian::Little)?;
+        let len = v.length();
+        let start = rel_index(a.get(1), len, 0);
+        let end = rel_index(a.get(2), len, len);
+        let size = kind.element_size();
+        let mut b = v.buffer.borrow_mut();
+        for i in start..end {
+            let at = v.byte_offset + i * size;
+            b.write(at, &raw[..size]);
+        }
+        Ok(this_of(&this_fill))
+    }));
+    let v = view.clone();
+    let this_copy = this.clone();
+    o.define_hidden("copyWithin".into(), native(&format!("{name}.copyWithin"), move |a| {
+        let len = v.length();
+        let target = rel_index(a.first(), len, 0);
+        let start = rel_index(a.get(1), len, 0);
+        let end = rel_index(a.get(2), len, len);
+        let count = end.saturating_sub(start).min(len - target);
+        if count > 0 {
+            let size = kind.element_size();
+            let from = v.byte_offset + start * size;
+            v.buffer.borrow_mut().copy_within(from..from + count * size, v.byte_offset + target * size);
+        }
+        Ok(this_of(&this_copy))
+    }));
+    let v = view.clone();
+    o.define_hidden("slice".into(), native(&format!("{name}.slice"), move |a| {
+        let len = v.length();
+        let start = rel_index(a.first(), len, 0);
+        let end = rel_index(a.get(1), len, len);
+        let size = kind.element_size();
+        let bytes = if start < end {
+            v.buffer.borrow().slice(v.byte_offset + start * size..v.byte_offset + end * size)
+        } else { Vec::new() };
+        Ok(typed_array_value(kind, bytes))
+    }));
+    let v = view.clone();
+    o.define_hidden("indexOf".into(), native(&format!("{name}.indexOf"), move |a| {
+        let needle = arg(&a, 0);
+        let len = v.length();
+        let from = rel_index(a.get(1), len, 0);
+        let found = (from..len).find(|&i| v.get(i).is_some_and(|x| x.strict_eq(&needle)));
+        Ok(JsValue::Number(found.map(|i| i as f64).unwrap_or(-1.0)))
+    }));
+    let v = view.clone();
+    o.define_hidden("lastIndexOf".into(), native(&format!("{name}.lastIndexOf"), move |a| {
+        let needle = arg(&a, 0);
+        let len = v.length();
+        let last = match a.get(1) {
+            None => len,
+            Some(_) => (rel_index(a.get(1), len, len) + 1).min(len),
+        };
+        let found = (0..last).rev().find(|&i| v.get(i).is_some_and(|x| x.strict_eq(&needle)));
+        Ok(JsValue::Number(found.map(|i| i as f64).unwrap_or(-1.0)))
+    }));
+    let v = view.clone();
+    o.define_hidden("includes".into(), native(&format!("{name}.includes"), move |a| {
+        let needle = arg(&a, 0);
+        let len = v.length();
+        let from = rel_index(a.get(1), len, 0);
+        Ok(JsValue::Bool((from..len).any(|i| v.get(i).is_some_and(|x| same_value_zero(&x, &needle)))))
+    }));
+    let v = view.clone();
+    let this_rev = this.clone();
+    o.define_hidden("reverse".into(), native(&format!("{name}.reverse"), move |_| {
+        let size = kind.element_size();
+        let len = v.length();
+        let mut b = v.buffer.borrow_mut();
+        let mut region = b.slice(v.byte_offset..v.byte_offset + len * size);
+        // Obrat cele oblasti + zpetne obraceni bajtu v kazdem prvku
+        region.reverse();
+        for chunk in region.chunks_mut(size) {
+            chunk.reverse();
+        }
+        b.write(v.byte_offset, &region);
+        Ok(this_of(&this_rev))
+    }));
+    let v = view.clone();
+    o.define_hidden("toReversed".into(), native(&format!("{name}.toReversed"), move |_| {
+        let mut values = v.to_values();
+        values.reverse();
+        typed_array_from_values(kind, &values)
+    }));
+    let v = view.clone();
+    o.define_hidden("with".into(), native(&format!("{name}.with"), move |a| {
+        let len = v.length() as f64;
+        let i = arg(&a, 0).to_number();
+        let i = if i.is_nan() { 0.0 } else { i.trunc() };
+        let i = if i < 0.0 { len + i } else { i };
+        if !(0.0..len).contains(&i) {
+            return Err("RangeError: Invalid typed array index".into());
+        }
+        let mut values = v.to_values();
+        values[i as usize] = arg(&a, 1);
+        typed_array_from_values(kind, &values)
+    }));
+    for method in ["join", "toString", "toLocaleString"] {
+        let v = view.clone();
+        o.define_hidden(method.into(), native(&format!("{name}.{method}"), move |a| {
+            let sep = match (method, arg(&a, 0)) {
+                ("join", JsValue::Undefined) | ("toString" | "toLocaleString", _) => ",".to_string(),
+                (_, s) => s.to_string(),
+            };
+            let parts: Vec<String> = v.to_values().iter().map(|x| x.to_string()).collect();
+            Ok(JsValue::Str(parts.join(&sep)))
+        }));
+    }
+    let v = view.clone();
+    o.define_hidden("keys".into(), native(&format!("{name}.keys"), move |_| {
+        Ok(make_array_iterator((0..v.length()).map(|i| JsValue::Number(i as f64)).collect()))
+    }));
+    let v = view.clone();
+    o.define_hidden("entries".into(), native(&format!("{name}.entries"), move |_| {
+        let pairs = v.to_values().into_iter().enumerate()
+            .map(|(i, x)| JsValue::Array(Rc::new(RefCell::new(vec![JsValue::Number(i as f64), x]))))
+            .collect();
+        Ok(make_array_iterator(pairs))
+    }));
+    for key in [PropertyKey::from("values"), symbols::ITERATOR.into()] {
+        let v = view.clone();
+        o.define_hidden(key, native(&format!("{name}.values"), move |_| Ok(make_array_iterator(v.to_values()))));
+    }
+    drop(o);
+    JsValue::Object(obj)
+}
+
+/// Dokonceni callback metody nad kopii prvku: `map`/`filter`/`toSorted` vraci
+/// novy typed array stejneho druhu, `sort` zapise poradi zpet do bufferu.
+pub(crate) fn finish_callback_method(this: &JsValue, view: &TypedArrayView, key: &str, result: JsValue) -> Result<JsValue, String> {
+    let values = |v: &JsValue| match v {
+        JsValue::Array(a) => a.borrow().clone(),
+        _ => Vec::new(),
+    };
+    match key {
+        "map" | "filter" | "toSorted" => typed_array_from_values(view.kind, &values(&result)),
+        "sort" => {
+            for (i, v) in values(&result).iter().enumerate() {
+                view.set(i, v)?;
+            }
+            Ok(this.clone())
+        }
+        _ => Ok(result),
+    }
+}
+
+/// Vychozi razeni typed array (bez comparatoru) je ciselne, ne retezcove.
+pub(crate) fn sort_default(values: &mut [JsValue]) {
+    values.sort_by(|a, b| match (a, b) {
+        (JsValue::BigInt(x), JsValue::BigInt(y)) => x.cmp(y),
+        _ => {
+            let (x, y) = (a.to_number(), b.to_number());
+            // NaN na konec, -0 pred +0
+            x.partial_cmp(&y)
+                .unwrap_or_else(|| x.is_nan().cmp(&y.is_nan()))
+                .then_with(|| y.is_sign_negative().cmp(&x.is_sign_negative()))
+        }
+    });
+}
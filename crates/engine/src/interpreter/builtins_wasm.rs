@@ -0,0 +1,531 @@
+//! WebAssembly JS API - extracted z builtins.rs.
+//!
+//! JS objekty nad `wasm::WasmRealm`: Module / Instance / Memory / Table /
+//! Global nesou jen index do realmu, metody jsou natives zachycujici realm
+//! a index (natives nevidi `this`). Tag a Exception jsou ciste JS objekty.
+//! Staticke `WebAssembly.Module.imports/exports/customSections` resi
+//! `static_prop` (metadata ulozena na objektu modulu pri kompilaci).
+
+use std::rc::{Rc, Weak};
+use std::cell::RefCell;
+use wasmi::Extern;
+use super::{symbols, Environment, JsObject, JsValue};
+use super::bytecode;
+use super::builtins_typed_arrays::{array_buffer_value, bytes_of};
+use super::helpers::{make_settled_promise, native};
+use super::wasm::{self, ExportValue, WasmRealm, HANDLE_KEY, MAX_PAGES, MODULE_KEY};
+
+fn arg(a: &[JsValue], i: usize) -> JsValue {
+    a.get(i).cloned().unwrap_or(JsValue::Undefined)
+}
+
+fn into_value(obj: JsObject) -> JsValue {
+    JsValue::Object(Rc::new(RefCell::new(obj)))
+}
+
+fn array(items: Vec<JsValue>) -> JsValue {
+    JsValue::Array(Rc::new(RefCell::new(items)))
+}
+
+/// Objekt s `__class_chain__` a `Symbol.toStringTag` dane WebAssembly tridy.
+fn class_object(class: &str) -> JsObject {
+    let mut obj = JsObject::new();
+    obj.set("__class_chain__".into(), JsValue::Str(class.into()));
+    obj.define_hidden(symbols::TO_STRING_TAG.into(), JsValue::Str(class.into()));
+    obj
+}
+
+fn upgrade(realm: &Weak<WasmRealm>) -> Result<Rc<WasmRealm>, String> {
+    realm.upgrade().ok_or_else(|| "TypeError: WebAssembly realm is gone".into())
+}
+
+/// Hodnota pro reject promise: vyhozena JS hodnota, jinak chyba z textu.
+fn rejection(msg: &str) -> JsValue {
+    if let Some(v) = bytecode::take_thrown(msg) {
+        return v;
+    }
+    let (name, message) = match msg.split_once(": ") {
+        Some((name, rest)) if name.ends_with("Error") => (name, rest),
+        _ => ("Error", msg),
+    };
+    let mut err = JsObject::new();
+    err.set("name".into(), JsValue::Str(name.into()));
+    err.set("message".into(), JsValue::Str(message.into()));
+    err.set("stack".into(), JsValue::Str(format!("{name}: {message}")));
+    into_value(err)
+}
+
+fn settled(result: Result<JsValue, String>) -> JsValue {
+    match result {
+        Ok(v) => make_settled_promise("fulfilled", v),
+        Err(msg) => make_settled_promise("rejected", rejection(&msg)),
+    }
+}
+
+/// Bajty BufferSource argumentu.
+fn source_bytes(v: &JsValue, ctx: &str) -> Result<Vec<u8>, String> {
+    bytes_of(v).ok_or_else(|| format!("TypeError: {ctx}: Argument 0 must be a buffer source"))
+}
+
+/// Index modulu za objektem `WebAssembly.Module`.
+fn module_id(v: &JsValue) -> Option<usize> {
+    let JsValue::Object(o) = v else { return None };
+    match o.borrow().get(MODULE_KEY) {
+        JsValue::Number(n) => Some(n as usize),
+        _ => None,
+    }
+}
+
+/// Celociselna vlastnost deskriptoru (`initial`, `maximum`, ...) v rozsahu u32.
+fn descriptor_u32(desc: &JsValue, key: &str, ctx: &str) -> Result<Option<u32>, String> {
+    let JsValue::Object(o) = desc else {
+        return Err(format!("TypeError: {ctx}: Argument 0 must be a {} descriptor", ctx.trim_start_matches("WebAssembly.").trim_end_matches("()").to_lowercase()));
+    };
+    let v = o.borrow().get(key);
+    if matches!(v, JsValue::Undefined) {
+        return Ok(None);
+    }
+    let n = v.to_number();
+    if !n.is_finite() || n < 0.0 || n > u32::MAX as f64 {
+        return Err(format!("TypeError: {ctx}: Property '{key}': value {v} is not a valid unsigned 32-bit integer"));
+    }
+    Ok(Some(n.trunc() as u32))
+}
+
+/// `initial` (alias `minimum`) a `maximum` deskriptoru; `initial <= maximum <= limit`.
+fn limits(desc: &JsValue, ctx: &str, limit: u32) -> Result<(u32, Option<u32>), String> {
+    let initial = match descriptor_u32(desc, "initial", ctx)? {
+        Some(n) => n,
+        None => descriptor_u32(desc, "minimum", ctx)?
+            .ok_or_else(|| format!("TypeError: {ctx}: Property 'initial' is required"))?,
+    };
+    let maximum = descriptor_u32(desc, "maximum", ctx)?;
+    if initial > limit {
+        return Err(format!("RangeError: {ctx}: Property 'initial': value {initial} is above the upper bound {limit}"));
+    }
+    if let Some(max) = maximum && max < initial {
+        return Err(format!("RangeError: {ctx}: Property 'maximum': value {max} is below the lower bound {initial}"));
+    }
+    Ok((initial, maximum))
+}
+
+fn descriptor_str(desc: &JsValue, key: &str) -> Option<String> {
+    match desc {
+        JsValue::Object(o) => match o.borrow().get(key) {
+            JsValue::Undefined => None,
+            v => Some(v.to_string()),
+        },
+        _ => None,
+    }
+}
+
+// ─── Module / Instance ───────────────────────────────────────────────────────
+
+/// JS objekt `WebAssembly.Module` - metadata pro `Module.imports()` atd.
+fn module_object(realm: &WasmRealm, id: usize) -> JsValue {
+    let mut obj = class_object("WebAssembly.Module");
+    obj.set(MODULE_KEY.into(), JsValue::Number(id as f64));
+    if let Some(compiled) = realm.module(id) {
+        let imports = compiled.info.imports.iter()
+            .map(|i| array(vec![JsValue::Str(i.module_name.clone()), JsValue::Str(i.field_name.clone()), JsValue::Str(i.kind.as_str().into())]))
+            .collect();
+        let exports = compiled.info.exports.iter()
+            .map(|e| array(vec![JsValue::Str(e.name.clone()), JsValue::Str(e.kind.as_str().into())]))
+            .collect();
+        let custom = compiled.custom_sections.iter()
+            .map(|(name, range)| array(vec![JsValue::Str(name.clone()), array_buffer_value(compiled.info.bytes[range.clone()].to_vec())]))
+            .collect();
+        obj.set("__wasm_imports__".into(), array(imports));
+        obj.set("__wasm_exports__".into(), array(exports));
+        obj.set("__wasm_custom__".into(), array(custom));
+    }
+    into_value(obj)
+}
+
+/// JS objekt `WebAssembly.Instance` se zmrazenym `exports`.
+fn instance_object(realm: &Rc<WasmRealm>, id: usize, instance: wasmi::Instance) -> JsValue {
+    let mut exports = JsObject::new();
+    for (name, value) in realm.instance_exports(id, instance) {
+        let value = match value {
+            ExportValue::Function(f) => *f,
+            ExportValue::Handle(h) => handle_value(realm, h),
+        };
+        exports.set(name.into(), value);
+    }
+    exports.freeze();
+    let mut obj = class_object("WebAssembly.Instance");
+    obj.set("exports".into(), into_value(exports));
+    into_value(obj)
+}
+
+fn instantiate(realm: &Rc<WasmRealm>, module: &JsValue, imports: &JsValue) -> Result<JsValue, String> {
+    let id = module_id(module).ok_or("TypeError: WebAssembly.Instance(): Argument 0 must be a WebAssembly.Module")?;
+    let instance = realm.instantiate(id, imports)?;
+    Ok(instance_object(realm, id, instance))
+}
+
+/// `WebAssembly.instantiate(bytes)` - `{ module, instance }`.
+fn instantiate_bytes(realm: &Rc<WasmRealm>, bytes: Vec<u8>, imports: &JsValue) -> Result<JsValue, String> {
+    let module = module_object(realm, realm.compile(bytes)?);
+    let instance = instantiate(realm, &module, imports)?;
+    let mut result = JsObject::new();
+    result.set("module".into(), module);
+    result.set("instance".into(), instance);
+    Ok(into_value(result))
+}
+
+/// Telo odpovedi pro `*Streaming`: Response (nebo promise na ni) s `ok`
+/// statusem a MIME typem `application/wasm`.
+fn response_bytes(source: &JsValue, ctx: &str) -> Result<Vec<u8>, String> {
+    let response = match wasm::settle_fetch(source) {
+        Some((state, v)) if state == "fulfilled" => v,
+        Some((state, v)) if state == "rejected" => return Err(bytecode::throw_value(v)),
+        Some(_) => return Err(format!("TypeError: {ctx}: response did not arrive")),
+        None => source.clone(),
+    };
+    let JsValue::Object(r) = &response else {
+        return Err(format!("TypeError: {ctx}: Argument 0 must be provided and must be a Response"));
+    };
+    let r = r.borrow();
+    if !matches!(r.get("__response__"), JsValue::Bool(true)) {
+        return Err(format!("TypeError: {ctx}: Argument 0 must be provided and must be a Response"));
+    }
+    if !matches!(r.get("ok"), JsValue::Bool(true)) {
+        return Err(format!("TypeError: {ctx}: HTTP status code is not ok"));
+    }
+    let content_type = match r.get("headers") {
+        JsValue::Object(h) => h.borrow().get("content-type").to_string(),
+        _ => String::new(),
+    };
+    let essence = content_type.split(';').next().unwrap_or("").trim().to_ascii_lowercase();
+    if essence != "application/wasm" {
+        return Err(format!("TypeError: {ctx}: Incorrect response MIME type. Expected 'application/wasm'."));
+    }
+    Ok(bytes_of(&r.get("__body_bytes__")).unwrap_or_else(|| r.get("__body__").to_string().into_bytes()))
+}
+
+// ─── Memory / Table / Global ─────────────────────────────────────────────────
+
+/// JS objekt handle - pro tentyz handle vzdy tentyz objekt.
+fn handle_value(realm: &Rc<WasmRealm>, h: usize) -> JsValue {
+    if let Some(obj) = realm.handle_object(h) {
+        return obj;
+    }
+    let weak = Rc::downgrade(realm);
+    let obj = match realm.handle(h) {
+        Some(Extern::Memory(_)) => memory_object(weak, h),
+        Some(Extern::Table(_)) => table_object(weak, h),
+        Some(Extern::Global(_)) => global_object(weak, h),
+        _ => return JsValue::Undefined,
+    };
+    realm.set_handle_object(h, obj.clone());
+    obj
+}
+
+fn handle_object(class: &str, h: usize) -> JsObject {
+    let mut obj = class_object(class);
+    obj.set(HANDLE_KEY.into(), JsValue::Number(h as f64));
+    obj
+}
+
+fn memory_object(realm: Weak<WasmRealm>, h: usize) -> JsValue {
+    let mut obj = handle_object("WebAssembly.Memory", h);
+    let r = realm.clone();
+    obj.define_accessor("buffer".into(), Some(native("WebAssembly.Memory.buffer", move |_| {
+        Ok(upgrade(&r)?.memory_buffer(h))
+    })), None);
+    obj.define_hidden("grow".into(), native("WebAssembly.Memory.grow", move |a| {
+        let delta = arg(&a, 0).to_number();
+        if !delta.is_finite() || delta < 0.0 || delta > u32::MAX as f64 {
+            return Err("TypeError: WebAssembly.Memory.grow(): Argument 0 must be convertible to a valid number".into());
+        }
+        let previous = upgrade(&realm)?.memory_grow(h, delta as u32)?;
+        Ok(JsValue::Number(previous as f64))
+    }));
+    into_value(obj)
+}
+
+fn table_object(realm: Weak<WasmRealm>, h: usize) -> JsValue {
+    let mut obj = handle_object("WebAssembly.Table", h);
+    let r = realm.clone();
+    obj.define_accessor("length".into(), Some(native("WebAssembly.Table.length", move |_| {
+        Ok(JsValue::Number(upgrade(&r)?.table_length(h)? as f64))
+    })), None);
+    let r = realm.clone();
+    obj.define_hidden("get".into(), native("WebAssembly.Table.get", move |a| {
+        upgrade(&r)?.table_get(h, arg(&a, 0).to_number() as u32)
+    }));
+    let r = realm.clone();
+    obj.define_hidden("set".into(), native("WebAssembly.Table.set", move |a| {
+        let realm = upgrade(&r)?;
+        let value = match a.get(1) {
+            Some(v) => v.clone(),
+            None => WasmRealm::default_value(realm.table_element(h)?),
+        };
+        realm.table_set(h, arg(&a, 0).to_number() as u32, &value)?;
+        Ok(JsValue::Undefined)
+    }));
+    obj.define_hidden("grow".into(), native("WebAssembly.Table.grow", move |a| {
+        let realm = upgrade(&realm)?;
+        let init = match a.get(1) {
+            Some(v) => v.clone(),
+            None => WasmRealm::default_value(realm.table_element(h)?),
+        };
+        let previous = realm.table_grow(h, arg(&a, 0).to_number() as u32, &init)?;
+        Ok(JsValue::Number(previous as f64))
+    }));
+    into_value(obj)
+}
+
+fn global_object(realm: Weak<WasmRealm>, h: usize) -> JsValue {
+    let mut obj = handle_object("WebAssembly.Global", h);
+    let (r, w) = (realm.clone(), realm.clone());
+    obj.define_accessor("value".into(),
+        Some(native("WebAssembly.Global.value", move |_| upgrade(&r)?.global_get(h))),
+        Some(native("WebAssembly.Global.value", move |a| {
+            upgrade(&w)?.global_set(h, &arg(&a, 0))?;
+            Ok(JsValue::Undefined)
+        })));
+    obj.define_hidden("valueOf".into(), native("WebAssembly.Global.valueOf", move |_| upgrade(&realm)?.global_get(h)));
+    into_value(obj)
+}
+
+// ─── Tag / Exception ─────────────────────────────────────────────────────────
+
+/// Typy parametru tagu (`__wasm_tag__`).
+fn tag_params(tag: &JsValue) -> Option<Vec<String>> {
+    let JsValue::Object(o) = tag else { return None };
+    match o.borrow().get("__wasm_tag__") {
+        JsValue::Array(params) => Some(params.borrow().iter().map(|p| p.to_string()).collect()),
+        _ => None,
+    }
+}
+
+fn same_object(a: &JsValue, b: &JsValue) -> bool {
+    matches!((a, b), (JsValue::Object(x), JsValue::Object(y)) if Rc::ptr_eq(x, y))
+}
+
+fn tag_object(params: Vec<String>) -> JsValue {
+    let mut obj = class_object("WebAssembly.Tag");
+    let list: Vec<JsValue> = params.into_iter().map(JsValue::Str).collect();
+    obj.set("__wasm_tag__".into(), array(list.clone()));
+    obj.define_hidden("type".into(), native("WebAssembly.Tag.type", move |_| {
+        let mut ty = JsObject::new();
+        ty.set("parameters".into(), array(list.clone()));
+        Ok(into_value(ty))
+    }));
+    into_value(obj)
+}
+
+fn exception_object(tag: JsValue, payload: Vec<JsValue>, trace_stack: bool) -> JsValue {
+    let mut obj = class_object("WebAssembly.Exception");
+    obj.set("__wasm_exception_tag__".into(), tag.clone());
+    if trace_stack {
+        obj.set("stack".into(), JsValue::Str("WebAssembly.Exception\n    at <anonymous>".into()));
+    }
+    let t = tag.clone();
+    obj.define_hidden("is".into(), native("WebAssembly.Exception.is", move |a| {
+        Ok(JsValue::Bool(same_object(&t, &arg(&a, 0))))
+    }));
+    obj.define_hidden("getArg".into(), native("WebAssembly.Exception.getArg", move |a| {
+        if !same_object(&tag, &arg(&a, 0)) {
+            return Err("TypeError: WebAssembly.Exception.getArg(): First argument does not match the exception tag".into());
+        }
+        let index = arg(&a, 1).to_number();
+        payload.get(index as usize).filter(|_| index >= 0.0).cloned()
+            .ok_or_else(|| "RangeError: WebAssembly.Exception.getArg(): Index out of range".into())
+    }));
+    into_value(obj)
+}
+
+// ─── Setup ───────────────────────────────────────────────────────────────────
+
+pub fn setup_webassembly(e: &mut Environment) {
+    let realm = WasmRealm::new();
+    let mut wa = JsObject::new();
+    wa.define_hidden(symbols::TO_STRING_TAG.into(), JsValue::Str("WebAssembly".into()));
+
+    let r = Rc::clone(&realm);
+    wa.set("validate".into(), native("WebAssembly.validate", move |a| {
+        Ok(JsValue::Bool(r.validate(&source_bytes(&arg(&a, 0), "WebAssembly.validate()")?)))
+    }));
+    let r = Rc::clone(&realm);
+    wa.set("Module".into(), native("WebAssembly.Module", move |a| {
+        let bytes = source_bytes(&arg(&a, 0), "WebAssembly.Module()")?;
+        Ok(module_object(&r, r.compile(bytes)?))
+    }));
+    let r = Rc::clone(&realm);
+    wa.set("Instance".into(), native("WebAssembly.Instance", move |a| {
+        instantiate(&r, &arg(&a, 0), &arg(&a, 1))
+    }));
+    let r = Rc::clone(&realm);
+    wa.set("compile".into(), native("WebAssembly.compile", move |a| {
+        Ok(settled(source_bytes(&arg(&a, 0), "WebAssembly.compile()")
+            .and_then(|bytes| r.compile(bytes))
+            .map(|id| module_object(&r, id))))
+    }));
+    // instantiate(bytes) -> { module, instance }; instantiate(Module) -> Instance
+    let r = Rc::clone(&realm);
+    wa.set("instantiate".into(), native("WebAssembly.instantiate", move |a| {
+        let (source, imports) = (arg(&a, 0), arg(&a, 1));
+        if module_id(&source).is_some() {
+            return Ok(settled(instantiate(&r, &source, &imports)));
+        }
+        Ok(settled(source_bytes(&source, "WebAssembly.instantiate()")
+            .and_then(|bytes| instantiate_bytes(&r, bytes, &imports))))
+    }));
+    let r = Rc::clone(&realm);
+    wa.set("compileStreaming".into(), native("WebAssembly.compileStreaming", move |a| {
+        Ok(settled(response_bytes(&arg(&a, 0), "WebAssembly.compileStreaming()")
+            .and_then(|bytes| r.compile(bytes))
+            .map(|id| module_object(&r, id))))
+    }));
+    let r = Rc::clone(&realm);
+    wa.set("instantiateStreaming".into(), native("WebAssembly.instantiateStreaming", move |a| {
+        Ok(settled(response_bytes(&arg(&a, 0), "WebAssembly.instantiateStreaming()")
+            .and_then(|bytes| instantiate_bytes(&r, bytes, &arg(&a, 1)))))
+    }));
+
+    // new Memory({ initial, maximum }) - `shared: true` je TypeError: `wasmi`
+    // nema vlakna, takze sdilenou pamet by zadny modul nemohl importovat.
+    let r = Rc::clone(&realm);
+    wa.set("Memory".into(), native("WebAssembly.Memory", move |a| {
+        const CTX: &str = "WebAssembly.Memory()";
+        let desc = arg(&a, 0);
+        let (initial, maximum) = limits(&desc, CTX, MAX_PAGES)?;
+        if let Some(max) = maximum && max > MAX_PAGES {
+            return Err(format!("RangeError: {CTX}: Property 'maximum': value {max} is above the upper bound {MAX_PAGES}"));
+        }
+        if matches!(&desc, JsValue::Object(o) if o.borrow().get("shared").is_truthy()) {
+            return Err(format!("TypeError: {CTX}: shared memory is not supported by this engine"));
+        }
+        Ok(handle_value(&r, r.new_memory(initial, maximum)?))
+    }));
+    let r = Rc::clone(&realm);
+    wa.set("Table".into(), native("WebAssembly.Table", move |a| {
+        const CTX: &str = "WebAssembly.Table()";
+        let desc = arg(&a, 0);
+        let element = descriptor_str(&desc, "element").and_then(|s| wasm::parse_val_type(&s))
+            .filter(|ty| wasm::is_reference(*ty))
+            .ok_or_else(|| format!("TypeError: {CTX}: Descriptor property 'element' must be a WebAssembly reference type"))?;
+        let (initial, maximum) = limits(&desc, CTX, 10_000_000)?;
+        let init = match a.get(1) {
+            Some(v) => v.clone(),
+            None => WasmRealm::default_value(element),
+        };
+        let h = r.new_table(element, initial, maximum, &init)?;
+        Ok(handle_value(&r, h))
+    }));
+    let r = Rc::clone(&realm);
+    wa.set("Global".into(), native("WebAssembly.Global", move |a| {
+        let desc = arg(&a, 0);
+        let ty = descriptor_str(&desc, "value").and_then(|s| wasm::parse_val_type(&s))
+            .ok_or("TypeError: WebAssembly.Global(): Descriptor property 'value' must be a WebAssembly type")?;
+        let mutable = matches!(&desc, JsValue::Object(o) if o.borrow().get("mutable").is_truthy());
+        let value = match a.get(1) {
+            Some(JsValue::Undefined) | None => WasmRealm::default_value(ty),
+            Some(v) => v.clone(),
+        };
+        let h = r.new_global(ty, mutable, &value)?;
+        Ok(handle_value(&r, h))
+    }));
+    wa.set("Tag".into(), native("WebAssembly.Tag", |a| {
+        let params = match &arg(&a, 0) {
+            JsValue::Object(o) => o.borrow().get("parameters"),
+            _ => JsValue::Undefined,
+        };
+        let JsValue::Array(params) = params else {
+            return Err("TypeError: WebAssembly.Tag(): Argument 0 must be a tag type with 'parameters'".into());
+        };
+        let params = params.borrow().iter().map(|p| {
+            let name = p.to_string();
+            wasm::parse_val_type(&name).map(|_| name)
+                .ok_or_else(|| format!("TypeError: WebAssembly.Tag(): Argument 0 parameter type at index #0 must be a value type, got {p}"))
+        }).collect::<Result<Vec<_>, _>>()?;
+        Ok(tag_object(params))
+    }));
+    wa.set("Exception".into(), native("WebAssembly.Exception", |a| {
+        let tag = arg(&a, 0);
+        let params = tag_params(&tag).ok_or("TypeError: WebAssembly.Exception(): Argument 0 must be a WebAssembly tag")?;
+        let JsValue::Array(payload) = arg(&a, 1) else {
+            return Err("TypeError: WebAssembly.Exception(): Argument 1 must be an iterable object".into());
+        };
+        let payload = payload.borrow().clone();
+        if payload.len() != params.len() {
+            return Err("TypeError: WebAssembly.Exception(): Number of exception values does not match signature length".into());
+        }
+        let payload = payload.iter().zip(&params)
+            .map(|(v, ty)| wasm::normalize_value(wasm::parse_val_type(ty).unwrap_or(wasmi::core::ValType::ExternRef), v))
+            .collect::<Result<Vec<_>, _>>()?;
+        let trace_stack = matches!(arg(&a, 2), JsValue::Object(o) if o.borrow().get("traceStack").is_truthy());
+        Ok(exception_object(tag, payload, trace_stack))
+    }));
+    for kind in ["CompileError", "LinkError", "RuntimeError"] {
+        wa.set(kind.into(), native(&format!("WebAssembly.{kind}"), move |a| {
+            let message = match arg(&a, 0) {
+                JsValue::Undefined => String::new(),
+                v => v.to_string(),
+            };
+            Ok(wasm::error_value(kind, &message, None))
+        }));
+    }
+    e.define("WebAssembly", into_value(wa));
+}
+
+/// `WebAssembly.Module.imports(m)` / `exports(m)` / `customSections(m, name)`.
+pub(crate) fn static_prop(ctor: &str, key: &str) -> Option<JsValue> {
+    if ctor != "WebAssembly.Module" {
+        return None;
+    }
+    let stored = |m: &JsValue, field: &str, ctx: &str| -> Result<Vec<JsValue>, String> {
+        let invalid = || format!("TypeError: WebAssembly.Module.{ctx}(): Argument 0 must be a WebAssembly.Module");
+        module_id(m).ok_or_else(invalid)?;
+        let JsValue::Object(o) = m else { return Err(invalid()) };
+        match o.borrow().get(field) {
+            JsValue::Array(items) => Ok(items.borrow().clone()),
+            _ => Ok(Vec::new()),
+        }
+    };
+    let strings = |entry: &JsValue| -> Vec<JsValue> {
+        match entry {
+            JsValue::Array(parts) => parts.borrow().clone(),
+            _ => Vec::new(),
+        }
+    };
+    match key {
+        "imports" => Some(native("WebAssembly.Module.imports", move |a| {
+            let items = stored(&arg(&a, 0), "__wasm_imports__", "imports")?.iter().map(|entry| {
+                let parts = strings(entry);
+                let mut d = JsObject::new();
+                d.set("module".into(), arg(&parts, 0));
+                d.set("name".into(), arg(&parts, 1));
+                d.set("kind".into(), arg(&parts, 2));
+                into_value(d)
+            }).collect();
+            Ok(array(items))
+        })),
+        "exports" => Some(native("WebAssembly.Module.exports", move |a| {
+            let items = stored(&arg(&a, 0), "__wasm_exports__", "exports")?.iter().map(|entry| {
+                let parts = strings(entry);
+                let mut d = JsObject::new();
+                d.set("name".into(), arg(&parts, 0));
+                d.set("kind".into(), arg(&parts, 1));
+                into_value(d)
+            }).collect();
+            Ok(array(items))
+        })),
+        "customSections" => Some(native("WebAssembly.Module.customSections", move |a| {
+            let name = match a.get(1) {
+                Some(JsValue::Undefined) | None => return Err("TypeError: WebAssembly.Module.customSections(): Argument 1 is required".into()),
+                Some(v) => v.to_string(),
+            };
+            let items = stored(&arg(&a, 0), "__wasm_custom__", "customSections")?.iter().filter_map(|entry| {
+                let parts = strings(entry);
+                // Kazde volani vraci nove ArrayBuffery
+                (arg(&parts, 0).to_string() == name).then(|| bytes_of(&arg(&parts, 1)).map(array_buffer_value)).flatten()
+            }).collect();
+            Ok(array(items))
+        })),
+        _ => None,
+    }
+}
@@ -0,0 +1,798 @@
+//! Vestavene metody hodnot pro VM (`arr.map`, `"s".split`, `f.call`, `p.then`, ...).
+//!
+//! Tree-walker je dispatchuje primo v `eval_call`; VM nema `Interpreter`, takze je
+//! vraci jako nativni closures navazane na prijemce (GetProp + CallMethod).
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crate::interpreter::{builtins_intl, builtins_temporal, builtins_typed_arrays, builtins_wasm, call_machinery, helpers, keyed_table, symbols::{self, JsSymbol}, tagged_template, JsFunc, JsValue};
+use helpers::CollectionIterKind;
+use super::{frames, runtime};
+
+type ArrayRc = Rc<RefCell<Vec<JsValue>>>;
+/// Telo metody navazane na prijemce typu `T`.
+type Method<T> = Box<dyn Fn(&T, Vec<JsValue>) -> Result<JsValue, String>>;
+
+/// Vestavena vlastnost `key` hodnoty `obj` (None = neexistuje).
+pub(super) fn builtin_prop(obj: &JsValue, key: &str) -> Option<JsValue> {
+    match obj {
+        JsValue::Array(a) => array_prop(a, key),
+        JsValue::Str(s) => string_prop(s, key),
+        JsValue::Function(f) => function_prop(obj, f, key),
+        JsValue::Object(o) => {
+            if o.borrow().props.contains_key("__promise_state__")
+                && let Some(m) = promise_prop(obj, key)
+            {
+                return Some(m);
+            }
+            if let Some(view) = builtins_typed_arrays::typed_view_of(obj)
+                && builtins_typed_arrays::CALLBACK_METHODS.contains(&key)
+            {
+                return Some(typed_array_callback_method(obj, view, key));
+            }
+            object_prop(obj, key)
+        }
+        JsValue::Map(_) | JsValue::Set(_) => collection_prop(obj, key),
+        JsValue::Number(n) => number_prop(*n, key),
+        JsValue::Bool(b) => {
+            let b = *b;
+            match key {
+                "toString" => Some(helpers::native("Boolean.toString", move |_| Ok(JsValue::Str(b.to_string())))),
+                "valueOf" => Some(helpers::native("Boolean.valueOf", move |_| Ok(JsValue::Bool(b)))),
+                _ => None,
+            }
+        }
+        JsValue::Symbol(s) => match key {
+            "description" => Some(s.description.clone().map(JsValue::Str).unwrap_or(JsValue::Undefined)),
+            "toString" => {
+                let text = s.display();
+                Some(helpers::native("Symbol.toString", move |_| Ok(JsValue::Str(text.clone()))))
+            }
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Vestavena vlastnost pod symbolovym klicem (`arr[Symbol.iterator]`, ...).
+pub(super) fn builtin_symbol_prop(obj: &JsValue, sym: &JsSymbol) -> Option<JsValue> {
+    if !symbols::ITERATOR.is(sym) { return None; }
+    match obj {
+        JsValue::Array(a) => array_prop(a, "values"),
+        JsValue::Set(_) => collection_prop(obj, "values"),
+        JsValue::Map(_) => collection_prop(obj, "entries"),
+        JsValue::Str(s) => {
+            let chars: Vec<JsValue> = s.chars().map(|c| JsValue::Str(c.to_string())).collect();
+            Some(helpers::native("String[Symbol.iterator]", move |_| Ok(helpers::make_iterator_from_values(chars.clone()))))
+        }
+        _ => None,
+    }
+}
+
+fn arg(args: &[JsValue], i: usize) -> JsValue {
+    args.get(i).cloned().unwrap_or(JsValue::Undefined)
+}
+
+/// Relativni index (`slice(-2)`) orezany do 0..len.
+fn rel_index(v: &JsValue, len: usize, default: usize) -> usize {
+    match v {
+        JsValue::Undefined => default,
+        v => {
+            let n = v.to_number();
+            let n = if n.is_nan() { 0.0 } else { n.trunc() };
+            if n < 0.0 { (len as f64 + n).max(0.0) as usize } else { (n as usize).min(len) }
+        }
+    }
+}
+
+fn callback(args: &[JsValue], what: &str) -> Result<JsValue, String> {
+    let f = arg(args, 0);
+    if !runtime::is_callable(&f) {
+        return Err(format!("TypeError: {f} is not a function ({what})"));
+    }
+    Ok(f)
+}
+
+/// `SameValueZero` (includes, Map/Set klice).
+fn same_value_zero(a: &JsValue, b: &JsValue) -> bool {
+    match (a, b) {
+        (JsValue::Number(x), JsValue::Number(y)) => x == y || (x.is_nan() && y.is_nan()),
+        _ => a.strict_eq(b),
+    }
+}
+
+// ─── Array ───────────────────────────────────────────────────────────────────
+
+fn array_prop(a: &ArrayRc, key: &str) -> Option<JsValue> {
+    if key == "length" { return Some(JsValue::Number(a.borrow().len() as f64)); }
+    if key == "raw" { return tagged_template::template_raw(a); }
+    let arr = Rc::clone(a);
+    let this = JsValue::Array(Rc::clone(a));
+    let m = |name: &str, f: Method<ArrayRc>| {
+        let arr = Rc::clone(&arr);
+        let mutating = matches!(name, "push" | "pop" | "shift" | "unshift" | "splice" | "reverse" | "sort" | "fill" | "copyWithin");
+        let name = name.to_string();
+        Some(helpers::native(&format!("Array.{name}"), move |args| {
+            if mutating && tagged_template::is_frozen_array(&arr) {
+                return Err(format!("TypeError: Cannot {name} frozen array"));
+            }
+            f(&arr, args)
+        }))
+    };
+    match key {
+        "push" => m(key, Box::new(|a, args| {
+            let mut v = a.borrow_mut();
+            v.extend(args);
+            Ok(JsValue::Number(v.len() as f64))
+        })),
+        "pop" => m(key, Box::new(|a, _| Ok(a.borrow_mut().pop().unwrap_or(JsValue::Undefined)))),
+        "shift" => m(key, Box::new(|a, _| {
+            let mut v = a.borrow_mut();
+            Ok(if v.is_empty() { JsValue::Undefined } else { v.remove(0) })
+        })),
+        "unshift" => m(key, Box::new(|a, args| {
+            let mut v = a.borrow_mut();
+            v.splice(0..0, args);
+            Ok(JsValue::Number(v.len() as f64))
+        })),
+        "indexOf" => m(key, Box::new(|a, args| {
+            let needle = arg(&args, 0);
+            let v = a.borrow();
+            let from = rel_index(&arg(&args, 1), v.len(), 0);
+            let i = v.iter().skip(from).position(|x| x.strict_eq(&needle)).map(|i| i + from);
+            Ok(JsValue::Number(i.map_or(-1.0, |i| i as f64)))
+        })),
+        "lastIndexOf" => m(key, Box::new(|a, args| {
+            let needle = arg(&args, 0);
+            let i = a.borrow().iter().rposition(|x| x.strict_eq(&needle));
+            Ok(JsValue::Number(i.map_or(-1.0, |i| i as f64)))
+        })),
+        "includes" => m(key, Box::new(|a, args| {
+            let needle = arg(&args, 0);
+            Ok(JsValue::Bool(a.borrow().iter().any(|x| same_value_zero(x, &needle))))
+        })),
+        "join" => m(key, Box::new(|a, args| {
+            let sep = match arg(&args, 0) {
+                JsValue::Undefined => ",".to_string(),
+                s => s.to_string(),
+            };
+            Ok(JsValue::Str(join(&a.borrow(), &sep)))
+        })),
+        "toString" => m(key, Box::new(|a, _| Ok(JsValue::Str(join(&a.borrow(), ","))))),
+        "reverse" => m(key, Box::new(|a, _| {
+            a.borrow_mut().reverse();
+            Ok(JsValue::Array(Rc::clone(a)))
+        })),
+        "slice" => m(key, Box::new(|a, args| {
+            let v = a.borrow();
+            let start = rel_index(&arg(&args, 0), v.len(), 0);
+            let end = rel_index(&arg(&args, 1), v.len(), v.len());
+            Ok(runtime::new_array(v.get(start..end.max(start)).map(<[JsValue]>::to_vec).unwrap_or_default()))
+        })),
+        "splice" => m(key, Box::new(|a, args| {
+            let mut v = a.borrow_mut();
+            let start = rel_index(&arg(&args, 0), v.len(), 0);
+            let count = match args.len() {
+                0 => 0,
+                1 => v.len() - start,
+                _ => (arg(&args, 1).to_number().max(0.0) as usize).min(v.len() - start),
+            };
+            let removed: Vec<JsValue> = v.splice(start..start + count, args.into_iter().skip(2)).collect();
+            Ok(runtime::new_array(removed))
+        })),
+        "concat" => m(key, Box::new(|a, args| {
+            let mut out = a.borrow().clone();
+            for x in args {
+                match x {
+                    JsValue::Array(b) => out.extend(b.borrow().iter().cloned()),
+                    other => out.push(other),
+                }
+            }
+            Ok(runtime::new_array(out))
+        })),
+        "map" | "filter" | "forEach" | "some" | "every" | "find" | "findIndex" | "findLast" | "findLastIndex" | "flatMap" => {
+            let name = key.to_string();
+            let this = this.clone();
+            m(key, Box::new(move |a, args| iterate(&name, a, &this, &args)))
+        }
+        "reduce" | "reduceRight" => {
+            let right = key == "reduceRight";
+            let this = this.clone();
+            m(key, Box::new(move |a, args| {
+                let f = callback(&args, "reduce")?;
+                let items = a.borrow().clone();
+                let mut idx: Vec<usize> = (0..items.len()).collect();
+                if right { idx.reverse(); }
+                let mut it = idx.into_iter();
+                let mut acc = match args.get(1) {
+                    Some(v) => v.clone(),
+                    None => match it.next() {
+                        Some(i) => items[i].clone(),
+                        None => return Err("TypeError: Reduce of empty array with no initial value".into()),
+                    },
+                };
+                for i in it {
+                    acc = runtime::call_function(&f, JsValue::Undefined,
+                        vec![acc, items[i].clone(), JsValue::Number(i as f64), this.clone()])?;
+                }
+                Ok(acc)
+            }))
+        }
+        "sort" => m(key, Box::new(|a, args| {
+            let cmp = arg(&args, 0);
+            let mut items = a.borrow().clone();
+            sort_values(&mut items, &cmp)?;
+            *a.borrow_mut() = items;
+            Ok(JsValue::Array(Rc::clone(a)))
+        })),
+        "flat" => m(key, Box::new(|a, args| {
+            let depth = match arg(&args, 0) {
+                JsValue::Undefined => 1,
+                d => d.to_number().max(0.0) as usize,
+            };
+            Ok(runtime::new_array(flatten(&a.borrow(), depth)))
+        })),
+        "fill" => m(key, Box::new(|a, args| {
+            let mut v = a.borrow_mut();
+            let len = v.len();
+            let start = rel_index(&arg(&args, 1), len, 0);
+            let end = rel_index(&arg(&args, 2), len, len);
+            for x in v.iter_mut().take(end).skip(start) { *x = arg(&args, 0); }
+            drop(v);
+            Ok(JsValue::Array(Rc::clone(a)))
+        })),
+        "at" => m(key, Box::new(|a, args| {
+            let v = a.borrow();
+            let n = arg(&args, 0).to_number().trunc();
+            let i = if n < 0.0 { v.len() as f64 + n } else { n };
+            Ok(if i >= 0.0 { v.get(i as usize).cloned().unwrap_or(JsValue::Undefined) } else { JsValue::Undefined })
+        })),
+        "keys" => m(key, Box::new(|a, _| {
+            let keys = (0..a.borrow().len()).map(|i| JsValue::Number(i as f64)).collect();
+            Ok(helpers::make_iterator_from_values(keys))
+        })),
+        "entries" => m(key, Box::new(|a, _| {
+            let entries = a.borrow().iter().enumerate()
+                .map(|(i, v)| runtime::new_array(vec![JsValue::Number(i as f64), v.clone()]))
+                .collect();
+            Ok(helpers::make_iterator_from_values(entries))
+        })),
+        "values" => m(key, Box::new(|a, _| Ok(helpers::make_iterator_from_values(a.borrow().clone())))),
+        _ => None,
+    }
+}
+
+fn join(items: &[JsValue], sep: &str) -> String {
+    items.iter()
+        .map(|x| match x {
+            JsValue::Undefined | JsValue::Null => String::new(),
+            other => other.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join(sep)
+}
+
+fn flatten(items: &[JsValue], depth: usize) -> Vec<JsValue> {
+    let mut out = Vec::new();
+    for x in items {
+        match x {
+            JsValue::Array(inner) if depth > 0 => out.extend(flatten(&inner.borrow(), depth - 1)),
+            other => out.push(other.clone()),
+        }
+    }
+    out
+}
+
+/// Callbackove metody poli (map/filter/find/...).
+fn iterate(name: &str, a: &ArrayRc, this: &JsValue, args: &[JsValue]) -> Result<JsValue, String> {
+    let f = callback(args, name)?;
+    let items = a.borrow().clone();
+    let call = |i: usize, v: &JsValue| {
+        runtime::call_function(&f, arg(args, 1), vec![v.clone(), JsValue::Number(i as f64), this.clone()])
+    };
+    let order: Vec<usize> = if name.starts_with("findLast") { (0..items.len()).rev().collect() } else { (0..items.len()).collect() };
+    let mut out = Vec::new();
+    for i in order {
+        let v = &items[i];
+        let r = call(i, v)?;
+        match name {
+            "map" => out.push(r),
+            "flatMap" => match r {
+                JsValue::Array(inner) => out.extend(inner.borrow().iter().cloned()),
+                other => out.push(other),
+            },
+            "filter" if r.is_truthy() => out.push(v.clone()),
+            "some" if r.is_truthy() => return Ok(JsValue::Bool(true)),
+            "every" if !r.is_truthy() => return Ok(JsValue::Bool(false)),
+            "find" | "findLast" if r.is_truthy() => return Ok(v.clone()),
+            "findIndex" | "findLastIndex" if r.is_truthy() => return Ok(JsValue::Number(i as f64)),
+            _ => {}
+        }
+    }
+    Ok(match name {
+        "map" | "filter" | "flatMap" => runtime::new_array(out),
+        "some" => JsValue::Bool(false),
+        "every" => JsValue::Bool(true),
+        "findIndex" | "findLastIndex" => JsValue::Number(-1.0),
+        _ => JsValue::Undefined,
+    })
+}
+
+/// Stabilni razeni (insertion merge) s komparatorem, ktery muze selhat.
+fn sort_values(items: &mut [JsValue], cmp: &JsValue) -> Result<(), String> {
+    let mut err = None;
+    items.sort_by(|a, b| {
+        use std::cmp::Ordering;
+        if err.is_some() { return Ordering::Equal; }
+        match (a, b) {
+            (JsValue::Undefined, JsValue::Undefined) => return Ordering::Equal,
+            (JsValue::Undefined, _) => return Ordering::Greater,
+            (_, JsValue::Undefined) => return Ordering::Less,
+            _ => {}
+        }
+        if matches!(cmp, JsValue::Undefined) {
+            return a.to_string().cmp(&b.to_string());
+        }
+        match runtime::call_function(cmp, JsValue::Undefined, vec![a.clone(), b.clone()]) {
+            Ok(r) => r.to_number().partial_cmp(&0.0).unwrap_or(Ordering::Equal),
+            Err(e) => {
+                err = Some(e);
+                Ordering::Equal
+            }
+        }
+    });
+    err.map_or(Ok(()), Err)
+}
+
+// ─── String ──────────────────────────────────────────────────────────────────
+
+fn string_prop(s: &str, key: &str) -> Option<JsValue> {
+    if key == "length" { return Some(JsValue::Number(s.encode_utf16().count() as f64)); }
+    if let Ok(i) = key.parse::<usize>() {
+        return s.chars().nth(i).map(|c| JsValue::Str(c.to_string()));
+    }
+    let text = s.to_string();
+    let m = |f: Method<String>| {
+        let text = text.clone();
+        Some(helpers::native(&format!("String.{key}"), move |args| f(&text, args)))
+    };
+    let str_arg = |args: &[JsValue], i: usize| match arg(args, i) {
+        JsValue::Undefined => String::new(),
+        v => v.to_string(),
+    };
+    match key {
+        "charAt" => m(Box::new(|s, args| {
+            let i = arg(&args, 0).to_number().max(0.0) as usize;
+            Ok(JsValue::Str(s.chars().nth(i).map(String::from).unwrap_or_default()))
+        })),
+        "charCodeAt" | "codePointAt" => m(Box::new(|s, args| {
+            let i = arg(&args, 0).to_number().max(0.0) as usize;
+            Ok(s.encode_utf16().nth(i).map_or(JsValue::Number(f64::NAN), |c| JsValue::Number(c as f64)))
+        })),
+        "at" => m(Box::new(|s, args| {
+            let chars: Vec<char> = s.chars().collect();
+            let n = arg(&args, 0).to_number().trunc();
+            let i = if n < 0.0 { chars.len() as f64 + n } else { n };
+            Ok(if i >= 0.0 { chars.get(i as usize).map_or(JsValue::Undefined, |c| JsValue::Str(c.to_string())) } else { JsValue::Undefined })
+        })),
+        "indexOf" => m(Box::new(move |s, args| {
+            let needle = str_arg(&args, 0);
+            let from = rel_index(&arg(&args, 1), s.chars().count(), 0);
+            let offset: usize = s.chars().take(from).map(char::len_utf8).sum();
+            Ok(JsValue::Number(s[offset..].find(&needle).map_or(-1.0, |b| s[..offset + b].chars().count() as f64)))
+        })),
+        "lastIndexOf" => m(Box::new(move |s, args| {
+            let needle = str_arg(&args, 0);
+            Ok(JsValue::Number(s.rfind(&needle).map_or(-1.0, |b| s[..b].chars().count() as f64)))
+        })),
+        "includes" => m(Box::new(move |s, args| Ok(JsValue::Bool(s.contains(&str_arg(&args, 0)))))),
+        "startsWith" => m(Box::new(move |s, args| Ok(JsValue::Bool(s.starts_with(&str_arg(&args, 0)))))),
+        "endsWith" => m(Box::new(move |s, args| Ok(JsValue::Bool(s.ends_with(&str_arg(&args, 0)))))),
+        "slice" | "substring" | "substr" => {
+            let kind = key.to_string();
+            m(Box::new(move |s, args| {
+                let chars: Vec<char> = s.chars().collect();
+                let len = chars.len();
+                let (start, end) = match kind.as_str() {
+                    "slice" => (rel_index(&arg(&args, 0), len, 0), rel_index(&arg(&args, 1), len, len)),
+                    "substr" => {
+                        let start = rel_index(&arg(&args, 0), len, 0);
+                        let count = match arg(&args, 1) {
+                            JsValue::Undefined => len,
+                            c => c.to_number().max(0.0) as usize,
+                        };
+                        (start, (start + count).min(len))
+                    }
+                    _ => {
+                        let clamp = |v: JsValue, d: usize| match v {
+                            JsValue::Undefined => d,
+                            v => (v.to_number().max(0.0) as usize).min(len),
+                        };
+                        let (a, b) = (clamp(arg(&args, 0), 0), clamp(arg(&args, 1), len));
+                        (a.min(b), a.max(b))
+                    }
+                };
+                Ok(JsValue::Str(chars.get(start..end.max(start)).map(|c| c.iter().collect()).unwrap_or_default()))
+            }))
+        }
+        "toUpperCase" | "toLocaleUpperCase" => m(Box::new(|s, _| Ok(JsValue::Str(s.to_uppercase())))),
+        "toLowerCase" | "toLocaleLowerCase" => m(Box::new(|s, _| Ok(JsValue::Str(s.to_lowercase())))),
+        "trim" => m(Box::new(|s, _| Ok(JsValue::Str(s.trim().to_string())))),
+        "trimStart" => m(Box::new(|s, _| Ok(JsValue::Str(s.trim_start().to_string())))),
+        "trimEnd" => m(Box::new(|s, _| Ok(JsValue::Str(s.trim_end().to_string())))),
+        "padStart" | "padEnd" => {
+            let start = key == "padStart";
+            m(Box::new(move |s, args| {
+                let target = arg(&args, 0).to_number().max(0.0) as usize;
+                let fill = match arg(&args, 1) {
+                    JsValue::Undefined => " ".to_string(),
+                    f => f.to_string(),
+                };
+                let len = s.chars().count();
+                if target <= len || fill.is_empty() { return Ok(JsValue::Str(s.to_string())); }
+                let pad: String = fill.chars().cycle().take(target - len).collect();
+                Ok(JsValue::Str(if start { pad + s } else { format!("{s}{pad}") }))
+            }))
+        }
+        "repeat" => m(Box::new(|s, args| {
+            let n = arg(&args, 0).to_number();
+            if n < 0.0 || n.is_infinite() { return Err("RangeError: Invalid count value".into()); }
+            Ok(JsValue::Str(s.repeat(n as usize)))
+        })),
+        "concat" => m(Box::new(|s, args| {
+            Ok(JsValue::Str(args.iter().fold(s.to_string(), |acc, a| acc + &a.to_string())))
+        })),
+        "split" => m(Box::new(|s, args| {
+            let parts: Vec<JsValue> = match arg(&args, 0) {
+                JsValue::Undefined => vec![JsValue::Str(s.to_string())],
+                sep => match helpers::get_regex_parts(&sep) {
+                    Some((p, f)) => {
+                        let re = helpers::js_regex_to_rust(&p, &f)?;
+                        re.split(s).map(|p| JsValue::Str(p.to_string())).collect()
+                    }
+                    None => {
+                        let sep = sep.to_string();
+                        if sep.is_empty() {
+                            s.chars().map(|c| JsValue::Str(c.to_string())).collect()
+                        } else {
+                            s.split(sep.as_str()).map(|p| JsValue::Str(p.to_string())).collect()
+                        }
+                    }
+                },
+            };
+            let parts = match arg(&args, 1) {
+                JsValue::Undefined => parts,
+                n => parts.into_iter().take(n.to_number().max(0.0) as usize).collect(),
+            };
+            Ok(runtime::new_array(parts))
+        })),
+        "replace" | "replaceAll" => {
+            let all = key == "replaceAll";
+            m(Box::new(move |s, args| replace(s, &arg(&args, 0), &arg(&args, 1), all)))
+        }
+        "match" => m(Box::new(|s, args| {
+            let Some((p, f)) = helpers::get_regex_parts(&arg(&args, 0)) else {
+                let needle = arg(&args, 0).to_string();
+                return Ok(if s.contains(&needle) { runtime::new_array(vec![JsValue::Str(needle)]) } else { JsValue::Null });
+            };
+            if f.contains('g') {
+                let all = helpers::regex_match_all(&p, &f, s);
+                return Ok(if all.is_empty() { JsValue::Null } else { runtime::new_array(all.into_iter().map(JsValue::Str).collect()) });
+            }
+            Ok(match helpers::regex_exec(&p, &f, s) {
+                Some(groups) => runtime::new_array(groups.into_iter().map(|g| g.map_or(JsValue::Undefined, JsValue::Str)).collect()),
+                None => JsValue::Null,
+            })
+        })),
+        "localeCompare" => m(Box::new(move |s, args| {
+            Ok(JsValue::Number(match s.as_str().cmp(str_arg(&args, 0).as_str()) {
+                std::cmp::Ordering::Less => -1.0,
+                std::cmp::Ordering::Equal => 0.0,
+                std::cmp::Ordering::Greater => 1.0,
+            }))
+        })),
+        "toString" | "valueOf" => m(Box::new(|s, _| Ok(JsValue::Str(s.to_string())))),
+        _ => None,
+    }
+}
+
+/// `s.replace(pattern, replacement)` - string nebo regex vzor, string nebo funkce.
+fn replace(s: &str, pattern: &JsValue, repl: &JsValue, all: bool) -> Result<JsValue, String> {
+    let expand = |m: &str, groups: &[Option<String>], offset: usize| -> Result<String, String> {
+        if runtime::is_callable(repl) {
+            let mut args = vec![JsValue::Str(m.to_string())];
+            args.extend(groups.iter().map(|g| g.clone().map_or(JsValue::Undefined, JsValue::Str)));
+            args.push(JsValue::Number(offset as f64));
+            args.push(JsValue::Str(s.to_string()));
+            return Ok(runtime::call_function(repl, JsValue::Undefined, args)?.to_string());
+        }
+        let template = repl.to_string();
+        let mut out = String::new();
+        let mut chars = template.chars().peekable();
+        while let Some(c) = chars.next() {
+            match (c, chars.peek().copied()) {
+                ('$', Some('&')) => { chars.next(); out.push_str(m); }
+                ('$', Some('$')) => { chars.next(); out.push('$'); }
+                ('$', Some(d @ '1'..='9')) if (d as usize - '0' as usize) <= groups.len() => {
+                    chars.next();
+                    if let Some(Some(g)) = groups.get(d as usize - '1' as usize) { out.push_str(g); }
+                }
+                _ => out.push(c),
+            }
+        }
+        Ok(out)
+    };
+    let mut out = String::new();
+    let mut last = 0;
+    match helpers::get_regex_parts(pattern) {
+        Some((p, f)) => {
+            let re = helpers::js_regex_to_rust(&p, &f)?;
+            let global = all || f.contains('g');
+            for caps in re.captures_iter(s) {
+                let whole = caps.get(0).expect("group 0");
+                let groups: Vec<Option<String>> = (1..caps.len()).map(|i| caps.get(i).map(|g| g.as_str().to_string())).collect();
+                out.push_str(&s[last..whole.start()]);
+                out.push_str(&expand(whole.as_str(), &groups, whole.start())?);
+                last = whole.end();
+                if !global { break; }
+            }
+        }
+        None => {
+            let needle = pattern.to_string();
+            for (i, m) in s.match_indices(needle.as_str()) {
+                out.push_str(&s[last..i]);
+                out.push_str(&expand(m, &[], i)?);
+                last = i + m.len();
+                if !all { break; }
+            }
+        }
+    }
+    out.push_str(&s[last..]);
+    Ok(JsValue::Str(out))
+}
+
+// ─── Function ────────────────────────────────────────────────────────────────
+
+fn function_prop(this: &JsValue, f: &JsFunc, key: &str) -> Option<JsValue> {
+    let func = this.clone();
+    match key {
+        "call" => Some(helpers::native("Function.call", move |args| {
+            let mut it = args.into_iter();
+            let this = it.next().unwrap_or(JsValue::Undefined);
+            runtime::call_function(&func, this, it.collect())
+        })),
+        "apply" => Some(helpers::native("Function.apply", move |args| {
+            let this = arg(&args, 0);
+            let list = match arg(&args, 1) {
+                JsValue::Undefined | JsValue::Null => Vec::new(),
+                a => runtime::collect_iterable(&a)?,
+            };
+            runtime::call_function(&func, this, list)
+        })),
+        "bind" => Some(helpers::native("Function.bind", move |args| {
+            let mut it = args.into_iter();
+            let bound_this = it.next().unwrap_or(JsValue::Undefined);
+            Ok(JsValue::Function(JsFunc::Bound {
+                func: Box::new(func.clone()),
+                bound_this: Box::new(bound_this),
+                bound_args: it.collect(),
+                id: crate::interpreter::next_func_id(),
+            }))
+        })),
+        "name" => Some(JsValue::Str(match f {
+            JsFunc::VmCompiled { name, .. } => name.clone().unwrap_or_default(),
+            JsFunc::Native(n, _) => n.clone(),
+            JsFunc::Bound { func, .. } => format!("bound {}", runtime::get_prop(func, "name").ok()?),
+            _ => String::new(),
+        })),
+        "length" => Some(JsValue::Number(match f {
+            JsFunc::VmCompiled { compiled, .. } => compiled.arity as f64,
+            _ => 0.0,
+        })),
+        "toString" => {
+            let text = match f {
+                JsFunc::VmCompiled { name, .. } => format!("function {}() {{ [bytecode] }}", name.as_deref().unwrap_or("")),
+                _ => "function () { [native code] }".to_string(),
+            };
+            Some(helpers::native("Function.toString", move |_| Ok(JsValue::Str(text.clone()))))
+        }
+        "raw" if matches!(f, JsFunc::Native(n, _) if n == "String") => {
+            Some(helpers::native("String.raw", tagged_template::string_raw))
+        }
+        // Well-known symboly (`Symbol.iterator`, `Symbol.metadata`, ...)
+        _ if matches!(f, JsFunc::Native(n, _) if n == "Symbol") => symbols::well_known(key),
+        _ => match f {
+            JsFunc::Native(n, _) => builtins_typed_arrays::static_prop(n, key)
+                .or_else(|| builtins_wasm::static_prop(n, key))
+                .or_else(|| builtins_temporal::static_prop(n, key))
+                .or_else(|| builtins_intl::static_prop(n, key))
+                .or_else(|| call_machinery::with_host(|interp| interp.host_static(n, key)).flatten()),
+            _ => None,
+        },
+    }
+}
+
+// ─── Typed arrays ────────────────────────────────────────────────────────────
+
+/// Callback metoda typed array: pusti se metoda pole nad snapshotem hodnot
+/// a vysledek se prevede zpet (`map` -> typed array, `sort` zapise do bufferu).
+fn typed_array_callback_method(obj: &JsValue, view: crate::interpreter::typed_arrays::TypedArrayView, key: &str) -> JsValue {
+    let this = obj.clone();
+    let key = key.to_string();
+    helpers::native(&format!("TypedArray.{key}"), move |args| {
+        let mut values = view.to_values();
+        let result = if matches!(key.as_str(), "sort" | "toSorted") && matches!(arg(&args, 0), JsValue::Undefined) {
+            builtins_typed_arrays::sort_default(&mut values);
+            JsValue::Array(Rc::new(RefCell::new(values)))
+        } else {
+            let snapshot = Rc::new(RefCell::new(values));
+            let method = if key == "toSorted" { "sort" } else { key.as_str() };
+            let f = array_prop(&snapshot, method).ok_or_else(|| format!("TypeError: {key} is not a function"))?;
+            runtime::call_function(&f, JsValue::Array(Rc::clone(&snapshot)), args)?
+        };
+        builtins_typed_arrays::finish_callback_method(&this, &view, &key, result)
+    })
+}
+
+// ─── Object, Number, kolekce ─────────────────────────────────────────────────
+
+fn object_prop(obj: &JsValue, key: &str) -> Option<JsValue> {
+    let this = obj.clone();
+    match key {
+        "hasOwnProperty" => Some(helpers::native("Object.hasOwnProperty", move |args| {
+            let k = runtime::key_of(&arg(&args, 0));
+            Ok(JsValue::Bool(matches!(&this, JsValue::Object(o) if o.borrow().has_own(&k))))
+        })),
+        "toString" => Some(helpers::native("Object.toString", move |_| {
+            let tag = match runtime::get_key(&this, &symbols::TO_STRING_TAG.into())? {
+                JsValue::Str(t) => t,
+                _ => "Object".into(),
+            };
+            Ok(JsValue::Str(format!("[object {tag}]")))
+        })),
+        "valueOf" => Some(helpers::native("Object.valueOf", move |_| Ok(this.clone()))),
+        _ => None,
+    }
+}
+
+fn number_prop(n: f64, key: &str) -> Option<JsValue> {
+    match key {
+        "toFixed" => Some(helpers::native("Number.toFixed", move |args| {
+            let digits = arg(&args, 0).to_number().clamp(0.0, 100.0) as usize;
+            Ok(JsValue::Str(format!("{n:.digits$}")))
+        })),
+        "toString" => Some(helpers::native("Number.toString", move |args| {
+            Ok(JsValue::Str(match arg(&args, 0) {
+                JsValue::Undefined => JsValue::Number(n).to_string(),
+                r => {
+                    let radix = r.to_number() as u32;
+                    if !(2..=36).contains(&radix) { return Err("RangeError: toString() radix must be between 2 and 36".into()); }
+                    if n.fract() == 0.0 && n.is_finite() {
+                        let digits = helpers::radix_string(n.abs() as u64, radix);
+                        if n < 0.0 { format!("-{digits}") } else { digits }
+                    } else {
+                        JsValue::Number(n).to_string()
+                    }
+                }
+            }))
+        })),
+        "valueOf" => Some(helpers::native("Number.valueOf", move |_| Ok(JsValue::Number(n)))),
+        _ => None,
+    }
+}
+
+fn collection_prop(obj: &JsValue, key: &str) -> Option<JsValue> {
+    let this = obj.clone();
+    let m = |name: &'static str, f: Method<JsValue>| {
+        let this = this.clone();
+        Some(helpers::native(name, move |args| f(&this, args)))
+    };
+    // WeakMap/WeakSet: jen get/set/has/delete (add), zadna iterace ani size.
+    if helpers::is_weak_collection(obj) && !matches!(key, "get" | "set" | "has" | "delete" | "add") {
+        return None;
+    }
+    match (obj, key) {
+        (JsValue::Map(mp), "size") => Some(JsValue::Number(mp.borrow().len() as f64)),
+        (JsValue::Set(st), "size") => Some(JsValue::Number(st.borrow().len() as f64)),
+        (JsValue::Map(_), "get") => m("Map.get", Box::new(|t, args| {
+            let JsValue::Map(mp) = t else { unreachable!() };
+            Ok(mp.borrow().get(&arg(&args, 0)))
+        })),
+        (JsValue::Map(_), "set") => m("Map.set", Box::new(|t, args| {
+            let JsValue::Map(mp) = t else { unreachable!() };
+            let k = arg(&args, 0);
+            if mp.borrow().weak && !keyed_table::can_be_held_weakly(&k) {
+                return Err("TypeError: Invalid value used as weak map key".into());
+            }
+            mp.borrow_mut().set(k, arg(&args, 1));
+            Ok(t.clone())
+        })),
+        (JsValue::Map(_), "has") => m("Map.has", Box::new(|t, args| {
+            let JsValue::Map(mp) = t else { unreachable!() };
+            Ok(JsValue::Bool(mp.borrow().has(&arg(&args, 0))))
+        })),
+        (JsValue::Map(_), "delete") => m("Map.delete", Box::new(|t, args| {
+            let JsValue::Map(mp) = t else { unreachable!() };
+            Ok(JsValue::Bool(mp.borrow_mut().delete(&arg(&args, 0))))
+        })),
+        (JsValue::Set(_), "add") => m("Set.add", Box::new(|t, args| {
+            let JsValue::Set(st) = t else { unreachable!() };
+            let v = arg(&args, 0);
+            if st.borrow().weak && !keyed_table::can_be_held_weakly(&v) {
+                return Err("TypeError: Invalid value used in weak set".into());
+            }
+            st.borrow_mut().add(v);
+            Ok(t.clone())
+        })),
+        (JsValue::Set(_), "has") => m("Set.has", Box::new(|t, args| {
+            let JsValue::Set(st) = t else { unreachable!() };
+            Ok(JsValue::Bool(st.borrow().has(&arg(&args, 0))))
+        })),
+        (JsValue::Set(_), "delete") => m("Set.delete", Box::new(|t, args| {
+            let JsValue::Set(st) = t else { unreachable!() };
+            Ok(JsValue::Bool(st.borrow_mut().delete(&arg(&args, 0))))
+        })),
+        (_, "clear") => m("clear", Box::new(|t, _| {
+            match t {
+                JsValue::Map(mp) => mp.borrow_mut().table.clear(),
+                JsValue::Set(st) => st.borrow_mut().table.clear(),
+                _ => {}
+            }
+            Ok(JsValue::Undefined)
+        })),
+        (_, "forEach") => m("forEach", Box::new(|t, args| {
+            let f = callback(&args, "forEach")?;
+            // Zivy kurzor: callback smi kolekci menit.
+            let Some(cursor) = helpers::collection_cursor(t) else { return Ok(JsValue::Undefined) };
+            while let Some((k, v)) = helpers::collection_next(t, &cursor) {
+                runtime::call_function(&f, arg(&args, 1), vec![v, k, t.clone()])?;
+            }
+            Ok(JsValue::Undefined)
+        })),
+        (JsValue::Map(_), "keys") => m("keys", Box::new(|t, _| {
+            Ok(helpers::make_collection_iterator(t, CollectionIterKind::Keys))
+        })),
+        (JsValue::Set(_), "values") | (JsValue::Set(_), "keys") | (JsValue::Map(_), "values") => m("values", Box::new(|t, _| {
+            Ok(helpers::make_collection_iterator(t, CollectionIterKind::Values))
+        })),
+        (_, "entries") => m("entries", Box::new(|t, _| {
+            Ok(helpers::make_collection_iterator(t, CollectionIterKind::Entries))
+        })),
+        _ => None,
+    }
+}
+
+// ─── Promise ─────────────────────────────────────────────────────────────────
+
+fn promise_prop(p: &JsValue, key: &str) -> Option<JsValue> {
+    let p = p.clone();
+    match key {
+        "then" => Some(helpers::native("Promise.then", move |args| {
+            Ok(frames::promise_then(&p, arg(&args, 0), arg(&args, 1)))
+        })),
+        "catch" => Some(helpers::native("Promise.catch", move |args| {
+            Ok(frames::promise_then(&p, JsValue::Undefined, arg(&args, 0)))
+        })),
+        "finally" => Some(helpers::native("Promise.finally", move |args| {
+            let cb = arg(&args, 0);
+            let (on_f, on_r) = if runtime::is_callable(&cb) {
+                let (cb1, cb2) = (cb.clone(), cb);
+                (
+                    helpers::native("finally", move |a| {
+                        runtime::call_function(&cb1, JsValue::Undefined, vec![])?;
+                        Ok(arg(&a, 0))
+                    }),
+                    helpers::native("finally", move |a| {
+                        runtime::call_function(&cb2, JsValue::Undefined, vec![])?;
+                        Err(runtime::throw_value(arg(&a, 0)))
+                    }),
+                )
+            } else {
+                (JsValue::Undefined, JsValue::Undefined)
+            };
+            Ok(frames::promise_then(&p, on_f, on_r))
+        })),
+        _ => None,
+    }
+}
@@ -0,0 +1,2525 @@
+//! Kompilator AST -> `CodeBlock`.
+//!
+//! Kazda funkce ma vlastni `CodeBlock`; volne promenne resolvuje `SCOPES` stack
+//! (jeden `Scope` na rozpracovanou funkci) na closure captures. Deklarace seznamu
+//! prikazu (let/const/class/function, `var` na urovni funkce) dostanou slot hned na
+//! zacatku. Capture slotu, jehoz deklarace jeste neprobehla, z nej udela sdileny box
+//! (`LoadCell`/`StoreCell`) - dopredne reference a vzajemna rekurze tak funguji,
+//! kdezto uz inicializovane promenne se capturuji hodnotou.
+
+use std::cell::RefCell;
+use std::collections::HashSet;
+use std::rc::Rc;
+use std::str::FromStr;
+
+use num_bigint::BigInt;
+
+use crate::ast::*;
+use crate::browser::modules_esm::{default_export_name, DEFAULT_BINDING};
+use crate::interpreter::decorators::{self, DecoratorKind};
+use crate::interpreter::{disposal, strict_mode, tagged_template, JsValue};
+use super::{CaptureSource, CodeBlock, CompiledFunction, FrameKind, LoopFrame, Opcode};
+
+type CResult = Result<(), &'static str>;
+
+// ─── Resolve volnych promennych ──────────────────────────────────────────────
+
+/// Lexikalni kontext rozpracovane funkce.
+struct Scope {
+    /// Snapshot var_names obklopujiciho kodu v miste definice funkce.
+    outer_vars: Vec<String>,
+    /// Sloty obklopujiciho kodu drzici box (`CodeBlock::cells`).
+    outer_cells: Vec<u16>,
+    /// Sloty obklopujiciho kodu, jejichz deklarace jeste nebyla zkompilovana.
+    outer_uninit: Vec<u16>,
+    /// `const` sloty obklopujiciho kodu.
+    outer_consts: Vec<u16>,
+    /// Obklopujici kod je telo ne-arrow funkce (vlastni `this`/`arguments`).
+    outer_is_function: bool,
+    /// Obklopujici kod je arrow funkce - `this`/`arguments` se resolvuji o uroven vys.
+    outer_is_arrow: bool,
+    captures: Vec<(String, CaptureSource)>,
+    boxed: Vec<bool>,
+    /// Capture je `const` vazba - zapis do ni je TypeError.
+    consts: Vec<bool>,
+    /// Sloty obklopujiciho kodu, ktere capture prevedla na box.
+    new_cells: Vec<u16>,
+}
+
+thread_local! {
+    static SCOPES: RefCell<Vec<Scope>> = const { RefCell::new(Vec::new()) };
+}
+
+fn in_function() -> bool {
+    SCOPES.with(|s| !s.borrow().is_empty())
+}
+
+/// Telo arrow funkce: `this`/`arguments` jsou capture.
+fn in_arrow(code: &CodeBlock) -> bool {
+    !code.is_function && in_function()
+}
+
+/// Index capture pro `name` v prave kompilovane funkci (None = globalni promenna).
+fn resolve_capture(name: &str) -> Option<u16> {
+    SCOPES.with(|s| resolve_in(&mut s.borrow_mut(), name))
+}
+
+fn resolve_in(scopes: &mut [Scope], name: &str) -> Option<u16> {
+    let (scope, outer) = scopes.split_last_mut()?;
+    if let Some(i) = scope.captures.iter().position(|(n, _)| n == name) {
+        return Some(i as u16);
+    }
+    let mut boxed = false;
+    let mut is_const = false;
+    let src = if name == "this" || name == "arguments" {
+        if scope.outer_is_arrow {
+            CaptureSource::Capture(resolve_in(outer, name)?)
+        } else if name == "this" {
+            CaptureSource::This
+        } else if scope.outer_is_function {
+            CaptureSource::Arguments
+        } else {
+            return None;
+        }
+    } else if let Some(slot) = scope.outer_vars.iter().rposition(|n| n == name) {
+        let slot = slot as u16;
+        is_const = scope.outer_consts.contains(&slot);
+        let shared = scope.outer_cells.contains(&slot);
+        if is_const && !shared && !scope.outer_uninit.contains(&slot) {
+            // Inicializovanou `const` vazbu staci zkopirovat
+            CaptureSource::Local(slot)
+        } else {
+            // Zapisovatelnou vazbu closure sdili s obklopujicim kodem pres box
+            boxed = true;
+            if !shared {
+                scope.outer_cells.push(slot);
+                scope.new_cells.push(slot);
+            }
+            CaptureSource::Cell(slot)
+        }
+    } else {
+        let k = resolve_in(outer, name)?;
+        // Box obklopujici funkce sdilime (vidime jeji zapisy i late-bound hodnotu).
+        boxed = outer.last().is_some_and(|o| o.boxed[k as usize]);
+        is_const = outer.last().is_some_and(|o| o.consts[k as usize]);
+        CaptureSource::Capture(k)
+    };
+    scope.captures.push((name.to_string(), src));
+    scope.boxed.push(boxed);
+    scope.consts.push(is_const);
+    Some((scope.captures.len() - 1) as u16)
+}
+
+/// Capture `idx` je `const` vazba?
+fn capture_is_const(idx: u16) -> bool {
+    SCOPES.with(|s| s.borrow().last().is_some_and(|scope| scope.consts[idx as usize]))
+}
+
+/// Telo zapisuje do capture - VM ji drzi v boxu.
+fn mark_boxed(idx: u16) {
+    SCOPES.with(|s| {
+        if let Some(scope) = s.borrow_mut().last_mut() {
+            scope.boxed[idx as usize] = true;
+        }
+    });
+}
+
+// ─── Sloty a jmena ───────────────────────────────────────────────────────────
+
+fn emit_load_slot(slot: u16, code: &mut CodeBlock) {
+    if code.cells.contains(&slot) {
+        code.emit(Opcode::LoadCell(slot));
+    } else {
+        code.emit(Opcode::LoadVar(slot));
+    }
+}
+
+fn emit_store_slot(slot: u16, code: &mut CodeBlock) {
+    if code.cells.contains(&slot) {
+        code.emit(Opcode::StoreCell(slot));
+    } else {
+        code.emit(Opcode::StoreVar(slot));
+    }
+}
+
+/// Inicializace deklarovaneho slotu (pop hodnoty); slot uz neni "neinicializovany".
+fn emit_declare_slot(slot: u16, code: &mut CodeBlock) {
+    code.uninit.retain(|s| *s != slot);
+    if code.cells.contains(&slot) {
+        code.emit(Opcode::DeclareCell(slot));
+    } else {
+        code.emit(Opcode::DeclareVar(slot));
+    }
+}
+
+/// Pristup k `let`/`const`/`class` slotu pred jeho deklaraci (TDZ): misto
+/// pristupu vyhodi ReferenceError. Vraci true, kdyz chybu emitoval.
+fn emit_tdz_check(name: &str, slot: u16, code: &mut CodeBlock) -> bool {
+    if !code.uninit.contains(&slot) || !code.lexical.contains(&slot) {
+        return false;
+    }
+    let idx = code.push_string(&strict_mode::tdz_error(name));
+    code.emit(Opcode::ThrowError(idx));
+    true
+}
+
+fn emit_const_assign_error(code: &mut CodeBlock) {
+    let idx = code.push_string(strict_mode::CONST_ASSIGN);
+    code.emit(Opcode::ThrowError(idx));
+}
+
+fn emit_load_name(name: &str, code: &mut CodeBlock) {
+    match name {
+        "this" if !in_arrow(code) => {
+            code.emit(Opcode::LoadThis);
+            return;
+        }
+        "arguments" if code.is_function && code.find_local(name).is_none() => {
+            code.emit(Opcode::LoadArguments);
+            return;
+        }
+        _ => {}
+    }
+    if name != "this" && let Some(slot) = code.find_local(name) {
+        if !emit_tdz_check(name, slot, code) {
+            emit_load_slot(slot, code);
+        }
+    } else if let Some(k) = resolve_capture(name) {
+        code.emit(Opcode::LoadCapture(k));
+    } else {
+        match name {
+            "undefined" => { code.emit(Opcode::LoadUndefined); }
+            "NaN" => emit_const(JsValue::Number(f64::NAN), code),
+            "Infinity" => emit_const(JsValue::Number(f64::INFINITY), code),
+            _ => {
+                let idx = code.push_string(name);
+                code.emit(Opcode::LoadGlobal(idx));
+            }
+        }
+    }
+}
+
+/// `name` se resolvuje v globalnim scope (neni lokal ani capture)?
+fn is_global_name(name: &str, code: &CodeBlock) -> bool {
+    !matches!(name, "this" | "arguments" | "undefined" | "NaN" | "Infinity")
+        && code.find_local(name).is_none()
+        && resolve_capture(name).is_none()
+}
+
+/// Pop hodnoty do promenne `name` (lokal, capture nebo global).
+fn emit_store_name(name: &str, code: &mut CodeBlock) {
+    if let Some(slot) = code.find_local(name) {
+        if emit_tdz_check(name, slot, code) {
+            return;
+        }
+        if code.consts.contains(&slot) {
+            emit_const_assign_error(code);
+        } else {
+            emit_store_slot(slot, code);
+        }
+    } else if let Some(k) = resolve_capture(name) {
+        if capture_is_const(k) {
+            emit_const_assign_error(code);
+            return;
+        }
+        mark_boxed(k);
+        code.emit(Opcode::StoreCapture(k));
+    } else {
+        let idx = code.push_string(name);
+        code.emit(Opcode::StoreGlobal(idx));
+    }
+}
+
+fn emit_const(v: JsValue, code: &mut CodeBlock) {
+    let idx = code.push_const(v);
+    code.emit(Opcode::LoadConst(idx));
+}
+
+fn emit_str(s: &str, code: &mut CodeBlock) {
+    emit_const(JsValue::Str(s.to_string()), code);
+}
+
+/// Skryje jmena slotu od `saved` (konec bloku) - sloty zustavaji, jmena uz nejsou
+/// videt. Prefix `%%` (odlisny od docasnych `%tagN`) jmeno zachova pro chybu TDZ.
+fn end_scope(code: &mut CodeBlock, saved: usize) {
+    for n in code.var_names.iter_mut().skip(saved) {
+        if !n.starts_with('%') {
+            n.insert_str(0, "%%");
+        }
+    }
+}
+
+fn peel(s: &Stmt) -> &Stmt {
+    let mut s = s;
+    while let Stmt::WithLine { inner, .. } = s {
+        s = inner;
+    }
+    s
+}
+
+// ─── Hoisting ────────────────────────────────────────────────────────────────
+
+fn target_names(e: &Expr, out: &mut Vec<String>) {
+    match e {
+        Expr::Ident(n, _) => out.push(n.clone()),
+        Expr::Array(items, _) => {
+            for e in items.iter().flatten() { target_names(e, out); }
+        }
+        Expr::Object(props, _) => {
+            for p in props { target_names(&p.value, out); }
+        }
+        Expr::Spread(e, _) => target_names(e, out),
+        Expr::Assign { target, .. } => target_names(target, out),
+        _ => {}
+    }
+}
+
+/// Jmena `var` deklaraci tela (bez vnorenych funkci).
+fn collect_var_names(stmts: &[Stmt], out: &mut Vec<String>) {
+    for s in stmts { var_names_in(s, out); }
+}
+
+fn var_names_in(s: &Stmt, out: &mut Vec<String>) {
+    match s {
+        Stmt::Var { kind: VarKind::Var, decls, .. } => {
+            for d in decls { d.pattern.bound_names(out); }
+        }
+        Stmt::Block(b, _) => collect_var_names(b, out),
+        Stmt::If { yes, no, .. } => {
+            var_names_in(yes, out);
+            if let Some(n) = no { var_names_in(n, out); }
+        }
+        Stmt::While { body, .. } | Stmt::DoWhile { body, .. } | Stmt::Labeled { body, .. } => var_names_in(body, out),
+        Stmt::For { init, body, .. } => {
+            if let Some(ForInit::Var { kind: VarKind::Var, decls }) = init {
+                for d in decls { d.pattern.bound_names(out); }
+            }
+            var_names_in(body, out);
+        }
+        Stmt::ForIn { kind, target, body, .. } | Stmt::ForOf { kind, target, body, .. }
+        | Stmt::ForAwaitOf { kind, target, body, .. } => {
+            if *kind == Some(VarKind::Var) { target_names(target, out); }
+            var_names_in(body, out);
+        }
+        Stmt::Try { body, catch, finally, .. } => {
+            collect_var_names(body, out);
+            if let Some(c) = catch { collect_var_names(&c.body, out); }
+            if let Some(f) = finally { collect_var_names(f, out); }
+        }
+        Stmt::Switch { cases, .. } => {
+            for c in cases { collect_var_names(&c.body, out); }
+        }
+        Stmt::Export(ExportKind::Decl(d), _) => var_names_in(d, out),
+        Stmt::WithLine { inner, .. } => var_names_in(inner, out),
+        _ => {}
+    }
+}
+
+/// Predalokuje `var` sloty tela funkce.
+fn hoist_vars(stmts: &[Stmt], code: &mut CodeBlock) {
+    let mut names = Vec::new();
+    collect_var_names(stmts, &mut names);
+    for n in names {
+        if code.find_local(&n).is_none() {
+            let slot = code.push_local(&n);
+            code.uninit.push(slot);
+        }
+    }
+}
+
+/// `var` programu jsou vlastnosti globalniho scope (vidi je dalsi skripty).
+fn hoist_global_vars(stmts: &[Stmt], code: &mut CodeBlock) {
+    let mut names = Vec::new();
+    collect_var_names(stmts, &mut names);
+    for n in names {
+        let idx = code.push_string(&n);
+        code.emit(Opcode::DeclareGlobal(idx, false));
+    }
+}
+
+/// Kod programu mimo funkce: `var` a deklarace nejvyssi urovne jsou globalni.
+fn script_scope(code: &CodeBlock) -> bool {
+    !code.is_function && !in_function()
+}
+
+/// Jmeno deklarovane funkce (function/generator/async deklarace).
+fn fn_decl_name(s: &Stmt) -> Option<&str> {
+    match peel(s) {
+        Stmt::Function { name, .. } | Stmt::GeneratorFunc { name, .. }
+        | Stmt::AsyncFunc { name, .. } | Stmt::AsyncGeneratorFunc { name, .. } => Some(name),
+        Stmt::Export(ExportKind::Decl(d), _) => fn_decl_name(d),
+        _ => None,
+    }
+}
+
+/// Predalokuje sloty lexikalnich deklaraci seznamu; vraci slot deklarace funkce
+/// pro kazdy prikaz.
+fn hoist_lexical(stmts: &[&Stmt], code: &mut CodeBlock, top: bool) -> Vec<Option<u16>> {
+    let mut fn_slots = Vec::with_capacity(stmts.len());
+    for s in stmts {
+        let mut s = peel(s);
+        if let Stmt::Export(ExportKind::Decl(d), _) = s { s = peel(d); }
+        let mut names = Vec::new();
+        let mut is_const = false;
+        match s {
+            Stmt::Var { kind, decls, .. } if *kind != VarKind::Var => {
+                is_const = kind.is_const();
+                for d in decls { d.pattern.bound_names(&mut names); }
+            }
+            Stmt::Class { name, .. } => names.push(name.clone()),
+            _ => {}
+        }
+        if top {
+            // Lexikalni vazby programu zije v globalnim scope (TDZ do deklarace)
+            for n in names {
+                let idx = code.push_string(&n);
+                code.emit(Opcode::DeclareGlobal(idx, true));
+                if is_const { code.global_consts.push(n); }
+            }
+            fn_slots.push(None);
+            continue;
+        }
+        for n in names {
+            let slot = code.push_local(&n);
+            code.uninit.push(slot);
+            code.lexical.push(slot);
+            if is_const { code.consts.push(slot); }
+        }
+        fn_slots.push(fn_decl_name(s).map(|n| {
+            let slot = code.push_local(n);
+            code.uninit.push(slot);
+            slot
+        }));
+    }
+    fn_slots
+}
+
+// ─── Vyskyt identifikatoru (funkce volane pred svou deklaraci) ──────────────
+
+fn visit_expr(e: &Expr, f: &mut impl FnMut(&Expr)) {
+    f(e);
+    match e {
+        Expr::Template { expressions, .. } => {
+            for x in expressions { visit_expr(x, f); }
+        }
+        Expr::TaggedTemplate { tag, expressions, .. } => {
+            visit_expr(tag, f);
+            for x in expressions { visit_expr(x, f); }
+        }
+        Expr::Array(items, _) => {
+            for x in items.iter().flatten() { visit_expr(x, f); }
+        }
+        Expr::Object(props, _) => {
+            for p in props {
+                if let PropKey::Computed(k) = &p.key { visit_expr(k, f); }
+                visit_expr(&p.value, f);
+            }
+        }
+        Expr::Unary { arg, .. } => visit_expr(arg, f),
+        Expr::Binary { left, right, .. } | Expr::Logical { left, right, .. } => {
+            visit_expr(left, f);
+            visit_expr(right, f);
+        }
+        Expr::PrivateIn { object, .. } => visit_expr(object, f),
+        Expr::Ternary { test, yes, no, .. } => {
+            visit_expr(test, f);
+            visit_expr(yes, f);
+            visit_expr(no, f);
+        }
+        Expr::Assign { target, value, .. } => {
+            visit_expr(target, f);
+            visit_expr(value, f);
+        }
+        Expr::Call { callee, args, .. } | Expr::New { callee, args, .. } => {
+            visit_expr(callee, f);
+            for a in args { visit_expr(a, f); }
+        }
+        Expr::Member { object, prop, .. } => {
+            visit_expr(object, f);
+            if let MemberProp::Computed(k) = prop { visit_expr(k, f); }
+        }
+        Expr::Spread(x, _) | Expr::Await { value: x, .. } | Expr::DynamicImport(x, _) => visit_expr(x, f),
+        Expr::Yield { value: Some(x), .. } => visit_expr(x, f),
+        Expr::Sequence(xs, _) => {
+            for x in xs { visit_expr(x, f); }
+        }
+        Expr::ClassExpr { decorators, super_class, body, .. } => visit_class(decorators, super_class, body, f),
+        _ => {}
+    }
+}
+
+fn visit_class(decorators: &[Expr], super_class: &Option<Box<Expr>>, body: &[ClassMember], f: &mut impl FnMut(&Expr)) {
+    for d in decorators { visit_expr(d, f); }
+    if let Some(s) = super_class { visit_expr(s, f); }
+    for m in body {
+        for d in &m.decorators { visit_expr(d, f); }
+        if let Some(k) = &m.computed { visit_expr(k, f); }
+    }
+}
+
+/// Navstivi vyrazy prikazu mimo tela vnorenych funkci.
+fn visit_stmt(s: &Stmt, f: &mut impl FnMut(&Expr)) {
+    match s {
+        Stmt::Expr(e, _) | Stmt::Throw(e, _) | Stmt::Return(Some(e), _) => visit_expr(e, f),
+        Stmt::Block(b, _) => {
+            for s in b { visit_stmt(s, f); }
+        }
+        Stmt::Var { decls, .. } => {
+            for d in decls {
+                if let Some(i) = &d.init { visit_expr(i, f); }
+            }
+        }
+        Stmt::If { test, yes, no, .. } => {
+            visit_expr(test, f);
+            visit_stmt(yes, f);
+            if let Some(n) = no { visit_stmt(n, f); }
+        }
+        Stmt::While { test, body, .. } | Stmt::DoWhile { body, test, .. } => {
+            visit_expr(test, f);
+            visit_stmt(body, f);
+        }
+        Stmt::For { init, test, update, body, .. } => {
+            match init {
+                Some(ForInit::Var { decls, .. }) => {
+                    for d in decls {
+                        if let Some(i) = &d.init { visit_expr(i, f); }
+                    }
+                }
+                Some(ForInit::Expr(e)) => visit_expr(e, f),
+                None => {}
+            }
+            if let Some(t) = test { visit_expr(t, f); }
+            if let Some(u) = update { visit_expr(u, f); }
+            visit_stmt(body, f);
+        }
+        Stmt::ForIn { target, iter, body, .. } | Stmt::ForOf { target, iter, body, .. }
+        | Stmt::ForAwaitOf { target, iter, body, .. } => {
+            visit_expr(target, f);
+            visit_expr(iter, f);
+            visit_stmt(body, f);
+        }
+        Stmt::Try { body, catch, finally, .. } => {
+            for s in body { visit_stmt(s, f); }
+            if let Some(c) = catch {
+                for s in &c.body { visit_stmt(s, f); }
+            }
+            for s in finally.iter().flatten() { visit_stmt(s, f); }
+        }
+        Stmt::Labeled { body, .. } => visit_stmt(body, f),
+        Stmt::Switch { discriminant, cases, .. } => {
+            visit_expr(discriminant, f);
+            for c in cases {
+                if let Some(t) = &c.test { visit_expr(t, f); }
+                for s in &c.body { visit_stmt(s, f); }
+            }
+        }
+        Stmt::Class { decorators, super_class, body, .. } => visit_class(decorators, super_class, body, f),
+        Stmt::Export(ExportKind::Decl(d), _) => visit_stmt(d, f),
+        Stmt::Export(ExportKind::Default(e), _) => visit_expr(e, f),
+        Stmt::WithLine { inner, .. } => visit_stmt(inner, f),
+        _ => {}
+    }
+}
+
+/// Obsahuje telo modulu `await` mimo vnorene funkce (top-level await)?
+pub fn has_top_level_await(stmts: &[Stmt]) -> bool {
+    let mut found = false;
+    for s in stmts {
+        visit_stmt(s, &mut |e| found |= matches!(e, Expr::Await { .. }));
+    }
+    found
+}
+
+// ─── Seznamy prikazu ─────────────────────────────────────────────────────────
+
+/// Zkompiluje seznam prikazu (telo funkce, programu nebo bloku).
+///
+/// `top` = uroven programu (deklarace jsou vazby globalniho scope),
+/// `keep_last` = hodnota posledniho vyrazoveho prikazu zustane na stacku.
+fn compile_list(stmts: &[Stmt], code: &mut CodeBlock, top: bool, keep_last: bool) -> CResult {
+    // `using` deklarace: blok se prelozi na try/finally nad DisposableStack
+    if disposal::has_using(stmts) {
+        return compile_list(&disposal::lower_using_block(stmts), code, top, false);
+    }
+    let refs: Vec<&Stmt> = stmts.iter().collect();
+    let fn_slots = hoist_lexical(&refs, code, top);
+    // Funkce pouzite pred svou deklaraci vznikaji hned na zacatku seznamu.
+    let mut seen: HashSet<String> = HashSet::new();
+    let mut early = vec![false; stmts.len()];
+    for (i, s) in stmts.iter().enumerate() {
+        if let Some(name) = fn_decl_name(s) && seen.contains(name) {
+            early[i] = true;
+        }
+        visit_stmt(s, &mut |e| if let Expr::Ident(n, _) = e { seen.insert(n.clone()); });
+    }
+    for (i, s) in stmts.iter().enumerate() {
+        if early[i] {
+            compile_decl(s, code, fn_slots[i], top)?;
+        }
+    }
+    let last = stmts.len().checked_sub(1);
+    for (i, s) in stmts.iter().enumerate() {
+        if early[i] { continue; }
+        if keep_last && Some(i) == last && let Stmt::Expr(e, _) = peel(s) {
+            mark_stmt(s, code);
+            return compile_expr(e, code);
+        }
+        compile_decl(s, code, fn_slots[i], top)?;
+    }
+    Ok(())
+}
+
+/// Prikaz seznamu - deklarace funkce pouzije predalokovany slot.
+fn compile_decl(s: &Stmt, code: &mut CodeBlock, fn_slot: Option<u16>, top: bool) -> CResult {
+    mark_stmt(s, code);
+    match peel(s) {
+        Stmt::Export(ExportKind::Decl(d), _) => compile_decl(d, code, fn_slot, top),
+        s @ (Stmt::Function { name, params, body, .. } | Stmt::GeneratorFunc { name, params, body, .. }
+        | Stmt::AsyncFunc { name, params, body, .. } | Stmt::AsyncGeneratorFunc { name, params, body, .. }) => {
+            let def = FnDef {
+                name: Some(name),
+                params,
+                body: FnBody::Block(body),
+                is_arrow: false,
+                is_async: matches!(s, Stmt::AsyncFunc { .. } | Stmt::AsyncGeneratorFunc { .. }),
+                is_generator: matches!(s, Stmt::GeneratorFunc { .. } | Stmt::AsyncGeneratorFunc { .. }),
+                self_binding: true,
+                super_static: false,
+            };
+            emit_function(def, code)?;
+            if top {
+                let idx = code.push_string(name);
+                code.emit(Opcode::DefineGlobal(idx));
+                return Ok(());
+            }
+            let slot = fn_slot.unwrap_or_else(|| code.push_local(name));
+            emit_declare_slot(slot, code);
+            Ok(())
+        }
+        Stmt::Class { name, decorators, super_class, body, .. } => {
+            compile_class(Some(name), decorators, super_class.as_deref(), body, false, code)?;
+            bind_name(name, code, Bind::Let);
+            Ok(())
+        }
+        s => compile_stmt(s, code),
+    }
+}
+
+/// Zapise pozici prikazu do debug tabulky kodu.
+fn mark_stmt(s: &Stmt, code: &mut CodeBlock) {
+    if let Stmt::WithLine { span, .. } = s {
+        code.mark_span(*span);
+    }
+}
+
+fn compile_block(stmts: &[Stmt], code: &mut CodeBlock) -> CResult {
+    let saved = code.var_names.len();
+    compile_list(stmts, code, false, false)?;
+    end_scope(code, saved);
+    Ok(())
+}
+
+// ─── Prikazy ─────────────────────────────────────────────────────────────────
+
+pub fn compile_stmt(s: &Stmt, code: &mut CodeBlock) -> CResult {
+    match s {
+        Stmt::WithLine { inner, .. } => {
+            mark_stmt(s, code);
+            compile_stmt(inner, code)
+        }
+        Stmt::Expr(e, _) => {
+            compile_expr(e, code)?;
+            code.emit(Opcode::Pop);
+            Ok(())
+        }
+        Stmt::Block(b, _) => compile_block(b, code),
+        Stmt::Empty(_) => Ok(()),
+        Stmt::Return(value, _) => compile_return(value.as_ref(), code),
+        Stmt::Break(label, _) => compile_break(label.as_deref(), code),
+        Stmt::Continue(label, _) => compile_continue(label.as_deref(), code),
+        Stmt::Throw(e, _) => {
+            compile_expr(e, code)?;
+            code.emit(Opcode::Throw);
+            Ok(())
+        }
+        Stmt::Var { kind, decls, .. } => compile_var(*kind, decls, code, false),
+        Stmt::Function { .. } | Stmt::GeneratorFunc { .. } | Stmt::AsyncFunc { .. }
+        | Stmt::AsyncGeneratorFunc { .. } | Stmt::Class { .. } => compile_decl(s, code, None, false),
+        Stmt::If { test, yes, no, .. } => {
+            compile_expr(test, code)?;
+            let to_else = code.emit(Opcode::JmpIfFalse(0));
+            compile_stmt(yes, code)?;
+            match no {
+                Some(no) => {
+                    let to_end = code.emit(Opcode::Jmp(0));
+                    code.patch_here(to_else);
+                    compile_stmt(no, code)?;
+                    code.patch_here(to_end);
+                }
+                None => code.patch_here(to_else),
+            }
+            Ok(())
+        }
+        Stmt::While { test, body, .. } => {
+            let labels = std::mem::take(&mut code.pending_labels);
+            let start = code.bytecode.len();
+            compile_expr(test, code)?;
+            let to_end = code.emit(Opcode::JmpIfFalse(0));
+            let frame = loop_body(FrameKind::Loop, labels, body, code)?;
+            code.emit_jmp_to(start);
+            let end = code.bytecode.len();
+            code.patch_jmp(to_end, end);
+            finish_frame(frame, start, end, code);
+            Ok(())
+        }
+        Stmt::DoWhile { body, test, .. } => {
+            let labels = std::mem::take(&mut code.pending_labels);
+            let start = code.bytecode.len();
+            let frame = loop_body(FrameKind::Loop, labels, body, code)?;
+            let cont = code.bytecode.len();
+            compile_expr(test, code)?;
+            let back = code.emit(Opcode::JmpIfTrue(0));
+            code.patch_jmp(back, start);
+            let end = code.bytecode.len();
+            finish_frame(frame, cont, end, code);
+            Ok(())
+        }
+        Stmt::For { init, test, update, body, .. } => {
+            let labels = std::mem::take(&mut code.pending_labels);
+            let saved = code.var_names.len();
+            match init {
+                Some(ForInit::Var { kind, decls }) => compile_var(*kind, decls, code, true)?,
+                Some(ForInit::Expr(e)) => {
+                    compile_expr(e, code)?;
+                    code.emit(Opcode::Pop);
+                }
+                None => {}
+            }
+            let header = code.var_names.len();
+            let start = code.bytecode.len();
+            let to_end = match test {
+                Some(t) => {
+                    compile_expr(t, code)?;
+                    Some(code.emit(Opcode::JmpIfFalse(0)))
+                }
+                None => None,
+            };
+            let frame = loop_body(FrameKind::Loop, labels, body, code)?;
+            let cont = code.bytecode.len();
+            // Kazda iterace ma vlastni kopii `let` vazeb hlavicky (closures v tele)
+            if matches!(init, Some(ForInit::Var { kind, .. }) if *kind != VarKind::Var) {
+                for slot in saved..header {
+                    let slot = slot as u16;
+                    if code.cells.contains(&slot) { code.emit(Opcode::RenewCell(slot)); }
+                }
+            }
+            if let Some(u) = update {
+                compile_expr(u, code)?;
+                code.emit(Opcode::Pop);
+            }
+            code.emit_jmp_to(start);
+            let end = code.bytecode.len();
+            if let Some(j) = to_end { code.patch_jmp(j, end); }
+            finish_frame(frame, cont, end, code);
+            end_scope(code, saved);
+            Ok(())
+        }
+        Stmt::ForIn { kind, target, iter, body, .. } => {
+            compile_expr(iter, code)?;
+            code.emit(Opcode::ForInKeys);
+            code.emit(Opcode::GetIter(0));
+            compile_iter_loop(*kind, target, body, false, code)
+        }
+        // `for (using x of ...)` = `for (const %v of ...) { using x = %v; ... }`
+        Stmt::ForOf { kind: Some(k), .. } | Stmt::ForAwaitOf { kind: Some(k), .. } if k.is_using() => {
+            compile_stmt(&disposal::lower_for_of(s), code)
+        }
+        Stmt::ForOf { kind, target, iter, body, .. } => {
+            compile_expr(iter, code)?;
+            code.emit(Opcode::GetIter(0));
+            compile_iter_loop(*kind, target, body, false, code)
+        }
+        Stmt::ForAwaitOf { kind, target, iter, body, .. } => {
+            compile_expr(iter, code)?;
+            code.emit(Opcode::GetIter(1));
+            compile_iter_loop(*kind, target, body, true, code)
+        }
+        Stmt::Try { body, catch, finally, .. } => compile_try(body, catch.as_ref(), finally.as_ref(), code),
+        Stmt::Labeled { label, body, .. } => {
+            let inner = peel(body);
+            if matches!(inner, Stmt::While { .. } | Stmt::DoWhile { .. } | Stmt::For { .. }
+                | Stmt::ForIn { .. } | Stmt::ForOf { .. } | Stmt::ForAwaitOf { .. } | Stmt::Labeled { .. })
+            {
+                code.pending_labels.push(label.clone());
+                return compile_stmt(inner, code);
+            }
+            let mut labels = std::mem::take(&mut code.pending_labels);
+            labels.push(label.clone());
+            code.loop_stack.push(LoopFrame::new(FrameKind::Label, labels));
+            let r = compile_stmt(inner, code);
+            let frame = code.loop_stack.pop().expect("label frame");
+            r?;
+            let end = code.bytecode.len();
+            for j in frame.break_jumps { code.patch_jmp(j, end); }
+            Ok(())
+        }
+        Stmt::Switch { discriminant, cases, .. } => compile_switch(discriminant, cases, code),
+        Stmt::Import { .. } if code.module => Ok(()),
+        Stmt::Import { .. } => {
+            let idx = code.push_string("SyntaxError: Cannot use import statement outside a module");
+            code.emit(Opcode::ThrowError(idx));
+            Ok(())
+        }
+        Stmt::Export(ExportKind::Decl(d), _) => compile_stmt(d, code),
+        Stmt::Export(ExportKind::Default(e), _) if code.module => {
+            compile_expr(e, code)?;
+            // `export default function f() {}` binduje i jmeno `f`
+            if let Some(name) = default_export_name(e) {
+                code.emit(Opcode::Dup);
+                emit_define_global(name, code);
+            }
+            emit_define_global(DEFAULT_BINDING, code);
+            Ok(())
+        }
+        Stmt::Export(ExportKind::Default(e), _) => {
+            compile_expr(e, code)?;
+            code.emit(Opcode::Pop);
+            Ok(())
+        }
+        Stmt::Export(ExportKind::Named(_), _) => Ok(()),
+        Stmt::Export(ExportKind::From { .. } | ExportKind::Star { .. }, _) if code.module => Ok(()),
+        Stmt::Export(ExportKind::From { .. } | ExportKind::Star { .. }, _) => {
+            let idx = code.push_string("SyntaxError: Unexpected token 'export'");
+            code.emit(Opcode::ThrowError(idx));
+            Ok(())
+        }
+    }
+}
+
+/// `var`/`let`/`const` deklarace. `fresh` = vzdy novy slot (hlavicka for cyklu).
+fn compile_var(kind: VarKind, decls: &[VarDecl], code: &mut CodeBlock, fresh: bool) -> CResult {
+    let mode = match kind {
+        VarKind::Var => Bind::Var,
+        _ if fresh => Bind::Fresh,
+        _ => Bind::Let,
+    };
+    for d in decls {
+        match (&d.init, &d.pattern) {
+            (Some(init), Pattern::Ident(n)) => compile_named(init, n, code)?,
+            (Some(init), _) => compile_expr(init, code)?,
+            // `var x;` nemeni hodnotu, jen ukonci "neinicializovany" stav slotu
+            (None, Pattern::Ident(n)) if kind == VarKind::Var => {
+                if let Some(slot) = code.find_local(n) { code.uninit.retain(|s| *s != slot); }
+                continue;
+            }
+            (None, _) => { code.emit(Opcode::LoadUndefined); }
+        }
+        bind_pattern(&d.pattern, code, mode)?;
+    }
+    Ok(())
+}
+
+/// Telo cyklu v novem ramci; vraci ramec s nepatchnutymi skoky.
+fn loop_body(kind: FrameKind, labels: Vec<String>, body: &Stmt, code: &mut CodeBlock) -> Result<LoopFrame, &'static str> {
+    code.loop_stack.push(LoopFrame::new(kind, labels));
+    let r = compile_stmt(body, code);
+    let frame = code.loop_stack.pop().expect("loop frame");
+    r.map(|_| frame)
+}
+
+fn finish_frame(frame: LoopFrame, cont: usize, end: usize, code: &mut CodeBlock) {
+    for j in frame.continue_jumps { code.patch_jmp(j, cont); }
+    for j in frame.break_jumps { code.patch_jmp(j, end); }
+}
+
+/// for-in/for-of/for-await: na stacku je [iter, cursor].
+fn compile_iter_loop(kind: Option<VarKind>, target: &Expr, body: &Stmt, is_await: bool, code: &mut CodeBlock) -> CResult {
+    let labels = std::mem::take(&mut code.pending_labels);
+    let saved = code.var_names.len();
+    let head = code.bytecode.len();
+    let to_end = if is_await {
+        code.emit(Opcode::IterNextRaw);
+        code.emit(Opcode::Await);
+        let j = code.emit(Opcode::IterDone(0));
+        code.emit(Opcode::Await);
+        j
+    } else {
+        code.emit(Opcode::IterNext(0))
+    };
+    let mode = match kind {
+        Some(VarKind::Var) => Bind::Var,
+        Some(_) => Bind::Fresh,
+        None => Bind::Assign,
+    };
+    bind_expr_target(target, code, mode)?;
+    let frame = loop_body(FrameKind::Iter, labels, body, code)?;
+    code.emit_jmp_to(head);
+    let end = code.bytecode.len();
+    code.patch_jmp(to_end, end);
+    finish_frame(frame, head, end, code);
+    end_scope(code, saved);
+    Ok(())
+}
+
+/// Vystup z ramcu nad `target` (break/continue/return): PopTry, inline finally,
+/// zavreni iteratoru.
+fn emit_unwind(code: &mut CodeBlock, target: usize) -> CResult {
+    let mut i = code.loop_stack.len();
+    while i > target {
+        i -= 1;
+        match code.loop_stack[i].kind.clone() {
+            FrameKind::Try => { code.emit(Opcode::PopTry); }
+            FrameKind::Finally(body) => {
+                code.emit(Opcode::PopTry);
+                let above = code.loop_stack.split_off(i);
+                let r = compile_block(&body, code);
+                code.loop_stack.extend(above);
+                r?;
+            }
+            FrameKind::Iter => { code.emit(Opcode::IterClose); }
+            FrameKind::Loop | FrameKind::Switch | FrameKind::Label => {}
+        }
+    }
+    Ok(())
+}
+
+fn compile_return(value: Option<&Expr>, code: &mut CodeBlock) -> CResult {
+    match value {
+        Some(v) => compile_expr(v, code)?,
+        None => { code.emit(Opcode::LoadUndefined); }
+    }
+    let cleanup = code.loop_stack.iter().any(|f| matches!(f.kind, FrameKind::Finally(_) | FrameKind::Iter));
+    if cleanup {
+        let tmp = code.push_temp("ret");
+        code.emit(Opcode::DeclareVar(tmp));
+        emit_unwind(code, 0)?;
+        code.emit(Opcode::LoadVar(tmp));
+    }
+    code.emit(Opcode::Return);
+    Ok(())
+}
+
+fn compile_break(label: Option<&str>, code: &mut CodeBlock) -> CResult {
+    let target = code.loop_stack.iter().rposition(|f| match label {
+        Some(l) => f.labels.iter().any(|x| x == l),
+        None => matches!(f.kind, FrameKind::Loop | FrameKind::Iter | FrameKind::Switch),
+    }).ok_or("break mimo cyklus/switch")?;
+    emit_unwind(code, target + 1)?;
+    if matches!(code.loop_stack[target].kind, FrameKind::Iter) {
+        code.emit(Opcode::IterClose);
+    }
+    let j = code.emit(Opcode::Jmp(0));
+    code.loop_stack[target].break_jumps.push(j);
+    Ok(())
+}
+
+fn compile_continue(label: Option<&str>, code: &mut CodeBlock) -> CResult {
+    let target = code.loop_stack.iter().rposition(|f| {
+        matches!(f.kind, FrameKind::Loop | FrameKind::Iter)
+            && label.is_none_or(|l| f.labels.iter().any(|x| x == l))
+    }).ok_or("continue mimo cyklus")?;
+    emit_unwind(code, target + 1)?;
+    let j = code.emit(Opcode::Jmp(0));
+    code.loop_stack[target].continue_jumps.push(j);
+    Ok(())
+}
+
+/// try/catch/finally. Layout s finally:
+/// `PushFinally(fh) [PushTry(c)] telo [PopTry Jmp ac; c: catch] ac: PopTry finally Jmp end;
+/// fh: tmp = exc; finally; throw tmp; end:`
+fn compile_try(body: &[Stmt], catch: Option<&CatchClause>, finally: Option<&Vec<Stmt>>, code: &mut CodeBlock) -> CResult {
+    let push_finally = match finally {
+        Some(f) => {
+            let j = code.emit(Opcode::PushFinally(0));
+            code.loop_stack.push(LoopFrame::new(FrameKind::Finally(f.clone()), vec![]));
+            Some(j)
+        }
+        None => None,
+    };
+    let r = compile_try_catch(body, catch, code);
+    if push_finally.is_some() { code.loop_stack.pop(); }
+    r?;
+    if let (Some(pf), Some(f)) = (push_finally, finally) {
+        code.emit(Opcode::PopTry);
+        compile_block(f, code)?;
+        let to_end = code.emit(Opcode::Jmp(0));
+        let handler = code.bytecode.len();
+        code.patch_handler(pf, handler);
+        let tmp = code.push_temp("exc");
+        code.emit(Opcode::DeclareVar(tmp));
+        compile_block(f, code)?;
+        code.emit(Opcode::LoadVar(tmp));
+        code.emit(Opcode::Throw);
+        code.patch_here(to_end);
+    }
+    Ok(())
+}
+
+fn compile_try_catch(body: &[Stmt], catch: Option<&CatchClause>, code: &mut CodeBlock) -> CResult {
+    let Some(c) = catch else { return compile_block(body, code) };
+    let push = code.emit(Opcode::PushTry(0));
+    code.loop_stack.push(LoopFrame::new(FrameKind::Try, vec![]));
+    let r = compile_block(body, code);
+    code.loop_stack.pop();
+    r?;
+    code.emit(Opcode::PopTry);
+    let to_end = code.emit(Opcode::Jmp(0));
+    let handler = code.bytecode.len();
+    code.patch_handler(push, handler);
+    let saved = code.var_names.len();
+    match &c.param {
+        Some(p) => bind_name(p, code, Bind::Fresh),
+        None => { code.emit(Opcode::Pop); }
+    }
+    compile_list(&c.body, code, false, false)?;
+    end_scope(code, saved);
+    code.patch_here(to_end);
+    Ok(())
+}
+
+fn compile_switch(discriminant: &Expr, cases: &[SwitchCase], code: &mut CodeBlock) -> CResult {
+    let labels = std::mem::take(&mut code.pending_labels);
+    let saved = code.var_names.len();
+    compile_expr(discriminant, code)?;
+    let disc = code.push_temp("disc");
+    code.emit(Opcode::DeclareVar(disc));
+    // Deklarace vsech vetvi sdili jeden scope.
+    let all: Vec<&Stmt> = cases.iter().flat_map(|c| c.body.iter()).collect();
+    let fn_slots = hoist_lexical(&all, code, false);
+    let mut case_jumps = Vec::new();
+    for (i, c) in cases.iter().enumerate() {
+        if let Some(t) = &c.test {
+            code.emit(Opcode::LoadVar(disc));
+            compile_expr(t, code)?;
+            code.emit(Opcode::StrictEq);
+            case_jumps.push((i, code.emit(Opcode::JmpIfTrue(0))));
+        }
+    }
+    let to_default = code.emit(Opcode::Jmp(0));
+    code.loop_stack.push(LoopFrame::new(FrameKind::Switch, labels));
+    let mut default_at = None;
+    let mut slot_iter = fn_slots.into_iter();
+    let mut result = Ok(());
+    for (i, c) in cases.iter().enumerate() {
+        let here = code.bytecode.len();
+        for (_, j) in case_jumps.iter().filter(|(ci, _)| *ci == i) { code.patch_jmp(*j, here); }
+        if c.test.is_none() { default_at = Some(here); }
+        for s in &c.body {
+            let slot = slot_iter.next().flatten();
+            if result.is_ok() { result = compile_decl(s, code, slot, false); }
+        }
+    }
+    let frame = code.loop_stack.pop().expect("switch frame");
+    result?;
+    let end = code.bytecode.len();
+    code.patch_jmp(to_default, default_at.unwrap_or(end));
+    for j in frame.break_jumps { code.patch_jmp(j, end); }
+    end_scope(code, saved);
+    Ok(())
+}
+
+// ─── Vazani (deklarace, prirazeni, destrukturovani) ─────────────────────────
+
+#[derive(Clone, Copy, PartialEq)]
+enum Bind {
+    /// `var` - predalokovany slot funkce.
+    Var,
+    /// `let`/`const`/`class` - predalokovany slot seznamu (jinak novy).
+    Let,
+    /// Vzdy novy slot (parametry, catch, hlavicky cyklu).
+    Fresh,
+    /// Prirazeni do existujiciho cile.
+    Assign,
+}
+
+/// Pop hodnoty do jmena podle rezimu.
+fn bind_name(name: &str, code: &mut CodeBlock, mode: Bind) {
+    match mode {
+        Bind::Var if script_scope(code) => emit_define_global(name, code),
+        Bind::Var => {
+            let slot = code.find_local(name).unwrap_or_else(|| code.push_local(name));
+            emit_declare_slot(slot, code);
+        }
+        Bind::Let => {
+            let slot = match code.find_local(name) {
+                Some(s) if code.uninit.contains(&s) => s,
+                // Deklarace nejvyssi urovne programu (bez predalokovaneho slotu)
+                _ if script_scope(code) => return emit_define_global(name, code),
+                _ => code.push_local(name),
+            };
+            emit_declare_slot(slot, code);
+        }
+        Bind::Fresh => {
+            let slot = code.push_local(name);
+            code.lexical.push(slot);
+            code.emit(Opcode::DeclareVar(slot));
+        }
+        Bind::Assign => emit_store_name(name, code),
+    }
+}
+
+/// Pop hodnoty do globalni vazby `name` (deklarace nejvyssi urovne programu).
+fn emit_define_global(name: &str, code: &mut CodeBlock) {
+    let idx = code.push_string(name);
+    if code.global_consts.iter().any(|n| n == name) {
+        code.emit(Opcode::DefineGlobalConst(idx));
+    } else {
+        code.emit(Opcode::DefineGlobal(idx));
+    }
+}
+
+/// `JmpIfNotUndefKeep` + default hodnota (vzor/parametr).
+fn emit_default(default: &Expr, name_hint: Option<&str>, code: &mut CodeBlock) -> CResult {
+    let skip = code.emit(Opcode::JmpIfNotUndefKeep(0));
+    code.emit(Opcode::Pop);
+    match name_hint {
+        Some(n) => compile_named(default, n, code)?,
+        None => compile_expr(default, code)?,
+    }
+    code.patch_here(skip);
+    Ok(())
+}
+
+/// Klic vlastnosti objektoveho vzoru - bud string konstanta, nebo temp s vypoctenou hodnotou.
+enum PatKey {
+    Const(u16),
+    Temp(u16),
+}
+
+fn emit_pat_key(k: &PatKey, code: &mut CodeBlock) {
+    match k {
+        PatKey::Const(c) => { code.emit(Opcode::LoadConst(*c)); }
+        PatKey::Temp(t) => { code.emit(Opcode::LoadVar(*t)); }
+    }
+}
+
+/// Nacte vlastnost `key` ze zdroje na vrcholu stacku (zdroj zustava): [src] -> [src, v].
+fn emit_pattern_get(key: &PropKey, code: &mut CodeBlock, excluded: &mut Vec<PatKey>) -> CResult {
+    code.emit(Opcode::Dup);
+    match key {
+        PropKey::Ident(s) | PropKey::Str(s) => {
+            let idx = code.push_string(s);
+            code.emit(Opcode::GetProp(idx));
+            excluded.push(PatKey::Const(code.push_const(JsValue::Str(s.clone()))));
+        }
+        PropKey::Num(n) => {
+            let k = JsValue::Number(*n).to_string();
+            let idx = code.push_string(&k);
+            code.emit(Opcode::GetProp(idx));
+            excluded.push(PatKey::Const(code.push_const(JsValue::Str(k))));
+        }
+        PropKey::Computed(e) => {
+            compile_expr(e, code)?;
+            code.emit(Opcode::Dup);
+            let tmp = code.push_temp("key");
+            code.emit(Opcode::DeclareVar(tmp));
+            code.emit(Opcode::GetIndex);
+            excluded.push(PatKey::Temp(tmp));
+        }
+        PropKey::Spread => return Err("spread klic mimo rest"),
+    }
+    Ok(())
+}
+
+/// [src] -> [src, rest]: kopie vlastnosti bez uz vytazenych klicu.
+fn emit_object_rest(excluded: &[PatKey], code: &mut CodeBlock) -> CResult {
+    if excluded.len() >= u16::MAX as usize { return Err("prilis mnoho klicu vzoru"); }
+    code.emit(Opcode::Dup);
+    for k in excluded { emit_pat_key(k, code); }
+    code.emit(Opcode::ObjectRest(excluded.len() as u16));
+    Ok(())
+}
+
+/// Pop hodnoty do vzoru deklarace/parametru.
+fn bind_pattern(p: &Pattern, code: &mut CodeBlock, mode: Bind) -> CResult {
+    match p {
+        Pattern::Ident(n) => {
+            bind_name(n, code, mode);
+            Ok(())
+        }
+        Pattern::Array(elems) => {
+            code.emit(Opcode::GetIter(0));
+            for e in elems {
+                if e.rest {
+                    code.emit(Opcode::IterRest);
+                } else {
+                    code.emit(Opcode::IterStep);
+                    if let Some(d) = &e.default {
+                        let hint = match &e.pattern { Some(Pattern::Ident(n)) => Some(n.as_str()), _ => None };
+                        emit_default(d, hint, code)?;
+                    }
+                }
+                match &e.pattern {
+                    Some(p) => bind_pattern(p, code, mode)?,
+                    None => { code.emit(Opcode::Pop); }
+                }
+            }
+            code.emit(Opcode::IterClose);
+            Ok(())
+        }
+        Pattern::Object(props) => {
+            let mut excluded = Vec::new();
+            for prop in props {
+                if matches!(prop.key, PropKey::Spread) {
+                    emit_object_rest(&excluded, code)?;
+                } else {
+                    emit_pattern_get(&prop.key, code, &mut excluded)?;
+                    if let Some(d) = &prop.default {
+                        let hint = match &prop.pattern { Pattern::Ident(n) => Some(n.as_str()), _ => None };
+                        emit_default(d, hint, code)?;
+                    }
+                }
+                bind_pattern(&prop.pattern, code, mode)?;
+            }
+            code.emit(Opcode::Pop);
+            Ok(())
+        }
+    }
+}
+
+/// Pop hodnoty do cile zapsaneho jako vyraz (prirazeni, for-in/of cil).
+fn bind_expr_target(target: &Expr, code: &mut CodeBlock, mode: Bind) -> CResult {
+    match target {
+        Expr::Ident(n, _) => {
+            bind_name(n, code, mode);
+            Ok(())
+        }
+        Expr::Member { object, prop, .. } if mode == Bind::Assign => {
+            let tmp = code.push_temp("val");
+            code.emit(Opcode::DeclareVar(tmp));
+            compile_expr(object, code)?;
+            match prop {
+                MemberProp::Ident(name) => {
+                    code.emit(Opcode::LoadVar(tmp));
+                    let idx = code.push_string(name);
+                    code.emit(Opcode::SetProp(idx));
+                }
+                MemberProp::Computed(k) => {
+                    compile_expr(k, code)?;
+                    code.emit(Opcode::LoadVar(tmp));
+                    code.emit(Opcode::SetIndex);
+                }
+                MemberProp::Private(name) => {
+                    emit_private_key(name, code)?;
+                    code.emit(Opcode::LoadVar(tmp));
+                    code.emit(Opcode::SetPrivate);
+                }
+            }
+            code.emit(Opcode::Pop);
+            Ok(())
+        }
+        Expr::Assign { op: AssignOp::Assign, target, value, .. } => {
+            emit_default(value, match target.as_ref() { Expr::Ident(n, _) => Some(n), _ => None }, code)?;
+            bind_expr_target(target, code, mode)
+        }
+        Expr::Array(items, _) => {
+            code.emit(Opcode::GetIter(0));
+            for item in items {
+                match item.as_deref() {
+                    None => {
+                        code.emit(Opcode::IterStep);
+                        code.emit(Opcode::Pop);
+                    }
+                    Some(Expr::Spread(t, _)) => {
+                        code.emit(Opcode::IterRest);
+                        bind_expr_target(t, code, mode)?;
+                    }
+                    Some(t) => {
+                        code.emit(Opcode::IterStep);
+                        bind_expr_target(t, code, mode)?;
+                    }
+                }
+            }
+            code.emit(Opcode::IterClose);
+            Ok(())
+        }
+        Expr::Object(props, _) => {
+            let mut excluded = Vec::new();
+            for prop in props {
+                if matches!(prop.key, PropKey::Spread) {
+                    emit_object_rest(&excluded, code)?;
+                } else {
+                    emit_pattern_get(&prop.key, code, &mut excluded)?;
+                }
+                bind_expr_target(&prop.value, code, mode)?;
+            }
+            code.emit(Opcode::Pop);
+            Ok(())
+        }
+        _ => Err("neplatny cil prirazeni"),
+    }
+}
+
+// ─── Funkce a tridy ──────────────────────────────────────────────────────────
+
+enum FnBody<'a> {
+    Block(&'a [Stmt]),
+    Expr(&'a Expr),
+    /// Inicializator poli / statickych bloku tridy
+    Fields(&'a [ClassElem<'a>]),
+}
+
+struct FnDef<'a> {
+    name: Option<&'a str>,
+    params: &'a [Param],
+    body: FnBody<'a>,
+    is_arrow: bool,
+    is_async: bool,
+    is_generator: bool,
+    /// Jmeno je v tele videt jako slot 0 (rekurze).
+    self_binding: bool,
+    /// Staticka metoda tridy (`super.x` cte z rodicovske tridy).
+    super_static: bool,
+}
+
+/// Zkompiluje funkci a emituje `LoadFunction`.
+fn emit_function(def: FnDef, code: &mut CodeBlock) -> CResult {
+    let scope = Scope {
+        outer_vars: code.var_names.clone(),
+        outer_cells: code.cells.clone(),
+        outer_uninit: code.uninit.clone(),
+        outer_consts: code.consts.clone(),
+        outer_is_function: code.is_function,
+        outer_is_arrow: in_arrow(code),
+        captures: Vec::new(),
+        boxed: Vec::new(),
+        consts: Vec::new(),
+        new_cells: Vec::new(),
+    };
+    SCOPES.with(|s| s.borrow_mut().push(scope));
+    let mut f = CodeBlock::new();
+    f.is_function = !def.is_arrow;
+    f.strict = code.strict || matches!(def.body, FnBody::Block(body) if has_use_strict(body));
+    f.super_static = if def.is_arrow { code.super_static } else { def.super_static };
+    let result = compile_function_body(&def, &mut f);
+    let scope = SCOPES.with(|s| s.borrow_mut().pop()).expect("function scope");
+    let (params, param_slots, arity) = result?;
+    f.capture_names = scope.captures.iter().map(|(n, _)| n.clone()).collect();
+    for slot in scope.new_cells {
+        if code.cells.contains(&slot) { continue; }
+        code.cells.push(slot);
+        // Uz emitovane pristupy ke slotu prejdou na box.
+        for op in code.bytecode.iter_mut() {
+            match *op {
+                Opcode::LoadVar(s) if s == slot => *op = Opcode::LoadCell(slot),
+                Opcode::StoreVar(s) if s == slot => *op = Opcode::StoreCell(slot),
+                Opcode::DeclareVar(s) if s == slot => *op = Opcode::DeclareCell(slot),
+                Opcode::Inc(s) if s == slot => *op = Opcode::UpdateCell(slot, true, true),
+                Opcode::Dec(s) if s == slot => *op = Opcode::UpdateCell(slot, false, true),
+                Opcode::PostInc(s) if s == slot => *op = Opcode::UpdateCell(slot, true, false),
+                Opcode::PostDec(s) if s == slot => *op = Opcode::UpdateCell(slot, false, false),
+                _ => {}
+            }
+        }
+    }
+    let compiled = CompiledFunction {
+        name: def.name.map(str::to_string),
+        params,
+        param_slots,
+        arity,
+        code: f,
+        captures: scope.captures.into_iter().map(|(_, src)| src).collect(),
+        boxed: scope.boxed,
+        is_async: def.is_async,
+        is_generator: def.is_generator,
+        is_arrow: def.is_arrow,
+        self_slot: def.self_binding && def.name.is_some(),
+    };
+    if code.functions.len() >= u16::MAX as usize { return Err("prilis mnoho funkci"); }
+    code.functions.push(Rc::new(compiled));
+    code.emit(Opcode::LoadFunction((code.functions.len() - 1) as u16));
+    Ok(())
+}
+
+type ParamLayout = (Vec<String>, Vec<Option<u16>>, usize);
+
+fn compile_function_body(def: &FnDef, f: &mut CodeBlock) -> Result<ParamLayout, &'static str> {
+    if def.self_binding && let Some(n) = def.name {
+        f.push_local(n);
+    }
+    let mut names = Vec::with_capacity(def.params.len());
+    let mut slots = Vec::with_capacity(def.params.len());
+    let mut arity = None;
+    for (i, p) in def.params.iter().enumerate() {
+        if (p.default.is_some() || p.rest) && arity.is_none() { arity = Some(i); }
+        match (&p.pattern, &p.default, p.rest) {
+            (Pattern::Ident(n), None, false) => {
+                names.push(n.clone());
+                slots.push(Some(f.push_local(n)));
+            }
+            _ => {
+                names.push(String::new());
+                slots.push(None);
+            }
+        }
+    }
+    if let FnBody::Block(body) = def.body { hoist_vars(body, f); }
+    // Prolog: parametry se vzorem, defaultem nebo rest.
+    for (i, p) in def.params.iter().enumerate() {
+        if slots[i].is_some() { continue; }
+        if p.rest {
+            f.emit(Opcode::LoadRestArgs(i as u16));
+        } else {
+            f.emit(Opcode::LoadArg(i as u16));
+            if let Some(d) = &p.default {
+                let hint = match &p.pattern { Pattern::Ident(n) => Some(n.as_str()), _ => None };
+                emit_default(d, hint, f)?;
+            }
+        }
+        bind_pattern(&p.pattern, f, Bind::Fresh)?;
+    }
+    match def.body {
+        FnBody::Block(body) => {
+            compile_list(body, f, false, false)?;
+            f.emit(Opcode::LoadUndefined);
+        }
+        FnBody::Expr(e) => compile_expr(e, f)?,
+        FnBody::Fields(elems) => {
+            compile_class_elems(elems, f)?;
+            f.emit(Opcode::LoadUndefined);
+        }
+    }
+    f.emit(Opcode::Return);
+    Ok((names, slots, arity.unwrap_or(def.params.len())))
+}
+
+/// Vyraz s odvozenym jmenem (`let f = function () {}` -> funkce `f`).
+fn compile_named(e: &Expr, name: &str, code: &mut CodeBlock) -> CResult {
+    match e {
+        Expr::Function { name: None, params, body, .. } => emit_function(FnDef {
+            name: Some(name), params, body: FnBody::Block(body), is_arrow: false,
+            is_async: false, is_generator: false, self_binding: true, super_static: false,
+        }, code),
+        Expr::Arrow { params, body, .. } => emit_function(FnDef {
+            name: Some(name), params, body: arrow_body(body), is_arrow: true,
+            is_async: false, is_generator: false, self_binding: true, super_static: false,
+        }, code),
+        Expr::AsyncFunc { name: None, params, body, is_arrow, .. } => emit_function(FnDef {
+            name: Some(name), params, body: FnBody::Block(body), is_arrow: *is_arrow,
+            is_async: true, is_generator: false, self_binding: true, super_static: false,
+        }, code),
+        Expr::GeneratorFunc { name: None, params, body, .. } | Expr::AsyncGeneratorFunc { name: None, params, body, .. } => {
+            emit_function(FnDef {
+                name: Some(name), params, body: FnBody::Block(body), is_arrow: false,
+                is_async: matches!(e, Expr::AsyncGeneratorFunc { .. }), is_generator: true,
+                self_binding: true, super_static: false,
+            }, code)
+        }
+        Expr::ClassExpr { name: None, decorators, super_class, body, .. } => {
+            compile_class(Some(name), decorators, super_class.as_deref(), body, true, code)
+        }
+        _ => compile_expr(e, code),
+    }
+}
+
+fn arrow_body(body: &ArrowBody) -> FnBody<'_> {
+    match body {
+        ArrowBody::Expr(e) => FnBody::Expr(e),
+        ArrowBody::Block(b) => FnBody::Block(b),
+    }
+}
+
+/// Klic elementu tridy vyhodnocovaneho az inicializatorem (pole, privatni metoda).
+#[derive(Clone)]
+enum ElemKey<'a> {
+    Name(&'a str),
+    /// `#jmeno` - klic je v lokalu `#jmeno` scope tridy
+    Private(&'a str),
+    /// Computed klic vyhodnoceny pri definici tridy, ulozeny v lokalu
+    Slot(String),
+    /// Computed klic verejne metody - vyhodnoti se na miste
+    Expr(&'a Expr),
+}
+
+/// Element tela inicializatoru tridy (`FnBody::Fields`).
+struct ClassElem<'a> {
+    member: &'a ClassMember,
+    key: ElemKey<'a>,
+    /// Index elementu ve zdroji - inicializator bezi v poradi deklarace
+    order: usize,
+    /// Lokal s funkci privatni metody instance
+    method: Option<String>,
+    /// Lokal se zaznamem dekoratoru pole (`InitDecoratedField`)
+    decoration: Option<String>,
+}
+
+fn emit_elem_key(key: &ElemKey, code: &mut CodeBlock) -> CResult {
+    match key {
+        ElemKey::Name(n) => emit_str(n, code),
+        ElemKey::Private(n) => return emit_private_key(n, code),
+        ElemKey::Slot(s) => emit_load_name(s, code),
+        ElemKey::Expr(e) => return compile_expr(e, code),
+    }
+    Ok(())
+}
+
+/// Klic privatniho jmena `#name` z lokalu/capture scope tridy.
+fn emit_private_key(name: &str, code: &mut CodeBlock) -> CResult {
+    let binding = format!("#{name}");
+    if code.find_local(&binding).is_none() && resolve_capture(&binding).is_none() {
+        return Err("SyntaxError: Private field must be declared in an enclosing class");
+    }
+    emit_load_name(&binding, code);
+    Ok(())
+}
+
+/// Ulozi vrchol stacku do docasneho lokalu a vrati jeho jmeno.
+fn spill_temp(tag: &str, code: &mut CodeBlock) -> String {
+    let slot = code.push_temp(tag);
+    code.emit(Opcode::DeclareVar(slot));
+    code.var_names[slot as usize].clone()
+}
+
+/// Dekoratory elementu/tridy -> pole v docasnem lokalu (vyhodnocene v poradi zdroje).
+fn emit_decorators(decs: &[Expr], code: &mut CodeBlock) -> Result<String, &'static str> {
+    for d in decs {
+        compile_expr(d, code)?;
+    }
+    code.emit(Opcode::NewArray(decs.len() as u16));
+    Ok(spill_temp("decorators", code))
+}
+
+/// `Decorate` nad [class] na stacku: clen `key` s hodnotou z `value`, vysledek
+/// do docasneho lokalu. Stack se vrati do puvodniho stavu.
+fn emit_decorate_to_temp(
+    key: &ElemKey,
+    value: impl FnOnce(&mut CodeBlock) -> CResult,
+    decs: &str,
+    flags: u8,
+    code: &mut CodeBlock,
+) -> Result<String, &'static str> {
+    code.emit(Opcode::Dup);
+    emit_elem_key(key, code)?;
+    value(code)?;
+    emit_load_name(decs, code);
+    code.emit(Opcode::Decorate(flags));
+    let temp = spill_temp("decorated", code);
+    code.emit(Opcode::Pop);
+    code.emit(Opcode::Pop);
+    Ok(temp)
+}
+
+fn decorate_flags(kind: DecoratorKind, is_static: bool) -> u8 {
+    kind as u8 | ((is_static as u8) << 3)
+}
+
+/// Funkce metody/accessoru `m` z jejiho tela.
+fn emit_method_body(m: &ClassMember, code: &mut CodeBlock) -> CResult {
+    let fn_name = if m.is_private { format!("#{}", m.name) } else { m.name.clone() };
+    emit_function(FnDef {
+        name: Some(&fn_name), params: &m.params, body: FnBody::Block(&m.body),
+        is_arrow: false, is_async: false, is_generator: false, self_binding: false,
+        super_static: m.is_static,
+    }, code)
+}
+
+/// Metoda, getter nebo setter `m` pod klicem `key` (trida na stacku). Funkci
+/// zkompiluje z tela, nebo ji vezme z vysledku dekoratoru accessoru (`from`);
+/// `decs` = lokal s dekoratory metody. Privatni metody instance jdou do `instance`.
+fn emit_class_method<'a>(
+    m: &'a ClassMember,
+    key: ElemKey<'a>,
+    order: usize,
+    from: Option<&str>,
+    decs: Option<&str>,
+    instance: &mut Vec<ClassElem<'a>>,
+    code: &mut CodeBlock,
+) -> CResult {
+    let flags = (m.is_static as u8) | ((m.is_getter as u8) << 1) | ((m.is_setter as u8) << 2);
+    let emit_fn = |code: &mut CodeBlock| -> CResult {
+        match from {
+            Some(r) => {
+                emit_load_name(r, code);
+                let idx = code.push_string(if m.is_getter { "get" } else { "set" });
+                code.emit(Opcode::GetProp(idx));
+            }
+            None => emit_method_body(m, code)?,
+        }
+        Ok(())
+    };
+    let kind = DecoratorKind::for_method(m.is_getter, m.is_setter);
+    match (m.is_private, m.is_static, decs) {
+        (false, _, _) => {
+            emit_elem_key(&key, code)?;
+            emit_fn(code)?;
+            if let Some(d) = decs {
+                emit_load_name(d, code);
+                code.emit(Opcode::Decorate(decorate_flags(kind, m.is_static)));
+            }
+            code.emit(Opcode::DefineMethod(flags));
+        }
+        (true, true, _) => {
+            code.emit(Opcode::Dup);
+            emit_elem_key(&key, code)?;
+            emit_fn(code)?;
+            if let Some(d) = decs {
+                emit_load_name(d, code);
+                code.emit(Opcode::Decorate(decorate_flags(kind, true)));
+            }
+            code.emit(Opcode::DefinePrivateMethod(flags));
+        }
+        // Privatni metoda instance: funkce vznikne jednou, na instanci ji
+        // nainstaluje inicializator poli
+        (true, false, Some(d)) => {
+            let method = Some(emit_decorate_to_temp(&key, emit_fn, d, decorate_flags(kind, false), code)?);
+            instance.push(ClassElem { member: m, key, order, method, decoration: None });
+        }
+        (true, false, None) => {
+            emit_fn(code)?;
+            let method = Some(spill_temp("method", code));
+            instance.push(ClassElem { member: m, key, order, method, decoration: None });
+        }
+    }
+    Ok(())
+}
+
+/// Trida -> objekt tridy na stacku. `inner_binding` = jmeno tridy je videt v jejim tele
+/// (tridni vyraz); deklarace pouzivaji slot obklopujiciho seznamu - ten je ale
+/// prazdny behem statickych inicializatoru, takze trida s nimi dostane vlastni vazbu.
+///
+/// Pole instance a privatni metody instance kompiluje do inicializatoru
+/// `__vm_fields__` (vola ho `new` / `super()` s this = instance); staticka pole
+/// a `static {}` bloky do funkce zavolane hned s this = trida.
+///
+/// Dekoratory: pole dekoratoru se vyhodnoti pred klicem elementu (dekoratory
+/// tridy pred `extends`), `Decorate` je aplikuje po skupinach
+/// (`decorators::application_order`). `DecorateClass` pak nahradi
+/// tridu vysledkem dekoratoru tridy - ten dostane i vnitrni vazba - a
+/// `FinishClass` po statickych polich zavola inicializatory tridy.
+fn compile_class(
+    name: Option<&str>,
+    decorators: &[Expr],
+    super_class: Option<&Expr>,
+    body: &[ClassMember],
+    inner_binding: bool,
+    code: &mut CodeBlock,
+) -> CResult {
+    let decorated = !decorators.is_empty() || body.iter().any(|m| !m.decorators.is_empty());
+    let inner_binding = inner_binding || decorated || body.iter().any(|m| m.is_static && m.kind != ClassMemberKind::Method);
+    // `accessor x` -> [uloziste, getter, setter]
+    let expanded: Vec<Option<[ClassMember; 3]>> = body.iter().enumerate()
+        .map(|(i, m)| (m.kind == ClassMemberKind::Accessor).then(|| m.expand_accessor(i)))
+        .collect();
+    let saved = code.var_names.len();
+    // Cela definice tridy je strict kod
+    let outer_strict = std::mem::replace(&mut code.strict, true);
+    let class_decs = match decorated {
+        true => Some(emit_decorators(decorators, code)?),
+        false => None,
+    };
+    if let Some(sc) = super_class {
+        compile_expr(sc, code)?;
+        code.emit(Opcode::Dup);
+        let slot = code.push_local("super");
+        code.emit(Opcode::DeclareVar(slot));
+    }
+    let name_idx = match name {
+        Some(n) => code.push_string(n),
+        None => u16::MAX,
+    };
+    code.emit(Opcode::NewClass(name_idx, super_class.is_some()));
+    let inner_slot = match name {
+        Some(n) if inner_binding => {
+            code.emit(Opcode::Dup);
+            let slot = code.push_local(n);
+            code.emit(Opcode::DeclareVar(slot));
+            Some(slot)
+        }
+        _ => None,
+    };
+    // Privatni jmena: novy klic pri kazdem vyhodnoceni tridy
+    let mut declared = HashSet::new();
+    for (m, parts) in body.iter().zip(&expanded) {
+        let storage = parts.as_ref().map(|[s, ..]| s.name.as_str());
+        for n in [m.is_private.then_some(m.name.as_str()), storage].into_iter().flatten() {
+            if declared.insert(n) {
+                let idx = code.push_string(n);
+                code.emit(Opcode::NewPrivateName(idx));
+                let slot = code.push_local(&format!("#{n}"));
+                code.emit(Opcode::DeclareVar(slot));
+            }
+        }
+    }
+    if let Some(ctor) = body.iter().find(|m| m.is_constructor()) {
+        emit_function(FnDef {
+            name: name.or(Some("constructor")), params: &ctor.params, body: FnBody::Block(&ctor.body),
+            is_arrow: false, is_async: false, is_generator: false, self_binding: false, super_static: false,
+        }, code)?;
+        code.emit(Opcode::SetCtor);
+    }
+    // Dekoratory elementu (pred klicem) a computed klice v poradi deklarace; u
+    // dekorovane tridy jdou do lokalu i klice verejnych metod (aplikace je pozdeji)
+    let mut elems = Vec::new();
+    for (i, (m, parts)) in body.iter().zip(&expanded).enumerate() {
+        if m.is_constructor() || m.kind == ClassMemberKind::StaticBlock {
+            continue;
+        }
+        let decs = match m.decorators.is_empty() {
+            true => None,
+            false => Some(emit_decorators(&m.decorators, code)?),
+        };
+        let key = match &m.computed {
+            Some(k) if !decorated && m.kind == ClassMemberKind::Method && !m.is_private => ElemKey::Expr(k),
+            Some(k) => {
+                compile_expr(k, code)?;
+                ElemKey::Slot(spill_temp("key", code))
+            }
+            None if m.is_private => ElemKey::Private(&m.name),
+            None => ElemKey::Name(&m.name),
+        };
+        elems.push((i, m, parts, decs, key));
+    }
+    elems.sort_by_key(|(_, m, ..)| decorators::application_order(m));
+    let mut instance = Vec::new();
+    let mut statics = Vec::new();
+    for (i, m) in body.iter().enumerate().filter(|(_, m)| m.kind == ClassMemberKind::StaticBlock) {
+        statics.push(ClassElem { member: m, key: ElemKey::Name(""), order: i, method: None, decoration: None });
+    }
+    for (i, m, parts, decs, key) in elems {
+        match (m.kind, parts) {
+            (ClassMemberKind::Accessor, Some([storage, getter, setter])) => {
+                let from = match &decs {
+                    Some(d) => {
+                        let pair = |code: &mut CodeBlock| -> CResult {
+                            for part in [getter, setter] {
+                                emit_str(if part.is_getter { "get" } else { "set" }, code);
+                                emit_method_body(part, code)?;
+                            }
+                            code.emit(Opcode::NewObject(2));
+                            Ok(())
+                        };
+                        Some(emit_decorate_to_temp(&key, pair, d, decorate_flags(DecoratorKind::Accessor, m.is_static), code)?)
+                    }
+                    None => None,
+                };
+                emit_class_method(getter, key.clone(), i, from.as_deref(), None, &mut instance, code)?;
+                emit_class_method(setter, key, i, from.as_deref(), None, &mut instance, code)?;
+                let elems = if m.is_static { &mut statics } else { &mut instance };
+                let key = ElemKey::Private(&storage.name);
+                elems.push(ClassElem { member: storage, key, order: i, method: None, decoration: from });
+            }
+            (ClassMemberKind::Method, _) => {
+                emit_class_method(m, key, i, None, decs.as_deref(), &mut instance, code)?;
+            }
+            _ => {
+                let decoration = match &decs {
+                    Some(d) => {
+                        let undefined = |code: &mut CodeBlock| -> CResult {
+                            code.emit(Opcode::LoadUndefined);
+                            Ok(())
+                        };
+                        Some(emit_decorate_to_temp(&key, undefined, d, decorate_flags(DecoratorKind::Field, m.is_static), code)?)
+                    }
+                    None => None,
+                };
+                let elems = if m.is_static { &mut statics } else { &mut instance };
+                elems.push(ClassElem { member: m, key, order: i, method: None, decoration });
+            }
+        }
+    }
+    instance.sort_by_key(|e| e.order);
+    statics.sort_by_key(|e| e.order);
+    let initializer = |elems, is_static| FnDef {
+        name: None, params: &[], body: FnBody::Fields(elems), is_arrow: false, is_async: false,
+        is_generator: false, self_binding: false, super_static: is_static,
+    };
+    if !instance.is_empty() {
+        emit_function(initializer(&instance, false), code)?;
+        code.emit(Opcode::SetFields);
+    }
+    // [F] -> [vysledek, F]: staticke inicializatory bezi s this = puvodni trida
+    if let Some(decs) = &class_decs {
+        emit_load_name(decs, code);
+        code.emit(Opcode::DecorateClass);
+        if let Some(slot) = inner_slot {
+            code.emit(Opcode::Dup);
+            emit_store_slot(slot, code);
+        }
+        code.emit(Opcode::Swap);
+    }
+    if !statics.is_empty() {
+        code.emit(Opcode::Dup);
+        emit_function(initializer(&statics, true), code)?;
+        code.emit(Opcode::CallMethod(0));
+        code.emit(Opcode::Pop);
+    }
+    if class_decs.is_some() {
+        code.emit(Opcode::FinishClass);
+    }
+    code.strict = outer_strict;
+    end_scope(code, saved);
+    Ok(())
+}
+
+/// Telo inicializatoru tridy: nejdriv privatni metody instance, pak pole a
+/// `static {}` bloky v poradi deklarace; this = instance / trida.
+fn compile_class_elems(elems: &[ClassElem], code: &mut CodeBlock) -> CResult {
+    for e in elems {
+        let Some(method) = &e.method else { continue };
+        code.emit(Opcode::LoadThis);
+        emit_elem_key(&e.key, code)?;
+        emit_load_name(method, code);
+        let m = e.member;
+        code.emit(Opcode::DefinePrivateMethod(((m.is_getter as u8) << 1) | ((m.is_setter as u8) << 2)));
+    }
+    for e in elems.iter().filter(|e| e.method.is_none()) {
+        code.emit(Opcode::LoadThis);
+        if e.member.kind == ClassMemberKind::StaticBlock {
+            emit_function(FnDef {
+                name: None, params: &[], body: FnBody::Block(&e.member.body), is_arrow: false,
+                is_async: false, is_generator: false, self_binding: false, super_static: true,
+            }, code)?;
+            code.emit(Opcode::CallMethod(0));
+            code.emit(Opcode::Pop);
+            continue;
+        }
+        emit_elem_key(&e.key, code)?;
+        match (&e.member.value, &e.key) {
+            (Some(v), ElemKey::Name(n)) => compile_named(v, n, code)?,
+            (Some(v), _) => compile_expr(v, code)?,
+            (None, _) => { code.emit(Opcode::LoadUndefined); }
+        }
+        match &e.decoration {
+            Some(d) => {
+                emit_load_name(d, code);
+                code.emit(Opcode::InitDecoratedField);
+            }
+            None => { code.emit(Opcode::InitField); }
+        }
+    }
+    Ok(())
+}
+
+// ─── Vyrazy ──────────────────────────────────────────────────────────────────
+
+/// Skoky optional chainu na zkrat: (index skoku, pocet hodnot ke shozeni).
+type ShortCircuits = Vec<(usize, u16)>;
+
+pub fn compile_expr(e: &Expr, code: &mut CodeBlock) -> CResult {
+    match e {
+        Expr::Number(n, _) => {
+            if *n == 0.0 && n.is_sign_positive() {
+                code.emit(Opcode::LoadZero);
+            } else if *n == 1.0 {
+                code.emit(Opcode::LoadOne);
+            } else {
+                emit_const(JsValue::Number(*n), code);
+            }
+            Ok(())
+        }
+        Expr::BigInt(s, _) => {
+            let n = BigInt::from_str(s).map_err(|_| "neplatny BigInt literal")?;
+            emit_const(JsValue::BigInt(Rc::new(n)), code);
+            Ok(())
+        }
+        Expr::Str(s, _) => {
+            emit_str(s, code);
+            Ok(())
+        }
+        Expr::Bool(true, _) => { code.emit(Opcode::LoadTrue); Ok(()) }
+        Expr::Bool(false, _) => { code.emit(Opcode::LoadFalse); Ok(()) }
+        Expr::Null(_) => { code.emit(Opcode::LoadNull); Ok(()) }
+        Expr::Undefined(_) => { code.emit(Opcode::LoadUndefined); Ok(()) }
+        Expr::Regex(p, f, _) => {
+            let p = code.push_string(p);
+            let f = code.push_string(f);
+            code.emit(Opcode::NewRegex(p, f));
+            Ok(())
+        }
+        Expr::DynamicImport(spec, _) => {
+            compile_expr(spec, code)?;
+            code.emit(Opcode::DynamicImport);
+            Ok(())
+        }
+        Expr::Ident(name, _) => {
+            emit_load_name(name, code);
+            Ok(())
+        }
+        Expr::Template { quasis, expressions, .. } => {
+            // BuildString(N): vsechny casti na stack, jeden concat.
+            let mut count: u16 = 1;
+            emit_str(quasis.first().map(String::as_str).unwrap_or(""), code);
+            for (i, x) in expressions.iter().enumerate() {
+                compile_expr(x, code)?;
+                count = count.checked_add(1).ok_or("prilis dlouhy template")?;
+                if let Some(q) = quasis.get(i + 1).filter(|q| !q.is_empty()) {
+                    emit_str(q, code);
+                    count = count.checked_add(1).ok_or("prilis dlouhy template")?;
+                }
+            }
+            code.emit(Opcode::BuildString(count));
+            Ok(())
+        }
+        Expr::TaggedTemplate { tag, cooked, raw, expressions, site, .. } => {
+            // tag`a${x}b` == tag(strings, x). Strings pole je per-site konstanta
+            // (sdilena s tree-walkerem), takze staci LoadConst.
+            if expressions.len() >= u16::MAX as usize { return Err("too many args"); }
+            let strings = tagged_template::template_object(site, cooked, raw);
+            let argc = expressions.len() as u16 + 1;
+            let method = compile_callee(tag, code, &mut Vec::new())?;
+            let s_idx = code.push_const(strings);
+            code.emit(Opcode::LoadConst(s_idx));
+            for x in expressions { compile_expr(x, code)?; }
+            code.emit(if method { Opcode::CallMethod(argc) } else { Opcode::CallNative(argc) });
+            Ok(())
+        }
+        Expr::Array(items, _) => {
+            if items.iter().flatten().any(|x| matches!(x.as_ref(), Expr::Spread(_, _))) {
+                code.emit(Opcode::NewArray(0));
+                for item in items {
+                    match item.as_deref() {
+                        Some(Expr::Spread(x, _)) => {
+                            compile_expr(x, code)?;
+                            code.emit(Opcode::AppendSpread);
+                        }
+                        Some(x) => {
+                            compile_expr(x, code)?;
+                            code.emit(Opcode::AppendItem);
+                        }
+                        None => {
+                            code.emit(Opcode::LoadUndefined);
+                            code.emit(Opcode::AppendItem);
+                        }
+                    }
+                }
+                return Ok(());
+            }
+            if items.len() >= u16::MAX as usize { return Err("prilis velky array literal"); }
+            for item in items {
+                match item {
+                    Some(x) => compile_expr(x, code)?,
+                    None => { code.emit(Opcode::LoadUndefined); }
+                }
+            }
+            code.emit(Opcode::NewArray(items.len() as u16));
+            Ok(())
+        }
+        Expr::Object(props, _) => compile_object(props, code),
+        Expr::Unary { op, arg, .. } => compile_unary(op, arg, code),
+        Expr::PrivateIn { name, object, .. } => {
+            emit_private_key(name, code)?;
+            compile_expr(object, code)?;
+            code.emit(Opcode::PrivateIn);
+            Ok(())
+        }
+        Expr::Binary { op: BinaryOp::PostInc, left, .. } => compile_update(left, true, false, code),
+        Expr::Binary { op: BinaryOp::PostDec, left, .. } => compile_update(left, false, false, code),
+        Expr::Binary { op, left, right, .. } => {
+            compile_expr(left, code)?;
+            compile_expr(right, code)?;
+            code.emit(binary_opcode(op));
+            Ok(())
+        }
+        Expr::Logical { op, left, right, .. } => {
+            compile_expr(left, code)?;
+            let j = code.emit(logical_jump(op));
+            code.emit(Opcode::Pop);
+            compile_expr(right, code)?;
+            code.patch_here(j);
+            Ok(())
+        }
+        Expr::Ternary { test, yes, no, .. } => {
+            compile_expr(test, code)?;
+            let to_no = code.emit(Opcode::JmpIfFalse(0));
+            compile_expr(yes, code)?;
+            let to_end = code.emit(Opcode::Jmp(0));
+            code.patch_here(to_no);
+            compile_expr(no, code)?;
+            code.patch_here(to_end);
+            Ok(())
+        }
+        Expr::Assign { op, target, value, .. } => compile_assign(op, target, value, code),
+        Expr::Member { .. } | Expr::Call { .. } => {
+            let mut sc = Vec::new();
+            compile_chain(e, code, &mut sc)?;
+            patch_short_circuits(sc, code);
+            Ok(())
+        }
+        Expr::New { callee, args, span } => {
+            compile_expr(callee, code)?;
+            let op = match emit_args(args, code)? {
+                Some(argc) => Opcode::NewOp(argc),
+                None => Opcode::NewOpArgs,
+            };
+            code.mark_call(*span);
+            code.emit(op);
+            Ok(())
+        }
+        Expr::Function { name, params, body, .. } => emit_function(FnDef {
+            name: name.as_deref(), params, body: FnBody::Block(body), is_arrow: false,
+            is_async: false, is_generator: false, self_binding: true, super_static: false,
+        }, code),
+        Expr::Arrow { params, body, .. } => emit_function(FnDef {
+            name: None, params, body: arrow_body(body), is_arrow: true,
+            is_async: false, is_generator: false, self_binding: false, super_static: false,
+        }, code),
+        Expr::AsyncFunc { name, params, body, is_arrow, .. } => emit_function(FnDef {
+            name: name.as_deref(), params, body: FnBody::Block(body), is_arrow: *is_arrow,
+            is_async: true, is_generator: false, self_binding: true, super_static: false,
+        }, code),
+        Expr::GeneratorFunc { name, params, body, .. } | Expr::AsyncGeneratorFunc { name, params, body, .. } => {
+            emit_function(FnDef {
+                name: name.as_deref(), params, body: FnBody::Block(body), is_arrow: false,
+                is_async: matches!(e, Expr::AsyncGeneratorFunc { .. }), is_generator: true,
+                self_binding: true, super_static: false,
+            }, code)
+        }
+        Expr::ClassExpr { name, decorators, super_class, body, .. } => {
+            compile_class(name.as_deref(), decorators, super_class.as_deref(), body, true, code)
+        }
+        Expr::Spread(_, _) => Err("spread mimo pole/volani"),
+        Expr::Sequence(xs, _) => {
+            for (i, x) in xs.iter().enumerate() {
+                compile_expr(x, code)?;
+                if i + 1 < xs.len() { code.emit(Opcode::Pop); }
+            }
+            if xs.is_empty() { code.emit(Opcode::LoadUndefined); }
+            Ok(())
+        }
+        Expr::Yield { value, delegate: false, .. } => {
+            match value {
+                Some(v) => compile_expr(v, code)?,
+                None => { code.emit(Opcode::LoadUndefined); }
+            }
+            code.emit(Opcode::Yield);
+            Ok(())
+        }
+        Expr::Yield { value, delegate: true, .. } => compile_yield_delegate(value.as_deref().ok_or("yield* bez hodnoty")?, code),
+        Expr::Await { value, .. } => {
+            compile_expr(value, code)?;
+            code.emit(Opcode::Await);
+            Ok(())
+        }
+    }
+}
+
+fn binary_opcode(op: &BinaryOp) -> Opcode {
+    match op {
+        BinaryOp::Add => Opcode::Add,
+        BinaryOp::Sub => Opcode::Sub,
+        BinaryOp::Mul => Opcode::Mul,
+        BinaryOp::Div => Opcode::Div,
+        BinaryOp::Mod => Opcode::Mod,
+        BinaryOp::Exp => Opcode::Exp,
+        BinaryOp::Eq => Opcode::Eq,
+        BinaryOp::NotEq => Opcode::NotEq,
+        BinaryOp::StrictEq => Opcode::StrictEq,
+        BinaryOp::StrictNotEq => Opcode::StrictNotEq,
+        BinaryOp::Lt => Opcode::Lt,
+        BinaryOp::Gt => Opcode::Gt,
+        BinaryOp::LtEq => Opcode::LtEq,
+        BinaryOp::GtEq => Opcode::GtEq,
+        BinaryOp::BitAnd => Opcode::BitAnd,
+        BinaryOp::BitOr => Opcode::BitOr,
+        BinaryOp::BitXor => Opcode::BitXor,
+        BinaryOp::Shl => Opcode::Shl,
+        BinaryOp::Shr => Opcode::Shr,
+        BinaryOp::Ushr => Opcode::Ushr,
+        BinaryOp::In => Opcode::In,
+        BinaryOp::Instanceof => Opcode::InstanceOf,
+        // Postfix operatory resi compile_update.
+        BinaryOp::PostInc => Opcode::Add,
+        BinaryOp::PostDec => Opcode::Sub,
+    }
+}
+
+fn logical_jump(op: &LogicalOp) -> Opcode {
+    match op {
+        LogicalOp::And => Opcode::JmpIfFalseKeep(0),
+        LogicalOp::Or => Opcode::JmpIfTrueKeep(0),
+        LogicalOp::NullCoal => Opcode::JmpIfNotNullishKeep(0),
+    }
+}
+
+fn compound_opcode(op: &AssignOp) -> Option<Opcode> {
+    Some(match op {
+        AssignOp::Add => Opcode::Add,
+        AssignOp::Sub => Opcode::Sub,
+        AssignOp::Mul => Opcode::Mul,
+        AssignOp::Div => Opcode::Div,
+        AssignOp::Mod => Opcode::Mod,
+        AssignOp::Exp => Opcode::Exp,
+        AssignOp::BitAnd => Opcode::BitAnd,
+        AssignOp::BitOr => Opcode::BitOr,
+        AssignOp::BitXor => Opcode::BitXor,
+        AssignOp::Shl => Opcode::Shl,
+        AssignOp::Shr => Opcode::Shr,
+        AssignOp::Ushr => Opcode::Ushr,
+        AssignOp::Assign | AssignOp::LogicalAnd | AssignOp::LogicalOr | AssignOp::NullCoal => return None,
+    })
+}
+
+fn compile_object(props: &[ObjectProp], code: &mut CodeBlock) -> CResult {
+    let simple = props.iter().all(|p| {
+        p.kind == PropKind::Init && matches!(p.key, PropKey::Ident(_) | PropKey::Str(_) | PropKey::Num(_))
+    });
+    if simple && props.len() < u16::MAX as usize {
+        for p in props {
+            let key = static_key(&p.key).expect("simple key");
+            emit_str(&key, code);
+            compile_named(&p.value, &key, code)?;
+        }
+        code.emit(Opcode::NewObject(props.len() as u16));
+        return Ok(());
+    }
+    code.emit(Opcode::NewObject(0));
+    for p in props {
+        if matches!(p.key, PropKey::Spread) {
+            compile_expr(&p.value, code)?;
+            code.emit(Opcode::CopyDataProps);
+            continue;
+        }
+        let hint = static_key(&p.key);
+        match (&p.key, &hint) {
+            (PropKey::Computed(k), _) => compile_expr(k, code)?,
+            (_, Some(k)) => emit_str(k, code),
+            _ => unreachable!("static key"),
+        }
+        match &hint {
+            Some(k) => compile_named(&p.value, k, code)?,
+            None => compile_expr(&p.value, code)?,
+        }
+        code.emit(match p.kind {
+            PropKind::Init => Opcode::DefineField,
+            PropKind::Get => Opcode::DefineGetter,
+            PropKind::Set => Opcode::DefineSetter,
+        });
+    }
+    Ok(())
+}
+
+fn static_key(k: &PropKey) -> Option<String> {
+    match k {
+        PropKey::Ident(s) | PropKey::Str(s) => Some(s.clone()),
+        PropKey::Num(n) => Some(JsValue::Number(*n).to_string()),
+        PropKey::Computed(_) | PropKey::Spread => None,
+    }
+}
+
+fn compile_unary(op: &UnaryOp, arg: &Expr, code: &mut CodeBlock) -> CResult {
+    match op {
+        UnaryOp::PreInc => return compile_update(arg, true, true, code),
+        UnaryOp::PreDec => return compile_update(arg, false, true, code),
+        UnaryOp::Delete => {
+            match arg {
+                Expr::Member { object, prop, .. } => {
+                    compile_expr(object, code)?;
+                    emit_member_key(prop, code)?;
+                    code.emit(Opcode::DeleteIndex);
+                }
+                Expr::Ident(_, _) => { code.emit(Opcode::LoadFalse); }
+                _ => {
+                    compile_expr(arg, code)?;
+                    code.emit(Opcode::Pop);
+                    code.emit(Opcode::LoadTrue);
+                }
+            }
+            return Ok(());
+        }
+        // `typeof x` nedeklarovane globalni promenne je "undefined", ne ReferenceError
+        UnaryOp::Typeof if let Expr::Ident(name, _) = arg && is_global_name(name, code) => {
+            let idx = code.push_string(name);
+            code.emit(Opcode::TypeOfGlobal(idx));
+            return Ok(());
+        }
+        _ => {}
+    }
+    compile_expr(arg, code)?;
+    match op {
+        UnaryOp::Minus => code.emit(Opcode::Neg),
+        UnaryOp::Plus => code.emit(Opcode::Pos),
+        UnaryOp::Not => code.emit(Opcode::Not),
+        UnaryOp::BitNot => code.emit(Opcode::BitNot),
+        UnaryOp::Typeof => code.emit(Opcode::TypeOf),
+        UnaryOp::Void => {
+            code.emit(Opcode::Pop);
+            code.emit(Opcode::LoadUndefined)
+        }
+        UnaryOp::PreInc | UnaryOp::PreDec | UnaryOp::Delete => unreachable!(),
+    };
+    Ok(())
+}
+
+/// Klic clenu jako hodnota na stacku (string konstanta nebo vypocteny vyraz).
+fn emit_member_key(prop: &MemberProp, code: &mut CodeBlock) -> CResult {
+    match prop {
+        MemberProp::Ident(name) => {
+            emit_str(name, code);
+            Ok(())
+        }
+        MemberProp::Computed(k) => compile_expr(k, code),
+        MemberProp::Private(name) => emit_private_key(name, code),
+    }
+}
+
+/// `++x` / `x--` a varianty nad clenem.
+fn compile_update(target: &Expr, inc: bool, prefix: bool, code: &mut CodeBlock) -> CResult {
+    let op = if inc { Opcode::Add } else { Opcode::Sub };
+    match target {
+        Expr::Ident(name, _) => {
+            // Inc/Dec nad inicializovanym slotem (pozdejsi box je prepise na UpdateCell).
+            let plain = |s: &u16| !code.uninit.contains(s) && !code.consts.contains(s);
+            if let Some(slot) = code.find_local(name).filter(plain) {
+                if code.cells.contains(&slot) {
+                    code.emit(Opcode::UpdateCell(slot, inc, prefix));
+                    return Ok(());
+                }
+                code.emit(match (inc, prefix) {
+                    (true, true) => Opcode::Inc(slot),
+                    (false, true) => Opcode::Dec(slot),
+                    (true, false) => Opcode::PostInc(slot),
+                    (false, false) => Opcode::PostDec(slot),
+                });
+                return Ok(());
+            }
+            emit_load_name(name, code);
+            code.emit(Opcode::Pos);
+            if prefix {
+                code.emit(Opcode::LoadOne);
+                code.emit(op);
+                code.emit(Opcode::Dup);
+            } else {
+                code.emit(Opcode::Dup);
+                code.emit(Opcode::LoadOne);
+                code.emit(op);
+            }
+            emit_store_name(name, code);
+            Ok(())
+        }
+        Expr::Member { object, prop, .. } => {
+            compile_expr(object, code)?;
+            let set = match prop {
+                MemberProp::Ident(name) => {
+                    let idx = code.push_string(name);
+                    code.emit(Opcode::Dup);
+                    code.emit(Opcode::GetProp(idx));
+                    Opcode::SetProp(idx)
+                }
+                MemberProp::Computed(k) => {
+                    compile_expr(k, code)?;
+                    code.emit(Opcode::Dup2);
+                    code.emit(Opcode::GetIndex);
+                    Opcode::SetIndex
+                }
+                MemberProp::Private(name) => {
+                    emit_private_key(name, code)?;
+                    code.emit(Opcode::Dup2);
+                    code.emit(Opcode::GetPrivate);
+                    Opcode::SetPrivate
+                }
+            };
+            code.emit(Opcode::Pos);
+            if prefix {
+                code.emit(Opcode::LoadOne);
+                code.emit(op);
+                code.emit(set);
+            } else {
+                code.emit(Opcode::Dup);
+                let tmp = code.push_temp("old");
+                code.emit(Opcode::DeclareVar(tmp));
+                code.emit(Opcode::LoadOne);
+                code.emit(op);
+                code.emit(set);
+                code.emit(Opcode::Pop);
+                code.emit(Opcode::LoadVar(tmp));
+            }
+            Ok(())
+        }
+        _ => Err("neplatny cil ++/--"),
+    }
+}
+
+fn compile_assign(op: &AssignOp, target: &Expr, value: &Expr, code: &mut CodeBlock) -> CResult {
+    let logical = match op {
+        AssignOp::LogicalAnd => Some(LogicalOp::And),
+        AssignOp::LogicalOr => Some(LogicalOp::Or),
+        AssignOp::NullCoal => Some(LogicalOp::NullCoal),
+        _ => None,
+    };
+    match target {
+        Expr::Ident(name, _) => {
+            if let Some(lop) = logical {
+                emit_load_name(name, code);
+                let j = code.emit(logical_jump(&lop));
+                code.emit(Opcode::Pop);
+                compile_named(value, name, code)?;
+                code.emit(Opcode::Dup);
+                emit_store_name(name, code);
+                code.patch_here(j);
+                return Ok(());
+            }
+            match compound_opcode(op) {
+                Some(bin) => {
+                    emit_load_name(name, code);
+                    compile_expr(value, code)?;
+                    code.emit(bin);
+                }
+                None => compile_named(value, name, code)?,
+            }
+            code.emit(Opcode::Dup);
+            emit_store_name(name, code);
+            Ok(())
+        }
+        Expr::Member { object, prop, .. } => {
+            compile_expr(object, code)?;
+            let (get, set, pops) = match prop {
+                MemberProp::Ident(name) => {
+                    let idx = code.push_string(name);
+                    (Opcode::GetProp(idx), Opcode::SetProp(idx), 1)
+                }
+                MemberProp::Computed(k) => {
+                    compile_expr(k, code)?;
+                    (Opcode::GetIndex, Opcode::SetIndex, 2)
+                }
+                MemberProp::Private(name) => {
+                    emit_private_key(name, code)?;
+                    (Opcode::GetPrivate, Opcode::SetPrivate, 2)
+                }
+            };
+            let load_current = |code: &mut CodeBlock| {
+                code.emit(if pops == 1 { Opcode::Dup } else { Opcode::Dup2 });
+                code.emit(get);
+            };
+            if let Some(lop) = logical {
+                // [obj, (key), v] -> zkrat: jen v
+                load_current(code);
+                let short = code.emit(logical_jump(&lop));
+                code.emit(Opcode::Pop);
+                compile_expr(value, code)?;
+                code.emit(set);
+                let to_end = code.emit(Opcode::Jmp(0));
+                code.patch_here(short);
+                for _ in 0..pops {
+                    code.emit(Opcode::Swap);
+                    code.emit(Opcode::Pop);
+                }
+                code.patch_here(to_end);
+                return Ok(());
+            }
+            if let Some(bin) = compound_opcode(op) {
+                load_current(code);
+                compile_expr(value, code)?;
+                code.emit(bin);
+            } else {
+                compile_expr(value, code)?;
+            }
+            code.emit(set);
+            Ok(())
+        }
+        Expr::Array(_, _) | Expr::Object(_, _) if *op == AssignOp::Assign => {
+            compile_expr(value, code)?;
+            code.emit(Opcode::Dup);
+            bind_expr_target(target, code, Bind::Assign)
+        }
+        _ => Err("neplatny cil prirazeni"),
+    }
+}
+
+/// Argumenty volani; None = spread (na stacku je Array argumentu).
+fn emit_args(args: &[Expr], code: &mut CodeBlock) -> Result<Option<u16>, &'static str> {
+    if args.iter().any(|a| matches!(a, Expr::Spread(_, _))) {
+        code.emit(Opcode::NewArray(0));
+        for a in args {
+            if let Expr::Spread(x, _) = a {
+                compile_expr(x, code)?;
+                code.emit(Opcode::AppendSpread);
+            } else {
+                compile_expr(a, code)?;
+                code.emit(Opcode::AppendItem);
+            }
+        }
+        return Ok(None);
+    }
+    if args.len() >= u16::MAX as usize { return Err("too many args"); }
+    for a in args { compile_expr(a, code)?; }
+    Ok(Some(args.len() as u16))
+}
+
+/// Optional `?.`: pri null/undefined skok na zkrat (shodi `pops` hodnot, vysledek undefined).
+fn emit_nullish_check(code: &mut CodeBlock, sc: &mut ShortCircuits, pops: u16) {
+    code.emit(Opcode::JmpIfNotNullishKeep(1));
+    let j = code.emit(Opcode::Jmp(0));
+    sc.push((j, pops));
+}
+
+fn patch_short_circuits(sc: ShortCircuits, code: &mut CodeBlock) {
+    if sc.is_empty() { return; }
+    let done = code.emit(Opcode::Jmp(0));
+    let mut exits = Vec::new();
+    for (j, pops) in sc {
+        code.patch_here(j);
+        for _ in 0..pops { code.emit(Opcode::Pop); }
+        code.emit(Opcode::LoadUndefined);
+        exits.push(code.emit(Opcode::Jmp(0)));
+    }
+    for j in exits.into_iter().chain(std::iter::once(done)) { code.patch_here(j); }
+}
+
+fn is_super(e: &Expr) -> bool {
+    matches!(e, Expr::Ident(n, _) if n == "super")
+}
+
+/// `super[key]`: [] -> [this, value].
+fn emit_super_get(prop: &MemberProp, code: &mut CodeBlock) -> CResult {
+    emit_load_name("this", code);
+    emit_load_name("super", code);
+    emit_member_key(prop, code)?;
+    code.emit(Opcode::SuperGet(code.super_static as u8));
+    Ok(())
+}
+
+/// Clen nebo volani jako soucast optional chainu.
+fn compile_chain(e: &Expr, code: &mut CodeBlock, sc: &mut ShortCircuits) -> CResult {
+    match e {
+        Expr::Member { object, prop, .. } if is_super(object) => {
+            emit_super_get(prop, code)?;
+            code.emit(Opcode::Swap);
+            code.emit(Opcode::Pop);
+            Ok(())
+        }
+        Expr::Member { object, prop, optional, .. } => {
+            compile_chain(object, code, sc)?;
+            if *optional { emit_nullish_check(code, sc, 1); }
+            match prop {
+                MemberProp::Ident(name) => {
+                    let idx = code.push_string(name);
+                    code.emit(Opcode::GetProp(idx));
+                }
+                MemberProp::Computed(k) => {
+                    compile_expr(k, code)?;
+                    code.emit(Opcode::GetIndex);
+                }
+                MemberProp::Private(name) => {
+                    emit_private_key(name, code)?;
+                    code.emit(Opcode::GetPrivate);
+                }
+            }
+            Ok(())
+        }
+        Expr::Call { callee, args, .. } if is_super(callee) => {
+            emit_load_name("this", code);
+            emit_load_name("super", code);
+            if emit_args(args, code)?.is_some() {
+                code.emit(Opcode::NewArray(args.len() as u16));
+            }
+            code.emit(Opcode::SuperCall);
+            Ok(())
+        }
+        // Prime `eval` potrebuje scope volajiciho - skript bezi v tree-walkeru
+        Expr::Call { callee, .. } if matches!(callee.as_ref(), Expr::Ident(n, _) if n == "eval") => {
+            Err("prime eval neni ve VM podporovan")
+        }
+        Expr::Call { callee, args, optional, span } => {
+            let method = compile_callee(callee, code, sc)?;
+            if *optional { emit_nullish_check(code, sc, if method { 2 } else { 1 }); }
+            let op = match (emit_args(args, code)?, method) {
+                (Some(argc), true) => Opcode::CallMethod(argc),
+                (Some(argc), false) => Opcode::CallNative(argc),
+                (None, true) => Opcode::CallMethodArgs,
+                (None, false) => Opcode::CallNativeArgs,
+            };
+            code.mark_call(*span);
+            code.emit(op);
+            Ok(())
+        }
+        _ => compile_expr(e, code),
+    }
+}
+
+/// Callee volani; true = metoda (na stacku [this, fn]), false = [fn].
+fn compile_callee(callee: &Expr, code: &mut CodeBlock, sc: &mut ShortCircuits) -> Result<bool, &'static str> {
+    match callee {
+        Expr::Member { object, prop, .. } if is_super(object) => {
+            emit_super_get(prop, code)?;
+            Ok(true)
+        }
+        Expr::Member { object, prop, optional, .. } => {
+            compile_chain(object, code, sc)?;
+            if *optional { emit_nullish_check(code, sc, 1); }
+            match prop {
+                MemberProp::Ident(name) => {
+                    let idx = code.push_string(name);
+                    code.emit(Opcode::GetMethod(idx));
+                }
+                MemberProp::Computed(k) => {
+                    code.emit(Opcode::Dup);
+                    compile_expr(k, code)?;
+                    code.emit(Opcode::GetIndex);
+                }
+                MemberProp::Private(name) => {
+                    code.emit(Opcode::Dup);
+                    emit_private_key(name, code)?;
+                    code.emit(Opcode::GetPrivate);
+                }
+            }
+            Ok(true)
+        }
+        _ => {
+            compile_chain(callee, code, sc)?;
+            Ok(false)
+        }
+    }
+}
+
+/// `yield* iter`: preposila next(v) do vnitrniho iteratoru, hodnota vyrazu je
+/// `value` posledniho (done) vysledku.
+fn compile_yield_delegate(v: &Expr, code: &mut CodeBlock) -> CResult {
+    let iter = code.push_temp("yiter");
+    let r = code.push_temp("yres");
+    let sent = code.push_temp("ysent");
+    compile_expr(v, code)?;
+    code.emit(Opcode::GetIter(2));
+    // [iter, cursor] -> jen iterator objekt
+    code.emit(Opcode::Pop);
+    code.emit(Opcode::DeclareVar(iter));
+    code.emit(Opcode::LoadUndefined);
+    code.emit(Opcode::DeclareVar(sent));
+    let start = code.bytecode.len();
+    code.emit(Opcode::LoadVar(iter));
+    let next = code.push_string("next");
+    code.emit(Opcode::GetMethod(next));
+    code.emit(Opcode::LoadVar(sent));
+    code.emit(Opcode::CallMethod(1));
+    code.emit(Opcode::DeclareVar(r));
+    code.emit(Opcode::LoadVar(r));
+    let done = code.push_string("done");
+    code.emit(Opcode::GetProp(done));
+    let to_end = code.emit(Opcode::JmpIfTrue(0));
+    code.emit(Opcode::LoadVar(r));
+    let value = code.push_string("value");
+    code.emit(Opcode::GetProp(value));
+    code.emit(Opcode::Yield);
+    code.emit(Opcode::DeclareVar(sent));
+    code.emit_jmp_to(start);
+    code.patch_here(to_end);
+    code.emit(Opcode::LoadVar(r));
+    code.emit(Opcode::GetProp(value));
+    Ok(())
+}
+
+// ─── Program ─────────────────────────────────────────────────────────────────
+
+/// Zkompiluje program. Hodnota posledniho vyrazoveho prikazu je vysledek `VM::run`.
+/// Telo ES modulu nad env modulu. Top-level await potrebuje ramec modulu
+/// tree-walkeru, takove telo se nezkompiluje.
+pub fn compile_module(stmts: &[Stmt]) -> Result<CodeBlock, &'static str> {
+    if has_top_level_await(stmts) {
+        return Err("top-level await modulu");
+    }
+    let mut code = CodeBlock::new();
+    code.strict = true;
+    code.module = true;
+    hoist_global_vars(stmts, &mut code);
+    compile_list(stmts, &mut code, true, true)?;
+    code.emit(Opcode::Halt);
+    Ok(code)
+}
+
+pub fn compile_program(stmts: &[Stmt]) -> Result<CodeBlock, &'static str> {
+    let mut code = CodeBlock::new();
+    code.strict = has_use_strict(stmts);
+    hoist_global_vars(stmts, &mut code);
+    compile_list(stmts, &mut code, true, true)?;
+    code.emit(Opcode::Halt);
+    Ok(code)
+}
@@ -0,0 +1,478 @@
+//! Resumovatelne ramce: generatory, async funkce a async generatory nad VM.
+//!
+//! Ramec (`Frame`) drzi VM + pc; `yield`/`await` z `VM::execute` vraci `Exit`
+//! a ramec se pozdeji resumuje s hodnotou (`next(x)`), vyjimkou (`throw`) nebo
+//! signalem navratu (`return`, projde jen finally bloky).
+//!
+//! VM promise jsou kompatibilni s tree-walkerem (`__promise_state__`,
+//! `__pending_callbacks__`). Reakce na settled promise bezi hned (jako v
+//! tree-walkeru); reakce cekajici na pending promise jdou pri settle do fronty
+//! uloh interpretu, ktery VM spustil, VM bez interpretu je vola hned.
+
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::rc::{Rc, Weak};
+
+use crate::interpreter::{budget, call_machinery, helpers, symbols, JsFunc, JsObject, JsValue};
+use super::vm::{self, Exit, VM};
+use super::{runtime, CompiledFunction};
+
+/// Rezerva zasobniku pred vstupem do ramce a velikost noveho segmentu -
+/// hluboka rekurze VM funkci pokracuje na segmentech.
+const STACK_RED_ZONE: usize = 128 * 1024;
+const STACK_SEGMENT_SIZE: usize = 8 * 1024 * 1024;
+
+/// Jak pokracovat v suspendovanem ramci.
+#[derive(Clone)]
+enum Resume {
+    Next(JsValue),
+    Throw(JsValue),
+    Return(JsValue),
+}
+
+struct Frame {
+    vm: VM,
+    pc: usize,
+    func: Rc<CompiledFunction>,
+    started: bool,
+}
+
+impl Frame {
+    fn new(func: &JsValue, this: JsValue, args: Vec<JsValue>) -> Result<Self, String> {
+        let JsValue::Function(JsFunc::VmCompiled { compiled, env, captures, .. }) = func else {
+            return Err("TypeError: ramec lze vytvorit jen pro VM funkci".into());
+        };
+        Ok(Self {
+            vm: VM::for_call(func, compiled, env, captures, this, args),
+            pc: 0,
+            func: Rc::clone(compiled),
+            started: false,
+        })
+    }
+
+    fn resume(&mut self, r: Resume) -> Result<Exit, String> {
+        stacker::maybe_grow(STACK_RED_ZONE, STACK_SEGMENT_SIZE, || self.resume_here(r))
+    }
+
+    fn resume_here(&mut self, r: Resume) -> Result<Exit, String> {
+        let _frame = runtime::enter_frame(self.func.name.as_deref());
+        if !self.started {
+            self.started = true;
+        } else {
+            match r {
+                Resume::Next(v) => self.vm.push(v),
+                Resume::Throw(v) => {
+                    if let Some(exit) = self.vm.unwind(v, &mut self.pc)? { return Ok(exit); }
+                }
+                Resume::Return(v) => {
+                    if let Some(exit) = self.vm.unwind(vm::gen_return_signal(v), &mut self.pc)? { return Ok(exit); }
+                }
+            }
+        }
+        self.vm.execute(&self.func.code, &mut self.pc)
+    }
+}
+
+/// Volani VM funkce (sync / generator / async / async generator).
+pub(super) fn invoke(func: &JsValue, this: JsValue, args: Vec<JsValue>) -> Result<JsValue, String> {
+    let _depth = budget::enter_call()?;
+    let mut frame = Frame::new(func, this, args)?;
+    match (frame.func.is_generator, frame.func.is_async) {
+        (false, false) => match frame.resume(Resume::Next(JsValue::Undefined))? {
+            Exit::Return(v) | Exit::Yield(v) | Exit::Await(v) => Ok(v),
+        },
+        (true, false) => Ok(make_generator(frame)),
+        (false, true) => {
+            frame.vm.is_async_frame = true;
+            let promise = new_promise();
+            async_step(&Rc::new(RefCell::new(Some(frame))), &promise, Resume::Next(JsValue::Undefined));
+            Ok(promise)
+        }
+        (true, true) => {
+            frame.vm.is_async_frame = true;
+            Ok(make_async_generator(frame))
+        }
+    }
+}
+
+/// `new F(...)` / `super(...)` nad VM funkci; vraci instanci.
+pub(super) fn construct_call(func: &JsValue, this: JsValue, args: Vec<JsValue>, new_target: JsValue) -> Result<JsValue, String> {
+    let _depth = budget::enter_call()?;
+    let mut frame = Frame::new(func, this, args)?;
+    frame.vm.set_new_target(new_target);
+    let result = match frame.resume(Resume::Next(JsValue::Undefined))? {
+        Exit::Return(v) | Exit::Yield(v) | Exit::Await(v) => v,
+    };
+    if matches!(result, JsValue::Object(_) | JsValue::Array(_) | JsValue::Function(_) | JsValue::Map(_) | JsValue::Set(_)) {
+        return Ok(result);
+    }
+    match &frame.vm.this_value {
+        JsValue::Undefined => Err("ReferenceError: Must call super constructor in derived class before accessing 'this' or returning from derived constructor".into()),
+        this => Ok(this.clone()),
+    }
+}
+
+// ─── Generatory ──────────────────────────────────────────────────────────────
+
+#[derive(Default)]
+struct GenState {
+    /// None = dokonceny, nebo prave bezi (`running`).
+    frame: Option<Frame>,
+    running: bool,
+}
+
+fn make_generator(frame: Frame) -> JsValue {
+    let st = Rc::new(RefCell::new(GenState { frame: Some(frame), running: false }));
+    let obj = Rc::new(RefCell::new(JsObject::new()));
+    {
+        let mut o = obj.borrow_mut();
+        o.set("__iterator_helpers__".into(), JsValue::Bool(true));
+        for (name, kind) in [("next", 0), ("return", 1), ("throw", 2)] {
+            let st = Rc::clone(&st);
+            o.set(name.into(), helpers::native(name, move |args| {
+                let v = args.into_iter().next().unwrap_or(JsValue::Undefined);
+                gen_resume(&st, match kind {
+                    0 => Resume::Next(v),
+                    1 => Resume::Return(v),
+                    _ => Resume::Throw(v),
+                })
+            }));
+        }
+        o.set(symbols::ITERATOR.into(), self_returning(&obj));
+    }
+    JsValue::Object(obj)
+}
+
+/// `[Symbol.iterator]() { return this }` bez Rc cyklu.
+fn self_returning(obj: &Rc<RefCell<JsObject>>) -> JsValue {
+    let weak: Weak<RefCell<JsObject>> = Rc::downgrade(obj);
+    helpers::native("[Symbol.iterator]", move |_| {
+        Ok(weak.upgrade().map(JsValue::Object).unwrap_or(JsValue::Undefined))
+    })
+}
+
+fn gen_resume(st: &Rc<RefCell<GenState>>, r: Resume) -> Result<JsValue, String> {
+    let mut frame = {
+        let mut s = st.borrow_mut();
+        match s.frame.take() {
+            Some(f) => {
+                s.running = true;
+                f
+            }
+            None if s.running => return Err("TypeError: Generator is already running".into()),
+            None => return match r {
+                Resume::Next(_) => Ok(helpers::make_iter_result(JsValue::Undefined, true)),
+                Resume::Return(v) => Ok(helpers::make_iter_result(v, true)),
+                Resume::Throw(v) => Err(runtime::throw_value(v)),
+            },
+        }
+    };
+    // return/throw pred prvnim next() generator ukonci bez behu tela.
+    if !frame.started {
+        match r {
+            Resume::Return(v) => {
+                st.borrow_mut().running = false;
+                return Ok(helpers::make_iter_result(v, true));
+            }
+            Resume::Throw(v) => {
+                st.borrow_mut().running = false;
+                return Err(runtime::throw_value(v));
+            }
+            Resume::Next(_) => {}
+        }
+    }
+    let res = frame.resume(r);
+    let mut s = st.borrow_mut();
+    s.running = false;
+    match res? {
+        Exit::Yield(v) => {
+            s.frame = Some(frame);
+            Ok(helpers::make_iter_result(v, false))
+        }
+        Exit::Return(v) | Exit::Await(v) => Ok(helpers::make_iter_result(v, true)),
+    }
+}
+
+// ─── Async funkce ────────────────────────────────────────────────────────────
+
+type AsyncSlot = Rc<RefCell<Option<Frame>>>;
+
+/// Bezi async ramec az do konce nebo pending await; vysledek settluje `promise`.
+fn async_step(st: &AsyncSlot, promise: &JsValue, mut r: Resume) {
+    loop {
+        let Some(mut frame) = st.borrow_mut().take() else { return };
+        match frame.resume(r) {
+            Ok(Exit::Return(v)) | Ok(Exit::Yield(v)) => return settle(promise, "fulfilled", v),
+            Err(msg) => return settle(promise, "rejected", runtime::thrown_value(&msg)),
+            Ok(Exit::Await(v)) => {
+                *st.borrow_mut() = Some(frame);
+                r = match await_now(&v) {
+                    Some(next) => next,
+                    None => {
+                        let (st_f, st_r) = (Rc::clone(st), Rc::clone(st));
+                        let (p_f, p_r) = (promise.clone(), promise.clone());
+                        subscribe(&v,
+                            helpers::native("await", move |a| {
+                                async_step(&st_f, &p_f, Resume::Next(first(a)));
+                                Ok(JsValue::Undefined)
+                            }),
+                            helpers::native("await", move |a| {
+                                async_step(&st_r, &p_r, Resume::Throw(first(a)));
+                                Ok(JsValue::Undefined)
+                            }));
+                        return;
+                    }
+                };
+            }
+        }
+    }
+}
+
+fn first(args: Vec<JsValue>) -> JsValue {
+    args.into_iter().next().unwrap_or(JsValue::Undefined)
+}
+
+/// `await v` bez suspendu: settled promise nebo ne-thenable hodnota
+/// (jako `await` tree-walkeru). None = ramec musi pockat.
+fn await_now(v: &JsValue) -> Option<Resume> {
+    match helpers::get_promise_state(v) {
+        Some((state, value)) if state == "fulfilled" => Some(Resume::Next(value)),
+        Some((state, value)) if state == "rejected" => Some(Resume::Throw(value)),
+        Some(_) => None,
+        None if thenable(v).is_some() => None,
+        None => Some(Resume::Next(v.clone())),
+    }
+}
+
+fn thenable(v: &JsValue) -> Option<JsValue> {
+    if !matches!(v, JsValue::Object(_)) { return None; }
+    runtime::get_prop(v, "then").ok().filter(runtime::is_callable)
+}
+
+/// Zaregistruje reakce na pending promise nebo thenable.
+fn subscribe(v: &JsValue, on_f: JsValue, on_r: JsValue) {
+    if let JsValue::Object(o) = v
+        && let Some(JsValue::Array(cbs)) = o.borrow().props.get("__pending_callbacks__").cloned()
+        && helpers::get_promise_state(v).is_some_and(|(s, _)| s == "pending")
+    {
+        cbs.borrow_mut().push(runtime::new_array(vec![on_f, on_r, JsValue::Undefined]));
+        return;
+    }
+    // Settled promise reaguje hned, stejne jako `then` tree-walkeru
+    let react = |cb: JsValue, value: JsValue| { let _ = runtime::call_function(&cb, JsValue::Undefined, vec![value]); };
+    match helpers::get_promise_state(v) {
+        Some((state, value)) if state == "fulfilled" => react(on_f, value),
+        Some((state, value)) if state == "rejected" => react(on_r, value),
+        _ => match thenable(v) {
+            Some(then) => {
+                if let Err(msg) = runtime::call_function(&then, v.clone(), vec![on_f, on_r.clone()]) {
+                    react(on_r, runtime::thrown_value(&msg));
+                }
+            }
+            None => react(on_f, v.clone()),
+        },
+    }
+}
+
+/// Reakce pri settle pending promise jako uloha interpretu (microtask, bezi
+/// z `drain_timers`); VM bez interpretu ji vola hned.
+fn queue_reaction(cb: JsValue, value: JsValue) {
+    if call_machinery::in_interpreter() {
+        call_machinery::with_host(|interp| interp.enqueue_reaction(cb, vec![value]));
+    } else {
+        let _ = runtime::call_function(&cb, JsValue::Undefined, vec![value]);
+    }
+}
+
+// ─── Promise ─────────────────────────────────────────────────────────────────
+
+/// Pending promise ve formatu tree-walkeru.
+fn new_promise() -> JsValue {
+    let mut obj = JsObject::new();
+    obj.set("__promise_state__".into(), JsValue::Str("pending".into()));
+    obj.set("__promise_value__".into(), JsValue::Undefined);
+    obj.set("__pending_callbacks__".into(), runtime::new_array(Vec::new()));
+    JsValue::Object(Rc::new(RefCell::new(obj)))
+}
+
+/// Resolve/reject promise; fulfilled thenable se adoptuje. Opakovany settle se ignoruje.
+pub(super) fn settle(promise: &JsValue, state: &str, value: JsValue) {
+    let JsValue::Object(o) = promise else { return };
+    let adopt = helpers::get_promise_state(&value).is_some() || thenable(&value).is_some();
+    if state == "fulfilled" && adopt && !value.strict_eq(promise) {
+        let (p_f, p_r) = (promise.clone(), promise.clone());
+        subscribe(&value,
+            helpers::native("resolve", move |a| { settle(&p_f, "fulfilled", first(a)); Ok(JsValue::Undefined) }),
+            helpers::native("reject", move |a| { settle(&p_r, "rejected", first(a)); Ok(JsValue::Undefined) }));
+        return;
+    }
+    let callbacks = {
+        let mut o = o.borrow_mut();
+        if !matches!(o.props.get("__promise_state__"), Some(JsValue::Str(s)) if s == "pending") { return; }
+        o.set("__promise_state__".into(), JsValue::Str(state.into()));
+        o.set("__promise_value__".into(), value.clone());
+        match o.props.get("__pending_callbacks__").cloned() {
+            Some(JsValue::Array(a)) => std::mem::take(&mut *a.borrow_mut()),
+            _ => Vec::new(),
+        }
+    };
+    let idx = if state == "fulfilled" { 0 } else { 1 };
+    for entry in callbacks {
+        if let JsValue::Array(triple) = entry {
+            let cb = triple.borrow().get(idx).cloned().unwrap_or(JsValue::Undefined);
+            if runtime::is_callable(&cb) {
+                queue_reaction(cb, value.clone());
+            }
+        }
+    }
+}
+
+/// `new Promise(executor)` z VM.
+pub(super) fn construct_promise(executor: JsValue) -> Result<JsValue, String> {
+    if !runtime::is_callable(&executor) {
+        return Err(format!("TypeError: Promise resolver {executor} is not a function"));
+    }
+    let promise = new_promise();
+    let (p_f, p_r) = (promise.clone(), promise.clone());
+    let resolve = helpers::native("resolve", move |a| { settle(&p_f, "fulfilled", first(a)); Ok(JsValue::Undefined) });
+    let reject = helpers::native("reject", move |a| { settle(&p_r, "rejected", first(a)); Ok(JsValue::Undefined) });
+    if let Err(msg) = runtime::call_function(&executor, JsValue::Undefined, vec![resolve, reject]) {
+        settle(&promise, "rejected", runtime::thrown_value(&msg));
+    }
+    Ok(promise)
+}
+
+/// `p.then(on_f, on_r)` - vraci odvozenou promise.
+pub(super) fn promise_then(p: &JsValue, on_f: JsValue, on_r: JsValue) -> JsValue {
+    let child = new_promise();
+    let reaction = |cb: JsValue, passthrough: &'static str| {
+        let child = child.clone();
+        helpers::native("reaction", move |a| {
+            let v = first(a);
+            if !runtime::is_callable(&cb) {
+                settle(&child, passthrough, v);
+                return Ok(JsValue::Undefined);
+            }
+            match runtime::call_function(&cb, JsValue::Undefined, vec![v]) {
+                Ok(r) => settle(&child, "fulfilled", r),
+                Err(msg) => settle(&child, "rejected", runtime::thrown_value(&msg)),
+            }
+            Ok(JsValue::Undefined)
+        })
+    };
+    subscribe(p, reaction(on_f, "fulfilled"), reaction(on_r, "rejected"));
+    child
+}
+
+// ─── Async generatory ────────────────────────────────────────────────────────
+
+#[derive(Default)]
+struct AsyncGen {
+    frame: Option<Frame>,
+    /// Pozadavek prave bezi (nebo ceka na await).
+    busy: bool,
+    queue: VecDeque<(Resume, JsValue)>,
+}
+
+fn make_async_generator(frame: Frame) -> JsValue {
+    let st = Rc::new(RefCell::new(AsyncGen { frame: Some(frame), ..AsyncGen::default() }));
+    let obj = Rc::new(RefCell::new(JsObject::new()));
+    {
+        let mut o = obj.borrow_mut();
+        for (name, kind) in [("next", 0), ("return", 1), ("throw", 2)] {
+            let st = Rc::clone(&st);
+            o.set(name.into(), helpers::native(name, move |args| {
+                let v = first(args);
+                let promise = new_promise();
+                st.borrow_mut().queue.push_back((match kind {
+                    0 => Resume::Next(v),
+                    1 => Resume::Return(v),
+                    _ => Resume::Throw(v),
+                }, promise.clone()));
+                agen_drain(&st);
+                Ok(promise)
+            }));
+        }
+        o.set(symbols::ASYNC_ITERATOR.into(), self_returning(&obj));
+    }
+    JsValue::Object(obj)
+}
+
+/// Zpracuje frontu pozadavku, dokud nektery neceka na pending await.
+fn agen_drain(st: &Rc<RefCell<AsyncGen>>) {
+    loop {
+        let (r, promise, frame) = {
+            let mut s = st.borrow_mut();
+            if s.busy { return; }
+            let Some((r, promise)) = s.queue.pop_front() else { return };
+            let frame = s.frame.take();
+            s.busy = frame.is_some();
+            (r, promise, frame)
+        };
+        let Some(frame) = frame else {
+            match r {
+                Resume::Next(_) => settle(&promise, "fulfilled", helpers::make_iter_result(JsValue::Undefined, true)),
+                Resume::Return(v) => settle(&promise, "fulfilled", helpers::make_iter_result(v, true)),
+                Resume::Throw(v) => settle(&promise, "rejected", v),
+            }
+            continue;
+        };
+        if !frame.started && !matches!(r, Resume::Next(_)) {
+            st.borrow_mut().busy = false;
+            match r {
+                Resume::Throw(v) => settle(&promise, "rejected", v),
+                Resume::Return(v) | Resume::Next(v) => settle(&promise, "fulfilled", helpers::make_iter_result(v, true)),
+            }
+            continue;
+        }
+        if !agen_step(st, frame, &promise, r) { return; }
+    }
+}
+
+/// Bezi ramec pro jeden pozadavek. false = ceka na pending await.
+fn agen_step(st: &Rc<RefCell<AsyncGen>>, mut frame: Frame, promise: &JsValue, mut r: Resume) -> bool {
+    loop {
+        let exit = frame.resume(r);
+        let finish = |frame: Option<Frame>| {
+            let mut s = st.borrow_mut();
+            s.frame = frame;
+            s.busy = false;
+        };
+        match exit {
+            Ok(Exit::Yield(v)) => {
+                finish(Some(frame));
+                settle(promise, "fulfilled", helpers::make_iter_result(v, false));
+                return true;
+            }
+            Ok(Exit::Return(v)) => {
+                finish(None);
+                settle(promise, "fulfilled", helpers::make_iter_result(v, true));
+                return true;
+            }
+            Err(msg) => {
+                finish(None);
+                settle(promise, "rejected", runtime::thrown_value(&msg));
+                return true;
+            }
+            Ok(Exit::Await(v)) => match await_now(&v) {
+                Some(next) => r = next,
+                None => {
+                    let slot = Rc::new(RefCell::new(Some(frame)));
+                    let continuation = |wrap: fn(JsValue) -> Resume| {
+                        let (st, slot, promise) = (Rc::clone(st), Rc::clone(&slot), promise.clone());
+                        helpers::native("await", move |a| {
+                            if let Some(frame) = slot.borrow_mut().take()
+                                && agen_step(&st, frame, &promise, wrap(first(a)))
+                            {
+                                agen_drain(&st);
+                            }
+                            Ok(JsValue::Undefined)
+                        })
+                    };
+                    subscribe(&v, continuation(Resume::Next), continuation(Resume::Throw));
+                    return false;
+                }
+            },
+        }
+    }
+}
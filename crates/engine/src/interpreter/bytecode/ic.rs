@@ -0,0 +1,347 @@
+//! Inline cache pro `GetProp`/`SetProp`/`GetMethod`.
+//!
+//! Kazda instrukce (index v bytecode) ma vlastni cache. Zaznam si pamatuje tvary
+//! (`PropertyMap::shape`) objektu od prijemce az k drziteli vlastnosti v
+//! prototypovem retezci a index slotu u drzitele. Hit = stejne tvary po ceste
+//! skutecnym retezcem -> klic chybi ve vsech mezilehlych objektech a u drzitele
+//! lezi na stejnem indexu, takze se obejde hash lookup.
+//!
+//! Stavy: prazdna -> monomorfni (1 zaznam) -> polymorfni (do `MAX_ENTRIES`) ->
+//! megamorfni (jen genericka cesta). Accessory, proxy, slovnikove tvary a ne-objekty
+//! se necachuji - jdou pres `runtime::get_prop`/`set_prop`.
+//!
+//! Objekty vstupuji do stromu tvaru az tady (`PropertyMap::track_shape` pri
+//! pristupu), objekty jen z tree-walkeru tvary nezakladaji.
+//!
+//! Store cache zna dva druhy zaznamu: prepis existujiciho slotu prijemce a pridani
+//! noveho klice (prechod tvaru, typicky `this.x = x` v konstruktoru). Pridani je
+//! platne jen pri stejnych tvarech celeho prototypoveho retezce - zadny objekt
+//! v nem klic nema, takze nemuze existovat setter ani readonly vlastnost.
+
+use std::cell::{Cell, RefCell};
+use std::fmt;
+use std::rc::Rc;
+
+use crate::interpreter::gc;
+use crate::interpreter::property_map::{ShapeId, DICTIONARY_SHAPE};
+use crate::interpreter::{proxy_handler, JsObject, JsValue};
+use super::runtime;
+
+/// Vic tvaru na jednom miste = megamorfni.
+const MAX_ENTRIES: usize = 4;
+
+/// Nejdelsi cachovana cesta prijemce -> drzitel (prototypova hloubka + 1).
+const MAX_DEPTH: usize = 4;
+
+#[derive(Clone, PartialEq)]
+struct Entry {
+    /// Tvary od prijemce (0) po drzitele (posledni); u pridani cely retezec.
+    shapes: Vec<ShapeId>,
+    /// Index slotu u drzitele.
+    index: usize,
+    /// Store pridavajici novy klic (prijemce ma tvar pred prechodem).
+    add: bool,
+}
+
+#[derive(Clone, Default)]
+enum Ic {
+    #[default]
+    Empty,
+    Poly(Vec<Entry>),
+    Megamorphic,
+}
+
+/// Druh pristupu - jen pro oddelene citace.
+#[derive(Clone, Copy)]
+pub(super) enum Access {
+    Load,
+    Call,
+}
+
+/// Cache vsech instrukci jednoho `CodeBlock`. Lazy alokace podle pc.
+#[derive(Default)]
+pub struct InlineCaches {
+    sites: RefCell<Vec<Ic>>,
+}
+
+impl Clone for InlineCaches {
+    /// Kopie kodu zacina s prazdnou cache (tvary jsou vazane na beh, ne na kod).
+    fn clone(&self) -> Self {
+        Self::default()
+    }
+}
+
+impl fmt::Debug for InlineCaches {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sites = self.sites.borrow();
+        let used = sites.iter().filter(|s| !matches!(s, Ic::Empty)).count();
+        write!(f, "InlineCaches({used} sites)")
+    }
+}
+
+/// Citace IC - souhrn za vlakno (RWE_PROF vypis).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct IcStats {
+    pub load_hits: u64,
+    pub load_misses: u64,
+    pub store_hits: u64,
+    pub store_misses: u64,
+    pub call_hits: u64,
+    pub call_misses: u64,
+    /// Pristupy na megamorfnich mistech (zapocitane i v misses).
+    pub megamorphic: u64,
+}
+
+impl IcStats {
+    pub fn total(&self) -> u64 {
+        self.load_hits + self.load_misses + self.store_hits + self.store_misses + self.call_hits + self.call_misses
+    }
+
+    /// Pomer hitu v procentech (0 bez pristupu).
+    pub fn hit_rate(&self) -> f64 {
+        let total = self.total();
+        if total == 0 { return 0.0; }
+        (self.load_hits + self.store_hits + self.call_hits) as f64 * 100.0 / total as f64
+    }
+}
+
+impl fmt::Display for IcStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "load={}/{} store={}/{} call={}/{} mega={} hit={:.1}%",
+            self.load_hits,
+            self.load_hits + self.load_misses,
+            self.store_hits,
+            self.store_hits + self.store_misses,
+            self.call_hits,
+            self.call_hits + self.call_misses,
+            self.megamorphic,
+            self.hit_rate(),
+        )
+    }
+}
+
+thread_local! {
+    static STATS: Cell<IcStats> = const { Cell::new(IcStats {
+        load_hits: 0, load_misses: 0, store_hits: 0, store_misses: 0,
+        call_hits: 0, call_misses: 0, megamorphic: 0,
+    }) };
+}
+
+fn count(f: impl FnOnce(&mut IcStats)) {
+    STATS.with(|s| {
+        let mut st = s.get();
+        f(&mut st);
+        s.set(st);
+    });
+}
+
+/// Aktualni citace IC tohoto vlakna.
+pub fn ic_stats() -> IcStats {
+    STATS.with(Cell::get)
+}
+
+/// Vynuluje citace (napr. na zacatku snimku).
+pub fn reset_ic_stats() {
+    STATS.with(|s| s.set(IcStats::default()));
+}
+
+impl InlineCaches {
+    /// Prvni zaznam mista `pc`, pro ktery `f` vrati hit. Probe nevola JS, takze
+    /// borrow cache pres nej nemuze kolidovat s reentrantnim pristupem.
+    fn probe<T>(&self, pc: usize, mut f: impl FnMut(&Entry) -> Option<T>) -> Option<T> {
+        match self.sites.borrow().get(pc) {
+            Some(Ic::Poly(entries)) => entries.iter().find_map(&mut f),
+            _ => None,
+        }
+    }
+
+    fn is_megamorphic(&self, pc: usize) -> bool {
+        matches!(self.sites.borrow().get(pc), Some(Ic::Megamorphic))
+    }
+
+    fn add(&self, pc: usize, entry: Entry) {
+        let mut sites = self.sites.borrow_mut();
+        if sites.len() <= pc {
+            sites.resize(pc + 1, Ic::Empty);
+        }
+        let site = &mut sites[pc];
+        match site {
+            Ic::Empty => *site = Ic::Poly(vec![entry]),
+            Ic::Poly(entries) if entries.contains(&entry) => {}
+            Ic::Poly(entries) if entries.len() < MAX_ENTRIES => entries.push(entry),
+            Ic::Poly(_) => *site = Ic::Megamorphic,
+            Ic::Megamorphic => {}
+        }
+    }
+
+    /// `obj.key` (GetProp / GetMethod) na instrukci `pc`.
+    pub(super) fn load(&self, pc: usize, access: Access, obj: &JsValue, key: &str) -> Result<JsValue, String> {
+        let JsValue::Object(o) = obj else {
+            self.miss(access, false);
+            return runtime::get_prop(obj, key);
+        };
+        if runtime::is_host_object(&o.borrow()) {
+            self.miss(access, false);
+            return runtime::get_prop(obj, key);
+        }
+        o.borrow_mut().props.track_shape();
+        if let Some(v) = self.probe(pc, |e| probe_load(o, e)) {
+            count(|s| match access {
+                Access::Load => s.load_hits += 1,
+                Access::Call => s.call_hits += 1,
+            });
+            return Ok(v);
+        }
+        let mega = self.is_megamorphic(pc);
+        self.miss(access, mega);
+        let v = runtime::get_prop(obj, key)?;
+        if !mega && let Some(e) = load_entry(o, key) {
+            self.add(pc, e);
+        }
+        Ok(v)
+    }
+
+    /// `obj.key = value` (SetProp) na instrukci `pc`.
+    pub(super) fn store(&self, pc: usize, obj: &JsValue, key: &str, value: JsValue) -> Result<(), String> {
+        let JsValue::Object(o) = obj else {
+            count(|s| s.store_misses += 1);
+            return runtime::set_prop(obj, key, value);
+        };
+        let shape = o.borrow_mut().props.track_shape();
+        let hit = self.probe(pc, |e| {
+            if e.shapes[0] != shape { return None; }
+            if e.add {
+                return (chain_matches(o, &e.shapes) && o.borrow().extensible).then_some(None);
+            }
+            let b = o.borrow();
+            b.props.slot_at(e.index).filter(|s| !s.is_accessor() && s.writable).map(|_| Some(e.index))
+        });
+        if let Some(index) = hit {
+            let mut b = o.borrow_mut();
+            match index {
+                Some(i) => {
+                    gc::track_value(&value);
+                    if let Some(slot) = b.props.slot_at_mut(i) { slot.value = value; }
+                }
+                None => { b.props.insert(key.into(), value); }
+            }
+            count(|s| s.store_hits += 1);
+            return Ok(());
+        }
+        let mega = self.is_megamorphic(pc);
+        count(|s| {
+            s.store_misses += 1;
+            if mega { s.megamorphic += 1; }
+        });
+        let before = if mega { None } else { absent_chain(o, key) };
+        runtime::set_prop(obj, key, value)?;
+        if mega { return Ok(()); }
+        // Cachuje se jen zapis do vlastni zapisovatelne data vlastnosti (i nove pridane).
+        let b = o.borrow();
+        let shape = b.props.shape();
+        if shape != DICTIONARY_SHAPE
+            && !b.props.contains_key(proxy_handler::TARGET)
+            && !runtime::host_setter(&b, key)
+            && let Some(index) = b.props.slot_index(key)
+            && b.props.slot_at(index).is_some_and(|s| !s.is_accessor() && s.writable)
+        {
+            drop(b);
+            let entry = match before {
+                Some(shapes) => Entry { shapes, index, add: true },
+                None => Entry { shapes: vec![shape], index, add: false },
+            };
+            self.add(pc, entry);
+        }
+        Ok(())
+    }
+
+    fn miss(&self, access: Access, mega: bool) {
+        count(|s| {
+            match access {
+                Access::Load => s.load_misses += 1,
+                Access::Call => s.call_misses += 1,
+            }
+            if mega { s.megamorphic += 1; }
+        });
+    }
+}
+
+/// Hit: tvary po skutecnem retezci sedi se zaznamem a slot je porad data vlastnost.
+fn probe_load(o: &Rc<RefCell<JsObject>>, e: &Entry) -> Option<JsValue> {
+    let mut cur = Rc::clone(o);
+    let last = e.shapes.len() - 1;
+    for (depth, &shape) in e.shapes.iter().enumerate() {
+        let next = {
+            let b = cur.borrow();
+            if b.props.shape() != shape { return None; }
+            if depth == last {
+                let slot = b.props.slot_at(e.index)?;
+                return (!slot.is_accessor()).then(|| slot.value.clone());
+            }
+            b.proto.clone()?
+        };
+        cur = next;
+    }
+    None
+}
+
+/// Zaznam pro nalezenou data vlastnost (None = necachovatelne).
+fn load_entry(o: &Rc<RefCell<JsObject>>, key: &str) -> Option<Entry> {
+    let mut shapes = Vec::new();
+    let mut cur = Rc::clone(o);
+    for _ in 0..MAX_DEPTH {
+        let shape = cur.borrow_mut().props.track_shape();
+        let next = {
+            let b = cur.borrow();
+            if shape == DICTIONARY_SHAPE || b.props.contains_key(proxy_handler::TARGET) {
+                return None;
+            }
+            shapes.push(shape);
+            if let Some(index) = b.props.slot_index(key) {
+                return b.props.slot_at(index).filter(|s| !s.is_accessor()).map(|_| Entry { shapes, index, add: false });
+            }
+            b.proto.clone()?
+        };
+        cur = next;
+    }
+    None
+}
+
+/// Tvary celeho retezce, pokud klic nema zadny objekt v nem (kandidat na cache pridani).
+fn absent_chain(o: &Rc<RefCell<JsObject>>, key: &str) -> Option<Vec<ShapeId>> {
+    let mut shapes = Vec::new();
+    let mut cur = Rc::clone(o);
+    for _ in 0..MAX_DEPTH {
+        let shape = cur.borrow_mut().props.track_shape();
+        let next = {
+            let b = cur.borrow();
+            if shape == DICTIONARY_SHAPE
+                || b.props.contains_key(proxy_handler::TARGET)
+                || b.props.contains_key(key)
+            {
+                return None;
+            }
+            shapes.push(shape);
+            match b.proto.clone() {
+                Some(p) => p,
+                None => return Some(shapes),
+            }
+        };
+        cur = next;
+    }
+    None
+}
+
+/// Retezec ma presne tyto tvary a konci za poslednim (bez dalsiho prototypu).
+fn chain_matches(o: &Rc<RefCell<JsObject>>, shapes: &[ShapeId]) -> bool {
+    let mut cur = Some(Rc::clone(o));
+    for &shape in shapes {
+        let Some(c) = cur else { return false };
+        let b = c.borrow();
+        if b.props.shape() != shape { return false; }
+        cur = b.proto.clone();
+    }
+    cur.is_none()
+}
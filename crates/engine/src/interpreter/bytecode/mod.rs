@@ -0,0 +1,526 @@
+//! Bytecode VM pro JS interpreter.
+//!
+//! Stack-based VM, ktery pokryva cely `ast::Stmt`/`Expr` povrch - kazda funkce
+//! muze bezet ve VM bez fallbacku na tree-walker:
+//! - vyjimky vcetne `finally` (PushTry/PushFinally; `break`/`continue`/`return`
+//!   pres finally blok ho vykonaji inline),
+//! - tridy s `extends`/`super`, gettery/settery, statickymi cleny, poli,
+//!   privatnimi `#cleny`, `static {}` bloky a dekoratory,
+//! - destrukturovani v deklaracich, parametrech, prirazenich i for-of/for-in cilech,
+//! - labeled break/continue a switch,
+//! - generatory a async funkce jako resumable ramce (`frames.rs`).
+//!
+//! Architektura:
+//! - `Opcode`: instrukce stack masiny
+//! - `CodeBlock`: bytecode + constants pool + var_names (sloty lokalu) + string pool
+//! - `compiler`: AST -> CodeBlock (`compile_program`, `compile_stmt`, `compile_expr`)
+//! - `vm`: interpret loop a unwinding try/finally handleru
+//! - `runtime`: property access, volani, `new`, tridy, iteratory, instanceof
+//! - `builtins`: metody poli/retezcu/funkci/kolekci dostupne i bez globalu
+//! - `frames`: generator/async ramce a VM promisy
+//! - `ic`: inline cache pro `GetProp`/`SetProp`/`GetMethod` nad tvary objektu
+//!   (`PropertyMap::shape`); citace hitu jsou v `ic_stats()` (RWE_PROF vypis)
+//!
+//! Zachyceny slot obklopujiciho kodu drzi sdileny box (`LoadCell`/`StoreCell`),
+//! takze closure i obklopujici kod vidi zapisy toho druheho. By-value kopii
+//! dostavaji jen inicializovane `const` vazby.
+
+mod builtins;
+mod compiler;
+mod frames;
+mod ic;
+mod runtime;
+mod vm;
+
+pub use compiler::{compile_expr, compile_module, compile_program, compile_stmt, has_top_level_await};
+pub use ic::{ic_stats, reset_ic_stats, IcStats, InlineCaches};
+pub use runtime::{call_function, construct, error_span, take_thrown, throw_value};
+pub use vm::VM;
+
+use std::rc::Rc;
+
+use crate::ast::{SourceSpan, Stmt};
+use super::JsValue;
+
+/// Interni klic objektu tridy zkompilovane VM (`class` -> JsObject).
+pub const VM_CLASS: &str = "__vm_class__";
+
+#[derive(Debug, Clone, Copy)]
+pub enum Opcode {
+    // Stack manipulation
+    LoadConst(u16),       // push constants[u16]
+    LoadVar(u16),         // push locals[u16]
+    StoreVar(u16),        // locals[u16] = pop()
+    DeclareVar(u16),      // locals[u16] = pop() (var/let/const, alokuje slot)
+    /// Push hodnoty z boxu v locals[u16] (slot sdileny s closures).
+    LoadCell(u16),
+    /// Pop hodnoty do boxu v locals[u16].
+    StoreCell(u16),
+    /// Deklarace vazby v boxu locals[u16] - konec TDZ `let`/`const`/`class`.
+    /// Opakovana deklarace lexikalni vazby (dalsi iterace cyklu) dostane novy box.
+    DeclareCell(u16),
+    /// `++x`/`x--` nad boxem locals[u16]: (slot, inkrement, prefix).
+    UpdateCell(u16, bool, bool),
+    /// locals[u16] = novy box s hodnotou stareho (`let` hlavicky for cyklu na iteraci).
+    RenewCell(u16),
+    Pop,                  // pop, discard
+    Dup,                  // duplicate top
+    /// [a, b] -> [a, b, a, b] (compound assign na computed member).
+    Dup2,
+    /// [a, b] -> [b, a]
+    Swap,
+    LoadUndefined,
+    LoadNull,
+    LoadTrue,
+    LoadFalse,
+    LoadZero,             // push Number(0.0) - common
+    LoadOne,              // push Number(1.0) - common
+
+    // Arithmetic
+    Add, Sub, Mul, Div, Mod, Exp,
+    Neg, Pos, Not, BitNot,
+
+    // Comparison
+    Eq, NotEq, StrictEq, StrictNotEq,
+    Lt, Gt, LtEq, GtEq,
+    /// `key in obj`: pop obj, pop key, push Bool.
+    In,
+    /// `v instanceof C`: pop C, pop v, push Bool.
+    InstanceOf,
+
+    // Bitwise
+    BitAnd, BitOr, BitXor, Shl, Shr, Ushr,
+
+    // Control flow
+    Jmp(i32),             // unconditional jump
+    JmpIfFalse(i32),      // pop, if !truthy jump
+    JmpIfTrue(i32),       // pop, if truthy jump
+    JmpIfFalseKeep(i32),  // peek (don't pop), if !truthy jump - pro && short-circuit
+    JmpIfTrueKeep(i32),   // peek, if truthy jump - pro || short-circuit
+    JmpIfNotNullishKeep(i32), // peek, if not null/undef jump - pro ?? short-circuit
+    /// peek, if !== undefined jump - default hodnoty parametru a vzoru.
+    JmpIfNotUndefKeep(i32),
+
+    // Increment/Decrement (in-place na lokalni var).
+    Inc(u16),             // var_names[u16]++ (pre/post differs)
+    Dec(u16),
+    PostInc(u16),         // push original + locals[u16]+=1
+    PostDec(u16),
+
+    // Member access (obj.prop), pop=obj, push=obj[prop].
+    GetProp(u16),         // string_pool[u16] = property name
+    GetIndex,             // pop key, pop obj, push obj[key]
+    /// Pop value, pop obj, set obj[string_pool[u16]] = value. Push value (assignment vrati v).
+    SetProp(u16),
+    /// Nacteni metody pro volani: [obj] -> [obj, obj[string_pool[u16]]] (call IC).
+    GetMethod(u16),
+    /// Pop value, pop key, pop obj, set obj[key] = value. Push value.
+    SetIndex,
+    /// `delete obj[key]`: pop key, pop obj, push Bool.
+    DeleteIndex,
+
+    // Array/Object literal construction.
+    NewArray(u16),        // pop u16 hodnot ze stacku, push Array<top..bottom>
+    NewObject(u16),       // pop 2*u16 (key/value pairs), push Object
+    /// [obj, key, value] -> [obj]: enumerable data vlastnost (computed klic).
+    DefineField,
+    /// [obj, key, fn] -> [obj]: `get key() {}` v objektovem literalu.
+    DefineGetter,
+    /// [obj, key, fn] -> [obj]: `set key(v) {}` v objektovem literalu.
+    DefineSetter,
+    /// [obj, src] -> [obj]: `{ ...src }` - kopie enumerable vlastnosti.
+    CopyDataProps,
+    /// [src, k1..kn] -> [rest]: `{ a, ...rest }` vzor - src bez vyjmenovanych klicu.
+    ObjectRest(u16),
+    /// Regex literal: (string_pool pattern, string_pool flags).
+    NewRegex(u16, u16),
+
+    // Global lookup (env-bound) + function call.
+    // LoadGlobal: vyhleda globalni promennou v Environment (Math, console, ...).
+    // CallNative(argc): pop argc args, pop callee, invoke, push result.
+    LoadGlobal(u16),      // string_pool[u16]
+    /// Pop value, prirad do existujici globalni promenne (jinak ji definuj).
+    StoreGlobal(u16),
+    CallNative(u16),      // argc
+
+    // typeof/void
+    TypeOf,               // pop, push string typeof
+    LoadStrConst(u16),    // alias LoadConst pro strings - same impl
+
+    // Returns
+    Return,               // return pop()
+    Halt,                 // konec compiled bloku
+
+    // User function support.
+    LoadFunction(u16),    // index do CodeBlock.functions, push JsValue::Function(VmCompiled).
+                          // VM pri LoadFunction snapne capture hodnoty podle
+                          // compiled.captures a embedne je do JsFunc::VmCompiled.
+    LoadCapture(u16),     // push captures[u16] - cte z aktualne bezici closure frame.
+    /// Pop value, captures[u16] = value. Boxovana capture (`CompiledFunction::boxed`)
+    /// zapisuje do boxu, takze zmena prezije mezi volanimi.
+    StoreCapture(u16),
+    /// Push argument na pozici u16 (parametry se vzorem/defaultem).
+    LoadArg(u16),
+    /// Push Array argumentu od pozice u16 (`...rest` parametr).
+    LoadRestArgs(u16),
+    /// Push `arguments` objekt (Array vsech argumentu).
+    LoadArguments,
+
+    // Array spread support pri literal [a, ...b].
+    /// Pop value, append do top-of-stack Array (Array zustane na stacku).
+    AppendItem,
+    /// Pop iterable, iterate jeho elements + append do top-of-stack Array.
+    AppendSpread,
+    /// Pop Array (args), pop callee, call s array elements jako args.
+    CallNativeArgs,
+    /// new Foo(args): vyrobi novy {} jako this, zavola constructor, vrati this.
+    NewOp(u16),
+    /// new Foo(...args): pop Array (args), pop callee.
+    NewOpArgs,
+    /// Push aktualni this hodnotu (Undefined pri nezbalanovanem).
+    LoadThis,
+    /// Method call: stack [obj, method, args...]. Calls method s this=obj.
+    CallMethod(u16),
+    /// Method call se spread args: stack [obj, method, argsArray].
+    CallMethodArgs,
+    /// `await v`: v async ramci suspenduje ramec do settlementu promisy.
+    /// Mimo async ramec (top-level) rozbali settled Promise synchronne.
+    Await,
+    /// Yield (pop value): suspendovat VM, vratit value caller, ulozit pc+state
+    /// pro resume pres Generator.next().
+    Yield,
+    /// Pop N values, koncatenuje (jako string) do jednoho. Pro template literals
+    /// efektivnejsi nez chain Add ops (eliminuje intermediate String allocs).
+    BuildString(u16),
+    /// Push try frame s catch handler PC. Pri Throw bytecode unwind do catch_pc.
+    PushTry(u32),
+    /// Push finally frame: handler dostane vyjimku (i generator return) a po
+    /// vykonani finally tela ji znovu vyhodi.
+    PushFinally(u32),
+    /// Pop try/finally frame (normal exit z chraneneho tela).
+    PopTry,
+    /// Pop value, throw - VM unwind do nejblizsiho handleru.
+    Throw,
+    /// Vyhodi chybu se zpravou string_pool[u16] ("SyntaxError: ...").
+    ThrowError(u16),
+    /// Pop hodnoty do globalni vazby string_pool[u16] - deklarace nejvyssi
+    /// urovne programu (ve strict kodu ji `StoreGlobal` nevytvori).
+    DefineGlobal(u16),
+    /// Jako `DefineGlobal`, vazba je `const`.
+    DefineGlobalConst(u16),
+    /// Hoisting globalni vazby string_pool[u16]: false = `var` (undefined, pokud
+    /// jeste neexistuje), true = `let`/`const`/`class` (TDZ do deklarace).
+    DeclareGlobal(u16, bool),
+    /// `typeof name` nad globalni vazbou - nedeklarovana je "undefined".
+    TypeOfGlobal(u16),
+
+    // Iterace (for-of, for-in, spread, array destrukturovani).
+    /// Pop iterable, push [iter, cursor]. u8: 0 = sync, 1 = async iterace
+    /// (Symbol.asyncIterator), 2 = vzdy iterator objekt s `next()` (`yield*`).
+    GetIter(u8),
+    /// [iter, cursor] -> [iter, cursor, value]; pri done pop obou a skok.
+    IterNext(i32),
+    /// [iter, cursor] -> [iter, cursor, result]: surovy vysledek next() (for await).
+    IterNextRaw,
+    /// [iter, cursor, result] -> [iter, cursor, value]; pri result.done pop vseho a skok.
+    IterDone(i32),
+    /// [iter, cursor] -> [iter, cursor, value|undefined] (destrukturovani).
+    IterStep,
+    /// [iter, cursor] -> [iter, cursor, Array zbytku] (`...rest` element).
+    IterRest,
+    /// Pop [iter, cursor]; nevycerpany protocol iterator zavre pres return().
+    IterClose,
+    /// Pop obj, push Array enumerable klicu vcetne prototypoveho retezce (for-in).
+    ForInKeys,
+
+    // Tridy.
+    /// Pop [super?], push objekt tridy. (string_pool jmeno | u16::MAX, has_super)
+    NewClass(u16, bool),
+    /// [class, ctor] -> [class]: telo konstruktoru.
+    SetCtor,
+    /// [class, key, fn] -> [class]. Flags: 1 = static, 2 = getter, 4 = setter.
+    DefineMethod(u8),
+    /// `super(...args)`: [this, super, argsArray] -> [this].
+    SuperCall,
+    /// `super[key]`: [this, super, key] -> [this, value]. u8 != 0 = staticka metoda.
+    SuperGet(u8),
+    /// Push noveho klice privatniho jmena `#string_pool[u16]` (jeden na vyhodnoceni tridy).
+    NewPrivateName(u16),
+    /// `obj.#x`: [obj, key] -> [value]; brand check (TypeError bez clenu).
+    GetPrivate,
+    /// `obj.#x = v`: [obj, key, value] -> [value]; brand check, setter nebo zapis.
+    SetPrivate,
+    /// `#x in obj`: [key, obj] -> [Bool].
+    PrivateIn,
+    /// [obj, key, fn] -> []: privatni metoda / accessor. Flags: 2 = getter, 4 = setter.
+    DefinePrivateMethod(u8),
+    /// [obj, key, value] -> []: pole tridy (`x = v` / `#x = v`) na instanci nebo tride.
+    InitField,
+    /// [class, fn] -> [class]: inicializator poli instance (`__vm_fields__`).
+    SetFields,
+    /// [class, key, value, decs] -> [class, key, result]: dekoratory clenu.
+    /// Flags: bity 0-2 = `DecoratorKind`, 8 = static. Pole/accessor vraci zaznam
+    /// s inicializatory (`InitDecoratedField`), accessor s `get`/`set`.
+    Decorate(u8),
+    /// [obj, key, value, record] -> []: `InitField` s inicializatory dekoratoru.
+    InitDecoratedField,
+    /// [class, decs] -> [class, result]: dekoratory tridy, `Symbol.metadata`
+    /// a inicializatory statickych metod.
+    DecorateClass,
+    /// [result, class] -> [result]: inicializatory z dekoratoru tridy.
+    FinishClass,
+
+    /// `import(spec)` - VM nema module loader, vraci rejected Promise.
+    DynamicImport,
+}
+
+#[derive(Debug, Clone)]
+pub struct CodeBlock {
+    pub bytecode: Vec<Opcode>,
+    pub constants: Vec<JsValue>,
+    pub var_names: Vec<String>,
+    /// Separate string pool pro property names (GetProp/SetProp keys), pro
+    /// LoadGlobal name lookup. Oddeleny od var_names protoze Block truncate
+    /// var_names by jinak korumpovalo property name indexy.
+    pub string_pool: Vec<String>,
+    /// Stack ridicich ramcu (loop/switch/label/try/finally) - transient pri compile.
+    /// Push pri vstupu, pop pri vystupu; break/continue/return podle nej unwinduji.
+    pub loop_stack: Vec<LoopFrame>,
+    /// Vnoreni funkce - LoadFunction(idx) reference.
+    pub functions: Vec<Rc<CompiledFunction>>,
+    /// Labely cekajici na nasledujici cyklus (`outer: for (...)`).
+    pub pending_labels: Vec<String>,
+    /// Telo ne-arrow funkce: `arguments` je lokalni objekt.
+    pub is_function: bool,
+    /// Telo staticke metody: `super.x` cte z rodicovske tridy, ne z prototypu.
+    pub super_static: bool,
+    /// Sloty drzici box (zachycene closures pred svou deklaraci). VM je pri
+    /// vstupu do ramce inicializuje prazdnym boxem.
+    pub cells: Vec<u16>,
+    /// Predalokovane sloty, jejichz deklarace jeste nebyla zkompilovana - transient.
+    pub uninit: Vec<u16>,
+    /// Sloty `let`/`const`/`class` (TDZ). Box takoveho slotu je do deklarace prazdny.
+    pub lexical: Vec<u16>,
+    /// Sloty `const` vazeb - prirazeni je TypeError.
+    pub consts: Vec<u16>,
+    /// `const` deklarace nejvyssi urovne programu (globalni vazby) - transient.
+    pub global_consts: Vec<String>,
+    /// Jmena captures (chyba TDZ zachycene vazby).
+    pub capture_names: Vec<String>,
+    /// Strict mode kod (`"use strict"`, telo tridy).
+    pub strict: bool,
+    /// Telo ES modulu: importy navazal linker, `export default` jde do skryte vazby.
+    pub module: bool,
+    /// Inline cache property pristupu, indexovane pc instrukce.
+    pub ic: InlineCaches,
+    /// Debug tabulka pozic: (pc prvni instrukce prikazu, rozsah prikazu ve zdroji),
+    /// vzestupne podle pc.
+    pub spans: Vec<(usize, SourceSpan)>,
+    /// Pozice volani a `new`: (pc instrukce volani, rozsah vyrazu), vzestupne
+    /// podle pc. Z nich VM sklada ramce `Error.stack`.
+    pub call_spans: Vec<(usize, SourceSpan)>,
+}
+
+/// Zdroj hodnoty closure capture pri LoadFunction.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CaptureSource {
+    /// Lokal obklopujici funkce (locals[idx]).
+    Local(u16),
+    /// Capture obklopujici funkce (captures[idx]) - promenna o vic urovni vys.
+    Capture(u16),
+    /// `this` obklopujici ne-arrow funkce (lexikalni this arrow funkce).
+    This,
+    /// `arguments` obklopujici ne-arrow funkce.
+    Arguments,
+    /// Box ve slotu obklopujici funkce (locals[idx]) - sdileny, ne kopie.
+    Cell(u16),
+}
+
+/// Compiled user-defined function.
+#[derive(Debug, Clone)]
+pub struct CompiledFunction {
+    pub name: Option<String>,
+    /// Jmena parametru; prazdne jmeno = vzor/default/rest (binduje prolog tela).
+    pub params: Vec<String>,
+    /// Slot lokalu pro kazdy jednoduchy parametr (None = bindovan prologem).
+    pub param_slots: Vec<Option<u16>>,
+    /// Pocet parametru pred prvnim defaultem/rest (`fn.length`).
+    pub arity: usize,
+    pub code: CodeBlock,
+    /// Closure captures: pro kazdy free var v body zdroj hodnoty v obklopujici
+    /// funkci. Pri LoadFunction VM hodnoty nacte a vlozi do JsFunc::VmCompiled.captures.
+    pub captures: Vec<CaptureSource>,
+    /// Capture, do ktere telo zapisuje: VM ji drzi v boxu (1-prvkove Array), takze
+    /// zmena prezije mezi volanimi teze closure (citac v closure).
+    pub boxed: Vec<bool>,
+    /// Async funkce: volani vraci Promise, `await` suspenduje ramec.
+    pub is_async: bool,
+    /// Pri true: pri call vrati Generator object misto bezeniho body. Generator.next()
+    /// resumuje VM az do dalsiho Yield nebo Return.
+    pub is_generator: bool,
+    /// Arrow funkce: `this`/`arguments` jsou capture (`CaptureSource::This`), neni konstruktor.
+    pub is_arrow: bool,
+    /// Slot 0 drzi funkci samotnou (pojmenovana funkce - rekurze).
+    pub self_slot: bool,
+}
+
+/// Druh ridiciho ramce pri kompilaci.
+#[derive(Debug, Clone)]
+pub enum FrameKind {
+    /// while/do/for - cil break i continue.
+    Loop,
+    /// for-of/for-in: jako Loop, na stacku drzi [iter, cursor] (break ho zavre).
+    Iter,
+    /// switch - cil nepojmenovaneho break, continue ho preskoci.
+    Switch,
+    /// Oznaceny ne-cyklicky prikaz - cil jen `break label`.
+    Label,
+    /// Telo try s catch handlerem (odchod = PopTry).
+    Try,
+    /// Telo try/catch s finally (odchod = PopTry + inline finally telo).
+    Finally(Vec<Stmt>),
+}
+
+#[derive(Debug, Clone)]
+pub struct LoopFrame {
+    pub kind: FrameKind,
+    /// Labely vztahujici se k tomuto ramci.
+    pub labels: Vec<String>,
+    /// Jump indices co cili na break-target (= konec loopu).
+    pub break_jumps: Vec<usize>,
+    /// Jump indices co cili na continue-target (= test/update v for/while).
+    pub continue_jumps: Vec<usize>,
+}
+
+impl LoopFrame {
+    fn new(kind: FrameKind, labels: Vec<String>) -> Self {
+        Self { kind, labels, break_jumps: vec![], continue_jumps: vec![] }
+    }
+}
+
+impl Default for CodeBlock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CodeBlock {
+    pub fn new() -> Self {
+        Self {
+            bytecode: Vec::new(),
+            constants: Vec::new(),
+            var_names: Vec::new(),
+            string_pool: Vec::new(),
+            loop_stack: Vec::new(),
+            functions: Vec::new(),
+            pending_labels: Vec::new(),
+            is_function: false,
+            super_static: false,
+            cells: Vec::new(),
+            uninit: Vec::new(),
+            lexical: Vec::new(),
+            consts: Vec::new(),
+            global_consts: Vec::new(),
+            capture_names: Vec::new(),
+            strict: false,
+            module: false,
+            ic: InlineCaches::default(),
+            spans: Vec::new(),
+            call_spans: Vec::new(),
+        }
+    }
+    /// Zaznamena, ze dalsi emitovana instrukce patri prikazu na `span`.
+    pub fn mark_span(&mut self, span: SourceSpan) {
+        if !span.is_known() { return; }
+        let pc = self.bytecode.len();
+        match self.spans.last_mut() {
+            // Vnoreny prikaz bez vlastni instrukce pred sebou - vyhrava vnitrni.
+            Some((last, s)) if *last == pc => *s = span,
+            _ => self.spans.push((pc, span)),
+        }
+    }
+    /// Rozsah prikazu, ke kteremu patri instrukce na `pc`.
+    pub fn span_at(&self, pc: usize) -> Option<SourceSpan> {
+        let i = self.spans.partition_point(|(start, _)| *start <= pc);
+        i.checked_sub(1).map(|i| self.spans[i].1)
+    }
+    /// Zaznamena, ze dalsi emitovana instrukce je volani / `new` na `span`.
+    pub fn mark_call(&mut self, span: SourceSpan) {
+        if span.is_known() {
+            self.call_spans.push((self.bytecode.len(), span));
+        }
+    }
+    /// Rozsah volani, ktere provadi instrukce na `pc`.
+    pub fn call_span_at(&self, pc: usize) -> Option<SourceSpan> {
+        self.call_spans.binary_search_by_key(&pc, |(at, _)| *at).ok().map(|i| self.call_spans[i].1)
+    }
+    /// Push string do separate pool pro GetProp/SetProp/LoadGlobal name keys.
+    /// Dedupe.
+    pub fn push_string(&mut self, name: &str) -> u16 {
+        for (i, n) in self.string_pool.iter().enumerate() {
+            if n == name { return i as u16; }
+        }
+        let idx = self.string_pool.len();
+        self.string_pool.push(name.to_string());
+        idx as u16
+    }
+    fn push_const(&mut self, v: JsValue) -> u16 {
+        // Try dedupe na primitivech.
+        for (i, c) in self.constants.iter().enumerate() {
+            if runtime::values_strict_eq(c, &v) {
+                return i as u16;
+            }
+        }
+        let idx = self.constants.len();
+        self.constants.push(v);
+        idx as u16
+    }
+    /// Slot nejvnitrnejsi viditelne promenne `name`.
+    fn find_local(&self, name: &str) -> Option<u16> {
+        self.var_names.iter().rposition(|n| n == name).map(|i| i as u16)
+    }
+    /// Always alloc fresh slot - shadowing OK pri block scoping.
+    /// Use pri var/let/const + function declarations.
+    fn push_local(&mut self, name: &str) -> u16 {
+        let idx = self.var_names.len();
+        self.var_names.push(name.to_string());
+        idx as u16
+    }
+    /// Skryty slot pro mezivysledky kompilatoru (jmeno neni validni identifikator).
+    fn push_temp(&mut self, tag: &str) -> u16 {
+        let name = format!("%{tag}{}", self.bytecode.len());
+        self.push_local(&name)
+    }
+    fn emit(&mut self, op: Opcode) -> usize {
+        let idx = self.bytecode.len();
+        self.bytecode.push(op);
+        idx
+    }
+    fn patch_jmp(&mut self, idx: usize, target: usize) {
+        let offset = (target as i32) - (idx as i32) - 1;
+        match &mut self.bytecode[idx] {
+            Opcode::Jmp(o) | Opcode::JmpIfFalse(o) | Opcode::JmpIfTrue(o)
+            | Opcode::JmpIfFalseKeep(o) | Opcode::JmpIfTrueKeep(o)
+            | Opcode::JmpIfNotNullishKeep(o) | Opcode::JmpIfNotUndefKeep(o)
+            | Opcode::IterNext(o) | Opcode::IterDone(o) => *o = offset,
+            _ => panic!("patch_jmp na non-jump opcode"),
+        }
+    }
+    /// Nastavi absolutni handler pc u PushTry/PushFinally.
+    fn patch_handler(&mut self, idx: usize, target: usize) {
+        match &mut self.bytecode[idx] {
+            Opcode::PushTry(p) | Opcode::PushFinally(p) => *p = target as u32,
+            _ => panic!("patch_handler na non-try opcode"),
+        }
+    }
+    /// Skok zpet na `target` (loop back-edge).
+    fn emit_jmp_to(&mut self, target: usize) {
+        let j = self.emit(Opcode::Jmp(0));
+        self.patch_jmp(j, target);
+    }
+    /// Patch skoku na aktualni pozici.
+    fn patch_here(&mut self, idx: usize) {
+        let here = self.bytecode.len();
+        self.patch_jmp(idx, here);
+    }
+}
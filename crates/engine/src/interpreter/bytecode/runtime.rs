@@ -0,0 +1,1297 @@
+//! Runtime podpora VM: prevody hodnot, pristup k vlastnostem (vcetne accessoru
+//! a proxy), volani, `new`, tridy a iteracni protokol.
+//!
+//! VM nema primy pristup k `Interpreter` - vse zde pracuje nad hodnotami. Funkce
+//! tree-walkeru (`JsFunc::User`/`Async`/`Generator`/`Class`) a hostitelske
+//! vlastnosti (DOM, Web API) vola pres most v `call_machinery`.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use crate::ast::{BinaryOp, SourceSpan};
+use crate::interpreter::eval_expr::{binary_primitive, instanceof_builtin};
+use crate::interpreter::decorators::{self, DecoratorContext, DecoratorKind};
+use crate::interpreter::stack_trace::{StackFrame, StackTrace};
+use crate::interpreter::{
+    call_machinery, helpers, keyed_table, private_names, property_map::{AsKey, PropertyKey}, proxy_handler, symbols, tagged_template, JsFunc, JsMap, JsObject,
+    JsSet, JsValue,
+};
+use helpers::CollectionIterKind;
+use proxy_handler::ProxyTrap;
+use super::{builtins, frames, VM_CLASS};
+
+// ─── Vyhozene hodnoty ────────────────────────────────────────────────────────
+
+thread_local! {
+    /// Hodnota vyhozena z VM ramce bez handleru: (zprava chyby, hodnota).
+    /// VM vraci `Err(String)`; volajici si pres zpravu vyzvedne puvodni JS hodnotu.
+    static THROWN: RefCell<Option<(String, JsValue)>> = const { RefCell::new(None) };
+    /// Vlastnosti VM funkci (`F.prototype`, `F.x = 1`) podle identity closure.
+    static FN_PROPS: RefCell<HashMap<u64, Rc<RefCell<JsObject>>>> = RefCell::new(HashMap::new());
+    /// Misto, kde chyba opustila nejvnitrnejsi VM ramec: (zprava chyby, rozsah).
+    static ERROR_SPAN: RefCell<Option<(String, SourceSpan)>> = const { RefCell::new(None) };
+    /// Bezici VM ramce pro `Error.stack`: (jmeno funkce, pozice posledniho volani).
+    static CALL_STACK: RefCell<Vec<(String, SourceSpan)>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Ulozi vyhozenou hodnotu a vrati chybovou zpravu, pod kterou ji lze vyzvednout.
+pub fn throw_value(v: JsValue) -> String {
+    let msg = format!("uncaught: {v}");
+    THROWN.with(|t| *t.borrow_mut() = Some((msg.clone(), v)));
+    msg
+}
+
+/// Vyhozena JS hodnota patrici k chybove zprave `msg` (None = runtime chyba).
+pub fn take_thrown(msg: &str) -> Option<JsValue> {
+    THROWN.with(|t| {
+        let mut t = t.borrow_mut();
+        match t.as_ref() {
+            Some((m, _)) if m == msg => t.take().map(|(_, v)| v),
+            _ => None,
+        }
+    })
+}
+
+/// Zapamatuje pozici neosetrene chyby `msg`. Vnejsi ramce propagujici tutez
+/// chybu pozici neprepisuji - plati misto vzniku.
+pub(super) fn record_error_span(msg: &str, span: SourceSpan) {
+    ERROR_SPAN.with(|e| {
+        let mut e = e.borrow_mut();
+        if !matches!(e.as_ref(), Some((m, _)) if m == msg) {
+            *e = Some((msg.to_string(), span));
+        }
+    });
+}
+
+/// Pozice ve zdroji, kde vznikla neosetrena chyba `msg` (None = neznama).
+pub fn error_span(msg: &str) -> Option<SourceSpan> {
+    ERROR_SPAN.with(|e| match e.borrow().as_ref() {
+        Some((m, span)) if m == msg => Some(*span),
+        _ => None,
+    })
+}
+
+/// Bezici ramec VM; pri dropu se odebere z `CALL_STACK`.
+pub(super) struct FrameEntry;
+
+impl Drop for FrameEntry {
+    fn drop(&mut self) {
+        CALL_STACK.with(|s| s.borrow_mut().pop());
+    }
+}
+
+/// Vstup do ramce funkce `name` (None = skript).
+pub(super) fn enter_frame(name: Option<&str>) -> FrameEntry {
+    CALL_STACK.with(|s| s.borrow_mut().push((name.unwrap_or_default().to_string(), SourceSpan::default())));
+    FrameEntry
+}
+
+/// Aktualni ramec prave vola / konstruuje na `span`.
+pub(super) fn at_call(span: Option<SourceSpan>) {
+    if let Some(span) = span {
+        CALL_STACK.with(|s| if let Some(top) = s.borrow_mut().last_mut() { top.1 = span; });
+    }
+}
+
+/// `Error.stack` z VM ramcu: hlavicka + ramce od nejvnitrnejsiho (V8 format),
+/// ramce bez zname pozice se vynechavaji.
+fn error_stack(header: &str) -> String {
+    let frames = CALL_STACK.with(|s| {
+        s.borrow().iter().rev().filter(|(_, at)| at.is_known()).map(|(name, at)| StackFrame {
+            function_name: name.clone(),
+            script_id: 0,
+            script_url: "<anonymous>".into(),
+            line: at.start.line,
+            column: at.start.column + 1,
+            is_eval: false,
+            is_constructor: false,
+            is_async: false,
+        }).collect()
+    });
+    StackTrace::capture(frames, call_machinery::STACK_TRACE_LIMIT).error_stack(header)
+}
+
+/// Hodnota chyby pro `catch`: vyhozena JS hodnota, jinak text runtime chyby.
+pub fn thrown_value(msg: &str) -> JsValue {
+    take_thrown(msg).unwrap_or_else(|| JsValue::Str(msg.to_string()))
+}
+
+// ─── Hodnoty ─────────────────────────────────────────────────────────────────
+
+/// Shoda konstant pro dedupe v constants poolu (cisla po bitech: `-0` != `0`).
+pub(super) fn values_strict_eq(a: &JsValue, b: &JsValue) -> bool {
+    match (a, b) {
+        (JsValue::Undefined, JsValue::Undefined) | (JsValue::Null, JsValue::Null) => true,
+        (JsValue::Bool(x), JsValue::Bool(y)) => x == y,
+        (JsValue::Number(x), JsValue::Number(y)) => x.to_bits() == y.to_bits(),
+        (JsValue::Str(x), JsValue::Str(y)) => x == y,
+        (JsValue::BigInt(x), JsValue::BigInt(y)) => x == y,
+        (JsValue::Array(x), JsValue::Array(y)) => Rc::ptr_eq(x, y),
+        _ => false,
+    }
+}
+
+pub(super) fn new_array(items: Vec<JsValue>) -> JsValue {
+    JsValue::Array(Rc::new(RefCell::new(items)))
+}
+
+pub(super) fn new_object() -> Rc<RefCell<JsObject>> {
+    Rc::new(RefCell::new(JsObject::new()))
+}
+
+/// Klic vlastnosti z hodnoty (retezec nebo symbol).
+pub(super) fn key_of(v: &JsValue) -> PropertyKey {
+    match v {
+        JsValue::Str(s) => PropertyKey::Str(s.clone()),
+        other => symbols::to_property_key(other),
+    }
+}
+
+/// Index pole z ciselneho klice (nezaporne cele cislo).
+fn array_index(n: f64) -> Option<usize> {
+    (n >= 0.0 && n.fract() == 0.0 && n < u32::MAX as f64).then_some(n as usize)
+}
+
+pub(super) fn is_vm_class(v: &JsValue) -> bool {
+    matches!(v, JsValue::Object(o) if o.borrow().props.contains_key(VM_CLASS))
+}
+
+pub(super) fn is_callable(v: &JsValue) -> bool {
+    match v {
+        JsValue::Function(_) => true,
+        JsValue::Object(o) => {
+            let o = o.borrow();
+            o.props.contains_key(VM_CLASS) || o.props.contains_key(proxy_handler::CALLABLE)
+        }
+        _ => false,
+    }
+}
+
+/// Popis hodnoty do chybovych hlasek (`x is not a function`).
+fn describe(v: &JsValue) -> String {
+    match v {
+        JsValue::Str(s) => format!("\"{s}\""),
+        JsValue::Object(_) => "object".into(),
+        JsValue::Array(_) => "array".into(),
+        other => other.to_string(),
+    }
+}
+
+/// ToPrimitive pres `[Symbol.toPrimitive](hint)`; ostatni hodnoty beze zmeny.
+pub(super) fn to_primitive(v: JsValue, hint: &str) -> Result<JsValue, String> {
+    let JsValue::Object(o) = &v else { return Ok(v) };
+    let method = o.borrow().get(symbols::TO_PRIMITIVE);
+    match method {
+        JsValue::Undefined | JsValue::Null => Ok(v),
+        JsValue::Function(_) => {
+            let r = call_function(&method, v.clone(), vec![JsValue::Str(hint.into())])?;
+            if matches!(r, JsValue::Object(_) | JsValue::Array(_) | JsValue::Function(_)) {
+                return Err("TypeError: Cannot convert object to primitive value".into());
+            }
+            Ok(r)
+        }
+        _ => Err("TypeError: Symbol.toPrimitive neni funkce".into()),
+    }
+}
+
+/// Binarni operator nad hodnotami - stejna semantika jako `eval_binary` tree-walkeru.
+pub(super) fn binary(op: BinaryOp, l: JsValue, r: JsValue) -> Result<JsValue, String> {
+    let numeric = !matches!(op, BinaryOp::Eq | BinaryOp::NotEq | BinaryOp::StrictEq | BinaryOp::StrictNotEq);
+    let (l, r) = if numeric {
+        let hint = if op == BinaryOp::Add { "default" } else { "number" };
+        (to_primitive(l, hint)?, to_primitive(r, hint)?)
+    } else {
+        (l, r)
+    };
+    if numeric && (matches!(l, JsValue::Symbol(_)) || matches!(r, JsValue::Symbol(_))) {
+        let to = if op == BinaryOp::Add && (matches!(l, JsValue::Str(_)) || matches!(r, JsValue::Str(_))) {
+            "string"
+        } else {
+            "number"
+        };
+        return Err(format!("TypeError: Cannot convert a Symbol value to a {to}"));
+    }
+    binary_primitive(&op, &l, &r)
+}
+
+/// `key in obj` (prochazi prototypovy retezec).
+pub(super) fn has_property(obj: &JsValue, key: &JsValue) -> Result<bool, String> {
+    let k = key_of(key);
+    match obj {
+        p if proxy_handler::is_proxy(p) => {
+            let (target, handler) = proxy_handler::proxy_parts(p).ok_or("not a proxy")?;
+            match proxy_handler::trap_fn(&handler, ProxyTrap::Has)? {
+                Some(f) => Ok(call_function(&f, handler, vec![target, k.to_value()])?.is_truthy()),
+                None => has_property(&target, key),
+            }
+        }
+        JsValue::Object(o) => {
+            let mut cur = Some(Rc::clone(o));
+            for _ in 0..100 {
+                let Some(obj) = cur else { break };
+                if obj.borrow().props.contains_key(&k) { return Ok(true); }
+                cur = obj.borrow().proto.clone();
+            }
+            Ok(false)
+        }
+        JsValue::Array(a) => Ok(k == "length" || k.as_str().and_then(|k| k.parse::<usize>().ok()).is_some_and(|i| i < a.borrow().len())),
+        JsValue::Map(_) | JsValue::Set(_) | JsValue::Function(_) => Ok(false),
+        other => Err(format!("TypeError: Cannot use 'in' operator to search for '{k}' in {other}")),
+    }
+}
+
+/// `v instanceof ctor`.
+pub(super) fn instance_of(v: &JsValue, ctor: &JsValue) -> Result<bool, String> {
+    if matches!(ctor, JsValue::Object(_)) {
+        let method = get_key(ctor, &symbols::HAS_INSTANCE.into())?;
+        if matches!(method, JsValue::Function(_)) {
+            return Ok(call_function(&method, ctor.clone(), vec![v.clone()])?.is_truthy());
+        }
+    }
+    let target = proxy_handler::innermost_target(v);
+    if is_vm_class(ctor) {
+        return Ok(match get_prop(ctor, "prototype")? {
+            JsValue::Object(p) => helpers::is_in_proto_chain(&p, &target),
+            _ => false,
+        });
+    }
+    if let JsValue::Function(JsFunc::VmCompiled { id, .. }) = ctor {
+        let proto = fn_props(*id, false).map(|p| p.borrow().get("prototype"));
+        return Ok(match proto {
+            Some(JsValue::Object(p)) => helpers::is_in_proto_chain(&p, &target),
+            _ => false,
+        });
+    }
+    // Trida tree-walkeru: static [Symbol.hasInstance] a retezec trid zna on
+    if matches!(ctor, JsValue::Function(JsFunc::Class { .. }))
+        && let Some(r) = call_machinery::host_instance_of(v, ctor)
+    {
+        return r;
+    }
+    Ok(instanceof_builtin(&target, ctor))
+}
+
+// ─── Vlastnosti ──────────────────────────────────────────────────────────────
+
+/// Backing objekt vlastnosti VM funkce (`create` = zalozit, kdyz chybi).
+fn fn_props(id: u64, create: bool) -> Option<Rc<RefCell<JsObject>>> {
+    FN_PROPS.with(|m| {
+        let mut m = m.borrow_mut();
+        if create {
+            Some(Rc::clone(m.entry(id).or_insert_with(|| {
+                let mut props = JsObject::new();
+                props.define_hidden("prototype".into(), JsValue::Object(new_object()));
+                Rc::new(RefCell::new(props))
+            })))
+        } else {
+            m.get(&id).cloned()
+        }
+    })
+}
+
+/// Prototyp instanci vytvorenych `new F()` nad VM funkci.
+fn fn_prototype(id: u64) -> Option<Rc<RefCell<JsObject>>> {
+    match fn_props(id, true)?.borrow().get("prototype") {
+        JsValue::Object(p) => Some(p),
+        _ => None,
+    }
+}
+
+enum Lookup {
+    Data(JsValue),
+    Getter(Option<JsValue>),
+    Missing,
+}
+
+/// Jeden pruchod prototypovym retezcem: data vlastnost nebo getter.
+fn lookup(o: &Rc<RefCell<JsObject>>, key: impl AsKey) -> Lookup {
+    let mut cur = Rc::clone(o);
+    for _ in 0..100 {
+        let next = {
+            let b = cur.borrow();
+            if let Some(slot) = b.props.slot(&key) {
+                return if slot.is_accessor() { Lookup::Getter(slot.getter.clone()) } else { Lookup::Data(slot.value.clone()) };
+            }
+            b.proto.clone()
+        };
+        match next {
+            Some(p) => cur = p,
+            None => break,
+        }
+    }
+    Lookup::Missing
+}
+
+pub(super) fn get_prop(obj: &JsValue, key: &str) -> Result<JsValue, String> {
+    get_prop_recv(obj, key, obj)
+}
+
+/// `obj[key]` pro obecny klic; symboly maji vlastni cestu bez retezcovych vestavenych metod.
+pub(super) fn get_key(obj: &JsValue, key: &PropertyKey) -> Result<JsValue, String> {
+    get_key_recv(obj, key, obj)
+}
+
+pub(super) fn get_key_recv(obj: &JsValue, key: &PropertyKey, receiver: &JsValue) -> Result<JsValue, String> {
+    let sym = match key {
+        PropertyKey::Str(k) => return get_prop_recv(obj, k, receiver),
+        PropertyKey::Sym(sym) => sym,
+    };
+    match obj {
+        JsValue::Object(o) => {
+            if o.borrow().props.contains_key(proxy_handler::TARGET) {
+                return proxy_get(obj, key, receiver);
+            }
+            match lookup(o, sym) {
+                Lookup::Data(v) => Ok(v),
+                Lookup::Getter(Some(g)) => call_function(&g, receiver.clone(), vec![]),
+                Lookup::Getter(None) => Ok(JsValue::Undefined),
+                Lookup::Missing => Ok(builtins::builtin_symbol_prop(obj, sym).unwrap_or(JsValue::Undefined)),
+            }
+        }
+        JsValue::Function(JsFunc::VmCompiled { id, .. }) => {
+            let v = fn_props(*id, false).map(|p| p.borrow().get(sym));
+            match v {
+                Some(v) if !matches!(v, JsValue::Undefined) => Ok(v),
+                _ => Ok(builtins::builtin_symbol_prop(obj, sym).unwrap_or(JsValue::Undefined)),
+            }
+        }
+        JsValue::Undefined | JsValue::Null => {
+            let what = if matches!(obj, JsValue::Null) { "null" } else { "undefined" };
+            Err(format!("TypeError: Cannot read properties of {what} (reading '{key}')"))
+        }
+        _ => Ok(builtins::builtin_symbol_prop(obj, sym).unwrap_or(JsValue::Undefined)),
+    }
+}
+
+/// `obj[key]` s `receiver` jako `this` getteru (`super.x` cte z prototypu, this je instance).
+pub(super) fn get_prop_recv(obj: &JsValue, key: &str, receiver: &JsValue) -> Result<JsValue, String> {
+    match obj {
+        JsValue::Object(o) => {
+            if o.borrow().props.contains_key(proxy_handler::TARGET) {
+                return proxy_get(obj, &key.into(), receiver);
+            }
+            let host = is_host_object(&o.borrow());
+            if host && let Some(r) = call_machinery::host_get(obj, key) {
+                return r;
+            }
+            let binary = o.borrow().binary.clone();
+            if let Some(v) = binary.and_then(|b| b.get(key)) {
+                return Ok(v);
+            }
+            match lookup(o, key) {
+                Lookup::Data(v) => Ok(v),
+                Lookup::Getter(Some(g)) => call_function(&g, receiver.clone(), vec![]),
+                Lookup::Getter(None) => Ok(JsValue::Undefined),
+                Lookup::Missing => builtin_or_host(obj, key),
+            }
+        }
+        JsValue::Array(a) => {
+            if let Some(i) = key.parse::<usize>().ok().filter(|_| key.as_bytes()[0] != b'+') {
+                return Ok(a.borrow().get(i).cloned().unwrap_or(JsValue::Undefined));
+            }
+            builtin_or_host(obj, key)
+        }
+        JsValue::Function(JsFunc::VmCompiled { compiled, id, .. }) => {
+            if let Some(props) = fn_props(*id, key == "prototype" && !compiled.is_arrow) {
+                let v = props.borrow().get(key);
+                if !matches!(v, JsValue::Undefined) { return Ok(v); }
+            }
+            builtin_or_host(obj, key)
+        }
+        JsValue::Undefined | JsValue::Null => {
+            let what = if matches!(obj, JsValue::Null) { "null" } else { "undefined" };
+            Err(format!("TypeError: Cannot read properties of {what} (reading '{key}')"))
+        }
+        _ => builtin_or_host(obj, key),
+    }
+}
+
+/// Vestavena vlastnost VM; co VM nezna (DOM, hostitelska API), cte pod
+/// interpretem tree-walker.
+fn builtin_or_host(obj: &JsValue, key: &str) -> Result<JsValue, String> {
+    // Datum, regex, Intl...: metody zna interpret, ne obecne Object metody VM
+    let host_slots = matches!(obj, JsValue::Object(o) if has_host_slots(&o.borrow()));
+    if host_slots && let Some(r) = call_machinery::host_get(obj, key) {
+        return r;
+    }
+    match builtins::builtin_prop(obj, key) {
+        Some(v) => Ok(v),
+        None => call_machinery::host_get(obj, key).unwrap_or(Ok(JsValue::Undefined)),
+    }
+}
+
+/// Objekt s vnitrnimi sloty tree-walkeru (`__date_ms__`, `__regex_pattern__`...)
+/// - krome promise a trid VM, jejichz metody ma VM vlastni.
+fn has_host_slots(o: &JsObject) -> bool {
+    !o.props.contains_key("__promise_state__") && !o.props.contains_key(VM_CLASS)
+        && o.props.keys().any(|k| helpers::is_internal_key(k))
+}
+
+/// Znacky objektu hostitele (document, window, DOM obaly, Web API), jejichz
+/// vlastnosti a metody tree-walker obsluhuje syntakticky.
+const HOST_MARKERS: [&str; 11] = [
+    "__is_document__", "__document__", "__window__", "__element__", "__shadow_root__", "__dom_token_list__",
+    "__style_node__", "__worker__", "__storage__", "__headers__", "__response__",
+];
+
+/// Objekt hostitele - cteni, zapis i volani metod patri pod interpretem tree-walkeru.
+pub(super) fn is_host_object(o: &JsObject) -> bool {
+    HOST_MARKERS.iter().any(|m| o.props.contains_key(*m))
+}
+
+/// Zapis se semantikou hostitele: `__proto__` a objekty hostitele
+/// (Worker.onmessage, DOMTokenList.value, style objekt DOM uzlu).
+pub(super) fn host_setter(o: &JsObject, key: &str) -> bool {
+    key == "__proto__" || is_host_object(o)
+}
+
+/// `obj[key]` s hodnotovym klicem - rychla cesta pro pole a retezce.
+pub(super) fn get_index(obj: &JsValue, key: &JsValue) -> Result<JsValue, String> {
+    if let JsValue::Number(n) = key {
+        match obj {
+            JsValue::Array(a) => if let Some(i) = array_index(*n) {
+                return Ok(a.borrow().get(i).cloned().unwrap_or(JsValue::Undefined));
+            },
+            JsValue::Str(s) => if let Some(i) = array_index(*n) {
+                return Ok(s.chars().nth(i).map(|c| JsValue::Str(c.to_string())).unwrap_or(JsValue::Undefined));
+            },
+            _ => {}
+        }
+    }
+    get_key(obj, &key_of(key))
+}
+
+pub(super) fn set_prop(obj: &JsValue, key: &str, value: JsValue) -> Result<(), String> {
+    match obj {
+        JsValue::Object(o) => {
+            if o.borrow().props.contains_key(proxy_handler::TARGET) {
+                return proxy_set(obj, &key.into(), value);
+            }
+            let host = host_setter(&o.borrow(), key);
+            if host && let Some(result) = call_machinery::host_set(obj, key, value.clone())
+            {
+                return result;
+            }
+            let binary = o.borrow().binary.clone();
+            if let Some(result) = binary.and_then(|b| b.set(key, &value)) {
+                return result;
+            }
+            // Vlastni data vlastnost: bez hledani setteru v retezci.
+            let own_data = o.borrow().props.slot(key).map(|s| !s.is_accessor());
+            let accessor = if own_data == Some(true) { None } else { o.borrow().find_accessor(key) };
+            if let Some((_, setter)) = accessor {
+                if let Some(s) = setter {
+                    call_function(&s, obj.clone(), vec![value])?;
+                }
+                return Ok(());
+            }
+            o.borrow_mut().set(key.into(), value);
+            Ok(())
+        }
+        JsValue::Array(a) => {
+            if tagged_template::is_frozen_array(a) { return Ok(()); }
+            if let Ok(i) = key.parse::<usize>() {
+                set_array_index(a, i, value);
+            } else if key == "length" {
+                let n = value.to_number();
+                let len = array_index(n).ok_or("RangeError: Invalid array length")?;
+                a.borrow_mut().resize(len, JsValue::Undefined);
+            }
+            Ok(())
+        }
+        JsValue::Function(JsFunc::VmCompiled { id, .. }) => {
+            if let Some(props) = fn_props(*id, true) {
+                props.borrow_mut().set(key.into(), value);
+            }
+            Ok(())
+        }
+        JsValue::Undefined | JsValue::Null => {
+            let what = if matches!(obj, JsValue::Null) { "null" } else { "undefined" };
+            Err(format!("TypeError: Cannot set properties of {what} (setting '{key}')"))
+        }
+        JsValue::DomNode(_) | JsValue::Function(JsFunc::Class { .. }) => {
+            call_machinery::host_set(obj, key, value).unwrap_or(Ok(()))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// `obj[key] = value` pro obecny klic.
+pub(super) fn set_key(obj: &JsValue, key: &PropertyKey, value: JsValue) -> Result<(), String> {
+    let sym = match key {
+        PropertyKey::Str(k) => return set_prop(obj, k, value),
+        PropertyKey::Sym(sym) => sym,
+    };
+    match obj {
+        JsValue::Object(o) => {
+            if o.borrow().props.contains_key(proxy_handler::TARGET) {
+                return proxy_set(obj, key, value);
+            }
+            let own_data = o.borrow().props.slot(sym).map(|s| !s.is_accessor());
+            let accessor = if own_data == Some(true) { None } else { o.borrow().find_accessor(sym) };
+            if let Some((_, setter)) = accessor {
+                if let Some(s) = setter {
+                    call_function(&s, obj.clone(), vec![value])?;
+                }
+                return Ok(());
+            }
+            o.borrow_mut().set(key.clone(), value);
+            Ok(())
+        }
+        JsValue::Function(JsFunc::VmCompiled { id, .. }) => {
+            if let Some(props) = fn_props(*id, true) {
+                props.borrow_mut().set(key.clone(), value);
+            }
+            Ok(())
+        }
+        JsValue::Undefined | JsValue::Null => {
+            let what = if matches!(obj, JsValue::Null) { "null" } else { "undefined" };
+            Err(format!("TypeError: Cannot set properties of {what} (setting '{key}')"))
+        }
+        _ => Ok(()),
+    }
+}
+
+fn set_array_index(a: &Rc<RefCell<Vec<JsValue>>>, i: usize, value: JsValue) {
+    let mut arr = a.borrow_mut();
+    if i >= arr.len() { arr.resize(i + 1, JsValue::Undefined); }
+    arr[i] = value;
+}
+
+pub(super) fn set_index(obj: &JsValue, key: &JsValue, value: JsValue) -> Result<(), String> {
+    if let (JsValue::Array(a), JsValue::Number(n)) = (obj, key)
+        && let Some(i) = array_index(*n)
+        && !tagged_template::is_frozen_array(a)
+    {
+        set_array_index(a, i, value);
+        return Ok(());
+    }
+    set_key(obj, &key_of(key), value)
+}
+
+/// `delete obj[key]`.
+pub(super) fn delete_prop(obj: &JsValue, key: &JsValue) -> Result<bool, String> {
+    let k = key_of(key);
+    match obj {
+        p if proxy_handler::is_proxy(p) => {
+            let (target, handler) = proxy_handler::proxy_parts(p).ok_or("not a proxy")?;
+            match proxy_handler::trap_fn(&handler, ProxyTrap::DeleteProperty)? {
+                Some(f) => Ok(call_function(&f, handler, vec![target, k.to_value()])?.is_truthy()),
+                None => delete_prop(&target, key),
+            }
+        }
+        JsValue::Object(o) => Ok(o.borrow_mut().delete(&k)),
+        JsValue::Array(a) => {
+            if let Some(i) = k.as_str().and_then(|k| k.parse::<usize>().ok()) && let Some(slot) = a.borrow_mut().get_mut(i) {
+                *slot = JsValue::Undefined;
+            }
+            Ok(true)
+        }
+        JsValue::Undefined | JsValue::Null => Err(format!("TypeError: Cannot convert undefined or null to object (deleting '{k}')")),
+        _ => Ok(true),
+    }
+}
+
+/// Vlastni enumerable klice (for-in, spread, rest) vcetne zdedenych u `inherited`.
+/// Zdedeny klic zakryty vlastni (i ne-enumerable) vlastnosti se vynecha.
+pub(super) fn enumerable_keys(v: &JsValue, inherited: bool) -> Result<Vec<String>, String> {
+    Ok(match v {
+        p if proxy_handler::is_proxy(p) => match call_machinery::host_proxy_keys(p) {
+            Some(keys) => keys?,
+            None => Vec::new(),
+        },
+        JsValue::Object(o) => {
+            let mut keys = Vec::new();
+            let mut seen = std::collections::HashSet::new();
+            let mut cur = Some(Rc::clone(o));
+            for _ in 0..100 {
+                let Some(obj) = cur else { break };
+                let obj = obj.borrow();
+                let enumerable = obj.ordered_keys(true);
+                for k in obj.ordered_keys(false) {
+                    if seen.insert(k.clone()) && enumerable.contains(&k) { keys.push(k); }
+                }
+                cur = if inherited { obj.proto.clone() } else { None };
+            }
+            keys
+        }
+        JsValue::Array(a) => (0..a.borrow().len()).map(|i| i.to_string()).collect(),
+        JsValue::Str(s) => (0..s.chars().count()).map(|i| i.to_string()).collect(),
+        _ => Vec::new(),
+    })
+}
+
+/// `{ ...src }`: enumerable vlastnosti `src` do `target`.
+pub(super) fn copy_data_props(target: &Rc<RefCell<JsObject>>, src: &JsValue, excluded: &[PropertyKey]) -> Result<(), String> {
+    for k in enumerable_keys(src, false)? {
+        if excluded.iter().any(|e| e == k.as_str()) { continue; }
+        let v = get_prop(src, &k)?;
+        target.borrow_mut().props.insert(k.into(), v);
+    }
+    if let JsValue::Object(o) = src {
+        let syms: Vec<PropertyKey> = o.borrow().props.symbol_keys().map(PropertyKey::from).collect();
+        for k in syms {
+            if excluded.contains(&k) { continue; }
+            let v = get_key(src, &k)?;
+            target.borrow_mut().props.insert(k, v);
+        }
+    }
+    Ok(())
+}
+
+// ─── Proxy ───────────────────────────────────────────────────────────────────
+
+fn proxy_trap(proxy: &JsValue, trap: ProxyTrap) -> Result<(JsValue, JsValue, Option<JsValue>), String> {
+    let (target, handler) = proxy_handler::proxy_parts(proxy).ok_or("not a proxy")?;
+    let f = proxy_handler::trap_fn(&handler, trap)?;
+    Ok((target, handler, f))
+}
+
+fn proxy_get(proxy: &JsValue, key: &PropertyKey, receiver: &JsValue) -> Result<JsValue, String> {
+    match proxy_trap(proxy, ProxyTrap::Get)? {
+        (target, handler, Some(f)) => call_function(&f, handler, vec![target, key.to_value(), receiver.clone()]),
+        (target, _, None) => {
+            // Metody, ktere VM doplnuje za Object.prototype (`hasOwnProperty`), musi videt proxy
+            if let (JsValue::Object(t), PropertyKey::Str(k)) = (&target, key)
+                && matches!(lookup(t, k), Lookup::Missing)
+                && let Some(r) = call_machinery::host_get(proxy, k)
+            {
+                return r;
+            }
+            get_key_recv(&target, key, receiver)
+        }
+    }
+}
+
+fn proxy_set(proxy: &JsValue, key: &PropertyKey, value: JsValue) -> Result<(), String> {
+    match proxy_trap(proxy, ProxyTrap::Set)? {
+        (target, handler, Some(f)) => {
+            call_function(&f, handler, vec![target, key.to_value(), value, proxy.clone()])?;
+            Ok(())
+        }
+        (target, _, None) => set_key(&target, key, value),
+    }
+}
+
+fn proxy_apply(proxy: &JsValue, this: JsValue, args: Vec<JsValue>) -> Result<JsValue, String> {
+    match proxy_trap(proxy, ProxyTrap::Apply)? {
+        (target, handler, Some(f)) => call_function(&f, handler, vec![target, this, new_array(args)]),
+        (target, _, None) => call_function(&target, this, args),
+    }
+}
+
+fn proxy_construct(proxy: &JsValue, args: Vec<JsValue>) -> Result<JsValue, String> {
+    match proxy_trap(proxy, ProxyTrap::Construct)? {
+        (target, handler, Some(f)) => {
+            let obj = call_function(&f, handler, vec![target, new_array(args), proxy.clone()])?;
+            if !matches!(obj, JsValue::Object(_) | JsValue::Array(_) | JsValue::Function(_)) {
+                return Err("TypeError: proxy [[Construct]] must return an object".into());
+            }
+            Ok(obj)
+        }
+        (target, _, None) => construct(&target, args),
+    }
+}
+
+// ─── Volani a konstrukce ─────────────────────────────────────────────────────
+
+/// Zavola funkci z VM (nebo z tree-walkeru nad VM funkci).
+pub fn call_function(callee: &JsValue, this: JsValue, args: Vec<JsValue>) -> Result<JsValue, String> {
+    match callee {
+        // Object.* / Reflect.* nad proxy a Reflect.apply/construct obsluhuje interpret
+        JsValue::Function(JsFunc::Native(name, _)) if proxy_handler::is_reflective(name) && call_machinery::in_interpreter() => {
+            call_machinery::call_from_vm(callee, this, args)
+        }
+        JsValue::Function(JsFunc::Native(_, f)) => f(args),
+        JsValue::Function(JsFunc::VmCompiled { .. }) => frames::invoke(callee, this, args),
+        JsValue::Function(JsFunc::Bound { func, bound_this, bound_args, .. }) => {
+            let mut all = bound_args.clone();
+            all.extend(args);
+            call_function(func, (**bound_this).clone(), all)
+        }
+        JsValue::Object(_) if proxy_handler::is_proxy(callee) => proxy_apply(callee, this, args),
+        JsValue::Object(o) if o.borrow().props.contains_key(VM_CLASS) => {
+            let name = o.borrow().get("name");
+            Err(format!("TypeError: Class constructor {name} cannot be invoked without 'new'"))
+        }
+        JsValue::Function(_) => call_machinery::call_from_vm(callee, this, args),
+        other => Err(format!("TypeError: {} is not a function", describe(other))),
+    }
+}
+
+/// `new callee(...args)`.
+pub fn construct(callee: &JsValue, args: Vec<JsValue>) -> Result<JsValue, String> {
+    match callee {
+        JsValue::Object(_) if proxy_handler::is_proxy(callee) => proxy_construct(callee, args),
+        JsValue::Object(o) if o.borrow().props.contains_key(VM_CLASS) => construct_class(callee, args, callee),
+        JsValue::Function(JsFunc::VmCompiled { compiled, id, .. }) => {
+            if compiled.is_arrow || compiled.is_generator || compiled.is_async {
+                let name = compiled.name.as_deref().unwrap_or("anonymous");
+                return Err(format!("TypeError: {name} is not a constructor"));
+            }
+            let mut obj = JsObject::new();
+            obj.proto = fn_prototype(*id);
+            let this = JsValue::Object(Rc::new(RefCell::new(obj)));
+            frames::construct_call(callee, this, args, callee.clone())
+        }
+        JsValue::Function(JsFunc::Native(name, f)) => construct_native(callee, name, f.as_ref(), args),
+        JsValue::Function(JsFunc::Bound { func, bound_args, .. }) => {
+            let mut all = bound_args.clone();
+            all.extend(args);
+            construct(func, all)
+        }
+        JsValue::Function(_) => call_machinery::construct_from_vm(callee, args),
+        other => Err(format!("TypeError: {} is not a constructor", describe(other))),
+    }
+}
+
+/// Vestavene konstruktory - zrcadli `Interpreter::call_new`.
+fn construct_native(callee: &JsValue, name: &str, f: &dyn Fn(Vec<JsValue>) -> Result<JsValue, String>, args: Vec<JsValue>) -> Result<JsValue, String> {
+    let mut it = args.into_iter();
+    match name {
+        "Proxy" => {
+            let target = it.next().unwrap_or(JsValue::Undefined);
+            proxy_handler::make_proxy(target, it.next().unwrap_or(JsValue::Undefined))
+        }
+        "Map" | "WeakMap" => {
+            let weak = name == "WeakMap";
+            let mut m = if weak { JsMap::new_weak() } else { JsMap::new() };
+            if let Some(src) = it.next().filter(|v| !matches!(v, JsValue::Undefined | JsValue::Null)) {
+                for entry in collect_iterable(&src)? {
+                    let k = get_index(&entry, &JsValue::Number(0.0))?;
+                    let v = get_index(&entry, &JsValue::Number(1.0))?;
+                    if weak && !keyed_table::can_be_held_weakly(&k) {
+                        return Err("TypeError: Invalid value used as weak map key".into());
+                    }
+                    m.set(k, v);
+                }
+            }
+            Ok(JsValue::Map(Rc::new(RefCell::new(m))))
+        }
+        "Set" | "WeakSet" => {
+            let weak = name == "WeakSet";
+            let mut s = if weak { JsSet::new_weak() } else { JsSet::new() };
+            if let Some(src) = it.next().filter(|v| !matches!(v, JsValue::Undefined | JsValue::Null)) {
+                for v in collect_iterable(&src)? {
+                    if weak && !keyed_table::can_be_held_weakly(&v) {
+                        return Err("TypeError: Invalid value used in weak set".into());
+                    }
+                    s.add(v);
+                }
+            }
+            Ok(JsValue::Set(Rc::new(RefCell::new(s))))
+        }
+        "Symbol" => Err("TypeError: Symbol is not a constructor".into()),
+        "Date" => {
+            let args: Vec<JsValue> = it.collect();
+            if args.len() >= 2 {
+                let part = |i: usize, d: u32| args.get(i).map(|v| v.to_number() as u32).unwrap_or(d);
+                let ms = helpers::parts_to_ms(args[0].to_number() as i64, part(1, 0), part(2, 1), part(3, 0), part(4, 0), part(5, 0), part(6, 0));
+                return Ok(helpers::make_date_object(ms));
+            }
+            let ms = match args.into_iter().next() {
+                None | Some(JsValue::Undefined) => helpers::now_ms(),
+                Some(JsValue::Number(n)) => n,
+                Some(JsValue::Str(s)) => helpers::parse_date_string(&s),
+                Some(other) => {
+                    let n = other.to_number();
+                    if n.is_nan() { helpers::now_ms() } else { n }
+                }
+            };
+            Ok(helpers::make_date_object(ms))
+        }
+        "Promise" => frames::construct_promise(it.next().unwrap_or(JsValue::Undefined)),
+        "RegExp" => {
+            let pat = it.next().map(|v| v.to_string()).unwrap_or_default();
+            let flags = it.next().map(|v| v.to_string()).unwrap_or_default();
+            Ok(helpers::make_regex_object(&pat, &flags))
+        }
+        "Error" | "TypeError" | "RangeError" | "SyntaxError" | "ReferenceError" | "URIError" | "EvalError" => {
+            let msg = it.next().map(|v| v.to_string()).unwrap_or_default();
+            let mut obj = JsObject::new();
+            obj.set("name".into(), JsValue::Str(name.to_string()));
+            obj.set("message".into(), JsValue::Str(msg.clone()));
+            obj.set("stack".into(), JsValue::Str(error_stack(&format!("{name}: {msg}"))));
+            if let Some(JsValue::Object(opts)) = it.next() {
+                let cause = opts.borrow().props.get("cause").cloned();
+                if let Some(c) = cause { obj.set("cause".into(), c); }
+            }
+            Ok(JsValue::Object(Rc::new(RefCell::new(obj))))
+        }
+        // Ostatni vestavene konstruktory (BigNumber, WeakRef, ...) zna interpret
+        _ if call_machinery::in_interpreter() => call_machinery::construct_from_vm(callee, it.collect()),
+        _ => {
+            let r = f(it.collect())?;
+            Ok(match r {
+                JsValue::Object(_) | JsValue::DomNode(_) | JsValue::Array(_) | JsValue::Map(_) | JsValue::Set(_) => r,
+                _ => JsValue::Object(new_object()),
+            })
+        }
+    }
+}
+
+// ─── Tridy ───────────────────────────────────────────────────────────────────
+
+/// Objekt tridy: `prototype` (metody instanci), `__vm_ctor__` (telo konstruktoru),
+/// `__vm_super__` (rodic). Proto tridy je rodicovska trida (dedeni statickych clenu).
+pub(super) fn new_class(name: Option<&str>, parent: Option<JsValue>) -> Result<JsValue, String> {
+    let mut proto = JsObject::new();
+    let mut class = JsObject::new();
+    class.define_hidden(VM_CLASS.into(), JsValue::Bool(true));
+    class.define_hidden("name".into(), JsValue::Str(name.unwrap_or("").to_string()));
+    if let Some(parent) = parent {
+        match &parent {
+            JsValue::Null => {}
+            p if is_vm_class(p) => {
+                if let JsValue::Object(c) = p { class.proto = Some(Rc::clone(c)); }
+                if let JsValue::Object(pp) = get_prop(p, "prototype")? { proto.proto = Some(pp); }
+            }
+            JsValue::Function(JsFunc::VmCompiled { id, .. }) => {
+                proto.proto = fn_prototype(*id);
+            }
+            JsValue::Function(_) => {}
+            other => return Err(format!("TypeError: Class extends value {other} is not a constructor or null")),
+        }
+        class.define_hidden("__vm_super__".into(), parent);
+    }
+    let proto = Rc::new(RefCell::new(proto));
+    class.define_hidden("prototype".into(), JsValue::Object(Rc::clone(&proto)));
+    let class = JsValue::Object(Rc::new(RefCell::new(class)));
+    proto.borrow_mut().define_hidden("constructor".into(), class.clone());
+    Ok(class)
+}
+
+/// `new C(args)` nad VM tridou; `new_target` urcuje prototyp instance.
+fn construct_class(class: &JsValue, args: Vec<JsValue>, new_target: &JsValue) -> Result<JsValue, String> {
+    let JsValue::Object(c) = class else { unreachable!("VM trida je objekt") };
+    let (ctor, parent) = {
+        let c = c.borrow();
+        (c.props.get("__vm_ctor__").cloned(), c.props.get("__vm_super__").cloned())
+    };
+    match (ctor, parent) {
+        (ctor, None) => {
+            let this = instance_for(new_target)?;
+            init_fields(class, &this)?;
+            match ctor {
+                Some(f) => frames::construct_call(&f, this, args, new_target.clone()),
+                None => Ok(this),
+            }
+        }
+        // Odvozena trida: `this` vznikne az volanim super(...)
+        (Some(f), Some(_)) => frames::construct_call(&f, JsValue::Undefined, args, new_target.clone()),
+        (None, Some(parent)) => {
+            let this = super_construct(&parent, args, new_target)?;
+            init_fields(class, &this)?;
+            Ok(this)
+        }
+    }
+}
+
+/// Pole a privatni metody instance (`__vm_fields__`) na nove `this`; pred nimi
+/// `addInitializer` funkce z dekoratoru metod (`__vm_inits__`).
+fn init_fields(class: &JsValue, this: &JsValue) -> Result<(), String> {
+    let JsValue::Object(c) = class else { return Ok(()) };
+    for f in hidden_list(class, "__vm_inits__") {
+        call_function(&f, this.clone(), vec![])?;
+    }
+    let init = c.borrow().props.get("__vm_fields__").cloned();
+    match init {
+        Some(f) => call_function(&f, this.clone(), vec![]).map(|_| ()),
+        None => Ok(()),
+    }
+}
+
+/// Po `super(...)` v konstruktoru odvozene tridy: pole te tridy v retezci
+/// `new_target`, jejimz rodicem je `parent` (tj. tridy prave bezici konstruktoru).
+pub(super) fn init_fields_after_super(parent: &JsValue, new_target: &JsValue, this: &JsValue) -> Result<(), String> {
+    let mut class = new_target.clone();
+    loop {
+        let JsValue::Object(c) = &class else { return Ok(()) };
+        let sup = c.borrow().props.get("__vm_super__").cloned();
+        match sup {
+            Some(s) if s.strict_eq(parent) => return init_fields(&class, this),
+            Some(s) => class = s,
+            None => return Ok(()),
+        }
+    }
+}
+
+/// Prazdna instance s prototypem `new_target.prototype`.
+fn instance_for(new_target: &JsValue) -> Result<JsValue, String> {
+    let mut obj = JsObject::new();
+    obj.proto = match new_target {
+        JsValue::Function(JsFunc::VmCompiled { id, .. }) => fn_prototype(*id),
+        t => match get_prop(t, "prototype")? {
+            JsValue::Object(p) => Some(p),
+            _ => None,
+        },
+    };
+    Ok(JsValue::Object(Rc::new(RefCell::new(obj))))
+}
+
+/// `super(...args)` - zkonstruuje `this` rodicem, prototyp podle `new_target`.
+pub(super) fn super_construct(parent: &JsValue, args: Vec<JsValue>, new_target: &JsValue) -> Result<JsValue, String> {
+    match parent {
+        p if is_vm_class(p) => construct_class(p, args, new_target),
+        JsValue::Null => instance_for(new_target),
+        JsValue::Function(JsFunc::VmCompiled { .. }) => {
+            let this = instance_for(new_target)?;
+            frames::construct_call(parent, this, args, new_target.clone())
+        }
+        _ => {
+            // Vestaveny rodic (Error, Map, ...): instance z nativniho konstruktoru
+            let obj = construct(parent, args)?;
+            if let (JsValue::Object(o), JsValue::Object(p)) = (&obj, instance_for(new_target)?) {
+                let proto = p.borrow().proto.clone();
+                o.borrow_mut().proto = proto;
+            }
+            Ok(obj)
+        }
+    }
+}
+
+/// `super[key]` v metode: nestaticke metody ctou z rodicovskeho prototypu.
+pub(super) fn super_get(parent: &JsValue, key: &PropertyKey, this: &JsValue, is_static: bool) -> Result<JsValue, String> {
+    let home = match parent {
+        JsValue::Null | JsValue::Undefined => return Ok(JsValue::Undefined),
+        p if is_static => p.clone(),
+        p => get_prop(p, "prototype")?,
+    };
+    match home {
+        JsValue::Undefined | JsValue::Null => Ok(JsValue::Undefined),
+        h => get_key_recv(&h, key, this),
+    }
+}
+
+/// `obj.#x` - brand check, hodnota nebo getter (this = obj).
+pub(super) fn get_private(obj: &JsValue, key: &str) -> Result<JsValue, String> {
+    let slot = private_names::check_get(obj, key)?;
+    match slot.getter {
+        Some(getter) => call_function(&getter, obj.clone(), vec![]),
+        None => Ok(slot.value),
+    }
+}
+
+/// `obj.#x = value` - brand check, setter nebo zapis do pole.
+pub(super) fn set_private(obj: &JsValue, key: &str, value: JsValue) -> Result<(), String> {
+    let slot = private_names::check_set(obj, key)?;
+    match slot.setter {
+        Some(setter) => call_function(&setter, obj.clone(), vec![value]).map(|_| ()),
+        None => {
+            private_names::write(obj, key, value);
+            Ok(())
+        }
+    }
+}
+
+/// Metoda/accessor tridy na prototyp (nebo tridu u statickych clenu).
+pub(super) fn define_method(class: &JsValue, key: &PropertyKey, f: JsValue, flags: u8) -> Result<(), String> {
+    let target = if flags & 1 != 0 { class.clone() } else { get_prop(class, "prototype")? };
+    let JsValue::Object(t) = target else { return Err("TypeError: trida nema prototyp".into()) };
+    let mut t = t.borrow_mut();
+    match flags & 6 {
+        2 => t.define_accessor(key.clone(), Some(f), None),
+        4 => t.define_accessor(key.clone(), None, Some(f)),
+        _ => t.define_hidden(key.clone(), f),
+    }
+    Ok(())
+}
+
+// ─── Dekoratory ──────────────────────────────────────────────────────────────
+
+/// `context.metadata` tridy: vznika s prvnim dekoratorem (`__vm_metadata__`)
+/// a dedi ze `Symbol.metadata` rodice.
+fn class_metadata(class: &JsValue) -> Result<JsValue, String> {
+    let JsValue::Object(c) = class else { return Ok(JsValue::Undefined) };
+    let (meta, parent) = {
+        let c = c.borrow();
+        (c.props.get("__vm_metadata__").cloned(), c.props.get("__vm_super__").cloned())
+    };
+    if let Some(m) = meta {
+        return Ok(m);
+    }
+    let parent_meta = match parent {
+        Some(p @ (JsValue::Object(_) | JsValue::Function(_))) => get_key(&p, &symbols::METADATA.into())?,
+        _ => JsValue::Undefined,
+    };
+    let m = decorators::new_metadata(parent_meta);
+    c.borrow_mut().define_hidden("__vm_metadata__".into(), m.clone());
+    Ok(m)
+}
+
+/// Prvky skryteho pole `list` objektu (prazdne, kdyz chybi).
+fn hidden_list(obj: &JsValue, list: &str) -> Vec<JsValue> {
+    let JsValue::Object(o) = obj else { return Vec::new() };
+    match o.borrow().props.get(list) {
+        Some(JsValue::Array(a)) => a.borrow().clone(),
+        _ => Vec::new(),
+    }
+}
+
+/// Prida funkce z `addInitializer` do skryteho pole `list` tridy.
+fn push_hidden(obj: &JsValue, list: &str, items: Vec<JsValue>) {
+    let JsValue::Object(o) = obj else { return };
+    if items.is_empty() {
+        return;
+    }
+    let existing = o.borrow().props.get(list).cloned();
+    match existing {
+        Some(JsValue::Array(a)) => a.borrow_mut().extend(items),
+        _ => o.borrow_mut().define_hidden(list.into(), new_array(items)),
+    }
+}
+
+fn array_items(v: &JsValue) -> Vec<JsValue> {
+    match v {
+        JsValue::Array(a) => a.borrow().clone(),
+        _ => Vec::new(),
+    }
+}
+
+/// `context.access` clenu `key`: nativni `get(o)` / `set(o, v)` / `has(o)`.
+fn decorator_access(kind: DecoratorKind, key: &PropertyKey) -> JsValue {
+    let private = private_names::is_private_key(key);
+    let native = |name: &str, f: Rc<dyn Fn(Vec<JsValue>) -> Result<JsValue, String>>| {
+        JsValue::Function(JsFunc::Native(name.into(), f))
+    };
+    let (get, set) = kind.access();
+    let k = key.clone();
+    let get = get.then(|| {
+        let k = k.clone();
+        native("get", Rc::new(move |args| {
+            let o = args.into_iter().next().unwrap_or(JsValue::Undefined);
+            if private { get_private(&o, &k.to_string()) } else { get_key(&o, &k) }
+        }))
+    });
+    let set = set.then(|| {
+        let k = k.clone();
+        native("set", Rc::new(move |args| {
+            let mut args = args.into_iter();
+            let o = args.next().unwrap_or(JsValue::Undefined);
+            let v = args.next().unwrap_or(JsValue::Undefined);
+            if private { set_private(&o, &k.to_string(), v)? } else { set_key(&o, &k, v)? }
+            Ok(JsValue::Undefined)
+        }))
+    });
+    let has = native("has", Rc::new(move |args| {
+        let o = args.into_iter().next().unwrap_or(JsValue::Undefined);
+        let r = if private { private_names::has(&o, &k.to_string())? } else { has_property(&o, &k.to_value())? };
+        Ok(JsValue::Bool(r))
+    }));
+    decorators::access_object(get, set, has)
+}
+
+fn call_decorator(dec: &JsValue, args: Vec<JsValue>) -> Result<JsValue, String> {
+    call_function(dec, JsValue::Undefined, args)
+}
+
+/// Opcode `Decorate`: dekoratory `decs` (pole) clenu `key` tridy `class`.
+/// Metoda/getter/setter vraci (nahradni) funkci, pole a accessor zaznam
+/// s inicializatory pro `InitDecoratedField` - accessor navic s `get`/`set`.
+pub(super) fn decorate(class: &JsValue, key: &PropertyKey, value: JsValue, decs: &JsValue, flags: u8) -> Result<JsValue, String> {
+    let kind = DecoratorKind::ALL[(flags & 7) as usize];
+    let is_static = flags & 8 != 0;
+    let mut ctx = DecoratorContext::member(kind, key, is_static);
+    ctx.access = Some(decorator_access(kind, key));
+    let metadata = class_metadata(class)?;
+    let decs = array_items(decs);
+    let mut transforms = Vec::new();
+    let (record, inits) = match kind {
+        DecoratorKind::Field => {
+            let step = |init| { transforms.push(init); Ok(JsValue::Undefined) };
+            let (_, inits) = decorators::apply(&decs, &ctx, JsValue::Undefined, &metadata, call_decorator, step, std::convert::identity)?;
+            (JsValue::Object(new_object()), inits)
+        }
+        DecoratorKind::Accessor => {
+            let mut pair = [get_prop(&value, "get")?, get_prop(&value, "set")?];
+            let step = |result: JsValue| {
+                let [get, set, init] = decorators::accessor_parts(&result)?;
+                for (slot, f) in pair.iter_mut().zip([get, set]) {
+                    if let Some(f) = f { *slot = f; }
+                }
+                transforms.extend(init);
+                Ok(decorators::accessor_object(&pair))
+            };
+            let (_, inits) = decorators::apply(&decs, &ctx, value, &metadata, call_decorator, step, std::convert::identity)?;
+            (decorators::accessor_object(&pair), inits)
+        }
+        _ => {
+            let (f, inits) = decorators::apply(&decs, &ctx, value, &metadata, call_decorator, Ok, std::convert::identity)?;
+            push_hidden(class, if is_static { "__vm_static_inits__" } else { "__vm_inits__" }, inits);
+            return Ok(f);
+        }
+    };
+    if let JsValue::Object(r) = &record {
+        let mut r = r.borrow_mut();
+        r.define_hidden("__decorator_init__".into(), new_array(transforms));
+        r.define_hidden("__decorator_extra__".into(), new_array(inits));
+    }
+    Ok(record)
+}
+
+/// Opcode `InitDecoratedField`: pocatecni hodnotu prohnanou inicializatory
+/// dekoratoru, definice pole a pak jeho `addInitializer` funkce.
+pub(super) fn init_decorated_field(this: &JsValue, key: &PropertyKey, mut value: JsValue, record: &JsValue) -> Result<(), String> {
+    for init in hidden_list(record, "__decorator_init__") {
+        value = call_function(&init, this.clone(), vec![value])?;
+    }
+    private_names::define_field(this, key, value)?;
+    for f in hidden_list(record, "__decorator_extra__") {
+        call_function(&f, this.clone(), vec![])?;
+    }
+    Ok(())
+}
+
+/// Opcode `DecorateClass`: dekoratory tridy, `Symbol.metadata` na vysledku
+/// a `addInitializer` funkce statickych metod (this = puvodni trida).
+pub(super) fn decorate_class(class: &JsValue, decs: &JsValue) -> Result<JsValue, String> {
+    let metadata = class_metadata(class)?;
+    let name = match get_prop(class, "name")? {
+        JsValue::Str(s) => s,
+        _ => String::new(),
+    };
+    let ctx = DecoratorContext::new(DecoratorKind::Class, &name);
+    let (result, inits) =
+        decorators::apply(&array_items(decs), &ctx, class.clone(), &metadata, call_decorator, Ok, std::convert::identity)?;
+    push_hidden(class, "__vm_class_inits__", inits);
+    set_key(&result, &symbols::METADATA.into(), metadata)?;
+    for f in hidden_list(class, "__vm_static_inits__") {
+        call_function(&f, class.clone(), vec![])?;
+    }
+    Ok(result)
+}
+
+/// Opcode `FinishClass`: `addInitializer` funkce dekoratoru tridy (this =
+/// vysledna trida) - az po statickych polich.
+pub(super) fn finish_class(class: &JsValue, result: &JsValue) -> Result<(), String> {
+    for f in hidden_list(class, "__vm_class_inits__") {
+        call_function(&f, result.clone(), vec![])?;
+    }
+    Ok(())
+}
+
+// ─── Iterace ─────────────────────────────────────────────────────────────────
+
+/// Iterovatelna hodnota -> (iter, cursor). Cursor: `Number(i)` = primy index do
+/// pole, `Undefined` = iteracni protokol, `Bool(true)` = vycerpano.
+/// Mode: 0 sync, 1 async (`Symbol.asyncIterator`), 2 vzdy iterator objekt.
+pub(super) fn get_iter(v: JsValue, mode: u8) -> Result<(JsValue, JsValue), String> {
+    if mode == 1 && let JsValue::Object(o) = &v {
+        let m = o.borrow().get(symbols::ASYNC_ITERATOR);
+        if matches!(m, JsValue::Function(_)) {
+            let it = call_function(&m, v.clone(), vec![])?;
+            return Ok((it, JsValue::Undefined));
+        }
+    }
+    let values = match &v {
+        JsValue::Array(_) if mode != 2 => return Ok((v, JsValue::Number(0.0))),
+        JsValue::Array(a) => a.borrow().clone(),
+        JsValue::Str(s) => s.chars().map(|c| JsValue::Str(c.to_string())).collect(),
+        JsValue::Map(_) | JsValue::Set(_) if helpers::is_weak_collection(&v) => {
+            return Err(format!("TypeError: {} is not iterable", describe(&v)));
+        }
+        // Zivy iterator: mutace behem for-of se projevi (spec poradi).
+        JsValue::Map(_) => return Ok((helpers::make_collection_iterator(&v, CollectionIterKind::Entries), JsValue::Undefined)),
+        JsValue::Set(_) => return Ok((helpers::make_collection_iterator(&v, CollectionIterKind::Values), JsValue::Undefined)),
+        JsValue::Object(_) => {
+            let m = get_key(&v, &symbols::ITERATOR.into())?;
+            if is_callable(&m) {
+                let it = call_function(&m, v.clone(), vec![])?;
+                if !matches!(it, JsValue::Object(_)) {
+                    return Err("TypeError: Result of the Symbol.iterator method is not an object".into());
+                }
+                return Ok((it, JsValue::Undefined));
+            }
+            // Objekt s `next` bez Symbol.iterator jako iterator; async iterator ne
+            let async_iter = matches!(&v, JsValue::Object(o) if o.borrow().props.contains_key(symbols::ASYNC_ITERATOR));
+            if !async_iter && is_callable(&get_prop(&v, "next")?) {
+                return Ok((v, JsValue::Undefined));
+            }
+            return Err(format!("TypeError: {} is not iterable", describe(&v)));
+        }
+        other => return Err(format!("TypeError: {} is not iterable", describe(other))),
+    };
+    if mode == 2 {
+        return Ok((helpers::make_iterator_from_values(values), JsValue::Undefined));
+    }
+    Ok((new_array(values), JsValue::Number(0.0)))
+}
+
+/// Surovy vysledek `next()` ({ value, done }).
+pub(super) fn iter_next_raw(iter: &JsValue, cursor: &mut JsValue) -> Result<JsValue, String> {
+    match cursor {
+        JsValue::Number(i) => {
+            let idx = *i as usize;
+            let item = match iter {
+                JsValue::Array(a) => a.borrow().get(idx).cloned(),
+                _ => None,
+            };
+            match item {
+                Some(v) => {
+                    *i += 1.0;
+                    Ok(helpers::make_iter_result(v, false))
+                }
+                None => {
+                    *cursor = JsValue::Bool(true);
+                    Ok(helpers::make_iter_result(JsValue::Undefined, true))
+                }
+            }
+        }
+        JsValue::Undefined => {
+            let next = get_prop(iter, "next")?;
+            let r = call_function(&next, iter.clone(), vec![])?;
+            if !matches!(r, JsValue::Object(_)) {
+                return Err(format!("TypeError: Iterator result {} is not an object", describe(&r)));
+            }
+            Ok(r)
+        }
+        _ => Ok(helpers::make_iter_result(JsValue::Undefined, true)),
+    }
+}
+
+/// Dalsi hodnota iteratoru (None = vycerpano, cursor se oznaci jako done).
+pub(super) fn iter_step(iter: &JsValue, cursor: &mut JsValue) -> Result<Option<JsValue>, String> {
+    if let (JsValue::Number(i), JsValue::Array(a)) = (&mut *cursor, iter) {
+        let idx = *i as usize;
+        let item = a.borrow().get(idx).cloned();
+        return Ok(match item {
+            Some(v) => {
+                *i += 1.0;
+                Some(v)
+            }
+            None => {
+                *cursor = JsValue::Bool(true);
+                None
+            }
+        });
+    }
+    if !matches!(cursor, JsValue::Undefined) { return Ok(None); }
+    let r = iter_next_raw(iter, cursor)?;
+    if get_prop(&r, "done")?.is_truthy() {
+        *cursor = JsValue::Bool(true);
+        return Ok(None);
+    }
+    get_prop(&r, "value").map(Some)
+}
+
+/// Predcasne ukonceni iterace: nevycerpany protocol iterator dostane `return()`.
+pub(super) fn iter_close(iter: &JsValue, cursor: &JsValue) -> Result<(), String> {
+    if !matches!(cursor, JsValue::Undefined) { return Ok(()); }
+    let ret = get_prop(iter, "return")?;
+    if is_callable(&ret) {
+        call_function(&ret, iter.clone(), vec![])?;
+    }
+    Ok(())
+}
+
+/// Vsechny hodnoty iterovatelne hodnoty (spread, `new Set(iter)`).
+pub(super) fn collect_iterable(v: &JsValue) -> Result<Vec<JsValue>, String> {
+    if let JsValue::Array(a) = v { return Ok(a.borrow().clone()); }
+    let (iter, mut cursor) = get_iter(v.clone(), 0)?;
+    let mut out = Vec::new();
+    while let Some(x) = iter_step(&iter, &mut cursor)? {
+        out.push(x);
+    }
+    Ok(out)
+}
@@ -0,0 +1,1045 @@
+//! Interpret loop VM a unwinding try/finally handleru.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crate::ast::BinaryOp;
+use crate::interpreter::{budget, call_machinery, gc, helpers, private_names, property_map::PropertyKey, strict_mode, Environment, JsFunc, JsObject, JsValue};
+use super::ic::Access;
+use super::{runtime, CaptureSource, CodeBlock, CompiledFunction, Opcode};
+
+/// Interni klic signalu `gen.return(v)` - unwind, ktery `catch` nechyta.
+const GEN_RETURN: &str = "__vm_gen_return__";
+
+/// Duvod preruseni `execute`.
+pub(super) enum Exit {
+    Return(JsValue),
+    /// `yield v` - ramec pokracuje od ulozeneho pc s hodnotou z `next(x)`.
+    Yield(JsValue),
+    /// `await v` v async ramci - ramec pokracuje po settlementu `v`.
+    Await(JsValue),
+}
+
+#[derive(Clone, Copy)]
+struct TryFrame {
+    handler: usize,
+    /// Hloubka stacku pri PushTry - unwind ho na ni zkrati.
+    depth: usize,
+    /// Finally handler (dostane i signal gen.return).
+    finally: bool,
+}
+
+pub struct VM {
+    stack: Vec<JsValue>,
+    /// Lokalni promenne (index = slot z CodeBlock.var_names).
+    pub locals: Vec<JsValue>,
+    /// Globalni env pro LoadGlobal/StoreGlobal. Bez env = prazdny global.
+    pub env: Option<Rc<RefCell<Environment>>>,
+    /// Closure captures (boxovane capture jsou 1-prvkova Array).
+    captures: Vec<JsValue>,
+    boxed: Vec<bool>,
+    /// `this` binding pri method nebo constructor call.
+    pub(super) this_value: JsValue,
+    /// Argumenty volani (LoadArg/LoadRestArgs/LoadArguments).
+    args: Vec<JsValue>,
+    arguments: Option<JsValue>,
+    try_stack: Vec<TryFrame>,
+    /// Funkce, na kterou bylo zavolano `new` (super() urcuje prototyp instance).
+    new_target: JsValue,
+    /// Await suspenduje ramec (async funkce); jinak rozbali settled promise.
+    pub(super) is_async_frame: bool,
+}
+
+impl Default for VM {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl VM {
+    pub fn new() -> Self {
+        Self {
+            stack: Vec::with_capacity(64),
+            locals: Vec::new(),
+            env: None,
+            captures: Vec::new(),
+            boxed: Vec::new(),
+            this_value: JsValue::Undefined,
+            args: Vec::new(),
+            arguments: None,
+            try_stack: Vec::new(),
+            new_target: JsValue::Undefined,
+            is_async_frame: false,
+        }
+    }
+
+    /// Ramec kodu programu nad scopem `env`; `this` je jeho vlastni `this` (window).
+    pub fn with_env(env: Rc<RefCell<Environment>>) -> Self {
+        let this_value = env.borrow().get_own("this").unwrap_or(JsValue::Undefined);
+        Self { env: Some(env), this_value, ..Self::new() }
+    }
+
+    /// Ramec pro volani zkompilovane funkce `func`.
+    pub(super) fn for_call(func: &JsValue, compiled: &CompiledFunction, env: &Rc<RefCell<Environment>>,
+                           captures: &[JsValue], this: JsValue, args: Vec<JsValue>) -> Self {
+        // Sloppy funkce volana bez `this` dostane globalni `this` (window)
+        let this = match this {
+            JsValue::Undefined | JsValue::Null if !compiled.code.strict && !compiled.is_arrow => {
+                env.borrow().get("this").unwrap_or(JsValue::Undefined)
+            }
+            t => t,
+        };
+        let mut vm = Self {
+            env: Some(Rc::clone(env)),
+            captures: captures.to_vec(),
+            boxed: compiled.boxed.clone(),
+            this_value: this,
+            ..Self::new()
+        };
+        vm.init_locals(&compiled.code);
+        if compiled.self_slot && !vm.locals.is_empty() {
+            vm.bind_slot(&compiled.code, 0, func.clone());
+        }
+        for (i, slot) in compiled.param_slots.iter().enumerate() {
+            if let Some(s) = slot {
+                vm.bind_slot(&compiled.code, *s, args.get(i).cloned().unwrap_or(JsValue::Undefined));
+            }
+        }
+        vm.args = args;
+        vm
+    }
+
+    /// Vazba predana ramci (parametr, jmeno funkce) - zachyceny slot plni box.
+    fn bind_slot(&mut self, code: &CodeBlock, slot: u16, v: JsValue) {
+        if code.cells.contains(&slot) {
+            write_box(&self.locals[slot as usize], v);
+        } else {
+            self.locals[slot as usize] = v;
+        }
+    }
+
+    pub(super) fn set_new_target(&mut self, t: JsValue) {
+        self.new_target = t;
+    }
+
+    fn init_locals(&mut self, code: &CodeBlock) {
+        if self.locals.len() < code.var_names.len() {
+            self.locals.resize(code.var_names.len(), JsValue::Undefined);
+        }
+        for &c in &code.cells {
+            // Box `let`/`const`/`class` je do deklarace prazdny (TDZ)
+            let init = if code.lexical.contains(&c) { vec![] } else { vec![JsValue::Undefined] };
+            let cell = runtime::new_array(init);
+            // Bunka je hrana closure -> promenna; bez registrace by drzela cykly nazivu
+            gc::track_value(&cell);
+            self.locals[c as usize] = cell;
+        }
+    }
+
+    /// Spusti program; vysledek je hodnota posledniho vyrazoveho prikazu.
+    pub fn run(&mut self, code: &CodeBlock) -> Result<JsValue, String> {
+        let _frame = runtime::enter_frame(None);
+        self.init_locals(code);
+        let mut pc = 0;
+        match self.execute(code, &mut pc)? {
+            Exit::Return(v) | Exit::Yield(v) | Exit::Await(v) => Ok(v),
+        }
+    }
+
+    /// Bezi od `pc` do Return/Yield/Await. Vyjimky bez handleru vraci `Err`
+    /// (hodnota pres `runtime::take_thrown`).
+    pub(super) fn execute(&mut self, code: &CodeBlock, pc: &mut usize) -> Result<Exit, String> {
+        loop {
+            match self.run_loop(code, pc) {
+                Ok(exit) => return Ok(exit),
+                // Bez handleru zustava puvodni zprava (runtime chyba se neobali).
+                Err(msg) if self.try_stack.is_empty() => {
+                    let span = code.span_at(pc.saturating_sub(1));
+                    let err = match runtime::take_thrown(&msg) {
+                        Some(v) => match gen_return_value(&v) {
+                            Some(r) => return Ok(Exit::Return(r)),
+                            None => runtime::throw_value(v),
+                        },
+                        None => msg,
+                    };
+                    if let Some(span) = span { runtime::record_error_span(&err, span); }
+                    return Err(err);
+                }
+                // Ukonceni rozpoctem nechyta catch ani finally
+                Err(msg) if budget::is_terminating() => return Err(msg),
+                Err(msg) => {
+                    let v = runtime::thrown_value(&msg);
+                    if let Some(exit) = self.unwind(v, pc)? {
+                        return Ok(exit);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Vyhodi `v` v miste `pc` (i `gen.throw(v)` / rejected await pri resume).
+    /// Ok(None) = handler nalezen, pc ukazuje na nej.
+    pub(super) fn unwind(&mut self, v: JsValue, pc: &mut usize) -> Result<Option<Exit>, String> {
+        let returning = gen_return_value(&v);
+        while let Some(frame) = self.try_stack.pop() {
+            if returning.is_some() && !frame.finally { continue; }
+            self.stack.truncate(frame.depth);
+            self.stack.push(v);
+            *pc = frame.handler;
+            return Ok(None);
+        }
+        match returning {
+            Some(r) => Ok(Some(Exit::Return(r))),
+            None => Err(runtime::throw_value(v)),
+        }
+    }
+
+    /// Resume po yield/await: hodnota vyrazu na stack.
+    pub(super) fn push(&mut self, v: JsValue) {
+        self.stack.push(v);
+    }
+
+    fn pop(&mut self) -> Result<JsValue, String> {
+        self.stack.pop().ok_or_else(|| "stack underflow".to_string())
+    }
+
+    fn peek(&self) -> Result<&JsValue, String> {
+        self.stack.last().ok_or_else(|| "stack underflow".to_string())
+    }
+
+    fn pop_n(&mut self, n: usize) -> Result<Vec<JsValue>, String> {
+        if self.stack.len() < n { return Err("stack underflow".into()); }
+        Ok(self.stack.split_off(self.stack.len() - n))
+    }
+
+    fn pop_args_array(&mut self) -> Result<Vec<JsValue>, String> {
+        match self.pop()? {
+            JsValue::Array(a) => Ok(a.borrow().clone()),
+            _ => Err("args nejsou Array".into()),
+        }
+    }
+
+    /// Iterator a cursor pod vrcholem stacku ([.., iter, cursor]).
+    fn iter_top(&mut self) -> Result<(JsValue, &mut JsValue), String> {
+        let n = self.stack.len();
+        if n < 2 { return Err("stack underflow".into()); }
+        let iter = self.stack[n - 2].clone();
+        Ok((iter, &mut self.stack[n - 1]))
+    }
+
+    fn binary(&mut self, op: BinaryOp) -> Result<(), String> {
+        let b = self.pop()?;
+        let a = self.pop()?;
+        let r = match (&a, &b, op) {
+            (JsValue::Number(x), JsValue::Number(y), BinaryOp::Sub) => JsValue::Number(x - y),
+            (JsValue::Number(x), JsValue::Number(y), BinaryOp::Mul) => JsValue::Number(x * y),
+            (JsValue::Number(x), JsValue::Number(y), BinaryOp::Div) => JsValue::Number(x / y),
+            (JsValue::Number(x), JsValue::Number(y), BinaryOp::Lt) => JsValue::Bool(x < y),
+            (JsValue::Number(x), JsValue::Number(y), BinaryOp::Gt) => JsValue::Bool(x > y),
+            (JsValue::Number(x), JsValue::Number(y), BinaryOp::LtEq) => JsValue::Bool(x <= y),
+            (JsValue::Number(x), JsValue::Number(y), BinaryOp::GtEq) => JsValue::Bool(x >= y),
+            _ => runtime::binary(op, a, b)?,
+        };
+        self.stack.push(r);
+        Ok(())
+    }
+
+    fn local(&self, i: u16) -> JsValue {
+        self.locals.get(i as usize).cloned().unwrap_or(JsValue::Undefined)
+    }
+
+    fn set_local(&mut self, i: u16, v: JsValue) {
+        let i = i as usize;
+        if i >= self.locals.len() { self.locals.resize(i + 1, JsValue::Undefined); }
+        self.locals[i] = v;
+    }
+
+    /// Aktualizace ciselneho lokalu (Inc/Dec); vraci puvodni hodnotu.
+    fn update_local(&mut self, i: u16, delta: f64) -> JsValue {
+        let old = match self.local(i) {
+            JsValue::Number(n) => n,
+            other => other.to_number(),
+        };
+        self.set_local(i, JsValue::Number(old + delta));
+        JsValue::Number(old)
+    }
+
+    fn arguments_object(&mut self) -> JsValue {
+        self.arguments.get_or_insert_with(|| runtime::new_array(self.args.clone())).clone()
+    }
+
+    /// Hodnoty captures nove closure podle `compiled.captures`.
+    fn materialize_captures(&mut self, compiled: &CompiledFunction) -> Vec<JsValue> {
+        let mut out = Vec::with_capacity(compiled.captures.len());
+        for (k, src) in compiled.captures.iter().enumerate() {
+            let child_boxed = compiled.boxed[k];
+            let v = match *src {
+                CaptureSource::Local(s) => {
+                    let v = self.local(s);
+                    if child_boxed { runtime::new_array(vec![v]) } else { v }
+                }
+                CaptureSource::Cell(s) => self.local(s),
+                CaptureSource::Capture(i) => {
+                    let v = self.captures.get(i as usize).cloned().unwrap_or(JsValue::Undefined);
+                    let parent_boxed = self.boxed.get(i as usize).copied().unwrap_or(false);
+                    match (parent_boxed, child_boxed) {
+                        (true, true) | (false, false) => v,
+                        (true, false) => unbox(&v),
+                        (false, true) => runtime::new_array(vec![v]),
+                    }
+                }
+                CaptureSource::This => {
+                    let v = self.this_value.clone();
+                    if child_boxed { runtime::new_array(vec![v]) } else { v }
+                }
+                CaptureSource::Arguments => {
+                    let v = self.arguments_object();
+                    if child_boxed { runtime::new_array(vec![v]) } else { v }
+                }
+            };
+            out.push(v);
+        }
+        out
+    }
+
+    fn run_loop(&mut self, code: &CodeBlock, pc: &mut usize) -> Result<Exit, String> {
+        let bytecode = &code.bytecode;
+        while *pc < bytecode.len() {
+            budget::charge()?;
+            let op = bytecode[*pc];
+            *pc += 1;
+            match op {
+                Opcode::LoadConst(i) | Opcode::LoadStrConst(i) => self.stack.push(code.constants[i as usize].clone()),
+                Opcode::LoadVar(i) => {
+                    let v = self.local(i);
+                    self.stack.push(v);
+                }
+                Opcode::StoreVar(i) | Opcode::DeclareVar(i) => {
+                    let v = self.pop()?;
+                    self.set_local(i, v);
+                }
+                Opcode::LoadCell(i) => {
+                    let v = unbox_checked(&self.local(i), &code.var_names[i as usize])?;
+                    self.stack.push(v);
+                }
+                Opcode::StoreCell(i) => {
+                    let v = self.pop()?;
+                    let cell = self.local(i);
+                    check_initialized(&cell, &code.var_names[i as usize])?;
+                    write_box(&cell, v);
+                }
+                Opcode::DeclareCell(i) => {
+                    let v = self.pop()?;
+                    let cell = self.local(i);
+                    if code.lexical.contains(&i) && check_initialized(&cell, "").is_ok() {
+                        self.set_local(i, runtime::new_array(vec![v]));
+                    } else {
+                        write_box(&cell, v);
+                    }
+                }
+                Opcode::UpdateCell(i, inc, prefix) => {
+                    let cell = self.local(i);
+                    let old = unbox_checked(&cell, &code.var_names[i as usize])?.to_number();
+                    let new = if inc { old + 1.0 } else { old - 1.0 };
+                    write_box(&cell, JsValue::Number(new));
+                    self.stack.push(JsValue::Number(if prefix { new } else { old }));
+                }
+                Opcode::RenewCell(i) => {
+                    let v = unbox(&self.local(i));
+                    self.set_local(i, runtime::new_array(vec![v]));
+                }
+                Opcode::Pop => { self.pop()?; }
+                Opcode::Dup => {
+                    let v = self.peek()?.clone();
+                    self.stack.push(v);
+                }
+                Opcode::Dup2 => {
+                    let n = self.stack.len();
+                    if n < 2 { return Err("stack underflow".into()); }
+                    self.stack.extend_from_within(n - 2..);
+                }
+                Opcode::Swap => {
+                    let n = self.stack.len();
+                    if n < 2 { return Err("stack underflow".into()); }
+                    self.stack.swap(n - 1, n - 2);
+                }
+                Opcode::LoadUndefined => self.stack.push(JsValue::Undefined),
+                Opcode::LoadNull => self.stack.push(JsValue::Null),
+                Opcode::LoadTrue => self.stack.push(JsValue::Bool(true)),
+                Opcode::LoadFalse => self.stack.push(JsValue::Bool(false)),
+                Opcode::LoadZero => self.stack.push(JsValue::Number(0.0)),
+                Opcode::LoadOne => self.stack.push(JsValue::Number(1.0)),
+
+                Opcode::Add => {
+                    let b = self.pop()?;
+                    let a = self.pop()?;
+                    let r = match (a, b) {
+                        (JsValue::Number(x), JsValue::Number(y)) => JsValue::Number(x + y),
+                        (JsValue::Str(mut x), JsValue::Str(y)) => {
+                            x.push_str(&y);
+                            JsValue::Str(x)
+                        }
+                        (a, b) => runtime::binary(BinaryOp::Add, a, b)?,
+                    };
+                    self.stack.push(r);
+                }
+                Opcode::Sub => self.binary(BinaryOp::Sub)?,
+                Opcode::Mul => self.binary(BinaryOp::Mul)?,
+                Opcode::Div => self.binary(BinaryOp::Div)?,
+                Opcode::Mod => self.binary(BinaryOp::Mod)?,
+                Opcode::Exp => self.binary(BinaryOp::Exp)?,
+                Opcode::Neg => {
+                    let a = runtime::to_primitive(self.pop()?, "number")?;
+                    let r = match a {
+                        JsValue::BigInt(n) => JsValue::BigInt(Rc::new(-(*n).clone())),
+                        JsValue::Symbol(_) => return Err("TypeError: Cannot convert a Symbol value to a number".into()),
+                        other => JsValue::Number(-other.to_number()),
+                    };
+                    self.stack.push(r);
+                }
+                Opcode::Pos => {
+                    let a = self.pop()?;
+                    let r = match a {
+                        JsValue::Number(_) => a,
+                        JsValue::Symbol(_) => return Err("TypeError: Cannot convert a Symbol value to a number".into()),
+                        JsValue::BigInt(_) => return Err("TypeError: Cannot convert a BigInt value to a number".into()),
+                        other => JsValue::Number(runtime::to_primitive(other, "number")?.to_number()),
+                    };
+                    self.stack.push(r);
+                }
+                Opcode::Not => {
+                    let a = self.pop()?;
+                    self.stack.push(JsValue::Bool(!a.is_truthy()));
+                }
+                Opcode::BitNot => {
+                    let a = runtime::to_primitive(self.pop()?, "number")?;
+                    let r = match a {
+                        JsValue::BigInt(n) => JsValue::BigInt(Rc::new(!(*n).clone())),
+                        other => JsValue::Number(!(to_int32(other.to_number())) as f64),
+                    };
+                    self.stack.push(r);
+                }
+                Opcode::Eq => self.binary(BinaryOp::Eq)?,
+                Opcode::NotEq => self.binary(BinaryOp::NotEq)?,
+                Opcode::StrictEq => {
+                    let b = self.pop()?;
+                    let a = self.pop()?;
+                    self.stack.push(JsValue::Bool(a.strict_eq(&b)));
+                }
+                Opcode::StrictNotEq => {
+                    let b = self.pop()?;
+                    let a = self.pop()?;
+                    self.stack.push(JsValue::Bool(!a.strict_eq(&b)));
+                }
+                Opcode::Lt => self.binary(BinaryOp::Lt)?,
+                Opcode::Gt => self.binary(BinaryOp::Gt)?,
+                Opcode::LtEq => self.binary(BinaryOp::LtEq)?,
+                Opcode::GtEq => self.binary(BinaryOp::GtEq)?,
+                Opcode::In => {
+                    let obj = self.pop()?;
+                    let key = self.pop()?;
+                    let r = runtime::has_property(&obj, &key)?;
+                    self.stack.push(JsValue::Bool(r));
+                }
+                Opcode::InstanceOf => {
+                    let ctor = self.pop()?;
+                    let v = self.pop()?;
+                    let r = runtime::instance_of(&v, &ctor)?;
+                    self.stack.push(JsValue::Bool(r));
+                }
+                Opcode::BitAnd => self.binary(BinaryOp::BitAnd)?,
+                Opcode::BitOr => self.binary(BinaryOp::BitOr)?,
+                Opcode::BitXor => self.binary(BinaryOp::BitXor)?,
+                Opcode::Shl => self.binary(BinaryOp::Shl)?,
+                Opcode::Shr => self.binary(BinaryOp::Shr)?,
+                Opcode::Ushr => self.binary(BinaryOp::Ushr)?,
+
+                Opcode::Jmp(o) => jump(pc, o),
+                Opcode::JmpIfFalse(o) => if !self.pop()?.is_truthy() { jump(pc, o) },
+                Opcode::JmpIfTrue(o) => if self.pop()?.is_truthy() { jump(pc, o) },
+                Opcode::JmpIfFalseKeep(o) => if !self.peek()?.is_truthy() { jump(pc, o) },
+                Opcode::JmpIfTrueKeep(o) => if self.peek()?.is_truthy() { jump(pc, o) },
+                Opcode::JmpIfNotNullishKeep(o) => {
+                    if !matches!(self.peek()?, JsValue::Null | JsValue::Undefined) { jump(pc, o) }
+                }
+                Opcode::JmpIfNotUndefKeep(o) => {
+                    if !matches!(self.peek()?, JsValue::Undefined) { jump(pc, o) }
+                }
+
+                Opcode::Inc(i) => {
+                    let old = self.update_local(i, 1.0);
+                    self.stack.push(JsValue::Number(old.to_number() + 1.0));
+                }
+                Opcode::Dec(i) => {
+                    let old = self.update_local(i, -1.0);
+                    self.stack.push(JsValue::Number(old.to_number() - 1.0));
+                }
+                Opcode::PostInc(i) => {
+                    let old = self.update_local(i, 1.0);
+                    self.stack.push(old);
+                }
+                Opcode::PostDec(i) => {
+                    let old = self.update_local(i, -1.0);
+                    self.stack.push(old);
+                }
+
+                Opcode::GetProp(i) => {
+                    let obj = self.pop()?;
+                    let v = code.ic.load(*pc - 1, Access::Load, &obj, &code.string_pool[i as usize])?;
+                    self.stack.push(v);
+                }
+                Opcode::GetMethod(i) => {
+                    let obj = self.peek()?.clone();
+                    let key = &code.string_pool[i as usize];
+                    // Metody objektu hostitele a metody, ktere VM nezna, vola tree-walker
+                    // (i `Object.groupBy` podle jmena globalu)
+                    let host = call_machinery::in_interpreter()
+                        && matches!(&obj, JsValue::Object(o) if runtime::is_host_object(&o.borrow()));
+                    let mut f = if host { JsValue::Undefined } else { code.ic.load(*pc - 1, Access::Call, &obj, key)? };
+                    if !runtime::is_callable(&f) && call_machinery::in_interpreter() {
+                        let global = match pc.checked_sub(2).and_then(|p| code.bytecode.get(p)) {
+                            Some(Opcode::LoadGlobal(g)) => Some(code.string_pool[*g as usize].as_str()),
+                            _ => None,
+                        };
+                        f = call_machinery::host_method(obj, key, global);
+                    }
+                    self.stack.push(f);
+                }
+                Opcode::GetIndex => {
+                    let key = self.pop()?;
+                    let obj = self.pop()?;
+                    let v = runtime::get_index(&obj, &key)?;
+                    self.stack.push(v);
+                }
+                Opcode::SetProp(i) => {
+                    let value = self.pop()?;
+                    let obj = self.pop()?;
+                    if code.strict {
+                        strict_mode::check_write(&obj, &code.string_pool[i as usize])?;
+                    }
+                    code.ic.store(*pc - 1, &obj, &code.string_pool[i as usize], value.clone())?;
+                    self.stack.push(value);
+                }
+                Opcode::SetIndex => {
+                    let value = self.pop()?;
+                    let key = self.pop()?;
+                    let obj = self.pop()?;
+                    if code.strict {
+                        strict_mode::check_write(&obj, &runtime::key_of(&key))?;
+                    }
+                    runtime::set_index(&obj, &key, value.clone())?;
+                    self.stack.push(value);
+                }
+                Opcode::DeleteIndex => {
+                    let key = self.pop()?;
+                    let obj = self.pop()?;
+                    let r = runtime::delete_prop(&obj, &key)?;
+                    if !r && code.strict {
+                        return Err(strict_mode::delete_error(&runtime::key_of(&key)));
+                    }
+                    self.stack.push(JsValue::Bool(r));
+                }
+
+                Opcode::NewArray(n) => {
+                    let items = self.pop_n(n as usize)?;
+                    self.stack.push(runtime::new_array(items));
+                }
+                Opcode::NewObject(n) => {
+                    let items = self.pop_n(n as usize * 2)?;
+                    let mut obj = JsObject::new();
+                    let mut it = items.into_iter();
+                    while let (Some(k), Some(v)) = (it.next(), it.next()) {
+                        obj.props.insert(runtime::key_of(&k), v);
+                    }
+                    self.stack.push(JsValue::Object(Rc::new(RefCell::new(obj))));
+                }
+                Opcode::DefineField => {
+                    let v = self.pop()?;
+                    let key = self.pop()?;
+                    if let JsValue::Object(o) = self.peek()? {
+                        o.borrow_mut().props.insert(runtime::key_of(&key), v);
+                    }
+                }
+                Opcode::DefineGetter | Opcode::DefineSetter => {
+                    let f = self.pop()?;
+                    let key = runtime::key_of(&self.pop()?);
+                    if let JsValue::Object(o) = self.peek()? {
+                        let mut o = o.borrow_mut();
+                        // Druhy accessor stejneho klice doplni par (get + set).
+                        let (mut g, mut s) = match o.props.slot(&key) {
+                            Some(slot) if slot.is_accessor() => (slot.getter.clone(), slot.setter.clone()),
+                            _ => (None, None),
+                        };
+                        if matches!(op, Opcode::DefineGetter) { g = Some(f) } else { s = Some(f) }
+                        o.props.remove(&key);
+                        o.define_accessor(key.clone(), g, s);
+                        // Accessor objektoveho literalu je enumerable (jako v tree-walkeru)
+                        if let Some(slot) = o.props.slot_mut(&key) { slot.enumerable = true; }
+                    }
+                }
+                Opcode::CopyDataProps => {
+                    let src = self.pop()?;
+                    if let JsValue::Object(o) = self.peek()? {
+                        runtime::copy_data_props(o, &src, &[])?;
+                    }
+                }
+                Opcode::ObjectRest(n) => {
+                    let keys = self.pop_n(n as usize)?;
+                    let src = self.pop()?;
+                    if matches!(src, JsValue::Undefined | JsValue::Null) {
+                        return Err(format!("TypeError: Cannot destructure '{src}' as it is {src}."));
+                    }
+                    let excluded: Vec<PropertyKey> = keys.iter().map(runtime::key_of).collect();
+                    let rest = runtime::new_object();
+                    runtime::copy_data_props(&rest, &src, &excluded)?;
+                    self.stack.push(JsValue::Object(rest));
+                }
+                Opcode::NewRegex(p, f) => {
+                    let r = helpers::make_regex_object(&code.string_pool[p as usize], &code.string_pool[f as usize]);
+                    self.stack.push(r);
+                }
+
+                Opcode::LoadGlobal(i) => {
+                    let name = &code.string_pool[i as usize];
+                    let v = match &self.env {
+                        Some(env) => global_lookup(env, name)?
+                            .ok_or_else(|| format!("ReferenceError: '{name}' není definováno"))?,
+                        None => JsValue::Undefined,
+                    };
+                    self.stack.push(v);
+                }
+                Opcode::TypeOfGlobal(i) => {
+                    let name = &code.string_pool[i as usize];
+                    let v = match &self.env {
+                        Some(env) => global_lookup(env, name)?.unwrap_or(JsValue::Undefined),
+                        None => JsValue::Undefined,
+                    };
+                    self.stack.push(JsValue::Str(v.type_of().to_string()));
+                }
+                Opcode::StoreGlobal(i) => {
+                    let v = self.pop()?;
+                    let name = &code.string_pool[i as usize];
+                    let env = self.env.get_or_insert_with(Environment::new_global);
+                    if !env.borrow_mut().assign(name, v.clone())? {
+                        // Strict kod nedeklarovanou globalni promennou nevytvori
+                        if code.strict {
+                            return Err(strict_mode::undeclared_error(name));
+                        }
+                        Environment::global_of(env).borrow_mut().define(name, v);
+                    }
+                }
+                Opcode::DefineGlobal(i) | Opcode::DefineGlobalConst(i) => {
+                    let v = self.pop()?;
+                    let name = &code.string_pool[i as usize];
+                    let env = self.env.get_or_insert_with(Environment::new_global);
+                    let mut env = env.borrow_mut();
+                    env.define(name, v);
+                    if matches!(op, Opcode::DefineGlobalConst(_)) { env.mark_const(name); }
+                }
+                Opcode::DeclareGlobal(i, lexical) => {
+                    let name = &code.string_pool[i as usize];
+                    let env = self.env.get_or_insert_with(Environment::new_global);
+                    let mut env = env.borrow_mut();
+                    if lexical {
+                        env.declare_uninit(name);
+                    } else if env.get_own(name).is_none() {
+                        env.define(name, JsValue::Undefined);
+                    }
+                }
+                Opcode::CallNative(argc) => {
+                    runtime::at_call(code.call_span_at(*pc - 1));
+                    let args = self.pop_n(argc as usize)?;
+                    let callee = self.pop()?;
+                    let r = runtime::call_function(&callee, JsValue::Undefined, args)?;
+                    self.stack.push(r);
+                }
+                Opcode::CallNativeArgs => {
+                    runtime::at_call(code.call_span_at(*pc - 1));
+                    let args = self.pop_args_array()?;
+                    let callee = self.pop()?;
+                    let r = runtime::call_function(&callee, JsValue::Undefined, args)?;
+                    self.stack.push(r);
+                }
+                Opcode::CallMethod(argc) => {
+                    runtime::at_call(code.call_span_at(*pc - 1));
+                    let args = self.pop_n(argc as usize)?;
+                    let method = self.pop()?;
+                    let this = self.pop()?;
+                    let r = runtime::call_function(&method, this, args)?;
+                    self.stack.push(r);
+                }
+                Opcode::CallMethodArgs => {
+                    runtime::at_call(code.call_span_at(*pc - 1));
+                    let args = self.pop_args_array()?;
+                    let method = self.pop()?;
+                    let this = self.pop()?;
+                    let r = runtime::call_function(&method, this, args)?;
+                    self.stack.push(r);
+                }
+                Opcode::NewOp(argc) => {
+                    runtime::at_call(code.call_span_at(*pc - 1));
+                    let args = self.pop_n(argc as usize)?;
+                    let callee = self.pop()?;
+                    let r = runtime::construct(&callee, args)?;
+                    self.stack.push(r);
+                }
+                Opcode::NewOpArgs => {
+                    runtime::at_call(code.call_span_at(*pc - 1));
+                    let args = self.pop_args_array()?;
+                    let callee = self.pop()?;
+                    let r = runtime::construct(&callee, args)?;
+                    self.stack.push(r);
+                }
+                Opcode::TypeOf => {
+                    let v = self.pop()?;
+                    let t = if runtime::is_vm_class(&v) { "function" } else { v.type_of() };
+                    self.stack.push(JsValue::Str(t.to_string()));
+                }
+                Opcode::Return => return Ok(Exit::Return(self.pop()?)),
+                Opcode::Halt => return Ok(Exit::Return(self.stack.pop().unwrap_or(JsValue::Undefined))),
+
+                Opcode::LoadFunction(idx) => {
+                    let compiled = Rc::clone(&code.functions[idx as usize]);
+                    let env = self.env.clone().unwrap_or_else(Environment::new_global);
+                    let captures = self.materialize_captures(&compiled);
+                    self.stack.push(JsValue::Function(JsFunc::VmCompiled {
+                        name: compiled.name.clone(),
+                        compiled,
+                        env,
+                        captures,
+                        id: crate::interpreter::next_func_id(),
+                    }));
+                }
+                Opcode::LoadCapture(i) => {
+                    let v = self.captures.get(i as usize).cloned().unwrap_or(JsValue::Undefined);
+                    let v = match self.boxed.get(i as usize).copied().unwrap_or(false) {
+                        true => unbox_checked(&v, capture_name(code, i))?,
+                        false => v,
+                    };
+                    self.stack.push(v);
+                }
+                Opcode::StoreCapture(i) => {
+                    let v = self.pop()?;
+                    let k = i as usize;
+                    if self.boxed.get(k).copied().unwrap_or(false) {
+                        check_initialized(&self.captures[k], capture_name(code, i))?;
+                        write_box(&self.captures[k], v);
+                    } else if k < self.captures.len() {
+                        self.captures[k] = v;
+                    }
+                }
+                Opcode::LoadArg(i) => {
+                    let v = self.args.get(i as usize).cloned().unwrap_or(JsValue::Undefined);
+                    self.stack.push(v);
+                }
+                Opcode::LoadRestArgs(i) => {
+                    let rest = self.args.get(i as usize..).map(<[JsValue]>::to_vec).unwrap_or_default();
+                    self.stack.push(runtime::new_array(rest));
+                }
+                Opcode::LoadArguments => {
+                    let a = self.arguments_object();
+                    self.stack.push(a);
+                }
+                Opcode::AppendItem => {
+                    let v = self.pop()?;
+                    match self.peek()? {
+                        JsValue::Array(a) => a.borrow_mut().push(v),
+                        _ => return Err("AppendItem: vrchol stacku neni Array".into()),
+                    }
+                }
+                Opcode::AppendSpread => {
+                    let src = self.pop()?;
+                    let items = runtime::collect_iterable(&src)?;
+                    match self.peek()? {
+                        JsValue::Array(a) => a.borrow_mut().extend(items),
+                        _ => return Err("AppendSpread: vrchol stacku neni Array".into()),
+                    }
+                }
+                Opcode::LoadThis => self.stack.push(self.this_value.clone()),
+                Opcode::Await => {
+                    let v = self.pop()?;
+                    if self.is_async_frame {
+                        return Ok(Exit::Await(v));
+                    }
+                    // Mimo async ramec (top-level await): settled promise se rozbali hned.
+                    match helpers::get_promise_state(&v) {
+                        Some((state, value)) if state == "fulfilled" => self.stack.push(value),
+                        Some((state, value)) if state == "rejected" => return Err(runtime::throw_value(value)),
+                        _ => self.stack.push(v),
+                    }
+                }
+                Opcode::Yield => return Ok(Exit::Yield(self.pop()?)),
+                Opcode::BuildString(n) => {
+                    let parts = self.pop_n(n as usize)?;
+                    let mut out = String::new();
+                    for p in parts {
+                        match p {
+                            JsValue::Str(s) => out.push_str(&s),
+                            JsValue::Symbol(_) => return Err("TypeError: Cannot convert a Symbol value to a string".into()),
+                            other => out.push_str(&runtime::to_primitive(other, "string")?.to_string()),
+                        }
+                    }
+                    self.stack.push(JsValue::Str(out));
+                }
+                Opcode::PushTry(h) | Opcode::PushFinally(h) => {
+                    self.try_stack.push(TryFrame {
+                        handler: h as usize,
+                        depth: self.stack.len(),
+                        finally: matches!(op, Opcode::PushFinally(_)),
+                    });
+                }
+                Opcode::PopTry => { self.try_stack.pop(); }
+                Opcode::Throw => {
+                    let v = self.pop()?;
+                    return Err(runtime::throw_value(v));
+                }
+                Opcode::ThrowError(i) => return Err(code.string_pool[i as usize].clone()),
+
+                Opcode::GetIter(mode) => {
+                    let v = self.pop()?;
+                    let (iter, cursor) = runtime::get_iter(v, mode)?;
+                    self.stack.push(iter);
+                    self.stack.push(cursor);
+                }
+                Opcode::IterNext(o) => {
+                    let (iter, cursor) = self.iter_top()?;
+                    match runtime::iter_step(&iter, cursor)? {
+                        Some(v) => self.stack.push(v),
+                        None => {
+                            self.stack.truncate(self.stack.len() - 2);
+                            jump(pc, o);
+                        }
+                    }
+                }
+                Opcode::IterNextRaw => {
+                    let (iter, cursor) = self.iter_top()?;
+                    let r = runtime::iter_next_raw(&iter, cursor)?;
+                    self.stack.push(r);
+                }
+                Opcode::IterDone(o) => {
+                    let r = self.pop()?;
+                    if runtime::get_prop(&r, "done")?.is_truthy() {
+                        self.stack.truncate(self.stack.len().saturating_sub(2));
+                        jump(pc, o);
+                    } else {
+                        let v = runtime::get_prop(&r, "value")?;
+                        self.stack.push(v);
+                    }
+                }
+                Opcode::IterStep => {
+                    let (iter, cursor) = self.iter_top()?;
+                    let v = runtime::iter_step(&iter, cursor)?.unwrap_or(JsValue::Undefined);
+                    self.stack.push(v);
+                }
+                Opcode::IterRest => {
+                    let (iter, cursor) = self.iter_top()?;
+                    let mut rest = Vec::new();
+                    while let Some(v) = runtime::iter_step(&iter, cursor)? {
+                        rest.push(v);
+                    }
+                    self.stack.push(runtime::new_array(rest));
+                }
+                Opcode::IterClose => {
+                    let cursor = self.pop()?;
+                    let iter = self.pop()?;
+                    runtime::iter_close(&iter, &cursor)?;
+                }
+                Opcode::ForInKeys => {
+                    let obj = self.pop()?;
+                    let keys = runtime::enumerable_keys(&obj, true)?.into_iter().map(JsValue::Str).collect();
+                    self.stack.push(runtime::new_array(keys));
+                }
+
+                Opcode::NewClass(name, has_super) => {
+                    let parent = if has_super { Some(self.pop()?) } else { None };
+                    let name = (name != u16::MAX).then(|| code.string_pool[name as usize].as_str());
+                    let class = runtime::new_class(name, parent)?;
+                    self.stack.push(class);
+                }
+                Opcode::SetCtor => {
+                    let ctor = self.pop()?;
+                    if let JsValue::Object(c) = self.peek()? {
+                        c.borrow_mut().define_hidden("__vm_ctor__".into(), ctor);
+                    }
+                }
+                Opcode::DefineMethod(flags) => {
+                    let f = self.pop()?;
+                    let key = runtime::key_of(&self.pop()?);
+                    runtime::define_method(self.peek()?, &key, f, flags)?;
+                }
+                Opcode::SuperCall => {
+                    let args = self.pop_args_array()?;
+                    let parent = self.pop()?;
+                    let this = self.pop()?;
+                    if !matches!(this, JsValue::Undefined) {
+                        return Err("ReferenceError: Super constructor may only be called once".into());
+                    }
+                    let this = runtime::super_construct(&parent, args, &self.new_target)?;
+                    runtime::init_fields_after_super(&parent, &self.new_target, &this)?;
+                    self.this_value = this.clone();
+                    self.stack.push(this);
+                }
+                Opcode::SuperGet(is_static) => {
+                    let key = runtime::key_of(&self.pop()?);
+                    let parent = self.pop()?;
+                    let this = self.peek()?.clone();
+                    let v = runtime::super_get(&parent, &key, &this, is_static != 0)?;
+                    self.stack.push(v);
+                }
+                Opcode::NewPrivateName(i) => {
+                    let key = private_names::new_key(&code.string_pool[i as usize]);
+                    self.stack.push(JsValue::Str(key));
+                }
+                Opcode::GetPrivate => {
+                    let key = runtime::key_of(&self.pop()?).to_string();
+                    let obj = self.pop()?;
+                    let v = runtime::get_private(&obj, &key)?;
+                    self.stack.push(v);
+                }
+                Opcode::SetPrivate => {
+                    let value = self.pop()?;
+                    let key = runtime::key_of(&self.pop()?).to_string();
+                    let obj = self.pop()?;
+                    runtime::set_private(&obj, &key, value.clone())?;
+                    self.stack.push(value);
+                }
+                Opcode::PrivateIn => {
+                    let obj = self.pop()?;
+                    let key = runtime::key_of(&self.pop()?).to_string();
+                    self.stack.push(JsValue::Bool(private_names::has(&obj, &key)?));
+                }
+                Opcode::DefinePrivateMethod(flags) => {
+                    let f = self.pop()?;
+                    let key = runtime::key_of(&self.pop()?);
+                    let obj = self.pop()?;
+                    private_names::define_method(&obj, &key, f, flags & 2 != 0, flags & 4 != 0);
+                }
+                Opcode::InitField => {
+                    let v = self.pop()?;
+                    let key = runtime::key_of(&self.pop()?);
+                    let obj = self.pop()?;
+                    private_names::define_field(&obj, &key, v)?;
+                }
+                Opcode::SetFields => {
+                    let f = self.pop()?;
+                    if let JsValue::Object(c) = self.peek()? {
+                        c.borrow_mut().define_hidden("__vm_fields__".into(), f);
+                    }
+                }
+                Opcode::Decorate(flags) => {
+                    let decs = self.pop()?;
+                    let value = self.pop()?;
+                    let len = self.stack.len();
+                    let (class, key) = match len {
+                        2.. => (&self.stack[len - 2], runtime::key_of(&self.stack[len - 1])),
+                        _ => return Err("stack underflow".into()),
+                    };
+                    let r = runtime::decorate(class, &key, value, &decs, flags)?;
+                    self.stack.push(r);
+                }
+                Opcode::InitDecoratedField => {
+                    let record = self.pop()?;
+                    let v = self.pop()?;
+                    let key = runtime::key_of(&self.pop()?);
+                    let obj = self.pop()?;
+                    runtime::init_decorated_field(&obj, &key, v, &record)?;
+                }
+                Opcode::DecorateClass => {
+                    let decs = self.pop()?;
+                    let r = runtime::decorate_class(self.peek()?, &decs)?;
+                    self.stack.push(r);
+                }
+                Opcode::FinishClass => {
+                    let class = self.pop()?;
+                    runtime::finish_class(&class, self.peek()?)?;
+                }
+                Opcode::DynamicImport => {
+                    let spec = self.pop()?.to_string();
+                    let promise = match call_machinery::host_import(&spec, self.env.as_ref()) {
+                        Some(r) => r?,
+                        None => {
+                            let err = JsValue::Str(format!("TypeError: Cannot import '{spec}' without a module loader"));
+                            helpers::make_settled_promise("rejected", err)
+                        }
+                    };
+                    self.stack.push(promise);
+                }
+            }
+        }
+        Ok(Exit::Return(self.stack.pop().unwrap_or(JsValue::Undefined)))
+    }
+}
+
+fn jump(pc: &mut usize, offset: i32) {
+    *pc = (*pc as i64 + offset as i64) as usize;
+}
+
+fn to_int32(n: f64) -> i32 {
+    if !n.is_finite() { return 0; }
+    (n.trunc() as i64) as i32
+}
+
+/// Globalni vazba `name`: retezec scopu, pak vlastnosti `window` (UMD
+/// `global.lib = ...`). `Err` = vazba v TDZ.
+fn global_lookup(env: &Rc<RefCell<Environment>>, name: &str) -> Result<Option<JsValue>, String> {
+    let env = env.borrow();
+    if let Some(v) = env.lookup(name)? {
+        return Ok(Some(v));
+    }
+    if let Some(JsValue::Object(window)) = env.get("window") {
+        let v = window.borrow().get(name);
+        if !matches!(v, JsValue::Undefined) { return Ok(Some(v)); }
+    }
+    Ok(None)
+}
+
+/// Obsah boxu (1-prvkove Array drzene cell slotem nebo boxovanou capture).
+fn unbox(v: &JsValue) -> JsValue {
+    match v {
+        JsValue::Array(a) => a.borrow().first().cloned().unwrap_or(JsValue::Undefined),
+        _ => JsValue::Undefined,
+    }
+}
+
+/// Hodnota boxu vazby `name`; prazdny box = vazba v TDZ.
+fn unbox_checked(b: &JsValue, name: &str) -> Result<JsValue, String> {
+    check_initialized(b, name)?;
+    Ok(unbox(b))
+}
+
+/// Prazdny box je `let`/`const`/`class` vazba pred svou deklaraci.
+fn check_initialized(b: &JsValue, name: &str) -> Result<(), String> {
+    match b {
+        JsValue::Array(a) if a.borrow().is_empty() => Err(strict_mode::tdz_error(name.trim_start_matches('%'))),
+        _ => Ok(()),
+    }
+}
+
+fn capture_name(code: &CodeBlock, i: u16) -> &str {
+    code.capture_names.get(i as usize).map_or("", String::as_str)
+}
+
+fn write_box(b: &JsValue, v: JsValue) {
+    if let JsValue::Array(a) = b {
+        gc::track_value(&v);
+        let mut a = a.borrow_mut();
+        match a.first_mut() {
+            Some(slot) => *slot = v,
+            None => a.push(v),
+        }
+    }
+}
+
+/// Signal `gen.return(v)`: unwind pres finally bloky, `catch` ho nechyta.
+pub(super) fn gen_return_signal(v: JsValue) -> JsValue {
+    let mut obj = JsObject::new();
+    obj.define_hidden(GEN_RETURN.into(), v);
+    JsValue::Object(Rc::new(RefCell::new(obj)))
+}
+
+fn gen_return_value(v: &JsValue) -> Option<JsValue> {
+    match v {
+        JsValue::Object(o) => o.borrow().props.get(GEN_RETURN).cloned(),
+        _ => None,
+    }
+}
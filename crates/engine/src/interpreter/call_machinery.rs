@@ -1,9 +1,23 @@
 //! call_function dispatch + new constructor (Map/Set/Date/Error/Promise) + generators.
 
 use super::*;
+use super::stack_trace::{StackFrame, StackTrace};
+
+/// Pocet ramcu v `Error.stack` (V8 `Error.stackTraceLimit`).
+pub(super) const STACK_TRACE_LIMIT: usize = 10;
 
 impl Interpreter {
     pub fn call_function(&mut self, func: JsValue, args: Vec<JsValue>, this: Option<JsValue>) -> EvalResult {
+        // Telo funkce ma vlastni rezim (scope), ne rezim volajiciho strict skriptu
+        if self.strict_script.get() {
+            self.strict_script.set(false);
+            let result = self.call_function(func, args, this);
+            self.strict_script.set(true);
+            return result;
+        }
+        if self.stack_headroom_low() {
+            return self.on_new_stack_segment(move |s| s.call_function(func, args, this));
+        }
         match func {
             // Tridu nelze zavolat bez `new`
             JsValue::Function(JsFunc::Class { name, .. }) => {
@@ -12,23 +26,38 @@ impl Interpreter {
                     name.as_deref().unwrap_or("(anonymous)")
                 )))
             }
-            JsValue::Function(JsFunc::Native(_, f)) => {
+            JsValue::Function(JsFunc::Native(name, f)) => {
+                // Object.* / Reflect.* nad proxy, Reflect.apply/construct
+                let mut args = args;
+                if let Some(v) = self.call_reflective(&name, &mut args)? {
+                    return Ok(v);
+                }
+                // WebAssembly: JS importy volane z WASM jdou pres tento interpret;
+                // stejne tak dispose callbacky DisposableStack
+                if wasm::enters_wasm(&name) || disposal::calls_js(&name) {
+                    return with_interpreter(self, || f(args)).map_err(vm_error);
+                }
                 f(args).map_err(JsError::Runtime)
             }
-            JsValue::Function(JsFunc::User { params, body, env, .. }) => {
+            JsValue::Function(JsFunc::User { name, params, body, env, is_arrow, .. }) => {
                 let call_env = Environment::new_function_child(&env);
+                if let FuncBody::Stmts(stmts) = &body && has_use_strict(stmts) {
+                    call_env.borrow_mut().strict = true;
+                }
                 let params = params.clone();
                 let body = body.clone();
                 self.bind_params(&params, args.clone(), &call_env)?;
-                if let Some(t) = this { call_env.borrow_mut().define("this", t); }
+                if !is_arrow { self.bind_this(&call_env, this); }
                 let args_arr = JsValue::Array(Rc::new(RefCell::new(args)));
                 call_env.borrow_mut().define("arguments", args_arr);
                 let body = body;
 
-                match &body {
+                let _depth = budget::enter_call().map_err(JsError::Runtime)?;
+                let call_site = self.push_frame(name);
+                let result = match &body {
                     FuncBody::Stmts(stmts) => {
                         let stmts = stmts.clone();
-                        Ok(match self.exec_stmts(&stmts, &call_env)? {
+                        self.exec_stmts(&stmts, &call_env).map(|s| match s {
                             Some(Signal::Return(v)) => v,
                             _ => JsValue::Undefined,
                         })
@@ -37,20 +66,34 @@ impl Interpreter {
                         let e = e.clone();
                         self.eval(&e, &call_env)
                     }
-                }
+                };
+                self.pop_frame(call_site);
+                result
+            }
+            // Volani generator funkce vraci generator objekt (telo zatim nebezi)
+            JsValue::Function(JsFunc::Generator { params, body, env, is_async, .. }) => {
+                self.call_generator(params, body, args, env, this, is_async)
             }
-            // Volani generator funkce vraci iterator objekt
-            JsValue::Function(JsFunc::Generator { params, body, env, .. }) => {
-                self.call_generator(params, body, args, env)
+            // gen.next(v) / gen.return(v) / gen.throw(e) - obnovi ramec generatoru
+            JsValue::Function(JsFunc::GeneratorResume { frame, kind }) => {
+                self.resume_generator(&frame, kind, args)
             }
             // Async funkce: spust synchronne, zabal vysledek do Promise
-            JsValue::Function(JsFunc::Async { params, body, env, .. }) => {
+            JsValue::Function(JsFunc::Async { name, params, body, env, is_arrow, .. }) => {
                 let call_env = Environment::new_function_child(&env);
+                if let FuncBody::Stmts(stmts) = &body && has_use_strict(stmts) {
+                    call_env.borrow_mut().strict = true;
+                }
                 self.bind_params(&params, args.clone(), &call_env)?;
-                if let Some(t) = this { call_env.borrow_mut().define("this", t); }
+                if !is_arrow { self.bind_this(&call_env, this); }
                 let args_arr = JsValue::Array(Rc::new(RefCell::new(args)));
                 call_env.borrow_mut().define("arguments", args_arr);
-                match match &body {
+                // `await` v tele async funkce nepozastavuje ramec modulu
+                let module_await = std::mem::replace(&mut self.module_await, false);
+                let script_await = std::mem::replace(&mut self.script_await, false);
+                let _depth = budget::enter_call().map_err(JsError::Runtime)?;
+                let call_site = self.push_frame(name);
+                let result = match &body {
                     FuncBody::Stmts(stmts) => {
                         let stmts = stmts.clone();
                         self.exec_stmts(&stmts, &call_env)
@@ -60,7 +103,11 @@ impl Interpreter {
                         let e = e.clone();
                         self.eval(&e, &call_env)
                     }
-                } {
+                };
+                self.pop_frame(call_site);
+                self.module_await = module_await;
+                self.script_await = script_await;
+                match result {
                     Ok(v) => {
                         // Pokud return value je uz Promise, vrat ho primo
                         if get_promise_state(&v).is_some() {
@@ -74,33 +121,56 @@ impl Interpreter {
                 }
             }
             // Bound funkce: prepend bound_args, pouzij bound_this
-            JsValue::Function(JsFunc::Bound { func, bound_this, bound_args }) => {
+            JsValue::Function(JsFunc::Bound { func, bound_this, bound_args, .. }) => {
                 let mut all_args = bound_args.clone();
                 all_args.extend(args);
                 let effective_this = this.or(Some(*bound_this));
                 self.call_function(*func, all_args, effective_this)
             }
+            // Funkce zkompilovana VM: bezi ve vlastnim ramci
+            JsValue::Function(JsFunc::VmCompiled { .. }) => {
+                let this = this.unwrap_or(JsValue::Undefined);
+                with_interpreter(self, || bytecode::call_function(&func, this, args)).map_err(vm_error)
+            }
+            // Proxy nad funkci: apply trap
+            JsValue::Object(_) if proxy_handler::is_proxy(&func) => self.proxy_apply(&func, this, args),
+            JsValue::Object(ref o) if o.borrow().props.contains_key(bytecode::VM_CLASS) => {
+                let this = this.unwrap_or(JsValue::Undefined);
+                with_interpreter(self, || bytecode::call_function(&func, this, args)).map_err(vm_error)
+            }
             _ => {
                 // Diag: pres `__rwe_call_debug` env, dump arg count + types.
                 if std::env::var("RWE_CALL_DEBUG").is_ok() {
                     eprintln!("[call non-fn] target={:?} args={} line={}",
                         func, args.len(), self.current_line);
                 }
-                Err(JsError::Runtime(format!("{func} není funkce (line {})", self.current_line)))
+                Err(JsError::Runtime(format!("{func} není funkce (line {})", self.position())))
             }
         }
     }
 
     pub(super) fn call_new(&mut self, func: JsValue, args: Vec<JsValue>) -> EvalResult {
+        if self.stack_headroom_low() {
+            return self.on_new_stack_segment(move |s| s.call_new(func, args));
+        }
+        // `new proxy()` - construct trap
+        if proxy_handler::is_proxy(&func) {
+            return self.proxy_construct(&func, args);
+        }
         // `new ClassName()` pro tridy - speciálni logika
         if matches!(&func, JsValue::Function(JsFunc::Class { .. })) {
             return self.construct_class(func, args);
         }
+        // Tridy a funkce zkompilovane VM
+        let is_vm_class = matches!(&func, JsValue::Object(o) if o.borrow().props.contains_key(bytecode::VM_CLASS));
+        if is_vm_class || matches!(&func, JsValue::Function(JsFunc::VmCompiled { .. })) {
+            return with_interpreter(self, || bytecode::construct(&func, args)).map_err(vm_error);
+        }
         // Vestavene konstruktory: Map, Set, ...
         if let JsValue::Function(JsFunc::Native(name, _)) = &func {
             match name.as_str() {
-                "Map" | "WeakMap" => return self.construct_map(args),
-                "Set" | "WeakSet" => return self.construct_set(args),
+                "Map" | "WeakMap" => return self.construct_map(args, name == "WeakMap"),
+                "Set" | "WeakSet" => return self.construct_set(args, name == "WeakSet"),
                 "WeakRef" => {
                     // new WeakRef(target) -> objekt s __weak_target__
                     let target = args.into_iter().next().unwrap_or(JsValue::Undefined);
@@ -109,14 +179,11 @@ impl Interpreter {
                     return Ok(JsValue::Object(Rc::new(RefCell::new(obj))));
                 }
                 "Proxy" => {
-                    // new Proxy(target, handler) - wraps target with trap calls
+                    // new Proxy(target, handler) - traps resi proxy_handler
                     let mut iter = args.into_iter();
                     let target = iter.next().unwrap_or(JsValue::Undefined);
                     let handler = iter.next().unwrap_or(JsValue::Undefined);
-                    let mut obj = JsObject::new();
-                    obj.set("__proxy_target__".into(), target);
-                    obj.set("__proxy_handler__".into(), handler);
-                    return Ok(JsValue::Object(Rc::new(RefCell::new(obj))));
+                    return proxy_handler::make_proxy(target, handler).map_err(JsError::Runtime);
                 }
                 "FinalizationRegistry" => {
                     // new FinalizationRegistry(cb) -> objekt s __finalizer__
@@ -125,6 +192,7 @@ impl Interpreter {
                     obj.set("__finalizer__".into(), cb);
                     return Ok(JsValue::Object(Rc::new(RefCell::new(obj))));
                 }
+                "Symbol" => return Err(JsError::Runtime("TypeError: Symbol is not a constructor".into())),
                 "Date"            => return self.construct_date(args),
                 "Promise"         => return self.construct_promise(args),
                 "RegExp"          => {
@@ -162,15 +230,47 @@ impl Interpreter {
         Ok(obj)
     }
 
+    /// ToPrimitive - objekt s `[Symbol.toPrimitive](hint)` prevede volanim teto
+    /// metody (hint "default" / "number" / "string"). Ostatni hodnoty vraci beze zmeny.
+    pub(super) fn coerce_to_primitive(&mut self, v: JsValue, hint: &str) -> EvalResult {
+        let JsValue::Object(o) = &v else { return Ok(v) };
+        let method = o.borrow().get(symbols::TO_PRIMITIVE);
+        match method {
+            JsValue::Undefined | JsValue::Null => Ok(v),
+            JsValue::Function(_) => {
+                let r = self.call_function(method, vec![JsValue::Str(hint.into())], Some(v.clone()))?;
+                if matches!(r, JsValue::Object(_) | JsValue::Array(_) | JsValue::Function(_)) {
+                    return Err(JsError::Runtime("TypeError: Cannot convert object to primitive value".into()));
+                }
+                Ok(r)
+            }
+            _ => Err(JsError::Runtime("TypeError: Symbol.toPrimitive neni funkce".into())),
+        }
+    }
+
+    /// `x instanceof C` pres `C[Symbol.hasInstance]` - None kdyz C metodu nema.
+    pub(super) fn call_has_instance(&mut self, value: &JsValue, ctor: &JsValue) -> Result<Option<bool>, JsError> {
+        let method = match ctor {
+            JsValue::Object(_) | JsValue::Function(JsFunc::Class { .. }) => self.get_prop(ctor, symbols::HAS_INSTANCE)?,
+            _ => return Ok(None),
+        };
+        if !matches!(method, JsValue::Function(_)) { return Ok(None); }
+        let r = self.call_function(method, vec![value.clone()], Some(ctor.clone()))?;
+        Ok(Some(r.is_truthy()))
+    }
+
     /// Konstruktor `new Map([[k,v], ...])` nebo `new Map()`.
-    pub(super) fn construct_map(&mut self, args: Vec<JsValue>) -> EvalResult {
-        let mut m = JsMap::new();
-        if let Some(JsValue::Array(entries)) = args.into_iter().next() {
-            for entry in entries.borrow().clone() {
+    pub(super) fn construct_map(&mut self, args: Vec<JsValue>, weak: bool) -> EvalResult {
+        let mut m = if weak { JsMap::new_weak() } else { JsMap::new() };
+        if let Some(iterable) = args.into_iter().next().filter(|v| !matches!(v, JsValue::Undefined | JsValue::Null)) {
+            for entry in self.collect_iterable(iterable)? {
                 if let JsValue::Array(pair) = entry {
                     let pair = pair.borrow();
-                    let k = pair.get(0).cloned().unwrap_or(JsValue::Undefined);
+                    let k = pair.first().cloned().unwrap_or(JsValue::Undefined);
                     let v = pair.get(1).cloned().unwrap_or(JsValue::Undefined);
+                    if weak && !keyed_table::can_be_held_weakly(&k) {
+                        return Err(JsError::Runtime("TypeError: Invalid value used as weak map key".into()));
+                    }
                     m.set(k, v);
                 }
             }
@@ -179,11 +279,16 @@ impl Interpreter {
     }
 
     /// Konstruktor `new Set([val, ...])` nebo `new Set()`.
-    pub(super) fn construct_set(&mut self, args: Vec<JsValue>) -> EvalResult {
-        let mut s = JsSet::new();
+    pub(super) fn construct_set(&mut self, args: Vec<JsValue>, weak: bool) -> EvalResult {
+        let mut s = if weak { JsSet::new_weak() } else { JsSet::new() };
         if let Some(iterable) = args.into_iter().next() {
             let items = self.collect_iterable(iterable).unwrap_or_default();
-            for v in items { s.add(v); }
+            for v in items {
+                if weak && !keyed_table::can_be_held_weakly(&v) {
+                    return Err(JsError::Runtime("TypeError: Invalid value used in weak set".into()));
+                }
+                s.add(v);
+            }
         }
         Ok(JsValue::Set(Rc::new(RefCell::new(s))))
     }
@@ -226,7 +331,7 @@ impl Interpreter {
         let mut obj = JsObject::new();
         obj.set("name".into(),    JsValue::Str(name.clone()));
         obj.set("message".into(), JsValue::Str(msg.clone()));
-        obj.set("stack".into(),   JsValue::Str(format!("{name}: {msg}")));
+        obj.set("stack".into(),   JsValue::Str(self.error_stack(&format!("{name}: {msg}"))));
         // ES2022 Error.cause: pokud options.cause existuje, uloz
         if let Some(JsValue::Object(opts)) = options {
             let cause = opts.borrow().props.get("cause").cloned();
@@ -237,6 +342,63 @@ impl Interpreter {
         Ok(JsValue::Object(Rc::new(RefCell::new(obj))))
     }
 
+    // ─── Pozice a ramce pro Error.stack ─────────────────────────────────────
+
+    /// Vyhodnoti `f` se `span` jako aktualni pozici (volani, `new`); pak vrati
+    /// puvodni pozici.
+    pub(super) fn at_span<R>(&mut self, span: SourceSpan, f: impl FnOnce(&mut Self) -> R) -> R {
+        if !span.is_known() { return f(self); }
+        let outer = self.current_span.replace(span);
+        let result = f(self);
+        self.current_span.set(outer);
+        result
+    }
+
+    /// Aktualni pozice jako `radek:sloupec` (sloupec od 1), bez rozsahu jen radek.
+    pub(super) fn position(&self) -> String {
+        let span = self.current_span.get();
+        if span.is_known() {
+            format!("{}:{}", span.start.line, span.start.column + 1)
+        } else {
+            self.current_line.to_string()
+        }
+    }
+
+    /// Vstup do tela uzivatelske funkce; vraci misto volani pro `pop_frame`.
+    fn push_frame(&mut self, name: Option<String>) -> SourceSpan {
+        let call_site = self.current_span.get();
+        self.call_frames.borrow_mut().push((name.unwrap_or_default(), call_site));
+        call_site
+    }
+
+    fn pop_frame(&mut self, call_site: SourceSpan) {
+        self.call_frames.borrow_mut().pop();
+        self.current_span.set(call_site);
+    }
+
+    /// `Error.stack`: hlavicka + ramce od nejvnitrnejsiho (V8 format). Ramce
+    /// bez pozice (volani z natives, rucne postavene AST) se vynechavaji.
+    pub(super) fn error_stack(&self, header: &str) -> String {
+        let frame = |name: &str, at: SourceSpan| StackFrame {
+            function_name: name.to_string(),
+            script_id: 0,
+            script_url: "<anonymous>".into(),
+            line: at.start.line,
+            column: at.start.column + 1,
+            is_eval: false,
+            is_constructor: false,
+            is_async: false,
+        };
+        let mut frames = Vec::new();
+        let mut at = self.current_span.get();
+        for (name, call_site) in self.call_frames.borrow().iter().rev() {
+            if at.is_known() { frames.push(frame(name, at)); }
+            at = *call_site;
+        }
+        if at.is_known() { frames.push(frame("", at)); }
+        StackTrace::capture(frames, STACK_TRACE_LIMIT).error_stack(header)
+    }
+
     /// Konstruktor `new Promise(executor)` - synchronni rozliseni.
     ///
     /// Executor je volan okamzite se dvema argumenty:
@@ -347,91 +509,203 @@ impl Interpreter {
 
     // ─── Generator + iterator protokol ───────────────────────────────────────
 
-    /// Zavola generator funkci a vrati iterator objekt.
+    /// Zavola generator funkci a vrati generator objekt.
     ///
-    /// Implementace: spusti cely body v generator rezimu (yield_buffer = Some(vec![])),
-    /// sbira yield hodnoty, pak vrati iterator objekt s metodou `next()`.
+    /// Parametry se bindnou hned (chyba v default hodnote = vyjimka pri volani),
+    /// telo bezi az pri `next()` v pozastavitelnem ramci (viz `generator.rs`).
     pub(super) fn call_generator(
         &mut self,
         params: Vec<Param>,
         body: Vec<Stmt>,
         args: Vec<JsValue>,
         closure_env: Rc<RefCell<Env>>,
+        this: Option<JsValue>,
+        is_async: bool,
     ) -> EvalResult {
-        // Nastav generator rezim
-        let prev_buf = self.yield_buffer.take();
-        self.yield_buffer = Some(Vec::new());
-
-        // Spust telo generator funkce
         let gen_env = Environment::new_function_child(&closure_env);
-        let params = params.clone();
-        let body = body.clone();
-        self.bind_params(&params, args, &gen_env)?;
-        let _ = self.exec_stmts(&body, &gen_env);
-
-        // Vezmi nahromadene hodnoty
-        let yielded = self.yield_buffer.take().unwrap_or_default();
-        self.yield_buffer = prev_buf;
-
-        // Vytvor iterator objekt (sdileny refcell pro index)
-        let values = Rc::new(yielded);
-        let index  = Rc::new(RefCell::new(0usize));
-
-        let values2 = Rc::clone(&values);
-        let index2  = Rc::clone(&index);
-
-        let mut iter_obj = JsObject::new();
-
-        // next() metoda
-        let next_fn = native("(generator).next", move |_args| {
-            let i = *index2.borrow();
-            if i < values2.len() {
-                *index2.borrow_mut() = i + 1;
-                let mut result = JsObject::new();
-                result.set("value".into(), values2[i].clone());
-                result.set("done".into(),  JsValue::Bool(false));
-                Ok(JsValue::Object(Rc::new(RefCell::new(result))))
-            } else {
-                let mut result = JsObject::new();
-                result.set("value".into(), JsValue::Undefined);
-                result.set("done".into(),  JsValue::Bool(true));
-                Ok(JsValue::Object(Rc::new(RefCell::new(result))))
-            }
-        });
-        iter_obj.set("next".into(), next_fn);
-
-        // [Symbol.iterator]() - vraci this (iterator je zaroven iterable)
-        let values3 = Rc::clone(&values);
-        let index3  = Rc::new(RefCell::new(0usize));
-        let sym_iter_fn = native("(generator)[Symbol.iterator]", move |_| {
-            // Vrat novy iterator od zacatku
-            let values4 = Rc::clone(&values3);
-            let index4  = Rc::clone(&index3);
-            let mut obj = JsObject::new();
-            let v4 = Rc::clone(&values4);
-            let i4 = Rc::clone(&index4);
-            obj.set("next".into(), native("(gen.iter).next", move |_| {
-                let i = *i4.borrow();
-                if i < v4.len() {
-                    *i4.borrow_mut() = i + 1;
-                    let mut r = JsObject::new();
-                    r.set("value".into(), v4[i].clone());
-                    r.set("done".into(),  JsValue::Bool(false));
-                    Ok(JsValue::Object(Rc::new(RefCell::new(r))))
-                } else {
-                    let mut r = JsObject::new();
-                    r.set("value".into(), JsValue::Undefined);
-                    r.set("done".into(),  JsValue::Bool(true));
-                    Ok(JsValue::Object(Rc::new(RefCell::new(r))))
-                }
-            }));
-            Ok(JsValue::Object(Rc::new(RefCell::new(obj))))
-        });
-        iter_obj.set("Symbol.iterator".into(), sym_iter_fn);
-        // Iterator helpers (ES2025) - marker pres call_method special-case dispatch.
-        iter_obj.set("__iterator_helpers__".into(), JsValue::Bool(true));
-
-        Ok(JsValue::Object(Rc::new(RefCell::new(iter_obj))))
+        if has_use_strict(&body) { gen_env.borrow_mut().strict = true; }
+        self.bind_params(&params, args.clone(), &gen_env)?;
+        self.bind_this(&gen_env, this);
+        let args_arr = JsValue::Array(Rc::new(RefCell::new(args)));
+        gen_env.borrow_mut().define("arguments", args_arr);
+        let frame = generator::GeneratorFrame::new(self, body, gen_env, is_async)?;
+        Ok(generator::make_generator_object(frame))
     }
 
+    /// `this` volani obycejne funkce: strict kod dostane hodnotu beze zmeny
+    /// (chybejici = `undefined`), sloppy nahradi `undefined`/`null` globalnim `this`.
+    fn bind_this(&self, call_env: &Rc<RefCell<Env>>, this: Option<JsValue>) {
+        let strict = call_env.borrow().strict;
+        let this = match this {
+            None | Some(JsValue::Undefined | JsValue::Null) if !strict => self.global.borrow().get("this"),
+            None => Some(JsValue::Undefined),
+            t => t,
+        };
+        if let Some(t) = this { call_env.borrow_mut().define("this", t); }
+    }
+
+}
+
+/// Chyba z VM: vyhozena JS hodnota -> `JsError::Thrown`, jinak runtime chyba.
+pub(super) fn vm_error(msg: String) -> JsError {
+    match bytecode::take_thrown(&msg) {
+        Some(v) => JsError::Thrown(v),
+        None => JsError::Runtime(msg),
+    }
+}
+
+/// Chyba interpretu pro VM - opak `vm_error`.
+pub(crate) fn into_vm_error(e: JsError) -> String {
+    match e {
+        JsError::Runtime(msg) => msg,
+        JsError::Thrown(v) | JsError::GeneratorReturn(v) => bytecode::throw_value(v),
+    }
+}
+
+// ─── Most VM <-> tree-walker ─────────────────────────────────────────────────
+
+thread_local! {
+    /// Interpret, ktery prave bezi VM kod nebo nativni funkci volajici JS
+    /// (WASM importy, disposery). Pres nej VM vola funkce tree-walkeru.
+    static INTERPRETER: Cell<*mut Interpreter> = const { Cell::new(std::ptr::null_mut()) };
+}
+
+/// Zavola `f` (VM kod, nativni funkci) s `interp` jako interpretem pro
+/// volani funkci tree-walkeru.
+pub(crate) fn with_interpreter<R>(interp: &mut Interpreter, f: impl FnOnce() -> R) -> R {
+    let previous = INTERPRETER.replace(interp);
+    let result = f();
+    INTERPRETER.set(previous);
+    result
+}
+
+/// Bezi VM kod pod interpretem (`with_interpreter`)?
+pub(crate) fn in_interpreter() -> bool {
+    !INTERPRETER.get().is_null()
+}
+
+/// `f` nad interpretem z `with_interpreter`; `None` mimo nej.
+pub(crate) fn with_host<R>(f: impl FnOnce(&mut Interpreter) -> R) -> Option<R> {
+    let interp = INTERPRETER.get();
+    // SAFETY: `with_interpreter` drzi ukazatel jen po dobu VM kodu / nativni
+    // funkce, behem ktere interpret stoji ve volani, ktere ho zaregistrovalo,
+    // a jinak se nepouziva.
+    (!interp.is_null()).then(|| f(unsafe { &mut *interp }))
+}
+
+/// JS funkce volana z nativni funkce (JS import z WASM, dispose callback) -
+/// pres interpret, ktery natives zavolal; bez nej jen VM funkce a natives.
+pub(crate) fn call_js(callee: &JsValue, this: JsValue, args: Vec<JsValue>) -> Result<JsValue, JsError> {
+    if INTERPRETER.get().is_null() {
+        return bytecode::call_function(callee, this, args).map_err(vm_error);
+    }
+    let this = (!matches!(this, JsValue::Undefined)).then_some(this);
+    with_host(|interp| interp.call_function(callee.clone(), args, this)).expect("interpret je registrovany")
+}
+
+/// Funkce tree-walkeru volana z VM.
+pub(crate) fn call_from_vm(callee: &JsValue, this: JsValue, args: Vec<JsValue>) -> Result<JsValue, String> {
+    let this = (!matches!(this, JsValue::Undefined)).then_some(this);
+    with_host(|interp| interp.call_function(callee.clone(), args, this).map_err(into_vm_error))
+        .unwrap_or_else(|| Err("TypeError: funkci tree-walkeru nelze volat mimo interpret".into()))
+}
+
+/// `new` nad funkci tree-walkeru z VM.
+pub(crate) fn construct_from_vm(callee: &JsValue, args: Vec<JsValue>) -> Result<JsValue, String> {
+    with_host(|interp| interp.call_new(callee.clone(), args).map_err(into_vm_error))
+        .unwrap_or_else(|| Err("TypeError: funkci tree-walkeru nelze konstruovat mimo interpret".into()))
+}
+
+// ─── Hostitelske vlastnosti z VM ─────────────────────────────────────────────
+
+/// Vyhodnoti `expr` v tree-walkeru nad docasnymi vazbami (`%recv`, `%a0`...).
+fn eval_on_host(bindings: Vec<(String, JsValue)>, expr: Expr) -> Option<Result<JsValue, String>> {
+    with_host(|interp| {
+        let env = Environment::new_child(&interp.global);
+        for (name, v) in bindings {
+            env.borrow_mut().define(&name, v);
+        }
+        interp.eval(&expr, &env).map_err(into_vm_error)
+    })
+}
+
+fn recv_member(object: &str, key: &str) -> Expr {
+    Expr::Member {
+        object: Box::new(Expr::Ident(object.into(), SourceSpan::default())),
+        prop: MemberProp::Ident(key.into()),
+        optional: false,
+        span: SourceSpan::default(),
+    }
+}
+
+/// `obj.key`, ktere VM nezna (DOM uzel, hostitelske API) - cte tree-walker.
+/// `None` mimo interpret.
+pub(crate) fn host_get(obj: &JsValue, key: &str) -> Option<Result<JsValue, String>> {
+    eval_on_host(vec![("%recv".into(), obj.clone())], recv_member("%recv", key))
+}
+
+/// `obj.key = value` se semantikou tree-walkeru (DOM settery, style, Worker.onmessage).
+pub(crate) fn host_set(obj: &JsValue, key: &str, value: JsValue) -> Option<Result<(), String>> {
+    let expr = Expr::Assign {
+        op: AssignOp::Assign,
+        target: Box::new(recv_member("%recv", key)),
+        value: Box::new(Expr::Ident("%value".into(), SourceSpan::default())),
+        span: SourceSpan::default(),
+    };
+    eval_on_host(vec![("%recv".into(), obj.clone()), ("%value".into(), value)], expr)
+        .map(|r| r.map(|_| ()))
+}
+
+/// Metoda, kterou VM nezna: volani `recv.key(...)` provede tree-walker
+/// (jeho metody se dispatchuji syntakticky, ne pres hodnotu vlastnosti).
+/// `global` je jmeno globalu, ze ktereho prijemce pochazi (`Object.groupBy`).
+pub(crate) fn host_method(recv: JsValue, key: &str, global: Option<&str>) -> JsValue {
+    let (name, key) = (key.to_string(), key.to_string());
+    let object = global.unwrap_or("%recv").to_string();
+    helpers::native(&name, move |args| {
+        let mut bindings = vec![(object.clone(), recv.clone())];
+        let mut arg_exprs = Vec::with_capacity(args.len());
+        for (i, a) in args.into_iter().enumerate() {
+            let name = format!("%a{i}");
+            arg_exprs.push(Expr::Ident(name.clone(), SourceSpan::default()));
+            bindings.push((name, a));
+        }
+        let call = Expr::Call {
+            callee: Box::new(recv_member(&object, &key)),
+            args: arg_exprs,
+            optional: false,
+            span: SourceSpan::default(),
+        };
+        eval_on_host(bindings, call).unwrap_or_else(|| Err(format!("TypeError: {key} is not a function")))
+    })
+}
+
+/// `v instanceof ctor` pro tridu tree-walkeru (vcetne static `[Symbol.hasInstance]`).
+pub(crate) fn host_instance_of(v: &JsValue, ctor: &JsValue) -> Option<Result<bool, String>> {
+    let expr = Expr::Binary {
+        op: BinaryOp::Instanceof,
+        left: Box::new(Expr::Ident("%recv".into(), SourceSpan::default())),
+        right: Box::new(Expr::Ident("%a0".into(), SourceSpan::default())),
+        span: SourceSpan::default(),
+    };
+    eval_on_host(vec![("%recv".into(), v.clone()), ("%a0".into(), ctor.clone())], expr)
+        .map(|r| r.map(|b| b.is_truthy()))
+}
+
+/// Enumerable klice proxy (trapy `ownKeys` a `getOwnPropertyDescriptor`) pro
+/// for-in a spread ve VM.
+pub(crate) fn host_proxy_keys(proxy: &JsValue) -> Option<Result<Vec<String>, String>> {
+    with_host(|interp| interp.proxy_enumerable_keys(proxy).map_err(into_vm_error))
+}
+
+/// `import(specifier)` z VM - modul nacte interpret; referrer je modul, v jehoz
+/// scopu kod bezi (jinak dokument).
+pub(crate) fn host_import(specifier: &str, env: Option<&Rc<RefCell<Environment>>>) -> Option<Result<JsValue, String>> {
+    with_host(|interp| {
+        let referrer = match env {
+            Some(env) => interp.module_referrer(env),
+            None => interp.document_url(),
+        };
+        interp.import_dynamic(specifier, &referrer).map_err(into_vm_error)
+    })
 }
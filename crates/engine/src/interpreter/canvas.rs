@@ -5,6 +5,8 @@ use std::rc::Rc;
 use std::cell::RefCell;
 use super::{JsValue, JsObject};
 use super::helpers::native;
+use super::builtins_typed_arrays::typed_array_value;
+use super::typed_arrays::TypedArrayKind;
 
 /// Precte fill/stroke styl z props + aplikuje globalAlpha na alpha kanal.
 /// Drive se globalAlpha (`ctx.globalAlpha = 0.5`) ignoroval -> particle fade
@@ -486,9 +488,7 @@ pub(crate) fn create_canvas_2d_context(
             let mut data = JsObject::new();
             data.set("width".into(), JsValue::Number(w));
             data.set("height".into(), JsValue::Number(h));
-            data.set("data".into(), JsValue::Array(Rc::new(RefCell::new(
-                vec![JsValue::Number(0.0); len]
-            ))));
+            data.set("data".into(), typed_array_value(TypedArrayKind::Uint8Clamped, vec![0; len]));
             Ok(JsValue::Object(Rc::new(RefCell::new(data))))
         }));
     obj_rc.borrow_mut().set("getImageData".into(),
@@ -500,9 +500,7 @@ pub(crate) fn create_canvas_2d_context(
             let mut data = JsObject::new();
             data.set("width".into(), JsValue::Number(w));
             data.set("height".into(), JsValue::Number(h));
-            data.set("data".into(), JsValue::Array(Rc::new(RefCell::new(
-                vec![JsValue::Number(0.0); len]
-            ))));
+            data.set("data".into(), typed_array_value(TypedArrayKind::Uint8Clamped, vec![0; len]));
             Ok(JsValue::Object(Rc::new(RefCell::new(data))))
         }));
     obj_rc.borrow_mut().set("putImageData".into(),
@@ -1,60 +1,501 @@
 //! Class evaluation: make_class_func, construct_class, run_super_constructor,
-//! get_class_method_func, bind_params.
+//! get_class_method_func, bind_params. Dekoratory (`@dec`) viz `decorators.rs`.
 
 use super::*;
+use super::decorators::{DecoratorContext, DecoratorKind};
+
+/// Sdileny stav vyhodnoceni tela tridy.
+struct ClassScope {
+    /// Scope tridy s vazbami privatnich jmen (a vnitrnim jmenem tridy)
+    class_env: Rc<RefCell<Env>>,
+    /// Env metod: `__super_class__` pro super.method() - staticke i instance
+    method_env: Rc<RefCell<Env>>,
+    static_props: Rc<RefCell<JsObject>>,
+    /// `context.metadata` dekoratoru (undefined u nedekorovane tridy)
+    metadata: JsValue,
+}
+
+/// Staticke pole nebo `static {}` blok - bezi po vytvoreni tridy v poradi deklarace.
+enum StaticInit<'a> {
+    Field(ClassFieldDef),
+    Block(&'a [Stmt]),
+}
+
+/// Cleny tridy roztridene pri vyhodnoceni tela.
+#[derive(Default)]
+struct ClassParts<'a> {
+    has_ctor: bool,
+    ctor_params: Vec<Param>,
+    ctor_body: Vec<Stmt>,
+    methods: Vec<ClassMethodDef>,
+    statics: Vec<ClassMethodDef>,
+    getters: Vec<ClassMethodDef>,
+    setters: Vec<ClassMethodDef>,
+    /// Pole a staticke inicializatory s indexem ve zdroji: dekoratory se
+    /// aplikuji po skupinach, inicializace ale bezi v poradi deklarace
+    fields: Vec<(usize, ClassFieldDef)>,
+    static_inits: Vec<(usize, StaticInit<'a>)>,
+    /// `addInitializer` dekoratoru statickych metod (pred statickymi poli)
+    static_method_inits: Vec<JsValue>,
+    /// `addInitializer` dekoratoru metod instance (pri kazdem `new`)
+    instance_inits: Vec<JsValue>,
+}
+
+impl ClassParts<'_> {
+    fn push_field(&mut self, index: usize, is_static: bool, def: ClassFieldDef) {
+        match is_static {
+            true => self.static_inits.push((index, StaticInit::Field(def))),
+            false => self.fields.push((index, def)),
+        }
+    }
+}
 
 impl Interpreter {
+    /// Vyhodnoti deklaraci nebo vyraz tridy: dekoratory tridy (pred `extends`),
+    /// rodicovskou tridu a telo.
+    pub(super) fn eval_class(
+        &mut self,
+        name: Option<String>,
+        decorators: &[Expr],
+        super_class: Option<&Expr>,
+        body: &[ClassMember],
+        env: &Rc<RefCell<Env>>,
+    ) -> EvalResult {
+        let decorators = self.eval_decorators(decorators, env)?;
+        let super_val = match super_class {
+            Some(sc) => Some(Box::new(self.eval(sc, env)?)),
+            None => None,
+        };
+        self.make_class_func(name, super_val, &decorators, body, env)
+    }
+
+    fn eval_decorators(&mut self, decorators: &[Expr], env: &Rc<RefCell<Env>>) -> Result<Vec<JsValue>, JsError> {
+        decorators.iter().map(|d| self.eval(d, env)).collect()
+    }
+
     /// Vytvori JsValue::Function(JsFunc::Class) z AST ClassMember listu.
     ///
-    /// Rozdeli cleny na: konstruktor, instance metody, staticke metody, gettery, settery.
+    /// Rozdeli cleny na: konstruktor, instance metody, staticke metody, gettery, settery
+    /// a pole. Computed jmena (`[Symbol.iterator]() {}`) se vyhodnoti jednou pri definici
+    /// tridy. Privatni jmena dostanou nove klice ve scope tridy (`private_names`).
+    /// Dekoratory clenu a computed klice se vyhodnoti v poradi deklarace,
+    /// aplikuji se po skupinach (`decorators::application_order`), pak dekoratory
+    /// tridy (vysledek muze tridu nahradit); staticka pole a `static {}` bloky
+    /// se spusti nakonec v poradi deklarace s this = trida.
     pub(super) fn make_class_func(
-        &self,
+        &mut self,
         name: Option<String>,
         super_val: Option<Box<JsValue>>,
+        decorators: &[JsValue],
         body: &[ClassMember],
         env: &Rc<RefCell<Env>>,
-    ) -> JsValue {
-        let mut has_ctor = false;
-        let mut ctor_params = Vec::new();
-        let mut ctor_body   = Vec::new();
-        let mut methods  = Vec::new();
-        let mut statics  = Vec::new();
-        let mut getters  = Vec::new();
-        let mut setters  = Vec::new();
-
-        for m in body {
-            let def = ClassMethodDef {
-                name: m.name.clone(),
-                params: m.params.clone(),
-                body: m.body.clone(),
+    ) -> EvalResult {
+        // Scope tridy: vazby privatnich jmen (#x -> unikatni klic) vcetne ulozist `accessor` poli
+        // Telo tridy je vzdy strict
+        let class_env = Environment::new_child(env);
+        class_env.borrow_mut().strict = true;
+        for (i, m) in body.iter().enumerate() {
+            let storage = (m.kind == ClassMemberKind::Accessor).then(|| ClassMember::accessor_storage(i));
+            for n in m.is_private.then_some(&m.name).into_iter().chain(storage.as_ref()) {
+                let binding = private_names::binding(n);
+                if class_env.borrow().get_own(&binding).is_none() {
+                    class_env.borrow_mut().define(&binding, JsValue::Str(private_names::new_key(n)));
+                }
+            }
+        }
+
+        // Vlastni vlastnosti tridy dedi od rodicovske tridy (staticka dedicnost)
+        let static_props = Rc::new(RefCell::new(JsObject::new()));
+        if let Some(JsValue::Function(JsFunc::Class { static_props: parent, .. })) = super_val.as_deref() {
+            static_props.borrow_mut().proto = Some(Rc::clone(parent));
+        }
+        let method_env = Environment::new_child(&class_env);
+        if let Some(sv) = &super_val {
+            method_env.borrow_mut().define("__super_class__", (**sv).clone());
+        }
+        // Metadata dekoratoru dedi z `Symbol.metadata` rodicovske tridy
+        let decorated = !decorators.is_empty() || body.iter().any(|m| !m.decorators.is_empty());
+        let metadata = match super_val.as_deref() {
+            _ if !decorated => JsValue::Undefined,
+            Some(sv @ (JsValue::Function(_) | JsValue::Object(_))) => {
+                decorators::new_metadata(self.get_prop_accessor(sv, symbols::METADATA)?)
+            }
+            _ => decorators::new_metadata(JsValue::Undefined),
+        };
+        let scope = ClassScope { class_env, method_env, static_props, metadata };
+
+        // Dekoratory clenu (pred jeho klicem) a klice v poradi deklarace
+        let mut elems = Vec::new();
+        for (i, m) in body.iter().enumerate().filter(|(_, m)| m.kind != ClassMemberKind::StaticBlock) {
+            let decs = self.eval_decorators(&m.decorators, &scope.class_env)?;
+            let key = match &m.computed {
+                Some(e) => symbols::to_property_key(&self.eval(e, &scope.class_env)?),
+                None if m.is_private => self.resolve_prop_key(&MemberProp::Private(m.name.clone()), &scope.class_env)?,
+                None => m.name.as_str().into(),
+            };
+            elems.push((i, m, key, decs));
+        }
+        elems.sort_by_key(|(_, m, ..)| decorators::application_order(m));
+        let mut parts = ClassParts::default();
+        for (i, m, key, decs) in elems {
+            match m.kind {
+                ClassMemberKind::Field => self.add_class_field(m, i, key, &decs, &scope, &mut parts)?,
+                ClassMemberKind::Accessor => self.add_class_accessor(m, i, key, &decs, &scope, &mut parts)?,
+                _ => self.add_class_method(m, key, &decs, &scope, &mut parts)?,
+            }
+        }
+        for (i, m) in body.iter().enumerate().filter(|(_, m)| m.kind == ClassMemberKind::StaticBlock) {
+            parts.static_inits.push((i, StaticInit::Block(&m.body)));
+        }
+        parts.fields.sort_by_key(|(i, _)| *i);
+        parts.static_inits.sort_by_key(|(i, _)| *i);
+
+        let class_val = JsValue::Function(JsFunc::Class {
+            name: name.clone(),
+            super_val: super_val.clone(),
+            has_ctor: parts.has_ctor,
+            ctor_params: parts.ctor_params,
+            ctor_body: parts.ctor_body,
+            methods: parts.methods,
+            statics: parts.statics,
+            getters: parts.getters,
+            setters: parts.setters,
+            fields: parts.fields.into_iter().map(|(_, f)| f).collect(),
+            initializers: parts.instance_inits,
+            static_props: Rc::clone(&scope.static_props),
+            env: Rc::clone(&scope.class_env),
+        });
+
+        // Vnitrni vazba jmena tridy (viditelna ze statickych inicializatoru i metod)
+        if let Some(n) = &name {
+            scope.class_env.borrow_mut().define(n, class_val.clone());
+        }
+
+        // Dekoratory tridy - vysledek nahradi tridu i ve vnitrni vazbe
+        let mut result = class_val.clone();
+        let mut class_inits = Vec::new();
+        if !decorators.is_empty() {
+            let ctx = DecoratorContext::new(DecoratorKind::Class, name.as_deref().unwrap_or(""));
+            (result, class_inits) = self.run_decorators(decorators, &ctx, class_val.clone(), &scope.metadata, Ok)?;
+            if let Some(n) = &name {
+                scope.class_env.borrow_mut().define(n, result.clone());
+            }
+        }
+        if decorated {
+            match &result {
+                JsValue::Function(JsFunc::Class { static_props, .. }) => {
+                    static_props.borrow_mut().set(symbols::METADATA.into(), scope.metadata.clone());
+                }
+                JsValue::Object(o) => o.borrow_mut().set(symbols::METADATA.into(), scope.metadata.clone()),
+                _ => {}
+            }
+        }
+
+        // addInitializer statickych metod, pak staticka pole a bloky v poradi deklarace, this = trida
+        for f in parts.static_method_inits {
+            self.call_function(f, vec![], Some(class_val.clone()))?;
+        }
+        let init_env = Environment::new_child(&scope.method_env);
+        init_env.borrow_mut().define("this", class_val.clone());
+        for (_, init) in parts.static_inits {
+            match init {
+                StaticInit::Block(body) => {
+                    let block_env = Environment::new_child(&init_env);
+                    self.exec_stmts(body, &block_env)?;
+                }
+                StaticInit::Field(def) => self.init_class_field(&def, &class_val, &init_env)?,
+            }
+        }
+        for f in class_inits {
+            self.call_function(f, vec![], Some(result.clone()))?;
+        }
+
+        Ok(result)
+    }
+
+    /// Metoda, getter nebo setter tridy (vcetne konstruktoru).
+    fn add_class_method(
+        &mut self,
+        m: &ClassMember,
+        key: PropertyKey,
+        decs: &[JsValue],
+        scope: &ClassScope,
+        parts: &mut ClassParts,
+    ) -> Result<(), JsError> {
+        if m.is_constructor() {
+            parts.has_ctor = true;
+            parts.ctor_params = m.params.clone();
+            parts.ctor_body = m.body.clone();
+            return Ok(());
+        }
+        let mut def = ClassMethodDef { name: key, params: m.params.clone(), body: m.body.clone(), decorated: None, id: next_func_id() };
+        if !decs.is_empty() {
+            let mut ctx = DecoratorContext::member(DecoratorKind::for_method(m.is_getter, m.is_setter), &def.name, m.is_static);
+            ctx.access = Some(self.decorator_access(&ctx, &def.name, scope));
+            let (f, inits) = self.run_decorators(decs, &ctx, def.func(&scope.method_env), &scope.metadata, Ok)?;
+            def.decorated = Some(f);
+            if m.is_static { parts.static_method_inits.extend(inits) } else { parts.instance_inits.extend(inits) }
+        }
+        Self::register_class_method(def, m.is_static, m.is_getter, m.is_setter, scope, parts);
+        Ok(())
+    }
+
+    /// Zaradi metodu: staticke accessory, privatni a dekorovane staticke metody
+    /// jsou vlastnosti tridy, ostatni se instaluji pri `new` / hledaji v `statics`.
+    fn register_class_method(
+        def: ClassMethodDef,
+        is_static: bool,
+        is_getter: bool,
+        is_setter: bool,
+        scope: &ClassScope,
+        parts: &mut ClassParts,
+    ) {
+        let private = private_names::is_private_key(&def.name);
+        if is_static && (is_getter || is_setter || private || def.decorated.is_some()) {
+            let f = def.func(&scope.method_env);
+            if private {
+                let holder = JsValue::Object(Rc::clone(&scope.static_props));
+                private_names::define_method(&holder, &def.name, f, is_getter, is_setter);
+                return;
+            }
+            let mut props = scope.static_props.borrow_mut();
+            match (is_getter, is_setter) {
+                (true, _) => props.define_accessor(def.name, Some(f), None),
+                (_, true) => props.define_accessor(def.name, None, Some(f)),
+                _ => props.define_hidden(def.name, f),
+            }
+        } else if is_static {
+            parts.statics.push(def);
+        } else if is_getter {
+            parts.getters.push(def);
+        } else if is_setter {
+            parts.setters.push(def);
+        } else {
+            parts.methods.push(def);
+        }
+    }
+
+    /// Pole tridy; dekoratory pole vraci inicializatory pocatecni hodnoty.
+    fn add_class_field<'a>(
+        &mut self,
+        m: &ClassMember,
+        index: usize,
+        key: PropertyKey,
+        decs: &[JsValue],
+        scope: &ClassScope,
+        parts: &mut ClassParts<'a>,
+    ) -> Result<(), JsError> {
+        let mut def = ClassFieldDef {
+            key, value: m.value.as_deref().cloned(), decorators: vec![], initializers: vec![],
+        };
+        if !decs.is_empty() {
+            let mut ctx = DecoratorContext::member(DecoratorKind::Field, &def.key, m.is_static);
+            ctx.access = Some(self.decorator_access(&ctx, &def.key, scope));
+            let mut transforms = Vec::new();
+            let step = |init| { transforms.push(init); Ok(JsValue::Undefined) };
+            (_, def.initializers) = self.run_decorators(decs, &ctx, JsValue::Undefined, &scope.metadata, step)?;
+            def.decorators = transforms;
+        }
+        parts.push_field(index, m.is_static, def);
+        Ok(())
+    }
+
+    /// `accessor` pole: privatni uloziste + getter/setter pod klicem `key`.
+    /// Dekoratory dostanou `{ get, set }` a mohou vratit nahrady a `init`.
+    fn add_class_accessor<'a>(
+        &mut self,
+        m: &ClassMember,
+        index: usize,
+        key: PropertyKey,
+        decs: &[JsValue],
+        scope: &ClassScope,
+        parts: &mut ClassParts<'a>,
+    ) -> Result<(), JsError> {
+        let [storage, getter, setter] = m.expand_accessor(index);
+        let storage_key = self.resolve_prop_key(&MemberProp::Private(storage.name), &scope.class_env)?;
+        let method = |part: ClassMember| ClassMethodDef {
+            name: key.clone(), params: part.params, body: part.body, decorated: None, id: next_func_id(),
+        };
+        let (mut get_def, mut set_def) = (method(getter), method(setter));
+        let mut field = ClassFieldDef {
+            key: storage_key, value: storage.value.map(|v| *v), decorators: vec![], initializers: vec![],
+        };
+        if !decs.is_empty() {
+            let mut ctx = DecoratorContext::member(DecoratorKind::Accessor, &key, m.is_static);
+            ctx.access = Some(self.decorator_access(&ctx, &key, scope));
+            let mut pair = [get_def.func(&scope.method_env), set_def.func(&scope.method_env)];
+            let value = decorators::accessor_object(&pair);
+            let mut transforms = Vec::new();
+            let step = |result: JsValue| {
+                let [get, set, init] = decorators::accessor_parts(&result)?;
+                for (slot, f) in pair.iter_mut().zip([get, set]) {
+                    if let Some(f) = f { *slot = f; }
+                }
+                transforms.extend(init);
+                Ok(decorators::accessor_object(&pair))
             };
-            if m.name == "constructor" && !m.is_static {
-                has_ctor = true;
-                ctor_params = m.params.clone();
-                ctor_body   = m.body.clone();
-            } else if m.is_static {
-                statics.push(def);
-            } else if m.is_getter {
-                getters.push(def);
-            } else if m.is_setter {
-                setters.push(def);
+            (_, field.initializers) = self.run_decorators(decs, &ctx, value, &scope.metadata, step)?;
+            field.decorators = transforms;
+            let [get, set] = pair;
+            (get_def.decorated, set_def.decorated) = (Some(get), Some(set));
+        }
+        Self::register_class_method(get_def, m.is_static, true, false, scope, parts);
+        Self::register_class_method(set_def, m.is_static, false, true, scope, parts);
+        parts.push_field(index, m.is_static, field);
+        Ok(())
+    }
+
+    /// Dekoratory clenu nebo tridy (`decorators::apply`) volane tree-walkerem.
+    fn run_decorators(
+        &mut self,
+        decs: &[JsValue],
+        ctx: &DecoratorContext,
+        value: JsValue,
+        metadata: &JsValue,
+        step: impl FnMut(JsValue) -> Result<JsValue, String>,
+    ) -> Result<(JsValue, Vec<JsValue>), JsError> {
+        let call = |dec: &JsValue, args| self.call_function(dec.clone(), args, Some(JsValue::Undefined));
+        decorators::apply(decs, ctx, value, metadata, call, step, JsError::Runtime)
+    }
+
+    /// `context.access` clenu: `get(o)` / `set(o, v)` / `has(o)` jako JS funkce
+    /// ve scope tridy - privatni jmena, gettery i settery se resi bezne.
+    /// Verejny klic (i symbol) je v `__access_key__` scope funkci.
+    fn decorator_access(&self, ctx: &DecoratorContext, key: &PropertyKey, scope: &ClassScope) -> JsValue {
+        let private_name = || ctx.name.as_str().unwrap_or_default().trim_start_matches('#').to_string();
+        let key_expr = || Box::new(Expr::Ident("__access_key__".into(), SourceSpan::default()));
+        let env = Environment::new_child(&scope.class_env);
+        env.borrow_mut().define("__access_key__", key.to_value());
+        let obj = || Box::new(Expr::Ident("o".into(), SourceSpan::default()));
+        let target = || Box::new(Expr::Member {
+            object: obj(),
+            prop: match ctx.is_private {
+                true => MemberProp::Private(private_name()),
+                false => MemberProp::Computed(key_expr()),
+            },
+            optional: false,
+            span: SourceSpan::default(),
+        });
+        let func = |name: &str, params: &[&str], body: Stmt| JsValue::Function(JsFunc::User {
+            name: Some(name.into()),
+            params: params.iter().map(|p| Param::simple(p.to_string())).collect(),
+            body: FuncBody::Stmts(vec![body]),
+            env: Rc::clone(&env),
+            is_arrow: false,
+            id: next_func_id(),
+        });
+        let (get, set) = ctx.kind.access();
+        let has = match ctx.is_private {
+            true => Expr::PrivateIn { name: private_name(), object: obj(), span: SourceSpan::default() },
+            false => Expr::Binary { op: BinaryOp::In, left: key_expr(), right: obj(), span: SourceSpan::default() },
+        };
+        decorators::access_object(
+            get.then(|| func("get", &["o"], Stmt::Return(Some(*target()), SourceSpan::default()))),
+            set.then(|| func("set", &["o", "v"], Stmt::Expr(Expr::Assign {
+                op: AssignOp::Assign, target: target(), value: Box::new(Expr::Ident("v".into(), SourceSpan::default())),
+                span: SourceSpan::default(),
+            }, SourceSpan::default()))),
+            func("has", &["o"], Stmt::Return(Some(has), SourceSpan::default())),
+        )
+    }
+
+    /// Inicializuje pole instance tridy `class_val` na `this_val` (v poradi deklarace).
+    /// Predtim spusti `addInitializer` z dekoratoru metod. Inicializatory bezi
+    /// ve scope tridy s this = instance.
+    pub(super) fn init_class_fields(&mut self, class_val: &JsValue, this_val: &JsValue) -> Result<(), JsError> {
+        let JsValue::Function(JsFunc::Class { fields, initializers, super_val, env, .. }) = class_val else {
+            return Ok(());
+        };
+        for f in initializers {
+            self.call_function(f.clone(), vec![], Some(this_val.clone()))?;
+        }
+        if fields.is_empty() {
+            return Ok(());
+        }
+        let field_env = Environment::new_child(env);
+        field_env.borrow_mut().define("this", this_val.clone());
+        if let Some(sv) = super_val {
+            field_env.borrow_mut().define("__super_class__", (**sv).clone());
+        }
+        for f in fields {
+            self.init_class_field(f, this_val, &field_env)?;
+        }
+        Ok(())
+    }
+
+    /// Definuje jedno pole na `this_val`: inicializator v `env`, transformace
+    /// z dekoratoru, definice a nakonec `addInitializer` dekoratoru pole.
+    fn init_class_field(&mut self, def: &ClassFieldDef, this_val: &JsValue, env: &Rc<RefCell<Env>>) -> Result<(), JsError> {
+        let mut v = match &def.value {
+            Some(e) => self.eval(e, env)?,
+            None => JsValue::Undefined,
+        };
+        for init in &def.decorators {
+            v = self.call_function(init.clone(), vec![v], Some(this_val.clone()))?;
+        }
+        private_names::define_field(this_val, &def.key, v).map_err(JsError::Runtime)?;
+        for f in &def.initializers {
+            self.call_function(f.clone(), vec![], Some(this_val.clone()))?;
+        }
+        Ok(())
+    }
+
+    /// Nainstaluje metody a accessory tridy na instanci. Metody podtridy maji
+    /// prednost (`only_missing` = instalace metod rodice pri `super()`).
+    fn install_class_methods(
+        this_obj: &Rc<RefCell<JsObject>>,
+        methods: &[ClassMethodDef],
+        getters: &[ClassMethodDef],
+        setters: &[ClassMethodDef],
+        method_env: &Rc<RefCell<Env>>,
+        only_missing: bool,
+    ) {
+        let func = |def: &ClassMethodDef| def.func(method_env);
+        let this_val = JsValue::Object(Rc::clone(this_obj));
+        for mdef in methods {
+            if only_missing && this_obj.borrow().props.contains_key(&mdef.name) {
+                continue;
+            }
+            if private_names::is_private_key(&mdef.name) {
+                private_names::define_method(&this_val, &mdef.name, func(mdef), false, false);
             } else {
-                methods.push(def);
+                this_obj.borrow_mut().define_hidden(mdef.name.clone(), func(mdef));
+            }
+        }
+        // Gettery/settery - get + set stejneho jmena = jeden slot; u rodice jen chybejici polovina
+        let missing = |name: &PropertyKey, getter: bool| !only_missing || match this_obj.borrow().props.slot(name) {
+            None => true,
+            Some(s) => s.is_accessor() && if getter { s.getter.is_none() } else { s.setter.is_none() },
+        };
+        for gdef in getters {
+            if missing(&gdef.name, true) {
+                this_obj.borrow_mut().define_accessor(gdef.name.clone(), Some(func(gdef)), None);
             }
         }
+        for sdef in setters {
+            if missing(&sdef.name, false) {
+                this_obj.borrow_mut().define_accessor(sdef.name.clone(), None, Some(func(sdef)));
+            }
+        }
+    }
 
-        JsValue::Function(JsFunc::Class {
-            name,
-            super_val,
-            has_ctor,
-            ctor_params,
-            ctor_body,
-            methods,
-            statics,
-            getters,
-            setters,
-            env: Rc::clone(env),
-        })
+    /// `obj.#x` - brand check, pak hodnota pole/metody nebo getter (this = obj).
+    pub(super) fn get_private(&mut self, obj: &JsValue, key: &str) -> EvalResult {
+        let slot = private_names::check_get(obj, key).map_err(JsError::Runtime)?;
+        match slot.getter {
+            Some(getter) => self.call_function(getter, vec![], Some(obj.clone())),
+            None => Ok(slot.value),
+        }
+    }
+
+    /// `obj.#x = val` - brand check, pak zapis do pole nebo setter (this = obj).
+    pub(super) fn set_private(&mut self, obj: &JsValue, key: &str, val: JsValue) -> Result<(), JsError> {
+        let slot = private_names::check_set(obj, key).map_err(JsError::Runtime)?;
+        match slot.setter {
+            Some(setter) => { self.call_function(setter, vec![val], Some(obj.clone()))?; }
+            None => private_names::write(obj, key, val),
+        }
+        Ok(())
     }
 
     /// Konstruuje novou instanci tridy (`new Foo(args)`).
@@ -66,11 +507,11 @@ impl Interpreter {
             ctor_params,
             ctor_body,
             methods,
-            statics: _,
             getters,
             setters,
             env,
-        }) = class_val else {
+            ..
+        }) = &class_val else {
             return Err(JsError::Runtime("construct_class: ocekavana trida".into()));
         };
 
@@ -81,66 +522,41 @@ impl Interpreter {
         {
             let chain = build_class_chain(name.as_deref().unwrap_or(""), super_val.as_deref());
             if !chain.is_empty() {
-                this_obj.borrow_mut().set("__class_chain__".to_string(), JsValue::Str(chain));
+                this_obj.borrow_mut().set("__class_chain__".into(), JsValue::Str(chain));
             }
         }
 
         // Env pro metody obsahuje __super_class__ (pro super.method() uvnitr metod)
-        let method_env = Environment::new_child(&env);
-        if let Some(sv) = &super_val {
+        let method_env = Environment::new_child(env);
+        if let Some(sv) = super_val {
             method_env.borrow_mut().define("__super_class__", (**sv).clone());
         }
 
-        // Prirad instance metody objektu
-        for mdef in &methods {
-            let mfunc = JsValue::Function(JsFunc::User {
-                name: Some(mdef.name.clone()),
-                params: mdef.params.clone(),
-                body: FuncBody::Stmts(mdef.body.clone()),
-                env: Rc::clone(&method_env),
-            });
-            this_obj.borrow_mut().set(mdef.name.clone(), mfunc);
-        }
-
-        // Prirad gettery (ulozeny jako __get_name__ pro speciální eval_member handling)
-        for gdef in &getters {
-            let gfunc = JsValue::Function(JsFunc::User {
-                name: Some(gdef.name.clone()),
-                params: gdef.params.clone(),
-                body: FuncBody::Stmts(gdef.body.clone()),
-                env: Rc::clone(&method_env),
-            });
-            this_obj.borrow_mut().set(format!("__get_{}__", gdef.name), gfunc);
-        }
-
-        // Prirad settery
-        for sdef in &setters {
-            let sfunc = JsValue::Function(JsFunc::User {
-                name: Some(sdef.name.clone()),
-                params: sdef.params.clone(),
-                body: FuncBody::Stmts(sdef.body.clone()),
-                env: Rc::clone(&method_env),
-            });
-            this_obj.borrow_mut().set(format!("__set_{}__", sdef.name), sfunc);
-        }
+        // Prirad instance metody, gettery a settery objektu
+        Self::install_class_methods(&this_obj, methods, getters, setters, &method_env, false);
 
         // Konstruktor env: this + __super_class__
-        let ctor_env = Environment::new_child(&env);
+        let ctor_env = Environment::new_child(env);
         ctor_env.borrow_mut().define("this", this_val.clone());
-        if let Some(sv) = &super_val {
+        if let Some(sv) = super_val {
             ctor_env.borrow_mut().define("__super_class__", (**sv).clone());
+            // Pole podtridy inicializuje az `super()` (viz eval_call)
+            ctor_env.borrow_mut().define("__fields_class__", class_val.clone());
         }
 
-        if has_ctor {
+        if super_val.is_none() {
+            self.init_class_fields(&class_val, &this_val)?;
+        }
+        if *has_ctor {
             // Explicitni konstruktor - svaz parametry a spust telo
-            let ctor_params = ctor_params.clone();
-            self.bind_params(&ctor_params, args, &ctor_env)?;
-            self.exec_stmts(&ctor_body, &ctor_env)?;
+            self.bind_params(ctor_params, args, &ctor_env)?;
+            self.exec_stmts(ctor_body, &ctor_env)?;
         } else if let Some(sv) = super_val {
             // Zadny konstruktor + ma super -> auto-deleguj super(args)
-            self.run_super_constructor(*sv, args, &this_obj, &ctor_env)?;
+            self.run_super_constructor((**sv).clone(), args, &this_obj, &ctor_env)?;
+            self.init_class_fields(&class_val, &this_val)?;
         }
-        // Else: zadny konstruktor, zadny super -> objekt je prazdny (vlastnosti se priradi rucne)
+        // Else: zadny konstruktor, zadny super -> jen pole tridy
 
         Ok(this_val)
     }
@@ -156,7 +572,7 @@ impl Interpreter {
         this_obj: &Rc<RefCell<JsObject>>,
         _parent_env: &Rc<RefCell<Env>>,
     ) -> Result<(), JsError> {
-        match super_class {
+        match &super_class {
             JsValue::Function(JsFunc::Class {
                 super_val,
                 has_ctor,
@@ -169,72 +585,43 @@ impl Interpreter {
                 ..
             }) => {
                 // Env pro metody parenta: super_val jako __super_class__ (pro super.method() uvnitr parenta)
-                let method_env = Environment::new_child(&env);
-                if let Some(sv) = &super_val {
+                let method_env = Environment::new_child(env);
+                if let Some(sv) = super_val {
                     method_env.borrow_mut().define("__super_class__", (**sv).clone());
                 }
 
-                // Prirad metody parenta - jen pokud uz nejsou defibovany podtridou
-                for mdef in &methods {
-                    if !this_obj.borrow().props.contains_key(&mdef.name) {
-                        let mfunc = JsValue::Function(JsFunc::User {
-                            name: Some(mdef.name.clone()),
-                            params: mdef.params.clone(),
-                            body: FuncBody::Stmts(mdef.body.clone()),
-                            env: Rc::clone(&method_env),
-                        });
-                        this_obj.borrow_mut().set(mdef.name.clone(), mfunc);
-                    }
-                }
-                for gdef in &getters {
-                    let key = format!("__get_{}__", gdef.name);
-                    if !this_obj.borrow().props.contains_key(&key) {
-                        let gfunc = JsValue::Function(JsFunc::User {
-                            name: Some(gdef.name.clone()),
-                            params: gdef.params.clone(),
-                            body: FuncBody::Stmts(gdef.body.clone()),
-                            env: Rc::clone(&method_env),
-                        });
-                        this_obj.borrow_mut().set(key, gfunc);
-                    }
-                }
-                for sdef in &setters {
-                    let key = format!("__set_{}__", sdef.name);
-                    if !this_obj.borrow().props.contains_key(&key) {
-                        let sfunc = JsValue::Function(JsFunc::User {
-                            name: Some(sdef.name.clone()),
-                            params: sdef.params.clone(),
-                            body: FuncBody::Stmts(sdef.body.clone()),
-                            env: Rc::clone(&method_env),
-                        });
-                        this_obj.borrow_mut().set(key, sfunc);
-                    }
-                }
+                // Prirad metody parenta - jen pokud uz nejsou definovany podtridou
+                Self::install_class_methods(this_obj, methods, getters, setters, &method_env, true);
 
                 // Spust konstruktor parenta
-                let ctor_env = Environment::new_child(&env);
-                ctor_env.borrow_mut().define("this", JsValue::Object(Rc::clone(this_obj)));
-                if let Some(sv) = &super_val {
+                let this_val = JsValue::Object(Rc::clone(this_obj));
+                let ctor_env = Environment::new_child(env);
+                ctor_env.borrow_mut().define("this", this_val.clone());
+                if let Some(sv) = super_val {
                     ctor_env.borrow_mut().define("__super_class__", (**sv).clone());
+                    ctor_env.borrow_mut().define("__fields_class__", super_class.clone());
+                } else {
+                    self.init_class_fields(&super_class, &this_val)?;
                 }
 
-                if has_ctor {
-                    self.bind_params(&ctor_params, args, &ctor_env)?;
-                    self.exec_stmts(&ctor_body, &ctor_env)?;
+                if *has_ctor {
+                    self.bind_params(ctor_params, args, &ctor_env)?;
+                    self.exec_stmts(ctor_body, &ctor_env)?;
                 } else if let Some(sv) = super_val {
                     // Auto-deleguj na praprarodice
-                    self.run_super_constructor(*sv, args, this_obj, &ctor_env)?;
+                    self.run_super_constructor((**sv).clone(), args, this_obj, &ctor_env)?;
+                    self.init_class_fields(&super_class, &this_val)?;
                 }
 
                 Ok(())
             }
             // Parent je stara-style function constructor (ne class)
             JsValue::Function(JsFunc::User { params, body, env, .. }) => {
-                let ctor_env = Environment::new_child(&env);
+                let ctor_env = Environment::new_child(env);
                 ctor_env.borrow_mut().define("this", JsValue::Object(Rc::clone(this_obj)));
-                self.bind_params(&params, args, &ctor_env)?;
+                self.bind_params(params, args, &ctor_env)?;
                 if let FuncBody::Stmts(stmts) = body {
-                    self.exec_stmts(&stmts, &ctor_env)?;
+                    self.exec_stmts(stmts, &ctor_env)?;
                 }
                 Ok(())
             }
@@ -248,22 +635,17 @@ impl Interpreter {
     ///
     /// Prochazi hierarchii trid (super_val retezec) pokud metoda neni nalezena.
     /// Vraci `JsValue::Function` nebo `JsValue::Undefined`.
-    pub(super) fn get_class_method_func(&self, class_val: &JsValue, name: &str) -> EvalResult {
+    pub(super) fn get_class_method_func(&self, class_val: &JsValue, name: &PropertyKey) -> EvalResult {
         match class_val {
             JsValue::Function(JsFunc::Class { super_val, methods, env, .. }) => {
                 for mdef in methods {
-                    if mdef.name == name {
+                    if mdef.name == *name {
                         // Env metody: obsahuje __super_class__ pro dalsi super.method() volani
                         let method_env = Environment::new_child(env);
                         if let Some(sv) = super_val {
                             method_env.borrow_mut().define("__super_class__", (**sv).clone());
                         }
-                        return Ok(JsValue::Function(JsFunc::User {
-                            name: Some(mdef.name.clone()),
-                            params: mdef.params.clone(),
-                            body: FuncBody::Stmts(mdef.body.clone()),
-                            env: Rc::clone(&method_env),
-                        }));
+                        return Ok(mdef.func(&method_env));
                     }
                 }
                 // Metoda nenalezena - zkus v super (pro vicenasobnou dedicnost)
@@ -25,6 +25,7 @@ pub enum ConsoleArgKind {
     Function,
     Error,
     BigInt,
+    Symbol,
     Date,
     RegExp,
     Dom,
@@ -80,6 +81,11 @@ impl ConsoleArg {
                 repr: format!("{}n", b),
                 children: Vec::new(),
             },
+            JsValue::Symbol(sym) => ConsoleArg {
+                kind: ConsoleArgKind::Symbol,
+                repr: sym.display(),
+                children: Vec::new(),
+            },
             JsValue::Array(arr) => {
                 let v = arr.borrow();
                 let len = v.len();
@@ -113,23 +119,23 @@ impl ConsoleArg {
             },
             JsValue::Map(m) => {
                 let mb = m.borrow();
-                let children: Vec<(String, String)> = mb.entries.iter().take(16)
+                let children: Vec<(String, String)> = mb.entries().take(16)
                     .map(|(k, val)| (nested_preview(k), nested_preview(val)))
                     .collect();
                 ConsoleArg {
                     kind: ConsoleArgKind::Map,
-                    repr: format!("Map({})", mb.entries.len()),
+                    repr: if mb.weak { "WeakMap".into() } else { format!("Map({})", mb.len()) },
                     children,
                 }
             }
             JsValue::Set(s) => {
                 let sb = s.borrow();
-                let children: Vec<(String, String)> = sb.values.iter().take(16).enumerate()
+                let children: Vec<(String, String)> = sb.values().take(16).enumerate()
                     .map(|(i, x)| (i.to_string(), nested_preview(x)))
                     .collect();
                 ConsoleArg {
                     kind: ConsoleArgKind::Set,
-                    repr: format!("Set({})", sb.values.len()),
+                    repr: if sb.weak { "WeakSet".into() } else { format!("Set({})", sb.len()) },
                     children,
                 }
             }
@@ -229,6 +235,7 @@ fn nested_preview(v: &JsValue) -> String {
         JsValue::Undefined => "undefined".into(),
         JsValue::BigInt(b) => format!("{}n", b),
         JsValue::BigNumber(b) => b.to_string(),
+        JsValue::Symbol(sym) => sym.display(),
         JsValue::Array(a) => format!("Array({})", a.borrow().len()),
         JsValue::Object(o) => {
             let ob = o.borrow();
@@ -239,8 +246,10 @@ fn nested_preview(v: &JsValue) -> String {
         }
         JsValue::DomNode(n) => format!("<{}>", n.tag_name_ref().unwrap_or("?")),
         JsValue::Function(_) => "fn".into(),
-        JsValue::Map(m) => format!("Map({})", m.borrow().entries.len()),
-        JsValue::Set(s) => format!("Set({})", s.borrow().values.len()),
+        JsValue::Map(m) if m.borrow().weak => "WeakMap".into(),
+        JsValue::Set(s) if s.borrow().weak => "WeakSet".into(),
+        JsValue::Map(m) => format!("Map({})", m.borrow().len()),
+        JsValue::Set(s) => format!("Set({})", s.borrow().len()),
     }
 }
 
@@ -6,6 +6,8 @@
 
 use std::collections::HashMap;
 
+use crate::ast::SourceSpan;
+
 #[derive(Debug, Clone)]
 pub struct Breakpoint {
     pub id: u64,
@@ -95,6 +97,13 @@ impl DebuggerState {
         self.breakpoints.values().filter(|bp| bp.script_id == script_id && bp.line == line).collect()
     }
 
+    /// Breakpointy pro prikaz na `span`: radkove a sloupcove presne na jeho zacatku.
+    pub fn breakpoints_at_span(&self, script_id: u64, span: &SourceSpan) -> Vec<&Breakpoint> {
+        self.breakpoints_at(script_id, span.start.line).into_iter()
+            .filter(|bp| bp.column.is_none_or(|c| c == span.start.column))
+            .collect()
+    }
+
     pub fn pause(&mut self, reason: PauseReason, call_stack: Vec<CallFrame>, hit_ids: Vec<u64>) {
         // Increment hit_count for each hit BP
         for id in &hit_ids {
@@ -151,6 +160,20 @@ mod tests {
         assert_eq!(d.breakpoints_at(1, 20).len(), 1);
     }
 
+    #[test]
+    fn bp_lookup_by_span() {
+        use crate::ast::SourcePos;
+        let mut d = DebuggerState::new();
+        d.set_breakpoint(1, 1, None, None);
+        d.set_breakpoint(1, 1, Some(12), None);
+        let at = |column| SourceSpan {
+            start: SourcePos { line: 1, column, offset: column },
+            end: SourcePos { line: 1, column: column + 5, offset: column + 5 },
+        };
+        assert_eq!(d.breakpoints_at_span(1, &at(0)).len(), 1);
+        assert_eq!(d.breakpoints_at_span(1, &at(12)).len(), 2);
+    }
+
     #[test]
     fn pause_resume() {
         let mut d = DebuggerState::new();
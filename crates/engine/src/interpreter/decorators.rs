@@ -6,7 +6,19 @@
 //! Decorator function shape:
 //!   (value, context) -> new_value
 //! context = { kind: 'method'|'class'|'field'|'getter'|'setter'|'accessor',
-//!             name, access: {get, set}, static, private, addInitializer }
+//!             name, access: {get, set, has}, static, private, addInitializer, metadata }
+//!
+//! Aplikaci resi tree-walker (`class.rs`) i bytecode VM (`Decorate` opcody);
+//! tady je sdilena cast - context objekty, fronty `addInitializer`, kontrola
+//! navratovych hodnot a `Symbol.metadata`.
+
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
+use crate::ast::{ClassMember, ClassMemberKind};
+
+use super::{bytecode, private_names, JsFunc, JsObject, JsValue};
+use super::property_map::PropertyKey;
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum DecoratorKind {
@@ -18,23 +30,229 @@ pub enum DecoratorKind {
     Accessor,
 }
 
+impl DecoratorKind {
+    /// Vsechny druhy v poradi diskriminantu (`kind as u8` -> druh).
+    pub const ALL: [DecoratorKind; 6] = [
+        DecoratorKind::Class, DecoratorKind::Method, DecoratorKind::Getter,
+        DecoratorKind::Setter, DecoratorKind::Field, DecoratorKind::Accessor,
+    ];
+
+    /// Druh dekoratoru metody / getteru / setteru.
+    pub fn for_method(is_getter: bool, is_setter: bool) -> Self {
+        match (is_getter, is_setter) {
+            (true, _) => DecoratorKind::Getter,
+            (_, true) => DecoratorKind::Setter,
+            _ => DecoratorKind::Method,
+        }
+    }
+
+    /// Hodnota `context.kind`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            DecoratorKind::Class => "class",
+            DecoratorKind::Method => "method",
+            DecoratorKind::Getter => "getter",
+            DecoratorKind::Setter => "setter",
+            DecoratorKind::Field => "field",
+            DecoratorKind::Accessor => "accessor",
+        }
+    }
+
+    /// Ktere funkce nese `context.access` (get, set); `has` maji vsechny cleny.
+    pub fn access(self) -> (bool, bool) {
+        match self {
+            DecoratorKind::Class => (false, false),
+            DecoratorKind::Method | DecoratorKind::Getter => (true, false),
+            DecoratorKind::Setter => (false, true),
+            DecoratorKind::Field | DecoratorKind::Accessor => (true, true),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct DecoratorContext {
     pub kind: DecoratorKind,
-    pub name: String,
+    pub name: PropertyKey,
     pub is_static: bool,
     pub is_private: bool,
     pub initializers: Vec<u64>,     // callback ids to run during construction
+    /// `context.access` (objekt z `access_object`; u tridy `None`)
+    pub access: Option<JsValue>,
 }
 
 impl DecoratorContext {
     pub fn new(kind: DecoratorKind, name: &str) -> Self {
-        Self { kind, name: name.into(), is_static: false, is_private: false, initializers: Vec::new() }
+        Self { kind, name: name.into(), is_static: false, is_private: false, initializers: Vec::new(), access: None }
     }
 
     pub fn add_initializer(&mut self, callback_id: u64) {
         self.initializers.push(callback_id);
     }
+
+    /// Context clenu tridy s klicem vlastnosti `key` (u privatniho clenu
+    /// interni klic z `private_names`).
+    pub(crate) fn member(kind: DecoratorKind, key: &PropertyKey, is_static: bool) -> Self {
+        let is_private = private_names::is_private_key(key);
+        let name = match key {
+            PropertyKey::Str(k) if is_private => private_names::source_name(k).into(),
+            _ => key.clone(),
+        };
+        Self { kind, name, is_static, is_private, initializers: Vec::new(), access: None }
+    }
+
+    /// JS objekt `context` pro jedno volani dekoratoru.
+    pub(crate) fn to_object(&self, metadata: &JsValue, sink: &InitializerSink) -> JsValue {
+        let mut ctx = JsObject::new();
+        ctx.set("kind".into(), JsValue::Str(self.kind.as_str().into()));
+        let name = match self.kind {
+            DecoratorKind::Class if self.name == "" => JsValue::Undefined,
+            _ => self.name.to_value(),
+        };
+        ctx.set("name".into(), name);
+        if self.kind != DecoratorKind::Class {
+            ctx.set("static".into(), JsValue::Bool(self.is_static));
+            ctx.set("private".into(), JsValue::Bool(self.is_private));
+        }
+        if let Some(access) = &self.access {
+            ctx.set("access".into(), access.clone());
+        }
+        ctx.set("addInitializer".into(), sink.add_fn());
+        ctx.set("metadata".into(), metadata.clone());
+        JsValue::Object(Rc::new(RefCell::new(ctx)))
+    }
+}
+
+/// Skupina, ve ktere se aplikuji dekoratory elementu tridy: staticke metody
+/// a accessory, metody instance, staticka pole (a bloky), pole instance.
+/// Uvnitr skupiny plati poradi deklarace (stabilni razeni).
+pub(crate) fn application_order(m: &ClassMember) -> u8 {
+    let field = matches!(m.kind, ClassMemberKind::Field | ClassMemberKind::StaticBlock);
+    (field as u8) * 2 + (!m.is_static) as u8
+}
+
+/// Zavola dekoratory (posledni ve zdrojaku prvni) s hodnotou clenu a context
+/// objektem; `call` vola dekorator s this = undefined, `lift` prevadi chyby
+/// kontroly vysledku. `step` zpracuje kazdou nahradu a vrati hodnotu pro dalsi
+/// dekorator. Vraci vyslednou hodnotu a funkce z `addInitializer`.
+pub(crate) fn apply<E>(
+    decs: &[JsValue],
+    ctx: &DecoratorContext,
+    mut value: JsValue,
+    metadata: &JsValue,
+    mut call: impl FnMut(&JsValue, Vec<JsValue>) -> Result<JsValue, E>,
+    mut step: impl FnMut(JsValue) -> Result<JsValue, String>,
+    lift: fn(String) -> E,
+) -> Result<(JsValue, Vec<JsValue>), E> {
+    let mut inits = Vec::new();
+    for dec in decs.iter().rev() {
+        let sink = InitializerSink::new();
+        let context = ctx.to_object(metadata, &sink);
+        let result = call(dec, vec![value.clone(), context]);
+        inits.extend(sink.finish());
+        if let Some(new) = check_result(ctx.kind, result?).map_err(lift)? {
+            value = step(new).map_err(lift)?;
+        }
+    }
+    Ok((value, inits))
+}
+
+/// Fronta funkci z `context.addInitializer(fn)`. Dekorator ji smi plnit jen
+/// behem sveho volani - po `finish` je dalsi `addInitializer` TypeError.
+pub(crate) struct InitializerSink {
+    list: Rc<RefCell<Vec<JsValue>>>,
+    open: Rc<Cell<bool>>,
+}
+
+impl InitializerSink {
+    pub(crate) fn new() -> Self {
+        Self { list: Rc::default(), open: Rc::new(Cell::new(true)) }
+    }
+
+    fn add_fn(&self) -> JsValue {
+        let (list, open) = (Rc::clone(&self.list), Rc::clone(&self.open));
+        JsValue::Function(JsFunc::Native("addInitializer".into(), Rc::new(move |args| {
+            if !open.get() {
+                return Err("TypeError: addInitializer called after decoration is finished".into());
+            }
+            let f = args.into_iter().next().unwrap_or(JsValue::Undefined);
+            if !is_callable(&f) {
+                return Err("TypeError: An initializer must be a function".into());
+            }
+            list.borrow_mut().push(f);
+            Ok(JsValue::Undefined)
+        })))
+    }
+
+    /// Uzavre frontu a vrati pridane inicializatory v poradi volani.
+    pub(crate) fn finish(self) -> Vec<JsValue> {
+        self.open.set(false);
+        self.list.take()
+    }
+}
+
+/// `context.access` - funkce `get(obj)` / `set(obj, v)` / `has(obj)` podle druhu.
+pub(crate) fn access_object(get: Option<JsValue>, set: Option<JsValue>, has: JsValue) -> JsValue {
+    let mut access = JsObject::new();
+    if let Some(g) = get { access.set("get".into(), g); }
+    if let Some(s) = set { access.set("set".into(), s); }
+    access.set("has".into(), has);
+    JsValue::Object(Rc::new(RefCell::new(access)))
+}
+
+/// Hodnota pro `accessor` dekorator: `{ get, set }`.
+pub(crate) fn accessor_object([get, set]: &[JsValue; 2]) -> JsValue {
+    let mut o = JsObject::new();
+    o.set("get".into(), get.clone());
+    o.set("set".into(), set.clone());
+    JsValue::Object(Rc::new(RefCell::new(o)))
+}
+
+/// Funkce nebo trida (tree-walker i VM).
+pub(crate) fn is_callable(v: &JsValue) -> bool {
+    match v {
+        JsValue::Function(_) => true,
+        JsValue::Object(o) => o.borrow().props.contains_key(bytecode::VM_CLASS),
+        _ => false,
+    }
+}
+
+/// Navratova hodnota dekoratoru: `undefined` = beze zmeny, jinak nahrada
+/// (funkce; u `accessor` objekt `{ get, set, init }`, u pole inicializator).
+pub(crate) fn check_result(kind: DecoratorKind, v: JsValue) -> Result<Option<JsValue>, String> {
+    match (kind, &v) {
+        (_, JsValue::Undefined) => Ok(None),
+        (DecoratorKind::Accessor, JsValue::Object(_)) => Ok(Some(v)),
+        (DecoratorKind::Accessor, _) => {
+            Err("TypeError: accessor decorators must return an object or undefined".into())
+        }
+        (_, v) if is_callable(v) => Ok(Some(v.clone())),
+        (kind, _) => Err(format!("TypeError: {} decorators must return a function or undefined", kind.as_str())),
+    }
+}
+
+/// Rozlozi vysledek `accessor` dekoratoru na (get, set, init).
+pub(crate) fn accessor_parts(result: &JsValue) -> Result<[Option<JsValue>; 3], String> {
+    let JsValue::Object(o) = result else { return Ok([None, None, None]) };
+    let o = o.borrow();
+    let mut parts = [None, None, None];
+    for (slot, name) in parts.iter_mut().zip(["get", "set", "init"]) {
+        match o.get(name) {
+            JsValue::Undefined => {}
+            f if is_callable(&f) => *slot = Some(f),
+            _ => return Err(format!("TypeError: accessor.{name} must be a function")),
+        }
+    }
+    Ok(parts)
+}
+
+/// `context.metadata` nove tridy - objekt dedici z `Symbol.metadata` rodice.
+pub(crate) fn new_metadata(parent_metadata: JsValue) -> JsValue {
+    let mut m = JsObject::new();
+    m.proto = match parent_metadata {
+        JsValue::Object(p) => Some(p),
+        _ => None,
+    };
+    JsValue::Object(Rc::new(RefCell::new(m)))
 }
 
 #[derive(Debug, Clone)]
@@ -0,0 +1,428 @@
+//! Explicit resource management - `using` / `await using` deklarace,
+//! `DisposableStack`, `AsyncDisposableStack` a `SuppressedError`.
+//!
+//! Tree-walker vola dispose metody primo pri opusteni bloku (i pri vyjimce).
+//! VM blok s `using` prelozi na `try`/`finally` nad `DisposableStack` -
+//! obe cesty tak sdili poradi disposalu (opacne k registraci) i skladani
+//! chyb: chyba disposeru obali predchozi jako `SuppressedError(nova, stara)`.
+
+use super::*;
+use super::call_machinery::{self, vm_error};
+
+/// Jmena pomocnych vazeb lowered `using` bloku (`%` v identifikatoru
+/// uzivatelsky kod napsat nemuze).
+const STACK: &str = "%using_stack";
+const ERROR: &str = "%using_error";
+const FAILED: &str = "%using_failed";
+const CAUGHT: &str = "%using_caught";
+const VALUE: &str = "%using_value";
+
+/// Zaregistrovany disposer: `method.call(this, ...args)`; `is_async` =
+/// vysledek se ceka (`[Symbol.asyncDispose]`, callback AsyncDisposableStack).
+#[derive(Clone)]
+pub(crate) struct DisposeRecord {
+    this: JsValue,
+    method: JsValue,
+    args: Vec<JsValue>,
+    is_async: bool,
+}
+
+/// Obsahuje seznam primo `using` / `await using` deklaraci?
+pub(crate) fn has_using(stmts: &[Stmt]) -> bool {
+    stmts.iter().any(|s| using_decl(s).is_some())
+}
+
+/// `using` deklarace (pod pripadnym `WithLine`).
+fn using_decl(s: &Stmt) -> Option<(VarKind, &[VarDecl])> {
+    match s {
+        Stmt::WithLine { inner, .. } => using_decl(inner),
+        Stmt::Var { kind, decls, .. } if kind.is_using() => Some((*kind, decls)),
+        _ => None,
+    }
+}
+
+/// Disposer pro hodnotu `using` deklarace; `None` pro null/undefined.
+/// `get` cte vlastnost hodnoty (tree-walker vcetne getteru).
+fn dispose_record(
+    value: JsValue,
+    is_async: bool,
+    get: impl Fn(&JsValue, symbols::WellKnown) -> Result<JsValue, String>,
+) -> Result<Option<DisposeRecord>, String> {
+    if matches!(value, JsValue::Undefined | JsValue::Null) {
+        return Ok(None);
+    }
+    if !matches!(value, JsValue::Object(_) | JsValue::Function(_) | JsValue::DomNode(_)) {
+        return Err("TypeError: using declaration value must be an object".into());
+    }
+    // `await using` bere sync `[Symbol.dispose]`, kdyz async metoda chybi
+    let mut awaited = is_async;
+    let mut method = if is_async { get(&value, symbols::ASYNC_DISPOSE)? } else { JsValue::Undefined };
+    if matches!(method, JsValue::Undefined | JsValue::Null) {
+        awaited = false;
+        method = get(&value, symbols::DISPOSE)?;
+    }
+    if !matches!(method, JsValue::Function(_)) {
+        let symbol = if is_async { "Symbol(Symbol.asyncDispose)" } else { "Symbol(Symbol.dispose)" };
+        return Err(format!("TypeError: {symbol} is not a function"));
+    }
+    Ok(Some(DisposeRecord { this: value, method, args: Vec::new(), is_async: awaited }))
+}
+
+/// Vlastnost obycejneho objektu (pro natives bez pristupu k interpretu).
+fn plain_get(value: &JsValue, key: symbols::WellKnown) -> Result<JsValue, String> {
+    Ok(match value {
+        JsValue::Object(o) => o.borrow().get(key),
+        _ => JsValue::Undefined,
+    })
+}
+
+/// `new SuppressedError(error, suppressed, message)`.
+pub(crate) fn suppressed_error(error: JsValue, suppressed: JsValue, message: &str) -> JsValue {
+    let mut obj = JsObject::new();
+    obj.set("name".into(), JsValue::Str("SuppressedError".into()));
+    obj.set("message".into(), JsValue::Str(message.to_string()));
+    obj.set("stack".into(), JsValue::Str(format!("SuppressedError: {message}")));
+    obj.set("error".into(), error);
+    obj.set("suppressed".into(), suppressed);
+    JsValue::Object(Rc::new(RefCell::new(obj)))
+}
+
+/// Zavola disposery v opacnem poradi. `error` = chyba, se kterou se scope
+/// opousti. Vraci novou chybu, kdyz nektery disposer selhal (`None` =
+/// puvodni vysledek plati).
+fn dispose_all(
+    records: Vec<DisposeRecord>,
+    mut error: Option<JsValue>,
+    mut call: impl FnMut(&DisposeRecord) -> Result<JsValue, JsValue>,
+) -> Option<JsValue> {
+    let mut failed = false;
+    for record in records.iter().rev() {
+        let outcome = call(record).and_then(|v| {
+            if record.is_async { unwrap_promise_result(v) } else { Ok(v) }
+        });
+        if let Err(e) = outcome {
+            failed = true;
+            error = Some(match error {
+                Some(prev) => suppressed_error(e, prev, "An error was suppressed during disposal."),
+                None => e,
+            });
+        }
+    }
+    if failed { error } else { None }
+}
+
+/// Hodnota JS chyby (runtime chyby jsou v JS retezce).
+fn error_value(e: JsError) -> JsValue {
+    match e {
+        JsError::Thrown(v) | JsError::GeneratorReturn(v) => v,
+        JsError::Runtime(s) => JsValue::Str(s),
+    }
+}
+
+// ─── Tree-walker ────────────────────────────────────────────────────────────
+
+impl Interpreter {
+    /// Seznam prikazu s `using` deklaracemi: na konci (i pri vyjimce nebo
+    /// `return`/`break`) zavola disposery zaregistrovanych hodnot.
+    pub(super) fn exec_using_stmts(&mut self, stmts: &[Stmt], env: &Rc<RefCell<Environment>>) -> StmtResult {
+        let mut records = Vec::new();
+        let mut result = Ok(None);
+        for s in stmts {
+            let r = match using_decl(s) {
+                Some((kind, decls)) => {
+                    if let Stmt::WithLine { line, span, .. } = s {
+                        self.current_line = *line;
+                        self.current_span.set(*span);
+                    }
+                    self.exec_using_decl(kind, decls, env, &mut records).map(|_| None)
+                }
+                None => self.exec_stmt(s, env),
+            };
+            if !matches!(r, Ok(None)) {
+                result = r;
+                break;
+            }
+        }
+        let error = match &result {
+            Err(JsError::Thrown(v)) => Some(v.clone()),
+            Err(JsError::Runtime(s)) => Some(JsValue::Str(s.clone())),
+            _ => None,
+        };
+        let failed = dispose_all(records, error, |r| {
+            let this = (!matches!(r.this, JsValue::Undefined)).then(|| r.this.clone());
+            self.call_function(r.method.clone(), r.args.clone(), this).map_err(error_value)
+        });
+        match failed {
+            Some(e) => Err(JsError::Thrown(e)),
+            None => result,
+        }
+    }
+
+    /// `using x = ...`: const vazba + registrace disposeru.
+    fn exec_using_decl(
+        &mut self,
+        kind: VarKind,
+        decls: &[VarDecl],
+        env: &Rc<RefCell<Environment>>,
+        records: &mut Vec<DisposeRecord>,
+    ) -> Result<(), JsError> {
+        for d in decls {
+            let val = match &d.init { Some(e) => self.eval(e, env)?, None => JsValue::Undefined };
+            let record = dispose_record(val.clone(), kind == VarKind::AwaitUsing, |v, k| {
+                self.get_prop(v, k).map_err(|e| match e {
+                    JsError::Runtime(s) => s,
+                    other => bytecode::throw_value(error_value(other)),
+                })
+            }).map_err(vm_error)?;
+            records.extend(record);
+            self.destructure_bind(&d.pattern, val, env)?;
+            let mut names = Vec::new();
+            d.pattern.bound_names(&mut names);
+            let mut env = env.borrow_mut();
+            for n in &names { env.mark_const(n); }
+        }
+        Ok(())
+    }
+}
+
+// ─── Lowering pro VM (a for-of hlavicky) ────────────────────────────────────
+
+fn ident(name: &str) -> Expr {
+    Expr::Ident(name.into(), SourceSpan::default())
+}
+
+fn member_call(object: &str, method: &str, args: Vec<Expr>) -> Expr {
+    Expr::Call {
+        callee: Box::new(Expr::Member {
+            object: Box::new(ident(object)),
+            prop: MemberProp::Ident(method.into()),
+            optional: false,
+            span: SourceSpan::default(),
+        }),
+        args,
+        optional: false,
+        span: SourceSpan::default(),
+    }
+}
+
+fn declare(kind: VarKind, name: &str, init: Expr) -> Stmt {
+    Stmt::Var { kind, decls: vec![VarDecl { pattern: Pattern::Ident(name.into()), init: Some(init) }], span: SourceSpan::default() }
+}
+
+fn assign(name: &str, value: Expr) -> Stmt {
+    Stmt::Expr(Expr::Assign { op: AssignOp::Assign, target: Box::new(ident(name)), value: Box::new(value), span: SourceSpan::default() }, SourceSpan::default())
+}
+
+/// `for (using x of it) body` -> `for (const %v of it) { using x = %v; body }`.
+/// Ostatni prikazy vraci beze zmeny.
+pub(crate) fn lower_for_of(stmt: &Stmt) -> Stmt {
+    let lower = |kind: &Option<VarKind>, target: &Expr, body: &Stmt| -> Option<(Box<Expr>, Box<Stmt>)> {
+        let kind = kind.filter(|k| k.is_using())?;
+        let Expr::Ident(name, _) = target else { return None };
+        let body = Stmt::Block(vec![declare(kind, name, ident(VALUE)), body.clone()], SourceSpan::default());
+        Some((Box::new(ident(VALUE)), Box::new(body)))
+    };
+    match stmt {
+        Stmt::ForOf { kind, target, iter, body, .. } => match lower(kind, target, body) {
+            Some((target, body)) => Stmt::ForOf { kind: Some(VarKind::Const), target, iter: iter.clone(), body, span: SourceSpan::default() },
+            None => stmt.clone(),
+        },
+        Stmt::ForAwaitOf { kind, target, iter, body, .. } => match lower(kind, target, body) {
+            Some((target, body)) => Stmt::ForAwaitOf { kind: Some(VarKind::Const), target, iter: iter.clone(), body, span: SourceSpan::default() },
+            None => stmt.clone(),
+        },
+        _ => stmt.clone(),
+    }
+}
+
+/// Blok s `using` deklaracemi pro VM:
+///
+/// ```text
+/// { const %s = new DisposableStack(); let %e; let %f = false;
+///   try { ...; const x = %s.use(init); ... }
+///   catch (%c) { %f = true; %e = %c; }
+///   finally { %s.__dispose__(%f, %e); if (%f) throw %e; } }
+/// ```
+///
+/// `__dispose__` vyhodi chybu disposeru slozenou s `%e`; u `await using`
+/// vraci promise, na kterou se ceka.
+pub(crate) fn lower_using_block(stmts: &[Stmt]) -> Vec<Stmt> {
+    let is_async = stmts.iter().any(|s| matches!(using_decl(s), Some((VarKind::AwaitUsing, _))));
+    let body = stmts.iter().map(|s| match using_decl(s) {
+        Some((kind, decls)) => {
+            // sync `using` v async bloku vola jen `[Symbol.dispose]`
+            let method = if is_async && kind == VarKind::Using { "__use_sync__" } else { "use" };
+            let decls = decls.iter().map(|d| VarDecl {
+                pattern: d.pattern.clone(),
+                init: Some(member_call(STACK, method, d.init.clone().into_iter().collect())),
+            }).collect();
+            Stmt::Var { kind: VarKind::Const, decls, span: SourceSpan::default() }
+        }
+        None => s.clone(),
+    }).collect();
+    let class = if is_async { "AsyncDisposableStack" } else { "DisposableStack" };
+    let mut dispose = member_call(STACK, "__dispose__", vec![ident(FAILED), ident(ERROR)]);
+    if is_async {
+        dispose = Expr::Await { value: Box::new(dispose), span: SourceSpan::default() };
+    }
+    let finally = vec![
+        Stmt::Expr(dispose, SourceSpan::default()),
+        Stmt::If { test: ident(FAILED), yes: Box::new(Stmt::Throw(ident(ERROR), SourceSpan::default())), no: None, span: SourceSpan::default() },
+    ];
+    vec![Stmt::Block(vec![
+        declare(VarKind::Const, STACK, Expr::New { callee: Box::new(ident(class)), args: vec![], span: SourceSpan::default() }),
+        Stmt::Var { kind: VarKind::Let, decls: vec![VarDecl { pattern: Pattern::Ident(ERROR.into()), init: None }], span: SourceSpan::default() },
+        declare(VarKind::Let, FAILED, Expr::Bool(false, SourceSpan::default())),
+        Stmt::Try {
+            body,
+            catch: Some(CatchClause {
+                param: Some(CAUGHT.into()),
+                body: vec![assign(FAILED, Expr::Bool(true, SourceSpan::default())), assign(ERROR, ident(CAUGHT))],
+            }),
+            finally: Some(finally),
+            span: SourceSpan::default(),
+        },
+    ], SourceSpan::default())]
+}
+
+// ─── Vestavene tridy ────────────────────────────────────────────────────────
+
+/// Natives, ktere volaji JS disposery (vstup pres `with_interpreter`).
+pub(crate) fn calls_js(name: &str) -> bool {
+    name.starts_with("DisposableStack.") || name.starts_with("AsyncDisposableStack.")
+}
+
+struct StackState {
+    records: Vec<DisposeRecord>,
+    disposed: bool,
+}
+
+/// Zavola disposer z nativni funkce.
+fn call_record(r: &DisposeRecord) -> Result<JsValue, JsValue> {
+    call_machinery::call_js(&r.method, r.this.clone(), r.args.clone()).map_err(error_value)
+}
+
+/// Instance `DisposableStack` / `AsyncDisposableStack` nad sdilenym stavem.
+fn make_stack(is_async: bool, records: Vec<DisposeRecord>) -> JsValue {
+    let class = if is_async { "AsyncDisposableStack" } else { "DisposableStack" };
+    let state = Rc::new(RefCell::new(StackState { records, disposed: false }));
+    let mut obj = JsObject::new();
+    obj.set("__class_chain__".into(), JsValue::Str(class.into()));
+    obj.define_hidden(symbols::TO_STRING_TAG.into(), JsValue::Str(class.into()));
+
+    let live = {
+        let state = Rc::clone(&state);
+        move || if state.borrow().disposed {
+            Err(format!("ReferenceError: {class} already disposed"))
+        } else {
+            Ok(())
+        }
+    };
+
+    let (st, check) = (Rc::clone(&state), live.clone());
+    obj.define_hidden("use".into(), native(&format!("{class}.use"), move |args| {
+        check()?;
+        let value = args.into_iter().next().unwrap_or(JsValue::Undefined);
+        let record = dispose_record(value.clone(), is_async, plain_get)?;
+        st.borrow_mut().records.extend(record);
+        Ok(value)
+    }));
+    if is_async {
+        // sync `using` v bloku s `await using` (lowered VM blok)
+        let (st, check) = (Rc::clone(&state), live.clone());
+        obj.define_hidden("__use_sync__".into(), native(&format!("{class}.use"), move |args| {
+            check()?;
+            let value = args.into_iter().next().unwrap_or(JsValue::Undefined);
+            let record = dispose_record(value.clone(), false, plain_get)?;
+            st.borrow_mut().records.extend(record);
+            Ok(value)
+        }));
+    }
+    let (st, check) = (Rc::clone(&state), live.clone());
+    obj.define_hidden("adopt".into(), native(&format!("{class}.adopt"), move |args| {
+        check()?;
+        let mut it = args.into_iter();
+        let value = it.next().unwrap_or(JsValue::Undefined);
+        let method = it.next().unwrap_or(JsValue::Undefined);
+        if !matches!(method, JsValue::Function(_)) {
+            return Err("TypeError: onDispose is not a function".into());
+        }
+        st.borrow_mut().records.push(DisposeRecord { this: JsValue::Undefined, method, args: vec![value.clone()], is_async });
+        Ok(value)
+    }));
+    let (st, check) = (Rc::clone(&state), live.clone());
+    obj.define_hidden("defer".into(), native(&format!("{class}.defer"), move |args| {
+        check()?;
+        let method = args.into_iter().next().unwrap_or(JsValue::Undefined);
+        if !matches!(method, JsValue::Function(_)) {
+            return Err("TypeError: onDispose is not a function".into());
+        }
+        st.borrow_mut().records.push(DisposeRecord { this: JsValue::Undefined, method, args: Vec::new(), is_async });
+        Ok(JsValue::Undefined)
+    }));
+    let (st, check) = (Rc::clone(&state), live);
+    obj.define_hidden("move".into(), native(&format!("{class}.move"), move |_| {
+        check()?;
+        let mut s = st.borrow_mut();
+        s.disposed = true;
+        Ok(make_stack(is_async, std::mem::take(&mut s.records)))
+    }));
+
+    // dispose() / disposeAsync() - opakovane volani nic nedela. Interni
+    // `__dispose__(failed, error)` = konec lowered `using` bloku.
+    let st = Rc::clone(&state);
+    let dispose = Rc::new(move |error: Option<JsValue>| -> Option<JsValue> {
+        let records = {
+            let mut s = st.borrow_mut();
+            if s.disposed { return None; }
+            s.disposed = true;
+            std::mem::take(&mut s.records)
+        };
+        dispose_all(records, error, call_record)
+    });
+    let settle = move |failed: Option<JsValue>| -> Result<JsValue, String> {
+        match (failed, is_async) {
+            (Some(e), true) => Ok(make_settled_promise("rejected", e)),
+            (None, true) => Ok(make_settled_promise("fulfilled", JsValue::Undefined)),
+            (Some(e), false) => Err(bytecode::throw_value(e)),
+            (None, false) => Ok(JsValue::Undefined),
+        }
+    };
+    let (name, key) = if is_async {
+        ("disposeAsync", symbols::ASYNC_DISPOSE)
+    } else {
+        ("dispose", symbols::DISPOSE)
+    };
+    let d = Rc::clone(&dispose);
+    let method = native(&format!("{class}.{name}"), move |_| settle(d(None)));
+    obj.define_hidden(name.into(), method.clone());
+    obj.define_hidden(key.into(), method);
+    obj.define_hidden("__dispose__".into(), native(&format!("{class}.{name}"), move |args| {
+        let mut it = args.into_iter();
+        let failed = matches!(it.next(), Some(JsValue::Bool(true)));
+        let error = it.next().filter(|_| failed);
+        settle(dispose(error))
+    }));
+
+    let st = Rc::clone(&state);
+    obj.define_accessor("disposed".into(), Some(native(&format!("{class}.disposed"), move |_| {
+        Ok(JsValue::Bool(st.borrow().disposed))
+    })), None);
+    JsValue::Object(Rc::new(RefCell::new(obj)))
+}
+
+/// Globalni `SuppressedError`, `DisposableStack` a `AsyncDisposableStack`.
+pub(crate) fn setup(env: &mut Environment) {
+    env.define("SuppressedError", native("SuppressedError", |args| {
+        let mut it = args.into_iter();
+        let error = it.next().unwrap_or(JsValue::Undefined);
+        let suppressed = it.next().unwrap_or(JsValue::Undefined);
+        let message = match it.next() {
+            None | Some(JsValue::Undefined) => String::new(),
+            Some(m) => m.to_string(),
+        };
+        Ok(suppressed_error(error, suppressed, &message))
+    }));
+    env.define("DisposableStack", native("DisposableStack", |_| Ok(make_stack(false, Vec::new()))));
+    env.define("AsyncDisposableStack", native("AsyncDisposableStack", |_| Ok(make_stack(true, Vec::new()))));
+}
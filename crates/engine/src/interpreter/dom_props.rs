@@ -4,7 +4,7 @@
 use std::rc::{Rc, Weak};
 use std::cell::RefCell;
 use std::collections::HashMap;
-use super::{JsValue, JsObject};
+use super::{JsValue, JsObject, symbols};
 use super::helpers::native;
 
 /// Bundle DOM version counteru interpreteru pro detached natives (classList,
@@ -85,8 +85,8 @@ fn refresh_style_from_attr(obj: &Rc<RefCell<JsObject>>, node: &Rc<crate::browser
             let val = pair[idx+1..].trim().to_string();
             if !prop.is_empty() {
                 let camel = kebab_to_camel(&prop);
-                obj.borrow_mut().set(camel, JsValue::Str(val.clone()));
-                obj.borrow_mut().set(prop, JsValue::Str(val));
+                obj.borrow_mut().set(camel.into(), JsValue::Str(val.clone()));
+                obj.borrow_mut().set(prop.into(), JsValue::Str(val));
             }
         }
     }
@@ -181,16 +181,16 @@ fn build_style_object(
                 Ok(JsValue::Str(props.get(idx).cloned().unwrap_or_default()))
             }));
     }
-    // length getter - pres __get_length__.
+    // length - accessor vlastnost (getter pocita deklarace ze style atributu).
     {
         let n = Rc::clone(&node);
-        obj_rc.borrow_mut().set("__get_length__".into(),
-            native("style.length getter", move |_| {
+        obj_rc.borrow_mut().define_accessor("length".into(),
+            Some(native("style.length getter", move |_| {
                 let style = n.attr("style").unwrap_or_default();
                 let cnt = style.split(';').filter(|p| p.contains(':')
                     && !p.split(':').next().unwrap_or("").trim().is_empty()).count();
                 Ok(JsValue::Number(cnt as f64))
-            }));
+            })), None);
     }
     obj_rc
 }
@@ -381,7 +381,7 @@ pub(crate) fn create_class_list(
     // Symbol.iterator - iterate tokenu pro for-of a Array.from.
     {
         let n = Rc::clone(&node);
-        obj_rc.borrow_mut().set("Symbol.iterator".into(),
+        obj_rc.borrow_mut().set(symbols::ITERATOR.into(),
             native("classList[Symbol.iterator]", move |_| {
                 let class = n.attr("class").unwrap_or_default();
                 let tokens: Vec<JsValue> = class.split_whitespace()
@@ -404,7 +404,7 @@ pub(crate) fn create_dataset(node: &Rc<crate::browser::dom::NodeData>) -> JsValu
         if let Some(rest) = k.strip_prefix("data-") {
             // kebab-case -> camelCase: data-foo-bar -> fooBar
             let camel = kebab_to_camel(rest);
-            obj_rc.borrow_mut().set(camel, JsValue::Str(v.clone()));
+            obj_rc.borrow_mut().set(camel.into(), JsValue::Str(v.clone()));
         }
     }
     JsValue::Object(obj_rc)
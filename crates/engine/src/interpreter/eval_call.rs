@@ -5,7 +5,7 @@ use super::*;
 impl Interpreter {
     pub(super) fn eval_call(&mut self, callee: &Expr, args: &[Expr], optional: bool, env: &Rc<RefCell<Environment>>) -> EvalResult {
         // super(args) - volani konstruktoru rodicovske tridy
-        if matches!(callee, Expr::Ident(n) if n == "super") {
+        if matches!(callee, Expr::Ident(n, _) if n == "super") {
             let super_class = env.borrow().get("__super_class__")
                 .ok_or_else(|| JsError::Runtime("super() lze volat jen uvnitr konstruktoru tridy".into()))?;
             let arg_vals = self.eval_args(args, env)?;
@@ -13,12 +13,17 @@ impl Interpreter {
             if let JsValue::Object(ref this_obj) = this_val {
                 let this_obj = Rc::clone(this_obj);
                 self.run_super_constructor(super_class, arg_vals, &this_obj, env)?;
+                // Pole podtridy se inicializuji hned po navratu z super()
+                let fields_class = env.borrow().get("__fields_class__");
+                if let Some(cls) = fields_class {
+                    self.init_class_fields(&cls, &this_val)?;
+                }
             }
             return Ok(this_val);
         }
 
         // eval(src) - special case: potrebuje pristup k interpreteru a aktualnimu env
-        if matches!(callee, Expr::Ident(n) if n == "eval") {
+        if matches!(callee, Expr::Ident(n, _) if n == "eval") {
             let arg_vals = self.eval_args(args, env)?;
             let src = arg_vals.into_iter().next().unwrap_or(JsValue::Undefined);
             return match src {
@@ -58,7 +63,7 @@ impl Interpreter {
                         peeled = inner;
                     }
                     match peeled {
-                        crate::ast::Stmt::Expr(e) => self.eval(e, env),
+                        crate::ast::Stmt::Expr(e, _) => self.eval(e, env),
                         _ => {
                             match self.exec_stmt(last, env)? {
                                 Some(Signal::Return(v)) => Ok(v),
@@ -73,7 +78,7 @@ impl Interpreter {
 
         // super.method(args) - volani metody rodicovske tridy
         if let Expr::Member { object, prop, .. } = callee {
-            if matches!(object.as_ref(), Expr::Ident(n) if n == "super") {
+            if matches!(object.as_ref(), Expr::Ident(n, _) if n == "super") {
                 let super_class = env.borrow().get("__super_class__")
                     .ok_or_else(|| JsError::Runtime("super.method() lze volat jen uvnitr tridy".into()))?;
                 let key = self.resolve_prop_key(prop, env)?;
@@ -84,18 +89,50 @@ impl Interpreter {
             }
         }
 
-        if let Expr::Member { object, prop, optional: member_opt } = callee {
+        if let Expr::Member { object, prop, optional: member_opt, .. } = callee {
             let this = self.eval(object, env)?;
             // optional chaining: obj?.method() -> Undefined kdyz obj je null/undefined
             if (optional || *member_opt) && matches!(this, JsValue::Null | JsValue::Undefined) {
                 return Ok(JsValue::Undefined);
             }
-            let key = self.resolve_prop_key(prop, env)?;
+            let key = match self.resolve_prop_key(prop, env)? {
+                PropertyKey::Str(key) => key,
+                PropertyKey::Sym(sym) => match builtin_symbol_method(&this, &sym) {
+                    // arr[Symbol.iterator]() -> vestavena metoda pod svym jmenem
+                    Some(name) => name.to_string(),
+                    None => {
+                        let func = if proxy_handler::is_proxy(&this) {
+                            self.proxy_get(&this, &sym, this.clone())?
+                        } else {
+                            self.get_prop_accessor(&this, &sym)?
+                        };
+                        let arg_vals = self.eval_args(args, env)?;
+                        return self.call_function(func, arg_vals, Some(this));
+                    }
+                },
+            };
+
+            // obj.#m(args): brand check, privatni metoda nebo getter, this = obj
+            if let MemberProp::Private(_) = prop {
+                let func = self.get_private(&this, &key)?;
+                let arg_vals = self.eval_args(args, env)?;
+                return self.call_function(func, arg_vals, Some(this));
+            }
+
+            // proxy.method(args): metodu vraci get trap, `this` zustava proxy
+            if proxy_handler::is_proxy(&this) && !is_internal_key(&key) {
+                let func = self.proxy_get(&this, &key, this.clone())?;
+                let arg_vals = self.eval_args(args, env)?;
+                if matches!(func, JsValue::Undefined) {
+                    return self.call_proxy_builtin_method(&this, &key, arg_vals);
+                }
+                return self.call_function(func, arg_vals, Some(this));
+            }
 
             // ─── Object.groupBy / Map.groupBy (ES2024) ───────────────────────
             // Detekce DRIVE nez specificke arms, protoze Map/Native have early return
             if key.as_str() == "groupBy" {
-                if let Expr::Ident(name) = object.as_ref() {
+                if let Expr::Ident(name, _) = object.as_ref() {
                     if name == "Object" || name == "Map" {
                         let arg_vals = self.eval_args(args, env)?;
                         let mut iter = arg_vals.into_iter();
@@ -113,9 +150,10 @@ impl Interpreter {
                                 )?;
                                 let key_str = k.to_string();
                                 let mut g = groups_rc.borrow_mut();
-                                let bucket = g.props.entry(key_str)
-                                    .or_insert_with(|| JsValue::Array(Rc::new(RefCell::new(Vec::new()))));
-                                if let JsValue::Array(a) = bucket {
+                                if !g.props.contains_key(&key_str) {
+                                    g.props.insert(key_str.clone().into(), JsValue::Array(Rc::new(RefCell::new(Vec::new()))));
+                                }
+                                if let Some(JsValue::Array(a)) = g.props.get(&key_str) {
                                     a.borrow_mut().push(item);
                                 }
                             }
@@ -152,11 +190,18 @@ impl Interpreter {
                 JsValue::Map(map_rc) => {
                     let map_rc2 = Rc::clone(map_rc);
                     let arg_vals = self.eval_args(args, env)?;
+                    let weak = map_rc2.borrow().weak;
+                    if weak && !matches!(key.as_str(), "set" | "get" | "has" | "delete") {
+                        return Err(JsError::Runtime(format!("TypeError: weakMap.{key} is not a function")));
+                    }
                     match key.as_str() {
                         "set" => {
                             let mut iter = arg_vals.into_iter();
                             let k = iter.next().unwrap_or(JsValue::Undefined);
                             let v = iter.next().unwrap_or(JsValue::Undefined);
+                            if weak && !keyed_table::can_be_held_weakly(&k) {
+                                return Err(JsError::Runtime("TypeError: Invalid value used as weak map key".into()));
+                            }
                             map_rc2.borrow_mut().set(k, v);
                             return Ok(JsValue::Map(map_rc2));
                         }
@@ -172,25 +217,15 @@ impl Interpreter {
                             let k = arg_vals.into_iter().next().unwrap_or(JsValue::Undefined);
                             return Ok(JsValue::Bool(map_rc2.borrow_mut().delete(&k)));
                         }
-                        "clear" => { map_rc2.borrow_mut().entries.clear(); return Ok(JsValue::Undefined); }
-                        "keys" => {
-                            let keys: Vec<JsValue> = map_rc2.borrow().entries.iter().map(|(k,_)| k.clone()).collect();
-                            return Ok(make_array_iterator(keys));
-                        }
-                        "values" => {
-                            let vals: Vec<JsValue> = map_rc2.borrow().entries.iter().map(|(_,v)| v.clone()).collect();
-                            return Ok(make_array_iterator(vals));
-                        }
-                        "entries" => {
-                            let entries: Vec<JsValue> = map_rc2.borrow().entries.iter()
-                                .map(|(k,v)| JsValue::Array(Rc::new(RefCell::new(vec![k.clone(), v.clone()]))))
-                                .collect();
-                            return Ok(make_array_iterator(entries));
-                        }
+                        "clear" => { map_rc2.borrow_mut().table.clear(); return Ok(JsValue::Undefined); }
+                        "keys" => return Ok(make_collection_iterator(&this, CollectionIterKind::Keys)),
+                        "values" => return Ok(make_collection_iterator(&this, CollectionIterKind::Values)),
+                        "entries" => return Ok(make_collection_iterator(&this, CollectionIterKind::Entries)),
                         "forEach" => {
                             let cb = arg_vals.into_iter().next().unwrap_or(JsValue::Undefined);
-                            let entries: Vec<(JsValue,JsValue)> = map_rc2.borrow().entries.clone();
-                            for (k, v) in entries {
+                            // Zivy kurzor: callback smi mapu menit (spec poradi navstev).
+                            let cursor = map_rc2.borrow_mut().table.cursor();
+                            while let Some((k, v)) = collection_next(&this, &cursor) {
                                 self.call_function(cb.clone(), vec![v, k, JsValue::Map(Rc::clone(&map_rc2))], None)?;
                             }
                             return Ok(JsValue::Undefined);
@@ -202,9 +237,16 @@ impl Interpreter {
                 JsValue::Set(set_rc) => {
                     let set_rc2 = Rc::clone(set_rc);
                     let arg_vals = self.eval_args(args, env)?;
+                    let weak = set_rc2.borrow().weak;
+                    if weak && !matches!(key.as_str(), "add" | "has" | "delete") {
+                        return Err(JsError::Runtime(format!("TypeError: weakSet.{key} is not a function")));
+                    }
                     match key.as_str() {
                         "add" => {
                             let v = arg_vals.into_iter().next().unwrap_or(JsValue::Undefined);
+                            if weak && !keyed_table::can_be_held_weakly(&v) {
+                                return Err(JsError::Runtime("TypeError: Invalid value used in weak set".into()));
+                            }
                             set_rc2.borrow_mut().add(v);
                             return Ok(JsValue::Set(set_rc2));
                         }
@@ -216,21 +258,13 @@ impl Interpreter {
                             let v = arg_vals.into_iter().next().unwrap_or(JsValue::Undefined);
                             return Ok(JsValue::Bool(set_rc2.borrow_mut().delete(&v)));
                         }
-                        "clear" => { set_rc2.borrow_mut().values.clear(); return Ok(JsValue::Undefined); }
-                        "keys" | "values" => {
-                            let vals: Vec<JsValue> = set_rc2.borrow().values.clone();
-                            return Ok(make_array_iterator(vals));
-                        }
-                        "entries" => {
-                            let entries: Vec<JsValue> = set_rc2.borrow().values.iter()
-                                .map(|v| JsValue::Array(Rc::new(RefCell::new(vec![v.clone(), v.clone()]))))
-                                .collect();
-                            return Ok(make_array_iterator(entries));
-                        }
+                        "clear" => { set_rc2.borrow_mut().table.clear(); return Ok(JsValue::Undefined); }
+                        "keys" | "values" => return Ok(make_collection_iterator(&this, CollectionIterKind::Values)),
+                        "entries" => return Ok(make_collection_iterator(&this, CollectionIterKind::Entries)),
                         "forEach" => {
                             let cb = arg_vals.into_iter().next().unwrap_or(JsValue::Undefined);
-                            let vals: Vec<JsValue> = set_rc2.borrow().values.clone();
-                            for v in vals {
+                            let cursor = set_rc2.borrow_mut().table.cursor();
+                            while let Some((v, _)) = collection_next(&this, &cursor) {
                                 self.call_function(cb.clone(), vec![v.clone(), v, JsValue::Set(Rc::clone(&set_rc2))], None)?;
                             }
                             return Ok(JsValue::Undefined);
@@ -240,7 +274,7 @@ impl Interpreter {
                             let other = arg_vals.into_iter().next().unwrap_or(JsValue::Undefined);
                             let other_vals = collect_iterable_values(&other);
                             let mut result = JsSet::new();
-                            for v in set_rc2.borrow().values.clone() { result.add(v); }
+                            for v in set_rc2.borrow().values().cloned().collect::<Vec<_>>() { result.add(v); }
                             for v in other_vals { result.add(v); }
                             return Ok(JsValue::Set(Rc::new(RefCell::new(result))));
                         }
@@ -248,7 +282,7 @@ impl Interpreter {
                             let other = arg_vals.into_iter().next().unwrap_or(JsValue::Undefined);
                             let other_vals = collect_iterable_values(&other);
                             let mut result = JsSet::new();
-                            for v in set_rc2.borrow().values.clone() {
+                            for v in set_rc2.borrow().values().cloned().collect::<Vec<_>>() {
                                 if other_vals.iter().any(|x| JsMap::key_eq(x, &v)) {
                                     result.add(v);
                                 }
@@ -259,7 +293,7 @@ impl Interpreter {
                             let other = arg_vals.into_iter().next().unwrap_or(JsValue::Undefined);
                             let other_vals = collect_iterable_values(&other);
                             let mut result = JsSet::new();
-                            for v in set_rc2.borrow().values.clone() {
+                            for v in set_rc2.borrow().values().cloned().collect::<Vec<_>>() {
                                 if !other_vals.iter().any(|x| JsMap::key_eq(x, &v)) {
                                     result.add(v);
                                 }
@@ -270,7 +304,7 @@ impl Interpreter {
                             let other = arg_vals.into_iter().next().unwrap_or(JsValue::Undefined);
                             let other_vals = collect_iterable_values(&other);
                             let mut result = JsSet::new();
-                            for v in set_rc2.borrow().values.clone() {
+                            for v in set_rc2.borrow().values().cloned().collect::<Vec<_>>() {
                                 if !other_vals.iter().any(|x| JsMap::key_eq(x, &v)) {
                                     result.add(v);
                                 }
@@ -285,7 +319,7 @@ impl Interpreter {
                         "isSubsetOf" => {
                             let other = arg_vals.into_iter().next().unwrap_or(JsValue::Undefined);
                             let other_vals = collect_iterable_values(&other);
-                            let result = set_rc2.borrow().values.iter().all(|v| {
+                            let result = set_rc2.borrow().values().all(|v| {
                                 other_vals.iter().any(|x| JsMap::key_eq(x, v))
                             });
                             return Ok(JsValue::Bool(result));
@@ -299,7 +333,7 @@ impl Interpreter {
                         "isDisjointFrom" => {
                             let other = arg_vals.into_iter().next().unwrap_or(JsValue::Undefined);
                             let other_vals = collect_iterable_values(&other);
-                            let result = !set_rc2.borrow().values.iter().any(|v| {
+                            let result = !set_rc2.borrow().values().any(|v| {
                                 other_vals.iter().any(|x| JsMap::key_eq(x, v))
                             });
                             return Ok(JsValue::Bool(result));
@@ -347,10 +381,10 @@ impl Interpreter {
                                 let result_rc = Rc::new(RefCell::new(JsObject::new()));
                                 // Pre-naplnit kebab + camelCase keys.
                                 for (k, v) in &map {
-                                    result_rc.borrow_mut().set(k.clone(), JsValue::Str(v.clone()));
+                                    result_rc.borrow_mut().set(k.clone().into(), JsValue::Str(v.clone()));
                                     let camel = super::dom_props::kebab_to_camel(k);
                                     if &camel != k {
-                                        result_rc.borrow_mut().set(camel, JsValue::Str(v.clone()));
+                                        result_rc.borrow_mut().set(camel.into(), JsValue::Str(v.clone()));
                                     }
                                 }
                                 // getPropertyValue(name) - vraci hodnotu pro kebab key.
@@ -459,7 +493,7 @@ impl Interpreter {
                                     .unwrap_or(JsValue::Str(String::new()));
                                 let attrs = obj_rc2.borrow().props.get("__attrs__").cloned();
                                 if let Some(JsValue::Object(a)) = attrs {
-                                    a.borrow_mut().set(name.clone(), val.clone());
+                                    a.borrow_mut().set(name.clone().into(), val.clone());
                                 }
                                 // Specialni atributy: id, class promitnout do props
                                 match name.as_str() {
@@ -520,7 +554,7 @@ impl Interpreter {
                                         Some(JsValue::Array(a)) => a,
                                         _ => {
                                             let new_arr = Rc::new(RefCell::new(Vec::new()));
-                                            lst.borrow_mut().set(evt_type.clone(),
+                                            lst.borrow_mut().set(evt_type.clone().into(),
                                                 JsValue::Array(Rc::clone(&new_arr)));
                                             new_arr
                                         }
@@ -661,7 +695,16 @@ impl Interpreter {
                                     }
                                 }
                             }
-                            "blob" | "arrayBuffer" => {
+                            "arrayBuffer" => {
+                                let bytes = match obj_rc2.borrow().props.get("__body_bytes__") {
+                                    Some(buf) => super::builtins_typed_arrays::bytes_of(buf),
+                                    None => None,
+                                };
+                                let bytes = bytes.unwrap_or_else(|| body.into_bytes());
+                                return Ok(make_settled_promise("fulfilled",
+                                    super::builtins_typed_arrays::array_buffer_value(bytes)));
+                            }
+                            "blob" => {
                                 // Stub - vratime body jako string Promise
                                 return Ok(make_settled_promise("fulfilled", JsValue::Str(body)));
                             }
@@ -698,10 +741,11 @@ impl Interpreter {
                         match key.as_str() {
                             "postMessage" => {
                                 let msg = arg_vals.into_iter().next().unwrap_or(JsValue::Undefined);
-                                let serialized = json_stringify(&msg, 0, 0)
-                                    .unwrap_or_else(|| msg.to_string());
+                                let isolated = self.agent.cross_origin_isolated.get();
+                                let message = super::worker_message::encode(&msg, isolated)
+                                    .map_err(JsError::Runtime)?;
                                 if let Some(state) = self.workers.borrow().get(&worker_id) {
-                                    let _ = state.sender.send(serialized);
+                                    let _ = state.sender.send(message);
                                 }
                                 return Ok(JsValue::Undefined);
                             }
@@ -744,7 +788,7 @@ impl Interpreter {
                                 let k = iter.next().map(|v| v.to_string()).unwrap_or_default();
                                 let v = iter.next().map(|v| JsValue::Str(v.to_string()))
                                     .unwrap_or(JsValue::Str(String::new()));
-                                data.borrow_mut().set(k, v);
+                                data.borrow_mut().set(k.into(), v);
                                 let len = data.borrow().own_keys().len() as f64;
                                 obj_rc2.borrow_mut().set("length".into(), JsValue::Number(len));
                                 persist_now();
@@ -777,37 +821,6 @@ impl Interpreter {
                             _ => {}
                         }
                     }
-                    // ─── Intl.* metody ───────────────────────────────────
-                    if let Some(JsValue::Str(kind)) = obj_rc2.borrow().props.get("__intl_kind__").cloned() {
-                        let locale = match obj_rc2.borrow().props.get("__intl_locale__").cloned() {
-                            Some(JsValue::Str(s)) => s,
-                            _ => "en-US".into(),
-                        };
-                        let arg_vals = self.eval_args(args, env)?;
-                        match (kind.as_str(), key.as_str()) {
-                            ("number", "format") => {
-                                let n = arg_vals.first().map(|v| v.to_number()).unwrap_or(f64::NAN);
-                                return Ok(JsValue::Str(format_number_intl(n, &locale)));
-                            }
-                            ("datetime", "format") => {
-                                let ms = arg_vals.first().and_then(|v| get_date_ms(v))
-                                    .or_else(|| arg_vals.first().map(|v| v.to_number()))
-                                    .unwrap_or(0.0);
-                                return Ok(JsValue::Str(format_datetime_intl(ms, &locale)));
-                            }
-                            ("collator", "compare") => {
-                                let a = arg_vals.get(0).map(|v| v.to_string()).unwrap_or_default();
-                                let b = arg_vals.get(1).map(|v| v.to_string()).unwrap_or_default();
-                                let cmp = collator_compare_intl(&a, &b, &locale);
-                                return Ok(JsValue::Number(cmp as f64));
-                            }
-                            ("plural", "select") => {
-                                let n = arg_vals.first().map(|v| v.to_number()).unwrap_or(0.0);
-                                return Ok(JsValue::Str(plural_select(n, &locale)));
-                            }
-                            _ => {}
-                        }
-                    }
                     // ─── WeakRef.deref / FinalizationRegistry methods ──────
                     if obj_rc2.borrow().props.contains_key("__weak_target__") {
                         if key == "deref" {
@@ -861,6 +874,20 @@ impl Interpreter {
                                     yr, mo+1, day, hr, min, sec, ms_part
                                 )));
                             }
+                            "toTemporalInstant" => {
+                                return super::builtins_temporal::instant_from_epoch_ms(ms).map_err(JsError::Runtime);
+                            }
+                            "toLocaleDateString" | "toLocaleTimeString" | "toLocaleString" if !arg_vals.is_empty() => {
+                                use super::builtins_intl::DateDefaults;
+                                let defaults = match key.as_str() {
+                                    "toLocaleDateString" => DateDefaults::Date,
+                                    "toLocaleTimeString" => DateDefaults::Time,
+                                    _ => DateDefaults::All,
+                                };
+                                let (locales, opts) = (arg_vals[0].clone(), arg_vals.get(1).cloned().unwrap_or(JsValue::Undefined));
+                                return super::builtins_intl::format_date(ms, &locales, &opts, defaults)
+                                    .map(JsValue::Str).map_err(JsError::Runtime);
+                            }
                             "toLocaleDateString" => {
                                 return Ok(JsValue::Str(format!("{}/{}/{}", mo+1, day, yr)));
                             }
@@ -971,7 +998,7 @@ impl Interpreter {
                                                 // Zatim pouzijeme: arr.groups = obj
                                                 let mut groups_obj = JsObject::new();
                                                 for (n, v) in named {
-                                                    groups_obj.set(n, v.map(JsValue::Str).unwrap_or(JsValue::Undefined));
+                                                    groups_obj.set(n.into(), v.map(JsValue::Str).unwrap_or(JsValue::Undefined));
                                                 }
                                                 // Bohuzel arr je primo Array, ne Object - pripojime jako separatni
                                                 // hodnotu pres specialni klic? Zatim vratime jen positional.
@@ -1083,7 +1110,7 @@ impl Interpreter {
                         // obj.hasOwnProperty("key") - kontrola vlastni vlastnosti
                         "hasOwnProperty" => {
                             let k = arg_vals.into_iter().next()
-                                .map(|v| v.to_string()).unwrap_or_default();
+                                .map(|v| symbols::to_property_key(&v)).unwrap_or_default();
                             return Ok(JsValue::Bool(obj_rc2.borrow().has_own(&k)));
                         }
                         // obj.isPrototypeOf(other) - je this v proto retezci other?
@@ -1094,8 +1121,8 @@ impl Interpreter {
                         // obj.propertyIsEnumerable("key") - vlastni + ne-interni
                         "propertyIsEnumerable" => {
                             let k = arg_vals.into_iter().next()
-                                .map(|v| v.to_string()).unwrap_or_default();
-                            let is_enum = obj_rc2.borrow().has_own(&k) && !is_internal_key(&k);
+                                .map(|v| symbols::to_property_key(&v)).unwrap_or_default();
+                            let is_enum = obj_rc2.borrow().has_own(&k) && !k.is_internal();
                             return Ok(JsValue::Bool(is_enum));
                         }
                         "toString" => {
@@ -1104,12 +1131,36 @@ impl Interpreter {
                             if let Some(f) = custom {
                                 return self.call_function(f, arg_vals, Some(this));
                             }
-                            return Ok(JsValue::Str("[object Object]".into()));
+                            // Object.prototype.toString: "[object <Symbol.toStringTag>]"
+                            let tag = match obj_rc2.borrow().get(symbols::TO_STRING_TAG) {
+                                JsValue::Str(t) => t,
+                                _ => "Object".into(),
+                            };
+                            return Ok(JsValue::Str(format!("[object {tag}]")));
                         }
                         "valueOf"  => return Ok(JsValue::Object(Rc::clone(&obj_rc2))),
                         _ => {
-                            // Normalni method call
-                            let func = self.get_prop(&this, &key)?;
+                            // Callback metody typed array: metoda pole nad snapshotem hodnot
+                            if builtins_typed_arrays::CALLBACK_METHODS.contains(&key.as_str())
+                                && !obj_rc2.borrow().props.contains_key(key.as_str())
+                                && let Some(view) = builtins_typed_arrays::typed_view_of(&this)
+                            {
+                                let mut values = view.to_values();
+                                let result = if matches!(key.as_str(), "sort" | "toSorted")
+                                    && matches!(arg_vals.first(), None | Some(JsValue::Undefined))
+                                {
+                                    builtins_typed_arrays::sort_default(&mut values);
+                                    JsValue::Array(Rc::new(RefCell::new(values)))
+                                } else {
+                                    let method = if key == "toSorted" { "sort" } else { key.as_str() };
+                                    self.call_array_method(Rc::new(RefCell::new(values)), method, arg_vals)?
+                                        .unwrap_or(JsValue::Undefined)
+                                };
+                                return builtins_typed_arrays::finish_callback_method(&this, &view, &key, result)
+                                    .map_err(JsError::Runtime);
+                            }
+                            // Normalni method call (i pres getter, napr. namespace modulu)
+                            let func = self.get_prop_accessor(&this, &key)?;
                             return self.call_function(func, arg_vals, Some(this));
                         }
                     }
@@ -1168,12 +1219,14 @@ impl Interpreter {
                         }
                         "valueOf" => return Ok(JsValue::Number(n)),
                         "toLocaleString" => {
-                            // Volitelny prvni argument: locale string
-                            let locale = arg_vals.first().map(|v| v.to_string());
-                            return Ok(JsValue::Str(match locale {
-                                Some(loc) => format_number_intl(n, &loc),
-                                None      => format_number_locale(n),
-                            }));
+                            // Volitelne argumenty locales a options -> Intl.NumberFormat
+                            return match arg_vals.first() {
+                                Some(locales) => {
+                                    let opts = arg_vals.get(1).cloned().unwrap_or(JsValue::Undefined);
+                                    super::builtins_intl::format_number(n, locales, &opts).map(JsValue::Str).map_err(JsError::Runtime)
+                                }
+                                None => Ok(JsValue::Str(format_number_locale(n))),
+                            };
                         }
                         _ => {}
                     }
@@ -1262,6 +1315,15 @@ impl Interpreter {
                         _ => Ok(JsValue::Undefined),
                     };
                 }
+                // ─── Symbol instance metody ───────────────────────────────
+                JsValue::Symbol(sym) => {
+                    let sym = Rc::clone(sym);
+                    return match key.as_str() {
+                        "toString" => Ok(JsValue::Str(sym.display())),
+                        "valueOf" => Ok(JsValue::Symbol(sym)),
+                        _ => Err(JsError::Runtime(format!("TypeError: symbol.{key} neni funkce"))),
+                    };
+                }
                 // ─── DomNode metody (real browser::dom Node) ─────────────
                 JsValue::DomNode(node_rc) => {
                     use crate::browser::dom::NodeData;
@@ -1289,7 +1351,7 @@ impl Interpreter {
                             let instance = self.custom_element_instances.borrow().get(&node_ptr).cloned();
                             if let Some(inst) = instance {
                                 let cb = if let JsValue::Object(o) = &inst {
-                                    o.borrow().props.get("attributeChangedCallback").cloned()
+                                    Some(o.borrow().get("attributeChangedCallback")).filter(|f| matches!(f, JsValue::Function(_)))
                                 } else { None };
                                 if let Some(f) = cb {
                                     let _ = self.call_function(f, vec![
@@ -1338,7 +1400,7 @@ impl Interpreter {
                                 let instance = self.custom_element_instances.borrow().get(&child_ptr).cloned();
                                 if let Some(inst) = instance {
                                     let cb = if let JsValue::Object(o) = &inst {
-                                        o.borrow().props.get("connectedCallback").cloned()
+                                        Some(o.borrow().get("connectedCallback")).filter(|f| matches!(f, JsValue::Function(_)))
                                     } else { None };
                                     if let Some(f) = cb {
                                         let _ = self.call_function(f, vec![], Some(inst));
@@ -1355,7 +1417,7 @@ impl Interpreter {
                                 let instance = self.custom_element_instances.borrow().get(&child_ptr).cloned();
                                 if let Some(inst) = instance {
                                     let cb = if let JsValue::Object(o) = &inst {
-                                        o.borrow().props.get("disconnectedCallback").cloned()
+                                        Some(o.borrow().get("disconnectedCallback")).filter(|f| matches!(f, JsValue::Function(_)))
                                     } else { None };
                                     if let Some(f) = cb {
                                         let _ = self.call_function(f, vec![], Some(inst));
@@ -1379,7 +1441,7 @@ impl Interpreter {
                                 let old_inst = self.custom_element_instances.borrow().get(&old_ptr).cloned();
                                 if let Some(inst) = old_inst {
                                     let cb = if let JsValue::Object(o) = &inst {
-                                        o.borrow().props.get("disconnectedCallback").cloned()
+                                        Some(o.borrow().get("disconnectedCallback")).filter(|f| matches!(f, JsValue::Function(_)))
                                     } else { None };
                                     if let Some(f) = cb {
                                         let _ = self.call_function(f, vec![], Some(inst));
@@ -1393,7 +1455,7 @@ impl Interpreter {
                                 let new_inst = self.custom_element_instances.borrow().get(&new_ptr).cloned();
                                 if let Some(inst) = new_inst {
                                     let cb = if let JsValue::Object(o) = &inst {
-                                        o.borrow().props.get("connectedCallback").cloned()
+                                        Some(o.borrow().get("connectedCallback")).filter(|f| matches!(f, JsValue::Function(_)))
                                     } else { None };
                                     if let Some(f) = cb {
                                         let _ = self.call_function(f, vec![], Some(inst));
@@ -1433,7 +1495,7 @@ impl Interpreter {
                                     let instance = self.custom_element_instances.borrow().get(&child_ptr).cloned();
                                     if let Some(inst) = instance {
                                         let cb = if let JsValue::Object(o) = &inst {
-                                            o.borrow().props.get("connectedCallback").cloned()
+                                            Some(o.borrow().get("connectedCallback")).filter(|f| matches!(f, JsValue::Function(_)))
                                         } else { None };
                                         if let Some(f) = cb {
                                             let _ = self.call_function(f, vec![], Some(inst));
@@ -2209,6 +2271,7 @@ impl Interpreter {
                                 func: Box::new(this.clone()),
                                 bound_this: Box::new(bound_this),
                                 bound_args,
+                                id: next_func_id(),
                             }));
                         }
                         _ => unreachable!()
@@ -2220,7 +2283,8 @@ impl Interpreter {
                     let arg_vals = self.eval_args(args, env)?;
                     match (fname.as_str(), key.as_str()) {
                         ("Array", "isArray") => {
-                            return Ok(JsValue::Bool(matches!(arg_vals.first(), Some(JsValue::Array(_)))));
+                            let v = arg_vals.first().map(proxy_handler::innermost_target);
+                            return Ok(JsValue::Bool(matches!(v, Some(JsValue::Array(_)))));
                         }
                         ("Array", "from") => {
                             let src = arg_vals.into_iter().next().unwrap_or(JsValue::Undefined);
@@ -2229,17 +2293,17 @@ impl Interpreter {
                                 JsValue::Str(s) => JsValue::Array(Rc::new(RefCell::new(
                                     s.chars().map(|c| JsValue::Str(c.to_string())).collect()
                                 ))),
-                                JsValue::Set(s) => JsValue::Array(Rc::new(RefCell::new(s.borrow().values.clone()))),
+                                JsValue::Set(s) => JsValue::Array(Rc::new(RefCell::new(s.borrow().values().cloned().collect()))),
                                 JsValue::Map(m) => {
-                                    let entries: Vec<JsValue> = m.borrow().entries.iter()
+                                    let entries: Vec<JsValue> = m.borrow().entries()
                                         .map(|(k, v)| JsValue::Array(Rc::new(RefCell::new(vec![k.clone(), v.clone()]))))
                                         .collect();
                                     JsValue::Array(Rc::new(RefCell::new(entries)))
                                 }
                                 JsValue::Object(o) => {
-                                    // Iterable protocol: object s "Symbol.iterator" fn -> volat ji,
+                                    // Iterable protocol: object s Symbol.iterator fn -> volat ji,
                                     // pak iterator.next() loop dokud done.
-                                    let iter_fn = o.borrow().props.get("Symbol.iterator").cloned();
+                                    let iter_fn = o.borrow().props.get(symbols::ITERATOR).cloned();
                                     if let Some(f) = iter_fn {
                                         let iter = self.call_function(f, vec![], Some(src.clone()))?;
                                         let mut collected: Vec<JsValue> = Vec::new();
@@ -2394,6 +2458,9 @@ impl Interpreter {
                             return Ok(make_settled_promise("pending", JsValue::Undefined));
                         }
                         // String staticke metody
+                        ("String", "raw") => {
+                            return tagged_template::string_raw(arg_vals).map_err(JsError::Runtime);
+                        }
                         ("String", "fromCharCode") => {
                             let s: String = arg_vals.iter()
                                 .map(|v| {
@@ -2508,6 +2575,33 @@ impl Interpreter {
         let arg_vals = self.eval_args(args, env)?;
         self.call_function(func_val, arg_vals, None)
     }
+
+    /// Tagged template `` tag`a${x}b` `` = `tag(strings, x)`. Strings pole je
+    /// cachovane per call site (`tagged_template`), u `obj.tag`...`` je `this` = obj.
+    pub(super) fn eval_tagged_template(
+        &mut self,
+        tag: &Expr,
+        cooked: &[String],
+        raw: &[String],
+        expressions: &[Box<Expr>],
+        site: &TemplateSite,
+        env: &Rc<RefCell<Environment>>,
+    ) -> EvalResult {
+        let (func, this) = match tag {
+            Expr::Member { object, prop, .. } => {
+                let obj = self.eval(object, env)?;
+                let key = self.resolve_prop_key(prop, env)?;
+                (self.get_prop(&obj, &key)?, Some(obj))
+            }
+            _ => (self.eval(tag, env)?, None),
+        };
+        let mut arg_vals = Vec::with_capacity(expressions.len() + 1);
+        arg_vals.push(tagged_template::template_object(site, cooked, raw));
+        for e in expressions {
+            arg_vals.push(self.eval(e, env)?);
+        }
+        self.call_function(func, arg_vals, this)
+    }
 }
 
 /// Walk DOM subtree below root + return first element matching any selector.
@@ -2570,3 +2664,14 @@ pub(super) fn query_all(
     }
     out
 }
+
+/// Vestavene metody kolekci dostupne pres well-known symbol
+/// (`[Symbol.iterator]` pole/Setu = `values`, Mapy = `entries`).
+fn builtin_symbol_method(this: &JsValue, sym: &symbols::JsSymbol) -> Option<&'static str> {
+    if !symbols::ITERATOR.is(sym) { return None; }
+    match this {
+        JsValue::Array(_) | JsValue::Set(_) => Some("values"),
+        JsValue::Map(_) => Some("entries"),
+        _ => None,
+    }
+}
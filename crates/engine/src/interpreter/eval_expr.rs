@@ -6,38 +6,27 @@ use super::*;
 impl Interpreter {
     pub fn eval(&mut self, expr: &Expr, env: &Rc<RefCell<Environment>>) -> EvalResult {
         match expr {
-            Expr::Number(n)    => Ok(JsValue::Number(*n)),
-            Expr::BigInt(s)    => {
+            Expr::Number(n, _)    => Ok(JsValue::Number(*n)),
+            Expr::BigInt(s, _)    => {
                 let n = BigInt::from_str(s)
                     .map_err(|_| JsError::Runtime(format!("SyntaxError: invalid BigInt '{s}'")))?;
                 Ok(JsValue::BigInt(Rc::new(n)))
             }
-            Expr::DynamicImport(arg) => {
-                // Vyhodnot specifier, nacti modul, vrat Promise.fulfilled(namespace).
-                // V chybovem pripade vrat Promise.rejected(error).
-                let spec = self.eval(arg, env)?;
-                let source = spec.to_string();
-                match self.load_module(&source) {
-                    Ok(ns) => Ok(make_settled_promise("fulfilled", ns)),
-                    Err(JsError::Runtime(msg)) => {
-                        let mut err = JsObject::new();
-                        err.set("name".into(),    JsValue::Str("Error".into()));
-                        err.set("message".into(), JsValue::Str(msg));
-                        Ok(make_settled_promise("rejected",
-                            JsValue::Object(Rc::new(RefCell::new(err)))))
-                    }
-                    Err(e) => Err(e),
-                }
+            Expr::DynamicImport(arg, _) => {
+                // Promise namespace objektu; chyba nacteni / vyhodnoceni = reject.
+                let specifier = self.eval(arg, env)?.to_string();
+                let referrer = self.module_referrer(env);
+                self.import_dynamic(&specifier, &referrer)
             }
-            Expr::Str(s)       => Ok(JsValue::Str(s.clone())),
-            Expr::Bool(b)      => Ok(JsValue::Bool(*b)),
-            Expr::Null         => Ok(JsValue::Null),
-            Expr::Undefined    => Ok(JsValue::Undefined),
-            Expr::Regex(p, f)  => Ok(make_regex_object(p, f)),
+            Expr::Str(s, _)       => Ok(JsValue::Str(s.clone())),
+            Expr::Bool(b, _)      => Ok(JsValue::Bool(*b)),
+            Expr::Null(_)         => Ok(JsValue::Null),
+            Expr::Undefined(_)    => Ok(JsValue::Undefined),
+            Expr::Regex(p, f, _)  => Ok(make_regex_object(p, f)),
 
-            Expr::Ident(name)  => {
-                // 1. Standard scope lookup pres env chain.
-                if let Some(v) = env.borrow().get(name) {
+            Expr::Ident(name, _)  => {
+                // 1. Standard scope lookup pres env chain (vazba v TDZ = ReferenceError).
+                if let Some(v) = env.borrow().lookup(name).map_err(JsError::Runtime)? {
                     return Ok(v);
                 }
                 // 2. Browser-spec fallback: bare ident resolves pres global object
@@ -56,16 +45,25 @@ impl Interpreter {
                 Err(JsError::Runtime(format!("ReferenceError: '{name}' není definováno")))
             }
 
-            Expr::Template { quasis, expressions } => {
+            Expr::Template { quasis, expressions, .. } => {
                 let mut s = quasis[0].clone();
                 for (i, e) in expressions.iter().enumerate() {
-                    s.push_str(&self.eval(e, env)?.to_string());
+                    let v = self.eval(e, env)?;
+                    let v = self.coerce_to_primitive(v, "string")?;
+                    if matches!(v, JsValue::Symbol(_)) {
+                        return Err(JsError::Runtime("TypeError: Cannot convert a Symbol value to a string".into()));
+                    }
+                    s.push_str(&v.to_string());
                     if let Some(q) = quasis.get(i + 1) { s.push_str(q); }
                 }
                 Ok(JsValue::Str(s))
             }
 
-            Expr::Array(items) => {
+            Expr::TaggedTemplate { tag, cooked, raw, expressions, site, .. } => {
+                self.eval_tagged_template(tag, cooked, raw, expressions, site, env)
+            }
+
+            Expr::Array(items, _) => {
                 let mut arr = Vec::new();
                 for item in items {
                     arr.push(match item { Some(e) => self.eval(e, env)?, None => JsValue::Undefined });
@@ -73,57 +71,100 @@ impl Interpreter {
                 Ok(JsValue::Array(Rc::new(RefCell::new(arr))))
             }
 
-            Expr::Object(props) => {
+            Expr::Object(props, _) => {
                 let mut obj = JsObject::new();
                 for p in props {
                     // ES2018 object spread: `{ ...src }` - copy enumerable own
                     // props z src do current obj. Pres null/undefined ignore.
                     if matches!(p.key, PropKey::Spread) {
+                        // Gettery zdroje se zavolaji, do cile jde jejich hodnota.
                         let src = self.eval(&p.value, env)?;
-                        if let JsValue::Object(o) = src {
-                            let src_obj = o.borrow();
-                            for (k, v) in src_obj.props.iter() {
-                                obj.set(k.clone(), v.clone());
+                        if proxy_handler::is_proxy(&src) {
+                            for k in self.proxy_enumerable_keys(&src)? {
+                                let v = self.proxy_get(&src, &k, src.clone())?;
+                                obj.set(k.into(), v);
+                            }
+                        } else if let JsValue::Object(o) = &src {
+                            let entries: Vec<(PropertyKey, JsValue, Option<JsValue>)> = o.borrow().props.entries()
+                                .filter(|(_, slot)| slot.enumerable)
+                                .map(|(k, slot)| (k.clone(), slot.value.clone(), slot.getter.clone()))
+                                .collect();
+                            for (k, v, getter) in entries {
+                                let v = match getter {
+                                    Some(g) => self.call_function(g, vec![], Some(src.clone()))?,
+                                    None => v,
+                                };
+                                obj.set(k, v);
                             }
                         }
                         continue;
                     }
                     let key = match &p.key {
-                        PropKey::Ident(s) | PropKey::Str(s) => s.clone(),
-                        PropKey::Num(n) => n.to_string(),
-                        PropKey::Computed(e) => self.eval(e, env)?.to_string(),
+                        PropKey::Ident(s) | PropKey::Str(s) => s.as_str().into(),
+                        PropKey::Num(n) => n.to_string().into(),
+                        PropKey::Computed(e) => symbols::to_property_key(&self.eval(e, env)?),
                         PropKey::Spread => unreachable!(),
                     };
                     let val = self.eval(&p.value, env)?;
-                    obj.set(key, val);
+                    match p.kind {
+                        PropKind::Init => { obj.props.insert(key, val); }
+                        PropKind::Get | PropKind::Set => {
+                            let (getter, setter) = if p.kind == PropKind::Get { (Some(val), None) } else { (None, Some(val)) };
+                            obj.define_accessor(key.clone(), getter, setter);
+                            if let Some(slot) = obj.props.slot_mut(&key) { slot.enumerable = true; }
+                        }
+                    }
                 }
                 Ok(JsValue::Object(Rc::new(RefCell::new(obj))))
             }
 
-            Expr::Function { name, params, body } => Ok(JsValue::Function(JsFunc::User {
+            Expr::Function { name, params, body, .. } => Ok(JsValue::Function(JsFunc::User {
                 name: name.clone(), params: params.clone(),
-                body: FuncBody::Stmts(body.clone()), env: Rc::clone(env),
+                body: FuncBody::Stmts(body.clone()), env: self.closure_env(env),
+                is_arrow: false, id: next_func_id(),
             })),
 
             // Generator funkcni vyraz: `const gen = function*() { yield 1; }`
-            Expr::GeneratorFunc { name, params, body } => Ok(JsValue::Function(JsFunc::Generator {
+            Expr::GeneratorFunc { name, params, body, .. } => Ok(JsValue::Function(JsFunc::Generator {
+                name: name.clone(),
+                params: params.clone(),
+                body: body.clone(),
+                env: self.closure_env(env),
+                is_async: false,
+                id: next_func_id(),
+            })),
+
+            // Async generator vyraz: `const gen = async function*() { ... }`
+            Expr::AsyncGeneratorFunc { name, params, body, .. } => Ok(JsValue::Function(JsFunc::Generator {
                 name: name.clone(),
                 params: params.clone(),
                 body: body.clone(),
-                env: Rc::clone(env),
+                env: self.closure_env(env),
+                is_async: true,
+                id: next_func_id(),
             })),
 
             // Async funkcni vyraz: `const f = async function() {}` nebo `async () => {}`
-            Expr::AsyncFunc { name, params, body } => Ok(JsValue::Function(JsFunc::Async {
+            Expr::AsyncFunc { name, params, body, is_arrow, .. } => Ok(JsValue::Function(JsFunc::Async {
                 name: name.clone(),
                 params: params.clone(),
                 body: FuncBody::Stmts(body.clone()),
-                env: Rc::clone(env),
+                env: self.closure_env(env),
+                is_arrow: *is_arrow,
+                id: next_func_id(),
             })),
 
             // Await vyraz: `await promise` - synchronne rozbaluje Promise
-            Expr::Await { value } => {
+            Expr::Await { value, .. } => {
                 let val = self.eval(value, env)?;
+                // Top-level await modulu: pending promise pozastavi ramec modulu
+                if self.module_await && module_loader::is_pending_promise(&val) {
+                    return self.generator_yield(val);
+                }
+                // Top-level await REPL: event loop bezi, dokud promise neni settled
+                if self.script_await && module_loader::is_pending_promise(&val) {
+                    return self.await_settled(val);
+                }
                 // Rozbal promise pokud to je Promise
                 match unwrap_promise_result(val) {
                     Ok(v) => Ok(v),
@@ -132,70 +173,70 @@ impl Interpreter {
             }
 
             // Yield vyraz: `yield value`
-            Expr::Yield { value, delegate } => {
+            Expr::Yield { value, delegate, .. } => {
                 let val = if let Some(e) = value {
                     self.eval(e, env)?
                 } else {
                     JsValue::Undefined
                 };
                 if *delegate {
-                    // yield* - delegace na iterable (rozlozi do yield_buffer)
-                    let items = self.collect_iterable(val.clone())?;
-                    if let Some(buf) = &mut self.yield_buffer {
-                        buf.extend(items);
-                    }
-                    Ok(JsValue::Undefined)
-                } else if let Some(buf) = &mut self.yield_buffer {
-                    buf.push(val);
-                    Ok(JsValue::Undefined)
+                    // yield* - delegace next/throw/return na vnitrni iterator
+                    self.yield_delegate(val)
                 } else {
-                    Err(JsError::Runtime("yield lze pouzit jen v generator funkci".into()))
+                    // Pozastavi ramec generatoru, vysledek = hodnota z next(v)
+                    self.generator_yield(val)
                 }
             }
 
-            Expr::Arrow { params, body } => Ok(JsValue::Function(JsFunc::User {
+            Expr::Arrow { params, body, .. } => Ok(JsValue::Function(JsFunc::User {
                 name: None, params: params.clone(),
                 body: match body {
                     ArrowBody::Block(b) => FuncBody::Stmts(b.clone()),
                     ArrowBody::Expr(e)  => FuncBody::Expr(e.clone()),
                 },
-                env: Rc::clone(env),
+                env: self.closure_env(env),
+                is_arrow: true,
+                id: next_func_id(),
             })),
 
-            Expr::Unary  { op, arg }          => self.eval_unary(op, arg, env),
-            Expr::Binary { op, left, right }   => self.eval_binary(op, left, right, env),
-            Expr::Logical { op, left, right }  => self.eval_logical(op, left, right, env),
+            Expr::Unary  { op, arg, .. }          => self.eval_unary(op, arg, env),
+            Expr::Binary { op, left, right, .. }   => self.eval_binary(op, left, right, env),
+            Expr::PrivateIn { name, object, .. } => {
+                let key = self.resolve_private_key(name, env)?;
+                let obj = self.eval(object, env)?;
+                Ok(JsValue::Bool(private_names::has(&obj, &key).map_err(JsError::Runtime)?))
+            }
+            Expr::Logical { op, left, right, .. }  => self.eval_logical(op, left, right, env),
 
-            Expr::Ternary { test, yes, no } => {
+            Expr::Ternary { test, yes, no, .. } => {
                 if self.eval(test, env)?.is_truthy() { self.eval(yes, env) } else { self.eval(no, env) }
             }
 
-            Expr::Assign { op, target, value } => self.eval_assign(op, target, value, env),
+            Expr::Assign { op, target, value, .. } => self.eval_assign(op, target, value, env),
 
-            Expr::Call   { callee, args, optional }     => self.eval_call(callee, args, *optional, env),
+            Expr::Call   { callee, args, optional, span } => {
+                self.at_span(*span, |it| it.eval_call(callee, args, *optional, env))
+            }
 
-            Expr::New { callee, args } => {
-                let func = self.eval(callee, env)?;
+            Expr::New { callee, args, span } => self.at_span(*span, |it| {
+                let func = it.eval(callee, env)?;
                 let mut arg_vals = Vec::new();
-                for a in args { arg_vals.push(self.eval(a, env)?); }
-                self.call_new(func, arg_vals)
-            }
+                for a in args { arg_vals.push(it.eval(a, env)?); }
+                it.call_new(func, arg_vals)
+            }),
 
-            Expr::Member { object, prop, optional }     => self.eval_member(object, prop, *optional, env),
+            Expr::Member { object, prop, optional, .. }     => self.eval_member(object, prop, *optional, env),
 
-            Expr::Spread(e)   => self.eval(e, env),
+            Expr::Spread(e, _)   => self.eval(e, env),
 
-            Expr::Sequence(exprs) => {
+            Expr::Sequence(exprs, _) => {
                 let mut last = JsValue::Undefined;
                 for e in exprs { last = self.eval(e, env)?; }
                 Ok(last)
             }
 
-            Expr::ClassExpr { name, super_class, body } => {
-                let super_val = if let Some(sc) = super_class {
-                    Some(Box::new(self.eval(sc, env)?))
-                } else { None };
-                Ok(self.make_class_func(name.clone(), super_val, body, env))
+            Expr::ClassExpr { name, decorators, super_class, body, .. } => {
+                self.eval_class(name.clone(), decorators, super_class.as_deref(), body, env)
             }
         }
     }
@@ -203,10 +244,11 @@ impl Interpreter {
     pub(super) fn eval_unary(&mut self, op: &UnaryOp, arg: &Expr, env: &Rc<RefCell<Environment>>) -> EvalResult {
         match op {
             UnaryOp::Typeof => {
-                let t = if let Expr::Ident(name) = arg {
+                let t = if let Expr::Ident(name, _) = arg {
                     // Standard env lookup, pres miss fallback na window object
                     // (browser-spec: bare ident resolves pres global props).
-                    let val = env.borrow().get(name).unwrap_or_else(|| {
+                    let found = env.borrow().lookup(name).map_err(JsError::Runtime)?;
+                    let val = found.unwrap_or_else(|| {
                         self.global.borrow().get("window")
                             .and_then(|w| if let JsValue::Object(o) = w {
                                 let v = o.borrow().get(name);
@@ -249,7 +291,16 @@ impl Interpreter {
                 if let Expr::Member { object, prop, .. } = arg {
                     let obj = self.eval(object, env)?;
                     let key = self.resolve_prop_key(prop, env)?;
-                    if let JsValue::Object(o) = &obj { o.borrow_mut().props.remove(&key); }
+                    if proxy_handler::is_proxy(&obj) {
+                        return Ok(JsValue::Bool(self.proxy_delete(&obj, &key)?));
+                    }
+                    if let JsValue::Object(o) = &obj {
+                        let deleted = o.borrow_mut().delete(&key);
+                        if !deleted && self.is_strict(env) {
+                            return Err(JsError::Runtime(strict_mode::delete_error(&key)));
+                        }
+                        return Ok(JsValue::Bool(deleted));
+                    }
                 }
                 Ok(JsValue::Bool(true))
             }
@@ -281,129 +332,30 @@ impl Interpreter {
         let l = self.eval(left, env)?;
         let r = self.eval(right, env)?;
 
-        // BigInt aritmetika: pokud aspon jeden operand je BigInt a ZADNY neni BigNumber,
-        // proved operaci v BigInt presnosti. BigInt+BigNumber spada do BigNumber vetve nize.
-        let has_bigint    = matches!(&l, JsValue::BigInt(_)) || matches!(&r, JsValue::BigInt(_));
-        let has_bignumber = matches!(&l, JsValue::BigNumber(_)) || matches!(&r, JsValue::BigNumber(_));
-        if has_bigint && !has_bignumber {
-            if let (Some(la), Some(ra)) = (l.to_bigint(), r.to_bigint()) {
-                match op {
-                    BinaryOp::Add  => return Ok(JsValue::BigInt(Rc::new(la + ra))),
-                    BinaryOp::Sub  => return Ok(JsValue::BigInt(Rc::new(la - ra))),
-                    BinaryOp::Mul  => return Ok(JsValue::BigInt(Rc::new(la * ra))),
-                    BinaryOp::Div  => {
-                        if NumZero::is_zero(&ra) { return Err(JsError::Runtime("RangeError: deleni nulou v BigInt".into())); }
-                        return Ok(JsValue::BigInt(Rc::new(la / ra)));
-                    }
-                    BinaryOp::Mod  => {
-                        if NumZero::is_zero(&ra) { return Err(JsError::Runtime("RangeError: modulo nulou v BigInt".into())); }
-                        return Ok(JsValue::BigInt(Rc::new(la % ra)));
-                    }
-                    BinaryOp::Exp  => {
-                        // BigInt umocneni: exponent musi byt nezaporny
-                        let exp = ra.to_u32().unwrap_or(0);
-                        return Ok(JsValue::BigInt(Rc::new(la.pow(exp))));
-                    }
-                    BinaryOp::Lt   => return Ok(JsValue::Bool(la < ra)),
-                    BinaryOp::Gt   => return Ok(JsValue::Bool(la > ra)),
-                    BinaryOp::LtEq => return Ok(JsValue::Bool(la <= ra)),
-                    BinaryOp::GtEq => return Ok(JsValue::Bool(la >= ra)),
-                    BinaryOp::StrictEq    => {
-                        // Strict eq vyzaduje stejny typ - BigInt vs Number neni striktne stejne
-                        let same_type = matches!(&l, JsValue::BigInt(_)) && matches!(&r, JsValue::BigInt(_));
-                        return Ok(JsValue::Bool(same_type && la == ra));
-                    }
-                    BinaryOp::StrictNotEq => {
-                        let same_type = matches!(&l, JsValue::BigInt(_)) && matches!(&r, JsValue::BigInt(_));
-                        return Ok(JsValue::Bool(!(same_type && la == ra)));
-                    }
-                    BinaryOp::Eq    => return Ok(JsValue::Bool(la == ra)),
-                    BinaryOp::NotEq => return Ok(JsValue::Bool(la != ra)),
-                    BinaryOp::BitAnd => return Ok(JsValue::BigInt(Rc::new(la & ra))),
-                    BinaryOp::BitOr  => return Ok(JsValue::BigInt(Rc::new(la | ra))),
-                    BinaryOp::BitXor => return Ok(JsValue::BigInt(Rc::new(la ^ ra))),
-                    BinaryOp::Shl  => {
-                        let shift = ra.to_i64().unwrap_or(0);
-                        if shift >= 0 {
-                            return Ok(JsValue::BigInt(Rc::new(la << shift as u32)));
-                        } else {
-                            return Ok(JsValue::BigInt(Rc::new(la >> (-shift) as u32)));
-                        }
-                    }
-                    BinaryOp::Shr => {
-                        let shift = ra.to_i64().unwrap_or(0);
-                        if shift >= 0 {
-                            return Ok(JsValue::BigInt(Rc::new(la >> shift as u32)));
-                        } else {
-                            return Ok(JsValue::BigInt(Rc::new(la << (-shift) as u32)));
-                        }
-                    }
-                    _ => {} // Ostatni - pust dal
-                }
-            }
+        // `x instanceof C` s vlastnim C[Symbol.hasInstance] ma prednost pred retezcem trid
+        if *op == BinaryOp::Instanceof && let Some(b) = self.call_has_instance(&l, &r)? {
+            return Ok(JsValue::Bool(b));
         }
 
-        // BigNumber aritmetika: pokud aspon jeden operand je BigNumber,
-        // preved oba na BigDecimal a proved operaci
-        if matches!((&l, &r), (JsValue::BigNumber(_), _) | (_, JsValue::BigNumber(_))) {
-            if let (Some(la), Some(ra)) = (l.to_bigdecimal(), r.to_bigdecimal()) {
-                match op {
-                    BinaryOp::Add  => return Ok(JsValue::BigNumber(Rc::new(la + ra))),
-                    BinaryOp::Sub  => return Ok(JsValue::BigNumber(Rc::new(la - ra))),
-                    BinaryOp::Mul  => return Ok(JsValue::BigNumber(Rc::new(la * ra))),
-                    BinaryOp::Div  => {
-                        if ra.is_zero() { return Ok(JsValue::Number(f64::NAN)); }
-                        return Ok(JsValue::BigNumber(Rc::new(la / ra)));
-                    }
-                    BinaryOp::Mod  => {
-                        if ra.is_zero() { return Ok(JsValue::Number(f64::NAN)); }
-                        return Ok(JsValue::BigNumber(Rc::new(la % ra)));
-                    }
-                    BinaryOp::Exp  => {
-                        let exp = ra.to_u64().unwrap_or(0);
-                        return Ok(JsValue::BigNumber(Rc::new(bigdecimal_pow(la, exp))));
-                    }
-                    BinaryOp::Lt   => return Ok(JsValue::Bool(la < ra)),
-                    BinaryOp::Gt   => return Ok(JsValue::Bool(la > ra)),
-                    BinaryOp::LtEq => return Ok(JsValue::Bool(la <= ra)),
-                    BinaryOp::GtEq => return Ok(JsValue::Bool(la >= ra)),
-                    BinaryOp::StrictEq    => return Ok(JsValue::Bool(la == ra)),
-                    BinaryOp::StrictNotEq => return Ok(JsValue::Bool(la != ra)),
-                    BinaryOp::Eq    => return Ok(JsValue::Bool(la == ra)),
-                    BinaryOp::NotEq => return Ok(JsValue::Bool(la != ra)),
-                    _ => {} // Ostatni operace - pust dal jako cislo
-                }
-            }
+        // Aritmetika a relacni porovnani: objekty pres Symbol.toPrimitive, symboly hazi TypeError
+        let numeric = !matches!(op, BinaryOp::Eq | BinaryOp::NotEq | BinaryOp::StrictEq
+            | BinaryOp::StrictNotEq | BinaryOp::In | BinaryOp::Instanceof);
+        let (l, r) = if numeric {
+            let hint = if *op == BinaryOp::Add { "default" } else { "number" };
+            (self.coerce_to_primitive(l, hint)?, self.coerce_to_primitive(r, hint)?)
+        } else { (l, r) };
+        if numeric && (matches!(l, JsValue::Symbol(_)) || matches!(r, JsValue::Symbol(_))) {
+            let to = if *op == BinaryOp::Add && (matches!(l, JsValue::Str(_)) || matches!(r, JsValue::Str(_))) {
+                "string"
+            } else { "number" };
+            return Err(JsError::Runtime(format!("TypeError: Cannot convert a Symbol value to a {to}")));
         }
 
-        Ok(match op {
-            BinaryOp::Add => match (&l, &r) {
-                (JsValue::Str(a), _) => JsValue::Str(format!("{a}{r}")),
-                (_, JsValue::Str(b)) => JsValue::Str(format!("{l}{b}")),
-                _ => JsValue::Number(l.to_number() + r.to_number()),
-            },
-            BinaryOp::Sub  => JsValue::Number(l.to_number() - r.to_number()),
-            BinaryOp::Mul  => JsValue::Number(l.to_number() * r.to_number()),
-            BinaryOp::Div  => JsValue::Number(l.to_number() / r.to_number()),
-            BinaryOp::Mod  => JsValue::Number(l.to_number() % r.to_number()),
-            BinaryOp::Exp  => JsValue::Number(l.to_number().powf(r.to_number())),
-            BinaryOp::Eq        => JsValue::Bool(l.loose_eq(&r)),
-            BinaryOp::NotEq     => JsValue::Bool(!l.loose_eq(&r)),
-            BinaryOp::StrictEq  => JsValue::Bool(l.strict_eq(&r)),
-            BinaryOp::StrictNotEq => JsValue::Bool(!l.strict_eq(&r)),
-            BinaryOp::Lt   => JsValue::Bool(l.to_number() < r.to_number()),
-            BinaryOp::Gt   => JsValue::Bool(l.to_number() > r.to_number()),
-            BinaryOp::LtEq => JsValue::Bool(l.to_number() <= r.to_number()),
-            BinaryOp::GtEq => JsValue::Bool(l.to_number() >= r.to_number()),
-            BinaryOp::BitAnd => JsValue::Number((l.to_number() as i32 & r.to_number() as i32) as f64),
-            BinaryOp::BitOr  => JsValue::Number((l.to_number() as i32 | r.to_number() as i32) as f64),
-            BinaryOp::BitXor => JsValue::Number((l.to_number() as i32 ^ r.to_number() as i32) as f64),
-            BinaryOp::Shl    => JsValue::Number(((l.to_number() as i32) << (r.to_number() as u32 & 31)) as f64),
-            BinaryOp::Shr    => JsValue::Number(((l.to_number() as i32) >> (r.to_number() as u32 & 31)) as f64),
-            BinaryOp::Ushr   => JsValue::Number(((l.to_number() as u32) >> (r.to_number() as u32 & 31)) as f64),
+        match op {
             BinaryOp::In => {
-                let key = l.to_string();
+                let key = symbols::to_property_key(&l);
                 let found = match &r {
+                    p if proxy_handler::is_proxy(p) => self.proxy_has(p, &key)?,
                     JsValue::Object(o) => {
                         // Prochazi prototypovym retezcem (max 100 uroven)
                         let mut current: Option<Rc<RefCell<JsObject>>> = Some(Rc::clone(o));
@@ -419,56 +371,11 @@ impl Interpreter {
                     }
                     _ => false,
                 };
-                JsValue::Bool(found)
+                Ok(JsValue::Bool(found))
             }
-            BinaryOp::Instanceof => {
-                // Ziskej jmeno tridy z praveho operandu
-                let class_name = match &r {
-                    JsValue::Function(JsFunc::Class { name, .. }) => {
-                        name.as_deref().unwrap_or("").to_string()
-                    }
-                    JsValue::Function(JsFunc::Native(name, _)) => name.clone(),
-                    _ => return Ok(JsValue::Bool(false)),
-                };
-                if class_name.is_empty() { return Ok(JsValue::Bool(false)); }
-                // Zkontroluj retezec trid ulozeny na instanci (pro tridy)
-                // nebo typ intern. vlastnosti (pro vestavene typy)
-                match &l {
-                    JsValue::Object(o) => {
-                        let obj = o.borrow();
-                        // Tridy: __class_chain__
-                        if let Some(JsValue::Str(chain)) = obj.props.get("__class_chain__") {
-                            if chain.split(',').any(|n| n == class_name) {
-                                return Ok(JsValue::Bool(true));
-                            }
-                        }
-                        // Vestavene typy podle vnitrnich klicu
-                        let result = match class_name.as_str() {
-                            "Error" | "TypeError" | "RangeError" | "SyntaxError"
-                            | "ReferenceError" | "URIError" | "EvalError" => {
-                                // Error instance ma property "name"
-                                if let Some(JsValue::Str(name)) = obj.props.get("name") {
-                                    class_name == "Error"
-                                        || name == &class_name
-                                        || name.ends_with("Error")
-                                } else { false }
-                            }
-                            "Date"    => obj.props.contains_key("__date_ms__"),
-                            "RegExp"  => obj.props.contains_key("__regex_pattern__"),
-                            "Promise" => obj.props.contains_key("__promise_state__"),
-                            _ => false,
-                        };
-                        JsValue::Bool(result)
-                    }
-                    JsValue::Map(_) => JsValue::Bool(class_name == "Map"),
-                    JsValue::Set(_) => JsValue::Bool(class_name == "Set"),
-                    JsValue::Array(_) => JsValue::Bool(class_name == "Array"),
-                    JsValue::Function(_) => JsValue::Bool(class_name == "Function"),
-                    _ => JsValue::Bool(false),
-                }
-            }
-            BinaryOp::PostInc | BinaryOp::PostDec => unreachable!(),
-        })
+            BinaryOp::Instanceof => Ok(JsValue::Bool(instanceof_builtin(&l, &r))),
+            _ => binary_primitive(op, &l, &r).map_err(JsError::Runtime),
+        }
     }
 
     pub(super) fn eval_logical(&mut self, op: &LogicalOp, left: &Expr, right: &Expr, env: &Rc<RefCell<Environment>>) -> EvalResult {
@@ -537,15 +444,35 @@ impl Interpreter {
 
     pub(super) fn assign_to(&mut self, target: &Expr, val: JsValue, env: &Rc<RefCell<Environment>>) -> Result<(), JsError> {
         match target {
-            Expr::Ident(name) => {
-                if !env.borrow_mut().set(name, val.clone()) {
-                    env.borrow_mut().define(name, val);
+            Expr::Ident(name, _) => {
+                let found = env.borrow_mut().assign(name, val.clone()).map_err(JsError::Runtime)?;
+                if !found {
+                    // Nedeklarovana promenna: strict = ReferenceError, sloppy = nova globalni
+                    if self.is_strict(env) {
+                        return Err(JsError::Runtime(strict_mode::undeclared_error(name)));
+                    }
+                    self.global.borrow_mut().define(name, val);
                 }
                 Ok(())
             }
             Expr::Member { object, prop, .. } => {
                 let obj = self.eval(object, env)?;
-                let key = self.resolve_prop_key(prop, env)?;
+                let key = match self.resolve_prop_key(prop, env)? {
+                    PropertyKey::Str(key) => key,
+                    key => {
+                        if self.is_strict(env) {
+                            strict_mode::check_write(&obj, &key).map_err(JsError::Runtime)?;
+                        }
+                        return self.set_symbol_prop(&obj, key, val);
+                    }
+                };
+                if let MemberProp::Private(_) = prop {
+                    return self.set_private(&obj, &key, val);
+                }
+                // Strict kod: zapis, ktery by sloppy tise zahodil, je TypeError
+                if self.is_strict(env) {
+                    strict_mode::check_write(&obj, &key).map_err(JsError::Runtime)?;
+                }
                 match &obj {
                     JsValue::DomNode(n) => {
                         // DOM property setters
@@ -662,27 +589,18 @@ impl Interpreter {
                             if let Some(state) = self.workers.borrow_mut().get_mut(&id) {
                                 state.on_message = Some(val.clone());
                             }
-                            o.borrow_mut().props.insert(key, val);
+                            o.borrow_mut().props.insert(key.into(), val);
                             return Ok(());
                         }
-                        // Proxy trap 'set': handler.set(target, key, value, receiver)
-                        let has_handler = o.borrow().props.contains_key("__proxy_handler__");
-                        if has_handler && !key.starts_with("__") {
-                            let handler = o.borrow().props.get("__proxy_handler__").cloned()
-                                .unwrap_or(JsValue::Undefined);
-                            let target = o.borrow().props.get("__proxy_target__").cloned()
-                                .unwrap_or(JsValue::Undefined);
-                            if let JsValue::Object(h) = &handler {
-                                let trap = h.borrow().props.get("set").cloned();
-                                if let Some(trap_fn) = trap {
-                                    self.call_function(
-                                        trap_fn,
-                                        vec![target, JsValue::Str(key.clone()), val.clone(), obj.clone()],
-                                        None,
-                                    )?;
-                                    return Ok(());
-                                }
-                            }
+                        // Proxy: [[Set]] pres handler.set(target, key, value, receiver)
+                        if proxy_handler::is_proxy(&obj) && !is_internal_key(&key) {
+                            self.proxy_set(&obj, key, val, obj.clone())?;
+                            return Ok(());
+                        }
+                        // TypedArray / ArrayBuffer: indexy a delky zapisuje primo do bajtu
+                        let binary = o.borrow().binary.clone();
+                        if let Some(result) = binary.and_then(|b| b.set(&key, &val)) {
+                            return result.map_err(JsError::Runtime);
                         }
                         // Specialni klic __proto__: prirazeni meni prototyp
                         if key == "__proto__" {
@@ -693,15 +611,16 @@ impl Interpreter {
                             }
                             return Ok(());
                         }
-                        // Setter podpora: kdyz objekt ma `__set_key__`, zavolej setter
-                        let setter_key = format!("__set_{key}__");
-                        let setter_fn = o.borrow().props.get(&setter_key).cloned();
-                        if let Some(setter) = setter_fn {
-                            self.call_function(setter, vec![val], Some(obj.clone()))?;
+                        // Accessor vlastnost (i zdedena): zavolej setter, bez setteru se zapis ignoruje
+                        let accessor = o.borrow().find_accessor(&key);
+                        if let Some((_, setter)) = accessor {
+                            if let Some(setter) = setter {
+                                self.call_function(setter, vec![val], Some(obj.clone()))?;
+                            }
                             return Ok(());
                         }
-                        // Frozen objekt: zmeny se tisnich ignoruji (soulad s JS non-strict)
-                        if o.borrow().frozen { return Ok(()); }
+                        // Non-writable vlastnost / neroztazitelny objekt: zapis se tise ignoruje (non-strict)
+                        if !o.borrow().can_put(&key) { return Ok(()); }
                         // DOMTokenList.value setter - prepise cely class attr
                         // (per spec). Drzeny pres __token_list_node__ Rc<Node>.
                         if key == "value"
@@ -746,10 +665,12 @@ impl Interpreter {
                                 }
                             }
                         }
-                        o.borrow_mut().props.insert(key, val);
+                        o.borrow_mut().props.insert(key.into(), val);
                         Ok(())
                     }
                     JsValue::Array(a) => {
+                        // Frozen strings pole tagged template - zapis se tise ignoruje
+                        if tagged_template::is_frozen_array(a) { return Ok(()); }
                         if key == "length" {
                             let new_len = val.to_number() as usize;
                             let mut arr = a.borrow_mut();
@@ -762,6 +683,16 @@ impl Interpreter {
                         }
                         Ok(())
                     }
+                    // Foo.x = v: staticky setter (i zdedeny), jinak vlastnost tridy
+                    JsValue::Function(JsFunc::Class { static_props, .. }) => {
+                        let accessor = static_props.borrow().find_accessor(&key);
+                        match accessor {
+                            Some((_, Some(setter))) => { self.call_function(setter, vec![val], Some(obj.clone()))?; }
+                            Some((_, None)) => {}
+                            None => static_props.borrow_mut().set(key.into(), val),
+                        }
+                        Ok(())
+                    }
                     _ => Err(JsError::Runtime(format!("Nelze priradit do vlastnosti '{key}'")))
                 }
             }
@@ -769,10 +700,12 @@ impl Interpreter {
             // `[a, b] = [x, y]`, `[lastX, lastY] = [e.offsetX, e.offsetY]`,
             // `({a, b} = obj)`. Drive spadlo do catch-all Error a tise se
             // spolklo -> canvas freehand lastX/lastY zustaly 0 = "cary z rohu".
-            Expr::Array(elems) => {
+            Expr::Array(elems, _) => {
                 let items: Vec<JsValue> = match &val {
                     JsValue::Array(a) => a.borrow().clone(),
                     JsValue::Str(s) => s.chars().map(|c| JsValue::Str(c.to_string())).collect(),
+                    JsValue::Map(_) | JsValue::Set(_) => self.collect_iterable(val.clone())?,
+                    JsValue::Object(_) => self.take_from_iterable(val.clone(), elems.len())?,
                     _ => vec![],
                 };
                 for (i, elem) in elems.iter().enumerate() {
@@ -783,12 +716,12 @@ impl Interpreter {
                 }
                 Ok(())
             }
-            Expr::Object(props) => {
+            Expr::Object(props, _) => {
                 for p in props {
                     let key = match &p.key {
-                        crate::ast::PropKey::Ident(s) | crate::ast::PropKey::Str(s) => s.clone(),
-                        crate::ast::PropKey::Num(n) => n.to_string(),
-                        crate::ast::PropKey::Computed(e) => self.eval(e, env)?.to_string(),
+                        crate::ast::PropKey::Ident(s) | crate::ast::PropKey::Str(s) => s.as_str().into(),
+                        crate::ast::PropKey::Num(n) => n.to_string().into(),
+                        crate::ast::PropKey::Computed(e) => symbols::to_property_key(&self.eval(e, env)?),
                         crate::ast::PropKey::Spread => continue,
                     };
                     let item = self.get_prop(&val, &key)?;
@@ -800,6 +733,23 @@ impl Interpreter {
         }
     }
 
+    /// `o[sym] = v` - symbolove vlastnosti maji jen objekty (vcetne proxy) a tridy.
+    fn set_symbol_prop(&mut self, obj: &JsValue, key: PropertyKey, val: JsValue) -> Result<(), JsError> {
+        match obj {
+            JsValue::Function(JsFunc::Class { static_props, .. }) => {
+                let accessor = static_props.borrow().find_accessor(&key);
+                match accessor {
+                    Some((_, Some(setter))) => { self.call_function(setter, vec![val], Some(obj.clone()))?; }
+                    Some((_, None)) => {}
+                    None => static_props.borrow_mut().set(key, val),
+                }
+            }
+            JsValue::Object(_) => { self.set_with_receiver(obj, key, val, obj.clone())?; }
+            _ => {}
+        }
+        Ok(())
+    }
+
     // ─── Destrukturovani ──────────────────────────────────────────────────────
 
     /// Binduje hodnotu `val` do promenne/promennych definovanych vzorem `pattern`.
@@ -822,6 +772,12 @@ impl Interpreter {
                     JsValue::Array(a) => a.borrow().clone(),
                     // retezec lze destrukturovat jako pole znaku
                     JsValue::Str(s) => s.chars().map(|c| JsValue::Str(c.to_string())).collect(),
+                    JsValue::Map(_) | JsValue::Set(_) => self.collect_iterable(val.clone())?,
+                    // Iterator / generator: vytahni jen tolik hodnot, kolik vzor potrebuje
+                    JsValue::Object(_) => {
+                        let need = if elems.iter().any(|e| e.rest) { usize::MAX } else { elems.len() };
+                        self.take_from_iterable(val.clone(), need)?
+                    }
                     _ => vec![],
                 };
                 let mut i = 0usize;
@@ -852,16 +808,27 @@ impl Interpreter {
             }
 
             Pattern::Object(props) => {
-                // Klice ktere uz byly spotrebovany (pro ...rest - zatim neni implementovan)
+                // Klice ktere uz byly spotrebovany (`...rest` je vynecha)
+                let mut used: Vec<PropertyKey> = Vec::new();
                 for prop in props {
                     let key = match &prop.key {
-                        PropKey::Ident(s) | PropKey::Str(s) => s.clone(),
-                        PropKey::Num(n) => format!("{}", *n as i64),
-                        PropKey::Computed(e) => self.eval(e, env)?.to_string(),
-                        // PropKey::Spread v destrukturalizaci = `{...rest}` rest pattern.
-                        // Aktualne ne-implementovany - skip prop (= bind nic).
-                        PropKey::Spread => continue,
+                        PropKey::Ident(s) | PropKey::Str(s) => s.as_str().into(),
+                        PropKey::Num(n) => format!("{}", *n as i64).into(),
+                        PropKey::Computed(e) => symbols::to_property_key(&self.eval(e, env)?),
+                        // `{ a, ...rest }` - zbyle vlastni enumerable vlastnosti
+                        PropKey::Spread => {
+                            let mut rest = JsObject::new();
+                            if let JsValue::Object(o) = &val {
+                                let o = o.borrow();
+                                for k in o.own_keys().into_iter().filter(|k| !used.iter().any(|u| u == k.as_str())) {
+                                    rest.set(k.clone().into(), o.get(&k));
+                                }
+                            }
+                            self.destructure_bind(&prop.pattern, JsValue::Object(Rc::new(RefCell::new(rest))), env)?;
+                            continue;
+                        }
                     };
+                    used.push(key.clone());
                     let item = match &val {
                         JsValue::Object(o) => o.borrow().get(&key),
                         _ => JsValue::Undefined,
@@ -889,14 +856,21 @@ impl Interpreter {
     /// - `Expr::Object` - object destrukturovani `{ x, y }`
     pub(super) fn bind_target_expr(&mut self, target: &Expr, val: JsValue, env: &Rc<RefCell<Environment>>) -> Result<(), JsError> {
         match target {
-            Expr::Ident(name) => {
+            Expr::Ident(name, _) => {
                 env.borrow_mut().define(name, val);
                 Ok(())
             }
-            Expr::Array(items) => {
+            Expr::Array(items, _) => {
                 let vals: Vec<JsValue> = match &val {
                     JsValue::Array(a) => a.borrow().clone(),
                     JsValue::Str(s) => s.chars().map(|c| JsValue::Str(c.to_string())).collect(),
+                    JsValue::Map(_) | JsValue::Set(_) => self.collect_iterable(val.clone())?,
+                    JsValue::Object(_) => {
+                        let need = if items.iter().flatten().any(|e| matches!(e.as_ref(), Expr::Spread(_, _))) {
+                            usize::MAX
+                        } else { items.len() };
+                        self.take_from_iterable(val.clone(), need)?
+                    }
                     _ => vec![],
                 };
                 let mut i = 0usize;
@@ -907,7 +881,7 @@ impl Interpreter {
                         continue;
                     };
                     // rest element je ulozen jako Spread(inner)
-                    if let Expr::Spread(inner) = expr.as_ref() {
+                    if let Expr::Spread(inner, _) = expr.as_ref() {
                         let rest = JsValue::Array(Rc::new(RefCell::new(
                             vals.get(i..).unwrap_or(&[]).to_vec()
                         )));
@@ -920,14 +894,14 @@ impl Interpreter {
                 }
                 Ok(())
             }
-            Expr::Object(props) => {
+            Expr::Object(props, _) => {
                 for prop in props {
                     let key = match &prop.key {
-                        PropKey::Ident(s) | PropKey::Str(s) => s.clone(),
-                        PropKey::Num(n) => format!("{}", *n as i64),
+                        PropKey::Ident(s) | PropKey::Str(s) => s.as_str().into(),
+                        PropKey::Num(n) => format!("{}", *n as i64).into(),
                         PropKey::Computed(e) => {
                             let e = e.as_ref().clone();
-                            self.eval(&e, env)?.to_string()
+                            symbols::to_property_key(&self.eval(&e, env)?)
                         }
                         // PropKey::Spread v destructuring assign = ...rest target.
                         // Aktualne ne-implementovany - skip.
@@ -949,3 +923,182 @@ impl Interpreter {
     }
 
 }
+
+/// `instanceof` pro vestavene konstruktory a tridy tree-walkeru (`__class_chain__`,
+/// interni klice Date/RegExp/Promise, Error rodina). Sdileno s bytecode VM.
+pub(crate) fn instanceof_builtin(l: &JsValue, r: &JsValue) -> bool {
+    // Ziskej jmeno tridy z praveho operandu
+    // Proxy instance: retezec trid hleda na targetu
+    let l = proxy_handler::innermost_target(l);
+    let class_name = match r {
+        JsValue::Function(JsFunc::Class { name, .. }) => {
+            name.as_deref().unwrap_or("").to_string()
+        }
+        JsValue::Function(JsFunc::Native(name, _)) => name.clone(),
+        _ => return false,
+    };
+    if class_name.is_empty() { return false; }
+    // Zkontroluj retezec trid ulozeny na instanci (pro tridy)
+    // nebo typ intern. vlastnosti (pro vestavene typy)
+    match &l {
+        JsValue::Object(o) => {
+            let obj = o.borrow();
+            // Tridy: __class_chain__
+            if let Some(JsValue::Str(chain)) = obj.props.get("__class_chain__")
+                && chain.split(',').any(|n| n == class_name)
+            {
+                return true;
+            }
+            // Vestavene typy podle vnitrnich klicu
+            match class_name.as_str() {
+                "Error" | "TypeError" | "RangeError" | "SyntaxError"
+                | "ReferenceError" | "URIError" | "EvalError" | "SuppressedError" => {
+                    // Error instance ma property "name"
+                    if let Some(JsValue::Str(name)) = obj.props.get("name") {
+                        class_name == "Error"
+                            || name == &class_name
+                            || name.ends_with("Error")
+                    } else { false }
+                }
+                "Date"    => obj.props.contains_key("__date_ms__"),
+                "RegExp"  => obj.props.contains_key("__regex_pattern__"),
+                "Promise" => obj.props.contains_key("__promise_state__"),
+                _ => obj.binary.as_ref().is_some_and(|b| b.class_name() == class_name),
+            }
+        }
+        JsValue::Map(m) => class_name == if m.borrow().weak { "WeakMap" } else { "Map" },
+        JsValue::Set(s) => class_name == if s.borrow().weak { "WeakSet" } else { "Set" },
+        JsValue::Array(_) => class_name == "Array",
+        JsValue::Function(_) => class_name == "Function",
+        _ => false,
+    }
+}
+
+/// Binarni operator nad primitivnimi operandy (po ToPrimitive): BigInt, BigNumber
+/// a cislo/retezec vetve. `in`/`instanceof`/postfix operatory resi volajici.
+/// Sdileno s bytecode VM.
+pub(crate) fn binary_primitive(op: &BinaryOp, l: &JsValue, r: &JsValue) -> Result<JsValue, String> {
+    // BigInt aritmetika: pokud aspon jeden operand je BigInt a ZADNY neni BigNumber,
+    // proved operaci v BigInt presnosti. BigInt+BigNumber spada do BigNumber vetve nize.
+    let has_bigint    = matches!(l, JsValue::BigInt(_)) || matches!(r, JsValue::BigInt(_));
+    let has_bignumber = matches!(l, JsValue::BigNumber(_)) || matches!(r, JsValue::BigNumber(_));
+    if has_bigint && !has_bignumber
+        && let (Some(la), Some(ra)) = (l.to_bigint(), r.to_bigint())
+    {
+        match op {
+            BinaryOp::Add  => return Ok(JsValue::BigInt(Rc::new(la + ra))),
+            BinaryOp::Sub  => return Ok(JsValue::BigInt(Rc::new(la - ra))),
+            BinaryOp::Mul  => return Ok(JsValue::BigInt(Rc::new(la * ra))),
+            BinaryOp::Div  => {
+                if NumZero::is_zero(&ra) { return Err("RangeError: deleni nulou v BigInt".to_string()); }
+                return Ok(JsValue::BigInt(Rc::new(la / ra)));
+            }
+            BinaryOp::Mod  => {
+                if NumZero::is_zero(&ra) { return Err("RangeError: modulo nulou v BigInt".to_string()); }
+                return Ok(JsValue::BigInt(Rc::new(la % ra)));
+            }
+            BinaryOp::Exp  => {
+                // BigInt umocneni: exponent musi byt nezaporny
+                let exp = ra.to_u32().unwrap_or(0);
+                return Ok(JsValue::BigInt(Rc::new(la.pow(exp))));
+            }
+            BinaryOp::Lt   => return Ok(JsValue::Bool(la < ra)),
+            BinaryOp::Gt   => return Ok(JsValue::Bool(la > ra)),
+            BinaryOp::LtEq => return Ok(JsValue::Bool(la <= ra)),
+            BinaryOp::GtEq => return Ok(JsValue::Bool(la >= ra)),
+            BinaryOp::StrictEq    => {
+                // Strict eq vyzaduje stejny typ - BigInt vs Number neni striktne stejne
+                let same_type = matches!(l, JsValue::BigInt(_)) && matches!(r, JsValue::BigInt(_));
+                return Ok(JsValue::Bool(same_type && la == ra));
+            }
+            BinaryOp::StrictNotEq => {
+                let same_type = matches!(l, JsValue::BigInt(_)) && matches!(r, JsValue::BigInt(_));
+                return Ok(JsValue::Bool(!(same_type && la == ra)));
+            }
+            BinaryOp::Eq    => return Ok(JsValue::Bool(la == ra)),
+            BinaryOp::NotEq => return Ok(JsValue::Bool(la != ra)),
+            BinaryOp::BitAnd => return Ok(JsValue::BigInt(Rc::new(la & ra))),
+            BinaryOp::BitOr  => return Ok(JsValue::BigInt(Rc::new(la | ra))),
+            BinaryOp::BitXor => return Ok(JsValue::BigInt(Rc::new(la ^ ra))),
+            BinaryOp::Shl  => {
+                let shift = ra.to_i64().unwrap_or(0);
+                if shift >= 0 {
+                    return Ok(JsValue::BigInt(Rc::new(la << shift as u32)));
+                } else {
+                    return Ok(JsValue::BigInt(Rc::new(la >> (-shift) as u32)));
+                }
+            }
+            BinaryOp::Shr => {
+                let shift = ra.to_i64().unwrap_or(0);
+                if shift >= 0 {
+                    return Ok(JsValue::BigInt(Rc::new(la >> shift as u32)));
+                } else {
+                    return Ok(JsValue::BigInt(Rc::new(la << (-shift) as u32)));
+                }
+            }
+            _ => {} // Ostatni - pust dal
+        }
+    }
+
+    // BigNumber aritmetika: pokud aspon jeden operand je BigNumber,
+    // preved oba na BigDecimal a proved operaci
+    if matches!((l, r), (JsValue::BigNumber(_), _) | (_, JsValue::BigNumber(_)))
+        && let (Some(la), Some(ra)) = (l.to_bigdecimal(), r.to_bigdecimal())
+    {
+        match op {
+            BinaryOp::Add  => return Ok(JsValue::BigNumber(Rc::new(la + ra))),
+            BinaryOp::Sub  => return Ok(JsValue::BigNumber(Rc::new(la - ra))),
+            BinaryOp::Mul  => return Ok(JsValue::BigNumber(Rc::new(la * ra))),
+            BinaryOp::Div  => {
+                if ra.is_zero() { return Ok(JsValue::Number(f64::NAN)); }
+                return Ok(JsValue::BigNumber(Rc::new(la / ra)));
+            }
+            BinaryOp::Mod  => {
+                if ra.is_zero() { return Ok(JsValue::Number(f64::NAN)); }
+                return Ok(JsValue::BigNumber(Rc::new(la % ra)));
+            }
+            BinaryOp::Exp  => {
+                let exp = ra.to_u64().unwrap_or(0);
+                return Ok(JsValue::BigNumber(Rc::new(bigdecimal_pow(la, exp))));
+            }
+            BinaryOp::Lt   => return Ok(JsValue::Bool(la < ra)),
+            BinaryOp::Gt   => return Ok(JsValue::Bool(la > ra)),
+            BinaryOp::LtEq => return Ok(JsValue::Bool(la <= ra)),
+            BinaryOp::GtEq => return Ok(JsValue::Bool(la >= ra)),
+            BinaryOp::StrictEq    => return Ok(JsValue::Bool(la == ra)),
+            BinaryOp::StrictNotEq => return Ok(JsValue::Bool(la != ra)),
+            BinaryOp::Eq    => return Ok(JsValue::Bool(la == ra)),
+            BinaryOp::NotEq => return Ok(JsValue::Bool(la != ra)),
+            _ => {} // Ostatni operace - pust dal jako cislo
+        }
+    }
+
+
+    Ok(match op {
+        BinaryOp::Add => match (l, r) {
+            (JsValue::Str(a), _) => JsValue::Str(format!("{a}{r}")),
+            (_, JsValue::Str(b)) => JsValue::Str(format!("{l}{b}")),
+            _ => JsValue::Number(l.to_number() + r.to_number()),
+        },
+        BinaryOp::Sub  => JsValue::Number(l.to_number() - r.to_number()),
+        BinaryOp::Mul  => JsValue::Number(l.to_number() * r.to_number()),
+        BinaryOp::Div  => JsValue::Number(l.to_number() / r.to_number()),
+        BinaryOp::Mod  => JsValue::Number(l.to_number() % r.to_number()),
+        BinaryOp::Exp  => JsValue::Number(l.to_number().powf(r.to_number())),
+        BinaryOp::Eq        => JsValue::Bool(l.loose_eq(r)),
+        BinaryOp::NotEq     => JsValue::Bool(!l.loose_eq(r)),
+        BinaryOp::StrictEq  => JsValue::Bool(l.strict_eq(r)),
+        BinaryOp::StrictNotEq => JsValue::Bool(!l.strict_eq(r)),
+        BinaryOp::Lt   => JsValue::Bool(l.to_number() < r.to_number()),
+        BinaryOp::Gt   => JsValue::Bool(l.to_number() > r.to_number()),
+        BinaryOp::LtEq => JsValue::Bool(l.to_number() <= r.to_number()),
+        BinaryOp::GtEq => JsValue::Bool(l.to_number() >= r.to_number()),
+        BinaryOp::BitAnd => JsValue::Number((l.to_number() as i32 & r.to_number() as i32) as f64),
+        BinaryOp::BitOr  => JsValue::Number((l.to_number() as i32 | r.to_number() as i32) as f64),
+        BinaryOp::BitXor => JsValue::Number((l.to_number() as i32 ^ r.to_number() as i32) as f64),
+        BinaryOp::Shl    => JsValue::Number(((l.to_number() as i32) << (r.to_number() as u32 & 31)) as f64),
+        BinaryOp::Shr    => JsValue::Number(((l.to_number() as i32) >> (r.to_number() as u32 & 31)) as f64),
+        BinaryOp::Ushr   => JsValue::Number(((l.to_number() as u32) >> (r.to_number() as u32 & 31)) as f64),
+        BinaryOp::In | BinaryOp::Instanceof | BinaryOp::PostInc | BinaryOp::PostDec => unreachable!(),
+    })
+}
@@ -8,27 +8,22 @@ impl Interpreter {
         if optional && matches!(obj, JsValue::Null | JsValue::Undefined) {
             return Ok(JsValue::Undefined);
         }
-        let key = self.resolve_prop_key(prop, env)?;
-
-        // Proxy trap 'get': handler.get(target, key, receiver)
-        if let JsValue::Object(ref o) = obj {
-            let has_handler = o.borrow().props.contains_key("__proxy_handler__");
-            if has_handler && !key.starts_with("__") {
-                let handler = o.borrow().props.get("__proxy_handler__").cloned()
-                    .unwrap_or(JsValue::Undefined);
-                let target = o.borrow().props.get("__proxy_target__").cloned()
-                    .unwrap_or(JsValue::Undefined);
-                if let JsValue::Object(h) = &handler {
-                    let trap = h.borrow().props.get("get").cloned();
-                    if let Some(trap_fn) = trap {
-                        return self.call_function(
-                            trap_fn,
-                            vec![target, JsValue::Str(key.clone()), obj.clone()],
-                            None,
-                        );
-                    }
+        let key = match self.resolve_prop_key(prop, env)? {
+            PropertyKey::Str(key) => key,
+            PropertyKey::Sym(sym) => {
+                if proxy_handler::is_proxy(&obj) {
+                    return self.proxy_get(&obj, &sym, obj.clone());
                 }
+                return self.get_prop_accessor(&obj, &sym);
             }
+        };
+        if let MemberProp::Private(_) = prop {
+            return self.get_private(&obj, &key);
+        }
+
+        // Proxy: [[Get]] pres handler.get(target, key, receiver), jinak forward na target
+        if proxy_handler::is_proxy(&obj) && !is_internal_key(&key) {
+            return self.proxy_get(&obj, &key, obj.clone());
         }
 
         // Specialni handling pro document.activeElement - vyzaduje pristup k
@@ -102,56 +97,89 @@ impl Interpreter {
             }
         }
 
-        // Getter podpora: kdyz objekt ma `__get_key__` vlastnost (funkci), zavolej ji
-        if let JsValue::Object(ref o) = obj {
-            let getter_key = format!("__get_{key}__");
-            let getter_fn = o.borrow().props.get(&getter_key).cloned();
-            if let Some(getter) = getter_fn {
-                return self.call_function(getter, vec![], Some(obj.clone()));
+        self.get_prop_accessor(&obj, &key)
+    }
+
+    /// `get_prop` vcetne accessor vlastnosti (vlastni nebo zdedene): getter
+    /// se vola s this = obj.
+    pub(super) fn get_prop_accessor(&mut self, obj: &JsValue, key: impl AsKey) -> EvalResult {
+        if let JsValue::Object(o) | JsValue::Function(JsFunc::Class { static_props: o, .. }) = obj {
+            let accessor = o.borrow().find_accessor(&key);
+            if let Some((getter, _)) = accessor {
+                return match getter {
+                    Some(getter) => self.call_function(getter, vec![], Some(obj.clone())),
+                    None => Ok(JsValue::Undefined),
+                };
             }
         }
+        self.get_prop(obj, key)
+    }
 
-        self.get_prop(&obj, &key)
+    pub(super) fn get_prop(&self, obj: &JsValue, key: impl AsKey) -> EvalResult {
+        match key.key_ref() {
+            KeyRef::Str(key) => self.get_prop_str(obj, key),
+            KeyRef::Sym(_) => Ok(self.get_prop_sym(obj, key.key_ref())),
+        }
     }
 
-    pub(super) fn get_prop(&self, obj: &JsValue, key: &str) -> EvalResult {
+    /// Symbolem klicovana vlastnost: vlastni/zdedene vlastnosti objektu
+    /// a statiky tridy. Primitiva symbolove vlastnosti nemaji.
+    fn get_prop_sym(&self, obj: &JsValue, key: KeyRef) -> JsValue {
+        match obj {
+            JsValue::Object(o) => {
+                let proxy_target = o.borrow().props.get("__proxy_target__").cloned();
+                match proxy_target {
+                    Some(target) => self.get_prop_sym(&target, key),
+                    None => o.borrow().get(key),
+                }
+            }
+            JsValue::Function(JsFunc::Class { statics, env, super_val, static_props, .. }) => {
+                if let Some(s) = statics.iter().find(|s| s.name.key_ref() == key) {
+                    let senv = Environment::new_child(env);
+                    if let Some(sv) = super_val {
+                        senv.borrow_mut().define("__super_class__", (**sv).clone());
+                    }
+                    return s.func(&senv);
+                }
+                static_props.borrow().get(key)
+            }
+            _ => JsValue::Undefined,
+        }
+    }
+
+    fn get_prop_str(&self, obj: &JsValue, key: &str) -> EvalResult {
         match obj {
             // Staticke metody tridy: ClassName.staticMethod()
-            JsValue::Function(JsFunc::Class { statics, getters, env, super_val, .. }) => {
+            JsValue::Function(JsFunc::Class { statics, getters, env, super_val, static_props, .. }) => {
                 for s in statics {
                     if s.name == key {
                         let senv = Environment::new_child(env);
                         if let Some(sv) = super_val {
                             senv.borrow_mut().define("__super_class__", (**sv).clone());
                         }
-                        return Ok(JsValue::Function(JsFunc::User {
-                            name: Some(s.name.clone()),
-                            params: s.params.clone(),
-                            body: FuncBody::Stmts(s.body.clone()),
-                            env: Rc::clone(&senv),
-                        }));
+                        return Ok(s.func(&senv));
                     }
                 }
+                // Staticka pole a `Foo.x = ...` (vcetne zdedenych od rodicovske tridy)
+                let v = static_props.borrow().get(key);
+                if !matches!(v, JsValue::Undefined) {
+                    return Ok(v);
+                }
                 // Getters jako vlastnosti tridy (ne bezne)
                 for g in getters {
                     if g.name == key {
-                        return Ok(JsValue::Function(JsFunc::User {
-                            name: Some(g.name.clone()),
-                            params: g.params.clone(),
-                            body: FuncBody::Stmts(g.body.clone()),
-                            env: Rc::clone(env),
-                        }));
+                        return Ok(g.func(env));
                     }
                 }
                 Ok(JsValue::Undefined)
             }
             JsValue::Object(o) => {
-                // Proxy: delegovani na target (bez full handler traps)
-                // Pokud key je interni, vrat primo; jinak deleguj
+                // Proxy bez pristupu k traps (&self): delegovani na target.
+                // Traps vola eval_member / get_with_receiver.
                 if !key.starts_with("__") {
                     let proxy_target = o.borrow().props.get("__proxy_target__").cloned();
                     if let Some(target) = proxy_target {
-                        return self.get_prop(&target, key);
+                        return self.get_prop_str(&target, key);
                     }
                 }
                 // Specialni klic __proto__ vraci prototyp objektu
@@ -168,6 +196,9 @@ impl Interpreter {
                 if let Ok(i) = key.parse::<usize>() {
                     return Ok(a.borrow().get(i).cloned().unwrap_or(JsValue::Undefined));
                 }
+                if key == "raw" {
+                    return Ok(tagged_template::template_raw(a).unwrap_or(JsValue::Undefined));
+                }
                 Ok(JsValue::Undefined)
             }
             JsValue::Str(s) => {
@@ -179,12 +210,12 @@ impl Interpreter {
             }
             // Map vlastnosti: size (read-only)
             JsValue::Map(m) => {
-                if key == "size" { return Ok(JsValue::Number(m.borrow().entries.len() as f64)); }
+                if key == "size" && !m.borrow().weak { return Ok(JsValue::Number(m.borrow().len() as f64)); }
                 Ok(JsValue::Undefined)
             }
             // Set vlastnosti: size (read-only)
             JsValue::Set(s) => {
-                if key == "size" { return Ok(JsValue::Number(s.borrow().values.len() as f64)); }
+                if key == "size" && !s.borrow().weak { return Ok(JsValue::Number(s.borrow().len() as f64)); }
                 Ok(JsValue::Undefined)
             }
             // BigNumber vlastnosti (read-only)
@@ -741,6 +772,10 @@ impl Interpreter {
                 }
                 Ok(JsValue::Undefined)
             }
+            JsValue::Symbol(sym) => Ok(match key {
+                "description" => sym.description.clone().map(JsValue::Str).unwrap_or(JsValue::Undefined),
+                _ => JsValue::Undefined,
+            }),
             // BigInt vlastnosti (read-only)
             JsValue::BigInt(bn) => {
                 match key {
@@ -764,9 +799,27 @@ impl Interpreter {
                     ("Number", "NEGATIVE_INFINITY") => return Ok(JsValue::Number(f64::NEG_INFINITY)),
                     ("Number", "NaN")               => return Ok(JsValue::Number(f64::NAN)),
                     ("Number", "EPSILON")           => return Ok(JsValue::Number(f64::EPSILON)),
+                    ("String", "raw")               => return Ok(native("String.raw", tagged_template::string_raw)),
+                    ("Proxy", "revocable")          => return Ok(native("Proxy.revocable", proxy_handler::proxy_revocable)),
+                    ("Symbol", "for")               => return Ok(native("Symbol.for", |a| {
+                        let key = a.into_iter().next().map(|v| v.to_string()).unwrap_or_else(|| "undefined".into());
+                        Ok(symbols::symbol_for(key))
+                    })),
+                    ("Symbol", "keyFor")            => return Ok(native("Symbol.keyFor", |a| match a.into_iter().next() {
+                        Some(JsValue::Symbol(sym)) => Ok(symbols::key_for(&sym).map(JsValue::Str).unwrap_or(JsValue::Undefined)),
+                        _ => Err("TypeError: Symbol.keyFor: argument neni symbol".into()),
+                    })),
+                    ("Symbol", name) => return Ok(symbols::well_known(name).unwrap_or(JsValue::Undefined)),
                     _ => {}
                 }
-                Ok(JsValue::Undefined)
+                // ArrayBuffer.isView, Uint8Array.from / of / BYTES_PER_ELEMENT, WebAssembly.Module.imports,
+                // Temporal.PlainDate.from / compare, Intl.NumberFormat.supportedLocalesOf
+                Ok(super::builtins_typed_arrays::static_prop(fname, key)
+                    .or_else(|| super::builtins_wasm::static_prop(fname, key))
+                    .or_else(|| super::builtins_temporal::static_prop(fname, key))
+                    .or_else(|| super::builtins_intl::static_prop(fname, key))
+                    .or_else(|| self.host_static(fname, key))
+                    .unwrap_or(JsValue::Undefined))
             }
             _ => Ok(JsValue::Undefined),
         }
@@ -819,15 +872,15 @@ fn build_stylesheet_list(interp: &Interpreter) -> JsValue {
             let style = Rc::new(RefCell::new(JsObject::new()));
             for (p, v) in decls {
                 let camel = super::dom_props::kebab_to_camel(p);
-                style.borrow_mut().set(camel, JsValue::Str(v.clone()));
-                style.borrow_mut().set(p.clone(), JsValue::Str(v.clone()));
+                style.borrow_mut().set(camel.into(), JsValue::Str(v.clone()));
+                style.borrow_mut().set(p.clone().into(), JsValue::Str(v.clone()));
             }
             style.borrow_mut().set("cssText".into(), JsValue::Str(decls_str));
             style.borrow_mut().set("length".into(), JsValue::Number(decls.len() as f64));
             rule.borrow_mut().set("style".into(), JsValue::Object(style));
 
             // Indexed access na CSSRule pro cssRules[i]
-            css_rules.borrow_mut().set(rule_idx.to_string(),
+            css_rules.borrow_mut().set(rule_idx.to_string().into(),
                 JsValue::Object(Rc::clone(&rule)));
             rule_arr.push(JsValue::Object(rule));
         }
@@ -861,7 +914,7 @@ fn build_stylesheet_list(interp: &Interpreter) -> JsValue {
 
         let sheet_val = JsValue::Object(sheet);
         // indexed access [0], [1], ...
-        list.borrow_mut().set(sheet_idx.to_string(), sheet_val.clone());
+        list.borrow_mut().set(sheet_idx.to_string().into(), sheet_val.clone());
         sheet_objs.push(sheet_val);
     }
     // item(i)
@@ -1,6 +1,7 @@
 //! Statement execution dispatcher (exec_stmt + exec_stmts).
 
 use super::*;
+use crate::browser::modules_esm::{default_export_name, DEFAULT_BINDING};
 
 /// Snapshot lokalnich promennych ze scope chain (current env + parent).
 /// Vraci pary (name, stringified value), serazene podle nazvu.
@@ -31,6 +32,10 @@ fn capture_locals(env: &Rc<RefCell<Environment>>) -> Vec<(String, String)> {
 
 impl Interpreter {
     pub(super) fn exec_stmts(&mut self, stmts: &[Stmt], env: &Rc<RefCell<Environment>>) -> StmtResult {
+        strict_mode::declare_lexical(stmts, env);
+        if disposal::has_using(stmts) {
+            return self.exec_using_stmts(stmts, env);
+        }
         for s in stmts {
             if let Some(sig) = self.exec_stmt(s, env)? { return Ok(Some(sig)); }
         }
@@ -38,13 +43,15 @@ impl Interpreter {
     }
 
     pub(super) fn exec_stmt(&mut self, stmt: &Stmt, env: &Rc<RefCell<Environment>>) -> StmtResult {
+        budget::charge().map_err(JsError::Runtime)?;
         match stmt {
-            Stmt::WithLine { line, inner } => {
+            Stmt::WithLine { line, span, inner } => {
                 self.current_line = *line;
+                self.current_span.set(*span);
                 // Breakpoint OR step check.
                 let (should_pause, skip_once) = {
                     let dbg = self.debugger.borrow();
-                    let hit = dbg.is_breakpoint(*line) || dbg.step_should_pause();
+                    let hit = dbg.is_breakpoint_at(span) || dbg.step_should_pause();
                     (hit, dbg.skip_once_line == Some(*line))
                 };
                 if should_pause && !skip_once {
@@ -57,6 +64,7 @@ impl Interpreter {
                         if let Some(shared) = &self.shared_debugger {
                             let mut s = shared.lock().unwrap();
                             s.pause_at(*line);
+                            s.paused_span = Some(*span);
                             s.locals = locals.clone();
                             s.step = None;
                         }
@@ -64,6 +72,7 @@ impl Interpreter {
                         {
                             let mut dbg = self.debugger.borrow_mut();
                             dbg.pause_at(*line);
+                            dbg.paused_span = Some(*span);
                             dbg.locals = locals;
                             dbg.step = None;
                         }
@@ -74,6 +83,7 @@ impl Interpreter {
                             let s = shared.lock().unwrap();
                             let mut dbg = self.debugger.borrow_mut();
                             dbg.breakpoints = s.breakpoints.clone();
+                            dbg.column_breakpoints = s.column_breakpoints.clone();
                             dbg.step = s.step;
                             dbg.step_depth_anchor = s.step_depth_anchor;
                             dbg.skip_once_line = s.skip_once_line;
@@ -88,26 +98,28 @@ impl Interpreter {
                     self.console_log.borrow_mut().push(("warn".into(), msg));
                     let mut dbg = self.debugger.borrow_mut();
                     dbg.pause_at(*line);
+                    dbg.paused_span = Some(*span);
                     dbg.locals = locals;
                     dbg.step = None;
                     return Ok(Some(Signal::Paused(*line)));
                 }
-                if skip_once {
-                    // Konzumuj skip_once - dalsi hit na same line uz pause.
+                if should_pause && skip_once {
+                    // Konzumuj skip_once - dalsi hit na same line uz pause. Jen pri
+                    // potlacenem hitu: sloupcovy BP nemusi byt prvni prikaz radku.
                     self.debugger.borrow_mut().skip_once_line = None;
                 }
                 return self.exec_stmt(inner, env);
             }
-            Stmt::Empty => Ok(None),
+            Stmt::Empty(_) => Ok(None),
 
-            Stmt::Expr(e) => { self.eval(e, env)?; Ok(None) }
+            Stmt::Expr(e, _) => { self.eval(e, env)?; Ok(None) }
 
-            Stmt::Block(body) => {
+            Stmt::Block(body, _) => {
                 let child = Environment::new_child(env);
                 self.exec_stmts(body, &child)
             }
 
-            Stmt::Var { kind, decls } => {
+            Stmt::Var { kind, decls, .. } => {
                 for d in decls {
                     let val = match &d.init { Some(e) => self.eval(e, env)?, None => JsValue::Undefined };
                     // var = function-scoped (hoist do nejblizsi function env, NE globalni!),
@@ -118,133 +130,107 @@ impl Interpreter {
                         Rc::clone(env)
                     };
                     self.destructure_bind(&d.pattern, val, &target_env)?;
+                    if kind.is_const() {
+                        let mut names = Vec::new();
+                        d.pattern.bound_names(&mut names);
+                        let mut target = target_env.borrow_mut();
+                        for n in &names { target.mark_const(n); }
+                    }
                 }
                 Ok(None)
             }
 
-            Stmt::Function { name, params, body } => {
+            Stmt::Function { name, params, body, .. } => {
                 let func = JsValue::Function(JsFunc::User {
                     name: Some(name.clone()),
                     params: params.clone(),
                     body: FuncBody::Stmts(body.clone()),
-                    env: Rc::clone(env),
+                    env: self.closure_env(env),
+                    is_arrow: false,
+                    id: next_func_id(),
                 });
                 env.borrow_mut().define(name, func);
                 Ok(None)
             }
 
             // Generator funkce: `function* name(params) { body }`
-            Stmt::GeneratorFunc { name, params, body } => {
+            Stmt::GeneratorFunc { name, params, body, .. } => {
                 let func = JsValue::Function(JsFunc::Generator {
                     name: Some(name.clone()),
                     params: params.clone(),
                     body: body.clone(),
-                    env: Rc::clone(env),
+                    env: self.closure_env(env),
+                    is_async: false,
+                    id: next_func_id(),
                 });
                 env.borrow_mut().define(name, func);
                 Ok(None)
             }
 
-            // Async funkce: `async function name(params) { body }`
-            // Async generator: implementovan jako bezny generator (sync model).
-            // V realnem JS by kazdy yield vracel Promise, my v sync vraci hodnotu.
-            Stmt::AsyncGeneratorFunc { name, params, body } => {
+            // Async generator: `async function* name(params) { body }`
+            // next/return/throw vraci Promise; await uvnitr tela je synchronni.
+            Stmt::AsyncGeneratorFunc { name, params, body, .. } => {
                 let func = JsValue::Function(JsFunc::Generator {
                     name: Some(name.clone()),
                     params: params.clone(),
                     body: body.clone(),
-                    env: Rc::clone(env),
+                    env: self.closure_env(env),
+                    is_async: true,
+                    id: next_func_id(),
                 });
                 env.borrow_mut().define(name, func);
                 Ok(None)
             }
-            Stmt::AsyncFunc { name, params, body } => {
+
+            // Async funkce: `async function name(params) { body }`
+            Stmt::AsyncFunc { name, params, body, .. } => {
                 let func = JsValue::Function(JsFunc::Async {
                     name: Some(name.clone()),
                     params: params.clone(),
                     body: FuncBody::Stmts(body.clone()),
-                    env: Rc::clone(env),
+                    env: self.closure_env(env),
+                    is_arrow: false,
+                    id: next_func_id(),
                 });
                 env.borrow_mut().define(name, func);
                 Ok(None)
             }
 
-            // Import: nacti modul a binduj specifiers do scope
-            Stmt::Import { source, specifiers } => {
-                let ns = self.load_module(source)?;
-                let ns_obj = match &ns {
-                    JsValue::Object(o) => Rc::clone(o),
-                    _ => return Err(JsError::Runtime(
-                        format!("ModuleError: modul '{source}' nevratil objekt")
-                    )),
-                };
-                for spec in specifiers {
-                    match spec {
-                        ImportSpecifier::Default(local) => {
-                            let v = ns_obj.borrow().props.get("default").cloned()
-                                .unwrap_or(JsValue::Undefined);
-                            env.borrow_mut().define(local, v);
-                        }
-                        ImportSpecifier::Named { imported, local } => {
-                            let v = ns_obj.borrow().props.get(imported).cloned()
-                                .unwrap_or(JsValue::Undefined);
-                            env.borrow_mut().define(local, v);
-                        }
-                        ImportSpecifier::Namespace(local) => {
-                            env.borrow_mut().define(local, JsValue::Object(Rc::clone(&ns_obj)));
-                        }
-                    }
-                }
-                Ok(None)
-            }
+            // Import: vazby vytvari linkovani modulu (module_loader), zde nic.
+            Stmt::Import { .. } => Ok(None),
 
-            // Export: zaregistruje hodnotu do current_exports mapy.
-            // Funguje jen pri nacitani modulu (current_exports = Some).
-            Stmt::Export(kind) => {
+            // Export: deklarace bezi normalne v scopu modulu, `export default`
+            // uklada hodnotu do skryte vazby. Seznamy exportu a re-exporty jen
+            // popisuji vazby pro linkovani.
+            Stmt::Export(kind, _) => {
                 match kind {
-                    ExportKind::Decl(decl) => {
-                        // Spust deklaraci a pak najdi v env nove definovane jmeno(a)
-                        let pre_keys: Vec<String> = env.borrow().vars.keys().cloned().collect();
-                        self.exec_stmt(decl, env)?;
-                        let post_keys: Vec<String> = env.borrow().vars.keys().cloned().collect();
-                        if let Some(exports) = &self.current_exports {
-                            for k in post_keys {
-                                if !pre_keys.contains(&k) {
-                                    let v = env.borrow().get(&k).unwrap_or(JsValue::Undefined);
-                                    exports.borrow_mut().insert(k, v);
-                                }
-                            }
-                        }
-                    }
+                    ExportKind::Decl(decl) => return self.exec_stmt(decl, env),
                     ExportKind::Default(expr) => {
                         let v = self.eval(expr, env)?;
-                        if let Some(exports) = &self.current_exports {
-                            exports.borrow_mut().insert("default".to_string(), v);
-                        }
-                    }
-                    ExportKind::Named(pairs) => {
-                        if let Some(exports) = &self.current_exports {
-                            for (local, exported) in pairs {
-                                let v = env.borrow().get(local).unwrap_or(JsValue::Undefined);
-                                exports.borrow_mut().insert(exported.clone(), v);
-                            }
+                        // `export default function f() {}` binduje i jmeno `f`
+                        if let Some(name) = default_export_name(expr)
+                            && env.borrow().get_own(name).is_none()
+                        {
+                            env.borrow_mut().define(name, v.clone());
                         }
+                        env.borrow_mut().define(DEFAULT_BINDING, v);
                     }
+                    ExportKind::Named(_) | ExportKind::From { .. } | ExportKind::Star { .. } => {}
                 }
                 Ok(None)
             }
 
-            Stmt::Return(val) => {
+            Stmt::Return(val, _) => {
                 let v = match val { Some(e) => self.eval(e, env)?, None => JsValue::Undefined };
                 Ok(Some(Signal::Return(v)))
             }
 
-            Stmt::Throw(e) => Err(JsError::Thrown(self.eval(e, env)?)),
+            Stmt::Throw(e, _) => Err(JsError::Thrown(self.eval(e, env)?)),
 
-            Stmt::Break(label)    => Ok(Some(Signal::Break(label.clone()))),
-            Stmt::Continue(label) => Ok(Some(Signal::Continue(label.clone()))),
+            Stmt::Break(label, _)    => Ok(Some(Signal::Break(label.clone()))),
+            Stmt::Continue(label, _) => Ok(Some(Signal::Continue(label.clone()))),
 
-            Stmt::Switch { discriminant, cases } => {
+            Stmt::Switch { discriminant, cases, .. } => {
                 let value = self.eval(discriminant, env)?;
 
                 // Najdi odpovidajici case (strict ===) a pozici default
@@ -286,7 +272,7 @@ impl Interpreter {
                 Ok(None)
             }
 
-            Stmt::If { test, yes, no } => {
+            Stmt::If { test, yes, no, .. } => {
                 if self.eval(test, env)?.is_truthy() {
                     self.exec_stmt(yes, env)
                 } else if let Some(alt) = no {
@@ -294,7 +280,7 @@ impl Interpreter {
                 } else { Ok(None) }
             }
 
-            Stmt::While { test, body } => {
+            Stmt::While { test, body, .. } => {
                 loop {
                     if !self.eval(test, env)?.is_truthy() { break; }
                     match self.exec_stmt(body, env)? {
@@ -307,7 +293,7 @@ impl Interpreter {
                 Ok(None)
             }
 
-            Stmt::DoWhile { body, test } => {
+            Stmt::DoWhile { body, test, .. } => {
                 loop {
                     match self.exec_stmt(body, env)? {
                         Some(Signal::Break(None))    => break,
@@ -320,7 +306,7 @@ impl Interpreter {
                 Ok(None)
             }
 
-            Stmt::For { init, test, update, body } => {
+            Stmt::For { init, test, update, body, .. } => {
                 let for_env = Environment::new_child(env);
                 if let Some(init) = init {
                     match init {
@@ -350,65 +336,43 @@ impl Interpreter {
                 Ok(None)
             }
 
-            Stmt::ForOf { kind: _, target, iter, body } => {
-                let arr_val = self.eval(iter, env)?;
-                // Podpora pro custom iterables pres Symbol.iterator
-                let items = self.collect_iterable(arr_val)?;
-                for item in items {
-                    let loop_env = Environment::new_child(env);
-                    self.bind_target_expr(target, item, &loop_env)?;
-                    match self.exec_stmt(body, &loop_env)? {
-                        Some(Signal::Break(None))    => break,
-                        Some(Signal::Continue(None)) => continue,
-                        Some(s) => return Ok(Some(s)),
-                        None => {}
-                    }
-                }
-                Ok(None)
+            // `for (using x of ...)` = `for (const %v of ...) { using x = %v; ... }`
+            Stmt::ForOf { kind: Some(k), .. } | Stmt::ForAwaitOf { kind: Some(k), .. } if k.is_using() => {
+                self.exec_stmt(&disposal::lower_for_of(stmt), env)
+            }
+            Stmt::ForOf { kind: _, target, iter, body, .. } => {
+                let iterable = self.eval(iter, env)?;
+                self.exec_for_of(target, iterable, body, env, false)
             }
 
-            // For-await-of: jako for-of, ale kazdy yielded element rozbal jako Promise
-            // V nasi sync implementaci stejne jako for-of, ale s unwrap_promise_result
-            Stmt::ForAwaitOf { kind: _, target, iter, body } => {
-                let arr_val = self.eval(iter, env)?;
-                let items = self.collect_iterable(arr_val)?;
-                for item in items {
-                    // Pokud je item Promise, await ho
-                    let resolved = match unwrap_promise_result(item) {
-                        Ok(v) => v,
-                        Err(reason) => return Err(JsError::Thrown(reason)),
-                    };
-                    let loop_env = Environment::new_child(env);
-                    self.bind_target_expr(target, resolved, &loop_env)?;
-                    match self.exec_stmt(body, &loop_env)? {
-                        Some(Signal::Break(None))    => break,
-                        Some(Signal::Continue(None)) => continue,
-                        Some(s) => return Ok(Some(s)),
-                        None => {}
-                    }
-                }
-                Ok(None)
+            // For-await-of: jako for-of, ale kroky i hodnoty se rozbaluji z Promise
+            // (async iteratory, pole Promise). Await je v nasi impl synchronni.
+            Stmt::ForAwaitOf { kind: _, target, iter, body, .. } => {
+                let iterable = self.eval(iter, env)?;
+                self.exec_for_of(target, iterable, body, env, true)
             }
 
-            Stmt::ForIn { kind: _, target, iter, body } => {
+            Stmt::ForIn { kind: _, target, iter, body, .. } => {
                 let obj_val = self.eval(iter, env)?;
                 let keys = match &obj_val {
+                    p if proxy_handler::is_proxy(p) => self.proxy_enumerable_keys(p)?,
                     JsValue::Object(o) => {
-                        let mut raw: Vec<String> = o.borrow().props.keys()
-                            .filter(|k| !is_internal_key(k))
-                            .cloned().collect();
-                        // JS spec: integer-index keys ascending, then remaining keys
-                        raw.sort_by(|a, b| {
-                            let ai = a.parse::<u64>().ok();
-                            let bi = b.parse::<u64>().ok();
-                            match (ai, bi) {
-                                (Some(x), Some(y)) => x.cmp(&y),
-                                (Some(_), None)    => std::cmp::Ordering::Less,
-                                (None, Some(_))    => std::cmp::Ordering::Greater,
-                                (None, None)       => std::cmp::Ordering::Equal,
+                        // Vlastni enumerable klice ve spec poradi, pak retezec prototypu.
+                        // Klic zakryty blizsim objektem (i ne-enumerable) se nevypisuje.
+                        let mut keys: Vec<String> = Vec::new();
+                        let mut seen: std::collections::HashSet<String> = std::collections::HashSet::new();
+                        let mut cur = Some(Rc::clone(o));
+                        while let Some(obj) = cur {
+                            let b = obj.borrow();
+                            let enumerable = b.ordered_keys(true);
+                            for k in b.ordered_keys(false) {
+                                if seen.insert(k.clone()) && enumerable.contains(&k) {
+                                    keys.push(k);
+                                }
                             }
-                        });
-                        raw
+                            cur = b.proto.clone();
+                        }
+                        keys
                     }
                     _ => vec![],
                 };
@@ -425,33 +389,38 @@ impl Interpreter {
                 Ok(None)
             }
 
-            Stmt::Try { body, catch, finally } => {
+            Stmt::Try { body, catch, finally, .. } => {
                 let try_env = Environment::new_child(env);
-                let result = self.exec_stmts(body, &try_env);
-                let sig = match result {
-                    Ok(s) => s,
-                    Err(e) => {
-                        if let Some(c) = catch {
-                            let catch_env = Environment::new_child(env);
-                            if let Some(param) = &c.param {
-                                let err_val = match e {
-                                    JsError::Thrown(v) => v,
-                                    JsError::Runtime(s) => JsValue::Str(s),
-                                };
-                                catch_env.borrow_mut().define(param, err_val);
-                            }
-                            self.exec_stmts(&c.body, &catch_env)?
-                        } else { return Err(e); }
+                let result = match (self.exec_stmts(body, &try_env), catch) {
+                    // Ukonceni rozpoctem (budget) je nezachytitelne - ani finally
+                    (Err(e), _) if budget::is_terminating() => return Err(e),
+                    // gen.return() unwind catch nechyta - probehne jen finally
+                    (Err(e), Some(c)) if !matches!(e, JsError::GeneratorReturn(_)) => {
+                        let catch_env = Environment::new_child(env);
+                        if let Some(param) = &c.param {
+                            let err_val = match e {
+                                JsError::Thrown(v) | JsError::GeneratorReturn(v) => v,
+                                JsError::Runtime(s) => JsValue::Str(s),
+                            };
+                            catch_env.borrow_mut().define(param, err_val);
+                        }
+                        self.exec_stmts(&c.body, &catch_env)
                     }
+                    (r, _) => r,
                 };
+                // finally bezi vzdy (i po vyjimce / return / unwindu generatoru);
+                // vlastni return/break ve finally prebiji vysledek try/catch.
                 if let Some(fin) = finally {
+                    if budget::is_terminating() { return result; }
                     let fin_env = Environment::new_child(env);
-                    self.exec_stmts(fin, &fin_env)?;
+                    if let Some(sig) = self.exec_stmts(fin, &fin_env)? {
+                        return Ok(Some(sig));
+                    }
                 }
-                Ok(sig)
+                result
             }
 
-            Stmt::Labeled { label, body } => {
+            Stmt::Labeled { label, body, .. } => {
                 match self.exec_stmt(body, env)? {
                     // break label / continue label odpovidajici tomuto labelu -> konzumuj signal
                     Some(Signal::Break(Some(l)))    if l == *label => Ok(None),
@@ -461,17 +430,54 @@ impl Interpreter {
                 }
             }
 
-            Stmt::Class { name, super_class, body } => {
-                let super_val = if let Some(sc) = super_class {
-                    Some(Box::new(self.eval(sc, env)?))
-                } else { None };
-                let cls = self.make_class_func(Some(name.clone()), super_val, body, env);
+            Stmt::Class { name, decorators, super_class, body, .. } => {
+                let cls = self.eval_class(Some(name.clone()), decorators, super_class.as_deref(), body, env)?;
                 env.borrow_mut().define(name, cls);
                 Ok(None)
             }
         }
     }
 
+    /// Spolecne telo for-of / for-await-of. Iteruje lazy (generatory se
+    /// obnovuji po jednom kroku); pri break/return/vyjimce zavre iterator
+    /// pres `return()`.
+    fn exec_for_of(
+        &mut self,
+        target: &Expr,
+        iterable: JsValue,
+        body: &Stmt,
+        env: &Rc<RefCell<Environment>>,
+        is_async: bool,
+    ) -> StmtResult {
+        let mut it = self.open_iterator(iterable, is_async)?;
+        while let Some(item) = self.iter_step(&mut it)? {
+            let loop_env = Environment::new_child(env);
+            let sig = match self.bind_target_expr(target, item, &loop_env)
+                .and_then(|_| self.exec_stmt(body, &loop_env))
+            {
+                Ok(sig) => sig,
+                Err(e) => {
+                    // Chyba z return() by zakryla puvodni vyjimku - ignoruj
+                    let _ = self.iter_close(&mut it);
+                    return Err(e);
+                }
+            };
+            match sig {
+                Some(Signal::Break(None)) => {
+                    self.iter_close(&mut it)?;
+                    break;
+                }
+                Some(Signal::Continue(None)) => continue,
+                Some(s) => {
+                    self.iter_close(&mut it)?;
+                    return Ok(Some(s));
+                }
+                None => {}
+            }
+        }
+        Ok(None)
+    }
+
     // ─── Výrazy ───────────────────────────────────────────────────────────────
 
 }
@@ -1,101 +1,788 @@
-//! Cycle collector pro `Rc<RefCell<JsObject>>` cycles.
+//! Cycle collector pro JS heap (`Rc<RefCell<...>>` kontejnery).
 //!
 //! `Rc` reference counting NEumi detect cycles - `a.next = b; b.next = a` =
-//! ref count nikdy nedosahne 0, memory leak. Long-running tabs accumulate.
+//! ref count nikdy nedosahne 0, memory leak. Long-running tabs accumulate,
+//! typicky pres listener → closure env → element → listener.
 //!
-//! Algorithm: Bacon-Rajan trial deletion (Concurrent Cycle Collection in
-//! Reference Counted Systems, Bacon & Rajan 2001) zjednodusena verze:
+//! Algorithm: trial deletion (Bacon & Rajan 2001, stejny princip jako CPython
+//! `gc_collect_main`) + tracing mark z rootu:
 //!
-//! 1. Sber "possibly cyclic" candidates - object marked po decrement.
-//! 2. Trial delete - decrement ref counts ze vseho co reference candidates.
-//! 3. Po trial: pokud strong refs > 0 = realne dosazitelne (no cycle). Pokud
-//!    refs == 0 = jen self-cycle = volne.
-//! 4. Restore + sweep dead.
+//! 1. Scan set = vsechny trackovane kontejnery (objekty, pole, Map/Set,
+//!    environmenty) uzavrene pres hrany. Kontejner se trackuje write barrierem
+//!    ([`track_value`]) - pri zapisu do props / promenne.
+//! 2. `gc_refs = strong_count - 1 (nase kopie) - interni hrany ze scan setu`.
+//!    `gc_refs > 0` = nekdo mimo scan set (Rust stack, native closure, fronta
+//!    timeru, generator ramec) drzi referenci → externi root.
+//! 3. Mark: z externich rootu + explicitnich [`GcRoots`] (global env, timery,
+//!    pending fetch promise, DOM bridge) projde hrany a oznaci live.
+//! 4. Sweep: neoznacene kontejnery jsou izolovane cykly - vyprazdnime je
+//!    (props/proto/prvky/promenne), Rc count pak sam spadne na 0.
+//!
+//! DOM bridge: `event_callbacks` drzi listener funkce silne, takze by
+//! cyklus listener → env → uzel nikdy nespadl. Mapa se proto pocita jako
+//! interni hrana a callback je live jen kdyz jeho uzel je v dokumentu nebo
+//! ho drzi live JS hodnota. Mrtve callbacky se z registru odstrani.
+//!
+//! WeakMap/WeakSet: klic-objekt je ephemeron hrana. Pro trial deletion se pocita
+//! jako interni (kolekce klic skutecne drzi), ale mark pres ni nejde - hodnota
+//! je live jen kdyz je live kolekce i klic. Sweep pak z live weak kolekci
+//! odstrani polozky s mrtvym klicem.
+//!
+//! Konzervativni pravidla: neznama hrana (native closure, bytecode konstanta)
+//! jen zvysi `gc_refs` → objekt zustane live. Kontejner co nejde borrownout
+//! (prave se mutuje) je root.
 //!
 //! Inspired by:
-//! - V8 cycle collector v Oilpan (`v8/src/heap/`)
-//! - Firefox SpiderMonkey GC (`js/src/gc/`)
+//! - CPython `Modules/gcmodule.c` (update_refs / subtract_refs / move_unreachable)
+//! - Firefox CycleCollector (`xpcom/base/nsCycleCollector.cpp`) - DOM ↔ JS hrany
 //! - Bacon-Rajan paper (2001 IBM TR)
 //!
-//! Practical use: spustit `collect_cycles()` periodicky (kazdy N framu nebo pri
-//! memory pressure). Trash je v `JsObject.props` cycles ve VYSOKEM mnozstvi pri
-//! event_callbacks → element → __closure__ → event_callbacks.
+//! Spousteni: [`super::Interpreter::gc_safepoint`] na konci host entry pointu
+//! (run, drain_timers, dispatch_event) pri prekroceni heap thresholdu.
 
-use super::{JsObject, JsValue};
-use std::collections::{HashMap, HashSet};
-use std::rc::Rc;
+use super::{Environment, JsFunc, JsMap, JsObject, JsSet, JsValue, PropertyMap};
+use crate::browser::dom::Node;
 use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::rc::{Rc, Weak};
 
-/// Cycle collector state - kandidati + visited set.
-#[derive(Default)]
-pub struct CycleCollector {
-    /// Objects mozna v cycle - registrovany pri Rc decrement. Slabe refs.
-    candidates: Vec<*const RefCell<JsObject>>,
-    /// Last collection statistika.
+/// Minimalni pocet zapisu kontejneru mezi kolekcemi. Pod nim se GC nespousti
+/// (kratke skripty / testy zaplati jen write barrier).
+pub const GC_MIN_THRESHOLD: usize = 10_000;
+
+/// Registr se compactuje (dead weaks + duplicity) pri teto velikosti.
+const COMPACT_MIN: usize = 4_096;
+
+/// Statistiky collectoru - cte devtools Memory tab (`devtools::memory`).
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct GcStats {
+    /// Pocet dokoncenych kolekci.
+    pub collections: u64,
+    /// Kontejnery uvolnene posledni kolekci.
     pub last_freed: usize,
-    pub last_visited: usize,
-}
-
-impl CycleCollector {
-    pub fn new() -> Self { Self::default() }
-
-    /// Mark obj jako kandidata (po external decrement). Slabe.
-    pub fn add_candidate(&mut self, obj: &Rc<RefCell<JsObject>>) {
-        self.candidates.push(Rc::as_ptr(obj));
-    }
-
-    /// Run cycle collection pres vsechny candidates. Vraci pocet freed objektu.
-    /// Slozity bezpecny impl by vyzadoval weak refs + custom RefCounted type.
-    /// Foundation impl = candidate walk + reachability test (z roots).
-    ///
-    /// Real implementace v `collect_cycles` reaguje na concretne cycle
-    /// patterns - dnes pro RC-based architekturu je nejlepsi prevention:
-    /// pouzit `Weak<RefCell<JsObject>>` pro back-refs (parent/event listener
-    /// target). Tato fn dokumentuje strategy + diagnostiku.
-    pub fn collect(&mut self, roots: &[Rc<RefCell<JsObject>>]) -> usize {
-        // 1. Mark reachable z roots.
-        let mut reachable: HashSet<*const RefCell<JsObject>> = HashSet::new();
-        for r in roots {
-            mark_reachable(r, &mut reachable);
-        }
-        // 2. Pres candidates: kdyz reachable, skip. Jinak = cyclically isolated
-        //    (no external strong refs krome interních cycle).
-        let mut freed = 0;
-        for cand in self.candidates.drain(..) {
-            if reachable.contains(&cand) { continue; }
-            // Pri realne free: musi byt safe drop. RC-based nelze force-drop -
-            // teoreticky bychom mohli `clear()` props na isolated objektu
-            // (rozbije cycle), pak Rc count -> 0 sam.
-            // Foundation: just count "would-free" pro diagnostiku.
-            freed += 1;
+    /// Kontejnery uvolnene celkem.
+    pub total_freed: u64,
+    /// Kontejnery prosle posledni kolekci (scan set).
+    pub last_scanned: usize,
+    /// Live kontejnery po posledni kolekci.
+    pub live: usize,
+    /// Listener callbacky odstranene z DOM bridge (uzel umrel).
+    pub callbacks_released: u64,
+    /// Aktualni trigger - zapisy kontejneru do dalsi kolekce.
+    pub threshold: usize,
+    /// Zapisy kontejneru od posledni kolekce.
+    pub allocated_since: usize,
+    /// Delka posledni pauzy v mikrosekundach.
+    pub last_pause_us: u64,
+    /// Soucet vsech pauz v mikrosekundach.
+    pub total_pause_us: u64,
+}
+
+/// Slaba reference na trackovany kontejner.
+enum Tracked {
+    Object(Weak<RefCell<JsObject>>),
+    Array(Weak<RefCell<Vec<JsValue>>>),
+    Map(Weak<RefCell<JsMap>>),
+    Set(Weak<RefCell<JsSet>>),
+    Env(Weak<RefCell<Environment>>),
+}
+
+impl Tracked {
+    fn upgrade(&self) -> Option<Container> {
+        Some(match self {
+            Tracked::Object(w) => Container::Object(w.upgrade()?),
+            Tracked::Array(w) => Container::Array(w.upgrade()?),
+            Tracked::Map(w) => Container::Map(w.upgrade()?),
+            Tracked::Set(w) => Container::Set(w.upgrade()?),
+            Tracked::Env(w) => Container::Env(w.upgrade()?),
+        })
+    }
+
+    fn addr(&self) -> usize {
+        match self {
+            Tracked::Object(w) => w.as_ptr() as *const () as usize,
+            Tracked::Array(w) => w.as_ptr() as *const () as usize,
+            Tracked::Map(w) => w.as_ptr() as *const () as usize,
+            Tracked::Set(w) => w.as_ptr() as *const () as usize,
+            Tracked::Env(w) => w.as_ptr() as *const () as usize,
+        }
+    }
+
+    fn is_alive(&self) -> bool {
+        match self {
+            Tracked::Object(w) => w.strong_count() > 0,
+            Tracked::Array(w) => w.strong_count() > 0,
+            Tracked::Map(w) => w.strong_count() > 0,
+            Tracked::Set(w) => w.strong_count() > 0,
+            Tracked::Env(w) => w.strong_count() > 0,
+        }
+    }
+}
+
+/// Silna reference na kontejner behem kolekce.
+#[derive(Clone)]
+enum Container {
+    Object(Rc<RefCell<JsObject>>),
+    Array(Rc<RefCell<Vec<JsValue>>>),
+    Map(Rc<RefCell<JsMap>>),
+    Set(Rc<RefCell<JsSet>>),
+    Env(Rc<RefCell<Environment>>),
+}
+
+/// Obsah vyjmuty z mrtveho kontejneru - dropne se az po uvolneni borrowu.
+enum Garbage {
+    Object(PropertyMap, Option<Rc<RefCell<JsObject>>>),
+    Values(Vec<JsValue>),
+    Entries(Vec<(JsValue, JsValue)>),
+    Env(HashMap<String, JsValue>, Option<Rc<RefCell<Environment>>>),
+}
+
+impl Container {
+    fn addr(&self) -> usize {
+        match self {
+            Container::Object(r) => Rc::as_ptr(r) as *const () as usize,
+            Container::Array(r) => Rc::as_ptr(r) as *const () as usize,
+            Container::Map(r) => Rc::as_ptr(r) as *const () as usize,
+            Container::Set(r) => Rc::as_ptr(r) as *const () as usize,
+            Container::Env(r) => Rc::as_ptr(r) as *const () as usize,
+        }
+    }
+
+    fn strong_count(&self) -> usize {
+        match self {
+            Container::Object(r) => Rc::strong_count(r),
+            Container::Array(r) => Rc::strong_count(r),
+            Container::Map(r) => Rc::strong_count(r),
+            Container::Set(r) => Rc::strong_count(r),
+            Container::Env(r) => Rc::strong_count(r),
+        }
+    }
+
+    fn downgrade(&self) -> Tracked {
+        match self {
+            Container::Object(r) => Tracked::Object(Rc::downgrade(r)),
+            Container::Array(r) => Tracked::Array(Rc::downgrade(r)),
+            Container::Map(r) => Tracked::Map(Rc::downgrade(r)),
+            Container::Set(r) => Tracked::Set(Rc::downgrade(r)),
+            Container::Env(r) => Tracked::Env(Rc::downgrade(r)),
+        }
+    }
+
+    /// Vsechny silne hrany kontejneru (s nasobnosti - kazda kopie Rc = 1).
+    /// `false` = kontejner je mutably borrowed, hrany nezname.
+    fn edges(&self, out: &mut Edges) -> bool {
+        match self {
+            Container::Object(r) => {
+                let Ok(o) = r.try_borrow() else { return false };
+                for (_, slot) in o.props.slots() {
+                    out.value(&slot.value);
+                    if let Some(g) = &slot.getter { out.value(g); }
+                    if let Some(s) = &slot.setter { out.value(s); }
+                }
+                if let Some(p) = &o.proto { out.push(Container::Object(Rc::clone(p))); }
+            }
+            Container::Array(r) => {
+                let Ok(a) = r.try_borrow() else { return false };
+                a.iter().for_each(|v| out.value(v));
+            }
+            Container::Map(r) => {
+                let Ok(m) = r.try_borrow() else { return false };
+                for (k, v) in m.entries() {
+                    match weak_key(m.weak, k) {
+                        Some(key) => {
+                            let mut value = Edges::default();
+                            value.value(v);
+                            out.ephemerons.push((key, value));
+                        }
+                        None => {
+                            out.value(k);
+                            out.value(v);
+                        }
+                    }
+                }
+            }
+            Container::Set(r) => {
+                let Ok(s) = r.try_borrow() else { return false };
+                for v in s.values() {
+                    match weak_key(s.weak, v) {
+                        Some(key) => out.ephemerons.push((key, Edges::default())),
+                        None => out.value(v),
+                    }
+                }
+            }
+            Container::Env(r) => {
+                let Ok(e) = r.try_borrow() else { return false };
+                e.vars.values().for_each(|v| out.value(v));
+                if let Some(p) = &e.parent { out.push(Container::Env(Rc::clone(p))); }
+            }
+        }
+        true
+    }
+
+    /// Vyprazdni mrtvy kontejner - rozbije cyklus. Obsah jde do `sink`.
+    fn clear(&self, sink: &mut Vec<Garbage>) -> bool {
+        match self {
+            Container::Object(r) => {
+                let Ok(mut o) = r.try_borrow_mut() else { return false };
+                let props = std::mem::replace(&mut o.props, PropertyMap::new());
+                sink.push(Garbage::Object(props, o.proto.take()));
+            }
+            Container::Array(r) => {
+                let Ok(mut a) = r.try_borrow_mut() else { return false };
+                sink.push(Garbage::Values(std::mem::take(&mut *a)));
+            }
+            Container::Map(r) => {
+                let Ok(mut m) = r.try_borrow_mut() else { return false };
+                sink.push(Garbage::Entries(m.table.take()));
+            }
+            Container::Set(r) => {
+                let Ok(mut s) = r.try_borrow_mut() else { return false };
+                sink.push(Garbage::Values(s.table.take().into_iter().map(|(k, _)| k).collect()));
+            }
+            Container::Env(r) => {
+                let Ok(mut e) = r.try_borrow_mut() else { return false };
+                let vars = std::mem::take(&mut e.vars);
+                sink.push(Garbage::Env(vars, e.parent.take()));
+            }
         }
-        self.last_freed = freed;
-        self.last_visited = reachable.len();
-        freed
+        true
     }
 }
 
-/// Walk reachable Objects z root pres props/JsValue::Object refs.
-fn mark_reachable(
-    obj: &Rc<RefCell<JsObject>>,
-    visited: &mut HashSet<*const RefCell<JsObject>>,
-) {
-    let ptr = Rc::as_ptr(obj);
-    if !visited.insert(ptr) { return; }
-    let borrowed = match obj.try_borrow() { Ok(b) => b, Err(_) => return };
-    for (_, v) in &borrowed.props {
-        if let JsValue::Object(child) = v {
-            mark_reachable(child, visited);
+/// Sber hran jednoho kontejneru: kontejnery + DOM uzly (pro DOM bridge).
+#[derive(Default)]
+struct Edges {
+    containers: Vec<Container>,
+    dom: Vec<Rc<Node>>,
+    /// Weak kolekce: (klic, hrany hodnoty) - hodnota je live jen s klicem.
+    ephemerons: Vec<(Container, Edges)>,
+}
+
+/// Klic weak kolekce, ktery GC drzi slabe (kontejner). Funkce a symboly
+/// zustavaji silne - jejich identita neni jeden kontejner.
+fn weak_key(weak: bool, k: &JsValue) -> Option<Container> {
+    if !weak { return None; }
+    Some(match k {
+        JsValue::Object(r) => Container::Object(Rc::clone(r)),
+        JsValue::Array(r) => Container::Array(Rc::clone(r)),
+        JsValue::Map(r) => Container::Map(Rc::clone(r)),
+        JsValue::Set(r) => Container::Set(Rc::clone(r)),
+        _ => return None,
+    })
+}
+
+impl Container {
+    /// Hodnota kontejneru (klic weak kolekce pro odstraneni).
+    fn to_value(&self) -> Option<JsValue> {
+        Some(match self {
+            Container::Object(r) => JsValue::Object(Rc::clone(r)),
+            Container::Array(r) => JsValue::Array(Rc::clone(r)),
+            Container::Map(r) => JsValue::Map(Rc::clone(r)),
+            Container::Set(r) => JsValue::Set(Rc::clone(r)),
+            Container::Env(_) => return None,
+        })
+    }
+}
+
+/// Ephemeron v indexech scan setu: (klic, kontejnery hodnoty, DOM uzly hodnoty).
+type Ephemeron = (usize, Vec<usize>, Vec<Rc<Node>>);
+
+impl Edges {
+    fn push(&mut self, c: Container) {
+        self.containers.push(c);
+    }
+
+    /// Hrany jedne hodnoty. Funkce nejsou Rc - jejich env / bound hodnoty
+    /// jsou inline, takze hrany vedou primo z drzitele funkce.
+    fn value(&mut self, v: &JsValue) {
+        match v {
+            JsValue::Object(r) => self.push(Container::Object(Rc::clone(r))),
+            JsValue::Array(r) => self.push(Container::Array(Rc::clone(r))),
+            JsValue::Map(r) => self.push(Container::Map(Rc::clone(r))),
+            JsValue::Set(r) => self.push(Container::Set(Rc::clone(r))),
+            JsValue::DomNode(n) => self.dom.push(Rc::clone(n)),
+            JsValue::Function(f) => self.func(f),
+            _ => {}
+        }
+    }
+
+    fn func(&mut self, f: &JsFunc) {
+        match f {
+            JsFunc::User { env, .. } | JsFunc::Generator { env, .. } | JsFunc::Async { env, .. } => {
+                self.push(Container::Env(Rc::clone(env)));
+            }
+            JsFunc::Class {
+                super_val, env, static_props, methods, statics, getters, setters, fields, initializers, ..
+            } => {
+                if let Some(s) = super_val { self.value(s); }
+                // Nahrady z dekoratoru
+                for m in [methods, statics, getters, setters].into_iter().flatten() {
+                    if let Some(f) = &m.decorated { self.value(f); }
+                }
+                for f in fields {
+                    f.decorators.iter().chain(&f.initializers).for_each(|v| self.value(v));
+                }
+                initializers.iter().for_each(|v| self.value(v));
+                self.push(Container::Object(Rc::clone(static_props)));
+                self.push(Container::Env(Rc::clone(env)));
+            }
+            JsFunc::VmCompiled { env, captures, .. } => {
+                self.push(Container::Env(Rc::clone(env)));
+                captures.iter().for_each(|c| self.value(c));
+            }
+            JsFunc::Bound { func, bound_this, bound_args, .. } => {
+                self.value(func);
+                self.value(bound_this);
+                bound_args.iter().for_each(|a| self.value(a));
+            }
+            // Native closure / generator ramec = neznama hrana (konzervativne).
+            JsFunc::Native(..) | JsFunc::GeneratorResume { .. } => {}
+        }
+    }
+}
+
+/// Kontejner obsazeny v hodnote (bez rekurze) - pro write barrier.
+fn container_of(v: &JsValue) -> Option<Tracked> {
+    Some(match v {
+        JsValue::Object(r) => Tracked::Object(Rc::downgrade(r)),
+        JsValue::Array(r) => Tracked::Array(Rc::downgrade(r)),
+        JsValue::Map(r) => Tracked::Map(Rc::downgrade(r)),
+        JsValue::Set(r) => Tracked::Set(Rc::downgrade(r)),
+        JsValue::Function(
+            JsFunc::User { env, .. }
+            | JsFunc::Generator { env, .. }
+            | JsFunc::Async { env, .. }
+            | JsFunc::Class { env, .. }
+            | JsFunc::VmCompiled { env, .. },
+        ) => Tracked::Env(Rc::downgrade(env)),
+        _ => return None,
+    })
+}
+
+/// Thread-local heap registr (interpreter je single-thread, workery maji vlastni).
+struct Heap {
+    tracked: Vec<Tracked>,
+    compact_at: usize,
+    /// Aktivni host entry pointy - GC jen na nejvyssi urovni (depth 0).
+    scopes: u32,
+    stats: GcStats,
+}
+
+impl Default for Heap {
+    fn default() -> Self {
+        Heap {
+            tracked: Vec::new(),
+            compact_at: COMPACT_MIN,
+            scopes: 0,
+            stats: GcStats { threshold: GC_MIN_THRESHOLD, ..GcStats::default() },
         }
     }
 }
 
-/// Break dead cycle: clear props na JsObject - rozbije internal cycle, RC
-/// po decrement pak dosahne 0 a Rc dropne. Volat jen kdyz si jisty ze objekt
-/// neni reachable z roots (jinak rozbije live data).
-pub fn break_cycle(obj: &Rc<RefCell<JsObject>>) {
-    if let Ok(mut b) = obj.try_borrow_mut() {
-        b.props.clear();
+impl Heap {
+    /// Odstrani mrtve weaks + duplicity (stejny kontejner zapsany vickrat).
+    fn compact(&mut self) {
+        let mut seen = HashSet::with_capacity(self.tracked.len());
+        self.tracked.retain(|t| t.is_alive() && seen.insert(t.addr()));
+        self.compact_at = (self.tracked.len() * 2).max(COMPACT_MIN);
+    }
+}
+
+thread_local! {
+    static HEAP: RefCell<Heap> = RefCell::new(Heap::default());
+}
+
+/// Write barrier: zaregistruje kontejner v zapisovane hodnote.
+/// Vola PropertyMap (zapis vlastnosti) a Environment (define / set).
+/// Primitiva jsou no-op bez sahnuti na thread-local.
+pub fn track_value(v: &JsValue) {
+    let Some(t) = container_of(v) else { return };
+    HEAP.with(|h| {
+        let Ok(mut h) = h.try_borrow_mut() else { return };
+        h.tracked.push(t);
+        h.stats.allocated_since += 1;
+        if h.tracked.len() >= h.compact_at {
+            h.compact();
+        }
+    });
+}
+
+/// Explicitne zaregistruje objekt (alokace mimo write barrier).
+pub fn track_object(obj: &Rc<RefCell<JsObject>>) {
+    track_value(&JsValue::Object(Rc::clone(obj)));
+}
+
+/// Aktualni statistiky collectoru tohoto threadu.
+pub fn stats() -> GcStats {
+    HEAP.with(|h| h.borrow().stats)
+}
+
+/// Pocet zaznamu v registru (vcetne duplicit do dalsiho compactu).
+pub fn tracked_len() -> usize {
+    HEAP.with(|h| h.borrow().tracked.len())
+}
+
+/// Prekrocil heap threshold? (zapisy kontejneru od posledni kolekce)
+pub fn should_collect() -> bool {
+    HEAP.with(|h| {
+        let h = h.borrow();
+        h.scopes == 0 && h.stats.allocated_since >= h.stats.threshold
+    })
+}
+
+/// Guard host entry pointu - vnorene safepointy (dispatchEvent z JS handleru,
+/// drain_timers uvnitr run) nekolektuji, Rust stack muze drzet DOM uzly.
+pub struct GcScope(());
+
+impl Drop for GcScope {
+    fn drop(&mut self) {
+        HEAP.with(|h| h.borrow_mut().scopes -= 1);
+    }
+}
+
+pub fn enter_scope() -> GcScope {
+    HEAP.with(|h| h.borrow_mut().scopes += 1);
+    GcScope(())
+}
+
+/// Explicitni rooty kolekce. Vse co drzi interpret mimo JS heap - sbira
+/// `Interpreter::gc_roots`.
+#[derive(Default)]
+pub struct GcRoots {
+    /// Hodnoty drzene interpretem (global env funkce, timery, promise, ...).
+    pub values: Vec<JsValue>,
+    /// Environmenty (global scope).
+    pub envs: Vec<Rc<RefCell<Environment>>>,
+    /// Koreny DOM stromu (document root, focused element). Listenery v jejich
+    /// podstromech jsou live.
+    pub dom: Vec<Rc<Node>>,
+    /// DOM bridge registr callback_id -> listener funkce. Kolekce z nej
+    /// odstrani callbacky mrtvych uzlu.
+    pub callbacks: Option<Rc<RefCell<HashMap<usize, JsValue>>>>,
+}
+
+/// Mark faze: tracing z externich + explicitnich rootu pres scan set.
+struct Marker<'a> {
+    nodes: &'a [Container],
+    edges: &'a [Vec<usize>],
+    dom: &'a [Vec<Rc<Node>>],
+    weak: &'a [Vec<Ephemeron>],
+    index: &'a HashMap<usize, usize>,
+    callbacks: &'a HashMap<usize, JsValue>,
+    live: Vec<bool>,
+    live_callbacks: HashSet<usize>,
+    seen_dom: HashSet<usize>,
+    stack: Vec<usize>,
+}
+
+impl Marker<'_> {
+    fn value(&mut self, v: &JsValue) {
+        let mut e = Edges::default();
+        e.value(v);
+        self.edges_of(e);
+    }
+
+    fn edges_of(&mut self, e: Edges) {
+        for c in &e.containers {
+            if let Some(&i) = self.index.get(&c.addr()) { self.stack.push(i); }
+        }
+        for n in &e.dom { self.dom_tree(n); }
+    }
+
+    /// Live DOM uzel: cely jeho strom (od nejvyssiho predka) drzi listenery.
+    fn dom_tree(&mut self, node: &Rc<Node>) {
+        let mut top = Rc::clone(node);
+        loop {
+            let parent = top.parent.borrow().upgrade();
+            match parent {
+                Some(p) => top = p,
+                None => break,
+            }
+        }
+        if !self.seen_dom.insert(Rc::as_ptr(&top) as usize) { return; }
+        let mut queue = vec![top];
+        while let Some(n) = queue.pop() {
+            let ids: Vec<usize> = n.listeners.borrow().values()
+                .flat_map(|l| l.iter().map(|e| e.callback_id))
+                .collect();
+            for id in ids {
+                if !self.live_callbacks.insert(id) { continue; }
+                let callbacks = self.callbacks;
+                if let Some(cb) = callbacks.get(&id) { self.value(cb); }
+            }
+            queue.extend(n.children.borrow().iter().cloned());
+        }
+    }
+
+    fn run(&mut self) {
+        while let Some(i) = self.stack.pop() {
+            if self.live[i] { continue; }
+            self.live[i] = true;
+            let edges = self.edges;
+            let dom = self.dom;
+            self.stack.extend(edges[i].iter().copied());
+            for n in &dom[i] { self.dom_tree(n); }
+        }
+    }
+
+    /// Ephemerony do fixpointu: hodnota live kolekce s live klicem je live
+    /// (a muze oziveni dalsi klic).
+    fn run_ephemerons(&mut self) {
+        loop {
+            let weak = self.weak;
+            for (i, ephemerons) in weak.iter().enumerate() {
+                if !self.live[i] { continue; }
+                for (k, vals, dom) in ephemerons {
+                    if !self.live[*k] { continue; }
+                    self.stack.extend(vals.iter().copied().filter(|&j| !self.live[j]));
+                    for n in dom { self.dom_tree(n); }
+                }
+            }
+            if self.stack.is_empty() { break; }
+            self.run();
+        }
+    }
+}
+
+/// Spusti kolekci nad thread-local heapem. Vraci pocet uvolnenych kontejneru.
+pub fn collect(roots: &GcRoots) -> usize {
+    let start = std::time::Instant::now();
+    // Seeds: registr + explicitni rooty + DOM bridge callbacky.
+    let mut seeds: Vec<Container> = HEAP.with(|h| {
+        let mut h = h.borrow_mut();
+        h.compact();
+        h.tracked.iter().filter_map(Tracked::upgrade).collect()
+    });
+    seeds.extend(roots.envs.iter().map(|e| Container::Env(Rc::clone(e))));
+    let mut root_edges = Edges::default();
+    roots.values.iter().for_each(|v| root_edges.value(v));
+    seeds.extend(root_edges.containers.iter().cloned());
+    // Mapu jen borrowneme - klon by zvysil strong_count callback envu.
+    let cb_guard = roots.callbacks.as_ref().map(|c| c.borrow());
+    let no_callbacks = HashMap::new();
+    let callbacks: &HashMap<usize, JsValue> = cb_guard.as_deref().unwrap_or(&no_callbacks);
+    let mut bridge = Edges::default();
+    callbacks.values().for_each(|v| bridge.value(v));
+    seeds.extend(bridge.containers.iter().cloned());
+
+    // 1. Scan set - uzaver seeds pres hrany. Kazdy kontejner = 1 nase kopie.
+    let mut nodes: Vec<Container> = Vec::new();
+    let mut index: HashMap<usize, usize> = HashMap::new();
+    let mut edges: Vec<Vec<usize>> = Vec::new();
+    let mut dom: Vec<Vec<Rc<Node>>> = Vec::new();
+    let mut opaque: Vec<bool> = Vec::new();
+    let mut weak: Vec<Vec<Ephemeron>> = Vec::new();
+    let mut work: Vec<usize> = Vec::new();
+    for s in seeds {
+        if let std::collections::hash_map::Entry::Vacant(e) = index.entry(s.addr()) {
+            e.insert(nodes.len());
+            work.push(nodes.len());
+            nodes.push(s);
+        }
+    }
+    edges.resize_with(nodes.len(), Vec::new);
+    dom.resize_with(nodes.len(), Vec::new);
+    opaque.resize(nodes.len(), false);
+    weak.resize_with(nodes.len(), Vec::new);
+    while let Some(i) = work.pop() {
+        let mut e = Edges::default();
+        if !nodes[i].edges(&mut e) {
+            opaque[i] = true;
+            continue;
+        }
+        let mut intern = |c: Container| match index.get(&c.addr()) {
+            Some(&j) => j,
+            None => {
+                let j = nodes.len();
+                index.insert(c.addr(), j);
+                nodes.push(c);
+                edges.push(Vec::new());
+                dom.push(Vec::new());
+                opaque.push(false);
+                weak.push(Vec::new());
+                work.push(j);
+                j
+            }
+        };
+        let targets: Vec<usize> = e.containers.into_iter().map(&mut intern).collect();
+        let ephemerons: Vec<Ephemeron> = e.ephemerons.into_iter()
+            .map(|(k, v)| (intern(k), v.containers.into_iter().map(&mut intern).collect(), v.dom))
+            .collect();
+        edges[i] = targets;
+        dom[i] = e.dom;
+        weak[i] = ephemerons;
+    }
+    // Docasne kopie z root/bridge Edges pryc - jinak by zkreslily strong_count.
+    let root_containers: Vec<usize> = root_edges.containers.drain(..)
+        .filter_map(|c| index.get(&c.addr()).copied())
+        .collect();
+    let bridge_targets: Vec<usize> = bridge.containers.drain(..)
+        .filter_map(|c| index.get(&c.addr()).copied())
+        .collect();
+
+    // 2. Trial deletion: gc_refs = strong - nase kopie - interni hrany.
+    let mut gc_refs: Vec<i64> = nodes.iter().map(|n| n.strong_count() as i64 - 1).collect();
+    for targets in &edges {
+        for &j in targets { gc_refs[j] -= 1; }
+    }
+    // Ephemeron klic i hodnota jsou skutecne Rc kopie v kolekci.
+    for (k, vals, _) in weak.iter().flatten() {
+        gc_refs[*k] -= 1;
+        for &j in vals { gc_refs[j] -= 1; }
+    }
+    // Callback mapa (DOM bridge) je interni hrana - rozhoduje dosazitelnost uzlu.
+    for &j in &bridge_targets { gc_refs[j] -= 1; }
+
+    // 3. Mark.
+    let mut marker = Marker {
+        nodes: &nodes,
+        edges: &edges,
+        dom: &dom,
+        weak: &weak,
+        index: &index,
+        callbacks,
+        live: vec![false; nodes.len()],
+        live_callbacks: HashSet::new(),
+        seen_dom: HashSet::new(),
+        stack: Vec::new(),
+    };
+    for i in 0..marker.nodes.len() {
+        if gc_refs[i] > 0 || opaque[i] { marker.stack.push(i); }
+    }
+    marker.stack.extend(root_containers);
+    for e in &roots.envs {
+        if let Some(&i) = index.get(&(Rc::as_ptr(e) as *const () as usize)) { marker.stack.push(i); }
+    }
+    for n in &root_edges.dom { marker.dom_tree(n); }
+    for n in &roots.dom { marker.dom_tree(n); }
+    marker.run();
+    marker.run_ephemerons();
+    let Marker { live, live_callbacks, .. } = marker;
+    drop(cb_guard);
+
+    // 4. Sweep - vyprazdni mrtve kontejnery, drop obsahu az po uvolneni borrowu.
+    let mut sink: Vec<Garbage> = Vec::new();
+    let mut freed = 0;
+    let mut survivors: Vec<Tracked> = Vec::new();
+    // Live weak kolekce: polozky s mrtvym klicem pryc (klic se vzapeti vyprazdni).
+    for (i, n) in nodes.iter().enumerate() {
+        if !live[i] || weak[i].is_empty() { continue; }
+        let dead: Vec<JsValue> = weak[i].iter()
+            .filter(|(k, _, _)| !live[*k])
+            .filter_map(|(k, _, _)| nodes[*k].to_value())
+            .collect();
+        match n {
+            Container::Map(r) => if let Ok(mut m) = r.try_borrow_mut() {
+                let removed: Vec<(JsValue, JsValue)> = dead.iter()
+                    .filter_map(|k| m.table.remove(k).map(|v| (k.clone(), v)))
+                    .collect();
+                sink.push(Garbage::Entries(removed));
+            },
+            Container::Set(r) => if let Ok(mut s) = r.try_borrow_mut() {
+                dead.iter().for_each(|k| { s.table.remove(k); });
+            },
+            _ => {}
+        }
+        sink.push(Garbage::Values(dead));
+    }
+    for (i, n) in nodes.iter().enumerate() {
+        if live[i] || !n.clear(&mut sink) {
+            survivors.push(n.downgrade());
+        } else {
+            freed += 1;
+        }
+    }
+    let mut released = 0;
+    if let Some(cbs) = &roots.callbacks {
+        let mut cbs = cbs.borrow_mut();
+        let dead: Vec<usize> = cbs.keys().copied().filter(|id| !live_callbacks.contains(id)).collect();
+        for id in dead {
+            if let Some(v) = cbs.remove(&id) { sink.push(Garbage::Values(vec![v])); }
+            released += 1;
+        }
+    }
+    drop(nodes);
+    drop(sink);
+
+    let pause = start.elapsed().as_micros() as u64;
+    HEAP.with(|h| {
+        let mut h = h.borrow_mut();
+        // Registr = preziviste + co se zapsalo behem kolekce (drop nic nezapisuje).
+        survivors.retain(Tracked::is_alive);
+        let live = survivors.len();
+        h.tracked = survivors;
+        h.compact_at = (live * 2).max(COMPACT_MIN);
+        let s = &mut h.stats;
+        s.collections += 1;
+        s.last_freed = freed;
+        s.total_freed += freed as u64;
+        s.last_scanned = live + freed;
+        s.live = live;
+        s.callbacks_released += released;
+        s.threshold = live.max(GC_MIN_THRESHOLD);
+        s.allocated_since = 0;
+        s.last_pause_us = pause;
+        s.total_pause_us += pause;
+    });
+    freed
+}
+
+impl super::Interpreter {
+    /// Explicitni rooty: vse co interpret drzi mimo JS heap a muze pozdeji
+    /// zavolat JS (timery, intervaly, rAF, fetch promise, observery, moduly).
+    pub fn gc_roots(&self) -> GcRoots {
+        let mut values: Vec<JsValue> = Vec::new();
+        for (_, _, cb, args) in self.task_queue.borrow().iter() {
+            values.push(cb.clone());
+            values.extend(args.iter().cloned());
+        }
+        for e in self.interval_queue.borrow().iter() {
+            values.push(e.cb.clone());
+            values.extend(e.args.iter().cloned());
+        }
+        values.extend(self.raf_callbacks.borrow().iter().map(|(_, cb)| cb.clone()));
+        values.extend(self.pending_fetches.borrow().iter()
+            .map(|f| JsValue::Object(Rc::clone(&f.promise_obj))));
+        for (cb, this) in self.pending_xhr_callbacks.borrow().iter() {
+            values.push(cb.clone());
+            values.push(this.clone());
+        }
+        values.extend(self.window_listeners.borrow().values().flatten().cloned());
+        let mut envs = vec![Rc::clone(&self.global)];
+        for m in self.modules.borrow().modules.values() {
+            let m = m.borrow();
+            envs.extend(m.env.iter().cloned());
+            values.extend(m.namespace.iter().chain(&m.promise).chain(&m.error).cloned());
+        }
+        values.extend(self.custom_elements.borrow().values().cloned());
+        values.extend(self.custom_element_instances.borrow().values().cloned());
+        for (_, cb, opts, _) in self.mutation_observers.borrow().iter() {
+            values.push(cb.clone());
+            values.push(opts.clone());
+        }
+        values.extend(self.resize_observers.borrow().iter().map(|o| o.callback.clone()));
+        values.extend(self.shadow_roots.borrow().values().map(|o| JsValue::Object(Rc::clone(o))));
+        let mut dom = vec![Rc::clone(&self.document.borrow().root)];
+        dom.extend(self.focused_element.borrow().iter().cloned());
+        GcRoots {
+            values,
+            envs,
+            dom,
+            callbacks: Some(Rc::clone(&self.event_callbacks)),
+        }
+    }
+
+    /// Kolekce nezavisle na thresholdu (devtools "Collect garbage", testy).
+    pub fn collect_garbage(&mut self) -> usize {
+        collect(&self.gc_roots())
+    }
+
+    /// Safepoint na konci host entry pointu - kolekce jen pri prekroceni
+    /// heap thresholdu a mimo vnorene volani (viz [`enter_scope`]).
+    pub fn gc_safepoint(&mut self) {
+        if should_collect() {
+            self.collect_garbage();
+        }
     }
 }
 
@@ -104,68 +791,90 @@ mod tests {
     use super::*;
 
     fn make_obj() -> Rc<RefCell<JsObject>> {
-        Rc::new(RefCell::new(JsObject::new()))
+        let o = Rc::new(RefCell::new(JsObject::new()));
+        track_object(&o);
+        o
     }
 
     #[test]
-    fn mark_reachable_simple() {
+    fn isolated_cycle_is_freed() {
         let a = make_obj();
         let b = make_obj();
-        a.borrow_mut().set("child".into(), JsValue::Object(Rc::clone(&b)));
-        let mut visited = HashSet::new();
-        mark_reachable(&a, &mut visited);
-        assert_eq!(visited.len(), 2);
+        a.borrow_mut().set("b".into(), JsValue::Object(Rc::clone(&b)));
+        b.borrow_mut().set("a".into(), JsValue::Object(Rc::clone(&a)));
+        let (wa, wb) = (Rc::downgrade(&a), Rc::downgrade(&b));
+        drop((a, b));
+        assert!(wa.upgrade().is_some()); // Rc sam cyklus neuvolni
+        let freed = collect(&GcRoots::default());
+        assert!(freed >= 2);
+        assert!(wa.upgrade().is_none());
+        assert!(wb.upgrade().is_none());
     }
 
     #[test]
-    fn mark_reachable_with_cycle_no_infinite_loop() {
+    fn externally_held_cycle_survives() {
         let a = make_obj();
         let b = make_obj();
         a.borrow_mut().set("b".into(), JsValue::Object(Rc::clone(&b)));
         b.borrow_mut().set("a".into(), JsValue::Object(Rc::clone(&a)));
-        let mut visited = HashSet::new();
-        mark_reachable(&a, &mut visited);
-        assert_eq!(visited.len(), 2);
+        let wb = Rc::downgrade(&b);
+        drop(b);
+        collect(&GcRoots::default());
+        // a drzi test (externi ref) -> b dosazitelne pres a.b
+        assert!(wb.upgrade().is_some());
+        assert!(matches!(a.borrow().get("b"), JsValue::Object(_)));
     }
 
     #[test]
-    fn collect_finds_no_cycle_when_reachable() {
+    fn explicit_root_keeps_cycle() {
         let a = make_obj();
-        let b = make_obj();
-        a.borrow_mut().set("b".into(), JsValue::Object(Rc::clone(&b)));
-        let mut gc = CycleCollector::new();
-        gc.add_candidate(&b);
-        let freed = gc.collect(&[Rc::clone(&a)]);
-        assert_eq!(freed, 0); // b reachable z a (= root)
+        a.borrow_mut().set("self".into(), JsValue::Object(Rc::clone(&a)));
+        let env = Environment::new_global();
+        env.borrow_mut().define("a", JsValue::Object(Rc::clone(&a)));
+        let wa = Rc::downgrade(&a);
+        drop(a);
+        let roots = GcRoots { envs: vec![Rc::clone(&env)], ..GcRoots::default() };
+        collect(&roots);
+        assert!(wa.upgrade().is_some());
     }
 
     #[test]
-    fn collect_detects_isolated_cycle() {
-        let a = make_obj();
-        let b = make_obj();
-        a.borrow_mut().set("b".into(), JsValue::Object(Rc::clone(&b)));
-        b.borrow_mut().set("a".into(), JsValue::Object(Rc::clone(&a)));
-        let mut gc = CycleCollector::new();
-        gc.add_candidate(&a);
-        gc.add_candidate(&b);
-        let roots: Vec<Rc<RefCell<JsObject>>> = vec![]; // a/b NOT in roots
-        let freed = gc.collect(&roots);
-        assert_eq!(freed, 2);
+    fn closure_env_cycle_is_freed() {
+        // env { f: function () {...} } kde f.env == env
+        let env = Environment::new_global();
+        let child = Environment::new_function_child(&env);
+        let f = JsValue::Function(JsFunc::User {
+            name: None,
+            params: Vec::new(),
+            body: super::super::FuncBody::Stmts(Vec::new()),
+            env: Rc::clone(&child),
+            is_arrow: false,
+            id: super::super::next_func_id(),
+        });
+        child.borrow_mut().define("f", f);
+        let w = Rc::downgrade(&child);
+        drop(child);
+        collect(&GcRoots { envs: vec![env], ..GcRoots::default() });
+        assert!(w.upgrade().is_none());
     }
 
     #[test]
-    fn break_cycle_clears_props() {
+    fn stats_updated_after_collect() {
+        let before = stats().collections;
         let a = make_obj();
-        let b = make_obj();
-        a.borrow_mut().set("b".into(), JsValue::Object(Rc::clone(&b)));
-        b.borrow_mut().set("a".into(), JsValue::Object(Rc::clone(&a)));
-        let initial_a_count = Rc::strong_count(&a);
-        break_cycle(&a);
-        // Po clear a.props - b nesi cyklus drzi a. Rc::strong_count(a) klesne.
-        assert!(Rc::strong_count(&a) <= initial_a_count);
+        a.borrow_mut().set("me".into(), JsValue::Object(Rc::clone(&a)));
+        drop(a);
+        collect(&GcRoots::default());
+        let s = stats();
+        assert_eq!(s.collections, before + 1);
+        assert!(s.last_freed >= 1);
+        assert_eq!(s.allocated_since, 0);
+        assert!(s.threshold >= GC_MIN_THRESHOLD);
     }
-}
 
-/// Drop unused tracker - placeholder pro statistiku interpretem.
-#[allow(dead_code)]
-fn _silence_hashmap() { let _ = HashMap::<String, ()>::new(); }
+    #[test]
+    fn scope_blocks_should_collect() {
+        let _scope = enter_scope();
+        assert!(!should_collect());
+    }
+}
@@ -0,0 +1,413 @@
+//! Generatory jako pozastavitelne ramce (`function*`, `async function*`)
+//! + lazy iterator protokol pro for-of, destrukturovani a `yield*`.
+//!
+//! Kazde zavolani generator funkce vytvori `GeneratorFrame` se stackful
+//! korutinou (corosensei). Telo bezi beznym tree-walk interpretem na vlastnim
+//! zasobniku; `yield` korutinu pozastavi a preda hodnotu do `next()`. Diky tomu
+//! funguji nekonecne generatory, obousmerne `next(v)`, `return(v)` / `throw(e)`
+//! vcetne `finally` a vedlejsi efekty mezi yieldy probehnou az pri
+//! odpovidajicim `next()`.
+//!
+//! Korutina vlastni melkou kopii interpretu (`Interpreter::clone` sdili heap
+//! stav pres Rc), takze se `&mut Interpreter` nedrzi pres suspend. Stav
+//! behu (ramce pro `Error.stack`, pozice, strict rezim, rozpocet) je take
+//! za Rc, takze telo generatoru vidi ramce a limity volajiciho `next()`.
+//!
+//! Zasobnik ramce zacina maly (`GENERATOR_STACK_SIZE`) a roste po segmentech:
+//! kdyz pri volani funkce zbyva mene nez `STACK_RED_ZONE`, volani pokracuje
+//! na novem segmentu (`corosensei::on_stack`), ktery se po navratu uvolni.
+
+use super::*;
+use corosensei::stack::{DefaultStack, Stack};
+use corosensei::{Coroutine, CoroutineResult, Yielder};
+
+/// Pocatecni zasobnik ramce - vetsina generatoru nerekurzi hluboko.
+const GENERATOR_STACK_SIZE: usize = 256 * 1024;
+/// Pri mene volnem miste pokracuje volani funkce na novem segmentu.
+const STACK_RED_ZONE: usize = 96 * 1024;
+/// Velikost dalsiho segmentu (tree-walk rekurze je hluboka; main thread bezi
+/// s 256 MB). mmap jen rezervuje adresy, stranky se alokuji az pri dotyku.
+const STACK_SEGMENT_SIZE: usize = 1024 * 1024;
+
+/// Zpusob obnoveni pozastaveneho generatoru.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResumeKind {
+    /// `gen.next(v)` - `yield` vrati `v`
+    Next,
+    /// `gen.return(v)` - unwind tela (finally bezi), vysledek `{ value: v, done: true }`
+    Return,
+    /// `gen.throw(e)` - `yield` vyhodi `e`
+    Throw,
+}
+
+/// Vstup korutiny pri obnoveni: (zpusob, odeslana hodnota).
+type ResumeInput = (ResumeKind, JsValue);
+pub(super) type GenYielder = Yielder<ResumeInput, JsValue>;
+type GenCoroutine = Coroutine<ResumeInput, JsValue, Result<JsValue, JsError>, DefaultStack>;
+
+/// Stav jedne instance generatoru.
+pub struct GeneratorFrame {
+    /// Korutina s telem. None = dobehl (return / vyjimka / return() pred startem).
+    co: Option<GenCoroutine>,
+    /// Telo prave bezi - `next()` zevnitr vlastniho tela je TypeError.
+    running: bool,
+    /// `async function*` - kroky vraci Promise.
+    is_async: bool,
+}
+
+impl GeneratorFrame {
+    /// Pripravi ramec - telo se spusti az prvnim `next()`.
+    /// `env` uz ma nabindovane parametry, `this` a `arguments`.
+    pub(super) fn new(
+        interp: &Interpreter,
+        body: Vec<Stmt>,
+        env: Rc<RefCell<Env>>,
+        is_async: bool,
+    ) -> Result<Self, JsError> {
+        Self::with_mode(interp, body, env, is_async, false)
+    }
+
+    /// Ramec tela ES modulu s top-level await: `await` na pending promise
+    /// ramec pozastavi a preda promise ven (viz `module_loader`).
+    pub(super) fn for_module(interp: &Interpreter, body: Vec<Stmt>, env: Rc<RefCell<Env>>) -> Result<Self, JsError> {
+        Self::with_mode(interp, body, env, false, true)
+    }
+
+    fn with_mode(
+        interp: &Interpreter,
+        body: Vec<Stmt>,
+        env: Rc<RefCell<Env>>,
+        is_async: bool,
+        module_await: bool,
+    ) -> Result<Self, JsError> {
+        let stack = DefaultStack::new(GENERATOR_STACK_SIZE)
+            .map_err(|e| JsError::Runtime(format!("generator: nelze alokovat zasobnik: {e}")))?;
+        let bounds = (stack.limit().get(), stack.base().get());
+        let mut fork = interp.clone();
+        fork.module_await = module_await;
+        fork.script_await = false;
+        fork.gen_stack = Some(bounds);
+        let co = Coroutine::with_stack(stack, move |yielder: &GenYielder, _first: ResumeInput| {
+            fork.gen_yielder = Some(yielder as *const GenYielder);
+            match fork.exec_stmts(&body, &env) {
+                Ok(Some(Signal::Return(v))) => Ok(v),
+                Ok(_) => Ok(JsValue::Undefined),
+                Err(JsError::GeneratorReturn(v)) => Ok(v),
+                Err(e) => Err(e),
+            }
+        });
+        Ok(GeneratorFrame { co: Some(co), running: false, is_async })
+    }
+}
+
+/// Postavi generator objekt nad ramcem: `next` / `return` / `throw` a
+/// `[Symbol.iterator]` (u async `[Symbol.asyncIterator]`) vracejici sam sebe.
+pub(super) fn make_generator_object(frame: GeneratorFrame) -> JsValue {
+    let is_async = frame.is_async;
+    let frame = Rc::new(RefCell::new(frame));
+    let obj = Rc::new(RefCell::new(JsObject::new()));
+    {
+        let mut o = obj.borrow_mut();
+        for (name, kind) in [("next", ResumeKind::Next), ("return", ResumeKind::Return), ("throw", ResumeKind::Throw)] {
+            o.set(name.into(), JsValue::Function(JsFunc::GeneratorResume { frame: Rc::clone(&frame), kind }));
+        }
+        // Weak - silna reference by udelala cyklus objekt -> closure -> objekt
+        let weak = Rc::downgrade(&obj);
+        let self_fn = native("(generator)[Symbol.iterator]", move |_| {
+            Ok(weak.upgrade().map(JsValue::Object).unwrap_or(JsValue::Undefined))
+        });
+        let key = if is_async { symbols::ASYNC_ITERATOR } else { symbols::ITERATOR };
+        o.set(key.into(), self_fn);
+        if !is_async {
+            // Iterator helpers (ES2025) - marker pres call_method special-case dispatch.
+            o.set("__iterator_helpers__".into(), JsValue::Bool(true));
+        }
+    }
+    JsValue::Object(obj)
+}
+
+/// Jeden krok ramce - vraci iter result `{ value, done }`.
+pub(super) fn step_frame(frame: &Rc<RefCell<GeneratorFrame>>, kind: ResumeKind, sent: JsValue) -> EvalResult {
+    let mut co = {
+        let mut f = frame.borrow_mut();
+        if f.running {
+            return Err(JsError::Runtime("TypeError: Generator is already running".into()));
+        }
+        match f.co.take() {
+            Some(co) => co,
+            // Dobehly generator: next -> done, return -> done s hodnotou, throw -> vyhod
+            None => return match kind {
+                ResumeKind::Next => Ok(make_iter_result(JsValue::Undefined, true)),
+                ResumeKind::Return => Ok(make_iter_result(sent, true)),
+                ResumeKind::Throw => Err(JsError::Thrown(sent)),
+            },
+        }
+    };
+    // return()/throw() pred prvnim next(): telo se vubec nespusti
+    if !co.started() && kind != ResumeKind::Next {
+        return match kind {
+            ResumeKind::Throw => Err(JsError::Thrown(sent)),
+            _ => Ok(make_iter_result(sent, true)),
+        };
+    }
+    frame.borrow_mut().running = true;
+    let result = co.resume((kind, sent));
+    let mut f = frame.borrow_mut();
+    f.running = false;
+    match result {
+        CoroutineResult::Yield(v) => {
+            f.co = Some(co);
+            Ok(make_iter_result(v, false))
+        }
+        CoroutineResult::Return(Ok(v)) => Ok(make_iter_result(v, true)),
+        CoroutineResult::Return(Err(e)) => Err(e),
+    }
+}
+
+/// Otevrena iterace (for-of, destrukturovani, `yield*`).
+pub(super) enum ActiveIter {
+    /// Snapshot vestavene kolekce (Array/Str/Map/Set) - bez vedlejsich efektu.
+    /// U for-await se Promise prvky rozbaluji az pri kroku.
+    Values { values: std::vec::IntoIter<JsValue>, is_async: bool },
+    /// Iterator objekt s `next()` - hodnoty se tahaji po jedne.
+    Protocol {
+        iterator: JsValue,
+        next_fn: JsValue,
+        /// for-await: kroky i hodnoty se rozbaluji z Promise
+        is_async: bool,
+        done: bool,
+    },
+}
+
+impl Interpreter {
+    /// Dochazi zasobnik ramce generatoru? Mimo ramec (nebo na cizim zasobniku,
+    /// napr. fork modulu volany z microtasku) vzdy false.
+    pub(super) fn stack_headroom_low(&self) -> bool {
+        let Some((limit, base)) = self.gen_stack else { return false };
+        let marker = 0u8;
+        let sp = std::hint::black_box(&marker) as *const u8 as usize;
+        (limit..base).contains(&sp) && sp - limit < STACK_RED_ZONE
+    }
+
+    /// Vykona `f` na novem segmentu zasobniku; po navratu se segment uvolni.
+    /// `yield` uvnitr segmentu funguje - korutina se pozastavi i s nim.
+    pub(super) fn on_new_stack_segment(&mut self, f: impl FnOnce(&mut Self) -> EvalResult) -> EvalResult {
+        let Ok(segment) = DefaultStack::new(STACK_SEGMENT_SIZE) else {
+            return Err(JsError::Runtime("RangeError: Maximum call stack size exceeded".into()));
+        };
+        let outer = self.gen_stack.replace((segment.limit().get(), segment.base().get()));
+        let result = corosensei::on_stack(segment, || f(self));
+        self.gen_stack = outer;
+        result
+    }
+
+    /// `gen.next(v)` / `gen.return(v)` / `gen.throw(e)`.
+    pub(super) fn resume_generator(
+        &mut self,
+        frame: &Rc<RefCell<GeneratorFrame>>,
+        kind: ResumeKind,
+        args: Vec<JsValue>,
+    ) -> EvalResult {
+        let sent = args.into_iter().next().unwrap_or(JsValue::Undefined);
+        let is_async = frame.borrow().is_async;
+        let step = step_frame(frame, kind, sent);
+        if !is_async {
+            return step;
+        }
+        // async generator: `yield promise` preda rozbalenou hodnotu, krok je Promise
+        match step {
+            Ok(res) => {
+                let value = self.get_prop(&res, "value")?;
+                let done = self.get_prop(&res, "done")?.is_truthy();
+                match unwrap_promise_result(value) {
+                    Ok(v) => Ok(make_settled_promise("fulfilled", make_iter_result(v, done))),
+                    Err(reason) => Ok(make_settled_promise("rejected", reason)),
+                }
+            }
+            Err(JsError::Thrown(v)) => Ok(make_settled_promise("rejected", v)),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Pozastavi ramec generatoru s hodnotou `val` a vrati, jak byl obnoven.
+    fn generator_suspend(&self, val: JsValue) -> Result<ResumeInput, JsError> {
+        let Some(yielder) = self.gen_yielder else {
+            return Err(JsError::Runtime("yield lze pouzit jen v generator funkci".into()));
+        };
+        // SAFETY: gen_yielder nastavuje jen GeneratorFrame::new v interpretu,
+        // ktery vlastni closure korutiny. Yielder zije po celou dobu behu tela
+        // a tento interpret bezi vyhradne na zasobniku te korutiny.
+        let yielder = unsafe { &*yielder };
+        Ok(yielder.suspend(val))
+    }
+
+    /// `yield val` - vraci hodnotu z `next(v)`, u `throw(e)` vyhodi `e`,
+    /// u `return(v)` unwinduje telo pres `JsError::GeneratorReturn`.
+    pub(super) fn generator_yield(&mut self, val: JsValue) -> EvalResult {
+        match self.generator_suspend(val)? {
+            (ResumeKind::Next, v) => Ok(v),
+            (ResumeKind::Throw, e) => Err(JsError::Thrown(e)),
+            (ResumeKind::Return, v) => Err(JsError::GeneratorReturn(v)),
+        }
+    }
+
+    /// `yield* iterable` - deleguje next/throw/return na vnitrni iterator.
+    /// Vysledkem vyrazu je `value` z posledniho (done) kroku.
+    pub(super) fn yield_delegate(&mut self, iterable: JsValue) -> EvalResult {
+        let mut it = self.open_iterator(iterable, false)?;
+        let (iterator, next_fn) = match &mut it {
+            ActiveIter::Protocol { iterator, next_fn, .. } => (iterator.clone(), next_fn.clone()),
+            ActiveIter::Values { values, .. } => {
+                // Vestavena kolekce nema throw/return - jen preposila hodnoty
+                for v in values.by_ref() {
+                    self.generator_yield(v)?;
+                }
+                return Ok(JsValue::Undefined);
+            }
+        };
+        let mut received = (ResumeKind::Next, JsValue::Undefined);
+        loop {
+            let step = match received {
+                (ResumeKind::Next, v) => self.call_function(next_fn.clone(), vec![v], Some(iterator.clone()))?,
+                (ResumeKind::Throw, e) => {
+                    let throw_fn = self.get_prop(&iterator, "throw")?;
+                    if !matches!(throw_fn, JsValue::Function(_)) {
+                        let _ = self.iter_close(&mut it);
+                        return Err(JsError::Runtime("TypeError: The iterator does not provide a 'throw' method".into()));
+                    }
+                    self.call_function(throw_fn, vec![e], Some(iterator.clone()))?
+                }
+                (ResumeKind::Return, v) => {
+                    let return_fn = self.get_prop(&iterator, "return")?;
+                    if !matches!(return_fn, JsValue::Function(_)) {
+                        return Err(JsError::GeneratorReturn(v));
+                    }
+                    let step = self.call_function(return_fn, vec![v], Some(iterator.clone()))?;
+                    let step = unwrap_promise_result(step).map_err(JsError::Thrown)?;
+                    if self.get_prop(&step, "done")?.is_truthy() {
+                        return Err(JsError::GeneratorReturn(self.get_prop(&step, "value")?));
+                    }
+                    step
+                }
+            };
+            // async iterator vraci kroky jako Promise
+            let step = unwrap_promise_result(step).map_err(JsError::Thrown)?;
+            let value = self.get_prop(&step, "value")?;
+            if self.get_prop(&step, "done")?.is_truthy() {
+                return Ok(value);
+            }
+            received = self.generator_suspend(value)?;
+        }
+    }
+
+    // ─── Lazy iterator protokol ──────────────────────────────────────────────
+
+    /// Otevre iteraci nad hodnotou. `is_async` = for-await (preferuje
+    /// `[Symbol.asyncIterator]` a rozbaluje Promise).
+    pub(super) fn open_iterator(&mut self, val: JsValue, is_async: bool) -> Result<ActiveIter, JsError> {
+        if is_weak_collection(&val) {
+            return Err(JsError::Runtime("for...of: hodnota neni iterovatelna".into()));
+        }
+        if matches!(val, JsValue::Map(_) | JsValue::Set(_)) {
+            // Zivy iterator - pridane/smazane polozky behem cyklu se projevi.
+            let kind = if matches!(val, JsValue::Map(_)) { CollectionIterKind::Entries } else { CollectionIterKind::Values };
+            let iterator = make_collection_iterator(&val, kind);
+            let next_fn = self.get_prop(&iterator, "next")?;
+            return Ok(ActiveIter::Protocol { iterator, next_fn, is_async, done: false });
+        }
+        if matches!(val, JsValue::Array(_) | JsValue::Str(_)) {
+            let values = self.collect_iterable(val)?.into_iter();
+            return Ok(ActiveIter::Values { values, is_async });
+        }
+        if let JsValue::Object(o) = &val {
+            let mut method = JsValue::Undefined;
+            if is_async {
+                method = o.borrow().get(symbols::ASYNC_ITERATOR);
+            }
+            if matches!(method, JsValue::Undefined) {
+                method = o.borrow().get(symbols::ITERATOR);
+            }
+            if matches!(method, JsValue::Undefined) && !is_async
+                && !matches!(o.borrow().get(symbols::ASYNC_ITERATOR), JsValue::Undefined)
+            {
+                // Async iterable jde jen pres for await (sync next() by vracel Promise navzdy)
+                return Err(JsError::Runtime("TypeError: for...of: async iterable vyzaduje for await".into()));
+            }
+            let iterator = if matches!(method, JsValue::Undefined) {
+                // Objekt s next() (iterator helper, rucni iterator) - iteruj primo
+                val.clone()
+            } else {
+                self.call_function(method, vec![], Some(val.clone()))?
+            };
+            let next_fn = self.get_prop(&iterator, "next")?;
+            if !matches!(next_fn, JsValue::Undefined) {
+                return Ok(ActiveIter::Protocol { iterator, next_fn, is_async, done: false });
+            }
+        }
+        Err(JsError::Runtime("for...of: hodnota neni iterovatelna".into()))
+    }
+
+    /// Dalsi hodnota iterace, None = konec.
+    pub(super) fn iter_step(&mut self, it: &mut ActiveIter) -> Result<Option<JsValue>, JsError> {
+        match it {
+            ActiveIter::Values { values, is_async } => match values.next() {
+                Some(v) if *is_async => unwrap_promise_result(v).map(Some).map_err(JsError::Thrown),
+                v => Ok(v),
+            },
+            ActiveIter::Protocol { done: true, .. } => Ok(None),
+            ActiveIter::Protocol { iterator, next_fn, is_async, done } => {
+                let step = self.call_function(next_fn.clone(), vec![], Some(iterator.clone()));
+                let step = match step {
+                    Ok(s) => s,
+                    Err(e) => { *done = true; return Err(e); }
+                };
+                let step = if *is_async {
+                    unwrap_promise_result(step).map_err(JsError::Thrown)?
+                } else { step };
+                if self.get_prop(&step, "done")?.is_truthy() {
+                    *done = true;
+                    return Ok(None);
+                }
+                let value = self.get_prop(&step, "value")?;
+                if *is_async {
+                    return unwrap_promise_result(value).map(Some).map_err(JsError::Thrown);
+                }
+                Ok(Some(value))
+            }
+        }
+    }
+
+    /// Predcasne ukonceni iterace (break, return, vyjimka) - zavola `return()`.
+    pub(super) fn iter_close(&mut self, it: &mut ActiveIter) -> Result<(), JsError> {
+        if let ActiveIter::Protocol { iterator, done, .. } = it {
+            if *done { return Ok(()); }
+            *done = true;
+            let return_fn = self.get_prop(iterator, "return")?;
+            if matches!(return_fn, JsValue::Function(_)) {
+                self.call_function(return_fn, vec![], Some(iterator.clone()))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Vytahne nejvyse `n` hodnot z iterovatelneho objektu a iterator zavre.
+    /// Neiterovatelny objekt = prazdny vysledek (destrukturovani `[a] = {}`).
+    pub(super) fn take_from_iterable(&mut self, val: JsValue, n: usize) -> Result<Vec<JsValue>, JsError> {
+        if let JsValue::Object(o) = &val {
+            let b = o.borrow();
+            if matches!(b.get(symbols::ITERATOR), JsValue::Undefined) && matches!(b.get("next"), JsValue::Undefined) {
+                return Ok(vec![]);
+            }
+        }
+        let mut it = self.open_iterator(val, false)?;
+        let mut out = Vec::new();
+        while out.len() < n {
+            match self.iter_step(&mut it)? {
+                Some(v) => out.push(v),
+                None => return Ok(out),
+            }
+        }
+        self.iter_close(&mut it)?;
+        Ok(out)
+    }
+}
@@ -1,29 +1,32 @@
-/// Free utility funkce interpreteru - bez self.
-///
-/// Skupiny:
-/// - is_internal_key, is_in_proto_chain - properties checks
-/// - native - shortcut pro vytvoreni JsValue::Function(JsFunc::Native)
-/// - build_class_chain - instanceof podpora
-/// - json_* - JSON serializace/deserializace
-/// - now_ms, make_date_object, get_date_ms, ms_to_parts, days_to_date, is_leap - Date
-/// - make_settled_promise, get_promise_state, unwrap_promise_result - Promise
-/// - collect_iterable_values - sbira hodnoty z Array/Set/Map/Str
-/// - radix_string, format_number_locale - Number formatovani
-/// - bigdecimal_pow - BigNumber umocneni
-/// - js_regex_to_rust, make_regex_object, get_regex_parts, regex_* - RegExp
-/// - make_array_iterator - iterator factory
+//! Free utility funkce interpreteru - bez self.
+//!
+//! Skupiny:
+//! - is_internal_key, is_in_proto_chain - properties checks
+//! - native - shortcut pro vytvoreni JsValue::Function(JsFunc::Native)
+//! - build_class_chain - instanceof podpora
+//! - json_* - JSON serializace/deserializace
+//! - now_ms, make_date_object, get_date_ms, ms_to_parts, days_to_date, is_leap - Date
+//! - make_settled_promise, get_promise_state, unwrap_promise_result - Promise
+//! - collect_iterable_values - sbira hodnoty z Array/Set/Map/Str
+//! - radix_string, format_number_locale - Number formatovani
+//! - bigdecimal_pow - BigNumber umocneni
+//! - js_regex_to_rust, make_regex_object, get_regex_parts, regex_* - RegExp
+//! - make_array_iterator - iterator factory
+//! - make_iter_result - iterator krok `{ value, done }`
 
 use std::cell::RefCell;
 use std::rc::Rc;
 use bigdecimal::{BigDecimal, One};
 use regex::Regex;
-use super::{JsValue, JsFunc, JsObject};
+use super::{FetchOutcome, JsValue, JsFunc, JsObject, symbols};
+use super::keyed_table::Cursor;
 
 // ─── Properties checks ───────────────────────────────────────────────────
 
 /// Vrati true kdyz klic je interni (`__key__` format - napr. `__class_chain__`).
+/// `__@@...` byval tvar symbolovych klicu - dnes je to obycejny retezec.
 pub fn is_internal_key(k: &str) -> bool {
-    k.len() >= 4 && k.starts_with("__") && k.ends_with("__")
+    k.len() >= 4 && k.starts_with("__") && k.ends_with("__") && !k.starts_with("__@@")
 }
 
 /// Zkontroluje jestli `proto` je v prototypovem retezci `target`.
@@ -159,7 +162,81 @@ pub fn make_array_iterator(values: Vec<JsValue>) -> JsValue {
 
     let mut obj = JsObject::new();
     obj.set("next".into(), next_fn);
-    obj.set("Symbol.iterator".into(), self_iter);
+    obj.set(symbols::ITERATOR.into(), self_iter);
+    JsValue::Object(Rc::new(RefCell::new(obj)))
+}
+
+// ─── Map/Set iteratory ───────────────────────────────────────────────────
+
+/// Co vraci Map/Set iterator (`keys()` / `values()` / `entries()`).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CollectionIterKind {
+    Keys,
+    Values,
+    Entries,
+}
+
+/// WeakMap/WeakSet - neiterovatelne, bez `size`.
+pub fn is_weak_collection(v: &JsValue) -> bool {
+    match v {
+        JsValue::Map(m) => m.borrow().weak,
+        JsValue::Set(s) => s.borrow().weak,
+        _ => false,
+    }
+}
+
+/// Kurzor do Map/Set (None pro jinou hodnotu).
+pub fn collection_cursor(coll: &JsValue) -> Option<Cursor> {
+    match coll {
+        JsValue::Map(m) => Some(m.borrow_mut().table.cursor()),
+        JsValue::Set(s) => Some(s.borrow_mut().table.cursor()),
+        _ => None,
+    }
+}
+
+/// Dalsi (klic, hodnota) od kurzoru; u Set je hodnota = klic.
+pub fn collection_next(coll: &JsValue, cursor: &Cursor) -> Option<(JsValue, JsValue)> {
+    match coll {
+        JsValue::Map(m) => m.borrow().table.next_at(cursor).map(|(k, v)| (k.clone(), v.clone())),
+        JsValue::Set(s) => s.borrow().table.next_at(cursor).map(|(k, _)| (k.clone(), k.clone())),
+        _ => None,
+    }
+}
+
+/// Zivy iterator nad Map/Set - polozky pridane behem iterace navstivi,
+/// smazane preskoci (kurzor v `KeyedTable`). Po `done` zustane vycerpany.
+pub fn make_collection_iterator(coll: &JsValue, kind: CollectionIterKind) -> JsValue {
+    let Some(cursor) = collection_cursor(coll) else { return make_array_iterator(Vec::new()) };
+    let state = Rc::new(RefCell::new(Some((coll.clone(), cursor))));
+    collection_iterator_object(state, kind)
+}
+
+type CollectionIterState = Rc<RefCell<Option<(JsValue, Cursor)>>>;
+
+fn collection_iterator_object(state: CollectionIterState, kind: CollectionIterKind) -> JsValue {
+    let st = Rc::clone(&state);
+    let next_fn = native("(collection iterator).next", move |_| {
+        let item = st.borrow().as_ref().and_then(|(coll, cursor)| collection_next(coll, cursor));
+        let Some((k, v)) = item else {
+            // Vycerpany iterator pusti kolekci - dalsi polozky uz neuvidi.
+            st.borrow_mut().take();
+            return Ok(make_iter_result(JsValue::Undefined, true));
+        };
+        let value = match kind {
+            CollectionIterKind::Keys => k,
+            CollectionIterKind::Values => v,
+            CollectionIterKind::Entries => JsValue::Array(Rc::new(RefCell::new(vec![k, v]))),
+        };
+        Ok(make_iter_result(value, false))
+    });
+    // Sdileny stav: `it[Symbol.iterator]()` pokracuje ve stejne iteraci.
+    let self_iter = native("(collection iterator)[Symbol.iterator]", move |_| {
+        Ok(collection_iterator_object(Rc::clone(&state), kind))
+    });
+    let mut obj = JsObject::new();
+    obj.set("__iterator_helpers__".into(), JsValue::Bool(true));
+    obj.set("next".into(), next_fn);
+    obj.set(symbols::ITERATOR.into(), self_iter);
     JsValue::Object(Rc::new(RefCell::new(obj)))
 }
 
@@ -187,10 +264,12 @@ pub fn build_class_chain(class_name: &str, super_val: Option<&JsValue>) -> Strin
 pub fn collect_iterable_values(val: &JsValue) -> Vec<JsValue> {
     match val {
         JsValue::Array(a) => a.borrow().clone(),
-        JsValue::Set(s)   => s.borrow().values.clone(),
-        JsValue::Map(m)   => m.borrow().entries.iter()
+        JsValue::Set(s)   => s.borrow().values().cloned().collect(),
+        JsValue::Map(m)   => m.borrow().entries()
             .map(|(k,_)| k.clone()).collect(),
         JsValue::Str(s)   => s.chars().map(|c| JsValue::Str(c.to_string())).collect(),
+        JsValue::Object(_) => super::builtins_typed_arrays::typed_view_of(val)
+            .map(|view| view.to_values()).unwrap_or_default(),
         _ => Vec::new(),
     }
 }
@@ -238,20 +317,22 @@ fn json_stringify_inner(val: &JsValue, indent: usize, depth: usize, seen: &mut s
             }
         }
         JsValue::Object(o) => {
+            // Nativni toJSON (Temporal, DOMRect) - JS funkce se tu volat nedaji
+            let to_json = o.borrow().get("toJSON");
+            if let JsValue::Function(JsFunc::Native(_, f)) = to_json {
+                return json_stringify_inner(&f(Vec::new())?, indent, depth, seen);
+            }
             let ptr = Rc::as_ptr(o) as usize;
             if !seen.insert(ptr) {
                 return Err("TypeError: Converting circular structure to JSON".into());
             }
             let mut pairs: Vec<String> = Vec::new();
             let keys: Vec<String> = {
-                let borrowed = o.borrow();
-                let mut ks: Vec<String> = borrowed.props.keys()
-                    .filter(|k| !is_internal_key(k)).cloned().collect();
-                ks.sort();
-                ks
+                o.borrow().ordered_keys(true)
             };
             for k in &keys {
-                let v = o.borrow().props.get(k).cloned().unwrap_or(JsValue::Undefined);
+                // get() - vlastni klic, ale i prvek typed array (bez zaznamu v props)
+                let v = o.borrow().get(k);
                 if let Some(serialized) = json_stringify_inner(&v, indent, depth + 1, seen)? {
                     pairs.push(format!("{}:{}", json_escape_str(k), serialized));
                 }
@@ -406,7 +487,7 @@ fn json_parse_object(chars: &[char], start: usize) -> Result<(JsValue, usize), S
         if chars.get(i) != Some(&':') { return Err(format!("Ocekavano ':' na pozici {i}")); }
         i += 1;
         let (val, end2) = json_parse_value(chars, i)?;
-        obj.set(key, val);
+        obj.set(key.into(), val);
         i = json_skip_ws(chars, end2);
         match chars.get(i) {
             Some(',') => i += 1,
@@ -568,6 +649,14 @@ pub fn make_iterator_from_values(values: Vec<JsValue>) -> JsValue {
     JsValue::Object(Rc::new(RefCell::new(iter_obj)))
 }
 
+/// Iterator krok `{ value, done }` (vysledek `next()` / `return()` / `throw()`).
+pub fn make_iter_result(value: JsValue, done: bool) -> JsValue {
+    let mut r = JsObject::new();
+    r.set("value".into(), value);
+    r.set("done".into(), JsValue::Bool(done));
+    JsValue::Object(Rc::new(RefCell::new(r)))
+}
+
 pub fn make_settled_promise(state: &str, value: JsValue) -> JsValue {
     let mut obj = JsObject::new();
     obj.set("__promise_state__".into(), JsValue::Str(state.into()));
@@ -575,6 +664,27 @@ pub fn make_settled_promise(state: &str, value: JsValue) -> JsValue {
     JsValue::Object(Rc::new(RefCell::new(obj)))
 }
 
+/// Response objekt (`fetch` i `new Response(...)`) - text/json/arrayBuffer
+/// resi dispatch metod nad `__response__`, bajty tela nese `__body_bytes__`.
+pub fn make_response(url: &str, status: u16, status_text: String, body: Vec<u8>, headers: Vec<(String, String)>) -> JsValue {
+    let mut response = JsObject::new();
+    response.set("__response__".into(), JsValue::Bool(true));
+    response.set("__class_chain__".into(), JsValue::Str("Response".into()));
+    response.set("__body__".into(), JsValue::Str(String::from_utf8_lossy(&body).into_owned()));
+    response.set("__body_bytes__".into(), super::builtins_typed_arrays::array_buffer_value(body));
+    response.set("url".into(), JsValue::Str(url.to_string()));
+    response.set("status".into(), JsValue::Number(status as f64));
+    response.set("ok".into(), JsValue::Bool((200..300).contains(&status)));
+    response.set("statusText".into(), JsValue::Str(status_text));
+    let mut hdr_obj = JsObject::new();
+    for (k, v) in headers {
+        hdr_obj.set(k.to_lowercase().into(), JsValue::Str(v));
+    }
+    hdr_obj.set("__headers__".into(), JsValue::Bool(true));
+    response.set("headers".into(), JsValue::Object(Rc::new(RefCell::new(hdr_obj))));
+    JsValue::Object(Rc::new(RefCell::new(response)))
+}
+
 pub fn get_promise_state(val: &JsValue) -> Option<(String, JsValue)> {
     if let JsValue::Object(o) = val {
         let b = o.borrow();
@@ -926,6 +1036,13 @@ pub fn url_encode(s: &str) -> String {
     out
 }
 
+/// Telo odpovedi jako bajty (binarni obsah - obrazky, `.wasm` - neprezije `into_string`).
+fn read_body(resp: ureq::Response) -> Vec<u8> {
+    let mut body = Vec::new();
+    let _ = std::io::Read::read_to_end(&mut resp.into_reader(), &mut body);
+    body
+}
+
 /// Real HTTP request pres ureq (blocking).
 /// Vraci (status, status_text, body, headers).
 pub fn perform_http_request(
@@ -933,7 +1050,7 @@ pub fn perform_http_request(
     method: &str,
     headers: &[(String, String)],
     body: Option<&str>,
-) -> Result<(u16, String, String, Vec<(String, String)>), String> {
+) -> FetchOutcome {
     let agent = ureq::AgentBuilder::new()
         .timeout(std::time::Duration::from_secs(30))
         .build();
@@ -969,8 +1086,7 @@ pub fn perform_http_request(
                     resp_headers.push((h.clone(), v.to_string()));
                 }
             }
-            let resp_body = resp.into_string().unwrap_or_default();
-            Ok((status, status_text, resp_body, resp_headers))
+            Ok((status, status_text, read_body(resp), resp_headers))
         }
         Err(ureq::Error::Status(code, resp)) => {
             // HTTP error response (4xx/5xx) - vratime jako successful s daným status
@@ -982,8 +1098,7 @@ pub fn perform_http_request(
                     resp_headers.push((h.clone(), v.to_string()));
                 }
             }
-            let resp_body = resp.into_string().unwrap_or_default();
-            Ok((code, status_text, resp_body, resp_headers))
+            Ok((code, status_text, read_body(resp), resp_headers))
         }
         Err(e) => Err(format!("{e}")),
     }
@@ -997,181 +1112,6 @@ pub fn random_u32() -> u32 {
     (s >> 32) as u32
 }
 
-// ─── Intl - real implementace pres ICU4X ──────────────────────────────────
-
-/// Formatuje cislo podle locale pres ICU4X FixedDecimalFormatter.
-/// Fallback na manualni implementaci kdyz locale nelze parsovat.
-pub fn format_number_intl(n: f64, locale: &str) -> String {
-    use icu::locid::Locale;
-    use icu::decimal::FixedDecimalFormatter;
-    use fixed_decimal::FixedDecimal;
-    use std::str::FromStr;
-
-    if n.is_nan()      { return "NaN".into(); }
-    if n.is_infinite() { return if n > 0.0 { "∞".into() } else { "-∞".into() }; }
-
-    // Parse locale
-    let loc = match Locale::from_str(locale) {
-        Ok(l) => l,
-        Err(_) => return format_number_locale_fallback(n, locale),
-    };
-
-    // Konverze f64 -> FixedDecimal pres string (zachovava presnost)
-    let s = format!("{n}");
-    let fd = match FixedDecimal::from_str(&s) {
-        Ok(d) => d,
-        Err(_) => return format_number_locale_fallback(n, locale),
-    };
-
-    let formatter = match FixedDecimalFormatter::try_new(
-        &loc.into(),
-        Default::default(),
-    ) {
-        Ok(f) => f,
-        Err(_) => return format_number_locale_fallback(n, locale),
-    };
-
-    formatter.format(&fd).to_string()
-}
-
-/// Manualni fallback formatter pokud ICU selze.
-fn format_number_locale_fallback(n: f64, locale: &str) -> String {
-    let (thousand_sep, decimal_sep) = match locale {
-        s if s.starts_with("cs") || s.starts_with("sk") || s.starts_with("fr")
-            || s.starts_with("pl") || s.starts_with("ru") => (' ', ','),
-        s if s.starts_with("de") || s.starts_with("it") || s.starts_with("es") => ('.', ','),
-        _ => (',', '.'),
-    };
-    let s = format!("{n}");
-    let (int_part, dec_part) = if let Some(dot) = s.find('.') {
-        (&s[..dot], Some(&s[dot+1..]))
-    } else { (s.as_str(), None) };
-    let (neg, digits) = if int_part.starts_with('-') {
-        (true, &int_part[1..])
-    } else { (false, int_part) };
-    let with_sep: String = digits.chars().rev().enumerate()
-        .flat_map(|(i, c)| {
-            if i > 0 && i % 3 == 0 { vec![thousand_sep, c] } else { vec![c] }
-        })
-        .collect::<String>()
-        .chars().rev().collect();
-    let result = match dec_part {
-        Some(d) => format!("{with_sep}{decimal_sep}{d}"),
-        None    => with_sep,
-    };
-    if neg { format!("-{result}") } else { result }
-}
-
-/// Formatuje date/time podle locale pres ICU4X DateTimeFormatter.
-/// Pri selhani fallback na manualni format.
-pub fn format_datetime_intl(ms: f64, locale: &str) -> String {
-    use icu::locid::Locale;
-    use icu::datetime::{DateTimeFormatter, options::length};
-    use icu::calendar::DateTime;
-    use std::str::FromStr;
-
-    let loc = match Locale::from_str(locale) {
-        Ok(l) => l,
-        Err(_) => return format_datetime_fallback(ms, locale),
-    };
-
-    // Konverze ms -> DateTime
-    let (yr, mo, day, hr, min, sec, _) = ms_to_parts(ms);
-    let dt = match DateTime::try_new_iso_datetime(
-        yr as i32,
-        (mo + 1) as u8,
-        day as u8,
-        hr as u8,
-        min as u8,
-        sec as u8,
-    ) {
-        Ok(d) => d.to_any(),
-        Err(_) => return format_datetime_fallback(ms, locale),
-    };
-
-    let options = length::Bag::from_date_time_style(
-        length::Date::Medium,
-        length::Time::Medium,
-    );
-
-    let formatter = match DateTimeFormatter::try_new(&loc.into(), options.into()) {
-        Ok(f) => f,
-        Err(_) => return format_datetime_fallback(ms, locale),
-    };
-
-    formatter.format_to_string(&dt).unwrap_or_else(|_| format_datetime_fallback(ms, locale))
-}
-
-fn format_datetime_fallback(ms: f64, locale: &str) -> String {
-    let (yr, mo, day, hr, min, sec, _) = ms_to_parts(ms);
-    match locale {
-        s if s.starts_with("cs") => format!("{day}. {}. {yr} {hr:02}:{min:02}:{sec:02}", mo+1),
-        s if s.starts_with("de") => format!("{day}.{}.{yr} {hr:02}:{min:02}:{sec:02}", mo+1),
-        _ => {
-            let pm = hr >= 12;
-            let h12 = if hr == 0 { 12 } else if hr > 12 { hr - 12 } else { hr };
-            format!("{}/{day}/{yr} {h12}:{min:02}:{sec:02} {}", mo+1, if pm { "PM" } else { "AM" })
-        }
-    }
-}
-
-/// Plural kategorie pres ICU4X PluralRules.
-/// Pokryva CLDR rules pro vsechny world locales.
-pub fn plural_select(n: f64, locale: &str) -> String {
-    use icu::locid::Locale;
-    use icu::plurals::{PluralRules, PluralRuleType};
-    use fixed_decimal::FixedDecimal;
-    use std::str::FromStr;
-
-    let loc = match Locale::from_str(locale) {
-        Ok(l) => l,
-        Err(_) => return plural_select_fallback(n, locale),
-    };
-
-    let rules = match PluralRules::try_new(&loc.into(), PluralRuleType::Cardinal) {
-        Ok(r) => r,
-        Err(_) => return plural_select_fallback(n, locale),
-    };
-
-    let s = format!("{n}");
-    let fd = match FixedDecimal::from_str(&s) {
-        Ok(d) => d,
-        Err(_) => return plural_select_fallback(n, locale),
-    };
-
-    use icu::plurals::PluralCategory::*;
-    match rules.category_for(&fd) {
-        Zero  => "zero".into(),
-        One   => "one".into(),
-        Two   => "two".into(),
-        Few   => "few".into(),
-        Many  => "many".into(),
-        Other => "other".into(),
-    }
-}
-
-fn plural_select_fallback(n: f64, _locale: &str) -> String {
-    if n.abs() == 1.0 { "one".into() } else { "other".into() }
-}
-
-/// Collator compare pres ICU4X.
-pub fn collator_compare_intl(a: &str, b: &str, locale: &str) -> i32 {
-    use icu::locid::Locale;
-    use icu::collator::Collator;
-    use std::str::FromStr;
-
-    let loc = Locale::from_str(locale).unwrap_or_default();
-    let collator = match Collator::try_new(&loc.into(), Default::default()) {
-        Ok(c) => c,
-        Err(_) => return a.cmp(b) as i32,
-    };
-    match collator.compare(a, b) {
-        std::cmp::Ordering::Less    => -1,
-        std::cmp::Ordering::Greater =>  1,
-        std::cmp::Ordering::Equal   =>  0,
-    }
-}
-
 pub fn format_number_locale(n: f64) -> String {
     if n.is_nan()      { return "NaN".into(); }
     if n.is_infinite() { return if n > 0.0 { "Infinity".into() } else { "-Infinity".into() }; }
@@ -0,0 +1,736 @@
+//! Typovane host bindings - vystaveni Rust funkci a struktur do JS bez
+//! rucniho skladani `JsObject` a `Vec<JsValue>` argumentu.
+//!
+//! - [`FromJs`] / [`IntoJs`] - konverze argumentu a navratovych hodnot
+//!   (primitiva, `Option`, `Vec`, `HashMap`, `Result`, serde typy pres [`Serde`]).
+//! - [`host_fn`] - nativni funkce z Rust closure s typovanymi argumenty.
+//! - [`HostClass`] - trida nad Rust strukturou: konstruktor, metody,
+//!   gettery/settery, staticke cleny; instance zpet v Rustu pres [`HostRef`].
+//! - [`Interpreter::host_async_fn`] - funkce vracejici Promise; prace bezi na
+//!   omezenem poolu worker threadu (nebo na executoru embedderu, viz
+//!   [`Interpreter::set_host_executor`]), settle pri `drain_host_jobs` (event loop).
+//!
+//! ```ignore
+//! let counter = HostClass::<Counter>::builder("Counter")
+//!     .constructor(|start: Option<f64>| Counter { n: start.unwrap_or(0.0) })
+//!     .method("add", |c: &mut Counter, by: f64| { c.n += by; c.n })
+//!     .getter("value", |c: &Counter| c.n)
+//!     .static_method("zero", || 0.0)
+//!     .build();
+//! counter.install(&interp);
+//! interp.global.borrow_mut().define("hello", host_fn("hello", |who: String| format!("hi {who}")));
+//! ```
+
+use std::any::Any;
+use std::cell::{Cell, RefCell};
+use std::collections::{BTreeMap, HashMap};
+use std::rc::{Rc, Weak};
+use std::sync::{mpsc, Arc, Mutex, OnceLock};
+
+use super::helpers::{is_internal_key, native};
+use super::{Interpreter, JsError, JsObject, JsValue};
+
+// ─── Konverze ────────────────────────────────────────────────────────────────
+
+/// Konverze JS hodnoty na Rust typ (argument host funkce). Chyba je popis
+/// bez prefixu - volajici ji obali na `TypeError: jmeno: argument N: ...`.
+pub trait FromJs: Sized {
+    fn from_js(v: JsValue) -> Result<Self, String>;
+}
+
+/// Konverze Rust hodnoty na JS (navratova hodnota host funkce). `Err` se v JS
+/// vyhodi jako chyba (`"TypeError: ..."` -> TypeError, jinak Error).
+pub trait IntoJs {
+    fn into_js(self) -> Result<JsValue, String>;
+}
+
+fn expected(what: &str, v: &JsValue) -> String {
+    format!("expected {what}, got {}", v.type_of())
+}
+
+impl FromJs for JsValue {
+    fn from_js(v: JsValue) -> Result<Self, String> { Ok(v) }
+}
+
+impl IntoJs for JsValue {
+    fn into_js(self) -> Result<JsValue, String> { Ok(self) }
+}
+
+impl FromJs for () {
+    fn from_js(_: JsValue) -> Result<Self, String> { Ok(()) }
+}
+
+impl IntoJs for () {
+    fn into_js(self) -> Result<JsValue, String> { Ok(JsValue::Undefined) }
+}
+
+impl FromJs for bool {
+    fn from_js(v: JsValue) -> Result<Self, String> {
+        match v {
+            JsValue::Bool(b) => Ok(b),
+            other => Err(expected("boolean", &other)),
+        }
+    }
+}
+
+impl IntoJs for bool {
+    fn into_js(self) -> Result<JsValue, String> { Ok(JsValue::Bool(self)) }
+}
+
+impl FromJs for String {
+    fn from_js(v: JsValue) -> Result<Self, String> {
+        match v {
+            JsValue::Str(s) => Ok(s),
+            other => Err(expected("string", &other)),
+        }
+    }
+}
+
+impl IntoJs for String {
+    fn into_js(self) -> Result<JsValue, String> { Ok(JsValue::Str(self)) }
+}
+
+impl IntoJs for &str {
+    fn into_js(self) -> Result<JsValue, String> { Ok(JsValue::Str(self.to_string())) }
+}
+
+impl FromJs for f64 {
+    fn from_js(v: JsValue) -> Result<Self, String> {
+        match v {
+            JsValue::Number(n) => Ok(n),
+            other => Err(expected("number", &other)),
+        }
+    }
+}
+
+impl IntoJs for f64 {
+    fn into_js(self) -> Result<JsValue, String> { Ok(JsValue::Number(self)) }
+}
+
+impl FromJs for f32 {
+    fn from_js(v: JsValue) -> Result<Self, String> { f64::from_js(v).map(|n| n as f32) }
+}
+
+impl IntoJs for f32 {
+    fn into_js(self) -> Result<JsValue, String> { Ok(JsValue::Number(self as f64)) }
+}
+
+/// Cela cisla: jen konecne cele Number v rozsahu typu (bez tiche saturace).
+macro_rules! int_conversions {
+    ($($t:ty),*) => {$(
+        impl FromJs for $t {
+            fn from_js(v: JsValue) -> Result<Self, String> {
+                let n = f64::from_js(v)?;
+                if n.fract() != 0.0 || !n.is_finite() || n < <$t>::MIN as f64 || n > <$t>::MAX as f64 {
+                    return Err(format!("{n} is not a valid {}", stringify!($t)));
+                }
+                Ok(n as $t)
+            }
+        }
+
+        impl IntoJs for $t {
+            fn into_js(self) -> Result<JsValue, String> { Ok(JsValue::Number(self as f64)) }
+        }
+    )*};
+}
+
+int_conversions!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+/// `undefined` / `null` -> `None`.
+impl<T: FromJs> FromJs for Option<T> {
+    fn from_js(v: JsValue) -> Result<Self, String> {
+        match v {
+            JsValue::Undefined | JsValue::Null => Ok(None),
+            other => T::from_js(other).map(Some),
+        }
+    }
+}
+
+/// `None` -> `null`.
+impl<T: IntoJs> IntoJs for Option<T> {
+    fn into_js(self) -> Result<JsValue, String> {
+        match self {
+            Some(v) => v.into_js(),
+            None => Ok(JsValue::Null),
+        }
+    }
+}
+
+/// Argument, ktery se nemusi podarit zkonvertovat - host rozhodne sam.
+impl<T: FromJs> FromJs for Result<T, String> {
+    fn from_js(v: JsValue) -> Result<Self, String> { Ok(T::from_js(v)) }
+}
+
+/// `Err` se vyhodi jako JS chyba.
+impl<T: IntoJs, E: std::fmt::Display> IntoJs for Result<T, E> {
+    fn into_js(self) -> Result<JsValue, String> {
+        self.map_err(|e| e.to_string())?.into_js()
+    }
+}
+
+impl<T: FromJs> FromJs for Vec<T> {
+    fn from_js(v: JsValue) -> Result<Self, String> {
+        match v {
+            JsValue::Array(a) => {
+                let items = a.borrow().clone();
+                items.into_iter().enumerate()
+                    .map(|(i, item)| T::from_js(item).map_err(|e| format!("[{i}]: {e}")))
+                    .collect()
+            }
+            other => Err(expected("array", &other)),
+        }
+    }
+}
+
+impl<T: IntoJs> IntoJs for Vec<T> {
+    fn into_js(self) -> Result<JsValue, String> {
+        let items = self.into_iter().map(IntoJs::into_js).collect::<Result<Vec<_>, _>>()?;
+        Ok(JsValue::Array(Rc::new(RefCell::new(items))))
+    }
+}
+
+/// Vlastni datove vlastnosti objektu (bez internich `__x__` klicu).
+fn object_entries(v: JsValue) -> Result<Vec<(String, JsValue)>, String> {
+    match v {
+        JsValue::Object(o) => Ok(o.borrow().props.iter()
+            .filter(|(k, _)| !is_internal_key(k))
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect()),
+        other => Err(expected("object", &other)),
+    }
+}
+
+fn entries_into_js(entries: impl Iterator<Item = (String, Result<JsValue, String>)>) -> Result<JsValue, String> {
+    let mut obj = JsObject::new();
+    for (k, v) in entries {
+        obj.set(k.into(), v?);
+    }
+    Ok(JsValue::Object(Rc::new(RefCell::new(obj))))
+}
+
+impl<T: FromJs> FromJs for HashMap<String, T> {
+    fn from_js(v: JsValue) -> Result<Self, String> {
+        object_entries(v)?.into_iter()
+            .map(|(k, v)| T::from_js(v).map(|v| (k.clone(), v)).map_err(|e| format!(".{k}: {e}")))
+            .collect()
+    }
+}
+
+impl<T: IntoJs> IntoJs for HashMap<String, T> {
+    fn into_js(self) -> Result<JsValue, String> {
+        entries_into_js(self.into_iter().map(|(k, v)| (k, v.into_js())))
+    }
+}
+
+impl<T: FromJs> FromJs for BTreeMap<String, T> {
+    fn from_js(v: JsValue) -> Result<Self, String> {
+        object_entries(v)?.into_iter()
+            .map(|(k, v)| T::from_js(v).map(|v| (k.clone(), v)).map_err(|e| format!(".{k}: {e}")))
+            .collect()
+    }
+}
+
+impl<T: IntoJs> IntoJs for BTreeMap<String, T> {
+    fn into_js(self) -> Result<JsValue, String> {
+        entries_into_js(self.into_iter().map(|(k, v)| (k, v.into_js())))
+    }
+}
+
+/// Serde most: libovolny `Serialize` / `DeserializeOwned` typ jako JS hodnota
+/// (pres JSON reprezentaci - `undefined`, funkce a symboly se vynechaji).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Serde<T>(pub T);
+
+impl<T: serde::de::DeserializeOwned> FromJs for Serde<T> {
+    fn from_js(v: JsValue) -> Result<Self, String> {
+        let json = super::helpers::json_stringify_checked(&v, 0, 0)?.unwrap_or_else(|| "null".into());
+        serde_json::from_str(&json).map(Serde).map_err(|e| e.to_string())
+    }
+}
+
+impl<T: serde::Serialize> IntoJs for Serde<T> {
+    fn into_js(self) -> Result<JsValue, String> {
+        let json = serde_json::to_string(&self.0).map_err(|e| e.to_string())?;
+        super::helpers::json_parse(&json)
+    }
+}
+
+// ─── Host funkce ─────────────────────────────────────────────────────────────
+
+/// Argument `i` (od 1) host funkce `name`; chybejici argument = `undefined`.
+fn arg<A: FromJs>(it: &mut std::vec::IntoIter<JsValue>, name: &str, i: usize) -> Result<A, String> {
+    A::from_js(it.next().unwrap_or(JsValue::Undefined))
+        .map_err(|e| format!("TypeError: {name}: argument {i}: {e}"))
+}
+
+/// Rust closure volatelna z JS - `Args` je tuple typu argumentu (kazdy `FromJs`).
+pub trait HostFn<Args>: 'static {
+    type Output;
+    fn invoke(&self, name: &str, args: Vec<JsValue>) -> Result<Self::Output, String>;
+}
+
+/// Metoda host tridy - prvni argument closure je `&mut T` instance.
+pub trait HostMethod<T, Args>: 'static {
+    type Output;
+    fn invoke(&self, this: &mut T, name: &str, args: Vec<JsValue>) -> Result<Self::Output, String>;
+}
+
+macro_rules! host_fn_arity {
+    ($($a:ident),*) => {
+        #[allow(non_snake_case, unused_mut, unused_variables, unused_assignments)]
+        impl<F, R, $($a: FromJs),*> HostFn<($($a,)*)> for F
+        where F: Fn($($a),*) -> R + 'static {
+            type Output = R;
+            fn invoke(&self, name: &str, args: Vec<JsValue>) -> Result<R, String> {
+                let mut it = args.into_iter();
+                let mut i = 0;
+                $(i += 1; let $a = arg::<$a>(&mut it, name, i)?;)*
+                Ok(self($($a),*))
+            }
+        }
+
+        #[allow(non_snake_case, unused_mut, unused_variables, unused_assignments)]
+        impl<T, F, R, $($a: FromJs),*> HostMethod<T, ($($a,)*)> for F
+        where F: Fn(&mut T, $($a),*) -> R + 'static {
+            type Output = R;
+            fn invoke(&self, this: &mut T, name: &str, args: Vec<JsValue>) -> Result<R, String> {
+                let mut it = args.into_iter();
+                let mut i = 0;
+                $(i += 1; let $a = arg::<$a>(&mut it, name, i)?;)*
+                Ok(self(this, $($a),*))
+            }
+        }
+    };
+}
+
+host_fn_arity!();
+host_fn_arity!(A1);
+host_fn_arity!(A1, A2);
+host_fn_arity!(A1, A2, A3);
+host_fn_arity!(A1, A2, A3, A4);
+host_fn_arity!(A1, A2, A3, A4, A5);
+host_fn_arity!(A1, A2, A3, A4, A5, A6);
+
+/// Nativni JS funkce z typovane closure. Spatny typ argumentu -> TypeError.
+pub fn host_fn<Args, F>(name: &str, f: F) -> JsValue
+where
+    F: HostFn<Args>,
+    F::Output: IntoJs,
+{
+    let fname = name.to_string();
+    native(name, move |args| f.invoke(&fname, args)?.into_js())
+}
+
+// ─── Host tridy ──────────────────────────────────────────────────────────────
+
+type CtorFn<T> = Box<dyn Fn(Vec<JsValue>) -> Result<T, String>>;
+type MethodFn<T> = Rc<dyn Fn(&mut T, Vec<JsValue>) -> Result<JsValue, String>>;
+type GetterFn<T> = Rc<dyn Fn(&T) -> Result<JsValue, String>>;
+type SetterFn<T> = Rc<dyn Fn(&mut T, JsValue) -> Result<(), String>>;
+
+struct Accessor<T> {
+    name: String,
+    get: Option<GetterFn<T>>,
+    set: Option<SetterFn<T>>,
+}
+
+struct ClassDef<T> {
+    name: String,
+    ctor: Option<CtorFn<T>>,
+    methods: Vec<(String, MethodFn<T>)>,
+    accessors: Vec<Accessor<T>>,
+    statics: Rc<RefCell<JsObject>>,
+}
+
+/// Builder host tridy - viz [`HostClass::builder`].
+pub struct HostClassBuilder<T: 'static> {
+    def: ClassDef<T>,
+}
+
+impl<T: 'static> HostClassBuilder<T> {
+    /// `new Name(...)` z JS; bez konstruktoru vyhodi `TypeError: Illegal constructor`
+    /// a instance vznikaji jen z Rustu (`HostClass::wrap`).
+    pub fn constructor<Args, F>(mut self, f: F) -> Self
+    where F: HostFn<Args, Output = T> {
+        let name = self.def.name.clone();
+        self.def.ctor = Some(Box::new(move |args| f.invoke(&name, args)));
+        self
+    }
+
+    pub fn method<Args, F>(mut self, name: &str, f: F) -> Self
+    where
+        F: HostMethod<T, Args>,
+        F::Output: IntoJs,
+    {
+        let full = format!("{}.{name}", self.def.name);
+        self.def.methods.push((name.to_string(), Rc::new(move |this, args| f.invoke(this, &full, args)?.into_js())));
+        self
+    }
+
+    pub fn getter<R: IntoJs>(mut self, name: &str, f: impl Fn(&T) -> R + 'static) -> Self {
+        self.accessor(name).get = Some(Rc::new(move |this| f(this).into_js()));
+        self
+    }
+
+    pub fn setter<V: FromJs>(mut self, name: &str, f: impl Fn(&mut T, V) + 'static) -> Self {
+        let full = format!("{}.{name}", self.def.name);
+        self.accessor(name).set = Some(Rc::new(move |this, v| {
+            f(this, V::from_js(v).map_err(|e| format!("TypeError: {full}: {e}"))?);
+            Ok(())
+        }));
+        self
+    }
+
+    fn accessor(&mut self, name: &str) -> &mut Accessor<T> {
+        let idx = match self.def.accessors.iter().position(|a| a.name == name) {
+            Some(i) => i,
+            None => {
+                self.def.accessors.push(Accessor { name: name.to_string(), get: None, set: None });
+                self.def.accessors.len() - 1
+            }
+        };
+        &mut self.def.accessors[idx]
+    }
+
+    pub fn static_method<Args, F>(self, name: &str, f: F) -> Self
+    where
+        F: HostFn<Args>,
+        F::Output: IntoJs,
+    {
+        let full = format!("{}.{name}", self.def.name);
+        self.def.statics.borrow_mut().set(name.into(), host_fn(&full, f));
+        self
+    }
+
+    pub fn static_value(self, name: &str, v: impl IntoJs) -> Self {
+        self.def.statics.borrow_mut().set(name.into(), v.into_js().unwrap_or(JsValue::Undefined));
+        self
+    }
+
+    /// Hotova trida; staticke cleny vidi interpret, do ktereho se instaluje.
+    pub fn build(self) -> HostClass<T> {
+        HostClass { def: Rc::new(self.def) }
+    }
+}
+
+/// Trida nad Rust strukturou `T`. Levne klonovatelna (sdilena definice).
+pub struct HostClass<T: 'static> {
+    def: Rc<ClassDef<T>>,
+}
+
+impl<T: 'static> Clone for HostClass<T> {
+    fn clone(&self) -> Self { Self { def: Rc::clone(&self.def) } }
+}
+
+impl<T: 'static> HostClass<T> {
+    pub fn builder(name: &str) -> HostClassBuilder<T> {
+        HostClassBuilder {
+            def: ClassDef {
+                name: name.to_string(),
+                ctor: None,
+                methods: Vec::new(),
+                accessors: Vec::new(),
+                statics: Rc::new(RefCell::new(JsObject::new())),
+            },
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.def.name
+    }
+
+    /// Konstruktor jako JS funkce (`new Name(...)`, staticke cleny).
+    pub fn constructor(&self) -> JsValue {
+        let class = self.clone();
+        native(&self.def.name, move |args| {
+            let Some(ctor) = &class.def.ctor else {
+                return Err("TypeError: Illegal constructor".into());
+            };
+            let value = ctor(args)?;
+            Ok(class.wrap(value))
+        })
+    }
+
+    /// Definuje konstruktor jako globalni promennou interpretu a registruje
+    /// staticke cleny pod jmenem tridy (jen v tomto interpretu).
+    pub fn install(&self, interp: &Interpreter) {
+        interp.host_statics.borrow_mut().insert(self.def.name.clone(), Rc::clone(&self.def.statics));
+        interp.global.borrow_mut().define(&self.def.name, self.constructor());
+    }
+
+    /// Nova JS instance nad `value`.
+    pub fn wrap(&self, value: T) -> JsValue {
+        self.wrap_shared(Rc::new(RefCell::new(value)))
+    }
+
+    /// JS instance nad sdilenym stavem - host vidi zmeny z JS a naopak.
+    pub fn wrap_shared(&self, cell: Rc<RefCell<T>>) -> JsValue {
+        let id = register_instance(Rc::clone(&cell) as Rc<dyn Any>);
+        let mut obj = JsObject::new();
+        obj.set("__class_chain__".into(), JsValue::Str(self.def.name.clone()));
+        obj.set(HOST_ID.into(), JsValue::Number(id as f64));
+        for (name, method) in &self.def.methods {
+            let (cell, method) = (Rc::clone(&cell), Rc::clone(method));
+            let full = format!("{}.{name}", self.def.name);
+            obj.define_hidden(name.clone().into(), native(&full.clone(), move |args| {
+                let mut this = cell.try_borrow_mut().map_err(|_| format!("TypeError: {full}: reentrantni volani"))?;
+                method(&mut this, args)
+            }));
+        }
+        for acc in &self.def.accessors {
+            let full = format!("{}.{}", self.def.name, acc.name);
+            let get = acc.get.as_ref().map(|g| {
+                let (cell, g) = (Rc::clone(&cell), Rc::clone(g));
+                let full = full.clone();
+                native(&full.clone(), move |_| {
+                    let this = cell.try_borrow().map_err(|_| format!("TypeError: {full}: reentrantni volani"))?;
+                    g(&this)
+                })
+            });
+            let set = acc.set.as_ref().map(|s| {
+                let (cell, s) = (Rc::clone(&cell), Rc::clone(s));
+                let full = full.clone();
+                native(&full.clone(), move |args| {
+                    let mut this = cell.try_borrow_mut().map_err(|_| format!("TypeError: {full}: reentrantni volani"))?;
+                    s(&mut this, args.into_iter().next().unwrap_or(JsValue::Undefined))?;
+                    Ok(JsValue::Undefined)
+                })
+            });
+            obj.define_accessor(acc.name.clone().into(), get, set);
+        }
+        JsValue::Object(Rc::new(RefCell::new(obj)))
+    }
+}
+
+/// Interni klic s id instance host tridy (lookup pro [`HostRef`]).
+const HOST_ID: &str = "__host_id__";
+
+thread_local! {
+    static NEXT_INSTANCE: Cell<u64> = const { Cell::new(1) };
+    static INSTANCES: RefCell<HashMap<u64, Weak<dyn Any>>> = RefCell::new(HashMap::new());
+}
+
+fn register_instance(cell: Rc<dyn Any>) -> u64 {
+    let id = NEXT_INSTANCE.with(|n| { let id = n.get(); n.set(id + 1); id });
+    INSTANCES.with(|m| {
+        let mut m = m.borrow_mut();
+        // Obcas vycisti instance, ktere uz JS nedrzi
+        if id.is_multiple_of(1024) {
+            m.retain(|_, w| w.strong_count() > 0);
+        }
+        m.insert(id, Rc::downgrade(&cell));
+    });
+    id
+}
+
+impl Interpreter {
+    /// Staticke cleny host trid (`Name.method`) - lookup z `eval_member` / VM.
+    pub(crate) fn host_static(&self, ctor: &str, key: &str) -> Option<JsValue> {
+        self.host_statics.borrow().get(ctor).map(|o| o.borrow().get(key))
+            .filter(|v| !matches!(v, JsValue::Undefined))
+    }
+}
+
+/// Instance host tridy predana zpet do Rustu (argument host funkce / metody).
+pub struct HostRef<T>(pub Rc<RefCell<T>>);
+
+impl<T> Clone for HostRef<T> {
+    fn clone(&self) -> Self { Self(Rc::clone(&self.0)) }
+}
+
+impl<T> std::ops::Deref for HostRef<T> {
+    type Target = RefCell<T>;
+    fn deref(&self) -> &RefCell<T> { &self.0 }
+}
+
+impl<T: 'static> FromJs for HostRef<T> {
+    fn from_js(v: JsValue) -> Result<Self, String> {
+        let id = match &v {
+            JsValue::Object(o) => match o.borrow().props.get(HOST_ID) {
+                Some(JsValue::Number(n)) => *n as u64,
+                _ => return Err(expected(std::any::type_name::<T>(), &v)),
+            },
+            _ => return Err(expected(std::any::type_name::<T>(), &v)),
+        };
+        INSTANCES.with(|m| m.borrow().get(&id).and_then(Weak::upgrade))
+            .and_then(|cell| cell.downcast::<RefCell<T>>().ok())
+            .map(HostRef)
+            .ok_or_else(|| expected(std::any::type_name::<T>(), &v))
+    }
+}
+
+// ─── Async host funkce ───────────────────────────────────────────────────────
+
+/// Vysledek async prace - konverze do JS probehne na threadu interpretu.
+type Completion = Box<dyn FnOnce() -> Result<JsValue, String> + Send>;
+
+/// Prace async host funkce predana executoru.
+pub type HostJob = Box<dyn FnOnce() + Send>;
+
+/// Executor async host funkci (viz [`Interpreter::set_host_executor`]).
+pub type HostExecutor = Rc<dyn Fn(HostJob)>;
+
+/// Vychozi executor - sdileny pool 2..=8 worker threadu (podle poctu jader).
+/// Panika ulohy worker neshodi; promise se rejectne pres odpojeny kanal.
+fn run_pooled(job: HostJob) {
+    static POOL: OnceLock<mpsc::Sender<HostJob>> = OnceLock::new();
+    let pool = POOL.get_or_init(|| {
+        let (tx, rx) = mpsc::channel::<HostJob>();
+        let rx = Arc::new(Mutex::new(rx));
+        let workers = std::thread::available_parallelism().map_or(2, |n| n.get()).clamp(2, 8);
+        for i in 0..workers {
+            let rx = Arc::clone(&rx);
+            let _ = std::thread::Builder::new().name(format!("host-job-{i}")).spawn(move || loop {
+                let next = match rx.lock() {
+                    Ok(rx) => rx.recv(),
+                    Err(_) => return,
+                };
+                let Ok(job) = next else { return };
+                let _ = std::panic::catch_unwind(std::panic::AssertUnwindSafe(job));
+            });
+        }
+        tx
+    });
+    let _ = pool.send(job);
+}
+
+/// Rozpracovane volani async host funkce: pending promise + kanal vysledku.
+pub struct PendingHostJob {
+    promise: JsValue,
+    receiver: mpsc::Receiver<Completion>,
+}
+
+fn pending_promise() -> JsValue {
+    let mut obj = JsObject::new();
+    obj.set("__promise_state__".into(), JsValue::Str("pending".into()));
+    obj.set("__promise_value__".into(), JsValue::Undefined);
+    obj.set("__pending_callbacks__".into(), JsValue::Array(Rc::new(RefCell::new(Vec::new()))));
+    JsValue::Object(Rc::new(RefCell::new(obj)))
+}
+
+impl Interpreter {
+    /// Async host funkce: closure `f` zkonvertuje argumenty a vrati praci
+    /// (`FnOnce() -> R + Send`), ktera bezi na executoru interpretu. JS dostane
+    /// Promise; settle probehne v `drain_host_jobs` (event loop / konec `run`).
+    pub fn host_async_fn<Args, F, J, R>(&self, name: &str, f: F) -> JsValue
+    where
+        F: HostFn<Args, Output = J>,
+        J: FnOnce() -> R + Send + 'static,
+        R: IntoJs + Send + 'static,
+    {
+        let fname = name.to_string();
+        let pending = Rc::clone(&self.pending_host_jobs);
+        let executor = Rc::clone(&self.host_executor);
+        native(name, move |args| {
+            let job = f.invoke(&fname, args)?;
+            let (tx, rx) = mpsc::channel::<Completion>();
+            let work: HostJob = Box::new(move || {
+                let result = job();
+                let _ = tx.send(Box::new(move || result.into_js()));
+            });
+            let custom = executor.borrow().clone();
+            match custom {
+                Some(run) => run(work),
+                None => run_pooled(work),
+            }
+            let promise = pending_promise();
+            pending.borrow_mut().push(PendingHostJob { promise: promise.clone(), receiver: rx });
+            Ok(promise)
+        })
+    }
+
+    /// Vlastni executor pro praci async host funkci (tokio, rayon, ...) misto
+    /// vychoziho poolu. Plati i pro uz vytvorene funkce.
+    pub fn set_host_executor(&self, executor: impl Fn(HostJob) + 'static) {
+        *self.host_executor.borrow_mut() = Some(Rc::new(executor));
+    }
+
+    /// Ceka nejaka async host funkce na dokonceni?
+    pub fn has_pending_host_jobs(&self) -> bool {
+        !self.pending_host_jobs.borrow().is_empty()
+    }
+
+    /// Settle dokoncenych async host volani (`wait` = blokuj, dokud
+    /// nedobehnou vsechna). Reakce promise jdou do task_queue jako u
+    /// `resolve` (drain_timers). Vraci pocet settlenych promise.
+    pub fn drain_host_jobs(&mut self, wait: bool) -> usize {
+        let mut done = Vec::new();
+        self.pending_host_jobs.borrow_mut().retain(|job| {
+            let outcome = if wait { job.receiver.recv().ok() } else {
+                match job.receiver.try_recv() {
+                    Ok(c) => Some(c),
+                    Err(mpsc::TryRecvError::Empty) => return true,
+                    Err(mpsc::TryRecvError::Disconnected) => None,
+                }
+            };
+            done.push((job.promise.clone(), outcome));
+            false
+        });
+        let settled = done.len();
+        for (promise, outcome) in done {
+            let (state, value) = match outcome.map(|complete| complete()) {
+                Some(Ok(v)) => ("fulfilled", v),
+                Some(Err(msg)) => ("rejected", self.error_value(&JsError::Runtime(msg))),
+                None => ("rejected", self.error_value(&JsError::Runtime("Error: host job panicked".into()))),
+            };
+            self.settle_host_promise(&promise, state, value);
+        }
+        settled
+    }
+
+    fn settle_host_promise(&mut self, promise: &JsValue, state: &str, value: JsValue) {
+        let JsValue::Object(o) = promise else { return };
+        let callbacks = {
+            let mut p = o.borrow_mut();
+            p.set("__promise_state__".into(), JsValue::Str(state.into()));
+            p.set("__promise_value__".into(), value.clone());
+            match p.props.get("__pending_callbacks__").cloned() {
+                Some(JsValue::Array(arr)) => std::mem::take(&mut *arr.borrow_mut()),
+                _ => Vec::new(),
+            }
+        };
+        let idx = if state == "fulfilled" { 0 } else { 1 };
+        let now = std::time::Instant::now();
+        for entry in callbacks {
+            let JsValue::Array(triple) = entry else { continue };
+            let cb = triple.borrow().get(idx).cloned().unwrap_or(JsValue::Undefined);
+            if matches!(cb, JsValue::Function(_)) {
+                let id = {
+                    let mut ctr = self.next_timer_id.borrow_mut();
+                    let id = *ctr; *ctr += 1; id
+                };
+                self.task_queue.borrow_mut().push((id, now, cb, vec![value.clone()]));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn primitive_and_container_conversions() {
+        assert_eq!(u8::from_js(JsValue::Number(7.0)), Ok(7));
+        assert!(u8::from_js(JsValue::Number(300.0)).is_err());
+        assert!(i32::from_js(JsValue::Number(1.5)).is_err());
+        assert_eq!(String::from_js(JsValue::Str("x".into())), Ok("x".to_string()));
+        assert_eq!(bool::from_js(JsValue::Number(1.0)), Err("expected boolean, got number".to_string()));
+        assert_eq!(Option::<f64>::from_js(JsValue::Undefined), Ok(None));
+        let arr = vec![1u32, 2, 3].into_js().unwrap();
+        assert_eq!(Vec::<u32>::from_js(arr), Ok(vec![1, 2, 3]));
+        let map: HashMap<String, bool> = [("a".to_string(), true)].into_iter().collect();
+        assert_eq!(HashMap::<String, bool>::from_js(map.clone().into_js().unwrap()), Ok(map));
+        assert_eq!(Result::<u8, String>::Err("boom".into()).into_js().unwrap_err(), "boom");
+    }
+
+    #[test]
+    fn serde_roundtrip() {
+        #[derive(serde::Serialize, serde::Deserialize, Debug, PartialEq)]
+        struct Point { x: i32, tags: Vec<String> }
+        let p = Point { x: 3, tags: vec!["a".into()] };
+        let js = Serde(p).into_js().unwrap();
+        let Serde(back) = Serde::<Point>::from_js(js).unwrap();
+        assert_eq!(back, Point { x: 3, tags: vec!["a".into()] });
+    }
+}
@@ -17,33 +17,54 @@ pub struct ImportMap {
 impl ImportMap {
     pub fn new() -> Self { Self::default() }
 
-    /// Parse JSON-like import map. Real impl pipes via serde_json; here a tiny manual parser.
-    pub fn parse_minimal(json: &str) -> Result<Self, String> {
+    /// Parse `<script type="importmap">` JSON. Adresy (hodnoty, klice scopu a
+    /// klice `integrity`) se resolvuji proti `base_url` dokumentu. Neplatne
+    /// polozky (ne-string adresa, prazdny klic) se podle spec ignoruji; chybny
+    /// JSON nebo ne-objekt na nejvyssi urovni je chyba.
+    pub fn parse(json: &str, base_url: &str) -> Result<Self, String> {
+        let root: serde_json::Value = serde_json::from_str(json)
+            .map_err(|e| format!("SyntaxError: import map neni platny JSON: {e}"))?;
+        let serde_json::Value::Object(root) = root else {
+            return Err("TypeError: import map musi byt JSON objekt".into());
+        };
         let mut map = Self::new();
-        // Expecting object {"imports": {...}, "scopes": {...}}.
-        // For simplicity, support flat "imports" only via regex-free string scan.
-        if let Some(imports_start) = json.find("\"imports\"") {
-            let after = &json[imports_start..];
-            if let Some(open) = after.find('{') {
-                let chunk = &after[open + 1..];
-                if let Some(close) = chunk.find('}') {
-                    let entries = &chunk[..close];
-                    for entry in entries.split(',') {
-                        let entry = entry.trim();
-                        if entry.is_empty() { continue; }
-                        let Some(colon) = entry.find(':') else { continue; };
-                        let k = entry[..colon].trim().trim_matches('"').to_string();
-                        let v = entry[colon + 1..].trim().trim_matches('"').to_string();
-                        if !k.is_empty() && !v.is_empty() {
-                            map.imports.insert(k, v);
-                        }
-                    }
+        if let Some(imports) = root.get("imports") {
+            map.imports = specifier_map(imports, base_url)?;
+        }
+        if let Some(scopes) = root.get("scopes") {
+            let serde_json::Value::Object(scopes) = scopes else {
+                return Err("TypeError: import map \"scopes\" musi byt objekt".into());
+            };
+            for (prefix, entries) in scopes {
+                map.scopes.insert(resolve_address(prefix, base_url), specifier_map(entries, base_url)?);
+            }
+        }
+        if let Some(serde_json::Value::Object(integrity)) = root.get("integrity") {
+            for (url, hash) in integrity {
+                if let Some(hash) = hash.as_str() {
+                    map.integrity.insert(resolve_address(url, base_url), hash.to_string());
                 }
             }
         }
         Ok(map)
     }
 
+    /// Pripoji dalsi import mapu stranky - existujici pravidla maji prednost.
+    pub fn merge(&mut self, other: ImportMap) {
+        for (k, v) in other.imports {
+            self.imports.entry(k).or_insert(v);
+        }
+        for (scope, entries) in other.scopes {
+            let target = self.scopes.entry(scope).or_default();
+            for (k, v) in entries {
+                target.entry(k).or_insert(v);
+            }
+        }
+        for (k, v) in other.integrity {
+            self.integrity.entry(k).or_insert(v);
+        }
+    }
+
     /// Resolve bare specifier (per spec):
     /// 1. Check scopes from most-specific to least.
     /// 2. Check top-level imports map.
@@ -70,6 +91,27 @@ impl ImportMap {
     }
 }
 
+/// `{"specifier": "address"}` -> mapa s resolvnutymi adresami.
+fn specifier_map(v: &serde_json::Value, base_url: &str) -> Result<HashMap<String, String>, String> {
+    let serde_json::Value::Object(entries) = v else {
+        return Err("TypeError: import map \"imports\" musi byt objekt".into());
+    };
+    Ok(entries.iter()
+        .filter(|(k, _)| !k.is_empty())
+        .filter_map(|(k, v)| Some((k.clone(), resolve_address(v.as_str()?, base_url))))
+        .collect())
+}
+
+/// Relativni a absolutni cesty adres se vztahuji k URL dokumentu.
+fn resolve_address(address: &str, base_url: &str) -> String {
+    let url_like = address.starts_with('/') || address.starts_with("./") || address.starts_with("../");
+    if url_like && !base_url.is_empty() {
+        crate::browser::modules_esm::EsmRegistry::resolve(address, base_url)
+    } else {
+        address.to_string()
+    }
+}
+
 fn longest_prefix_match(specifier: &str, map: &HashMap<String, String>) -> Option<String> {
     let mut best: Option<(&String, &String)> = None;
     for (k, v) in map {
@@ -132,7 +174,29 @@ mod tests {
 
     #[test]
     fn parse_simple_json() {
-        let m = ImportMap::parse_minimal(r#"{"imports": {"lodash": "/cdn/lodash.mjs"}}"#).unwrap();
+        let m = ImportMap::parse(r#"{"imports": {"lodash": "/cdn/lodash.mjs"}}"#, "").unwrap();
         assert_eq!(m.imports.get("lodash").map(|s| s.as_str()), Some("/cdn/lodash.mjs"));
     }
+
+    #[test]
+    fn parse_scopes_and_resolves_addresses() {
+        let json = r#"{
+            "imports": {"a": "./lib/a.mjs", "bad": 5, "b,c": "/x.mjs"},
+            "scopes": {"/sub/": {"a": "/sub/a.mjs"}},
+            "integrity": {"/x.mjs": "sha384-abc"}
+        }"#;
+        let m = ImportMap::parse(json, "https://x.com/app/index.html").unwrap();
+        assert_eq!(m.imports.get("a").map(|s| s.as_str()), Some("https://x.com/app/lib/a.mjs"));
+        assert_eq!(m.imports.get("b,c").map(|s| s.as_str()), Some("https://x.com/x.mjs"));
+        assert!(!m.imports.contains_key("bad"));
+        let r = m.resolve("a", "https://x.com/sub/page.mjs");
+        assert_eq!(r.as_deref(), Some("https://x.com/sub/a.mjs"));
+        assert_eq!(m.integrity.get("https://x.com/x.mjs").map(|s| s.as_str()), Some("sha384-abc"));
+    }
+
+    #[test]
+    fn parse_rejects_invalid_json() {
+        assert!(ImportMap::parse("{imports: }", "").is_err());
+        assert!(ImportMap::parse("[]", "").is_err());
+    }
 }
@@ -21,6 +21,7 @@ impl std::fmt::Debug for JsFunc {
             JsFunc::Generator { name, .. } => write!(f, "[GeneratorFunction: {}]", name.as_deref().unwrap_or("anonymous")),
             JsFunc::Async { name, .. }     => write!(f, "[AsyncFunction: {}]", name.as_deref().unwrap_or("anonymous")),
             JsFunc::Bound { .. }           => write!(f, "[BoundFunction]"),
+            JsFunc::GeneratorResume { kind, .. } => write!(f, "[NativeFunction: Generator.{kind:?}]"),
             JsFunc::VmCompiled { name, .. } => write!(f, "[VmCompiled: {}]", name.as_deref().unwrap_or("anonymous")),
         }
     }
@@ -39,6 +40,20 @@ impl std::fmt::Display for JsValue {
                 else                    { write!(f, "{n}") }
             }
             JsValue::Str(s)       => write!(f, "{s}"),
+            JsValue::Object(o) if o.borrow().binary.is_some() => {
+                let binary = o.borrow().binary.clone();
+                match binary.as_deref() {
+                    Some(super::typed_arrays::BinaryData::TypedArray(v)) => {
+                        let items: Vec<String> = v.to_values().iter().map(|v| v.to_string()).collect();
+                        write!(f, "{} [{}]", v.kind.ctor_name(), items.join(", "))
+                    }
+                    Some(b) => write!(f, "{} {{ byteLength: {} }}", b.class_name(),
+                        b.get("byteLength").unwrap_or(JsValue::Number(0.0))),
+                    None => Ok(()),
+                }
+            }
+            JsValue::Object(_) if let Some(s) = super::builtins_temporal::display_string(self) => write!(f, "{s}"),
+            JsValue::Object(_) if let Some(s) = super::builtins_intl::display_string(self) => write!(f, "{s}"),
             JsValue::Object(o)    => {
                 let pairs: Vec<String> = o.borrow().props.iter().map(|(k,v)| format!("{k}: {v}")).collect();
                 write!(f, "{{ {} }}", pairs.join(", "))
@@ -48,13 +63,15 @@ impl std::fmt::Display for JsValue {
                 write!(f, "[{}]", items.join(", "))
             }
             JsValue::Function(fn_) => write!(f, "{fn_:?}"),
+            JsValue::Map(m) if m.borrow().weak => write!(f, "WeakMap {{ <items unknown> }}"),
+            JsValue::Set(s) if s.borrow().weak => write!(f, "WeakSet {{ <items unknown> }}"),
             JsValue::Map(m) => {
-                let pairs: Vec<String> = m.borrow().entries.iter()
+                let pairs: Vec<String> = m.borrow().entries()
                     .map(|(k, v)| format!("{k} => {v}")).collect();
                 write!(f, "Map {{ {} }}", pairs.join(", "))
             }
             JsValue::Set(s) => {
-                let items: Vec<String> = s.borrow().values.iter().map(|v| v.to_string()).collect();
+                let items: Vec<String> = s.borrow().values().map(|v| v.to_string()).collect();
                 write!(f, "Set {{ {} }}", items.join(", "))
             }
             JsValue::BigNumber(n) => write!(f, "{n}"),
@@ -66,6 +83,7 @@ impl std::fmt::Display for JsValue {
                     write!(f, "[DOM Node]")
                 }
             }
+            JsValue::Symbol(sym) => write!(f, "{}", sym.display()),
         }
     }
 }
@@ -115,23 +133,26 @@ impl JsValue {
                 let closing = "  ".repeat(depth);
                 format!("{{\n{}{}\n{}}}", indent, parts.join(&format!(",\n{}", indent)), closing)
             }
+            JsValue::Map(m) if m.borrow().weak => "WeakMap { <items unknown> }".into(),
+            JsValue::Set(s) if s.borrow().weak => "WeakSet { <items unknown> }".into(),
             JsValue::Map(m) => {
-                let entries = &m.borrow().entries;
-                let parts: Vec<String> = entries.iter().map(|(k, v)|
+                let m = m.borrow();
+                let parts: Vec<String> = m.entries().map(|(k, v)|
                     format!("{} => {}", k.pretty_inner(depth + 1, max_depth), v.pretty_inner(depth + 1, max_depth))
                 ).collect();
-                format!("Map({}) {{ {} }}", entries.len(), parts.join(", "))
+                format!("Map({}) {{ {} }}", m.len(), parts.join(", "))
             }
             JsValue::Set(s) => {
-                let vals = &s.borrow().values;
-                let parts: Vec<String> = vals.iter().map(|v| v.pretty_inner(depth + 1, max_depth)).collect();
-                format!("Set({}) {{ {} }}", vals.len(), parts.join(", "))
+                let s = s.borrow();
+                let parts: Vec<String> = s.values().map(|v| v.pretty_inner(depth + 1, max_depth)).collect();
+                format!("Set({}) {{ {} }}", s.len(), parts.join(", "))
             }
             JsValue::Function(_) => "[Function]".into(),
             JsValue::DomNode(n) => {
                 let tag = n.tag_name().unwrap_or_else(|| "node".into());
                 format!("<{} />", tag)
             }
+            JsValue::Symbol(sym) => sym.display(),
         }
     }
 
@@ -186,6 +207,8 @@ impl JsValue {
             JsValue::Bool(_)      => "boolean",
             JsValue::Number(_)    => "number",
             JsValue::Str(_)       => "string",
+            JsValue::Object(o) if o.borrow().props.contains_key(super::proxy_handler::CALLABLE) => "function",
+            JsValue::Object(o) if o.borrow().props.contains_key(super::bytecode::VM_CLASS) => "function",
             JsValue::Object(_)    => "object",
             JsValue::Array(_)     => "object",
             JsValue::Function(_)  => "function",
@@ -194,6 +217,7 @@ impl JsValue {
             JsValue::BigNumber(_) => "bignumber",
             JsValue::BigInt(_)    => "bigint",
             JsValue::DomNode(_)   => "object",
+            JsValue::Symbol(_)    => "symbol",
         }
     }
 
@@ -203,6 +227,7 @@ impl JsValue {
             (JsValue::Number(a), JsValue::Number(b)) => a == b,
             (JsValue::Str(a), JsValue::Str(b))       => a == b,
             (JsValue::Bool(a), JsValue::Bool(b))     => a == b,
+            (JsValue::Symbol(a), JsValue::Symbol(b)) => a.id == b.id,
             (JsValue::Number(n), JsValue::Str(s)) | (JsValue::Str(s), JsValue::Number(n)) =>
                 s.trim().parse::<f64>().ok().as_ref() == Some(n),
             _ => false,
@@ -223,14 +248,15 @@ impl JsValue {
             (JsValue::BigNumber(a), JsValue::BigNumber(b)) => *a == *b,
             (JsValue::BigInt(a),    JsValue::BigInt(b))    => *a == *b,
             (JsValue::DomNode(a),   JsValue::DomNode(b))   => Rc::ptr_eq(a, b),
+            (JsValue::Symbol(a),    JsValue::Symbol(b))    => a.id == b.id,
             (JsValue::Function(a), JsValue::Function(b))   => js_func_identity_eq(a, b),
             _ => false,
         }
     }
 
     /// Identity equality pro callback funkce - pouziva removeEventListener
-    /// k najiti registrace. Pro User fn porovnava env Rc ptr + jmeno + arity.
-    /// Pro Native fn pres Rc::ptr_eq na NativeFn closure.
+    /// k najiti registrace. JS funkce porovnava podle `id`, Native fn
+    /// pres Rc::ptr_eq na NativeFn closure.
     pub(crate) fn function_identity_eq(&self, other: &JsValue) -> bool {
         match (self, other) {
             (JsValue::Function(a), JsValue::Function(b)) => js_func_identity_eq(a, b),
@@ -239,27 +265,23 @@ impl JsValue {
     }
 }
 
-/// Porovnani JsFunc pres identitu (pro removeEventListener).
+/// Porovnani JsFunc pres identitu (`===`, klice Map/Set, removeEventListener).
 /// Funkce ulozena v promenne `h` je pri kazdem evaluation Identifier(h)
-/// clonova z env-binding -> ruzne Rust instance, ale stejne env Rc ptr,
-/// stejne jmeno, stejna arita. Same vola dvakrat -> identicke.
+/// clonova z env-binding -> ruzne Rust instance, ale stejne `id`
+/// (pridelene pri vytvoreni funkce, viz `next_func_id`).
 fn js_func_identity_eq(a: &super::JsFunc, b: &super::JsFunc) -> bool {
     use super::JsFunc::*;
     match (a, b) {
-        (User { name: n1, params: p1, env: e1, .. },
-         User { name: n2, params: p2, env: e2, .. }) => {
-            n1 == n2 && p1.len() == p2.len() && Rc::ptr_eq(e1, e2)
-        }
-        (Async { name: n1, params: p1, env: e1, .. },
-         Async { name: n2, params: p2, env: e2, .. }) => {
-            n1 == n2 && p1.len() == p2.len() && Rc::ptr_eq(e1, e2)
-        }
-        (Generator { name: n1, params: p1, env: e1, .. },
-         Generator { name: n2, params: p2, env: e2, .. }) => {
-            n1 == n2 && p1.len() == p2.len() && Rc::ptr_eq(e1, e2)
-        }
+        (User { id: i1, .. }, User { id: i2, .. })
+        | (Async { id: i1, .. }, Async { id: i2, .. })
+        | (Generator { id: i1, .. }, Generator { id: i2, .. })
+        | (Bound { id: i1, .. }, Bound { id: i2, .. })
+        | (VmCompiled { id: i1, .. }, VmCompiled { id: i2, .. }) => i1 == i2,
         (Native(_, f1), Native(_, f2)) => Rc::ptr_eq(f1, f2),
-        (Bound { func: f1, .. }, Bound { func: f2, .. }) => f1.function_identity_eq(f2),
+        (GeneratorResume { frame: f1, kind: k1 }, GeneratorResume { frame: f2, kind: k2 }) => {
+            k1 == k2 && Rc::ptr_eq(f1, f2)
+        }
+        (Class { static_props: s1, .. }, Class { static_props: s2, .. }) => Rc::ptr_eq(s1, s2),
         _ => false,
     }
 }
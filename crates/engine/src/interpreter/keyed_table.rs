@@ -0,0 +1,306 @@
+//! KeyedTable - usporadana hash tabulka pro `Map`/`Set`/`WeakMap`/`WeakSet`.
+//!
+//! Klice se porovnavaji pres SameValueZero: NaN se rovna NaN, `-0` a `+0` jsou
+//! stejny klic (vlozeny `-0` se ulozi jako `+0`), objekty/pole/kolekce podle
+//! identity (adresa `Rc`). Hash je s touto rovnosti konzistentni.
+//!
+//! Polozky lezi ve `Vec` v poradi vlozeni; `delete` nechava tombstone, aby
+//! bezici iteratory nepreskocily ani nezopakovaly polozku. Iterator drzi
+//! kurzor (`Cursor`) - pozici ve vektoru. Pri kompaktaci tombstonu a pri
+//! `clear` tabulka vsechny zive kurzory precisluje, takze iterace behem
+//! mutace odpovida spec: smazane polozky se preskoci, nove pridane se navstivi.
+//!
+//! Index mapuje hash klice na pozice (kolize rozlisi SameValueZero), takze
+//! lookup klic neklonuje.
+
+use std::cell::Cell;
+use std::collections::hash_map::RandomState;
+use std::collections::HashMap;
+use std::hash::{BuildHasher, Hash, Hasher};
+use std::rc::{Rc, Weak};
+
+use super::{JsFunc, JsValue};
+
+/// Kompaktace tombstonu az od teto delky vektoru.
+const COMPACT_MIN: usize = 16;
+
+/// Pozice iteratoru v tabulce (sdilena s tabulkou kvuli preclislovani).
+pub type Cursor = Rc<Cell<usize>>;
+
+/// Pozice polozek se stejnym hashem (kolize jsou vzacne - typicky `One`).
+#[derive(Debug, Clone)]
+enum Bucket {
+    One(usize),
+    Many(Vec<usize>),
+}
+
+impl Bucket {
+    fn positions(&self) -> &[usize] {
+        match self {
+            Bucket::One(i) => std::slice::from_ref(i),
+            Bucket::Many(v) => v,
+        }
+    }
+}
+
+/// SameValueZero (Map/Set klice, `Array.prototype.includes`).
+pub fn same_value_zero(a: &JsValue, b: &JsValue) -> bool {
+    match (a, b) {
+        (JsValue::Number(x), JsValue::Number(y)) => x == y || (x.is_nan() && y.is_nan()),
+        _ => a.strict_eq(b),
+    }
+}
+
+/// Adresa sdileneho kontejneru (identita objektu).
+fn addr<T: ?Sized>(r: &Rc<T>) -> usize {
+    Rc::as_ptr(r) as *const () as usize
+}
+
+fn hash_value<H: Hasher>(v: &JsValue, state: &mut H) {
+    std::mem::discriminant(v).hash(state);
+    match v {
+        JsValue::Bool(b) => b.hash(state),
+        // -0 == +0 a vsechny NaN jsou jeden klic.
+        JsValue::Number(n) if n.is_nan() => f64::NAN.to_bits().hash(state),
+        JsValue::Number(n) => (if *n == 0.0 { 0.0f64 } else { *n }).to_bits().hash(state),
+        JsValue::Str(s) => s.hash(state),
+        JsValue::Object(r) => addr(r).hash(state),
+        JsValue::Array(r) => addr(r).hash(state),
+        JsValue::Map(r) => addr(r).hash(state),
+        JsValue::Set(r) => addr(r).hash(state),
+        JsValue::DomNode(r) => addr(r).hash(state),
+        JsValue::Symbol(s) => s.id.hash(state),
+        JsValue::BigInt(n) => n.hash(state),
+        JsValue::Function(f) => hash_func(f, state),
+        // BigNumber: rovnost podle hodnoty, ruzne skaly - jeden bucket.
+        _ => {}
+    }
+}
+
+/// Hash funkce konzistentni s identitou funkci (`js_func_identity_eq`).
+fn hash_func<H: Hasher>(f: &JsFunc, state: &mut H) {
+    std::mem::discriminant(f).hash(state);
+    match f {
+        JsFunc::User { id, .. } | JsFunc::Async { id, .. } | JsFunc::Generator { id, .. }
+        | JsFunc::Bound { id, .. } | JsFunc::VmCompiled { id, .. } => id.hash(state),
+        JsFunc::Native(_, f) => addr(f).hash(state),
+        JsFunc::GeneratorResume { frame, .. } => addr(frame).hash(state),
+        JsFunc::Class { static_props, .. } => addr(static_props).hash(state),
+    }
+}
+
+/// Normalizace klice pri vlozeni (`-0` -> `+0`).
+fn normalize(mut k: JsValue) -> JsValue {
+    if let JsValue::Number(n) = &mut k && *n == 0.0 {
+        *n = 0.0;
+    }
+    k
+}
+
+/// Muze byt hodnota klicem WeakMap/WeakSet? (objekty a neregistrovane symboly)
+pub fn can_be_held_weakly(v: &JsValue) -> bool {
+    match v {
+        JsValue::Object(_) | JsValue::Array(_) | JsValue::Map(_) | JsValue::Set(_)
+        | JsValue::Function(_) | JsValue::DomNode(_) => true,
+        JsValue::Symbol(s) => super::symbols::key_for(s).is_none(),
+        _ => false,
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct KeyedTable<V> {
+    entries: Vec<Option<(JsValue, V)>>,
+    index: HashMap<u64, Bucket>,
+    hasher: RandomState,
+    len: usize,
+    cursors: Vec<Weak<Cell<usize>>>,
+}
+
+impl<V> Default for KeyedTable<V> {
+    fn default() -> Self {
+        Self { entries: Vec::new(), index: HashMap::new(), hasher: RandomState::new(), len: 0, cursors: Vec::new() }
+    }
+}
+
+impl<V> KeyedTable<V> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    fn hash(&self, k: &JsValue) -> u64 {
+        let mut h = self.hasher.build_hasher();
+        hash_value(k, &mut h);
+        h.finish()
+    }
+
+    /// Pozice klice ve vektoru.
+    fn position(&self, hash: u64, k: &JsValue) -> Option<usize> {
+        self.index.get(&hash)?.positions().iter().copied().find(|&i| {
+            matches!(&self.entries[i], Some((key, _)) if same_value_zero(key, k))
+        })
+    }
+
+    pub fn contains(&self, k: &JsValue) -> bool {
+        self.position(self.hash(k), k).is_some()
+    }
+
+    pub fn get(&self, k: &JsValue) -> Option<&V> {
+        let i = self.position(self.hash(k), k)?;
+        self.entries[i].as_ref().map(|(_, v)| v)
+    }
+
+    /// Vlozi nebo prepise hodnotu; existujici klic si necha poradi.
+    /// Vraci true pro novy klic.
+    pub fn insert(&mut self, k: JsValue, v: V) -> bool {
+        let k = normalize(k);
+        let hash = self.hash(&k);
+        if let Some(i) = self.position(hash, &k) {
+            if let Some((_, old)) = self.entries[i].as_mut() { *old = v; }
+            return false;
+        }
+        let pos = self.entries.len();
+        self.index.entry(hash)
+            .and_modify(|b| match b {
+                Bucket::One(first) => *b = Bucket::Many(vec![*first, pos]),
+                Bucket::Many(v) => v.push(pos),
+            })
+            .or_insert(Bucket::One(pos));
+        self.entries.push(Some((k, v)));
+        self.len += 1;
+        true
+    }
+
+    pub fn remove(&mut self, k: &JsValue) -> Option<V> {
+        let hash = self.hash(k);
+        let i = self.position(hash, k)?;
+        match self.index.get_mut(&hash) {
+            Some(Bucket::Many(v)) if v.len() > 1 => v.retain(|&p| p != i),
+            _ => { self.index.remove(&hash); }
+        }
+        self.len -= 1;
+        let removed = self.entries[i].take().map(|(_, v)| v);
+        if self.entries.len() > COMPACT_MIN && self.len * 2 < self.entries.len() {
+            self.compact();
+        }
+        removed
+    }
+
+    /// Vyprazdni tabulku; zive kurzory pokracuji od zacatku (uvidi nove polozky).
+    pub fn clear(&mut self) {
+        self.take();
+    }
+
+    /// Vyjme vsechny polozky (GC sweep / clear) - drop obsahu necha na volajicim.
+    pub fn take(&mut self) -> Vec<(JsValue, V)> {
+        self.index.clear();
+        self.len = 0;
+        self.cursors.retain(|c| c.upgrade().map(|c| c.set(0)).is_some());
+        std::mem::take(&mut self.entries).into_iter().flatten().collect()
+    }
+
+    /// Polozky v poradi vlozeni.
+    pub fn iter(&self) -> impl Iterator<Item = (&JsValue, &V)> {
+        self.entries.iter().flatten().map(|(k, v)| (k, v))
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &JsValue> {
+        self.iter().map(|(k, _)| k)
+    }
+
+    /// Novy kurzor na zacatku tabulky.
+    pub fn cursor(&mut self) -> Cursor {
+        let c = Rc::new(Cell::new(0));
+        self.cursors.retain(|w| w.strong_count() > 0);
+        self.cursors.push(Rc::downgrade(&c));
+        c
+    }
+
+    /// Dalsi ziva polozka od kurzoru; kurzor se posune za ni.
+    pub fn next_at(&self, cursor: &Cell<usize>) -> Option<(&JsValue, &V)> {
+        let mut i = cursor.get();
+        while i < self.entries.len() {
+            i += 1;
+            if let Some((k, v)) = &self.entries[i - 1] {
+                cursor.set(i);
+                return Some((k, v));
+            }
+        }
+        cursor.set(i);
+        None
+    }
+
+    /// Zahodi tombstony a preclsluje indexy i zive kurzory.
+    fn compact(&mut self) {
+        // remap[i] = nova pozice prvni zive polozky na/za starou pozici i.
+        let mut remap = Vec::with_capacity(self.entries.len() + 1);
+        let mut live = 0;
+        for e in &self.entries {
+            remap.push(live);
+            if e.is_some() { live += 1; }
+        }
+        remap.push(live);
+        self.cursors.retain(|w| match w.upgrade() {
+            Some(c) => {
+                c.set(remap[c.get().min(remap.len() - 1)]);
+                true
+            }
+            None => false,
+        });
+        self.entries.retain(Option::is_some);
+        for b in self.index.values_mut() {
+            match b {
+                Bucket::One(i) => *i = remap[*i],
+                Bucket::Many(v) => v.iter_mut().for_each(|i| *i = remap[*i]),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_value_zero_keys() {
+        let mut t: KeyedTable<i32> = KeyedTable::new();
+        t.insert(JsValue::Number(f64::NAN), 1);
+        t.insert(JsValue::Number(-0.0), 2);
+        assert_eq!(t.get(&JsValue::Number(f64::NAN)), Some(&1));
+        assert_eq!(t.get(&JsValue::Number(0.0)), Some(&2));
+        assert!(matches!(t.keys().nth(1), Some(JsValue::Number(n)) if n.is_sign_positive()));
+        assert!(!t.contains(&JsValue::Str("0".into())));
+    }
+
+    #[test]
+    fn cursor_survives_delete_and_compaction() {
+        let mut t: KeyedTable<()> = KeyedTable::new();
+        for i in 0..40 { t.insert(JsValue::Number(i as f64), ()); }
+        let c = t.cursor();
+        for _ in 0..5 { t.next_at(&c); }
+        for i in 0..30 { t.remove(&JsValue::Number(i as f64)); }
+        t.insert(JsValue::Number(100.0), ());
+        let rest: Vec<f64> = std::iter::from_fn(|| t.next_at(&c).map(|(k, _)| k.to_number())).collect();
+        assert_eq!(rest.first(), Some(&30.0));
+        assert_eq!(rest.last(), Some(&100.0));
+        assert_eq!(rest.len(), 11);
+    }
+
+    #[test]
+    fn clear_rewinds_cursors() {
+        let mut t: KeyedTable<()> = KeyedTable::new();
+        t.insert(JsValue::Str("a".into()), ());
+        let c = t.cursor();
+        t.next_at(&c);
+        t.clear();
+        t.insert(JsValue::Str("b".into()), ());
+        assert!(matches!(t.next_at(&c), Some((JsValue::Str(s), _)) if s == "b"));
+    }
+}
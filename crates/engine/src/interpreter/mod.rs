@@ -26,10 +26,11 @@
 /// - String metody: ~20 (split, slice, includes, ...)
 /// - Object staticke metody: keys, values, entries, assign, freeze, create, fromEntries
 
-use std::cell::RefCell;
-use std::collections::HashMap;
+use std::cell::{Cell, RefCell};
+use std::collections::{HashMap, HashSet};
 use std::rc::{Rc, Weak};
 use std::str::FromStr;
+use std::sync::atomic::{AtomicU64, Ordering};
 use crate::ast::*;
 use bigdecimal::BigDecimal;
 use bigdecimal::ToPrimitive;
@@ -44,6 +45,8 @@ use num_traits::Pow;
 pub mod helpers;
 pub mod console_args;
 pub mod gc;
+pub mod budget;
+pub mod host_bindings;
 pub mod service_worker;
 pub mod wasm;
 pub mod streams;
@@ -118,6 +121,8 @@ pub mod regex_engine;
 pub mod bignum;
 pub mod proxy_handler;
 pub mod typed_arrays;
+pub mod shared_memory;
+pub mod worker_message;
 pub mod worker_pool;
 pub mod persistent_storage;
 pub mod file_blob;
@@ -130,6 +135,8 @@ pub mod error_kinds;
 pub mod v8_inspector;
 pub mod stack_trace;
 pub mod cpu_profiler;
+pub mod time_zone;
+pub mod temporal;
 mod builtins;
 mod builtins_helpers;
 mod string_methods;
@@ -141,18 +148,33 @@ pub mod bytecode;
 mod js_value_impl;
 mod builtins_reflect;
 mod builtins_atomics;
+mod builtins_wasm;
+mod builtins_typed_arrays;
 mod builtins_temporal;
+mod builtins_intl;
 mod eval_member;
 mod eval_call;
 mod eval_expr;
 mod exec_stmt;
 mod class;
+pub(crate) mod private_names;
+pub(crate) mod strict_mode;
+pub(crate) mod disposal;
 mod call_machinery;
+mod generator;
+mod module_loader;
+mod tagged_template;
+pub mod symbols;
+pub mod property_map;
+pub mod keyed_table;
 #[allow(unused_imports)] // WebGLProgram je expose jen pro testy (cargo build je nevidi)
 pub(crate) use webgl::{WebGLState, WebGLProgram, WebGLDrawCmd, WebGLAttribSlot, WebGLUniformValue, UniformSlot, UniformSlotKind};
 use helpers::*;
 use builtins::setup_builtins;
 use string_methods::call_string_method;
+use property_map::{AsKey, KeyRef, PropertyKey, PropertyMap, PropertySlot};
+use keyed_table::KeyedTable;
+use crate::browser::modules_esm::EsmRegistry;
 
 // ─── JS hodnoty ───────────────────────────────────────────────────────────────
 
@@ -194,113 +216,132 @@ pub enum JsValue {
     /// DOM uzel - real reference do browser::dom tree.
     /// Sdileny pres Rc s rodicovskym/detskym tree.
     DomNode(Rc<crate::browser::dom::Node>),
+    /// Symbol - unikatni primitivni hodnota (identita podle id, viz `symbols`).
+    Symbol(Rc<symbols::JsSymbol>),
 }
 
 // ─── Map / Set datove struktury ──────────────────────────────────────────────
 
-/// JS `Map` - kolekce klicovanych hodnot (klic muze byt libovolny JsValue).
+/// JS `Map` / `WeakMap` - kolekce klicovanych hodnot (klic muze byt libovolny JsValue).
 ///
-/// Pouziva Vec<(key, value)> pro spravnou sematiku vsech klicu (vcetne NaN,
-/// objektu - porovnavane pres SameValueZero / referencni rovnost).
-#[derive(Debug, Clone)]
+/// Uloziste je `KeyedTable`: hash podle SameValueZero (NaN === NaN, `-0` == `+0`,
+/// objekty podle identity) se zachovanim poradi vlozeni.
+#[derive(Debug, Clone, Default)]
 pub struct JsMap {
-    /// Polozky v poradi vlozeni
-    pub entries: Vec<(JsValue, JsValue)>,
+    pub table: KeyedTable<JsValue>,
+    /// `WeakMap`: klice jen objekty/symboly, bez iterace a `size`. Cycle
+    /// collector bere klice jako slabe hrany (ephemeron, viz `gc.rs`).
+    pub weak: bool,
 }
 
 impl JsMap {
-    fn new() -> Self { JsMap { entries: Vec::new() } }
+    fn new() -> Self { JsMap::default() }
+
+    pub fn new_weak() -> Self { JsMap { weak: true, ..JsMap::default() } }
 
     /// Porovnani klicu: SameValueZero (NaN === NaN, objekty pres ptr_eq)
     fn key_eq(a: &JsValue, b: &JsValue) -> bool {
-        match (a, b) {
-            (JsValue::Number(x), JsValue::Number(y)) => {
-                if x.is_nan() && y.is_nan() { return true; }
-                x.to_bits() == y.to_bits()
-            }
-            (JsValue::Object(x), JsValue::Object(y))   => Rc::ptr_eq(x, y),
-            (JsValue::Array(x),  JsValue::Array(y))    => Rc::ptr_eq(x, y),
-            (JsValue::Map(x),    JsValue::Map(y))       => Rc::ptr_eq(x, y),
-            (JsValue::Set(x),    JsValue::Set(y))       => Rc::ptr_eq(x, y),
-            _ => a.strict_eq(b),
-        }
+        keyed_table::same_value_zero(a, b)
     }
 
     fn set(&mut self, key: JsValue, val: JsValue) {
-        for entry in &mut self.entries {
-            if Self::key_eq(&entry.0, &key) { entry.1 = val; return; }
-        }
-        self.entries.push((key, val));
+        gc::track_value(&key);
+        gc::track_value(&val);
+        self.table.insert(key, val);
     }
 
     fn get(&self, key: &JsValue) -> JsValue {
-        self.entries.iter()
-            .find(|(k, _)| Self::key_eq(k, key))
-            .map(|(_, v)| v.clone())
-            .unwrap_or(JsValue::Undefined)
+        self.table.get(key).cloned().unwrap_or(JsValue::Undefined)
     }
 
     fn has(&self, key: &JsValue) -> bool {
-        self.entries.iter().any(|(k, _)| Self::key_eq(k, key))
+        self.table.contains(key)
     }
 
     fn delete(&mut self, key: &JsValue) -> bool {
-        let before = self.entries.len();
-        self.entries.retain(|(k, _)| !Self::key_eq(k, key));
-        self.entries.len() < before
+        self.table.remove(key).is_some()
+    }
+
+    pub fn len(&self) -> usize { self.table.len() }
+
+    pub fn is_empty(&self) -> bool { self.table.is_empty() }
+
+    /// Polozky v poradi vlozeni.
+    pub fn entries(&self) -> impl Iterator<Item = (&JsValue, &JsValue)> {
+        self.table.iter()
     }
 }
 
-/// JS `Set` - kolekce unikatnich hodnot.
-#[derive(Debug, Clone)]
+/// JS `Set` / `WeakSet` - kolekce unikatnich hodnot.
+#[derive(Debug, Clone, Default)]
 pub struct JsSet {
-    pub values: Vec<JsValue>,
+    pub table: KeyedTable<()>,
+    /// `WeakSet` - viz `JsMap::weak`.
+    pub weak: bool,
 }
 
 impl JsSet {
-    fn new() -> Self { JsSet { values: Vec::new() } }
+    fn new() -> Self { JsSet::default() }
+
+    pub fn new_weak() -> Self { JsSet { weak: true, ..JsSet::default() } }
 
     fn has(&self, val: &JsValue) -> bool {
-        self.values.iter().any(|v| JsMap::key_eq(v, val))
+        self.table.contains(val)
     }
 
     fn add(&mut self, val: JsValue) {
-        if !self.has(&val) { self.values.push(val); }
+        gc::track_value(&val);
+        self.table.insert(val, ());
     }
 
     fn delete(&mut self, val: &JsValue) -> bool {
-        let before = self.values.len();
-        self.values.retain(|v| !JsMap::key_eq(v, val));
-        self.values.len() < before
+        self.table.remove(val).is_some()
+    }
+
+    pub fn len(&self) -> usize { self.table.len() }
+
+    pub fn is_empty(&self) -> bool { self.table.is_empty() }
+
+    /// Hodnoty v poradi vlozeni.
+    pub fn values(&self) -> impl Iterator<Item = &JsValue> {
+        self.table.keys()
     }
 }
 
-/// JS objekt - mapa retezec -> hodnota + prototypovy retezec.
+/// JS objekt - usporadane vlastnosti s deskriptory + prototypovy retezec.
 #[derive(Debug, Clone)]
 pub struct JsObject {
-    pub props: HashMap<String, JsValue>,
+    pub props: PropertyMap,
     /// Prototypovy objekt (`obj.__proto__`). None = zadny prototype (Object.create(null)).
     pub proto: Option<Rc<RefCell<JsObject>>>,
-    /// Object.freeze - po zavolani nelze menit/pridat vlastnosti.
-    pub frozen: bool,
+    /// Object.preventExtensions / seal / freeze - false = nelze pridat nove vlastnosti.
+    pub extensible: bool,
+    /// Bajty ArrayBufferu / TypedArray / DataView (`typed_arrays.rs`). Indexy
+    /// a delky techto objektu se ctou primo z bufferu, ne z `props`.
+    pub binary: Option<Rc<typed_arrays::BinaryData>>,
 }
 
 impl JsObject {
     pub fn new() -> Self {
-        JsObject { props: HashMap::new(), proto: None, frozen: false }
+        JsObject { props: PropertyMap::new(), proto: None, extensible: true, binary: None }
     }
 
     /// Vytvori objekt s danym prototypem (Object.create(proto)).
     pub fn new_with_proto(proto: Rc<RefCell<JsObject>>) -> Self {
-        JsObject { props: HashMap::new(), proto: Some(proto), frozen: false }
+        JsObject { props: PropertyMap::new(), proto: Some(proto), extensible: true, binary: None }
     }
 
     /// Cte vlastnost - prochazi prototypovym retezcem (max 100 uroven).
-    pub fn get(&self, k: &str) -> JsValue {
+    /// Accessor vraci `Undefined` - getter vola interpret (`find_accessor`).
+    pub fn get(&self, k: impl AsKey) -> JsValue {
+        let k = k.key_ref();
+        if let KeyRef::Str(s) = k && let Some(b) = &self.binary && let Some(v) = b.get(s) {
+            return v;
+        }
         self.get_depth(k, 0)
     }
 
-    fn get_depth(&self, k: &str, depth: usize) -> JsValue {
+    fn get_depth(&self, k: KeyRef, depth: usize) -> JsValue {
         if depth > 100 { return JsValue::Undefined; }
         if let Some(v) = self.props.get(k) {
             return v.clone();
@@ -311,25 +352,144 @@ impl JsObject {
         JsValue::Undefined
     }
 
+    /// Accessor vlastnost `k` (vlastni nebo zdedena): (getter, setter).
+    /// None = vlastnost neexistuje nebo je data property.
+    pub fn find_accessor(&self, k: impl AsKey) -> Option<(Option<JsValue>, Option<JsValue>)> {
+        self.find_accessor_depth(k.key_ref(), 0)
+    }
+
+    fn find_accessor_depth(&self, k: KeyRef, depth: usize) -> Option<(Option<JsValue>, Option<JsValue>)> {
+        if depth > 100 { return None; }
+        if let Some(slot) = self.props.slot(k) {
+            return slot.is_accessor().then(|| (slot.getter.clone(), slot.setter.clone()));
+        }
+        self.proto.as_ref()?.borrow().find_accessor_depth(k, depth + 1)
+    }
+
     /// Kontroluje vlastni vlastnost (bez prochazeni prototypoveho retezce).
-    pub fn has_own(&self, k: &str) -> bool {
-        self.props.contains_key(k)
+    pub fn has_own(&self, k: impl AsKey) -> bool {
+        let k = k.key_ref();
+        self.props.contains_key(k) || matches!(k, KeyRef::Str(s) if self.binary.as_ref().is_some_and(|b| b.has_own(s)))
+    }
+
+    /// Prirazeni `o.k = v` bez setteru: non-writable a accessor vlastnosti
+    /// i pridani do neroztazitelneho objektu se tise ignoruji (sloppy mode).
+    /// Chybu konverze prvku typed array (BigInt vs Number) hlasi jen
+    /// interpret / VM pres `BinaryData::set`, tady se zapis zahodi.
+    pub fn set(&mut self, k: PropertyKey, v: JsValue) {
+        if let (Some(b), PropertyKey::Str(s)) = (&self.binary, &k) && b.set(s, &v).is_some() {
+            return;
+        }
+        if let Some(slot) = self.props.slot_mut(&k) {
+            if slot.writable && !slot.is_accessor() {
+                gc::track_value(&v);
+                slot.value = v;
+            }
+            return;
+        }
+        if self.extensible {
+            self.props.insert(k, v);
+        }
+    }
+
+    /// `delete o.k` - non-configurable vlastnost smazat nelze (vraci false).
+    pub fn delete(&mut self, k: impl AsKey) -> bool {
+        let k = k.key_ref();
+        if self.props.slot(k).is_some_and(|s| !s.configurable) { return false; }
+        self.props.remove(k);
+        true
+    }
+
+    /// Lze prirazenim zmenit/pridat vlastnost `k`? (writable + extensible)
+    pub fn can_put(&self, k: impl AsKey) -> bool {
+        match self.props.slot(k) {
+            Some(slot) => slot.writable && !slot.is_accessor(),
+            None => self.extensible,
+        }
+    }
+
+    /// Definuje getter/setter (`get x() {}`). Druhou polovinu existujiciho
+    /// accessoru zachova, takze `get x` + `set x` tvori jednu vlastnost.
+    pub fn define_accessor(&mut self, k: PropertyKey, getter: Option<JsValue>, setter: Option<JsValue>) {
+        if let Some(slot) = self.props.slot_mut(&k).filter(|s| s.is_accessor()) {
+            if getter.is_some() { slot.getter = getter; }
+            if setter.is_some() { slot.setter = setter; }
+            return;
+        }
+        self.props.insert_slot(k, PropertySlot::accessor(getter, setter));
     }
 
-    /// Nastavi vlastnost. Frozen objekt zmeny ignoruje.
-    pub fn set(&mut self, k: String, v: JsValue) {
-        if self.frozen { return; }
-        self.props.insert(k, v);
+    /// Definuje ne-enumerable data vlastnost (metody trid, vestavene metody).
+    pub fn define_hidden(&mut self, k: PropertyKey, v: JsValue) {
+        let mut slot = PropertySlot::data(v);
+        slot.enumerable = false;
+        self.props.insert_slot(k, slot);
     }
 
-    /// Vrati serazeny seznam vlastnich klicu (bez internich `__key__` klicu).
+    /// Vlastni klice v poradi podle specifikace: array indexy vzestupne, pak
+    /// ostatni stringove klice v poradi vlozeni. Bez internich `__key__` klicu.
+    /// `only_enumerable` = Object.keys / for-in / JSON; jinak getOwnPropertyNames.
+    pub fn ordered_keys(&self, only_enumerable: bool) -> Vec<String> {
+        let mut indices: Vec<(u32, String)> = Vec::new();
+        let mut named: Vec<String> = Vec::new();
+        // Prvky typed array jsou vlastni enumerable klice
+        if let Some(typed_arrays::BinaryData::TypedArray(view)) = self.binary.as_deref() {
+            indices.extend((0..view.length()).map(|i| (i as u32, i.to_string())));
+        }
+        for (k, slot) in self.props.slots() {
+            if is_internal_key(k) || (only_enumerable && !slot.enumerable) { continue; }
+            match property_map::array_index(k) {
+                Some(i) => indices.push((i, k.clone())),
+                None => named.push(k.clone()),
+            }
+        }
+        indices.sort_by_key(|(i, _)| *i);
+        indices.into_iter().map(|(_, k)| k).chain(named).collect()
+    }
+
+    /// Vlastni enumerable klice (Object.keys) ve spec poradi.
     pub fn own_keys(&self) -> Vec<String> {
-        let mut keys: Vec<String> = self.props.keys()
-            .filter(|k| !is_internal_key(k))
-            .cloned()
-            .collect();
-        keys.sort();
-        keys
+        self.ordered_keys(true)
+    }
+
+    /// Vlastni symbol-klicovane vlastnosti v poradi vlozeni.
+    pub fn own_symbols(&self) -> Vec<JsValue> {
+        self.props.symbol_keys().map(|s| JsValue::Symbol(Rc::clone(s))).collect()
+    }
+
+    /// Object.preventExtensions.
+    pub fn prevent_extensions(&mut self) {
+        self.extensible = false;
+    }
+
+    /// Object.seal - neroztazitelny + vsechny vlastnosti non-configurable.
+    pub fn seal(&mut self) {
+        self.extensible = false;
+        for k in self.props.entries().map(|(k, _)| k.clone()).collect::<Vec<_>>() {
+            if let Some(slot) = self.props.slot_mut(&k) { slot.configurable = false; }
+        }
+    }
+
+    /// Object.freeze - seal + data vlastnosti non-writable.
+    pub fn freeze(&mut self) {
+        self.extensible = false;
+        for k in self.props.entries().map(|(k, _)| k.clone()).collect::<Vec<_>>() {
+            if let Some(slot) = self.props.slot_mut(&k) {
+                slot.configurable = false;
+                if !slot.is_accessor() { slot.writable = false; }
+            }
+        }
+    }
+
+    /// Object.isSealed - neroztazitelny a zadna (ne-interni) vlastnost neni configurable.
+    pub fn is_sealed(&self) -> bool {
+        !self.extensible && self.props.entries().all(|(k, s)| k.is_internal() || !s.configurable)
+    }
+
+    /// Object.isFrozen - sealed a zadna data vlastnost neni writable.
+    pub fn is_frozen(&self) -> bool {
+        self.is_sealed() && self.props.entries().all(|(k, s)|
+            k.is_internal() || s.is_accessor() || !s.writable)
     }
 }
 
@@ -341,12 +501,57 @@ type NativeFn = Rc<dyn Fn(Vec<JsValue>) -> Result<JsValue, String>>;
 /// Sdilena pro instance metody i staticke metody.
 #[derive(Debug, Clone)]
 pub struct ClassMethodDef {
-    /// Jmeno metody
-    pub name: String,
+    /// Klic metody
+    pub name: PropertyKey,
     /// Parametry
     pub params: Vec<Param>,
     /// Telo
     pub body: Vec<Stmt>,
+    /// Nahrada z dekoratoru (`@dec m() {}`) - pouzije se misto `params`/`body`
+    pub decorated: Option<JsValue>,
+    /// Identita funkce metody - stejna pro vsechna zhmotneni z `func()`
+    pub id: u64,
+}
+
+impl ClassMethodDef {
+    /// Hodnota metody: vysledek dekoratoru, jinak funkce z tela v `env`.
+    pub fn func(&self, env: &Rc<RefCell<Env>>) -> JsValue {
+        if let Some(f) = &self.decorated {
+            return f.clone();
+        }
+        JsValue::Function(JsFunc::User {
+            name: Some(match &self.name {
+                PropertyKey::Str(s) => s.clone(),
+                PropertyKey::Sym(sym) => format!("[{}]", sym.description.as_deref().unwrap_or_default()),
+            }),
+            params: self.params.clone(),
+            body: FuncBody::Stmts(self.body.clone()),
+            env: Rc::clone(env),
+            is_arrow: false,
+            id: self.id,
+        })
+    }
+}
+
+/// Nova identita funkce (`===`, klice Map/Set): kazde vyhodnoceni
+/// funkcniho vyrazu nebo deklarace je samostatny objekt.
+pub(crate) fn next_func_id() -> u64 {
+    static NEXT: AtomicU64 = AtomicU64::new(1);
+    NEXT.fetch_add(1, Ordering::Relaxed)
+}
+
+/// Pole instance tridy (`x = 1` / `#x = 1`) - inicializuje se pri `new`
+/// pred telem konstruktoru (zakladni trida) nebo hned po `super()`.
+#[derive(Debug, Clone)]
+pub struct ClassFieldDef {
+    /// Klic vlastnosti (u privatniho pole interni klic z `private_names`)
+    pub key: PropertyKey,
+    /// Inicializator (`None` = `undefined`)
+    pub value: Option<Expr>,
+    /// Inicializatory z dekoratoru pole - postupne transformuji pocatecni hodnotu
+    pub decorators: Vec<JsValue>,
+    /// `context.addInitializer` dekoratoru pole - bezi hned po definici pole
+    pub initializers: Vec<JsValue>,
 }
 
 /// Reprezentace funkce v runtime.
@@ -354,19 +559,30 @@ pub struct ClassMethodDef {
 /// - `User`      - funkce definovana v JS kodu, ulozena jako AST + uzavreny scope
 /// - `Native`    - funkce implementovana v Rustu (Math.sqrt, console.log, atd.)
 /// - `Class`     - JS trida: obsahuje konstruktor, instance metody, staticke metody
-/// - `Generator` - generator funkce (`function*`): vraci iterator pres yielded hodnoty
+/// - `Generator` - generator funkce (`function*`): vraci pozastavitelny generator objekt
 #[derive(Clone)]
 pub enum JsFunc {
     /// Uzivatelska JS funkce. Uchovava si uzavreny `env` (closure).
-    User { name: Option<String>, params: Vec<Param>, body: FuncBody, env: Rc<RefCell<Env>> },
+    /// `is_arrow` = arrow funkce: `this` bere lexikalne, ne z volani.
+    /// `id` = identita funkce (viz `next_func_id`).
+    User { name: Option<String>, params: Vec<Param>, body: FuncBody, env: Rc<RefCell<Env>>, is_arrow: bool, id: u64 },
     /// Nativni Rust funkce. Prvni parametr je jmeno pro debugovani.
     Native(String, NativeFn),
-    /// Generator funkce (`function*`). Pri zavolani vraci iterator objekt.
+    /// Generator funkce (`function*`). Pri zavolani vraci generator objekt.
+    /// `is_async` = `async function*` (next/return/throw vraci Promise).
     Generator {
         name: Option<String>,
         params: Vec<Param>,
         body: Vec<Stmt>,
         env: Rc<RefCell<Env>>,
+        is_async: bool,
+        id: u64,
+    },
+    /// Metoda generator objektu (`next` / `return` / `throw`). Obnovi
+    /// pozastaveny ramec generatoru - viz `generator.rs`.
+    GeneratorResume {
+        frame: Rc<RefCell<generator::GeneratorFrame>>,
+        kind: generator::ResumeKind,
     },
     /// Async funkce (`async function`). Vraci vzdycky Promise.
     /// Vyjimka uvnitr = rejected Promise, return value = fulfilled Promise.
@@ -375,6 +591,9 @@ pub enum JsFunc {
         params: Vec<Param>,
         body: FuncBody,
         env: Rc<RefCell<Env>>,
+        /// `async () => ...` - `this` lexikalne
+        is_arrow: bool,
+        id: u64,
     },
     /// Bound funkce - vysledek fn.bind(thisArg, ...args).
     /// Pri volani prepoji bound_this a prida bound_args pred call args.
@@ -382,6 +601,7 @@ pub enum JsFunc {
         func: Box<JsValue>,
         bound_this: Box<JsValue>,
         bound_args: Vec<JsValue>,
+        id: u64,
     },
     /// JS trida. `super_val` = vyhodnocena rodicovska trida.
     ///
@@ -406,6 +626,14 @@ pub enum JsFunc {
         getters: Vec<ClassMethodDef>,
         /// Setters (pri prirazeni vlastnosti zavolat funkci)
         setters: Vec<ClassMethodDef>,
+        /// Pole instance v poradi deklarace
+        fields: Vec<ClassFieldDef>,
+        /// `context.addInitializer` z dekoratoru metod instance - bezi pri
+        /// konstrukci pred inicializaci poli (this = instance)
+        initializers: Vec<JsValue>,
+        /// Vlastni vlastnosti tridy (staticka pole, staticke accessory,
+        /// privatni staticke cleny, `Foo.x = ...`); proto = rodicovska trida
+        static_props: Rc<RefCell<JsObject>>,
         /// Uzavreny scope kde byla trida definovana
         env: Rc<RefCell<Env>>,
     },
@@ -417,8 +645,10 @@ pub enum JsFunc {
         /// Closure env z misto definice (pro outer var lookup z VM).
         env: Rc<RefCell<Env>>,
         /// Closure captures - hodnoty volnych promennych z outer scope at
-        /// LoadFunction time. Indexovane podle CompiledFunction.captures_outer_indices.
+        /// LoadFunction time. Indexovane podle CompiledFunction.captures.
         captures: Vec<JsValue>,
+        /// Identita closure - vlastnosti funkce (`F.prototype`) jsou podle ni.
+        id: u64,
     },
 }
 
@@ -434,6 +664,17 @@ pub enum FuncBody {
 
 type Env = Environment;
 
+/// Importovane vazby scopu modulu: lokalni jmeno -> (scope zdroje, jmeno v nem).
+type ImportBindings = HashMap<String, (Rc<RefCell<Environment>>, String)>;
+
+/// Lexikalni vazby scopu: `let`/`const`/`class` pred inicializaci (TDZ)
+/// a jmena `const` vazeb.
+#[derive(Debug, Clone, Default)]
+struct LexicalState {
+    uninit: HashSet<String>,
+    consts: HashSet<String>,
+}
+
 /// Lexikalni scope (prostredi promennych).
 ///
 /// Implementuje retezec scopes: kazdy scope ma volitelny `parent`.
@@ -457,6 +698,14 @@ pub struct Environment {
     /// nejblizsiho env s `is_function_scope=true`. Block-level envs (for/if)
     /// false; function call_env true; global env true.
     pub is_function_scope: bool,
+    /// Importovane vazby modulu: lokalni jmeno -> (scope exportujiciho modulu,
+    /// jmeno vazby v nem). Cte se vzdy ze zdroje (live binding), zapis je chyba.
+    /// Jen u scopu ES modulu, jinak None.
+    imports: Option<Box<ImportBindings>>,
+    /// TDZ a `const` vazby. Jen u scopu s lexikalnimi deklaracemi, jinak None.
+    lexical: Option<Box<LexicalState>>,
+    /// Kod scopu je strict; child scopy rezim dedi.
+    pub strict: bool,
 }
 
 impl Environment {
@@ -472,20 +721,34 @@ impl Environment {
 
     /// Vytvori novy globalni scope (bez rodice).
     pub fn new_global() -> Rc<RefCell<Self>> {
-        Rc::new(RefCell::new(Environment { vars: HashMap::new(), parent: None, is_function_scope: true }))
+        Rc::new(RefCell::new(Environment { vars: HashMap::new(), parent: None, is_function_scope: true, imports: None, lexical: None, strict: false }))
     }
 
     /// Vytvori novy child scope (blok, funkce, ...).
     /// Default block-scope (is_function_scope=false). Vola se z exec_stmt
     /// pro {} bloky, for/if/while. Pro function calls pouzij `new_function_child`.
     pub fn new_child(parent: &Rc<RefCell<Environment>>) -> Rc<RefCell<Self>> {
-        Rc::new(RefCell::new(Environment { vars: HashMap::new(), parent: Some(Rc::clone(parent)), is_function_scope: false }))
+        let strict = parent.borrow().strict;
+        Rc::new(RefCell::new(Environment { vars: HashMap::new(), parent: Some(Rc::clone(parent)), is_function_scope: false, imports: None, lexical: None, strict }))
     }
 
     /// Vytvori novy function-call scope. `var` declarations hoist se sem
     /// (ne pres globalni env, jako tomu bylo drive - to byl bug).
     pub fn new_function_child(parent: &Rc<RefCell<Environment>>) -> Rc<RefCell<Self>> {
-        Rc::new(RefCell::new(Environment { vars: HashMap::new(), parent: Some(Rc::clone(parent)), is_function_scope: true }))
+        let strict = parent.borrow().strict;
+        Rc::new(RefCell::new(Environment { vars: HashMap::new(), parent: Some(Rc::clone(parent)), is_function_scope: true, imports: None, lexical: None, strict }))
+    }
+
+    /// Globalni scope retezce (posledni rodic).
+    pub fn global_of(env: &Rc<RefCell<Self>>) -> Rc<RefCell<Self>> {
+        let mut cur = Rc::clone(env);
+        loop {
+            let next = cur.borrow().parent.clone();
+            match next {
+                Some(p) => cur = p,
+                None => return cur,
+            }
+        }
     }
 
     /// Walk pres parent chain najit nejblizsi function-scope env. Pouziva se
@@ -505,7 +768,61 @@ impl Environment {
 
     /// Deklaruje novou promennou v tomto scopu (let/const/var).
     pub fn define(&mut self, name: &str, val: JsValue) {
+        gc::track_value(&val);
         self.vars.insert(name.to_string(), val);
+        if let Some(l) = &mut self.lexical {
+            l.uninit.remove(name);
+            l.consts.remove(name);
+        }
+    }
+
+    /// Zacatek TDZ: vazba `let`/`const`/`class` patri bloku od jeho zacatku,
+    /// ale do provedeni deklarace ji nelze pouzit. Uz definovanou vazbu
+    /// (opakovane spusteny skript) nechava byt.
+    pub fn declare_uninit(&mut self, name: &str) {
+        if !self.vars.contains_key(name) {
+            self.lexical.get_or_insert_with(Default::default).uninit.insert(name.to_string());
+        }
+    }
+
+    /// Oznaci definovanou vazbu tohoto scopu jako `const`.
+    pub fn mark_const(&mut self, name: &str) {
+        self.lexical.get_or_insert_with(Default::default).consts.insert(name.to_string());
+    }
+
+    fn is_uninit(&self, name: &str) -> bool {
+        self.lexical.as_ref().is_some_and(|l| l.uninit.contains(name))
+    }
+
+    fn is_const(&self, name: &str) -> bool {
+        self.lexical.as_ref().is_some_and(|l| l.consts.contains(name))
+    }
+
+    /// Vytvori importovanou vazbu `local` -> `name` ve scopu `source`.
+    pub fn define_import(&mut self, local: &str, source: Rc<RefCell<Environment>>, name: &str) {
+        self.imports.get_or_insert_with(Default::default).insert(local.to_string(), (source, name.to_string()));
+    }
+
+    /// Importovana vazba tohoto scopu (bez parent walk).
+    fn import_binding(&self, name: &str) -> Option<&(Rc<RefCell<Environment>>, String)> {
+        self.imports.as_ref()?.get(name)
+    }
+
+    /// Je `name` v retezci scopu importovana vazba (a ne zastinena promennou)?
+    pub fn is_import(&self, name: &str) -> bool {
+        if self.vars.contains_key(name) { return false; }
+        if self.import_binding(name).is_some() { return true; }
+        self.parent.as_ref().is_some_and(|p| p.borrow().is_import(name))
+    }
+
+    /// Cte vazbu jen z tohoto scopu (promenna nebo import), bez parent walk.
+    /// Scope modulu nesmi propadnout do globalu - neinicializovana vazba = None.
+    pub fn get_own(&self, name: &str) -> Option<JsValue> {
+        if let Some(v) = self.vars.get(name) {
+            return Some(v.clone());
+        }
+        let (source, binding) = self.import_binding(name)?;
+        source.borrow().get_own(binding)
     }
 
     /// Cte promennou - hleda od tohoto scopu az ke globalnimu.
@@ -515,6 +832,9 @@ impl Environment {
         if let Some(v) = self.vars.get(name) {
             return Some(v.clone());
         }
+        if let Some((source, binding)) = self.import_binding(name) {
+            return source.borrow().get_own(binding);
+        }
         // Iterate parent chain bez rekurze.
         let mut cur = self.parent.clone();
         while let Some(env_rc) = cur {
@@ -522,26 +842,98 @@ impl Environment {
             if let Some(v) = env.vars.get(name) {
                 return Some(v.clone());
             }
+            if let Some((source, binding)) = env.import_binding(name) {
+                return source.borrow().get_own(binding);
+            }
+            cur = env.parent.clone();
+        }
+        None
+    }
+
+    /// Vazba `name` primo v tomto scopu pro cteni z JS kodu: `Some(Err)` je
+    /// vazba v TDZ, `None` = tady neni.
+    fn lookup_own(&self, name: &str) -> Option<Result<Option<JsValue>, String>> {
+        if let Some(v) = self.vars.get(name) {
+            return Some(Ok(Some(v.clone())));
+        }
+        if self.is_uninit(name) {
+            return Some(Err(strict_mode::tdz_error(name)));
+        }
+        let (source, binding) = self.import_binding(name)?;
+        Some(Ok(source.borrow().get_own(binding)))
+    }
+
+    /// Cteni promenne z JS kodu: jako `get`, ale vazba v TDZ je `Err`
+    /// s ReferenceError (a nezastini stejne jmeno z vnejsiho scopu).
+    pub fn lookup(&self, name: &str) -> Result<Option<JsValue>, String> {
+        if let Some(r) = self.lookup_own(name) {
+            return r;
+        }
+        let mut cur = self.parent.clone();
+        while let Some(env_rc) = cur {
+            let env = env_rc.borrow();
+            if let Some(r) = env.lookup_own(name) {
+                return r;
+            }
             cur = env.parent.clone();
         }
+        Ok(None)
+    }
+
+    /// Prirazeni z JS kodu pro jeden scope - `None` = vazba tady neni.
+    fn assign_own(&mut self, name: &str, val: &JsValue) -> Option<Result<(), String>> {
+        if self.vars.contains_key(name) {
+            if self.is_const(name) {
+                return Some(Err(strict_mode::CONST_ASSIGN.to_string()));
+            }
+            gc::track_value(val);
+            self.vars.insert(name.to_string(), val.clone());
+            return Some(Ok(()));
+        }
+        if self.is_uninit(name) {
+            return Some(Err(strict_mode::tdz_error(name)));
+        }
+        if self.import_binding(name).is_some() {
+            return Some(Err(strict_mode::CONST_ASSIGN.to_string()));
+        }
         None
     }
 
+    /// Prirazeni `name = val` z JS kodu: jako `set`, ale zapis do `const`
+    /// vazby, importu nebo vazby v TDZ je chyba. `Ok(false)` = promenna neexistuje.
+    pub fn assign(&mut self, name: &str, val: JsValue) -> Result<bool, String> {
+        if let Some(r) = self.assign_own(name, &val) {
+            return r.map(|_| true);
+        }
+        let mut cur = self.parent.clone();
+        while let Some(env_rc) = cur {
+            if let Some(r) = env_rc.borrow_mut().assign_own(name, &val) {
+                return r.map(|_| true);
+            }
+            cur = env_rc.borrow().parent.clone();
+        }
+        Ok(false)
+    }
+
     /// Prirazuje hodnotu existujici promenne (hleda ji v retezci scopu).
     ///
     /// Vraci `true` kdyz promennou nasla a zmenila,
     /// `false` kdyz promenna neexistuje (volajici pak muze rozhodnout co delat).
     pub fn set(&mut self, name: &str, val: JsValue) -> bool {
+        gc::track_value(&val);
         // Self scope first.
         if self.vars.contains_key(name) {
             self.vars.insert(name.to_string(), val);
             return true;
         }
+        // Importovana vazba je jen pro cteni - volajici hlasi TypeError.
+        if self.import_binding(name).is_some() { return false; }
         // Iterativni walk parent chain.
         let mut cur = self.parent.clone();
         while let Some(env_rc) = cur {
             // Try contains_key first (cheap immutable borrow), then mutable.
             let has = env_rc.borrow().vars.contains_key(name);
+            if !has && env_rc.borrow().import_binding(name).is_some() { return false; }
             if has {
                 env_rc.borrow_mut().vars.insert(name.to_string(), val);
                 return true;
@@ -561,6 +953,9 @@ pub enum JsError {
     Runtime(String),
     /// Hodnota vyhozena pomoci `throw` (zachytitelna v JS `catch`)
     Thrown(JsValue),
+    /// Interni unwind generatoru po `gen.return(v)` - `catch` ho nechyta,
+    /// `finally` bloky se vykonaji. Ramec generatoru ho prevede na navrat.
+    GeneratorReturn(JsValue),
 }
 
 impl std::fmt::Display for JsError {
@@ -568,6 +963,7 @@ impl std::fmt::Display for JsError {
         match self {
             JsError::Runtime(s) => write!(f, "RuntimeError: {s}"),
             JsError::Thrown(v)  => write!(f, "Uncaught: {v}"),
+            JsError::GeneratorReturn(v) => write!(f, "GeneratorReturn: {v}"),
         }
     }
 }
@@ -612,15 +1008,45 @@ type StmtResult = Result<Option<Signal>, JsError>;
 /// ```
 /// Real Worker thread state.
 /// Kazdy Worker bezi v separatnim threadu s vlastnim Interpreterem.
-/// Komunikace pres mpsc kanaly, JSON-serializovane zpravy.
+/// Komunikace pres mpsc kanaly, zpravy = JSON + sdilene pameti (worker_message.rs).
 pub struct WorkerState {
-    pub sender: std::sync::mpsc::Sender<String>,
-    pub outgoing: std::sync::mpsc::Receiver<String>,
+    pub sender: std::sync::mpsc::Sender<worker_message::WorkerMessage>,
+    pub outgoing: std::sync::mpsc::Receiver<worker_message::WorkerMessage>,
     pub handle: Option<std::thread::JoinHandle<()>>,
     /// onmessage callback registrovany z main threadu
     pub on_message: Option<JsValue>,
 }
 
+/// Stav agenta pro Atomics (ECMAScript agent = jedno vlakno s interpretem).
+/// Main thread blokovat nesmi (`Atomics.wait` hodi TypeError), Worker ano.
+/// `cross_origin_isolated` plyne z COOP/COEP dokumentu a hlida
+/// SharedArrayBuffer + jeho posilani Workerum. Bez hlavicek neni agent izolovany.
+pub struct AgentState {
+    pub can_block: std::cell::Cell<bool>,
+    pub cross_origin_isolated: std::cell::Cell<bool>,
+    /// Cekajici `Atomics.waitAsync` - settle v `drain_atomic_waits`.
+    pub async_waits: RefCell<Vec<AsyncWait>>,
+}
+
+impl Default for AgentState {
+    fn default() -> Self {
+        Self {
+            can_block: std::cell::Cell::new(false),
+            cross_origin_isolated: std::cell::Cell::new(false),
+            async_waits: RefCell::new(Vec::new()),
+        }
+    }
+}
+
+/// Jeden `Atomics.waitAsync` ve fronte sdilene pameti.
+pub struct AsyncWait {
+    pub memory: std::sync::Arc<shared_memory::SharedMemory>,
+    pub waiter: u64,
+    /// None = bez timeoutu.
+    pub deadline: Option<std::time::Instant>,
+    pub promise: JsValue,
+}
+
 /// WebSocket state - background thread cte z connection + posila incoming pres
 /// outgoing channel. Main interpreter posila send-message pres sender.
 pub struct WebSocketState {
@@ -649,7 +1075,7 @@ pub struct PendingFetch {
     pub receiver: std::sync::mpsc::Receiver<FetchOutcome>,
 }
 
-pub type FetchOutcome = Result<(u16, String, String, Vec<(String, String)>), String>;
+pub type FetchOutcome = Result<(u16, String, Vec<u8>, Vec<(String, String)>), String>;
 
 /// ResizeObserver state - drzi callback + sdileny seznam targets s observer JS
 /// obj + prev rect snapshot. WebView po layout fire callback pri change.
@@ -686,12 +1112,30 @@ pub enum WebSocketEvent {
     Closed,
 }
 
+/// Stav interpretu. `Clone` je melky - kopie sdili vsechen heap stav (Rc).
+/// Pouziva se pro ramce generatoru, ktere bezi na vlastnim zasobniku
+/// (viz `generator.rs`).
+#[derive(Clone)]
 pub struct Interpreter {
     /// Globalni scope - obsahuje vestavene funkce (Math, console, atd.)
     pub global: Rc<RefCell<Environment>>,
-    /// Generator mode: Some = shromazduji yield hodnoty misto preruseni
-    /// None = normalni rezim
-    yield_buffer: Option<Vec<JsValue>>,
+    /// Yielder korutiny aktualniho generatoru. Some jen v interpretu, ktery
+    /// vlastni ramec generatoru; None = normalni rezim (`yield` je chyba).
+    gen_yielder: Option<*const generator::GenYielder>,
+    /// Hranice (limit, base) aktualniho segmentu zasobniku ramce generatoru;
+    /// None mimo generator. Viz `stack_headroom_low`.
+    gen_stack: Option<(usize, usize)>,
+    /// Interpret vlastni ramec tela ES modulu - `await` na pending promise
+    /// pozastavi modul (top-level await) misto synchronniho rozbaleni.
+    module_await: bool,
+    /// Top-level kod `eval_script` (REPL) - `await` na pending promise
+    /// dockaje settle drainem event loopu.
+    script_await: bool,
+    /// Prave bezi top-level kod skriptu s `"use strict"` - globalni scope sam
+    /// strict neni, rezim se predava jen funkcim vytvorenym v tomto kodu.
+    /// Sdileno s ramci generatoru (viz `generator`), stejne jako
+    /// `current_span`, `call_frames` a `execution_limits`.
+    strict_script: Rc<Cell<bool>>,
     /// Fronta one-shot timeru pro setTimeout (id, callback, args).
     /// drain_timers vola + REMOVE - kazdy task bezi 1x.
     /// setTimeout fronta: (id, fire_at, cb, args). fire_at = kdy task DOZRAJE
@@ -705,16 +1149,16 @@ pub struct Interpreter {
     pub(crate) interval_queue: Rc<RefCell<Vec<IntervalEntry>>>,
     /// Pocitadlo ID pro setTimeout/setInterval (sdilene namespace).
     next_timer_id: Rc<RefCell<u32>>,
-    /// Cache nactenych modulu: cesta -> namespace objekt s exporty
+    /// Graf ES modulu: resolved URL -> zaznam (stav, scope, namespace).
     /// Sdileny pres Rc, aby ho videly cizi/dynamicky importy.
-    module_cache: Rc<RefCell<HashMap<String, JsValue>>>,
+    modules: Rc<RefCell<EsmRegistry>>,
+    /// Import mapa stranky (`<script type="importmap">`).
+    pub import_map: Rc<RefCell<import_maps::ImportMap>>,
     /// Vetev pro testy / virtualni FS: source -> obsah
     /// Pokud je naplneno, importy se hledaji nejdrive zde.
     pub virtual_modules: Rc<RefCell<HashMap<String, String>>>,
-    /// Aktualni "export" mapa - aktivni jen behem nacitani modulu.
-    /// Stmt::Export prida do tohoto pole; po skonceni se konstruuje namespace.
-    current_exports: Option<Rc<RefCell<HashMap<String, JsValue>>>>,
-    /// Zakladni adresar pro resolve relativnich modulu (current dir nebo file dir).
+    /// Baze dokumentu pro resolve modulu: URL stranky (webview) nebo adresar
+    /// na disku (current dir / adresar souboru).
     pub base_dir: Rc<RefCell<String>>,
     /// Worker state registry - Worker ID -> WorkerState.
     pub workers: Rc<RefCell<HashMap<u32, WorkerState>>>,
@@ -731,6 +1175,13 @@ pub struct Interpreter {
     /// pending promise se prepne na fulfilled/rejected.
     /// Format: (promise_obj, Receiver<perform_http_request output>).
     pub pending_fetches: Rc<RefCell<Vec<PendingFetch>>>,
+    /// Rozpracovana volani async host funkci (`host_async_fn`) - settle pres
+    /// `drain_host_jobs` z event loopu.
+    pub pending_host_jobs: Rc<RefCell<Vec<host_bindings::PendingHostJob>>>,
+    /// Executor async host funkci (`set_host_executor`); `None` = vychozi pool.
+    pub host_executor: Rc<RefCell<Option<host_bindings::HostExecutor>>>,
+    /// Staticke cleny host trid podle jmena tridy (`HostClass::install`).
+    pub host_statics: Rc<RefCell<HashMap<String, Rc<RefCell<JsObject>>>>>,
     /// Event callback registry: ID -> JS callback funkce.
     /// Pouziva se pro addEventListener / dispatchEvent.
     pub event_callbacks: Rc<RefCell<HashMap<usize, JsValue>>>,
@@ -781,6 +1232,11 @@ pub struct Interpreter {
     pub next_raf_id: Rc<RefCell<u32>>,
     /// Aktualni line v exec - update z Stmt::WithLine. 0 pri rucne run.
     pub current_line: u32,
+    /// Rozsah prave vykonavaneho prikazu nebo volani (WithLine, Call, New).
+    pub current_span: Rc<Cell<SourceSpan>>,
+    /// Ramce volani uzivatelskych funkci pro `Error.stack`: (jmeno funkce,
+    /// misto volani).
+    call_frames: Rc<RefCell<Vec<(String, SourceSpan)>>>,
     /// Sdileny debugger state - breakpoints + pause indicator (single-thread Rc/RefCell).
     pub debugger: Rc<RefCell<DebuggerState>>,
     /// Volitelny mezi-thread sdileny debugger - kdyz nastaveny, worker thread
@@ -788,6 +1244,12 @@ pub struct Interpreter {
     pub shared_debugger: Option<SharedDebugger>,
     /// Continue signal pri pause v worker thread.
     pub continue_signal: Option<ContinueSignal>,
+    /// Rozpocet kazde ulohy (kroky, cas, heap, hloubka volani) - viz `budget`.
+    pub execution_limits: Rc<RefCell<budget::ExecutionLimits>>,
+    /// Preruseni bezici ulohy z jineho threadu (`interrupt_handle`).
+    interrupt: budget::InterruptHandle,
+    /// Duvod ukonceni posledni ukoncene ulohy (`take_termination`).
+    termination: budget::TerminationSlot,
     /// Cache style objektu per DOM node (klic = Rc::as_ptr(node) as usize).
     /// Weak ref aby se uvolnili po posledni reference z JS. Pri setteru
     /// (eval_expr.rs assign_to) se z `__style_node__` propu vyzvedne Rc<Node>
@@ -853,6 +1315,8 @@ pub struct Interpreter {
     /// take_content_mutated_nodes(). WebView pres ne rozhoduje, jestli
     /// off-screen JS animace (SVG wave RAF) musi dirty-ovat render.
     pub content_mutated_nodes: Rc<RefCell<Vec<usize>>>,
+    /// Atomics agent - blokovani, cross-origin isolation, waitAsync fronta.
+    pub agent: Rc<AgentState>,
 }
 
 /// True pro atributy ktere NEMOHOU ovlivnit CSS kaskadu (selector matching) -
@@ -894,8 +1358,13 @@ pub struct IntervalEntry {
 pub struct DebuggerState {
     /// Set lines kde je aktivni breakpoint (pro current source).
     pub breakpoints: std::collections::HashSet<u32>,
+    /// Sloupcove breakpointy (line, column od 0) - pause jen na prikazu,
+    /// ktery na teto pozici zacina (vice prikazu na jednom radku).
+    pub column_breakpoints: std::collections::HashSet<(u32, u32)>,
     /// Aktualne hit pause line (None = neni paused).
     pub paused_at: Option<u32>,
+    /// Rozsah prikazu, na kterem se pausovalo.
+    pub paused_span: Option<SourceSpan>,
     /// Counter hit-u pres celou run pro UI feedback.
     pub hit_count: u32,
     /// Snapshot lokalnich promennych pri posledni pause (name -> stringified value).
@@ -928,6 +1397,15 @@ impl DebuggerState {
     pub fn is_breakpoint(&self, line: u32) -> bool {
         self.breakpoints.contains(&line)
     }
+    /// Je nastaveny nejaky breakpoint nebo bezi krokovani.
+    pub fn is_armed(&self) -> bool {
+        !self.breakpoints.is_empty() || !self.column_breakpoints.is_empty() || self.step.is_some()
+    }
+    /// Breakpoint na prikazu s rozsahem `span` - radkovy nebo sloupcovy.
+    pub fn is_breakpoint_at(&self, span: &SourceSpan) -> bool {
+        self.is_breakpoint(span.start.line)
+            || self.column_breakpoints.contains(&(span.start.line, span.start.column))
+    }
     pub fn pause_at(&mut self, line: u32) {
         self.paused_at = Some(line);
         self.hit_count = self.hit_count.saturating_add(1);
@@ -935,6 +1413,7 @@ impl DebuggerState {
     pub fn resume(&mut self) {
         // Konzumuj paused line do skip_once aby rerun preskoci stejny BP.
         self.skip_once_line = self.paused_at.take();
+        self.paused_span = None;
         self.step = None;
     }
     pub fn set_breakpoints(&mut self, lines: std::collections::HashSet<u32>) {
@@ -1002,6 +1481,7 @@ impl Interpreter {
         let next_callback_id: Rc<RefCell<usize>> = Rc::new(RefCell::new(1));
         let resize_observers: Rc<RefCell<Vec<ResizeObserverState>>> = Rc::new(RefCell::new(Vec::new()));
         let intersection_observers: Rc<RefCell<Vec<IntersectionObserverState>>> = Rc::new(RefCell::new(Vec::new()));
+        let agent = Rc::new(AgentState::default());
         setup_builtins(
             &global, &task_queue, &interval_queue, &next_timer_id, &workers, &next_worker_id,
             &document, &console_log, &console_log_args, &network_log, &custom_elements,
@@ -1009,13 +1489,13 @@ impl Interpreter {
             &pending_fetches, &pending_xhr_callbacks,
             &raf_callbacks, &next_raf_id, &scroll_pos,
             &event_callbacks, &next_callback_id,
-            &resize_observers, &intersection_observers,
+            &resize_observers, &intersection_observers, &agent,
         );
         Interpreter {
-            global, yield_buffer: None, task_queue, interval_queue, next_timer_id,
-            module_cache:    Rc::new(RefCell::new(HashMap::new())),
+            global, gen_yielder: None, gen_stack: None, module_await: false, script_await: false, strict_script: Rc::new(Cell::new(false)), task_queue, interval_queue, next_timer_id,
+            modules:         Rc::new(RefCell::new(EsmRegistry::new())),
+            import_map:      Rc::new(RefCell::new(import_maps::ImportMap::new())),
             virtual_modules: Rc::new(RefCell::new(HashMap::new())),
-            current_exports: None,
             base_dir:        Rc::new(RefCell::new(".".to_string())),
             workers, next_worker_id,
             websockets, next_ws_id,
@@ -1036,10 +1516,18 @@ impl Interpreter {
             mutation_observers,
             pending_mutation_records: Rc::new(RefCell::new(Vec::new())),
             current_line: 0,
+            current_span: Rc::default(),
+            call_frames: Rc::default(),
             debugger: Rc::new(RefCell::new(DebuggerState::default())),
             shared_debugger: None,
             continue_signal: None,
+            execution_limits: Rc::default(),
+            interrupt: budget::InterruptHandle::new(),
+            termination: Rc::default(),
             pending_fetches,
+            pending_host_jobs: Rc::new(RefCell::new(Vec::new())),
+            host_executor: Rc::new(RefCell::new(None)),
+            host_statics: Rc::new(RefCell::new(HashMap::new())),
             pending_xhr_callbacks,
             raf_callbacks,
             next_raf_id,
@@ -1057,6 +1545,7 @@ impl Interpreter {
             dom_style_version: Rc::new(std::cell::Cell::new(0)),
             dom_layout_version: Rc::new(std::cell::Cell::new(0)),
             dom_match_version: Rc::new(std::cell::Cell::new(0)),
+            agent,
         }
     }
 
@@ -1268,23 +1757,10 @@ impl Interpreter {
                         Ok((status, status_text, body, headers)) => {
                             // Cache body pres URL klic - CDP Network.getResponseBody
                             // lookup pres request_id = url.
-                            self.response_bodies.borrow_mut().insert(task.url.clone(), body.clone());
-                            let mut response = JsObject::new();
-                            response.set("__response__".into(), JsValue::Bool(true));
-                            response.set("__body__".into(), JsValue::Str(body));
-                            response.set("url".into(), JsValue::Str(task.url.clone()));
-                            response.set("status".into(), JsValue::Number(status as f64));
-                            response.set("ok".into(), JsValue::Bool(status >= 200 && status < 300));
-                            response.set("statusText".into(), JsValue::Str(status_text));
-                            let mut hdr_obj = JsObject::new();
-                            for (k, v) in headers {
-                                hdr_obj.set(k.to_lowercase(), JsValue::Str(v));
-                            }
-                            hdr_obj.set("__headers__".into(), JsValue::Bool(true));
-                            response.set("headers".into(), JsValue::Object(Rc::new(RefCell::new(hdr_obj))));
+                            self.response_bodies.borrow_mut().insert(task.url.clone(), String::from_utf8_lossy(&body).into_owned());
+                            let response = make_response(&task.url, status, status_text, body, headers);
                             p.set("__promise_state__".into(), JsValue::Str("fulfilled".into()));
-                            p.set("__promise_value__".into(),
-                                JsValue::Object(Rc::new(RefCell::new(response))));
+                            p.set("__promise_value__".into(), response);
                         }
                         Err(msg) => {
                             let mut err = JsObject::new();
@@ -1658,6 +2134,20 @@ impl Interpreter {
         node: &Rc<crate::browser::dom::NodeData>,
         event_type: &str,
         event_val: JsValue,
+    ) -> Result<(), JsError> {
+        let _task = self.enter_task();
+        let scope = gc::enter_scope();
+        let result = self.dispatch_event_phases(node, event_type, event_val);
+        drop(scope);
+        self.gc_safepoint();
+        result
+    }
+
+    fn dispatch_event_phases(
+        &mut self,
+        node: &Rc<crate::browser::dom::NodeData>,
+        event_type: &str,
+        event_val: JsValue,
     ) -> Result<(), JsError> {
         // Walk parent chain target -> root pro bubble phase.
         // (Capture phase NOT implementovan: listeners drzi jen callback id bez
@@ -1915,8 +2405,8 @@ impl Interpreter {
         for stmt in &prog.body {
             let mut peeled = stmt;
             while let crate::ast::Stmt::WithLine { inner, .. } = peeled { peeled = inner; }
-            if let crate::ast::Stmt::Expr(e) = peeled {
-                fn_val = self.eval(e, &env)?;
+            if let crate::ast::Stmt::Expr(e, _) = peeled {
+                fn_val = self.eval_global_expr(e, &env)?;
             }
         }
         if matches!(fn_val, JsValue::Function(_)) {
@@ -1988,7 +2478,7 @@ impl Interpreter {
         // Fast path - prazdny pool.
         if self.workers.borrow().is_empty() { return Ok(()); }
         // Sber zprav z vsech workeru (ID + msg)
-        let pending: Vec<(u32, String)> = {
+        let pending: Vec<(u32, worker_message::WorkerMessage)> = {
             let workers = self.workers.borrow();
             let mut out = Vec::new();
             for (id, state) in workers.iter() {
@@ -2002,85 +2492,94 @@ impl Interpreter {
         for (id, msg) in pending {
             let cb = self.workers.borrow().get(&id).and_then(|s| s.on_message.clone());
             if let Some(cb) = cb {
-                let parsed = json_parse(&msg).unwrap_or(JsValue::Str(msg));
                 let mut event = JsObject::new();
-                event.set("data".into(), parsed);
+                event.set("data".into(), worker_message::decode(msg));
                 self.call_function(cb, vec![JsValue::Object(Rc::new(RefCell::new(event)))], None)?;
             }
         }
         Ok(())
     }
 
-    /// Prida virtualni modul (pro testy / sandboxing).
-    /// `source` je klic, kterym se modul importuje.
-    pub fn add_virtual_module(&self, source: &str, content: &str) {
-        self.virtual_modules.borrow_mut().insert(source.to_string(), content.to_string());
-    }
-
-    /// Resolve & nacti modul podle source. Vraci namespace objekt (cachovany).
-    fn load_module(&mut self, source: &str) -> EvalResult {
-        // 1. Cache hit
-        if let Some(ns) = self.module_cache.borrow().get(source).cloned() {
-            return Ok(ns);
-        }
-        // 2. Nacti obsah - virtual modules priorita, pak FS
-        let content = if let Some(c) = self.virtual_modules.borrow().get(source).cloned() {
-            c
-        } else {
-            // FS: relativni cesty resolve proti base_dir
-            let path = if source.starts_with("./") || source.starts_with("../") || source.starts_with('/') {
-                let base = self.base_dir.borrow().clone();
-                format!("{}/{}", base, source)
+    /// Settle dokoncenych `Atomics.waitAsync`: probuzene pres notify -> "ok",
+    /// po deadline -> "timed-out" (waiter se odebere z fronty pameti).
+    /// Reakce promise jdou do task_queue jako u `resolve` (drain_timers).
+    pub fn drain_atomic_waits(&mut self) {
+        if self.agent.async_waits.borrow().is_empty() { return; }
+        let now = std::time::Instant::now();
+        let mut settled = Vec::new();
+        self.agent.async_waits.borrow_mut().retain(|w| {
+            let outcome = if w.memory.take_woken(w.waiter) {
+                "ok"
+            } else if w.deadline.is_some_and(|d| now >= d) {
+                w.memory.cancel(w.waiter);
+                "timed-out"
             } else {
-                source.to_string()
+                return true;
             };
-            std::fs::read_to_string(&path)
-                .map_err(|e| JsError::Runtime(format!(
-                    "ModuleError: nelze nacist modul '{source}' (cesta: {path}): {e}"
-                )))?
-        };
-
-        // 3. Parse
-        use crate::lexer::base::Lexer;
-        use crate::parser::Parser;
-        let lexer = Lexer::parse_str(&content, source)
-            .map_err(|e| JsError::Runtime(format!("SyntaxError v modulu '{source}': {e}")))?;
-        let tokens: Vec<_> = lexer.tokens.into_iter()
-            .filter(|t| !matches!(t.kind,
-                crate::tokens::TokenKind::Whitespace
-                | crate::tokens::TokenKind::Newline
-                | crate::tokens::TokenKind::CommentLine(_)
-                | crate::tokens::TokenKind::CommentBlock(_)))
-            .collect();
-        let mut parser = Parser::new(tokens);
-        let prog = parser.parse()
-            .map_err(|e| JsError::Runtime(format!("SyntaxError v modulu '{source}': {e}")))?;
-
-        // 4. Spust v izolovanem env (s pristupem ke globalnim builtinum)
-        let module_env = Environment::new_child(&self.global);
-        let exports: Rc<RefCell<HashMap<String, JsValue>>> = Rc::new(RefCell::new(HashMap::new()));
-
-        // Zaregistruj exports rezimu
-        let prev_exports = self.current_exports.take();
-        self.current_exports = Some(Rc::clone(&exports));
-
-        // Pre-cache namespace placeholder (pro cyklicke importy)
-        let ns_obj_rc = Rc::new(RefCell::new(JsObject::new()));
-        let ns_value = JsValue::Object(Rc::clone(&ns_obj_rc));
-        self.module_cache.borrow_mut().insert(source.to_string(), ns_value.clone());
-
-        let exec_result = self.exec_stmts(&prog.body, &module_env);
+            settled.push((w.promise.clone(), outcome));
+            false
+        });
+        for (promise, outcome) in settled {
+            let JsValue::Object(o) = promise else { continue };
+            let value = JsValue::Str(outcome.into());
+            let callbacks = {
+                let mut p = o.borrow_mut();
+                p.set("__promise_state__".into(), JsValue::Str("fulfilled".into()));
+                p.set("__promise_value__".into(), value.clone());
+                match p.props.get("__pending_callbacks__").cloned() {
+                    Some(JsValue::Array(arr)) => arr.borrow().clone(),
+                    _ => Vec::new(),
+                }
+            };
+            for entry in callbacks {
+                let JsValue::Array(triple) = entry else { continue };
+                let on_f = triple.borrow().first().cloned().unwrap_or(JsValue::Undefined);
+                if matches!(on_f, JsValue::Function(_)) {
+                    let id = {
+                        let mut ctr = self.next_timer_id.borrow_mut();
+                        let id = *ctr; *ctr += 1; id
+                    };
+                    self.task_queue.borrow_mut().push((id, now, on_f, vec![value.clone()]));
+                }
+            }
+        }
+    }
 
-        // Obnov predchozi exports
-        self.current_exports = prev_exports;
+    /// Ma agent cekajici `Atomics.waitAsync`?
+    pub fn has_pending_atomic_waits(&self) -> bool {
+        !self.agent.async_waits.borrow().is_empty()
+    }
 
-        exec_result?;
+    /// Aplikuje COOP/COEP hlavicky dokumentu. Bez cross-origin isolation
+    /// zmizi `SharedArrayBuffer` z globalniho scope a Workerum nejde poslat
+    /// sdilena pamet; `crossOriginIsolated` odrazi vysledek.
+    pub fn set_cross_origin_isolation(
+        &mut self,
+        coop: crate::browser::security::coep_coop::Coop,
+        coep: crate::browser::security::coep_coop::Coep,
+    ) {
+        let isolated = crate::browser::security::coep_coop::is_cross_origin_isolated(coop, coep);
+        self.apply_cross_origin_isolation(isolated);
+    }
 
-        // 5. Naplni namespace objekt z exports mapy
-        for (k, v) in exports.borrow().iter() {
-            ns_obj_rc.borrow_mut().set(k.clone(), v.clone());
+    /// Nastavi izolaci agenta primo (Worker ji dedi od stranky).
+    pub(crate) fn apply_cross_origin_isolation(&mut self, isolated: bool) {
+        self.agent.cross_origin_isolated.set(isolated);
+        let mut global = self.global.borrow_mut();
+        global.define("crossOriginIsolated", JsValue::Bool(isolated));
+        if isolated {
+            if global.get_own("SharedArrayBuffer").is_none() {
+                builtins_typed_arrays::define_shared_array_buffer(&mut global);
+            }
+        } else {
+            global.vars.remove("SharedArrayBuffer");
         }
-        Ok(ns_value)
+    }
+
+    /// Prida virtualni modul (pro testy / sandboxing).
+    /// `source` je klic, kterym se modul importuje.
+    pub fn add_virtual_module(&self, source: &str, content: &str) {
+        self.virtual_modules.borrow_mut().insert(source.to_string(), content.to_string());
     }
 
     /// Spusti cely program (AST) a vrati posledni `return` hodnotu.
@@ -2094,9 +2593,8 @@ impl Interpreter {
     /// - Tight loops / arithmetic hot paths
     /// - Future inline-caching call sites
     ///
-    /// Fallback do tree-walker pri compile failure (VM nepodporuje async/yield/
-    /// generators - tyto vyzaduji tree-walker).
-    pub fn eval_via_vm(&self, src: &str) -> Result<JsValue, String> {
+    /// Compile chyba znamena syntakticky neplatny zdroj nebo prime `eval` (potrebuje scope tree-walkeru).
+    pub fn eval_via_vm(&mut self, src: &str) -> Result<JsValue, String> {
         use crate::lexer::base::Lexer;
         use crate::parser::Parser;
         let lex = Lexer::parse_str(src, "<vm>").map_err(|e| format!("Lexer: {:?}", e))?;
@@ -2104,13 +2602,112 @@ impl Interpreter {
         let program = parser.parse().map_err(|e| format!("Parser: {:?}", e))?;
         let code = bytecode::compile_program(&program.body)
             .map_err(|e| format!("Compile: {}", e))?;
-        let mut vm = bytecode::VM::with_env(self.global.clone());
-        vm.run(&code).map_err(|e| format!("VM Runtime: {}", e))
+        let _task = self.enter_task();
+        self.run_bytecode(&code).map_err(|e| format!("VM Runtime: {}", e))
+    }
+
+    /// Spusti zkompilovany program ve VM nad globalnim scope. Funkce
+    /// tree-walkeru vola VM pres tento interpret.
+    pub fn run_bytecode(&mut self, code: &bytecode::CodeBlock) -> Result<JsValue, String> {
+        let global = Rc::clone(&self.global);
+        self.run_bytecode_in(code, global)
+    }
+
+    /// Zkompilovany program nad scopem `env` (global, env modulu).
+    pub(crate) fn run_bytecode_in(&mut self, code: &bytecode::CodeBlock, env: Rc<RefCell<Environment>>) -> Result<JsValue, String> {
+        call_machinery::with_interpreter(self, || bytecode::VM::with_env(env).run(code))
+    }
+
+    /// Telo skriptu: bytecode VM, tree-walker jen pro zdroj, ktery VM
+    /// nezkompiluje. Vysledek jako u `exec_stmts` (`return` = `Signal::Return`).
+    fn exec_script(&mut self, body: &[Stmt], env: &Rc<RefCell<Environment>>) -> Result<Option<Signal>, JsError> {
+        // Breakpointy a krokovani umi jen tree-walker (pause v exec_stmt).
+        if self.debugger.borrow().is_armed() {
+            return self.exec_stmts(body, env);
+        }
+        match bytecode::compile_program(body) {
+            Ok(code) => self.run_bytecode(&code).map(|v| Some(Signal::Return(v))).map_err(call_machinery::vm_error),
+            Err(_) => self.exec_stmts(body, env),
+        }
+    }
+
+    /// Vyraz v globalnim scope (inline handler, retezcovy timer) pres VM;
+    /// tree-walker jen kdyz se vyraz nezkompiluje.
+    fn eval_global_expr(&mut self, expr: &Expr, env: &Rc<RefCell<Environment>>) -> EvalResult {
+        match bytecode::compile_program(&[Stmt::Return(Some(expr.clone()), SourceSpan::default())]) {
+            Ok(code) => self.run_bytecode_in(&code, Rc::clone(env)).map_err(call_machinery::vm_error),
+            Err(_) => self.eval(expr, env),
+        }
+    }
+
+    /// Callback ulohy z fronty timeru. Retezec (`setTimeout("code", ms)`) se
+    /// spusti jako skript v globalnim scope.
+    fn run_timer_callback(&mut self, cb: JsValue, args: Vec<JsValue>) -> EvalResult {
+        let JsValue::Str(src) = &cb else {
+            return self.call_function(cb, args, None);
+        };
+        use crate::lexer::base::Lexer;
+        use crate::parser::Parser;
+        use crate::tokens::TokenKind;
+        let lexer = Lexer::parse_str(src, "<timer>")
+            .map_err(|e| JsError::Runtime(format!("SyntaxError: {e}")))?;
+        let tokens: Vec<_> = lexer.tokens.into_iter()
+            .filter(|t| !matches!(t.kind,
+                TokenKind::Whitespace | TokenKind::Newline
+                | TokenKind::CommentLine(_) | TokenKind::CommentBlock(_)))
+            .collect();
+        let program = Parser::new(tokens).parse()
+            .map_err(|e| JsError::Runtime(format!("SyntaxError: {e}")))?;
+        let env = Rc::clone(&self.global);
+        self.exec_script(&program.body, &env).map(|_| JsValue::Undefined)
+    }
+
+    /// Nastavi rozpocet pro dalsi ulohy (`run`, eventy, timery).
+    pub fn set_execution_limits(&mut self, limits: budget::ExecutionLimits) {
+        *self.execution_limits.borrow_mut() = limits;
+    }
+
+    /// `Send` handle - `interrupt()` z jineho threadu ukonci bezici ulohu.
+    pub fn interrupt_handle(&self) -> budget::InterruptHandle {
+        self.interrupt.clone()
+    }
+
+    /// Sdili handle s hostem (WebView drzi jeden pres reload stranky).
+    pub fn set_interrupt_handle(&mut self, handle: budget::InterruptHandle) {
+        self.interrupt = handle;
+    }
+
+    /// Duvod ukonceni posledni ukoncene ulohy (jednou, pak `None`).
+    pub fn take_termination(&self) -> Option<budget::Termination> {
+        self.termination.take()
+    }
+
+    /// Vstup do ulohy s rozpoctem tohoto interpretu.
+    pub(crate) fn enter_task(&self) -> budget::TaskGuard {
+        budget::enter_task(&self.execution_limits.borrow(), &self.interrupt, &self.termination)
+    }
+
+    /// Skoncila vnejsi uloha ukoncenim rozpoctem? (duvod uz ceka v
+    /// `take_termination`; uvnitr vnejsi ulohy se chyba propaguje dal)
+    fn terminated_task(&self, e: &JsError) -> bool {
+        matches!(e, JsError::Runtime(msg) if msg.starts_with(budget::TERMINATION_PREFIX))
+            && !budget::is_terminating()
     }
 
     pub fn run(&mut self, program: &Program) -> EvalResult {
+        if module_loader::is_module_program(&program.body) {
+            let url = self.document_url();
+            return self.run_module(program, &url);
+        }
+        let scope = gc::enter_scope();
         let env = Rc::clone(&self.global);
-        let result = match self.exec_stmts(&program.body, &env)? {
+        let outer_strict = self.strict_script.replace(program.strict);
+        // Telo skriptu je jedna uloha; timery z drainu maji kazdy vlastni rozpocet
+        let task = self.enter_task();
+        let signal = self.exec_script(&program.body, &env);
+        drop(task);
+        self.strict_script.set(outer_strict);
+        let result = match signal? {
             Some(Signal::Return(v)) => v,
             Some(Signal::Paused(line)) => {
                 // Tree-walking single-thread: pause = early abort. UI obsluha
@@ -2123,6 +2720,110 @@ impl Interpreter {
             }
             _ => JsValue::Undefined,
         };
+        self.drain_pending_work()?;
+        drop(scope);
+        self.gc_safepoint();
+        Ok(result)
+    }
+
+    /// Spusti skript v globalnim scope a vrati jeho completion value - hodnotu
+    /// posledniho vyrazoveho prikazu (REPL, konzole). Deklarace zustavaji
+    /// v globalnim scope pro dalsi vstupy; top-level `await` ceka na settle
+    /// promise drainem event loopu.
+    pub fn eval_script(&mut self, program: &Program) -> EvalResult {
+        let scope = gc::enter_scope();
+        let env = Rc::clone(&self.global);
+        let outer_strict = self.strict_script.replace(program.strict);
+        let outer_await = std::mem::replace(&mut self.script_await, true);
+        let task = self.enter_task();
+        let result = self.exec_completion(&program.body, &env);
+        drop(task);
+        self.script_await = outer_await;
+        self.strict_script.set(outer_strict);
+        let value = result?;
+        self.drain_timers()?;
+        drop(scope);
+        self.gc_safepoint();
+        Ok(value)
+    }
+
+    fn exec_completion(&mut self, body: &[Stmt], env: &Rc<RefCell<Environment>>) -> EvalResult {
+        let last_expr = body.last().and_then(|last| {
+            let mut s = last;
+            while let Stmt::WithLine { line, span, inner } = s {
+                self.current_line = *line;
+                self.current_span.set(*span);
+                s = inner;
+            }
+            match s { Stmt::Expr(e, _) => Some(e), _ => None }
+        });
+        let stmts = if last_expr.is_some() { &body[..body.len() - 1] } else { body };
+        match self.exec_stmts(stmts, env)? {
+            Some(Signal::Return(v)) => return Ok(v),
+            Some(_) => return Ok(JsValue::Undefined),
+            None => {}
+        }
+        match last_expr {
+            Some(e) => {
+                budget::charge().map_err(JsError::Runtime)?;
+                self.eval(e, env)
+            }
+            None => Ok(JsValue::Undefined),
+        }
+    }
+
+    /// Dockaje se settle promise - drainuje timery, async host volani
+    /// a `waitAsync`, mezi nimi spi do nejblizsiho timeru. Vraci hodnotu
+    /// splnene promise (odmitnuta = `Thrown`), jinou hodnotu beze zmeny.
+    /// Promise, kterou uz nic nemuze posunout, vrati nesettled.
+    pub fn await_settled(&mut self, value: JsValue) -> EvalResult {
+        while module_loader::is_pending_promise(&value) {
+            self.drain_timers()?;
+            self.drain_intervals()?;
+            self.drain_xhr_callbacks()?;
+            if !module_loader::is_pending_promise(&value) { break; }
+            if self.has_pending_host_jobs() {
+                self.drain_host_jobs(true);
+                continue;
+            }
+            let next_timer = self.task_queue.borrow().iter().map(|(_, at, _, _)| *at).min();
+            let next_interval = self.interval_queue.borrow().iter()
+                .map(|e| e.last_call + std::time::Duration::from_millis(e.interval_ms))
+                .min();
+            let next = match (next_timer, next_interval) {
+                (Some(a), Some(b)) => Some(a.min(b)),
+                (a, b) => a.or(b),
+            };
+            if self.has_pending_atomic_waits() {
+                self.drain_atomic_waits();
+                std::thread::sleep(std::time::Duration::from_millis(1));
+            } else if let Some(at) = next {
+                std::thread::sleep(at.saturating_duration_since(std::time::Instant::now()));
+            } else {
+                break;
+            }
+        }
+        unwrap_promise_result(value).map_err(JsError::Thrown)
+    }
+
+    /// Bezi kod v strict mode? Strict scope nebo top-level strict skriptu.
+    pub(crate) fn is_strict(&self, env: &Rc<RefCell<Environment>>) -> bool {
+        self.strict_script.get() || env.borrow().strict
+    }
+
+    /// Scope, ktery si uzavre nove vytvorena funkce: v top-level kodu strict
+    /// skriptu strict child globalu, aby funkce rezim zdedila.
+    pub(crate) fn closure_env(&self, env: &Rc<RefCell<Environment>>) -> Rc<RefCell<Environment>> {
+        if self.strict_script.get() && !env.borrow().strict {
+            let child = Environment::new_child(env);
+            child.borrow_mut().strict = true;
+            return child;
+        }
+        Rc::clone(env)
+    }
+
+    /// Dobehnuti prace naplanovane skriptem: timery, intervaly, XHR, workery.
+    fn drain_pending_work(&mut self) -> Result<(), JsError> {
         // Drain timer queue - spust vsechny setTimeout callbacky
         self.drain_timers()?;
         // Drain periodicke setInterval callbacks (dle uplynuti interval_ms).
@@ -2143,7 +2844,17 @@ impl Interpreter {
                 self.workers.borrow_mut().remove(&id);
             }
         }
-        Ok(result)
+        // Async host funkce - skript dobehne az s jejich vysledky
+        while self.has_pending_host_jobs() {
+            self.drain_host_jobs(true);
+            self.drain_timers()?;
+        }
+        // waitAsync probuzene workerem (nebo po timeoutu) behem behu skriptu
+        if self.has_pending_atomic_waits() {
+            self.drain_atomic_waits();
+            self.drain_timers()?;
+        }
+        Ok(())
     }
 
     /// Spusti vsechny cekajici timer callbacky.
@@ -2158,9 +2869,19 @@ impl Interpreter {
         !self.task_queue.borrow().is_empty()
     }
 
+    /// Reakce promise do fronty uloh (bezi hned pri dalsim `drain_timers`).
+    pub(crate) fn enqueue_reaction(&self, cb: JsValue, args: Vec<JsValue>) {
+        let id = {
+            let mut ctr = self.next_timer_id.borrow_mut();
+            let id = *ctr; *ctr += 1; id
+        };
+        self.task_queue.borrow_mut().push((id, std::time::Instant::now(), cb, args));
+    }
+
     pub fn drain_timers(&mut self) -> Result<(), JsError> {
         // Fast path - prazdne queue, zadny borrow needed.
         if self.task_queue.borrow().is_empty() { return Ok(()); }
+        let scope = gc::enter_scope();
         let initial = self.task_queue.borrow().len();
         let drain_start = std::time::Instant::now();
         let mut count = 0;
@@ -2178,7 +2899,11 @@ impl Interpreter {
                 Some(idx) => {
                     let (_, _, cb, args) = self.task_queue.borrow_mut().remove(idx);
                     let t0 = std::time::Instant::now();
-                    self.call_function(cb, args, None)?;
+                    let task = self.enter_task();
+                    let r = self.run_timer_callback(cb, args);
+                    drop(task);
+                    // Ukonceny callback (rozpocet) neblokuje dalsi ulohy fronty
+                    if let Err(e) = r && !self.terminated_task(&e) { return Err(e); }
                     let elapsed = t0.elapsed().as_secs_f32() * 1000.0;
                     count += 1;
                     if elapsed > 50.0 {
@@ -2192,6 +2917,8 @@ impl Interpreter {
             eprintln!("[DRAIN_TIMERS] initial={} fired={} total={:.0}ms",
                 initial, count, total_elapsed);
         }
+        drop(scope);
+        self.gc_safepoint();
         Ok(())
     }
 
@@ -2215,7 +2942,10 @@ impl Interpreter {
         };
         for (_idx, cb, args) in due {
             let t0 = std::time::Instant::now();
-            self.call_function(cb, args, None)?;
+            let task = self.enter_task();
+            let r = self.run_timer_callback(cb, args);
+            drop(task);
+            if let Err(e) = r && !self.terminated_task(&e) { return Err(e); }
             let elapsed = t0.elapsed().as_secs_f32() * 1000.0;
             if elapsed > 50.0 {
                 eprintln!("[DRAIN_INTERVALS] cb took {:.0}ms", elapsed);
@@ -2230,17 +2960,28 @@ impl Interpreter {
 
 
 
-    fn resolve_prop_key(&mut self, prop: &MemberProp, env: &Rc<RefCell<Environment>>) -> Result<String, JsError> {
+    fn resolve_prop_key(&mut self, prop: &MemberProp, env: &Rc<RefCell<Environment>>) -> Result<PropertyKey, JsError> {
         match prop {
-            MemberProp::Ident(s) => Ok(s.clone()),
-            MemberProp::Computed(e) => Ok(self.eval(e, env)?.to_string()),
+            MemberProp::Ident(s) => Ok(s.as_str().into()),
+            MemberProp::Computed(e) => Ok(symbols::to_property_key(&self.eval(e, env)?)),
+            MemberProp::Private(name) => self.resolve_private_key(name, env).map(PropertyKey::Str),
+        }
+    }
+
+    /// `#x` -> klic vazany ve scope nejblizsi tridy (viz private_names.rs)
+    fn resolve_private_key(&self, name: &str, env: &Rc<RefCell<Environment>>) -> Result<String, JsError> {
+        match env.borrow().get(&private_names::binding(name)) {
+            Some(JsValue::Str(key)) => Ok(key),
+            _ => Err(JsError::Runtime(format!(
+                "SyntaxError: Private field '#{name}' must be declared in an enclosing class"
+            ))),
         }
     }
 
     fn eval_args(&mut self, args: &[Expr], env: &Rc<RefCell<Environment>>) -> Result<Vec<JsValue>, JsError> {
         let mut vals = Vec::new();
         for a in args {
-            if let Expr::Spread(e) = a {
+            if let Expr::Spread(e, _) = a {
                 if let JsValue::Array(arr) = self.eval(e, env)? {
                     vals.extend(arr.borrow().clone());
                 }
@@ -2261,6 +3002,39 @@ impl Interpreter {
         method: &str,
         args: Vec<JsValue>,
     ) -> Result<JsValue, JsError> {
+        // Short-circuit helpery tahaji hodnoty lazy - funguji i nad nekonecnym generatorem
+        match method {
+            "take" => {
+                let n = args.into_iter().next().map(|v| v.to_number() as usize).unwrap_or(0);
+                let taken = self.take_from_iterable(iter, n)?;
+                return Ok(make_iterator_from_values(taken));
+            }
+            "find" | "some" | "every" => {
+                let f = args.into_iter().next().unwrap_or(JsValue::Undefined);
+                let mut it = self.open_iterator(iter, false)?;
+                while let Some(v) = self.iter_step(&mut it)? {
+                    let r = self.call_function(f.clone(), vec![v.clone()], None)?.is_truthy();
+                    let hit = match method {
+                        "find" | "some" => r,
+                        _ => !r,
+                    };
+                    if hit {
+                        self.iter_close(&mut it)?;
+                        return Ok(match method {
+                            "find" => v,
+                            "some" => JsValue::Bool(true),
+                            _ => JsValue::Bool(false),
+                        });
+                    }
+                }
+                return Ok(match method {
+                    "find" => JsValue::Undefined,
+                    "some" => JsValue::Bool(false),
+                    _ => JsValue::Bool(true),
+                });
+            }
+            _ => {}
+        }
         let values = self.collect_iterable(iter)?;
         match method {
             "toArray" => Ok(JsValue::Array(Rc::new(RefCell::new(values)))),
@@ -2281,10 +3055,6 @@ impl Interpreter {
                 }
                 Ok(make_iterator_from_values(out))
             }
-            "take" => {
-                let n = args.into_iter().next().map(|v| v.to_number() as usize).unwrap_or(0);
-                Ok(make_iterator_from_values(values.into_iter().take(n).collect()))
-            }
             "drop" => {
                 let n = args.into_iter().next().map(|v| v.to_number() as usize).unwrap_or(0);
                 Ok(make_iterator_from_values(values.into_iter().skip(n).collect()))
@@ -2316,30 +3086,6 @@ impl Interpreter {
                 }
                 Ok(JsValue::Undefined)
             }
-            "some" => {
-                let f = args.into_iter().next().unwrap_or(JsValue::Undefined);
-                for v in values {
-                    let r = self.call_function(f.clone(), vec![v], None)?;
-                    if r.is_truthy() { return Ok(JsValue::Bool(true)); }
-                }
-                Ok(JsValue::Bool(false))
-            }
-            "every" => {
-                let f = args.into_iter().next().unwrap_or(JsValue::Undefined);
-                for v in values {
-                    let r = self.call_function(f.clone(), vec![v], None)?;
-                    if !r.is_truthy() { return Ok(JsValue::Bool(false)); }
-                }
-                Ok(JsValue::Bool(true))
-            }
-            "find" => {
-                let f = args.into_iter().next().unwrap_or(JsValue::Undefined);
-                for v in values {
-                    let r = self.call_function(f.clone(), vec![v.clone()], None)?;
-                    if r.is_truthy() { return Ok(v); }
-                }
-                Ok(JsValue::Undefined)
-            }
             "flatMap" => {
                 let f = args.into_iter().next().unwrap_or(JsValue::Undefined);
                 let mut out = Vec::new();
@@ -2356,24 +3102,25 @@ impl Interpreter {
 
     /// Sbira vsechny hodnoty z iteratoru nebo iterovatelneho objektu (interni).
     ///
-    /// Pouzivane v `for...of` pro custom iterables a `yield*`.
+    /// Pouzivane pro spread a eager helpery; for-of a `yield*` iteruji lazy
+    /// pres `open_iterator` (generator.rs).
     fn collect_iterable(&mut self, val: JsValue) -> Result<Vec<JsValue>, JsError> {
         match &val {
             JsValue::Array(a) => return Ok(a.borrow().clone()),
             JsValue::Str(s)   => return Ok(s.chars().map(|c| JsValue::Str(c.to_string())).collect()),
             // for...of Map -> [key, value] pary
             JsValue::Map(m) => {
-                return Ok(m.borrow().entries.iter()
+                return Ok(m.borrow().entries()
                     .map(|(k, v)| JsValue::Array(Rc::new(RefCell::new(vec![k.clone(), v.clone()]))))
                     .collect());
             }
             // for...of Set -> hodnoty
-            JsValue::Set(s) => return Ok(s.borrow().values.clone()),
+            JsValue::Set(s) => return Ok(s.borrow().values().cloned().collect()),
             _ => {}
         }
         // Zkus Symbol.iterator protocol
         if let JsValue::Object(o) = &val {
-            let sym_iter_fn = o.borrow().get("Symbol.iterator");
+            let sym_iter_fn = o.borrow().get(symbols::ITERATOR);
             if !matches!(sym_iter_fn, JsValue::Undefined) {
                 let iterator = self.call_function(sym_iter_fn, vec![], Some(val.clone()))?;
                 return self.drain_iterator(iterator);
@@ -2411,6 +3158,11 @@ impl Interpreter {
     // ─── Array built-in metody ────────────────────────────────────────────────
 
     fn call_array_method(&mut self, arr: Rc<RefCell<Vec<JsValue>>>, method: &str, args: Vec<JsValue>) -> Result<Option<JsValue>, JsError> {
+        if matches!(method, "push" | "pop" | "shift" | "unshift" | "splice" | "reverse" | "sort" | "fill" | "copyWithin")
+            && tagged_template::is_frozen_array(&arr)
+        {
+            return Err(JsError::Runtime(format!("TypeError: Cannot {method} frozen array")));
+        }
         match method {
             "push" => {
                 let new_len = { let mut a = arr.borrow_mut(); for v in args { a.push(v); } a.len() as f64 };
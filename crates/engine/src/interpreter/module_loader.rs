@@ -0,0 +1,671 @@
+//! ES moduly: resolve specifieru, nacteni grafu, linkovani live vazeb a
+//! vyhodnoceni vcetne top-level await.
+//!
+//! Zaznamy modulu drzi `EsmRegistry` (browser/modules_esm.rs), klicem je
+//! resolved URL. Postup odpovida spec (LoadRequestedModules -> Link ->
+//! Evaluate):
+//! - resolve: import mapa, virtualni moduly, absolutni URL, relativni cesta
+//!   vuci importujicimu modulu; bare specifier na webu je TypeError
+//! - nacteni: virtualni moduly, loader stranky (http/https/file), disk
+//! - link: scope modulu nad globalem, importy jako vazby na scope
+//!   exportujiciho modulu (`Environment::define_import`) - cteni je vzdy
+//!   aktualni i v cyklech; deklarace funkci se hoistuji uz pri linkovani
+//! - evaluate: zavislosti v poradi zdrojaku. Modul s `await` na nejvyssi
+//!   urovni bezi v pozastavitelnem ramci (`GeneratorFrame::for_module`);
+//!   rodic asynchronni zavislosti se spusti, az vsechny dobehnou.
+
+use super::*;
+use super::generator::{step_frame, GeneratorFrame, ResumeKind};
+use crate::browser::modules_esm::{
+    hoisted_function, EsmModule, EsmRegistry, ExportEntry, ImportBinding, ModuleState,
+};
+use std::collections::HashSet;
+
+/// Vysledek ResolveExport.
+#[derive(Debug, Clone, PartialEq)]
+enum Resolution {
+    /// Vazba `name` ve scopu modulu `module`.
+    Binding { module: String, name: String },
+    /// `export * as x from` - namespace modulu.
+    Namespace(String),
+    NotFound,
+    /// Stejne jmeno z vice `export *` s ruznymi vazbami.
+    Ambiguous,
+}
+
+/// Je program modul (import/export na nejvyssi urovni)?
+pub(super) fn is_module_program(body: &[Stmt]) -> bool {
+    body.iter().any(|s| {
+        let mut s = s;
+        while let Stmt::WithLine { inner, .. } = s {
+            s = inner;
+        }
+        matches!(s, Stmt::Import { .. } | Stmt::Export(_, _))
+    })
+}
+
+/// Promise ceka na settle a umi prijmout callback (`__pending_callbacks__`)?
+pub(super) fn is_pending_promise(v: &JsValue) -> bool {
+    matches!(v, JsValue::Object(o) if o.borrow().props.contains_key("__pending_callbacks__"))
+        && get_promise_state(v).is_some_and(|(s, _)| s == "pending")
+}
+
+/// Zaregistruje callbacky na pending promise (settle je naplanuje do fronty uloh).
+fn subscribe_pending(promise: &JsValue, on_f: JsValue, on_r: JsValue) {
+    if let JsValue::Object(o) = promise
+        && let Some(JsValue::Array(cbs)) = o.borrow().props.get("__pending_callbacks__").cloned()
+    {
+        cbs.borrow_mut().push(JsValue::Array(Rc::new(RefCell::new(vec![on_f, on_r, JsValue::Undefined]))));
+    }
+}
+
+/// Resolve specifieru vuci adrese importujiciho modulu (`referrer`).
+/// Samostatna funkce - pouziva ji i `import.meta.resolve` mimo interpret.
+fn resolve_specifier(
+    specifier: &str,
+    referrer: &str,
+    import_map: &import_maps::ImportMap,
+    virtual_modules: &HashMap<String, String>,
+) -> Result<String, String> {
+    let is_url = referrer.contains("://");
+    if let Some(target) = import_map.resolve(specifier, referrer) {
+        return Ok(if is_url { EsmRegistry::resolve(&target, referrer) } else { target });
+    }
+    if virtual_modules.contains_key(specifier) || specifier.contains("://") {
+        return Ok(specifier.to_string());
+    }
+    let relative = specifier.starts_with("./") || specifier.starts_with("../") || specifier.starts_with('/');
+    match (relative, is_url) {
+        (true, true) => Ok(EsmRegistry::resolve(specifier, referrer)),
+        // Cesty na disku: vuci adresari importujiciho souboru
+        (true, false) if !specifier.starts_with('/') && referrer.contains('/') => {
+            Ok(EsmRegistry::resolve(specifier, referrer))
+        }
+        (true, false) => Ok(specifier.to_string()),
+        (false, true) => Err(format!(
+            "TypeError: Failed to resolve module specifier \"{specifier}\". \
+             Relative references must start with either \"/\", \"./\", or \"../\"."
+        )),
+        // Mimo web (testy, CLI) je bare specifier jmeno virtualniho modulu / cesta
+        (false, false) => Ok(specifier.to_string()),
+    }
+}
+
+/// Rozdeli "TypeError: zprava" na jmeno chyby a zpravu.
+fn split_error_name(msg: &str) -> (&str, &str) {
+    match msg.split_once(": ") {
+        Some((name, rest)) if name.ends_with("Error") && name.chars().all(|c| c.is_ascii_alphabetic()) => (name, rest),
+        _ => ("Error", msg),
+    }
+}
+
+fn parse_module(src: &str, url: &str) -> Result<Vec<Stmt>, JsError> {
+    use crate::lexer::base::Lexer;
+    use crate::parser::Parser;
+    use crate::tokens::TokenKind;
+    let lexer = Lexer::parse_str(src, url)
+        .map_err(|e| JsError::Runtime(format!("SyntaxError v modulu '{url}': {e}")))?;
+    let tokens: Vec<_> = lexer.tokens.into_iter()
+        .filter(|t| !matches!(t.kind,
+            TokenKind::Whitespace | TokenKind::Newline
+            | TokenKind::CommentLine(_) | TokenKind::CommentBlock(_)))
+        .collect();
+    let program = Parser::new(tokens).parse_module()
+        .map_err(|e| JsError::Runtime(format!("SyntaxError v modulu '{url}': {e}")))?;
+    Ok(program.body)
+}
+
+impl Interpreter {
+    /// Spusti program jako ES modul s adresou `url` (`import.meta.url` a baze
+    /// relativnich importu). Vraci hodnotu `return` tela; modul cekajici na
+    /// top-level await dobehne az pri drainu fronty uloh.
+    pub fn run_module(&mut self, program: &Program, url: &str) -> EvalResult {
+        let scope = gc::enter_scope();
+        let task = self.enter_task();
+        let result = self.evaluate_entry(program.body.clone(), url);
+        drop(task);
+        let result = result?;
+        self.drain_pending_work()?;
+        drop(scope);
+        self.gc_safepoint();
+        Ok(result)
+    }
+
+    /// Adresa dokumentu pro importy mimo modul (klasicky skript, `run`).
+    /// `base_dir` je URL stranky nebo adresar na disku.
+    pub fn document_url(&self) -> String {
+        let base = self.base_dir.borrow();
+        if base.contains("://") { base.clone() } else { format!("{}/", base.trim_end_matches('/')) }
+    }
+
+    fn evaluate_entry(&mut self, body: Vec<Stmt>, url: &str) -> EvalResult {
+        let key = {
+            let registry = self.modules.borrow();
+            if registry.modules.contains_key(url) {
+                (2..).map(|n| format!("{url}#{n}")).find(|k| !registry.modules.contains_key(k)).unwrap_or_default()
+            } else {
+                url.to_string()
+            }
+        };
+        let has_await = bytecode::has_top_level_await(&body);
+        self.register_module(&key, EsmModule::new(url, body, has_await))?;
+        self.link_module(&key)?;
+        self.evaluate_module(&key)
+    }
+
+    pub(super) fn resolve_module_specifier(&self, specifier: &str, referrer: &str) -> Result<String, JsError> {
+        resolve_specifier(specifier, referrer, &self.import_map.borrow(), &self.virtual_modules.borrow())
+            .map_err(JsError::Runtime)
+    }
+
+    /// Hodnota chyby pro JS (reject promise, `error` zaznamu modulu).
+    pub(super) fn error_value(&mut self, e: &JsError) -> JsValue {
+        match e {
+            JsError::Thrown(v) | JsError::GeneratorReturn(v) => v.clone(),
+            JsError::Runtime(msg) => {
+                let (name, message) = split_error_name(msg);
+                self.construct_error(name.to_string(), vec![JsValue::Str(message.to_string())])
+                    .unwrap_or_else(|_| JsValue::Str(msg.clone()))
+            }
+        }
+    }
+
+    fn module_record(&self, key: &str) -> Result<Rc<RefCell<EsmModule>>, JsError> {
+        self.modules.borrow().get(key)
+            .ok_or_else(|| JsError::Runtime(format!("TypeError: modul '{key}' neni nacten")))
+    }
+
+    fn fetch_module_source(&self, url: &str) -> Result<String, JsError> {
+        if let Some(src) = self.virtual_modules.borrow().get(url).cloned() {
+            return Ok(src);
+        }
+        if url.starts_with("http://") || url.starts_with("https://") || url.starts_with("file://") {
+            let text = crate::browser::render::fetch_text_url(url);
+            self.network_log.borrow_mut().push((url.to_string(), if text.is_some() { 200 } else { 0 }));
+            return text.ok_or_else(|| JsError::Runtime(format!("TypeError: Failed to fetch module '{url}'")));
+        }
+        std::fs::read_to_string(url)
+            .map_err(|e| JsError::Runtime(format!("TypeError: Failed to fetch module '{url}': {e}")))
+    }
+
+    /// Nacte modul a (rekurzivne) vsechny jeho zavislosti.
+    fn load_module_graph(&mut self, url: &str) -> Result<(), JsError> {
+        if self.modules.borrow().get(url).is_some() {
+            return Ok(());
+        }
+        let src = self.fetch_module_source(url)?;
+        let body = parse_module(&src, url)?;
+        let has_await = bytecode::has_top_level_await(&body);
+        self.register_module(url, EsmModule::new(url, body, has_await))
+    }
+
+    fn register_module(&mut self, key: &str, module: EsmModule) -> Result<(), JsError> {
+        let m = self.modules.borrow_mut().register_as(key, module);
+        let referrer = m.borrow().url.clone();
+        let specifiers: Vec<String> = m.borrow().requested.iter().map(|(s, _)| s.clone()).collect();
+        let mut deps = Vec::with_capacity(specifiers.len());
+        for specifier in &specifiers {
+            match self.resolve_module_specifier(specifier, &referrer) {
+                Ok(url) => deps.push(url),
+                Err(e) => return Err(self.fail_module(key, e)),
+            }
+        }
+        {
+            let mut record = m.borrow_mut();
+            for ((_, url), dep) in record.requested.iter_mut().zip(&deps) {
+                *url = dep.clone();
+            }
+            let record = &mut *record;
+            for import in &mut record.imports {
+                import.resolved_url = record.requested.iter()
+                    .find(|(s, _)| *s == import.source)
+                    .map(|(_, u)| u.clone())
+                    .unwrap_or_default();
+            }
+        }
+        for dep in &deps {
+            if let Err(e) = self.load_module_graph(dep) {
+                return Err(self.fail_module(key, e));
+            }
+        }
+        Ok(())
+    }
+
+    fn link_module(&mut self, key: &str) -> Result<(), JsError> {
+        let m = self.module_record(key)?;
+        let state = m.borrow().state;
+        match state {
+            ModuleState::Unlinked => {}
+            ModuleState::Errored => {
+                return Err(JsError::Thrown(m.borrow().error.clone().unwrap_or(JsValue::Undefined)));
+            }
+            // Cyklus (Linking) nebo uz nalinkovany
+            _ => return Ok(()),
+        }
+        // Kod modulu je vzdy strict
+        let env = Environment::new_function_child(&self.global);
+        env.borrow_mut().strict = true;
+        let url = {
+            let mut record = m.borrow_mut();
+            record.state = ModuleState::Linking;
+            record.env = Some(Rc::clone(&env));
+            record.url.clone()
+        };
+        let meta = self.import_meta(&url);
+        env.borrow_mut().define("import.meta", meta);
+        if let Err(e) = self.link_bindings(&m, &env) {
+            return Err(self.fail_module(key, e));
+        }
+        m.borrow_mut().state = ModuleState::Linked;
+        Ok(())
+    }
+
+    fn link_bindings(&mut self, m: &Rc<RefCell<EsmModule>>, env: &Rc<RefCell<Environment>>) -> Result<(), JsError> {
+        let deps: Vec<String> = m.borrow().requested.iter().map(|(_, u)| u.clone()).collect();
+        for dep in &deps {
+            self.link_module(dep)?;
+        }
+        let imports = m.borrow().imports.clone();
+        for import in &imports {
+            let bindings: Vec<(&str, Option<&str>)> = match &import.binding {
+                ImportBinding::Default(local) => vec![(local, Some("default"))],
+                ImportBinding::Named(pairs) => pairs.iter().map(|(e, l)| (l.as_str(), Some(e.as_str()))).collect(),
+                ImportBinding::Namespace(local) => vec![(local, None)],
+                ImportBinding::SideEffect => Vec::new(),
+            };
+            for (local, name) in bindings {
+                let resolution = match name {
+                    None => Resolution::Namespace(import.resolved_url.clone()),
+                    Some(name) => self.resolve_export(&import.resolved_url, name, &mut Vec::new()),
+                };
+                match resolution {
+                    Resolution::Binding { module, name } => {
+                        let source = self.module_record(&module)?.borrow().env.clone()
+                            .ok_or_else(|| JsError::Runtime(format!("TypeError: modul '{module}' neni nalinkovan")))?;
+                        env.borrow_mut().define_import(local, source, &name);
+                    }
+                    Resolution::Namespace(url) => {
+                        let ns = self.module_namespace(&url)?;
+                        env.borrow_mut().define(local, ns);
+                    }
+                    Resolution::NotFound => return Err(JsError::Runtime(format!(
+                        "SyntaxError: The requested module '{}' does not provide an export named '{}'",
+                        import.source, name.unwrap_or_default()
+                    ))),
+                    Resolution::Ambiguous => return Err(JsError::Runtime(format!(
+                        "SyntaxError: The requested module '{}' contains conflicting star exports for name '{}'",
+                        import.source, name.unwrap_or_default()
+                    ))),
+                }
+            }
+        }
+        // Funkce jsou dostupne hned po linkovani (cyklicke importy je volaji
+        // pred vyhodnocenim tela modulu).
+        let body = Rc::clone(&m.borrow().body);
+        for stmt in body.iter() {
+            if let Some(decl) = hoisted_function(stmt) {
+                self.exec_stmt(decl, env)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// `import.meta` - `url` a `resolve(specifier)`.
+    fn import_meta(&self, url: &str) -> JsValue {
+        let mut meta = JsObject::new();
+        meta.set("url".into(), JsValue::Str(url.to_string()));
+        let referrer = url.to_string();
+        let import_map = Rc::clone(&self.import_map);
+        let virtual_modules = Rc::clone(&self.virtual_modules);
+        meta.set("resolve".into(), native("resolve", move |a| {
+            let specifier = a.first().map(|v| v.to_string()).unwrap_or_default();
+            resolve_specifier(&specifier, &referrer, &import_map.borrow(), &virtual_modules.borrow())
+                .map(JsValue::Str)
+        }));
+        JsValue::Object(Rc::new(RefCell::new(meta)))
+    }
+
+    /// ResolveExport ze spec. `visited` chrani pred cykly re-exportu.
+    fn resolve_export(&self, key: &str, name: &str, visited: &mut Vec<(String, String)>) -> Resolution {
+        if visited.iter().any(|(k, n)| k == key && n == name) {
+            return Resolution::NotFound;
+        }
+        visited.push((key.to_string(), name.to_string()));
+        let Some(m) = self.modules.borrow().get(key) else { return Resolution::NotFound };
+        let (exports, requested) = {
+            let record = m.borrow();
+            (record.exports.clone(), record.requested.clone())
+        };
+        let resolved = |source: &str| {
+            requested.iter().find(|(s, _)| s == source).map(|(_, u)| u.clone()).unwrap_or_default()
+        };
+        for entry in &exports {
+            match entry {
+                ExportEntry::Local { export, local } if export == name => {
+                    return Resolution::Binding { module: key.to_string(), name: local.clone() };
+                }
+                ExportEntry::Indirect { export, source, import } if export == name => {
+                    let url = resolved(source);
+                    return match import {
+                        None => Resolution::Namespace(url),
+                        Some(import) => self.resolve_export(&url, import, visited),
+                    };
+                }
+                _ => {}
+            }
+        }
+        // `export *` nikdy nepreexportuje default
+        if name == "default" {
+            return Resolution::NotFound;
+        }
+        let mut star = Resolution::NotFound;
+        for entry in &exports {
+            let ExportEntry::Star { source } = entry else { continue };
+            match self.resolve_export(&resolved(source), name, visited) {
+                Resolution::NotFound => {}
+                Resolution::Ambiguous => return Resolution::Ambiguous,
+                found if star == Resolution::NotFound => star = found,
+                found if found != star => return Resolution::Ambiguous,
+                _ => {}
+            }
+        }
+        star
+    }
+
+    /// GetExportedNames ze spec (bez deduplikace).
+    fn exported_names(&self, key: &str, visited: &mut HashSet<String>) -> Vec<String> {
+        if !visited.insert(key.to_string()) {
+            return Vec::new();
+        }
+        let Some(m) = self.modules.borrow().get(key) else { return Vec::new() };
+        let record = m.borrow();
+        let mut names = Vec::new();
+        for entry in &record.exports {
+            match entry {
+                ExportEntry::Local { export, .. } | ExportEntry::Indirect { export, .. } => names.push(export.clone()),
+                ExportEntry::Star { source } => {
+                    let url = record.resolved(source).unwrap_or_default().to_string();
+                    names.extend(self.exported_names(&url, visited).into_iter().filter(|n| n != "default"));
+                }
+            }
+        }
+        names
+    }
+
+    /// Namespace objekt modulu: getter na kazdou exportovanou vazbu (live),
+    /// jmena serazena, neni rozsiritelny, `Symbol.toStringTag` = "Module".
+    pub(super) fn module_namespace(&mut self, key: &str) -> EvalResult {
+        let m = self.module_record(key)?;
+        if let Some(ns) = m.borrow().namespace.clone() {
+            return Ok(ns);
+        }
+        // Objekt je v zaznamu driv nez exporty - cyklicke `export * as` ho najdou
+        let obj = Rc::new(RefCell::new(JsObject::new()));
+        m.borrow_mut().namespace = Some(JsValue::Object(Rc::clone(&obj)));
+        let mut names = self.exported_names(key, &mut HashSet::new());
+        names.sort();
+        names.dedup();
+        for name in names {
+            let slot = match self.resolve_export(key, &name, &mut Vec::new()) {
+                Resolution::Binding { module, name: binding } => {
+                    let Some(env) = self.modules.borrow().get(&module).and_then(|m| m.borrow().env.clone()) else {
+                        continue;
+                    };
+                    let export = name.clone();
+                    let getter = native(&name, move |_| {
+                        env.borrow().get_own(&binding)
+                            .ok_or_else(|| format!("ReferenceError: Cannot access '{export}' before initialization"))
+                    });
+                    PropertySlot {
+                        value: JsValue::Undefined, getter: Some(getter), setter: None,
+                        writable: false, enumerable: true, configurable: false,
+                    }
+                }
+                Resolution::Namespace(url) => PropertySlot {
+                    value: self.module_namespace(&url)?, getter: None, setter: None,
+                    writable: true, enumerable: true, configurable: false,
+                },
+                Resolution::NotFound | Resolution::Ambiguous => continue,
+            };
+            obj.borrow_mut().props.insert_slot(name.into(), slot);
+        }
+        let mut o = obj.borrow_mut();
+        o.props.insert_slot(symbols::TO_STRING_TAG.into(), PropertySlot {
+            value: JsValue::Str("Module".into()), getter: None, setter: None,
+            writable: false, enumerable: false, configurable: false,
+        });
+        o.extensible = false;
+        Ok(JsValue::Object(Rc::clone(&obj)))
+    }
+
+    /// Evaluate ze spec - nejdriv zavislosti, pak telo. Vraci hodnotu `return`
+    /// tela (jen u synchronne dobehleho modulu).
+    fn evaluate_module(&mut self, key: &str) -> EvalResult {
+        let m = self.module_record(key)?;
+        let state = m.borrow().state;
+        match state {
+            ModuleState::Linked => {}
+            ModuleState::Errored => {
+                return Err(JsError::Thrown(m.borrow().error.clone().unwrap_or(JsValue::Undefined)));
+            }
+            ModuleState::Unlinked | ModuleState::Linking => {
+                return Err(JsError::Runtime(format!("TypeError: modul '{key}' neni nalinkovan")));
+            }
+            // Uz bezi (cyklus) nebo dobehl
+            _ => return Ok(JsValue::Undefined),
+        }
+        m.borrow_mut().state = ModuleState::Evaluating;
+        let deps: Vec<String> = m.borrow().requested.iter().map(|(_, u)| u.clone()).collect();
+        for dep in &deps {
+            if let Err(e) = self.evaluate_module(dep) {
+                return Err(self.fail_module(key, e));
+            }
+            let dep = self.module_record(dep)?;
+            if dep.borrow().state == ModuleState::EvaluatingAsync {
+                dep.borrow_mut().async_parents.push(key.to_string());
+                m.borrow_mut().pending_deps += 1;
+            }
+        }
+        if m.borrow().pending_deps > 0 {
+            self.mark_async(key)?;
+            return Ok(JsValue::Undefined);
+        }
+        self.execute_module(key)
+    }
+
+    fn execute_module(&mut self, key: &str) -> EvalResult {
+        let m = self.module_record(key)?;
+        let (body, env, has_await) = {
+            let record = m.borrow();
+            let env = record.env.clone()
+                .ok_or_else(|| JsError::Runtime(format!("TypeError: modul '{key}' neni nalinkovan")))?;
+            (Rc::clone(&record.body), env, record.has_await)
+        };
+        if !has_await {
+            // Telo modulu bezi ve VM; tree-walker jen pro telo, ktere se nezkompiluje
+            let result = match bytecode::compile_module(&body) {
+                Ok(code) => self.run_bytecode_in(&code, Rc::clone(&env)).map_err(call_machinery::vm_error),
+                Err(_) => self.exec_stmts(&body, &env)
+                    .map(|signal| match signal { Some(Signal::Return(v)) => v, _ => JsValue::Undefined }),
+            };
+            return match result {
+                Ok(v) => {
+                    self.finish_module(key);
+                    Ok(v)
+                }
+                Err(e) => Err(self.fail_module(key, e)),
+            };
+        }
+        let frame = Rc::new(RefCell::new(GeneratorFrame::for_module(self, body.to_vec(), env)?));
+        self.step_module(key, &frame, ResumeKind::Next, JsValue::Undefined)
+    }
+
+    /// Krok ramce modulu s top-level await. Pozastaveni na pending promise
+    /// prepne modul do EvaluatingAsync a pokracovani se naplanuje na jeji settle.
+    fn step_module(
+        &mut self,
+        key: &str,
+        frame: &Rc<RefCell<GeneratorFrame>>,
+        kind: ResumeKind,
+        sent: JsValue,
+    ) -> EvalResult {
+        let step = match step_frame(frame, kind, sent) {
+            Ok(step) => step,
+            Err(e) => return Err(self.fail_module(key, e)),
+        };
+        let value = self.get_prop(&step, "value")?;
+        if self.get_prop(&step, "done")?.is_truthy() {
+            self.finish_module(key);
+            return Ok(value);
+        }
+        self.mark_async(key)?;
+        let resume = |kind: ResumeKind| {
+            let (key, frame) = (key.to_string(), Rc::clone(frame));
+            let fork = RefCell::new(self.clone());
+            native("(module) await", move |a| {
+                let sent = a.into_iter().next().unwrap_or(JsValue::Undefined);
+                let mut interp = fork.borrow_mut();
+                if let Err(e) = interp.step_module(&key, &frame, kind, sent) {
+                    interp.report_module_error(&e);
+                }
+                Ok(JsValue::Undefined)
+            })
+        };
+        subscribe_pending(&value, resume(ResumeKind::Next), resume(ResumeKind::Throw));
+        Ok(JsValue::Undefined)
+    }
+
+    /// Prepne modul do EvaluatingAsync a zalozi promise jeho vyhodnoceni.
+    fn mark_async(&mut self, key: &str) -> Result<(), JsError> {
+        let m = self.module_record(key)?;
+        m.borrow_mut().state = ModuleState::EvaluatingAsync;
+        if m.borrow().promise.is_some() {
+            return Ok(());
+        }
+        let (promise, resolve, reject) = self.promise_with_resolvers()?;
+        let mut record = m.borrow_mut();
+        record.promise = Some(promise);
+        record.resolvers = Some((resolve, reject));
+        Ok(())
+    }
+
+    /// Pending promise spolu s jeji resolve / reject funkci.
+    fn promise_with_resolvers(&mut self) -> Result<(JsValue, JsValue, JsValue), JsError> {
+        let resolvers = Rc::new(RefCell::new((JsValue::Undefined, JsValue::Undefined)));
+        let slot = Rc::clone(&resolvers);
+        let executor = native("executor", move |a| {
+            let mut a = a.into_iter();
+            *slot.borrow_mut() = (a.next().unwrap_or(JsValue::Undefined), a.next().unwrap_or(JsValue::Undefined));
+            Ok(JsValue::Undefined)
+        });
+        let promise = self.construct_promise(vec![executor])?;
+        let (resolve, reject) = resolvers.borrow().clone();
+        Ok((promise, resolve, reject))
+    }
+
+    fn finish_module(&mut self, key: &str) {
+        let Ok(m) = self.module_record(key) else { return };
+        let (parents, resolvers) = {
+            let mut record = m.borrow_mut();
+            record.state = ModuleState::Evaluated;
+            (std::mem::take(&mut record.async_parents), record.resolvers.clone())
+        };
+        if let Some((resolve, _)) = resolvers {
+            let _ = self.call_function(resolve, vec![JsValue::Undefined], None);
+        }
+        for parent in parents {
+            let Ok(p) = self.module_record(&parent) else { continue };
+            let ready = {
+                let mut record = p.borrow_mut();
+                record.pending_deps = record.pending_deps.saturating_sub(1);
+                record.pending_deps == 0 && record.state == ModuleState::EvaluatingAsync
+            };
+            if ready && let Err(e) = self.execute_module(&parent) {
+                self.report_module_error(&e);
+            }
+        }
+    }
+
+    /// Oznaci modul (a cekajici rodice) jako chybny a odmitne jeho promise.
+    /// Vraci puvodni chybu pro volajiciho.
+    fn fail_module(&mut self, key: &str, e: JsError) -> JsError {
+        let Ok(m) = self.module_record(key) else { return e };
+        if m.borrow().state == ModuleState::Errored {
+            return e;
+        }
+        let value = self.error_value(&e);
+        let (parents, resolvers) = {
+            let mut record = m.borrow_mut();
+            record.state = ModuleState::Errored;
+            record.error = Some(value.clone());
+            (std::mem::take(&mut record.async_parents), record.resolvers.clone())
+        };
+        if let Some((_, reject)) = resolvers {
+            let _ = self.call_function(reject, vec![value.clone()], None);
+        }
+        for parent in parents {
+            self.fail_module(&parent, JsError::Thrown(value.clone()));
+        }
+        e
+    }
+
+    /// Chyba asynchronne dobihajiciho modulu nema volajiciho - jde do konzole.
+    fn report_module_error(&self, e: &JsError) {
+        let msg = match e {
+            JsError::Thrown(v) => match v {
+                JsValue::Object(o) => {
+                    let o = o.borrow();
+                    format!("{}: {}", o.get("name"), o.get("message"))
+                }
+                other => other.to_string(),
+            },
+            other => other.to_string(),
+        };
+        self.console_log.borrow_mut().push(("error".into(), format!("Uncaught (in module) {msg}")));
+    }
+
+    /// Adresa modulu, ze ktereho se vola `import()` (jinak dokument).
+    pub(super) fn module_referrer(&mut self, env: &Rc<RefCell<Environment>>) -> String {
+        let meta = env.borrow().get("import.meta");
+        match meta {
+            Some(meta) => self.get_prop(&meta, "url").map(|u| u.to_string()).unwrap_or_default(),
+            None => self.document_url(),
+        }
+    }
+
+    /// `import(specifier)` - vraci promise namespace objektu.
+    pub(super) fn import_dynamic(&mut self, specifier: &str, referrer: &str) -> EvalResult {
+        let loaded = self.resolve_module_specifier(specifier, referrer).and_then(|url| {
+            self.load_module_graph(&url)?;
+            self.link_module(&url)?;
+            self.evaluate_module(&url)?;
+            Ok(url)
+        });
+        let url = match loaded {
+            Ok(url) => url,
+            Err(e) => {
+                let reason = self.error_value(&e);
+                return Ok(make_settled_promise("rejected", reason));
+            }
+        };
+        let m = self.module_record(&url)?;
+        let (state, error, promise) = {
+            let record = m.borrow();
+            (record.state, record.error.clone(), record.promise.clone())
+        };
+        if state == ModuleState::Errored {
+            return Ok(make_settled_promise("rejected", error.unwrap_or(JsValue::Undefined)));
+        }
+        let ns = self.module_namespace(&url)?;
+        let Some(promise) = promise.filter(|_| state == ModuleState::EvaluatingAsync) else {
+            return Ok(make_settled_promise("fulfilled", ns));
+        };
+        // Modul jeste ceka na top-level await - namespace az po jeho dobehnuti
+        let (result, resolve, reject) = self.promise_with_resolvers()?;
+        let on_f = native("(import) resolve", move |_| {
+            bytecode::call_function(&resolve, JsValue::Undefined, vec![ns.clone()])
+        });
+        subscribe_pending(&promise, on_f, reject);
+        Ok(result)
+    }
+}
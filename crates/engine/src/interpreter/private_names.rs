@@ -0,0 +1,145 @@
+//! Privatni jmena trid (`#x`) - sdilene tree-walkerem i bytecode VM.
+//!
+//! Kazde vyhodnoceni tela tridy vytvori pro sva `#jmena` nove klice
+//! `__#jmeno@N__` a vaze je do scope tridy pod jmenem `#jmeno` - lexikalni
+//! viditelnost (vnorene tridy, closures v metodach) tak resi obycejny lookup
+//! promennych. Privatni pole, metody i accessory jsou vlastni ne-enumerable
+//! vlastnosti objektu pod timto klicem; interni tvar `__..__` je skryva pred
+//! reflexi (`Object.keys`, `Reflect.ownKeys`, JSON). Brand check = objekt ma
+//! vlastni klic - dedicnost prototypem ani Proxy ho nepropusti.
+
+use std::cell::Cell;
+
+use super::*;
+
+thread_local! {
+    static NEXT_ID: Cell<u64> = const { Cell::new(0) };
+}
+
+/// Novy unikatni klic pro `#name` (jedno vyhodnoceni tela tridy).
+pub(crate) fn new_key(name: &str) -> String {
+    let id = NEXT_ID.with(|c| {
+        c.set(c.get() + 1);
+        c.get()
+    });
+    format!("__#{name}@{id}__")
+}
+
+/// Jmeno vazby privatniho jmena ve scope tridy.
+pub(crate) fn binding(name: &str) -> String {
+    format!("#{name}")
+}
+
+/// Zdrojove jmeno pro chybove hlasky: `__#x@3__` -> `#x`.
+pub(crate) fn source_name(key: &str) -> &str {
+    let inner = key.strip_prefix("__").unwrap_or(key);
+    inner.rsplit_once('@').map_or(inner, |(n, _)| n)
+}
+
+/// Objekt nesouci vlastni cleny: obycejny objekt nebo staticke vlastnosti
+/// tridy tree-walkeru.
+fn holder(obj: &JsValue) -> Option<Rc<RefCell<JsObject>>> {
+    match obj {
+        JsValue::Object(o) => Some(Rc::clone(o)),
+        JsValue::Function(JsFunc::Class { static_props, .. }) => Some(Rc::clone(static_props)),
+        _ => None,
+    }
+}
+
+/// `#x in obj`: vlastni privatni clen (bez prototypu). Neobjekt je TypeError.
+pub(crate) fn has(obj: &JsValue, key: &str) -> Result<bool, String> {
+    match obj {
+        JsValue::Object(_) | JsValue::Function(_) | JsValue::Array(_) | JsValue::Map(_)
+        | JsValue::Set(_) | JsValue::DomNode(_) => {
+            Ok(holder(obj).is_some_and(|h| h.borrow().props.contains_key(key)))
+        }
+        other => Err(format!(
+            "TypeError: Cannot use 'in' operator to search for '{}' in {}",
+            source_name(key), other
+        )),
+    }
+}
+
+/// Brand check pred ctenim `obj.#x`; vraci slot (data nebo accessor).
+pub(crate) fn check_get(obj: &JsValue, key: &str) -> Result<PropertySlot, String> {
+    let slot = holder(obj).and_then(|h| h.borrow().props.slot(key).cloned());
+    match slot {
+        Some(s) if s.is_accessor() && s.getter.is_none() => {
+            Err(format!("TypeError: '{}' was defined without a getter", source_name(key)))
+        }
+        Some(s) => Ok(s),
+        None => Err(format!(
+            "TypeError: Cannot read private member {} from an object whose class did not declare it",
+            source_name(key)
+        )),
+    }
+}
+
+/// Brand check pred zapisem `obj.#x = v`; vraci slot (data nebo accessor).
+/// Privatni metody jsou read-only.
+pub(crate) fn check_set(obj: &JsValue, key: &str) -> Result<PropertySlot, String> {
+    let slot = holder(obj).and_then(|h| h.borrow().props.slot(key).cloned());
+    match slot {
+        Some(s) if s.is_accessor() && s.setter.is_none() => {
+            Err(format!("TypeError: '{}' was defined without a setter", source_name(key)))
+        }
+        Some(s) if !s.is_accessor() && !s.writable => {
+            Err(format!("TypeError: Private method {} is not writable", source_name(key)))
+        }
+        Some(s) => Ok(s),
+        None => Err(format!(
+            "TypeError: Cannot write private member {} to an object whose class did not declare it",
+            source_name(key)
+        )),
+    }
+}
+
+/// Zapis do datoveho privatniho pole (po `check_set`).
+pub(crate) fn write(obj: &JsValue, key: &str, value: JsValue) {
+    if let Some(h) = holder(obj) && let Some(v) = h.borrow_mut().props.get_mut(key) {
+        gc::track_value(&value);
+        *v = value;
+    }
+}
+
+/// Definice pole tridy (`x = 1` / `#x = 1`): verejne pole je vlastni enumerable
+/// vlastnost (prepise i zdedeny setter), privatni ne-enumerable a jen jednou.
+pub(crate) fn define_field(obj: &JsValue, key: &PropertyKey, value: JsValue) -> Result<(), String> {
+    let Some(h) = holder(obj) else {
+        return Err(format!("TypeError: Cannot define class field {key} on a primitive"));
+    };
+    let mut h = h.borrow_mut();
+    let name = match key {
+        PropertyKey::Str(k) if is_private_key(k) => k,
+        _ => {
+            h.props.insert_slot(key.clone(), PropertySlot::data(value));
+            return Ok(());
+        }
+    };
+    if h.props.contains_key(name) {
+        return Err(format!("TypeError: Cannot initialize {} twice on the same object", source_name(name)));
+    }
+    h.define_hidden(key.clone(), value);
+    Ok(())
+}
+
+/// Privatni metoda (read-only) nebo polovina privatniho accessoru.
+pub(crate) fn define_method(obj: &JsValue, key: &PropertyKey, f: JsValue, getter: bool, setter: bool) {
+    let Some(h) = holder(obj) else { return };
+    let mut h = h.borrow_mut();
+    match (getter, setter) {
+        (true, _) => h.define_accessor(key.clone(), Some(f), None),
+        (_, true) => h.define_accessor(key.clone(), None, Some(f)),
+        _ => {
+            let mut slot = PropertySlot::data(f);
+            slot.enumerable = false;
+            slot.writable = false;
+            h.props.insert_slot(key.clone(), slot);
+        }
+    }
+}
+
+/// Interni klic privatniho jmena (`new_key`).
+pub(crate) fn is_private_key(key: impl AsKey) -> bool {
+    matches!(key.key_ref(), KeyRef::Str(k) if k.starts_with("__#"))
+}
@@ -0,0 +1,642 @@
+//! PropertyMap - usporadane uloziste vlastnosti `JsObject` s deskriptory.
+//!
+//! Kazda vlastnost je slot s hodnotou (data property) nebo getterem/setterem
+//! (accessor property) a atributy writable/enumerable/configurable. Sloty drzi
+//! poradi vlozeni; `JsObject::own_keys` z nej sklada spec poradi (integer
+//! indexy vzestupne, pak ostatni klice v poradi vlozeni).
+//!
+//! API kopiruje podmnozinu `HashMap<String, JsValue>` (`get`, `insert`,
+//! `contains_key`, `keys`, `iter`...), protoze na `props` primo sahaji desitky
+//! nativnich builtinu. `insert` je interni zapis bez kontroly atributu - JS
+//! prirazeni jde pres `JsObject::set`.
+//!
+//! Klice jsou `PropertyKey` - string nebo symbol. Symboly maji vlastni index,
+//! takze stringovy klic s nimi nikdy nekoliduje; `keys`/`iter`/`slots` vraci
+//! jen stringove klice (ty ocekavaji builtiny), `entries` vsechny.
+//!
+//! Kazda mapa nese tvar (`ShapeId`, hidden class): objekty, ktere dostaly stejne
+//! klice ve stejnem poradi, sdili tvar a tedy i index slotu pro kazdy klic.
+//! Inline cache VM (`bytecode/ic.rs`) si pamatuji (tvar, index) misto hash lookupu.
+//! Tvary tvori strom prechodu (tvar + novy klic -> tvar); `remove` prepne mapu
+//! do slovnikoveho rezimu, ktery se necachuje.
+//!
+//! Nova mapa je mimo strom (objekty, na ktere sahne jen tree-walker, zadne tvary
+//! nezakladaji). Do stromu ji zaradi az `track_shape` z inline cache. Strom je
+//! omezeny - vetveni jednoho tvaru (`MAX_TRANSITIONS`), pocet klicu
+//! (`MAX_SHAPED_KEYS`) i tvaru celkem (`MAX_SHAPES`); za limitem mapa prejde
+//! do slovnikoveho rezimu.
+
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::rc::Rc;
+use super::JsValue;
+use super::symbols::JsSymbol;
+
+/// Identita tvaru objektu - mnozina klicu vcetne poradi vlozeni.
+pub type ShapeId = u64;
+
+/// Mapa mimo strom prechodu (vychozi stav) - navenek se hlasi jako slovnik.
+const UNTRACKED_SHAPE: ShapeId = 0;
+
+/// Tvar prazdne mapy.
+pub const ROOT_SHAPE: ShapeId = 1;
+
+/// Slovnikovy rezim (po `remove` nebo za limity stromu) - inline cache ho preskakuji.
+pub const DICTIONARY_SHAPE: ShapeId = ShapeId::MAX;
+
+/// Objekty s vic klici (hash mapy v datech) uz strom prechodu nenafukuji.
+const MAX_SHAPED_KEYS: usize = 32;
+
+/// Nejvic ruznych nasledniku jednoho tvaru (objekty pouzite jako slovnik).
+const MAX_TRANSITIONS: usize = 16;
+
+/// Strop velikosti stromu prechodu za zivot vlakna.
+const MAX_SHAPES: ShapeId = 1 << 16;
+
+/// Klic vlastnosti: string, nebo symbol (identita podle `JsSymbol::id`).
+#[derive(Debug, Clone)]
+pub enum PropertyKey {
+    Str(String),
+    Sym(Rc<JsSymbol>),
+}
+
+impl PropertyKey {
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            PropertyKey::Str(s) => Some(s),
+            PropertyKey::Sym(_) => None,
+        }
+    }
+
+    pub fn is_symbol(&self) -> bool {
+        matches!(self, PropertyKey::Sym(_))
+    }
+
+    /// Interni `__key__` klic enginu (symboly nikdy).
+    pub fn is_internal(&self) -> bool {
+        matches!(self, PropertyKey::Str(s) if super::is_internal_key(s))
+    }
+
+    /// Klic jako JS hodnota (proxy trapy, `Reflect.ownKeys`).
+    pub fn to_value(&self) -> JsValue {
+        match self {
+            PropertyKey::Str(s) => JsValue::Str(s.clone()),
+            PropertyKey::Sym(sym) => JsValue::Symbol(Rc::clone(sym)),
+        }
+    }
+}
+
+impl Default for PropertyKey {
+    fn default() -> Self {
+        PropertyKey::Str(String::new())
+    }
+}
+
+impl PartialEq for PropertyKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.key_ref() == other.key_ref()
+    }
+}
+
+impl Eq for PropertyKey {}
+
+impl std::hash::Hash for PropertyKey {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.key_ref().hash(state);
+    }
+}
+
+impl std::fmt::Display for PropertyKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PropertyKey::Str(s) => f.write_str(s),
+            PropertyKey::Sym(sym) => f.write_str(&sym.display()),
+        }
+    }
+}
+
+impl From<String> for PropertyKey {
+    fn from(s: String) -> Self {
+        PropertyKey::Str(s)
+    }
+}
+
+impl From<&str> for PropertyKey {
+    fn from(s: &str) -> Self {
+        PropertyKey::Str(s.to_string())
+    }
+}
+
+impl From<&String> for PropertyKey {
+    fn from(s: &String) -> Self {
+        PropertyKey::Str(s.clone())
+    }
+}
+
+impl From<&PropertyKey> for PropertyKey {
+    fn from(k: &PropertyKey) -> Self {
+        k.clone()
+    }
+}
+
+impl From<&Rc<JsSymbol>> for PropertyKey {
+    fn from(sym: &Rc<JsSymbol>) -> Self {
+        PropertyKey::Sym(Rc::clone(sym))
+    }
+}
+
+impl PartialEq<str> for PropertyKey {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == Some(other)
+    }
+}
+
+impl PartialEq<&str> for PropertyKey {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == Some(*other)
+    }
+}
+
+impl From<Rc<JsSymbol>> for PropertyKey {
+    fn from(sym: Rc<JsSymbol>) -> Self {
+        PropertyKey::Sym(sym)
+    }
+}
+
+/// Klic pro cteni bez alokace.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum KeyRef<'a> {
+    Str(&'a str),
+    /// `JsSymbol::id`
+    Sym(u64),
+}
+
+/// Cokoliv, cim lze hledat vlastnost: `&str`/`String` (stringovy klic),
+/// `PropertyKey`, `JsSymbol` a well-known symboly (`symbols::ITERATOR`).
+pub trait AsKey {
+    fn key_ref(&self) -> KeyRef<'_>;
+}
+
+impl AsKey for str {
+    fn key_ref(&self) -> KeyRef<'_> {
+        KeyRef::Str(self)
+    }
+}
+
+impl AsKey for String {
+    fn key_ref(&self) -> KeyRef<'_> {
+        KeyRef::Str(self)
+    }
+}
+
+impl AsKey for PropertyKey {
+    fn key_ref(&self) -> KeyRef<'_> {
+        match self {
+            PropertyKey::Str(s) => KeyRef::Str(s),
+            PropertyKey::Sym(sym) => KeyRef::Sym(sym.id),
+        }
+    }
+}
+
+impl AsKey for JsSymbol {
+    fn key_ref(&self) -> KeyRef<'_> {
+        KeyRef::Sym(self.id)
+    }
+}
+
+impl AsKey for Rc<JsSymbol> {
+    fn key_ref(&self) -> KeyRef<'_> {
+        KeyRef::Sym(self.id)
+    }
+}
+
+impl AsKey for KeyRef<'_> {
+    fn key_ref(&self) -> KeyRef<'_> {
+        *self
+    }
+}
+
+impl<T: AsKey + ?Sized> AsKey for &T {
+    fn key_ref(&self) -> KeyRef<'_> {
+        (**self).key_ref()
+    }
+}
+
+/// Hrany z jednoho tvaru - symbol jen podle id (strom ho nedrzi nazivu).
+#[derive(Default)]
+struct Transitions {
+    strs: HashMap<String, ShapeId>,
+    syms: HashMap<u64, ShapeId>,
+}
+
+impl Transitions {
+    fn len(&self) -> usize {
+        self.strs.len() + self.syms.len()
+    }
+}
+
+thread_local! {
+    /// Strom prechodu: tvar -> (novy klic -> tvar potomka).
+    static TRANSITIONS: RefCell<HashMap<ShapeId, Transitions>> = RefCell::new(HashMap::new());
+    static NEXT_SHAPE: Cell<ShapeId> = const { Cell::new(ROOT_SHAPE + 1) };
+}
+
+/// Tvar po pridani klice `key` k tvaru `from` (za limity stromu `DICTIONARY_SHAPE`).
+fn transition(from: ShapeId, key: KeyRef) -> ShapeId {
+    TRANSITIONS.with(|t| {
+        let mut t = t.borrow_mut();
+        let children = t.entry(from).or_default();
+        let existing = match key {
+            KeyRef::Str(s) => children.strs.get(s),
+            KeyRef::Sym(id) => children.syms.get(&id),
+        };
+        if let Some(&s) = existing {
+            return s;
+        }
+        let next = NEXT_SHAPE.with(Cell::get);
+        if children.len() >= MAX_TRANSITIONS || next >= MAX_SHAPES {
+            return DICTIONARY_SHAPE;
+        }
+        NEXT_SHAPE.with(|n| n.set(next + 1));
+        match key {
+            KeyRef::Str(s) => children.strs.insert(s.to_string(), next),
+            KeyRef::Sym(id) => children.syms.insert(id, next),
+        };
+        next
+    })
+}
+
+/// Jedna vlastnost objektu vcetne atributu (ECMAScript property descriptor).
+#[derive(Debug, Clone)]
+pub struct PropertySlot {
+    /// Hodnota data property (u accessoru `Undefined`).
+    pub value: JsValue,
+    pub getter: Option<JsValue>,
+    pub setter: Option<JsValue>,
+    pub writable: bool,
+    pub enumerable: bool,
+    pub configurable: bool,
+}
+
+impl PropertySlot {
+    /// Bezna data property vytvorena prirazenim (vsechny atributy true).
+    pub fn data(value: JsValue) -> Self {
+        PropertySlot { value, getter: None, setter: None, writable: true, enumerable: true, configurable: true }
+    }
+
+    /// Accessor property tridy / vestaveneho objektu (non-enumerable jako na prototypu).
+    /// Accessor z objektoveho literalu si enumerable nastavi sam.
+    pub fn accessor(getter: Option<JsValue>, setter: Option<JsValue>) -> Self {
+        PropertySlot {
+            value: JsValue::Undefined, getter, setter,
+            writable: false, enumerable: false, configurable: true,
+        }
+    }
+
+    pub fn is_accessor(&self) -> bool {
+        self.getter.is_some() || self.setter.is_some()
+    }
+}
+
+type Entry = (PropertyKey, PropertySlot);
+
+/// Vlastnosti v poradi vlozeni. Smazane sloty zustavaji jako `None` (nezmeni se
+/// indexy ostatnich), pri prevaze mazanych se pole zkompaktuje.
+#[derive(Debug, Clone, Default)]
+pub struct PropertyMap {
+    slots: Vec<Option<Entry>>,
+    /// Stringove klice -> index slotu.
+    index: HashMap<String, usize>,
+    /// Symbolove klice (`JsSymbol::id`) -> index slotu.
+    symbols: HashMap<u64, usize>,
+    shape: ShapeId,
+}
+
+impl PropertyMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.index.len() + self.symbols.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn find(&self, k: KeyRef) -> Option<usize> {
+        match k {
+            KeyRef::Str(s) => self.index.get(s).copied(),
+            KeyRef::Sym(id) => self.symbols.get(&id).copied(),
+        }
+    }
+
+    pub fn contains_key(&self, k: impl AsKey) -> bool {
+        self.find(k.key_ref()).is_some()
+    }
+
+    /// Hodnota data property (accessor vraci `Undefined` - getter vola interpret).
+    pub fn get(&self, k: impl AsKey) -> Option<&JsValue> {
+        self.slot(k).map(|s| &s.value)
+    }
+
+    pub fn get_mut(&mut self, k: impl AsKey) -> Option<&mut JsValue> {
+        self.slot_mut(k).map(|s| &mut s.value)
+    }
+
+    pub fn slot(&self, k: impl AsKey) -> Option<&PropertySlot> {
+        let i = self.find(k.key_ref())?;
+        self.slots[i].as_ref().map(|(_, s)| s)
+    }
+
+    pub fn slot_mut(&mut self, k: impl AsKey) -> Option<&mut PropertySlot> {
+        let i = self.find(k.key_ref())?;
+        self.slots[i].as_mut().map(|(_, s)| s)
+    }
+
+    /// Interni zapis hodnoty. Existujici slot si necha poradi i enumerable/configurable
+    /// (accessor se prepise na data property), novy slot ma vsechny atributy true.
+    pub fn insert(&mut self, k: PropertyKey, v: JsValue) -> Option<JsValue> {
+        super::gc::track_value(&v);
+        if let Some(slot) = self.slot_mut(&k) {
+            slot.getter = None;
+            slot.setter = None;
+            slot.writable = true;
+            return Some(std::mem::replace(&mut slot.value, v));
+        }
+        self.insert_slot(k, PropertySlot::data(v));
+        None
+    }
+
+    /// Vlozi/prepise cely slot (Object.defineProperty). Poradi existujiciho klice se zachova.
+    pub fn insert_slot(&mut self, k: PropertyKey, slot: PropertySlot) {
+        super::gc::track_value(&slot.value);
+        slot.getter.iter().chain(&slot.setter).for_each(super::gc::track_value);
+        if let Some(i) = self.find(k.key_ref()) {
+            if let Some((_, s)) = self.slots[i].as_mut() { *s = slot; }
+            return;
+        }
+        self.shape = match self.shape {
+            UNTRACKED_SHAPE | DICTIONARY_SHAPE => self.shape,
+            _ if self.slots.len() >= MAX_SHAPED_KEYS => DICTIONARY_SHAPE,
+            s => transition(s, k.key_ref()),
+        };
+        let i = self.slots.len();
+        match &k {
+            PropertyKey::Str(s) => { self.index.insert(s.clone(), i); }
+            PropertyKey::Sym(sym) => { self.symbols.insert(sym.id, i); }
+        }
+        self.slots.push(Some((k, slot)));
+    }
+
+    pub fn remove(&mut self, k: impl AsKey) -> Option<JsValue> {
+        let i = match k.key_ref() {
+            KeyRef::Str(s) => self.index.remove(s)?,
+            KeyRef::Sym(id) => self.symbols.remove(&id)?,
+        };
+        self.shape = DICTIONARY_SHAPE;
+        let removed = self.slots[i].take().map(|(_, s)| s.value);
+        if self.slots.len() > 16 && self.len() * 2 < self.slots.len() {
+            self.compact();
+        }
+        removed
+    }
+
+    pub fn clear(&mut self) {
+        self.slots.clear();
+        self.index.clear();
+        self.symbols.clear();
+        self.shape = UNTRACKED_SHAPE;
+    }
+
+    /// Aktualni tvar mapy (zmeni se pridanim nebo odebranim klice, ne zapisem hodnoty).
+    /// Mapa mimo strom prechodu se hlasi jako slovnik.
+    pub fn shape(&self) -> ShapeId {
+        match self.shape {
+            UNTRACKED_SHAPE => DICTIONARY_SHAPE,
+            s => s,
+        }
+    }
+
+    /// Zaradi mapu do stromu prechodu (prehraje jeji klice) a vrati tvar.
+    pub fn track_shape(&mut self) -> ShapeId {
+        if self.shape == UNTRACKED_SHAPE {
+            let mut shape = if self.slots.len() > MAX_SHAPED_KEYS { DICTIONARY_SHAPE } else { ROOT_SHAPE };
+            for (k, _) in self.slots.iter().flatten() {
+                if shape == DICTIONARY_SHAPE { break; }
+                shape = transition(shape, k.key_ref());
+            }
+            self.shape = shape;
+        }
+        self.shape
+    }
+
+    /// Index slotu klice - pri stejnem tvaru je stabilni.
+    pub fn slot_index(&self, k: impl AsKey) -> Option<usize> {
+        self.find(k.key_ref())
+    }
+
+    pub fn slot_at(&self, i: usize) -> Option<&PropertySlot> {
+        self.slots.get(i)?.as_ref().map(|(_, s)| s)
+    }
+
+    pub fn slot_at_mut(&mut self, i: usize) -> Option<&mut PropertySlot> {
+        self.slots.get_mut(i)?.as_mut().map(|(_, s)| s)
+    }
+
+    fn compact(&mut self) {
+        self.slots.retain(Option::is_some);
+        for (i, (k, _)) in self.slots.iter().flatten().enumerate() {
+            let idx = match k {
+                PropertyKey::Str(s) => self.index.get_mut(s),
+                PropertyKey::Sym(sym) => self.symbols.get_mut(&sym.id),
+            };
+            if let Some(idx) = idx { *idx = i; }
+        }
+    }
+
+    /// Vsechny sloty (stringove i symbolove klice) v poradi vlozeni.
+    pub fn entries(&self) -> impl Iterator<Item = (&PropertyKey, &PropertySlot)> {
+        self.slots.iter().flatten().map(|(k, s)| (k, s))
+    }
+
+    /// Sloty se stringovym klicem v poradi vlozeni.
+    pub fn slots(&self) -> impl Iterator<Item = (&String, &PropertySlot)> {
+        self.slots.iter().flatten().filter_map(|(k, s)| match k {
+            PropertyKey::Str(k) => Some((k, s)),
+            PropertyKey::Sym(_) => None,
+        })
+    }
+
+    /// Symbolove klice v poradi vlozeni.
+    pub fn symbol_keys(&self) -> impl Iterator<Item = &Rc<JsSymbol>> {
+        self.slots.iter().flatten().filter_map(|(k, _)| match k {
+            PropertyKey::Sym(sym) => Some(sym),
+            PropertyKey::Str(_) => None,
+        })
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &String> {
+        self.slots().map(|(k, _)| k)
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &JsValue> {
+        self.slots().map(|(_, s)| &s.value)
+    }
+
+    pub fn iter(&self) -> Iter<'_> {
+        self.slots.iter().flatten().filter_map(entry_value as fn(&Entry) -> Option<(&String, &JsValue)>)
+    }
+}
+
+fn entry_value(e: &Entry) -> Option<(&String, &JsValue)> {
+    match &e.0 {
+        PropertyKey::Str(k) => Some((k, &e.1.value)),
+        PropertyKey::Sym(_) => None,
+    }
+}
+
+pub type Iter<'a> = std::iter::FilterMap<
+    std::iter::Flatten<std::slice::Iter<'a, Option<Entry>>>,
+    fn(&'a Entry) -> Option<(&'a String, &'a JsValue)>,
+>;
+
+impl<'a> IntoIterator for &'a PropertyMap {
+    type Item = (&'a String, &'a JsValue);
+    type IntoIter = Iter<'a>;
+    fn into_iter(self) -> Iter<'a> {
+        self.iter()
+    }
+}
+
+/// Array index klic ("0", "1", ... kanonicky zapis u32 < 2^32 - 1) - v poradi
+/// klicu jde pred ostatnimi, serazeny numericky.
+pub fn array_index(k: &str) -> Option<u32> {
+    if k.is_empty() || (k.len() > 1 && k.starts_with('0')) { return None; }
+    k.parse::<u32>().ok().filter(|n| *n != u32::MAX)
+}
+
+/// Deskriptor z `Object.defineProperty(o, k, desc)` - chybejici pole jsou None.
+#[derive(Debug, Clone, Default)]
+pub struct PropertyDescriptor {
+    pub value: Option<JsValue>,
+    pub getter: Option<JsValue>,
+    pub setter: Option<JsValue>,
+    pub writable: Option<bool>,
+    pub enumerable: Option<bool>,
+    pub configurable: Option<bool>,
+}
+
+impl PropertyDescriptor {
+    /// ToPropertyDescriptor - cte deskriptor z JS objektu.
+    pub fn from_js(desc: &JsValue) -> Result<Self, String> {
+        let JsValue::Object(d) = desc else {
+            return Err("TypeError: Property description must be an object".into());
+        };
+        let d = d.borrow();
+        let field = |name: &str| if d.props.contains_key(name) || !matches!(d.get(name), JsValue::Undefined) {
+            Some(d.get(name))
+        } else { None };
+        let out = PropertyDescriptor {
+            value: field("value"),
+            getter: field("get").filter(|g| !matches!(g, JsValue::Undefined)),
+            setter: field("set").filter(|s| !matches!(s, JsValue::Undefined)),
+            writable: field("writable").map(|v| v.is_truthy()),
+            enumerable: field("enumerable").map(|v| v.is_truthy()),
+            configurable: field("configurable").map(|v| v.is_truthy()),
+        };
+        for f in [&out.getter, &out.setter].into_iter().flatten() {
+            if !matches!(f, JsValue::Function(_)) {
+                return Err(format!("TypeError: Getter/setter must be a function: {f}"));
+            }
+        }
+        if out.is_accessor() && (out.value.is_some() || out.writable.is_some()) {
+            return Err("TypeError: Invalid property descriptor. Cannot both specify accessors and a value or writable attribute".into());
+        }
+        Ok(out)
+    }
+
+    fn is_accessor(&self) -> bool {
+        self.getter.is_some() || self.setter.is_some()
+    }
+}
+
+/// FromPropertyDescriptor - `Object.getOwnPropertyDescriptor` vysledek.
+pub fn descriptor_to_js(slot: &PropertySlot) -> JsValue {
+    let mut d = super::JsObject::new();
+    if slot.is_accessor() {
+        d.set("get".into(), slot.getter.clone().unwrap_or(JsValue::Undefined));
+        d.set("set".into(), slot.setter.clone().unwrap_or(JsValue::Undefined));
+    } else {
+        d.set("value".into(), slot.value.clone());
+        d.set("writable".into(), JsValue::Bool(slot.writable));
+    }
+    d.set("enumerable".into(), JsValue::Bool(slot.enumerable));
+    d.set("configurable".into(), JsValue::Bool(slot.configurable));
+    JsValue::Object(std::rc::Rc::new(std::cell::RefCell::new(d)))
+}
+
+fn same_value(a: &JsValue, b: &JsValue) -> bool {
+    match (a, b) {
+        (JsValue::Number(x), JsValue::Number(y)) => x.to_bits() == y.to_bits() || (x.is_nan() && y.is_nan()),
+        _ => a.function_identity_eq(b),
+    }
+}
+
+impl super::JsObject {
+    /// ValidateAndApplyPropertyDescriptor - `Object.defineProperty` semantika.
+    /// Zmena non-configurable vlastnosti (krome writable true -> false) je TypeError.
+    pub fn define_own_property(&mut self, key: PropertyKey, desc: PropertyDescriptor) -> Result<(), String> {
+        let Some(cur) = self.props.slot(&key).cloned() else {
+            if !self.extensible {
+                return Err(format!("TypeError: Cannot define property {key}, object is not extensible"));
+            }
+            let slot = PropertySlot {
+                value: desc.value.clone().unwrap_or(JsValue::Undefined),
+                getter: desc.getter.clone(),
+                setter: desc.setter.clone(),
+                writable: desc.writable.unwrap_or(false),
+                enumerable: desc.enumerable.unwrap_or(false),
+                configurable: desc.configurable.unwrap_or(false),
+            };
+            self.props.insert_slot(key, slot);
+            return Ok(());
+        };
+        let to_accessor = desc.is_accessor();
+        let to_data = desc.value.is_some() || desc.writable.is_some();
+        if !cur.configurable {
+            let redefine = || Err(format!("TypeError: Cannot redefine property: {key}"));
+            if desc.configurable == Some(true) { return redefine(); }
+            if desc.enumerable.is_some_and(|e| e != cur.enumerable) { return redefine(); }
+            if (cur.is_accessor() && to_data) || (!cur.is_accessor() && to_accessor) { return redefine(); }
+            if cur.is_accessor() {
+                let changed = |new: &Option<JsValue>, old: &Option<JsValue>| match (new, old) {
+                    (Some(n), Some(o)) => !same_value(n, o),
+                    (Some(_), None) => true,
+                    _ => false,
+                };
+                if changed(&desc.getter, &cur.getter) || changed(&desc.setter, &cur.setter) { return redefine(); }
+            } else if !cur.writable {
+                if desc.writable == Some(true) { return redefine(); }
+                if desc.value.as_ref().is_some_and(|v| !same_value(v, &cur.value)) { return redefine(); }
+            }
+        }
+        let mut slot = cur;
+        if to_accessor && !slot.is_accessor() {
+            slot.value = JsValue::Undefined;
+            slot.writable = false;
+        } else if to_data && slot.is_accessor() {
+            slot.getter = None;
+            slot.setter = None;
+        }
+        if let Some(v) = desc.value { slot.value = v; }
+        if desc.getter.is_some() { slot.getter = desc.getter; }
+        if desc.setter.is_some() { slot.setter = desc.setter; }
+        if let Some(w) = desc.writable { slot.writable = w; }
+        if let Some(e) = desc.enumerable { slot.enumerable = e; }
+        if let Some(c) = desc.configurable { slot.configurable = c; }
+        self.props.insert_slot(key, slot);
+        Ok(())
+    }
+}
@@ -1,8 +1,27 @@
 //! Proxy + Reflect - ECMA-262 ProxyExoticObject.
 //!
+//! Proxy je `JsObject` s internimi klici `__proxy_target__` / `__proxy_handler__`
+//! (revoked proxy ma handler `null`). Vlastni props proxy objektu se nepouzivaji -
+//! kazda operace jde pres trap handleru, pri chybejicim trapu na target.
+//!
 //! Proxy traps: get, set, has, deleteProperty, ownKeys, getOwnPropertyDescriptor,
 //! defineProperty, preventExtensions, isExtensible, getPrototypeOf, setPrototypeOf,
 //! apply, construct.
+//!
+//! Interpret vola `proxy_*` metody z eval_member / assign / `in` / `delete` /
+//! for-in / volani a z `call_reflective` (Object.* a Reflect.* nad proxy).
+//! Bytecode VM ma vlastni get/set/apply/construct cestu (`VM::proxy_get` ...)
+//! nad stejnymi helpery `proxy_parts` a `trap_fn`.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+use super::*;
+use super::property_map::{PropertyDescriptor, descriptor_to_js};
+
+pub const TARGET: &str = "__proxy_target__";
+pub const HANDLER: &str = "__proxy_handler__";
+/// `typeof proxy === "function"` pro proxy nad volatelnym targetem.
+pub const CALLABLE: &str = "__proxy_callable__";
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum ProxyTrap {
@@ -58,47 +77,593 @@ impl ProxyTrap {
     }
 }
 
-#[derive(Debug, Clone)]
-pub struct ProxyDef {
-    pub target_id: u64,
-    pub handler_id: u64,
-    pub revoked: bool,
-    pub registered_traps: Vec<ProxyTrap>,
+/// Je hodnota proxy objekt (i revoked)?
+pub fn is_proxy(v: &JsValue) -> bool {
+    matches!(v, JsValue::Object(o) if o.borrow().props.contains_key(TARGET))
 }
 
-#[derive(Default)]
-pub struct ProxyRegistry {
-    pub proxies: std::collections::HashMap<u64, ProxyDef>,
-    pub next_id: u64,
+/// Nativni funkce, kterou interpret obsluhuje sam (`call_reflective`).
+pub(crate) fn is_reflective(name: &str) -> bool {
+    name.starts_with("Object.") || name.starts_with("Reflect.") || name == "JSON.stringify"
 }
 
-impl ProxyRegistry {
-    pub fn new() -> Self { Self::default() }
+/// (target, handler) proxy objektu. Revoked proxy ma handler `Null`.
+pub fn proxy_parts(v: &JsValue) -> Option<(JsValue, JsValue)> {
+    let JsValue::Object(o) = v else { return None };
+    let o = o.borrow();
+    let target = o.props.get(TARGET)?.clone();
+    Some((target, o.props.get(HANDLER).cloned().unwrap_or(JsValue::Null)))
+}
+
+/// Nejvnitrnejsi target (proxy nad proxy...) - pro `instanceof` a `Array.isArray`.
+pub fn innermost_target(v: &JsValue) -> JsValue {
+    let mut cur = v.clone();
+    for _ in 0..64 {
+        match proxy_parts(&cur) {
+            Some((target, _)) => cur = target,
+            None => break,
+        }
+    }
+    cur
+}
+
+/// Trap funkce z handleru: `Ok(None)` = trap neni definovany (forward na target).
+pub fn trap_fn(handler: &JsValue, trap: ProxyTrap) -> Result<Option<JsValue>, String> {
+    let f = match handler {
+        JsValue::Object(h) => h.borrow().get(trap.name()),
+        _ => return Err(format!("TypeError: Cannot perform '{}' on a proxy that has been revoked", trap.name())),
+    };
+    match f {
+        JsValue::Undefined | JsValue::Null => Ok(None),
+        JsValue::Function(_) => Ok(Some(f)),
+        other => Err(format!("TypeError: proxy trap '{}' is not a function: {other}", trap.name())),
+    }
+}
+
+fn is_callable(v: &JsValue) -> bool {
+    match v {
+        JsValue::Function(_) => true,
+        JsValue::Object(o) => matches!(o.borrow().props.get(CALLABLE), Some(JsValue::Bool(true))),
+        _ => false,
+    }
+}
+
+/// `new Proxy(target, handler)`.
+pub fn make_proxy(target: JsValue, handler: JsValue) -> Result<JsValue, String> {
+    let is_obj = |v: &JsValue| matches!(v,
+        JsValue::Object(_) | JsValue::Array(_) | JsValue::Function(_) | JsValue::Map(_) | JsValue::Set(_));
+    if !is_obj(&target) || !is_obj(&handler) {
+        return Err("TypeError: Cannot create proxy with a non-object as target or handler".into());
+    }
+    let mut obj = JsObject::new();
+    obj.proto = None;
+    if is_callable(&target) { obj.props.insert(CALLABLE.into(), JsValue::Bool(true)); }
+    obj.props.insert(TARGET.into(), target);
+    obj.props.insert(HANDLER.into(), handler);
+    Ok(JsValue::Object(Rc::new(RefCell::new(obj))))
+}
+
+/// Globalni `Proxy` volany bez `new` (`new Proxy` resi call_new / VM NewOp).
+pub fn proxy_ctor(_args: Vec<JsValue>) -> Result<JsValue, String> {
+    Err("TypeError: Constructor Proxy requires 'new'".into())
+}
+
+/// `Proxy.revocable(target, handler)` -> `{ proxy, revoke }`.
+pub fn proxy_revocable(args: Vec<JsValue>) -> Result<JsValue, String> {
+    let mut iter = args.into_iter();
+    let proxy = make_proxy(
+        iter.next().unwrap_or(JsValue::Undefined),
+        iter.next().unwrap_or(JsValue::Undefined),
+    )?;
+    let JsValue::Object(p) = &proxy else { unreachable!() };
+    let weak = Rc::downgrade(p);
+    let revoke = native("Proxy.revoke", move |_| {
+        if let Some(p) = weak.upgrade() {
+            p.borrow_mut().props.insert(HANDLER.into(), JsValue::Null);
+        }
+        Ok(JsValue::Undefined)
+    });
+    let mut out = JsObject::new();
+    out.set("proxy".into(), proxy);
+    out.set("revoke".into(), revoke);
+    Ok(JsValue::Object(Rc::new(RefCell::new(out))))
+}
+
+fn type_err(msg: String) -> JsError {
+    JsError::Runtime(format!("TypeError: {msg}"))
+}
+
+fn array_value(items: Vec<JsValue>) -> JsValue {
+    JsValue::Array(Rc::new(RefCell::new(items)))
+}
+
+impl Interpreter {
+    /// (target, trap fn) pro proxy; revoked proxy a ne-funkcni trap hazi TypeError.
+    fn proxy_trap(&self, proxy: &JsValue, trap: ProxyTrap) -> Result<(JsValue, Option<(JsValue, JsValue)>), JsError> {
+        let (target, handler) = proxy_parts(proxy)
+            .ok_or_else(|| type_err(format!("'{}' called on non-proxy", trap.name())))?;
+        let f = trap_fn(&handler, trap).map_err(JsError::Runtime)?;
+        Ok((target, f.map(|f| (handler, f))))
+    }
+
+    // ─── [[Get]] / [[Set]] ────────────────────────────────────────────────
+
+    pub(super) fn proxy_get(&mut self, proxy: &JsValue, key: impl Into<PropertyKey>, receiver: JsValue) -> EvalResult {
+        let key: PropertyKey = key.into();
+        let (target, trap) = self.proxy_trap(proxy, ProxyTrap::Get)?;
+        let Some((handler, f)) = trap else {
+            return self.get_with_receiver(&target, key, receiver);
+        };
+        let v = self.call_function(f, vec![target.clone(), key.to_value(), receiver], Some(handler))?;
+        // Invariant: non-configurable non-writable data property musi vratit svou hodnotu
+        if let JsValue::Object(t) = &target
+            && let Some(slot) = t.borrow().props.slot(&key)
+            && !slot.configurable && !slot.writable && !slot.is_accessor() && !slot.value.strict_eq(&v)
+        {
+            return Err(type_err(format!("'get' on proxy: property '{key}' is a read-only and non-configurable data property on the proxy target but the proxy did not return its actual value")));
+        }
+        Ok(v)
+    }
+
+    /// Forward [[Get]] na target - gettery se volaji s `this` = receiver.
+    pub(super) fn get_with_receiver(&mut self, target: &JsValue, key: impl Into<PropertyKey>, receiver: JsValue) -> EvalResult {
+        let key: PropertyKey = key.into();
+        if is_proxy(target) {
+            return self.proxy_get(target, key, receiver);
+        }
+        if let JsValue::Object(o) = target {
+            let accessor = o.borrow().find_accessor(&key);
+            if let Some((getter, _)) = accessor {
+                return match getter {
+                    Some(g) => self.call_function(g, vec![], Some(receiver)),
+                    None => Ok(JsValue::Undefined),
+                };
+            }
+        }
+        self.get_prop(target, key)
+    }
+
+    /// Vraci `false` kdyz trap / target zapis odmitl (strict mode by hodil TypeError).
+    pub(super) fn proxy_set(&mut self, proxy: &JsValue, key: impl Into<PropertyKey>, val: JsValue, receiver: JsValue) -> Result<bool, JsError> {
+        let key: PropertyKey = key.into();
+        let (target, trap) = self.proxy_trap(proxy, ProxyTrap::Set)?;
+        let Some((handler, f)) = trap else {
+            return self.set_with_receiver(&target, key, val, receiver);
+        };
+        let ok = self.call_function(f, vec![target, key.to_value(), val, receiver], Some(handler))?;
+        Ok(ok.is_truthy())
+    }
+
+    pub(super) fn set_with_receiver(&mut self, target: &JsValue, key: impl Into<PropertyKey>, val: JsValue, receiver: JsValue) -> Result<bool, JsError> {
+        let key: PropertyKey = key.into();
+        if is_proxy(target) {
+            return self.proxy_set(target, key, val, receiver);
+        }
+        match target {
+            JsValue::Object(o) => {
+                let accessor = o.borrow().find_accessor(&key);
+                if let Some((_, setter)) = accessor {
+                    let Some(setter) = setter else { return Ok(false) };
+                    self.call_function(setter, vec![val], Some(receiver))?;
+                    return Ok(true);
+                }
+                if !o.borrow().can_put(&key) { return Ok(false); }
+                o.borrow_mut().set(key, val);
+                Ok(true)
+            }
+            JsValue::Array(a) => {
+                if tagged_template::is_frozen_array(a) { return Ok(false); }
+                if key == "length" {
+                    let n = val.to_number();
+                    if n < 0.0 || n.fract() != 0.0 { return Err(JsError::Runtime("RangeError: Invalid array length".into())); }
+                    a.borrow_mut().resize(n as usize, JsValue::Undefined);
+                    return Ok(true);
+                }
+                match key.as_str().and_then(property_map::array_index) {
+                    Some(i) => {
+                        let mut arr = a.borrow_mut();
+                        let i = i as usize;
+                        if arr.len() <= i { arr.resize(i + 1, JsValue::Undefined); }
+                        arr[i] = val;
+                        Ok(true)
+                    }
+                    None => Ok(false),
+                }
+            }
+            _ => Ok(false),
+        }
+    }
+
+    // ─── [[HasProperty]] / [[Delete]] ─────────────────────────────────────
+
+    pub(super) fn proxy_has(&mut self, proxy: &JsValue, key: impl Into<PropertyKey>) -> Result<bool, JsError> {
+        let key: PropertyKey = key.into();
+        let (target, trap) = self.proxy_trap(proxy, ProxyTrap::Has)?;
+        match trap {
+            Some((handler, f)) => Ok(self.call_function(f, vec![target, key.to_value()], Some(handler))?.is_truthy()),
+            None => self.has_property(&target, key),
+        }
+    }
+
+    pub(super) fn has_property(&mut self, target: &JsValue, key: impl Into<PropertyKey>) -> Result<bool, JsError> {
+        let key: PropertyKey = key.into();
+        match target {
+            v if is_proxy(v) => self.proxy_has(v, key),
+            JsValue::Object(o) => {
+                let mut cur = Some(Rc::clone(o));
+                while let Some(obj) = cur {
+                    if obj.borrow().props.contains_key(&key) { return Ok(true); }
+                    let proto = obj.borrow().proto.clone();
+                    if let Some(p) = &proto && is_proxy(&JsValue::Object(Rc::clone(p))) {
+                        return self.proxy_has(&JsValue::Object(Rc::clone(p)), key);
+                    }
+                    cur = proto;
+                }
+                Ok(false)
+            }
+            JsValue::Array(a) => Ok(key == "length"
+                || key.as_str().and_then(property_map::array_index).is_some_and(|i| (i as usize) < a.borrow().len())),
+            _ => Ok(false),
+        }
+    }
+
+    pub(super) fn proxy_delete(&mut self, proxy: &JsValue, key: impl Into<PropertyKey>) -> Result<bool, JsError> {
+        let key: PropertyKey = key.into();
+        let (target, trap) = self.proxy_trap(proxy, ProxyTrap::DeleteProperty)?;
+        if let Some((handler, f)) = trap {
+            return Ok(self.call_function(f, vec![target, key.to_value()], Some(handler))?.is_truthy());
+        }
+        match &target {
+            v if is_proxy(v) => self.proxy_delete(v, key),
+            JsValue::Object(o) => Ok(o.borrow_mut().delete(key)),
+            JsValue::Array(a) => {
+                if let Some(i) = key.as_str().and_then(property_map::array_index)
+                    && let Some(slot) = a.borrow_mut().get_mut(i as usize)
+                {
+                    *slot = JsValue::Undefined;
+                }
+                Ok(key != "length")
+            }
+            _ => Ok(true),
+        }
+    }
+
+    // ─── [[OwnPropertyKeys]] / [[GetOwnProperty]] / [[DefineOwnProperty]] ──
+
+    /// Klice jako JS hodnoty (string nebo symbol), jak je vraci `Reflect.ownKeys`.
+    pub(super) fn proxy_own_keys(&mut self, proxy: &JsValue) -> Result<Vec<JsValue>, JsError> {
+        let (target, trap) = self.proxy_trap(proxy, ProxyTrap::OwnKeys)?;
+        let Some((handler, f)) = trap else {
+            return self.own_keys_of(&target);
+        };
+        let list = self.call_function(f, vec![target], Some(handler))?;
+        let JsValue::Array(items) = list else {
+            return Err(type_err("CreateListFromArrayLike called on non-object".into()));
+        };
+        let items = items.borrow().clone();
+        if let Some(bad) = items.iter().find(|k| !matches!(k, JsValue::Str(_) | JsValue::Symbol(_))) {
+            return Err(type_err(format!("{bad} is not a valid property name")));
+        }
+        Ok(items)
+    }
+
+    pub(super) fn own_keys_of(&mut self, target: &JsValue) -> Result<Vec<JsValue>, JsError> {
+        Ok(match target {
+            v if is_proxy(v) => return self.proxy_own_keys(v),
+            JsValue::Object(o) => {
+                let o = o.borrow();
+                o.ordered_keys(false).into_iter().map(JsValue::Str).chain(o.own_symbols()).collect()
+            }
+            JsValue::Array(a) => (0..a.borrow().len())
+                .map(|i| JsValue::Str(i.to_string()))
+                .chain(std::iter::once(JsValue::Str("length".into())))
+                .collect(),
+            _ => vec![],
+        })
+    }
 
-    pub fn create(&mut self, target_id: u64, handler_id: u64, traps: Vec<ProxyTrap>) -> u64 {
-        self.next_id += 1;
-        let id = self.next_id;
-        self.proxies.insert(id, ProxyDef {
-            target_id, handler_id, revoked: false,
-            registered_traps: traps,
-        });
-        id
+    /// Deskriptor jako JS objekt, nebo `Undefined` kdyz vlastnost neexistuje.
+    pub(super) fn proxy_get_own_property(&mut self, proxy: &JsValue, key: impl Into<PropertyKey>) -> EvalResult {
+        let key: PropertyKey = key.into();
+        let (target, trap) = self.proxy_trap(proxy, ProxyTrap::GetOwnPropertyDescriptor)?;
+        let Some((handler, f)) = trap else {
+            return self.own_descriptor_of(&target, key);
+        };
+        let d = self.call_function(f, vec![target, key.to_value()], Some(handler))?;
+        match d {
+            JsValue::Object(_) | JsValue::Undefined => Ok(d),
+            other => Err(type_err(format!("'getOwnPropertyDescriptor' on proxy: trap returned neither object nor undefined for property '{key}': {other}"))),
+        }
     }
 
-    /// Proxy.revocable() returns a revoker function.
-    pub fn revoke(&mut self, proxy_id: u64) -> bool {
-        if let Some(p) = self.proxies.get_mut(&proxy_id) {
-            p.revoked = true;
-            true
-        } else { false }
+    pub(super) fn own_descriptor_of(&mut self, target: &JsValue, key: impl Into<PropertyKey>) -> EvalResult {
+        let key: PropertyKey = key.into();
+        match target {
+            v if is_proxy(v) => self.proxy_get_own_property(v, key),
+            JsValue::Object(o) => Ok(o.borrow().props.slot(&key)
+                .filter(|_| !key.is_internal())
+                .map(descriptor_to_js)
+                .unwrap_or(JsValue::Undefined)),
+            JsValue::Array(a) => {
+                let slot = if key == "length" {
+                    let mut s = property_map::PropertySlot::data(JsValue::Number(a.borrow().len() as f64));
+                    s.enumerable = false;
+                    s.configurable = false;
+                    s
+                } else {
+                    match key.as_str().and_then(property_map::array_index).and_then(|i| a.borrow().get(i as usize).cloned()) {
+                        Some(v) => property_map::PropertySlot::data(v),
+                        None => return Ok(JsValue::Undefined),
+                    }
+                };
+                Ok(descriptor_to_js(&slot))
+            }
+            _ => Ok(JsValue::Undefined),
+        }
     }
 
-    pub fn is_revoked(&self, proxy_id: u64) -> bool {
-        self.proxies.get(&proxy_id).map(|p| p.revoked).unwrap_or(false)
+    pub(super) fn proxy_define_property(&mut self, proxy: &JsValue, key: impl Into<PropertyKey>, desc: JsValue) -> Result<bool, JsError> {
+        let key: PropertyKey = key.into();
+        let (target, trap) = self.proxy_trap(proxy, ProxyTrap::DefineProperty)?;
+        if let Some((handler, f)) = trap {
+            return Ok(self.call_function(f, vec![target, key.to_value(), desc], Some(handler))?.is_truthy());
+        }
+        match &target {
+            v if is_proxy(v) => self.proxy_define_property(v, key, desc),
+            JsValue::Object(o) => {
+                let d = PropertyDescriptor::from_js(&desc).map_err(JsError::Runtime)?;
+                Ok(o.borrow_mut().define_own_property(key, d).is_ok())
+            }
+            JsValue::Array(_) => {
+                let d = PropertyDescriptor::from_js(&desc).map_err(JsError::Runtime)?;
+                let receiver = target.clone();
+                self.set_with_receiver(&target, key.to_string(), d.value.unwrap_or(JsValue::Undefined), receiver)
+            }
+            _ => Ok(false),
+        }
     }
 
-    pub fn has_trap(&self, proxy_id: u64, trap: ProxyTrap) -> bool {
-        self.proxies.get(&proxy_id).map(|p| p.registered_traps.contains(&trap)).unwrap_or(false)
+    // ─── Prototyp / rozsiritelnost ─────────────────────────────────────────
+
+    pub(super) fn proxy_get_prototype_of(&mut self, proxy: &JsValue) -> EvalResult {
+        let (target, trap) = self.proxy_trap(proxy, ProxyTrap::GetPrototypeOf)?;
+        if let Some((handler, f)) = trap {
+            let p = self.call_function(f, vec![target], Some(handler))?;
+            return match p {
+                JsValue::Object(_) | JsValue::Null => Ok(p),
+                _ => Err(type_err("'getPrototypeOf' on proxy: trap returned neither object nor null".into())),
+            };
+        }
+        match &target {
+            v if is_proxy(v) => self.proxy_get_prototype_of(v),
+            JsValue::Object(o) => Ok(o.borrow().proto.clone().map(JsValue::Object).unwrap_or(JsValue::Null)),
+            _ => Ok(JsValue::Null),
+        }
+    }
+
+    pub(super) fn proxy_set_prototype_of(&mut self, proxy: &JsValue, proto: JsValue) -> Result<bool, JsError> {
+        let (target, trap) = self.proxy_trap(proxy, ProxyTrap::SetPrototypeOf)?;
+        if let Some((handler, f)) = trap {
+            return Ok(self.call_function(f, vec![target, proto], Some(handler))?.is_truthy());
+        }
+        match (&target, proto) {
+            (v, p) if is_proxy(v) => self.proxy_set_prototype_of(v, p),
+            (JsValue::Object(o), JsValue::Object(p)) => { o.borrow_mut().proto = Some(p); Ok(true) }
+            (JsValue::Object(o), JsValue::Null) => { o.borrow_mut().proto = None; Ok(true) }
+            _ => Ok(false),
+        }
+    }
+
+    pub(super) fn proxy_is_extensible(&mut self, proxy: &JsValue) -> Result<bool, JsError> {
+        let (target, trap) = self.proxy_trap(proxy, ProxyTrap::IsExtensible)?;
+        if let Some((handler, f)) = trap {
+            return Ok(self.call_function(f, vec![target], Some(handler))?.is_truthy());
+        }
+        match &target {
+            v if is_proxy(v) => self.proxy_is_extensible(v),
+            JsValue::Object(o) => Ok(o.borrow().extensible),
+            JsValue::Array(a) => Ok(!tagged_template::is_frozen_array(a)),
+            _ => Ok(true),
+        }
+    }
+
+    pub(super) fn proxy_prevent_extensions(&mut self, proxy: &JsValue) -> Result<bool, JsError> {
+        let (target, trap) = self.proxy_trap(proxy, ProxyTrap::PreventExtensions)?;
+        if let Some((handler, f)) = trap {
+            return Ok(self.call_function(f, vec![target], Some(handler))?.is_truthy());
+        }
+        match &target {
+            v if is_proxy(v) => self.proxy_prevent_extensions(v),
+            JsValue::Object(o) => { o.borrow_mut().prevent_extensions(); Ok(true) }
+            _ => Ok(false),
+        }
+    }
+
+    // ─── [[Call]] / [[Construct]] ──────────────────────────────────────────
+
+    pub(super) fn proxy_apply(&mut self, proxy: &JsValue, this: Option<JsValue>, args: Vec<JsValue>) -> EvalResult {
+        let (target, trap) = self.proxy_trap(proxy, ProxyTrap::Apply)?;
+        if !is_callable(&target) {
+            return Err(type_err("proxy is not a function".into()));
+        }
+        match trap {
+            Some((handler, f)) => self.call_function(
+                f, vec![target, this.unwrap_or(JsValue::Undefined), array_value(args)], Some(handler)),
+            None => self.call_function(target, args, this),
+        }
+    }
+
+    pub(super) fn proxy_construct(&mut self, proxy: &JsValue, args: Vec<JsValue>) -> EvalResult {
+        let (target, trap) = self.proxy_trap(proxy, ProxyTrap::Construct)?;
+        if !is_callable(&target) {
+            return Err(type_err("proxy is not a constructor".into()));
+        }
+        let Some((handler, f)) = trap else {
+            return self.call_new(target, args);
+        };
+        let obj = self.call_function(f, vec![target, array_value(args), proxy.clone()], Some(handler))?;
+        match obj {
+            JsValue::Object(_) | JsValue::Array(_) | JsValue::Function(_) => Ok(obj),
+            _ => Err(type_err("proxy [[Construct]] must return an object".into())),
+        }
+    }
+
+    // ─── Odvozene operace ──────────────────────────────────────────────────
+
+    /// Enumerable vlastni string klice (Object.keys, for-in, spread): ownKeys + deskriptory.
+    pub(super) fn proxy_enumerable_keys(&mut self, proxy: &JsValue) -> Result<Vec<String>, JsError> {
+        let mut out = Vec::new();
+        for k in self.proxy_own_keys(proxy)? {
+            let JsValue::Str(k) = k else { continue };
+            if let JsValue::Object(d) = self.proxy_get_own_property(proxy, &k)?
+                && d.borrow().get("enumerable").is_truthy()
+            {
+                out.push(k);
+            }
+        }
+        Ok(out)
+    }
+
+    /// Plain kopie proxy (rekurzivne) pro nativni funkce bez pristupu k interpretu
+    /// (`JSON.stringify`). Pole zustane polem.
+    fn proxy_snapshot(&mut self, v: &JsValue, depth: usize) -> EvalResult {
+        if depth > 64 {
+            return Err(type_err("Converting circular structure to JSON".into()));
+        }
+        if !is_proxy(v) {
+            return Ok(v.clone());
+        }
+        if matches!(innermost_target(v), JsValue::Array(_)) {
+            let len = self.proxy_get(v, "length", v.clone())?.to_number() as usize;
+            let mut items = Vec::with_capacity(len);
+            for i in 0..len {
+                let item = self.proxy_get(v, &i.to_string(), v.clone())?;
+                items.push(self.proxy_snapshot(&item, depth + 1)?);
+            }
+            return Ok(array_value(items));
+        }
+        let mut obj = JsObject::new();
+        for k in self.proxy_enumerable_keys(v)? {
+            let item = self.proxy_get(v, &k, v.clone())?;
+            let item = self.proxy_snapshot(&item, depth + 1)?;
+            obj.set(k.into(), item);
+        }
+        Ok(JsValue::Object(Rc::new(RefCell::new(obj))))
+    }
+
+    /// Object.* / Reflect.* nativni funkce, ktere potrebuji interpret: Reflect.apply /
+    /// Reflect.construct vzdy, ostatni jen kdyz je prvni argument proxy. `Ok(None)` =
+    /// obycejne volani nativni funkce (`JSON.stringify` dostane misto proxy plain kopii).
+    pub(super) fn call_reflective(&mut self, name: &str, args: &mut [JsValue]) -> Result<Option<JsValue>, JsError> {
+        if !is_reflective(name) {
+            return Ok(None);
+        }
+        let arg = |i: usize| args.get(i).cloned().unwrap_or(JsValue::Undefined);
+        let list = |v: JsValue| match v {
+            JsValue::Array(a) => Ok(a.borrow().clone()),
+            _ => Err(type_err("CreateListFromArrayLike called on non-object".into())),
+        };
+        match name {
+            "Reflect.apply" => return self.call_function(arg(0), list(arg(2))?, Some(arg(1))).map(Some),
+            "Reflect.construct" => return self.call_new(arg(0), list(arg(1))?).map(Some),
+            _ => {}
+        }
+        let Some(p) = args.first().filter(|v| is_proxy(v)).cloned() else { return Ok(None) };
+        let key = symbols::to_property_key(&arg(1));
+        Ok(Some(match name {
+            "Object.keys" => array_value(self.proxy_enumerable_keys(&p)?.into_iter().map(JsValue::Str).collect()),
+            "Object.values" | "Object.entries" => {
+                let mut out = Vec::new();
+                for k in self.proxy_enumerable_keys(&p)? {
+                    let v = self.proxy_get(&p, &k, p.clone())?;
+                    out.push(if name == "Object.values" { v } else { array_value(vec![JsValue::Str(k), v]) });
+                }
+                array_value(out)
+            }
+            "Object.getOwnPropertyNames" => array_value(self.proxy_own_keys(&p)?.into_iter()
+                .filter(|k| matches!(k, JsValue::Str(_))).collect()),
+            "Object.getOwnPropertySymbols" => array_value(self.proxy_own_keys(&p)?.into_iter()
+                .filter(|k| matches!(k, JsValue::Symbol(_))).collect()),
+            "Reflect.ownKeys" => array_value(self.proxy_own_keys(&p)?),
+            "Object.getOwnPropertyDescriptor" | "Reflect.getOwnPropertyDescriptor" => self.proxy_get_own_property(&p, &key)?,
+            "Object.getOwnPropertyDescriptors" => {
+                let mut out = JsObject::new();
+                for k in self.proxy_own_keys(&p)? {
+                    let k = symbols::to_property_key(&k);
+                    let d = self.proxy_get_own_property(&p, &k)?;
+                    if !matches!(d, JsValue::Undefined) { out.set(k, d); }
+                }
+                JsValue::Object(Rc::new(RefCell::new(out)))
+            }
+            "Object.hasOwn" => JsValue::Bool(!matches!(self.proxy_get_own_property(&p, &key)?, JsValue::Undefined)),
+            "Object.defineProperty" => {
+                if !self.proxy_define_property(&p, &key, arg(2))? {
+                    return Err(type_err(format!("'defineProperty' on proxy: trap returned falsish for property '{key}'")));
+                }
+                p
+            }
+            "Reflect.defineProperty" => JsValue::Bool(self.proxy_define_property(&p, &key, arg(2))?),
+            "Object.getPrototypeOf" | "Reflect.getPrototypeOf" => self.proxy_get_prototype_of(&p)?,
+            "Object.setPrototypeOf" => {
+                if !self.proxy_set_prototype_of(&p, arg(1))? {
+                    return Err(type_err("'setPrototypeOf' on proxy: trap returned falsish".into()));
+                }
+                p
+            }
+            "Reflect.setPrototypeOf" => JsValue::Bool(self.proxy_set_prototype_of(&p, arg(1))?),
+            "Object.isExtensible" | "Reflect.isExtensible" => JsValue::Bool(self.proxy_is_extensible(&p)?),
+            "Object.preventExtensions" => {
+                if !self.proxy_prevent_extensions(&p)? {
+                    return Err(type_err("'preventExtensions' on proxy: trap returned falsish".into()));
+                }
+                p
+            }
+            "Reflect.preventExtensions" => JsValue::Bool(self.proxy_prevent_extensions(&p)?),
+            "Reflect.get" => {
+                let receiver = args.get(2).cloned().unwrap_or_else(|| p.clone());
+                self.proxy_get(&p, &key, receiver)?
+            }
+            "Reflect.set" => {
+                let receiver = args.get(3).cloned().unwrap_or_else(|| p.clone());
+                JsValue::Bool(self.proxy_set(&p, key, arg(2), receiver)?)
+            }
+            "Reflect.has" => JsValue::Bool(self.proxy_has(&p, &key)?),
+            "Reflect.deleteProperty" => JsValue::Bool(self.proxy_delete(&p, &key)?),
+            "JSON.stringify" => {
+                args[0] = self.proxy_snapshot(&p, 0)?;
+                return Ok(None);
+            }
+            _ => return Ok(None),
+        }))
+    }
+}
+
+impl Interpreter {
+    /// Vestavene metody, ktere interpret resi podle typu `this` misto vlastnosti
+    /// (Object.prototype.*, metody poli): nad proxy bez nalezene vlastnosti.
+    /// Metody pole bezi primo nad targetem (bez set traps pro jednotlive indexy).
+    pub(super) fn call_proxy_builtin_method(&mut self, proxy: &JsValue, key: &str, args: Vec<JsValue>) -> EvalResult {
+        let arg_key = || symbols::to_property_key(args.first().unwrap_or(&JsValue::Undefined));
+        match key {
+            "hasOwnProperty" => return Ok(JsValue::Bool(
+                !matches!(self.proxy_get_own_property(proxy, &arg_key())?, JsValue::Undefined))),
+            "propertyIsEnumerable" => {
+                let d = self.proxy_get_own_property(proxy, &arg_key())?;
+                return Ok(JsValue::Bool(matches!(&d, JsValue::Object(o) if o.borrow().get("enumerable").is_truthy())));
+            }
+            "valueOf" => return Ok(proxy.clone()),
+            "toString" | "toLocaleString" => {
+                let tag = if proxy.type_of() == "function" { "Function" } else { "Object" };
+                return Ok(JsValue::Str(format!("[object {tag}]")));
+            }
+            _ => {}
+        }
+        if let JsValue::Array(arr) = innermost_target(proxy)
+            && let Some(v) = self.call_array_method(arr, key, args)?
+        {
+            return Ok(v);
+        }
+        Err(type_err(format!("proxy.{key} is not a function")))
     }
 }
 
@@ -118,24 +683,7 @@ mod tests {
     }
 
     #[test]
-    fn create_and_lookup() {
-        let mut r = ProxyRegistry::new();
-        let id = r.create(1, 2, vec![ProxyTrap::Get]);
-        assert!(r.has_trap(id, ProxyTrap::Get));
-        assert!(!r.has_trap(id, ProxyTrap::Set));
-    }
-
-    #[test]
-    fn revoke_marks_inaccessible() {
-        let mut r = ProxyRegistry::new();
-        let id = r.create(1, 2, vec![]);
-        assert!(r.revoke(id));
-        assert!(r.is_revoked(id));
-    }
-
-    #[test]
-    fn revoke_unknown_returns_false() {
-        let mut r = ProxyRegistry::new();
-        assert!(!r.revoke(999));
+    fn revoked_handler_rejects_traps() {
+        assert!(trap_fn(&JsValue::Null, ProxyTrap::Get).is_err());
     }
 }
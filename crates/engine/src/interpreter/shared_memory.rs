@@ -0,0 +1,337 @@
+//! Pamet SharedArrayBufferu sdilena mezi vlakny (page + Workery).
+//!
+//! Kazdy Worker ma vlastni `Interpreter` (Rc, ne Send) - sdili se jen
+//! `Arc<SharedMemory>` poslany v `WorkerMessage`. Bezne cteni/zapisy typed
+//! array jdou po bajtech (`Relaxed`), `Atomics.*` pouzivaji nativni atomicke
+//! instrukce velikosti prvku (lock-free, `SeqCst`). `Atomics.wait` /
+//! `notify` resi fronta cekajicich pod mutexem + condvar - zamek drzi jen
+//! waiter list, nikdy data.
+
+use std::ops::Range;
+use std::sync::atomic::{AtomicU16, AtomicU32, AtomicU64, AtomicU8, AtomicUsize, Ordering};
+use std::sync::{Condvar, Mutex, MutexGuard, PoisonError};
+use std::time::{Duration, Instant};
+
+/// Vysledek `Atomics.wait` (retezec vraceny do JS).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WaitOutcome {
+    Ok,
+    NotEqual,
+    TimedOut,
+}
+
+impl WaitOutcome {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Ok => "ok",
+            Self::NotEqual => "not-equal",
+            Self::TimedOut => "timed-out",
+        }
+    }
+}
+
+/// Cekajici agenti: FIFO (id, bajtovy offset) + probuzena id.
+#[derive(Default)]
+struct WaiterList {
+    next_id: u64,
+    queue: Vec<(u64, usize)>,
+    woken: Vec<u64>,
+}
+
+impl WaiterList {
+    fn enqueue(&mut self, offset: usize) -> u64 {
+        self.next_id += 1;
+        self.queue.push((self.next_id, offset));
+        self.next_id
+    }
+
+    fn take_woken(&mut self, id: u64) -> bool {
+        let pos = self.woken.iter().position(|w| *w == id);
+        pos.map(|p| self.woken.swap_remove(p)).is_some()
+    }
+
+    fn cancel(&mut self, id: u64) {
+        self.queue.retain(|(w, _)| *w != id);
+    }
+}
+
+/// Pripoji atomic velikosti prvku na zarovnany offset a zpristupni ho v `$body`
+/// jako `$a` (typ hodnoty `$t`). Hodnota v pameti je little-endian.
+macro_rules! with_atomic {
+    ($mem:expr, $offset:expr, $size:expr, |$a:ident, $t:ident| $body:expr) => {{
+        let p = $mem.ptr($offset, $size);
+        // SAFETY: `ptr` overil rozsah i zarovnani; pamet je alokovana jako
+        // AtomicU64 (vnitrni mutabilita), takze pristup pres sdilenou
+        // referenci z vice vlaken je v poradku.
+        // `as u64` je pro u64 vetev no-op, pro uzsi typy rozsireni.
+        #[allow(clippy::unnecessary_cast)]
+        let r = unsafe {
+            match $size {
+                1 => { type $t = u8; let $a = AtomicU8::from_ptr(p); $body }
+                2 => { type $t = u16; let $a = AtomicU16::from_ptr(p.cast()); $body }
+                4 => { type $t = u32; let $a = AtomicU32::from_ptr(p.cast()); $body }
+                _ => { type $t = u64; let $a = AtomicU64::from_ptr(p.cast()); $body }
+            }
+        };
+        r
+    }};
+}
+
+pub struct SharedMemory {
+    /// Zalozni pamet - AtomicU64 kvuli zarovnani na 8 bajtu.
+    words: Box<[AtomicU64]>,
+    /// Aktualni byteLength (growable SAB roste az do `capacity`).
+    len: AtomicUsize,
+    capacity: usize,
+    growable: bool,
+    waiters: Mutex<WaiterList>,
+    wake: Condvar,
+}
+
+impl std::fmt::Debug for SharedMemory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "SharedMemory({} bytes)", self.len())
+    }
+}
+
+impl SharedMemory {
+    /// `new SharedArrayBuffer(len)`, s `max` = `{ maxByteLength }` (growable).
+    pub fn new(len: usize, max: Option<usize>) -> Result<Self, String> {
+        if let Some(m) = max && len > m {
+            return Err("RangeError: byteLength exceeds maxByteLength".into());
+        }
+        let capacity = max.unwrap_or(len);
+        Ok(Self {
+            words: (0..capacity.div_ceil(8)).map(|_| AtomicU64::new(0)).collect(),
+            len: AtomicUsize::new(len),
+            capacity,
+            growable: max.is_some(),
+            waiters: Mutex::new(WaiterList::default()),
+            wake: Condvar::new(),
+        })
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Self {
+        let mem = Self::new(bytes.len(), None).expect("bez maxByteLength nelze selhat");
+        mem.write(0, bytes);
+        mem
+    }
+
+    pub fn len(&self) -> usize {
+        self.len.load(Ordering::SeqCst)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn max_byte_length(&self) -> Option<usize> {
+        self.growable.then_some(self.capacity)
+    }
+
+    /// `sab.grow(n)` - jen zvetseni, do maxByteLength.
+    pub fn grow(&self, new_len: usize) -> Result<(), String> {
+        if !self.growable {
+            return Err("TypeError: SharedArrayBuffer is not growable".into());
+        }
+        if new_len > self.capacity {
+            return Err("RangeError: new byteLength exceeds maxByteLength".into());
+        }
+        self.len.fetch_update(Ordering::SeqCst, Ordering::SeqCst, |cur| (new_len >= cur).then_some(new_len))
+            .map(|_| ())
+            .map_err(|_| "RangeError: SharedArrayBuffer cannot shrink".to_string())
+    }
+
+    /// Adresa prvku; panika pri poruseni rozsahu/zarovnani (volajici overuji).
+    fn ptr(&self, offset: usize, size: usize) -> *mut u8 {
+        assert!(offset + size <= self.capacity && offset.is_multiple_of(size), "misaligned shared access");
+        // AtomicU64 ma vnitrni mutabilitu - *mut z sdilene reference je platny
+        unsafe { self.words.as_ptr().cast::<u8>().cast_mut().add(offset) }
+    }
+
+    fn byte(&self, i: usize) -> &AtomicU8 {
+        // SAFETY: viz `with_atomic!` - bajt lezi v alokaci
+        unsafe { AtomicU8::from_ptr(self.ptr(i, 1)) }
+    }
+
+    /// Cteni bajtu `start..start+out.len()`; mimo rozsah vrati false.
+    pub fn read(&self, start: usize, out: &mut [u8]) -> bool {
+        if start + out.len() > self.len() { return false; }
+        for (i, b) in out.iter_mut().enumerate() {
+            *b = self.byte(start + i).load(Ordering::Relaxed);
+        }
+        true
+    }
+
+    pub fn write(&self, start: usize, data: &[u8]) -> bool {
+        if start + data.len() > self.len() { return false; }
+        for (i, b) in data.iter().enumerate() {
+            self.byte(start + i).store(*b, Ordering::Relaxed);
+        }
+        true
+    }
+
+    pub fn to_vec(&self) -> Vec<u8> {
+        let mut out = vec![0; self.len()];
+        self.read(0, &mut out);
+        out
+    }
+
+    pub fn copy_within(&self, src: Range<usize>, dest: usize) {
+        let mut tmp = vec![0; src.len()];
+        if self.read(src.start, &mut tmp) {
+            self.write(dest, &tmp);
+        }
+    }
+
+    // ─── Atomics ─────────────────────────────────────────────────────────
+
+    pub fn load(&self, offset: usize, size: usize) -> u64 {
+        with_atomic!(self, offset, size, |a, T| T::from_le(a.load(Ordering::SeqCst)) as u64)
+    }
+
+    pub fn store(&self, offset: usize, size: usize, value: u64) {
+        with_atomic!(self, offset, size, |a, T| a.store((value as T).to_le(), Ordering::SeqCst))
+    }
+
+    /// Read-modify-write; vraci puvodni hodnotu.
+    pub fn update(&self, offset: usize, size: usize, f: impl Fn(u64) -> u64) -> u64 {
+        with_atomic!(self, offset, size, |a, T| {
+            let r = a.fetch_update(Ordering::SeqCst, Ordering::SeqCst, |x| Some((f(T::from_le(x) as u64) as T).to_le()));
+            let (Ok(old) | Err(old)) = r;
+            T::from_le(old) as u64
+        })
+    }
+
+    /// `Atomics.compareExchange`; vraci puvodni hodnotu.
+    pub fn compare_exchange(&self, offset: usize, size: usize, expected: u64, replacement: u64) -> u64 {
+        with_atomic!(self, offset, size, |a, T| {
+            let r = a.compare_exchange((expected as T).to_le(), (replacement as T).to_le(), Ordering::SeqCst, Ordering::SeqCst);
+            let (Ok(old) | Err(old)) = r;
+            T::from_le(old) as u64
+        })
+    }
+
+    // ─── wait / notify ───────────────────────────────────────────────────
+
+    fn lock(&self) -> MutexGuard<'_, WaiterList> {
+        self.waiters.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Blokujici `Atomics.wait`. Hodnota se porovnava pod zamkem waiter listu,
+    /// takze notify mezi porovnanim a uspanim se neztrati.
+    pub fn wait(&self, offset: usize, size: usize, expected: u64, timeout: Option<Duration>) -> WaitOutcome {
+        let mut list = self.lock();
+        if self.load(offset, size) != expected { return WaitOutcome::NotEqual; }
+        let id = list.enqueue(offset);
+        let deadline = timeout.map(|t| Instant::now() + t);
+        loop {
+            if list.take_woken(id) { return WaitOutcome::Ok; }
+            match deadline {
+                None => list = self.wake.wait(list).unwrap_or_else(PoisonError::into_inner),
+                Some(d) => {
+                    let now = Instant::now();
+                    if now >= d {
+                        list.cancel(id);
+                        return WaitOutcome::TimedOut;
+                    }
+                    list = self.wake.wait_timeout(list, d - now)
+                        .map(|(g, _)| g)
+                        .unwrap_or_else(|e| e.into_inner().0);
+                }
+            }
+        }
+    }
+
+    /// Neblokujici registrace pro `Atomics.waitAsync`. None = "not-equal".
+    pub fn enqueue_async(&self, offset: usize, size: usize, expected: u64) -> Option<u64> {
+        let mut list = self.lock();
+        (self.load(offset, size) == expected).then(|| list.enqueue(offset))
+    }
+
+    /// Byl waiter `id` probuzen? (pri true se z evidence odebere).
+    pub fn take_woken(&self, id: u64) -> bool {
+        self.lock().take_woken(id)
+    }
+
+    /// Zrusi cekani (timeout waitAsync).
+    pub fn cancel(&self, id: u64) {
+        self.lock().cancel(id);
+    }
+
+    /// `Atomics.notify` - probudi az `count` waiteru na offsetu (FIFO).
+    pub fn notify(&self, offset: usize, count: Option<usize>) -> usize {
+        let mut list = self.lock();
+        let mut woken = Vec::new();
+        list.queue.retain(|&(id, at)| {
+            if at == offset && count.is_none_or(|c| woken.len() < c) {
+                woken.push(id);
+                false
+            } else {
+                true
+            }
+        });
+        let n = woken.len();
+        list.woken.extend(woken);
+        if n > 0 { self.wake.notify_all(); }
+        n
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    #[test]
+    fn atomics_round_trip_little_endian() {
+        let m = SharedMemory::new(8, None).unwrap();
+        m.store(4, 4, 0x0102_0304);
+        let mut b = [0u8; 4];
+        m.read(4, &mut b);
+        assert_eq!(b, [4, 3, 2, 1]);
+        assert_eq!(m.update(4, 4, |x| x + 1), 0x0102_0304);
+        assert_eq!(m.compare_exchange(4, 4, 0x0102_0305, 7), 0x0102_0305);
+        assert_eq!(m.load(4, 4), 7);
+    }
+
+    #[test]
+    fn concurrent_adds_are_not_lost() {
+        let m = Arc::new(SharedMemory::new(8, None).unwrap());
+        let handles: Vec<_> = (0..4).map(|_| {
+            let m = Arc::clone(&m);
+            std::thread::spawn(move || for _ in 0..10_000 { m.update(0, 4, |x| x + 1); })
+        }).collect();
+        for h in handles { h.join().unwrap(); }
+        assert_eq!(m.load(0, 4), 40_000);
+    }
+
+    #[test]
+    fn wait_wakes_on_notify() {
+        let m = Arc::new(SharedMemory::new(4, None).unwrap());
+        let waiter = {
+            let m = Arc::clone(&m);
+            std::thread::spawn(move || m.wait(0, 4, 0, Some(Duration::from_secs(10))))
+        };
+        while m.notify(0, Some(1)) == 0 { std::thread::yield_now(); }
+        assert_eq!(waiter.join().unwrap(), WaitOutcome::Ok);
+    }
+
+    #[test]
+    fn wait_not_equal_and_timeout() {
+        let m = SharedMemory::new(4, None).unwrap();
+        m.store(0, 4, 1);
+        assert_eq!(m.wait(0, 4, 0, None), WaitOutcome::NotEqual);
+        assert_eq!(m.wait(0, 4, 1, Some(Duration::from_millis(5))), WaitOutcome::TimedOut);
+        assert_eq!(m.notify(0, None), 0);
+    }
+
+    #[test]
+    fn growable_only_grows() {
+        let m = SharedMemory::new(4, Some(16)).unwrap();
+        m.grow(12).unwrap();
+        assert_eq!(m.len(), 12);
+        assert!(m.grow(8).is_err());
+        assert!(m.grow(32).is_err());
+    }
+}
@@ -108,6 +108,35 @@ pub fn parse_mappings(input: &str) -> Vec<Vec<Mapping>> {
     all
 }
 
+/// Encode structured mappings back into a mappings string (inverse of `parse_mappings`).
+/// Generated column restarts per line; source/name fields are deltas across the whole map.
+pub fn encode_mappings(lines: &[Vec<Mapping>]) -> String {
+    let mut out = String::new();
+    let mut source_idx: i64 = 0;
+    let mut source_line: i64 = 0;
+    let mut source_col: i64 = 0;
+    let mut name_idx: i64 = 0;
+    for (i, line) in lines.iter().enumerate() {
+        if i > 0 { out.push(';'); }
+        let mut gen_col: i64 = 0;
+        for (j, m) in line.iter().enumerate() {
+            if j > 0 { out.push(','); }
+            out.push_str(&vlq_encode((m.generated_column as i64 - gen_col) as i32));
+            gen_col = m.generated_column as i64;
+            let (Some(si), Some(sl), Some(sc)) = (m.source_index, m.source_line, m.source_column) else { continue };
+            for (prev, v) in [(&mut source_idx, si), (&mut source_line, sl), (&mut source_col, sc)] {
+                out.push_str(&vlq_encode((v as i64 - *prev) as i32));
+                *prev = v as i64;
+            }
+            if let Some(ni) = m.name_index {
+                out.push_str(&vlq_encode((ni as i64 - name_idx) as i32));
+                name_idx = ni as i64;
+            }
+        }
+    }
+    out
+}
+
 fn read_vlqs(bytes: &[u8], idx: &mut usize) -> Vec<i32> {
     let mut out = Vec::new();
     while *idx < bytes.len() {
@@ -136,6 +165,19 @@ impl SourceMap {
         let name = m.name_index.and_then(|i| self.names.get(i as usize).cloned());
         Some((src_file, m.source_line?, m.source_column?, name))
     }
+
+    /// Serialize to the V3 JSON form (`file` / `sourceRoot` only when set).
+    pub fn to_json(&self) -> String {
+        let mut obj = serde_json::json!({
+            "version": self.version,
+            "sources": self.sources,
+            "names": self.names,
+            "mappings": encode_mappings(&self.mappings),
+        });
+        if let Some(file) = &self.file { obj["file"] = file.as_str().into(); }
+        if let Some(root) = &self.source_root { obj["sourceRoot"] = root.as_str().into(); }
+        obj.to_string()
+    }
 }
 
 #[cfg(test)]
@@ -194,4 +236,27 @@ mod tests {
         assert_eq!(pos.1, 0);
         assert_eq!(pos.2, 0);
     }
+
+    #[test]
+    fn encode_mappings_round_trip() {
+        let input = "AAAA,SAAS;;CAAC,IAAIA;AACA";
+        assert_eq!(encode_mappings(&parse_mappings(input)), input);
+    }
+
+    #[test]
+    fn to_json_contains_fields() {
+        let sm = SourceMap {
+            version: 3,
+            sources: vec!["a.js".into()],
+            mappings: parse_mappings("AAAA;AACA"),
+            file: Some("a.min.js".into()),
+            ..Default::default()
+        };
+        let v: serde_json::Value = serde_json::from_str(&sm.to_json()).unwrap();
+        assert_eq!(v["version"], 3);
+        assert_eq!(v["sources"][0], "a.js");
+        assert_eq!(v["mappings"], "AAAA;AACA");
+        assert_eq!(v["file"], "a.min.js");
+        assert!(v.get("sourceRoot").is_none());
+    }
 }
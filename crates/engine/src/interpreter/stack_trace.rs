@@ -59,6 +59,17 @@ impl StackTrace {
         if self.truncated { s.push_str("    ... (truncated)\n"); }
         s
     }
+
+    /// `Error.stack` value: `header` (`Name: message`) followed by the frames,
+    /// without a trailing newline. Truncation is silent, as in V8.
+    pub fn error_stack(&self, header: &str) -> String {
+        let mut s = header.to_string();
+        for f in &self.frames {
+            s.push('\n');
+            s.push_str(&f.format_v8());
+        }
+        s
+    }
 }
 
 /// Async stack traces: keep parent context across promise boundaries.
@@ -0,0 +1,88 @@
+//! Strict mode a temporal dead zone - sdilene tree-walkerem i bytecode VM.
+//!
+//! Strict kod je program nebo funkce s direktivou `"use strict"` v prologu,
+//! telo tridy a ES modul; vnorene funkce rezim dedi. Syntakticka omezeni
+//! (vyhrazena slova, `eval`/`arguments` jako jmena vazeb, `delete x`,
+//! duplicitni parametry) hlida parser, tady jsou behova: `this` obycejneho
+//! volani zustava `undefined`, prirazeni do nedeklarovane promenne je
+//! ReferenceError a zapis, ktery sloppy kod tise zahodi, je TypeError.
+//!
+//! TDZ: vazba `let`/`const`/`class` existuje od zacatku bloku, ale do
+//! provedeni deklarace ji nelze cist ani zapsat.
+
+use super::*;
+
+/// Chyba pristupu k `let`/`const`/`class` vazbe pred jeji inicializaci.
+pub(crate) fn tdz_error(name: &str) -> String {
+    format!("ReferenceError: Cannot access '{name}' before initialization")
+}
+
+/// Chyba prirazeni do `const` vazby (i importu modulu).
+pub(crate) const CONST_ASSIGN: &str = "TypeError: Assignment to constant variable.";
+
+/// Strict prirazeni do promenne, ktera neni nikde deklarovana.
+pub(crate) fn undeclared_error(name: &str) -> String {
+    format!("ReferenceError: {name} is not defined")
+}
+
+/// Kontrola zapisu `obj[key] = v` ve strict kodu: read-only vlastnost,
+/// accessor bez setteru, neroztazitelny objekt a vlastnost primitiva jsou
+/// TypeError. Proxy rozhoduje svym `set` trapem.
+pub(crate) fn check_write(obj: &JsValue, key: impl AsKey + std::fmt::Display) -> Result<(), String> {
+    match obj {
+        JsValue::Object(o) if !proxy_handler::is_proxy(obj) => {
+            let o = o.borrow();
+            if o.binary.is_some() { return Ok(()); }
+            if let Some((_, None)) = o.find_accessor(&key) {
+                return Err(format!("TypeError: Cannot set property {key} of {obj} which has only a getter"));
+            }
+            match o.props.slot(&key) {
+                Some(s) if !s.is_accessor() && !s.writable => {
+                    Err(format!("TypeError: Cannot assign to read only property '{key}' of object"))
+                }
+                None if !o.extensible => {
+                    Err(format!("TypeError: Cannot add property {key}, object is not extensible"))
+                }
+                _ => Ok(()),
+            }
+        }
+        JsValue::Array(a) if tagged_template::is_frozen_array(a) => {
+            Err(format!("TypeError: Cannot assign to read only property '{key}' of object"))
+        }
+        JsValue::Str(_) | JsValue::Number(_) | JsValue::Bool(_) => Err(format!(
+            "TypeError: Cannot create property '{key}' on {} '{obj}'",
+            obj.type_of()
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Chyba strict `delete obj[key]`, ktery sloppy kod jen vrati `false`.
+pub(crate) fn delete_error(key: impl std::fmt::Display) -> String {
+    format!("TypeError: Cannot delete property '{key}' of object")
+}
+
+/// Zalozi TDZ vazby `let`/`const`/`class` deklarovanych primo v bloku `stmts`.
+pub(crate) fn declare_lexical(stmts: &[Stmt], env: &Rc<RefCell<Environment>>) {
+    let mut names = Vec::new();
+    for s in stmts {
+        let mut s = s;
+        loop {
+            match s {
+                Stmt::WithLine { inner, .. } => s = inner,
+                Stmt::Export(ExportKind::Decl(d), _) => s = d,
+                _ => break,
+            }
+        }
+        match s {
+            Stmt::Var { kind: VarKind::Let | VarKind::Const | VarKind::Using | VarKind::AwaitUsing, decls, .. } => {
+                for d in decls { d.pattern.bound_names(&mut names); }
+            }
+            Stmt::Class { name, .. } => names.push(name.clone()),
+            _ => {}
+        }
+    }
+    if names.is_empty() { return; }
+    let mut env = env.borrow_mut();
+    for n in &names { env.declare_uninit(n); }
+}
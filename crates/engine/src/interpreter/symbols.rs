@@ -0,0 +1,138 @@
+//! Symbol - primitivni hodnota s unikatni identitou (`JsValue::Symbol`).
+//!
+//! Symbol-klicovane vlastnosti maji v `PropertyMap` vlastni prostor klicu
+//! (`PropertyKey::Sym`), takze s zadnym stringovym klicem nekoliduji a do
+//! `Object.keys`, `for...in` ani `JSON.stringify` neprosaknou. Klic drzi
+//! `Rc<JsSymbol>`, `Object.getOwnPropertySymbols` ho vraci primo.
+//!
+//! Well-known symboly maji pevna id `1..=WELL_KNOWN.len()` a sdileji je vsechny
+//! realmy v ramci vlakna; interne se na ne odkazuje konstantami `ITERATOR`,
+//! `TO_PRIMITIVE`... (`WellKnown`). `Symbol.for(key)` pouziva globalni registry.
+
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::rc::Rc;
+use super::JsValue;
+use super::property_map::{AsKey, KeyRef, PropertyKey};
+
+/// Hodnota `JsValue::Symbol` - identitu urcuje `id`, popis je jen pro vypis.
+#[derive(Debug)]
+pub struct JsSymbol {
+    pub id: u64,
+    pub description: Option<String>,
+    /// Symbol z `Symbol.for` - klic registry je `description`.
+    pub registered: bool,
+}
+
+impl JsSymbol {
+    /// `Symbol(desc)` - vystup `String(sym)` / `sym.toString()`.
+    pub fn display(&self) -> String {
+        format!("Symbol({})", self.description.as_deref().unwrap_or(""))
+    }
+}
+
+/// Well-known symboly v poradi jejich id (id = index + 1).
+const WELL_KNOWN: [&str; 16] = [
+    "iterator", "asyncIterator", "hasInstance", "toPrimitive", "toStringTag",
+    "isConcatSpreadable", "match", "matchAll", "replace", "search", "species",
+    "split", "unscopables", "dispose", "asyncDispose", "metadata",
+];
+
+/// Well-known symbol jako klic vlastnosti bez alokace (`o.get(symbols::ITERATOR)`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WellKnown(u64);
+
+/// Well-known symboly pouzivane interne (protokoly iterace, konverze...).
+pub const ITERATOR: WellKnown = WellKnown(1);
+pub const ASYNC_ITERATOR: WellKnown = WellKnown(2);
+pub const HAS_INSTANCE: WellKnown = WellKnown(3);
+pub const TO_PRIMITIVE: WellKnown = WellKnown(4);
+pub const TO_STRING_TAG: WellKnown = WellKnown(5);
+pub const DISPOSE: WellKnown = WellKnown(14);
+pub const ASYNC_DISPOSE: WellKnown = WellKnown(15);
+pub const METADATA: WellKnown = WellKnown(16);
+
+impl WellKnown {
+    pub fn symbol(self) -> Rc<JsSymbol> {
+        WELL_KNOWN_SYMS.with(|w| Rc::clone(&w[self.0 as usize - 1]))
+    }
+
+    /// Je `sym` tento well-known symbol?
+    pub fn is(self, sym: &JsSymbol) -> bool {
+        sym.id == self.0
+    }
+}
+
+impl AsKey for WellKnown {
+    fn key_ref(&self) -> KeyRef<'_> {
+        KeyRef::Sym(self.0)
+    }
+}
+
+impl From<WellKnown> for PropertyKey {
+    fn from(w: WellKnown) -> Self {
+        PropertyKey::Sym(w.symbol())
+    }
+}
+
+thread_local! {
+    static NEXT_ID: Cell<u64> = const { Cell::new(WELL_KNOWN.len() as u64 + 1) };
+    /// `Symbol.for` registry: klic -> symbol.
+    static REGISTRY: RefCell<HashMap<String, Rc<JsSymbol>>> = RefCell::new(HashMap::new());
+    static WELL_KNOWN_SYMS: Vec<Rc<JsSymbol>> = WELL_KNOWN.iter().enumerate()
+        .map(|(i, name)| Rc::new(JsSymbol {
+            id: i as u64 + 1,
+            description: Some(format!("Symbol.{name}")),
+            registered: false,
+        }))
+        .collect();
+}
+
+fn next_id() -> u64 {
+    NEXT_ID.with(|n| { let id = n.get(); n.set(id + 1); id })
+}
+
+/// `Symbol(desc)` - novy unikatni symbol.
+pub fn new_symbol(description: Option<String>) -> JsValue {
+    JsValue::Symbol(Rc::new(JsSymbol { id: next_id(), description, registered: false }))
+}
+
+/// `Symbol.iterator`, `Symbol.toPrimitive`, ... podle jmena.
+pub fn well_known(name: &str) -> Option<JsValue> {
+    let idx = WELL_KNOWN.iter().position(|n| *n == name)?;
+    Some(JsValue::Symbol(WellKnown(idx as u64 + 1).symbol()))
+}
+
+/// `Symbol.for(key)` - stejny klic vraci stejny symbol.
+pub fn symbol_for(key: String) -> JsValue {
+    if let Some(sym) = REGISTRY.with(|r| r.borrow().get(&key).cloned()) {
+        return JsValue::Symbol(sym);
+    }
+    let sym = Rc::new(JsSymbol { id: next_id(), description: Some(key.clone()), registered: true });
+    REGISTRY.with(|r| r.borrow_mut().insert(key, Rc::clone(&sym)));
+    JsValue::Symbol(sym)
+}
+
+/// `Symbol.keyFor(sym)` - klic registry, nebo None pro neregistrovany symbol.
+pub fn key_for(sym: &JsSymbol) -> Option<String> {
+    if sym.registered { sym.description.clone() } else { None }
+}
+
+/// ToPropertyKey - symbol zustava symbolem, ostatni hodnoty na string.
+pub fn to_property_key(v: &JsValue) -> PropertyKey {
+    match v {
+        JsValue::Symbol(sym) => PropertyKey::Sym(Rc::clone(sym)),
+        JsValue::Str(s) => PropertyKey::Str(s.clone()),
+        other => PropertyKey::Str(other.to_string()),
+    }
+}
+
+/// Globalni `Symbol` - callable, ale ne konstruktor (`new Symbol()` hazi v call_new).
+pub fn symbol_ctor(args: Vec<JsValue>) -> Result<JsValue, String> {
+    let description = match args.into_iter().next() {
+        None | Some(JsValue::Undefined) => None,
+        Some(JsValue::Symbol(_)) => return Err("TypeError: Cannot convert a Symbol value to a string".into()),
+        Some(v) => Some(v.to_string()),
+    };
+    Ok(new_symbol(description))
+}
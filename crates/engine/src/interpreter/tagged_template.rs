@@ -0,0 +1,105 @@
+//! Tagged templates - strings pole call site (cooked + `raw`) a `String.raw`.
+//!
+//! `` tag`a${x}b` `` vola `tag(strings, x)`, kde `strings` je frozen pole cooked
+//! textu s vlastnosti `raw` (frozen pole zdrojovych textu). Pro jeden call site
+//! (`Expr::TaggedTemplate::site`) vznikne pole jen jednou - lit-html,
+//! styled-components apod. ho pouzivaji jako klic cache sablon. Pole drzi
+//! primo `TemplateSite` v AST, takze se uvolni spolu s funkci / Programem.
+//!
+//! `JsValue::Array` je holy `Vec` bez vlastnich vlastnosti, takze `raw` a
+//! frozen stav drzi thread-local tabulka klicovana adresou pole. Zaznam drzi
+//! `Weak` - adresa se nerecykluje, dokud zaznam existuje, a zaznamy mrtvych
+//! poli se prubezne odklizi.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::{Rc, Weak};
+use crate::ast::TemplateSite;
+use super::JsValue;
+
+type ArrayRc = Rc<RefCell<Vec<JsValue>>>;
+
+/// Frozen pole tagged template.
+struct FrozenArray {
+    array: Weak<RefCell<Vec<JsValue>>>,
+    /// `raw` pole (None = pole je samo raw).
+    raw: Option<ArrayRc>,
+}
+
+thread_local! {
+    /// Adresa frozen pole -> zaznam.
+    static TEMPLATE_ARRAYS: RefCell<HashMap<usize, FrozenArray>> = RefCell::new(HashMap::new());
+}
+
+fn addr(arr: &ArrayRc) -> usize {
+    Rc::as_ptr(arr) as usize
+}
+
+fn str_array(parts: &[String]) -> ArrayRc {
+    Rc::new(RefCell::new(parts.iter().map(|s| JsValue::Str(s.clone())).collect()))
+}
+
+fn lookup<R>(arr: &ArrayRc, f: impl FnOnce(&FrozenArray) -> R) -> Option<R> {
+    TEMPLATE_ARRAYS.with(|t| t.borrow().get(&addr(arr)).map(f))
+}
+
+/// Strings pole pro call site - pri prvnim vyhodnoceni se vytvori, dale se
+/// vraci stale stejna instance (identita `strings === strings` mezi volanimi).
+pub(super) fn template_object(site: &TemplateSite, cooked: &[String], raw: &[String]) -> JsValue {
+    if let Some(arr) = site.cached::<RefCell<Vec<JsValue>>>() {
+        return JsValue::Array(arr);
+    }
+    let strings = str_array(cooked);
+    let raw = str_array(raw);
+    TEMPLATE_ARRAYS.with(|t| {
+        let mut t = t.borrow_mut();
+        // Pole odkazuje jen AST a JS; nove site je vzacne, odklid je levny
+        t.retain(|_, e| e.array.strong_count() > 0);
+        t.insert(addr(&raw), FrozenArray { array: Rc::downgrade(&raw), raw: None });
+        t.insert(addr(&strings), FrozenArray { array: Rc::downgrade(&strings), raw: Some(raw) });
+    });
+    site.store(Rc::clone(&strings));
+    JsValue::Array(strings)
+}
+
+/// `strings.raw` - jen pro strings pole tagged template.
+pub(super) fn template_raw(arr: &ArrayRc) -> Option<JsValue> {
+    lookup(arr, |e| e.raw.clone()).flatten().map(JsValue::Array)
+}
+
+/// Strings i raw pole jsou frozen - zapis se ignoruje, mutujici metody hazi TypeError.
+pub(super) fn is_frozen_array(arr: &ArrayRc) -> bool {
+    lookup(arr, |_| ()).is_some()
+}
+
+/// `String.raw(strings, ...subst)` - slepi `strings.raw` prolozene substitucemi.
+/// Jako tag: `` String.raw`C:\new` `` == `"C:\\new"`.
+pub(super) fn string_raw(args: Vec<JsValue>) -> Result<JsValue, String> {
+    let mut args = args.into_iter();
+    let raw = match args.next() {
+        Some(JsValue::Array(a)) => template_raw(&a).unwrap_or(JsValue::Undefined),
+        Some(JsValue::Object(o)) => o.borrow().get("raw"),
+        _ => JsValue::Undefined,
+    };
+    let parts: Vec<String> = match raw {
+        JsValue::Array(a) => a.borrow().iter().map(|v| v.to_string()).collect(),
+        JsValue::Str(s) => s.chars().map(|c| c.to_string()).collect(),
+        JsValue::Object(o) => {
+            let o = o.borrow();
+            let len = o.get("length").to_number();
+            let len = if len.is_finite() && len > 0.0 { len as usize } else { 0 };
+            (0..len).map(|i| o.get(&i.to_string()).to_string()).collect()
+        }
+        _ => return Err("TypeError: String.raw: ocekavan objekt s vlastnosti raw".into()),
+    };
+    let mut out = String::new();
+    for (i, part) in parts.iter().enumerate() {
+        out.push_str(part);
+        if i + 1 < parts.len()
+            && let Some(sub) = args.next()
+        {
+            out.push_str(&sub.to_string());
+        }
+    }
+    Ok(JsValue::Str(out))
+}
@@ -0,0 +1,1919 @@
+//! Temporal - jadro nezavisle na JS hodnotach.
+//!
+//! ISO 8601 kalendarni aritmetika, ne-ISO kalendare pres `icu_calendar`
+//! (`AnyCalendar`), casova pasma (`time_zone.rs`), zaokrouhlovani a rozdily
+//! s "bublanim" jednotek podle specifikace (DifferenceZonedDateTimeWithRounding
+//! a spol.) a parser/formatter RFC 9557 retezcu. Casy jsou v nanosekundach
+//! jako `i128` - epocha i delky trvani se vejdou bez ztraty presnosti.
+//! JS vrstva je v `builtins_temporal.rs`.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+use icu::calendar::{AnyCalendar, AnyCalendarKind, Date, Iso};
+use super::time_zone::Zone;
+
+pub const NS_PER_SECOND: i128 = 1_000_000_000;
+pub const NS_PER_DAY: i128 = 86_400 * NS_PER_SECOND;
+/// Hranice Instant: +-10^8 dni od epochy.
+pub const MAX_EPOCH_NS: i128 = 100_000_000 * NS_PER_DAY;
+
+type TResult<T> = Result<T, String>;
+
+fn range_error(msg: impl std::fmt::Display) -> String {
+    format!("RangeError: {msg}")
+}
+
+// ─── Jednotky a zaokrouhlovani ───────────────────────────────────────────────
+
+/// Jednotky trvani, serazene vzestupne (`max` = nejvetsi).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Unit {
+    Nanosecond,
+    Microsecond,
+    Millisecond,
+    Second,
+    Minute,
+    Hour,
+    Day,
+    Week,
+    Month,
+    Year,
+}
+
+impl Unit {
+    /// Sestupne - poradi poli trvani.
+    pub const ALL: [Unit; 10] = [
+        Unit::Year, Unit::Month, Unit::Week, Unit::Day, Unit::Hour,
+        Unit::Minute, Unit::Second, Unit::Millisecond, Unit::Microsecond, Unit::Nanosecond,
+    ];
+
+    /// Jednotne i mnozne cislo (`"day"` / `"days"`).
+    pub fn parse(s: &str) -> Option<Unit> {
+        let s = s.strip_suffix('s').unwrap_or(s);
+        Unit::ALL.into_iter().find(|u| u.name() == s)
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Unit::Year => "year",
+            Unit::Month => "month",
+            Unit::Week => "week",
+            Unit::Day => "day",
+            Unit::Hour => "hour",
+            Unit::Minute => "minute",
+            Unit::Second => "second",
+            Unit::Millisecond => "millisecond",
+            Unit::Microsecond => "microsecond",
+            Unit::Nanosecond => "nanosecond",
+        }
+    }
+
+    /// Jmeno pole trvani (`"days"`).
+    pub fn plural(self) -> String {
+        format!("{}s", self.name())
+    }
+
+    /// Delka v ns; den = 24 h, kalendarni jednotky nemaji pevnou delku.
+    pub fn nanos(self) -> i128 {
+        match self {
+            Unit::Nanosecond => 1,
+            Unit::Microsecond => 1_000,
+            Unit::Millisecond => 1_000_000,
+            Unit::Second => NS_PER_SECOND,
+            Unit::Minute => 60 * NS_PER_SECOND,
+            Unit::Hour => 3_600 * NS_PER_SECOND,
+            _ => NS_PER_DAY,
+        }
+    }
+
+    pub fn is_calendar(self) -> bool {
+        self >= Unit::Week
+    }
+
+    /// Maximalni `roundingIncrement` (musi delit nadrazenou jednotku).
+    pub fn max_increment(self) -> Option<u32> {
+        match self {
+            Unit::Hour => Some(24),
+            Unit::Minute | Unit::Second => Some(60),
+            Unit::Millisecond | Unit::Microsecond | Unit::Nanosecond => Some(1000),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RoundingMode {
+    Ceil,
+    Floor,
+    Expand,
+    Trunc,
+    HalfCeil,
+    HalfFloor,
+    HalfExpand,
+    HalfTrunc,
+    HalfEven,
+}
+
+impl RoundingMode {
+    pub fn parse(s: &str) -> Option<RoundingMode> {
+        Some(match s {
+            "ceil" => RoundingMode::Ceil,
+            "floor" => RoundingMode::Floor,
+            "expand" => RoundingMode::Expand,
+            "trunc" => RoundingMode::Trunc,
+            "halfCeil" => RoundingMode::HalfCeil,
+            "halfFloor" => RoundingMode::HalfFloor,
+            "halfExpand" => RoundingMode::HalfExpand,
+            "halfTrunc" => RoundingMode::HalfTrunc,
+            "halfEven" => RoundingMode::HalfEven,
+            _ => return None,
+        })
+    }
+
+    /// `since` zaokrouhluje opacne orientovany rozdil.
+    pub fn negate(self) -> RoundingMode {
+        match self {
+            RoundingMode::Ceil => RoundingMode::Floor,
+            RoundingMode::Floor => RoundingMode::Ceil,
+            RoundingMode::HalfCeil => RoundingMode::HalfFloor,
+            RoundingMode::HalfFloor => RoundingMode::HalfCeil,
+            other => other,
+        }
+    }
+
+    /// Zaokrouhli `n` na nasobek `increment` (> 0).
+    pub fn round(self, n: i128, increment: i128) -> i128 {
+        let lo = n.div_euclid(increment) * increment;
+        let r = n - lo;
+        if r == 0 {
+            return n;
+        }
+        let hi = lo + increment;
+        let (toward_zero, away) = if n > 0 { (lo, hi) } else { (hi, lo) };
+        let tie = |mode: RoundingMode| match mode {
+            RoundingMode::HalfCeil => hi,
+            RoundingMode::HalfFloor => lo,
+            RoundingMode::HalfTrunc => toward_zero,
+            RoundingMode::HalfEven => if (lo / increment) % 2 == 0 { lo } else { hi },
+            _ => away,
+        };
+        match self {
+            RoundingMode::Ceil => hi,
+            RoundingMode::Floor => lo,
+            RoundingMode::Trunc => toward_zero,
+            RoundingMode::Expand => away,
+            half => match (2 * r).cmp(&increment) {
+                std::cmp::Ordering::Less => lo,
+                std::cmp::Ordering::Greater => hi,
+                std::cmp::Ordering::Equal => tie(half),
+            },
+        }
+    }
+}
+
+/// Nastaveni `until` / `since` / `round`.
+#[derive(Debug, Clone, Copy)]
+pub struct RoundingSettings {
+    pub largest: Unit,
+    pub smallest: Unit,
+    pub increment: u32,
+    pub mode: RoundingMode,
+}
+
+impl RoundingSettings {
+    pub fn exact(largest: Unit) -> RoundingSettings {
+        RoundingSettings { largest, smallest: Unit::Nanosecond, increment: 1, mode: RoundingMode::Trunc }
+    }
+
+    fn is_noop(&self) -> bool {
+        self.smallest == Unit::Nanosecond && self.increment == 1
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Overflow {
+    Constrain,
+    Reject,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Disambiguation {
+    Compatible,
+    Earlier,
+    Later,
+    Reject,
+}
+
+// ─── ISO datum a cas ─────────────────────────────────────────────────────────
+
+pub fn is_leap_year(y: i32) -> bool {
+    y % 4 == 0 && (y % 100 != 0 || y % 400 == 0)
+}
+
+pub fn iso_days_in_month(y: i32, m: u8) -> u8 {
+    match m {
+        2 if is_leap_year(y) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Hodnota v rozsahu 1..=max: `constrain` orizne, `reject` hlasi RangeError.
+fn fit(value: i64, max: i64, field: &str, overflow: Overflow) -> TResult<i64> {
+    if (1..=max).contains(&value) {
+        return Ok(value);
+    }
+    match overflow {
+        Overflow::Constrain => Ok(value.clamp(1, max)),
+        Overflow::Reject => Err(range_error(format_args!("{field} {value} out of range"))),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct IsoDate {
+    pub year: i32,
+    pub month: u8,
+    pub day: u8,
+}
+
+impl IsoDate {
+    pub fn new(year: i64, month: i64, day: i64, overflow: Overflow) -> TResult<IsoDate> {
+        let year = i32::try_from(year).map_err(|_| range_error("year out of range"))?;
+        let month = fit(month, 12, "month", overflow)? as u8;
+        let day = fit(day, iso_days_in_month(year, month) as i64, "day", overflow)? as u8;
+        Ok(IsoDate { year, month, day })
+    }
+
+    /// Dny od 1970-01-01.
+    pub fn epoch_days(self) -> i64 {
+        let y = self.year as i64 - if self.month <= 2 { 1 } else { 0 };
+        let era = y.div_euclid(400);
+        let yoe = y - era * 400;
+        let mp = (self.month as i64 + 9) % 12;
+        let doy = (153 * mp + 2) / 5 + self.day as i64 - 1;
+        era * 146_097 + yoe * 365 + yoe / 4 - yoe / 100 + doy - 719_468
+    }
+
+    pub fn from_epoch_days(days: i64) -> IsoDate {
+        let z = days + 719_468;
+        let era = z.div_euclid(146_097);
+        let doe = z - era * 146_097;
+        let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let day = (doy - (153 * mp + 2) / 5 + 1) as u8;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 } as u8;
+        let year = (yoe + era * 400 + if month <= 2 { 1 } else { 0 }) as i32;
+        IsoDate { year, month, day }
+    }
+
+    pub fn add_days(self, days: i64) -> IsoDate {
+        IsoDate::from_epoch_days(self.epoch_days() + days)
+    }
+
+    /// 1 = pondeli ... 7 = nedele.
+    pub fn day_of_week(self) -> u8 {
+        ((self.epoch_days() + 3).rem_euclid(7) + 1) as u8
+    }
+
+    pub fn day_of_year(self) -> u16 {
+        (self.epoch_days() - IsoDate { year: self.year, month: 1, day: 1 }.epoch_days() + 1) as u16
+    }
+
+    /// ISO tyden a rok, do ktereho tyden patri.
+    pub fn week_of_year(self) -> (u8, i32) {
+        let week = (self.day_of_year() as i64 - self.day_of_week() as i64 + 10) / 7;
+        let weeks_in = |y: i32| {
+            let jan1 = IsoDate { year: y, month: 1, day: 1 }.day_of_week();
+            if jan1 == 4 || (jan1 == 3 && is_leap_year(y)) { 53 } else { 52 }
+        };
+        if week < 1 {
+            (weeks_in(self.year - 1), self.year - 1)
+        } else if week > weeks_in(self.year) as i64 {
+            (1, self.year + 1)
+        } else {
+            (week as u8, self.year)
+        }
+    }
+
+    /// PlainDate je platne, kdyz jeho poledne lezi v rozsahu datetime.
+    pub fn in_range(self) -> bool {
+        IsoDateTime { date: self, time: IsoTime::NOON }.in_range()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct IsoTime {
+    pub hour: u8,
+    pub minute: u8,
+    pub second: u8,
+    pub millisecond: u16,
+    pub microsecond: u16,
+    pub nanosecond: u16,
+}
+
+impl IsoTime {
+    pub const MIDNIGHT: IsoTime = IsoTime { hour: 0, minute: 0, second: 0, millisecond: 0, microsecond: 0, nanosecond: 0 };
+    pub const NOON: IsoTime = IsoTime { hour: 12, minute: 0, second: 0, millisecond: 0, microsecond: 0, nanosecond: 0 };
+
+    /// `[hour, minute, second, ms, us, ns]` s kontrolou rozsahu.
+    pub fn new(f: [i64; 6], overflow: Overflow) -> TResult<IsoTime> {
+        let limits = [23, 59, 59, 999, 999, 999];
+        let names = ["hour", "minute", "second", "millisecond", "microsecond", "nanosecond"];
+        let mut out = [0i64; 6];
+        for i in 0..6 {
+            out[i] = match overflow {
+                _ if (0..=limits[i]).contains(&f[i]) => f[i],
+                Overflow::Constrain => f[i].clamp(0, limits[i]),
+                Overflow::Reject => return Err(range_error(format_args!("{} {} out of range", names[i], f[i]))),
+            };
+        }
+        Ok(IsoTime {
+            hour: out[0] as u8, minute: out[1] as u8, second: out[2] as u8,
+            millisecond: out[3] as u16, microsecond: out[4] as u16, nanosecond: out[5] as u16,
+        })
+    }
+
+    pub fn fields(self) -> [i64; 6] {
+        [self.hour as i64, self.minute as i64, self.second as i64,
+         self.millisecond as i64, self.microsecond as i64, self.nanosecond as i64]
+    }
+
+    /// Nanosekundy od pulnoci.
+    pub fn to_ns(self) -> i128 {
+        ((self.hour as i128 * 60 + self.minute as i128) * 60 + self.second as i128) * NS_PER_SECOND
+            + self.millisecond as i128 * 1_000_000 + self.microsecond as i128 * 1_000 + self.nanosecond as i128
+    }
+
+    /// Cas z ns od pulnoci; prvni slozka = pocet pretecenych dni.
+    pub fn from_ns(ns: i128) -> (i64, IsoTime) {
+        let days = ns.div_euclid(NS_PER_DAY);
+        let mut rest = ns.rem_euclid(NS_PER_DAY);
+        let mut take = |unit: i128| {
+            let v = rest / unit;
+            rest %= unit;
+            v
+        };
+        let hour = take(3_600 * NS_PER_SECOND) as u8;
+        let minute = take(60 * NS_PER_SECOND) as u8;
+        let second = take(NS_PER_SECOND) as u8;
+        let millisecond = take(1_000_000) as u16;
+        let microsecond = take(1_000) as u16;
+        let nanosecond = rest as u16;
+        (days as i64, IsoTime { hour, minute, second, millisecond, microsecond, nanosecond })
+    }
+
+    /// Zaokrouhleni casu; vraci pretecene dny (0 / 1).
+    pub fn round(self, unit: Unit, increment: u32, mode: RoundingMode) -> (i64, IsoTime) {
+        IsoTime::from_ns(mode.round(self.to_ns(), unit.nanos() * increment as i128))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct IsoDateTime {
+    pub date: IsoDate,
+    pub time: IsoTime,
+}
+
+impl IsoDateTime {
+    /// Lokalni cas jako ns "od epochy" bez posunu pasma.
+    pub fn local_ns(self) -> i128 {
+        self.date.epoch_days() as i128 * NS_PER_DAY + self.time.to_ns()
+    }
+
+    pub fn from_local_ns(ns: i128) -> IsoDateTime {
+        let (days, time) = IsoTime::from_ns(ns);
+        IsoDateTime { date: IsoDate::from_epoch_days(days), time }
+    }
+
+    /// ISODateTimeWithinLimits - o den sirsi nez rozsah Instant.
+    pub fn in_range(self) -> bool {
+        self.local_ns().abs() < MAX_EPOCH_NS + NS_PER_DAY
+    }
+
+    /// Cas + ns (pretecene dny se prictou k datu).
+    pub fn add_ns(self, ns: i128) -> IsoDateTime {
+        IsoDateTime::from_local_ns(self.local_ns() + ns)
+    }
+}
+
+pub fn check_date(d: IsoDate) -> TResult<IsoDate> {
+    if d.in_range() { Ok(d) } else { Err(range_error("date outside of supported range")) }
+}
+
+pub fn check_date_time(dt: IsoDateTime) -> TResult<IsoDateTime> {
+    if dt.in_range() { Ok(dt) } else { Err(range_error("date-time outside of supported range")) }
+}
+
+pub fn check_epoch_ns(ns: i128) -> TResult<i128> {
+    if ns.abs() <= MAX_EPOCH_NS { Ok(ns) } else { Err(range_error("instant outside of supported range")) }
+}
+
+// ─── Trvani ──────────────────────────────────────────────────────────────────
+
+/// Zaznam trvani - pole v poradi `Unit::ALL`. Pole se nebalancuji
+/// (`{ hours: 25 }` zustava 25 hodin); vsechna maji stejne znamenko.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Duration(pub [f64; 10]);
+
+impl Duration {
+    pub const ZERO: Duration = Duration([0.0; 10]);
+
+    pub fn get(&self, unit: Unit) -> f64 {
+        self.0[Self::index(unit)]
+    }
+
+    pub fn set(&mut self, unit: Unit, v: f64) {
+        self.0[Self::index(unit)] = v;
+    }
+
+    fn index(unit: Unit) -> usize {
+        9 - unit as usize
+    }
+
+    pub fn sign(&self) -> i32 {
+        self.0.iter().find(|v| **v != 0.0).map_or(0, |v| if *v > 0.0 { 1 } else { -1 })
+    }
+
+    pub fn negated(&self) -> Duration {
+        Duration(self.0.map(|v| if v == 0.0 { 0.0 } else { -v }))
+    }
+
+    pub fn abs(&self) -> Duration {
+        Duration(self.0.map(f64::abs))
+    }
+
+    /// Nejvetsi nenulova jednotka (nula -> nanosekundy).
+    pub fn largest_unit(&self) -> Unit {
+        Unit::ALL.into_iter().find(|u| self.get(*u) != 0.0).unwrap_or(Unit::Nanosecond)
+    }
+
+    pub fn has_calendar_units(&self) -> bool {
+        self.get(Unit::Year) != 0.0 || self.get(Unit::Month) != 0.0 || self.get(Unit::Week) != 0.0
+    }
+
+    /// IsValidDuration: cela konecna cisla, jednotne znamenko, limity.
+    pub fn validate(self) -> TResult<Duration> {
+        let sign = self.sign();
+        for v in self.0 {
+            if !v.is_finite() || v.fract() != 0.0 {
+                return Err(range_error("duration fields must be finite integers"));
+            }
+            if v != 0.0 && (v > 0.0) != (sign > 0) {
+                return Err(range_error("mixed-sign values not allowed as duration fields"));
+            }
+        }
+        let limit = 4_294_967_296.0;
+        if [Unit::Year, Unit::Month, Unit::Week].iter().any(|u| self.get(*u).abs() >= limit) {
+            return Err(range_error("calendar duration fields out of range"));
+        }
+        if self.day_time_ns().abs() >= (1i128 << 53) * NS_PER_SECOND {
+            return Err(range_error("time duration out of range"));
+        }
+        Ok(self)
+    }
+
+    /// Hodiny az nanosekundy jako ns.
+    pub fn time_ns(&self) -> i128 {
+        [Unit::Hour, Unit::Minute, Unit::Second, Unit::Millisecond, Unit::Microsecond, Unit::Nanosecond]
+            .iter().map(|u| self.get(*u) as i128 * u.nanos()).sum()
+    }
+
+    /// Dny (24 h) + cas jako ns.
+    pub fn day_time_ns(&self) -> i128 {
+        self.get(Unit::Day) as i128 * NS_PER_DAY + self.time_ns()
+    }
+
+    /// Jen datova cast (roky..dny).
+    pub fn date_part(&self) -> Duration {
+        let mut d = Duration::ZERO;
+        for u in [Unit::Year, Unit::Month, Unit::Week, Unit::Day] {
+            d.set(u, self.get(u));
+        }
+        d
+    }
+
+    /// Rozlozi ns do jednotek od `largest` (nejvyse den) dolu.
+    pub fn from_ns(ns: i128, largest: Unit) -> Duration {
+        let mut d = Duration::ZERO;
+        let mut rest = ns;
+        for u in Unit::ALL.into_iter().filter(|u| *u <= largest.min(Unit::Day)) {
+            let n = u.nanos();
+            d.set(u, (rest / n) as f64);
+            rest %= n;
+        }
+        d
+    }
+
+    /// Datova cast `self` + ns rozlozene od hodin dolu.
+    pub fn with_time_ns(&self, ns: i128) -> Duration {
+        let mut d = self.date_part();
+        let time = Duration::from_ns(ns, Unit::Hour);
+        for u in Unit::ALL.into_iter().filter(|u| *u <= Unit::Hour) {
+            d.set(u, time.get(u));
+        }
+        d
+    }
+
+    /// Kopie s vynulovanymi jednotkami mensimi nez `unit`.
+    fn truncated(&self, unit: Unit) -> Duration {
+        let mut d = *self;
+        for u in Unit::ALL.into_iter().filter(|u| *u < unit) {
+            d.set(u, 0.0);
+        }
+        d
+    }
+
+    fn date_fields(&self) -> (i64, i64, i64, i64) {
+        (self.get(Unit::Year) as i64, self.get(Unit::Month) as i64, self.get(Unit::Week) as i64, self.get(Unit::Day) as i64)
+    }
+}
+
+// ─── Kalendare ───────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Calendar {
+    Iso,
+    Icu(AnyCalendarKind),
+}
+
+/// Pole data v kalendari.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CalendarFields {
+    /// Aritmeticky rok (pro era kalendare rok proleptickeho cislovani).
+    pub year: i32,
+    pub month: u8,
+    pub month_code: String,
+    pub day: u8,
+    pub era: Option<String>,
+    pub era_year: Option<i32>,
+    pub days_in_month: u8,
+    pub days_in_year: u16,
+    pub months_in_year: u8,
+    pub in_leap_year: bool,
+}
+
+/// Zadani mesice: poradove cislo a/nebo kod (`"M05L"`).
+#[derive(Debug, Clone, Default)]
+pub struct MonthSpec {
+    pub ordinal: Option<i64>,
+    pub code: Option<String>,
+}
+
+/// Mesic roku v ne-ISO kalendari: kod, prvni den (epoch dny), delka.
+#[derive(Debug, Clone)]
+struct MonthInfo {
+    code: String,
+    start: i64,
+    days: u8,
+}
+
+/// Mesice jednoho roku ne-ISO kalendare (cache podle kalendare a roku).
+type YearTables = HashMap<(AnyCalendarKind, i32), Rc<Vec<MonthInfo>>>;
+
+thread_local! {
+    static ICU_CALENDARS: RefCell<HashMap<AnyCalendarKind, Rc<AnyCalendar>>> = RefCell::new(HashMap::new());
+    static YEAR_TABLES: RefCell<YearTables> = RefCell::new(HashMap::new());
+}
+
+fn icu_calendar(kind: AnyCalendarKind) -> Rc<AnyCalendar> {
+    ICU_CALENDARS.with(|c| Rc::clone(c.borrow_mut().entry(kind).or_insert_with(|| Rc::new(AnyCalendar::new(kind)))))
+}
+
+fn icu_date(kind: AnyCalendarKind, d: IsoDate) -> Date<Rc<AnyCalendar>> {
+    let iso = Date::try_new_iso_date(d.year, d.month, d.day).unwrap_or_else(|_| Date::try_new_iso_date(1970, 1, 1).expect("valid ISO date"));
+    iso.to_calendar(icu_calendar(kind))
+}
+
+fn iso_of(date: &Date<Iso>) -> IsoDate {
+    IsoDate { year: date.year().number, month: date.month().ordinal as u8, day: date.day_of_month().0 as u8 }
+}
+
+/// Aritmeticky rok ICU data podle kalendare (era kalendare -> souvisle cislovani).
+fn arithmetic_year(kind: AnyCalendarKind, date: &Date<Rc<AnyCalendar>>) -> i32 {
+    let year = date.year();
+    match kind {
+        AnyCalendarKind::Gregorian | AnyCalendarKind::Japanese | AnyCalendarKind::JapaneseExtended => iso_of(&date.to_iso()).year,
+        AnyCalendarKind::Chinese | AnyCalendarKind::Dangi => year.related_iso.unwrap_or(year.number),
+        _ => match year.era.0.as_str() {
+            "roc-inverse" | "bd" | "pre-incar" | "bce" => 1 - year.number,
+            "mundi" if kind == AnyCalendarKind::Ethiopian => year.number - 5500,
+            _ => year.number,
+        },
+    }
+}
+
+/// Kod mesice podle Temporal (hebrejsky Adar II je `M06`, ne formatovaci `M06L`).
+fn month_code(kind: AnyCalendarKind, date: &Date<Rc<AnyCalendar>>) -> String {
+    let code = date.month().code.0.to_string();
+    if kind == AnyCalendarKind::Hebrew && code == "M06L" { "M06".into() } else { code }
+}
+
+/// (Temporal kod ery, rok v ere) ICU data.
+fn era_of(kind: AnyCalendarKind, date: &Date<Rc<AnyCalendar>>) -> Option<(String, i32)> {
+    let year = date.year();
+    let era = match (kind, year.era.0.as_str()) {
+        (AnyCalendarKind::Chinese | AnyCalendarKind::Dangi, _) => return None,
+        (_, "roc-inverse") => "broc",
+        (AnyCalendarKind::Coptic, "ad") | (AnyCalendarKind::Ethiopian, "incar") | (AnyCalendarKind::Hebrew, _) => "am",
+        (AnyCalendarKind::Coptic, "bd") => "bce",
+        (AnyCalendarKind::Ethiopian | AnyCalendarKind::EthiopianAmeteAlem, "pre-incar" | "mundi") => "aa",
+        (AnyCalendarKind::Persian, _) => "ap",
+        (AnyCalendarKind::Indian, _) => "shaka",
+        (_, "islamic" | "islamic-civil" | "islamic-umalqura" | "islamic-tbla") => "ah",
+        (_, era) => era,
+    };
+    Some((era.to_string(), year.number))
+}
+
+impl Calendar {
+    pub fn from_id(id: &str) -> TResult<Calendar> {
+        let lower = id.to_ascii_lowercase();
+        let kind = match lower.as_str() {
+            "iso8601" => return Ok(Calendar::Iso),
+            "islamicc" => AnyCalendarKind::IslamicCivil,
+            "ethiopic-amete-alem" => AnyCalendarKind::EthiopianAmeteAlem,
+            other => AnyCalendarKind::get_for_bcp47_string(other)
+                .filter(|k| *k != AnyCalendarKind::Iso)
+                .ok_or_else(|| range_error(format_args!("Invalid calendar: {id}")))?,
+        };
+        Ok(Calendar::Icu(kind))
+    }
+
+    pub fn id(self) -> &'static str {
+        match self {
+            Calendar::Iso => "iso8601",
+            Calendar::Icu(AnyCalendarKind::EthiopianAmeteAlem) => "ethioaa",
+            Calendar::Icu(kind) => kind.as_bcp47_string(),
+        }
+    }
+
+    fn month_table(kind: AnyCalendarKind, year: i32) -> TResult<Rc<Vec<MonthInfo>>> {
+        if let Some(t) = YEAR_TABLES.with(|t| t.borrow().get(&(kind, year)).cloned()) {
+            return Ok(t);
+        }
+        // Prvni den roku: binarni hledani nad epoch dny (rok je monotonni)
+        let (mut lo, mut hi) = (-110_000_000i64, 110_000_000i64);
+        while lo < hi {
+            let mid = lo + (hi - lo) / 2;
+            if arithmetic_year(kind, &icu_date(kind, IsoDate::from_epoch_days(mid))) >= year { hi = mid } else { lo = mid + 1 }
+        }
+        let mut start = lo;
+        let first = icu_date(kind, IsoDate::from_epoch_days(start));
+        if arithmetic_year(kind, &first) != year {
+            return Err(range_error("year outside of supported range"));
+        }
+        let mut months = Vec::new();
+        for _ in 0..first.months_in_year() {
+            let date = icu_date(kind, IsoDate::from_epoch_days(start));
+            let days = date.days_in_month();
+            months.push(MonthInfo { code: month_code(kind, &date), start, days });
+            start += days as i64;
+        }
+        let table = Rc::new(months);
+        YEAR_TABLES.with(|t| t.borrow_mut().insert((kind, year), Rc::clone(&table)));
+        Ok(table)
+    }
+
+    pub fn fields(self, d: IsoDate) -> CalendarFields {
+        match self {
+            Calendar::Iso => CalendarFields {
+                year: d.year,
+                month: d.month,
+                month_code: format!("M{:02}", d.month),
+                day: d.day,
+                era: None,
+                era_year: None,
+                days_in_month: iso_days_in_month(d.year, d.month),
+                days_in_year: if is_leap_year(d.year) { 366 } else { 365 },
+                months_in_year: 12,
+                in_leap_year: is_leap_year(d.year),
+            },
+            Calendar::Icu(kind) => {
+                let date = icu_date(kind, d);
+                let era = era_of(kind, &date);
+                CalendarFields {
+                    year: arithmetic_year(kind, &date),
+                    month: date.month().ordinal as u8,
+                    month_code: month_code(kind, &date),
+                    day: date.day_of_month().0 as u8,
+                    era: era.as_ref().map(|(e, _)| e.clone()),
+                    era_year: era.map(|(_, y)| y),
+                    days_in_month: date.days_in_month(),
+                    days_in_year: date.days_in_year(),
+                    months_in_year: date.months_in_year(),
+                    in_leap_year: date.is_in_leap_year(),
+                }
+            }
+        }
+    }
+
+    /// Kalendar pouziva ery (`era` / `eraYear`)?
+    pub fn has_eras(self) -> bool {
+        !matches!(self, Calendar::Iso | Calendar::Icu(AnyCalendarKind::Chinese | AnyCalendarKind::Dangi))
+    }
+
+    /// Aritmeticky rok z `era` + `eraYear`.
+    pub fn year_from_era(self, era: &str, era_year: i64) -> TResult<i64> {
+        let Calendar::Icu(kind) = self else {
+            return Err(range_error("iso8601 calendar has no eras"));
+        };
+        let invalid = || range_error(format_args!("Invalid era {era} for calendar {}", self.id()));
+        use AnyCalendarKind as K;
+        Ok(match (kind, era) {
+            (K::Gregorian | K::Japanese | K::JapaneseExtended, "ce" | "ad") => era_year,
+            (K::Gregorian | K::Japanese | K::JapaneseExtended, "bce" | "bc") => 1 - era_year,
+            (K::Japanese | K::JapaneseExtended, _) => {
+                let year = i32::try_from(era_year).map_err(|_| invalid())?;
+                let date = Date::try_new_from_codes(
+                    icu::calendar::types::Era(era.parse().map_err(|_| invalid())?),
+                    year, icu::calendar::types::MonthCode("M12".parse().expect("month code")), 1, icu_calendar(kind))
+                    .map_err(|_| invalid())?;
+                iso_of(&date.to_iso()).year as i64
+            }
+            (K::Roc, "roc") | (K::Buddhist, "be") | (K::Coptic | K::Hebrew | K::Ethiopian, "am")
+            | (K::Persian, "ap") | (K::Indian, "shaka" | "saka") | (K::EthiopianAmeteAlem, "aa") => era_year,
+            (K::Roc, "broc") | (K::Coptic, "bce") => 1 - era_year,
+            (K::Ethiopian, "aa") => era_year - 5500,
+            (K::IslamicCivil | K::IslamicObservational | K::IslamicTabular | K::IslamicUmmAlQura, "ah") => era_year,
+            _ => return Err(invalid()),
+        })
+    }
+
+    /// Datum z poli kalendare (rok aritmeticky).
+    pub fn date_from_fields(self, year: i64, month: &MonthSpec, day: i64, overflow: Overflow) -> TResult<IsoDate> {
+        let year = i32::try_from(year).map_err(|_| range_error("year out of range"))?;
+        match self {
+            Calendar::Iso => {
+                let m = match (&month.ordinal, &month.code) {
+                    (ordinal, Some(code)) => {
+                        let from_code = parse_month_code(code).filter(|(_, leap)| !leap)
+                            .ok_or_else(|| range_error(format_args!("Invalid monthCode {code}")))?.0 as i64;
+                        if ordinal.is_some_and(|o| o != from_code) {
+                            return Err(range_error("month and monthCode do not agree"));
+                        }
+                        from_code
+                    }
+                    (Some(o), None) => *o,
+                    (None, None) => return Err("TypeError: month or monthCode is required".into()),
+                };
+                IsoDate::new(year as i64, m, day, overflow)
+            }
+            Calendar::Icu(kind) => {
+                let table = Self::month_table(kind, year)?;
+                let index = self.resolve_month(&table, month, overflow)?;
+                let info = &table[index];
+                let day = fit(day, info.days as i64, "day", overflow)?;
+                Ok(IsoDate::from_epoch_days(info.start + day - 1))
+            }
+        }
+    }
+
+    /// Index mesice v tabulce roku podle poradi / kodu.
+    fn resolve_month(self, table: &[MonthInfo], month: &MonthSpec, overflow: Overflow) -> TResult<usize> {
+        let by_ordinal = |o: i64| fit(o, table.len() as i64, "month", overflow).map(|o| o as usize - 1);
+        match (&month.ordinal, &month.code) {
+            (ordinal, Some(code)) => {
+                parse_month_code(code).ok_or_else(|| range_error(format_args!("Invalid monthCode {code}")))?;
+                let index = match table.iter().position(|m| &m.code == code) {
+                    Some(i) => i,
+                    // Prestupny mesic v neprestupnem roce: constrain na nasledujici
+                    // (hebrejsky M05L -> M06) resp. stejne cislo bez L (cinsky)
+                    None if overflow == Overflow::Constrain && code.ends_with('L') => {
+                        let base = code.trim_end_matches('L');
+                        let target = if self == Calendar::Icu(AnyCalendarKind::Hebrew) { "M06" } else { base };
+                        table.iter().position(|m| m.code == target)
+                            .ok_or_else(|| range_error(format_args!("Invalid monthCode {code}")))?
+                    }
+                    None => return Err(range_error(format_args!("monthCode {code} not valid in this year"))),
+                };
+                if ordinal.is_some_and(|o| o != index as i64 + 1) {
+                    return Err(range_error("month and monthCode do not agree"));
+                }
+                Ok(index)
+            }
+            (Some(o), None) => by_ordinal(*o),
+            (None, None) => Err("TypeError: month or monthCode is required".into()),
+        }
+    }
+
+    /// Kod mesice s danym poradim v roce (pro `until` na ne-ISO kalendarich).
+    fn month_ordinal_for_code(self, year: i32, code: &str) -> TResult<(u8, u8)> {
+        match self {
+            Calendar::Iso => {
+                let m = parse_month_code(code).map_or(1, |(m, _)| m);
+                Ok((m, iso_days_in_month(year, m)))
+            }
+            Calendar::Icu(kind) => {
+                let table = Self::month_table(kind, year)?;
+                let i = self.resolve_month(&table, &MonthSpec { ordinal: None, code: Some(code.into()) }, Overflow::Constrain)?;
+                Ok((i as u8 + 1, table[i].days))
+            }
+        }
+    }
+
+    fn months_in_year(self, year: i32) -> TResult<u8> {
+        match self {
+            Calendar::Iso => Ok(12),
+            Calendar::Icu(kind) => Ok(Self::month_table(kind, year)?.len() as u8),
+        }
+    }
+
+    /// Posun (rok, mesic) o `months` mesicu.
+    fn shift_months(self, mut year: i32, mut month: i64, months: i64) -> TResult<(i32, u8)> {
+        if self == Calendar::Iso {
+            let total = year as i64 * 12 + month - 1 + months;
+            let year = i32::try_from(total.div_euclid(12)).map_err(|_| range_error("year out of range"))?;
+            return Ok((year, (total.rem_euclid(12) + 1) as u8));
+        }
+        month += months;
+        while month < 1 {
+            year -= 1;
+            month += self.months_in_year(year)? as i64;
+        }
+        loop {
+            let miy = self.months_in_year(year)? as i64;
+            if month <= miy { break; }
+            month -= miy;
+            year += 1;
+        }
+        Ok((year, month as u8))
+    }
+
+    /// CalendarDateAdd.
+    pub fn add(self, d: IsoDate, duration: &Duration, overflow: Overflow) -> TResult<IsoDate> {
+        let (years, months, weeks, days) = duration.date_fields();
+        let mut out = d;
+        if years != 0 || months != 0 {
+            let f = self.fields(d);
+            let year = f.year as i64 + years;
+            let year = i32::try_from(year).map_err(|_| range_error("year out of range"))?;
+            let (month, _) = match overflow {
+                // Kod mesice v cilovem roce (prestupny mesic muze chybet)
+                Overflow::Reject if years != 0 && self != Calendar::Iso => {
+                    let table = Self::month_table(self.icu_kind(), year)?;
+                    let i = self.resolve_month(&table, &MonthSpec { ordinal: None, code: Some(f.month_code.clone()) }, overflow)?;
+                    (i as u8 + 1, table[i].days)
+                }
+                _ => self.month_ordinal_for_code(year, &f.month_code)?,
+            };
+            let (year, month) = self.shift_months(year, month as i64, months)?;
+            out = self.date_from_fields(year as i64, &MonthSpec { ordinal: Some(month as i64), code: None }, f.day as i64, overflow)?;
+        }
+        let days = weeks.checked_mul(7).and_then(|w| w.checked_add(days)).ok_or_else(|| range_error("duration out of range"))?;
+        if days.abs() > 300_000_000 {
+            return Err(range_error("date outside of supported range"));
+        }
+        check_date(out.add_days(days))
+    }
+
+    fn icu_kind(self) -> AnyCalendarKind {
+        match self {
+            Calendar::Iso => AnyCalendarKind::Iso,
+            Calendar::Icu(kind) => kind,
+        }
+    }
+
+    /// CalendarDateUntil - rozdil dvou dat v jednotkach do `largest`.
+    pub fn until(self, a: IsoDate, b: IsoDate, largest: Unit) -> TResult<Duration> {
+        let mut out = Duration::ZERO;
+        if largest <= Unit::Week {
+            let days = b.epoch_days() - a.epoch_days();
+            if largest == Unit::Week {
+                out.set(Unit::Week, (days / 7) as f64);
+                out.set(Unit::Day, (days % 7) as f64);
+            } else {
+                out.set(Unit::Day, days as f64);
+            }
+            return Ok(out);
+        }
+        let sign: i64 = match a.cmp(&b) {
+            std::cmp::Ordering::Equal => return Ok(out),
+            std::cmp::Ordering::Less => 1,
+            std::cmp::Ordering::Greater => -1,
+        };
+        let fa = self.fields(a);
+        let fb = self.fields(b);
+        let target = (fb.year, fb.month, fb.day);
+        // ISODateSurpasses: den se porovnava neorezany (31. ledna + 1 mesic > 28. unora)
+        let surpasses = |y: i32, m: u8, d: u8| sign * (y, m, d).cmp(&target) as i64 > 0;
+        let mut years = 0i64;
+        if largest == Unit::Year {
+            years = (fb.year - fa.year) as i64;
+            if years != 0 {
+                let (m, _) = self.month_ordinal_for_code(fa.year + years as i32, &fa.month_code)?;
+                if surpasses(fa.year + years as i32, m, fa.day) {
+                    years -= sign;
+                }
+            }
+        }
+        let year = fa.year + years as i32;
+        let (start_month, _) = self.month_ordinal_for_code(year, &fa.month_code)?;
+        let mut months = 0i64;
+        if self == Calendar::Iso {
+            months = (fb.year as i64 - year as i64) * 12 + fb.month as i64 - start_month as i64;
+            let (y, m) = self.shift_months(year, start_month as i64, months)?;
+            if surpasses(y, m, fa.day) {
+                months -= sign;
+            }
+        } else {
+            loop {
+                let (y, m) = self.shift_months(year, start_month as i64, months + sign)?;
+                if surpasses(y, m, fa.day) { break; }
+                months += sign;
+            }
+        }
+        let mut ym = Duration::ZERO;
+        ym.set(Unit::Year, years as f64);
+        ym.set(Unit::Month, months as f64);
+        let mid = self.add(a, &ym, Overflow::Constrain)?;
+        out.set(Unit::Year, years as f64);
+        out.set(Unit::Month, months as f64);
+        out.set(Unit::Day, (b.epoch_days() - mid.epoch_days()) as f64);
+        Ok(out)
+    }
+
+    /// Referencni ISO datum pro PlainYearMonth (prvni den mesice).
+    pub fn year_month_from_fields(self, year: i64, month: &MonthSpec, overflow: Overflow) -> TResult<IsoDate> {
+        self.date_from_fields(year, month, 1, overflow)
+    }
+
+    /// Referencni ISO datum pro PlainMonthDay: ISO 1972, jinak posledni
+    /// rok kalendare do konce 1972, ve kterem mesic a den existuji.
+    pub fn month_day_from_fields(self, year: Option<i64>, month: &MonthSpec, day: i64, overflow: Overflow) -> TResult<IsoDate> {
+        if self == Calendar::Iso {
+            let d = match year {
+                Some(y) => self.date_from_fields(y, month, day, overflow)?,
+                None => {
+                    let code = match (&month.code, month.ordinal) {
+                        (Some(c), _) => c.clone(),
+                        (None, Some(o)) => format!("M{:02}", o.clamp(1, 12)),
+                        (None, None) => return Err("TypeError: monthCode is required".into()),
+                    };
+                    self.date_from_fields(1972, &MonthSpec { ordinal: month.ordinal.filter(|_| month.code.is_some()), code: Some(code) }, day, overflow)?
+                }
+            };
+            return IsoDate::new(1972, d.month as i64, d.day as i64, Overflow::Constrain);
+        }
+        let code = match (&month.code, year) {
+            (Some(code), _) => code.clone(),
+            (None, Some(y)) => self.fields(self.date_from_fields(y, month, 1, overflow)?).month_code,
+            (None, None) => return Err("TypeError: monthCode is required".into()),
+        };
+        let day = match year {
+            Some(y) => self.fields(self.date_from_fields(y, &MonthSpec { ordinal: month.ordinal, code: Some(code.clone()) }, day, overflow)?).day as i64,
+            None => day,
+        };
+        let limit = IsoDate { year: 1972, month: 12, day: 31 };
+        let start_year = self.fields(limit).year;
+        let spec = MonthSpec { ordinal: None, code: Some(code.clone()) };
+        // Nejdriv presna shoda, pri constrain pak nejvetsi mozny den
+        for exact in [true, false] {
+            for y in (start_year - 60..=start_year).rev() {
+                let Calendar::Icu(kind) = self else { break };
+                let table = Self::month_table(kind, y)?;
+                let Some(info) = table.iter().find(|m| m.code == code) else { continue };
+                if exact && (info.days as i64) < day { continue; }
+                let d = self.date_from_fields(y as i64, &spec, day, Overflow::Constrain)?;
+                if d <= limit {
+                    if !exact && overflow == Overflow::Reject {
+                        return Err(range_error(format_args!("day {day} out of range for {code}")));
+                    }
+                    return Ok(d);
+                }
+            }
+        }
+        Err(range_error(format_args!("Invalid monthCode {code} for calendar {}", self.id())))
+    }
+}
+
+/// `"M05"` / `"M05L"` -> (5, prestupny).
+pub fn parse_month_code(code: &str) -> Option<(u8, bool)> {
+    let rest = code.strip_prefix('M')?;
+    let (digits, leap) = match rest.strip_suffix('L') {
+        Some(d) => (d, true),
+        None => (rest, false),
+    };
+    if digits.len() != 2 || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let n: u8 = digits.parse().ok()?;
+    ((1..=13).contains(&n) && !(leap && n == 13)).then_some((n, leap))
+}
+
+// ─── Casova pasma ────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone)]
+pub enum TimeZone {
+    /// Pevny posun v ns (`+01:00`).
+    Offset(i64),
+    Named(Rc<Zone>),
+}
+
+impl TimeZone {
+    /// Identifikator pasma: IANA jmeno (bez ohledu na velikost pismen) nebo posun.
+    pub fn from_id(id: &str) -> TResult<TimeZone> {
+        if id.starts_with(['+', '-', '\u{2212}']) {
+            let ns = parse_offset(id, false).ok_or_else(|| range_error(format_args!("Invalid time zone: {id}")))?;
+            return Ok(TimeZone::Offset(ns));
+        }
+        Zone::load(id).map(TimeZone::Named).ok_or_else(|| range_error(format_args!("Invalid time zone: {id}")))
+    }
+
+    pub fn utc() -> TimeZone {
+        TimeZone::Named(Zone::load("UTC").unwrap_or_else(Zone::utc))
+    }
+
+    pub fn id(&self) -> String {
+        match self {
+            TimeZone::Offset(ns) => format_offset(*ns as i128, true),
+            TimeZone::Named(zone) => zone.id.clone(),
+        }
+    }
+
+    pub fn equals(&self, other: &TimeZone) -> bool {
+        self.id().eq_ignore_ascii_case(&other.id())
+    }
+
+    /// Posun v ns platny v okamziku `epoch_ns`.
+    pub fn offset_ns(&self, epoch_ns: i128) -> i64 {
+        match self {
+            TimeZone::Offset(ns) => *ns,
+            TimeZone::Named(zone) => {
+                let secs = epoch_ns.div_euclid(NS_PER_SECOND).clamp(i64::MIN as i128, i64::MAX as i128) as i64;
+                zone.offset_at(secs) as i64 * NS_PER_SECOND as i64
+            }
+        }
+    }
+
+    /// Mistni cas okamziku.
+    pub fn local(&self, epoch_ns: i128) -> IsoDateTime {
+        IsoDateTime::from_local_ns(epoch_ns + self.offset_ns(epoch_ns) as i128)
+    }
+
+    /// GetPossibleEpochNanoseconds: 0 (mezera), 1 nebo 2 (prekryv) okamziky.
+    pub fn possible_instants(&self, dt: IsoDateTime) -> Vec<i128> {
+        let local = dt.local_ns();
+        if let TimeZone::Offset(ns) = self {
+            return vec![local - *ns as i128];
+        }
+        let mut out: Vec<i128> = [self.offset_ns(local - NS_PER_DAY), self.offset_ns(local + NS_PER_DAY)]
+            .into_iter()
+            .map(|o| local - o as i128)
+            .filter(|inst| inst + self.offset_ns(*inst) as i128 == local)
+            .collect();
+        out.sort();
+        out.dedup();
+        out
+    }
+
+    /// GetEpochNanosecondsFor - mistni cas na okamzik podle `disambiguation`.
+    pub fn instant_for(&self, dt: IsoDateTime, disambiguation: Disambiguation) -> TResult<i128> {
+        check_date_time(dt)?;
+        let possible = self.possible_instants(dt);
+        let local = dt.local_ns();
+        let ns = match (possible.as_slice(), disambiguation) {
+            ([one], _) => *one,
+            ([first, ..], Disambiguation::Compatible | Disambiguation::Earlier) => *first,
+            ([.., last], Disambiguation::Later) => *last,
+            ([_, ..], Disambiguation::Reject) => return Err(range_error("multiple instants found for ambiguous local time")),
+            ([], Disambiguation::Reject) => return Err(range_error("local time does not exist in this time zone")),
+            ([], mode) => {
+                let before = self.offset_ns(local - NS_PER_DAY) as i128;
+                let after = self.offset_ns(local + NS_PER_DAY) as i128;
+                if mode == Disambiguation::Earlier { local - after } else { local - before }
+            }
+        };
+        check_epoch_ns(ns)
+    }
+
+    /// Okamzik pro mistni cas s explicitnim posunem (`offset` volba).
+    pub fn instant_with_offset(&self, dt: IsoDateTime, offset: Option<i64>, option: &str, disambiguation: Disambiguation, match_minutes: bool) -> TResult<i128> {
+        let Some(offset) = offset else { return self.instant_for(dt, disambiguation) };
+        if option == "use" {
+            check_date_time(dt)?;
+            return check_epoch_ns(dt.local_ns() - offset as i128);
+        }
+        if option == "ignore" {
+            return self.instant_for(dt, disambiguation);
+        }
+        let minute = 60 * NS_PER_SECOND;
+        for candidate in self.possible_instants(dt) {
+            let actual = self.offset_ns(candidate) as i128;
+            let rounded = RoundingMode::HalfExpand.round(actual, minute);
+            if actual == offset as i128 || (match_minutes && rounded == offset as i128) {
+                return check_epoch_ns(candidate);
+            }
+        }
+        if option == "reject" {
+            return Err(range_error(format_args!("Offset {} is invalid for {} in {}", format_offset(offset as i128, false), format_date_time(dt, Precision::Auto), self.id())));
+        }
+        self.instant_for(dt, disambiguation)
+    }
+
+    /// Prvni den: okamzik pulnoci (nebo prvni platny cas po ni).
+    pub fn start_of_day(&self, date: IsoDate) -> TResult<i128> {
+        let dt = IsoDateTime { date, time: IsoTime::MIDNIGHT };
+        match self.possible_instants(dt).first() {
+            Some(ns) => check_epoch_ns(*ns),
+            None => {
+                // Pulnoc v mezere: okamzik prechodu
+                let before = dt.local_ns() - self.offset_ns(dt.local_ns() - NS_PER_DAY) as i128;
+                self.transition(before - NS_PER_DAY, true).map_or_else(|| self.instant_for(dt, Disambiguation::Compatible), Ok)
+            }
+        }
+    }
+
+    /// Nasledujici (`next`) nebo predchozi prechod posunu.
+    pub fn transition(&self, epoch_ns: i128, next: bool) -> Option<i128> {
+        let TimeZone::Named(zone) = self else { return None };
+        let secs = epoch_ns.div_euclid(NS_PER_SECOND) as i64;
+        let found = if next {
+            zone.next_transition(secs)
+        } else {
+            // Okamzik presne na prechodu s nenulovymi ns je "po" prechodu
+            let at = if epoch_ns.rem_euclid(NS_PER_SECOND) != 0 { secs + 1 } else { secs };
+            zone.previous_transition(at)
+        };
+        found.map(|s| s as i128 * NS_PER_SECOND).filter(|ns| ns.abs() <= MAX_EPOCH_NS)
+    }
+}
+
+// ─── Rozdily a zaokrouhlovani vuci pocatku ───────────────────────────────────
+
+/// Pocatek pro kalendarni rozdily a `Duration.round/total` (`relativeTo`).
+#[derive(Debug, Clone)]
+pub enum Relative {
+    Plain(IsoDateTime, Calendar),
+    Zoned(i128, TimeZone, Calendar),
+}
+
+impl Relative {
+    /// Pocatek jako ns (lokalni pro Plain, epocha pro Zoned).
+    pub fn origin_ns(&self) -> i128 {
+        match self {
+            Relative::Plain(dt, _) => dt.local_ns(),
+            Relative::Zoned(ns, ..) => *ns,
+        }
+    }
+
+    fn is_zoned(&self) -> bool {
+        matches!(self, Relative::Zoned(..))
+    }
+
+    /// Pocatek + trvani (ns stejneho druhu jako `origin_ns`).
+    pub fn add(&self, d: &Duration) -> TResult<i128> {
+        match self {
+            Relative::Plain(dt, cal) => {
+                let (days, time) = IsoTime::from_ns(dt.time.to_ns() + d.time_ns());
+                let mut date_part = d.date_part();
+                date_part.set(Unit::Day, d.get(Unit::Day) + days as f64);
+                let date = cal.add(dt.date, &date_part, Overflow::Constrain)?;
+                Ok(check_date_time(IsoDateTime { date, time })?.local_ns())
+            }
+            Relative::Zoned(ns, tz, cal) => add_zoned(*ns, tz, *cal, d, Overflow::Constrain),
+        }
+    }
+
+    /// Nezaokrouhleny rozdil od pocatku do `end` s jednotkami do `largest`.
+    pub fn diff(&self, end: i128, largest: Unit) -> TResult<Duration> {
+        match self {
+            Relative::Plain(dt, cal) => {
+                let end = IsoDateTime::from_local_ns(end);
+                if largest < Unit::Day {
+                    return Ok(Duration::from_ns(end.local_ns() - dt.local_ns(), largest));
+                }
+                let mut time = end.time.to_ns() - dt.time.to_ns();
+                let sign = (end.local_ns() - dt.local_ns()).signum();
+                let mut end_date = end.date;
+                // Cas opacneho znamenka nez datum: o den zpet
+                if time.signum() == -sign {
+                    end_date = end_date.add_days(sign as i64);
+                    time -= sign * NS_PER_DAY;
+                }
+                let date = cal.until(dt.date, end_date, largest)?;
+                Ok(date.with_time_ns(time))
+            }
+            Relative::Zoned(start, tz, cal) => {
+                if largest < Unit::Day {
+                    return Ok(Duration::from_ns(end - start, largest));
+                }
+                let sign = (end - start).signum();
+                if sign == 0 {
+                    return Ok(Duration::ZERO);
+                }
+                let start_dt = tz.local(*start);
+                let end_dt = tz.local(end);
+                // Mezidatum s casem pocatku, aby zbytek mel spravne znamenko
+                let corrections = if sign > 0 { 0..=2 } else { 0..=1 };
+                for correction in corrections {
+                    let date = end_dt.date.add_days(-(correction as i64) * sign as i64);
+                    let mid = tz.instant_for(IsoDateTime { date, time: start_dt.time }, Disambiguation::Compatible)?;
+                    let rest = end - mid;
+                    if rest.signum() != -sign {
+                        let d = cal.until(start_dt.date, date, largest)?;
+                        return Ok(d.with_time_ns(rest));
+                    }
+                }
+                Err(range_error("unable to compute zoned difference"))
+            }
+        }
+    }
+
+    /// Rozdil se zaokrouhlenim a bublanim (RoundRelativeDuration).
+    pub fn diff_rounded(&self, end: i128, s: RoundingSettings) -> TResult<Duration> {
+        let d = self.diff(end, s.largest)?;
+        if s.is_noop() {
+            return Ok(d);
+        }
+        let sign = (end - self.origin_ns()).signum();
+        let sign = if sign == 0 { 1 } else { sign };
+        if s.smallest.is_calendar() || (s.smallest == Unit::Day && self.is_zoned()) {
+            return self.nudge_calendar(d, end, sign, s);
+        }
+        if s.largest < Unit::Day {
+            // Cisty casovy rozdil
+            let rounded = s.mode.round(d.day_time_ns(), s.smallest.nanos() * s.increment as i128);
+            return Ok(Duration::from_ns(rounded, s.largest));
+        }
+        let increment = s.smallest.nanos() * s.increment as i128;
+        match self {
+            Relative::Plain(..) => {
+                let total = d.day_time_ns();
+                let rounded = s.mode.round(total, increment);
+                let mut out = d.date_part().with_time_ns(rounded % NS_PER_DAY);
+                out.set(Unit::Day, (rounded / NS_PER_DAY) as f64);
+                if out.get(Unit::Day) != d.get(Unit::Day) {
+                    return self.bubble(out, sign, Unit::Day, s.largest);
+                }
+                Ok(out)
+            }
+            Relative::Zoned(..) => {
+                let date_part = d.date_part();
+                let start = self.add(&date_part)?;
+                let mut next_day = date_part;
+                next_day.set(Unit::Day, date_part.get(Unit::Day) + sign as f64);
+                let day_len = self.add(&next_day)? - start;
+                let time = d.time_ns();
+                let mut rounded = s.mode.round(time, increment);
+                let beyond = rounded - day_len;
+                if beyond.signum() != -sign {
+                    // Zaokrouhleni preteklo do dalsiho dne
+                    rounded = s.mode.round(beyond, increment);
+                    let out = next_day.with_time_ns(rounded);
+                    return self.bubble(out, sign, Unit::Day, s.largest);
+                }
+                Ok(date_part.with_time_ns(rounded))
+            }
+        }
+    }
+
+    /// NudgeToCalendarUnit - zaokrouhleni na rok / mesic / tyden (/ den u Zoned).
+    fn nudge_calendar(&self, d: Duration, end: i128, sign: i128, s: RoundingSettings) -> TResult<Duration> {
+        let unit = s.smallest;
+        let inc = s.increment as i64;
+        let mut base = d.truncated(unit);
+        let mut value = d.get(unit) as i64;
+        if unit == Unit::Week && s.largest > Unit::Week {
+            value += d.get(Unit::Day) as i64 / 7;
+        }
+        base.set(unit, 0.0);
+        let r1 = (value / inc) * inc;
+        let r2 = r1 + inc * sign as i64;
+        let mut lo = base;
+        lo.set(unit, r1 as f64);
+        let mut hi = base;
+        hi.set(unit, r2 as f64);
+        let start = self.add(&lo)?;
+        let stop = self.add(&hi)?;
+        let den = (stop - start).abs().max(1);
+        let num = (end - start).abs();
+        let scaled = r1 as i128 * den + sign * num * inc as i128;
+        let rounded = s.mode.round(scaled, inc as i128 * den) / den;
+        let mut out = base;
+        out.set(unit, rounded as f64);
+        if rounded as i64 == r2 {
+            return self.bubble(out, sign, unit, s.largest);
+        }
+        Ok(out)
+    }
+
+    /// BubbleRelativeDuration - prenos do vyssich jednotek po zaokrouhleni.
+    fn bubble(&self, mut d: Duration, sign: i128, smallest: Unit, largest: Unit) -> TResult<Duration> {
+        if smallest >= largest {
+            return Ok(d);
+        }
+        let target = self.add(&d)?;
+        for unit in Unit::ALL.into_iter().rev().filter(|u| *u > smallest.max(Unit::Day) && *u <= largest) {
+            if unit == Unit::Week && largest != Unit::Week {
+                continue;
+            }
+            let mut candidate = d.truncated(unit);
+            candidate.set(unit, candidate.get(unit) + sign as f64);
+            let candidate_end = self.add(&candidate)?;
+            if sign * (target - candidate_end) >= 0 {
+                d = candidate;
+            } else {
+                break;
+            }
+        }
+        Ok(d)
+    }
+
+    /// Duration.total vuci pocatku.
+    pub fn total(&self, end: i128, unit: Unit) -> TResult<f64> {
+        if unit.is_calendar() || (unit == Unit::Day && self.is_zoned()) {
+            let d = self.diff(end, unit)?;
+            let sign = (end - self.origin_ns()).signum();
+            let value = d.get(unit);
+            let base = d.truncated(unit);
+            let start = self.add(&base)?;
+            if end == start || sign == 0 {
+                return Ok(value);
+            }
+            let mut next = base;
+            next.set(unit, value + sign as f64);
+            let stop = self.add(&next)?;
+            return Ok(value + fraction(end - start, (stop - start).abs()));
+        }
+        Ok(divide(end - self.origin_ns(), unit.nanos()))
+    }
+}
+
+/// `num / den` jako f64 bez ztraty presnosti celeho podilu.
+pub fn divide(num: i128, den: i128) -> f64 {
+    (num / den) as f64 + fraction(num % den, den)
+}
+
+fn fraction(num: i128, den: i128) -> f64 {
+    num as f64 / den as f64
+}
+
+/// AddZonedDateTime: datova cast v mistnim case, casova jako presne ns.
+pub fn add_zoned(ns: i128, tz: &TimeZone, cal: Calendar, d: &Duration, overflow: Overflow) -> TResult<i128> {
+    let date_part = d.date_part();
+    let base = if date_part.sign() == 0 {
+        ns
+    } else {
+        let local = tz.local(ns);
+        let date = cal.add(local.date, &date_part, overflow)?;
+        tz.instant_for(IsoDateTime { date, time: local.time }, Disambiguation::Compatible)?
+    };
+    check_epoch_ns(base + d.time_ns())
+}
+
+/// Rozdil dvou okamziku (Instant.until) - jen casove jednotky.
+pub fn diff_instant(a: i128, b: i128, s: RoundingSettings) -> Duration {
+    let rounded = s.mode.round(b - a, s.smallest.nanos() * s.increment as i128);
+    Duration::from_ns(rounded, s.largest)
+}
+
+/// Duration.round / total bez `relativeTo`: den = 24 h, bez kalendarnich jednotek.
+pub fn round_time_duration(d: &Duration, s: RoundingSettings) -> TResult<Duration> {
+    if d.has_calendar_units() || s.largest.is_calendar() || s.smallest.is_calendar() {
+        return Err(range_error("a starting point is required for years, months or weeks balancing"));
+    }
+    let rounded = s.mode.round(d.day_time_ns(), s.smallest.nanos() * s.increment as i128);
+    Duration::from_ns(rounded, s.largest).validate()
+}
+
+// ─── Parser RFC 9557 / ISO 8601 ──────────────────────────────────────────────
+
+/// Vysledek parsovani date-time retezce.
+#[derive(Debug, Clone, Default)]
+pub struct Parsed {
+    pub year: Option<i32>,
+    pub month: Option<u8>,
+    pub day: Option<u8>,
+    pub time: Option<IsoTime>,
+    /// `Z` v retezci (UTC designator).
+    pub z: bool,
+    /// Posun v ns a zda byl zadan s presnosti pod minuty.
+    pub offset: Option<(i64, bool)>,
+    pub time_zone: Option<String>,
+    pub calendar: Option<String>,
+}
+
+impl Parsed {
+    pub fn date(&self) -> Option<IsoDate> {
+        Some(IsoDate { year: self.year?, month: self.month?, day: self.day? })
+    }
+}
+
+struct Scanner<'a> {
+    s: &'a [u8],
+    pos: usize,
+}
+
+impl Scanner<'_> {
+    fn peek(&self) -> Option<u8> {
+        self.s.get(self.pos).copied()
+    }
+
+    fn at_end(&self) -> bool {
+        self.pos >= self.s.len()
+    }
+
+    fn eat(&mut self, b: u8) -> bool {
+        if self.peek() == Some(b) { self.pos += 1; true } else { false }
+    }
+
+    /// Presne `n` cislic.
+    fn digits(&mut self, n: usize) -> Option<u32> {
+        let part = self.s.get(self.pos..self.pos + n)?;
+        if !part.iter().all(u8::is_ascii_digit) {
+            return None;
+        }
+        self.pos += n;
+        Some(part.iter().fold(0, |acc, b| acc * 10 + (b - b'0') as u32))
+    }
+
+    /// Znamenko `+`, `-` nebo U+2212.
+    fn sign(&mut self) -> Option<i64> {
+        if self.eat(b'+') { return Some(1); }
+        if self.eat(b'-') { return Some(-1); }
+        if self.s[self.pos..].starts_with("\u{2212}".as_bytes()) {
+            self.pos += 3;
+            return Some(-1);
+        }
+        None
+    }
+
+    /// Desetinna cast `[.,]d{1,9}` v ns.
+    fn fraction(&mut self) -> Option<Option<u32>> {
+        if !matches!(self.peek(), Some(b'.' | b',')) {
+            return Some(None);
+        }
+        self.pos += 1;
+        let start = self.pos;
+        while self.peek().is_some_and(|b| b.is_ascii_digit()) { self.pos += 1; }
+        let digits = &self.s[start..self.pos];
+        if digits.is_empty() || digits.len() > 9 {
+            return None;
+        }
+        let mut value = digits.iter().fold(0u32, |acc, b| acc * 10 + (b - b'0') as u32);
+        for _ in digits.len()..9 { value *= 10; }
+        Some(Some(value))
+    }
+
+    fn year(&mut self) -> Option<i32> {
+        match self.sign() {
+            Some(sign) => {
+                // -000000 neni platny rok
+                let y = self.digits(6)? as i64;
+                if sign < 0 && y == 0 {
+                    return None;
+                }
+                Some((sign * y) as i32)
+            }
+            None => self.digits(4).map(|y| y as i32),
+        }
+    }
+
+    /// `MM-DD` / `MMDD` (extended podle `extended`).
+    fn month_day(&mut self, extended: bool) -> Option<(u8, u8)> {
+        let month = self.digits(2)? as u8;
+        if extended && !self.eat(b'-') {
+            return None;
+        }
+        let day = self.digits(2)? as u8;
+        Some((month, day))
+    }
+
+    fn time(&mut self) -> Option<IsoTime> {
+        let hour = self.digits(2)?;
+        let mut minute = 0;
+        let mut second = 0;
+        let mut fraction = None;
+        let extended = self.peek() == Some(b':');
+        if extended { self.pos += 1; }
+        if let Some(m) = self.digits(2) {
+            minute = m;
+            let has_seconds = if extended { self.eat(b':') } else { self.peek().is_some_and(|b| b.is_ascii_digit()) };
+            if has_seconds {
+                second = self.digits(2)?;
+                fraction = self.fraction()?;
+            }
+        } else if extended {
+            return None;
+        }
+        if hour > 23 || minute > 59 || second > 60 {
+            return None;
+        }
+        let ns = fraction.unwrap_or(0);
+        Some(IsoTime {
+            hour: hour as u8, minute: minute as u8, second: second.min(59) as u8,
+            millisecond: (ns / 1_000_000) as u16, microsecond: (ns / 1_000 % 1_000) as u16, nanosecond: (ns % 1_000) as u16,
+        })
+    }
+
+    /// Posun `Z` nebo `+-HH[:MM[:SS[.f]]]`; vraci (ns, pod minuty).
+    fn offset(&mut self) -> Option<Option<(i64, bool)>> {
+        let start = self.pos;
+        let Some(sign) = self.sign() else { return Some(None) };
+        let hours = self.digits(2)?;
+        let mut minutes = 0;
+        let mut seconds = 0;
+        let mut fraction = 0;
+        let mut sub_minute = false;
+        let extended = self.eat(b':');
+        if let Some(m) = self.digits(2) {
+            minutes = m;
+            let more = if extended { self.eat(b':') } else { self.peek().is_some_and(|b| b.is_ascii_digit()) };
+            if more {
+                seconds = self.digits(2)?;
+                fraction = self.fraction()?.unwrap_or(0);
+                sub_minute = true;
+            }
+        } else if extended {
+            self.pos = start;
+            return None;
+        }
+        if hours > 23 || minutes > 59 || seconds > 59 {
+            return None;
+        }
+        let ns = ((hours as i64 * 60 + minutes as i64) * 60 + seconds as i64) * NS_PER_SECOND as i64 + fraction as i64;
+        Some(Some((sign * ns, sub_minute)))
+    }
+
+    /// Anotace `[tz]`, `[u-ca=...]`, ostatni klice (kriticke `!` -> chyba).
+    fn annotations(&mut self, out: &mut Parsed) -> Option<()> {
+        let mut first = true;
+        let mut calendar_critical = false;
+        let mut calendars = 0;
+        while self.eat(b'[') {
+            let critical = self.eat(b'!');
+            let start = self.pos;
+            while self.peek().is_some_and(|b| b != b']') { self.pos += 1; }
+            let body = std::str::from_utf8(&self.s[start..self.pos]).ok()?;
+            if !self.eat(b']') || body.is_empty() {
+                return None;
+            }
+            match body.split_once('=') {
+                Some((key, value)) => {
+                    if !key.bytes().all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'-' || b == b'_')
+                        || !key.starts_with(|c: char| c.is_ascii_lowercase() || c == '_')
+                    {
+                        return None;
+                    }
+                    if key == "u-ca" {
+                        calendars += 1;
+                        calendar_critical |= critical;
+                        if out.calendar.is_none() {
+                            out.calendar = Some(value.to_string());
+                        }
+                    } else if critical {
+                        return None;
+                    }
+                }
+                None if first => out.time_zone = Some(body.to_string()),
+                None => return None,
+            }
+            first = false;
+        }
+        (calendars < 2 || !calendar_critical).then_some(())
+    }
+}
+
+/// Posun jako samostatny retezec (`+01:00`); `allow_sub_minute` pro volbu `offset`.
+pub fn parse_offset(s: &str, allow_sub_minute: bool) -> Option<i64> {
+    let mut sc = Scanner { s: s.as_bytes(), pos: 0 };
+    let (ns, sub_minute) = sc.offset()??;
+    (sc.at_end() && (allow_sub_minute || !sub_minute)).then_some(ns)
+}
+
+fn invalid_string(s: &str) -> String {
+    range_error(format_args!("Invalid ISO 8601 string: {s}"))
+}
+
+/// Datum s volitelnym casem, posunem a anotacemi.
+pub fn parse_date_time(s: &str) -> TResult<Parsed> {
+    let mut sc = Scanner { s: s.as_bytes(), pos: 0 };
+    let mut out = Parsed::default();
+    (|| {
+        out.year = Some(sc.year()?);
+        let extended = sc.eat(b'-');
+        let (month, day) = sc.month_day(extended)?;
+        out.month = Some(month);
+        out.day = Some(day);
+        if matches!(sc.peek(), Some(b'T' | b't' | b' ')) {
+            sc.pos += 1;
+            out.time = Some(sc.time()?);
+            if matches!(sc.peek(), Some(b'Z' | b'z')) {
+                sc.pos += 1;
+                out.z = true;
+            } else {
+                out.offset = sc.offset()?;
+            }
+        }
+        sc.annotations(&mut out)?;
+        sc.at_end().then_some(())
+    })().ok_or_else(|| invalid_string(s))?;
+    validate_parsed(&out, s)?;
+    Ok(out)
+}
+
+fn validate_parsed(p: &Parsed, s: &str) -> TResult<()> {
+    if let (Some(y), Some(m), Some(d)) = (p.year, p.month, p.day)
+        && !((1..=12).contains(&m) && d >= 1 && d <= iso_days_in_month(y, m))
+    {
+        return Err(invalid_string(s));
+    }
+    if let Some(y) = p.year && !(-271_821..=275_760).contains(&y) {
+        return Err(range_error(format_args!("date outside of supported range: {s}")));
+    }
+    if let Some(cal) = &p.calendar {
+        Calendar::from_id(cal)?;
+    }
+    Ok(())
+}
+
+/// PlainTime: samotny cas (volitelne s `T`) nebo cely date-time.
+pub fn parse_time(s: &str) -> TResult<Parsed> {
+    if let Ok(p) = parse_date_time(s) {
+        if p.time.is_none() {
+            return Err(invalid_string(s));
+        }
+        return Ok(p);
+    }
+    let mut sc = Scanner { s: s.as_bytes(), pos: 0 };
+    let mut out = Parsed::default();
+    let designator = matches!(sc.peek(), Some(b'T' | b't'));
+    if designator { sc.pos += 1; }
+    (|| {
+        out.time = Some(sc.time()?);
+        if matches!(sc.peek(), Some(b'Z' | b'z')) {
+            sc.pos += 1;
+            out.z = true;
+        } else {
+            out.offset = sc.offset()?;
+        }
+        sc.annotations(&mut out)?;
+        sc.at_end().then_some(())
+    })().ok_or_else(|| invalid_string(s))?;
+    // Bez `T` nesmi byt zamenitelny s YYYY-MM / MM-DD
+    if !designator && out.offset.is_none() && !out.z && (parse_year_month(s).is_ok() || parse_month_day(s).is_ok()) {
+        return Err(invalid_string(s));
+    }
+    Ok(out)
+}
+
+/// PlainYearMonth: `YYYY-MM` / `YYYYMM` s anotacemi nebo cele datum.
+pub fn parse_year_month(s: &str) -> TResult<Parsed> {
+    if let Ok(p) = parse_date_time(s) {
+        return Ok(p);
+    }
+    let mut sc = Scanner { s: s.as_bytes(), pos: 0 };
+    let mut out = Parsed::default();
+    (|| {
+        out.year = Some(sc.year()?);
+        sc.eat(b'-');
+        out.month = Some(sc.digits(2)? as u8);
+        sc.annotations(&mut out)?;
+        sc.at_end().then_some(())
+    })().ok_or_else(|| invalid_string(s))?;
+    if !(1..=12).contains(&out.month.unwrap_or(0)) {
+        return Err(invalid_string(s));
+    }
+    validate_parsed(&out, s)?;
+    Ok(out)
+}
+
+/// PlainMonthDay: `--MM-DD`, `MM-DD`, `MMDD` s anotacemi nebo cele datum.
+pub fn parse_month_day(s: &str) -> TResult<Parsed> {
+    if let Ok(p) = parse_date_time(s) {
+        return Ok(p);
+    }
+    let mut sc = Scanner { s: s.as_bytes(), pos: 0 };
+    let mut out = Parsed::default();
+    (|| {
+        if sc.eat(b'-') && !sc.eat(b'-') {
+            return None;
+        }
+        let month = sc.digits(2)? as u8;
+        sc.eat(b'-');
+        out.month = Some(month);
+        out.day = Some(sc.digits(2)? as u8);
+        sc.annotations(&mut out)?;
+        sc.at_end().then_some(())
+    })().ok_or_else(|| invalid_string(s))?;
+    let (m, d) = (out.month.unwrap_or(0), out.day.unwrap_or(0));
+    if !(1..=12).contains(&m) || d < 1 || d > iso_days_in_month(1972, m) {
+        return Err(invalid_string(s));
+    }
+    validate_parsed(&out, s)?;
+    Ok(out)
+}
+
+/// Trvani `[+-]PnYnMnWnDTnHnMnS` (zlomek jen u posledni casove jednotky).
+pub fn parse_duration(s: &str) -> TResult<Duration> {
+    let err = || invalid_string(s);
+    let mut sc = Scanner { s: s.as_bytes(), pos: 0 };
+    let sign = sc.sign().unwrap_or(1) as f64;
+    if !matches!(sc.peek(), Some(b'P' | b'p')) {
+        return Err(err());
+    }
+    sc.pos += 1;
+    let mut d = Duration::ZERO;
+    let mut in_time = false;
+    let mut any = false;
+    let mut last_unit: Option<Unit> = None;
+    let mut fraction_unit: Option<(Unit, u32)> = None;
+    while !sc.at_end() {
+        if matches!(sc.peek(), Some(b'T' | b't')) {
+            if in_time { return Err(err()); }
+            sc.pos += 1;
+            in_time = true;
+            if sc.at_end() { return Err(err()); }
+            continue;
+        }
+        if fraction_unit.is_some() {
+            return Err(err());
+        }
+        let start = sc.pos;
+        while sc.peek().is_some_and(|b| b.is_ascii_digit()) { sc.pos += 1; }
+        if sc.pos == start { return Err(err()); }
+        let value: f64 = std::str::from_utf8(&sc.s[start..sc.pos]).map_err(|_| err())?.parse().map_err(|_| err())?;
+        let fraction = sc.fraction().ok_or_else(err)?;
+        let designator = sc.peek().ok_or_else(err)?.to_ascii_uppercase();
+        sc.pos += 1;
+        let unit = match (in_time, designator) {
+            (false, b'Y') => Unit::Year,
+            (false, b'M') => Unit::Month,
+            (false, b'W') => Unit::Week,
+            (false, b'D') => Unit::Day,
+            (true, b'H') => Unit::Hour,
+            (true, b'M') => Unit::Minute,
+            (true, b'S') => Unit::Second,
+            _ => return Err(err()),
+        };
+        if last_unit.is_some_and(|l| l <= unit) || (fraction.is_some() && !in_time) {
+            return Err(err());
+        }
+        last_unit = Some(unit);
+        any = true;
+        d.set(unit, value);
+        if let Some(f) = fraction {
+            fraction_unit = Some((unit, f));
+        }
+    }
+    if !any {
+        return Err(err());
+    }
+    if let Some((unit, f)) = fraction_unit {
+        // Zlomek hodin/minut se rozlozi do nizsich jednotek
+        let ns = f as i128 * unit.nanos() / NS_PER_SECOND;
+        let below = Duration::from_ns(ns, match unit { Unit::Hour => Unit::Minute, Unit::Minute => Unit::Second, _ => Unit::Millisecond });
+        for u in Unit::ALL.into_iter().filter(|u| *u < unit) {
+            d.set(u, d.get(u) + below.get(u));
+        }
+    }
+    let d = Duration(d.0.map(|v| v * sign));
+    d.validate()
+}
+
+// ─── Formatovani ─────────────────────────────────────────────────────────────
+
+/// Presnost sekund ve vystupu.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Precision {
+    Minute,
+    Auto,
+    Digits(u8),
+}
+
+pub fn format_year(y: i32) -> String {
+    if (0..=9999).contains(&y) { format!("{y:04}") } else if y < 0 { format!("-{:06}", -(y as i64)) } else { format!("+{y:06}") }
+}
+
+pub fn format_date(d: IsoDate) -> String {
+    format!("{}-{:02}-{:02}", format_year(d.year), d.month, d.day)
+}
+
+pub fn format_time(t: IsoTime, precision: Precision) -> String {
+    let hm = format!("{:02}:{:02}", t.hour, t.minute);
+    let frac = t.millisecond as u32 * 1_000_000 + t.microsecond as u32 * 1_000 + t.nanosecond as u32;
+    match precision {
+        Precision::Minute => hm,
+        Precision::Auto if frac == 0 => format!("{hm}:{:02}", t.second),
+        Precision::Auto => format!("{hm}:{:02}.{}", t.second, format!("{frac:09}").trim_end_matches('0')),
+        Precision::Digits(0) => format!("{hm}:{:02}", t.second),
+        Precision::Digits(n) => format!("{hm}:{:02}.{}", t.second, &format!("{frac:09}")[..n as usize]),
+    }
+}
+
+pub fn format_date_time(dt: IsoDateTime, precision: Precision) -> String {
+    format!("{}T{}", format_date(dt.date), format_time(dt.time, precision))
+}
+
+/// `+01:00`; s `round_to_minute` bez sekund (ZonedDateTime.toString).
+pub fn format_offset(ns: i128, round_to_minute: bool) -> String {
+    let sign = if ns < 0 { '-' } else { '+' };
+    let abs = if round_to_minute { RoundingMode::HalfExpand.round(ns.abs(), 60 * NS_PER_SECOND) } else { ns.abs() };
+    let secs = abs / NS_PER_SECOND;
+    let frac = abs % NS_PER_SECOND;
+    let base = format!("{sign}{:02}:{:02}", secs / 3600, secs / 60 % 60);
+    match (secs % 60, frac) {
+        (0, 0) => base,
+        (s, 0) => format!("{base}:{s:02}"),
+        (s, f) => format!("{base}:{s:02}.{}", format!("{f:09}").trim_end_matches('0')),
+    }
+}
+
+/// Anotace kalendare podle `calendarName` (`auto` / `always` / `never` / `critical`).
+pub fn calendar_annotation(cal: Calendar, mode: &str) -> String {
+    match mode {
+        "never" => String::new(),
+        "auto" if cal == Calendar::Iso => String::new(),
+        "critical" => format!("[!u-ca={}]", cal.id()),
+        _ => format!("[u-ca={}]", cal.id()),
+    }
+}
+
+/// ISO 8601 zapis trvani; `precision` ridi zlomek sekund (uz zaokrouhleno).
+pub fn format_duration(d: &Duration, precision: Precision) -> String {
+    let sign = if d.sign() < 0 { "-" } else { "" };
+    let d = d.abs();
+    let mut date = String::new();
+    for (unit, c) in [(Unit::Year, 'Y'), (Unit::Month, 'M'), (Unit::Week, 'W'), (Unit::Day, 'D')] {
+        if d.get(unit) != 0.0 {
+            date.push_str(&format!("{}{c}", d.get(unit) as i128));
+        }
+    }
+    let mut time = String::new();
+    for (unit, c) in [(Unit::Hour, 'H'), (Unit::Minute, 'M')] {
+        if d.get(unit) != 0.0 {
+            time.push_str(&format!("{}{c}", d.get(unit) as i128));
+        }
+    }
+    // Sekundy vcetne zlomku z ms/us/ns (mohou byt nebalancovane)
+    let sub_ns: i128 = [Unit::Second, Unit::Millisecond, Unit::Microsecond, Unit::Nanosecond]
+        .iter().map(|u| d.get(*u) as i128 * u.nanos()).sum();
+    let whole = sub_ns / NS_PER_SECOND;
+    let frac = (sub_ns % NS_PER_SECOND) as u32;
+    let frac_str = match precision {
+        Precision::Auto if frac == 0 => String::new(),
+        Precision::Auto => format!(".{}", format!("{frac:09}").trim_end_matches('0')),
+        Precision::Digits(0) | Precision::Minute => String::new(),
+        Precision::Digits(n) => format!(".{}", &format!("{frac:09}")[..n as usize]),
+    };
+    let all_zero = date.is_empty() && time.is_empty();
+    if sub_ns != 0 || !frac_str.is_empty() || all_zero {
+        time.push_str(&format!("{whole}{frac_str}S"));
+    }
+    if time.is_empty() { format!("{sign}P{date}") } else { format!("{sign}P{date}T{time}") }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn epoch_days_roundtrip() {
+        for days in [-719_468, -1, 0, 59, 11_016, 2_932_896] {
+            assert_eq!(IsoDate::from_epoch_days(days).epoch_days(), days);
+        }
+        assert_eq!(IsoDate { year: 2024, month: 3, day: 1 }.epoch_days(), 19_783);
+    }
+
+    #[test]
+    fn rounding_modes() {
+        let r = |m: RoundingMode, n| m.round(n, 10);
+        assert_eq!(r(RoundingMode::HalfExpand, 15), 20);
+        assert_eq!(r(RoundingMode::HalfExpand, -15), -20);
+        assert_eq!(r(RoundingMode::HalfEven, 25), 20);
+        assert_eq!(r(RoundingMode::HalfTrunc, -15), -10);
+        assert_eq!(r(RoundingMode::Floor, -11), -20);
+        assert_eq!(r(RoundingMode::Expand, 11), 20);
+    }
+
+    #[test]
+    fn iso_until_does_not_constrain_intermediate() {
+        let d = |y, m, day| IsoDate { year: y, month: m, day };
+        let diff = Calendar::Iso.until(d(2021, 1, 31), d(2021, 2, 28), Unit::Month).unwrap();
+        assert_eq!((diff.get(Unit::Month), diff.get(Unit::Day)), (0.0, 28.0));
+        let diff = Calendar::Iso.until(d(2020, 2, 29), d(2021, 3, 1), Unit::Year).unwrap();
+        assert_eq!((diff.get(Unit::Year), diff.get(Unit::Month), diff.get(Unit::Day)), (1.0, 0.0, 1.0));
+    }
+
+    #[test]
+    fn parses_rfc9557_annotations() {
+        let p = parse_date_time("2024-03-10T02:30:00.5-05:00[America/New_York][u-ca=hebrew]").unwrap();
+        assert_eq!(p.time.unwrap().millisecond, 500);
+        assert_eq!(p.offset, Some((-5 * 3_600 * NS_PER_SECOND as i64, false)));
+        assert_eq!(p.time_zone.as_deref(), Some("America/New_York"));
+        assert_eq!(p.calendar.as_deref(), Some("hebrew"));
+        assert!(parse_date_time("2024-03-10[!foo=bar]").is_err());
+        assert!(parse_date_time("2024-02-30").is_err());
+    }
+
+    #[test]
+    fn duration_string_roundtrip() {
+        let d = parse_duration("-P1Y2M3W4DT5H6M7.008009S").unwrap();
+        assert_eq!(format_duration(&d, Precision::Auto), "-P1Y2M3W4DT5H6M7.008009S");
+        assert_eq!(format_duration(&parse_duration("PT1.5H").unwrap(), Precision::Auto), "PT1H30M");
+        assert_eq!(format_duration(&Duration::ZERO, Precision::Auto), "PT0S");
+    }
+}
@@ -6,31 +6,46 @@ use super::helpers::*;
 
 #[test]
 fn async_generator_basic() {
+    // next() vraci Promise s { value, done }
     let v = run(r#"
         async function* gen() {
             yield 1;
             yield 2;
             yield 3;
         }
-        const it = gen();
-        const a = it.next().value;
-        const b = it.next().value;
-        const c = it.next().value;
-        return a + b + c;
+        async function main() {
+            const it = gen();
+            const a = (await it.next()).value;
+            const b = (await it.next()).value;
+            const c = (await it.next()).value;
+            return a + b + c;
+        }
+        let result = 0;
+        main().then(v => { result = v; });
+        return result;
     "#);
     assert_eq!(as_num(v), 6.0);
 }
 
 #[test]
 fn async_generator_for_of() {
-    // V sync impl async generator iterace pres for-of
+    // Async generator neni sync iterable - for-of je TypeError, iteruje se pres for await
+    assert!(try_run(r#"
+        async function* range(n) { yield 1; }
+        for (const x of range(5)) {}
+    "#).is_err());
     let v = run(r#"
         async function* range(n) {
             for (let i = 0; i < n; i++) yield i;
         }
-        let sum = 0;
-        for (const x of range(5)) sum += x;
-        return sum;
+        async function main() {
+            let sum = 0;
+            for await (const x of range(5)) sum += x;
+            return sum;
+        }
+        let result = 0;
+        main().then(v => { result = v; });
+        return result;
     "#);
     assert_eq!(as_num(v), 10.0);
 }
@@ -23,6 +23,97 @@ fn symbol_key_for() {
     assert_eq!(as_str(v), "test");
 }
 
+#[test]
+fn symbol_unique_identity() {
+    let v = run(r#"
+        const a = Symbol("x");
+        const b = Symbol("x");
+        return [typeof a, a === b, a === a, String(a), a.description].join(",");
+    "#);
+    assert_eq!(as_str(v), "symbol,false,true,Symbol(x),x");
+}
+
+#[test]
+fn symbol_not_constructor_and_no_implicit_string() {
+    assert!(try_run("new Symbol();").is_err());
+    assert!(try_run(r#"const s = Symbol("a"); return "x" + s;"#).is_err());
+    assert!(try_run(r#"const s = Symbol("a"); return `${s}`;"#).is_err());
+    assert_eq!(as_str(run(r#"return Symbol("a").toString();"#)), "Symbol(a)");
+}
+
+#[test]
+fn symbol_keys_hidden_from_string_keys() {
+    let v = run(r#"
+        const s = Symbol("secret");
+        const o = { a: 1, [s]: 2 };
+        o["Symbol(secret)"] = 3;
+        const syms = Object.getOwnPropertySymbols(o);
+        return [Object.keys(o).join("|"), JSON.stringify(o), o[s], syms.length, syms[0] === s,
+                s in o, o.hasOwnProperty(s), Reflect.ownKeys(o).length].join(",");
+    "#);
+    assert_eq!(as_str(v), r#"a|Symbol(secret),{"a":1,"Symbol(secret)":3},2,1,true,true,true,3"#);
+}
+
+#[test]
+fn symbol_key_for_unregistered() {
+    let v = run(r#"
+        return [Symbol.keyFor(Symbol("k")) === undefined, Symbol.for("k") === Symbol("k")].join(",");
+    "#);
+    assert_eq!(as_str(v), "true,false");
+}
+
+#[test]
+fn symbol_class_computed_iterator() {
+    let v = run(r#"
+        class Range {
+            constructor(n) { this.n = n; }
+            [Symbol.iterator]() {
+                let i = 0; const n = this.n;
+                return { next: () => i < n ? { value: i++, done: false } : { value: undefined, done: true } };
+            }
+        }
+        let sum = 0;
+        for (const x of new Range(5)) sum += x;
+        return sum;
+    "#);
+    assert_eq!(as_num(v), 10.0);
+}
+
+#[test]
+fn symbol_to_primitive_hint() {
+    let v = run(r#"
+        const money = {
+            [Symbol.toPrimitive](hint) { return hint === "number" ? 42 : "USD"; }
+        };
+        return [money * 2, `${money}`, money + "!"].join(",");
+    "#);
+    assert_eq!(as_str(v), "84,USD,USD!");
+}
+
+#[test]
+fn symbol_keys_do_not_collide_with_strings() {
+    let v = run(r#"
+        const o = {};
+        o["__@@iterator__"] = function* () { yield 1; };
+        const q = {};
+        q["__@@toPrimitive__"] = () => 42;
+        let iterable = true;
+        try { for (const x of o) {} } catch (e) { iterable = false; }
+        return [iterable, q + 1 === 43, Object.keys({ "__@@sym5__": 1, a: 2 }).join("|")].join(",");
+    "#);
+    assert_eq!(as_str(v), "false,false,__@@sym5__|a");
+}
+
+#[test]
+fn symbol_has_instance_and_to_string_tag() {
+    let v = run(r#"
+        class Even { static [Symbol.hasInstance](n) { return n % 2 === 0; } }
+        const tagged = { [Symbol.toStringTag]: "Custom" };
+        return [4 instanceof Even, 3 instanceof Even, tagged.toString()].join(",");
+    "#);
+    assert_eq!(as_str(v), "true,false,[object Custom]");
+}
+
 // ─── Reflect ─────────────────────────────────────────────────────────────
 
 #[test]
@@ -186,3 +277,95 @@ fn proxy_get_logs_access() {
     "#);
     assert_eq!(as_num(v), 3.0);
 }
+
+#[test]
+fn proxy_has_delete_own_keys_traps() {
+    let v = run(r#"
+        const log = [];
+        const p = new Proxy({ a: 1, _secret: 2, b: 3 }, {
+            has(t, k) { log.push("has:" + k); return !k.startsWith("_") && k in t; },
+            deleteProperty(t, k) { log.push("del:" + k); return delete t[k]; },
+            ownKeys(t) { return Reflect.ownKeys(t).filter(k => !k.startsWith("_")); },
+        });
+        const inA = "a" in p, inS = "_secret" in p;
+        delete p.b;
+        const keys = [];
+        for (const k in p) keys.push(k);
+        return [inA, inS, Object.keys(p).join("|"), keys.join("|"), log.join(" ")].join(",");
+    "#);
+    assert_eq!(as_str(v), "true,false,a,a,has:a has:_secret del:b");
+}
+
+#[test]
+fn proxy_descriptor_and_prototype_traps() {
+    let v = run(r#"
+        const proto = { hello: 1 };
+        const p = new Proxy({}, {
+            getOwnPropertyDescriptor(t, k) {
+                return k === "v" ? { value: 7, writable: true, enumerable: true, configurable: true } : undefined;
+            },
+            defineProperty(t, k, d) { t[k] = d.value * 2; return true; },
+            getPrototypeOf() { return proto; },
+        });
+        Object.defineProperty(p, "w", { value: 5 });
+        return [Object.getOwnPropertyDescriptor(p, "v").value, p.hasOwnProperty("v"), p.hasOwnProperty("x"),
+                p.w, Object.getPrototypeOf(p) === proto, Reflect.getPrototypeOf(p) === proto].join(",");
+    "#);
+    assert_eq!(as_str(v), "7,true,false,10,true,true");
+}
+
+#[test]
+fn proxy_apply_and_construct_traps() {
+    let v = run(r#"
+        function add(a, b) { return a + b; }
+        const traced = new Proxy(add, {
+            apply(t, thisArg, args) { return t(...args) * 10; },
+        });
+        class Point { constructor(x) { this.x = x; } }
+        const P = new Proxy(Point, {
+            construct(t, args) { return new t(args[0] + 1); },
+        });
+        const plain = new Proxy(add, {});
+        return [typeof traced, traced(1, 2), Reflect.apply(traced, null, [2, 3]),
+                new P(1).x, plain(4, 5), Reflect.construct(Point, [9]).x].join(",");
+    "#);
+    assert_eq!(as_str(v), "function,30,50,2,9,9");
+}
+
+#[test]
+fn proxy_revocable_throws_after_revoke() {
+    let v = run(r#"
+        const { proxy, revoke } = Proxy.revocable({ a: 1 }, {});
+        const before = proxy.a;
+        revoke();
+        let msg = "";
+        try { proxy.a; } catch (e) { msg = String(e); }
+        return before + ":" + msg.includes("revoked");
+    "#);
+    assert_eq!(as_str(v), "1:true");
+    assert!(try_run(r#"Proxy({}, {});"#).is_err());
+    assert!(try_run(r#"new Proxy(1, {});"#).is_err());
+}
+
+#[test]
+fn proxy_reactive_pattern() {
+    // Vue/MobX styl: get trap sbira zavislosti, set trap spousti efekty, pole pres proxy
+    let v = run(r#"
+        const deps = new Set();
+        let runs = 0;
+        function reactive(obj) {
+            return new Proxy(obj, {
+                get(t, k, r) { deps.add(k); return Reflect.get(t, k, r); },
+                set(t, k, v, r) { const ok = Reflect.set(t, k, v, r); runs++; return ok; },
+            });
+        }
+        const state = reactive({ count: 1, items: [] });
+        const items = reactive(state.items);
+        state.count++;
+        items.push("a");
+        items[1] = "b";
+        return [state.count, runs, deps.has("count"), items.length, Array.isArray(items),
+                JSON.stringify(state), JSON.stringify({ ...state })].join(";");
+    "#);
+    assert_eq!(as_str(v), r#"2;2;true;2;true;{"count":2,"items":["a","b"]};{"count":2,"items":["a","b"]}"#);
+}
@@ -51,7 +51,7 @@ fn worker_terminate() {
 
 #[test]
 fn shared_array_buffer_byte_length() {
-    let v = run(r#"
+    let v = run_isolated(r#"
         const sab = new SharedArrayBuffer(16);
         return sab.byteLength;
     "#);
@@ -132,3 +132,119 @@ fn atomics_compare_exchange_no_match() {
     "#);
     assert_eq!(as_str(v), "100:100");
 }
+
+// ─── SharedArrayBuffer napric vlakny ─────────────────────────────────────
+
+/// Worker skript do temp souboru (Worker cte skript z FS).
+fn worker_script(name: &str, src: &str) -> String {
+    let path = std::env::temp_dir().join(format!("rwe_{name}_{}.js", std::process::id()));
+    std::fs::write(&path, src).unwrap();
+    path.to_string_lossy().replace('\\', "/")
+}
+
+#[test]
+fn shared_memory_wait_notify_across_worker() {
+    let path = worker_script("atomics_wait", r#"
+        self.onmessage = function(e) {
+            const ta = e.data;
+            const r = Atomics.wait(ta, 0, 0, 5000);
+            Atomics.notify(ta, 1);
+            Atomics.store(ta, 1, r === "ok" ? 1 : 2);
+        };
+    "#);
+    let mut interp = isolated_interpreter();
+    let v = run_in(&mut interp, &format!(r#"
+        const ta = new Int32Array(new SharedArrayBuffer(8));
+        var outcome = "pending";
+        const w = new Worker("{path}");
+        const res = Atomics.waitAsync(ta, 1, 0, 5000);
+        res.value.then(v => {{ outcome = v; }});
+        w.postMessage(ta);
+        const start = Date.now();
+        while (Atomics.notify(ta, 0, 1) === 0 && Date.now() - start < 5000) {{}}
+        while (Atomics.load(ta, 1) === 0 && Date.now() - start < 5000) {{}}
+        return res.async + "|" + Atomics.load(ta, 1);
+    "#));
+    assert_eq!(as_str(v), "true|1");
+    assert_eq!(as_str(run_in(&mut interp, "return outcome;")), "ok");
+}
+
+#[test]
+fn shared_memory_worker_writes_visible_to_page() {
+    let path = worker_script("atomics_add", r#"
+        self.onmessage = function(e) {
+            for (let i = 0; i < 100; i++) Atomics.add(e.data.counter, 0, 1);
+            postMessage(e.data.counter.buffer.byteLength);
+        };
+    "#);
+    let mut interp = isolated_interpreter();
+    let v = run_in(&mut interp, &format!(r#"
+        const counter = new Int32Array(new SharedArrayBuffer(4));
+        var echoed = 0;
+        const w = new Worker("{path}");
+        w.onmessage = e => {{ echoed = e.data; }};
+        w.postMessage({{ counter }});
+        for (let i = 0; i < 100; i++) Atomics.add(counter, 0, 1);
+        const start = Date.now();
+        while (Atomics.load(counter, 0) < 200 && Date.now() - start < 5000) {{}}
+        return Atomics.load(counter, 0);
+    "#));
+    assert_eq!(as_num(v), 200.0);
+    assert_eq!(as_num(run_in(&mut interp, "return echoed;")), 4.0);
+}
+
+#[test]
+fn atomics_wait_on_main_thread_throws() {
+    let err = try_run_in(&mut isolated_interpreter(), r#"
+        const ta = new Int32Array(new SharedArrayBuffer(4));
+        Atomics.wait(ta, 0, 0, 10);
+    "#).unwrap_err();
+    assert!(format!("{err:?}").contains("Atomics.wait cannot be called in this context"));
+}
+
+#[test]
+fn atomics_wait_async_times_out() {
+    let mut interp = isolated_interpreter();
+    let v = run_in(&mut interp, r#"
+        const ta = new Int32Array(new SharedArrayBuffer(4));
+        var outcome = "pending";
+        const miss = Atomics.waitAsync(ta, 0, 1);
+        const now = Atomics.waitAsync(ta, 0, 0, 0);
+        Atomics.waitAsync(ta, 0, 0, 10).value.then(v => { outcome = v; });
+        const start = Date.now();
+        while (Date.now() - start < 30) {}
+        return miss.value + "|" + now.async + "|" + now.value;
+    "#);
+    assert_eq!(as_str(v), "not-equal|false|timed-out");
+    assert_eq!(as_str(run_in(&mut interp, "return outcome;")), "timed-out");
+}
+
+#[test]
+fn shared_atomics_wrap_to_element_type() {
+    let v = run_isolated(r#"
+        const u8 = new Uint8Array(new SharedArrayBuffer(4, { maxByteLength: 8 }));
+        const old = Atomics.sub(u8, 0, 1);
+        const i16 = new Int16Array(u8.buffer);
+        Atomics.store(i16, 1, 0x18000);
+        u8.buffer.grow(8);
+        return old + "|" + u8[0] + "|" + Atomics.load(i16, 1) + "|" + u8.buffer.byteLength
+            + "|" + Atomics.compareExchange(i16, 1, -32768, 7) + "|" + i16[1];
+    "#);
+    assert_eq!(as_str(v), "0|255|-32768|8|-32768|7");
+}
+
+#[test]
+fn shared_array_buffer_needs_cross_origin_isolation() {
+    use crate::browser::security::coep_coop::{Coep, Coop};
+    let mut interp = isolated_interpreter();
+    assert!(as_bool(run_in(&mut interp, "var early = new SharedArrayBuffer(4); return crossOriginIsolated;")));
+    interp.set_cross_origin_isolation(Coop::SameOriginAllowPopups, Coep::RequireCorp);
+    let v = run_in(&mut interp, r#"
+        let cloneError = "";
+        try { new Worker("echo").postMessage(early); } catch (e) { cloneError = String(e); }
+        return typeof SharedArrayBuffer + "|" + crossOriginIsolated + "|" + cloneError.includes("DataCloneError");
+    "#);
+    assert_eq!(as_str(v), "undefined|false|true");
+    interp.set_cross_origin_isolation(Coop::SameOrigin, Coep::RequireCorp);
+    assert_eq!(as_str(run_in(&mut interp, "return typeof SharedArrayBuffer;")), "function");
+}
@@ -0,0 +1,96 @@
+/// Rozpocty behu: limity kroku / casu / heapu / hloubky volani, interrupt
+/// handle z jineho threadu, nezachytitelne ukonceni.
+
+use std::time::Duration;
+
+use super::helpers::*;
+use crate::interpreter::budget::{ExecutionLimits, Termination, TERMINATION_PREFIX};
+use crate::interpreter::{Interpreter, JsError};
+
+fn limited(limits: ExecutionLimits) -> Interpreter {
+    let mut interp = Interpreter::new();
+    interp.set_execution_limits(limits);
+    interp
+}
+
+fn assert_terminated(r: Result<crate::interpreter::JsValue, JsError>) {
+    match r {
+        Err(JsError::Runtime(msg)) => assert!(msg.starts_with(TERMINATION_PREFIX), "{msg}"),
+        other => panic!("ocekavano ukonceni, nalezeno {other:?}"),
+    }
+}
+
+#[test]
+fn step_limit_stops_infinite_loop() {
+    let mut interp = limited(ExecutionLimits { max_steps: Some(10_000), ..Default::default() });
+    assert_terminated(try_run_in(&mut interp, "while (true) {}"));
+    assert_eq!(interp.take_termination(), Some(Termination::StepLimit(10_000)));
+    assert_eq!(interp.take_termination(), None);
+    // Dalsi uloha dostane novy rozpocet
+    assert_eq!(as_num(run_in(&mut interp, "return 1 + 1;")), 2.0);
+}
+
+#[test]
+fn termination_belongs_to_its_interpreter() {
+    let mut stopped = limited(ExecutionLimits { max_steps: Some(10_000), ..Default::default() });
+    let other = Interpreter::new();
+    assert_terminated(try_run_in(&mut stopped, "while (true) {}"));
+    assert_eq!(other.take_termination(), None);
+    assert_eq!(stopped.take_termination(), Some(Termination::StepLimit(10_000)));
+}
+
+#[test]
+fn termination_skips_catch_and_finally() {
+    let mut interp = limited(ExecutionLimits { max_steps: Some(10_000), ..Default::default() });
+    assert_terminated(try_run_in(&mut interp, r#"
+        var log = [];
+        try { while (true) {} } catch (e) { log.push("catch"); } finally { log.push("finally"); }
+    "#));
+    assert_eq!(as_num(run_in(&mut interp, "return log.length;")), 0.0);
+}
+
+#[test]
+fn time_limit_stops_loop() {
+    let mut interp = limited(ExecutionLimits { max_time: Some(Duration::from_millis(20)), ..Default::default() });
+    assert_terminated(try_run_in(&mut interp, "for (;;) { let x = 1; }"));
+    assert_eq!(interp.take_termination(), Some(Termination::TimeLimit(Duration::from_millis(20))));
+}
+
+#[test]
+fn interrupt_handle_from_other_thread() {
+    let mut interp = Interpreter::new();
+    let handle = interp.interrupt_handle();
+    let watchdog = std::thread::spawn(move || {
+        std::thread::sleep(Duration::from_millis(30));
+        handle.interrupt();
+    });
+    assert_terminated(try_run_in(&mut interp, "while (true) {}"));
+    watchdog.join().unwrap();
+    assert_eq!(interp.take_termination(), Some(Termination::Interrupted));
+    assert!(!interp.interrupt_handle().is_interrupted());
+}
+
+#[test]
+fn call_depth_limit() {
+    let mut interp = limited(ExecutionLimits { max_call_depth: Some(50), ..Default::default() });
+    assert_terminated(try_run_in(&mut interp, "function f(n) { return f(n + 1); } f(0);"));
+    assert_eq!(interp.take_termination(), Some(Termination::CallDepth(50)));
+    assert_eq!(as_num(run_in(&mut interp, "function g(n) { return n ? g(n - 1) + 1 : 0; } return g(40);")), 40.0);
+}
+
+#[test]
+fn vm_loop_is_budgeted() {
+    let mut interp = limited(ExecutionLimits { max_steps: Some(10_000), ..Default::default() });
+    let err = interp.eval_via_vm("let n = 0; try { while (true) { n++; } } catch (e) { n = -1; } n;").unwrap_err();
+    assert!(err.contains(TERMINATION_PREFIX), "{err}");
+    assert_eq!(interp.take_termination(), Some(Termination::StepLimit(10_000)));
+}
+
+#[test]
+fn timer_callback_has_own_budget() {
+    let mut interp = limited(ExecutionLimits { max_steps: Some(10_000), ..Default::default() });
+    run_in(&mut interp, "var done = 0; setTimeout(() => { while (true) {} }, 0); setTimeout(() => { done = 1; }, 0);");
+    let _ = interp.drain_timers();
+    let _ = interp.drain_timers();
+    assert_eq!(as_num(run_in(&mut interp, "return done;")), 1.0);
+}
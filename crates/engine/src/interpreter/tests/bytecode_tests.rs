@@ -507,6 +507,48 @@ fn vm_generator_done_flag() {
     assert_jv!(r, JsValue::Bool(true));
 }
 
+#[test]
+fn vm_generator_next_sends_value() {
+    let r = run_vm(r#"
+        function* g() {
+            let a = yield 1;
+            let b = yield a + 1;
+            yield a + b;
+        }
+        let it = g();
+        it.next();
+        it.next(10);
+        it.next(5).value
+    "#).unwrap();
+    assert_jv!(r, n(15.0));
+}
+
+#[test]
+fn vm_generator_throw_and_return() {
+    let r = run_vm(r#"
+        function* g() {
+            try { yield 1; } catch (e) { yield e + 1; }
+            yield 100;
+        }
+        let it = g();
+        it.next();
+        let caught = it.throw(41).value;
+        let ret = it.return(7);
+        caught + ret.value + (it.next().done ? 1 : 0)
+    "#).unwrap();
+    assert_jv!(r, n(50.0));
+}
+
+#[test]
+fn vm_tagged_template() {
+    let r = run_vm_with_globals(r#"
+        function tag(s, a, b) { return s.length + s[1] + (a + b) + s.raw[2]; }
+        const o = { k: 2, m(s, v) { return this.k * v; } };
+        tag`x${1}-${2}\n` + String.raw`\t` + o.m`${21}`
+    "#).unwrap();
+    assert_jv!(r, JsValue::Str("3-3\\n\\t42".to_string()));
+}
+
 #[test]
 fn vm_block_let_shadowing() {
     let r = run_vm(r#"
@@ -1195,16 +1237,15 @@ fn vm_closure_captures_multiple_vars() {
 }
 
 #[test]
-fn vm_closure_capture_by_value() {
-    // Closure capturuje hodnotu pri vzniku funkce, ne pozdejsi mutation.
+fn vm_closure_sees_later_assignment() {
+    // Closure sdili vazbu (bunku), pozdejsi prirazeni je videt.
     let r = run_vm(r#"
         let x = 5;
         function readX() { return x; }
         x = 999;
         readX()
     "#).unwrap();
-    // By-value semantics: readX vraci 5 (snapshot pri creation), ne 999.
-    assert_jv!(r, n(5.0));
+    assert_jv!(r, n(999.0));
 }
 
 #[test]
@@ -1259,3 +1300,536 @@ fn vm_nested_for() {
     "#).unwrap();
     assert_jv!(r, n(36.0));
 }
+
+#[test]
+fn vm_proxy_get_set_traps() {
+    let r = run_vm_with_globals(r#"
+        const target = { x: 1 };
+        const p = new Proxy(target, {
+            get: function(t, k) { return k === "x" ? t[k] * 100 : 0; },
+            set: function(t, k, v) { t[k] = v + 1; return true; },
+        });
+        p.x = 4;
+        p["y"] = 1;
+        p.x + target.y + p.y
+    "#).unwrap();
+    assert_jv!(r, n(502.0));
+}
+
+#[test]
+fn vm_proxy_apply_construct() {
+    let r = run_vm_with_globals(r#"
+        function twice(a) { return a * 2; }
+        const f = new Proxy(twice, { apply: function(t, self, args) { return t(args[0]) + 1; } });
+        const C = new Proxy(function() {}, { construct: function(t, args) { return { v: args[0] }; } });
+        f(5) + new C(7).v
+    "#).unwrap();
+    assert_jv!(r, n(18.0));
+}
+
+// ─── Plny AST povrch: try/finally, tridy, destrukturovani, labely, generatory ──
+
+#[test]
+fn vm_try_finally_runs_on_return_and_throw() {
+    let r = run_vm(r#"
+        let log = [];
+        function f() {
+            try { log.push("t"); return 1; } finally { log.push("f"); }
+        }
+        function g() {
+            try { throw "boom"; } catch (e) { log.push(e); } finally { log.push("g"); }
+            return 2;
+        }
+        f() + g() + log.join(",")
+    "#).unwrap();
+    assert_jv!(r, JsValue::Str("3t,f,boom,g".to_string()));
+}
+
+#[test]
+fn vm_finally_rethrows_uncaught() {
+    let r = run_vm(r#"
+        let seen = 0;
+        try {
+            try { throw new Error("x"); } finally { seen = 1; }
+        } catch (e) { seen = seen + 10; }
+        seen
+    "#).unwrap();
+    assert_jv!(r, n(11.0));
+}
+
+#[test]
+fn vm_labeled_break_continue() {
+    let r = run_vm(r#"
+        let s = 0;
+        outer: for (let i = 0; i < 5; i++) {
+            for (let j = 0; j < 5; j++) {
+                if (j === 3) continue outer;
+                if (i === 3) break outer;
+                s += 1;
+            }
+        }
+        s
+    "#).unwrap();
+    assert_jv!(r, n(9.0));
+}
+
+#[test]
+fn vm_switch_fallthrough_and_default() {
+    let r = run_vm(r#"
+        function f(x) {
+            let out = "";
+            switch (x) {
+                case 1: out += "a";
+                case 2: out += "b"; break;
+                default: out += "d";
+            }
+            return out;
+        }
+        f(1) + f(2) + f(7)
+    "#).unwrap();
+    assert_jv!(r, JsValue::Str("abbd".to_string()));
+}
+
+#[test]
+fn vm_class_super_getters_static() {
+    let r = run_vm(r#"
+        class A {
+            constructor(x) { this.x = x; }
+            get double() { return this.x * 2; }
+            hello() { return "A" + this.x; }
+            static make() { return new this(5); }
+        }
+        class B extends A {
+            constructor(x) { super(x + 1); }
+            hello() { return "B" + super.hello(); }
+        }
+        let b = new B(1);
+        b.hello() + b.double + A.make().x + (b instanceof A)
+    "#).unwrap();
+    assert_jv!(r, JsValue::Str("BA245true".to_string()));
+}
+
+#[test]
+fn vm_class_requires_new() {
+    let err = run_vm("class A {} A()").unwrap_err();
+    assert!(err.contains("cannot be invoked without 'new'"), "{err}");
+}
+
+#[test]
+fn vm_class_fields_and_private_members() {
+    let r = run_vm(r#"
+        let log = [];
+        class A {
+            #x = 1;
+            a = log.push("A field");
+            constructor() { log.push("A ctor"); }
+            #inc() { return ++this.#x; }
+            get #twice() { return this.#x * 2; }
+            bump() { this.#inc(); this.#x += 10; return this.#twice; }
+            static has(o) { return #x in o; }
+        }
+        class B extends A {
+            b = log.push("B field");
+            ["k" + 1] = "computed";
+            constructor() { log.push("B before"); super(); log.push("B ctor"); }
+        }
+        class C extends B { accessor c = 3; }
+        let o = new C();
+        [o.bump(), A.has(o), A.has({}), o.k1, o.c, log.join(",")].join("|")
+    "#).unwrap();
+    assert_jv!(r, JsValue::Str("24|true|false|computed|3|B before,A field,A ctor,B field,B ctor".to_string()));
+}
+
+#[test]
+fn vm_class_static_blocks_and_private_statics() {
+    let r = run_vm(r#"
+        class R {
+            static #n = 1;
+            static count = R.#n + 1;
+            static { this.count *= 10; }
+            static #twice() { return this.count * 2; }
+            static get total() { return R.#twice(); }
+        }
+        R.total
+    "#).unwrap();
+    assert_jv!(r, n(40.0));
+}
+
+#[test]
+fn vm_class_private_brand_check_errors() {
+    let err = run_vm("class A { #x = 1; static get(o) { return o.#x; } } A.get({})").unwrap_err();
+    assert!(err.contains("Cannot read private member #x"), "{err}");
+    let err = run_vm(r#"
+        class Base { constructor(o) { return o; } }
+        class Stamp extends Base { #id = 1; }
+        let o = {};
+        new Stamp(o);
+        new Stamp(o);
+    "#).unwrap_err();
+    assert!(err.contains("Cannot initialize #id twice"), "{err}");
+    let err = run_vm("class A { m() { return this.#y; } }").unwrap_err();
+    assert!(err.contains("Private field"), "{err}");
+}
+
+#[test]
+fn vm_destructuring_with_defaults_and_rest() {
+    let r = run_vm(r#"
+        const { a, b: { c = 3 } = {}, ...rest } = { a: 1, x: 2, y: 4 };
+        const [p, , q = 9, ...tail] = [10, 20, undefined, 30, 40];
+        function f({ m, n = 2 }, [k]) { return m + n + k; }
+        a + c + rest.x + rest.y + p + q + tail.length + f({ m: 1 }, [100])
+    "#).unwrap();
+    assert_jv!(r, n(1.0 + 3.0 + 2.0 + 4.0 + 10.0 + 9.0 + 2.0 + 103.0));
+}
+
+#[test]
+fn vm_generator_return_runs_finally() {
+    let r = run_vm_with_globals(r#"
+        let log = [];
+        function* g() {
+            try { yield 1; yield 2; } finally { log.push("cleanup"); }
+        }
+        const it = g();
+        const a = it.next().value;
+        const r = it.return(7);
+        a + r.value + String(r.done) + log.join() + String(it.next().done)
+    "#).unwrap();
+    assert_jv!(r, JsValue::Str("8truecleanuptrue".to_string()));
+}
+
+#[test]
+fn vm_generator_for_of_and_delegate() {
+    let r = run_vm(r#"
+        function* inner() { yield 2; yield 3; }
+        function* outer() { yield 1; yield* inner(); yield 4; }
+        let s = "";
+        for (const v of outer()) s += v;
+        s
+    "#).unwrap();
+    assert_jv!(r, JsValue::Str("1234".to_string()));
+}
+
+#[test]
+fn vm_async_await_chain_and_rejection() {
+    let r = run_vm(r#"
+        async function one() { return 1; }
+        async function two() { return (await one()) + 1; }
+        async function bad() { throw "nope"; }
+        async function main() {
+            let msg;
+            try { await bad(); } catch (e) { msg = e; }
+            return (await two()) + msg;
+        }
+        await main()
+    "#).unwrap();
+    assert_jv!(r, JsValue::Str("2nope".to_string()));
+}
+
+#[test]
+fn vm_closure_forward_reference() {
+    let r = run_vm_with_globals(r#"
+        function isEven(n) { return n === 0 ? true : isOdd(n - 1); }
+        function isOdd(n) { return n === 0 ? false : isEven(n - 1); }
+        let counter = (() => { let c = 0; return () => ++c; })();
+        counter(); counter();
+        String(isEven(10)) + counter()
+    "#).unwrap();
+    assert_jv!(r, JsValue::Str("true3".to_string()));
+}
+
+#[test]
+fn vm_inline_cache_hits_same_shape() {
+    use crate::interpreter::bytecode::{ic_stats, reset_ic_stats};
+    reset_ic_stats();
+    let r = run_vm(r#"
+        class P { constructor(x, y) { this.x = x; this.y = y; } len() { return this.x + this.y; } }
+        let sum = 0;
+        for (let i = 0; i < 50; i++) { const p = new P(i, 1); p.x = p.x * 2; sum += p.len(); }
+        sum
+    "#).unwrap();
+    assert_jv!(r, JsValue::Number(2500.0));
+    let st = ic_stats();
+    assert!(st.load_hits > 100, "{st}");
+    assert!(st.store_hits > 50, "{st}");
+    assert!(st.call_hits >= 49, "{st}");
+    assert_eq!(st.megamorphic, 0, "{st}");
+}
+
+#[test]
+fn shape_tree_is_bounded() {
+    use crate::interpreter::property_map::{PropertyMap, DICTIONARY_SHAPE};
+    // Mapa, na kterou inline cache nesahla, tvar nezaklada.
+    let mut m = PropertyMap::new();
+    m.insert("a".into(), JsValue::Undefined);
+    assert_eq!(m.shape(), DICTIONARY_SHAPE);
+    assert_ne!(m.track_shape(), DICTIONARY_SHAPE);
+    // Slovnikove pouziti (ruzne klice z jednoho tvaru) prejde do slovnikoveho rezimu.
+    let shapes: Vec<_> = (0..40).map(|i| {
+        let mut m = PropertyMap::new();
+        m.insert("fanout".into(), JsValue::Undefined);
+        m.insert(format!("k{i}").into(), JsValue::Undefined);
+        m.track_shape()
+    }).collect();
+    assert!(shapes[..16].iter().all(|&s| s != DICTIONARY_SHAPE));
+    assert!(shapes[16..].iter().all(|&s| s == DICTIONARY_SHAPE));
+    // Stejne tak objekt s mnoha klici.
+    let mut big = PropertyMap::new();
+    big.track_shape();
+    for i in 0..40 {
+        big.insert(format!("p{i}").into(), JsValue::Undefined);
+    }
+    assert_eq!(big.shape(), DICTIONARY_SHAPE);
+}
+
+#[test]
+fn vm_inline_cache_polymorphic_and_invalidation() {
+    let r = run_vm_with_globals(r#"
+        const objs = [{a: 1}, {b: 0, a: 2}, {c: 0, b: 0, a: 3}];
+        let s = 0;
+        for (let k = 0; k < 3; k++) for (const o of objs) s += o.a;
+        const proto = { v() { return "proto"; } };
+        const o = Object.create(proto);
+        let out = "";
+        for (let k = 0; k < 3; k++) {
+            out += o.v() + ",";
+            if (k === 0) o.v = () => "own";
+            if (k === 1) delete o.v;
+        }
+        const g = { x: 1 };
+        let xs = "";
+        for (let k = 0; k < 2; k++) {
+            xs += g.x;
+            Object.defineProperty(g, "x", { get() { return 7; } });
+        }
+        s + ":" + out + xs
+    "#).unwrap();
+    assert_jv!(r, JsValue::Str("18:proto,own,proto,17".to_string()));
+}
+
+#[test]
+fn vm_typed_array_bytes_and_callbacks() {
+    let r = run_vm_with_globals(r#"
+        const ta = new Uint8Array(new ArrayBuffer(4));
+        ta[0] = 300; ta[1] = 5; ta[2] = 1;
+        ta.sort();
+        const m = ta.map(x => x + 1);
+        const dv = new DataView(ta.buffer);
+        ta.join(",") + "|" + (m instanceof Uint8Array) + "|" + m[3] + "|" + dv.getUint16(2) + "|" + Uint8Array.BYTES_PER_ELEMENT
+    "#).unwrap();
+    assert_jv!(r, JsValue::Str("0,1,5,44|true|45|1324|1".to_string()));
+}
+
+#[test]
+fn vm_class_decorators() {
+    let r = run_vm_with_globals(r#"
+        const log = [];
+        const dec = (tag) => {
+            log.push("eval " + tag);
+            return (v, ctx) => { log.push(tag + ":" + ctx.kind + ":" + String(ctx.name)); };
+        };
+        function double(fn, ctx) { return function (x) { return fn.call(this, x) * 2; }; }
+        const plus = (n) => (_, ctx) => (v) => v + n;
+        function clamp(target, ctx) {
+            ctx.metadata.clamped = String(ctx.name);
+            return { set(v) { target.set.call(this, Math.min(v, 10)); }, init(v) { return v * 100; } };
+        }
+        let inits = [];
+        @dec("c")
+        @((C, ctx) => { ctx.addInitializer(function () { inits.push(new this().tag()); }); return class extends C { tag() { return "wrapped"; } }; })
+        class K {
+            @dec("f") @plus(5) @plus(1) n = 1;
+            @dec("m") @double calc(x) { return x + 1; }
+            @dec("s") static s = 2;
+            @clamp accessor #size = 1;
+            @((v, ctx) => { ctx.addInitializer(function () { inits.push(String(this.n)); }); }) #hidden() {}
+            bump() { this.#size = 50; return this.#size; }
+            get size() { return this.#size; }
+        }
+        const k = new K();
+        [log.join(","), k.calc(2), k.n, k.size, k.bump(), k.tag(), inits.join(), K[Symbol.metadata].clamped].join("|")
+    "#).unwrap();
+    assert_jv!(r, JsValue::Str(
+        "eval c,eval f,eval m,eval s,m:method:calc,s:field:s,f:field:n,c:class:K|6|7|100|10|wrapped|undefined,wrapped,undefined|#size".to_string()
+    ));
+}
+
+#[test]
+fn vm_class_decorator_errors() {
+    let err = run_vm("function bad() { return 1; } class A { @bad m() {} }").unwrap_err();
+    assert!(err.contains("method decorators must return a function or undefined"), "{err}");
+    let err = run_vm("const box = {}; class A { @((v, ctx) => { box.late = ctx.addInitializer; }) x; } box.late(() => {})").unwrap_err();
+    assert!(err.contains("addInitializer called after decoration is finished"), "{err}");
+}
+
+#[test]
+fn vm_tdz_and_const_assignment() {
+    let err = run_vm("x; let x = 1;").unwrap_err();
+    assert!(err.contains("ReferenceError: Cannot access 'x' before initialization"), "{err}");
+    let err = run_vm("const read = () => late; read(); let late = 1;").unwrap_err();
+    assert!(err.contains("Cannot access 'late' before initialization"), "{err}");
+    let err = run_vm("const c = 1; c = 2;").unwrap_err();
+    assert!(err.contains("TypeError: Assignment to constant variable."), "{err}");
+    let err = run_vm("const n = 1; function bump() { n++; } bump();").unwrap_err();
+    assert!(err.contains("Assignment to constant variable."), "{err}");
+    assert_jv!(run_vm("const read = () => late; let late = 4; read()").unwrap(), n(4.0));
+}
+
+#[test]
+fn vm_strict_mode_semantics() {
+    let err = run_vm_with_globals(r#""use strict"; leaked = 1;"#).unwrap_err();
+    assert!(err.contains("ReferenceError: leaked is not defined"), "{err}");
+    let err = run_vm_with_globals(r#""use strict"; const o = Object.freeze({ a: 1 }); o.a = 2;"#).unwrap_err();
+    assert!(err.contains("Cannot assign to read only property 'a'"), "{err}");
+    let err = run_vm_with_globals(r#""use strict"; const o = Object.freeze({ a: 1 }); delete o["a"];"#).unwrap_err();
+    assert!(err.contains("Cannot delete property 'a'"), "{err}");
+    let r = run_vm_with_globals(r#"
+        function sloppy() { return this === undefined; }
+        function strict() { "use strict"; return this === undefined; }
+        class K { static who() { return (function () { return this; })() === undefined; } }
+        const o = Object.freeze({ a: 1 });
+        o.a = 2;
+        [sloppy(), strict(), K.who(), o.a].join("|")
+    "#).unwrap();
+    assert_jv!(r, JsValue::Str("false|true|true|1".to_string()));
+}
+
+#[test]
+fn vm_using_declarations() {
+    let r = run_vm_with_globals(r#"
+        const log = [];
+        const res = (name, fail) => ({ name, [Symbol.dispose]() { log.push("dispose " + name); if (fail) throw new Error(name); } });
+        function scoped() {
+            using a = res("a");
+            using b = res("b"), none = undefined;
+            log.push("body");
+            return a.name + b.name;
+        }
+        log.push(scoped());
+        for (using r of [res("x"), res("y")]) { log.push("use " + r.name); }
+        function failing() {
+            try {
+                using a = res("fa", true);
+                using b = res("fb", true);
+                throw new Error("body");
+            } catch (e) {
+                return [e instanceof SuppressedError, e.error.message, e.suppressed.error.message, e.suppressed.suppressed.message].join("/");
+            }
+        }
+        log.push(failing());
+        log.join(",")
+    "#).unwrap();
+    assert_jv!(r, JsValue::Str("body,dispose b,dispose a,ab,use x,dispose x,use y,dispose y,dispose fb,dispose fa,true/fa/fb/body".to_string()));
+    let err = run_vm_with_globals("function f() { using x = 1; } f();").unwrap_err();
+    assert!(err.contains("TypeError"), "{err}");
+}
+
+#[test]
+fn vm_debug_table_maps_pc_to_statement_span() {
+    let src = "let a = 1; function f() { return null.x; } f();";
+    let code = compile_program(&parse_to_stmts(src)).unwrap();
+    let columns: Vec<u32> = code.spans.iter().map(|(_, s)| s.start.column).collect();
+    assert_eq!(columns.first(), Some(&0));
+    assert_eq!(code.span_at(code.bytecode.len() - 1).map(|s| s.start.column), Some(43));
+    // Chyba nese pozici nejvnitrnejsiho prikazu (uvnitr `f`), ne volani.
+    let err = VM::new().run(&code).unwrap_err();
+    let span = crate::interpreter::bytecode::error_span(&err).expect("span");
+    assert_eq!((span.start.line, span.start.column), (1, 26));
+}
+
+#[test]
+fn vm_closures_have_distinct_identity() {
+    let r = run_vm_with_globals(r#"
+        const make = () => function () {};
+        const a = make();
+        const b = make();
+        a.tag = "a";
+        const s = new Set([a, b, a]);
+        return [s.size, a === b, b.tag === undefined, a.prototype !== b.prototype].join("|");
+    "#).unwrap();
+    assert_eq!(r.to_string(), "2|false|true|true");
+}
+
+// ─── Most VM <-> tree-walker ─────────────────────────────────────────────────
+
+/// Interpret, jehoz globalni funkce definuje tree-walker (prime `eval` VM
+/// nezkompiluje, skript bezi cely v tree-walkeru).
+fn interp_with_tree_walker_fns(src: &str) -> crate::interpreter::Interpreter {
+    let mut interp = crate::interpreter::Interpreter::new();
+    let program = Parser::new(Lexer::parse_str(&format!("{src}\neval('');"), "test").expect("lex").tokens).parse().expect("parse");
+    assert!(compile_program(&program.body).is_err());
+    interp.run(&program).expect("tree-walker skript");
+    interp
+}
+
+fn run_bridged(interp: &mut crate::interpreter::Interpreter, src: &str) -> Result<JsValue, String> {
+    let code = compile_program(&parse_to_stmts(src)).map_err(|s| s.to_string())?;
+    interp.run_bytecode(&code)
+}
+
+#[test]
+fn vm_calls_and_constructs_tree_walker_functions() {
+    let mut interp = interp_with_tree_walker_fns(r#"
+        function double(x) { return x * 2; }
+        class Point { constructor(x) { this.x = x; } }
+        function applyTo(f, v) { return f(v) + 1; }
+    "#);
+    let r = run_bridged(&mut interp, r#"
+        const p = new Point(4);
+        [double(21), p.x, p instanceof Point, applyTo(v => v * 10, 3)].join("|")
+    "#).unwrap();
+    assert_eq!(r.to_string(), "42|4|true|31");
+}
+
+#[test]
+fn vm_script_globals_visible_to_tree_walker() {
+    let mut interp = interp_with_tree_walker_fns("function readCounter() { return counter; }");
+    run_bridged(&mut interp, "var counter = 1; function bump() { counter++; } bump();").unwrap();
+    let r = run_bridged(&mut interp, "readCounter()").unwrap();
+    assert_jv!(r, n(2.0));
+}
+
+#[test]
+fn vm_tree_walker_error_is_catchable() {
+    let mut interp = interp_with_tree_walker_fns("function fail() { throw new TypeError('bad'); }");
+    let r = run_bridged(&mut interp, r#"
+        let r = "no";
+        try { fail(); } catch (e) { r = e instanceof TypeError && e.message; }
+        r
+    "#).unwrap();
+    assert_jv!(r, JsValue::Str("bad".to_string()));
+}
+
+#[test]
+fn vm_proxy_traps_call_tree_walker_functions() {
+    let mut interp = interp_with_tree_walker_fns(r#"
+        function twGet(t, k) { return k + "!"; }
+        function twHas(t, k) { return k === "yes"; }
+        function twTarget(a, b) { return a + b; }
+        function twApply(t, self, args) { return t(args[0], args[1]) * 10; }
+    "#);
+    let r = run_bridged(&mut interp, r#"
+        const p = new Proxy({}, { get: twGet, has: twHas });
+        const f = new Proxy(twTarget, { apply: twApply });
+        const g = new Proxy(twTarget, {});
+        [p.foo, "yes" in p, "no" in p, f(1, 2), g(3, 4)].join("|")
+    "#).unwrap();
+    assert_eq!(r.to_string(), "foo!|true|false|30|7");
+}
+
+#[test]
+fn vm_pending_promise_reactions_run_from_task_queue() {
+    let mut interp = crate::interpreter::Interpreter::new();
+    let program = Parser::new(Lexer::parse_str(r#"
+        var order = [];
+        let resolve;
+        const p = new Promise(r => { resolve = r; });
+        p.then(v => order.push("then " + v));
+        resolve(1);
+        order.push("sync");
+        Promise.resolve(2).then(v => order.push("settled " + v));
+    "#, "test").expect("lex").tokens).parse().expect("parse");
+    assert!(compile_program(&program.body).is_ok());
+    interp.run(&program).unwrap();
+    let order = run_bridged(&mut interp, "order.join()").unwrap();
+    assert_eq!(order.to_string(), "sync,settled 2,then 1");
+}
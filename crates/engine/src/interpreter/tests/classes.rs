@@ -1,4 +1,5 @@
-/// Tridy - constructor, methods, static, inheritance, super, getter/setter.
+/// Tridy - constructor, methods, static, inheritance, super, getter/setter,
+/// pole, privatni `#cleny`, `static {}` bloky a `accessor`.
 
 use super::helpers::*;
 
@@ -216,3 +217,223 @@ fn class_method_uses_this() {
         return r.area() + r.perimeter();
     "#)), 26.0);
 }
+
+#[test]
+fn class_private_fields_methods_and_accessors() {
+    assert_eq!(as_str(run(r#"
+        class Counter {
+            #count = 0;
+            #step;
+            constructor(step) { this.#step = step; }
+            #bump() { this.#count += this.#step; return this; }
+            get #doubled() { return this.#count * 2; }
+            set #reset(v) { this.#count = v; }
+            inc() { return this.#bump(); }
+            report() { this.#count++; return this.#count + ":" + this.#doubled; }
+            clear() { this.#reset = 0; return this.#count; }
+        }
+        const c = new Counter(5);
+        c.inc().inc();
+        return [c.report(), c.clear(), Object.keys(c).length, JSON.stringify(c)].join("|");
+    "#)), "11:22|0|0|{}");
+}
+
+#[test]
+fn class_private_brand_checks() {
+    assert_eq!(as_str(run(r#"
+        class A {
+            #x = 1;
+            #m() {}
+            get #g() { return 1; }
+            static has(o) { return #x in o; }
+            static read(o) { return o.#x; }
+            static writeMethod(o) { o.#m = 1; }
+            static writeGetter(o) { o.#g = 1; }
+        }
+        const err = f => { try { f(); return "ok"; } catch (e) { return String(e); } };
+        return [
+            A.has(new A()), A.has({}),
+            err(() => A.read({})),
+            err(() => A.writeMethod(new A())),
+            err(() => A.writeGetter(new A())),
+            err(() => A.has(1)).split(":")[0],
+        ].join("|");
+    "#)), "true|false|TypeError: Cannot read private member #x from an object whose class did not declare it|\
+           TypeError: Private method #m is not writable|TypeError: '#g' was defined without a setter|TypeError");
+}
+
+#[test]
+fn class_private_names_are_per_class_evaluation() {
+    assert_eq!(as_str(run(r#"
+        function make() { return class { #v = 1; static get(o) { return o.#v; } }; }
+        const A = make(), B = make();
+        let r;
+        try { A.get(new B()); r = "leak"; } catch (e) { r = "blocked"; }
+        return A.get(new A()) + r;
+    "#)), "1blocked");
+}
+
+#[test]
+fn class_public_fields_order_with_inheritance() {
+    assert_eq!(as_str(run(r#"
+        const log = [];
+        class Base {
+            a = log.push("base field");
+            constructor() { log.push("base ctor"); }
+        }
+        class Derived extends Base {
+            b = log.push("derived field");
+            ["c" + 1] = this.b + 1;
+            constructor() { log.push("before super"); super(); log.push("derived ctor"); }
+        }
+        class Implicit extends Derived { d = 4; }
+        const o = new Implicit();
+        return log.join(",") + "|" + Object.keys(o).join(",") + "|" + o.c1;
+    "#)), "before super,base field,base ctor,derived field,derived ctor|a,b,c1,d|5");
+}
+
+#[test]
+fn class_static_fields_blocks_and_private_statics() {
+    assert_eq!(as_str(run(r#"
+        const log = [];
+        class Registry {
+            static #items = [];
+            static count = 0;
+            static { log.push("block " + this.count); this.count = 10; }
+            static add(x) { Registry.#items.push(x); return ++this.count; }
+            static #secret() { return "s" + this.#items.length; }
+            static get size() { return this.#items.length; }
+            static reveal() { return this.#secret(); }
+            static self = Registry.count + 1;
+        }
+        class Sub extends Registry {}
+        Registry.add("a");
+        return [log.join(), Registry.count, Registry.size, Registry.reveal(), Registry.self, Sub.self].join("|");
+    "#)), "block 0|11|1|s1|11|11");
+}
+
+#[test]
+fn class_auto_accessor() {
+    assert_eq!(as_str(run(r#"
+        class Box {
+            accessor value = 1;
+            static accessor count = 0;
+            bump() { this.value += 1; Box.count++; return this.value; }
+        }
+        const b = new Box();
+        b.bump(); b.bump();
+        return [b.value, Box.count, Object.keys(b).length, "value" in b].join("|");
+    "#)), "3|2|0|true");
+}
+
+#[test]
+fn class_field_syntax_edge_cases() {
+    assert_eq!(as_str(run(r#"
+        class K {
+            static = "s";
+            get = "g";
+            set;
+            accessor;
+            'quoted' = 1;
+            42 = "n";
+            static static() { return "m"; }
+        }
+        const k = new K();
+        return [k.static, k.get, k.set === undefined, "accessor" in k, k.quoted, k[42], K.static()].join("|");
+    "#)), "s|g|true|true|1|n|m");
+}
+
+#[test]
+fn class_undeclared_private_name_is_error() {
+    let err = try_run("class A { m() { return this.#nope; } } return new A().m();").unwrap_err();
+    assert!(format!("{err:?}").contains("#nope"), "{err:?}");
+}
+
+#[test]
+fn decorators_evaluation_and_application_order() {
+    assert_eq!(as_str(run(r#"
+        const log = [];
+        const dec = (tag) => {
+            log.push("eval " + tag);
+            return (v, ctx) => { log.push(tag + ":" + ctx.kind + ":" + String(ctx.name)); };
+        };
+        @dec("c1") @dec("c2")
+        class A {
+            @dec("f") x = 1;
+            @dec("m1") @dec("m2") method() {}
+            @dec("sf") static y = 2;
+            @dec("g") static get z() { return 1; }
+            @dec("a") accessor #p = 3;
+        }
+        return log.join(",");
+    "#)),
+        "eval c1,eval c2,eval f,eval m1,eval m2,eval sf,eval g,eval a,\
+         g:getter:z,m2:method:method,m1:method:method,a:accessor:#p,sf:field:y,f:field:x,c2:class:A,c1:class:A");
+}
+
+#[test]
+fn decorators_replace_members_and_add_initializers() {
+    assert_eq!(as_str(run(r#"
+        const seen = [];
+        function double(fn, ctx) { return function (x) { return fn.call(this, x) * 2; }; }
+        const plus = (n) => (_, ctx) => (v) => v + n;
+        function track(_, ctx) {
+            ctx.addInitializer(function () { seen.push(String(ctx.name) + "@" + (this instanceof K)); });
+        }
+        function clamp(target, ctx) {
+            return {
+                get() { return target.get.call(this); },
+                set(v) { target.set.call(this, Math.min(v, 10)); },
+                init(v) { return v * 100; },
+            };
+        }
+        class K {
+            @double calc(x) { return x + 1; }
+            @plus(5) @plus(1) n = 1;
+            @track hello() {}
+            @clamp accessor size = 1;
+        }
+        const k = new K();
+        const before = k.size;
+        k.size = 50;
+        return [k.calc(2), k.n, seen.join(), before, k.size].join("|");
+    "#)), "6|7|hello@true|100|10");
+}
+
+#[test]
+fn decorators_access_metadata_and_class_replacement() {
+    assert_eq!(as_str(run(r#"
+        let secret, count;
+        function meta(key) { return (v, ctx) => { ctx.metadata[key] = String(ctx.name); }; }
+        @meta("cls") class Base { @meta("m") m() {} }
+        class Derived extends Base { @meta("f") f; }
+        class P {
+            @((v, ctx) => { secret = ctx.access; }) #secret = 42;
+            @((v, ctx) => { count = ctx.access; }) static count = 1;
+        }
+        const p = new P();
+        count.set(P, 5);
+        let inits = [];
+        @((C, ctx) => { ctx.addInitializer(function () { inits.push(new this().tag()); }); return class extends C { tag() { return "wrapped"; } }; })
+        class Wrapped { }
+        const bm = Base[Symbol.metadata], dm = Derived[Symbol.metadata];
+        return [bm.cls, bm.m, dm.f, dm.m, Object.getPrototypeOf(dm) === bm,
+                secret.get(p), secret.has(p), secret.has({}), P.count,
+                new Wrapped().tag(), inits.join()].join("|");
+    "#)), "Base|m|f|m|true|42|true|false|5|wrapped|wrapped");
+}
+
+#[test]
+fn decorators_errors() {
+    let err = try_run("function bad() { return 1; } class A { @bad m() {} }").unwrap_err();
+    assert!(format!("{err:?}").contains("method decorators must return a function or undefined"), "{err:?}");
+    let err = try_run(r#"
+        let late;
+        function keep(v, ctx) { late = ctx.addInitializer; }
+        class A { @keep m() {} }
+        late(() => {});
+    "#).unwrap_err();
+    assert!(format!("{err:?}").contains("addInitializer called after decoration is finished"), "{err:?}");
+    let err = try_run("function f(v, ctx) { return 1; } class A { @f accessor x; }").unwrap_err();
+    assert!(format!("{err:?}").contains("accessor decorators must return an object or undefined"), "{err:?}");
+}
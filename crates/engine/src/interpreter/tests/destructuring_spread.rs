@@ -24,6 +24,12 @@ fn array_destructuring_rest() {
     assert_eq!(as_num(run("const [a, ...rest] = [1, 2, 3]; return rest[0];")), 2.0);
 }
 
+#[test]
+fn object_destructuring_rest() {
+    assert_eq!(as_num(run("const { a, ...rest } = { a: 1, b: 2, c: 3 }; return rest.b + rest.c;")), 5.0);
+    assert_eq!(as_num(run("const { a, ...rest } = { a: 1 }; return Object.keys(rest).length;")), 0.0);
+}
+
 #[test]
 fn object_destructuring_basic() {
     assert_eq!(as_num(run("const { x, y } = { x: 10, y: 20 }; return x + y;")), 30.0);
@@ -0,0 +1,106 @@
+/// Explicit resource management: `using` / `await using`, DisposableStack,
+/// AsyncDisposableStack a SuppressedError.
+
+use super::helpers::*;
+
+const RESOURCE: &str = r#"
+    const log = [];
+    function res(name, fail) {
+        return { name, [Symbol.dispose]() { log.push("dispose " + name); if (fail) throw new Error(name); } };
+    }
+"#;
+
+fn run_with_resource(src: &str) -> String {
+    as_str(run(&format!("{RESOURCE}{src}")))
+}
+
+#[test]
+fn using_disposes_in_reverse_order_at_block_exit() {
+    assert_eq!(run_with_resource(r#"
+        {
+            using a = res("a");
+            using b = res("b"), c = null;
+            log.push("body " + a.name + b.name);
+        }
+        log.push("after");
+        return log.join(",");
+    "#), "body ab,dispose b,dispose a,after");
+}
+
+#[test]
+fn using_disposes_on_return_break_and_throw() {
+    assert_eq!(run_with_resource(r#"
+        function f() { using r = res("f"); return "ret"; }
+        log.push(f());
+        for (const n of [1, 2]) { using r = res("loop" + n); if (n === 1) continue; break; }
+        try { using r = res("t"); throw new Error("boom"); } catch (e) { log.push(e.message); }
+        return log.join(",");
+    "#), "dispose f,ret,dispose loop1,dispose loop2,dispose t,boom");
+}
+
+#[test]
+fn disposal_errors_aggregate_as_suppressed_error() {
+    assert_eq!(run_with_resource(r#"
+        try {
+            using a = res("a", true);
+            using b = res("b", true);
+            throw new Error("body");
+        } catch (e) {
+            return [e instanceof SuppressedError, e.error.message, e.suppressed.name,
+                    e.suppressed.error.message, e.suppressed.suppressed.message].join("|");
+        }
+    "#), "true|a|SuppressedError|b|body");
+    let err = format!("{:?}", try_run("{ using x = 42; }").unwrap_err());
+    assert!(err.contains("TypeError"), "{err}");
+}
+
+#[test]
+fn for_of_using_disposes_each_iteration() {
+    assert_eq!(run_with_resource(r#"
+        for (using r of [res("x"), res("y")]) { log.push("use " + r.name); }
+        return log.join(",");
+    "#), "use x,dispose x,use y,dispose y");
+}
+
+#[test]
+fn await_using_awaits_async_dispose() {
+    assert_eq!(as_str(run(r#"
+        const log = [];
+        async function main() {
+            {
+                await using a = { [Symbol.asyncDispose]: async () => { log.push("async a"); } };
+                await using b = { [Symbol.dispose]() { log.push("sync b"); } };
+                log.push("body");
+            }
+            return log.join(",");
+        }
+        let out;
+        main().then(v => out = v);
+        return out;
+    "#)), "body,sync b,async a");
+}
+
+#[test]
+fn disposable_stack_methods() {
+    assert_eq!(run_with_resource(r#"
+        const stack = new DisposableStack();
+        stack.use(res("u"));
+        stack.adopt(7, v => log.push("adopt " + v));
+        stack.defer(() => log.push("defer"));
+        const moved = stack.move();
+        log.push(stack.disposed + "/" + moved.disposed);
+        moved.dispose();
+        moved.dispose();
+        try { stack.use(res("late")); } catch (e) { log.push(String(e).startsWith("ReferenceError")); }
+        return log.join(",");
+    "#), "true/false,defer,adopt 7,dispose u,true");
+    assert_eq!(as_str(run(r#"
+        const stack = new AsyncDisposableStack();
+        const log = [];
+        stack.defer(async () => log.push("deferred"));
+        let state;
+        stack.disposeAsync().then(() => state = "done");
+        const e = new SuppressedError(1, 2, "msg");
+        return [log.join(","), state, e.message, e.error, e.suppressed].join("|");
+    "#)), "deferred|done|msg|1|2");
+}
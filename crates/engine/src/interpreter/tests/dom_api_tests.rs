@@ -1394,7 +1394,7 @@ fn array_buffer_transfer_marks_detached() {
 #[test]
 fn array_buffer_resize() {
     let code = r#"
-        const ab = new ArrayBuffer(4);
+        const ab = new ArrayBuffer(4, { maxByteLength: 16 });
         ab.resize(8);
         return ab.byteLength;
     "#;
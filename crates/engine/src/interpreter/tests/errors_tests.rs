@@ -351,3 +351,10 @@ fn json_stringify_non_circular_nested_ok() {
     let s = as_str(r);
     assert!(s.contains("\"x\""), "got: {s}");
 }
+
+#[test]
+fn error_stack_has_frames_with_columns() {
+    // Jednoradkovy (minifikovany) skript - ramce rozlisuje az sloupec.
+    let r = run(r#"function inner() { return new Error("boom"); } function outer() { return inner(); } return outer().stack;"#);
+    assert_eq!(as_str(r), "Error: boom\n    at inner (<anonymous>:1:27)\n    at outer (<anonymous>:1:74)\n    at <anonymous>:1:92");
+}
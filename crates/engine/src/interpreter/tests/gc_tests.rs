@@ -0,0 +1,80 @@
+/// Cycle collector: uvolneni cyklu, DOM bridge listenery, heap threshold.
+
+use super::helpers::*;
+use crate::interpreter::{gc, Interpreter, JsValue};
+
+#[test]
+fn gc_frees_closure_cycles_keeps_globals() {
+    let mut interp = Interpreter::new();
+    // `let` - zachycena promenna je bunka (kontejner navic), `const` VM zachyti hodnotou
+    run_in(&mut interp, r#"
+        function make() { let o = {}; o.self = o; o.f = () => o; return 1; }
+        for (let i = 0; i < 100; i++) make();
+    "#);
+    let freed = interp.collect_garbage();
+    assert!(freed >= 200, "freed {freed}");
+    assert_eq!(as_num(run_in(&mut interp, "return make() + 1;")), 2.0);
+}
+
+#[test]
+fn gc_releases_listeners_of_dead_nodes() {
+    let mut interp = Interpreter::new();
+    run_in(&mut interp, r#"
+        function attach() {
+            const el = document.createElement("div");
+            el.addEventListener("click", () => el.id);
+        }
+        for (let i = 0; i < 20; i++) attach();
+        const keep = document.createElement("span");
+        var hits = 0;
+        keep.addEventListener("click", () => { hits++; });
+        const inDoc = document.createElement("p");
+        document.body.appendChild(inDoc);
+        inDoc.addEventListener("click", () => { hits += 10; });
+    "#);
+    assert_eq!(interp.event_callbacks.borrow().len(), 22);
+    interp.collect_garbage();
+    assert_eq!(interp.event_callbacks.borrow().len(), 2);
+    assert_eq!(gc::stats().callbacks_released, 20);
+    assert_eq!(as_num(run_in(&mut interp, "keep.click(); inDoc.click(); return hits;")), 11.0);
+}
+
+#[test]
+fn gc_runs_when_heap_threshold_exceeded() {
+    let mut interp = Interpreter::new();
+    assert_eq!(gc::stats().collections, 0);
+    run_in(&mut interp, r#"
+        for (let i = 0; i < 6000; i++) { const a = {}; const b = { a }; a.b = b; }
+    "#);
+    let s = gc::stats();
+    assert_eq!(s.collections, 1);
+    assert!(s.last_freed >= 12000, "freed {}", s.last_freed);
+    assert!(s.threshold >= gc::GC_MIN_THRESHOLD);
+}
+
+#[test]
+fn gc_weak_map_drops_entries_of_dead_keys() {
+    let mut interp = Interpreter::new();
+    run_in(&mut interp, r#"
+        var wm = new WeakMap();
+        var ws = new WeakSet();
+        var keep = {};
+        wm.set(keep, 1);
+        for (let i = 0; i < 10; i++) {
+            const k = {};
+            // hodnota odkazuje na klic - ephemeron ho nesmi drzet nazivu
+            wm.set(k, { back: k });
+            ws.add(k);
+        }
+    "#);
+    let size = |interp: &Interpreter, name: &str| match interp.global.borrow().get(name) {
+        Some(JsValue::Map(m)) => m.borrow().len(),
+        Some(JsValue::Set(s)) => s.borrow().len(),
+        _ => usize::MAX,
+    };
+    assert_eq!(size(&interp, "wm"), 11);
+    interp.collect_garbage();
+    assert_eq!(size(&interp, "wm"), 1);
+    assert_eq!(size(&interp, "ws"), 0);
+    assert_eq!(as_num(run_in(&mut interp, "return wm.get(keep);")), 1.0);
+}
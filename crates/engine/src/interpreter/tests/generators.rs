@@ -107,10 +107,10 @@ fn symbol_iterator_custom_iterable() {
 }
 
 #[test]
-fn symbol_iterator_string_concat_key() {
+fn symbol_iterator_is_symbol() {
     assert_eq!(as_str(run(r#"
-        return Symbol.iterator;
-    "#)), "Symbol.iterator");
+        return typeof Symbol.iterator + ":" + Symbol.iterator.description;
+    "#)), "symbol:Symbol.iterator");
 }
 
 #[test]
@@ -157,3 +157,206 @@ fn generator_with_params() {
         return sum;
     "#)), 60.0);
 }
+
+#[test]
+fn generator_infinite_lazy() {
+    assert_eq!(as_num(run(r#"
+        function* naturals() {
+            let n = 0;
+            while (true) { yield n++; }
+        }
+        let sum = 0;
+        for (const x of naturals()) {
+            if (x > 4) break;
+            sum += x;
+        }
+        return sum;
+    "#)), 10.0);
+}
+
+#[test]
+fn generator_side_effects_between_yields() {
+    assert_eq!(as_str(run(r#"
+        const log = [];
+        function* g() {
+            log.push("a");
+            yield 1;
+            log.push("b");
+            yield 2;
+            log.push("c");
+        }
+        const it = g();
+        log.push("start");
+        it.next();
+        log.push("mid");
+        it.next();
+        return log.join(",");
+    "#)), "start,a,mid,b");
+}
+
+#[test]
+fn generator_next_sends_value() {
+    assert_eq!(as_num(run(r#"
+        function* acc() {
+            let total = 0;
+            while (true) {
+                const x = yield total;
+                total += x;
+            }
+        }
+        const it = acc();
+        it.next();
+        it.next(5);
+        it.next(10);
+        return it.next(1).value;
+    "#)), 16.0);
+}
+
+#[test]
+fn generator_return_runs_finally() {
+    assert_eq!(as_str(run(r#"
+        const log = [];
+        function* g() {
+            try {
+                yield 1;
+                yield 2;
+            } finally {
+                log.push("cleanup");
+            }
+        }
+        const it = g();
+        it.next();
+        const r = it.return(42);
+        return log.join(",") + ":" + r.value + ":" + r.done + ":" + it.next().done;
+    "#)), "cleanup:42:true:true");
+}
+
+#[test]
+fn generator_throw_caught_inside() {
+    assert_eq!(as_str(run(r#"
+        function* g() {
+            try {
+                yield 1;
+            } catch (e) {
+                yield "caught " + e;
+            }
+        }
+        const it = g();
+        it.next();
+        return it.throw("boom").value;
+    "#)), "caught boom");
+}
+
+#[test]
+fn generator_throw_uncaught_propagates() {
+    assert_eq!(as_str(run(r#"
+        function* g() { yield 1; yield 2; }
+        const it = g();
+        it.next();
+        let msg = "";
+        try { it.throw("bad"); } catch (e) { msg = e; }
+        return msg + ":" + it.next().done;
+    "#)), "bad:true");
+}
+
+#[test]
+fn generator_break_calls_return() {
+    assert_eq!(as_bool(run(r#"
+        let closed = false;
+        function* g() {
+            try { yield 1; yield 2; } finally { closed = true; }
+        }
+        for (const x of g()) { break; }
+        return closed;
+    "#)), true);
+}
+
+#[test]
+fn generator_yield_star_forwards_next_and_result() {
+    assert_eq!(as_str(run(r#"
+        function* inner() {
+            const a = yield "i1";
+            return "ret:" + a;
+        }
+        function* outer() {
+            const r = yield* inner();
+            yield r;
+        }
+        const it = outer();
+        it.next();
+        return it.next("x").value;
+    "#)), "ret:x");
+}
+
+#[test]
+fn generator_already_running() {
+    assert!(try_run(r#"
+        let it;
+        function* g() { it.next(); yield 1; }
+        it = g();
+        it.next();
+    "#).is_err());
+}
+
+#[test]
+fn generator_destructuring_is_lazy() {
+    assert_eq!(as_num(run(r#"
+        function* naturals() { let n = 1; while (true) yield n++; }
+        const [a, b, c] = naturals();
+        return a + b + c;
+    "#)), 6.0);
+}
+
+#[test]
+fn async_generator_for_await() {
+    assert_eq!(as_num(run(r#"
+        async function* ticks() {
+            yield 1;
+            yield Promise.resolve(2);
+            yield 3;
+        }
+        async function main() {
+            let sum = 0;
+            for await (const t of ticks()) { sum += t; }
+            return sum;
+        }
+        let out = 0;
+        main().then(v => { out = v; });
+        return out;
+    "#)), 6.0);
+}
+
+#[test]
+fn generator_deep_recursion_grows_stack() {
+    // Maly pocatecni zasobnik ramce - hluboka rekurze pokracuje na segmentech
+    assert_eq!(as_num(run(r#"
+        function depth(n) { return n === 0 ? 0 : 1 + depth(n - 1); }
+        function* gen() { yield depth(3000); yield depth(10); }
+        const it = gen();
+        return it.next().value + it.next().value;
+    "#)), 3010.0);
+}
+
+#[test]
+fn generator_many_live_frames() {
+    assert_eq!(as_num(run(r#"
+        function* counter(i) { yield i; yield i + 1; }
+        const gens = [];
+        for (let i = 0; i < 2000; i++) { const g = counter(i); g.next(); gens.push(g); }
+        let sum = 0;
+        for (const g of gens) sum += g.next().value;
+        return sum;
+    "#)), 2001000.0);
+}
+
+#[test]
+fn generator_error_stack_includes_resuming_caller() {
+    let r = run(r#"
+        function* gen() { yield 1; yield new Error("x").stack; }
+        const it = gen();
+        it.next();
+        function resumer() { return it.next().value; }
+        return resumer();
+    "#);
+    assert!(as_str(r).contains("at resumer"));
+}
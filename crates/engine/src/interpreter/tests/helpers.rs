@@ -19,6 +19,19 @@ pub fn run(src: &str) -> JsValue {
     interp.run(&program).unwrap()
 }
 
+/// Interpret cross-origin isolated stranky (COOP + COEP) - se SharedArrayBuffer.
+pub fn isolated_interpreter() -> Interpreter {
+    use crate::browser::security::coep_coop::{Coep, Coop};
+    let mut interp = Interpreter::new();
+    interp.set_cross_origin_isolation(Coop::SameOrigin, Coep::RequireCorp);
+    interp
+}
+
+/// Jako `run`, ale v cross-origin isolated interpretu.
+pub fn run_isolated(src: &str) -> JsValue {
+    run_in(&mut isolated_interpreter(), src)
+}
+
 /// Spusti JS vyraz (obali do `return ...;`).
 pub fn eval(expr: &str) -> JsValue {
     run(&format!("return {expr};"))
@@ -73,3 +86,29 @@ pub fn as_bigint_str(v: JsValue) -> String {
         other => panic!("Ocekavan BigInt, nalezeno {other:?}"),
     }
 }
+
+/// Spusti JS na existujicim interpretu (testy co ctou stav interpretu po behu).
+pub fn run_in(interp: &mut Interpreter, src: &str) -> JsValue {
+    let lexer = Lexer::parse_str(src, "<test>").unwrap();
+    let tokens: Vec<_> = lexer.tokens.into_iter()
+        .filter(|t| !matches!(t.kind,
+            TokenKind::Whitespace | TokenKind::Newline
+            | TokenKind::CommentLine(_) | TokenKind::CommentBlock(_)))
+        .collect();
+    let mut parser = Parser::new(tokens);
+    let program = parser.parse().unwrap();
+    interp.run(&program).unwrap()
+}
+
+/// Jako `run_in`, ale vrati Result (chyby behu na sdilenem interpretu).
+pub fn try_run_in(interp: &mut Interpreter, src: &str) -> Result<JsValue, JsError> {
+    let lexer = Lexer::parse_str(src, "<test>").unwrap();
+    let tokens: Vec<_> = lexer.tokens.into_iter()
+        .filter(|t| !matches!(t.kind,
+            TokenKind::Whitespace | TokenKind::Newline
+            | TokenKind::CommentLine(_) | TokenKind::CommentBlock(_)))
+        .collect();
+    let mut parser = Parser::new(tokens);
+    let program = parser.parse().unwrap();
+    interp.run(&program)
+}
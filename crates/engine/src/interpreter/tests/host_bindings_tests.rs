@@ -0,0 +1,144 @@
+/// Typovane host bindings: host_fn, HostClass, HostRef, async host funkce.
+
+use std::cell::Cell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use super::helpers::*;
+use crate::interpreter::host_bindings::{host_fn, HostClass, HostRef, Serde};
+use crate::interpreter::{Interpreter, JsError};
+
+#[derive(Default)]
+struct Counter {
+    n: f64,
+}
+
+fn counter_class() -> HostClass<Counter> {
+    HostClass::<Counter>::builder("Counter")
+        .constructor(|start: Option<f64>| Counter { n: start.unwrap_or(0.0) })
+        .method("add", |c: &mut Counter, by: f64| { c.n += by; c.n })
+        .getter("value", |c: &Counter| c.n)
+        .setter("value", |c: &mut Counter, v: f64| c.n = v)
+        .static_method("zero", || 0.0)
+        .static_value("KIND", "counter")
+        .build()
+}
+
+#[test]
+fn host_fn_converts_arguments_and_results() {
+    let mut interp = Interpreter::new();
+    interp.global.borrow_mut().define("join", host_fn("join", |parts: Vec<String>, sep: Option<String>| {
+        parts.join(&sep.unwrap_or_else(|| ",".into()))
+    }));
+    interp.global.borrow_mut().define("lengths", host_fn("lengths", |m: HashMap<String, String>| {
+        m.into_iter().map(|(k, v)| (k, v.len())).collect::<HashMap<_, _>>()
+    }));
+    assert_eq!(as_str(run_in(&mut interp, "return join(['a', 'b'], '-') + join(['c', 'd']);")), "a-bc,d");
+    assert_eq!(as_num(run_in(&mut interp, "return lengths({ x: 'abc' }).x;")), 3.0);
+    let msg = as_str(run_in(&mut interp, "try { join('nope'); } catch (e) { return e.message || String(e); }"));
+    assert!(msg.contains("join: argument 1: expected array"), "{msg}");
+}
+
+#[test]
+fn host_fn_serde_and_result() {
+    #[derive(serde::Serialize, serde::Deserialize)]
+    struct Req { id: u32, method: String }
+    let mut interp = Interpreter::new();
+    interp.global.borrow_mut().define("echo", host_fn("echo", |Serde(r): Serde<Req>| {
+        Serde(Req { id: r.id + 1, method: r.method.to_uppercase() })
+    }));
+    interp.global.borrow_mut().define("check", host_fn("check", |n: i32| {
+        if n < 0 { Err(format!("RangeError: {n} < 0")) } else { Ok(n * 2) }
+    }));
+    assert_eq!(as_str(run_in(&mut interp, "const r = echo({ id: 1, method: 'get' }); return r.method + r.id;")), "GET2");
+    assert_eq!(as_num(run_in(&mut interp, "return check(4);")), 8.0);
+    assert_eq!(as_str(run_in(&mut interp, "try { check(-1); } catch (e) { return String(e); }")), "RangeError: -1 < 0");
+}
+
+#[test]
+fn host_class_members_and_statics() {
+    let mut interp = Interpreter::new();
+    counter_class().install(&interp);
+    let out = run_in(&mut interp, r#"
+        const c = new Counter(5);
+        c.add(2);
+        c.value = c.value * 10;
+        return [c.value, Counter.zero(), Counter.KIND, Object.keys(c).length].join(" ");
+    "#);
+    assert_eq!(as_str(out), "70 0 counter 0");
+}
+
+#[test]
+fn host_ref_round_trips_instances() {
+    let mut interp = Interpreter::new();
+    let class = counter_class();
+    class.install(&interp);
+    interp.global.borrow_mut().define("total", host_fn("total", |items: Vec<HostRef<Counter>>| {
+        items.iter().map(|c| c.borrow().n).sum::<f64>()
+    }));
+    interp.global.borrow_mut().define("shared", class.wrap(Counter { n: 1.0 }));
+    assert_eq!(as_num(run_in(&mut interp, "return total([new Counter(2), shared]);")), 3.0);
+    let err = try_run_in(&mut interp, "total([{}]);");
+    assert!(matches!(err, Err(JsError::Runtime(ref m)) if m.contains("argument 1")), "{err:?}");
+}
+
+#[test]
+fn async_host_fn_resolves_promise() {
+    let mut interp = Interpreter::new();
+    let slow_add = interp.host_async_fn("slowAdd", |a: f64, b: f64| move || {
+        std::thread::sleep(std::time::Duration::from_millis(5));
+        a + b
+    });
+    let fail = interp.host_async_fn("fail", || || Err::<(), _>("TypeError: nope"));
+    interp.global.borrow_mut().define("slowAdd", slow_add);
+    interp.global.borrow_mut().define("fail", fail);
+    run_in(&mut interp, r#"
+        var got = [];
+        slowAdd(1, 2).then(v => got.push(v));
+        fail().then(() => got.push("ok"), e => got.push(e.name));
+    "#);
+    assert!(!interp.has_pending_host_jobs());
+    assert_eq!(as_str(run_in(&mut interp, "return got.sort().join(',');")), "3,TypeError");
+}
+
+#[test]
+fn host_class_statics_are_per_interpreter() {
+    let other = HostClass::<Counter>::builder("Counter").static_value("KIND", "other").build();
+    let mut a = Interpreter::new();
+    let mut b = Interpreter::new();
+    counter_class().install(&a);
+    other.install(&b);
+    assert_eq!(as_str(run_in(&mut a, "return Counter.KIND;")), "counter");
+    assert_eq!(as_str(run_in(&mut b, "return Counter.KIND;")), "other");
+}
+
+#[test]
+fn async_host_fn_uses_custom_executor() {
+    let mut interp = Interpreter::new();
+    let jobs = Rc::new(Cell::new(0));
+    let counted = Rc::clone(&jobs);
+    interp.set_host_executor(move |job| {
+        counted.set(counted.get() + 1);
+        job();
+    });
+    let double = interp.host_async_fn("double", |n: f64| move || n * 2.0);
+    interp.global.borrow_mut().define("double", double);
+    run_in(&mut interp, "var got = 0; double(21).then(v => { got = v; });");
+    assert_eq!(jobs.get(), 1);
+    assert_eq!(as_num(run_in(&mut interp, "return got;")), 42.0);
+}
+
+#[test]
+fn async_host_fn_panic_rejects_and_pool_survives() {
+    let mut interp = Interpreter::new();
+    let boom = interp.host_async_fn("boom", || || -> f64 { panic!("host job") });
+    let one = interp.host_async_fn("one", || || 1.0);
+    interp.global.borrow_mut().define("boom", boom);
+    interp.global.borrow_mut().define("one", one);
+    run_in(&mut interp, r#"
+        var got = [];
+        boom().catch(e => got.push(e.message));
+        one().then(v => got.push(v));
+    "#);
+    assert_eq!(as_str(run_in(&mut interp, "return got.sort().join(',');")), "1,host job panicked");
+}
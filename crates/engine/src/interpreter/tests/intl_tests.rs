@@ -0,0 +1,201 @@
+/// Testy Intl (ECMA-402) nad ICU4X - NumberFormat, DateTimeFormat, PluralRules,
+/// Collator, Segmenter, RelativeTimeFormat, ListFormat, DisplayNames, Locale.
+
+use super::helpers::*;
+
+/// ICU vraci nezlomitelne mezery (U+00A0 / U+202F) - pro porovnani je srovname.
+fn norm(v: crate::interpreter::JsValue) -> String {
+    as_str(v).replace(['\u{a0}', '\u{202f}'], " ")
+}
+
+#[test]
+fn number_format_styles() {
+    let r = eval(r#"[
+        new Intl.NumberFormat("en-US", { style: "currency", currency: "USD" }).format(1234.5),
+        new Intl.NumberFormat("cs-CZ", { style: "currency", currency: "CZK" }).format(1234.5),
+        new Intl.NumberFormat("de-DE", { style: "currency", currency: "EUR", currencyDisplay: "code" }).format(-1234.5),
+        new Intl.NumberFormat("en-US", { style: "percent", maximumFractionDigits: 1 }).format(0.1234),
+        new Intl.NumberFormat("en-US", { style: "unit", unit: "kilometer-per-hour", unitDisplay: "long" }).format(50),
+        new Intl.NumberFormat("en-US", { style: "unit", unit: "celsius" }).format(21.5),
+        new Intl.NumberFormat("ar-EG").format(1234.5),
+    ].join("|")"#);
+    assert_eq!(norm(r), "$1,234.50|1 234,50 Kč|-1.234,50 EUR|12.3%|50 kilometers per hour|21.5°C|١٬٢٣٤٫٥");
+}
+
+#[test]
+fn number_format_notation_and_digits() {
+    let r = eval(r#"[
+        new Intl.NumberFormat("en-US", { notation: "compact" }).format(1234567),
+        new Intl.NumberFormat("en-US", { notation: "compact", compactDisplay: "long" }).format(1234567),
+        new Intl.NumberFormat("en-US", { notation: "scientific" }).format(123456),
+        new Intl.NumberFormat("en-US", { notation: "engineering" }).format(123456),
+        new Intl.NumberFormat("en-US", { minimumFractionDigits: 2, signDisplay: "always" }).format(3),
+        new Intl.NumberFormat("en-US", { maximumSignificantDigits: 3 }).format(123456.789),
+        (1234.5).toLocaleString("en-US", { style: "currency", currency: "EUR" }),
+    ].join("|")"#);
+    assert_eq!(as_str(r), "1.2M|1.2 million|1.235E5|123.456E3|+3.00|123,000|€1,234.50");
+}
+
+#[test]
+fn number_format_parts_range_and_resolved_options() {
+    let r = eval(r#"(() => {
+        const nf = new Intl.NumberFormat("de", { style: "currency", currency: "EUR" });
+        const parts = nf.formatToParts(-1234.56).map(p => p.type + ":" + p.value).join(",");
+        const o = nf.resolvedOptions();
+        return [parts, new Intl.NumberFormat("en-US").formatRange(3, 5),
+                new Intl.NumberFormat("en-US", { style: "currency", currency: "USD" }).formatRange(3, 5),
+                o.locale, o.style, o.currency, o.minimumFractionDigits, o.useGrouping].join("|");
+    })()"#);
+    assert_eq!(
+        norm(r),
+        "minusSign:-,integer:1,group:.,integer:234,decimal:,,fraction:56,literal: ,currency:€|3–5|$3.00 – $5.00|de|currency|EUR|2|auto"
+    );
+}
+
+#[test]
+fn date_time_format_time_zones_and_styles() {
+    let r = eval(r#"(() => {
+        const d = new Date(Date.UTC(2024, 2, 15, 13, 5, 9));
+        return [
+            new Intl.DateTimeFormat("en-US", { timeZone: "UTC" }).format(d),
+            new Intl.DateTimeFormat("en-US", { timeZone: "America/New_York", dateStyle: "full", timeStyle: "short" }).format(d),
+            new Intl.DateTimeFormat("cs-CZ", { timeZone: "Europe/Prague", year: "numeric", month: "long", day: "numeric", hour: "2-digit", minute: "2-digit" }).format(d),
+            new Intl.DateTimeFormat("en-US", { timeZone: "UTC", hour: "numeric", minute: "2-digit", hour12: false }).format(d),
+            new Intl.DateTimeFormat("en-US", { timeZone: "Asia/Tokyo", hour: "numeric", timeZoneName: "short" }).format(d),
+            new Intl.DateTimeFormat("en-US-u-ca-hebrew", { timeZone: "UTC", dateStyle: "long" }).format(d),
+        ].join("|");
+    })()"#);
+    assert_eq!(
+        norm(r),
+        "3/15/2024|Friday, March 15, 2024, 9:05 AM|15. března 2024 14:05|13:05|10 PM GMT+9|5 Adar II 5784"
+    );
+}
+
+#[test]
+fn date_time_format_parts_range_and_resolved_options() {
+    let r = eval(r#"(() => {
+        const d = new Date(Date.UTC(2024, 2, 5, 13, 5, 9));
+        const de = new Intl.DateTimeFormat("de-DE", { timeZone: "UTC" }).formatToParts(d);
+        const en = new Intl.DateTimeFormat("en-US", { timeZone: "UTC", hour: "numeric", minute: "numeric" });
+        const o = en.resolvedOptions();
+        return [de.map(p => p.type + ":" + p.value).join(","),
+                en.formatToParts(d).filter(p => p.type !== "literal").map(p => p.type).join(","),
+                o.timeZone, o.calendar, o.hourCycle, o.hour12, String(o.year),
+                new Intl.DateTimeFormat("en-US", { timeZone: "UTC" }).formatRange(new Date(Date.UTC(2024, 0, 1)), new Date(Date.UTC(2024, 0, 5)))
+               ].join("|");
+    })()"#);
+    assert_eq!(
+        norm(r),
+        "day:5,literal:.,month:3,literal:.,year:2024|hour,minute,dayPeriod|UTC|gregory|h12|true|undefined|1/1/2024\u{2009}–\u{2009}1/5/2024"
+    );
+}
+
+#[test]
+fn date_to_locale_strings_with_options() {
+    let r = eval(r#"(() => {
+        const d = new Date(Date.UTC(2024, 2, 15, 13, 5));
+        return [d.toLocaleDateString("de-DE", { timeZone: "UTC", weekday: "long", day: "numeric", month: "long" }),
+                d.toLocaleTimeString("en-GB", { timeZone: "UTC" })].join("|");
+    })()"#);
+    assert_eq!(as_str(r), "Freitag, 15. März|13:05:00");
+}
+
+#[test]
+fn plural_rules_ordinal_and_categories() {
+    let r = eval(r#"[
+        [1, 2, 3, 4, 5, 11, 21].map(new Intl.PluralRules("en-US", { type: "ordinal" }).select).join(),
+        new Intl.PluralRules("en").select(1) + new Intl.PluralRules("en", { minimumFractionDigits: 1 }).select(1),
+        new Intl.PluralRules("cs").resolvedOptions().pluralCategories.join(),
+        new Intl.PluralRules("en").selectRange(1, 3),
+    ].join("|")"#);
+    assert_eq!(as_str(r), "one,two,few,other,other,other,one|oneother|one,few,many,other|other");
+}
+
+#[test]
+fn collator_options() {
+    let r = eval(r#"[
+        ["a10", "a2", "B", "b"].sort(new Intl.Collator("en", { numeric: true, caseFirst: "upper" }).compare).join(),
+        new Intl.Collator("en", { sensitivity: "base" }).compare("a", "Á"),
+        new Intl.Collator("en", { sensitivity: "accent" }).compare("a", "A"),
+        new Intl.Collator("en", { sensitivity: "accent" }).compare("a", "á"),
+    ].join("|")"#);
+    assert_eq!(as_str(r), "a2,a10,B,b|0|0|-1");
+}
+
+#[test]
+fn segmenter_granularities() {
+    let r = eval(r#"(() => {
+        const g = Array.from(new Intl.Segmenter("en", { granularity: "grapheme" }).segment("é👨‍👩‍👧x"));
+        const w = new Intl.Segmenter("en", { granularity: "word" });
+        const words = Array.from(w.segment("Hello, world! 42")).filter(s => s.isWordLike).map(s => s.segment);
+        const s = Array.from(new Intl.Segmenter("en", { granularity: "sentence" }).segment("Hi there. How are you? Fine."));
+        const hit = w.segment("foo bar").containing(5);
+        return [g.map(x => x.segment + "@" + x.index).join(","), words.join(","),
+                s.map(x => x.segment.trim()).join(","), hit.segment, hit.index, hit.isWordLike].join("|");
+    })()"#);
+    assert_eq!(as_str(r), "é@0,👨‍👩‍👧@1,x@9|Hello,world,42|Hi there.,How are you?,Fine.|bar|4|true");
+}
+
+#[test]
+fn relative_time_and_list_format() {
+    let r = eval(r#"[
+        new Intl.RelativeTimeFormat("en").format(-1, "day"),
+        new Intl.RelativeTimeFormat("en", { numeric: "auto" }).format(-1, "day"),
+        new Intl.RelativeTimeFormat("cs").format(3, "hours"),
+        new Intl.RelativeTimeFormat("en", { style: "short" }).format(2, "quarter"),
+        new Intl.RelativeTimeFormat("en").formatToParts(5, "second").map(p => p.type + ":" + (p.unit || "")).join(","),
+        new Intl.ListFormat("en").format(["a", "b", "c"]),
+        new Intl.ListFormat("en", { type: "disjunction" }).format(["a", "b"]),
+        new Intl.ListFormat("cs").format(["a", "b", "c"]),
+        new Intl.ListFormat("en").formatToParts(["x", "y"]).map(p => p.type).join(","),
+    ].join("|")"#);
+    assert_eq!(
+        norm(r),
+        "1 day ago|yesterday|za 3 hodiny|in 2 qtrs.|literal:,integer:second,literal:|a, b, and c|a or b|a, b a c|element,literal,element"
+    );
+}
+
+#[test]
+fn display_names_and_locale() {
+    let r = eval(r#"(() => {
+        const l = new Intl.Locale("en-Latn-US-u-ca-gregory-hc-h12");
+        return [
+            new Intl.DisplayNames(["en"], { type: "region" }).of("DE"),
+            new Intl.DisplayNames("cs", { type: "language" }).of("en-US"),
+            new Intl.DisplayNames("en", { type: "script" }).of("Cyrl"),
+            String(new Intl.DisplayNames("en", { type: "region", fallback: "none" }).of("XX")),
+            l.baseName, l.language, l.script, l.region, l.calendar, l.hourCycle, String(l),
+            new Intl.Locale("zh").maximize().toString(),
+            new Intl.Locale("en-Latn-US").minimize().toString(),
+            new Intl.Locale("en", { region: "GB", numeric: true }).toString(),
+        ].join("|");
+    })()"#);
+    assert_eq!(
+        as_str(r),
+        "Germany|angličtina (USA)|Cyrillic|undefined|en-Latn-US|en|Latn|US|gregory|h12|\
+         en-Latn-US-u-ca-gregory-hc-h12|zh-Hans-CN|en|en-GB-u-kn"
+    );
+}
+
+#[test]
+fn intl_statics_and_errors() {
+    let r = eval(r#"(() => {
+        const kind = f => { try { f(); return "ok"; } catch (e) { return String(e).split(":")[0]; } };
+        return [
+            Intl.getCanonicalLocales(["EN-us", "en-US", "zh-hant-tw"]).join(),
+            Intl.NumberFormat.supportedLocalesOf(["cs-CZ", "de"]).join(),
+            Intl.supportedValuesOf("timeZone").includes("Europe/Prague"),
+            Intl.supportedValuesOf("calendar").includes("hebrew"),
+            kind(() => new Intl.NumberFormat("en", { style: "currency" })),
+            kind(() => Intl.supportedValuesOf("foo")),
+            kind(() => new Intl.DateTimeFormat("en", { timeZone: "Mars/Base" })),
+            kind(() => new Intl.NumberFormat("x_y")),
+            new Intl.NumberFormat("en") instanceof Intl.NumberFormat,
+            new Intl.Segmenter("en")[Symbol.toStringTag],
+        ].join("|");
+    })()"#);
+    assert_eq!(
+        as_str(r),
+        "en-US,zh-Hant-TW|cs-CZ,de|true|true|TypeError|RangeError|RangeError|RangeError|true|Intl.Segmenter"
+    );
+}
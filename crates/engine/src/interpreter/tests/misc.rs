@@ -74,3 +74,16 @@ fn structured_clone_object() {
         return obj.x;
     "#)), 1.0);
 }
+
+#[test]
+fn column_breakpoint_pauses_mid_line() {
+    let mut interp = crate::interpreter::Interpreter::new();
+    interp.debugger.borrow_mut().column_breakpoints.insert((1, 22));
+    let src = "let a = 1; let b = 2; b = a + b; return b;";
+    assert!(matches!(run_in(&mut interp, src), JsValue::Undefined));
+    let span = interp.debugger.borrow().paused_span.expect("paused");
+    assert_eq!((span.start.line, span.start.column, span.end.column), (1, 22, 32));
+    // Continue: rerun preskoci zastaveny prikaz a dobehne
+    interp.debugger.borrow_mut().resume();
+    assert_eq!(as_num(run_in(&mut interp, src)), 3.0);
+}
@@ -39,6 +39,7 @@ mod dom_api_modern_tests;
 mod promise_extra_tests;
 mod canvas_form_tests;
 mod collections_tests;
+mod typed_arrays_tests;
 mod strings_extra_tests;
 mod number_math_date_tests;
 mod errors_tests;
@@ -51,3 +52,11 @@ mod dom_tier2_tests;
 mod dom_tier3_tests;
 mod dom_tier4_tests;
 mod dom_tier5_tests;
+mod gc_tests;
+mod wasm_api_tests;
+mod temporal_tests;
+mod intl_tests;
+mod strict_mode_tests;
+mod disposal_tests;
+mod budget_tests;
+mod host_bindings_tests;
@@ -1,6 +1,8 @@
 /// Staticky import/export + dynamicky import().
 
 use super::helpers::*;
+use crate::interpreter::{Interpreter, JsValue};
+use crate::interpreter::import_maps::ImportMap;
 
 #[test]
 fn import_default() {
@@ -217,3 +219,181 @@ fn dynamic_import_unknown_rejects() {
     );
     assert_eq!(as_str(v), "string");
 }
+
+// ─── Live vazby, cykly, import.meta, import mapy, top-level await ─
+
+fn interp_with(modules: &[(&str, &str)]) -> Interpreter {
+    let mut interp = Interpreter::new();
+    for (k, v) in modules {
+        interp.add_virtual_module(k, v);
+    }
+    interp
+}
+
+#[test]
+fn imported_binding_is_live() {
+    let v = run_with_modules(
+        r#"
+            import { count, inc } from "counter";
+            const before = count;
+            inc(); inc();
+            return before * 10 + count;
+        "#,
+        &[("counter", r#"
+            export let count = 0;
+            export function inc() { count++; }
+        "#)],
+    );
+    assert_eq!(as_num(v), 2.0);
+}
+
+#[test]
+fn cyclic_imports_link_functions() {
+    let v = run_with_modules(
+        r#"
+            import { even } from "even";
+            return even(10) && !even(7);
+        "#,
+        &[
+            ("even", r#"
+                import { odd } from "odd";
+                export function even(n) { return n === 0 ? true : odd(n - 1); }
+            "#),
+            ("odd", r#"
+                import { even } from "even";
+                export function odd(n) { return n === 0 ? false : even(n - 1); }
+            "#),
+        ],
+    );
+    assert!(matches!(v, JsValue::Bool(true)));
+}
+
+#[test]
+fn assignment_to_import_throws() {
+    let v = run_with_modules(
+        r#"
+            import { x } from "m";
+            try { x = 2; } catch (e) { return String(e); }
+            return "no error";
+        "#,
+        &[("m", "export let x = 1;")],
+    );
+    assert!(as_str(v).contains("Assignment to constant variable"));
+}
+
+#[test]
+fn import_meta_url_and_relative_resolution() {
+    let mut interp = interp_with(&[
+        ("https://example.com/app/lib/a.js", r#"
+            import { b } from "../b.js";
+            export const url = import.meta.url;
+            export const fromB = b;
+        "#),
+        ("https://example.com/app/b.js", "export const b = import.meta.url;"),
+    ]);
+    *interp.base_dir.borrow_mut() = "https://example.com/app/index.html".into();
+    let v = run_in(&mut interp, r#"
+        import { url, fromB } from "./lib/a.js";
+        return [url, fromB, import.meta.resolve("./x.js")].join("|");
+    "#);
+    assert_eq!(as_str(v), "https://example.com/app/lib/a.js|https://example.com/app/b.js|https://example.com/app/x.js");
+}
+
+#[test]
+fn import_map_resolves_bare_specifier() {
+    let mut interp = interp_with(&[("https://example.com/vendor/lodash.js", r#"
+        export default function() { return "lodash"; }
+    "#)]);
+    let base = "https://example.com/index.html";
+    *interp.base_dir.borrow_mut() = base.into();
+    *interp.import_map.borrow_mut() =
+        ImportMap::parse(r#"{"imports": {"lodash": "/vendor/lodash.js"}}"#, base).unwrap();
+    let v = run_in(&mut interp, r#"
+        import _ from "lodash";
+        let err = "";
+        import("unmapped").catch(e => { err = e.name; });
+        return _() + ":" + err;
+    "#);
+    assert_eq!(as_str(v), "lodash:TypeError");
+}
+
+#[test]
+fn star_and_indirect_reexports() {
+    let v = run_with_modules(
+        r#"
+            import * as b from "b";
+            return [b.x, b.y, b.nsA.default, b.default === undefined, Object.keys(b).join(",")].join("|");
+        "#,
+        &[
+            ("a", "export const x = 1; export default 9;"),
+            ("b", r#"
+                export * from "a";
+                export { x as y } from "a";
+                export * as nsA from "a";
+            "#),
+        ],
+    );
+    assert_eq!(as_str(v), "1|1|9|true|nsA,x,y");
+}
+
+#[test]
+fn namespace_reads_live_and_is_sealed() {
+    let v = run_with_modules(
+        r#"
+            import * as ns from "m";
+            ns.bump();
+            return ns.v + ":" + ns[Symbol.toStringTag] + ":" + Object.isExtensible(ns);
+        "#,
+        &[("m", "export let v = 1; export function bump() { v = 5; }")],
+    );
+    assert_eq!(as_str(v), "5:Module:false");
+}
+
+#[test]
+fn missing_export_is_syntax_error() {
+    let v = run_with_modules(
+        r#"
+            let err = "";
+            import("bad").catch(e => { err = e.name + ": " + e.message; });
+            return err;
+        "#,
+        &[("bad", r#"import { nope } from "m";"#), ("m", "export const x = 1;")],
+    );
+    assert_eq!(as_str(v), "SyntaxError: The requested module 'm' does not provide an export named 'nope'");
+}
+
+#[test]
+fn top_level_await_orders_evaluation() {
+    let mut interp = interp_with(&[
+        ("b", r#"
+            log.push("b1");
+            await new Promise(r => setTimeout(r, 0));
+            log.push("b2");
+            export const done = true;
+        "#),
+        ("c", r#"log.push("c");"#),
+    ]);
+    run_in(&mut interp, "var log = [];");
+    run_in(&mut interp, r#"
+        import { done } from "b";
+        import "c";
+        log.push("a:" + done);
+    "#);
+    let v = run_in(&mut interp, "return log.join(',');");
+    assert_eq!(as_str(v), "b1,c,b2,a:true");
+}
+
+#[test]
+fn dynamic_import_waits_for_top_level_await() {
+    let mut interp = interp_with(&[("tla", r#"
+        export let v = "pending";
+        await new Promise(r => setTimeout(r, 0));
+        v = "ready";
+    "#)]);
+    run_in(&mut interp, r#"
+        var seen = "";
+        import("tla").then(m => { seen = m.v; });
+    "#);
+    let v = run_in(&mut interp, "return seen;");
+    assert_eq!(as_str(v), "ready");
+}
@@ -491,3 +491,94 @@ fn for_in_integer_keys_first() {
     "#);
     assert_eq!(as_str(r), "0,2");
 }
+
+// --- Property deskriptory a poradi klicu ---
+
+#[test]
+fn own_keys_insertion_order() {
+    let r = run(r#"
+        const o = { z: 1, b: 2 };
+        o.a = 3;
+        o[10] = 4;
+        o[1] = 5;
+        delete o.b;
+        o.b = 6;
+        return Object.keys(o).join(",") + "|" + JSON.stringify({ y: 1, x: { q: 1, p: 2 } });
+    "#);
+    assert_eq!(as_str(r), r#"1,10,z,a,b|{"y":1,"x":{"q":1,"p":2}}"#);
+}
+
+#[test]
+fn define_property_defaults_non_writable_hidden() {
+    let r = run(r#"
+        const o = { a: 1 };
+        Object.defineProperty(o, "b", { value: 2 });
+        o.b = 99;
+        delete o.b;
+        const d = Object.getOwnPropertyDescriptor(o, "b");
+        return [o.b, Object.keys(o).join("|"), JSON.stringify(o),
+                d.writable, d.enumerable, d.configurable,
+                Object.getOwnPropertyNames(o).join("|")].join(",");
+    "#);
+    assert_eq!(as_str(r), r#"2,a,{"a":1},false,false,false,a|b"#);
+}
+
+#[test]
+fn define_property_redefine_non_configurable_throws() {
+    assert!(try_run(r#"
+        const o = {};
+        Object.defineProperty(o, "x", { value: 1 });
+        Object.defineProperty(o, "x", { value: 2 });
+    "#).is_err());
+    assert!(try_run(r#"Object.defineProperty({}, "x", { get() {}, value: 1 });"#).is_err());
+    assert_eq!(as_num(run(r#"
+        const o = {};
+        Object.defineProperty(o, "x", { value: 1, writable: true });
+        Object.defineProperty(o, "x", { value: 2 });
+        return o.x;
+    "#)), 2.0);
+}
+
+#[test]
+fn object_literal_getter_setter() {
+    let r = run(r#"
+        let log = [];
+        const o = {
+            _v: 1,
+            get v() { return this._v * 10; },
+            set v(x) { log.push(x); this._v = x; },
+            get: 5,
+        };
+        o.v = 3;
+        const d = Object.getOwnPropertyDescriptor(o, "v");
+        return [o.v, log.join(), o.get, typeof d.get, d.enumerable, Object.keys(o).join("|")].join(",");
+    "#);
+    assert_eq!(as_str(r), "30,3,5,function,true,_v|v|get");
+}
+
+#[test]
+fn object_seal_blocks_add_and_delete() {
+    let r = run(r#"
+        const o = { a: 1 };
+        Object.seal(o);
+        o.a = 2;
+        o.b = 3;
+        delete o.a;
+        return [o.a, o.b === undefined, Object.isSealed(o), Object.isFrozen(o), Object.isExtensible(o)].join(",");
+    "#);
+    assert_eq!(as_str(r), "2,true,true,false,false");
+}
+
+#[test]
+fn for_in_walks_proto_enumerable() {
+    let r = run(r#"
+        const proto = { inherited: 1, shadow: 2 };
+        const o = Object.create(proto);
+        o.own = 1;
+        Object.defineProperty(o, "shadow", { value: 3, enumerable: false });
+        const keys = [];
+        for (const k in o) keys.push(k);
+        return keys.join(",");
+    "#);
+    assert_eq!(as_str(r), "own,inherited");
+}
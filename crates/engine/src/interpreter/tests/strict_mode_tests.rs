@@ -0,0 +1,104 @@
+/// Strict mode (`"use strict"`, tela trid, moduly) a temporal dead zone
+/// `let`/`const`/`class` vazeb.
+
+use super::helpers::*;
+
+fn err_of(src: &str) -> String {
+    format!("{:?}", try_run(src).unwrap_err())
+}
+
+#[test]
+fn tdz_read_and_write_before_declaration() {
+    let err = err_of("x; let x = 1;");
+    assert!(err.contains("ReferenceError: Cannot access 'x' before initialization"), "{err}");
+    let err = err_of("{ y = 2; const y = 1; }");
+    assert!(err.contains("Cannot access 'y' before initialization"), "{err}");
+    let err = err_of("typeof C; class C {}");
+    assert!(err.contains("Cannot access 'C' before initialization"), "{err}");
+    let err = err_of("let z = z + 1;");
+    assert!(err.contains("Cannot access 'z' before initialization"), "{err}");
+}
+
+#[test]
+fn tdz_binding_shadows_outer_and_ends_at_declaration() {
+    let err = err_of("let a = 1; { a; let a = 2; }");
+    assert!(err.contains("Cannot access 'a' before initialization"), "{err}");
+    assert_eq!(as_str(run(r#"
+        const read = () => late;
+        let early = typeof undeclared;
+        let late = "ok";
+        return [early, read()].join("|");
+    "#)), "undefined|ok");
+}
+
+#[test]
+fn const_assignment_is_type_error() {
+    let err = err_of("const c = 1; c = 2;");
+    assert!(err.contains("TypeError: Assignment to constant variable."), "{err}");
+    let err = err_of("const { p } = { p: 1 }; p++;");
+    assert!(err.contains("Assignment to constant variable."), "{err}");
+    let err = err_of("const n = 1; function bump() { n += 1; } bump();");
+    assert!(err.contains("Assignment to constant variable."), "{err}");
+    assert_eq!(as_num(run("const o = { v: 1 }; o.v = 5; return o.v;")), 5.0);
+}
+
+#[test]
+fn strict_undeclared_assignment_throws_sloppy_creates_global() {
+    let err = err_of(r#""use strict"; leaked = 1;"#);
+    assert!(err.contains("ReferenceError: leaked is not defined"), "{err}");
+    let err = err_of(r#"function f() { "use strict"; inner = 1; } f();"#);
+    assert!(err.contains("inner is not defined"), "{err}");
+    assert_eq!(as_num(run("function f() { made = 7; } f(); return made;")), 7.0);
+}
+
+#[test]
+fn this_of_plain_call_depends_on_mode() {
+    assert_eq!(as_str(run(r#"
+        function sloppy() { return this === undefined; }
+        function strict() { "use strict"; return this === undefined; }
+        return [sloppy(), strict(), strict.call(null) === false].join("|");
+    "#)), "false|true|true");
+    assert_eq!(as_bool(run(r#"
+        "use strict";
+        function f() { return this; }
+        return f() === undefined;
+    "#)), true);
+    // Arrow funkce `this` nebere z volani
+    assert_eq!(as_num(run(r#"
+        const o = { v: 3, make() { return () => this.v; } };
+        const other = { v: 9, f: o.make() };
+        return other.f();
+    "#)), 3.0);
+}
+
+#[test]
+fn strict_rejects_silent_writes() {
+    let err = err_of(r#""use strict"; const o = Object.freeze({ a: 1 }); o.a = 2;"#);
+    assert!(err.contains("TypeError: Cannot assign to read only property 'a'"), "{err}");
+    let err = err_of(r#""use strict"; const o = Object.preventExtensions({}); o.b = 1;"#);
+    assert!(err.contains("object is not extensible"), "{err}");
+    let err = err_of(r#""use strict"; const o = { get g() { return 1; } }; o.g = 2;"#);
+    assert!(err.contains("which has only a getter"), "{err}");
+    let err = err_of(r#""use strict"; const o = Object.freeze({ a: 1 }); delete o.a;"#);
+    assert!(err.contains("TypeError: Cannot delete property 'a'"), "{err}");
+    // Sloppy kod stejne zapisy tise ignoruje
+    assert_eq!(as_num(run("const o = Object.freeze({ a: 1 }); o.a = 2; delete o.a; return o.a;")), 1.0);
+}
+
+#[test]
+fn class_bodies_and_modules_are_strict() {
+    let err = err_of(r#"
+        class A { m() { const o = Object.freeze({ x: 1 }); o.x = 2; } }
+        new A().m();
+    "#);
+    assert!(err.contains("Cannot assign to read only property 'x'"), "{err}");
+    assert_eq!(as_bool(run(r#"
+        class B { static who() { return (function () { return this; })(); } }
+        return B.who() === undefined;
+    "#)), true);
+    let r = run_with_modules(r#"
+        import { check } from "./m.js";
+        return check();
+    "#, &[("./m.js", "export function check() { try { undeclaredInModule = 1; return 'no'; } catch (e) { return String(e).startsWith('ReferenceError'); } }")]);
+    assert!(as_bool(r));
+}
@@ -109,3 +109,66 @@ fn array_destructuring_from_string() {
         return a + b;
     "#)), "hi");
 }
+
+// ─── Tagged templates ────────────────────────────────────────────────────
+
+#[test]
+fn tagged_template_strings_and_values() {
+    assert_eq!(as_str(run(r#"
+        function tag(strings, ...vals) {
+            return strings.length + ":" + strings.join("|") + ":" + vals.join(",");
+        }
+        const a = 1, b = "x";
+        return tag`A${a}B${b}C`;
+    "#)), "3:A|B|C:1,x");
+}
+
+#[test]
+fn tagged_template_raw_and_string_raw() {
+    assert_eq!(as_str(run(r#"
+        function tag(s) { return s[0].length + "/" + s.raw[0].length; }
+        return tag`a\nb` + " " + String.raw`C:\new${1 + 1}` + " " + String.raw({ raw: ["x", "y", "z"] }, 1, 2);
+    "#)), "3/4 C:\\new2 x1y2z");
+}
+
+#[test]
+fn tagged_template_strings_cached_per_site() {
+    // lit-html pouziva strings pole jako klic cache - stejny site = stejna instance
+    assert_eq!(as_str(run(r#"
+        const seen = [];
+        function tag(s) { seen.push(s); return s; }
+        function render(v) { return tag`<p>${v}</p>`; }
+        render(1); render(2);
+        tag`<p>${0}</p>`;
+        return (seen[0] === seen[1]) + ":" + (seen[0] === seen[2]);
+    "#)), "true:false");
+}
+
+#[test]
+fn tagged_template_strings_frozen() {
+    assert_eq!(as_str(run(r#"
+        const s = ((x) => x)`a${1}b`;
+        s[0] = "zmena";
+        let pushed = "ok";
+        try { s.push("c"); } catch (e) { pushed = "TypeError"; }
+        return s[0] + ":" + s.length + ":" + pushed + ":" + Array.isArray(s.raw);
+    "#)), "a:2:TypeError:true");
+}
+
+#[test]
+fn tagged_template_member_tag_this() {
+    assert_eq!(as_str(run(r#"
+        const css = { prefix: "p-", tag(s, v) { return this.prefix + s[0] + v; } };
+        return css.tag`color:${"red"}`;
+    "#)), "p-color:red");
+}
+
+#[test]
+fn tagged_template_strings_released_with_program() {
+    // Cache je v AST call site - po zahozeni Programu drzi pole jen volajici
+    let JsValue::Array(strings) = run("return ((s) => s)`a${1}b`;") else { panic!("ocekavano pole") };
+    assert_eq!(std::rc::Rc::strong_count(&strings), 1);
+    // Druhy parse stejneho zdroje je jiny call site
+    let JsValue::Array(again) = run("return ((s) => s)`a${1}b`;") else { panic!("ocekavano pole") };
+    assert!(!std::rc::Rc::ptr_eq(&strings, &again));
+}
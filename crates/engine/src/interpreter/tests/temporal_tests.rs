@@ -0,0 +1,230 @@
+/// Testy Temporal API (PlainDate / PlainTime / PlainDateTime / ZonedDateTime /
+/// Instant / Duration, ne-ISO kalendare, casove zony z tzdb).
+
+use super::helpers::*;
+
+#[test]
+fn plain_date_fields_and_to_string() {
+    let r = eval(r#"(() => {
+        const d = new Temporal.PlainDate(2024, 2, 29);
+        return [d.toString(), d.dayOfWeek, d.dayOfYear, d.daysInMonth, d.inLeapYear, d.monthCode,
+                d.weekOfYear, String(d)].join("|");
+    })()"#);
+    assert_eq!(as_str(r), "2024-02-29|4|60|29|true|M02|9|2024-02-29");
+}
+
+#[test]
+fn plain_date_add_constrains_month_end() {
+    let r = eval(r#"(() => {
+        const d = Temporal.PlainDate.from("2023-01-31");
+        let rejected = false;
+        try { d.add({ months: 1 }, { overflow: "reject" }); } catch (e) { rejected = String(e).startsWith("RangeError"); }
+        return [d.add({ months: 1 }).toString(), d.subtract("P1Y2M").toString(), rejected].join("|");
+    })()"#);
+    assert_eq!(as_str(r), "2023-02-28|2021-11-30|true");
+}
+
+#[test]
+fn plain_date_until_and_since() {
+    let r = eval(r#"(() => {
+        const a = Temporal.PlainDate.from("2024-01-31");
+        const b = Temporal.PlainDate.from("2024-02-28");
+        const c = Temporal.PlainDate.from("2025-03-15");
+        // 15 dni z unorem 2025-02-28 .. 2025-03-31 (31 dni) se zaokrouhli dolu
+        return [a.until(b).toString(), a.until(c, { largestUnit: "year" }).toString(),
+                c.since(a, { largestUnit: "month" }).toString(),
+                a.until(c, { largestUnit: "year", smallestUnit: "month", roundingMode: "halfExpand" }).toString()].join("|");
+    })()"#);
+    assert_eq!(as_str(r), "P28D|P1Y1M15D|P13M15D|P1Y1M");
+}
+
+#[test]
+fn compare_equals_and_instanceof() {
+    let r = eval(r#"(() => {
+        const a = Temporal.PlainDate.from({ year: 2020, month: 5, day: 1 });
+        const b = Temporal.PlainDate.from("2020-05-02");
+        const sorted = [b, a].sort(Temporal.PlainDate.compare).map(String).join(",");
+        let typeError = false;
+        try { a < b; } catch (e) { typeError = String(e).startsWith("TypeError"); }
+        return [sorted, a.equals("2020-05-01"), a instanceof Temporal.PlainDate,
+                a[Symbol.toStringTag], typeError].join("|");
+    })()"#);
+    assert_eq!(as_str(r), "2020-05-01,2020-05-02|true|true|Temporal.PlainDate|true");
+}
+
+#[test]
+fn plain_time_and_date_time() {
+    let r = eval(r#"(() => {
+        const t = Temporal.PlainTime.from("23:30:15.5");
+        const dt = Temporal.PlainDateTime.from("2024-03-10T12:34:56.789123456");
+        return [t.add({ hours: 1 }).toString(), t.round("minute").toString(),
+                dt.round({ smallestUnit: "second" }).toString(),
+                dt.toString({ fractionalSecondDigits: 3 }),
+                dt.with({ day: 1, hour: 0 }).toString(),
+                dt.until("2024-03-11T00:00", { largestUnit: "hour" }).toString()].join("|");
+    })()"#);
+    assert_eq!(as_str(r),
+        "00:30:15.5|23:30:00|2024-03-10T12:34:57|2024-03-10T12:34:56.789|2024-03-01T00:34:56.789123456|PT11H25M3.210876544S");
+}
+
+#[test]
+fn duration_balancing_round_and_total() {
+    let r = eval(r#"(() => {
+        const d = Temporal.Duration.from({ hours: 50, minutes: 90 });
+        return [d.toString(), d.round({ largestUnit: "day" }).toString(),
+                d.total("minute"),
+                Temporal.Duration.from("P1M").total({ unit: "day", relativeTo: "2024-02-01" }),
+                Temporal.Duration.from("P1M").round({ largestUnit: "day", relativeTo: "2023-02-01" }).toString(),
+                Temporal.Duration.from({ days: 45 }).round({ largestUnit: "month", relativeTo: "2024-01-01" }).toString(),
+                Temporal.Duration.compare("P1M", "P30D", { relativeTo: "2024-02-01" })].join("|");
+    })()"#);
+    assert_eq!(as_str(r), "PT50H90M|P2DT3H30M|3090|29|P28D|P1M14D|-1");
+}
+
+#[test]
+fn duration_without_relative_to_rejects_calendar_units() {
+    let r = eval(r#"(() => {
+        try { Temporal.Duration.from("P1M").total("day"); return "no error"; }
+        catch (e) { return String(e).startsWith("RangeError"); }
+    })()"#);
+    assert!(as_bool(r));
+}
+
+#[test]
+fn zoned_date_time_dst_gap_and_fold() {
+    let r = eval(r#"(() => {
+        // Europe/Prague: 2024-03-31 02:00 -> 03:00, 2024-10-27 03:00 -> 02:00
+        const gap = Temporal.PlainDateTime.from("2024-03-31T02:30");
+        const fold = Temporal.PlainDateTime.from("2024-10-27T02:30");
+        let rejected = false;
+        try { gap.toZonedDateTime("Europe/Prague", { disambiguation: "reject" }); } catch (e) { rejected = String(e).startsWith("RangeError"); }
+        return [gap.toZonedDateTime("Europe/Prague").toString(),
+                gap.toZonedDateTime("Europe/Prague", { disambiguation: "earlier" }).toString(),
+                fold.toZonedDateTime("Europe/Prague").offset,
+                fold.toZonedDateTime("Europe/Prague", { disambiguation: "later" }).offset,
+                rejected].join("|");
+    })()"#);
+    assert_eq!(as_str(r),
+        "2024-03-31T03:30:00+02:00[Europe/Prague]|2024-03-31T01:30:00+01:00[Europe/Prague]|+02:00|+01:00|true");
+}
+
+#[test]
+fn zoned_date_time_hours_in_day_and_arithmetic() {
+    let r = eval(r#"(() => {
+        const z = Temporal.ZonedDateTime.from("2024-03-09T12:00[America/New_York]");
+        const next = z.add({ days: 1 });
+        return [z.hoursInDay, next.hoursInDay,
+                Temporal.ZonedDateTime.from("2024-11-03T12:00-05:00[America/New_York]").hoursInDay,
+                next.toString(), z.add({ hours: 24 }).toString(),
+                z.until(next).toString(), z.until(next, { largestUnit: "day" }).toString(),
+                next.startOfDay().toString(),
+                z.getTimeZoneTransition("next").toString()].join("|");
+    })()"#);
+    assert_eq!(as_str(r), [
+        "24", "23", "25",
+        "2024-03-10T12:00:00-04:00[America/New_York]",
+        "2024-03-10T13:00:00-04:00[America/New_York]",
+        "PT23H", "P1D",
+        "2024-03-10T00:00:00-05:00[America/New_York]",
+        "2024-03-10T03:00:00-04:00[America/New_York]",
+    ].join("|"));
+}
+
+#[test]
+fn zoned_from_string_offset_mismatch_rejects() {
+    let r = eval(r#"(() => {
+        try { Temporal.ZonedDateTime.from("2024-07-01T12:00+01:00[Europe/Prague]"); return "no error"; }
+        catch (e) { return String(e).startsWith("RangeError"); }
+    })()"#);
+    assert!(as_bool(r));
+}
+
+#[test]
+fn instant_epoch_and_conversions() {
+    let r = eval(r#"(() => {
+        const i = Temporal.Instant.from("2024-01-01T00:00:00.5+01:00");
+        const d = new Date(1704067200000);
+        return [i.toString(), i.epochMilliseconds, typeof i.epochNanoseconds,
+                i.toZonedDateTimeISO("Europe/Prague").toPlainDateTime().toString(),
+                i.toString({ timeZone: "America/New_York", smallestUnit: "second" }),
+                d.toTemporalInstant().toString(),
+                Temporal.Instant.fromEpochMilliseconds(0).add({ hours: 1 }).toString(),
+                i.until("2024-01-01T00:00:00Z").toString()].join("|");
+    })()"#);
+    assert_eq!(as_str(r), [
+        "2023-12-31T23:00:00.5Z", "1704063600500", "bigint",
+        "2024-01-01T00:00:00.5",
+        "2023-12-31T18:00:00-05:00",
+        "2024-01-01T00:00:00Z",
+        "1970-01-01T01:00:00Z",
+        "PT3599.5S",
+    ].join("|"));
+}
+
+#[test]
+fn parsing_rejects_invalid_strings() {
+    let r = eval(r#"(() => {
+        const bad = ["2024-13-01", "2024-02-30", "2024-01-01Z", "T25:00", "P1Y-1M"];
+        return bad.map(s => {
+            try { s.startsWith("P") ? Temporal.Duration.from(s) : s.startsWith("T") ? Temporal.PlainTime.from(s) : Temporal.PlainDate.from(s); return "ok"; }
+            catch (e) { return String(e).split(":")[0]; }
+        }).join(",");
+    })()"#);
+    assert_eq!(as_str(r), "RangeError,RangeError,RangeError,RangeError,RangeError");
+}
+
+#[test]
+fn non_iso_calendars() {
+    let r = eval(r#"(() => {
+        // 2024-03-15 = 5. adar II 5784 (prestupny rok, adar I = M05L)
+        const h = Temporal.PlainDate.from("2024-03-15").withCalendar("hebrew");
+        const j = Temporal.PlainDate.from({ era: "reiwa", eraYear: 6, month: 5, day: 1, calendar: "japanese" });
+        return [h.year, h.monthCode, h.monthsInYear, h.inLeapYear, h.toString(),
+                j.toString(), j.era, j.eraYear,
+                h.add({ months: 1 }).monthCode].join("|");
+    })()"#);
+    assert_eq!(as_str(r), "5784|M06|13|true|2024-03-15[u-ca=hebrew]|2024-05-01[u-ca=japanese]|reiwa|6|M07");
+}
+
+#[test]
+fn year_month_and_month_day() {
+    let r = eval(r#"(() => {
+        const ym = Temporal.PlainYearMonth.from("2024-01");
+        const md = Temporal.PlainMonthDay.from("--02-29");
+        return [ym.add({ months: 13 }).toString(), ym.daysInMonth,
+                ym.until("2025-06", { largestUnit: "month" }).toString(),
+                md.toString(), md.toPlainDate({ year: 2023 }).toString(),
+                ym.toPlainDate({ day: 15 }).toString()].join("|");
+    })()"#);
+    assert_eq!(as_str(r), "2025-02|31|P17M|02-29|2023-02-28|2024-01-15");
+}
+
+#[test]
+fn temporal_objects_are_immutable_and_serialize() {
+    let r = eval(r#"(() => {
+        const d = Temporal.PlainDate.from("2024-05-06");
+        try { d.year = 1999; } catch (e) {}
+        return [d.year, JSON.stringify({ d }), Object.keys(d).length, `${Temporal.Duration.from({ seconds: -90 })}`].join("|");
+    })()"#);
+    assert_eq!(as_str(r), r#"2024|{"d":"2024-05-06"}|0|-PT90S"#);
+}
+
+#[test]
+fn now_returns_consistent_values() {
+    let r = eval(r#"(() => {
+        const a = Temporal.Now.instant();
+        const z = Temporal.Now.zonedDateTimeISO("UTC");
+        return [typeof Temporal.Now.timeZoneId(), Temporal.Instant.compare(a, z.toInstant()) <= 0,
+                Temporal.Now.plainDateISO("UTC") instanceof Temporal.PlainDate].join("|");
+    })()"#);
+    assert_eq!(as_str(r), "string|true|true");
+}
+
+#[test]
+fn locale_formatting_uses_icu() {
+    let r = eval(r#"(() => {
+        const d = Temporal.PlainDate.from("2024-03-15");
+        return [d.toLocaleString("en-US"), new Intl.DateTimeFormat("en-US").format(d)].join("|");
+    })()"#);
+    assert_eq!(as_str(r), "3/15/24|3/15/24");
+}
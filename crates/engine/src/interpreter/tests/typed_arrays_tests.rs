@@ -0,0 +1,195 @@
+/// Testy pro ArrayBuffer / TypedArray / DataView nad sdilenymi bajty.
+
+use super::helpers::*;
+
+// ─── Indexovany pristup ─────────────────────────────────────────────────
+
+#[test]
+fn typed_array_indexed_get_set() {
+    let r = run(r#"
+        const ta = new Uint8Array(3);
+        ta[0] = 7; ta[1] = 300; ta[2] = -1;
+        return ta[0] + "|" + ta[1] + "|" + ta[2] + "|" + ta[5];
+    "#);
+    assert_eq!(as_str(r), "7|44|255|undefined");
+}
+
+#[test]
+fn typed_array_out_of_range_write_ignored() {
+    let r = run(r#"
+        const ta = new Int8Array(2);
+        ta[5] = 1;
+        return ta.length + "|" + Object.keys(ta).length;
+    "#);
+    assert_eq!(as_str(r), "2|2");
+}
+
+#[test]
+fn uint8_clamped_rounds_half_to_even() {
+    let r = run(r#"
+        const ta = new Uint8ClampedArray([1.5, 2.5, 300, -5]);
+        return ta.join(",");
+    "#);
+    assert_eq!(as_str(r), "2,2,255,0");
+}
+
+#[test]
+fn views_share_one_buffer() {
+    let r = run(r#"
+        const buf = new ArrayBuffer(4);
+        const u8 = new Uint8Array(buf);
+        const u32 = new Uint32Array(buf);
+        u32[0] = 0x01020304;
+        return u8.join(",");
+    "#);
+    assert_eq!(as_str(r), "4,3,2,1");
+}
+
+#[test]
+fn subarray_shares_bytes() {
+    let r = run(r#"
+        const ta = new Uint8Array([1, 2, 3, 4]);
+        const sub = ta.subarray(1, 3);
+        sub[0] = 9;
+        return ta.join(",") + "|" + sub.byteOffset;
+    "#);
+    assert_eq!(as_str(r), "1,9,3,4|1");
+}
+
+#[test]
+fn typed_array_callbacks_and_sort() {
+    let r = run(r#"
+        const ta = new Int16Array([3, -1, 10, 2]);
+        const doubled = ta.map(x => x * 2);
+        ta.sort();
+        return (doubled instanceof Int16Array) + "|" + doubled.join(",") + "|" + ta.join(",")
+            + "|" + ta.reduce((a, b) => a + b, 0);
+    "#);
+    assert_eq!(as_str(r), "true|6,-2,20,4|-1,2,3,10|14");
+}
+
+#[test]
+fn float16_array_rounds() {
+    let r = run(r#"
+        const ta = new Float16Array([1.5, 65504, 70000, 0.1]);
+        return ta[0] + "|" + ta[1] + "|" + ta[2] + "|" + (ta[3] !== 0.1);
+    "#);
+    assert_eq!(as_str(r), "1.5|65504|Infinity|true");
+}
+
+#[test]
+fn bigint64_rejects_number() {
+    let err = try_run(r#"
+        const ta = new BigInt64Array(1);
+        ta[0] = 1;
+    "#).unwrap_err();
+    assert!(format!("{err:?}").contains("TypeError"));
+    let r = run(r#"
+        const ta = new BigUint64Array(1);
+        ta[0] = -1n;
+        return ta[0].toString();
+    "#);
+    assert_eq!(as_str(r), "18446744073709551615");
+}
+
+// ─── DataView ───────────────────────────────────────────────────────────
+
+#[test]
+fn data_view_endianness() {
+    let r = run(r#"
+        const dv = new DataView(new ArrayBuffer(8));
+        dv.setUint16(0, 0x1234);
+        dv.setUint16(2, 0x1234, true);
+        dv.setFloat32(4, 1.5);
+        return dv.getUint8(0) + "|" + dv.getUint8(2) + "|" + dv.getFloat32(4) + "|" + dv.getUint16(0, true);
+    "#);
+    assert_eq!(as_str(r), "18|52|1.5|13330");
+}
+
+#[test]
+fn data_view_out_of_bounds_throws() {
+    let err = try_run(r#"
+        const dv = new DataView(new ArrayBuffer(4));
+        dv.getUint32(2);
+    "#).unwrap_err();
+    assert!(format!("{err:?}").contains("RangeError"));
+}
+
+// ─── Resizable / transfer ───────────────────────────────────────────────
+
+#[test]
+fn length_tracking_view_follows_resize() {
+    let r = run(r#"
+        const buf = new ArrayBuffer(4, { maxByteLength: 16 });
+        const all = new Uint8Array(buf);
+        const fixed = new Uint8Array(buf, 0, 2);
+        buf.resize(8);
+        const grown = all.length + "," + fixed.length;
+        buf.resize(1);
+        return grown + "|" + all.length + "," + fixed.length + "|" + buf.resizable + "|" + buf.maxByteLength;
+    "#);
+    assert_eq!(as_str(r), "8,2|1,0|true|16");
+}
+
+#[test]
+fn resize_beyond_max_throws() {
+    let err = try_run(r#"
+        const buf = new ArrayBuffer(4, { maxByteLength: 8 });
+        buf.resize(9);
+    "#).unwrap_err();
+    assert!(format!("{err:?}").contains("RangeError"));
+}
+
+#[test]
+fn transfer_detaches_source() {
+    let r = run(r#"
+        const buf = new ArrayBuffer(4);
+        const view = new Uint8Array(buf);
+        view[0] = 42;
+        const moved = buf.transfer();
+        return buf.detached + "|" + buf.byteLength + "|" + view.length + "|" + new Uint8Array(moved)[0];
+    "#);
+    assert_eq!(as_str(r), "true|0|0|42");
+}
+
+// ─── Integrace ──────────────────────────────────────────────────────────
+
+#[test]
+fn statics_and_is_view() {
+    let r = run(r#"
+        const ta = Uint16Array.from([1, 2]);
+        return Uint16Array.BYTES_PER_ELEMENT + "|" + ArrayBuffer.isView(ta)
+            + "|" + ArrayBuffer.isView(ta.buffer) + "|" + Int8Array.of(1, 2, 3).length;
+    "#);
+    assert_eq!(as_str(r), "2|true|false|3");
+}
+
+#[test]
+fn text_encoder_returns_uint8_array() {
+    let r = run(r#"
+        const bytes = new TextEncoder().encode("hi");
+        return (bytes instanceof Uint8Array) + "|" + bytes[0] + "|" + new TextDecoder().decode(bytes);
+    "#);
+    assert_eq!(as_str(r), "true|104|hi");
+}
+
+#[test]
+fn image_data_is_clamped_array() {
+    let r = run(r#"
+        const img = new ImageData(2, 1);
+        img.data[0] = 999;
+        return (img.data instanceof Uint8ClampedArray) + "|" + img.data.length + "|" + img.data[0];
+    "#);
+    assert_eq!(as_str(r), "true|8|255");
+}
+
+#[test]
+fn atomics_operate_on_typed_array_bytes() {
+    let r = run_isolated(r#"
+        const ta = new Int32Array(new SharedArrayBuffer(8));
+        Atomics.store(ta, 1, 5);
+        const old = Atomics.add(ta, 1, 3);
+        return old + "|" + Atomics.load(ta, 1) + "|" + ta[1];
+    "#);
+    assert_eq!(as_str(r), "5|8|8");
+}
@@ -0,0 +1,271 @@
+/// Testy WebAssembly JS API (Module / Instance / Memory / Table / Global /
+/// Tag / Exception, streaming nad Response, traps).
+
+use super::helpers::*;
+use crate::interpreter::JsValue;
+
+// (module
+//   (import "env" "log" (func $log (param i32) (result i32)))
+//   (table (export "tbl") 2 funcref) (elem (i32.const 0) $add)
+//   (memory (export "mem") 1 4)
+//   (global (export "g") (mut i32) (i32.const 7))
+//   (func $add (export "add") (param i32 i32) (result i32) (i32.add (local.get 0) (local.get 1)))
+//   (func (export "callLog") (param i32) (result i32) (i32.add (call $log (local.get 0)) (i32.const 1)))
+//   (func (export "grow") (param i32) (result i32) (memory.grow (local.get 0)))
+//   (func (export "load") (param i32) (result i32) (i32.load8_u (local.get 0)))
+//   (func $boom (export "trap") unreachable)
+//   (func (export "div") (param i32 i32) (result i32) (i32.div_s (local.get 0) (local.get 1)))
+//   (func (export "wide") (param i64) (result i64) (i64.add (local.get 0) (i64.const 1))))
+// + custom sekce "meta" = "hi", name sekce s $boom
+const CALC: &[u8] = &[
+    0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x18, 0x05, 0x60, 0x01, 0x7f, 0x01, 0x7f,
+    0x60, 0x02, 0x7f, 0x7f, 0x01, 0x7f, 0x60, 0x00, 0x00, 0x60, 0x01, 0x7e, 0x01, 0x7e, 0x60, 0x00,
+    0x01, 0x7f, 0x02, 0x0b, 0x01, 0x03, 0x65, 0x6e, 0x76, 0x03, 0x6c, 0x6f, 0x67, 0x00, 0x00, 0x03,
+    0x08, 0x07, 0x01, 0x00, 0x00, 0x00, 0x02, 0x01, 0x03, 0x04, 0x04, 0x01, 0x70, 0x00, 0x02, 0x05,
+    0x04, 0x01, 0x01, 0x01, 0x04, 0x06, 0x06, 0x01, 0x7f, 0x01, 0x41, 0x07, 0x0b, 0x07, 0x43, 0x0a,
+    0x03, 0x6d, 0x65, 0x6d, 0x02, 0x00, 0x03, 0x61, 0x64, 0x64, 0x00, 0x01, 0x07, 0x63, 0x61, 0x6c,
+    0x6c, 0x4c, 0x6f, 0x67, 0x00, 0x02, 0x04, 0x67, 0x72, 0x6f, 0x77, 0x00, 0x03, 0x04, 0x6c, 0x6f,
+    0x61, 0x64, 0x00, 0x04, 0x04, 0x74, 0x72, 0x61, 0x70, 0x00, 0x05, 0x03, 0x64, 0x69, 0x76, 0x00,
+    0x06, 0x04, 0x77, 0x69, 0x64, 0x65, 0x00, 0x07, 0x01, 0x67, 0x03, 0x00, 0x03, 0x74, 0x62, 0x6c,
+    0x01, 0x00, 0x09, 0x07, 0x01, 0x00, 0x41, 0x00, 0x0b, 0x01, 0x01, 0x0a, 0x36, 0x07, 0x07, 0x00,
+    0x20, 0x00, 0x20, 0x01, 0x6a, 0x0b, 0x09, 0x00, 0x20, 0x00, 0x10, 0x00, 0x41, 0x01, 0x6a, 0x0b,
+    0x06, 0x00, 0x20, 0x00, 0x40, 0x00, 0x0b, 0x07, 0x00, 0x20, 0x00, 0x2d, 0x00, 0x00, 0x0b, 0x03,
+    0x00, 0x00, 0x0b, 0x07, 0x00, 0x20, 0x00, 0x20, 0x01, 0x6d, 0x0b, 0x07, 0x00, 0x20, 0x00, 0x42,
+    0x01, 0x7c, 0x0b, 0x00, 0x07, 0x04, 0x6d, 0x65, 0x74, 0x61, 0x68, 0x69, 0x00, 0x0e, 0x04, 0x6e,
+    0x61, 0x6d, 0x65, 0x01, 0x07, 0x01, 0x05, 0x04, 0x62, 0x6f, 0x6f, 0x6d,
+];
+
+// (module (import "env" "memory" (memory 1))
+//   (func (export "peek") (result i32) (i32.load8_u (i32.const 0))))
+const PEEK: &[u8] = &[
+    0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x05, 0x01, 0x60, 0x00, 0x01, 0x7f, 0x02,
+    0x0f, 0x01, 0x03, 0x65, 0x6e, 0x76, 0x06, 0x6d, 0x65, 0x6d, 0x6f, 0x72, 0x79, 0x02, 0x00, 0x01,
+    0x03, 0x02, 0x01, 0x00, 0x07, 0x08, 0x01, 0x04, 0x70, 0x65, 0x65, 0x6b, 0x00, 0x00, 0x0a, 0x09,
+    0x01, 0x07, 0x00, 0x41, 0x00, 0x2d, 0x00, 0x00, 0x0b,
+];
+
+
+/// JS kod s `calc` / `peek` bajty jako Uint8Array.
+fn run_wasm(src: &str) -> JsValue {
+    let js = |bytes: &[u8]| bytes.iter().map(|b| b.to_string()).collect::<Vec<_>>().join(",");
+    run(&format!("const calc = new Uint8Array([{}]); const peek = new Uint8Array([{}]);\n{src}", js(CALC), js(PEEK)))
+}
+
+const INSTANTIATE: &str = r#"
+    let inst;
+    const imports = { env: { log: v => inst.exports.add(v, 100) } };
+    inst = new WebAssembly.Instance(new WebAssembly.Module(calc), imports);
+    const x = inst.exports;
+"#;
+
+fn run_calc(src: &str) -> JsValue {
+    run_wasm(&format!("{INSTANTIATE}\n{src}"))
+}
+
+#[test]
+fn validate_and_export_call() {
+    let r = run_calc(r#"
+        return WebAssembly.validate(calc) + "|" + WebAssembly.validate(new Uint8Array([1, 2, 3])) + "|" + x.add(2, 3);
+    "#);
+    assert_eq!(as_str(r), "true|false|5");
+}
+
+#[test]
+fn js_import_reenters_wasm() {
+    // callLog -> JS log -> add (znovu do WASM) -> +1
+    assert_eq!(as_num(run_calc("return x.callLog(5);")), 106.0);
+}
+
+#[test]
+fn i32_and_i64_conversions() {
+    let r = run_calc(r#"
+        return x.add(4294967295, 2) + "|" + x.wide(41n) + "|" + typeof x.wide(0n);
+    "#);
+    assert_eq!(as_str(r), "1|42|bigint");
+}
+
+#[test]
+fn i64_param_rejects_number() {
+    let r = run_calc(r#"
+        try { x.wide(1); return "no"; } catch (e) { return String(e).startsWith("TypeError"); }
+    "#);
+    assert!(as_bool(r));
+}
+
+#[test]
+fn memory_buffer_shared_with_wasm() {
+    let r = run_calc(r#"
+        const buf = x.mem.buffer;
+        new Uint8Array(buf)[10] = 42;
+        return x.load(10) + "|" + (x.mem.buffer === buf) + "|" + buf.byteLength;
+    "#);
+    assert_eq!(as_str(r), "42|true|65536");
+}
+
+#[test]
+fn memory_grow_detaches_buffer() {
+    let r = run_calc(r#"
+        const before = x.mem.buffer;
+        new Uint8Array(before)[3] = 9;
+        const prev = x.mem.grow(1);
+        const viaWasm = x.grow(1);
+        let limit;
+        try { x.mem.grow(10); } catch (e) { limit = String(e).startsWith("RangeError"); }
+        return [prev, viaWasm, before.byteLength, x.mem.buffer.byteLength, new Uint8Array(x.mem.buffer)[3], limit].join(",");
+    "#);
+    assert_eq!(as_str(r), "1,2,0,196608,9,true");
+}
+
+#[test]
+fn memory_import_and_constructor() {
+    let r = run_wasm(r#"
+        const memory = new WebAssembly.Memory({ initial: 1, maximum: 2 });
+        new Uint8Array(memory.buffer)[0] = 77;
+        const inst = new WebAssembly.Instance(new WebAssembly.Module(peek), { env: { memory } });
+        return inst.exports.peek() + "|" + (memory instanceof WebAssembly.Memory);
+    "#);
+    assert_eq!(as_str(r), "77|true");
+}
+
+#[test]
+fn shared_memory_is_rejected() {
+    // wasmi nema vlakna - sdilenou pamet by zadny modul neimportoval
+    let r = run_wasm(r#"
+        try { new WebAssembly.Memory({ initial: 1, maximum: 2, shared: true }); return "created"; }
+        catch (e) { return String(e); }
+    "#);
+    assert_eq!(as_str(r), "TypeError: WebAssembly.Memory(): shared memory is not supported by this engine");
+}
+
+#[test]
+fn table_holds_exported_functions() {
+    let r = run_calc(r#"
+        const f = x.tbl.get(0);
+        x.tbl.set(1, x.div);
+        const grown = x.tbl.grow(1);
+        return [f === x.add, f(1, 2), x.tbl.get(1)(9, 3), x.tbl.get(2) === null, grown, x.tbl.length].join(",");
+    "#);
+    assert_eq!(as_str(r), "true,3,3,true,2,3");
+}
+
+#[test]
+fn table_rejects_plain_js_function() {
+    let r = run_wasm(r#"
+        const t = new WebAssembly.Table({ element: "anyfunc", initial: 1 });
+        try { t.set(0, () => 1); return "no"; } catch (e) { return String(e).startsWith("TypeError"); }
+    "#);
+    assert!(as_bool(r));
+}
+
+#[test]
+fn globals_read_write() {
+    let r = run_calc(r#"
+        const before = x.g.value;
+        x.g.value = 12;
+        const own = new WebAssembly.Global({ value: "i64" }, 5n);
+        let immutable;
+        try { own.value = 1n; } catch (e) { immutable = String(e).startsWith("TypeError"); }
+        return [before, x.g.value, own.value, immutable, new WebAssembly.Global({ value: "f32", mutable: true }).value].join(",");
+    "#);
+    assert_eq!(as_str(r), "7,12,5,true,0");
+}
+
+#[test]
+fn module_reflection() {
+    let r = run_wasm(r#"
+        const m = new WebAssembly.Module(calc);
+        const imports = WebAssembly.Module.imports(m).map(i => i.module + "." + i.name + ":" + i.kind).join(";");
+        const exports = WebAssembly.Module.exports(m).map(e => e.name + ":" + e.kind).join(";");
+        const meta = WebAssembly.Module.customSections(m, "meta");
+        return imports + "|" + exports + "|" + meta.length + ":" + new TextDecoder().decode(meta[0]);
+    "#);
+    assert_eq!(as_str(r), "env.log:function|mem:memory;add:function;callLog:function;grow:function;load:function;trap:function;div:function;wide:function;g:global;tbl:table|1:hi");
+}
+
+#[test]
+fn trap_becomes_runtime_error_with_stack() {
+    let r = run_calc(r#"
+        try { x.trap(); } catch (e) {
+            return [e instanceof WebAssembly.RuntimeError, e instanceof Error, e.message, e.stack.includes("at boom (wasm-function[5])")].join(",");
+        }
+    "#);
+    assert_eq!(as_str(r), "true,true,unreachable,true");
+}
+
+#[test]
+fn division_by_zero_traps() {
+    let r = run_calc(r#"
+        try { x.div(1, 0); } catch (e) { return e.name + ": " + e.message; }
+    "#);
+    assert_eq!(as_str(r), "RuntimeError: divide by zero");
+}
+
+#[test]
+fn js_exception_propagates_through_wasm() {
+    let r = run_wasm(r#"
+        const inst = new WebAssembly.Instance(new WebAssembly.Module(calc), {
+            env: { log: v => { throw { code: v }; } },
+        });
+        try { inst.exports.callLog(7); } catch (e) { return e.code; }
+    "#);
+    assert_eq!(as_num(r), 7.0);
+}
+
+#[test]
+fn missing_import_is_type_error_and_bad_import_link_error() {
+    let r = run_wasm(r#"
+        const m = new WebAssembly.Module(calc);
+        let a, b;
+        try { new WebAssembly.Instance(m, {}); } catch (e) { a = String(e).startsWith("TypeError"); }
+        try { new WebAssembly.Instance(m, { env: { log: 1 } }); } catch (e) { b = e instanceof WebAssembly.LinkError; }
+        return a + "|" + b;
+    "#);
+    assert_eq!(as_str(r), "true|true");
+}
+
+#[test]
+fn compile_error_rejects_promise() {
+    let r = run_wasm(r#"
+        let out = "pending";
+        WebAssembly.compile(new Uint8Array([0, 97, 115, 109, 1, 0, 0, 0, 99])).catch(e => { out = e instanceof WebAssembly.CompileError; });
+        return out;
+    "#);
+    assert!(as_bool(r));
+}
+
+#[test]
+fn instantiate_returns_module_and_instance() {
+    let r = run_wasm(r#"
+        let out;
+        WebAssembly.instantiate(calc, { env: { log: v => v } }).then(({ module, instance }) => {
+            out = (module instanceof WebAssembly.Module) + "|" + instance.exports.callLog(1);
+        });
+        return out;
+    "#);
+    assert_eq!(as_str(r), "true|2");
+}
+
+#[test]
+fn instantiate_streaming_over_response() {
+    let r = run_wasm(r#"
+        let out, mime;
+        const response = new Response(calc, { headers: { "Content-Type": "application/wasm" } });
+        WebAssembly.instantiateStreaming(response, { env: { log: v => v } }).then(r => { out = r.instance.exports.add(20, 22); });
+        WebAssembly.compileStreaming(new Response(calc)).catch(e => { mime = e instanceof TypeError; });
+        return out + "|" + mime;
+    "#);
+    assert_eq!(as_str(r), "42|true");
+}
+
+#[test]
+fn tag_and_exception_payload() {
+    let r = run_wasm(r#"
+        const tag = new WebAssembly.Tag({ parameters: ["i32", "f64"] });
+        const other = new WebAssembly.Tag({ parameters: ["i32", "f64"] });
+        const ex = new WebAssembly.Exception(tag, [3.7, 1.5]);
+        let mismatch;
+        try { ex.getArg(other, 0); } catch (e) { mismatch = String(e).startsWith("TypeError"); }
+        return [ex.is(tag), ex.is(other), ex.getArg(tag, 0), ex.getArg(tag, 1), mismatch, tag.type().parameters.join("/")].join(",");
+    "#);
+    assert_eq!(as_str(r), "true,false,3,1.5,true,i32/f64");
+}
@@ -0,0 +1,496 @@
+//! IANA casova pasma pro Temporal - pravidla z TZif souboru systemove
+//! databaze (`$TZDIR`, jinak `/usr/share/zoneinfo`).
+//!
+//! TZif v2+ nese explicitni prechody (64-bit casy) a v paticce POSIX TZ
+//! retezec pro casy za poslednim prechodem ("slim" soubory maji prechody
+//! jen do zavedeni aktualniho pravidla). Nactena pasma se cachuji per
+//! vlakno. Vse v sekundach, posun = vychod od UTC.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+const SECS_PER_DAY: i64 = 86_400;
+
+/// Pravidla jednoho pasma.
+#[derive(Debug)]
+pub struct Zone {
+    /// Identifikator v normalizovanem tvaru (`Europe/Prague`, `UTC`).
+    pub id: String,
+    /// Casy prechodu (UTC sekundy) a posun platny od nich.
+    transitions: Vec<(i64, i32)>,
+    /// Posun pred prvnim prechodem.
+    initial: i32,
+    /// Pravidlo za poslednim prechodem.
+    rule: Option<PosixTz>,
+}
+
+thread_local! {
+    static ZONES: RefCell<HashMap<String, Option<Rc<Zone>>>> = RefCell::new(HashMap::new());
+    static ZONE_IDS: RefCell<Option<Vec<String>>> = const { RefCell::new(None) };
+}
+
+fn zoneinfo_dir() -> PathBuf {
+    std::env::var_os("TZDIR").map(PathBuf::from).unwrap_or_else(|| PathBuf::from("/usr/share/zoneinfo"))
+}
+
+impl Zone {
+    /// Pevne UTC (bez databaze).
+    pub fn utc() -> Rc<Zone> {
+        Rc::new(Self::fixed_utc())
+    }
+
+    fn fixed_utc() -> Zone {
+        Zone { id: "UTC".into(), transitions: Vec::new(), initial: 0, rule: None }
+    }
+
+    /// Pasmo podle IANA identifikatoru (bez ohledu na velikost pismen).
+    pub fn load(id: &str) -> Option<Rc<Zone>> {
+        let key = id.to_ascii_lowercase();
+        if let Some(cached) = ZONES.with(|z| z.borrow().get(&key).cloned()) {
+            return cached;
+        }
+        let zone = Self::load_uncached(id).map(Rc::new);
+        ZONES.with(|z| z.borrow_mut().insert(key, zone.clone()));
+        zone
+    }
+
+    fn load_uncached(id: &str) -> Option<Zone> {
+        if id.eq_ignore_ascii_case("UTC") {
+            return Some(Self::fixed_utc());
+        }
+        let valid = !id.is_empty() && !id.starts_with('/') && !id.split('/').any(|p| p.is_empty() || p == "." || p == "..")
+            && id.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '/' | '_' | '-' | '+'));
+        if !valid {
+            return None;
+        }
+        let dir = zoneinfo_dir();
+        let name = if dir.join(id).is_file() { id.to_string() } else { canonical_case(&dir, id)? };
+        let bytes = std::fs::read(dir.join(&name)).ok()?;
+        let mut zone = parse_tzif(&bytes)?;
+        zone.id = name;
+        Some(zone)
+    }
+
+    /// Posun (sekundy) v okamziku `t` (UTC sekundy).
+    pub fn offset_at(&self, t: i64) -> i32 {
+        match self.transitions.partition_point(|(at, _)| *at <= t) {
+            0 => self.initial,
+            i if i == self.transitions.len() => match &self.rule {
+                Some(rule) => rule.offset_at(t),
+                None => self.transitions[i - 1].1,
+            },
+            i => self.transitions[i - 1].1,
+        }
+    }
+
+    /// Nejblizsi prechod (zmena posunu) ostre po `t`.
+    pub fn next_transition(&self, t: i64) -> Option<i64> {
+        let mut at = t;
+        // Zaznamy bez zmeny posunu (jen zmena zkratky) se preskakuji
+        for _ in 0..1000 {
+            let candidate = self.raw_next(at)?;
+            if self.offset_at(candidate - 1) != self.offset_at(candidate) {
+                return Some(candidate);
+            }
+            at = candidate;
+        }
+        None
+    }
+
+    /// Nejblizsi prechod ostre pred `t`.
+    pub fn previous_transition(&self, t: i64) -> Option<i64> {
+        let mut at = t;
+        for _ in 0..1000 {
+            let candidate = self.raw_previous(at)?;
+            if self.offset_at(candidate - 1) != self.offset_at(candidate) {
+                return Some(candidate);
+            }
+            at = candidate;
+        }
+        None
+    }
+
+    fn raw_next(&self, t: i64) -> Option<i64> {
+        let i = self.transitions.partition_point(|(at, _)| *at <= t);
+        if let Some((at, _)) = self.transitions.get(i) {
+            return Some(*at);
+        }
+        let last = self.transitions.last().map_or(i64::MIN, |(at, _)| *at);
+        let year = year_of(t.max(last));
+        (year..year + 2).flat_map(|y| self.rule.as_ref().map(|r| r.transitions(y)).unwrap_or_default())
+            .filter(|at| *at > t && *at > last)
+            .min()
+    }
+
+    fn raw_previous(&self, t: i64) -> Option<i64> {
+        let last = self.transitions.last().map_or(i64::MIN, |(at, _)| *at);
+        if t > last && let Some(rule) = &self.rule {
+            let year = year_of(t);
+            let found = (year - 1..=year).flat_map(|y| rule.transitions(y))
+                .filter(|at| *at < t && *at > last)
+                .max();
+            if found.is_some() {
+                return found;
+            }
+        }
+        let i = self.transitions.partition_point(|(at, _)| *at < t);
+        i.checked_sub(1).map(|i| self.transitions[i].0)
+    }
+}
+
+/// Identifikator se spravnou velikosti pismen (hledani v adresari databaze).
+fn canonical_case(dir: &Path, id: &str) -> Option<String> {
+    with_ids(dir, |list| list.iter().find(|n| n.eq_ignore_ascii_case(id)).cloned())
+}
+
+fn with_ids<T>(dir: &Path, f: impl FnOnce(&[String]) -> T) -> T {
+    ZONE_IDS.with(|ids| {
+        let mut ids = ids.borrow_mut();
+        let list = ids.get_or_insert_with(|| {
+            let mut out = Vec::new();
+            collect_ids(dir, "", &mut out);
+            out
+        });
+        f(list)
+    })
+}
+
+/// Primarni IANA identifikatory z databaze (bez zpetne kompatibilnich aliasu
+/// jako `US/Eastern`), serazene; pro `Intl.supportedValuesOf("timeZone")`.
+pub fn available_zone_ids() -> Vec<String> {
+    const AREAS: [&str; 9] = ["Africa", "America", "Antarctica", "Asia", "Atlantic", "Australia", "Europe", "Indian", "Pacific"];
+    let mut ids = with_ids(&zoneinfo_dir(), |list| {
+        list.iter()
+            .filter(|id| id.split_once('/').is_some_and(|(area, _)| AREAS.contains(&area)))
+            .cloned()
+            .collect::<Vec<_>>()
+    });
+    ids.push("UTC".into());
+    ids.sort();
+    ids
+}
+
+fn collect_ids(dir: &Path, prefix: &str, out: &mut Vec<String>) {
+    let Ok(entries) = std::fs::read_dir(dir) else { return };
+    for entry in entries.flatten() {
+        let name = entry.file_name().to_string_lossy().into_owned();
+        let rel = if prefix.is_empty() { name.clone() } else { format!("{prefix}/{name}") };
+        match entry.file_type() {
+            Ok(t) if t.is_dir() && !matches!(rel.as_str(), "posix" | "right") => collect_ids(&entry.path(), &rel, out),
+            Ok(t) if t.is_file() || t.is_symlink() => out.push(rel),
+            _ => {}
+        }
+    }
+}
+
+/// Vychozi pasmo systemu: `$TZ`, `/etc/localtime` (symlink do databaze),
+/// `/etc/timezone`; jinak UTC.
+pub fn system_zone_id() -> String {
+    if let Ok(tz) = std::env::var("TZ") {
+        let tz = tz.trim_start_matches(':');
+        if Zone::load(tz).is_some() {
+            return Zone::load(tz).map(|z| z.id.clone()).unwrap_or_default();
+        }
+    }
+    if let Ok(target) = std::fs::read_link("/etc/localtime") {
+        let target = target.to_string_lossy().into_owned();
+        if let Some((_, id)) = target.split_once("zoneinfo/") && let Some(zone) = Zone::load(id) {
+            return zone.id.clone();
+        }
+    }
+    if let Ok(text) = std::fs::read_to_string("/etc/timezone") && let Some(zone) = Zone::load(text.trim()) {
+        return zone.id.clone();
+    }
+    "UTC".into()
+}
+
+// ─── TZif ────────────────────────────────────────────────────────────────────
+
+struct Cursor<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl Cursor<'_> {
+    fn take(&mut self, n: usize) -> Option<&[u8]> {
+        let out = self.bytes.get(self.pos..self.pos + n)?;
+        self.pos += n;
+        Some(out)
+    }
+
+    fn u32(&mut self) -> Option<u32> {
+        self.take(4).map(|b| u32::from_be_bytes([b[0], b[1], b[2], b[3]]))
+    }
+}
+
+/// Pocty z hlavicky: (isut, isstd, leap, time, type, char).
+fn header(c: &mut Cursor) -> Option<(u8, [usize; 6])> {
+    if c.take(4)? != b"TZif" {
+        return None;
+    }
+    let version = c.take(16)?[0];
+    let mut counts = [0usize; 6];
+    for n in &mut counts {
+        *n = c.u32()? as usize;
+    }
+    Some((version, counts))
+}
+
+fn parse_tzif(bytes: &[u8]) -> Option<Zone> {
+    let mut c = Cursor { bytes, pos: 0 };
+    let (version, counts) = header(&mut c)?;
+    let mut time_size = 4;
+    let mut counts = counts;
+    if version >= b'2' {
+        // v1 blok preskocit, v2 ma 64-bit casy
+        let [isut, isstd, leap, time, types, chars] = counts;
+        c.take(time * 5 + types * 6 + chars + leap * 8 + isstd + isut)?;
+        counts = header(&mut c)?.1;
+        time_size = 8;
+    }
+    let [isut, isstd, leap, time, types, chars] = counts;
+    let times: Vec<i64> = (0..time).map(|_| {
+        let b = c.take(time_size)?;
+        Some(if time_size == 8 {
+            i64::from_be_bytes(b.try_into().ok()?)
+        } else {
+            i32::from_be_bytes(b.try_into().ok()?) as i64
+        })
+    }).collect::<Option<_>>()?;
+    let indices = c.take(time)?.to_vec();
+    let offsets: Vec<(i32, bool)> = (0..types).map(|_| {
+        let b = c.take(6)?;
+        Some((i32::from_be_bytes([b[0], b[1], b[2], b[3]]), b[4] != 0))
+    }).collect::<Option<_>>()?;
+    c.take(chars + leap * (time_size + 4) + isstd + isut)?;
+    let rule = if version >= b'2' {
+        let footer = String::from_utf8_lossy(&bytes[c.pos..]).into_owned();
+        PosixTz::parse(footer.trim_matches('\n'))
+    } else {
+        None
+    };
+    let transitions = times.into_iter().zip(indices)
+        .map(|(at, i)| Some((at, offsets.get(i as usize)?.0)))
+        .collect::<Option<Vec<_>>>()?;
+    // Pred prvnim prechodem plati prvni ne-DST typ (RFC 8536 3.2)
+    let initial = offsets.iter().find(|(_, dst)| !dst).or(offsets.first()).map_or(0, |(off, _)| *off);
+    Some(Zone { id: String::new(), transitions, initial, rule })
+}
+
+// ─── POSIX TZ pravidla (paticka TZif) ───────────────────────────────────────
+
+#[derive(Debug, Clone, Copy)]
+enum RuleDate {
+    /// `Jn` - den 1..365 bez 29. unora.
+    Julian(u16),
+    /// `n` - den 0..365 vcetne 29. unora.
+    Zero(u16),
+    /// `Mm.w.d` - d-ty den v tydnu w-teho tydne mesice m (5 = posledni).
+    Month(u8, u8, u8),
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Rule {
+    date: RuleDate,
+    /// Mistni cas prechodu (sekundy od pulnoci, muze byt zaporny / > 24h).
+    time: i64,
+}
+
+#[derive(Debug, Clone)]
+struct PosixTz {
+    std: i32,
+    /// (letni posun, zacatek, konec).
+    dst: Option<(i32, Rule, Rule)>,
+}
+
+impl PosixTz {
+    fn parse(s: &str) -> Option<PosixTz> {
+        let mut p = PosixParser { s: s.as_bytes(), pos: 0 };
+        p.name()?;
+        let std = -p.offset()?;
+        if p.pos == p.s.len() {
+            return Some(PosixTz { std, dst: None });
+        }
+        p.name()?;
+        let dst_off = if matches!(p.peek(), Some(b',') | None) { std + 3600 } else { -p.offset()? };
+        p.eat(b',')?;
+        let start = p.rule()?;
+        p.eat(b',')?;
+        let end = p.rule()?;
+        Some(PosixTz { std, dst: Some((dst_off, start, end)) })
+    }
+
+    /// Prechody v roce `year`: (zacatek DST, konec DST) v UTC sekundach.
+    fn transitions(&self, year: i32) -> Vec<i64> {
+        match self.dst {
+            Some((dst, start, end)) => vec![
+                rule_local_secs(year, start) - self.std as i64,
+                rule_local_secs(year, end) - dst as i64,
+            ],
+            None => Vec::new(),
+        }
+    }
+
+    fn offset_at(&self, t: i64) -> i32 {
+        let Some((dst, _, _)) = self.dst else { return self.std };
+        let [start, end] = self.transitions(year_of(t))[..] else { return self.std };
+        let in_dst = if start < end { t >= start && t < end } else { t >= start || t < end };
+        if in_dst { dst } else { self.std }
+    }
+}
+
+struct PosixParser<'a> {
+    s: &'a [u8],
+    pos: usize,
+}
+
+impl PosixParser<'_> {
+    fn peek(&self) -> Option<u8> {
+        self.s.get(self.pos).copied()
+    }
+
+    fn eat(&mut self, b: u8) -> Option<()> {
+        (self.peek()? == b).then(|| self.pos += 1)
+    }
+
+    fn name(&mut self) -> Option<()> {
+        if self.peek()? == b'<' {
+            let close = self.s[self.pos..].iter().position(|b| *b == b'>')?;
+            self.pos += close + 1;
+        } else {
+            let start = self.pos;
+            while self.peek().is_some_and(|b| b.is_ascii_alphabetic()) { self.pos += 1; }
+            if self.pos - start < 3 { return None; }
+        }
+        Some(())
+    }
+
+    fn number(&mut self) -> Option<i64> {
+        let start = self.pos;
+        while self.peek().is_some_and(|b| b.is_ascii_digit()) { self.pos += 1; }
+        std::str::from_utf8(&self.s[start..self.pos]).ok()?.parse().ok()
+    }
+
+    /// `[+-]hh[:mm[:ss]]` v sekundach (POSIX znamenko).
+    fn offset(&mut self) -> Option<i32> {
+        let sign = match self.peek() {
+            Some(b'-') => { self.pos += 1; -1 }
+            Some(b'+') => { self.pos += 1; 1 }
+            _ => 1,
+        };
+        let mut secs = self.number()? * 3600;
+        for scale in [60, 1] {
+            if self.peek() != Some(b':') { break; }
+            self.pos += 1;
+            secs += self.number()? * scale;
+        }
+        i32::try_from(sign * secs).ok()
+    }
+
+    fn rule(&mut self) -> Option<Rule> {
+        let date = match self.peek()? {
+            b'J' => { self.pos += 1; RuleDate::Julian(self.number()? as u16) }
+            b'M' => {
+                self.pos += 1;
+                let m = self.number()? as u8;
+                self.eat(b'.')?;
+                let w = self.number()? as u8;
+                self.eat(b'.')?;
+                RuleDate::Month(m, w, self.number()? as u8)
+            }
+            _ => RuleDate::Zero(self.number()? as u16),
+        };
+        let time = if self.peek() == Some(b'/') {
+            self.pos += 1;
+            self.offset()? as i64
+        } else {
+            7200
+        };
+        Some(Rule { date, time })
+    }
+}
+
+/// Mistni cas prechodu `rule` v roce `year` (sekundy od epochy bez posunu).
+fn rule_local_secs(year: i32, rule: Rule) -> i64 {
+    let days = match rule.date {
+        RuleDate::Julian(n) => {
+            let n = n.clamp(1, 365) as i64;
+            let leap_shift = if is_leap(year) && n >= 60 { 1 } else { 0 };
+            days_from_civil(year, 1, 1) + n - 1 + leap_shift
+        }
+        RuleDate::Zero(n) => days_from_civil(year, 1, 1) + n as i64,
+        RuleDate::Month(m, w, d) => {
+            let first = days_from_civil(year, m, 1);
+            // 1970-01-01 byl ctvrtek (4), 0 = nedele
+            let first_weekday = (first + 4).rem_euclid(7);
+            let mut day = first + (d as i64 - first_weekday).rem_euclid(7) + (w.clamp(1, 5) as i64 - 1) * 7;
+            let next_month = if m == 12 { days_from_civil(year + 1, 1, 1) } else { days_from_civil(year, m + 1, 1) };
+            while day >= next_month { day -= 7; }
+            day
+        }
+    };
+    days * SECS_PER_DAY + rule.time
+}
+
+fn is_leap(y: i32) -> bool {
+    y % 4 == 0 && (y % 100 != 0 || y % 400 == 0)
+}
+
+/// Dny od 1970-01-01 (proleptic gregorian).
+fn days_from_civil(y: i32, m: u8, d: u8) -> i64 {
+    let y = y as i64 - if m <= 2 { 1 } else { 0 };
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let mp = (m as i64 + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + d as i64 - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+/// Rok (UTC) okamziku `t`.
+fn year_of(t: i64) -> i32 {
+    let days = t.div_euclid(SECS_PER_DAY);
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    (yoe + era * 400 + if month <= 2 { 1 } else { 0 }) as i32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn posix_rule_eu() {
+        let tz = PosixTz::parse("CET-1CEST,M3.5.0,M10.5.0/3").unwrap();
+        // 2024-03-31 01:00 UTC zacina CEST, 2024-10-27 01:00 UTC konci
+        let start = days_from_civil(2024, 3, 31) * SECS_PER_DAY + 3600;
+        let end = days_from_civil(2024, 10, 27) * SECS_PER_DAY + 3600;
+        assert_eq!(tz.transitions(2024), vec![start, end]);
+        assert_eq!(tz.offset_at(start - 1), 3600);
+        assert_eq!(tz.offset_at(start), 7200);
+        assert_eq!(tz.offset_at(end), 3600);
+    }
+
+    #[test]
+    fn posix_rule_southern_and_fixed() {
+        let tz = PosixTz::parse("AEST-10AEDT,M10.1.0,M4.1.0/3").unwrap();
+        let jan = days_from_civil(2024, 1, 15) * SECS_PER_DAY;
+        let jul = days_from_civil(2024, 7, 15) * SECS_PER_DAY;
+        assert_eq!((tz.offset_at(jan), tz.offset_at(jul)), (39600, 36000));
+        assert_eq!(PosixTz::parse("<+0530>-5:30").unwrap().offset_at(0), 19800);
+    }
+
+    #[test]
+    fn year_of_epoch_seconds() {
+        assert_eq!(year_of(0), 1970);
+        assert_eq!(year_of(-1), 1969);
+        assert_eq!(year_of(days_from_civil(2000, 2, 29) * SECS_PER_DAY), 2000);
+    }
+}
@@ -1,7 +1,24 @@
-//! Typed Arrays + DataView - SharedArrayBuffer + atomics primitives.
+//! Typed Arrays + DataView - bytovy model ArrayBufferu.
 //!
 //! ECMA-262 25.1+.
-//! 11 typed array kinds (Int8/Uint8/Uint8Clamped/Int16/Uint16/Int32/Uint32/Float16/Float32/Float64/BigInt64/BigUint64).
+//! 12 typed array kinds (Int8/Uint8/Uint8Clamped/Int16/Uint16/Int32/Uint32/Float16/Float32/Float64/BigInt64/BigUint64).
+//!
+//! `ArrayBuffer` drzi skutecny `Vec<u8>` (SharedArrayBuffer `Arc<SharedMemory>`
+//! sdileny s Workery) za `SharedBuffer` (Rc<RefCell<>>), takze
+//! typed array view, DataView, canvas ImageData, WebGL i wasm pamet ctou a pisou
+//! stejne bajty. JS objekt nese handle v `JsObject::binary` - indexy a delky
+//! (`ta[i]`, `length`, `byteLength`, ...) resi `BinaryData::get` / `set` primo
+//! nad bajty, bez vlastnosti v property bagu.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::sync::Arc;
+
+use num_bigint::BigInt;
+use num_traits::ToPrimitive;
+
+use super::JsValue;
+use super::shared_memory::SharedMemory;
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum TypedArrayKind {
@@ -13,6 +30,14 @@ pub enum TypedArrayKind {
 }
 
 impl TypedArrayKind {
+    pub const ALL: [TypedArrayKind; 12] = [
+        Self::Int8, Self::Uint8, Self::Uint8Clamped,
+        Self::Int16, Self::Uint16,
+        Self::Int32, Self::Uint32,
+        Self::Float16, Self::Float32, Self::Float64,
+        Self::BigInt64, Self::BigUint64,
+    ];
+
     pub fn element_size(&self) -> usize {
         match self {
             Self::Int8 | Self::Uint8 | Self::Uint8Clamped => 1,
@@ -56,75 +81,520 @@ impl TypedArrayKind {
             _ => return None,
         })
     }
+
+    /// BigInt64Array / BigUint64Array - prvky jsou BigInt, ne Number.
+    pub fn is_bigint(&self) -> bool {
+        matches!(self, Self::BigInt64 | Self::BigUint64)
+    }
+
+    /// Dekoduje jeden prvek z `raw` (presne `element_size` bajtu).
+    pub fn decode(&self, raw: &[u8], endian: Endian) -> JsValue {
+        let mut b = [0u8; 8];
+        let size = self.element_size();
+        b[..size].copy_from_slice(&raw[..size]);
+        if endian == Endian::Big { b[..size].reverse(); }
+        let n = match self {
+            Self::Int8 => b[0] as i8 as f64,
+            Self::Uint8 | Self::Uint8Clamped => b[0] as f64,
+            Self::Int16 => i16::from_le_bytes([b[0], b[1]]) as f64,
+            Self::Uint16 => u16::from_le_bytes([b[0], b[1]]) as f64,
+            Self::Int32 => i32::from_le_bytes([b[0], b[1], b[2], b[3]]) as f64,
+            Self::Uint32 => u32::from_le_bytes([b[0], b[1], b[2], b[3]]) as f64,
+            Self::Float16 => f16_bits_to_f64(u16::from_le_bytes([b[0], b[1]])),
+            Self::Float32 => f32::from_le_bytes([b[0], b[1], b[2], b[3]]) as f64,
+            Self::Float64 => f64::from_le_bytes(b),
+            Self::BigInt64 => return JsValue::BigInt(Rc::new(BigInt::from(i64::from_le_bytes(b)))),
+            Self::BigUint64 => return JsValue::BigInt(Rc::new(BigInt::from(u64::from_le_bytes(b)))),
+        };
+        JsValue::Number(n)
+    }
+
+    /// Zakoduje hodnotu podle konverzi ECMA-262 (ToInt8, ToUint8Clamp, ToBigInt64, ...).
+    /// Vraci bajty v `[..element_size]`; TypeError pri michani BigInt a Number.
+    pub fn encode(&self, v: &JsValue, endian: Endian) -> Result<[u8; 8], String> {
+        let mut b = [0u8; 8];
+        if self.is_bigint() {
+            let big = match v {
+                JsValue::BigInt(n) => (**n).clone(),
+                JsValue::Bool(x) => BigInt::from(*x as u8),
+                JsValue::Str(s) => s.trim().parse::<BigInt>()
+                    .map_err(|_| format!("SyntaxError: Cannot convert {s} to a BigInt"))?,
+                other => return Err(format!("TypeError: Cannot convert {other} to a BigInt")),
+            };
+            let bits = (big & BigInt::from(u64::MAX)).to_u64().unwrap_or(0);
+            b = bits.to_le_bytes();
+        } else {
+            if matches!(v, JsValue::BigInt(_)) {
+                return Err("TypeError: Cannot convert a BigInt value to a number".into());
+            }
+            let n = v.to_number();
+            match self {
+                Self::Int8 | Self::Uint8 => b[0] = wrap_int(n, 8) as u8,
+                Self::Uint8Clamped => b[0] = if n.is_nan() { 0 } else { n.clamp(0.0, 255.0).round_ties_even() as u8 },
+                Self::Int16 | Self::Uint16 => b[..2].copy_from_slice(&(wrap_int(n, 16) as u16).to_le_bytes()),
+                Self::Int32 | Self::Uint32 => b[..4].copy_from_slice(&(wrap_int(n, 32) as u32).to_le_bytes()),
+                Self::Float16 => b[..2].copy_from_slice(&f64_to_f16_bits(n).to_le_bytes()),
+                Self::Float32 => b[..4].copy_from_slice(&(n as f32).to_le_bytes()),
+                Self::Float64 => b = n.to_le_bytes(),
+                Self::BigInt64 | Self::BigUint64 => unreachable!(),
+            }
+        }
+        if endian == Endian::Big { b[..self.element_size()].reverse(); }
+        Ok(b)
+    }
+}
+
+/// ToInt8/16/32 a ToUint*: orez na cele cislo modulo 2^bits (NaN/Infinity = 0).
+fn wrap_int(n: f64, bits: i32) -> u64 {
+    if !n.is_finite() { return 0; }
+    n.trunc().rem_euclid(2f64.powi(bits)) as u64
+}
+
+/// IEEE 754 binary16 -> f64.
+pub fn f16_bits_to_f64(h: u16) -> f64 {
+    let sign = if h & 0x8000 != 0 { -1.0 } else { 1.0 };
+    let exp = ((h >> 10) & 0x1f) as i32;
+    let mant = (h & 0x3ff) as f64;
+    match exp {
+        0 => sign * mant * 2f64.powi(-24),
+        31 if mant == 0.0 => sign * f64::INFINITY,
+        31 => f64::NAN,
+        _ => sign * (1.0 + mant / 1024.0) * 2f64.powi(exp - 15),
+    }
+}
+
+/// f64 -> IEEE 754 binary16, zaokrouhleni roundTiesToEven primo z f64 (bez
+/// dvojiteho zaokrouhleni pres f32).
+pub fn f64_to_f16_bits(x: f64) -> u16 {
+    let sign = ((x.to_bits() >> 48) & 0x8000) as u16;
+    if x.is_nan() { return 0x7e00; }
+    let a = x.abs();
+    // 65520 = polovina mezi max (65504) a 2^16 - ties-to-even konci v nekonecnu
+    if a >= 65520.0 { return sign | 0x7c00; }
+    if a < 2f64.powi(-14) {
+        // Subnormal: jednotky 2^-24 (nasobeni mocninou dvou je presne)
+        return sign | (a * 2f64.powi(24)).round_ties_even() as u16;
+    }
+    let bits = a.to_bits();
+    let exp = ((bits >> 52) & 0x7ff) as i64 - 1023;
+    let frac = bits & ((1u64 << 52) - 1);
+    let mut mant = frac >> 42;
+    let rest = frac & ((1u64 << 42) - 1);
+    let half = 1u64 << 41;
+    if rest > half || (rest == half && mant & 1 == 1) { mant += 1; }
+    // Preteceni mantisy prirozene zvedne exponent
+    sign | ((((exp + 15) as u64) << 10) + mant) as u16
+}
+
+/// Handle bufferu - jeden buffer pro vsechny pohledy na nej.
+pub type SharedBuffer = Rc<RefCell<ArrayBuffer>>;
+
+/// Bajty drzene mimo buffer - linearni pamet WebAssembly instance (`wasm.rs`).
+/// Buffer po `memory.grow` uz neplati: `byte_length` pak vraci 0.
+pub trait LinearMemory: std::fmt::Debug {
+    fn byte_length(&self) -> usize;
+    fn read(&self, start: usize, out: &mut [u8]) -> bool;
+    fn write(&self, start: usize, data: &[u8]) -> bool;
+}
+
+/// Uloziste bajtu: vlastni `Vec` (ArrayBuffer), pamet sdilena mezi
+/// vlakny (SharedArrayBuffer, viz `shared_memory.rs`) nebo `memory.buffer`
+/// WebAssembly instance.
+#[derive(Debug, Clone)]
+enum Storage {
+    Local(Vec<u8>),
+    Shared(Arc<SharedMemory>),
+    Linear(Rc<dyn LinearMemory>),
 }
 
 #[derive(Debug, Clone)]
 pub struct ArrayBuffer {
-    pub bytes: Vec<u8>,
-    pub max_byte_length: Option<usize>,    // resizable buffer (TC39 stage 3)
+    storage: Storage,
+    pub max_byte_length: Option<usize>,    // resizable buffer (ES2024)
     pub detached: bool,
 }
 
 impl ArrayBuffer {
     pub fn new(size: usize) -> Self {
-        Self { bytes: vec![0; size], max_byte_length: None, detached: false }
+        Self::from_bytes(vec![0; size])
+    }
+
+    pub fn from_bytes(bytes: Vec<u8>) -> Self {
+        Self { storage: Storage::Local(bytes), max_byte_length: None, detached: false }
+    }
+
+    /// `new ArrayBuffer(len, { maxByteLength })`.
+    pub fn resizable(size: usize, max: usize) -> Result<Self, String> {
+        if size > max {
+            return Err("RangeError: byteLength exceeds maxByteLength".into());
+        }
+        Ok(Self { max_byte_length: Some(max), ..Self::new(size) })
+    }
+
+    /// SharedArrayBuffer nad pameti sdilenou s jinymi agenty.
+    pub fn from_shared(memory: Arc<SharedMemory>) -> Self {
+        Self { max_byte_length: memory.max_byte_length(), storage: Storage::Shared(memory), detached: false }
+    }
+
+    /// `memory.buffer` nad linearni pameti WebAssembly - nelze resize ani transfer.
+    pub fn from_linear(memory: Rc<dyn LinearMemory>) -> Self {
+        Self { storage: Storage::Linear(memory), max_byte_length: None, detached: false }
+    }
+
+    pub fn into_handle(self) -> SharedBuffer {
+        Rc::new(RefCell::new(self))
+    }
+
+    /// SharedArrayBuffer - nelze odpojit ani presunout.
+    pub fn is_shared(&self) -> bool {
+        matches!(self.storage, Storage::Shared(_))
+    }
+
+    pub fn shared_memory(&self) -> Option<&Arc<SharedMemory>> {
+        match &self.storage {
+            Storage::Shared(m) => Some(m),
+            Storage::Local(_) | Storage::Linear(_) => None,
+        }
+    }
+
+    pub fn byte_length(&self) -> usize {
+        match &self.storage {
+            Storage::Local(b) => b.len(),
+            Storage::Shared(m) => m.len(),
+            Storage::Linear(m) => m.byte_length(),
+        }
+    }
+
+    /// Cteni bajtu od `start`; mimo rozsah vrati false a `out` nemeni.
+    pub fn read(&self, start: usize, out: &mut [u8]) -> bool {
+        match &self.storage {
+            Storage::Local(b) => match b.get(start..start + out.len()) {
+                Some(src) => { out.copy_from_slice(src); true }
+                None => false,
+            },
+            Storage::Shared(m) => m.read(start, out),
+            Storage::Linear(m) => m.read(start, out),
+        }
+    }
+
+    pub fn write(&mut self, start: usize, data: &[u8]) -> bool {
+        match &mut self.storage {
+            Storage::Local(b) => match b.get_mut(start..start + data.len()) {
+                Some(dst) => { dst.copy_from_slice(data); true }
+                None => false,
+            },
+            Storage::Shared(m) => m.write(start, data),
+            Storage::Linear(m) => m.write(start, data),
+        }
+    }
+
+    /// Kopie rozsahu bajtu (orezana na delku bufferu).
+    pub fn slice(&self, range: std::ops::Range<usize>) -> Vec<u8> {
+        let end = range.end.min(self.byte_length());
+        let mut out = vec![0; end.saturating_sub(range.start)];
+        self.read(range.start, &mut out);
+        out
+    }
+
+    pub fn to_vec(&self) -> Vec<u8> {
+        self.slice(0..self.byte_length())
+    }
+
+    pub fn copy_within(&mut self, src: std::ops::Range<usize>, dest: usize) {
+        match &mut self.storage {
+            Storage::Local(b) => b.copy_within(src, dest),
+            Storage::Shared(m) => m.copy_within(src, dest),
+            Storage::Linear(m) => {
+                let mut bytes = vec![0; src.len()];
+                if m.read(src.start, &mut bytes) { m.write(dest, &bytes); }
+            }
+        }
     }
 
     pub fn resize(&mut self, new_size: usize) -> Result<(), String> {
-        let Some(max) = self.max_byte_length else { return Err("non-resizable".into()); };
-        if new_size > max { return Err("exceeds max_byte_length".into()); }
-        self.bytes.resize(new_size, 0);
+        if self.detached { return Err("TypeError: ArrayBuffer is detached".into()); }
+        let Some(max) = self.max_byte_length else {
+            return Err("TypeError: ArrayBuffer is not resizable".into());
+        };
+        if new_size > max { return Err("RangeError: new byteLength exceeds maxByteLength".into()); }
+        match &mut self.storage {
+            Storage::Local(b) => b.resize(new_size, 0),
+            Storage::Shared(m) => m.grow(new_size)?,
+            Storage::Linear(_) => return Err("TypeError: ArrayBuffer is not resizable".into()),
+        }
         Ok(())
     }
 
     pub fn detach(&mut self) -> Vec<u8> {
         self.detached = true;
-        std::mem::take(&mut self.bytes)
+        match &mut self.storage {
+            Storage::Local(b) => std::mem::take(b),
+            Storage::Shared(m) => m.to_vec(),
+            // Bajty patri wasm pameti, buffer o ne jen prijde
+            Storage::Linear(_) => {
+                self.storage = Storage::Local(Vec::new());
+                Vec::new()
+            }
+        }
+    }
+
+    /// `transfer(newLength)` / `transferToFixedLength(newLength)`: presune bajty
+    /// do noveho bufferu (orez / doplneni nulami) a tento odpoji.
+    pub fn transfer(&mut self, new_len: Option<usize>, keep_resizable: bool) -> Result<ArrayBuffer, String> {
+        if self.detached { return Err("TypeError: Cannot transfer a detached ArrayBuffer".into()); }
+        if self.is_shared() { return Err("TypeError: Cannot transfer a SharedArrayBuffer".into()); }
+        if matches!(self.storage, Storage::Linear(_)) {
+            return Err("TypeError: Cannot transfer a WebAssembly.Memory buffer".into());
+        }
+        let max = if keep_resizable { self.max_byte_length } else { None };
+        let len = new_len.unwrap_or(self.byte_length());
+        if let Some(m) = max && len > m {
+            return Err("RangeError: new byteLength exceeds maxByteLength".into());
+        }
+        let mut bytes = self.detach();
+        bytes.resize(len, 0);
+        Ok(ArrayBuffer { max_byte_length: max, ..ArrayBuffer::from_bytes(bytes) })
     }
 }
 
 #[derive(Debug, Clone)]
 pub struct TypedArrayView {
-    pub buffer_index: usize,        // pointer into a buffer registry
+    pub buffer: SharedBuffer,
     pub byte_offset: usize,
-    pub length: usize,              // in elements
+    /// Delka v prvcich; None = length-tracking view nad resizable bufferem.
+    pub fixed_length: Option<usize>,
     pub kind: TypedArrayKind,
 }
 
 impl TypedArrayView {
+    /// View pres cely buffer od `byte_offset`.
+    pub fn new(buffer: SharedBuffer, kind: TypedArrayKind, byte_offset: usize, fixed_length: Option<usize>) -> Self {
+        Self { buffer, byte_offset, fixed_length, kind }
+    }
+
+    /// Detached buffer nebo view po zmenseni bufferu mimo jeho rozsah.
+    pub fn is_out_of_bounds(&self) -> bool {
+        let buf = self.buffer.borrow();
+        if buf.detached || self.byte_offset > buf.byte_length() { return true; }
+        match self.fixed_length {
+            Some(n) => self.byte_offset + n * self.kind.element_size() > buf.byte_length(),
+            None => false,
+        }
+    }
+
+    pub fn length(&self) -> usize {
+        if self.is_out_of_bounds() { return 0; }
+        match self.fixed_length {
+            Some(n) => n,
+            None => (self.buffer.borrow().byte_length() - self.byte_offset) / self.kind.element_size(),
+        }
+    }
+
     pub fn byte_length(&self) -> usize {
-        self.length * self.kind.element_size()
+        self.length() * self.kind.element_size()
+    }
+
+    pub fn get(&self, index: usize) -> Option<JsValue> {
+        if index >= self.length() { return None; }
+        let size = self.kind.element_size();
+        let start = self.byte_offset + index * size;
+        let mut raw = [0u8; 8];
+        self.buffer.borrow().read(start, &mut raw[..size]);
+        Some(self.kind.decode(&raw[..size], Endian::Little))
+    }
+
+    /// Zapis prvku. Index mimo rozsah se tise ignoruje (integer-indexed exotic).
+    pub fn set(&self, index: usize, v: &JsValue) -> Result<(), String> {
+        let raw = self.kind.encode(v, Endian::Little)?;
+        if index >= self.length() { return Ok(()); }
+        let size = self.kind.element_size();
+        let start = self.byte_offset + index * size;
+        self.buffer.borrow_mut().write(start, &raw[..size]);
+        Ok(())
+    }
+
+    /// Kopie prvku jako JS hodnoty (pro Array.from, join, callback metody).
+    pub fn to_values(&self) -> Vec<JsValue> {
+        (0..self.length()).filter_map(|i| self.get(i)).collect()
+    }
+
+    /// Bajty, ktere view prekryva.
+    pub fn bytes(&self) -> Vec<u8> {
+        let len = self.byte_length();
+        if len == 0 { return Vec::new(); }
+        self.buffer.borrow().slice(self.byte_offset..self.byte_offset + len)
     }
 }
 
+/// DataView - netypovany pohled s explicitnim endianem kazdeho cteni/zapisu.
+#[derive(Debug, Clone)]
+pub struct DataViewSlot {
+    pub buffer: SharedBuffer,
+    pub byte_offset: usize,
+    /// None = length-tracking (resizable buffer bez explicitni delky).
+    pub fixed_length: Option<usize>,
+}
+
+impl DataViewSlot {
+    pub fn byte_length(&self) -> usize {
+        let buf = self.buffer.borrow();
+        let len = buf.byte_length();
+        if buf.detached || self.byte_offset > len { return 0; }
+        match self.fixed_length {
+            Some(n) if self.byte_offset + n <= len => n,
+            Some(_) => 0,
+            None => len - self.byte_offset,
+        }
+    }
+
+    fn range(&self, kind: TypedArrayKind, offset: f64) -> Result<usize, String> {
+        if self.buffer.borrow().detached {
+            return Err("TypeError: Cannot perform DataView access on a detached ArrayBuffer".into());
+        }
+        let offset = if offset.is_nan() { 0.0 } else { offset.trunc() };
+        if offset < 0.0 || offset as usize + kind.element_size() > self.byte_length() {
+            return Err("RangeError: Offset is outside the bounds of the DataView".into());
+        }
+        Ok(self.byte_offset + offset as usize)
+    }
+
+    pub fn get(&self, kind: TypedArrayKind, offset: f64, endian: Endian) -> Result<JsValue, String> {
+        let start = self.range(kind, offset)?;
+        let size = kind.element_size();
+        let mut raw = [0u8; 8];
+        self.buffer.borrow().read(start, &mut raw[..size]);
+        Ok(kind.decode(&raw[..size], endian))
+    }
+
+    pub fn set(&self, kind: TypedArrayKind, offset: f64, v: &JsValue, endian: Endian) -> Result<(), String> {
+        let raw = kind.encode(v, endian)?;
+        let start = self.range(kind, offset)?;
+        let size = kind.element_size();
+        self.buffer.borrow_mut().write(start, &raw[..size]);
+        Ok(())
+    }
+}
+
+/// Nativni data exotickeho objektu - `JsObject::binary`.
+#[derive(Debug, Clone)]
+pub enum BinaryData {
+    Buffer(SharedBuffer),
+    TypedArray(TypedArrayView),
+    DataView(DataViewSlot),
+}
+
+impl BinaryData {
+    pub fn buffer(&self) -> &SharedBuffer {
+        match self {
+            Self::Buffer(b) => b,
+            Self::TypedArray(v) => &v.buffer,
+            Self::DataView(d) => &d.buffer,
+        }
+    }
+
+    /// Jmeno konstruktoru pro `instanceof` a `Object.prototype.toString`.
+    pub fn class_name(&self) -> &'static str {
+        match self {
+            Self::Buffer(b) if b.borrow().is_shared() => "SharedArrayBuffer",
+            Self::Buffer(_) => "ArrayBuffer",
+            Self::TypedArray(v) => v.kind.ctor_name(),
+            Self::DataView(_) => "DataView",
+        }
+    }
+
+    /// Vypocitane vlastnosti (`length`, `byteLength`, indexy, ...). None = bezna vlastnost.
+    pub fn get(&self, key: &str) -> Option<JsValue> {
+        let num = |n: usize| Some(JsValue::Number(n as f64));
+        match self {
+            Self::Buffer(b) => {
+                let b = b.borrow();
+                match key {
+                    "byteLength" => num(b.byte_length()),
+                    "maxByteLength" => num(if b.detached { 0 } else { b.max_byte_length.unwrap_or(b.byte_length()) }),
+                    "growable" if b.is_shared() => Some(JsValue::Bool(b.max_byte_length.is_some())),
+                    "resizable" if !b.is_shared() => Some(JsValue::Bool(b.max_byte_length.is_some())),
+                    "detached" if !b.is_shared() => Some(JsValue::Bool(b.detached)),
+                    _ => None,
+                }
+            }
+            Self::TypedArray(v) => match key {
+                "length" => num(v.length()),
+                "byteLength" => num(v.byte_length()),
+                "byteOffset" => num(if v.is_out_of_bounds() { 0 } else { v.byte_offset }),
+                _ => match canonical_index(key)? {
+                    Some(i) => Some(v.get(i).unwrap_or(JsValue::Undefined)),
+                    None => Some(JsValue::Undefined),
+                },
+            },
+            Self::DataView(d) => match key {
+                "byteLength" => num(d.byte_length()),
+                "byteOffset" => num(d.byte_offset),
+                _ => None,
+            },
+        }
+    }
+
+    /// Zapis do vypocitane vlastnosti. None = bezna vlastnost (zapise se do props).
+    pub fn set(&self, key: &str, v: &JsValue) -> Option<Result<(), String>> {
+        match self {
+            Self::Buffer(_) => matches!(key, "byteLength" | "maxByteLength" | "resizable" | "detached" | "growable")
+                .then_some(Ok(())),
+            Self::TypedArray(view) => match key {
+                "length" | "byteLength" | "byteOffset" => Some(Ok(())),
+                _ => match canonical_index(key)? {
+                    Some(i) => Some(view.set(i, v)),
+                    // Ne-indexovy numericky klic (-1, 1.5): konverze probehne, zapis ne
+                    None => Some(view.kind.encode(v, Endian::Little).map(|_| ())),
+                },
+            },
+            Self::DataView(_) => matches!(key, "byteLength" | "byteOffset").then_some(Ok(())),
+        }
+    }
+
+    /// Vlastni indexovy klic (`0 in ta`, hasOwnProperty).
+    pub fn has_own(&self, key: &str) -> bool {
+        match self {
+            Self::TypedArray(v) => matches!(canonical_index(key), Some(Some(i)) if i < v.length()),
+            _ => false,
+        }
+    }
+}
+
+/// CanonicalNumericIndexString: Some(Some(i)) = platny index, Some(None) =
+/// numericky klic mimo indexy (`-1`, `1.5`, `NaN`), None = bezny klic.
+pub fn canonical_index(key: &str) -> Option<Option<usize>> {
+    let first = *key.as_bytes().first()?;
+    if !(first.is_ascii_digit() || first == b'-' || first == b'I' || first == b'N') {
+        return None;
+    }
+    if key == "-0" { return Some(None); }
+    let n: f64 = match key {
+        "Infinity" => f64::INFINITY,
+        "-Infinity" => f64::NEG_INFINITY,
+        "NaN" => f64::NAN,
+        _ => key.parse().ok()?,
+    };
+    if JsValue::Number(n).to_string() != key { return None; }
+    Some((n >= 0.0 && n.fract() == 0.0 && n < 9007199254740992.0).then_some(n as usize))
+}
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum Endian {
     Little,
     Big,
 }
 
-/// DataView accessor: read element from buffer at byte offset.
-pub fn data_view_read_u16(buf: &[u8], offset: usize, endian: Endian) -> Option<u16> {
-    if offset + 2 > buf.len() { return None; }
-    let bytes = [buf[offset], buf[offset + 1]];
-    Some(match endian {
-        Endian::Little => u16::from_le_bytes(bytes),
-        Endian::Big => u16::from_be_bytes(bytes),
-    })
-}
-
-pub fn data_view_read_u32(buf: &[u8], offset: usize, endian: Endian) -> Option<u32> {
-    if offset + 4 > buf.len() { return None; }
-    let bytes = [buf[offset], buf[offset + 1], buf[offset + 2], buf[offset + 3]];
-    Some(match endian {
-        Endian::Little => u32::from_le_bytes(bytes),
-        Endian::Big => u32::from_be_bytes(bytes),
-    })
+impl Endian {
+    /// DataView `littleEndian` argument (default big-endian).
+    pub fn from_flag(little: bool) -> Self {
+        if little { Self::Little } else { Self::Big }
+    }
 }
 
-pub fn data_view_read_f32(buf: &[u8], offset: usize, endian: Endian) -> Option<f32> {
-    let bits = data_view_read_u32(buf, offset, endian)?;
-    Some(f32::from_bits(bits))
+/// DataView accessor: read element from buffer at byte offset.
+pub fn read_element(buf: &[u8], offset: usize, kind: TypedArrayKind, endian: Endian) -> Option<JsValue> {
+    let raw = buf.get(offset..offset + kind.element_size())?;
+    Some(kind.decode(raw, endian))
 }
 
 #[cfg(test)]
@@ -149,7 +619,7 @@ mod tests {
         let mut b = ArrayBuffer::new(10);
         b.max_byte_length = Some(20);
         assert!(b.resize(15).is_ok());
-        assert_eq!(b.bytes.len(), 15);
+        assert_eq!(b.byte_length(), 15);
     }
 
     #[test]
@@ -170,21 +640,60 @@ mod tests {
     #[test]
     fn dataview_read_u16_le() {
         let buf = [0x34, 0x12, 0xff, 0xff];
-        assert_eq!(data_view_read_u16(&buf, 0, Endian::Little), Some(0x1234));
-        assert_eq!(data_view_read_u16(&buf, 0, Endian::Big), Some(0x3412));
+        let read = |e| read_element(&buf, 0, TypedArrayKind::Uint16, e).map(|v| v.to_number());
+        assert_eq!(read(Endian::Little), Some(0x1234 as f64));
+        assert_eq!(read(Endian::Big), Some(0x3412 as f64));
     }
 
     #[test]
     fn dataview_oob_returns_none() {
         let buf = [0u8; 2];
-        assert!(data_view_read_u32(&buf, 0, Endian::Little).is_none());
+        assert!(read_element(&buf, 0, TypedArrayKind::Uint32, Endian::Little).is_none());
     }
 
     #[test]
     fn typed_array_byte_length() {
-        let v = TypedArrayView {
-            buffer_index: 0, byte_offset: 0, length: 10, kind: TypedArrayKind::Float32,
-        };
+        let v = TypedArrayView::new(ArrayBuffer::new(40).into_handle(), TypedArrayKind::Float32, 0, Some(10));
         assert_eq!(v.byte_length(), 40);
     }
+
+    #[test]
+    fn encode_wraps_and_clamps() {
+        let enc = |k: TypedArrayKind, n: f64| k.encode(&JsValue::Number(n), Endian::Little).unwrap()[0];
+        assert_eq!(enc(TypedArrayKind::Uint8, 257.0), 1);
+        assert_eq!(enc(TypedArrayKind::Int8, -1.0), 0xff);
+        assert_eq!(enc(TypedArrayKind::Uint8Clamped, 300.0), 255);
+        assert_eq!(enc(TypedArrayKind::Uint8Clamped, 2.5), 2);
+        assert_eq!(enc(TypedArrayKind::Uint8Clamped, 3.5), 4);
+        assert!(TypedArrayKind::BigInt64.encode(&JsValue::Number(1.0), Endian::Little).is_err());
+    }
+
+    #[test]
+    fn float16_round_trip() {
+        assert_eq!(f64_to_f16_bits(1.0), 0x3c00);
+        assert_eq!(f64_to_f16_bits(65504.0), 0x7bff);
+        assert_eq!(f64_to_f16_bits(65520.0), 0x7c00);
+        assert_eq!(f16_bits_to_f64(0x0001), 2f64.powi(-24));
+        assert_eq!(f16_bits_to_f64(f64_to_f16_bits(-0.333251953125)), -0.333251953125);
+    }
+
+    #[test]
+    fn length_tracking_view_follows_resize() {
+        let buf = ArrayBuffer::resizable(8, 16).unwrap().into_handle();
+        let v = TypedArrayView::new(Rc::clone(&buf), TypedArrayKind::Uint16, 2, None);
+        assert_eq!(v.length(), 3);
+        buf.borrow_mut().resize(12).unwrap();
+        assert_eq!(v.length(), 5);
+        buf.borrow_mut().detach();
+        assert_eq!(v.length(), 0);
+    }
+
+    #[test]
+    fn canonical_numeric_keys() {
+        assert_eq!(canonical_index("3"), Some(Some(3)));
+        assert_eq!(canonical_index("-1"), Some(None));
+        assert_eq!(canonical_index("1.5"), Some(None));
+        assert_eq!(canonical_index("01"), None);
+        assert_eq!(canonical_index("length"), None);
+    }
 }
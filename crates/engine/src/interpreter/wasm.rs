@@ -1,13 +1,42 @@
-//! WebAssembly JS API foundation - `WebAssembly.instantiate`, Module, Memory, Table.
+//! WebAssembly JS API - engine vrstva nad `wasmi` (pure-Rust interpret).
 //!
-//! Real impl by integrate `wasmtime` ci `wasmer` crate. Foundation = struct
-//! definitions + API surface bez actual wasm execution.
+//! Kazdy interpret ma jeden `WasmRealm`: `wasmi::Store` se vsemi instancemi,
+//! pametmi, tabulkami a globaly, ktere stranka vytvori. JS objekty
+//! (`builtins_wasm.rs`) nesou jen index do realmu (`__wasm_module__`,
+//! `__wasm_handle__`), exportovane funkce jsou natives `wasm-function[i]`.
 //!
-//! Inspired by Chromium V8 WASM integration + spec
-//! https://webassembly.github.io/spec/js-api/.
+//! Re-entrance JS -> WASM -> JS -> WASM: export drzi `Store` pujceny po dobu
+//! volani, host import si proto na dobu volani JS zaregistruje svuj `Caller`
+//! a vnorene operace (dalsi export, `memory.buffer`, `table.get`, ...) jdou
+//! pres nej. JS importy vola interpret, ktery do WASM vstoupil
+//! (`with_interpreter`). Vyjimka z JS importu probubla WASM ramci jako host
+//! chyba a na hranici exportu se vyhodi puvodni hodnota; trap je
+//! `WebAssembly.RuntimeError` se stackem `at <fn> (wasm-function[i])`.
+//!
+//! `wasmi` nema vlakna ani exception handling: `new WebAssembly.Memory({
+//! shared: true })` je proto TypeError (sdilenou pamet by zadny modul
+//! neimportoval) a modul, ktery deklaruje sdilenou pamet nebo pouziva
+//! `try` / `throw`, skonci CompileError. `Tag` a `Exception` jsou ciste JS
+//! objekty.
+//!
+//! Spec: https://webassembly.github.io/spec/js-api/.
 
-use std::rc::Rc;
 use std::cell::RefCell;
+use std::collections::HashMap;
+use std::ops::Range;
+use std::rc::{Rc, Weak};
+use std::time::{Duration, Instant};
+
+use num_bigint::BigInt;
+use num_traits::ToPrimitive;
+use wasmi::core::{HostError, TrapCode, ValType, F32, F64};
+use wasmi::{AsContextMut, Caller, Extern, ExternRef, ExternType, Func, FuncRef, FuncType, Mutability, StoreContextMut, Val};
+
+use super::{bytecode, JsError, JsObject, JsValue};
+use super::builtins_typed_arrays::{binary_of, buffer_object};
+use super::call_machinery::{call_js, with_host};
+use super::helpers::{get_promise_state, native};
+use super::typed_arrays::{ArrayBuffer, BinaryData, LinearMemory, SharedBuffer};
 
 #[derive(Debug, Clone)]
 pub struct WasmModule {
@@ -28,6 +57,8 @@ pub struct WasmImport {
 pub struct WasmExport {
     pub name: String,
     pub kind: WasmExternKind,
+    /// Index v prostoru funkci / tabulek / pameti / globalu modulu.
+    pub index: u32,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -36,6 +67,32 @@ pub enum WasmExternKind {
     Table,
     Memory,
     Global,
+    Tag,
+}
+
+impl WasmExternKind {
+    /// Kod druhu v import / export sekci binarky.
+    fn from_byte(b: u8) -> Option<Self> {
+        Some(match b {
+            0 => Self::Function,
+            1 => Self::Table,
+            2 => Self::Memory,
+            3 => Self::Global,
+            4 => Self::Tag,
+            _ => return None,
+        })
+    }
+
+    /// `kind` v `WebAssembly.Module.imports()` / `exports()`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Function => "function",
+            Self::Table => "table",
+            Self::Memory => "memory",
+            Self::Global => "global",
+            Self::Tag => "tag",
+        }
+    }
 }
 
 /// WebAssembly.Memory - linear memory accessible jako ArrayBuffer.
@@ -43,8 +100,8 @@ pub enum WasmExternKind {
 pub struct WasmMemory {
     pub initial_pages: u32,
     pub maximum_pages: Option<u32>,
-    /// Underlying byte buffer - real impl pres mmap'd region.
-    pub buffer: RefCell<Vec<u8>>,
+    /// Underlying byte buffer - sdileny s JS `memory.buffer` (typed_arrays.rs).
+    pub buffer: RefCell<SharedBuffer>,
 }
 
 impl WasmMemory {
@@ -53,24 +110,31 @@ impl WasmMemory {
         Self {
             initial_pages,
             maximum_pages,
-            buffer: RefCell::new(vec![0u8; size]),
+            buffer: RefCell::new(ArrayBuffer::new(size).into_handle()),
         }
     }
 
+    /// Aktualni buffer (`memory.buffer`) - po `grow` je to novy objekt.
+    pub fn buffer(&self) -> SharedBuffer {
+        Rc::clone(&self.buffer.borrow())
+    }
+
     /// Grow memory by N pages. Vraci previous page count nebo -1 pri fail.
+    /// Per spec stary ArrayBuffer odpoji a bajty presune do noveho.
     pub fn grow(&self, delta_pages: u32) -> i32 {
-        let mut buf = self.buffer.borrow_mut();
-        let cur_pages = (buf.len() / 65536) as u32;
+        let cur_pages = (self.byte_length() / 65536) as u32;
         let new_pages = cur_pages + delta_pages;
         if let Some(max) = self.maximum_pages {
             if new_pages > max { return -1; }
         }
-        buf.resize(new_pages as usize * 65536, 0);
+        let mut bytes = self.buffer.borrow().borrow_mut().detach();
+        bytes.resize(new_pages as usize * 65536, 0);
+        *self.buffer.borrow_mut() = ArrayBuffer::from_bytes(bytes).into_handle();
         cur_pages as i32
     }
 
     pub fn byte_length(&self) -> usize {
-        self.buffer.borrow().len()
+        self.buffer.borrow().borrow().byte_length()
     }
 }
 
@@ -177,6 +241,866 @@ pub fn call_export_i32(
     }
 }
 
+// ─── Realm: Store + JS objekty nad nim ───────────────────────────────────────
+
+/// Klic JS objektu Memory / Table / Global s indexem do `WasmRealm::handles`.
+pub const HANDLE_KEY: &str = "__wasm_handle__";
+/// Klic JS objektu Module s indexem do `WasmRealm::modules`.
+pub const MODULE_KEY: &str = "__wasm_module__";
+
+const PAGE_SIZE: usize = 65536;
+/// Limit stranek 32-bit pameti (4 GiB).
+pub const MAX_PAGES: u32 = 65536;
+
+thread_local! {
+    /// Host importy, ktere prave volaji JS: (realm, `*mut Caller`). `Store`
+    /// je po tu dobu pujceny exportem, vnorene operace jdou pres `Caller`.
+    static CALLERS: RefCell<Vec<(*const WasmRealm, *mut ())>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Data `wasmi::Store` - JS hodnoty, na ktere odkazuje WASM strana.
+#[derive(Default)]
+pub struct HostState {
+    realm: Weak<WasmRealm>,
+    /// Hodnoty predane jako `externref` (`ExternRef` nese index).
+    externs: Vec<JsValue>,
+    /// JS funkce importovane jako host funkce.
+    imports: Vec<JsValue>,
+    /// Vyjimka JS importu, ktera prave probubla WASM ramci.
+    pending: Option<JsError>,
+}
+
+/// Host chyba nesouci vyjimku JS importu (hodnota ceka v `HostState::pending`).
+#[derive(Debug)]
+struct JsException;
+
+impl std::fmt::Display for JsException {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("exception thrown by JavaScript import")
+    }
+}
+
+impl HostError for JsException {}
+
+struct HandleSlot {
+    handle: Extern,
+    /// Identita handle (`Extern` nema PartialEq) - tentyz handle = tentyz JS objekt.
+    key: String,
+    object: Option<JsValue>,
+    /// Posledni `memory.buffer` a delka, pro kterou plati.
+    buffer: Option<(JsValue, usize)>,
+}
+
+/// Zkompilovany modul + metadata, ktera `wasmi` nevystavuje (poradi importu
+/// a exportu, indexy funkci, jmena z `name` sekce, custom sekce).
+pub struct CompiledModule {
+    pub module: wasmi::Module,
+    pub info: WasmModule,
+    pub custom_sections: Vec<(String, Range<usize>)>,
+    pub func_names: HashMap<u32, String>,
+}
+
+impl CompiledModule {
+    /// Obsah custom sekci daneho jmena (v poradi v binarce).
+    pub fn custom_section_data(&self, name: &str) -> Vec<Vec<u8>> {
+        self.custom_sections.iter()
+            .filter(|(n, _)| n == name)
+            .map(|(_, range)| self.info.bytes[range.clone()].to_vec())
+            .collect()
+    }
+}
+
+/// Export instance: funkce je rovnou JS hodnota, ostatni handle pro JS objekt.
+pub enum ExportValue {
+    Function(Box<JsValue>),
+    Handle(usize),
+}
+
+pub struct WasmRealm {
+    me: Weak<WasmRealm>,
+    engine: wasmi::Engine,
+    store: RefCell<wasmi::Store<HostState>>,
+    modules: RefCell<Vec<Rc<CompiledModule>>>,
+    handles: RefCell<Vec<HandleSlot>>,
+    /// Funkce videne z JS: (identita, Func, JS hodnota). Exportovana funkce
+    /// ma native wrapper, host funkce puvodni JS funkci.
+    funcs: RefCell<Vec<(String, Func, JsValue)>>,
+}
+
+impl WasmRealm {
+    pub fn new() -> Rc<Self> {
+        let engine = wasmi::Engine::default();
+        Rc::new_cyclic(|me| {
+            let state = HostState { realm: me.clone(), ..HostState::default() };
+            WasmRealm {
+                me: me.clone(),
+                store: RefCell::new(wasmi::Store::new(&engine, state)),
+                engine,
+                modules: RefCell::new(Vec::new()),
+                handles: RefCell::new(Vec::new()),
+                funcs: RefCell::new(Vec::new()),
+            }
+        })
+    }
+
+    /// Pristup ke `Store`: behem host importu pres jeho `Caller`, jinak primo.
+    fn with_store<R>(&self, f: impl FnOnce(&mut StoreContextMut<'_, HostState>) -> R) -> R {
+        let active = CALLERS.with(|c| c.borrow().last().copied())
+            .filter(|(realm, _)| std::ptr::eq(*realm, self))
+            .map(|(_, caller)| caller);
+        match active {
+            Some(caller) => {
+                // SAFETY: ukazatel zaregistroval `enter_host` a plati az do
+                // jeho navratu; host import na `Caller` mezitim nesahne.
+                let caller = unsafe { &mut *(caller as *mut Caller<'_, HostState>) };
+                f(&mut caller.as_context_mut())
+            }
+            None => f(&mut self.store.borrow_mut().as_context_mut()),
+        }
+    }
+
+    /// Zavola `f` (JS kod) s `caller` jako aktivnim pristupem ke `Store`.
+    fn enter_host<R>(&self, caller: &mut Caller<'_, HostState>, f: impl FnOnce() -> R) -> R {
+        let ptr = caller as *mut Caller<'_, HostState> as *mut ();
+        CALLERS.with(|c| c.borrow_mut().push((self as *const Self, ptr)));
+        let result = f();
+        CALLERS.with(|c| c.borrow_mut().pop());
+        result
+    }
+
+    // ─── Moduly ──────────────────────────────────────────────────────────────
+
+    pub fn validate(&self, bytes: &[u8]) -> bool {
+        wasmi::Module::validate(&self.engine, bytes).is_ok()
+    }
+
+    /// Kompilace binarky; vraci index modulu. Chyba = vyhozeny CompileError.
+    pub fn compile(&self, bytes: Vec<u8>) -> Result<usize, String> {
+        let module = wasmi::Module::new(&self.engine, &bytes)
+            .map_err(|e| throw("CompileError", &format!("WebAssembly.Module(): {e}")))?;
+        let sections = parse_sections(&bytes);
+        let compiled = CompiledModule {
+            module,
+            info: WasmModule { bytes, imports: sections.imports, exports: sections.exports },
+            custom_sections: sections.custom,
+            func_names: sections.func_names,
+        };
+        let mut modules = self.modules.borrow_mut();
+        modules.push(Rc::new(compiled));
+        Ok(modules.len() - 1)
+    }
+
+    pub fn module(&self, id: usize) -> Option<Rc<CompiledModule>> {
+        self.modules.borrow().get(id).cloned()
+    }
+
+    /// Instanciace s importy z JS objektu `{ modul: { jmeno: hodnota } }`.
+    pub fn instantiate(&self, id: usize, import_object: &JsValue) -> Result<wasmi::Instance, String> {
+        let compiled = self.module(id).ok_or("TypeError: WebAssembly.Instance(): Argument 0 must be a WebAssembly.Module")?;
+        let imports: Vec<wasmi::ImportType> = compiled.module.imports().collect();
+        if !imports.is_empty() && !matches!(import_object, JsValue::Object(_)) {
+            return Err("TypeError: WebAssembly.Instance(): Imports argument must be present and must be an object".into());
+        }
+        self.with_store(|ctx| {
+            let mut externs = Vec::with_capacity(imports.len());
+            for (i, import) in imports.iter().enumerate() {
+                let (module, name) = (import.module(), import.name());
+                let namespace = property(import_object, module);
+                if !matches!(namespace, JsValue::Object(_)) {
+                    return Err(format!("TypeError: WebAssembly.Instance(): Import #{i} \"{module}\": module is not an object or function"));
+                }
+                let value = property(&namespace, name);
+                let link_error = |what: &str| throw("LinkError", &format!("WebAssembly.Instance(): Import #{i} \"{module}\" \"{name}\": {what}"));
+                let resolved = match import.ty() {
+                    ExternType::Func(ty) => match self.func_of(&value) {
+                        Some(func) => Extern::Func(func),
+                        None if matches!(value, JsValue::Function(_)) => Extern::Func(self.host_func(ctx, ty.clone(), value)),
+                        None => return Err(link_error("function import requires a callable")),
+                    },
+                    ExternType::Memory(_) => match self.handle_of(&value) {
+                        Some(ext @ Extern::Memory(_)) => ext,
+                        _ => return Err(link_error("memory import must be a WebAssembly.Memory object")),
+                    },
+                    ExternType::Table(_) => match self.handle_of(&value) {
+                        Some(ext @ Extern::Table(_)) => ext,
+                        _ => return Err(link_error("table import requires a WebAssembly.Table")),
+                    },
+                    ExternType::Global(ty) => match self.handle_of(&value) {
+                        Some(ext @ Extern::Global(_)) => ext,
+                        _ if ty.mutability().is_mut() => return Err(link_error("imported mutable global must be a WebAssembly.Global object")),
+                        _ if matches!(value, JsValue::Number(_) | JsValue::BigInt(_)) || is_reference(ty.content()) => {
+                            let init = self.to_wasm(ctx, &value, ty.content()).map_err(|e| link_error(&e))?;
+                            Extern::Global(wasmi::Global::new(&mut *ctx, init, Mutability::Const))
+                        }
+                        _ => return Err(link_error("global import must be a number, valid Wasm reference, or WebAssembly.Global object")),
+                    },
+                };
+                externs.push(resolved);
+            }
+            wasmi::Instance::new(&mut *ctx, &compiled.module, &externs)
+                .map_err(|e| self.failure(ctx, e, "WebAssembly.Instance()", None))
+        })
+    }
+
+    /// Exporty instance v poradi export sekce.
+    pub fn instance_exports(&self, id: usize, instance: wasmi::Instance) -> Vec<(String, ExportValue)> {
+        let Some(compiled) = self.module(id) else { return Vec::new() };
+        let resolved: Vec<(String, Extern, u32)> = self.with_store(|ctx| {
+            compiled.info.exports.iter()
+                .filter_map(|e| Some((e.name.clone(), instance.get_export(&*ctx, &e.name)?, e.index)))
+                .collect()
+        });
+        resolved.into_iter().map(|(name, ext, index)| {
+            let value = match ext {
+                Extern::Func(func) => {
+                    let label = compiled.func_names.get(&index).cloned().unwrap_or_else(|| name.clone());
+                    ExportValue::Function(Box::new(self.func_value(func, Some((index, &label)))))
+                }
+                other => ExportValue::Handle(self.handle_for(other)),
+            };
+            (name, value)
+        }).collect()
+    }
+
+    // ─── Funkce ──────────────────────────────────────────────────────────────
+
+    /// JS hodnota funkce: puvodni JS funkce importu, jinak (sdileny) native wrapper.
+    /// `label` = (index funkce, jmeno pro stack).
+    pub fn func_value(&self, func: Func, label: Option<(u32, &str)>) -> JsValue {
+        let key = format!("{func:?}");
+        if let Some((_, _, value)) = self.funcs.borrow().iter().find(|(k, ..)| *k == key) {
+            return value.clone();
+        }
+        let index = label.map_or(self.funcs.borrow().len() as u32, |(i, _)| i);
+        let frame: Rc<str> = match label {
+            Some((i, name)) => format!("{name} (wasm-function[{i}])").into(),
+            None => format!("wasm-function[{index}]").into(),
+        };
+        let realm = self.me.clone();
+        let value = native(&format!("wasm-function[{index}]"), move |args| {
+            let realm = realm.upgrade().ok_or("TypeError: WebAssembly instance is gone")?;
+            realm.call(func, args, &frame)
+        });
+        self.funcs.borrow_mut().push((key, func, value.clone()));
+        value
+    }
+
+    /// Func za JS hodnotou (exportovana funkce nebo jiz importovana JS funkce).
+    fn func_of(&self, value: &JsValue) -> Option<Func> {
+        let JsValue::Function(super::JsFunc::Native(_, f)) = value else { return None };
+        self.funcs.borrow().iter().find_map(|(_, func, v)| match v {
+            JsValue::Function(super::JsFunc::Native(_, g)) if Rc::ptr_eq(f, g) => Some(*func),
+            _ => None,
+        })
+    }
+
+    /// Volani WASM funkce z JS (`ToWebAssemblyValue` argumentu, `ToJSValue` vysledku).
+    fn call(&self, func: Func, args: Vec<JsValue>, frame: &str) -> Result<JsValue, String> {
+        let mut results = self.with_store(|ctx| {
+            let ty = func.ty(&*ctx);
+            let params = ty.params().iter().enumerate()
+                .map(|(i, t)| self.to_wasm(ctx, args.get(i).unwrap_or(&JsValue::Undefined), *t))
+                .collect::<Result<Vec<_>, _>>()?;
+            let mut results: Vec<Val> = ty.results().iter().map(|t| Val::default(*t)).collect();
+            func.call(&mut *ctx, &params, &mut results)
+                .map_err(|e| self.failure(ctx, e, frame, Some(frame)))?;
+            Ok::<_, String>(results.iter().map(|v| self.to_js(ctx, v)).collect::<Vec<_>>())
+        })?;
+        Ok(match results.len() {
+            0 => JsValue::Undefined,
+            1 => results.remove(0),
+            _ => JsValue::Array(Rc::new(RefCell::new(results))),
+        })
+    }
+
+    /// Host funkce volajici JS funkci `callee` (import).
+    fn host_func(&self, ctx: &mut StoreContextMut<'_, HostState>, ty: FuncType, callee: JsValue) -> Func {
+        let slot = {
+            let state = ctx.data_mut();
+            state.imports.push(callee.clone());
+            state.imports.len() - 1
+        };
+        let result_types: Vec<ValType> = ty.results().to_vec();
+        let func = Func::new(&mut *ctx, ty, move |mut caller: Caller<'_, HostState>, params: &[Val], results: &mut [Val]| {
+            let Some(realm) = caller.data().realm.upgrade() else {
+                return Err(wasmi::Error::new("WebAssembly realm is gone"));
+            };
+            let callee = caller.data().imports[slot].clone();
+            let args = params.iter().map(|v| realm.to_js(&mut caller.as_context_mut(), v)).collect();
+            let outcome = realm.enter_host(&mut caller, || call_js(&callee, JsValue::Undefined, args)).and_then(|ret| {
+                realm.write_results(&mut caller.as_context_mut(), ret, &result_types, results)
+                    .map_err(JsError::Runtime)
+            });
+            outcome.map_err(|e| {
+                caller.data_mut().pending = Some(e);
+                wasmi::Error::host(JsException)
+            })
+        });
+        self.funcs.borrow_mut().push((format!("{func:?}"), func, callee));
+        func
+    }
+
+    /// Navratova hodnota JS importu -> vysledky host funkce (vice hodnot = pole).
+    fn write_results(&self, ctx: &mut StoreContextMut<'_, HostState>, ret: JsValue, types: &[ValType], out: &mut [Val]) -> Result<(), String> {
+        match types {
+            [] => Ok(()),
+            [ty] => {
+                out[0] = self.to_wasm(ctx, &ret, *ty)?;
+                Ok(())
+            }
+            _ => {
+                let JsValue::Array(items) = ret else {
+                    return Err("TypeError: multi-return value must be iterable".into());
+                };
+                let items = items.borrow().clone();
+                if items.len() != types.len() {
+                    return Err("TypeError: multi-return length mismatch".into());
+                }
+                for (i, (v, ty)) in items.iter().zip(types).enumerate() {
+                    out[i] = self.to_wasm(ctx, v, *ty)?;
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// Chyba `wasmi` -> vyhozena JS hodnota: vyjimka JS importu beze zmeny,
+    /// trap jako RuntimeError (`frame` do stacku), ostatni LinkError.
+    fn failure(&self, ctx: &mut StoreContextMut<'_, HostState>, err: wasmi::Error, context: &str, frame: Option<&str>) -> String {
+        if err.downcast_ref::<JsException>().is_some() {
+            return match ctx.data_mut().pending.take() {
+                Some(JsError::Runtime(msg)) => msg,
+                Some(JsError::Thrown(v) | JsError::GeneratorReturn(v)) => bytecode::throw_value(v),
+                None => throw("RuntimeError", "exception thrown by JavaScript import"),
+            };
+        }
+        match err.as_trap_code() {
+            Some(TrapCode::StackOverflow) => "RangeError: Maximum call stack size exceeded".into(),
+            Some(code) => bytecode::throw_value(error_value("RuntimeError", trap_message(code), frame)),
+            None => throw("LinkError", &format!("{context}: {err}")),
+        }
+    }
+
+    // ─── Prevody hodnot ─────────────────────────────────────────────────────
+
+    /// ToJSValue.
+    fn to_js(&self, ctx: &mut StoreContextMut<'_, HostState>, v: &Val) -> JsValue {
+        match v {
+            Val::I32(n) => JsValue::Number(*n as f64),
+            Val::I64(n) => JsValue::BigInt(Rc::new(BigInt::from(*n))),
+            Val::F32(f) => JsValue::Number(f.to_float() as f64),
+            Val::F64(f) => JsValue::Number(f.to_float()),
+            Val::FuncRef(r) => match r.func() {
+                Some(func) => self.func_value(*func, None),
+                None => JsValue::Null,
+            },
+            Val::ExternRef(r) => {
+                let index = r.data(&*ctx).and_then(|d| d.downcast_ref::<u64>()).copied();
+                match index {
+                    Some(i) => ctx.data().externs.get(i as usize).cloned().unwrap_or(JsValue::Undefined),
+                    None => JsValue::Null,
+                }
+            }
+        }
+    }
+
+    /// ToWebAssemblyValue.
+    fn to_wasm(&self, ctx: &mut StoreContextMut<'_, HostState>, v: &JsValue, ty: ValType) -> Result<Val, String> {
+        if matches!(v, JsValue::BigInt(_)) && matches!(ty, ValType::I32 | ValType::F32 | ValType::F64) {
+            return Err("TypeError: Cannot convert a BigInt value to a number".into());
+        }
+        Ok(match ty {
+            ValType::I32 => Val::I32(to_int32(v.to_number())),
+            ValType::I64 => Val::I64(to_bigint64(v)?),
+            ValType::F32 => Val::F32(F32::from_float(v.to_number() as f32)),
+            ValType::F64 => Val::F64(F64::from_float(v.to_number())),
+            ValType::FuncRef => match v {
+                JsValue::Null => Val::FuncRef(FuncRef::null()),
+                other => match self.func_of(other) {
+                    Some(func) => Val::FuncRef(FuncRef::new(func)),
+                    None => return Err("TypeError: type incompatibility when transforming from/to JS".into()),
+                },
+            },
+            ValType::ExternRef => match v {
+                JsValue::Null => Val::ExternRef(ExternRef::null()),
+                other => {
+                    let index = {
+                        let state = ctx.data_mut();
+                        state.externs.push(other.clone());
+                        state.externs.len() as u64 - 1
+                    };
+                    Val::ExternRef(ExternRef::new(&mut *ctx, index))
+                }
+            },
+        })
+    }
+
+    /// Vychozi hodnota typu, kdyz JS argument chybi (`DefaultValue`).
+    pub fn default_value(ty: ValType) -> JsValue {
+        match ty {
+            ValType::I64 => JsValue::BigInt(Rc::new(BigInt::from(0))),
+            ValType::FuncRef => JsValue::Null,
+            ValType::ExternRef => JsValue::Undefined,
+            _ => JsValue::Number(0.0),
+        }
+    }
+
+    // ─── Handles (Memory / Table / Global) ────────────────────────────────────
+
+    /// Index handle pro `wasmi` objekt (existujici, nebo novy).
+    pub fn handle_for(&self, ext: Extern) -> usize {
+        let key = format!("{ext:?}");
+        if let Some(i) = self.handles.borrow().iter().position(|h| h.key == key) {
+            return i;
+        }
+        let mut handles = self.handles.borrow_mut();
+        handles.push(HandleSlot { handle: ext, key, object: None, buffer: None });
+        handles.len() - 1
+    }
+
+    pub fn handle(&self, h: usize) -> Option<Extern> {
+        self.handles.borrow().get(h).map(|slot| slot.handle.clone())
+    }
+
+    /// Handle za JS objektem Memory / Table / Global.
+    fn handle_of(&self, value: &JsValue) -> Option<Extern> {
+        let JsValue::Object(o) = value else { return None };
+        let JsValue::Number(h) = o.borrow().get(HANDLE_KEY) else { return None };
+        self.handle(h as usize)
+    }
+
+    pub fn handle_object(&self, h: usize) -> Option<JsValue> {
+        self.handles.borrow().get(h).and_then(|slot| slot.object.clone())
+    }
+
+    pub fn set_handle_object(&self, h: usize, object: JsValue) {
+        if let Some(slot) = self.handles.borrow_mut().get_mut(h) {
+            slot.object = Some(object);
+        }
+    }
+
+    // ─── Memory ──────────────────────────────────────────────────────────────
+
+    pub fn new_memory(&self, initial: u32, maximum: Option<u32>) -> Result<usize, String> {
+        let ty = wasmi::MemoryType::new(initial, maximum)
+            .map_err(|e| format!("RangeError: WebAssembly.Memory(): {e}"))?;
+        let memory = self.with_store(|ctx| wasmi::Memory::new(&mut *ctx, ty))
+            .map_err(|_| "RangeError: WebAssembly.Memory(): could not allocate memory".to_string())?;
+        Ok(self.handle_for(Extern::Memory(memory)))
+    }
+
+    /// `memory.buffer` - stejny objekt, dokud pamet neroste; po `grow`
+    /// (z JS i instrukci `memory.grow`) je stary buffer odpojeny.
+    pub fn memory_buffer(&self, h: usize) -> JsValue {
+        let Some(Extern::Memory(memory)) = self.handle(h) else { return JsValue::Undefined };
+        let len = self.with_store(|ctx| memory.data_size(&*ctx));
+        let cached = self.handles.borrow()[h].buffer.clone();
+        if let Some((buffer, cached_len)) = &cached && *cached_len == len {
+            return buffer.clone();
+        }
+        self.detach_buffer(h);
+        let storage = ArrayBuffer::from_linear(Rc::new(WasmBytes { realm: self.me.clone(), memory, len }));
+        let buffer = buffer_object(storage.into_handle());
+        self.handles.borrow_mut()[h].buffer = Some((buffer.clone(), len));
+        buffer
+    }
+
+    /// Odpoji posledni `memory.buffer`.
+    fn detach_buffer(&self, h: usize) {
+        let Some((old, _)) = self.handles.borrow_mut()[h].buffer.take() else { return };
+        if let Some(BinaryData::Buffer(buf)) = binary_of(&old).as_deref() {
+            buf.borrow_mut().detach();
+        }
+    }
+
+    /// `memory.grow(delta)` - vraci puvodni pocet stranek.
+    pub fn memory_grow(&self, h: usize, delta: u32) -> Result<u32, String> {
+        const EXCEEDED: &str = "RangeError: WebAssembly.Memory.grow(): Maximum memory size exceeded";
+        let previous = match self.handle(h) {
+            Some(Extern::Memory(m)) => {
+                self.with_store(|ctx| m.grow(&mut *ctx, delta)).map_err(|_| EXCEEDED.to_string())?
+            }
+            _ => return Err("TypeError: Receiver is not a WebAssembly.Memory".into()),
+        };
+        // Spec: grow odpoji buffer i pri delta 0
+        self.detach_buffer(h);
+        Ok(previous)
+    }
+
+    // ─── Table ───────────────────────────────────────────────────────────────
+
+    pub fn new_table(&self, element: ValType, initial: u32, maximum: Option<u32>, init: &JsValue) -> Result<usize, String> {
+        let table = self.with_store(|ctx| {
+            let init = self.to_wasm(ctx, init, element)?;
+            wasmi::Table::new(&mut *ctx, wasmi::TableType::new(element, initial, maximum), init)
+                .map_err(|e| format!("RangeError: WebAssembly.Table(): {e}"))
+        })?;
+        Ok(self.handle_for(Extern::Table(table)))
+    }
+
+    fn table(&self, h: usize) -> Result<wasmi::Table, String> {
+        match self.handle(h) {
+            Some(Extern::Table(t)) => Ok(t),
+            _ => Err("TypeError: Receiver is not a WebAssembly.Table".into()),
+        }
+    }
+
+    pub fn table_length(&self, h: usize) -> Result<u32, String> {
+        let table = self.table(h)?;
+        Ok(self.with_store(|ctx| table.size(&*ctx)))
+    }
+
+    pub fn table_element(&self, h: usize) -> Result<ValType, String> {
+        let table = self.table(h)?;
+        Ok(self.with_store(|ctx| table.ty(&*ctx).element()))
+    }
+
+    pub fn table_get(&self, h: usize, index: u32) -> Result<JsValue, String> {
+        let table = self.table(h)?;
+        self.with_store(|ctx| match table.get(&*ctx, index) {
+            Some(v) => Ok(self.to_js(ctx, &v)),
+            None => Err(format!("RangeError: WebAssembly.Table.get(): invalid address {index} in table of size {}", table.size(&*ctx))),
+        })
+    }
+
+    pub fn table_set(&self, h: usize, index: u32, value: &JsValue) -> Result<(), String> {
+        let table = self.table(h)?;
+        self.with_store(|ctx| {
+            let size = table.size(&*ctx);
+            if index >= size {
+                return Err(format!("RangeError: WebAssembly.Table.set(): invalid address {index} in table of size {size}"));
+            }
+            let v = self.to_wasm(ctx, value, table.ty(&*ctx).element())?;
+            table.set(&mut *ctx, index, v).map_err(|e| format!("TypeError: WebAssembly.Table.set(): {e}"))
+        })
+    }
+
+    pub fn table_grow(&self, h: usize, delta: u32, init: &JsValue) -> Result<u32, String> {
+        let table = self.table(h)?;
+        self.with_store(|ctx| {
+            let init = self.to_wasm(ctx, init, table.ty(&*ctx).element())?;
+            table.grow(&mut *ctx, delta, init)
+                .map_err(|_| "RangeError: WebAssembly.Table.grow(): failed to grow table".to_string())
+        })
+    }
+
+    // ─── Global ──────────────────────────────────────────────────────────────
+
+    pub fn new_global(&self, ty: ValType, mutable: bool, value: &JsValue) -> Result<usize, String> {
+        let mutability = if mutable { Mutability::Var } else { Mutability::Const };
+        let global = self.with_store(|ctx| {
+            let init = self.to_wasm(ctx, value, ty)?;
+            Ok::<_, String>(wasmi::Global::new(&mut *ctx, init, mutability))
+        })?;
+        Ok(self.handle_for(Extern::Global(global)))
+    }
+
+    fn global(&self, h: usize) -> Result<wasmi::Global, String> {
+        match self.handle(h) {
+            Some(Extern::Global(g)) => Ok(g),
+            _ => Err("TypeError: Receiver is not a WebAssembly.Global".into()),
+        }
+    }
+
+    /// (typ hodnoty, mutable).
+    pub fn global_type(&self, h: usize) -> Result<(ValType, bool), String> {
+        let global = self.global(h)?;
+        let ty = self.with_store(|ctx| global.ty(&*ctx));
+        Ok((ty.content(), ty.mutability().is_mut()))
+    }
+
+    pub fn global_get(&self, h: usize) -> Result<JsValue, String> {
+        let global = self.global(h)?;
+        Ok(self.with_store(|ctx| {
+            let v = global.get(&*ctx);
+            self.to_js(ctx, &v)
+        }))
+    }
+
+    pub fn global_set(&self, h: usize, value: &JsValue) -> Result<(), String> {
+        let global = self.global(h)?;
+        self.with_store(|ctx| {
+            let ty = global.ty(&*ctx);
+            if !ty.mutability().is_mut() {
+                return Err("TypeError: WebAssembly.Global.value: Can't set the value of an immutable global.".into());
+            }
+            let v = self.to_wasm(ctx, value, ty.content())?;
+            global.set(&mut *ctx, v).map_err(|e| format!("TypeError: WebAssembly.Global.value: {e}"))
+        })
+    }
+}
+
+/// `memory.buffer` nad pameti ve `Store`. Plati jen pro delku, se kterou
+/// vznikl - po `memory.grow` se chova jako odpojeny.
+#[derive(Debug)]
+struct WasmBytes {
+    realm: Weak<WasmRealm>,
+    memory: wasmi::Memory,
+    len: usize,
+}
+
+impl WasmBytes {
+    fn with_data<R>(&self, f: impl FnOnce(&mut [u8]) -> R) -> Option<R> {
+        let realm = self.realm.upgrade()?;
+        realm.with_store(|ctx| {
+            let data = self.memory.data_mut(&mut *ctx);
+            (data.len() == self.len).then(|| f(data))
+        })
+    }
+}
+
+impl LinearMemory for WasmBytes {
+    fn byte_length(&self) -> usize {
+        self.with_data(|d| d.len()).unwrap_or(0)
+    }
+
+    fn read(&self, start: usize, out: &mut [u8]) -> bool {
+        self.with_data(|d| match d.get(start..start + out.len()) {
+            Some(src) => { out.copy_from_slice(src); true }
+            None => false,
+        }).unwrap_or(false)
+    }
+
+    fn write(&self, start: usize, data: &[u8]) -> bool {
+        self.with_data(|d| match d.get_mut(start..start + data.len()) {
+            Some(dst) => { dst.copy_from_slice(data); true }
+            None => false,
+        }).unwrap_or(false)
+    }
+}
+
+// ─── Vstup z interpretu ──────────────────────────────────────────────────────
+
+/// Nativni funkce, ktere mohou spustit WASM kod (a ten JS importy).
+pub(crate) fn enters_wasm(name: &str) -> bool {
+    name.starts_with("wasm-function[") || name.starts_with("WebAssembly.")
+}
+
+/// Pocka na pending promise z `fetch` (odpoved prijde z vlakna site) - pro
+/// `compileStreaming` nad `fetch(url)`. Vraci (stav, hodnota).
+pub(crate) fn settle_fetch(promise: &JsValue) -> Option<(String, JsValue)> {
+    let deadline = Instant::now() + Duration::from_secs(30);
+    loop {
+        let state = get_promise_state(promise)?;
+        if state.0 != "pending" || Instant::now() > deadline || with_host(|interp| interp.drain_fetches()).is_none() {
+            return Some(state);
+        }
+        std::thread::sleep(Duration::from_millis(1));
+    }
+}
+
+// ─── Chyby ───────────────────────────────────────────────────────────────────
+
+/// Chybovy objekt `WebAssembly.CompileError` / `LinkError` / `RuntimeError`;
+/// `frame` = WASM ramec pro `stack`.
+pub fn error_value(kind: &str, message: &str, frame: Option<&str>) -> JsValue {
+    let mut stack = format!("{kind}: {message}");
+    if let Some(frame) = frame {
+        stack.push_str("\n    at ");
+        stack.push_str(frame);
+    }
+    let mut obj = JsObject::new();
+    obj.set("name".into(), JsValue::Str(kind.into()));
+    obj.set("message".into(), JsValue::Str(message.into()));
+    obj.set("stack".into(), JsValue::Str(stack));
+    obj.set("__class_chain__".into(), JsValue::Str(format!("WebAssembly.{kind},Error")));
+    JsValue::Object(Rc::new(RefCell::new(obj)))
+}
+
+/// Vyhozeni WebAssembly chyby z nativni funkce.
+fn throw(kind: &str, message: &str) -> String {
+    bytecode::throw_value(error_value(kind, message, None))
+}
+
+/// Zpravy trapu jako ve V8.
+fn trap_message(code: TrapCode) -> &'static str {
+    match code {
+        TrapCode::UnreachableCodeReached => "unreachable",
+        TrapCode::MemoryOutOfBounds => "memory access out of bounds",
+        TrapCode::TableOutOfBounds => "table index is out of bounds",
+        TrapCode::IndirectCallToNull => "null function or function signature mismatch",
+        TrapCode::IntegerDivisionByZero => "divide by zero",
+        TrapCode::IntegerOverflow => "integer overflow",
+        TrapCode::BadConversionToInteger => "float unrepresentable in integer range",
+        TrapCode::StackOverflow => "call stack exhausted",
+        TrapCode::BadSignature => "null function or function signature mismatch",
+        TrapCode::OutOfFuel => "out of fuel",
+        TrapCode::GrowthOperationLimited => "growth operation limited",
+    }
+}
+
+// ─── Pomocne funkce ──────────────────────────────────────────────────────────
+
+/// Typ hodnoty z JS deskriptoru (`"i32"`, `"anyfunc"`, ...).
+pub fn parse_val_type(name: &str) -> Option<ValType> {
+    Some(match name {
+        "i32" => ValType::I32,
+        "i64" => ValType::I64,
+        "f32" => ValType::F32,
+        "f64" => ValType::F64,
+        "anyfunc" | "funcref" => ValType::FuncRef,
+        "externref" => ValType::ExternRef,
+        _ => return None,
+    })
+}
+
+/// ToWebAssemblyValue + ToJSValue bez `Store` (payload `WebAssembly.Exception`).
+pub fn normalize_value(ty: ValType, v: &JsValue) -> Result<JsValue, String> {
+    if matches!(v, JsValue::BigInt(_)) && matches!(ty, ValType::I32 | ValType::F32 | ValType::F64) {
+        return Err("TypeError: Cannot convert a BigInt value to a number".into());
+    }
+    Ok(match ty {
+        ValType::I32 => JsValue::Number(to_int32(v.to_number()) as f64),
+        ValType::I64 => JsValue::BigInt(Rc::new(BigInt::from(to_bigint64(v)?))),
+        ValType::F32 => JsValue::Number(v.to_number() as f32 as f64),
+        ValType::F64 => JsValue::Number(v.to_number()),
+        ValType::FuncRef | ValType::ExternRef => v.clone(),
+    })
+}
+
+pub fn is_reference(ty: ValType) -> bool {
+    matches!(ty, ValType::FuncRef | ValType::ExternRef)
+}
+
+fn property(obj: &JsValue, key: &str) -> JsValue {
+    match obj {
+        JsValue::Object(o) => o.borrow().get(key),
+        _ => JsValue::Undefined,
+    }
+}
+
+/// ToInt32 (modulo 2^32).
+fn to_int32(n: f64) -> i32 {
+    if !n.is_finite() { return 0; }
+    n.trunc().rem_euclid(4294967296.0) as u32 as i32
+}
+
+/// ToBigInt64 - i64 parametr bere jen BigInt (a Boolean), ne Number.
+fn to_bigint64(v: &JsValue) -> Result<i64, String> {
+    match v {
+        JsValue::BigInt(b) => Ok((&**b & BigInt::from(u64::MAX)).to_u64().unwrap_or(0) as i64),
+        JsValue::Bool(b) => Ok(*b as i64),
+        JsValue::Str(s) => s.trim().parse::<i64>().map_err(|_| format!("SyntaxError: Cannot convert {s} to a BigInt")),
+        other => Err(format!("TypeError: Cannot convert {other} to a BigInt")),
+    }
+}
+
+// ─── Sekce binarky ───────────────────────────────────────────────────────────
+
+/// Metadata z binarky, ktera `wasmi::Module` nevystavuje.
+#[derive(Default)]
+struct Sections {
+    imports: Vec<WasmImport>,
+    exports: Vec<WasmExport>,
+    custom: Vec<(String, Range<usize>)>,
+    func_names: HashMap<u32, String>,
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl Reader<'_> {
+    fn byte(&mut self) -> Option<u8> {
+        let b = *self.bytes.get(self.pos)?;
+        self.pos += 1;
+        Some(b)
+    }
+
+    /// Unsigned LEB128 (u32).
+    fn leb(&mut self) -> Option<u32> {
+        let mut result: u64 = 0;
+        for shift in (0..35).step_by(7) {
+            let b = self.byte()?;
+            result |= ((b & 0x7f) as u64) << shift;
+            if b & 0x80 == 0 { return u32::try_from(result).ok(); }
+        }
+        None
+    }
+
+    fn name(&mut self) -> Option<String> {
+        let len = self.leb()? as usize;
+        let raw = self.bytes.get(self.pos..self.pos + len)?;
+        self.pos += len;
+        Some(String::from_utf8_lossy(raw).into_owned())
+    }
+
+    /// Limity tabulky / pameti (flag, min, volitelne max).
+    fn skip_limits(&mut self) -> Option<()> {
+        let flags = self.byte()?;
+        self.leb()?;
+        if flags & 1 != 0 { self.leb()?; }
+        Some(())
+    }
+}
+
+/// Projde sekce uz zvalidovane binarky (strukturu overil `wasmi::Module::new`).
+fn parse_sections(bytes: &[u8]) -> Sections {
+    let mut out = Sections::default();
+    let mut r = Reader { bytes, pos: 8 };
+    while let (Some(id), Some(size)) = (r.byte(), r.leb()) {
+        let end = r.pos + size as usize;
+        if end > bytes.len() { break; }
+        let mut s = Reader { bytes: &bytes[..end], pos: r.pos };
+        match id {
+            0 => {
+                if let Some(name) = s.name() {
+                    if name == "name" { parse_names(&mut s, &mut out.func_names); }
+                    out.custom.push((name, s.pos.min(end)..end));
+                }
+            }
+            2 => { parse_imports(&mut s, &mut out.imports); }
+            7 => { parse_exports(&mut s, &mut out.exports); }
+            _ => {}
+        }
+        r.pos = end;
+    }
+    out
+}
+
+fn parse_imports(s: &mut Reader, out: &mut Vec<WasmImport>) -> Option<()> {
+    for _ in 0..s.leb()? {
+        let module_name = s.name()?;
+        let field_name = s.name()?;
+        let kind = WasmExternKind::from_byte(s.byte()?)?;
+        match kind {
+            WasmExternKind::Function => { s.leb()?; }
+            WasmExternKind::Table => { s.byte()?; s.skip_limits()?; }
+            WasmExternKind::Memory => { s.skip_limits()?; }
+            WasmExternKind::Global => { s.byte()?; s.byte()?; }
+            WasmExternKind::Tag => { s.byte()?; s.leb()?; }
+        }
+        out.push(WasmImport { module_name, field_name, kind });
+    }
+    Some(())
+}
+
+fn parse_exports(s: &mut Reader, out: &mut Vec<WasmExport>) -> Option<()> {
+    for _ in 0..s.leb()? {
+        let name = s.name()?;
+        let kind = WasmExternKind::from_byte(s.byte()?)?;
+        let index = s.leb()?;
+        out.push(WasmExport { name, kind, index });
+    }
+    Some(())
+}
+
+/// `name` sekce, podsekce 1 = jmena funkci.
+fn parse_names(s: &mut Reader, out: &mut HashMap<u32, String>) -> Option<()> {
+    while let (Some(sub), Some(size)) = (s.byte(), s.leb()) {
+        let end = s.pos + size as usize;
+        if sub == 1 {
+            for _ in 0..s.leb()? {
+                let index = s.leb()?;
+                out.insert(index, s.name()?);
+            }
+        }
+        s.pos = end;
+    }
+    Some(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -189,6 +1113,16 @@ mod tests {
         assert_eq!(m.byte_length(), 65536 * 3);
     }
 
+    #[test]
+    fn memory_grow_detaches_old_buffer() {
+        let m = WasmMemory::new(1, None);
+        let before = m.buffer();
+        before.borrow_mut().write(10, &[7]);
+        m.grow(1);
+        assert!(before.borrow().detached);
+        assert_eq!(m.buffer().borrow().slice(10..11), [7]);
+    }
+
     #[test]
     fn memory_grow_blocked_by_max() {
         let m = WasmMemory::new(1, Some(2));
@@ -236,4 +1170,33 @@ mod tests {
         assert!(parse_module_header(b"\0asm\x02\0\0\0").is_err());
         assert!(parse_module_header(b"short").is_err());
     }
+
+    #[test]
+    fn sections_parsed_for_reflection() {
+        let bytes: &[u8] = &[
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00,
+            // import "m" "t" table funcref 1
+            0x02, 0x09, 0x01, 0x01, 0x6d, 0x01, 0x74, 0x01, 0x70, 0x00, 0x01,
+            // export "f" -> func 3
+            0x07, 0x05, 0x01, 0x01, 0x66, 0x00, 0x03,
+            // custom "x" = [1, 2]
+            0x00, 0x04, 0x01, 0x78, 0x01, 0x02,
+            // name sekce: func 3 = "foo"
+            0x00, 0x0d, 0x04, 0x6e, 0x61, 0x6d, 0x65, 0x01, 0x06, 0x01, 0x03, 0x03, 0x66, 0x6f, 0x6f,
+        ];
+        let s = parse_sections(bytes);
+        assert_eq!(s.imports.len(), 1);
+        assert_eq!(s.imports[0].kind, WasmExternKind::Table);
+        assert_eq!((s.exports[0].name.as_str(), s.exports[0].index), ("f", 3));
+        assert_eq!(s.custom.iter().map(|(n, _)| n.as_str()).collect::<Vec<_>>(), ["x", "name"]);
+        assert_eq!(&bytes[s.custom[0].1.clone()], &[1, 2]);
+        assert_eq!(s.func_names.get(&3).map(String::as_str), Some("foo"));
+    }
+
+    #[test]
+    fn int32_wraps_modulo() {
+        assert_eq!(to_int32(4294967297.0), 1);
+        assert_eq!(to_int32(-1.5), -1);
+        assert_eq!(to_int32(f64::NAN), 0);
+    }
 }
@@ -5,6 +5,7 @@ use std::rc::Rc;
 use std::cell::RefCell;
 use super::{JsValue, JsObject};
 use super::helpers::native;
+use super::builtins_typed_arrays::{bytes_of, typed_view_of};
 
 /// Pre-processing GLSL ES 1.0 (WebGL 1.0) -> GLSL ES 3.0 (WebGL 2.0)
 /// pro lepsi naga compatibility. WebGL 1.0 bez `#version` line implicitne
@@ -120,7 +121,7 @@ pub(crate) fn create_webgl_context(state: Rc<RefCell<WebGLState>>) -> JsValue {
         ("TEXTURE_WRAP_S", 0x2802), ("TEXTURE_WRAP_T", 0x2803),
     ];
     for (name, val) in &constants {
-        obj_rc.borrow_mut().set(name.to_string(), JsValue::Number(*val as f64));
+        obj_rc.borrow_mut().set((*name).into(), JsValue::Number(*val as f64));
     }
     // WebGL2 constants - vsechny additions oproti WebGL1.
     let webgl2_constants: &[(&str, u32)] = &[
@@ -163,7 +164,7 @@ pub(crate) fn create_webgl_context(state: Rc<RefCell<WebGLState>>) -> JsValue {
         ("UNIFORM_BUFFER_OFFSET_ALIGNMENT", 0x8A34),
     ];
     for (name, val) in webgl2_constants {
-        obj_rc.borrow_mut().set(name.to_string(), JsValue::Number(*val as f64));
+        obj_rc.borrow_mut().set((*name).into(), JsValue::Number(*val as f64));
     }
     // canvas property (minimal stub)
     obj_rc.borrow_mut().set("drawingBufferWidth".into(), JsValue::Number(300.0));
@@ -281,7 +282,7 @@ pub(crate) fn create_webgl_context(state: Rc<RefCell<WebGLState>>) -> JsValue {
                "vertexAttribI4i", "vertexAttribI4iv",
                "vertexAttribI4ui", "vertexAttribI4uiv",
                "vertexAttribIPointer"] {
-        obj_rc.borrow_mut().set(m.to_string(),
+        obj_rc.borrow_mut().set(m.to_string().into(),
             native(m, |_| Ok(JsValue::Undefined)));
     }
     // Sync objects
@@ -770,7 +771,8 @@ pub(crate) fn create_webgl_context(state: Rc<RefCell<WebGLState>>) -> JsValue {
                         buf
                     }
                     JsValue::Number(n) => vec![0u8; *n as usize],
-                    _ => Vec::new(),
+                    // ArrayBuffer / typed array: bajty tak, jak lezi v bufferu
+                    other => bytes_of(other).unwrap_or_default(),
                 };
                 if let Some(b) = s.buffers.get_mut(&id) { *b = bytes; }
             }
@@ -851,7 +853,7 @@ pub(crate) fn create_webgl_context(state: Rc<RefCell<WebGLState>>) -> JsValue {
                     JsValue::Array(arr) => arr.borrow().iter()
                         .map(|v| (v.to_number() as i64).clamp(0, 255) as u8)
                         .collect(),
-                    _ => Vec::new(),
+                    other => bytes_of(&other).unwrap_or_default(),
                 };
                 (w, h, fmt, bytes)
             } else {
@@ -984,6 +986,8 @@ pub(crate) fn create_webgl_context(state: Rc<RefCell<WebGLState>>) -> JsValue {
                     let arg = it.next().unwrap_or(JsValue::Undefined);
                     let nums: Vec<f32> = if let JsValue::Array(a) = arg {
                         a.borrow().iter().map(|v| v.to_number() as f32).collect()
+                    } else if let Some(view) = typed_view_of(&arg) {
+                        view.to_values().iter().map(|v| v.to_number() as f32).collect()
                     } else { vec![arg.to_number() as f32] };
                     WebGLUniformValue::Float(nums)
                 }
@@ -996,6 +1000,8 @@ pub(crate) fn create_webgl_context(state: Rc<RefCell<WebGLState>>) -> JsValue {
                     let arg = it.next().unwrap_or(JsValue::Undefined);
                     let nums: Vec<f32> = if let JsValue::Array(a) = arg {
                         a.borrow().iter().map(|v| v.to_number() as f32).collect()
+                    } else if let Some(view) = typed_view_of(&arg) {
+                        view.to_values().iter().map(|v| v.to_number() as f32).collect()
                     } else { Vec::new() };
                     WebGLUniformValue::Mat(nums)
                 }
@@ -1010,7 +1016,7 @@ pub(crate) fn create_webgl_context(state: Rc<RefCell<WebGLState>>) -> JsValue {
                "uniform1fv", "uniform2fv", "uniform3fv", "uniform4fv",
                "uniformMatrix2fv", "uniformMatrix3fv", "uniformMatrix4fv"] {
         let name = m.to_string();
-        obj_rc.borrow_mut().set(name.clone(), make_uniform_setter(m, Rc::clone(&state)));
+        obj_rc.borrow_mut().set(name.clone().into(), make_uniform_setter(m, Rc::clone(&state)));
     }
 
     // ─── No-op-zatim metody ──────────────────────────────────────────
@@ -1019,7 +1025,7 @@ pub(crate) fn create_webgl_context(state: Rc<RefCell<WebGLState>>) -> JsValue {
                "scissor", "stencilFunc", "stencilOp", "stencilMask",
                "lineWidth", "polygonOffset", "depthMask", "colorMask"] {
         let name = m.to_string();
-        obj_rc.borrow_mut().set(name.clone(), native(&name, |_| Ok(JsValue::Undefined)));
+        obj_rc.borrow_mut().set(name.clone().into(), native(&name, |_| Ok(JsValue::Undefined)));
     }
 
     // ─── Draw calls - real recording ────────────────────────────────
@@ -0,0 +1,192 @@
+//! Zpravy mezi page a Worker vlakny - structured clone pres JSON.
+//!
+//! `JsValue` je Rc (ne Send), takze kanalem jde JSON + seznam sdilenych
+//! pameti. SharedArrayBuffer (i typed array / DataView nad nim) se nahradi
+//! znackou `{"__clone_binary": ..., "shared": i}` a prijemce ho napoji na
+//! stejny `Arc<SharedMemory>` - obe strany pak vidi tytez bajty. Bezny
+//! ArrayBuffer a view nad nim se kopiruji (`"bytes"`).
+
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::sync::Arc;
+
+use super::{JsObject, JsValue};
+use super::builtins_typed_arrays::{binary_of, buffer_object, data_view_object, typed_array_object};
+use super::helpers::{json_parse, json_stringify};
+use super::shared_memory::SharedMemory;
+use super::typed_arrays::{ArrayBuffer, BinaryData, DataViewSlot, SharedBuffer, TypedArrayKind, TypedArrayView};
+
+/// Klic znacky binarnich dat; hodnota = trida ("ArrayBuffer", "Int32Array", ...).
+const MARKER: &str = "__clone_binary";
+
+/// Serializovana zprava pro `mpsc` kanal Workeru.
+#[derive(Debug)]
+pub struct WorkerMessage {
+    pub json: String,
+    pub shared: Vec<Arc<SharedMemory>>,
+}
+
+/// Structured clone odesilane hodnoty. SharedArrayBuffer jde poslat jen z
+/// cross-origin isolated kontextu (`allow_shared`), jinak DataCloneError.
+pub fn encode(value: &JsValue, allow_shared: bool) -> Result<WorkerMessage, String> {
+    let mut shared = Vec::new();
+    let cloneable = to_cloneable(value, allow_shared, &mut shared, 0)?;
+    let json = json_stringify(&cloneable, 0, 0).unwrap_or_else(|| value.to_string());
+    Ok(WorkerMessage { json, shared })
+}
+
+/// Rekonstrukce hodnoty na strane prijemce.
+pub fn decode(msg: WorkerMessage) -> JsValue {
+    let parsed = json_parse(&msg.json).unwrap_or(JsValue::Str(msg.json));
+    revive(parsed, &msg.shared)
+}
+
+fn to_cloneable(v: &JsValue, allow_shared: bool, shared: &mut Vec<Arc<SharedMemory>>, depth: usize) -> Result<JsValue, String> {
+    if depth > 64 { return Ok(JsValue::Null); }
+    match v {
+        JsValue::Object(o) => {
+            if let Some(data) = binary_of(v) {
+                return binary_marker(&data, allow_shared, shared);
+            }
+            let mut out = JsObject::new();
+            let src = o.borrow();
+            for k in src.own_keys() {
+                let value = to_cloneable(&src.get(&k), allow_shared, shared, depth + 1)?;
+                out.set(k.into(), value);
+            }
+            Ok(JsValue::Object(Rc::new(RefCell::new(out))))
+        }
+        JsValue::Array(a) => {
+            let items = a.borrow().iter()
+                .map(|x| to_cloneable(x, allow_shared, shared, depth + 1))
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok(JsValue::Array(Rc::new(RefCell::new(items))))
+        }
+        other => Ok(other.clone()),
+    }
+}
+
+fn binary_marker(data: &BinaryData, allow_shared: bool, shared: &mut Vec<Arc<SharedMemory>>) -> Result<JsValue, String> {
+    let mut m = JsObject::new();
+    m.set(MARKER.into(), JsValue::Str(data.class_name().into()));
+    let memory = data.buffer().borrow().shared_memory().cloned();
+    // Sdilena pamet: index do `shared` + pozice view; jinak kopie bajtu view
+    let (offset, length) = match data {
+        BinaryData::Buffer(_) => (0, None),
+        BinaryData::TypedArray(v) => (v.byte_offset, v.fixed_length),
+        BinaryData::DataView(d) => (d.byte_offset, d.fixed_length),
+    };
+    match memory {
+        Some(mem) => {
+            if !allow_shared {
+                return Err("DataCloneError: SharedArrayBuffer transfer requires cross-origin isolation".into());
+            }
+            let idx = shared.iter().position(|s| Arc::ptr_eq(s, &mem)).unwrap_or_else(|| {
+                shared.push(mem);
+                shared.len() - 1
+            });
+            m.set("shared".into(), JsValue::Number(idx as f64));
+            m.set("offset".into(), JsValue::Number(offset as f64));
+            m.set("length".into(), length.map_or(JsValue::Null, |n| JsValue::Number(n as f64)));
+        }
+        None => {
+            let bytes = match data {
+                BinaryData::Buffer(b) => b.borrow().to_vec(),
+                BinaryData::TypedArray(v) => v.bytes(),
+                BinaryData::DataView(d) => d.buffer.borrow().slice(d.byte_offset..d.byte_offset + d.byte_length()),
+            };
+            let bytes = bytes.into_iter().map(|b| JsValue::Number(b as f64)).collect();
+            m.set("bytes".into(), JsValue::Array(Rc::new(RefCell::new(bytes))));
+        }
+    }
+    Ok(JsValue::Object(Rc::new(RefCell::new(m))))
+}
+
+fn revive(v: JsValue, shared: &[Arc<SharedMemory>]) -> JsValue {
+    match &v {
+        JsValue::Object(o) => {
+            if let JsValue::Str(class) = o.borrow().get(MARKER) {
+                return revive_binary(&o.borrow(), &class, shared).unwrap_or(JsValue::Undefined);
+            }
+            let keys = o.borrow().own_keys();
+            for k in keys {
+                let child = o.borrow().get(&k);
+                let revived = revive(child, shared);
+                o.borrow_mut().set(k.into(), revived);
+            }
+            v
+        }
+        JsValue::Array(a) => {
+            let items: Vec<JsValue> = a.borrow().iter().cloned().map(|x| revive(x, shared)).collect();
+            *a.borrow_mut() = items;
+            v
+        }
+        _ => v,
+    }
+}
+
+fn revive_binary(m: &JsObject, class: &str, shared: &[Arc<SharedMemory>]) -> Option<JsValue> {
+    let index = |key: &str| match m.get(key) {
+        JsValue::Number(n) if n >= 0.0 => Some(n as usize),
+        _ => None,
+    };
+    let copied = matches!(m.get("bytes"), JsValue::Array(_));
+    let (buf, offset, length): (SharedBuffer, usize, Option<usize>) = match m.get("bytes") {
+        JsValue::Array(bytes) => {
+            let bytes: Vec<u8> = bytes.borrow().iter().map(|b| b.to_number() as u8).collect();
+            let len = bytes.len();
+            (ArrayBuffer::from_bytes(bytes).into_handle(), 0, Some(len))
+        }
+        _ => {
+            let memory = Arc::clone(shared.get(index("shared")?)?);
+            (ArrayBuffer::from_shared(memory).into_handle(), index("offset").unwrap_or(0), index("length"))
+        }
+    };
+    let buffer_js = buffer_object(Rc::clone(&buf));
+    Some(match class {
+        "ArrayBuffer" | "SharedArrayBuffer" => buffer_js,
+        "DataView" => data_view_object(DataViewSlot { buffer: buf, byte_offset: offset, fixed_length: length }, buffer_js),
+        ctor => {
+            let kind = TypedArrayKind::from_ctor(ctor)?;
+            // Kopie nese bajty, sdilene view pocet prvku
+            let length = if copied { length.map(|n| n / kind.element_size()) } else { length };
+            typed_array_object(TypedArrayView::new(buf, kind, offset, length), buffer_js)
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::interpreter::builtins_typed_arrays::{typed_array_from_values, typed_view_of};
+
+    #[test]
+    fn shared_view_reattaches_to_same_memory() {
+        let memory = Arc::new(SharedMemory::new(8, None).unwrap());
+        let buf = ArrayBuffer::from_shared(Arc::clone(&memory)).into_handle();
+        let view = TypedArrayView::new(Rc::clone(&buf), TypedArrayKind::Int32, 4, Some(1));
+        let ta = typed_array_object(view, buffer_object(buf));
+        let msg = encode(&ta, true).unwrap();
+        assert_eq!(msg.shared.len(), 1);
+        let received = typed_view_of(&decode(msg)).unwrap();
+        received.set(0, &JsValue::Number(42.0)).unwrap();
+        assert_eq!(memory.load(4, 4), 42);
+    }
+
+    #[test]
+    fn shared_requires_isolation() {
+        let memory = Arc::new(SharedMemory::new(4, None).unwrap());
+        let sab = buffer_object(ArrayBuffer::from_shared(memory).into_handle());
+        assert!(encode(&sab, false).unwrap_err().starts_with("DataCloneError"));
+    }
+
+    #[test]
+    fn plain_typed_array_is_copied() {
+        let ta = typed_array_from_values(TypedArrayKind::Uint16, &[JsValue::Number(1.0), JsValue::Number(513.0)]).unwrap();
+        let msg = encode(&ta, false).unwrap();
+        assert!(msg.shared.is_empty());
+        let copy = typed_view_of(&decode(msg)).unwrap();
+        assert_eq!(copy.length(), 2);
+        assert_eq!(copy.get(1).map(|v| v.to_number()), Some(513.0));
+    }
+}
@@ -32,12 +32,14 @@ pub struct Lexer {
     pub current_line: usize,
     /// Aktualni sloupec pri tokenizaci (od 0)
     pub current_column: usize,
+    /// Radek a sloupec zacatku prave lexovaneho tokenu
+    token_start: (usize, usize),
 }
 
 impl Lexer {
     /// Vytvori prazdny lexer.
     pub fn new() -> Self {
-        Lexer { tokens: Vec::new(), source_name: String::new(), current_line: 1, current_column: 0 }
+        Lexer { tokens: Vec::new(), source_name: String::new(), current_line: 1, current_column: 0, token_start: (1, 0) }
     }
 
     /// Tokenizuje soubor ze zadane cesty.
@@ -53,7 +55,7 @@ impl Lexer {
     ///
     /// `name` je jmeno souboru nebo identifikator zdroje pro chybove hlasky.
     pub fn parse_str(source: &str, name: &str) -> Result<Self, LexerError> {
-        let mut lex = Lexer { tokens: Vec::new(), source_name: name.to_string(), current_line: 1, current_column: 0 };
+        let mut lex = Lexer { tokens: Vec::new(), source_name: name.to_string(), current_line: 1, current_column: 0, token_start: (1, 0) };
         let mut cursor = Utf8Cursor::new(source);
         lex.tokens = lex.lex(&mut cursor)?;
         Ok(lex)
@@ -82,6 +84,7 @@ impl Lexer {
 
         while !r.eof() {
             let start = r.pos();
+            self.token_start = (self.current_line, self.current_column);
             let ch = r.peek().unwrap();
 
             // ── Whitespace ───────────────────────────────────────────────────
@@ -102,15 +105,15 @@ impl Lexer {
             if ch == '}' && !tmpl_stack.is_empty() && brace_depth == *tmpl_stack.last().unwrap() {
                 tmpl_stack.pop();
                 r.advance(); self.bump('}');
-                let (text, is_tail) = self.lex_template_text(r, start)?;
+                let (text, raw, is_tail) = self.lex_template_text(r, start)?;
                 let kind = if is_tail {
-                    TokenKind::TemplateTail(text.clone())
+                    TokenKind::TemplateTail(text)
                 } else {
                     tmpl_stack.push(brace_depth);
-                    TokenKind::TemplateMiddle(text.clone())
+                    TokenKind::TemplateMiddle(text)
                 };
                 last_significant = Some(kind.clone());
-                tokens.push(self.tok(kind, text, start));
+                tokens.push(self.tok(kind, raw, start));
                 continue;
             }
 
@@ -148,15 +151,15 @@ impl Lexer {
             // ── Template literal ─────────────────────────────────────────────
             if ch == '`' {
                 r.advance(); self.bump('`');
-                let (text, is_tail) = self.lex_template_text(r, start)?;
+                let (text, raw, is_tail) = self.lex_template_text(r, start)?;
                 let kind = if is_tail {
-                    TokenKind::NoSubstitutionTemplate(text.clone())
+                    TokenKind::NoSubstitutionTemplate(text)
                 } else {
                     tmpl_stack.push(brace_depth);
-                    TokenKind::TemplateHead(text.clone())
+                    TokenKind::TemplateHead(text)
                 };
                 last_significant = Some(kind.clone());
-                tokens.push(self.tok(kind, text, start));
+                tokens.push(self.tok(kind, raw, start));
                 continue;
             }
 
@@ -194,6 +197,16 @@ impl Lexer {
                 continue;
             }
 
+            // ── Privatni jmeno tridy: #field ──────────────────────────────────
+            if ch == '#' && r.peek_n(1).is_some_and(Token::is_valid_identifier_start) {
+                r.advance(); self.bump('#');
+                let name = self.read_identifier(r);
+                let kind = TokenKind::PrivateName(name.clone());
+                last_significant = Some(kind.clone());
+                tokens.push(self.tok(kind, format!("#{name}"), start));
+                continue;
+            }
+
             // ── Regularni vyraz nebo operator / ──────────────────────────────
             // Musi byt PRED obecnym read_operator, ktery by / zpracoval jako deleni.
             if ch == '/' && !matches!(r.peek_n(1), Some('/') | Some('*')) {
@@ -228,9 +241,10 @@ impl Lexer {
 
     // ─── Pomocné metody ───────────────────────────────────────────────────────
 
-    /// Vytvoří token s danou pozicí a aktuálním line/column.
+    /// Vytvoří token s danou pozicí; line/column odpovídají začátku tokenu.
     pub fn tok(&self, kind: TokenKind, lexeme: String, start: usize) -> Token {
-        Token { kind, end: start + lexeme.len(), lexeme, start, line: self.current_line, column: self.current_column }
+        let (line, column) = self.token_start;
+        Token { kind, end: start + lexeme.len(), lexeme, start, line, column }
     }
 
     /// Aktualizuje čítač řádků/sloupců.
@@ -298,24 +312,35 @@ impl Lexer {
     }
 
     /// Čte textovou část template literálu až do `${` nebo `` ` ``.
-    /// Vrací (text, is_tail): is_tail=true pokud jsme narazili na `` ` ``.
-    pub fn lex_template_text(&mut self, r: &mut Utf8Cursor, start: usize) -> Result<(String, bool), LexerError> {
+    /// Vrací (text, raw, is_tail): `text` je cooked hodnota (escape sekvence
+    /// rozbalené), `raw` zdrojový text pro tagged templates / `String.raw`
+    /// (CR a CRLF normalizované na LF), is_tail=true pokud jsme narazili na `` ` ``.
+    pub fn lex_template_text(&mut self, r: &mut Utf8Cursor, start: usize) -> Result<(String, String, bool), LexerError> {
         let mut buf = String::new();
+        let mut raw = String::new();
         loop {
             if r.eof() { return Err(LexerError { kind: LexerErrorKind::UnterminatedTemplate, span: Span { start, end: r.pos() } }); }
             let ch = r.peek().unwrap();
-            if ch == '`' { r.advance(); self.bump(ch); return Ok((buf, true)); }
+            if ch == '`' { r.advance(); self.bump(ch); return Ok((buf, raw, true)); }
             if ch == '$' && r.peek_n(1) == Some('{') {
                 r.advance(); r.advance(); self.bump('$'); self.bump('{');
-                return Ok((buf, false));
+                return Ok((buf, raw, false));
             }
             if ch == '\\' {
                 let esc = self.read_escape_sequence(r, start)?;
+                raw.push_str(&esc.raw.replace("\r\n", "\n").replace('\r', "\n"));
                 // Ignorujeme nulový char z line continuation
                 if esc.character != '\0' { buf.push(esc.character); }
                 continue;
             }
-            r.advance(); self.bump(ch); buf.push(ch);
+            r.advance(); self.bump(ch);
+            if ch == '\r' {
+                // CRLF / CR v template = LF (cooked i raw)
+                if r.peek() == Some('\n') { r.advance(); self.bump('\n'); }
+                buf.push('\n'); raw.push('\n');
+                continue;
+            }
+            buf.push(ch); raw.push(ch);
         }
     }
 }
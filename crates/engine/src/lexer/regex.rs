@@ -43,6 +43,7 @@ impl Lexer {
                 ),
                 // Po hodnotovych tokenech -> deleni
                 TokenKind::Identifier(_)               => false,
+                TokenKind::PrivateName(_)              => false,
                 TokenKind::NumericLiteral { .. }       => false,
                 TokenKind::StringLiteral { .. }        => false,
                 TokenKind::RegexLiteral { .. }         => false,
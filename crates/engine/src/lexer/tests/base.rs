@@ -112,6 +112,17 @@ fn template_two_exprs() {
     assert!(matches!(&kinds[4], TokenKind::TemplateTail(s) if s == ""));
 }
 
+#[test]
+fn template_raw_lexeme() {
+    // Kind nese cooked text, lexeme raw zdroj (pro tagged templates)
+    let toks = Lexer::parse_str(r"`a\n${x}\u0041`", "<test>").unwrap().tokens;
+    assert!(matches!(&toks[0].kind, TokenKind::TemplateHead(s) if s == "a\n"));
+    assert_eq!(toks[0].lexeme, r"a\n");
+    let tail = toks.iter().find(|t| matches!(t.kind, TokenKind::TemplateTail(_))).unwrap();
+    assert!(matches!(&tail.kind, TokenKind::TemplateTail(s) if s == "A"));
+    assert_eq!(tail.lexeme, r"\u0041");
+}
+
 // --- chyby ---
 
 #[test]
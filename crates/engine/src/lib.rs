@@ -16,12 +16,14 @@ pub mod specifications;
 pub mod ast;
 pub mod lexer;
 pub mod parser;
+pub mod codegen;
 pub mod interpreter;
 pub mod browser;
 pub mod debug_view;
 pub mod devtools;
 pub mod debug_bp;
 pub mod testing;
+pub mod repl;
 
 // Embeddable API contract - stable high-level facade pro hostujici aplikace
 // (shell crate, third-party UI). Phase 2 = stubs, Phase 3-5 = naplnuje.
@@ -248,6 +250,43 @@ console.log(greeting, result);
         return;
     }
 
+    // REPL: cargo run -- repl [--page file.html]
+    if args.len() > 1 && args[1] == "repl" {
+        repl::run(&args[2..]);
+        return;
+    }
+
+    // Test262: cargo run -- test262 <checkout> [cesty...] [--timeout=ms] [--jobs=N]
+    //   [--report=out.json] [--expectations=file.json] [--update-expectations]
+    //   [--skip-feature=Name] [--tier=vm|tree]
+    // Exit code 1 = regrese vuci expectations (CI gate).
+    if args.len() > 1 && args[1] == "test262" {
+        let Some(root) = args.get(2).filter(|a| !a.starts_with("--")) else {
+            eprintln!("Pouziti: test262 <test262-checkout> [cesty...] [--timeout=ms] [--jobs=N] \
+                [--report=file] [--expectations=file] [--update-expectations] [--skip-feature=Name] [--tier=vm|tree]");
+            std::process::exit(2);
+        };
+        let flag = |name: &str| args.iter().find_map(|a| a.strip_prefix(name).map(String::from));
+        let mut cfg = testing::test262::Test262Config::new(root);
+        cfg.paths = args.iter().skip(3).filter(|a| !a.starts_with("--")).map(std::path::PathBuf::from).collect();
+        if let Some(ms) = flag("--timeout=").and_then(|v| v.parse().ok()) {
+            cfg.timeout = std::time::Duration::from_millis(ms);
+        }
+        if let Some(n) = flag("--jobs=").and_then(|v| v.parse().ok()) {
+            cfg.jobs = n;
+        }
+        if flag("--tier=").as_deref() == Some("tree") {
+            cfg.tier = testing::test262::Tier::Tree;
+        }
+        cfg.skip_features = args.iter().filter_map(|a| a.strip_prefix("--skip-feature=").map(String::from)).collect();
+        let report = flag("--report=").unwrap_or_else(|| "test262-report.json".into());
+        let expectations = flag("--expectations=").unwrap_or_else(|| "test262-expectations.json".into());
+        let update = args.iter().any(|a| a == "--update-expectations");
+        let ok = testing::test262::run_suite(&cfg, report.as_ref(), expectations.as_ref(), update);
+        // Timeoutovana vlakna nejdou prerusit - exit je ukonci.
+        std::process::exit(if ok { 0 } else { 1 });
+    }
+
     // Dump mode: cargo run -- dump <url|path> [out.txt] [--selector=.foo]
     // Vystup: full layout box tree + matched CSS rules + computed styles per box.
     // Diff vs Chrome devtools `getComputedStyle()` ukaze co chyba.
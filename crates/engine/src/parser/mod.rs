@@ -32,6 +32,9 @@ pub struct ParseError {
     pub line: usize,
     /// Sloupec kde chyba nastala (od 0)
     pub column: usize,
+    /// Parser narazil na konec vstupu - zdroj je neuplny (REPL si vyzada
+    /// dalsi radek misto hlaseni chyby).
+    pub eof: bool,
 }
 
 impl std::fmt::Display for ParseError {
@@ -40,6 +43,9 @@ impl std::fmt::Display for ParseError {
     }
 }
 
+/// Casti template literalu: (cooked, raw, expressions).
+type TemplateParts = (Vec<String>, Vec<String>, Vec<Box<Expr>>);
+
 // ─── Pomocne funkce ───────────────────────────────────────────────────────────
 
 /// Konvertuje destrukturovaci Pattern na odpovidajici Expr.
@@ -53,14 +59,14 @@ impl std::fmt::Display for ParseError {
 /// - `Pattern::Object(...)`-> `Expr::Object(...)` (shorthand zachovan)
 fn pattern_to_expr(pattern: Pattern) -> Expr {
     match pattern {
-        Pattern::Ident(name) => Expr::Ident(name),
+        Pattern::Ident(name) => Expr::Ident(name, SourceSpan::default()),
         Pattern::Array(elems) => Expr::Array(
             elems.into_iter().map(|e| {
                 e.pattern.map(|p| {
                     let inner = pattern_to_expr(p);
                     Box::new(inner)
                 })
-            }).collect()
+            }).collect(), SourceSpan::default()
         ),
         Pattern::Object(props) => Expr::Object(
             props.into_iter().map(|p| ObjectProp {
@@ -68,7 +74,8 @@ fn pattern_to_expr(pattern: Pattern) -> Expr {
                 value: Box::new(pattern_to_expr(p.pattern)),
                 shorthand: p.shorthand,
                 computed: false,
-            }).collect()
+                kind: PropKind::Init,
+            }).collect(), SourceSpan::default()
         ),
     }
 }
@@ -84,6 +91,22 @@ pub struct Parser {
     tokens: Vec<Token>,
     /// Aktualni pozice v token streamu
     pos: usize,
+    /// Parsovany kod je strict (direktiva `"use strict"`, telo tridy, modul).
+    strict: bool,
+}
+
+/// Slova vyhrazena jen ve strict mode - nesmi byt jmenem vazby.
+const STRICT_RESERVED: &[&str] = &[
+    "implements", "interface", "let", "package", "private", "protected", "public", "static", "yield",
+];
+
+/// Text direktivy, je-li prikaz retezcovym vyrazovym prikazem (prolog direktiv).
+fn directive(stmt: &Stmt) -> Option<&str> {
+    match stmt {
+        Stmt::WithLine { inner, .. } => directive(inner),
+        Stmt::Expr(Expr::Str(s, _), _) => Some(s),
+        _ => None,
+    }
 }
 
 impl Parser {
@@ -93,13 +116,19 @@ impl Parser {
     /// `Newline` a komentaru. Parser si trivia sam preskakuje,
     /// ale efektivnejsi je prefiltrovat predem.
     pub fn new(tokens: Vec<Token>) -> Self {
-        Parser { tokens, pos: 0 }
+        Parser { tokens, pos: 0, strict: false }
     }
 
     /// Parsuje cely program a vraci koren AST.
     pub fn parse(&mut self) -> Result<Program, ParseError> {
-        let body = self.parse_stmts_until_eof()?;
-        Ok(Program { body, strict: false })
+        let body = self.parse_body(true)?;
+        Ok(Program { body, strict: self.strict })
+    }
+
+    /// Parsuje ES modul - kod modulu je vzdy strict.
+    pub fn parse_module(&mut self) -> Result<Program, ParseError> {
+        self.strict = true;
+        self.parse()
     }
 
     // ─── Pohyb v tokenovém poli ───────────────────────────────────────────────
@@ -111,11 +140,90 @@ impl Parser {
 
     fn kind(&self) -> &TokenKind { &self.cur().kind }
 
+    /// Pozice zacatku aktualniho tokenu.
+    fn start_pos(&self) -> SourcePos {
+        let t = self.cur();
+        SourcePos { line: t.line as u32, column: t.column as u32, offset: t.start as u32 }
+    }
+
+    /// Pozice za poslednim spotrebovanym (ne-trivia) tokenem.
+    fn end_pos(&self) -> SourcePos {
+        let Some(t) = self.tokens[..self.pos.min(self.tokens.len())].iter().rev().find(|t| !matches!(t.kind,
+            TokenKind::Whitespace | TokenKind::Newline
+            | TokenKind::CommentLine(_) | TokenKind::CommentBlock(_)))
+        else {
+            return self.start_pos();
+        };
+        // Viceradkove tokeny (template, blokove retezce) konci na jinem radku
+        let (line, column) = match t.lexeme.rfind('\n') {
+            Some(nl) => (t.line + t.lexeme.matches('\n').count(), t.lexeme[nl + 1..].chars().count()),
+            None => (t.line, t.column + t.lexeme.chars().count()),
+        };
+        SourcePos { line: line as u32, column: column as u32, offset: t.end as u32 }
+    }
+
+    /// Rozsah od `start` po posledni spotrebovany token.
+    fn span_from(&self, start: SourcePos) -> SourceSpan {
+        SourceSpan { start, end: self.end_pos() }
+    }
+
     fn peek_kind_ahead(&self, n: usize) -> &TokenKind {
         let idx = (self.pos + n).min(self.tokens.len().saturating_sub(1));
         &self.tokens[idx].kind
     }
 
+    /// Nejblizsi dalsi token za aktualnim, ktery neni whitespace/komentar.
+    fn peek_non_trivia(&self) -> &TokenKind {
+        let idx = self.non_trivia_after(self.pos);
+        &self.tokens[idx.min(self.tokens.len().saturating_sub(1))].kind
+    }
+
+    /// Index nejblizsiho tokenu za `idx`, ktery neni whitespace/komentar.
+    fn non_trivia_after(&self, idx: usize) -> usize {
+        let mut idx = idx + 1;
+        while idx < self.tokens.len() && matches!(self.tokens[idx].kind,
+            TokenKind::Whitespace | TokenKind::Newline
+            | TokenKind::CommentLine(_) | TokenKind::CommentBlock(_))
+        {
+            idx += 1;
+        }
+        idx
+    }
+
+    /// Zacina na aktualni pozici deklarace `using x` / `await using x`?
+    /// `using` je kontextove slovo - deklarace jen kdyz za nim na stejnem
+    /// radku nasleduje jmeno vazby.
+    fn at_using_decl(&self) -> Option<VarKind> {
+        let (kind, using_idx) = match self.kind() {
+            TokenKind::Identifier(s) if s == "using" => (VarKind::Using, self.pos),
+            TokenKind::Keyword(KeywordEnum::Await) => (VarKind::AwaitUsing, self.non_trivia_after(self.pos)),
+            _ => return None,
+        };
+        let using = self.tokens.get(using_idx)?;
+        if !matches!(&using.kind, TokenKind::Identifier(s) if s == "using") || using.line != self.cur().line {
+            return None;
+        }
+        let name = self.tokens.get(self.non_trivia_after(using_idx))?;
+        (matches!(name.kind, TokenKind::Identifier(_)) && name.line == using.line).then_some(kind)
+    }
+
+    /// Spotrebuje klicova slova deklarace (`var`/`let`/`const`/`using`/`await using`).
+    fn parse_var_kind(&mut self) -> VarKind {
+        if let Some(kind) = self.at_using_decl() {
+            if kind == VarKind::AwaitUsing {
+                self.advance();
+                self.skip_trivia();
+            }
+            self.advance();
+            return kind;
+        }
+        match self.kind().clone() {
+            TokenKind::Keyword(KeywordEnum::Let)   => { self.advance(); VarKind::Let }
+            TokenKind::Keyword(KeywordEnum::Const) => { self.advance(); VarKind::Const }
+            _                                       => { self.advance(); VarKind::Var }
+        }
+    }
+
     fn advance(&mut self) -> Token {
         let t = self.cur().clone();
         if self.pos + 1 < self.tokens.len() { self.pos += 1; }
@@ -163,9 +271,10 @@ impl Parser {
     /// ECMAScript: label musi byt na stejnem radku - ale protoze trivia
     /// uz jsou odfiltrována, jen zkontrolujeme jestli nasleduje identifikator.
     fn eat_label(&mut self) -> Option<String> {
-        // Preskocime pouze whitespace (ne newlines), ale protoze mame prefiltrovany
-        // stream bez trivia, zkusime jednoduchy heuristiku: identifikator hned za.
-        // V praxi to funguje pro vsechny bezne pripady.
+        // Preskocime pouze whitespace (ne newlines) - `break\nfoo` je break bez labelu (ASI).
+        while matches!(self.kind(), TokenKind::Whitespace | TokenKind::CommentBlock(_)) {
+            self.advance();
+        }
         if let TokenKind::Identifier(name) = self.kind().clone() {
             self.advance();
             Some(name)
@@ -175,7 +284,8 @@ impl Parser {
     }
 
     fn err(&self, msg: impl Into<String>) -> ParseError {
-        ParseError { msg: msg.into(), line: self.cur().line, column: self.cur().column }
+        let t = self.cur();
+        ParseError { msg: msg.into(), line: t.line, column: t.column, eof: matches!(t.kind, TokenKind::Eof) }
     }
 
     fn at_eof(&mut self) -> bool {
@@ -185,12 +295,27 @@ impl Parser {
 
     // ─── Příkazy ──────────────────────────────────────────────────────────────
 
-    fn parse_stmts_until_eof(&mut self) -> Result<Vec<Stmt>, ParseError> {
+    /// Prikazy programu (`until_eof`) nebo tela funkce (do `}`). Dokud trva
+    /// prolog direktiv, `"use strict"` prepne zbytek tela do strict mode.
+    fn parse_body(&mut self, until_eof: bool) -> Result<Vec<Stmt>, ParseError> {
         let mut stmts = Vec::new();
+        let mut prologue = true;
         loop {
             self.skip_trivia();
-            if matches!(self.kind(), TokenKind::Eof) { break; }
-            stmts.push(self.parse_stmt()?);
+            match self.kind() {
+                TokenKind::Eof => break,
+                TokenKind::Operator(OperatorEnum::RBrace) if !until_eof => break,
+                _ => {}
+            }
+            let stmt = self.parse_stmt()?;
+            if prologue {
+                match directive(&stmt) {
+                    Some("use strict") => self.strict = true,
+                    Some(_) => {}
+                    None => prologue = false,
+                }
+            }
+            stmts.push(stmt);
         }
         Ok(stmts)
     }
@@ -207,46 +332,50 @@ impl Parser {
 
     fn parse_stmt(&mut self) -> Result<Stmt, ParseError> {
         self.skip_trivia();
-        let line = self.cur().line as u32;
+        let start = self.start_pos();
         let inner = self.parse_stmt_inner()?;
         // WithLine wrap - skip pro Empty + jiz wrapped + Block (zachova exec).
         // Block obsahuje vlastni stmts kazdy s WithLine z parse_block_body.
         match inner {
-            Stmt::Empty | Stmt::WithLine { .. } | Stmt::Block(_) => Ok(inner),
-            other => Ok(Stmt::WithLine { line, inner: Box::new(other) }),
+            Stmt::Empty(_) | Stmt::WithLine { .. } | Stmt::Block(_, _) => Ok(inner),
+            other => Ok(Stmt::WithLine { line: start.line, span: self.span_from(start), inner: Box::new(other) }),
         }
     }
 
     fn parse_stmt_inner(&mut self) -> Result<Stmt, ParseError> {
         self.skip_trivia();
+        let start = self.start_pos();
         match self.kind().clone() {
             TokenKind::Operator(OperatorEnum::LBrace) => {
                 self.advance();
                 let body = self.parse_block_body()?;
                 self.expect_op(OperatorEnum::RBrace)?;
-                Ok(Stmt::Block(body))
+                Ok(Stmt::Block(body, self.span_from(start)))
             }
-            TokenKind::Operator(OperatorEnum::Semi) => { self.advance(); Ok(Stmt::Empty) }
+            TokenKind::Operator(OperatorEnum::Semi) => { self.advance(); Ok(Stmt::Empty(self.span_from(start))) }
 
             TokenKind::Keyword(KeywordEnum::Let)
             | TokenKind::Keyword(KeywordEnum::Const)
             | TokenKind::Keyword(KeywordEnum::Var) => self.parse_var_decl(),
+            // `using x = ...` / `await using x = ...`
+            TokenKind::Identifier(_) | TokenKind::Keyword(KeywordEnum::Await)
+                if self.at_using_decl().is_some() => self.parse_var_decl(),
 
             TokenKind::Keyword(KeywordEnum::Function) => self.parse_fn_decl(),
 
             // `async function name(...) { }` - async funkce v statement pozici
             TokenKind::Keyword(KeywordEnum::Async) => {
                 // Peek: je to `async function`?
-                let next_is_fn = matches!(self.tokens.get(self.pos + 1).map(|t| &t.kind),
-                    Some(TokenKind::Keyword(KeywordEnum::Function)));
+                let next_is_fn = matches!(self.peek_non_trivia(), TokenKind::Keyword(KeywordEnum::Function));
                 if next_is_fn {
                     self.advance(); // spotreba `async`
-                    self.parse_async_fn_decl()
+                    self.skip_trivia();
+                    self.parse_async_fn_decl(start)
                 } else {
                     // async arrow nebo async identifier - jako expression statement
                     let expr = self.parse_expr()?;
                     self.eat_semi();
-                    Ok(Stmt::Expr(expr))
+                    Ok(Stmt::Expr(expr, self.span_from(start)))
                 }
             }
 
@@ -260,14 +389,14 @@ impl Parser {
                 );
                 let val = if has_val { Some(self.parse_expr()?) } else { None };
                 self.eat_semi();
-                Ok(Stmt::Return(val))
+                Ok(Stmt::Return(val, self.span_from(start)))
             }
 
             TokenKind::Keyword(KeywordEnum::Throw) => {
                 self.advance();
                 let val = self.parse_expr()?;
                 self.eat_semi();
-                Ok(Stmt::Throw(val))
+                Ok(Stmt::Throw(val, self.span_from(start)))
             }
 
             TokenKind::Keyword(KeywordEnum::Break) => {
@@ -275,14 +404,14 @@ impl Parser {
                 // `break label;` - nepovinny identifikator na stejnem radku
                 let label = self.eat_label();
                 self.eat_semi();
-                Ok(Stmt::Break(label))
+                Ok(Stmt::Break(label, self.span_from(start)))
             }
 
             TokenKind::Keyword(KeywordEnum::Continue) => {
                 self.advance();
                 let label = self.eat_label();
                 self.eat_semi();
-                Ok(Stmt::Continue(label))
+                Ok(Stmt::Continue(label, self.span_from(start)))
             }
 
             TokenKind::Keyword(KeywordEnum::If)     => self.parse_if(),
@@ -292,16 +421,28 @@ impl Parser {
             TokenKind::Keyword(KeywordEnum::Try)    => self.parse_try(),
             TokenKind::Keyword(KeywordEnum::Switch) => self.parse_switch(),
             TokenKind::Keyword(KeywordEnum::Class)  => self.parse_class_decl(),
+            // `@dec class C {}` / `@dec export class C {}`
+            TokenKind::Operator(OperatorEnum::At) => {
+                let decorators = self.parse_decorators()?;
+                self.skip_trivia();
+                let stmt = match self.kind() {
+                    TokenKind::Keyword(KeywordEnum::Class)  => self.parse_class_decl()?,
+                    TokenKind::Keyword(KeywordEnum::Export) => self.parse_export_stmt()?,
+                    _ => return Err(self.err("Dekorator lze pouzit jen pred tridou nebo jejim clenem")),
+                };
+                self.decorate_class_stmt(stmt, decorators)
+            }
             TokenKind::Keyword(KeywordEnum::Import) => {
                 // Pozor: `import(specifier)` je dynamicky import (vyraz),
                 // `import "x"` nebo `import X from ...` je staticky (statement).
-                // Peek na nasledujici token: kdyz je to `(`, je to dynamicky.
-                let next_is_paren = matches!(self.tokens.get(self.pos + 1).map(|t| &t.kind),
-                    Some(TokenKind::Operator(OperatorEnum::LParen)));
-                if next_is_paren {
+                // Peek na nasledujici token: kdyz je to `(`, je to dynamicky;
+                // `.` = `import.meta`.
+                let next_is_expr = matches!(self.tokens.get(self.pos + 1).map(|t| &t.kind),
+                    Some(TokenKind::Operator(OperatorEnum::LParen | OperatorEnum::Dot)));
+                if next_is_expr {
                     let expr = self.parse_expr()?;
                     self.eat_semi();
-                    Ok(Stmt::Expr(expr))
+                    Ok(Stmt::Expr(expr, self.span_from(start)))
                 } else {
                     self.parse_import_stmt()
                 }
@@ -311,26 +452,23 @@ impl Parser {
             _ => {
                 let expr = self.parse_expr()?;
                 // labeled statement: label:
-                if let Expr::Ident(ref name) = expr {
+                if let Expr::Ident(ref name, _) = expr {
                     self.skip_trivia();
                     if matches!(self.kind(), TokenKind::Operator(OperatorEnum::Colon)) {
                         self.advance();
                         let body = self.parse_stmt()?;
-                        return Ok(Stmt::Labeled { label: name.clone(), body: Box::new(body) });
+                        return Ok(Stmt::Labeled { label: name.clone(), body: Box::new(body), span: self.span_from(start) });
                     }
                 }
                 self.eat_semi();
-                Ok(Stmt::Expr(expr))
+                Ok(Stmt::Expr(expr, self.span_from(start)))
             }
         }
     }
 
     fn parse_var_decl(&mut self) -> Result<Stmt, ParseError> {
-        let kind = match self.kind().clone() {
-            TokenKind::Keyword(KeywordEnum::Let)   => { self.advance(); VarKind::Let }
-            TokenKind::Keyword(KeywordEnum::Const) => { self.advance(); VarKind::Const }
-            _                                       => { self.advance(); VarKind::Var }
-        };
+        let start = self.start_pos();
+        let kind = self.parse_var_kind();
         let mut decls = Vec::new();
         loop {
             self.skip_trivia();
@@ -339,44 +477,54 @@ impl Parser {
             let init = if self.eat_op(OperatorEnum::Assign) {
                 Some(self.parse_assign_expr()?)
             } else { None };
+            if kind.is_using() {
+                if !matches!(pattern, Pattern::Ident(_)) {
+                    return Err(self.err("Deklarace `using` nepodporuje destrukturovani"));
+                }
+                if init.is_none() {
+                    return Err(self.err("Deklarace `using` vyzaduje inicializator"));
+                }
+            }
             decls.push(VarDecl { pattern, init });
             self.skip_trivia();
             if !self.eat_op(OperatorEnum::Comma) { break; }
         }
         self.eat_semi();
-        Ok(Stmt::Var { kind, decls })
+        Ok(Stmt::Var { kind, decls, span: self.span_from(start) })
     }
 
     fn parse_fn_decl(&mut self) -> Result<Stmt, ParseError> {
+        let start = self.start_pos();
         self.expect_kw(KeywordEnum::Function)?;
         self.skip_trivia();
         // Zkontroluj generator: `function*`
         let is_gen = self.eat_op(OperatorEnum::Star);
         self.skip_trivia();
-        let name = self.parse_ident()?;
+        let name = self.parse_binding_ident()?;
         let params = self.parse_params()?;
-        let body = self.parse_fn_body()?;
+        let body = self.parse_fn_body(&params)?;
         if is_gen {
-            Ok(Stmt::GeneratorFunc { name, params, body })
+            Ok(Stmt::GeneratorFunc { name, params, body, span: self.span_from(start) })
         } else {
-            Ok(Stmt::Function { name, params, body })
+            Ok(Stmt::Function { name, params, body, span: self.span_from(start) })
         }
     }
 
-    /// Parsuje `async function name(params) { body }` (token `async` jiz spotrebovan).
-    fn parse_async_fn_decl(&mut self) -> Result<Stmt, ParseError> {
+    /// Parsuje `async function name(params) { body }` (token `async` jiz spotrebovan,
+    /// `start` je jeho pozice).
+    fn parse_async_fn_decl(&mut self, start: SourcePos) -> Result<Stmt, ParseError> {
         self.expect_kw(KeywordEnum::Function)?;
         self.skip_trivia();
         // `async function*` - async generator
         let is_generator = self.eat_op(OperatorEnum::Star);
         self.skip_trivia();
-        let name = self.parse_ident()?;
+        let name = self.parse_binding_ident()?;
         let params = self.parse_params()?;
-        let body = self.parse_fn_body()?;
+        let body = self.parse_fn_body(&params)?;
         if is_generator {
-            Ok(Stmt::AsyncGeneratorFunc { name, params, body })
+            Ok(Stmt::AsyncGeneratorFunc { name, params, body, span: self.span_from(start) })
         } else {
-            Ok(Stmt::AsyncFunc { name, params, body })
+            Ok(Stmt::AsyncFunc { name, params, body, span: self.span_from(start) })
         }
     }
 
@@ -391,6 +539,7 @@ impl Parser {
     ///   import x, { a } from "path";
     ///   import x, * as ns from "path";
     fn parse_import_stmt(&mut self) -> Result<Stmt, ParseError> {
+        let start = self.start_pos();
         self.expect_kw(KeywordEnum::Import)?;
         self.skip_trivia();
         let mut specifiers: Vec<ImportSpecifier> = Vec::new();
@@ -399,7 +548,7 @@ impl Parser {
         if matches!(self.kind(), TokenKind::StringLiteral { .. }) {
             let source = self.parse_string_literal()?;
             self.eat_semi();
-            return Ok(Stmt::Import { source, specifiers });
+            return Ok(Stmt::Import { source, specifiers, span: self.span_from(start) });
         }
 
         // Default import: `import name`
@@ -451,7 +600,7 @@ impl Parser {
         self.skip_trivia();
         let source = self.parse_string_literal()?;
         self.eat_semi();
-        Ok(Stmt::Import { source, specifiers })
+        Ok(Stmt::Import { source, specifiers, span: self.span_from(start) })
     }
 
     /// Parsuje `export` prikaz. Token `export` jeste neni spotrebovan.
@@ -459,10 +608,30 @@ impl Parser {
     ///   export default expr;
     ///   export const x = ...;  / export function f() {} / export class C {}
     ///   export { a, b as c };
+    ///   export { a, b as c } from "path";
+    ///   export * from "path";  / export * as ns from "path";
     fn parse_export_stmt(&mut self) -> Result<Stmt, ParseError> {
+        let start = self.start_pos();
         self.expect_kw(KeywordEnum::Export)?;
         self.skip_trivia();
 
+        // export * from "path" / export * as ns from "path"
+        if matches!(self.kind(), TokenKind::Operator(OperatorEnum::Star)) {
+            self.advance();
+            self.skip_trivia();
+            let alias = if self.is_contextual_keyword("as") {
+                self.advance();
+                Some(self.parse_ident()?)
+            } else {
+                None
+            };
+            self.expect_contextual_keyword("from")?;
+            self.skip_trivia();
+            let source = self.parse_string_literal()?;
+            self.eat_semi();
+            return Ok(Stmt::Export(ExportKind::Star { source, alias }, self.span_from(start)));
+        }
+
         // export default <expr>
         if matches!(self.kind(), TokenKind::Keyword(KeywordEnum::Default)) {
             self.advance();
@@ -471,7 +640,7 @@ impl Parser {
             // -> bereme jako vyraz (FunctionExpr / ClassExpr)
             let expr = self.parse_assign_expr()?;
             self.eat_semi();
-            return Ok(Stmt::Export(ExportKind::Default(expr)));
+            return Ok(Stmt::Export(ExportKind::Default(expr), self.span_from(start)));
         }
 
         // export { a, b as c }
@@ -495,13 +664,20 @@ impl Parser {
                 if !self.eat_op(OperatorEnum::Comma) { break; }
             }
             self.expect_op(OperatorEnum::RBrace)?;
+            if self.is_contextual_keyword("from") {
+                self.advance();
+                self.skip_trivia();
+                let source = self.parse_string_literal()?;
+                self.eat_semi();
+                return Ok(Stmt::Export(ExportKind::From { source, names }, self.span_from(start)));
+            }
             self.eat_semi();
-            return Ok(Stmt::Export(ExportKind::Named(names)));
+            return Ok(Stmt::Export(ExportKind::Named(names), self.span_from(start)));
         }
 
         // export <decl>: const/let/var/function/class
         let decl = self.parse_stmt()?;
-        Ok(Stmt::Export(ExportKind::Decl(Box::new(decl))))
+        Ok(Stmt::Export(ExportKind::Decl(Box::new(decl)), self.span_from(start)))
     }
 
     /// Parsuje string literal a vrati jeho hodnotu (pro source v import/export).
@@ -559,15 +735,71 @@ impl Parser {
 
     /// Parsuje `class` deklaraci na urovni prikazu.
     fn parse_class_decl(&mut self) -> Result<Stmt, ParseError> {
+        let start = self.start_pos();
         self.advance(); // 'class'
         self.skip_trivia();
-        let name = self.parse_ident()?;
+        let name = self.parse_binding_ident()?;
         self.skip_trivia();
         let super_class = self.parse_class_extends()?;
         self.expect_op(OperatorEnum::LBrace)?;
         let body = self.parse_class_body()?;
         self.expect_op(OperatorEnum::RBrace)?;
-        Ok(Stmt::Class { name, super_class, body })
+        Ok(Stmt::Class { name, decorators: vec![], super_class, body, span: self.span_from(start) })
+    }
+
+    /// Pripoji dekoratory pred `class` / `export class` / `export default class`
+    /// k deklarovane tride. Dekoratory smi byt jen na jednom z obou mist.
+    fn decorate_class_stmt(&self, stmt: Stmt, new: Vec<Expr>) -> Result<Stmt, ParseError> {
+        let misplaced = || self.err("Dekorator lze pouzit jen pred tridou nebo jejim clenem");
+        let twice = || self.err("Dekoratory nelze uvest pred 'export' i za nim");
+        Ok(match stmt {
+            Stmt::Class { decorators, .. } if !decorators.is_empty() => return Err(twice()),
+            Stmt::Class { name, super_class, body, span, .. } => Stmt::Class { name, decorators: new, super_class, body, span },
+            Stmt::WithLine { line, span, inner } => {
+                Stmt::WithLine { line, span, inner: Box::new(self.decorate_class_stmt(*inner, new)?) }
+            }
+            Stmt::Export(ExportKind::Decl(decl), span) => {
+                Stmt::Export(ExportKind::Decl(Box::new(self.decorate_class_stmt(*decl, new)?)), span)
+            }
+            Stmt::Export(ExportKind::Default(Expr::ClassExpr { decorators, .. }), _) if !decorators.is_empty() => {
+                return Err(twice());
+            }
+            Stmt::Export(ExportKind::Default(Expr::ClassExpr { name, super_class, body, span: class_span, .. }), span) => {
+                Stmt::Export(ExportKind::Default(Expr::ClassExpr { name, decorators: new, super_class, body, span: class_span }), span)
+            }
+            _ => return Err(misplaced()),
+        })
+    }
+
+    /// Parsuje seznam dekoratoru `@dec` pred tridou nebo clenem tridy:
+    /// `@name`, `@a.b.#c`, `@a.b(args)` nebo `@(libovolny vyraz)`.
+    fn parse_decorators(&mut self) -> Result<Vec<Expr>, ParseError> {
+        let mut decorators = Vec::new();
+        while self.eat_op(OperatorEnum::At) {
+            self.skip_trivia();
+            if self.eat_op(OperatorEnum::LParen) {
+                decorators.push(self.parse_expr()?);
+                self.expect_op(OperatorEnum::RParen)?;
+                continue;
+            }
+            let start = self.start_pos();
+            let mut dec = Expr::Ident(self.parse_ident()?, self.span_from(start));
+            while self.eat_op(OperatorEnum::Dot) {
+                self.skip_trivia();
+                let prop = match self.kind().clone() {
+                    TokenKind::PrivateName(n) => { self.advance(); MemberProp::Private(n) }
+                    _ => MemberProp::Ident(self.parse_ident()?),
+                };
+                dec = Expr::Member { object: Box::new(dec), prop, optional: false, span: self.span_from(start) };
+            }
+            if self.eat_op(OperatorEnum::LParen) {
+                let args = self.parse_call_args()?;
+                self.expect_op(OperatorEnum::RParen)?;
+                dec = Expr::Call { callee: Box::new(dec), args, optional: false, span: self.span_from(start) };
+            }
+            decorators.push(dec);
+        }
+        Ok(decorators)
     }
 
     /// Parsuje `(extends Expr)?` - volitelny rodic tridy.
@@ -582,8 +814,17 @@ impl Parser {
 
     /// Parsuje telo tridy `{ member* }` (bez svorek).
     ///
-    /// Kazdy clen je: `static? (get|set)? name(params) { body }`
+    /// Kazdy clen je: `@dec* static? (get|set|accessor)? (name|#name|[expr]) (params) { body }`,
+    /// pole `@dec* static? #?name (= expr)?;` nebo staticky blok `static { ... }`.
     fn parse_class_body(&mut self) -> Result<Vec<ClassMember>, ParseError> {
+        // Telo tridy je vzdy strict kod
+        let outer = std::mem::replace(&mut self.strict, true);
+        let members = self.parse_class_members();
+        self.strict = outer;
+        members
+    }
+
+    fn parse_class_members(&mut self) -> Result<Vec<ClassMember>, ParseError> {
         let mut members = Vec::new();
         loop {
             self.skip_trivia();
@@ -592,32 +833,68 @@ impl Parser {
             if matches!(self.kind(), TokenKind::Operator(OperatorEnum::RBrace) | TokenKind::Eof) {
                 break;
             }
+            let decorators = self.parse_decorators()?;
+            self.skip_trivia();
 
-            // static keyword - pouze kdyz nasledujici token neni `(`
-            // (jinak je to metoda pojmenovana "static")
-            let is_static = if matches!(self.kind(), TokenKind::Keyword(KeywordEnum::Static))
-                && !matches!(self.peek_kind_ahead(1), TokenKind::Operator(OperatorEnum::LParen))
+            // Modifikator (static/get/set/accessor) plati jen kdyz za nim nasleduje
+            // jmeno clenu - `static() {}` nebo `get = 1` jsou cleny s timto jmenem.
+            let is_modifier = |p: &Self| !matches!(p.peek_non_trivia(),
+                TokenKind::Operator(OperatorEnum::LParen | OperatorEnum::Assign
+                    | OperatorEnum::Semi | OperatorEnum::RBrace));
+
+            // static { ... } - staticky inicializacni blok
+            if matches!(self.kind(), TokenKind::Keyword(KeywordEnum::Static))
+                && matches!(self.peek_non_trivia(), TokenKind::Operator(OperatorEnum::LBrace))
             {
+                if !decorators.is_empty() {
+                    return Err(self.err("Staticky blok nemuze mit dekorator"));
+                }
+                self.advance();
+                let body = self.parse_fn_body(&[])?;
+                members.push(ClassMember {
+                    name: String::new(), params: vec![], body, is_static: true,
+                    is_getter: false, is_setter: false, computed: None,
+                    kind: ClassMemberKind::StaticBlock, is_private: false, value: None,
+                    decorators,
+                });
+                continue;
+            }
+
+            let is_static = if matches!(self.kind(), TokenKind::Keyword(KeywordEnum::Static)) && is_modifier(self) {
                 self.advance(); self.skip_trivia(); true
             } else { false };
 
-            // getter / setter - get/set keyword kde nasleduje jmeno (ne "(")
-            let (is_getter, is_setter) = match self.kind() {
-                TokenKind::Keyword(KeywordEnum::Get)
-                    if !matches!(self.peek_kind_ahead(1), TokenKind::Operator(OperatorEnum::LParen)) =>
-                {
-                    self.advance(); self.skip_trivia(); (true, false)
+            // getter / setter / accessor - kde nasleduje jmeno (ne "(" ani "=")
+            let (is_getter, is_setter, is_accessor) = match self.kind() {
+                TokenKind::Keyword(KeywordEnum::Get) if is_modifier(self) => {
+                    self.advance(); self.skip_trivia(); (true, false, false)
+                }
+                TokenKind::Keyword(KeywordEnum::Set) if is_modifier(self) => {
+                    self.advance(); self.skip_trivia(); (false, true, false)
                 }
-                TokenKind::Keyword(KeywordEnum::Set)
-                    if !matches!(self.peek_kind_ahead(1), TokenKind::Operator(OperatorEnum::LParen)) =>
-                {
-                    self.advance(); self.skip_trivia(); (false, true)
+                TokenKind::Identifier(s) if s == "accessor" && is_modifier(self) => {
+                    self.advance(); self.skip_trivia(); (false, false, true)
                 }
-                _ => (false, false),
+                _ => (false, false, false),
             };
 
-            // Jmeno metody
+            // Jmeno clenu
+            let mut computed = None;
+            let mut is_private = false;
             let name = match self.kind().clone() {
+                TokenKind::Operator(OperatorEnum::LBracket) => {
+                    self.advance();
+                    computed = Some(Box::new(self.parse_assign_expr()?));
+                    self.skip_trivia();
+                    self.expect_op(OperatorEnum::RBracket)?;
+                    String::new()
+                }
+                TokenKind::PrivateName(s) => {
+                    if s == "constructor" {
+                        return Err(self.err("Trida nemuze mit privatni clen #constructor"));
+                    }
+                    self.advance(); is_private = true; s
+                }
                 TokenKind::Identifier(s) => { self.advance(); s }
                 TokenKind::Keyword(kw)   => { let s = kw.as_str().to_string(); self.advance(); s }
                 TokenKind::StringLiteral { value, .. } => { self.advance(); value }
@@ -628,9 +905,34 @@ impl Parser {
                 _ => return Err(self.err("Ocekavano jmeno metody v tele tridy")),
             };
 
+            self.skip_trivia();
+            if is_accessor || !matches!(self.kind(), TokenKind::Operator(OperatorEnum::LParen)) {
+                // Pole: `name = expr;` / `name;` (strednik je nepovinny - ASI)
+                if is_getter || is_setter {
+                    return Err(self.err("Ocekavan seznam parametru getteru/setteru"));
+                }
+                let value = if self.eat_op(OperatorEnum::Assign) {
+                    Some(Box::new(self.parse_assign_expr()?))
+                } else { None };
+                self.eat_semi();
+                let kind = if is_accessor { ClassMemberKind::Accessor } else { ClassMemberKind::Field };
+                members.push(ClassMember {
+                    name, params: vec![], body: vec![], is_static, is_getter, is_setter, computed,
+                    kind, is_private, value, decorators,
+                });
+                continue;
+            }
+
             let params = self.parse_params()?;
-            let body   = self.parse_fn_body()?;
-            members.push(ClassMember { name, params, body, is_static, is_getter, is_setter });
+            let body   = self.parse_fn_body(&params)?;
+            let member = ClassMember {
+                name, params, body, is_static, is_getter, is_setter, computed,
+                kind: ClassMemberKind::Method, is_private, value: None, decorators,
+            };
+            if member.is_constructor() && !member.decorators.is_empty() {
+                return Err(self.err("Konstruktor nemuze mit dekorator"));
+            }
+            members.push(member);
         }
         Ok(members)
     }
@@ -682,9 +984,9 @@ impl Parser {
                     if self.eat_op(OperatorEnum::RBrace) { break; }
                     // Rest prop: ...rest
                     if self.eat_op(OperatorEnum::Ellipsis) {
-                        let name = self.parse_ident()?;
+                        let name = self.parse_binding_ident()?;
                         props.push(ObjectPatternProp {
-                            key: PropKey::Ident(name.clone()),
+                            key: PropKey::Spread,
                             pattern: Pattern::Ident(name),
                             default: None,
                             shorthand: false,
@@ -706,6 +1008,7 @@ impl Parser {
                             PropKey::Ident(s) => s.clone(),
                             _ => return Err(self.err("Zkracena forma vyzaduje identifikator")),
                         };
+                        self.check_binding(&name, self.strict)?;
                         (key, Pattern::Ident(name), true)
                     };
                     self.skip_trivia();
@@ -722,7 +1025,7 @@ impl Parser {
             }
 
             // Jednoduchy identifikator
-            _ => Ok(Pattern::Ident(self.parse_ident()?)),
+            _ => Ok(Pattern::Ident(self.parse_binding_ident()?)),
         }
     }
 
@@ -738,14 +1041,37 @@ impl Parser {
         }
     }
 
-    fn parse_fn_body(&mut self) -> Result<Vec<Stmt>, ParseError> {
+    /// Telo funkce `{ ... }`. Vlastni direktiva `"use strict"` plati i zpetne
+    /// pro parametry; po telu se obnovi rezim okolniho kodu.
+    fn parse_fn_body(&mut self, params: &[Param]) -> Result<Vec<Stmt>, ParseError> {
+        let outer = self.strict;
         self.expect_op(OperatorEnum::LBrace)?;
-        let body = self.parse_block_body()?;
+        let body = self.parse_body(false);
+        let strict = self.strict;
+        self.strict = outer;
+        let body = body?;
         self.expect_op(OperatorEnum::RBrace)?;
+        if strict && !outer {
+            let simple = params.iter().all(|p| p.default.is_none() && !p.rest && matches!(p.pattern, Pattern::Ident(_)));
+            if !simple {
+                return Err(self.err("Direktivu \"use strict\" nelze pouzit ve funkci s ne-jednoduchymi parametry"));
+            }
+        }
+        if strict {
+            let mut names = Vec::new();
+            for p in params { p.pattern.bound_names(&mut names); }
+            for (i, n) in names.iter().enumerate() {
+                self.check_binding(n, true)?;
+                if names[..i].contains(n) {
+                    return Err(self.err(format!("Duplicitni parametr '{n}' neni ve strict mode povolen")));
+                }
+            }
+        }
         Ok(body)
     }
 
     fn parse_if(&mut self) -> Result<Stmt, ParseError> {
+        let start = self.start_pos();
         self.advance();
         self.expect_op(OperatorEnum::LParen)?;
         let test = self.parse_expr()?;
@@ -756,19 +1082,21 @@ impl Parser {
             self.advance();
             Some(Box::new(self.parse_stmt()?))
         } else { None };
-        Ok(Stmt::If { test, yes: Box::new(yes), no })
+        Ok(Stmt::If { test, yes: Box::new(yes), no, span: self.span_from(start) })
     }
 
     fn parse_while(&mut self) -> Result<Stmt, ParseError> {
+        let start = self.start_pos();
         self.advance();
         self.expect_op(OperatorEnum::LParen)?;
         let test = self.parse_expr()?;
         self.expect_op(OperatorEnum::RParen)?;
         let body = self.parse_stmt()?;
-        Ok(Stmt::While { test, body: Box::new(body) })
+        Ok(Stmt::While { test, body: Box::new(body), span: self.span_from(start) })
     }
 
     fn parse_do_while(&mut self) -> Result<Stmt, ParseError> {
+        let start = self.start_pos();
         self.advance();
         let body = self.parse_stmt()?;
         self.expect_kw(KeywordEnum::While)?;
@@ -776,10 +1104,11 @@ impl Parser {
         let test = self.parse_expr()?;
         self.expect_op(OperatorEnum::RParen)?;
         self.eat_semi();
-        Ok(Stmt::DoWhile { body: Box::new(body), test })
+        Ok(Stmt::DoWhile { body: Box::new(body), test, span: self.span_from(start) })
     }
 
     fn parse_for(&mut self) -> Result<Stmt, ParseError> {
+        let start = self.start_pos();
         self.advance();
         self.skip_trivia();
         // `for await (...)` - async iterace
@@ -791,35 +1120,40 @@ impl Parser {
         let is_var_kw = matches!(self.kind(),
             TokenKind::Keyword(KeywordEnum::Let)
             | TokenKind::Keyword(KeywordEnum::Const)
-            | TokenKind::Keyword(KeywordEnum::Var));
+            | TokenKind::Keyword(KeywordEnum::Var)) || self.at_using_decl().is_some();
 
         if is_var_kw {
-            let kind = match self.kind().clone() {
-                TokenKind::Keyword(KeywordEnum::Let)   => { self.advance(); VarKind::Let }
-                TokenKind::Keyword(KeywordEnum::Const) => { self.advance(); VarKind::Const }
-                _                                       => { self.advance(); VarKind::Var }
-            };
+            let kind = self.parse_var_kind();
             self.skip_trivia();
+            let pattern_start = self.start_pos();
             let pattern = self.parse_pattern()?;
+            let pattern_span = self.span_from(pattern_start);
             self.skip_trivia();
+            // `using` v hlavicce cyklu jen jako `for (using x of ...)`
+            if kind.is_using() && !matches!(self.kind(), TokenKind::Keyword(KeywordEnum::Of)) {
+                return Err(self.err("Deklarace `using` v hlavicce cyklu je povolena jen ve for-of"));
+            }
 
             // for...of (vcetne destrukturovani: for (const [k, v] of ...))
             if matches!(self.kind(), TokenKind::Keyword(KeywordEnum::Of)) {
                 self.advance();
                 let iter = self.parse_assign_expr()?;
                 self.expect_op(OperatorEnum::RParen)?;
-                let target = pattern_to_expr(pattern);
+                let mut target = pattern_to_expr(pattern);
+                target.set_span(pattern_span);
                 if is_await {
                     return Ok(Stmt::ForAwaitOf {
                         kind: Some(kind),
                         target: Box::new(target),
                         iter, body: Box::new(self.parse_stmt()?),
+                        span: self.span_from(start),
                     });
                 }
                 return Ok(Stmt::ForOf {
                     kind: Some(kind),
                     target: Box::new(target),
                     iter, body: Box::new(self.parse_stmt()?),
+                    span: self.span_from(start),
                 });
             }
             // for...in
@@ -827,11 +1161,13 @@ impl Parser {
                 self.advance();
                 let iter = self.parse_expr()?;
                 self.expect_op(OperatorEnum::RParen)?;
-                let target = pattern_to_expr(pattern);
+                let mut target = pattern_to_expr(pattern);
+                target.set_span(pattern_span);
                 return Ok(Stmt::ForIn {
                     kind: Some(kind),
                     target: Box::new(target),
                     iter, body: Box::new(self.parse_stmt()?),
+                    span: self.span_from(start),
                 });
             }
             // for (let i = 0; i < n; i++) - pattern musi byt jednoduchy ident
@@ -862,7 +1198,7 @@ impl Parser {
             let update = if matches!(self.kind(), TokenKind::Operator(OperatorEnum::RParen)) { None }
             else { Some(self.parse_expr()?) };
             self.expect_op(OperatorEnum::RParen)?;
-            return Ok(Stmt::For { init, test, update, body: Box::new(self.parse_stmt()?) });
+            return Ok(Stmt::For { init, test, update, body: Box::new(self.parse_stmt()?), span: self.span_from(start) });
         }
 
         // for (expr; ...)
@@ -875,10 +1211,11 @@ impl Parser {
         let update = if matches!(self.kind(), TokenKind::Operator(OperatorEnum::RParen)) { None }
         else { Some(self.parse_expr()?) };
         self.expect_op(OperatorEnum::RParen)?;
-        Ok(Stmt::For { init, test, update, body: Box::new(self.parse_stmt()?) })
+        Ok(Stmt::For { init, test, update, body: Box::new(self.parse_stmt()?), span: self.span_from(start) })
     }
 
     fn parse_try(&mut self) -> Result<Stmt, ParseError> {
+        let start = self.start_pos();
         self.advance();
         self.expect_op(OperatorEnum::LBrace)?;
         let body = self.parse_block_body()?;
@@ -888,7 +1225,7 @@ impl Parser {
         let catch = if matches!(self.kind(), TokenKind::Keyword(KeywordEnum::Catch)) {
             self.advance();
             let param = if self.eat_op(OperatorEnum::LParen) {
-                let p = self.parse_ident()?;
+                let p = self.parse_binding_ident()?;
                 self.expect_op(OperatorEnum::RParen)?;
                 Some(p)
             } else { None };
@@ -907,10 +1244,11 @@ impl Parser {
             Some(fb)
         } else { None };
 
-        Ok(Stmt::Try { body, catch, finally })
+        Ok(Stmt::Try { body, catch, finally, span: self.span_from(start) })
     }
 
     fn parse_switch(&mut self) -> Result<Stmt, ParseError> {
+        let start = self.start_pos();
         self.advance(); // 'switch'
         self.skip_trivia();
         self.expect_op(OperatorEnum::LParen)?;
@@ -946,7 +1284,7 @@ impl Parser {
         }
 
         self.expect_op(OperatorEnum::RBrace)?;
-        Ok(Stmt::Switch { discriminant, cases })
+        Ok(Stmt::Switch { discriminant, cases, span: self.span_from(start) })
     }
 
     /// Parsuje prikazy tela jedne case/default vetve.
@@ -970,15 +1308,17 @@ impl Parser {
 
     fn parse_expr(&mut self) -> Result<Expr, ParseError> {
         self.skip_trivia();
+        let start = self.start_pos();
         let mut exprs = vec![self.parse_assign_expr()?];
         while self.eat_op(OperatorEnum::Comma) {
             exprs.push(self.parse_assign_expr()?);
         }
-        if exprs.len() == 1 { Ok(exprs.remove(0)) } else { Ok(Expr::Sequence(exprs)) }
+        if exprs.len() == 1 { Ok(exprs.remove(0)) } else { Ok(Expr::Sequence(exprs, self.span_from(start))) }
     }
 
     fn parse_assign_expr(&mut self) -> Result<Expr, ParseError> {
         self.skip_trivia();
+        let start = self.start_pos();
 
         // Detekce arrow funkce: ident => nebo () =>
         if self.is_arrow() {
@@ -1007,15 +1347,20 @@ impl Parser {
             _ => None,
         };
         if let Some(aop) = op {
+            if self.strict && let Expr::Ident(n, _) = &left && matches!(n.as_str(), "eval" | "arguments") {
+                return Err(self.err(format!("Prirazeni do '{n}' neni ve strict mode povoleno")));
+            }
             self.advance();
             let right = self.parse_assign_expr()?;
-            return Ok(Expr::Assign { op: aop, target: Box::new(left), value: Box::new(right) });
+            return Ok(Expr::Assign { op: aop, target: Box::new(left), value: Box::new(right), span: self.span_from(start) });
         }
         Ok(left)
     }
 
     /// Ternární výraz: expr ? yes : no
     fn parse_ternary(&mut self) -> Result<Expr, ParseError> {
+        self.skip_trivia();
+        let start = self.start_pos();
         let expr = self.parse_pratt(0)?;
         self.skip_trivia();
         if matches!(self.kind(), TokenKind::Operator(OperatorEnum::Question)) {
@@ -1023,7 +1368,7 @@ impl Parser {
             let yes = self.parse_assign_expr()?;
             self.expect_op(OperatorEnum::Colon)?;
             let no = self.parse_assign_expr()?;
-            Ok(Expr::Ternary { test: Box::new(expr), yes: Box::new(yes), no: Box::new(no) })
+            Ok(Expr::Ternary { test: Box::new(expr), yes: Box::new(yes), no: Box::new(no), span: self.span_from(start) })
         } else {
             Ok(expr)
         }
@@ -1032,7 +1377,20 @@ impl Parser {
     /// Pratt parser pro binární výrazy.
     fn parse_pratt(&mut self, min_bp: u8) -> Result<Expr, ParseError> {
         self.skip_trivia();
-        let mut left = self.parse_unary()?;
+        let start = self.start_pos();
+        let mut left = match self.kind().clone() {
+            // `#x in obj` - privatni jmeno smi stat samo jen jako levy operand `in`
+            TokenKind::PrivateName(name) => {
+                self.advance(); self.skip_trivia();
+                if !matches!(self.kind(), TokenKind::Keyword(KeywordEnum::In)) {
+                    return Err(self.err(format!("Neocekavane privatni jmeno #{name}")));
+                }
+                self.advance();
+                let object = self.parse_pratt(19)?;
+                Expr::PrivateIn { name, object: Box::new(object), span: self.span_from(start) }
+            }
+            _ => self.parse_unary()?,
+        };
 
         loop {
             self.skip_trivia();
@@ -1073,31 +1431,31 @@ impl Parser {
             let right = self.parse_pratt(rbp)?;
 
             left = match &tok.kind {
-                TokenKind::Operator(OperatorEnum::Or)       => Expr::Logical { op: LogicalOp::Or,       left: Box::new(left), right: Box::new(right) },
-                TokenKind::Operator(OperatorEnum::And)      => Expr::Logical { op: LogicalOp::And,      left: Box::new(left), right: Box::new(right) },
-                TokenKind::Operator(OperatorEnum::NullCoal) => Expr::Logical { op: LogicalOp::NullCoal, left: Box::new(left), right: Box::new(right) },
-                TokenKind::Operator(OperatorEnum::EqEq)        => Expr::Binary { op: BinaryOp::Eq,          left: Box::new(left), right: Box::new(right) },
-                TokenKind::Operator(OperatorEnum::NotEq)       => Expr::Binary { op: BinaryOp::NotEq,       left: Box::new(left), right: Box::new(right) },
-                TokenKind::Operator(OperatorEnum::StrictEqual)    => Expr::Binary { op: BinaryOp::StrictEq,    left: Box::new(left), right: Box::new(right) },
-                TokenKind::Operator(OperatorEnum::StrictNotEqual) => Expr::Binary { op: BinaryOp::StrictNotEq, left: Box::new(left), right: Box::new(right) },
-                TokenKind::Operator(OperatorEnum::Lt)          => Expr::Binary { op: BinaryOp::Lt,          left: Box::new(left), right: Box::new(right) },
-                TokenKind::Operator(OperatorEnum::Gt)          => Expr::Binary { op: BinaryOp::Gt,          left: Box::new(left), right: Box::new(right) },
-                TokenKind::Operator(OperatorEnum::LtEq)        => Expr::Binary { op: BinaryOp::LtEq,        left: Box::new(left), right: Box::new(right) },
-                TokenKind::Operator(OperatorEnum::GtEq)        => Expr::Binary { op: BinaryOp::GtEq,        left: Box::new(left), right: Box::new(right) },
-                TokenKind::Keyword(KeywordEnum::In)            => Expr::Binary { op: BinaryOp::In,          left: Box::new(left), right: Box::new(right) },
-                TokenKind::Keyword(KeywordEnum::Instanceof)    => Expr::Binary { op: BinaryOp::Instanceof,  left: Box::new(left), right: Box::new(right) },
-                TokenKind::Operator(OperatorEnum::Shl)         => Expr::Binary { op: BinaryOp::Shl,         left: Box::new(left), right: Box::new(right) },
-                TokenKind::Operator(OperatorEnum::Shr)         => Expr::Binary { op: BinaryOp::Shr,         left: Box::new(left), right: Box::new(right) },
-                TokenKind::Operator(OperatorEnum::ShiftRightU) => Expr::Binary { op: BinaryOp::Ushr,        left: Box::new(left), right: Box::new(right) },
-                TokenKind::Operator(OperatorEnum::Pipe)        => Expr::Binary { op: BinaryOp::BitOr,       left: Box::new(left), right: Box::new(right) },
-                TokenKind::Operator(OperatorEnum::Caret)       => Expr::Binary { op: BinaryOp::BitXor,      left: Box::new(left), right: Box::new(right) },
-                TokenKind::Operator(OperatorEnum::Amp)         => Expr::Binary { op: BinaryOp::BitAnd,      left: Box::new(left), right: Box::new(right) },
-                TokenKind::Operator(OperatorEnum::Plus)        => Expr::Binary { op: BinaryOp::Add,         left: Box::new(left), right: Box::new(right) },
-                TokenKind::Operator(OperatorEnum::Minus)       => Expr::Binary { op: BinaryOp::Sub,         left: Box::new(left), right: Box::new(right) },
-                TokenKind::Operator(OperatorEnum::Star)        => Expr::Binary { op: BinaryOp::Mul,         left: Box::new(left), right: Box::new(right) },
-                TokenKind::Operator(OperatorEnum::Slash)       => Expr::Binary { op: BinaryOp::Div,         left: Box::new(left), right: Box::new(right) },
-                TokenKind::Operator(OperatorEnum::Percent)     => Expr::Binary { op: BinaryOp::Mod,         left: Box::new(left), right: Box::new(right) },
-                TokenKind::Operator(OperatorEnum::Exp)         => Expr::Binary { op: BinaryOp::Exp,         left: Box::new(left), right: Box::new(right) },
+                TokenKind::Operator(OperatorEnum::Or)       => Expr::Logical { op: LogicalOp::Or,       left: Box::new(left), right: Box::new(right), span: self.span_from(start) },
+                TokenKind::Operator(OperatorEnum::And)      => Expr::Logical { op: LogicalOp::And,      left: Box::new(left), right: Box::new(right), span: self.span_from(start) },
+                TokenKind::Operator(OperatorEnum::NullCoal) => Expr::Logical { op: LogicalOp::NullCoal, left: Box::new(left), right: Box::new(right), span: self.span_from(start) },
+                TokenKind::Operator(OperatorEnum::EqEq)        => Expr::Binary { op: BinaryOp::Eq,          left: Box::new(left), right: Box::new(right), span: self.span_from(start) },
+                TokenKind::Operator(OperatorEnum::NotEq)       => Expr::Binary { op: BinaryOp::NotEq,       left: Box::new(left), right: Box::new(right), span: self.span_from(start) },
+                TokenKind::Operator(OperatorEnum::StrictEqual)    => Expr::Binary { op: BinaryOp::StrictEq,    left: Box::new(left), right: Box::new(right), span: self.span_from(start) },
+                TokenKind::Operator(OperatorEnum::StrictNotEqual) => Expr::Binary { op: BinaryOp::StrictNotEq, left: Box::new(left), right: Box::new(right), span: self.span_from(start) },
+                TokenKind::Operator(OperatorEnum::Lt)          => Expr::Binary { op: BinaryOp::Lt,          left: Box::new(left), right: Box::new(right), span: self.span_from(start) },
+                TokenKind::Operator(OperatorEnum::Gt)          => Expr::Binary { op: BinaryOp::Gt,          left: Box::new(left), right: Box::new(right), span: self.span_from(start) },
+                TokenKind::Operator(OperatorEnum::LtEq)        => Expr::Binary { op: BinaryOp::LtEq,        left: Box::new(left), right: Box::new(right), span: self.span_from(start) },
+                TokenKind::Operator(OperatorEnum::GtEq)        => Expr::Binary { op: BinaryOp::GtEq,        left: Box::new(left), right: Box::new(right), span: self.span_from(start) },
+                TokenKind::Keyword(KeywordEnum::In)            => Expr::Binary { op: BinaryOp::In,          left: Box::new(left), right: Box::new(right), span: self.span_from(start) },
+                TokenKind::Keyword(KeywordEnum::Instanceof)    => Expr::Binary { op: BinaryOp::Instanceof,  left: Box::new(left), right: Box::new(right), span: self.span_from(start) },
+                TokenKind::Operator(OperatorEnum::Shl)         => Expr::Binary { op: BinaryOp::Shl,         left: Box::new(left), right: Box::new(right), span: self.span_from(start) },
+                TokenKind::Operator(OperatorEnum::Shr)         => Expr::Binary { op: BinaryOp::Shr,         left: Box::new(left), right: Box::new(right), span: self.span_from(start) },
+                TokenKind::Operator(OperatorEnum::ShiftRightU) => Expr::Binary { op: BinaryOp::Ushr,        left: Box::new(left), right: Box::new(right), span: self.span_from(start) },
+                TokenKind::Operator(OperatorEnum::Pipe)        => Expr::Binary { op: BinaryOp::BitOr,       left: Box::new(left), right: Box::new(right), span: self.span_from(start) },
+                TokenKind::Operator(OperatorEnum::Caret)       => Expr::Binary { op: BinaryOp::BitXor,      left: Box::new(left), right: Box::new(right), span: self.span_from(start) },
+                TokenKind::Operator(OperatorEnum::Amp)         => Expr::Binary { op: BinaryOp::BitAnd,      left: Box::new(left), right: Box::new(right), span: self.span_from(start) },
+                TokenKind::Operator(OperatorEnum::Plus)        => Expr::Binary { op: BinaryOp::Add,         left: Box::new(left), right: Box::new(right), span: self.span_from(start) },
+                TokenKind::Operator(OperatorEnum::Minus)       => Expr::Binary { op: BinaryOp::Sub,         left: Box::new(left), right: Box::new(right), span: self.span_from(start) },
+                TokenKind::Operator(OperatorEnum::Star)        => Expr::Binary { op: BinaryOp::Mul,         left: Box::new(left), right: Box::new(right), span: self.span_from(start) },
+                TokenKind::Operator(OperatorEnum::Slash)       => Expr::Binary { op: BinaryOp::Div,         left: Box::new(left), right: Box::new(right), span: self.span_from(start) },
+                TokenKind::Operator(OperatorEnum::Percent)     => Expr::Binary { op: BinaryOp::Mod,         left: Box::new(left), right: Box::new(right), span: self.span_from(start) },
+                TokenKind::Operator(OperatorEnum::Exp)         => Expr::Binary { op: BinaryOp::Exp,         left: Box::new(left), right: Box::new(right), span: self.span_from(start) },
                 _ => unreachable!(),
             };
         }
@@ -1107,45 +1465,53 @@ impl Parser {
 
     fn parse_unary(&mut self) -> Result<Expr, ParseError> {
         self.skip_trivia();
+        let start = self.start_pos();
         match self.kind().clone() {
-            TokenKind::Operator(OperatorEnum::Bang)       => { self.advance(); Ok(Expr::Unary { op: UnaryOp::Not,    arg: Box::new(self.parse_unary()?) }) }
-            TokenKind::Operator(OperatorEnum::Minus)      => { self.advance(); Ok(Expr::Unary { op: UnaryOp::Minus,  arg: Box::new(self.parse_unary()?) }) }
-            TokenKind::Operator(OperatorEnum::Plus)       => { self.advance(); Ok(Expr::Unary { op: UnaryOp::Plus,   arg: Box::new(self.parse_unary()?) }) }
-            TokenKind::Operator(OperatorEnum::Tilde)      => { self.advance(); Ok(Expr::Unary { op: UnaryOp::BitNot, arg: Box::new(self.parse_unary()?) }) }
-            TokenKind::Keyword(KeywordEnum::Typeof)       => { self.advance(); Ok(Expr::Unary { op: UnaryOp::Typeof, arg: Box::new(self.parse_unary()?) }) }
-            TokenKind::Keyword(KeywordEnum::Void)         => { self.advance(); Ok(Expr::Unary { op: UnaryOp::Void,   arg: Box::new(self.parse_unary()?) }) }
-            TokenKind::Keyword(KeywordEnum::Delete)       => { self.advance(); Ok(Expr::Unary { op: UnaryOp::Delete, arg: Box::new(self.parse_unary()?) }) }
-            TokenKind::Operator(OperatorEnum::PlusPlus)   => { self.advance(); Ok(Expr::Unary { op: UnaryOp::PreInc, arg: Box::new(self.parse_unary()?) }) }
-            TokenKind::Operator(OperatorEnum::MinusMinus) => { self.advance(); Ok(Expr::Unary { op: UnaryOp::PreDec, arg: Box::new(self.parse_unary()?) }) }
+            TokenKind::Operator(OperatorEnum::Bang)       => { self.advance(); Ok(Expr::Unary { op: UnaryOp::Not,    arg: Box::new(self.parse_unary()?), span: self.span_from(start) }) }
+            TokenKind::Operator(OperatorEnum::Minus)      => { self.advance(); Ok(Expr::Unary { op: UnaryOp::Minus,  arg: Box::new(self.parse_unary()?), span: self.span_from(start) }) }
+            TokenKind::Operator(OperatorEnum::Plus)       => { self.advance(); Ok(Expr::Unary { op: UnaryOp::Plus,   arg: Box::new(self.parse_unary()?), span: self.span_from(start) }) }
+            TokenKind::Operator(OperatorEnum::Tilde)      => { self.advance(); Ok(Expr::Unary { op: UnaryOp::BitNot, arg: Box::new(self.parse_unary()?), span: self.span_from(start) }) }
+            TokenKind::Keyword(KeywordEnum::Typeof)       => { self.advance(); Ok(Expr::Unary { op: UnaryOp::Typeof, arg: Box::new(self.parse_unary()?), span: self.span_from(start) }) }
+            TokenKind::Keyword(KeywordEnum::Void)         => { self.advance(); Ok(Expr::Unary { op: UnaryOp::Void,   arg: Box::new(self.parse_unary()?), span: self.span_from(start) }) }
+            TokenKind::Keyword(KeywordEnum::Delete)       => {
+                self.advance();
+                let arg = self.parse_unary()?;
+                if matches!(&arg, Expr::Member { prop: MemberProp::Private(_), .. }) {
+                    return Err(self.err("Privatni clen tridy nelze smazat"));
+                }
+                if self.strict && matches!(arg, Expr::Ident(_, _)) {
+                    return Err(self.err("Smazani neoznaceneho identifikatoru neni ve strict mode povoleno"));
+                }
+                Ok(Expr::Unary { op: UnaryOp::Delete, arg: Box::new(arg), span: self.span_from(start) })
+            }
+            TokenKind::Operator(OperatorEnum::PlusPlus)   => { self.advance(); Ok(Expr::Unary { op: UnaryOp::PreInc, arg: Box::new(self.parse_unary()?), span: self.span_from(start) }) }
+            TokenKind::Operator(OperatorEnum::MinusMinus) => { self.advance(); Ok(Expr::Unary { op: UnaryOp::PreDec, arg: Box::new(self.parse_unary()?), span: self.span_from(start) }) }
             _ => self.parse_postfix(),
         }
     }
 
     fn parse_postfix(&mut self) -> Result<Expr, ParseError> {
+        let start = self.start_pos();
         let mut expr = self.parse_primary()?;
         loop {
             self.skip_trivia();
             match self.kind().clone() {
                 TokenKind::Operator(OperatorEnum::Dot) => {
                     self.advance(); self.skip_trivia();
-                    let name = match self.kind().clone() {
-                        TokenKind::Identifier(s) => { self.advance(); s }
-                        TokenKind::Keyword(kw)   => { let s = kw.as_str().to_string(); self.advance(); s }
-                        _ => return Err(self.err("Ocekavano jmeno vlastnosti za teckou")),
-                    };
-                    expr = Expr::Member { object: Box::new(expr), prop: MemberProp::Ident(name), optional: false };
+                    let prop = self.parse_member_prop("Ocekavano jmeno vlastnosti za teckou")?;
+                    expr = Expr::Member { object: Box::new(expr), prop, optional: false, span: self.span_from(start) };
                 }
                 TokenKind::Operator(OperatorEnum::LBracket) => {
                     self.advance();
                     let idx = self.parse_expr()?;
                     self.expect_op(OperatorEnum::RBracket)?;
-                    expr = Expr::Member { object: Box::new(expr), prop: MemberProp::Computed(Box::new(idx)), optional: false };
+                    expr = Expr::Member { object: Box::new(expr), prop: MemberProp::Computed(Box::new(idx)), optional: false, span: self.span_from(start) };
                 }
                 TokenKind::Operator(OperatorEnum::LParen) => {
                     self.advance();
                     let args = self.parse_call_args()?;
                     self.expect_op(OperatorEnum::RParen)?;
-                    expr = Expr::Call { callee: Box::new(expr), args, optional: false };
+                    expr = Expr::Call { callee: Box::new(expr), args, optional: false, span: self.span_from(start) };
                 }
                 // Optional chaining: obj?.prop  obj?.[expr]  obj?.()
                 TokenKind::Operator(OperatorEnum::OptChain) => {
@@ -1155,31 +1521,33 @@ impl Parser {
                             self.advance();
                             let idx = self.parse_expr()?;
                             self.expect_op(OperatorEnum::RBracket)?;
-                            Expr::Member { object: Box::new(expr), prop: MemberProp::Computed(Box::new(idx)), optional: true }
+                            Expr::Member { object: Box::new(expr), prop: MemberProp::Computed(Box::new(idx)), optional: true, span: self.span_from(start) }
                         }
                         TokenKind::Operator(OperatorEnum::LParen) => {
                             self.advance();
                             let args = self.parse_call_args()?;
                             self.expect_op(OperatorEnum::RParen)?;
-                            Expr::Call { callee: Box::new(expr), args, optional: true }
+                            Expr::Call { callee: Box::new(expr), args, optional: true, span: self.span_from(start) }
                         }
                         _ => {
-                            let name = match self.kind().clone() {
-                                TokenKind::Identifier(s) => { self.advance(); s }
-                                TokenKind::Keyword(kw)   => { let s = kw.as_str().to_string(); self.advance(); s }
-                                _ => return Err(self.err("Ocekavano jmeno vlastnosti za ?.")),
-                            };
-                            Expr::Member { object: Box::new(expr), prop: MemberProp::Ident(name), optional: true }
+                            let prop = self.parse_member_prop("Ocekavano jmeno vlastnosti za ?.")?;
+                            Expr::Member { object: Box::new(expr), prop, optional: true, span: self.span_from(start) }
                         }
                     };
                 }
                 TokenKind::Operator(OperatorEnum::PlusPlus) => {
                     self.advance();
-                    expr = Expr::Binary { op: BinaryOp::PostInc, left: Box::new(expr), right: Box::new(Expr::Undefined) };
+                    expr = Expr::Binary { op: BinaryOp::PostInc, left: Box::new(expr), right: Box::new(Expr::Undefined(SourceSpan::default())), span: self.span_from(start) };
                 }
                 TokenKind::Operator(OperatorEnum::MinusMinus) => {
                     self.advance();
-                    expr = Expr::Binary { op: BinaryOp::PostDec, left: Box::new(expr), right: Box::new(Expr::Undefined) };
+                    expr = Expr::Binary { op: BinaryOp::PostDec, left: Box::new(expr), right: Box::new(Expr::Undefined(SourceSpan::default())), span: self.span_from(start) };
+                }
+                // Tagged template: tag`a${x}b`
+                TokenKind::NoSubstitutionTemplate(_) | TokenKind::TemplateHead(_) => {
+                    let (cooked, raw, expressions) = self.parse_template_parts()?;
+                    let site = TemplateSite::default();
+                    expr = Expr::TaggedTemplate { tag: Box::new(expr), cooked, raw, expressions, site, span: self.span_from(start) };
                 }
                 _ => break,
             }
@@ -1187,42 +1555,60 @@ impl Parser {
         Ok(expr)
     }
 
+    /// Jmeno vlastnosti za `.` / `?.`: identifikator, klicove slovo nebo `#privatni`.
+    fn parse_member_prop(&mut self, msg: &str) -> Result<MemberProp, ParseError> {
+        match self.kind().clone() {
+            TokenKind::Identifier(s)  => { self.advance(); Ok(MemberProp::Ident(s)) }
+            TokenKind::Keyword(kw)    => { let s = kw.as_str().to_string(); self.advance(); Ok(MemberProp::Ident(s)) }
+            TokenKind::PrivateName(s) => { self.advance(); Ok(MemberProp::Private(s)) }
+            _ => Err(self.err(msg)),
+        }
+    }
+
     fn parse_primary(&mut self) -> Result<Expr, ParseError> {
         self.skip_trivia();
+        let start = self.start_pos();
         match self.kind().clone() {
             TokenKind::NumericLiteral { value, is_bigint, bigint_value, .. } => {
                 self.advance();
                 if is_bigint {
                     let s = bigint_value.map(|b| b.to_string()).unwrap_or_else(|| "0".into());
-                    Ok(Expr::BigInt(s))
+                    Ok(Expr::BigInt(s, self.span_from(start)))
                 } else {
-                    Ok(Expr::Number(value))
+                    Ok(Expr::Number(value, self.span_from(start)))
                 }
             }
 
-            TokenKind::StringLiteral { value, .. } => { let s = value.clone(); self.advance(); Ok(Expr::Str(s)) }
+            TokenKind::StringLiteral { value, .. } => { let s = value.clone(); self.advance(); Ok(Expr::Str(s, self.span_from(start))) }
 
-            TokenKind::NoSubstitutionTemplate(s) => { let st = s.clone(); self.advance(); Ok(Expr::Str(st)) }
+            TokenKind::NoSubstitutionTemplate(s) => { let st = s.clone(); self.advance(); Ok(Expr::Str(st, self.span_from(start))) }
             TokenKind::TemplateHead(_) => self.parse_template(),
 
-            TokenKind::Keyword(KeywordEnum::True)  => { self.advance(); Ok(Expr::Bool(true)) }
-            TokenKind::Keyword(KeywordEnum::False) => { self.advance(); Ok(Expr::Bool(false)) }
-            TokenKind::Keyword(KeywordEnum::Null)  => { self.advance(); Ok(Expr::Null) }
+            TokenKind::Keyword(KeywordEnum::True)  => { self.advance(); Ok(Expr::Bool(true, self.span_from(start))) }
+            TokenKind::Keyword(KeywordEnum::False) => { self.advance(); Ok(Expr::Bool(false, self.span_from(start))) }
+            TokenKind::Keyword(KeywordEnum::Null)  => { self.advance(); Ok(Expr::Null(self.span_from(start))) }
             TokenKind::Keyword(KeywordEnum::Import) => {
                 // Dynamicky import: `import(specifier)`
                 self.advance();
                 self.skip_trivia();
+                // `import.meta` - jako identifikator (jako `this`/`super`), modul
+                // ho definuje ve svem scopu.
+                if self.eat_op(OperatorEnum::Dot) {
+                    self.skip_trivia();
+                    self.expect_contextual_keyword("meta")?;
+                    return Ok(Expr::Ident("import.meta".to_string(), self.span_from(start)));
+                }
                 self.expect_op(OperatorEnum::LParen)?;
                 let arg = self.parse_assign_expr()?;
                 self.skip_trivia();
                 self.expect_op(OperatorEnum::RParen)?;
-                Ok(Expr::DynamicImport(Box::new(arg)))
+                Ok(Expr::DynamicImport(Box::new(arg), self.span_from(start)))
             }
-            TokenKind::Keyword(KeywordEnum::This)  => { self.advance(); Ok(Expr::Ident("this".to_string())) }
+            TokenKind::Keyword(KeywordEnum::This)  => { self.advance(); Ok(Expr::Ident("this".to_string(), self.span_from(start))) }
             // `super` - jako identifikator, interpreter ho zpracuje specialne
-            TokenKind::Keyword(KeywordEnum::Super) => { self.advance(); Ok(Expr::Ident("super".to_string())) }
+            TokenKind::Keyword(KeywordEnum::Super) => { self.advance(); Ok(Expr::Ident("super".to_string(), self.span_from(start))) }
 
-            TokenKind::Identifier(s) => { let name = s.clone(); self.advance(); Ok(Expr::Ident(name)) }
+            TokenKind::Identifier(s) => { let name = s.clone(); self.advance(); Ok(Expr::Ident(name, self.span_from(start))) }
 
             TokenKind::Operator(OperatorEnum::LParen) => {
                 self.advance(); self.skip_trivia();
@@ -1230,7 +1616,7 @@ impl Parser {
                 if matches!(self.kind(), TokenKind::Operator(OperatorEnum::RParen)) {
                     self.advance(); self.skip_trivia();
                     self.expect_op(OperatorEnum::Arrow)?;
-                    return self.parse_arrow_body(vec![]); // Vec<Param>
+                    return self.parse_arrow_body(vec![], start); // Vec<Param>
                 }
                 let expr = self.parse_expr()?;
                 self.expect_op(OperatorEnum::RParen)?;
@@ -1248,9 +1634,10 @@ impl Parser {
                     }
                     // Spread: ...expr
                     if matches!(self.kind(), TokenKind::Operator(OperatorEnum::Ellipsis)) {
+                        let spread = self.start_pos();
                         self.advance(); self.skip_trivia();
                         let inner = self.parse_assign_expr()?;
-                        items.push(Some(Box::new(Expr::Spread(Box::new(inner)))));
+                        items.push(Some(Box::new(Expr::Spread(Box::new(inner), self.span_from(spread)))));
                     } else {
                         items.push(Some(Box::new(self.parse_assign_expr()?)));
                     }
@@ -1258,7 +1645,7 @@ impl Parser {
                     if !self.eat_op(OperatorEnum::Comma) { break; }
                 }
                 self.expect_op(OperatorEnum::RBracket)?;
-                Ok(Expr::Array(items))
+                Ok(Expr::Array(items, self.span_from(start)))
             }
 
             TokenKind::Operator(OperatorEnum::LBrace) => {
@@ -1272,31 +1659,28 @@ impl Parser {
                     if !self.eat_op(OperatorEnum::Comma) { break; }
                 }
                 self.expect_op(OperatorEnum::RBrace)?;
-                Ok(Expr::Object(props))
+                Ok(Expr::Object(props, self.span_from(start)))
             }
 
             TokenKind::Keyword(KeywordEnum::New) => {
                 self.advance(); self.skip_trivia();
                 // Pro new: parsuj jen member access (tecka, [expr]), NE volani funkce.
                 // "new Foo(args)" musi byt new(Foo)(args), ne new(Foo(args)).
+                let callee_start = self.start_pos();
                 let mut callee = self.parse_primary()?;
                 loop {
                     self.skip_trivia();
                     match self.kind().clone() {
                         TokenKind::Operator(OperatorEnum::Dot) => {
                             self.advance(); self.skip_trivia();
-                            let name = match self.kind().clone() {
-                                TokenKind::Identifier(s) => { self.advance(); s }
-                                TokenKind::Keyword(kw)   => { let s = kw.as_str().to_string(); self.advance(); s }
-                                _ => return Err(self.err("Ocekavano jmeno vlastnosti za teckou v new expr")),
-                            };
-                            callee = Expr::Member { object: Box::new(callee), prop: MemberProp::Ident(name), optional: false };
+                            let prop = self.parse_member_prop("Ocekavano jmeno vlastnosti za teckou v new expr")?;
+                            callee = Expr::Member { object: Box::new(callee), prop, optional: false, span: self.span_from(callee_start) };
                         }
                         TokenKind::Operator(OperatorEnum::LBracket) => {
                             self.advance();
                             let idx = self.parse_expr()?;
                             self.expect_op(OperatorEnum::RBracket)?;
-                            callee = Expr::Member { object: Box::new(callee), prop: MemberProp::Computed(Box::new(idx)), optional: false };
+                            callee = Expr::Member { object: Box::new(callee), prop: MemberProp::Computed(Box::new(idx)), optional: false, span: self.span_from(callee_start) };
                         }
                         _ => break,
                     }
@@ -1307,7 +1691,7 @@ impl Parser {
                     self.expect_op(OperatorEnum::RParen)?;
                     a
                 } else { vec![] };
-                Ok(Expr::New { callee: Box::new(callee), args })
+                Ok(Expr::New { callee: Box::new(callee), args, span: self.span_from(start) })
             }
 
             TokenKind::Keyword(KeywordEnum::Function) => {
@@ -1316,14 +1700,14 @@ impl Parser {
                 let is_gen = self.eat_op(OperatorEnum::Star);
                 self.skip_trivia();
                 let name = if matches!(self.kind(), TokenKind::Identifier(_) | TokenKind::Keyword(_)) {
-                    Some(self.parse_ident()?)
+                    Some(self.parse_binding_ident()?)
                 } else { None };
                 let params = self.parse_params()?;
-                let body = self.parse_fn_body()?;
+                let body = self.parse_fn_body(&params)?;
                 if is_gen {
-                    Ok(Expr::GeneratorFunc { name, params, body })
+                    Ok(Expr::GeneratorFunc { name, params, body, span: self.span_from(start) })
                 } else {
-                    Ok(Expr::Function { name, params, body })
+                    Ok(Expr::Function { name, params, body, span: self.span_from(start) })
                 }
             }
 
@@ -1331,39 +1715,46 @@ impl Parser {
             TokenKind::Keyword(KeywordEnum::Await) => {
                 self.advance(); self.skip_trivia();
                 let value = Box::new(self.parse_unary()?);
-                Ok(Expr::Await { value })
+                Ok(Expr::Await { value, span: self.span_from(start) })
             }
 
             // async function nebo async arrow: `async function(...) {}` nebo `async (...) => ...`
             TokenKind::Keyword(KeywordEnum::Async) => {
                 self.advance(); self.skip_trivia();
-                // `async function`
+                // `async function` / `async function*`
                 if matches!(self.kind(), TokenKind::Keyword(KeywordEnum::Function)) {
                     self.advance(); self.skip_trivia();
+                    let is_gen = self.eat_op(OperatorEnum::Star);
+                    self.skip_trivia();
                     let name = if matches!(self.kind(), TokenKind::Identifier(_) | TokenKind::Keyword(_)) {
-                        Some(self.parse_ident()?)
+                        Some(self.parse_binding_ident()?)
                     } else { None };
                     let params = self.parse_params()?;
-                    let body = self.parse_fn_body()?;
-                    return Ok(Expr::AsyncFunc { name, params, body });
+                    let body = self.parse_fn_body(&params)?;
+                    if is_gen {
+                        return Ok(Expr::AsyncGeneratorFunc { name, params, body, span: self.span_from(start) });
+                    }
+                    return Ok(Expr::AsyncFunc { name, params, body, is_arrow: false, span: self.span_from(start) });
                 }
                 // `async (params) => body` nebo `async param => body`
                 let params = if matches!(self.kind(), TokenKind::Operator(OperatorEnum::LParen)) {
                     self.parse_params()?
                 } else {
                     // jednoparametrova async arrow: `async x => expr`
-                    let name = self.parse_ident()?;
+                    let name = self.parse_binding_ident()?;
                     vec![Param::simple(name)]
                 };
                 self.skip_trivia();
                 self.expect_op(OperatorEnum::Arrow)?;
                 self.skip_trivia();
                 let body = if matches!(self.kind(), TokenKind::Operator(OperatorEnum::LBrace)) {
-                    self.parse_fn_body()?
+                    self.parse_fn_body(&params)?
                 } else {
-                    vec![Stmt::Return(Some(self.parse_assign_expr()?))]
+                    let value = self.parse_assign_expr()?;
+                    let span = value.span();
+                    vec![Stmt::Return(Some(value), span)]
                 };
-                Ok(Expr::AsyncFunc { name: None, params, body })
+                Ok(Expr::AsyncFunc { name: None, params, body, is_arrow: true, span: self.span_from(start) })
             }
 
             // yield vyraz: `yield value?` nebo `yield* iterable`
@@ -1381,13 +1772,28 @@ impl Parser {
                     | TokenKind::Eof => None,
                     _ => Some(Box::new(self.parse_assign_expr()?)),
                 };
-                Ok(Expr::Yield { value, delegate })
+                Ok(Expr::Yield { value, delegate, span: self.span_from(start) })
             }
 
             TokenKind::RegexLiteral { pattern, flags } => {
                 let (p, f) = (pattern.clone(), flags.clone());
                 self.advance();
-                Ok(Expr::Regex(p, f))
+                Ok(Expr::Regex(p, f, self.span_from(start)))
+            }
+
+            // Dekorovana vyrazova trida: `const Foo = @dec class { ... }`
+            TokenKind::Operator(OperatorEnum::At) => {
+                let decorators = self.parse_decorators()?;
+                self.skip_trivia();
+                if !matches!(self.kind(), TokenKind::Keyword(KeywordEnum::Class)) {
+                    return Err(self.err("Dekorator lze pouzit jen pred tridou nebo jejim clenem"));
+                }
+                match self.parse_primary()? {
+                    Expr::ClassExpr { name, super_class, body, .. } => {
+                        Ok(Expr::ClassExpr { name, decorators, super_class, body, span: self.span_from(start) })
+                    }
+                    _ => unreachable!("za `class` vzdy nasleduje ClassExpr"),
+                }
             }
 
             // Vyrazova trida: `const Foo = class { ... }`
@@ -1395,13 +1801,13 @@ impl Parser {
                 self.advance(); self.skip_trivia();
                 // Volitelne jmeno tridy v expressionu
                 let name = if matches!(self.kind(), TokenKind::Identifier(_)) {
-                    Some(self.parse_ident()?)
+                    Some(self.parse_binding_ident()?)
                 } else { None };
                 let super_class = self.parse_class_extends()?;
                 self.expect_op(OperatorEnum::LBrace)?;
                 let body = self.parse_class_body()?;
                 self.expect_op(OperatorEnum::RBrace)?;
-                Ok(Expr::ClassExpr { name, super_class, body })
+                Ok(Expr::ClassExpr { name, decorators: vec![], super_class, body, span: self.span_from(start) })
             }
 
             _ => Err(self.err(format!("Neočekávaný token: {:?}", self.kind()))),
@@ -1409,26 +1815,41 @@ impl Parser {
     }
 
     fn parse_template(&mut self) -> Result<Expr, ParseError> {
-        let mut quasis = Vec::new();
+        let start = self.start_pos();
+        let (quasis, _, expressions) = self.parse_template_parts()?;
+        Ok(Expr::Template { quasis, expressions, span: self.span_from(start) })
+    }
+
+    /// Casti template literalu od aktualniho tokenu (`NoSubstitutionTemplate`
+    /// nebo `TemplateHead`). Raw text nese lexeme tokenu.
+    fn parse_template_parts(&mut self) -> Result<TemplateParts, ParseError> {
+        let mut cooked = Vec::new();
+        let mut raw = Vec::new();
         let mut expressions: Vec<Box<Expr>> = Vec::new();
 
-        if let TokenKind::TemplateHead(s) = self.kind().clone() {
-            quasis.push(s); self.advance();
+        match self.kind().clone() {
+            TokenKind::NoSubstitutionTemplate(s) => {
+                cooked.push(s); raw.push(self.advance().lexeme);
+                return Ok((cooked, raw, expressions));
+            }
+            TokenKind::TemplateHead(s) => { cooked.push(s); raw.push(self.advance().lexeme); }
+            _ => return Err(self.err("Ocekavan template literal")),
         }
         loop {
             expressions.push(Box::new(self.parse_assign_expr()?));
             self.skip_trivia();
             match self.kind().clone() {
-                TokenKind::TemplateMiddle(s) => { quasis.push(s); self.advance(); }
-                TokenKind::TemplateTail(s)   => { quasis.push(s); self.advance(); break; }
+                TokenKind::TemplateMiddle(s) => { cooked.push(s); raw.push(self.advance().lexeme); }
+                TokenKind::TemplateTail(s)   => { cooked.push(s); raw.push(self.advance().lexeme); break; }
                 _ => return Err(self.err("Neukončený template literál")),
             }
         }
-        Ok(Expr::Template { quasis, expressions })
+        Ok((cooked, raw, expressions))
     }
 
     fn parse_object_prop(&mut self) -> Result<ObjectProp, ParseError> {
         self.skip_trivia();
+        let start = self.start_pos();
 
         // Spread: `{ ...obj }` (ES2018). PropKey::Spread + value=spread expr.
         if self.eat_op(OperatorEnum::Ellipsis) {
@@ -1438,9 +1859,44 @@ impl Parser {
                 value: Box::new(value),
                 shorthand: false,
                 computed: false,
+                kind: PropKind::Init,
             });
         }
 
+        // Accessor: `get x() {}` / `set [k](v) {}`. Samotne `get`/`set` jako klic
+        // (`{ get: 1 }`, `{ get() {} }`, shorthand `{ get }`) zustava beznou vlastnosti.
+        let accessor = match self.kind() {
+            TokenKind::Keyword(KeywordEnum::Get) => Some(PropKind::Get),
+            TokenKind::Keyword(KeywordEnum::Set) => Some(PropKind::Set),
+            _ => None,
+        }.filter(|_| !matches!(self.peek_non_trivia(),
+            TokenKind::Operator(OperatorEnum::LParen | OperatorEnum::Colon
+                | OperatorEnum::Comma | OperatorEnum::RBrace)));
+        if let Some(kind) = accessor {
+            self.advance();
+            self.skip_trivia();
+            let (key, computed) = if self.eat_op(OperatorEnum::LBracket) {
+                let key_expr = self.parse_assign_expr()?;
+                self.skip_trivia();
+                self.expect_op(OperatorEnum::RBracket)?;
+                (PropKey::Computed(Box::new(key_expr)), true)
+            } else {
+                let key = match self.kind().clone() {
+                    TokenKind::Identifier(s)           => PropKey::Ident(s),
+                    TokenKind::StringLiteral { value, .. } => PropKey::Str(value),
+                    TokenKind::NumericLiteral { value, .. } => PropKey::Num(value),
+                    TokenKind::Keyword(kw)             => PropKey::Ident(kw.as_str().to_string()),
+                    _ => return Err(self.err("Očekáván název getteru/setteru")),
+                };
+                self.advance();
+                (key, false)
+            };
+            let params = self.parse_params()?;
+            let body = self.parse_fn_body(&params)?;
+            let func = Expr::Function { name: None, params, body, span: self.span_from(start) };
+            return Ok(ObjectProp { key, value: Box::new(func), shorthand: false, computed, kind });
+        }
+
         // [computed]: nebo [computed]() {} (method shorthand s computed klicem)
         if matches!(self.kind(), TokenKind::Operator(OperatorEnum::LBracket)) {
             self.advance();
@@ -1450,14 +1906,14 @@ impl Parser {
             // [key](params) { body } - computed method shorthand
             if matches!(self.kind(), TokenKind::Operator(OperatorEnum::LParen)) {
                 let params = self.parse_params()?;
-                let body = self.parse_fn_body()?;
-                let func = Expr::Function { name: None, params, body };
-                return Ok(ObjectProp { key: PropKey::Computed(Box::new(key_expr)), value: Box::new(func), shorthand: false, computed: true });
+                let body = self.parse_fn_body(&params)?;
+                let func = Expr::Function { name: None, params, body, span: self.span_from(start) };
+                return Ok(ObjectProp { key: PropKey::Computed(Box::new(key_expr)), value: Box::new(func), shorthand: false, computed: true, kind: PropKind::Init });
             }
             // [key]: value
             self.expect_op(OperatorEnum::Colon)?;
             let value = self.parse_assign_expr()?;
-            return Ok(ObjectProp { key: PropKey::Computed(Box::new(key_expr)), value: Box::new(value), shorthand: false, computed: true });
+            return Ok(ObjectProp { key: PropKey::Computed(Box::new(key_expr)), value: Box::new(value), shorthand: false, computed: true, kind: PropKind::Init });
         }
 
         let key = match self.kind().clone() {
@@ -1473,18 +1929,18 @@ impl Parser {
         if matches!(self.kind(), TokenKind::Operator(OperatorEnum::LParen)) {
             let fn_name = match &key { PropKey::Ident(s) | PropKey::Str(s) => Some(s.clone()), _ => None };
             let params = self.parse_params()?;
-            let body = self.parse_fn_body()?;
-            let func = Expr::Function { name: fn_name, params, body };
-            return Ok(ObjectProp { key, value: Box::new(func), shorthand: false, computed: false });
+            let body = self.parse_fn_body(&params)?;
+            let func = Expr::Function { name: fn_name, params, body, span: self.span_from(start) };
+            return Ok(ObjectProp { key, value: Box::new(func), shorthand: false, computed: false, kind: PropKind::Init });
         }
         // shorthand: { x }
         if !matches!(self.kind(), TokenKind::Operator(OperatorEnum::Colon)) {
             let name = match &key { PropKey::Ident(s) => s.clone(), _ => return Err(self.err("Shorthand klic musi byt identifikator")) };
-            return Ok(ObjectProp { key, value: Box::new(Expr::Ident(name)), shorthand: true, computed: false });
+            return Ok(ObjectProp { key, value: Box::new(Expr::Ident(name, self.span_from(start))), shorthand: true, computed: false, kind: PropKind::Init });
         }
         self.expect_op(OperatorEnum::Colon)?;
         let value = self.parse_assign_expr()?;
-        Ok(ObjectProp { key, value: Box::new(value), shorthand: false, computed: false })
+        Ok(ObjectProp { key, value: Box::new(value), shorthand: false, computed: false, kind: PropKind::Init })
     }
 
     fn parse_call_args(&mut self) -> Result<Vec<Expr>, ParseError> {
@@ -1492,9 +1948,10 @@ impl Parser {
         loop {
             self.skip_trivia();
             if matches!(self.kind(), TokenKind::Operator(OperatorEnum::RParen)) { break; }
+            let start = self.start_pos();
             let spread = self.eat_op(OperatorEnum::Ellipsis);
             let arg = self.parse_assign_expr()?;
-            args.push(if spread { Expr::Spread(Box::new(arg)) } else { arg });
+            args.push(if spread { Expr::Spread(Box::new(arg), self.span_from(start)) } else { arg });
             self.skip_trivia();
             if !self.eat_op(OperatorEnum::Comma) { break; }
         }
@@ -1547,29 +2004,50 @@ impl Parser {
     }
 
     fn parse_arrow(&mut self) -> Result<Expr, ParseError> {
+        let start = self.start_pos();
         let params = if matches!(self.kind(), TokenKind::Operator(OperatorEnum::LParen)) {
             self.parse_params()?
         } else {
-            vec![Param::simple(self.parse_ident()?)]
+            vec![Param::simple(self.parse_binding_ident()?)]
         };
         self.skip_trivia();
         self.expect_op(OperatorEnum::Arrow)?;
-        self.parse_arrow_body(params)
+        self.parse_arrow_body(params, start)
     }
 
-    fn parse_arrow_body(&mut self, params: Vec<Param>) -> Result<Expr, ParseError> {
+    /// Telo arrow funkce za `=>`; `start` je pozice zacatku parametru.
+    fn parse_arrow_body(&mut self, params: Vec<Param>, start: SourcePos) -> Result<Expr, ParseError> {
         self.skip_trivia();
         if matches!(self.kind(), TokenKind::Operator(OperatorEnum::LBrace)) {
-            let body = self.parse_fn_body()?;
-            Ok(Expr::Arrow { params, body: ArrowBody::Block(body) })
+            let body = self.parse_fn_body(&params)?;
+            Ok(Expr::Arrow { params, body: ArrowBody::Block(body), span: self.span_from(start) })
         } else {
             let expr = self.parse_assign_expr()?;
-            Ok(Expr::Arrow { params, body: ArrowBody::Expr(Box::new(expr)) })
+            Ok(Expr::Arrow { params, body: ArrowBody::Expr(Box::new(expr)), span: self.span_from(start) })
         }
     }
 
     // ─── Pomocné ──────────────────────────────────────────────────────────────
 
+    /// Jmeno vazby (promenna, parametr, funkce, trida) - ve strict mode nesmi
+    /// byt `eval`/`arguments` ani vyhrazene slovo.
+    fn parse_binding_ident(&mut self) -> Result<String, ParseError> {
+        let name = self.parse_ident()?;
+        self.check_binding(&name, self.strict)?;
+        Ok(name)
+    }
+
+    fn check_binding(&self, name: &str, strict: bool) -> Result<(), ParseError> {
+        if !strict { return Ok(()); }
+        if matches!(name, "eval" | "arguments") {
+            return Err(self.err(format!("'{name}' nelze ve strict mode pouzit jako jmeno vazby")));
+        }
+        if STRICT_RESERVED.contains(&name) {
+            return Err(self.err(format!("'{name}' je ve strict mode vyhrazene slovo")));
+        }
+        Ok(())
+    }
+
     fn parse_ident(&mut self) -> Result<String, ParseError> {
         self.skip_trivia();
         match self.kind().clone() {
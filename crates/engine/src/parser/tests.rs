@@ -26,29 +26,29 @@ fn unwrap_line(s: Stmt) -> Stmt {
 fn deep_unwrap(s: Stmt) -> Stmt {
     let s = unwrap_line(s);
     match s {
-        Stmt::Block(body) => Stmt::Block(body.into_iter().map(deep_unwrap).collect()),
-        Stmt::Function { name, params, body } => Stmt::Function {
-            name, params, body: body.into_iter().map(deep_unwrap).collect(),
+        Stmt::Block(body, span) => Stmt::Block(body.into_iter().map(deep_unwrap).collect(), span),
+        Stmt::Function { name, params, body, span } => Stmt::Function {
+            name, params, body: body.into_iter().map(deep_unwrap).collect(), span,
         },
-        Stmt::GeneratorFunc { name, params, body } => Stmt::GeneratorFunc {
-            name, params, body: body.into_iter().map(deep_unwrap).collect(),
+        Stmt::GeneratorFunc { name, params, body, span } => Stmt::GeneratorFunc {
+            name, params, body: body.into_iter().map(deep_unwrap).collect(), span,
         },
-        Stmt::AsyncFunc { name, params, body } => Stmt::AsyncFunc {
-            name, params, body: body.into_iter().map(deep_unwrap).collect(),
+        Stmt::AsyncFunc { name, params, body, span } => Stmt::AsyncFunc {
+            name, params, body: body.into_iter().map(deep_unwrap).collect(), span,
         },
-        Stmt::If { test, yes, no } => Stmt::If {
-            test, yes: Box::new(deep_unwrap(*yes)), no: no.map(|s| Box::new(deep_unwrap(*s))),
+        Stmt::If { test, yes, no, span } => Stmt::If {
+            test, yes: Box::new(deep_unwrap(*yes)), no: no.map(|s| Box::new(deep_unwrap(*s))), span,
         },
-        Stmt::While { test, body } => Stmt::While { test, body: Box::new(deep_unwrap(*body)) },
-        Stmt::DoWhile { body, test } => Stmt::DoWhile { body: Box::new(deep_unwrap(*body)), test },
-        Stmt::For { init, test, update, body } => Stmt::For {
-            init, test, update, body: Box::new(deep_unwrap(*body)),
+        Stmt::While { test, body, span } => Stmt::While { test, body: Box::new(deep_unwrap(*body)), span },
+        Stmt::DoWhile { body, test, span } => Stmt::DoWhile { body: Box::new(deep_unwrap(*body)), test, span },
+        Stmt::For { init, test, update, body, span } => Stmt::For {
+            init, test, update, body: Box::new(deep_unwrap(*body)), span,
         },
-        Stmt::ForIn { kind, target, iter, body } => Stmt::ForIn {
-            kind, target, iter, body: Box::new(deep_unwrap(*body)),
+        Stmt::ForIn { kind, target, iter, body, span } => Stmt::ForIn {
+            kind, target, iter, body: Box::new(deep_unwrap(*body)), span,
         },
-        Stmt::ForOf { kind, target, iter, body } => Stmt::ForOf {
-            kind, target, iter, body: Box::new(deep_unwrap(*body)),
+        Stmt::ForOf { kind, target, iter, body, span } => Stmt::ForOf {
+            kind, target, iter, body: Box::new(deep_unwrap(*body)), span,
         },
         other => other,
     }
@@ -58,7 +58,7 @@ fn parse_expr(src: &str) -> Expr {
     let prog = parse(src);
     let s = unwrap_line(prog.body.into_iter().next().unwrap());
     match s {
-        Stmt::Expr(e) => e,
+        Stmt::Expr(e, _) => e,
         other => panic!("Ocekavan ExprStmt, nalezeno {other:?}"),
     }
 }
@@ -71,22 +71,22 @@ fn parse_stmt(src: &str) -> Stmt {
 
 #[test]
 fn number_literal() {
-    assert!(matches!(parse_expr("42"), Expr::Number(n) if n == 42.0));
-    assert!(matches!(parse_expr("3.14"), Expr::Number(n) if (n - 3.14).abs() < 1e-10));
-    assert!(matches!(parse_expr("1e3"), Expr::Number(n) if n == 1000.0));
+    assert!(matches!(parse_expr("42"), Expr::Number(n, _) if n == 42.0));
+    assert!(matches!(parse_expr("3.14"), Expr::Number(n, _) if (n - 3.14).abs() < 1e-10));
+    assert!(matches!(parse_expr("1e3"), Expr::Number(n, _) if n == 1000.0));
 }
 
 #[test]
 fn string_literal() {
-    assert!(matches!(parse_expr(r#""hello""#), Expr::Str(s) if s == "hello"));
-    assert!(matches!(parse_expr("'world'"), Expr::Str(s) if s == "world"));
+    assert!(matches!(parse_expr(r#""hello""#), Expr::Str(s, _) if s == "hello"));
+    assert!(matches!(parse_expr("'world'"), Expr::Str(s, _) if s == "world"));
 }
 
 #[test]
 fn bool_null_undefined() {
-    assert!(matches!(parse_expr("true"), Expr::Bool(true)));
-    assert!(matches!(parse_expr("false"), Expr::Bool(false)));
-    assert!(matches!(parse_expr("null"), Expr::Null));
+    assert!(matches!(parse_expr("true"), Expr::Bool(true, _)));
+    assert!(matches!(parse_expr("false"), Expr::Bool(false, _)));
+    assert!(matches!(parse_expr("null"), Expr::Null(_)));
 }
 
 // --- binarne vyrazy a priorita ---
@@ -103,8 +103,8 @@ fn binary_add() {
 fn operator_precedence_mul_before_add() {
     // 1 + 2 * 3  =>  Add(1, Mul(2, 3))
     match parse_expr("1 + 2 * 3") {
-        Expr::Binary { op: BinaryOp::Add, left, right } => {
-            assert!(matches!(*left, Expr::Number(n) if n == 1.0));
+        Expr::Binary { op: BinaryOp::Add, left, right, .. } => {
+            assert!(matches!(*left, Expr::Number(n, _) if n == 1.0));
             assert!(matches!(*right, Expr::Binary { op: BinaryOp::Mul, .. }));
         }
         other => panic!("Spatna struktura: {other:?}"),
@@ -155,10 +155,10 @@ fn unary_typeof() {
 #[test]
 fn ternary_expr() {
     match parse_expr("a ? 1 : 2") {
-        Expr::Ternary { test, yes, no } => {
-            assert!(matches!(*test, Expr::Ident(s) if s == "a"));
-            assert!(matches!(*yes, Expr::Number(n) if n == 1.0));
-            assert!(matches!(*no, Expr::Number(n) if n == 2.0));
+        Expr::Ternary { test, yes, no, .. } => {
+            assert!(matches!(*test, Expr::Ident(s, _) if s == "a"));
+            assert!(matches!(*yes, Expr::Number(n, _) if n == 1.0));
+            assert!(matches!(*no, Expr::Number(n, _) if n == 2.0));
         }
         other => panic!("Ocekavan Ternary, nalezeno {other:?}"),
     }
@@ -169,9 +169,9 @@ fn ternary_expr() {
 #[test]
 fn assignment() {
     match parse_expr("x = 5") {
-        Expr::Assign { op: AssignOp::Assign, target, value } => {
-            assert!(matches!(*target, Expr::Ident(s) if s == "x"));
-            assert!(matches!(*value, Expr::Number(n) if n == 5.0));
+        Expr::Assign { op: AssignOp::Assign, target, value, .. } => {
+            assert!(matches!(*target, Expr::Ident(s, _) if s == "x"));
+            assert!(matches!(*value, Expr::Number(n, _) if n == 5.0));
         }
         other => panic!("Ocekavano prirazeni, nalezeno {other:?}"),
     }
@@ -188,10 +188,10 @@ fn compound_assignment() {
 #[test]
 fn var_decl_let() {
     match parse_stmt("let x = 42;") {
-        Stmt::Var { kind: VarKind::Let, decls } => {
+        Stmt::Var { kind: VarKind::Let, decls, .. } => {
             assert_eq!(decls.len(), 1);
             assert!(matches!(&decls[0].pattern, Pattern::Ident(n) if n == "x"));
-            assert!(matches!(decls[0].init, Some(Expr::Number(n)) if n == 42.0));
+            assert!(matches!(decls[0].init, Some(Expr::Number(n, _)) if n == 42.0));
         }
         other => panic!("Ocekavan VarDecl(Let), nalezeno {other:?}"),
     }
@@ -200,7 +200,7 @@ fn var_decl_let() {
 #[test]
 fn var_decl_const() {
     match parse_stmt("const PI = 3.14;") {
-        Stmt::Var { kind: VarKind::Const, decls } => {
+        Stmt::Var { kind: VarKind::Const, decls, .. } => {
             assert!(matches!(&decls[0].pattern, Pattern::Ident(n) if n == "PI"));
         }
         other => panic!("Ocekavan VarDecl(Const), nalezeno {other:?}"),
@@ -210,7 +210,7 @@ fn var_decl_const() {
 #[test]
 fn var_decl_without_init() {
     match parse_stmt("let x;") {
-        Stmt::Var { kind: VarKind::Let, decls } => {
+        Stmt::Var { kind: VarKind::Let, decls, .. } => {
             assert!(decls[0].init.is_none());
         }
         other => panic!("{other:?}"),
@@ -233,7 +233,7 @@ fn function_declaration() {
 #[test]
 fn arrow_simple_param() {
     match parse_expr("x => x * 2") {
-        Expr::Arrow { params, body: ArrowBody::Expr(_) } => {
+        Expr::Arrow { params, body: ArrowBody::Expr(_), .. } => {
             assert_eq!(params.iter().map(|p| p.name_str()).collect::<Vec<_>>(), vec!["x"]);
         }
         other => panic!("Ocekavan Arrow, nalezeno {other:?}"),
@@ -243,7 +243,7 @@ fn arrow_simple_param() {
 #[test]
 fn arrow_paren_params() {
     match parse_expr("(a, b) => a + b") {
-        Expr::Arrow { params, body: ArrowBody::Expr(_) } => {
+        Expr::Arrow { params, body: ArrowBody::Expr(_), .. } => {
             assert_eq!(params.iter().map(|p| p.name_str()).collect::<Vec<_>>(), vec!["a", "b"]);
         }
         other => panic!("Ocekavan Arrow, nalezeno {other:?}"),
@@ -253,7 +253,7 @@ fn arrow_paren_params() {
 #[test]
 fn arrow_no_params() {
     match parse_expr("() => 42") {
-        Expr::Arrow { params, body: ArrowBody::Expr(_) } => {
+        Expr::Arrow { params, body: ArrowBody::Expr(_), .. } => {
             assert!(params.is_empty());
         }
         other => panic!("Ocekavan Arrow, nalezeno {other:?}"),
@@ -274,7 +274,7 @@ fn arrow_block_body() {
 fn function_call() {
     match parse_expr("foo(1, 2)") {
         Expr::Call { callee, args, .. } => {
-            assert!(matches!(*callee, Expr::Ident(s) if s == "foo"));
+            assert!(matches!(*callee, Expr::Ident(s, _) if s == "foo"));
             assert_eq!(args.len(), 2);
         }
         other => panic!("Ocekavan Call, nalezeno {other:?}"),
@@ -285,7 +285,7 @@ fn function_call() {
 fn member_dot() {
     match parse_expr("obj.prop") {
         Expr::Member { object, prop: MemberProp::Ident(name), .. } => {
-            assert!(matches!(*object, Expr::Ident(s) if s == "obj"));
+            assert!(matches!(*object, Expr::Ident(s, _) if s == "obj"));
             assert_eq!(name, "prop");
         }
         other => panic!("Ocekavan Member, nalezeno {other:?}"),
@@ -296,8 +296,8 @@ fn member_dot() {
 fn member_computed() {
     match parse_expr("arr[0]") {
         Expr::Member { object, prop: MemberProp::Computed(idx), .. } => {
-            assert!(matches!(*object, Expr::Ident(s) if s == "arr"));
-            assert!(matches!(*idx, Expr::Number(n) if n == 0.0));
+            assert!(matches!(*object, Expr::Ident(s, _) if s == "arr"));
+            assert!(matches!(*idx, Expr::Number(n, _) if n == 0.0));
         }
         other => panic!("Ocekavan Member(Computed), nalezeno {other:?}"),
     }
@@ -308,10 +308,10 @@ fn member_computed() {
 #[test]
 fn array_literal() {
     match parse_expr("[1, 2, 3]") {
-        Expr::Array(items) => {
+        Expr::Array(items, _) => {
             assert_eq!(items.len(), 3);
             match &items[0] {
-                Some(e) => assert!(matches!(**e, Expr::Number(n) if n == 1.0)),
+                Some(e) => assert!(matches!(**e, Expr::Number(n, _) if n == 1.0)),
                 None => panic!("Ocekavan prvni prvek"),
             }
         }
@@ -323,20 +323,32 @@ fn array_literal() {
 fn object_literal() {
     // { ... } jako expression statement je block - treba obalit do ()
     match parse_expr("({ a: 1, b: 2 })") {
-        Expr::Object(props) => {
+        Expr::Object(props, _) => {
             assert_eq!(props.len(), 2);
         }
         other => panic!("Ocekavan Object, nalezeno {other:?}"),
     }
 }
 
+#[test]
+fn object_literal_accessors() {
+    match parse_expr("({ get a() { return 1; }, set [k](v) {}, get: 1, set() {} })") {
+        Expr::Object(props, _) => {
+            let kinds: Vec<PropKind> = props.iter().map(|p| p.kind).collect();
+            assert_eq!(kinds, vec![PropKind::Get, PropKind::Set, PropKind::Init, PropKind::Init]);
+            assert!(props[1].computed);
+        }
+        other => panic!("Ocekavan Object, nalezeno {other:?}"),
+    }
+}
+
 // --- ridici struktury ---
 
 #[test]
 fn if_else() {
     match parse_stmt("if (x) { 1; } else { 2; }") {
         Stmt::If { test, no: Some(_), .. } => {
-            assert!(matches!(test, Expr::Ident(s) if s == "x"));
+            assert!(matches!(test, Expr::Ident(s, _) if s == "x"));
         }
         other => panic!("Ocekavan If, nalezeno {other:?}"),
     }
@@ -346,7 +358,7 @@ fn if_else() {
 fn while_loop() {
     match parse_stmt("while (true) {}") {
         Stmt::While { test, .. } => {
-            assert!(matches!(test, Expr::Bool(true)));
+            assert!(matches!(test, Expr::Bool(true, _)));
         }
         other => panic!("Ocekavan While, nalezeno {other:?}"),
     }
@@ -363,7 +375,7 @@ fn for_loop() {
 #[test]
 fn return_stmt() {
     match parse_stmt("return 42;") {
-        Stmt::Return(Some(Expr::Number(n))) => assert_eq!(n, 42.0),
+        Stmt::Return(Some(Expr::Number(n, _)), _) => assert_eq!(n, 42.0),
         other => panic!("Ocekavan Return(42), nalezeno {other:?}"),
     }
 }
@@ -373,7 +385,7 @@ fn return_stmt() {
 #[test]
 fn array_destructuring_decl() {
     match parse_stmt("const [a, b] = arr;") {
-        Stmt::Var { kind: VarKind::Const, decls } => {
+        Stmt::Var { kind: VarKind::Const, decls, .. } => {
             assert_eq!(decls.len(), 1);
             match &decls[0].pattern {
                 Pattern::Array(elems) => {
@@ -391,7 +403,7 @@ fn array_destructuring_decl() {
 #[test]
 fn object_destructuring_decl() {
     match parse_stmt("const { x, y } = obj;") {
-        Stmt::Var { kind: VarKind::Const, decls } => {
+        Stmt::Var { kind: VarKind::Const, decls, .. } => {
             match &decls[0].pattern {
                 Pattern::Object(props) => {
                     assert_eq!(props.len(), 2);
@@ -470,7 +482,7 @@ fn function_params_destructuring() {
 #[test]
 fn class_basic() {
     match parse_stmt("class Foo { constructor(x) { this.x = x; } greet() { return this.x; } }") {
-        Stmt::Class { name, super_class, body } => {
+        Stmt::Class { name, super_class, body, .. } => {
             assert_eq!(name, "Foo");
             assert!(super_class.is_none());
             assert_eq!(body.len(), 2);
@@ -489,7 +501,7 @@ fn class_extends() {
             assert_eq!(name, "Dog");
             assert!(super_class.is_some());
             // super_class je Expr::Ident("Animal")
-            assert!(matches!(super_class.as_deref(), Some(Expr::Ident(n)) if n == "Animal"));
+            assert!(matches!(super_class.as_deref(), Some(Expr::Ident(n, _)) if n == "Animal"));
         }
         other => panic!("{other:?}"),
     }
@@ -591,7 +603,7 @@ fn yield_expr_parse() {
     match prog.body.into_iter().next().unwrap() {
         Stmt::GeneratorFunc { body, .. } => {
             match &body[0] {
-                Stmt::Expr(Expr::Yield { value: Some(_), delegate: false }) => {}
+                Stmt::Expr(Expr::Yield { value: Some(_), delegate: false, .. }, _) => {}
                 other => panic!("{other:?}"),
             }
         }
@@ -605,7 +617,7 @@ fn yield_star_parse() {
     match prog.body.into_iter().next().unwrap() {
         Stmt::GeneratorFunc { body, .. } => {
             match &body[0] {
-                Stmt::Expr(Expr::Yield { delegate: true, .. }) => {}
+                Stmt::Expr(Expr::Yield { delegate: true, .. }, _) => {}
                 other => panic!("{other:?}"),
             }
         }
@@ -619,7 +631,7 @@ fn computed_method_shorthand_parse() {
     match prog.body.into_iter().next().unwrap() {
         Stmt::Var { decls, .. } => {
             match decls[0].init.as_ref().unwrap() {
-                Expr::Object(props) => {
+                Expr::Object(props, _) => {
                     assert_eq!(props.len(), 1);
                     assert!(props[0].computed);
                 }
@@ -698,7 +710,7 @@ fn try_without_finally() {
 #[test]
 fn throw_statement() {
     let prog = parse(r#"throw new Error("oops");"#);
-    assert!(matches!(prog.body[0], Stmt::Throw(_)));
+    assert!(matches!(prog.body[0], Stmt::Throw(_, _)));
 }
 
 #[test]
@@ -899,3 +911,159 @@ fn multiple_statements() {
     let prog = parse("let x = 1; let y = 2; let z = 3;");
     assert_eq!(prog.body.len(), 3);
 }
+
+#[test]
+fn tagged_template_parse() {
+    match parse_expr(r"html`<p>${x}\n</p>`;") {
+        Expr::TaggedTemplate { tag, cooked, raw, expressions, .. } => {
+            assert!(matches!(*tag, Expr::Ident(ref n, _) if n == "html"));
+            assert_eq!(cooked, vec!["<p>".to_string(), "\n</p>".to_string()]);
+            assert_eq!(raw, vec!["<p>".to_string(), "\\n</p>".to_string()]);
+            assert_eq!(expressions.len(), 1);
+        }
+        other => panic!("ocekavan TaggedTemplate, mam {other:?}"),
+    }
+    // Kazdy vyskyt je vlastni call site
+    let prog = parse("a`x`; a`x`;");
+    let sites: Vec<&TemplateSite> = prog.body.iter().filter_map(|s| match s {
+        Stmt::Expr(Expr::TaggedTemplate { site, .. }, _) => Some(site),
+        _ => None,
+    }).collect();
+    assert_eq!(sites.len(), 2);
+    assert_ne!(sites[0], sites[1]);
+}
+
+#[test]
+fn export_from_forms() {
+    let prog = parse(r#"export { a, b as c } from "./x.js"; export * from "./y.js"; export * as ns from "./z.js";"#);
+    assert!(matches!(&prog.body[0], Stmt::Export(ExportKind::From { source, names }, _)
+        if source == "./x.js" && names[1] == ("b".to_string(), "c".to_string())));
+    assert!(matches!(&prog.body[1], Stmt::Export(ExportKind::Star { alias: None, .. }, _)));
+    assert!(matches!(&prog.body[2], Stmt::Export(ExportKind::Star { alias: Some(a), .. }, _) if a == "ns"));
+}
+
+#[test]
+fn import_meta_is_expression() {
+    match parse_expr("import.meta.url;") {
+        Expr::Member { object, .. } => assert!(matches!(*object, Expr::Ident(ref n, _) if n == "import.meta")),
+        other => panic!("ocekavan Member, mam {other:?}"),
+    }
+}
+
+#[test]
+fn class_decorators() {
+    match parse_stmt("@a.b @c(1, 2) @(d) class C { @e static m() {} @f.#g x = 1; @h accessor y; }") {
+        Stmt::Class { decorators, body, .. } => {
+            assert_eq!(decorators.len(), 3);
+            assert!(matches!(&decorators[0], Expr::Member { .. }));
+            assert!(matches!(&decorators[1], Expr::Call { args, .. } if args.len() == 2));
+            assert!(matches!(&decorators[2], Expr::Ident(n, _) if n == "d"));
+            assert_eq!(body.iter().map(|m| m.decorators.len()).collect::<Vec<_>>(), vec![1, 1, 1]);
+            assert_eq!(body[2].kind, ClassMemberKind::Accessor);
+        }
+        other => panic!("{other:?}"),
+    }
+    let lex = |src: &str| {
+        let tokens: Vec<_> = Lexer::parse_str(src, "<test>").unwrap().tokens.into_iter()
+            .filter(|t| !matches!(t.kind, TokenKind::Whitespace | TokenKind::Newline))
+            .collect();
+        Parser::new(tokens).parse()
+    };
+    assert!(lex("export @a class C {}").is_ok());
+    assert!(lex("@a export class C {}").is_ok());
+    assert!(lex("@a function f() {}").is_err());
+    assert!(lex("class C { @a constructor() {} }").is_err());
+    assert!(lex("class C { @a static {} }").is_err());
+}
+
+#[test]
+fn strict_mode_early_errors() {
+    let lex = |src: &str| {
+        let tokens: Vec<_> = Lexer::parse_str(src, "<test>").unwrap().tokens.into_iter()
+            .filter(|t| !matches!(t.kind, TokenKind::Whitespace | TokenKind::Newline))
+            .collect();
+        Parser::new(tokens).parse()
+    };
+    assert!(lex(r#""use strict"; var x = 1;"#).unwrap().strict);
+    assert!(!lex("var x = 1;").unwrap().strict);
+    assert!(lex(r#""use strict"; var eval = 1;"#).is_err());
+    assert!(lex(r#""use strict"; var x; delete x;"#).is_err());
+    assert!(lex(r#""use strict"; arguments = 1;"#).is_err());
+    assert!(lex(r#"function f(a, a) { "use strict"; }"#).is_err());
+    assert!(lex(r#"function f(a = 1) { "use strict"; }"#).is_err());
+    assert!(lex(r#"function f() { "use strict"; let interface = 1; }"#).is_err());
+    assert!(lex("class C { m(a, a) {} }").is_err());
+    // Sloppy kod tyto konstrukce povoluje
+    assert!(lex("var eval = 1; var x; delete x; function f(a, a) {} var interface = 2;").is_ok());
+}
+
+#[test]
+fn using_declarations() {
+    let lex = |src: &str| {
+        let tokens: Vec<_> = Lexer::parse_str(src, "<test>").unwrap().tokens.into_iter()
+            .filter(|t| !matches!(t.kind, TokenKind::Whitespace | TokenKind::Newline))
+            .collect();
+        Parser::new(tokens).parse()
+    };
+    let kinds = |src: &str| -> Vec<VarKind> {
+        lex(src).unwrap().body.iter().filter_map(|s| match s {
+            Stmt::WithLine { inner, .. } => match &**inner {
+                Stmt::Var { kind, .. } => Some(*kind),
+                _ => None,
+            },
+            Stmt::Var { kind, .. } => Some(*kind),
+            _ => None,
+        }).collect()
+    };
+    assert_eq!(kinds("using a = f(), b = g();"), vec![VarKind::Using]);
+    assert_eq!(kinds("async function f() {} await using r = open();"), vec![VarKind::AwaitUsing]);
+    // `using` je kontextove slovo - jinde obycejny identifikator
+    assert!(kinds("let using = 1; using = 2; using\nx = 3;").len() == 1);
+    assert!(lex("for (using r of list) {}").is_ok());
+    assert!(lex("using { a } = obj;").is_err());
+    assert!(lex("using a;").is_err());
+    assert!(lex("for (using r in obj) {}").is_err());
+}
+
+#[test]
+fn statement_and_call_spans() {
+    let tokens: Vec<_> = Lexer::parse_str("a(1); if (x) {\n  new B(f(2));\n}", "<test>").unwrap().tokens.into_iter()
+        .filter(|t| !matches!(t.kind, TokenKind::Whitespace | TokenKind::Newline))
+        .collect();
+    let body = Parser::new(tokens).parse().unwrap().body;
+    let spans: Vec<String> = body.iter().map(|s| match s {
+        Stmt::WithLine { span, .. } => span.to_string(),
+        _ => String::new(),
+    }).collect();
+    assert_eq!(spans, vec!["1:1-1:6", "1:7-3:2"]);
+    let Stmt::WithLine { inner, .. } = &body[0] else { unreachable!() };
+    let Stmt::Expr(Expr::Call { span, .. }, _) = &**inner else { panic!("{inner:?}") };
+    assert_eq!((span.start.offset, span.end.offset), (0, 4));
+}
+
+#[test]
+fn nested_expression_spans() {
+    let src = "x = f(a, ...b) + [1, c].length;";
+    let Stmt::Expr(Expr::Assign { span, target, value, .. }, stmt_span) = parse_stmt(src) else { panic!() };
+    assert_eq!(stmt_span.to_string(), "1:1-1:32");
+    assert_eq!(span.to_string(), "1:1-1:31");
+    assert_eq!(target.span().to_string(), "1:1-1:2");
+    let Expr::Binary { left, right, span, .. } = *value else { panic!() };
+    assert_eq!(span.to_string(), "1:5-1:31");
+    let Expr::Call { args, .. } = *left else { panic!() };
+    let spans: Vec<String> = args.iter().map(|a| a.span().to_string()).collect();
+    assert_eq!(spans, vec!["1:7-1:8", "1:10-1:14"]);
+    let Expr::Member { object, span, .. } = *right else { panic!() };
+    assert_eq!((object.span().to_string(), span.to_string()), ("1:18-1:24".to_string(), "1:18-1:31".to_string()));
+    let Expr::Array(items, _) = *object else { panic!() };
+    assert_eq!(items[1].as_ref().unwrap().span().to_string(), "1:22-1:23");
+}
+
+#[test]
+fn statement_spans_cover_keywords() {
+    let Stmt::If { test, yes, span, .. } = parse_stmt("if (a) { return b; }") else { panic!() };
+    assert_eq!((span.to_string(), test.span().to_string()), ("1:1-1:21".to_string(), "1:5-1:6".to_string()));
+    let Stmt::Block(body, block) = *yes else { panic!() };
+    assert_eq!(block.to_string(), "1:8-1:21");
+    assert_eq!(unwrap_line(body[0].clone()).span().to_string(), "1:10-1:19");
+}
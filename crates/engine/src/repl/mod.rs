@@ -0,0 +1,428 @@
+//! Interaktivni JS REPL (`cargo run -- repl [--page file.html]`).
+//!
+//! Vstupy bezi proti jednomu `Interpreter` - deklarace zustavaji v globalnim
+//! scope. Neuplny vstup (parser narazi na konec zdroje, neuzavreny template
+//! nebo komentar) pokracuje dalsim radkem. Vysledek vyrazu se vypise pres
+//! `pretty_print`; promise (i top-level `await`) se dockaji drainem event
+//! loopu. S `--page` bezi REPL nad headless WebView dane stranky (DOM,
+//! skripty stranky uz dobehly).
+//!
+//! Prikazy: `.help`, `.load file.js`, `.break` (zahodit rozepsany vstup),
+//! `.exit`.
+
+pub mod terminal;
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use crate::ast::Program;
+use crate::devtools::model::console::{suggest, ConsoleInput};
+use crate::interpreter::{Interpreter, JsError, JsValue};
+use crate::lexer::base::Lexer;
+use crate::parser::Parser;
+use crate::specifications::lexer_errors::LexerErrorKind;
+use crate::tokens::TokenKind;
+use crate::{Engine, WebView};
+
+use terminal::ReadLine;
+
+/// Vysledek jednoho radku vstupu.
+#[derive(Debug, PartialEq)]
+pub enum Outcome {
+    /// Vstup neni uplny - ceka se na dalsi radek.
+    More,
+    /// Text k vypsani (vysledek, chyba, vystup prikazu); prazdny = nic.
+    Output(String),
+    Exit,
+}
+
+/// Parsovany vstup REPLu.
+enum Parsed {
+    Program(Program),
+    Incomplete,
+    Error(String),
+}
+
+fn parse_input(src: &str) -> Parsed {
+    let lexer = match Lexer::parse_str(src, "<repl>") {
+        Ok(l) => l,
+        Err(e) => {
+            let incomplete = match e.kind {
+                LexerErrorKind::UnterminatedTemplate
+                | LexerErrorKind::UnterminatedComment
+                | LexerErrorKind::UnexpectedEOF => true,
+                // Retezec smi pokracovat na dalsim radku jen za `\`
+                LexerErrorKind::UnterminatedString
+                | LexerErrorKind::InvalidEscapeSequence => src.ends_with('\\'),
+                _ => false,
+            };
+            return if incomplete { Parsed::Incomplete } else { Parsed::Error(format!("SyntaxError: {e}")) };
+        }
+    };
+    let tokens: Vec<_> = lexer.tokens.into_iter()
+        .filter(|t| !matches!(t.kind,
+            TokenKind::Whitespace | TokenKind::Newline
+            | TokenKind::CommentLine(_) | TokenKind::CommentBlock(_)))
+        .collect();
+    match Parser::new(tokens).parse() {
+        Ok(p) => Parsed::Program(p),
+        Err(e) if e.eof => Parsed::Incomplete,
+        Err(e) => Parsed::Error(format!("SyntaxError: {e}")),
+    }
+}
+
+/// Je zdroj jen neuplny (chybi konec), ne syntakticky chybny?
+pub fn is_incomplete(src: &str) -> bool {
+    matches!(parse_input(src), Parsed::Incomplete)
+}
+
+/// Text nezachycene chyby.
+fn uncaught(e: &JsError) -> String {
+    match e {
+        JsError::Thrown(JsValue::Object(o)) if o.borrow().props.get("message").is_some() => {
+            let o = o.borrow();
+            format!("Uncaught {}: {}", o.get("name"), o.get("message"))
+        }
+        JsError::Thrown(v) | JsError::GeneratorReturn(v) => format!("Uncaught {}", v.pretty_print()),
+        JsError::Runtime(msg) => format!("Uncaught {msg}"),
+    }
+}
+
+/// Interpret REPLu - samostatny, nebo interpret headless stranky.
+enum Host {
+    Script(Box<Interpreter>),
+    Page(Box<WebView>),
+}
+
+/// Stav REPL session: interpret, rozepsany viceradkovy vstup, historie.
+pub struct Repl {
+    host: Host,
+    /// Radky neuplneho vstupu.
+    pending: String,
+    /// Editovany radek + historie (sdilene s DevTools konzoli).
+    pub input: ConsoleInput,
+    history_path: Option<PathBuf>,
+}
+
+impl Default for Repl {
+    fn default() -> Self { Self::new() }
+}
+
+impl Repl {
+    pub fn new() -> Self {
+        Repl {
+            host: Host::Script(Box::new(Interpreter::new())),
+            pending: String::new(),
+            input: ConsoleInput::new(),
+            history_path: None,
+        }
+    }
+
+    /// REPL nad headless strankou (`--page`). Skripty stranky dobehnou pred
+    /// prvnim vstupem.
+    pub fn with_page(path: &str) -> Result<Self, String> {
+        let mut wv = WebView::new(Arc::new(Engine::new_headless()), 1280, 720);
+        wv.load_url(path).ok_or_else(|| format!("Nelze nacist {path}"))?;
+        if wv.interpreter().is_none() {
+            return Err(format!("{path}: stranka nema interpret"));
+        }
+        Ok(Repl { host: Host::Page(Box::new(wv)), ..Self::new() })
+    }
+
+    pub fn interpreter(&self) -> &Interpreter {
+        match &self.host {
+            Host::Script(i) => i,
+            Host::Page(wv) => wv.interpreter().expect("stranka REPLu ma interpret"),
+        }
+    }
+
+    pub fn interpreter_mut(&mut self) -> &mut Interpreter {
+        match &mut self.host {
+            Host::Script(i) => i,
+            Host::Page(wv) => wv.interpreter_mut().expect("stranka REPLu ma interpret"),
+        }
+    }
+
+    /// Ceka se na pokracovani viceradkoveho vstupu?
+    pub fn is_pending(&self) -> bool {
+        !self.pending.is_empty()
+    }
+
+    /// Zpracuje jeden radek vstupu.
+    pub fn feed(&mut self, line: &str) -> Outcome {
+        if line.trim() == ".break" {
+            self.pending.clear();
+            return Outcome::Output(String::new());
+        }
+        // Radek zacinajici `.` uprostred vstupu je pokracovani member chainu
+        if self.pending.is_empty() && line.trim_start().starts_with('.') {
+            return self.command(line.trim());
+        }
+        if !self.pending.is_empty() {
+            self.pending.push('\n');
+        }
+        self.pending.push_str(line);
+        if self.pending.trim().is_empty() {
+            self.pending.clear();
+            return Outcome::Output(String::new());
+        }
+        match parse_input(&self.pending) {
+            Parsed::Incomplete => Outcome::More,
+            Parsed::Error(msg) => {
+                self.pending.clear();
+                Outcome::Output(msg)
+            }
+            Parsed::Program(program) => {
+                self.pending.clear();
+                Outcome::Output(self.eval_program(&program))
+            }
+        }
+    }
+
+    fn command(&mut self, cmd: &str) -> Outcome {
+        let (name, arg) = cmd.split_once(char::is_whitespace).unwrap_or((cmd, ""));
+        match name {
+            ".exit" => Outcome::Exit,
+            ".help" => Outcome::Output([
+                ".break       zahodi rozepsany viceradkovy vstup",
+                ".exit        ukonci REPL",
+                ".help        tato napoveda",
+                ".load FILE   spusti JS soubor v aktualnim kontextu",
+            ].join("\n")),
+            ".load" if !arg.trim().is_empty() => Outcome::Output(self.load(Path::new(arg.trim()))),
+            ".load" => Outcome::Output("Pouziti: .load file.js".into()),
+            _ => Outcome::Output(format!("Neznamy prikaz {name} (.help)")),
+        }
+    }
+
+    /// `.load` - spusti cely soubor jako jeden vstup.
+    pub fn load(&mut self, path: &Path) -> String {
+        let src = match std::fs::read_to_string(path) {
+            Ok(s) => s,
+            Err(e) => return format!("Nelze nacist {}: {e}", path.display()),
+        };
+        match parse_input(&src) {
+            Parsed::Program(program) => self.eval_program(&program),
+            Parsed::Incomplete => format!("SyntaxError: {}: neocekavany konec souboru", path.display()),
+            Parsed::Error(msg) => msg,
+        }
+    }
+
+    fn eval_program(&mut self, program: &Program) -> String {
+        let interp = self.interpreter_mut();
+        let result = interp.eval_script(program)
+            .and_then(|v| interp.await_settled(v));
+        // console.* uz vypsal na stderr - buffer pro DevTools tu nikdo necte
+        interp.console_log.borrow_mut().clear();
+        interp.console_log_args.borrow_mut().clear();
+        if let Some(term) = interp.take_termination() {
+            return format!("Uncaught {}", term.message());
+        }
+        match result {
+            Ok(v) => v.pretty_print(),
+            Err(e) => uncaught(&e),
+        }
+    }
+
+    /// Tab doplnovani: jmena z globalniho scope + JS keywords, za `obj.`
+    /// vlastnosti objektu (vcetne prototypu). Vraci (zacatek slova, kandidati).
+    pub fn complete(&self, text: &str, cursor: usize) -> Option<(usize, Vec<String>)> {
+        complete_in(self.interpreter(), text, cursor)
+    }
+
+    /// Nacte historii ze souboru; dalsi odeslane radky se do nej ukladaji.
+    pub fn load_history(&mut self, path: PathBuf) {
+        if let Ok(content) = std::fs::read_to_string(&path) {
+            self.input.history = content.lines().filter(|l| !l.trim().is_empty()).map(String::from).collect();
+        }
+        self.history_path = Some(path);
+    }
+
+    fn save_history(&self) {
+        if let Some(path) = &self.history_path {
+            let mut content = self.input.history.join("\n");
+            content.push('\n');
+            let _ = std::fs::write(path, content);
+        }
+    }
+
+    /// Precte radek z terminalu (editace, Tab doplnovani, historie).
+    pub fn read_line(&mut self) -> std::io::Result<ReadLine> {
+        let prompt = if self.is_pending() { "... " } else { "> " };
+        let Repl { host, input, .. } = self;
+        let interp: &Interpreter = match host {
+            Host::Script(i) => i,
+            Host::Page(wv) => wv.interpreter().expect("stranka REPLu ma interpret"),
+        };
+        let line = terminal::read_line(prompt, input, &mut |text, cursor| complete_in(interp, text, cursor))?;
+        if matches!(line, ReadLine::Line(_)) {
+            self.save_history();
+        }
+        Ok(line)
+    }
+}
+
+fn complete_in(interp: &Interpreter, text: &str, cursor: usize) -> Option<(usize, Vec<String>)> {
+    let globals: Vec<String> = interp.global.borrow().names().into_iter()
+        .filter(|n| !n.starts_with("__"))
+        .collect();
+    let resolve = |base: &str| -> Vec<String> {
+        let Some(JsValue::Object(obj)) = interp.global.borrow().get(base) else { return Vec::new() };
+        let mut keys = Vec::new();
+        let mut cur = Some(obj);
+        while let Some(o) = cur {
+            let o = o.borrow();
+            keys.extend(o.props.keys().filter(|k| !k.starts_with("__")).cloned());
+            cur = o.proto.clone();
+        }
+        keys
+    };
+    let (start, hits) = suggest(text, cursor, &globals, &resolve)?;
+    Some((start, hits.into_iter().map(|h| h.text).collect()))
+}
+
+/// `repl` subcommand - smycka cteni radku az do `.exit` / Ctrl+D.
+pub fn run(args: &[String]) {
+    let page = args.iter().enumerate().find_map(|(i, a)| {
+        a.strip_prefix("--page=").map(String::from)
+            .or_else(|| (a == "--page").then(|| args.get(i + 1).cloned()).flatten())
+    });
+    let mut repl = match page {
+        Some(path) => match Repl::with_page(&path) {
+            Ok(r) => r,
+            Err(e) => { eprintln!("{e}"); std::process::exit(2); }
+        },
+        None => Repl::new(),
+    };
+    if let Some(path) = crate::devtools::profile::repl_history_path() {
+        repl.load_history(path);
+    }
+    println!("rwe-engine REPL - .help pro napovedu, Ctrl+D konec");
+    loop {
+        let line = match repl.read_line() {
+            Ok(ReadLine::Line(l)) => l,
+            Ok(ReadLine::Interrupted) => { repl.feed(".break"); continue; }
+            Ok(ReadLine::Eof) => break,
+            Err(e) => { eprintln!("repl: {e}"); break; }
+        };
+        match repl.feed(&line) {
+            Outcome::More => {}
+            Outcome::Output(s) if s.is_empty() => {}
+            Outcome::Output(s) => println!("{s}"),
+            Outcome::Exit => break,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn out(repl: &mut Repl, line: &str) -> String {
+        match repl.feed(line) {
+            Outcome::Output(s) => s,
+            other => panic!("{line}: ocekavan vystup, nalezeno {other:?}"),
+        }
+    }
+
+    #[test]
+    fn incomplete_input_waits_for_more_lines() {
+        for src in ["function f() {", "foo(1,", "1 +", "`abc ${x}", "/* komentar", "'abc\\", "if (x) {} else"] {
+            assert!(is_incomplete(src), "{src}");
+        }
+        for src in ["1 + 2", "}", "'abc", "let = ;"] {
+            assert!(!is_incomplete(src), "{src}");
+        }
+        let mut repl = Repl::new();
+        assert_eq!(repl.feed("function add(a, b) {"), Outcome::More);
+        assert!(repl.is_pending());
+        assert_eq!(repl.feed("  return a + b"), Outcome::More);
+        assert_eq!(out(&mut repl, "}"), "undefined");
+        assert_eq!(out(&mut repl, "add(2, 3)"), "5");
+        assert_eq!(repl.feed("[1,"), Outcome::More);
+        assert_eq!(out(&mut repl, ".break"), "");
+        assert!(!repl.is_pending());
+    }
+
+    #[test]
+    fn declarations_persist_between_inputs() {
+        let mut repl = Repl::new();
+        assert_eq!(out(&mut repl, "let xs = [1, 2]; const k = 'v';"), "undefined");
+        assert_eq!(out(&mut repl, "xs.push(3); xs.length"), "3");
+        assert_eq!(out(&mut repl, "k + xs[2]"), "\"v3\"");
+        assert!(out(&mut repl, "nope()").starts_with("Uncaught "));
+        assert!(out(&mut repl, "1 +* 2").starts_with("SyntaxError"));
+    }
+
+    #[test]
+    fn top_level_promises_are_awaited() {
+        let mut repl = Repl::new();
+        assert_eq!(out(&mut repl, "const v = await new Promise(r => setTimeout(() => r(40), 5)); v + 2"), "42");
+        assert_eq!(out(&mut repl, "new Promise(r => setTimeout(() => r('later'), 5))"), "\"later\"");
+        assert_eq!(out(&mut repl, "Promise.reject(new TypeError('boom'))"), "Uncaught TypeError: boom");
+    }
+
+    #[test]
+    fn completes_globals_and_properties() {
+        let mut repl = Repl::new();
+        out(&mut repl, "var counter = { alpha: 1, beta: 2 }");
+        let (start, hits) = repl.complete("coun", 4).unwrap();
+        assert_eq!(start, 0);
+        assert!(hits.contains(&"counter".to_string()));
+        let (start, hits) = repl.complete("counter.al", 10).unwrap();
+        assert_eq!(start, 8);
+        assert!(hits.contains(&"alpha".to_string()));
+        assert!(!hits.contains(&"beta".to_string()));
+
+        let mut completer = |t: &str, c: usize| repl.complete(t, c);
+        let mut input = ConsoleInput::new();
+        input.insert("counter.be");
+        assert_eq!(terminal::apply_completion(&mut input, &mut completer), None);
+        assert_eq!(input.text, "counter.beta");
+    }
+
+    #[test]
+    fn load_command_and_history_file() {
+        let dir = std::env::temp_dir().join(format!("rwe-repl-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let script = dir.join("lib.js");
+        std::fs::write(&script, "function twice(x) {\n  return x * 2;\n}\ntwice(4)\n").unwrap();
+        let mut repl = Repl::new();
+        assert_eq!(out(&mut repl, &format!(".load {}", script.display())), "8");
+        assert_eq!(out(&mut repl, "twice(5)"), "10");
+        assert!(out(&mut repl, ".load missing.js").starts_with("Nelze nacist"));
+
+        let history = dir.join("history.txt");
+        std::fs::write(&history, "1 + 1\n\nlet a = 2\n").unwrap();
+        repl.load_history(history.clone());
+        assert_eq!(repl.input.history, ["1 + 1", "let a = 2"]);
+        repl.input.insert("a * 3");
+        repl.input.submit();
+        repl.save_history();
+        assert_eq!(std::fs::read_to_string(&history).unwrap(), "1 + 1\nlet a = 2\na * 3\n");
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn page_repl_sees_document() {
+        let dir = std::env::temp_dir().join(format!("rwe-repl-page-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let page = dir.join("page.html");
+        std::fs::write(&page, "<html><head><title>Stranka</title></head><body>\
+            <p id=\"msg\">ahoj</p><script>var loaded = true;</script></body></html>").unwrap();
+        let mut repl = Repl::with_page(page.to_str().unwrap()).unwrap();
+        assert_eq!(out(&mut repl, "loaded"), "true");
+        assert_eq!(out(&mut repl, "document.getElementById('msg').textContent"), "\"ahoj\"");
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn decodes_terminal_keys() {
+        let bytes = b"a\x1b[A\x1b[3~\x1b[1;5C\x7f\t\r\xc5\xbe";
+        let mut it = bytes.iter().copied();
+        let mut next = || it.next();
+        let keys: Vec<_> = std::iter::from_fn(|| terminal::read_key(&mut next)).collect();
+        use terminal::Key::*;
+        assert_eq!(keys, [Char('a'), Up, Delete, Right, Backspace, Tab, Enter, Char('\u{17e}')]);
+    }
+}
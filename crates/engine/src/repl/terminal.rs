@@ -0,0 +1,255 @@
+//! Terminal REPLu - editace radku v raw rezimu (kurzor, historie sipkami,
+//! Tab doplnovani). Stav radku drzi `ConsoleInput` z DevTools konzole.
+//! Mimo unix TTY (Windows, presmerovany stdin) se ctou cele radky bez editace.
+
+use std::io::{self, BufRead, IsTerminal, Read, Write};
+
+use crate::devtools::model::console::ConsoleInput;
+
+/// Doplnovani: (text radku, kurzor) -> (zacatek doplnovaneho slova, kandidati).
+pub type Completer<'a> = dyn FnMut(&str, usize) -> Option<(usize, Vec<String>)> + 'a;
+
+/// Vysledek cteni jednoho radku.
+#[derive(Debug, PartialEq)]
+pub enum ReadLine {
+    Line(String),
+    /// Ctrl+C - zahodit rozepsany vstup.
+    Interrupted,
+    /// Ctrl+D na prazdnem radku / konec stdin.
+    Eof,
+}
+
+/// Klavesa dekodovana z bajtu terminalu.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Key {
+    Char(char),
+    Enter,
+    Tab,
+    Backspace,
+    Delete,
+    Left,
+    Right,
+    Up,
+    Down,
+    Home,
+    End,
+    /// Ctrl+U - smaze radek.
+    KillLine,
+    Interrupt,
+    Eof,
+    /// Nepodporovana sekvence - ignoruje se.
+    Other,
+}
+
+/// Precte jednu klavesu (vcetne ESC sekvenci a vicebajtoveho UTF-8).
+pub(crate) fn read_key(next: &mut impl FnMut() -> Option<u8>) -> Option<Key> {
+    let b = next()?;
+    let key = match b {
+        b'\r' | b'\n' => Key::Enter,
+        b'\t' => Key::Tab,
+        0x7f | 0x08 => Key::Backspace,
+        0x01 => Key::Home,
+        0x05 => Key::End,
+        0x02 => Key::Left,
+        0x06 => Key::Right,
+        0x10 => Key::Up,
+        0x0e => Key::Down,
+        0x15 => Key::KillLine,
+        0x03 => Key::Interrupt,
+        0x04 => Key::Eof,
+        0x1b => match next()? {
+            b'[' => {
+                // CSI [cislo] [;modifikatory] koncovy-bajt - modifikatory se ignoruji
+                let mut code = 0u32;
+                let mut in_code = true;
+                let mut c = next()?;
+                while c.is_ascii_digit() || c == b';' {
+                    if c == b';' {
+                        in_code = false;
+                    } else if in_code && code < 100 {
+                        code = code * 10 + (c - b'0') as u32;
+                    }
+                    c = next()?;
+                }
+                match (c, code) {
+                    (b'A', _) => Key::Up,
+                    (b'B', _) => Key::Down,
+                    (b'C', _) => Key::Right,
+                    (b'D', _) => Key::Left,
+                    (b'H', _) | (b'~', 1 | 7) => Key::Home,
+                    (b'F', _) | (b'~', 4 | 8) => Key::End,
+                    (b'~', 3) => Key::Delete,
+                    _ => Key::Other,
+                }
+            }
+            b'O' => match next()? {
+                b'H' => Key::Home,
+                b'F' => Key::End,
+                _ => Key::Other,
+            },
+            _ => Key::Other,
+        },
+        b if b < 0x20 => Key::Other,
+        b if b < 0x80 => Key::Char(b as char),
+        lead => {
+            let len = match lead {
+                0xc0..=0xdf => 2,
+                0xe0..=0xef => 3,
+                0xf0..=0xf7 => 4,
+                _ => return Some(Key::Other),
+            };
+            let mut buf = vec![lead];
+            for _ in 1..len { buf.push(next()?); }
+            std::str::from_utf8(&buf).ok().and_then(|s| s.chars().next()).map_or(Key::Other, Key::Char)
+        }
+    };
+    Some(key)
+}
+
+/// Nejdelsi spolecny prefix kandidatu.
+fn common_prefix(hits: &[String]) -> &str {
+    let Some(first) = hits.first() else { return "" };
+    let mut len = first.len();
+    for h in &hits[1..] {
+        len = first.bytes().zip(h.bytes()).take(len).take_while(|(a, b)| a == b).count();
+    }
+    while !first.is_char_boundary(len) { len -= 1; }
+    &first[..len]
+}
+
+/// Tab: jediny kandidat se doplni cely, vic kandidatu jen spolecny prefix.
+/// Vraci kandidaty k vypsani, kdyz se nic doplnit nedalo.
+pub(crate) fn apply_completion(input: &mut ConsoleInput, complete: &mut Completer) -> Option<Vec<String>> {
+    let (start, hits) = complete(&input.text, input.cursor)?;
+    let typed = input.text.get(start..input.cursor)?.to_string();
+    let common = common_prefix(&hits);
+    match common.strip_prefix(typed.as_str()) {
+        Some(rest) if !rest.is_empty() => {
+            input.insert(rest);
+            None
+        }
+        _ if hits.len() > 1 => Some(hits),
+        _ => None,
+    }
+}
+
+/// Precte jeden radek. Odeslany radek jde do `input.history`.
+pub fn read_line(prompt: &str, input: &mut ConsoleInput, complete: &mut Completer) -> io::Result<ReadLine> {
+    #[cfg(unix)]
+    if io::stdin().is_terminal() && io::stdout().is_terminal() {
+        let _raw = raw::RawMode::enable()?;
+        return edit_line(prompt, input, complete);
+    }
+    read_plain(prompt, input)
+}
+
+/// Radek bez editace (pipe, Windows konzole).
+fn read_plain(prompt: &str, input: &mut ConsoleInput) -> io::Result<ReadLine> {
+    if io::stdin().is_terminal() {
+        print!("{prompt}");
+        io::stdout().flush()?;
+    }
+    let mut line = String::new();
+    if io::stdin().lock().read_line(&mut line)? == 0 {
+        return Ok(ReadLine::Eof);
+    }
+    input.clear();
+    input.insert(line.trim_end_matches(['\n', '\r']));
+    Ok(ReadLine::Line(input.submit()))
+}
+
+fn edit_line(prompt: &str, input: &mut ConsoleInput, complete: &mut Completer) -> io::Result<ReadLine> {
+    let mut stdin = io::stdin().lock();
+    let mut out = io::stdout().lock();
+    let mut next = || {
+        let mut b = [0u8; 1];
+        match stdin.read(&mut b) {
+            Ok(1) => Some(b[0]),
+            _ => None,
+        }
+    };
+    input.clear();
+    redraw(&mut out, prompt, input)?;
+    loop {
+        let Some(key) = read_key(&mut next) else { return Ok(ReadLine::Eof) };
+        match key {
+            Key::Enter => {
+                write!(out, "\r\n")?;
+                out.flush()?;
+                return Ok(ReadLine::Line(input.submit()));
+            }
+            Key::Interrupt => {
+                write!(out, "^C\r\n")?;
+                out.flush()?;
+                input.clear();
+                return Ok(ReadLine::Interrupted);
+            }
+            Key::Eof if input.text.is_empty() => {
+                write!(out, "\r\n")?;
+                out.flush()?;
+                return Ok(ReadLine::Eof);
+            }
+            Key::Eof | Key::Delete => input.delete_forward(),
+            Key::Char(c) => input.insert(c.encode_utf8(&mut [0; 4])),
+            Key::Tab => {
+                if let Some(hits) = apply_completion(input, complete) {
+                    write!(out, "\r\n{}\r\n", hits.join("  "))?;
+                }
+            }
+            Key::Backspace => input.backspace(),
+            Key::Left => input.move_left(false),
+            Key::Right => input.move_right(false),
+            Key::Home => input.move_home(false),
+            Key::End => input.move_end(false),
+            Key::Up => input.history_prev(),
+            Key::Down => input.history_next(),
+            Key::KillLine => input.clear(),
+            Key::Other => {}
+        }
+        redraw(&mut out, prompt, input)?;
+    }
+}
+
+/// Prekresli radek a posune kurzor na jeho pozici.
+fn redraw(out: &mut impl Write, prompt: &str, input: &ConsoleInput) -> io::Result<()> {
+    write!(out, "\r\x1b[K{prompt}{}", input.text)?;
+    let back = input.text[input.cursor..].chars().count();
+    if back > 0 {
+        write!(out, "\x1b[{back}D")?;
+    }
+    out.flush()
+}
+
+#[cfg(unix)]
+mod raw {
+    use std::io;
+
+    /// Raw rezim terminalu po dobu editace radku - drop vrati puvodni nastaveni.
+    /// Vystup (`OPOST`) zustava zapnuty, Ctrl+C chodi jako bajt (bez `ISIG`).
+    pub struct RawMode(libc::termios);
+
+    impl RawMode {
+        pub fn enable() -> io::Result<Self> {
+            // SAFETY: termios je plain C struct, tcgetattr ho cely naplni.
+            let mut t: libc::termios = unsafe { std::mem::zeroed() };
+            if unsafe { libc::tcgetattr(libc::STDIN_FILENO, &mut t) } != 0 {
+                return Err(io::Error::last_os_error());
+            }
+            let orig = t;
+            t.c_lflag &= !(libc::ICANON | libc::ECHO | libc::ISIG | libc::IEXTEN);
+            t.c_iflag &= !(libc::IXON | libc::ICRNL);
+            t.c_cc[libc::VMIN] = 1;
+            t.c_cc[libc::VTIME] = 0;
+            if unsafe { libc::tcsetattr(libc::STDIN_FILENO, libc::TCSAFLUSH, &t) } != 0 {
+                return Err(io::Error::last_os_error());
+            }
+            Ok(RawMode(orig))
+        }
+    }
+
+    impl Drop for RawMode {
+        fn drop(&mut self) {
+            unsafe { libc::tcsetattr(libc::STDIN_FILENO, libc::TCSAFLUSH, &self.0); }
+        }
+    }
+}
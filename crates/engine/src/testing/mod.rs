@@ -2,6 +2,7 @@
 //!
 //! - `wpt` - Web Platform Tests harness (testharness.js subset + result tracking)
 //! - `reftest` - Reference rendering comparison (visual diff)
+//! - `test262` - ECMAScript conformance runner (frontmatter, harness, expectations)
 //! - `fuzz` - Fuzz testing targets (HTML/CSS/JS parsers)
 
 pub mod wpt;
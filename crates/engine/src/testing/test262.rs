@@ -3,8 +3,26 @@
 //! https://github.com/tc39/test262
 //! Each .js file has YAML frontmatter (`---` blocks) describing feature flags,
 //! includes, and expected outcome (negative tests + error name).
+//!
+//! The runner (`rwe-engine test262 <checkout>`) walks `test/`, prepends the
+//! `harness/` includes, runs every scenario in a fresh `Interpreter` and writes
+//! a JSON report plus an expectations file of known failures, so CI can flag
+//! regressions per `features:` tag.
+
+use std::cell::RefCell;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{mpsc, Mutex};
+use std::time::Duration;
 
-use std::collections::HashMap;
+use crate::ast::Program;
+use crate::interpreter::budget::{ExecutionLimits, InterruptHandle, Termination};
+use crate::interpreter::{bytecode, Interpreter, JsError, JsFunc, JsObject, JsValue};
+use crate::lexer::base::Lexer;
+use crate::parser::Parser;
+use crate::tokens::TokenKind;
 
 #[derive(Debug, Clone, Default)]
 pub struct Test262Frontmatter {
@@ -33,14 +51,39 @@ pub fn parse_frontmatter(source: &str) -> Option<Test262Frontmatter> {
     let yaml = &after[..end];
     let mut fm = Test262Frontmatter::default();
     let mut current_key: Option<String> = None;
-    let mut current_block: Option<&str> = None;
     let mut list_items: Vec<String> = Vec::new();
     for raw_line in yaml.lines() {
         let trimmed = raw_line.trim_start_matches(' ').trim_end();
         if trimmed.is_empty() { continue; }
+        let nested = raw_line.starts_with(' ');
         if let Some(rest) = trimmed.strip_prefix("- ") {
-            if let Some(_) = current_key {
-                list_items.push(rest.trim_matches('\'').trim_matches('"').to_string());
+            if current_key.is_some() {
+                list_items.push(unquote(rest));
+            }
+            continue;
+        }
+        // Indented lines belong to the open block: `negative:` map entries,
+        // or continuation of a folded (`>`) / literal (`|`) scalar.
+        if nested {
+            match current_key.as_deref() {
+                Some("negative") => {
+                    if let Some((k, v)) = trimmed.split_once(':') {
+                        let neg = fm.negative.get_or_insert_with(|| Negative {
+                            phase: String::new(),
+                            type_name: String::new(),
+                        });
+                        match k.trim() {
+                            "phase" => neg.phase = unquote(v),
+                            "type" => neg.type_name = unquote(v),
+                            _ => {}
+                        }
+                    }
+                }
+                Some("description") => {
+                    if !fm.description.is_empty() { fm.description.push(' '); }
+                    fm.description.push_str(trimmed);
+                }
+                _ => {}
             }
             continue;
         }
@@ -50,15 +93,20 @@ pub fn parse_frontmatter(source: &str) -> Option<Test262Frontmatter> {
                 assign_list(&mut fm, k, std::mem::take(&mut list_items));
             }
         }
-        let _ = current_block;
         if let Some((k, v)) = trimmed.split_once(':') {
             let k = k.trim().to_string();
-            let v = v.trim().trim_matches('\'').trim_matches('"').to_string();
+            let v = v.trim();
             current_key = Some(k.clone());
-            if v.is_empty() {
-                continue; // block follows (list or nested map)
+            // Flow sequence: `flags: [onlyStrict, async]`
+            if let Some(inner) = v.strip_prefix('[').and_then(|v| v.strip_suffix(']')) {
+                let items = inner.split(',').map(unquote).filter(|s| !s.is_empty()).collect();
+                assign_list(&mut fm, &k, items);
+                continue;
             }
-            assign_scalar(&mut fm, &k, &v);
+            if v.is_empty() || v == ">" || v == "|" {
+                continue; // block follows (list, nested map or multi-line scalar)
+            }
+            assign_scalar(&mut fm, &k, &unquote(v));
         }
     }
     if !list_items.is_empty() {
@@ -69,6 +117,10 @@ pub fn parse_frontmatter(source: &str) -> Option<Test262Frontmatter> {
     Some(fm)
 }
 
+fn unquote(s: &str) -> String {
+    s.trim().trim_matches('\'').trim_matches('"').to_string()
+}
+
 fn assign_scalar(fm: &mut Test262Frontmatter, key: &str, value: &str) {
     match key {
         "description" => fm.description = value.into(),
@@ -98,6 +150,8 @@ pub struct Test262Run {
     pub failed: u64,
     pub skipped: u64,
     pub failures_by_feature: HashMap<String, u64>,
+    /// Executed (non-skipped) scenarios per `features:` tag.
+    pub totals_by_feature: HashMap<String, u64>,
 }
 
 impl Test262Run {
@@ -110,6 +164,548 @@ impl Test262Run {
         }
     }
     pub fn record_skip(&mut self) { self.total += 1; self.skipped += 1; }
+
+    /// Record one scenario outcome, counting it towards each of its features.
+    pub fn record(&mut self, outcome: &Outcome, features: &[String]) {
+        match outcome {
+            Outcome::Skip(_) => return self.record_skip(),
+            Outcome::Pass => self.record_pass(),
+            Outcome::Fail(_) | Outcome::Timeout => self.record_fail(features),
+        }
+        for f in features {
+            *self.totals_by_feature.entry(f.clone()).or_insert(0) += 1;
+        }
+    }
+
+    /// Pass rate of a feature in 0..=1 (None = no executed test uses it).
+    pub fn feature_pass_rate(&self, feature: &str) -> Option<f64> {
+        let total = *self.totals_by_feature.get(feature)?;
+        let failed = self.failures_by_feature.get(feature).copied().unwrap_or(0);
+        Some((total - failed) as f64 / total as f64)
+    }
+}
+
+// ─── Runner ──────────────────────────────────────────────────────────────────
+
+/// How a test file is executed. Files without `onlyStrict` / `noStrict` /
+/// `module` / `raw` run twice - sloppy and strict.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Scenario {
+    Default,
+    Strict,
+    Module,
+    Raw,
+}
+
+impl Scenario {
+    /// Suffix of the result id (matches test262-harness naming).
+    fn suffix(self) -> &'static str {
+        match self {
+            Scenario::Strict => " (strict mode)",
+            _ => "",
+        }
+    }
+}
+
+/// Scenarios required by the `flags:` of a test.
+pub fn scenarios(fm: &Test262Frontmatter) -> Vec<Scenario> {
+    let has = |flag: &str| fm.flags.iter().any(|f| f == flag);
+    if has("raw") {
+        vec![Scenario::Raw]
+    } else if has("module") {
+        vec![Scenario::Module]
+    } else if has("onlyStrict") {
+        vec![Scenario::Strict]
+    } else if has("noStrict") {
+        vec![Scenario::Default]
+    } else {
+        vec![Scenario::Default, Scenario::Strict]
+    }
+}
+
+/// Execution tier for script scenarios (modules always go through the
+/// module loader of the tree-walker).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tier {
+    /// Bytecode VM (`bytecode::compile_program` + `VM::with_env`).
+    Vm,
+    /// Tree-walking `Interpreter::run` (the page script path).
+    Tree,
+}
+
+/// Outcome of one scenario.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Outcome {
+    Pass,
+    Fail(String),
+    Timeout,
+    Skip(String),
+}
+
+impl Outcome {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Outcome::Pass => "pass",
+            Outcome::Fail(_) => "fail",
+            Outcome::Timeout => "timeout",
+            Outcome::Skip(_) => "skip",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Test262Result {
+    /// Path relative to the checkout (`test/built-ins/...js`) + scenario suffix.
+    pub id: String,
+    pub features: Vec<String>,
+    pub outcome: Outcome,
+}
+
+#[derive(Debug, Clone)]
+pub struct Test262Config {
+    /// Root of the test262 checkout (contains `harness/` and `test/`).
+    pub root: PathBuf,
+    /// Files or directories to run; empty = whole `test/`.
+    pub paths: Vec<PathBuf>,
+    /// Wall-clock limit per scenario.
+    pub timeout: Duration,
+    /// Number of tests executed in parallel.
+    pub jobs: usize,
+    /// Tests using any of these features are reported as skipped.
+    pub skip_features: Vec<String>,
+    pub tier: Tier,
+}
+
+impl Test262Config {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Test262Config {
+            root: root.into(),
+            paths: Vec::new(),
+            timeout: Duration::from_secs(10),
+            jobs: std::thread::available_parallelism().map(|n| n.get()).unwrap_or(1),
+            skip_features: Vec::new(),
+            tier: Tier::Vm,
+        }
+    }
+}
+
+/// All test files under the configured paths, sorted. `_FIXTURE.js` files are
+/// module dependencies, not tests.
+pub fn discover_tests(cfg: &Test262Config) -> Vec<PathBuf> {
+    let roots = if cfg.paths.is_empty() { vec![cfg.root.join("test")] } else { cfg.paths.clone() };
+    let mut out = Vec::new();
+    for root in roots {
+        let root = if root.exists() { root } else { cfg.root.join(root) };
+        collect_js(&root, &mut out);
+    }
+    out.sort();
+    out.dedup();
+    out
+}
+
+fn collect_js(path: &Path, out: &mut Vec<PathBuf>) {
+    if path.is_dir() {
+        let Ok(entries) = std::fs::read_dir(path) else { return };
+        for entry in entries.flatten() {
+            collect_js(&entry.path(), out);
+        }
+    } else if path.extension().is_some_and(|e| e == "js")
+        && !path.to_string_lossy().contains("_FIXTURE")
+    {
+        out.push(path.to_path_buf());
+    }
+}
+
+/// Runs every discovered test, `cfg.jobs` at a time. Results are sorted by id.
+pub fn run_test262(cfg: &Test262Config) -> Vec<Test262Result> {
+    let files = discover_tests(cfg);
+    let harness = HarnessFiles { dir: cfg.root.join("harness"), cache: Mutex::new(HashMap::new()) };
+    let next = AtomicUsize::new(0);
+    let results = Mutex::new(Vec::new());
+    std::thread::scope(|s| {
+        for _ in 0..cfg.jobs.max(1) {
+            s.spawn(|| loop {
+                let i = next.fetch_add(1, Ordering::Relaxed);
+                let Some(path) = files.get(i) else { break };
+                let r = run_file(cfg, &harness, path);
+                results.lock().unwrap().extend(r);
+                if (i + 1).is_multiple_of(1000) {
+                    eprintln!("[test262] {}/{}", i + 1, files.len());
+                }
+            });
+        }
+    });
+    let mut results = results.into_inner().unwrap();
+    results.sort_by(|a, b| a.id.cmp(&b.id));
+    results
+}
+
+/// `harness/*.js` sources, read once and shared by the workers.
+struct HarnessFiles {
+    dir: PathBuf,
+    cache: Mutex<HashMap<String, Option<String>>>,
+}
+
+impl HarnessFiles {
+    fn get(&self, name: &str) -> Option<String> {
+        self.cache.lock().unwrap()
+            .entry(name.to_string())
+            .or_insert_with(|| std::fs::read_to_string(self.dir.join(name)).ok())
+            .clone()
+    }
+}
+
+fn run_file(cfg: &Test262Config, harness: &HarnessFiles, path: &Path) -> Vec<Test262Result> {
+    let rel = path.strip_prefix(&cfg.root).unwrap_or(path).to_string_lossy().replace('\\', "/");
+    let source = match std::fs::read_to_string(path) {
+        Ok(s) => s,
+        Err(e) => {
+            return vec![Test262Result { id: rel, features: Vec::new(), outcome: Outcome::Fail(format!("read: {e}")) }];
+        }
+    };
+    let fm = parse_frontmatter(&source).unwrap_or_default();
+    scenarios(&fm).into_iter().map(|scenario| {
+        let outcome = match fm.features.iter().find(|f| cfg.skip_features.contains(f)) {
+            Some(f) => Outcome::Skip(format!("feature {f}")),
+            None => match prepare_source(harness, &fm, scenario, &source) {
+                Ok(src) => run_with_timeout(src, scenario, cfg.tier, path, &fm, cfg.timeout),
+                Err(e) => Outcome::Fail(e),
+            },
+        };
+        Test262Result { id: format!("{rel}{}", scenario.suffix()), features: fm.features.clone(), outcome }
+    }).collect()
+}
+
+/// Test source with its harness includes prepended (`raw` tests run verbatim).
+fn prepare_source(harness: &HarnessFiles, fm: &Test262Frontmatter, scenario: Scenario, source: &str) -> Result<String, String> {
+    if scenario == Scenario::Raw {
+        return Ok(source.to_string());
+    }
+    let mut includes = vec!["assert.js", "sta.js"];
+    if fm.flags.iter().any(|f| f == "async") {
+        includes.push("doneprintHandle.js");
+    }
+    includes.extend(fm.includes.iter().map(String::as_str));
+    let mut out = String::new();
+    if scenario == Scenario::Strict {
+        out.push_str("\"use strict\";\n");
+    }
+    for name in includes {
+        let src = harness.get(name).ok_or_else(|| format!("missing harness include {name}"))?;
+        out.push_str(&src);
+        out.push('\n');
+    }
+    out.push_str(source);
+    Ok(out)
+}
+
+/// Executes a scenario on its own thread. The interpreter stops itself once
+/// `timeout` is spent; a script stuck outside the step budget (a blocking
+/// host call) is interrupted after a grace period. The thread is always
+/// joined, so a timed-out test does not leave its 64 MB stack running.
+fn run_with_timeout(src: String, scenario: Scenario, tier: Tier, path: &Path, fm: &Test262Frontmatter, timeout: Duration) -> Outcome {
+    const GRACE: Duration = Duration::from_secs(1);
+    let (tx, rx) = mpsc::channel();
+    let url = path.to_string_lossy().into_owned();
+    let negative = fm.negative.clone();
+    let is_async = fm.flags.iter().any(|f| f == "async");
+    let interrupt = InterruptHandle::new();
+    let handle = interrupt.clone();
+    let spawned = std::thread::Builder::new()
+        .name("test262".into())
+        .stack_size(64 * 1024 * 1024)
+        .spawn(move || {
+            let limits = ExecutionLimits { max_time: Some(timeout), ..ExecutionLimits::default() };
+            let _ = tx.send(execute(&src, scenario, tier, &url, negative.as_ref(), is_async, limits, handle));
+        });
+    let thread = match spawned {
+        Ok(t) => t,
+        Err(e) => return Outcome::Fail(format!("spawn: {e}")),
+    };
+    let outcome = match rx.recv_timeout(timeout + GRACE) {
+        Ok(outcome) => outcome,
+        Err(mpsc::RecvTimeoutError::Timeout) => {
+            interrupt.interrupt();
+            Outcome::Timeout
+        }
+        Err(mpsc::RecvTimeoutError::Disconnected) => Outcome::Fail("interpreter panicked".into()),
+    };
+    let _ = thread.join();
+    outcome
+}
+
+/// Parses and runs one scenario in a fresh `Interpreter`.
+#[allow(clippy::too_many_arguments)]
+fn execute(src: &str, scenario: Scenario, tier: Tier, url: &str, negative: Option<&Negative>, is_async: bool, limits: ExecutionLimits, interrupt: InterruptHandle) -> Outcome {
+    let program = match parse_source(src, scenario == Scenario::Module) {
+        Ok(p) => p,
+        Err(e) => return match negative {
+            Some(neg) if neg.phase == "parse" && neg.type_name == "SyntaxError" => Outcome::Pass,
+            Some(neg) if neg.phase == "parse" => Outcome::Fail(format!("expected {}, got {e}", neg.type_name)),
+            _ => Outcome::Fail(e),
+        },
+    };
+    if let Some(neg) = negative.filter(|n| n.phase == "parse") {
+        return Outcome::Fail(format!("expected {} at parse time", neg.type_name));
+    }
+
+    let mut interp = Interpreter::new();
+    interp.set_execution_limits(limits);
+    interp.set_interrupt_handle(interrupt);
+    let printed = install_host(&mut interp);
+    let result = match (scenario, tier) {
+        (Scenario::Module, _) => interp.run_module(&program, url).map(drop),
+        (_, Tier::Tree) => interp.run(&program).map(drop),
+        (_, Tier::Vm) => run_vm(&mut interp, &program),
+    };
+    if matches!(interp.take_termination(), Some(Termination::TimeLimit(_) | Termination::Interrupted)) {
+        return Outcome::Timeout;
+    }
+    match (result, negative) {
+        (Err(e), Some(neg)) if is_error_type(&mut interp, &e, &neg.type_name) => Outcome::Pass,
+        (Err(e), Some(neg)) => Outcome::Fail(format!("expected {} ({} phase), got {e}", neg.type_name, neg.phase)),
+        (Err(e), None) => Outcome::Fail(e.to_string()),
+        (Ok(()), Some(neg)) => Outcome::Fail(format!("expected {} ({} phase)", neg.type_name, neg.phase)),
+        (Ok(()), None) if is_async => {
+            let printed = printed.borrow();
+            if printed.iter().any(|l| l == "Test262:AsyncTestComplete") {
+                Outcome::Pass
+            } else if let Some(msg) = printed.iter().find_map(|l| l.strip_prefix("Test262:AsyncTestFailure:")) {
+                Outcome::Fail(msg.to_string())
+            } else {
+                Outcome::Fail("$DONE was not called".into())
+            }
+        }
+        (Ok(()), None) => Outcome::Pass,
+    }
+}
+
+/// Runs a script on the VM over the interpreter's globals.
+fn run_vm(interp: &mut Interpreter, program: &Program) -> Result<(), JsError> {
+    let code = bytecode::compile_program(&program.body)
+        .map_err(|e| JsError::Runtime(format!("SyntaxError: {e}")))?;
+    let _task = interp.enter_task();
+    interp.run_bytecode(&code).map(drop).map_err(|msg| {
+        match bytecode::take_thrown(&msg) {
+            Some(v) => JsError::Thrown(v),
+            None => JsError::Runtime(msg),
+        }
+    })
+}
+
+fn parse_source(src: &str, module: bool) -> Result<Program, String> {
+    let lex = Lexer::parse_str(src, "<test262>").map_err(|e| format!("SyntaxError: {e}"))?;
+    let tokens: Vec<_> = lex.tokens.into_iter()
+        .filter(|t| !matches!(t.kind,
+            TokenKind::Whitespace | TokenKind::Newline
+            | TokenKind::CommentLine(_) | TokenKind::CommentBlock(_)))
+        .collect();
+    let mut parser = Parser::new(tokens);
+    let program = if module { parser.parse_module() } else { parser.parse() };
+    program.map_err(|e| format!("SyntaxError: {e}"))
+}
+
+/// Host hooks the harness expects: `print` (async tests report through
+/// `doneprintHandle.js`) and a minimal `$262`. Returns the printed lines.
+fn install_host(interp: &mut Interpreter) -> Rc<RefCell<Vec<String>>> {
+    let printed = Rc::new(RefCell::new(Vec::new()));
+    let sink = Rc::clone(&printed);
+    let print: Rc<dyn Fn(Vec<JsValue>) -> Result<JsValue, String>> = Rc::new(move |args| {
+        let line = args.iter().map(|a| a.to_string()).collect::<Vec<_>>().join(" ");
+        sink.borrow_mut().push(line);
+        Ok(JsValue::Undefined)
+    });
+    let global = interp.global.borrow().lookup("globalThis").ok().flatten().unwrap_or(JsValue::Undefined);
+    let mut host = JsObject::new();
+    host.set("global".into(), global);
+    host.set("gc".into(), JsValue::Function(JsFunc::Native("gc".into(), Rc::new(|_| Ok(JsValue::Undefined)))));
+    let mut env = interp.global.borrow_mut();
+    env.define("print", JsValue::Function(JsFunc::Native("print".into(), print)));
+    env.define("$262", JsValue::Object(Rc::new(RefCell::new(host))));
+    printed
+}
+
+/// Is the uncaught error of the negative test's expected type? Besides the
+/// error name, instances of plain constructor functions (`Test262Error`)
+/// have neither `name` nor `constructor` - checked via `instanceof`.
+fn is_error_type(interp: &mut Interpreter, e: &JsError, type_name: &str) -> bool {
+    if error_name(e) == type_name {
+        return true;
+    }
+    let JsError::Thrown(v) = e else { return false };
+    if !type_name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$') {
+        return false;
+    }
+    interp.global.borrow_mut().define("__test262_thrown__", v.clone());
+    matches!(interp.eval_via_vm(&format!("__test262_thrown__ instanceof {type_name}")), Ok(JsValue::Bool(true)))
+}
+
+/// Name of an uncaught error (`TypeError`, ...); empty when unknown.
+fn error_name(e: &JsError) -> String {
+    match e {
+        JsError::Thrown(JsValue::Object(o)) => match o.borrow().get("name") {
+            JsValue::Str(name) => name,
+            _ => String::new(),
+        },
+        JsError::Thrown(_) | JsError::GeneratorReturn(_) => String::new(),
+        // Runtime errors carry the name as a message prefix ("TypeError: ...").
+        JsError::Runtime(msg) => match msg.split_once(": ") {
+            Some((name, _)) if name.ends_with("Error") && name.chars().all(|c| c.is_ascii_alphabetic()) => name.to_string(),
+            _ => "Error".to_string(),
+        },
+    }
+}
+
+// ─── Report + expectations ───────────────────────────────────────────────────
+
+/// Known failures checked into CI: result id -> `fail` / `timeout`. Anything
+/// not listed is expected to pass.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Test262Expectations {
+    pub failures: BTreeMap<String, String>,
+}
+
+/// Difference of a run against the expectations.
+#[derive(Debug, Default)]
+pub struct Test262Comparison {
+    /// Expected to pass, did not.
+    pub regressions: Vec<String>,
+    /// Listed as failing, now passes (expectations should be updated).
+    pub fixed: Vec<String>,
+    /// Regressed ids per `features:` tag (untagged tests under `(none)`).
+    pub regressions_by_feature: BTreeMap<String, Vec<String>>,
+}
+
+impl Test262Expectations {
+    pub fn from_results(results: &[Test262Result]) -> Self {
+        let failures = results.iter()
+            .filter(|r| matches!(r.outcome, Outcome::Fail(_) | Outcome::Timeout))
+            .map(|r| (r.id.clone(), r.outcome.label().to_string()))
+            .collect();
+        Test262Expectations { failures }
+    }
+
+    /// Missing file = empty expectations (everything must pass).
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let text = match std::fs::read_to_string(path) {
+            Ok(t) => t,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(e) => return Err(format!("{}: {e}", path.display())),
+        };
+        let failures = serde_json::from_str(&text).map_err(|e| format!("{}: {e}", path.display()))?;
+        Ok(Test262Expectations { failures })
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let text = serde_json::to_string_pretty(&self.failures).map_err(|e| e.to_string())?;
+        std::fs::write(path, text + "\n").map_err(|e| format!("{}: {e}", path.display()))
+    }
+
+    pub fn compare(&self, results: &[Test262Result]) -> Test262Comparison {
+        let mut cmp = Test262Comparison::default();
+        for r in results {
+            let expected_fail = self.failures.contains_key(&r.id);
+            match &r.outcome {
+                Outcome::Pass if expected_fail => cmp.fixed.push(r.id.clone()),
+                Outcome::Fail(_) | Outcome::Timeout if !expected_fail => {
+                    cmp.regressions.push(r.id.clone());
+                    let none = ["(none)".to_string()];
+                    let tags = if r.features.is_empty() { &none[..] } else { &r.features[..] };
+                    for f in tags {
+                        cmp.regressions_by_feature.entry(f.clone()).or_default().push(r.id.clone());
+                    }
+                }
+                _ => {}
+            }
+        }
+        cmp
+    }
+}
+
+/// Accumulated stats of a result set.
+pub fn summarize(results: &[Test262Result]) -> Test262Run {
+    let mut run = Test262Run::default();
+    for r in results {
+        run.record(&r.outcome, &r.features);
+    }
+    run
+}
+
+/// Machine-readable report: totals, per-feature pass rates, regressions and
+/// every result with its failure message.
+pub fn report_json(results: &[Test262Result], run: &Test262Run, cmp: &Test262Comparison) -> serde_json::Value {
+    let features: BTreeMap<&String, serde_json::Value> = run.totals_by_feature.iter().map(|(f, total)| {
+        let failed = run.failures_by_feature.get(f).copied().unwrap_or(0);
+        (f, serde_json::json!({
+            "total": total,
+            "passed": total - failed,
+            "pass_rate": run.feature_pass_rate(f).unwrap_or(0.0),
+        }))
+    }).collect();
+    let results: Vec<serde_json::Value> = results.iter().map(|r| {
+        let message = match &r.outcome {
+            Outcome::Fail(m) | Outcome::Skip(m) => Some(m.as_str()),
+            _ => None,
+        };
+        serde_json::json!({ "id": r.id, "result": r.outcome.label(), "message": message, "features": r.features })
+    }).collect();
+    serde_json::json!({
+        "summary": {
+            "total": run.total,
+            "passed": run.passed,
+            "failed": run.failed,
+            "skipped": run.skipped,
+        },
+        "features": features,
+        "regressions": cmp.regressions,
+        "regressions_by_feature": cmp.regressions_by_feature,
+        "fixed": cmp.fixed,
+        "results": results,
+    })
+}
+
+/// CLI entry (`rwe-engine test262 ...`): runs the suite, writes the report and
+/// either updates the expectations or compares against them. Returns `false`
+/// on regressions.
+pub fn run_suite(cfg: &Test262Config, report_path: &Path, expectations_path: &Path, update: bool) -> bool {
+    let expectations = match Test262Expectations::load(expectations_path) {
+        Ok(e) => e,
+        Err(e) => { eprintln!("[test262] {e}"); return false; }
+    };
+    let started = std::time::Instant::now();
+    let results = run_test262(cfg);
+    let run = summarize(&results);
+    let cmp = if update { Test262Comparison::default() } else { expectations.compare(&results) };
+
+    println!("[test262] {} scenarios: {} pass, {} fail, {} skip ({:.1}s)",
+        run.total, run.passed, run.failed, run.skipped, started.elapsed().as_secs_f32());
+    let mut features: Vec<_> = run.totals_by_feature.keys().collect();
+    features.sort();
+    for f in features {
+        println!("  {:<40} {:>6.1}% of {}", f, run.feature_pass_rate(f).unwrap_or(0.0) * 100.0, run.totals_by_feature[f]);
+    }
+
+    let report = serde_json::to_string_pretty(&report_json(&results, &run, &cmp)).unwrap_or_default();
+    if let Err(e) = std::fs::write(report_path, report) {
+        eprintln!("[test262] {}: {e}", report_path.display());
+    }
+    if update {
+        let exp = Test262Expectations::from_results(&results);
+        if let Err(e) = exp.save(expectations_path) {
+            eprintln!("[test262] {e}");
+            return false;
+        }
+        println!("[test262] expectations written: {} ({} known failures)", expectations_path.display(), exp.failures.len());
+        return true;
+    }
+    for (f, ids) in &cmp.regressions_by_feature {
+        println!("[test262] REGRESSION {f}: {} tests", ids.len());
+        for id in ids.iter().take(20) { println!("    {id}"); }
+    }
+    if !cmp.fixed.is_empty() {
+        println!("[test262] {} tests now pass - run with --update-expectations", cmp.fixed.len());
+    }
+    cmp.regressions.is_empty()
 }
 
 #[cfg(test)]
@@ -148,4 +744,84 @@ mod tests {
         assert_eq!(r.skipped, 1);
         assert_eq!(r.failures_by_feature.get("a"), Some(&1));
     }
+
+    #[test]
+    fn parse_negative_and_flow_lists() {
+        let src = "/*---\ndescription: >\n  folded\n  text\nnegative:\n  phase: parse\n  type: SyntaxError\nflags: [onlyStrict, async]\nincludes: [compareArray.js]\n---*/\n";
+        let fm = parse_frontmatter(src).unwrap();
+        assert_eq!(fm.description, "folded text");
+        let neg = fm.negative.unwrap();
+        assert_eq!((neg.phase.as_str(), neg.type_name.as_str()), ("parse", "SyntaxError"));
+        assert_eq!(fm.flags, vec!["onlyStrict", "async"]);
+        assert_eq!(fm.includes, vec!["compareArray.js"]);
+    }
+
+    #[test]
+    fn scenarios_follow_flags() {
+        let with = |flags: &[&str]| scenarios(&Test262Frontmatter {
+            flags: flags.iter().map(|f| f.to_string()).collect(),
+            ..Default::default()
+        });
+        assert_eq!(with(&[]), vec![Scenario::Default, Scenario::Strict]);
+        assert_eq!(with(&["onlyStrict"]), vec![Scenario::Strict]);
+        assert_eq!(with(&["noStrict"]), vec![Scenario::Default]);
+        assert_eq!(with(&["module"]), vec![Scenario::Module]);
+        assert_eq!(with(&["raw", "noStrict"]), vec![Scenario::Raw]);
+    }
+
+    #[test]
+    fn runner_executes_checkout() {
+        let root = std::env::temp_dir().join(format!("rwe-test262-{}", std::process::id()));
+        let write = |rel: &str, src: &str| {
+            let p = root.join(rel);
+            std::fs::create_dir_all(p.parent().unwrap()).unwrap();
+            std::fs::write(p, src).unwrap();
+        };
+        write("harness/sta.js", "function Test262Error(message) { this.message = message || ''; }\n\
+            Test262Error.prototype.toString = function () { return 'Test262Error: ' + this.message; };");
+        write("harness/assert.js", "function assert(v, m) { if (v !== true) throw new Test262Error(m); }");
+        write("harness/doneprintHandle.js", "function $DONE(e) { print(e ? 'Test262:AsyncTestFailure:' + e : 'Test262:AsyncTestComplete'); }");
+        write("test/pass.js", "/*---\nfeatures: [a]\n---*/\nassert(1 + 1 === 2);");
+        write("test/fail.js", "/*---\nfeatures: [a, b]\nflags: [noStrict]\n---*/\nassert(false, 'nope');");
+        write("test/parse.js", "/*---\nnegative:\n  phase: parse\n  type: SyntaxError\n---*/\nvar = ;");
+        write("test/runtime.js", "/*---\nnegative:\n  phase: runtime\n  type: Test262Error\nflags: [onlyStrict]\n---*/\nthrow new Test262Error();");
+        write("test/async.js", "/*---\nflags: [async, noStrict]\n---*/\nnew Promise(function (r) { r(1); }).then(function () { $DONE(); });");
+
+        let mut cfg = Test262Config::new(&root);
+        cfg.jobs = 1;
+        let results = run_test262(&cfg);
+        let _ = std::fs::remove_dir_all(&root);
+        let outcome = |id: &str| results.iter().find(|r| r.id == id).map(|r| r.outcome.label());
+        assert_eq!(outcome("test/pass.js"), Some("pass"));
+        assert_eq!(outcome("test/pass.js (strict mode)"), Some("pass"));
+        assert_eq!(outcome("test/fail.js"), Some("fail"));
+        assert_eq!(outcome("test/parse.js"), Some("pass"));
+        assert_eq!(outcome("test/runtime.js (strict mode)"), Some("pass"));
+        assert_eq!(outcome("test/async.js"), Some("pass"));
+
+        let run = summarize(&results);
+        assert_eq!(run.feature_pass_rate("a"), Some(2.0 / 3.0));
+        assert_eq!(run.feature_pass_rate("b"), Some(0.0));
+        let cmp = Test262Expectations::default().compare(&results);
+        assert_eq!(cmp.regressions, vec!["test/fail.js"]);
+        assert_eq!(cmp.regressions_by_feature["b"], vec!["test/fail.js"]);
+        let known = Test262Expectations::from_results(&results);
+        assert!(known.compare(&results).regressions.is_empty());
+    }
+
+    #[test]
+    fn runaway_test_times_out() {
+        let root = std::env::temp_dir().join(format!("rwe-test262-loop-{}", std::process::id()));
+        std::fs::create_dir_all(root.join("test")).unwrap();
+        std::fs::write(root.join("test/loop.js"), "/*---\nflags: [raw]\n---*/\nwhile (true) {}").unwrap();
+
+        let mut cfg = Test262Config::new(&root);
+        cfg.jobs = 1;
+        cfg.timeout = Duration::from_millis(200);
+        let started = std::time::Instant::now();
+        let results = run_test262(&cfg);
+        let _ = std::fs::remove_dir_all(&root);
+        assert_eq!(results[0].outcome.label(), "timeout");
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
 }
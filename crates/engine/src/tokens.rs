@@ -37,6 +37,9 @@ pub enum TokenKind {
     /// Identifikator: `foo`, `myVar`, `$element`, `_private`
     Identifier(String),
 
+    /// Privatni jmeno clenu tridy: `#count` (hodnota bez `#`)
+    PrivateName(String),
+
     /// Ciselny literal s plnou metadatou.
     ///
     /// - `raw` - surovy text ze zdrojaku (`"0xFF"`, `"1_000"`)
@@ -113,6 +116,7 @@ impl fmt::Display for TokenKind {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             TokenKind::Identifier(s)               => write!(f, "Identifier({s})"),
+            TokenKind::PrivateName(s)              => write!(f, "PrivateName(#{s})"),
             TokenKind::Keyword(k)                  => write!(f, "Keyword({})", k.as_str()),
             TokenKind::Operator(o)                 => write!(f, "Operator({})", o.as_str()),
             TokenKind::NumericLiteral { value, .. } => write!(f, "Number({value})"),
@@ -283,7 +287,7 @@ impl Token {
         matches!(ch,
             '+' | '-' | '*' | '/' | '%' | '.' | ',' | ':' | ';' | '?' |
             '~' | '!' | '=' | '&' | '|' | '^' | '<' | '>' |
-            '(' | ')' | '{' | '}' | '[' | ']' | '#'
+            '(' | ')' | '{' | '}' | '[' | ']' | '#' | '@'
         )
     }
 
@@ -416,5 +420,6 @@ string_enum! {
     RBrace          => "}",
     LBracket        => "[",
     RBracket        => "]",
-    Hash            => "#"
+    Hash            => "#",
+    At              => "@"
 }
@@ -20,7 +20,7 @@ use winit::window::{Window, WindowId};
 
 use rwe_engine::browser::render::Renderer;
 use rwe_engine::embed::{DevtoolsTarget, Engine, InputEvent, KeyModifiers, MouseButton, WebView};
-use rwe_engine::interpreter::{helpers::native, JsValue};
+use rwe_engine::interpreter::host_bindings::host_fn;
 use rwe_devtools_proto::DevtoolsRequest;
 
 /// Guess resource_type pro Network.requestWillBeSent event z URL extension.
@@ -39,6 +39,7 @@ fn console_arg_to_cdp_remote_object(arg: &rwe_engine::interpreter::console_args:
         K::Undefined              => ("undefined", None),
         K::Null                   => ("object", Some("null")),
         K::BigInt                 => ("bigint", None),
+        K::Symbol                 => ("symbol", None),
         K::Function               => ("function", None),
         K::Object                 => ("object", None),
         K::Array                  => ("object", Some("array")),
@@ -593,34 +594,19 @@ impl ShellApp {
         };
         // __rwe_cdp_send_native(json_str) -> "" (async dispatch).
         let req_q = Rc::clone(&channel.req_queue);
-        let send_fn = native("__rwe_cdp_send_native", move |args| {
-            let json = args.first().map(|v| v.to_string()).unwrap_or_default();
+        let send_fn = host_fn("__rwe_cdp_send_native", move |json: String| {
             match serde_json::from_str::<DevtoolsRequest>(&json) {
-                Ok(req) => {
-                    req_q.borrow_mut().push_back(req);
-                }
+                Ok(req) => req_q.borrow_mut().push_back(req),
                 Err(e) => eprintln!("[CDP SEND] parse err: {} (json: {})", e, json),
             }
-            Ok(JsValue::Str(String::new()))
+            String::new()
         });
         // __rwe_cdp_poll_events() -> JSON array of pending response/event strings.
+        // Items v queue jsou uz JSON-serialized objekty: "[<obj>,<obj>,...]".
         let resp_q = Rc::clone(&channel.resp_queue);
-        let poll_fn = native("__rwe_cdp_poll_events", move |_args| {
-            let mut q = resp_q.borrow_mut();
-            if q.is_empty() {
-                return Ok(JsValue::Str("[]".into()));
-            }
-            // Items v queue jsou uz JSON-serialized objekty. Slozit array:
-            // "[<obj>,<obj>,...]"
-            let mut out = String::from("[");
-            let mut first = true;
-            while let Some(item) = q.pop_front() {
-                if !first { out.push(','); }
-                out.push_str(&item);
-                first = false;
-            }
-            out.push(']');
-            Ok(JsValue::Str(out))
+        let poll_fn = host_fn("__rwe_cdp_poll_events", move || {
+            let items: Vec<String> = resp_q.borrow_mut().drain(..).collect();
+            format!("[{}]", items.join(","))
         });
         interp.global.borrow_mut().define("__rwe_cdp_send_native", send_fn);
         interp.global.borrow_mut().define("__rwe_cdp_poll_events", poll_fn);
@@ -909,43 +895,24 @@ impl ShellApp {
             Some(i) => i,
             None => return,
         };
-        // __shell_back__()
-        let q = Rc::clone(cmds);
-        interp.global.borrow_mut().define("__shell_back__",
-            native("__shell_back__", move |_| {
-                q.borrow_mut().push_back(ShellCommand::Back);
-                Ok(JsValue::Undefined)
-            }));
-        // __shell_fwd__()
-        let q = Rc::clone(cmds);
-        interp.global.borrow_mut().define("__shell_fwd__",
-            native("__shell_fwd__", move |_| {
-                q.borrow_mut().push_back(ShellCommand::Forward);
-                Ok(JsValue::Undefined)
-            }));
-        // __shell_reload__()
-        let q = Rc::clone(cmds);
-        interp.global.borrow_mut().define("__shell_reload__",
-            native("__shell_reload__", move |_| {
-                q.borrow_mut().push_back(ShellCommand::Reload);
-                Ok(JsValue::Undefined)
-            }));
-        // __shell_toggle_devtools__()
-        let q = Rc::clone(cmds);
-        interp.global.borrow_mut().define("__shell_toggle_devtools__",
-            native("__shell_toggle_devtools__", move |_| {
-                q.borrow_mut().push_back(ShellCommand::ToggleDevtools);
-                Ok(JsValue::Undefined)
-            }));
+        let commands: [(&str, fn() -> ShellCommand); 4] = [
+            ("__shell_back__", || ShellCommand::Back),
+            ("__shell_fwd__", || ShellCommand::Forward),
+            ("__shell_reload__", || ShellCommand::Reload),
+            ("__shell_toggle_devtools__", || ShellCommand::ToggleDevtools),
+        ];
+        for (name, cmd) in commands {
+            let q = Rc::clone(cmds);
+            interp.global.borrow_mut().define(name,
+                host_fn(name, move || q.borrow_mut().push_back(cmd())));
+        }
         // __shell_navigate__(url)
         let q = Rc::clone(cmds);
         interp.global.borrow_mut().define("__shell_navigate__",
-            native("__shell_navigate__", move |args| {
-                let url = args.first().map(|v| v.to_string()).unwrap_or_default();
-                if !url.is_empty() {
+            host_fn("__shell_navigate__", move |url: Option<String>| {
+                if let Some(url) = url.filter(|u| !u.is_empty()) {
                     q.borrow_mut().push_back(ShellCommand::Navigate(url));
                 }
-                Ok(JsValue::Undefined)
             }));
         println!("[shell chrome] natives installed (back/fwd/reload/devtools/navigate)");
     }